@@ -16,6 +16,23 @@ impl<'a> Buffer<'a> {
         self.flush();
         &mut self.inner
     }
+
+    /// Inserts `count` copies of `value` in front of the bytes already written to the buffer
+    ///
+    /// Unlike `write_repeated`, this doesn't flush the extra bytes into the main buffer. This can
+    /// be used to avoid copying the extra bytes when the written values don't need to come last,
+    /// as is the case with padding.
+    #[inline]
+    pub fn prepend_repeated(&mut self, count: usize, value: u8) {
+        let extra_len = self.len() - self.inner.len();
+        self.inner.assert_capacity(count + extra_len);
+
+        let len = self.inner.len();
+        self.inner.advance_position(count);
+        let slice = self.inner.as_mut_slice();
+        slice.copy_within(..len, count);
+        slice[..count].fill(value);
+    }
 }
 
 /// Implement a version with `bytes` enabled
@@ -36,6 +53,12 @@ impl<'a> Buffer<'a> {
         Self { inner, extra }
     }
 
+    /// Returns `true` if the buffer ends with extra bytes which haven't been copied yet
+    #[inline]
+    pub fn has_extra(&self) -> bool {
+        self.extra.is_some()
+    }
+
     /// Converts the buffer into its inner parts
     ///
     /// NOTE: the EncoderBuffer position will not include the extra bytes. The caller will need to
@@ -81,6 +104,11 @@ impl<'a> Buffer<'a> {
         Self { inner }
     }
 
+    #[inline]
+    pub fn has_extra(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn into_inner(self) -> (EncoderBuffer<'a>, Option<&'static [u8]>) {
         (self.inner, None)
@@ -174,3 +202,27 @@ impl<'a> Encoder for Buffer<'a> {
         self.inner.len()
     }
 }
+
+#[cfg(all(test, feature = "bytes"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepend_repeated_test() {
+        let mut storage = [0xffu8; 16];
+        let mut buffer = Buffer::new(EncoderBuffer::new(&mut storage));
+        buffer.write_slice(&[1, 2, 3]);
+        buffer.write_bytes(bytes::Bytes::from_static(&[4, 5, 6]));
+
+        buffer.prepend_repeated(2, 0);
+
+        // the extra bytes are still deferred
+        assert!(buffer.has_extra());
+        assert_eq!(buffer.len(), 8);
+
+        buffer.flatten();
+        assert_eq!(buffer.len(), 8);
+        drop(buffer);
+        assert_eq!(&storage[..8], &[0, 0, 1, 2, 3, 4, 5, 6]);
+    }
+}
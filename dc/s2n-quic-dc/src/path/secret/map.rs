@@ -33,6 +33,8 @@ const TLS_EXPORTER_LABEL: &str = "EXPERIMENTAL EXPORTER s2n-quic-dc";
 const TLS_EXPORTER_CONTEXT: &str = "";
 const TLS_EXPORTER_LENGTH: usize = schedule::EXPORT_SECRET_LEN;
 
+const DEFAULT_REHANDSHAKE_PERIOD: Duration = Duration::from_secs(3600 * 24);
+
 // FIXME: Most of this comment is not true today, we're expecting to implement the details
 // contained here. This is presented as a roadmap.
 /// This map caches path secrets derived from handshakes.
@@ -192,8 +194,14 @@ impl Cleaner {
                     // For non-retired entries, if it's time for them to handshake again, request a
                     // handshake to happen. This handshake will happen on the next request for this
                     // particular peer.
-                    if entry.rehandshake_time() <= now {
-                        state.requested_handshakes.pin().insert(entry.peer);
+                    if entry.rehandshake_time() <= now
+                        && state.requested_handshakes.pin().insert(entry.peer)
+                    {
+                        tracing::debug!(
+                            peer = %entry.peer,
+                            generation = entry.generation(),
+                            "path secret rotation scheduled"
+                        );
                     }
 
                     // Not retired.
@@ -235,6 +243,23 @@ const EVICTION_CYCLES: u64 = if cfg!(test) { 0 } else { 10 };
 
 impl Map {
     pub fn new(signer: stateless_reset::Signer) -> Self {
+        Self::with_rehandshake_period(signer, DEFAULT_REHANDSHAKE_PERIOD)
+    }
+
+    /// Creates a map which rotates the path secret for each peer on the given schedule
+    ///
+    /// Each path secret is scheduled for rotation at a random point between 6 minutes (or
+    /// `rehandshake_period`, if smaller) and `rehandshake_period` after it was established.
+    /// Spreading out the rotations avoids re-handshaking with every peer at the same time.
+    pub fn with_rehandshake_period(
+        signer: stateless_reset::Signer,
+        rehandshake_period: Duration,
+    ) -> Self {
+        assert!(
+            rehandshake_period.as_secs() > 0,
+            "rehandshake_period must be at least 1 second"
+        );
+
         // FIXME: Avoid unwrap and the whole socket.
         //
         // We only ever send on this socket - but we really should be sending on the same
@@ -247,8 +272,7 @@ impl Map {
         let state = State {
             // This is around 500MB with current entry size.
             max_capacity: 500_000,
-            // FIXME: Allow configuring the rehandshake_period.
-            rehandshake_period: Duration::from_secs(3600 * 24),
+            rehandshake_period,
             peers: Default::default(),
             requested_handshakes: Default::default(),
             ids: Default::default(),
@@ -278,6 +302,62 @@ impl Map {
             && !self.state.requested_handshakes.pin().contains(&peer)
     }
 
+    /// Requests that the path secret for `peer` be rotated
+    ///
+    /// The rotation happens on the next handshake with the peer. Returns `false` if there is no
+    /// path secret for the peer.
+    pub fn rotate(&self, peer: SocketAddr) -> bool {
+        let Some(generation) = self.secret_generation(peer) else {
+            return false;
+        };
+
+        if self.state.requested_handshakes.pin().insert(peer) {
+            tracing::debug!(peer = %peer, generation, "path secret rotation requested");
+        }
+
+        true
+    }
+
+    /// Marks each of the `peers` for which there is no path secret yet as requiring a handshake
+    ///
+    /// The map doesn't perform handshakes itself. The requested peers are returned from
+    /// [`Self::requested_handshakes`], and the application is responsible for handshaking with
+    /// them, e.g. at startup to establish path secrets with a known set of peers before any traffic
+    /// needs to be sent to them.
+    pub fn request_handshakes<P: IntoIterator<Item = SocketAddr>>(&self, peers: P) {
+        let peers_guard = self.state.peers.guard();
+        let requested = self.state.requested_handshakes.pin();
+        for peer in peers {
+            if !self.state.peers.contains_key(&peer, &peers_guard) {
+                requested.insert(peer);
+            }
+        }
+    }
+
+    /// Returns the peers which require a handshake
+    ///
+    /// This includes peers with a path secret that is due for rotation, peers which reported the
+    /// path secret as unknown, and peers passed to [`Self::request_handshakes`]. A peer is removed from the
+    /// set once a new path secret has been established with it.
+    pub fn requested_handshakes(&self) -> Vec<SocketAddr> {
+        self.state
+            .requested_handshakes
+            .pin()
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Returns the generation of the current path secret for `peer`
+    ///
+    /// The generation starts at 1 for the first path secret established with a peer and is
+    /// incremented on each rotation.
+    pub fn secret_generation(&self, peer: SocketAddr) -> Option<u64> {
+        let peers_guard = self.state.peers.guard();
+        let entry = self.state.peers.get(&peer, &peers_guard)?;
+        Some(entry.generation())
+    }
+
     pub fn sealer(&self, peer: SocketAddr) -> Option<(Sealer, ApplicationParams)> {
         let peers_guard = self.state.peers.guard();
         let state = self.state.peers.get(&peer, &peers_guard)?;
@@ -459,13 +539,45 @@ impl Map {
         }
 
         let peers_guard = self.state.peers.guard();
-        if let Some(prev) = self.state.peers.insert(peer, entry, &peers_guard) {
+        let mut entry = entry;
+        let mut prev = None;
+        // The generation is assigned while the peer's entry is replaced, so concurrent insertions
+        // for the same peer are each assigned a different generation.
+        loop {
+            entry.generation.store(1, Ordering::Relaxed);
+            match self.state.peers.try_insert(peer, entry, &peers_guard) {
+                Ok(_) => break,
+                Err(error) => entry = error.not_inserted,
+            }
+
+            let replaced = self.state.peers.compute_if_present(
+                &peer,
+                |_, current| {
+                    entry
+                        .generation
+                        .store(current.generation() + 1, Ordering::Relaxed);
+                    prev = Some(current.clone());
+                    Some(entry.clone())
+                },
+                &peers_guard,
+            );
+
+            if replaced.is_some() {
+                break;
+            }
+
+            // the previous entry was removed in the meantime, so try inserting again
+        }
+
+        if let Some(prev) = prev {
             // This shouldn't happen due to the panic above, but just in case something went wrong
             // with the secret map we double check here.
             // FIXME: Make insertion fallible and fail handshakes instead?
             assert_ne!(*prev.secret.id(), id, "duplicate path secret id");
 
             prev.retire(self.state.cleaner.epoch());
+
+            tracing::info!(peer = %peer, generation = prev.generation() + 1, "path secret rotated");
         }
     }
 
@@ -605,6 +717,9 @@ pub(super) struct Entry {
     // start getting pulled out shortly. This is used for the LRU mechanism, see the Cleaner impl
     // for details.
     used_at: AtomicU64,
+    // The number of path secrets which have been established with the peer, including this one.
+    // This is assigned when the entry is inserted into the map.
+    generation: AtomicU64,
     sender: sender::State,
     receiver: receiver::State,
     parameters: ApplicationParams,
@@ -641,12 +756,13 @@ impl Entry {
             creation_time: Instant::now(),
             // Schedule another handshake sometime in [5 minutes, rehandshake_time] from now.
             rehandshake_delta_secs: rand::thread_rng().gen_range(
-                std::cmp::min(rehandshake_time.as_secs(), 360)..rehandshake_time.as_secs(),
+                std::cmp::min(rehandshake_time.as_secs(), 360)..=rehandshake_time.as_secs(),
             ) as u32,
             peer,
             secret,
             retired: Default::default(),
             used_at: AtomicU64::new(0),
+            generation: AtomicU64::new(0),
             sender,
             receiver,
             parameters,
@@ -657,6 +773,10 @@ impl Entry {
         self.retired.0.store(at_epoch, Ordering::Relaxed);
     }
 
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
     fn mark_live(&self, at_epoch: u64) {
         self.used_at.store(at_epoch, Ordering::Relaxed);
     }
@@ -54,6 +54,84 @@ fn cleans_after_delay() {
     assert!(map.state.ids.contains_key(third.secret.id(), &guard));
 }
 
+#[test]
+fn secret_generation_rotation() {
+    let signer = stateless_reset::Signer::new(b"secret");
+    let map = Map::new(signer);
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+
+    assert_eq!(map.secret_generation(peer), None);
+    assert!(!map.rotate(peer));
+
+    map.insert(fake_entry(1));
+    assert_eq!(map.secret_generation(peer), Some(1));
+    assert!(map.contains(peer));
+
+    // requesting a rotation triggers a handshake with the peer
+    assert!(map.rotate(peer));
+    assert!(!map.contains(peer));
+    assert_eq!(map.requested_handshakes(), vec![peer]);
+
+    // establishing a new secret completes the rotation
+    map.insert(fake_entry(1));
+    assert_eq!(map.secret_generation(peer), Some(2));
+    assert!(map.contains(peer));
+    assert!(map.requested_handshakes().is_empty());
+}
+
+/// Ensures concurrent insertions for the same peer are each assigned a different generation
+#[test]
+fn concurrent_secret_generation() {
+    let signer = stateless_reset::Signer::new(b"secret");
+    let map = Map::new(signer);
+    let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+
+    let threads = 4;
+    let inserts = 50;
+
+    let generations: HashSet<u64> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    (0..inserts)
+                        .map(|_| {
+                            let entry = fake_entry(1);
+                            map.insert(entry.clone());
+                            entry.generation()
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    let total = threads * inserts;
+    assert_eq!(generations, (1..=total).collect());
+    assert_eq!(map.secret_generation(peer), Some(total));
+}
+
+#[test]
+fn request_handshakes() {
+    let signer = stateless_reset::Signer::new(b"secret");
+    let map = Map::new(signer);
+    let known = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+    let unknown = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 2));
+    map.insert(fake_entry(1));
+
+    // only peers without a path secret are requested
+    map.request_handshakes([known, unknown]);
+    assert_eq!(map.requested_handshakes(), vec![unknown]);
+
+    map.insert(fake_entry(2));
+    assert!(map.requested_handshakes().is_empty());
+    assert_eq!(map.secret_generation(unknown), Some(1));
+}
+
 #[test]
 fn thread_shutdown() {
     let signer = stateless_reset::Signer::new(b"secret");
@@ -12,17 +12,19 @@ pub mod custom_congestion_controller {
     use s2n_quic::provider::{
         congestion_controller,
         congestion_controller::{
-            CongestionController, Publisher, RandomGenerator, RttEstimator, Timestamp,
+            CongestionController, CongestionPhase, CongestionState, Publisher, RandomGenerator,
+            RttEstimator, Timestamp,
         },
     };
 
     /// Define a congestion controller containing any state you wish to track.
-    /// For this example, we track the size of the congestion window in bytes and
-    /// the number of bytes in flight.
+    /// For this example, we track the size of the congestion window in bytes,
+    /// the number of bytes in flight and the number of times the window was reduced.
     #[derive(Debug, Clone)]
     pub struct MyCongestionController {
         congestion_window: u32,
         bytes_in_flight: u32,
+        loss_epoch: u64,
     }
 
     /// The following is a simple implementation of the `CongestionController` trait
@@ -104,6 +106,7 @@ pub mod custom_congestion_controller {
             // further reduction.
             self.bytes_in_flight -= lost_bytes;
             self.congestion_window = (self.congestion_window as f32 * 0.5) as u32;
+            self.loss_epoch += 1;
         }
 
         fn on_explicit_congestion<Pub: Publisher>(
@@ -113,6 +116,7 @@ pub mod custom_congestion_controller {
             publisher: &mut Pub,
         ) {
             self.congestion_window = (self.congestion_window as f32 * 0.5) as u32;
+            self.loss_epoch += 1;
         }
 
         fn on_mtu_update<Pub: Publisher>(&mut self, max_data_size: u16, publisher: &mut Pub) {
@@ -126,6 +130,18 @@ pub mod custom_congestion_controller {
         fn earliest_departure_time(&self) -> Option<Timestamp> {
             None
         }
+
+        // The snapshot is emitted periodically in the `CongestionState` event, which allows
+        // the same dashboards to be used for every congestion controller.
+        fn congestion_state(&self) -> CongestionState {
+            CongestionState {
+                congestion_window: self.congestion_window,
+                bytes_in_flight: self.bytes_in_flight,
+                pacing_rate: None,
+                phase: CongestionPhase::CongestionAvoidance,
+                loss_epoch: self.loss_epoch,
+            }
+        }
     }
 
     // Define an endpoint for the custom congestion controller so it may be used as a
@@ -145,6 +161,7 @@ pub mod custom_congestion_controller {
                 // Specify the initial congestion window
                 congestion_window: 10 * path_info.max_datagram_size as u32,
                 bytes_in_flight: 0,
+                loss_epoch: 0,
             }
         }
     }
@@ -0,0 +1,35 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{black_box, BenchmarkId, Criterion};
+use s2n_quic_core::crypto::InitialKey as _;
+use s2n_quic_crypto::initial::InitialKey;
+
+pub fn benchmarks(c: &mut Criterion) {
+    initial(c);
+}
+
+fn initial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("crypto/initial");
+
+    for len in [8, 20] {
+        let connection_id = vec![123u8; len];
+
+        group.bench_with_input(
+            BenchmarkId::new("new_server", len),
+            &connection_id,
+            |b, connection_id| {
+                b.iter(|| InitialKey::new_server(black_box(connection_id)));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("new_client", len),
+            &connection_id,
+            |b, connection_id| {
+                b.iter(|| InitialKey::new_client(black_box(connection_id)));
+            },
+        );
+    }
+
+    group.finish();
+}
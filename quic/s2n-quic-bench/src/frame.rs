@@ -3,10 +3,11 @@
 
 use criterion::{black_box, BenchmarkId, Criterion, Throughput};
 use s2n_codec::{DecoderBufferMut, Encoder, EncoderBuffer, EncoderValue};
-use s2n_quic_core::frame::FrameMut;
+use s2n_quic_core::{ack, frame::FrameMut, packet::number::PacketNumberSpace, varint::VarInt};
 
 pub fn benchmarks(c: &mut Criterion) {
     codec(c);
+    ack(c);
 }
 
 struct Input {
@@ -88,3 +89,34 @@ fn codec(c: &mut Criterion) {
 
     group.finish();
 }
+
+fn ack(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frame/ack");
+
+    for range_count in [1u32, 10, 100, 255] {
+        let mut ranges = ack::Ranges::new(range_count as usize);
+        // skip every other packet number so each one is its own range
+        for pn in (0..range_count * 2).step_by(2) {
+            let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u32(pn));
+            ranges.insert_packet_number(pn).unwrap();
+        }
+
+        for capacity in [64usize, 1200] {
+            let mut buffer = vec![0; capacity];
+            group.bench_with_input(
+                BenchmarkId::new(format!("encode/{capacity}"), range_count),
+                &ranges,
+                |b, ranges| {
+                    b.iter(|| {
+                        let frame = ranges.fit(VarInt::from_u8(25), None, capacity);
+                        if let Some(frame) = black_box(frame) {
+                            EncoderBuffer::new(&mut buffer).encode(&frame);
+                        }
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
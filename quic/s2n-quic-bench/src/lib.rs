@@ -4,18 +4,22 @@
 use criterion::Criterion;
 
 mod buffer;
+mod crypto;
 mod frame;
 mod inet;
 mod packet;
+mod scenario;
 mod sync;
 mod varint;
 mod xdp;
 
 pub fn benchmarks(c: &mut Criterion) {
     buffer::benchmarks(c);
+    crypto::benchmarks(c);
     frame::benchmarks(c);
     inet::benchmarks(c);
     packet::benchmarks(c);
+    scenario::benchmarks(c);
     sync::benchmarks(c);
     varint::benchmarks(c);
     xdp::benchmarks(c);
@@ -0,0 +1,267 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! End-to-end scenarios which drive a client and server over the simulated network
+//!
+//! Criterion measures the wall-clock time of each scenario, which reflects the CPU cost of the
+//! endpoints. The scenarios also record their throughput in simulated time, which only depends on
+//! the protocol behavior and can be checked against a baseline. See [`regression`] for details.
+
+use bytes::Bytes;
+use criterion::{BenchmarkId, Criterion, Throughput};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use s2n_quic::{
+    client::Connect,
+    provider::{
+        io::testing::{primary, spawn, test, Handle, Model, Result},
+        random,
+    },
+    stream::PeerStream,
+    Client, Server,
+};
+use s2n_quic_core::{crypto::tls::testing::certificates, stream::testing::Data};
+use std::{net::SocketAddr, time::Duration};
+
+mod regression;
+
+/// The one-way delays of the simulated networks
+const DELAYS: [Duration; 3] = [
+    Duration::from_millis(1),
+    Duration::from_millis(25),
+    Duration::from_millis(100),
+];
+
+/// The packet drop rates of the simulated networks
+const DROP_RATES: [f64; 3] = [0.0, 0.01, 0.05];
+
+/// The number of concurrent connections established by the handshake workload
+const CONNECTIONS: u64 = 10;
+
+/// The number of streams opened by the stream workload
+const STREAMS: u64 = 100;
+
+/// The amount of data sent on each stream by the stream workload
+const STREAM_DATA: usize = 1_000;
+
+/// The amount of data transferred by the goodput workload
+const TRANSFER: u64 = 1_000_000;
+
+pub fn benchmarks(c: &mut Criterion) {
+    let mut results = regression::Results::default();
+
+    for workload in [Workload::Handshakes, Workload::Streams, Workload::Goodput] {
+        let mut group = c.benchmark_group(format!("scenario/{}", workload.name()));
+        // each iteration runs a complete simulation so limit the number of samples
+        group.sample_size(10);
+        group.throughput(workload.throughput());
+
+        for delay in DELAYS {
+            for drop_rate in DROP_RATES {
+                let network = Network { delay, drop_rate };
+                let id = network.to_string();
+
+                let mut simulated = None;
+
+                group.bench_with_input(BenchmarkId::from_parameter(&id), &network, |b, network| {
+                    b.iter(|| {
+                        let duration = workload.run(network.model());
+                        // the simulated time is the same for every iteration so only record it once
+                        simulated.get_or_insert(duration);
+                    })
+                });
+
+                // the scenario doesn't run if it's excluded by the benchmark filter
+                if let Some(simulated) = simulated {
+                    results.record(
+                        format!("{}/{id}", workload.name()),
+                        workload.units() as f64 / simulated.as_secs_f64(),
+                    );
+                }
+            }
+        }
+
+        group.finish();
+    }
+
+    results.check();
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Network {
+    delay: Duration,
+    drop_rate: f64,
+}
+
+impl Network {
+    fn model(&self) -> Model {
+        let model = Model::default();
+        model.set_delay(self.delay).set_drop_rate(self.drop_rate);
+        model
+    }
+}
+
+impl core::fmt::Display for Network {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "rtt={}ms,loss={}%",
+            (self.delay * 2).as_millis(),
+            self.drop_rate * 100.0
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Workload {
+    /// Establishes concurrent connections
+    Handshakes,
+    /// Opens many short streams on a single connection
+    Streams,
+    /// Transfers data on a single stream
+    Goodput,
+}
+
+impl Workload {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Handshakes => "handshakes",
+            Self::Streams => "streams",
+            Self::Goodput => "goodput",
+        }
+    }
+
+    /// The number of connections, streams or bytes processed by a single run
+    fn units(&self) -> u64 {
+        match self {
+            Self::Handshakes => CONNECTIONS,
+            Self::Streams => STREAMS,
+            Self::Goodput => TRANSFER,
+        }
+    }
+
+    fn throughput(&self) -> Throughput {
+        match self {
+            Self::Handshakes | Self::Streams => Throughput::Elements(self.units()),
+            Self::Goodput => Throughput::Bytes(self.units()),
+        }
+    }
+
+    /// Runs the workload over the network and returns the simulated duration
+    fn run(self, network: Model) -> Duration {
+        test(network, |handle| {
+            let server_addr = server(handle)?;
+            let client = client(handle)?;
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+
+            match self {
+                Self::Handshakes => {
+                    for _ in 0..CONNECTIONS {
+                        let client = client.clone();
+                        let connect = connect.clone();
+                        primary::spawn(async move {
+                            client.connect(connect).await.unwrap();
+                        });
+                    }
+                }
+                Self::Streams => {
+                    primary::spawn(async move {
+                        let mut connection = client.connect(connect).await.unwrap();
+                        let data = Bytes::from(vec![42; STREAM_DATA]);
+
+                        let mut streams = vec![];
+                        for _ in 0..STREAMS {
+                            let mut stream = connection.open_send_stream().await.unwrap();
+                            stream.send_data(data.clone()).unwrap();
+                            stream.finish().unwrap();
+                            streams.push(stream);
+                        }
+
+                        // wait for all of the streams to be acknowledged
+                        for mut stream in streams {
+                            stream.close().await.unwrap();
+                        }
+                    });
+                }
+                Self::Goodput => {
+                    primary::spawn(async move {
+                        let mut connection = client.connect(connect).await.unwrap();
+                        let mut stream = connection.open_send_stream().await.unwrap();
+
+                        let mut data = Data::new(TRANSFER);
+                        while let Some(chunk) = data.send_one(usize::MAX) {
+                            stream.send(chunk).await.unwrap();
+                        }
+
+                        stream.close().await.unwrap();
+                    });
+                }
+            }
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
+
+fn server(handle: &Handle) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+        .with_random(Random::with_seed(456))?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    // accept connections and drain all of the streams
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            spawn(async move {
+                while let Ok(Some(stream)) = connection.accept().await {
+                    let mut stream = match stream {
+                        PeerStream::Receive(stream) => stream,
+                        PeerStream::Bidirectional(stream) => stream.split().0,
+                    };
+
+                    spawn(async move { while let Ok(Some(_)) = stream.receive().await {} });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+fn client(handle: &Handle) -> Result<Client> {
+    Ok(Client::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(certificates::CERT_PEM)?
+        .with_random(Random::with_seed(123))?
+        .start()?)
+}
+
+/// A seeded random generator so the simulated results are reproducible
+struct Random(StdRng);
+
+impl Random {
+    fn with_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl random::Provider for Random {
+    type Generator = Self;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> core::result::Result<Self::Generator, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl random::Generator for Random {
+    fn public_random_fill(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    fn private_random_fill(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+}
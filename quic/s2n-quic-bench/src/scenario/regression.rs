@@ -0,0 +1,100 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks the simulated throughput of the scenarios against a baseline
+//!
+//! The check is enabled by setting `S2N_QUIC_BENCH_BASELINE` to a file path. If the file doesn't
+//! exist, the results of the run are written to it. Otherwise, the run fails if the throughput of
+//! any scenario dropped by more than `S2N_QUIC_BENCH_THRESHOLD` (default: 0.05) relative to the
+//! baseline.
+
+use std::{collections::BTreeMap, fmt::Write as _, path::PathBuf};
+
+const BASELINE_VAR: &str = "S2N_QUIC_BENCH_BASELINE";
+const THRESHOLD_VAR: &str = "S2N_QUIC_BENCH_THRESHOLD";
+const DEFAULT_THRESHOLD: f64 = 0.05;
+
+#[derive(Debug, Default)]
+pub struct Results {
+    values: BTreeMap<String, f64>,
+}
+
+impl Results {
+    /// Records the simulated throughput of a scenario
+    pub fn record(&mut self, name: String, value: f64) {
+        self.values.insert(name, value);
+    }
+
+    /// Compares the results against the baseline, if configured
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the scenarios regressed by more than the threshold
+    pub fn check(&self) {
+        let Some(path) = std::env::var_os(BASELINE_VAR).map(PathBuf::from) else {
+            return;
+        };
+
+        let threshold = std::env::var(THRESHOLD_VAR)
+            .ok()
+            .map(|value| value.parse::<f64>().expect("invalid regression threshold"))
+            .unwrap_or(DEFAULT_THRESHOLD);
+
+        let baseline = match std::fs::read_to_string(&path) {
+            Ok(baseline) => baseline,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                std::fs::write(&path, self.encode()).expect("could not write baseline");
+                eprintln!("saved scenario baseline to {}", path.display());
+                return;
+            }
+            Err(err) => panic!("could not read baseline {}: {err}", path.display()),
+        };
+
+        let regressions = self.compare(&decode(&baseline), threshold);
+
+        assert!(
+            regressions.is_empty(),
+            "scenarios regressed by more than {:.1}%:\n{}",
+            threshold * 100.0,
+            regressions.join("\n")
+        );
+    }
+
+    /// Returns a description of each scenario which regressed by more than the threshold
+    fn compare(&self, baseline: &BTreeMap<String, f64>, threshold: f64) -> Vec<String> {
+        let mut regressions = vec![];
+
+        for (name, value) in &self.values {
+            let Some(expected) = baseline.get(name) else {
+                continue;
+            };
+
+            if *value < expected * (1.0 - threshold) {
+                let change = (value - expected) / expected * 100.0;
+                regressions.push(format!(
+                    "  {name}: {expected:.2} -> {value:.2} ({change:.1}%)"
+                ));
+            }
+        }
+
+        regressions
+    }
+
+    fn encode(&self) -> String {
+        let mut out = String::new();
+        for (name, value) in &self.values {
+            let _ = writeln!(out, "{name} {value}");
+        }
+        out
+    }
+}
+
+fn decode(baseline: &str) -> BTreeMap<String, f64> {
+    baseline
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.rsplit_once(' ')?;
+            Some((name.to_string(), value.parse().ok()?))
+        })
+        .collect()
+}
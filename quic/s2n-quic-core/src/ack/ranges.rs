@@ -3,7 +3,7 @@
 
 use crate::{
     ack::Settings,
-    frame::ack,
+    frame::{self, ack},
     interval_set::{IntervalSet, RangeInclusiveIter},
     packet::number::{PacketNumber, PacketNumberRange},
     varint::VarInt,
@@ -13,6 +13,7 @@ use core::{
     num::NonZeroUsize,
     ops::{Bound, Deref, DerefMut, RangeInclusive},
 };
+use s2n_codec::EncoderValue;
 
 #[derive(Clone, Debug)]
 pub struct Ranges(IntervalSet<PacketNumber>);
@@ -100,6 +101,57 @@ impl Ranges {
             _ => 0,
         }
     }
+
+    /// Returns an ACK frame which can be encoded in at most `max_len` bytes
+    ///
+    /// If all of the ranges don't fit, the ranges with the smallest packet numbers are omitted,
+    /// since those are the most likely to have already been reported to the peer. `None` is
+    /// returned if there are no ranges or the frame doesn't fit with only the largest range.
+    #[inline]
+    pub fn fit(
+        &self,
+        ack_delay: VarInt,
+        ecn_counts: Option<ack::EcnCounts>,
+        max_len: usize,
+    ) -> Option<frame::Ack<Limited>> {
+        let frame = |len| frame::Ack {
+            ack_delay,
+            ack_ranges: Limited { ranges: self, len },
+            ecn_counts,
+        };
+
+        let mut len = self.interval_len();
+
+        if len == 0 {
+            return None;
+        }
+
+        // the common case is all of the ranges fit in the frame
+        if frame(len).encoding_size() <= max_len {
+            return Some(frame(len));
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.3
+        //# An ACK frame is expected to fit within a
+        //# single QUIC packet.  If it does not, then older ranges (those with
+        //# the smallest packet numbers) are omitted.
+        if frame(1).encoding_size() > max_len {
+            return None;
+        }
+
+        // find the largest number of ranges that fit
+        let mut fits = 1;
+        while fits + 1 < len {
+            let mid = fits + (len - fits) / 2;
+            if frame(mid).encoding_size() <= max_len {
+                fits = mid;
+            } else {
+                len = mid;
+            }
+        }
+
+        Some(frame(fits))
+    }
 }
 
 type Iter<'a> = core::iter::Map<
@@ -119,6 +171,38 @@ impl<'a> ack::AckRanges for &'a Ranges {
     }
 }
 
+/// The most recent ranges of a [`Ranges`] set, as returned by [`Ranges::fit`]
+#[derive(Clone, Copy, Debug)]
+pub struct Limited<'a> {
+    ranges: &'a Ranges,
+    len: usize,
+}
+
+impl<'a> Limited<'a> {
+    /// Returns the number of ranges which are included
+    #[inline]
+    pub fn interval_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the smallest packet number which is included
+    #[inline]
+    pub fn min_value(&self) -> Option<PacketNumber> {
+        let index = self.ranges.interval_len().checked_sub(self.len)?;
+        let range = self.ranges.inclusive_ranges().nth(index)?;
+        Some(*range.start())
+    }
+}
+
+impl<'a> ack::AckRanges for Limited<'a> {
+    type Iter = core::iter::Take<Iter<'a>>;
+
+    #[inline]
+    fn ack_ranges(&self) -> Self::Iter {
+        self.ranges.ack_ranges().take(self.len)
+    }
+}
+
 impl Deref for Ranges {
     type Target = IntervalSet<PacketNumber>;
 
@@ -154,6 +238,7 @@ mod tests {
         packet::number::{testing::iter as packet_numbers_iter, PacketNumberSpace},
         varint,
     };
+    use ack::AckRanges as _;
     use bolero::check;
 
     #[test]
@@ -309,4 +394,55 @@ mod tests {
                 assert!(ack_ranges.insert_packet_number_range(range_1).is_ok());
             });
     }
+
+    #[test]
+    fn fit_fuzz() {
+        check!()
+            .with_type::<(Vec<u16>, u16)>()
+            .for_each(|(packet_numbers, max_len)| {
+                let mut ack_ranges = Ranges::new(u8::MAX as usize);
+                for packet_number in packet_numbers {
+                    let packet_number = PacketNumberSpace::ApplicationData
+                        .new_packet_number(VarInt::from_u16(*packet_number));
+                    let _ = ack_ranges.insert_packet_number(packet_number);
+                }
+
+                let max_len = *max_len as usize;
+                let ack_delay = VarInt::from_u8(25);
+                let all_ranges: Vec<_> = (&ack_ranges).ack_ranges().collect();
+
+                let limited = |len| frame::Ack {
+                    ack_delay,
+                    ack_ranges: Limited {
+                        ranges: &ack_ranges,
+                        len,
+                    },
+                    ecn_counts: None,
+                };
+
+                let Some(frame) = ack_ranges.fit(ack_delay, None, max_len) else {
+                    // either there's nothing to ACK or the largest range doesn't fit
+                    if !all_ranges.is_empty() {
+                        assert!(limited(1).encoding_size() > max_len);
+                    }
+                    return;
+                };
+
+                assert!(frame.encoding_size() <= max_len);
+
+                // the included ranges should always be the most recent
+                let len = frame.ack_ranges.interval_len();
+                let ranges: Vec<_> = frame.ack_ranges().collect();
+                assert_eq!(&all_ranges[..len], &ranges[..]);
+                assert_eq!(
+                    frame.ack_ranges.min_value().map(PacketNumber::as_varint),
+                    ranges.last().map(|range| *range.start())
+                );
+
+                // including another range would exceed the limit
+                if len < all_ranges.len() {
+                    assert!(limited(len + 1).encoding_size() > max_len);
+                }
+            });
+    }
 }
@@ -16,6 +16,11 @@ const RECOMMENDED_ELICITATION_INTERVAL: u8 = 4;
 /// The recommended number of packet number ranges that an endpoint should store
 const RECOMMENDED_RANGES_LIMIT: u8 = 10;
 
+// An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
+// https://github.com/quicwg/base-drafts/pull/3623
+/// The recommended value for the ack_eliciting_threshold setting
+const RECOMMENDED_ACK_ELICITING_THRESHOLD: u8 = 10;
+
 /// Settings for ACK frames
 #[derive(Clone, Copy, Debug)]
 pub struct Settings {
@@ -39,6 +44,17 @@ pub struct Settings {
 
     /// The number of packet number intervals an endpoint is willing to store
     pub ack_ranges_limit: u8,
+
+    /// The number of ack-eliciting packets received before an ACK is sent without waiting
+    /// for the `max_ack_delay` to expire
+    pub ack_eliciting_threshold: u8,
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
+    //# In order to assist loss detection at the sender, an endpoint SHOULD
+    //# generate and send an ACK frame without delay when it receives an ack-
+    //# eliciting packet either:
+    /// Whether ack-eliciting packets which are received out of order are acknowledged immediately
+    pub ack_on_reordering: bool,
 }
 
 impl Default for Settings {
@@ -72,6 +88,8 @@ impl Settings {
         ack_delay_exponent: AckDelayExponent::RECOMMENDED.as_u8(),
         ack_elicitation_interval: RECOMMENDED_ELICITATION_INTERVAL,
         ack_ranges_limit: RECOMMENDED_RANGES_LIMIT,
+        ack_eliciting_threshold: RECOMMENDED_ACK_ELICITING_THRESHOLD,
+        ack_on_reordering: true,
     };
 
     /// Decodes the peer's `Ack Delay` field
@@ -115,4 +115,99 @@ impl TryFrom<u64> for Error {
 pub trait TryInto {
     /// Returns the associated [`Error`], if any
     fn application_error(&self) -> Option<Error>;
+
+    /// Returns the associated [`Error`] as a typed application error code
+    ///
+    /// Returns `None` if there is no associated error or if the code isn't known to `T`.
+    /// Error code types can be defined with [`error_codes!`](crate::application::error_codes).
+    #[inline]
+    fn application_error_as<T: TryFrom<Error>>(&self) -> Option<T>
+    where
+        Self: Sized,
+    {
+        T::try_from(self.application_error()?).ok()
+    }
 }
+
+/// Defines an enum of application error codes
+///
+/// Application protocols can share the enum between peers and use it with the close and reset
+/// APIs, which avoids mismatched codes. The enum converts into an [`Error`] and can be
+/// recovered from an [`Error`] with [`TryFrom`], or from connection and stream errors with
+/// [`TryInto::application_error_as`]. Codes must fit in a `u32`.
+///
+/// ```
+/// s2n_quic_core::application::error_codes! {
+///     /// Errors for my protocol
+///     pub enum MyError {
+///         NoError = 0x0,
+///         InternalError = 0x1,
+///     }
+/// }
+///
+/// let error = s2n_quic_core::application::Error::from(MyError::InternalError);
+/// assert_eq!(*error, 0x1);
+/// assert_eq!(MyError::try_from(error), Ok(MyError::InternalError));
+/// ```
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __application_error_codes__ {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $code:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// Returns the error code of the variant
+            #[inline]
+            pub const fn code(self) -> u32 {
+                match self {
+                    $(Self::$variant => $code,)*
+                }
+            }
+        }
+
+        impl From<$name> for $crate::application::Error {
+            #[inline]
+            fn from(value: $name) -> Self {
+                $crate::varint::VarInt::from_u32(value.code()).into()
+            }
+        }
+
+        impl TryFrom<$crate::application::Error> for $name {
+            /// The error code wasn't defined by the enum
+            type Error = $crate::application::Error;
+
+            #[inline]
+            fn try_from(error: $crate::application::Error) -> Result<Self, Self::Error> {
+                $(
+                    if *error == $code as u64 {
+                        return Ok(Self::$variant);
+                    }
+                )*
+                Err(error)
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{:?} ({:#x})", self, self.code())
+            }
+        }
+    };
+}
+
+pub use crate::__application_error_codes__ as error_codes;
@@ -5,6 +5,6 @@ pub mod error;
 #[cfg(feature = "alloc")]
 pub(crate) mod server_name;
 
-pub use error::Error;
+pub use error::{error_codes, Error};
 #[cfg(feature = "alloc")]
 pub use server_name::ServerName;
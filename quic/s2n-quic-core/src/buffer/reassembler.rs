@@ -14,6 +14,8 @@ use alloc::collections::{vec_deque, VecDeque};
 use bytes::BytesMut;
 
 mod duplex;
+#[cfg(feature = "std")]
+pub mod pool;
 mod probe;
 mod reader;
 mod request;
@@ -23,6 +25,8 @@ mod writer;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "std")]
+pub use pool::Pool;
 use request::Request;
 use slot::Slot;
 
@@ -85,6 +89,8 @@ const UNKNOWN_FINAL_SIZE: u64 = u64::MAX;
 pub struct Reassembler {
     slots: VecDeque<Slot>,
     cursors: Cursors,
+    #[cfg(feature = "std")]
+    pool: Option<Pool>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -123,6 +129,19 @@ impl Reassembler {
         Self::default()
     }
 
+    /// Creates a new `Reassembler` which allocates its buffers from a shared [`Pool`]
+    ///
+    /// Buffers are allocated and aligned to the pool's chunk size rather than growing with the
+    /// stream offset.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn with_pool(pool: Pool) -> Reassembler {
+        Self {
+            pool: Some(pool),
+            ..Self::default()
+        }
+    }
+
     /// Returns true if the buffer has completely been written to and the final size is known
     #[inline]
     pub fn is_writing_complete(&self) -> bool {
@@ -425,8 +444,8 @@ impl Reassembler {
 
             ensure!(next.start() == end, continue);
 
-            let current_block = Self::align_offset(start, Self::allocation_size(start));
-            let next_block = Self::align_offset(next.start(), Self::allocation_size(next.start()));
+            let current_block = Self::align_offset(start, self.allocation_size(start));
+            let next_block = Self::align_offset(next.start(), self.allocation_size(next.start()));
             ensure!(current_block == next_block, continue);
 
             if let Some(next) = self.slots.remove(idx + 1) {
@@ -573,7 +592,7 @@ impl Reassembler {
         R: Reader + ?Sized,
     {
         let start = reader.current_offset().as_u64();
-        let mut size = Self::allocation_size(start);
+        let mut size = self.allocation_size(start);
         let mut offset = Self::align_offset(start, size);
 
         // don't allocate for data we've already consumed
@@ -599,7 +618,7 @@ impl Reassembler {
             }
         }
 
-        let buffer = BytesMut::with_capacity(size);
+        let buffer = self.alloc(size);
 
         let end = offset + size as u64;
         Slot::new(offset, end, buffer)
@@ -614,8 +633,32 @@ impl Reassembler {
         (offset / (alignment as u64)) * (alignment as u64)
     }
 
+    /// Allocates a buffer with the given capacity
+    #[inline(always)]
+    fn alloc(&self, size: usize) -> BytesMut {
+        #[cfg(feature = "std")]
+        if let Some(pool) = self.pool.as_ref() {
+            return pool.alloc(size);
+        }
+
+        BytesMut::with_capacity(size)
+    }
+
     /// Returns the desired allocation size for the given offset
     ///
+    /// If the buffer was created with a [`Pool`], the pool's chunk size is always used.
+    #[inline(always)]
+    fn allocation_size(&self, offset: u64) -> usize {
+        #[cfg(feature = "std")]
+        if let Some(pool) = self.pool.as_ref() {
+            return pool.chunk_size();
+        }
+
+        Self::default_allocation_size(offset)
+    }
+
+    /// Returns the default allocation size for the given offset
+    ///
     /// The allocation size gradually increases as the offset increases. This is under
     /// the assumption that streams that receive a lot of data will continue to receive
     /// a lot of data.
@@ -629,7 +672,7 @@ impl Reassembler {
     /// | 262144         | 32768           |
     /// | >=1048575      | 65536           |
     #[inline(always)]
-    fn allocation_size(offset: u64) -> usize {
+    fn default_allocation_size(offset: u64) -> usize {
         for pow in (2..=4).rev() {
             let mult = 1 << pow;
             let square = mult * mult;
@@ -675,9 +718,9 @@ impl Reassembler {
 
                     ensure!(next.start() == end, continue);
 
-                    let current_block = Self::align_offset(start, Self::allocation_size(start));
+                    let current_block = Self::align_offset(start, self.allocation_size(start));
                     let next_block =
-                        Self::align_offset(next.start(), Self::allocation_size(next.start()));
+                        Self::align_offset(next.start(), self.allocation_size(next.start()));
                     ensure!(current_block == next_block, continue);
 
                     panic!("unmerged slots at {idx} and {} {self:#?}", idx + 1);
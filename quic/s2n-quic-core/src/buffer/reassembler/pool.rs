@@ -0,0 +1,183 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A shared allocator for [`Reassembler`](super::Reassembler) buffers
+//!
+//! Without a pool, each `Reassembler` allocates buffers which grow with the stream offset and
+//! returns them to the global allocator once the application drops the received chunks. Servers
+//! with many concurrent streams end up with a wide spread of allocation sizes and lifetimes,
+//! which fragments the heap.
+//!
+//! A [`Pool`] hands out buffers from fixed-size chunks instead. Each chunk is retained by the pool
+//! for its entire lifetime and is reused once every reference to it, including any chunks which
+//! were handed to the application, has been dropped. The pool never retains more than its
+//! configured maximum size; allocations past that point fall back to the global allocator.
+
+use alloc::{collections::VecDeque, sync::Arc};
+use bytes::BytesMut;
+use core::fmt;
+use std::sync::Mutex;
+
+/// The maximum size of a chunk
+///
+/// The [`Reassembler`](super::Reassembler) assumes a single buffer never exceeds 64K.
+pub const MAX_CHUNK_SIZE: u32 = 1 << 16;
+
+/// The number of retained chunks checked for reuse before allocating a new one
+///
+/// Chunks are checked in the order they were handed out, so the oldest chunks, which are
+/// the most likely to have been released, are checked first.
+const MAX_RECLAIM_ATTEMPTS: usize = 8;
+
+/// A pool of fixed-size chunks which is shared by all of the receive buffers on an endpoint
+#[derive(Clone)]
+pub struct Pool(Arc<Inner>);
+
+struct Inner {
+    chunk_size: usize,
+    max_chunks: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Empty handles for each of the retained chunks
+    ///
+    /// A handle can reclaim the entire chunk once it's the only remaining reference.
+    chunks: VecDeque<BytesMut>,
+}
+
+impl Pool {
+    /// Creates a pool of `chunk_size` chunks which retains at most `max_size` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0 or greater than [`MAX_CHUNK_SIZE`].
+    pub fn new(chunk_size: u32, max_size: u64) -> Self {
+        assert!(
+            (1..=MAX_CHUNK_SIZE).contains(&chunk_size),
+            "chunk_size must be within 1..={MAX_CHUNK_SIZE}"
+        );
+
+        let max_chunks = (max_size / chunk_size as u64)
+            .try_into()
+            .unwrap_or(usize::MAX);
+
+        Self(Arc::new(Inner {
+            chunk_size: chunk_size as usize,
+            max_chunks,
+            state: Mutex::new(State::default()),
+        }))
+    }
+
+    /// Returns the size of each chunk in the pool
+    #[inline]
+    pub fn chunk_size(&self) -> usize {
+        self.0.chunk_size
+    }
+
+    /// Returns the total number of bytes retained by the pool
+    ///
+    /// This includes chunks which are currently in use by receive buffers or the application.
+    #[inline]
+    pub fn retained_size(&self) -> u64 {
+        let chunks = self.0.state.lock().map_or(0, |state| state.chunks.len());
+        (chunks * self.0.chunk_size) as u64
+    }
+
+    /// Allocates a buffer with a capacity of `len` bytes
+    #[inline]
+    pub(super) fn alloc(&self, len: usize) -> BytesMut {
+        debug_assert!(len <= self.0.chunk_size);
+
+        let mut chunk = self
+            .take_chunk()
+            .unwrap_or_else(|| BytesMut::with_capacity(len));
+
+        // trim the chunk down to the requested size
+        if chunk.capacity() > len {
+            drop(chunk.split_off(len));
+        }
+
+        chunk
+    }
+
+    #[inline]
+    fn take_chunk(&self) -> Option<BytesMut> {
+        let chunk_size = self.0.chunk_size;
+        let mut state = self.0.state.lock().ok()?;
+
+        for _ in 0..MAX_RECLAIM_ATTEMPTS.min(state.chunks.len()) {
+            let mut handle = state.chunks.pop_front()?;
+
+            // the handle is the only reference so we can reuse the chunk
+            let is_reclaimed = handle.try_reclaim(chunk_size);
+            let chunk = is_reclaimed.then(|| handle.split_off(0));
+            state.chunks.push_back(handle);
+
+            if chunk.is_some() {
+                return chunk;
+            }
+        }
+
+        ensure!(state.chunks.len() < self.0.max_chunks, None);
+
+        let mut handle = BytesMut::with_capacity(chunk_size);
+        let chunk = handle.split_off(0);
+        state.chunks.push_back(handle);
+        Some(chunk)
+    }
+}
+
+impl PartialEq for Pool {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("chunk_size", &self.0.chunk_size)
+            .field("max_chunks", &self.0.max_chunks)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuse_test() {
+        let pool = Pool::new(1024, 2048);
+
+        let a = pool.alloc(1024);
+        let b = pool.alloc(100);
+        assert_eq!(a.capacity(), 1024);
+        assert_eq!(b.capacity(), 100);
+        assert_eq!(pool.retained_size(), 2048);
+
+        // the pool is exhausted so this falls back to the global allocator
+        let c = pool.alloc(1024);
+        assert_eq!(pool.retained_size(), 2048);
+        drop(c);
+
+        // frozen chunks still hold a reference until the application drops them
+        let a = a.freeze();
+        let d = pool.alloc(1024);
+        assert_eq!(pool.retained_size(), 2048);
+        drop(d);
+
+        let a_ptr = a.as_ptr();
+        drop(a);
+        drop(b);
+
+        // both chunks are reused once they've been released
+        let a = pool.alloc(1024);
+        let b = pool.alloc(1024);
+        assert!(a_ptr == a.as_ptr() || a_ptr == b.as_ptr());
+        assert_eq!(pool.retained_size(), 2048);
+    }
+}
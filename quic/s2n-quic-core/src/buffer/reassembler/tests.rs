@@ -45,6 +45,14 @@ impl Default for Model {
 }
 
 impl Model {
+    #[cfg(feature = "std")]
+    fn with_pool(pool: Pool) -> Self {
+        Self {
+            buffer: Reassembler::with_pool(pool),
+            ..Default::default()
+        }
+    }
+
     fn apply_all(&mut self, ops: &[Op]) {
         for op in ops {
             self.apply(op);
@@ -99,6 +107,13 @@ impl Model {
     fn finish(&mut self) {
         // make sure a cleared buffer is the same as a new one
         self.buffer.reset();
+
+        #[cfg(feature = "std")]
+        if let Some(pool) = self.buffer.pool.clone() {
+            assert_eq!(self.buffer, Reassembler::with_pool(pool));
+            return;
+        }
+
         assert_eq!(self.buffer, Reassembler::new());
     }
 }
@@ -113,6 +128,40 @@ fn model_test() {
     })
 }
 
+#[test]
+#[cfg(feature = "std")]
+#[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+fn pool_model_test() {
+    check!()
+        .with_type::<(u16, Vec<Op>)>()
+        .for_each(|(chunk_size, ops)| {
+            let chunk_size = (*chunk_size).max(1) as u32;
+            let pool = Pool::new(chunk_size, chunk_size as u64 * 4);
+            let mut model = Model::with_pool(pool.clone());
+            model.apply_all(ops);
+            model.finish();
+            assert!(pool.retained_size() <= chunk_size as u64 * 4);
+        })
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pool_alignment_test() {
+    let pool = Pool::new(1000, u64::MAX);
+    let mut buffer = Reassembler::with_pool(pool.clone());
+
+    // writes are split on chunk boundaries rather than the default allocation sizes
+    buffer.write_at(VarInt::from_u32(0), &[1; 2500]).unwrap();
+    let chunks: Vec<_> = buffer.drain().map(|chunk| chunk.len()).collect();
+    assert_eq!(chunks, [1000, 1000, 500]);
+    assert_eq!(pool.retained_size(), 3000);
+
+    // the chunks were released so they're reused for the next writes
+    buffer.write_at(VarInt::from_u32(2500), &[2; 3500]).unwrap();
+    assert_eq!(buffer.drain().count(), 4);
+    assert_eq!(pool.retained_size(), 4000);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
 fn write_and_pop() {
@@ -1064,7 +1113,7 @@ fn allocation_size_test() {
 
     for (index, (offset, size)) in received.iter().copied().enumerate() {
         assert_eq!(
-            Reassembler::allocation_size(offset),
+            Reassembler::default_allocation_size(offset),
             size,
             "offset = {}",
             offset
@@ -1073,7 +1122,7 @@ fn allocation_size_test() {
         if let Some((offset, _)) = received.get(index + 1) {
             let offset = offset - 1;
             assert_eq!(
-                Reassembler::allocation_size(offset),
+                Reassembler::default_allocation_size(offset),
                 size,
                 "offset = {}",
                 offset
@@ -3,6 +3,48 @@
 
 use crate::{application, crypto::tls, transport};
 pub use crate::{frame::ConnectionClose, inet::SocketAddress};
+use core::time::Duration;
+
+/// Controls how long a connection closed by the application retains its state
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Linger {
+    /// The connection remains in the closing state for three times the probe timeout (PTO)
+    #[default]
+    Default,
+    /// The connection remains in the closing state for the provided duration
+    Duration(Duration),
+    /// The connection skips the closing state and its state is dropped immediately
+    ///
+    /// No CONNECTION_CLOSE frame is sent to the peer.
+    Abort,
+}
+
+impl Linger {
+    /// Returns `true` if the connection state should be dropped without notifying the peer
+    #[inline]
+    pub fn is_abort(&self) -> bool {
+        matches!(self, Self::Abort)
+    }
+}
+
+/// Controls what happens to a connection once the application drops its last handle
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DropBehavior {
+    /// The connection flushes all of the outstanding streams before closing without an error
+    #[default]
+    Linger,
+    /// The connection is closed immediately with the provided error code
+    ///
+    /// All of the outstanding streams are terminated.
+    Close(application::Error),
+    /// The connection is left open until it's closed by the peer or the idle timeout expires
+    ///
+    /// Keep-alive PINGs are disabled so the connection is able to idle out. This gives the peer
+    /// a chance to finish its streams without the connection being closed from under it.
+    KeepAlive,
+}
 
 /// Provides a hook for applications to rewrite CONNECTION_CLOSE frames
 ///
@@ -158,3 +200,128 @@ impl Formatter for Production {
         transport::Error::APPLICATION_ERROR.into()
     }
 }
+
+/// A CONNECTION_REFUSED reason phrase which tells the client how long to wait before retrying
+///
+/// Servers which shed load send this hint so clients can tell an overloaded server apart from a
+/// network failure and back off for at least the requested duration.
+#[derive(Clone, Copy)]
+pub struct RetryAfter {
+    buffer: [u8; RetryAfter::MAX_LEN],
+    len: u8,
+}
+
+impl RetryAfter {
+    /// The longest duration a hint can request
+    ///
+    /// The reason phrase is sent in an unauthenticated Initial packet, so anyone on the path can
+    /// forge it. Longer hints are clamped to keep a forged one from holding off clients
+    /// indefinitely.
+    pub const MAX: Duration = Duration::from_secs(60);
+
+    const PREFIX: &'static [u8] = b"retry-after-ms=";
+    // the prefix followed by the largest `u64` value
+    const MAX_LEN: usize = Self::PREFIX.len() + 20;
+
+    /// Encodes a reason phrase for the provided duration
+    pub fn new(duration: Duration) -> Self {
+        let mut buffer = [0u8; Self::MAX_LEN];
+        buffer[..Self::PREFIX.len()].copy_from_slice(Self::PREFIX);
+
+        let mut millis = duration.as_millis().min(u64::MAX as u128) as u64;
+        let mut digits = [0u8; 20];
+        let mut digit_count = 0;
+        loop {
+            digits[digit_count] = b'0' + (millis % 10) as u8;
+            digit_count += 1;
+            millis /= 10;
+            if millis == 0 {
+                break;
+            }
+        }
+
+        let mut len = Self::PREFIX.len();
+        for digit in digits[..digit_count].iter().rev() {
+            buffer[len] = *digit;
+            len += 1;
+        }
+
+        Self {
+            buffer,
+            len: len as u8,
+        }
+    }
+
+    /// Decodes the duration from a reason phrase, if it contains a hint
+    ///
+    /// The returned duration is clamped to [`Self::MAX`].
+    pub fn decode(reason: &[u8]) -> Option<Duration> {
+        let digits = reason.strip_prefix(Self::PREFIX)?;
+        if digits.is_empty() || digits.len() > 20 {
+            return None;
+        }
+
+        let mut millis = 0u64;
+        for digit in digits {
+            if !digit.is_ascii_digit() {
+                return None;
+            }
+            millis = millis.checked_mul(10)?.checked_add((digit - b'0') as u64)?;
+        }
+
+        Some(Duration::from_millis(millis).min(Self::MAX))
+    }
+
+    /// Returns the encoded reason phrase
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.len as usize]
+    }
+}
+
+impl core::fmt::Debug for RetryAfter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("RetryAfter")
+            .field(&Self::decode(self.as_bytes()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_after_round_trip() {
+        for millis in [0, 1, 9, 10, 1500, 60_000] {
+            let duration = Duration::from_millis(millis);
+            let reason = RetryAfter::new(duration);
+            assert_eq!(RetryAfter::decode(reason.as_bytes()), Some(duration));
+        }
+
+        assert_eq!(
+            RetryAfter::new(Duration::from_millis(1500)).as_bytes(),
+            b"retry-after-ms=1500"
+        );
+    }
+
+    #[test]
+    fn retry_after_oversized() {
+        for millis in [60_001, u32::MAX as u64, u64::MAX] {
+            let reason = RetryAfter::new(Duration::from_millis(millis));
+            assert_eq!(RetryAfter::decode(reason.as_bytes()), Some(RetryAfter::MAX));
+        }
+    }
+
+    #[test]
+    fn retry_after_invalid() {
+        for reason in [
+            &b""[..],
+            b"connection evicted",
+            b"retry-after-ms=",
+            b"retry-after-ms=12a",
+            b"retry-after-ms=99999999999999999999",
+        ] {
+            assert_eq!(RetryAfter::decode(reason), None);
+        }
+    }
+}
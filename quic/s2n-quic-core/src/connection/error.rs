@@ -28,6 +28,10 @@ pub enum Error {
         frame_type: u64,
         reason: &'static str,
         initiator: endpoint::Location,
+        /// The amount of time the peer asked the client to wait before connecting again
+        ///
+        /// This is only set when the server refused the connection with a retry-after hint.
+        retry_after: Option<Duration>,
         source: &'static panic::Location<'static>,
     },
 
@@ -94,6 +98,45 @@ pub enum Error {
         source: &'static panic::Location<'static>,
     },
 
+    /// The connection was closed because the network reported the peer as unreachable before the
+    /// handshake was confirmed
+    #[non_exhaustive]
+    Unreachable {
+        source: &'static panic::Location<'static>,
+    },
+
+    /// The connection attempt was abandoned because the server doesn't support the QUIC version
+    /// chosen by the client
+    #[non_exhaustive]
+    UnsupportedVersion {
+        source: &'static panic::Location<'static>,
+    },
+
+    /// The operation requires the handshake to be complete
+    ///
+    /// The connection remains open and the operation can be retried once the handshake completes.
+    #[non_exhaustive]
+    HandshakeNotComplete {
+        source: &'static panic::Location<'static>,
+    },
+
+    /// Keying material could not be exported from the TLS session
+    ///
+    /// The connection remains open.
+    #[non_exhaustive]
+    KeyingMaterialExport {
+        reason: &'static str,
+        source: &'static panic::Location<'static>,
+    },
+
+    /// The connection state was handed off to another endpoint
+    ///
+    /// The peer wasn't notified since it continues the connection with the other endpoint.
+    #[non_exhaustive]
+    HandedOff {
+        source: &'static panic::Location<'static>,
+    },
+
     /// The connection was closed due to an unspecified reason
     #[non_exhaustive]
     Unspecified {
@@ -160,6 +203,26 @@ impl fmt::Display for Error {
                 f,
                 "The connection was closed due to: {reason}"
             ),
+            Self::Unreachable { .. } => write!(
+                f,
+                "The connection was closed because the peer was reported as unreachable"
+            ),
+            Self::UnsupportedVersion { .. } => write!(
+                f,
+                "The connection attempt was abandoned because the server doesn't support the QUIC version"
+            ),
+            Self::HandshakeNotComplete { .. } => write!(
+                f,
+                "The operation requires the handshake to be complete"
+            ),
+            Self::KeyingMaterialExport { reason, .. } => write!(
+                f,
+                "Keying material could not be exported: {reason}"
+            ),
+            Self::HandedOff { .. } => write!(
+                f,
+                "The connection state was handed off to another endpoint"
+            ),
             Self::Unspecified { .. } => {
                 write!(f, "The connection was closed due to an unspecified reason")
             }
@@ -232,6 +295,18 @@ impl PartialEq for Error {
                     reason: b_reason, ..
                 },
             ) => a_reason.eq(b_reason),
+            (Error::Unreachable { .. }, Error::Unreachable { .. }) => true,
+            (Error::UnsupportedVersion { .. }, Error::UnsupportedVersion { .. }) => true,
+            (Error::HandshakeNotComplete { .. }, Error::HandshakeNotComplete { .. }) => true,
+            (
+                Error::KeyingMaterialExport {
+                    reason: a_reason, ..
+                },
+                Error::KeyingMaterialExport {
+                    reason: b_reason, ..
+                },
+            ) => a_reason.eq(b_reason),
+            (Error::HandedOff { .. }, Error::HandedOff { .. }) => true,
             (Error::Unspecified { .. }, Error::Unspecified { .. }) => true,
             _ => false,
         }
@@ -255,10 +330,26 @@ impl Error {
             Error::ImmediateClose { source, .. } => source,
             Error::EndpointClosing { source } => source,
             Error::InvalidConfiguration { source, .. } => source,
+            Error::Unreachable { source } => source,
+            Error::UnsupportedVersion { source } => source,
+            Error::HandshakeNotComplete { source } => source,
+            Error::KeyingMaterialExport { source, .. } => source,
+            Error::HandedOff { source } => source,
             Error::Unspecified { source } => source,
         }
     }
 
+    /// Returns the amount of time the server asked the client to wait before connecting again
+    ///
+    /// Servers which are shedding load can refuse connections with a `CONNECTION_REFUSED` error
+    /// which carries this hint.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::Transport { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
     #[track_caller]
     fn from_transport_error(error: transport::Error, initiator: endpoint::Location) -> Self {
         let source = panic::Location::caller();
@@ -275,6 +366,7 @@ impl Error {
                 frame_type: error.frame_type.into(),
                 reason: error.reason,
                 initiator,
+                retry_after: None,
                 source,
             },
         }
@@ -367,6 +459,46 @@ impl Error {
         Error::InvalidConfiguration { source, reason }
     }
 
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn unreachable() -> Error {
+        let source = panic::Location::caller();
+        Error::Unreachable { source }
+    }
+
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn unsupported_version() -> Error {
+        let source = panic::Location::caller();
+        Error::UnsupportedVersion { source }
+    }
+
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn handshake_not_complete() -> Error {
+        let source = panic::Location::caller();
+        Error::HandshakeNotComplete { source }
+    }
+
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn keying_material_export(reason: &'static str) -> Error {
+        let source = panic::Location::caller();
+        Error::KeyingMaterialExport { reason, source }
+    }
+
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn handed_off() -> Error {
+        let source = panic::Location::caller();
+        Error::HandedOff { source }
+    }
+
     #[inline]
     #[track_caller]
     #[doc(hidden)]
@@ -469,6 +601,15 @@ pub fn as_frame<'a, F: connection::close::Formatter>(
         Error::ImmediateClose { .. } => None,
         Error::EndpointClosing { .. } => None,
         Error::InvalidConfiguration { .. } => None,
+        // The peer can't be reached so there's no point in notifying it
+        Error::Unreachable { .. } => None,
+        // The server didn't create any state for the connection so it's abandoned silently
+        Error::UnsupportedVersion { .. } => None,
+        // These errors are only returned to the application and don't close the connection
+        Error::HandshakeNotComplete { .. } => None,
+        Error::KeyingMaterialExport { .. } => None,
+        // The peer continues the connection with the endpoint the state was handed off to
+        Error::HandedOff { .. } => None,
         Error::Unspecified { .. } => {
             let error =
                 transport::Error::INTERNAL_ERROR.with_reason("an unspecified error occurred");
@@ -500,20 +641,33 @@ impl From<transport::Error> for Error {
 
 impl<'a> From<ConnectionClose<'a>> for Error {
     #[track_caller]
-    fn from(error: ConnectionClose) -> Self {
-        if let Some(frame_type) = error.frame_type {
+    fn from(connection_close: ConnectionClose) -> Self {
+        if let Some(frame_type) = connection_close.frame_type {
             let error = transport::Error {
-                code: transport::error::Code::new(error.error_code),
+                code: transport::error::Code::new(connection_close.error_code),
                 // we use an empty `&'static str` so we don't allocate anything
                 // in the event of an error
                 reason: "",
                 frame_type,
             };
-            Self::from_transport_error(error, endpoint::Location::Remote)
+            let mut error = Self::from_transport_error(error, endpoint::Location::Remote);
+
+            if let Self::Transport {
+                code, retry_after, ..
+            } = &mut error
+            {
+                if *code == transport::Error::CONNECTION_REFUSED.code {
+                    *retry_after = connection_close
+                        .reason
+                        .and_then(connection::close::RetryAfter::decode);
+                }
+            }
+
+            error
         } else {
             let source = panic::Location::caller();
             Self::Application {
-                error: error.error_code.into(),
+                error: connection_close.error_code.into(),
                 initiator: endpoint::Location::Remote,
                 source,
             }
@@ -548,6 +702,11 @@ impl From<Error> for std::io::ErrorKind {
             Error::ImmediateClose { .. } => ErrorKind::Other,
             Error::EndpointClosing { .. } => ErrorKind::Other,
             Error::InvalidConfiguration { .. } => ErrorKind::Other,
+            Error::Unreachable { .. } => ErrorKind::ConnectionRefused,
+            Error::UnsupportedVersion { .. } => ErrorKind::Unsupported,
+            Error::HandshakeNotComplete { .. } => ErrorKind::NotConnected,
+            Error::KeyingMaterialExport { .. } => ErrorKind::Other,
+            Error::HandedOff { .. } => ErrorKind::Other,
             Error::Unspecified { .. } => ErrorKind::Other,
         }
     }
@@ -0,0 +1,40 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Determines when a connection flush is considered complete
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FlushMode {
+    /// The flush completes once all of the buffered application data has been written into
+    /// packets and handed off to the endpoint for transmission
+    #[default]
+    Transmitted,
+
+    /// The flush completes once all of the buffered stream data has been transmitted and
+    /// acknowledged by the peer
+    ///
+    /// Datagrams are not acknowledged, so they only need to be transmitted.
+    Acknowledged,
+
+    /// The flush completes once all of the buffered stream data has been acknowledged by the
+    /// peer, no frames are waiting to be transmitted and no ack-eliciting packets remain in
+    /// flight
+    ///
+    /// At this point, the connection has fully drained and is only waiting on the peer.
+    Idle,
+}
+
+impl FlushMode {
+    /// Returns `true` if the flush waits for the peer to acknowledge the data
+    #[inline]
+    pub fn is_acknowledged(self) -> bool {
+        matches!(self, Self::Acknowledged | Self::Idle)
+    }
+
+    /// Returns `true` if the flush waits for all of the ack-eliciting packets to be acknowledged
+    /// or declared lost
+    #[inline]
+    pub fn is_idle(self) -> bool {
+        matches!(self, Self::Idle)
+    }
+}
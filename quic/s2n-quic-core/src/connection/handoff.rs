@@ -0,0 +1,311 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Connection state which is handed off from the server endpoint that completed the handshake
+//! to the endpoint which serves the connection from then on
+//!
+//! The state is taken before either endpoint sent or processed any 1-RTT packets, so both packet
+//! number spaces, the streams and the key phase are still at their initial values and aren't part
+//! of the state.
+
+use crate::{
+    application::ServerName,
+    connection::{InitialId, LocalId, PeerId},
+    crypto::tls::{CipherSuite, OneRttSecrets, Secret},
+    inet::{SocketAddress, SocketAddressV4, SocketAddressV6},
+    stateless_reset,
+};
+use alloc::vec::Vec;
+use bytes::Bytes;
+use core::time::Duration;
+use s2n_codec::{
+    decoder_invariant, decoder_value, DecoderBuffer, DecoderError, Encoder, EncoderValue,
+};
+
+/// The version of the encoding, which changes whenever the layout of [`State`] does
+const ENCODING_VERSION: u8 = 1;
+
+const IPV4: u8 = 4;
+const IPV6: u8 = 6;
+
+/// The state needed to resume a server connection on another endpoint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    /// The QUIC version of the connection
+    pub quic_version: u32,
+    /// The traffic secrets the 1-RTT keys are derived from
+    pub secrets: OneRttSecrets,
+    /// The destination connection ID of the client's first Initial packet
+    pub original_destination_connection_id: InitialId,
+    /// The connection ID the server chose during the handshake, which has the sequence number 0
+    pub local_connection_id: LocalId,
+    pub stateless_reset_token: stateless_reset::Token,
+    /// The connection ID sent in the `preferred_address` transport parameter, which has the
+    /// sequence number 1
+    pub preferred_address_connection_id: Option<(LocalId, stateless_reset::Token)>,
+    /// The connection ID the client chose during the handshake
+    pub peer_connection_id: PeerId,
+    pub remote_address: SocketAddress,
+    pub local_address: SocketAddress,
+    /// The smoothed RTT measured during the handshake
+    pub smoothed_rtt: Duration,
+    /// The encoded transport parameters of the client
+    pub peer_transport_parameters: Bytes,
+    pub application_protocol: Bytes,
+    pub server_name: Option<ServerName>,
+}
+
+impl State {
+    /// Encodes the state
+    ///
+    /// The encoding contains the traffic secrets of the connection, so it must be protected
+    /// accordingly while it's transferred to the other endpoint.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.encode_to_vec()
+    }
+
+    /// Decodes a state produced by [`Self::to_vec`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecoderError> {
+        let (state, remaining) = DecoderBuffer::new(bytes).decode::<Self>()?;
+        remaining.ensure_empty()?;
+        Ok(state)
+    }
+}
+
+decoder_value!(
+    impl<'a> State {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (version, buffer) = buffer.decode::<u8>()?;
+            decoder_invariant!(
+                version == ENCODING_VERSION,
+                "unsupported handoff state encoding"
+            );
+
+            let (quic_version, buffer) = buffer.decode::<u32>()?;
+
+            let (cipher_suite, buffer) = buffer.decode::<u8>()?;
+            let cipher_suite = match cipher_suite {
+                1 => CipherSuite::TLS_AES_128_GCM_SHA256,
+                2 => CipherSuite::TLS_AES_256_GCM_SHA384,
+                3 => CipherSuite::TLS_CHACHA20_POLY1305_SHA256,
+                _ => return Err(DecoderError::InvariantViolation("unsupported cipher suite")),
+            };
+            let (client, buffer) = buffer.decode_slice_with_len_prefix::<u8>()?;
+            let client = Secret::new(client.into_less_safe_slice())
+                .ok_or(DecoderError::InvariantViolation("invalid secret"))?;
+            let (server, buffer) = buffer.decode_slice_with_len_prefix::<u8>()?;
+            let server = Secret::new(server.into_less_safe_slice())
+                .ok_or(DecoderError::InvariantViolation("invalid secret"))?;
+            let secrets = OneRttSecrets {
+                cipher_suite,
+                client,
+                server,
+            };
+
+            let (original_destination_connection_id, buffer) =
+                buffer.decode_with_len_prefix::<u8, InitialId>()?;
+            let (local_connection_id, buffer) = buffer.decode_with_len_prefix::<u8, LocalId>()?;
+            let (stateless_reset_token, buffer) = buffer.decode()?;
+
+            let (has_preferred_address, buffer) = buffer.decode::<u8>()?;
+            let (preferred_address_connection_id, buffer) = if has_preferred_address != 0 {
+                let (id, buffer) = buffer.decode_with_len_prefix::<u8, LocalId>()?;
+                let (token, buffer) = buffer.decode()?;
+                (Some((id, token)), buffer)
+            } else {
+                (None, buffer)
+            };
+
+            let (peer_connection_id, buffer) = buffer.decode_with_len_prefix::<u8, PeerId>()?;
+
+            let (Address(remote_address), buffer) = buffer.decode()?;
+            let (Address(local_address), buffer) = buffer.decode()?;
+
+            let (smoothed_rtt, buffer) = buffer.decode::<u64>()?;
+            let smoothed_rtt = Duration::from_micros(smoothed_rtt);
+
+            let (peer_transport_parameters, buffer) =
+                buffer.decode_slice_with_len_prefix::<u16>()?;
+            let peer_transport_parameters =
+                Bytes::copy_from_slice(peer_transport_parameters.into_less_safe_slice());
+
+            let (application_protocol, buffer) = buffer.decode_slice_with_len_prefix::<u8>()?;
+            let application_protocol =
+                Bytes::copy_from_slice(application_protocol.into_less_safe_slice());
+
+            let (has_server_name, buffer) = buffer.decode::<u8>()?;
+            let (server_name, buffer) = if has_server_name != 0 {
+                let (server_name, buffer) = buffer.decode_slice_with_len_prefix::<u16>()?;
+                let server_name = core::str::from_utf8(server_name.into_less_safe_slice())
+                    .map_err(|_| DecoderError::InvariantViolation("invalid server name"))?;
+                (Some(server_name.into()), buffer)
+            } else {
+                (None, buffer)
+            };
+
+            let state = Self {
+                quic_version,
+                secrets,
+                original_destination_connection_id,
+                local_connection_id,
+                stateless_reset_token,
+                preferred_address_connection_id,
+                peer_connection_id,
+                remote_address,
+                local_address,
+                smoothed_rtt,
+                peer_transport_parameters,
+                application_protocol,
+                server_name,
+            };
+
+            Ok((state, buffer))
+        }
+    }
+);
+
+impl EncoderValue for State {
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        encoder.encode(&ENCODING_VERSION);
+        encoder.encode(&self.quic_version);
+
+        let cipher_suite: u8 = match self.secrets.cipher_suite {
+            CipherSuite::TLS_AES_128_GCM_SHA256 => 1,
+            CipherSuite::TLS_AES_256_GCM_SHA384 => 2,
+            CipherSuite::TLS_CHACHA20_POLY1305_SHA256 => 3,
+            CipherSuite::Unknown => 0,
+        };
+        encoder.encode(&cipher_suite);
+        encoder.encode_with_len_prefix::<u8, _>(&self.secrets.client.as_bytes());
+        encoder.encode_with_len_prefix::<u8, _>(&self.secrets.server.as_bytes());
+
+        encoder.encode_with_len_prefix::<u8, _>(&self.original_destination_connection_id);
+        encoder.encode_with_len_prefix::<u8, _>(&self.local_connection_id);
+        encoder.encode(&self.stateless_reset_token);
+
+        if let Some((id, token)) = self.preferred_address_connection_id.as_ref() {
+            encoder.encode(&1u8);
+            encoder.encode_with_len_prefix::<u8, _>(id);
+            encoder.encode(token);
+        } else {
+            encoder.encode(&0u8);
+        }
+
+        encoder.encode_with_len_prefix::<u8, _>(&self.peer_connection_id);
+
+        encoder.encode(&Address(self.remote_address));
+        encoder.encode(&Address(self.local_address));
+
+        encoder.encode(&(self.smoothed_rtt.as_micros() as u64));
+
+        encoder.encode_with_len_prefix::<u16, _>(&&self.peer_transport_parameters[..]);
+        encoder.encode_with_len_prefix::<u8, _>(&&self.application_protocol[..]);
+
+        if let Some(server_name) = self.server_name.as_ref() {
+            encoder.encode(&1u8);
+            encoder.encode_with_len_prefix::<u16, _>(&server_name.as_bytes());
+        } else {
+            encoder.encode(&0u8);
+        }
+    }
+}
+
+/// A socket address prefixed with its family
+struct Address(SocketAddress);
+
+decoder_value!(
+    impl<'a> Address {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (family, buffer) = buffer.decode::<u8>()?;
+            match family {
+                IPV4 => {
+                    let (address, buffer) = buffer.decode::<SocketAddressV4>()?;
+                    Ok((Self(address.into()), buffer))
+                }
+                IPV6 => {
+                    let (address, buffer) = buffer.decode::<SocketAddressV6>()?;
+                    Ok((Self(address.into()), buffer))
+                }
+                _ => Err(DecoderError::InvariantViolation("invalid address family")),
+            }
+        }
+    }
+);
+
+impl EncoderValue for Address {
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        match &self.0 {
+            SocketAddress::IpV4(address) => {
+                encoder.encode(&IPV4);
+                encoder.encode(address);
+            }
+            SocketAddress::IpV6(address) => {
+                encoder.encode(&IPV6);
+                encoder.encode(address);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inet::{IpV4Address, IpV6Address};
+
+    fn state() -> State {
+        State {
+            quic_version: 1,
+            secrets: OneRttSecrets {
+                cipher_suite: CipherSuite::TLS_AES_256_GCM_SHA384,
+                client: Secret::new(&[1; 48]).unwrap(),
+                server: Secret::new(&[2; 48]).unwrap(),
+            },
+            original_destination_connection_id: InitialId::try_from_bytes(&[3; 8]).unwrap(),
+            local_connection_id: LocalId::try_from_bytes(&[4; 16]).unwrap(),
+            stateless_reset_token: [5; 16].into(),
+            preferred_address_connection_id: Some((
+                LocalId::try_from_bytes(&[6; 16]).unwrap(),
+                [7; 16].into(),
+            )),
+            peer_connection_id: PeerId::try_from_bytes(&[8; 20]).unwrap(),
+            remote_address: SocketAddressV4::new(IpV4Address::new([192, 0, 2, 1]), 4433).into(),
+            local_address: SocketAddressV6::new(IpV6Address::new([9; 16]), 443).into(),
+            smoothed_rtt: Duration::from_millis(25),
+            peer_transport_parameters: Bytes::from_static(&[10; 100]),
+            application_protocol: Bytes::from_static(b"h3"),
+            server_name: Some("example.com".into()),
+        }
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let state = state();
+        let decoded = State::from_bytes(&state.to_vec()).unwrap();
+        assert_eq!(state, decoded);
+
+        let state = State {
+            preferred_address_connection_id: None,
+            server_name: None,
+            ..state
+        };
+        let decoded = State::from_bytes(&state.to_vec()).unwrap();
+        assert_eq!(state, decoded);
+    }
+
+    #[test]
+    fn invalid_encoding_test() {
+        let mut bytes = state().to_vec();
+
+        // trailing bytes are rejected
+        bytes.push(0);
+        assert!(State::from_bytes(&bytes).is_err());
+        bytes.pop();
+
+        // truncated states are rejected
+        assert!(State::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // states from other versions of the encoding are rejected
+        bytes[0] = ENCODING_VERSION + 1;
+        assert!(State::from_bytes(&bytes).is_err());
+    }
+}
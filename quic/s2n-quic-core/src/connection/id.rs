@@ -5,7 +5,7 @@
 
 use crate::{
     event::{api::SocketAddress, IntoEvent},
-    inet, transport,
+    inet, random, transport,
 };
 use core::time::Duration;
 use s2n_codec::{decoder_value, Encoder, EncoderValue};
@@ -385,6 +385,21 @@ pub trait Generator {
     /// otherwise the endpoint may terminate.
     fn generate(&mut self, connection_info: &ConnectionInfo) -> LocalId;
 
+    /// Generates a connection ID using the endpoint's random provider
+    ///
+    /// The endpoint calls this method instead of [`Self::generate`]. Formats which derive IDs
+    /// from random bytes should draw them from `random` so that ID generation follows the
+    /// configured provider, e.g. a seeded generator in simulations or a hardware RNG. The
+    /// default implementation calls [`Self::generate`].
+    #[inline]
+    fn generate_with_random(
+        &mut self,
+        connection_info: &ConnectionInfo,
+        _random: &mut dyn random::Generator,
+    ) -> LocalId {
+        self.generate(connection_info)
+    }
+
     /// The maximum amount of time each generated connection ID should be
     /// used for. By default there is no maximum, though connection IDs
     /// may be retired due to rotation requirements or peer requests.
@@ -401,6 +416,17 @@ pub trait Generator {
     fn rotate_handshake_connection_id(&self) -> bool {
         true
     }
+
+    /// If true, every generated connection ID consists of uniformly random bytes (default: false)
+    ///
+    /// The endpoint maps the connection ID of every received packet to a connection. Since random
+    /// IDs can't be crafted to collide, the endpoint can use the ID bytes directly for the lookup,
+    /// rather than hashing them with a keyed hash function first. Formats which encode any
+    /// information into the connection ID must not return true.
+    #[inline]
+    fn generates_random_ids(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
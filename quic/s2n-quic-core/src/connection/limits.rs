@@ -3,13 +3,16 @@
 
 use crate::{
     ack,
+    connection::{close::DropBehavior, PriorityClass},
     event::{api::SocketAddress, IntoEvent},
-    inet, recovery, stream,
+    inet::{self, Unspecified as _},
+    recovery, stream,
     transport::parameters::{
         AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
         InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
         InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, MigrationSupport, TransportParameters,
+        MaxDatagramFrameSize, MaxIdleTimeout, MaxUdpPayloadSize, MigrationSupport,
+        ResetStreamAtSupport, TimestampSupport, TransportParameters,
     },
 };
 use core::time::Duration;
@@ -19,6 +22,10 @@ pub use crate::transport::parameters::ValidationError;
 
 const MAX_HANDSHAKE_DURATION_DEFAULT: Duration = Duration::from_secs(10);
 
+/// The number of bytes a connection can transmit in a single round before yielding the TX queue
+/// to other connections on the endpoint
+const TRANSMIT_QUANTUM_DEFAULT: u32 = 64 * 1024;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1.2
 //# A connection will time out if no packets are sent or received for a
 //# period longer than the time negotiated using the max_idle_timeout
@@ -29,6 +36,22 @@ const MAX_HANDSHAKE_DURATION_DEFAULT: Duration = Duration::from_secs(10);
 //# middleboxes from losing state for UDP flows [GATEWAY].
 const MAX_KEEP_ALIVE_PERIOD_DEFAULT: Duration = Duration::from_secs(30);
 
+/// The default number of sent packets tracked while waiting for acknowledgement
+///
+/// This is large enough to not constrain connections with a bandwidth-delay product of ~90MB.
+const MAX_SENT_PACKETS_DEFAULT: u32 = 65_536;
+
+/// The default number of PATH_CHALLENGE frames sent when validating a path
+///
+/// Sending more than one frame makes validation resilient to the loss of a single packet.
+const PATH_CHALLENGE_TRANSMISSIONS_DEFAULT: u8 = 2;
+
+/// The default number of path validations a connection performs concurrently
+///
+/// This matches the number of paths a connection tracks, so only the path limit applies by
+/// default.
+const MAX_PENDING_PATH_VALIDATIONS_DEFAULT: u8 = 5;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1
 //# Prior to validating the client address, servers MUST NOT send more
 //# than three times as many bytes as the number of bytes they have
@@ -51,6 +74,26 @@ impl<'a> ConnectionInfo<'a> {
     }
 }
 
+/// Where the padding for datagrams carrying Initial packets is placed
+///
+/// Datagrams carrying Initial packets are expanded to at least 1200 bytes. Some middleboxes
+/// inspect the Initial packet and behave differently depending on how the datagram was expanded.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InitialPadding {
+    /// The last packet coalesced into the datagram is padded
+    ///
+    /// When a Handshake or 1-RTT packet is sent along with the Initial packet, the trailing packet
+    /// carries the padding. Otherwise, PADDING frames are added to the Initial packet.
+    #[default]
+    Coalesced,
+    /// PADDING frames are always added to the Initial packet
+    ///
+    /// The Initial packet fills the datagram, so packets from other spaces are sent in separate
+    /// datagrams.
+    Initial,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Limits {
     pub(crate) max_idle_timeout: MaxIdleTimeout,
@@ -62,18 +105,53 @@ pub struct Limits {
     pub(crate) max_open_local_unidirectional_streams: stream::limits::LocalUnidirectional,
     pub(crate) max_open_remote_bidirectional_streams: InitialMaxStreamsBidi,
     pub(crate) max_open_remote_unidirectional_streams: InitialMaxStreamsUni,
+    pub(crate) max_accept_queue_bidirectional_streams: stream::limits::AcceptQueue,
+    pub(crate) max_accept_queue_unidirectional_streams: stream::limits::AcceptQueue,
     pub(crate) max_ack_delay: MaxAckDelay,
     pub(crate) ack_delay_exponent: AckDelayExponent,
     pub(crate) max_active_connection_ids: ActiveConnectionIdLimit,
     pub(crate) ack_elicitation_interval: u8,
     pub(crate) ack_ranges_limit: u8,
+    pub(crate) ack_eliciting_threshold: u8,
+    pub(crate) ack_on_reordering: bool,
+    pub(crate) max_sent_packets: u32,
     pub(crate) max_send_buffer_size: stream::limits::MaxSendBufferSize,
+    pub(crate) connection_send_buffer_high_watermark: u64,
+    pub(crate) connection_send_buffer_low_watermark: u64,
+    pub(crate) receive_buffer_chunk_size: u32,
+    pub(crate) receive_buffer_pool_size: u64,
     pub(crate) max_handshake_duration: Duration,
     pub(crate) max_keep_alive_period: Duration,
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
+    pub(crate) max_udp_payload_size: MaxUdpPayloadSize,
     pub(crate) initial_round_trip_time: Duration,
+    pub(crate) handshake_probe_timeout: Duration,
+    pub(crate) duplicate_first_flight: bool,
+    pub(crate) initial_padding: InitialPadding,
+    pub(crate) min_datagram_size: u16,
     pub(crate) migration_support: MigrationSupport,
+    pub(crate) preferred_address_v4: Option<inet::SocketAddressV4>,
+    pub(crate) preferred_address_v6: Option<inet::SocketAddressV6>,
+    pub(crate) reset_stream_at_support: ResetStreamAtSupport,
+    pub(crate) peer_reset_stream_at_support: ResetStreamAtSupport,
+    pub(crate) timestamp_support: TimestampSupport,
+    pub(crate) peer_timestamp_support: TimestampSupport,
+    pub(crate) peer_ack_delay_exponent: AckDelayExponent,
     pub(crate) anti_amplification_multiplier: u8,
+    pub(crate) transmit_quantum: u32,
+    pub(crate) congestion_state_interval: Duration,
+    pub(crate) path_statistics_interval: Duration,
+    pub(crate) path_challenge_transmissions: u8,
+    pub(crate) path_challenge_interval: Duration,
+    pub(crate) path_validation_timeout: Duration,
+    pub(crate) max_pending_path_validations: u8,
+    pub(crate) drop_behavior: DropBehavior,
+    pub(crate) priority_class: PriorityClass,
+    pub(crate) keying_material_export: bool,
+    pub(crate) connection_handoff: bool,
+    pub(crate) half_rtt_data: bool,
+    pub(crate) early_accept: bool,
+    pub(crate) packet_number_skipping: bool,
 }
 
 impl Default for Limits {
@@ -108,18 +186,53 @@ impl Limits {
             max_open_local_unidirectional_streams: stream::limits::LocalUnidirectional::RECOMMENDED,
             max_open_remote_bidirectional_streams: InitialMaxStreamsBidi::RECOMMENDED,
             max_open_remote_unidirectional_streams: InitialMaxStreamsUni::RECOMMENDED,
+            max_accept_queue_bidirectional_streams: stream::limits::AcceptQueue::RECOMMENDED,
+            max_accept_queue_unidirectional_streams: stream::limits::AcceptQueue::RECOMMENDED,
             max_ack_delay: MaxAckDelay::RECOMMENDED,
             ack_delay_exponent: AckDelayExponent::RECOMMENDED,
             max_active_connection_ids: ActiveConnectionIdLimit::RECOMMENDED,
             ack_elicitation_interval: ack::Settings::RECOMMENDED.ack_elicitation_interval,
             ack_ranges_limit: ack::Settings::RECOMMENDED.ack_ranges_limit,
+            ack_eliciting_threshold: ack::Settings::RECOMMENDED.ack_eliciting_threshold,
+            ack_on_reordering: ack::Settings::RECOMMENDED.ack_on_reordering,
+            max_sent_packets: MAX_SENT_PACKETS_DEFAULT,
             max_send_buffer_size: stream::Limits::RECOMMENDED.max_send_buffer_size,
+            connection_send_buffer_high_watermark: u64::MAX,
+            connection_send_buffer_low_watermark: u64::MAX,
+            receive_buffer_chunk_size: 0,
+            receive_buffer_pool_size: 0,
             max_handshake_duration: MAX_HANDSHAKE_DURATION_DEFAULT,
             max_keep_alive_period: MAX_KEEP_ALIVE_PERIOD_DEFAULT,
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
+            max_udp_payload_size: MaxUdpPayloadSize::DEFAULT,
             initial_round_trip_time: recovery::DEFAULT_INITIAL_RTT,
+            handshake_probe_timeout: Duration::ZERO,
+            duplicate_first_flight: false,
+            initial_padding: InitialPadding::Coalesced,
+            min_datagram_size: 0,
             migration_support: MigrationSupport::RECOMMENDED,
+            preferred_address_v4: None,
+            preferred_address_v6: None,
+            reset_stream_at_support: ResetStreamAtSupport::RECOMMENDED,
+            peer_reset_stream_at_support: ResetStreamAtSupport::Disabled,
+            timestamp_support: TimestampSupport::RECOMMENDED,
+            peer_timestamp_support: TimestampSupport::DISABLED,
+            peer_ack_delay_exponent: AckDelayExponent::RECOMMENDED,
             anti_amplification_multiplier: ANTI_AMPLIFICATION_MULTIPLIER,
+            transmit_quantum: TRANSMIT_QUANTUM_DEFAULT,
+            congestion_state_interval: Duration::ZERO,
+            path_statistics_interval: Duration::ZERO,
+            path_challenge_transmissions: PATH_CHALLENGE_TRANSMISSIONS_DEFAULT,
+            path_challenge_interval: Duration::ZERO,
+            path_validation_timeout: Duration::ZERO,
+            max_pending_path_validations: MAX_PENDING_PATH_VALIDATIONS_DEFAULT,
+            drop_behavior: DropBehavior::Linger,
+            priority_class: PriorityClass::Normal,
+            keying_material_export: false,
+            connection_handoff: false,
+            half_rtt_data: false,
+            early_accept: false,
+            packet_number_skipping: true,
         }
     }
 
@@ -216,7 +329,40 @@ impl Limits {
         max_open_remote_unidirectional_streams,
         u64
     );
-    setter!(with_max_ack_delay, max_ack_delay, Duration);
+    setter!(
+        /// Sets the maximum number of peer-initiated bidirectional streams which may be
+        /// waiting to be accepted by the application
+        ///
+        /// Once the application's accept queue is full, the endpoint stops issuing additional
+        /// `MAX_STREAMS` credits for bidirectional streams until the application has accepted
+        /// some of the pending streams. This bounds the queue independently of unidirectional
+        /// streams, so a flood of one type of stream cannot starve the other.
+        with_max_accept_queue_bidirectional_streams,
+        max_accept_queue_bidirectional_streams,
+        u64
+    );
+    setter!(
+        /// Sets the maximum number of peer-initiated unidirectional streams which may be
+        /// waiting to be accepted by the application
+        ///
+        /// Once the application's accept queue is full, the endpoint stops issuing additional
+        /// `MAX_STREAMS` credits for unidirectional streams until the application has accepted
+        /// some of the pending streams.
+        with_max_accept_queue_unidirectional_streams,
+        max_accept_queue_unidirectional_streams,
+        u64
+    );
+    setter!(
+        /// Sets the maximum amount of time the endpoint will delay sending acknowledgements
+        /// (default: 25ms)
+        ///
+        /// The value is advertised to the peer with the `max_ack_delay` transport parameter.
+        /// Larger values reduce the number of ACK frames sent on high-throughput connections at
+        /// the cost of slower loss recovery at the peer.
+        with_max_ack_delay,
+        max_ack_delay,
+        Duration
+    );
     setter!(
         with_max_active_connection_ids,
         max_active_connection_ids,
@@ -224,6 +370,40 @@ impl Limits {
     );
     setter!(with_ack_elicitation_interval, ack_elicitation_interval, u8);
     setter!(with_max_ack_ranges, ack_ranges_limit, u8);
+    setter!(
+        /// Sets the maximum number of sent packets tracked while waiting for acknowledgement
+        /// (default: 65536)
+        ///
+        /// The metadata for sent packets is stored in blocks of consecutive packet numbers, which
+        /// are released once all of their packets are acknowledged or declared lost. The limit
+        /// applies to the span of packet numbers between the oldest unacknowledged packet and the
+        /// most recently sent packet, which bounds the number of blocks a connection can retain
+        /// when a lossy peer leaves gaps in its acknowledgements. Once the limit is exceeded, the
+        /// oldest packets are no longer tracked, any data they carried is retransmitted and their
+        /// metadata is released. Evicted packets are removed from flight without reducing the
+        /// congestion window.
+        with_max_sent_packets,
+        max_sent_packets,
+        u32,
+        |validate_value| {
+            decoder_invariant!(validate_value > 0, "max_sent_packets must be > 0");
+        }
+    );
+    setter!(
+        /// Sets the number of ack-eliciting packets received before an ACK is sent immediately
+        /// (default: 10)
+        ///
+        /// Below this threshold, acknowledgements are delayed by up to the `max_ack_delay`.
+        /// Setting the value to 1 acknowledges every ack-eliciting packet without delay, which
+        /// is useful for latency-sensitive applications, while larger values reduce the number
+        /// of ACK frames for high-throughput applications.
+        with_ack_eliciting_threshold,
+        ack_eliciting_threshold,
+        u8,
+        |validate_value| {
+            decoder_invariant!(validate_value > 0, "ack_eliciting_threshold must be > 0");
+        }
+    );
     setter!(
         /// Sets the maximum send buffer size for a Stream
         ///
@@ -240,12 +420,198 @@ impl Limits {
         max_send_buffer_size,
         u32
     );
+    /// Sets the watermarks for the total amount of unacknowledged data buffered by all of the
+    /// Streams on a connection (default: unlimited)
+    ///
+    /// Once the streams on the connection buffer `high` bytes in total, sending on any of the
+    /// streams will wait until enough data has been acknowledged by the peer to drain the total
+    /// to `low` bytes. This bounds the memory a connection consumes when the application produces
+    /// data faster than a slow peer is able to acknowledge it, while the gap between the
+    /// watermarks avoids waking up senders for every acknowledgement.
+    ///
+    /// The `low` watermark must not exceed the `high` watermark, which must be greater than 0.
+    pub fn with_connection_send_buffer_watermarks(
+        mut self,
+        high: u64,
+        low: u64,
+    ) -> Result<Self, ValidationError> {
+        ensure!(high > 0, Err(ValidationError("high watermark must be > 0")));
+        ensure!(
+            low <= high,
+            Err(ValidationError(
+                "low watermark must not exceed the high watermark"
+            ))
+        );
+
+        self.connection_send_buffer_high_watermark = high;
+        self.connection_send_buffer_low_watermark = low;
+        Ok(self)
+    }
+
+    /// Sets the chunk size and total size of the pool which stream receive buffers are
+    /// allocated from (default: disabled)
+    ///
+    /// By default, each stream allocates receive buffers which grow with the amount of data
+    /// received, from 4KB up to 64KB. With a pool, buffers are allocated in fixed `chunk_size`
+    /// chunks which are shared by every connection on the endpoint and reused once the
+    /// application has dropped the data, which avoids fragmenting the heap when there are many
+    /// concurrent streams. Connections with the same pool settings share the same pool.
+    ///
+    /// The pool retains at most `max_size` bytes of chunks. Once all of them are in use, buffers
+    /// are allocated individually until chunks are released again.
+    ///
+    /// The `chunk_size` must be greater than 0 and at most 64KB, and `max_size` must be at
+    /// least `chunk_size`.
+    pub fn with_receive_buffer_pool(
+        mut self,
+        chunk_size: u32,
+        max_size: u64,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            (1..=1 << 16).contains(&chunk_size),
+            Err(ValidationError("chunk_size must be within 1..=65536"))
+        );
+        ensure!(
+            max_size >= chunk_size as u64,
+            Err(ValidationError("max_size must be at least chunk_size"))
+        );
+
+        self.receive_buffer_chunk_size = chunk_size;
+        self.receive_buffer_pool_size = max_size;
+        Ok(self)
+    }
+
     setter!(
         with_max_handshake_duration,
         max_handshake_duration,
         Duration
     );
     setter!(with_max_keep_alive_period, max_keep_alive_period, Duration);
+    setter!(
+        /// Sets the number of bytes the connection can transmit in a single round (default: 65536)
+        ///
+        /// Connections on an endpoint take turns filling the transmission queue. Once a
+        /// connection has transmitted its quantum, it yields the remaining capacity to the other
+        /// connections until the next round, which prevents a single high-throughput connection
+        /// from consuming the entire queue. Connections with a larger quantum receive a
+        /// proportionally larger share of the queue.
+        with_transmit_quantum,
+        transmit_quantum,
+        u32,
+        |validate_value| {
+            decoder_invariant!(validate_value > 0, "transmit_quantum must be > 0");
+        }
+    );
+    setter!(
+        /// Sets the interval at which the `CongestionState` event is emitted for the connection
+        /// (default: disabled)
+        ///
+        /// The event contains a snapshot of the congestion controller state which is supplied by
+        /// every congestion controller. The snapshot is taken when acknowledgements are processed
+        /// so the event is emitted at most once per interval. Setting the interval to zero
+        /// disables the event.
+        with_congestion_state_interval,
+        congestion_state_interval,
+        Duration
+    );
+    setter!(
+        /// Sets the interval at which the `PathStatistics` event is emitted for each path of the
+        /// connection (default: disabled)
+        ///
+        /// The event contains the round-trip time, loss and validation state of the path, which
+        /// can be used to decide which path the application should prefer. The snapshots are
+        /// taken when datagrams are received so the event is emitted at most once per interval.
+        /// Setting the interval to zero disables the event.
+        with_path_statistics_interval,
+        path_statistics_interval,
+        Duration
+    );
+    setter!(
+        /// Sets the number of PATH_CHALLENGE frames sent when validating a path (default: 2)
+        ///
+        /// Sending more than one PATH_CHALLENGE guards against packet loss, which is useful on
+        /// lossy networks. The frames are spaced out by the interval configured with
+        /// [`Self::with_path_challenge_interval`].
+        with_path_challenge_transmissions,
+        path_challenge_transmissions,
+        u8,
+        |validate_value| {
+            decoder_invariant!(
+                validate_value > 0,
+                "path_challenge_transmissions must be > 0"
+            );
+        }
+    );
+    setter!(
+        /// Sets the minimum interval between the PATH_CHALLENGE frames sent when validating a
+        /// path (default: zero)
+        ///
+        /// By default, the frames are sent in consecutive packets. Spacing them out makes it more
+        /// likely at least one of them survives a burst of loss.
+        with_path_challenge_interval,
+        path_challenge_interval,
+        Duration
+    );
+    setter!(
+        /// Sets the amount of time after which a path validation is abandoned if the peer hasn't
+        /// responded (default: three times the larger of the PTO of the current and new paths)
+        ///
+        /// Setting the timeout to zero restores the default, which is recommended by RFC 9000.
+        with_path_validation_timeout,
+        path_validation_timeout,
+        Duration
+    );
+    setter!(
+        /// Sets the maximum number of path validations a connection performs concurrently
+        /// (default: 5)
+        ///
+        /// Datagrams from new peer addresses are dropped while the limit is reached. This bounds
+        /// the amount of probing traffic sent in response to rapid address changes, such as
+        /// those seen on flaky mobile networks.
+        with_max_pending_path_validations,
+        max_pending_path_validations,
+        u8,
+        |validate_value| {
+            decoder_invariant!(
+                validate_value > 0,
+                "max_pending_path_validations must be > 0"
+            );
+        }
+    );
+    setter!(
+        /// Sets the maximum UDP payload size for the connection (default: 65527)
+        ///
+        /// The value is advertised to the peer with the `max_udp_payload_size` transport
+        /// parameter and clamps the MTU configuration of every path on the connection, so
+        /// datagrams larger than this value will neither be sent nor probed for. This can be
+        /// used to restrict connections over paths which are known to be unable to carry
+        /// larger datagrams, without changing the endpoint-wide MTU configuration.
+        ///
+        /// The value must be within 1200 and 65527 bytes.
+        with_max_udp_payload_size,
+        max_udp_payload_size,
+        u16
+    );
+    /// Sets what happens to the connection once the application drops its last handle
+    /// (default: `DropBehavior::Linger`)
+    ///
+    /// The behavior can also be changed on an individual connection after it was accepted or
+    /// opened.
+    pub fn with_drop_behavior(mut self, behavior: DropBehavior) -> Result<Self, ValidationError> {
+        self.drop_behavior = behavior;
+        Ok(self)
+    }
+
+    /// Sets the class the connection is placed in when competing with other connections on the
+    /// endpoint (default: `PriorityClass::Normal`)
+    ///
+    /// The class can also be changed on an individual connection after it was accepted or
+    /// opened.
+    pub fn with_priority_class(mut self, class: PriorityClass) -> Result<Self, ValidationError> {
+        self.priority_class = class;
+        Ok(self)
+    }
+
     /// Sets whether active connection migration is supported for a server endpoint (default: true)
     ///
     /// If set to false, the `disable_active_migration` transport parameter will be sent to the
@@ -262,6 +628,151 @@ impl Limits {
         Ok(self)
     }
 
+    /// Sets the address the server would like the client to migrate to once the handshake
+    /// is confirmed (default: none)
+    ///
+    /// The address is sent in the `preferred_address` transport parameter, along with a
+    /// connection ID for the client to use after migrating. A preferred address can be
+    /// set for each of the IPv4 and IPv6 address families by calling this method once for each.
+    /// Since the [`Limiter`] is called for each connection, the address can be chosen based on
+    /// the client's address.
+    ///
+    /// This is typically used for anycast deployments, where the handshake arrives on a shared
+    /// address but the rest of the connection should be sent to a unicast address of the host
+    /// that accepted it. Clients are allowed to migrate to the preferred address even if active
+    /// connection migration is disabled. Clients may also ignore the preferred address and
+    /// continue to use the original address. Pinning connection IDs to the host, for example with
+    /// a host Id prefix, lets a load balancer route packets to the same host in either case.
+    ///
+    /// The address must have a specified IP address and a non-zero port. This setting has no
+    /// effect on clients.
+    pub fn with_preferred_address(
+        mut self,
+        address: inet::SocketAddress,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            !address.ip().is_unspecified() && address.port() != 0,
+            Err(ValidationError("preferred address must be specified"))
+        );
+
+        match address.unmap() {
+            inet::SocketAddress::IpV4(address) => self.preferred_address_v4 = Some(address),
+            inet::SocketAddress::IpV6(address) => self.preferred_address_v6 = Some(address),
+        }
+        Ok(self)
+    }
+
+    /// Sets whether reliable stream resets are supported (default: false)
+    ///
+    /// If set to true, the `reset_stream_at` transport parameter will be sent to the peer,
+    /// allowing either endpoint to reset a stream while still delivering the data up to a
+    /// given offset, as described in the QUIC Reliable Stream Reset draft.
+    pub fn with_reset_stream_at(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        if enabled {
+            self.reset_stream_at_support = ResetStreamAtSupport::Enabled
+        } else {
+            self.reset_stream_at_support = ResetStreamAtSupport::Disabled
+        }
+        Ok(self)
+    }
+
+    /// Sets whether the timestamps extension is supported (default: false)
+    ///
+    /// If set to true, the `enable_timestamp` transport parameter will be sent to the peer.
+    /// When both endpoints support the extension, TIMESTAMP frames carrying the time each
+    /// acknowledgement was sent are exchanged, which allows the congestion controller to
+    /// measure the queuing delay in each direction of the path.
+    pub fn with_timestamps(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        if enabled {
+            self.timestamp_support = TimestampSupport::SEND_AND_RECEIVE
+        } else {
+            self.timestamp_support = TimestampSupport::DISABLED
+        }
+        Ok(self)
+    }
+
+    /// Sets whether ack-eliciting packets received out of order are acknowledged immediately
+    /// (default: true)
+    ///
+    /// Acknowledging reordered packets without delay helps the peer detect losses sooner.
+    /// Disabling this reduces the number of ACK frames sent on paths which frequently reorder
+    /// packets, in which case they are sent according to the `max_ack_delay` and
+    /// `ack_eliciting_threshold` instead.
+    pub fn with_ack_on_reordering(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.ack_on_reordering = enabled;
+        Ok(self)
+    }
+
+    /// Sets whether packet numbers are randomly skipped to detect optimistic acknowledgements
+    /// (default: true)
+    ///
+    /// A peer which acknowledges packets it has not received can cause the congestion
+    /// controller to send at rates beyond what the network supports. When enabled, a packet
+    /// number is skipped roughly once every one to two congestion windows, and an
+    /// acknowledgement for the skipped packet number closes the connection with a
+    /// `PROTOCOL_VIOLATION` error. Disabling this is only recommended for trusted peers.
+    pub fn with_packet_number_skipping(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.packet_number_skipping = enabled;
+        Ok(self)
+    }
+
+    /// Sets whether servers hand connections over to the application as soon as the 1-RTT keys
+    /// are available (default: false)
+    ///
+    /// This allows the server to open streams and send data before it has received the client's
+    /// `Finished` message, which saves a round trip for protocols where the server speaks first.
+    /// This data is commonly referred to as 0.5-RTT data.
+    ///
+    /// At this point, the client has not proven possession of its address or its certificate,
+    /// so the server can't know who the data is sent to. Applications using client
+    /// authentication must not send any data which depends on the identity of the client.
+    /// Streams opened by the client can only be accepted once the handshake completes, and
+    /// the connection may still fail the handshake after it was accepted.
+    ///
+    /// This setting has no effect on clients.
+    pub fn with_half_rtt_data(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.half_rtt_data = enabled;
+        Ok(self)
+    }
+
+    /// Sets whether servers hand connections over to the application as soon as the client's
+    /// first flight has been processed (default: false)
+    ///
+    /// At this point the peer's address, server name and application protocol are known, which
+    /// allows the application to perform per-tenant setup concurrently with the rest of the
+    /// handshake. The connection can't be used until the handshake completes.
+    ///
+    /// This setting has no effect on clients.
+    pub fn with_early_accept(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.early_accept = enabled;
+        Ok(self)
+    }
+
+    /// Sets whether the application can export keying material from the connection
+    /// (default: false)
+    ///
+    /// Exporting keying material requires the TLS session to be retained for the lifetime of
+    /// the connection, rather than being discarded once the handshake completes, which
+    /// increases the amount of memory each connection consumes.
+    pub fn with_keying_material_export(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.keying_material_export = enabled;
+        Ok(self)
+    }
+
+    /// Sets whether the server only completes the handshake so the connection can be handed off
+    /// to another endpoint (default: false)
+    ///
+    /// The connection never sends or processes 1-RTT packets. Instead, the traffic secrets are
+    /// retained until the application exports the connection state and imports it into the
+    /// endpoint which serves the connection from then on.
+    ///
+    /// This setting has no effect on clients.
+    #[cfg(feature = "unstable-limits")]
+    pub fn with_connection_handoff(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.connection_handoff = enabled;
+        Ok(self)
+    }
+
     /// Sets the initial round trip time (RTT) for use in recovery mechanisms prior to
     /// measuring an actual RTT sample.
     ///
@@ -282,6 +793,65 @@ impl Limits {
         Ok(self)
     }
 
+    /// Sets the probe timeout (PTO) used for the Initial and Handshake packet number spaces
+    /// before the first RTT sample has been taken (default: derived from the initial RTT)
+    ///
+    /// By default, the first PTO of the handshake is three times the initial round trip time,
+    /// which delays the handshake by about a second whenever the first flight is lost. Lowering
+    /// the timeout reduces the tail handshake latency on lossy networks at the cost of spurious
+    /// retransmissions on paths with a round trip time above the configured value. The timeout
+    /// still backs off exponentially on consecutive expirations and servers never send probes
+    /// past the anti-amplification limit.
+    ///
+    /// The value must be at least 1 millisecond.
+    pub fn with_handshake_probe_timeout(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            value >= recovery::K_GRANULARITY,
+            Err(ValidationError(
+                "provided value must be at least 1 millisecond",
+            ))
+        );
+
+        self.handshake_probe_timeout = value;
+        Ok(self)
+    }
+
+    /// Sets whether the first flight of Initial packets is sent twice (default: false)
+    ///
+    /// Once the first ack-eliciting Initial packet has been sent, an immediate probe
+    /// retransmits the unacknowledged CRYPTO data instead of waiting for the probe timeout.
+    /// This trades a single extra datagram for a lower handshake latency when the first flight
+    /// is lost. Servers only duplicate the flight if it doesn't exceed the anti-amplification
+    /// limit.
+    pub fn with_duplicate_first_flight(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.duplicate_first_flight = enabled;
+        Ok(self)
+    }
+
+    /// Sets where the padding for datagrams carrying Initial packets is placed
+    /// (default: `InitialPadding::Coalesced`)
+    pub fn with_initial_padding(
+        mut self,
+        padding: InitialPadding,
+    ) -> Result<Self, ValidationError> {
+        self.initial_padding = padding;
+        Ok(self)
+    }
+
+    setter!(
+        /// Sets the minimum size of datagrams which don't carry Initial packets (default: 0)
+        ///
+        /// The last packet in a datagram is expanded with PADDING frames until the datagram
+        /// reaches the minimum size. Datagrams are never expanded beyond the maximum datagram
+        /// size of the path.
+        with_min_datagram_size,
+        min_datagram_size,
+        u16
+    );
+
     #[cfg(feature = "unstable-limits")]
     setter!(
         /// Limit how many bytes the Server sends prior to address validation (default: 3)
@@ -301,6 +871,9 @@ impl Limits {
     pub fn load_peer<A, B, C, D>(&mut self, peer_parameters: &TransportParameters<A, B, C, D>) {
         self.max_idle_timeout
             .load_peer(&peer_parameters.max_idle_timeout);
+        self.peer_reset_stream_at_support = peer_parameters.reset_stream_at_support;
+        self.peer_timestamp_support = peer_parameters.timestamp_support;
+        self.peer_ack_delay_exponent = peer_parameters.ack_delay_exponent;
     }
 
     #[doc(hidden)]
@@ -311,6 +884,8 @@ impl Limits {
             max_ack_delay: self.max_ack_delay.as_duration(),
             ack_ranges_limit: self.ack_ranges_limit,
             ack_elicitation_interval: self.ack_elicitation_interval,
+            ack_eliciting_threshold: self.ack_eliciting_threshold,
+            ack_on_reordering: self.ack_on_reordering,
         }
     }
 
@@ -346,6 +921,8 @@ impl Limits {
             max_send_buffer_size: self.max_send_buffer_size,
             max_open_local_unidirectional_streams: self.max_open_local_unidirectional_streams,
             max_open_local_bidirectional_streams: self.max_open_local_bidirectional_streams,
+            max_accept_queue_bidirectional_streams: self.max_accept_queue_bidirectional_streams,
+            max_accept_queue_unidirectional_streams: self.max_accept_queue_unidirectional_streams,
         }
     }
 
@@ -367,23 +944,235 @@ impl Limits {
         self.max_keep_alive_period
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_udp_payload_size(&self) -> u16 {
+        self.max_udp_payload_size
+            .as_u64()
+            .try_into()
+            .unwrap_or(u16::MAX)
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn initial_round_trip_time(&self) -> Duration {
         self.initial_round_trip_time
     }
 
+    /// Returns the probe timeout used during the handshake, if it was configured
+    #[doc(hidden)]
+    #[inline]
+    pub fn handshake_probe_timeout(&self) -> Option<Duration> {
+        Some(self.handshake_probe_timeout).filter(|timeout| !timeout.is_zero())
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn duplicate_first_flight(&self) -> bool {
+        self.duplicate_first_flight
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn initial_padding(&self) -> InitialPadding {
+        self.initial_padding
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn min_datagram_size(&self) -> u16 {
+        self.min_datagram_size
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn active_migration_enabled(&self) -> bool {
         matches!(self.migration_support, MigrationSupport::Enabled)
     }
 
+    /// Returns the IPv4 and IPv6 preferred addresses of the server
+    #[doc(hidden)]
+    #[inline]
+    pub fn preferred_address(
+        &self,
+    ) -> (Option<inet::SocketAddressV4>, Option<inet::SocketAddressV6>) {
+        (self.preferred_address_v4, self.preferred_address_v6)
+    }
+
+    /// Returns `true` if the address is one of the server's preferred addresses
+    #[doc(hidden)]
+    #[inline]
+    pub fn is_preferred_address(&self, address: &inet::SocketAddress) -> bool {
+        match address.unmap() {
+            inet::SocketAddress::IpV4(address) => self.preferred_address_v4 == Some(address),
+            inet::SocketAddress::IpV6(address) => self.preferred_address_v6 == Some(address),
+        }
+    }
+
+    /// Returns `true` if the local endpoint accepts reliable stream resets from the peer
+    #[doc(hidden)]
+    #[inline]
+    pub fn reset_stream_at_enabled(&self) -> bool {
+        matches!(self.reset_stream_at_support, ResetStreamAtSupport::Enabled)
+    }
+
+    /// Returns `true` if the peer accepts reliable stream resets from the local endpoint
+    ///
+    /// This is only valid after the peer's transport parameters have been loaded.
+    #[doc(hidden)]
+    #[inline]
+    pub fn peer_reset_stream_at_enabled(&self) -> bool {
+        matches!(
+            self.peer_reset_stream_at_support,
+            ResetStreamAtSupport::Enabled
+        )
+    }
+
+    /// Returns `true` if TIMESTAMP frames are sent to the peer
+    ///
+    /// This is only valid after the peer's transport parameters have been loaded.
+    #[doc(hidden)]
+    #[inline]
+    pub fn send_timestamps(&self) -> bool {
+        self.timestamp_support.can_send() && self.peer_timestamp_support.can_receive()
+    }
+
+    /// Returns the settings for decoding the TIMESTAMP frames sent by the peer, if the local
+    /// endpoint accepts them
+    ///
+    /// This is only valid after the peer's transport parameters have been loaded.
+    #[doc(hidden)]
+    #[inline]
+    pub fn peer_timestamp_settings(&self) -> Option<ack::Settings> {
+        if !self.timestamp_support.can_receive() {
+            return None;
+        }
+
+        Some(ack::Settings {
+            ack_delay_exponent: self.peer_ack_delay_exponent.as_u8(),
+            ..ack::Settings::default()
+        })
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn anti_amplification_multiplier(&self) -> u8 {
         self.anti_amplification_multiplier
     }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn transmit_quantum(&self) -> u32 {
+        self.transmit_quantum
+    }
+
+    /// Returns the interval of the `CongestionState` event, if it's enabled
+    #[doc(hidden)]
+    #[inline]
+    pub fn congestion_state_interval(&self) -> Option<Duration> {
+        Some(self.congestion_state_interval).filter(|interval| !interval.is_zero())
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn path_challenge_transmissions(&self) -> u8 {
+        self.path_challenge_transmissions
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn path_challenge_interval(&self) -> Duration {
+        self.path_challenge_interval
+    }
+
+    /// Returns the configured path validation timeout, if it overrides the default
+    #[doc(hidden)]
+    #[inline]
+    pub fn path_validation_timeout(&self) -> Option<Duration> {
+        Some(self.path_validation_timeout).filter(|timeout| !timeout.is_zero())
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_pending_path_validations(&self) -> u8 {
+        self.max_pending_path_validations
+    }
+
+    /// Returns the interval of the `PathStatistics` event, if it's enabled
+    #[doc(hidden)]
+    #[inline]
+    pub fn path_statistics_interval(&self) -> Option<Duration> {
+        Some(self.path_statistics_interval).filter(|interval| !interval.is_zero())
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn drop_behavior(&self) -> DropBehavior {
+        self.drop_behavior
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn priority_class(&self) -> PriorityClass {
+        self.priority_class
+    }
+
+    /// Returns the chunk size and total size of the receive buffer pool, if enabled
+    #[doc(hidden)]
+    #[inline]
+    pub fn receive_buffer_pool(&self) -> Option<(u32, u64)> {
+        ensure!(self.receive_buffer_chunk_size > 0, None);
+        Some((
+            self.receive_buffer_chunk_size,
+            self.receive_buffer_pool_size,
+        ))
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn keying_material_export(&self) -> bool {
+        self.keying_material_export
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn connection_handoff(&self) -> bool {
+        self.connection_handoff
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn half_rtt_data(&self) -> bool {
+        self.half_rtt_data
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn early_accept(&self) -> bool {
+        self.early_accept
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn packet_number_skipping(&self) -> bool {
+        self.packet_number_skipping
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_sent_packets(&self) -> u32 {
+        self.max_sent_packets
+    }
+
+    /// Returns the high and low watermarks for the connection-wide send buffer
+    #[doc(hidden)]
+    #[inline]
+    pub fn connection_send_buffer_watermarks(&self) -> (u64, u64) {
+        (
+            self.connection_send_buffer_high_watermark,
+            self.connection_send_buffer_low_watermark,
+        )
+    }
 }
 
 /// Creates limits for a given connection
@@ -417,5 +1206,46 @@ mod tests {
         assert!(limits.with_bidirectional_local_data_window(data).is_ok());
         assert!(limits.with_bidirectional_remote_data_window(data).is_ok());
         assert!(limits.with_unidirectional_data_window(data).is_ok());
+
+        assert!(limits.with_max_sent_packets(0).is_err());
+        assert!(limits.with_max_sent_packets(1).is_ok());
+
+        assert!(limits.with_connection_send_buffer_watermarks(0, 0).is_err());
+        assert!(limits
+            .with_connection_send_buffer_watermarks(10, 11)
+            .is_err());
+        assert!(limits
+            .with_connection_send_buffer_watermarks(10, 10)
+            .is_ok());
+        assert!(limits.with_connection_send_buffer_watermarks(10, 0).is_ok());
+    }
+
+    #[test]
+    fn max_udp_payload_size_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.max_udp_payload_size(), 65527);
+        assert!(limits.with_max_udp_payload_size(1199).is_err());
+        assert!(limits.with_max_udp_payload_size(65528).is_err());
+
+        let limits = limits.with_max_udp_payload_size(1200).unwrap();
+        assert_eq!(limits.max_udp_payload_size(), 1200);
+    }
+
+    #[test]
+    fn ack_settings_test() {
+        let limits = Limits::default();
+        assert!(limits.with_ack_eliciting_threshold(0).is_err());
+
+        let settings = limits
+            .with_max_ack_delay(Duration::from_millis(5))
+            .unwrap()
+            .with_ack_eliciting_threshold(1)
+            .unwrap()
+            .with_ack_on_reordering(false)
+            .unwrap()
+            .ack_settings();
+        assert_eq!(settings.max_ack_delay, Duration::from_millis(5));
+        assert_eq!(settings.ack_eliciting_threshold, 1);
+        assert!(!settings.ack_on_reordering);
     }
 }
@@ -3,9 +3,17 @@
 
 pub mod close;
 pub mod error;
+pub mod flush;
+#[cfg(feature = "alloc")]
+pub mod handoff;
 pub mod id;
 pub mod limits;
+pub mod priority;
+pub mod statistics;
 
 pub use error::{Error, ProcessingError};
+pub use flush::FlushMode;
 pub use id::{InitialId, LocalId, PeerId, UnboundedId};
 pub use limits::Limits;
+pub use priority::PriorityClass;
+pub use statistics::{PathInfo, PathStatistics, PathValidation, Statistics};
@@ -0,0 +1,47 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+/// The class a connection is placed in when it competes with other connections on the endpoint
+/// for resources
+///
+/// When the transmission queue is full, connections in a more urgent class transmit before any
+/// connection in a less urgent class. Connections within the same class take turns transmitting.
+/// When connections need to be evicted, connections in less urgent classes are selected first.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PriorityClass {
+    /// Latency sensitive connections, such as those carrying control plane traffic
+    Control,
+    /// The class connections are placed in unless otherwise configured
+    #[default]
+    Normal,
+    /// Throughput oriented connections which yield to the other classes
+    Bulk,
+}
+
+impl PriorityClass {
+    /// All of the priority classes, ordered from the most to the least urgent
+    pub const ALL: [Self; 3] = [Self::Control, Self::Normal, Self::Bulk];
+
+    /// Returns the position of the class in [`Self::ALL`]
+    #[inline]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordering_test() {
+        for (index, class) in PriorityClass::ALL.iter().enumerate() {
+            assert_eq!(class.index(), index);
+        }
+
+        assert!(PriorityClass::Control < PriorityClass::Normal);
+        assert!(PriorityClass::Normal < PriorityClass::Bulk);
+        assert_eq!(PriorityClass::default(), PriorityClass::Normal);
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Point-in-time transport statistics of a connection
+
+use crate::{
+    event::{self, api, builder, IntoEvent},
+    inet::SocketAddress,
+};
+use core::time::Duration;
+
+/// A snapshot of the transport statistics of a connection
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// The smoothed round-trip time of the active path
+    pub smoothed_rtt: Duration,
+    /// The minimum round-trip time observed on the active path
+    pub min_rtt: Duration,
+    /// The most recent round-trip time sample of the active path
+    pub latest_rtt: Duration,
+    /// The congestion window of the active path, in bytes
+    pub congestion_window: u32,
+    /// The number of bytes in flight on the active path
+    pub bytes_in_flight: u32,
+    /// The most recent delivery rate sample of the active path, in bytes per second
+    ///
+    /// This is `None` if the congestion controller doesn't sample the delivery rate or no data
+    /// has been acknowledged yet.
+    pub delivery_rate: Option<u64>,
+    /// The total number of UDP payload bytes sent
+    pub bytes_sent: u64,
+    /// The total number of UDP payload bytes received
+    pub bytes_received: u64,
+    /// The total number of QUIC packets sent
+    pub packets_sent: u64,
+    /// The total number of QUIC packets received
+    pub packets_received: u64,
+    /// The total number of QUIC packets declared lost
+    pub packets_lost: u64,
+    /// The total number of bytes declared lost
+    pub bytes_lost: u64,
+    /// The total number of received datagrams which were dropped
+    pub datagrams_dropped: u64,
+    /// The number of streams opened by both endpoints
+    pub streams_opened: u64,
+    /// The number of bytes buffered by all of the streams which have not been acknowledged yet
+    pub send_buffer_occupancy: u64,
+    /// The number of network paths the connection is tracking, including the active path
+    pub paths: usize,
+}
+
+/// A lightweight snapshot of the network characteristics of the active path
+///
+/// Unlike [`Statistics`], this doesn't require querying the connection's event context so it is
+/// cheap enough to poll frequently, such as when adapting an application's sending rate.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PathInfo {
+    /// The smoothed round-trip time of the active path
+    pub smoothed_rtt: Duration,
+    /// The minimum round-trip time observed on the active path
+    pub min_rtt: Duration,
+    /// The bottleneck bandwidth estimated by the congestion controller, in bytes per second
+    ///
+    /// This is `None` if the congestion controller doesn't estimate bandwidth (e.g. CUBIC) or
+    /// hasn't collected enough samples yet.
+    pub bandwidth_estimate: Option<u64>,
+    /// The congestion window of the active path, in bytes
+    pub congestion_window: u32,
+}
+
+/// A snapshot of the statistics of a single network path of a connection
+///
+/// These can be used to decide which path should be preferred, for example after a migration.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PathStatistics {
+    /// The identifier of the path, which is unique within the connection
+    pub id: u64,
+    /// The local address of the path
+    pub local_address: SocketAddress,
+    /// The remote address of the path
+    pub remote_address: SocketAddress,
+    /// True if the path is currently used to send application data
+    pub is_active: bool,
+    /// The validation state of the path
+    pub validation: PathValidation,
+    /// The smoothed round-trip time of the path
+    pub smoothed_rtt: Duration,
+    /// The minimum round-trip time observed on the path
+    pub min_rtt: Duration,
+    /// The most recent round-trip time sample of the path
+    pub latest_rtt: Duration,
+    /// The congestion window of the path, in bytes
+    pub congestion_window: u32,
+    /// The number of bytes in flight on the path
+    pub bytes_in_flight: u32,
+    /// The number of UDP payload bytes sent on the path
+    pub bytes_sent: u64,
+    /// The number of UDP payload bytes received on the path
+    pub bytes_received: u64,
+    /// The number of QUIC packets sent on the path
+    pub packets_sent: u64,
+    /// The number of QUIC packets sent on the path which were declared lost
+    pub packets_lost: u64,
+    /// The number of bytes sent on the path which were declared lost
+    pub bytes_lost: u64,
+}
+
+impl PathStatistics {
+    /// Returns the fraction of packets sent on the path which were declared lost
+    ///
+    /// Returns `0.0` if no packets have been sent on the path.
+    #[inline]
+    pub fn loss_rate(&self) -> f64 {
+        if self.packets_sent == 0 {
+            return 0.0;
+        }
+
+        self.packets_lost as f64 / self.packets_sent as f64
+    }
+}
+
+/// The validation state of a network path
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathValidation {
+    /// The path has been validated
+    Validated,
+    /// A PATH_CHALLENGE has been sent on the path and is awaiting a response
+    Pending,
+    /// The path failed validation
+    Failed,
+    /// The path hasn't been validated and no PATH_CHALLENGE is in progress
+    #[default]
+    NotValidated,
+}
+
+impl IntoEvent<builder::PathValidation> for PathValidation {
+    #[inline]
+    fn into_event(self) -> builder::PathValidation {
+        match self {
+            Self::Validated => builder::PathValidation::Validated {},
+            Self::Pending => builder::PathValidation::Pending {},
+            Self::Failed => builder::PathValidation::Failed {},
+            Self::NotValidated => builder::PathValidation::NotValidated {},
+        }
+    }
+}
+
+/// The packet and byte counters of a single network path
+///
+/// These are maintained by the transport for each path, rather than by an event subscriber, since
+/// packets are attributed to paths as they are sent and declared lost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PathCounters {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_lost: u64,
+    pub bytes_lost: u64,
+}
+
+impl PathCounters {
+    /// Writes the counters into the path statistics snapshot
+    #[inline]
+    pub fn write(&self, statistics: &mut PathStatistics) {
+        statistics.bytes_sent = self.bytes_sent;
+        statistics.bytes_received = self.bytes_received;
+        statistics.packets_sent = self.packets_sent;
+        statistics.packets_lost = self.packets_lost;
+        statistics.bytes_lost = self.bytes_lost;
+    }
+}
+
+/// The packet and datagram counters of a connection
+///
+/// These are maintained by the statistics [`Subscriber`] and stored in the connection's event
+/// context.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counters {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    pub packets_lost: u64,
+    pub bytes_lost: u64,
+    pub datagrams_dropped: u64,
+}
+
+impl Counters {
+    /// Writes the counters into the statistics snapshot
+    #[inline]
+    pub fn write(&self, statistics: &mut Statistics) {
+        statistics.bytes_sent = self.bytes_sent;
+        statistics.bytes_received = self.bytes_received;
+        statistics.packets_sent = self.packets_sent;
+        statistics.packets_received = self.packets_received;
+        statistics.packets_lost = self.packets_lost;
+        statistics.bytes_lost = self.bytes_lost;
+        statistics.datagrams_dropped = self.datagrams_dropped;
+    }
+}
+
+/// An event subscriber which maintains the [`Counters`] for each connection
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Subscriber;
+
+impl event::Subscriber for Subscriber {
+    type ConnectionContext = Counters;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        _meta: &api::ConnectionMeta,
+        _info: &api::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        Counters::default()
+    }
+
+    #[inline]
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        _event: &api::PacketSent,
+    ) {
+        context.packets_sent += 1;
+    }
+
+    #[inline]
+    fn on_packet_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        _event: &api::PacketReceived,
+    ) {
+        context.packets_received += 1;
+    }
+
+    #[inline]
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::PacketLost,
+    ) {
+        context.packets_lost += 1;
+        context.bytes_lost += event.bytes_lost as u64;
+    }
+
+    #[inline]
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::DatagramSent,
+    ) {
+        context.bytes_sent += event.len as u64;
+    }
+
+    #[inline]
+    fn on_datagram_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::DatagramReceived,
+    ) {
+        context.bytes_received += event.len as u64;
+    }
+
+    #[inline]
+    fn on_datagram_dropped(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        _event: &api::DatagramDropped,
+    ) {
+        context.datagrams_dropped += 1;
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     connection::ProcessingError,
-    crypto::{application::limited, OneRttKey, ProtectedPayload},
+    crypto::{application::limited, OneRttKey},
     packet::{
         encoding::PacketEncodingError,
         number::PacketNumber,
@@ -121,7 +121,11 @@ impl<K: OneRttKey> KeySet<K> {
         let phase_switch = phase_to_use != (packet_phase as u8);
         phase_to_use ^= phase_switch as u8;
 
-        if self.key_update_in_progress() && phase_switch {
+        // While a key update is in progress, the other phase still holds the previous key rather
+        // than the next one, so packets protected with it can't initiate another key update.
+        let is_previous_key = self.key_update_in_progress() && phase_switch;
+
+        if is_previous_key {
             //= https://www.rfc-editor.org/rfc/rfc9001#section-6.5
             //# An endpoint MAY allow a period of approximately the Probe Timeout
             //# (PTO; see [QUIC-RECOVERY]) after promoting the next set of receive
@@ -148,7 +152,7 @@ impl<K: OneRttKey> KeySet<K> {
 
         match result {
             Ok(packet) => {
-                let generation = if packet_phase != self.key_phase() {
+                let generation = if phase_switch && !is_previous_key {
                     //= https://www.rfc-editor.org/rfc/rfc9001#section-6.2
                     //# Sending keys MUST be updated before sending an
                     //# acknowledgement for the packet that was received with updated keys.
@@ -221,18 +225,17 @@ impl<K: OneRttKey> KeySet<K> {
         self.key_phase()
     }
 
-    pub fn encrypt_packet<'a, F>(
+    pub fn encrypt_packet<'a, P, F>(
         &mut self,
         buffer: EncoderBuffer<'a>,
         f: F,
-    ) -> Result<(ProtectedPayload<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>>
+    ) -> Result<(P, EncoderBuffer<'a>), PacketEncodingError<'a>>
     where
         F: FnOnce(
             EncoderBuffer<'a>,
             &mut K,
             KeyPhase,
-        )
-            -> Result<(ProtectedPayload<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>>,
+        ) -> Result<(P, EncoderBuffer<'a>), PacketEncodingError<'a>>,
     {
         let phase = self.encryption_phase();
         if self.crypto[phase].expired() {
@@ -284,6 +287,14 @@ impl<K: OneRttKey> KeySet<K> {
         &mut self.crypto[self.key_phase]
     }
 
+    /// Returns the key for the given `phase`
+    ///
+    /// Packets which are encoded in [`Self::encrypt_packet`] but sealed later need the key of
+    /// the phase they were encoded with, which isn't always the active phase.
+    pub fn key_mut(&mut self, phase: KeyPhase) -> &mut limited::Key<K> {
+        &mut self.crypto[phase]
+    }
+
     fn decryption_error_count(&self) -> u64 {
         self.packet_decryption_failures
     }
@@ -319,6 +330,9 @@ impl<K> ops::IndexMut<KeyPhase> for KeyArray<K> {
     }
 }
 
+#[cfg(test)]
+mod fuzz_target;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,444 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A model of a peer performing key updates while its 1-RTT packets are delayed, reordered and
+//! tampered with on the way to a [`KeySet`]
+//!
+//! The [`Oracle`] tracks which key generation is installed for each key phase, which is used to
+//! check that the [`KeySet`] decrypts exactly the packets it should be able to, and that its key
+//! phase only moves forward.
+
+use super::KeySet;
+use crate::{
+    connection::id::ConnectionInfo,
+    crypto::{self, packet_protection, scatter, HeaderProtectionMask, OneRttHeaderKey, OneRttKey},
+    inet::SocketAddress,
+    packet::{
+        encoding::PacketEncoder,
+        number::{PacketNumber, PacketNumberSpace},
+        short::{Short, SpinBit},
+        KeyPhase, ProtectedPacket,
+    },
+    time::{testing::Clock, Clock as _, Timestamp},
+    varint::VarInt,
+};
+use bolero::{check, generator::*};
+use core::{mem::size_of, time::Duration};
+use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+
+const DESTINATION_CONNECTION_ID: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+const PAYLOAD: [u8; 32] = [0xaa; 32];
+const PTO: Duration = Duration::from_millis(100);
+const TAG_LEN: usize = size_of::<u64>();
+
+#[derive(Clone, Copy, Debug, TypeGenerator)]
+enum Tamper {
+    /// The packet is delivered unmodified
+    None,
+    /// The protected key phase bit is flipped
+    KeyPhase,
+    /// The protected packet number is modified
+    PacketNumber,
+    /// The header protection sample is modified
+    Sample,
+}
+
+#[derive(Debug, TypeGenerator)]
+enum Operation {
+    /// The peer sends a packet with its current keys
+    Send,
+    /// The peer initiates a key update, if it's allowed to
+    UpdateKey,
+    /// An in-flight packet is delivered to the key set
+    Deliver { index: u8, tamper: Tamper },
+    /// Time advances by the specified number of milliseconds
+    Advance { millis: u8 },
+}
+
+struct Packet {
+    packet_number: PacketNumber,
+    generation: u16,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Oracle {
+    /// The number of key updates performed by the receiver
+    generation: u16,
+    /// The key generation installed for each key phase
+    slots: [u16; 2],
+    /// The time at which the next key will be derived, if a key update is in progress
+    derivation: Option<Timestamp>,
+    largest_packet_number: PacketNumber,
+}
+
+impl Oracle {
+    fn new() -> Self {
+        Self {
+            generation: 0,
+            slots: [0, 1],
+            derivation: None,
+            largest_packet_number: PacketNumberSpace::ApplicationData
+                .new_packet_number(VarInt::from_u8(0)),
+        }
+    }
+
+    fn key_phase(&self) -> KeyPhase {
+        phase(self.generation)
+    }
+
+    /// Returns `true` if the packet should be successfully decrypted
+    fn can_decrypt(&self, packet: &Packet) -> bool {
+        self.slots[phase(packet.generation) as usize] == packet.generation
+    }
+
+    /// Returns the new generation if the packet causes a key update
+    fn on_decrypt(&mut self, packet: &Packet, now: Timestamp) -> Option<u16> {
+        self.largest_packet_number = self.largest_packet_number.max(packet.packet_number);
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.5
+        //# An endpoint MAY allow a period of approximately the Probe Timeout
+        //# (PTO; see [QUIC-RECOVERY]) after promoting the next set of receive
+        //# keys to be current before it creates the subsequent set of packet
+        //# protection keys.
+        // Packets from the previous phase can still be decrypted during this period but they
+        // shouldn't cause another key update.
+        if phase(packet.generation) == self.key_phase() || self.derivation.is_some() {
+            return None;
+        }
+
+        self.generation += 1;
+        self.derivation = Some(now + PTO);
+        Some(self.generation)
+    }
+
+    fn on_timeout(&mut self, now: Timestamp) {
+        if self
+            .derivation
+            .map_or(false, |derivation| derivation <= now)
+        {
+            self.derivation = None;
+            let next = self.generation + 1;
+            self.slots[phase(next) as usize] = next;
+        }
+    }
+}
+
+struct Peer {
+    generation: u16,
+    next_packet_number: u64,
+    in_flight: Vec<Packet>,
+}
+
+impl Peer {
+    fn new() -> Self {
+        Self {
+            generation: 0,
+            next_packet_number: 0,
+            in_flight: vec![],
+        }
+    }
+
+    fn send(&mut self) {
+        let packet_number = PacketNumberSpace::ApplicationData
+            .new_packet_number(VarInt::new(self.next_packet_number).unwrap());
+        self.next_packet_number += 1;
+
+        let packet = Short {
+            spin_bit: SpinBit::Zero,
+            key_phase: phase(self.generation),
+            destination_connection_id: &DESTINATION_CONNECTION_ID[..],
+            packet_number,
+            payload: &PAYLOAD[..],
+        };
+
+        let mut bytes = vec![0; 1200];
+        let (protected, _) = packet
+            .encode_packet(
+                &mut Key::new(self.generation),
+                &HeaderKey,
+                // truncate the packet number against the first packet so any reordering can be
+                // recovered by the receiver
+                PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
+                None,
+                EncoderBuffer::new(&mut bytes),
+            )
+            .unwrap();
+        let len = protected.len();
+        bytes.truncate(len);
+
+        self.in_flight.push(Packet {
+            packet_number,
+            generation: self.generation,
+            bytes,
+        });
+    }
+}
+
+struct Model {
+    subject: KeySet<Key>,
+    oracle: Oracle,
+    peer: Peer,
+    clock: Clock,
+}
+
+impl Model {
+    fn new() -> Self {
+        Self {
+            subject: KeySet::new(Key::new(0), Default::default()),
+            oracle: Oracle::new(),
+            peer: Peer::new(),
+            clock: Clock::default(),
+        }
+    }
+
+    fn apply(&mut self, operation: &Operation) {
+        match operation {
+            Operation::Send => self.peer.send(),
+            Operation::UpdateKey => {
+                //= https://www.rfc-editor.org/rfc/rfc9001#section-6.1
+                //# An endpoint MUST NOT initiate a subsequent key update unless it has
+                //# received an acknowledgment for a packet that was sent protected with
+                //# keys from the current key phase.
+                if self.peer.generation == self.oracle.generation {
+                    self.peer.generation += 1;
+                }
+            }
+            Operation::Deliver { index, tamper } => self.deliver(*index, *tamper),
+            Operation::Advance { millis } => {
+                self.clock.inc_by(Duration::from_millis(*millis as u64));
+                let now = self.clock.get_time();
+                self.subject.on_timeout(now);
+                self.oracle.on_timeout(now);
+            }
+        }
+    }
+
+    fn deliver(&mut self, index: u8, tamper: Tamper) {
+        if self.peer.in_flight.is_empty() {
+            return;
+        }
+
+        let index = index as usize % self.peer.in_flight.len();
+        let packet = self.peer.in_flight.remove(index);
+        let mut bytes = packet.bytes.clone();
+
+        // the first byte of the packet number follows the tag and connection ID
+        let packet_number_offset = 1 + DESTINATION_CONNECTION_ID.len();
+        match tamper {
+            Tamper::None => {}
+            Tamper::KeyPhase => bytes[0] ^= KeyPhase::One.into_packet_tag_mask(),
+            Tamper::PacketNumber => bytes[packet_number_offset] ^= 1,
+            Tamper::Sample => bytes[packet_number_offset + 4] ^= 1,
+        }
+
+        let now = self.clock.get_time();
+        let largest_packet_number = self.oracle.largest_packet_number;
+        let key_phase = self.subject.key_phase();
+
+        let remote_address = SocketAddress::default();
+        let connection_info = ConnectionInfo::new(&remote_address);
+        let decoder = DecoderBufferMut::new(&mut bytes);
+        let (protected, _) =
+            ProtectedPacket::decode(decoder, &connection_info, &DESTINATION_CONNECTION_ID.len())
+                .unwrap();
+        let ProtectedPacket::Short(protected) = protected else {
+            panic!("expected a short packet");
+        };
+
+        let result = protected
+            .unprotect(&HeaderKey, largest_packet_number)
+            .map_err(|_| ())
+            .and_then(|encrypted| {
+                if matches!(tamper, Tamper::None) {
+                    // header protection should be removed exactly as the peer applied it
+                    assert_eq!(encrypted.packet_number, packet.packet_number);
+                    assert_eq!(encrypted.key_phase(), phase(packet.generation));
+                }
+
+                self.subject
+                    .decrypt_packet(encrypted, largest_packet_number, now + PTO)
+                    .map_err(|_| ())
+            });
+
+        match result {
+            Ok((cleartext, generation)) => {
+                assert!(
+                    matches!(tamper, Tamper::None),
+                    "tampered packets should not be authenticated"
+                );
+                assert!(self.oracle.can_decrypt(&packet));
+                assert_eq!(cleartext.packet_number, packet.packet_number);
+                assert_eq!(generation, self.oracle.on_decrypt(&packet, now));
+            }
+            Err(()) => {
+                if matches!(tamper, Tamper::None) {
+                    assert!(!self.oracle.can_decrypt(&packet));
+                }
+
+                //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
+                //# Once generated, the next set of packet protection keys SHOULD be
+                //# retained, even if the packet that was received was subsequently
+                //# discarded.
+                // Packets which fail authentication can't change the key phase
+                assert_eq!(self.subject.key_phase(), key_phase);
+            }
+        }
+    }
+
+    fn invariants(&self) {
+        assert_eq!(self.subject.key_phase(), self.oracle.key_phase());
+        assert_eq!(
+            self.subject.active_key().key().generation,
+            self.oracle.generation
+        );
+        assert_eq!(
+            self.subject.key_update_in_progress(),
+            self.oracle.derivation.is_some()
+        );
+        for key_phase in [KeyPhase::Zero, KeyPhase::One] {
+            assert_eq!(
+                self.subject.crypto[key_phase].key().generation,
+                self.oracle.slots[key_phase as usize]
+            );
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-6.2
+        //# The endpoint MUST update its
+        //# send keys to the corresponding key phase in response, as described in
+        //# Section 6.1.
+        assert_eq!(
+            self.subject.encryption_phase(),
+            self.oracle.key_phase(),
+            "the send keys should follow the receive keys"
+        );
+        assert!(self.oracle.generation <= self.peer.generation);
+    }
+}
+
+fn phase(generation: u16) -> KeyPhase {
+    KeyPhase::from((generation % 2) as u8)
+}
+
+/// A key which authenticates the packet number, header and payload along with its generation
+#[derive(Debug)]
+struct Key {
+    generation: u16,
+}
+
+impl Key {
+    fn new(generation: u16) -> Self {
+        Self { generation }
+    }
+
+    fn tag(&self, packet_number: u64, header: &[u8], payload: &[u8]) -> [u8; TAG_LEN] {
+        // FNV-1a is used to make sure any modification to the packet is detected
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        for chunk in [
+            &self.generation.to_be_bytes()[..],
+            &packet_number.to_be_bytes()[..],
+            header,
+            payload,
+        ] {
+            for byte in chunk {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        hash.to_be_bytes()
+    }
+}
+
+impl crypto::Key for Key {
+    fn decrypt(
+        &self,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), packet_protection::Error> {
+        let payload_len = payload
+            .len()
+            .checked_sub(TAG_LEN)
+            .ok_or(packet_protection::Error::DECRYPT_ERROR)?;
+        let (payload, tag) = payload.split_at(payload_len);
+
+        if self.tag(packet_number, header, payload) != tag {
+            return Err(packet_protection::Error::DECRYPT_ERROR);
+        }
+
+        Ok(())
+    }
+
+    fn encrypt(
+        &mut self,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), packet_protection::Error> {
+        let (payload, remaining) = payload.flatten().split_mut();
+        let tag = self.tag(packet_number, header, payload);
+        remaining[..TAG_LEN].copy_from_slice(&tag);
+        Ok(())
+    }
+
+    fn tag_len(&self) -> usize {
+        TAG_LEN
+    }
+
+    fn aead_confidentiality_limit(&self) -> u64 {
+        u64::MAX
+    }
+
+    fn aead_integrity_limit(&self) -> u64 {
+        u64::MAX
+    }
+
+    fn cipher_suite(&self) -> crypto::tls::CipherSuite {
+        crypto::tls::CipherSuite::Unknown
+    }
+}
+
+impl OneRttKey for Key {
+    fn derive_next_key(&self) -> Self {
+        Self::new(self.generation + 1)
+    }
+}
+
+/// A header key which uses the sample as the protection mask
+#[derive(Debug)]
+struct HeaderKey;
+
+impl crypto::HeaderKey for HeaderKey {
+    fn opening_header_protection_mask(&self, sample: &[u8]) -> HeaderProtectionMask {
+        sample.try_into().unwrap()
+    }
+
+    fn opening_sample_len(&self) -> usize {
+        size_of::<HeaderProtectionMask>()
+    }
+
+    fn sealing_header_protection_mask(&self, sample: &[u8]) -> HeaderProtectionMask {
+        self.opening_header_protection_mask(sample)
+    }
+
+    fn sealing_sample_len(&self) -> usize {
+        self.opening_sample_len()
+    }
+}
+
+impl OneRttHeaderKey for HeaderKey {}
+
+#[test]
+#[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+fn key_phase_fuzz() {
+    check!()
+        .with_type::<Vec<Operation>>()
+        .for_each(|operations| {
+            let mut model = Model::new();
+
+            for operation in operations.iter() {
+                model.apply(operation);
+                model.invariants();
+            }
+        });
+}
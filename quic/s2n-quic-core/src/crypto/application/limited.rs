@@ -79,6 +79,11 @@ impl<K: OneRttKey> Key<K> {
         self.decrypted_packets += 1;
     }
 
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
     #[inline]
     pub fn key_mut(&mut self) -> &mut K {
         &mut self.key
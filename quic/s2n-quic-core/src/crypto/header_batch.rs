@@ -0,0 +1,111 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    crypto::{
+        header_crypto::apply_header_protection, payload::header_protection_sample,
+        EncryptedPayload, HeaderKey, HeaderProtectionMask,
+    },
+    packet::number::PacketNumberLen,
+};
+use alloc::vec::Vec;
+use s2n_codec::{DecoderBuffer, DecoderError};
+
+/// Applies header protection to multiple encrypted packets in a buffer at once
+///
+/// Packets are recorded with [`HeaderProtectionBatch::push`] after being encrypted. Once all
+/// of the packets have been written to the buffer, [`HeaderProtectionBatch::protect`] derives
+/// the masks for all of them with a single call to
+/// [`HeaderKey::sealing_header_protection_masks`].
+#[derive(Debug, Default)]
+pub struct HeaderProtectionBatch {
+    packets: Vec<Packet>,
+    samples: Vec<u8>,
+    masks: Vec<HeaderProtectionMask>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Packet {
+    offset: usize,
+    header_len: usize,
+    packet_number_len: PacketNumberLen,
+}
+
+impl HeaderProtectionBatch {
+    /// Records an encrypted packet which starts at `offset` in the buffer
+    #[inline]
+    pub fn push(&mut self, offset: usize, payload: &EncryptedPayload) {
+        self.packets.push(Packet {
+            offset,
+            header_len: payload.header_len,
+            packet_number_len: payload.packet_number_len,
+        });
+    }
+
+    /// Returns the number of packets waiting on header protection
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    /// Returns `true` if no packets are waiting on header protection
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// Applies header protection to all of the recorded packets in `buffer`
+    ///
+    /// The batch is empty after this call, even if an error is returned.
+    #[inline]
+    pub fn protect<K: HeaderKey>(
+        &mut self,
+        key: &K,
+        buffer: &mut [u8],
+    ) -> Result<(), DecoderError> {
+        if self.packets.is_empty() {
+            return Ok(());
+        }
+
+        let result = self.protect_packets(key, buffer);
+        self.packets.clear();
+        result
+    }
+
+    #[inline]
+    fn protect_packets<K: HeaderKey>(
+        &mut self,
+        key: &K,
+        buffer: &mut [u8],
+    ) -> Result<(), DecoderError> {
+        let sample_len = key.sealing_sample_len();
+
+        self.samples.clear();
+        for packet in &self.packets {
+            let packet_buffer = buffer
+                .get(packet.offset..)
+                .ok_or(DecoderError::UnexpectedEof(packet.offset))?;
+            let sample = header_protection_sample(
+                DecoderBuffer::new(packet_buffer),
+                packet.header_len,
+                sample_len,
+            )?;
+            self.samples.extend_from_slice(sample);
+        }
+
+        self.masks.clear();
+        self.masks.resize(self.packets.len(), Default::default());
+        key.sealing_header_protection_masks(&mut self.samples, &mut self.masks);
+
+        for (packet, mask) in self.packets.iter().zip(&self.masks) {
+            let payload = EncryptedPayload::new(
+                packet.header_len,
+                packet.packet_number_len,
+                &mut buffer[packet.offset..],
+            );
+            apply_header_protection(*mask, payload);
+        }
+
+        Ok(())
+    }
+}
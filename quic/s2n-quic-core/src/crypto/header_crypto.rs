@@ -25,6 +25,27 @@ pub trait HeaderKey: Send {
     /// The sample size is determined by the key function.
     fn sealing_header_protection_mask(&self, ciphertext_sample: &[u8]) -> HeaderProtectionMask;
 
+    /// Derives a header protection mask for each sample in a buffer, to be
+    /// used for sealing multiple packets.
+    ///
+    /// `ciphertext_samples` contains one sample of `sealing_sample_len` bytes for each
+    /// entry in `masks` and may be overwritten by the implementation. Keys can override
+    /// this to derive all of the masks in a single pass.
+    #[inline]
+    fn sealing_header_protection_masks(
+        &self,
+        ciphertext_samples: &mut [u8],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        let sample_len = self.sealing_sample_len();
+        debug_assert_eq!(ciphertext_samples.len(), sample_len * masks.len());
+
+        for (index, mask) in masks.iter_mut().enumerate() {
+            let sample = &ciphertext_samples[index * sample_len..][..sample_len];
+            *mask = self.sealing_header_protection_mask(sample);
+        }
+    }
+
     /// Returns the sample size needed for the header protection
     /// buffer
     fn sealing_sample_len(&self) -> usize;
@@ -133,6 +133,8 @@
 
 pub mod application;
 pub mod handshake;
+#[cfg(feature = "alloc")]
+pub mod header_batch;
 pub mod header_crypto;
 pub mod initial;
 pub mod key;
@@ -149,6 +151,8 @@ mod tests;
 
 pub use application::*;
 pub use handshake::*;
+#[cfg(feature = "alloc")]
+pub use header_batch::*;
 pub use header_crypto::*;
 pub use initial::*;
 pub use key::*;
@@ -246,6 +250,32 @@ pub fn encrypt<'a, K: Key>(
     Ok((encrypted_payload, remaining))
 }
 
+/// Encrypts a packet written by [`crate::packet::encoding::PacketEncoder::encode_unsealed_packet`]
+///
+/// `buffer` starts with the packet. The tag is written into the space reserved at the end of it.
+#[inline]
+pub fn seal<'a, K: Key>(
+    key: &mut K,
+    packet: UnsealedPacket,
+    buffer: &'a mut [u8],
+) -> Result<EncryptedPayload<'a>, packet_protection::Error> {
+    let header_with_pn_len = packet.packet_number_len.bytesize() + packet.header_len;
+    let payload_len = packet.len - header_with_pn_len - key.tag_len();
+
+    let payload = &mut buffer[..packet.len];
+    let (header, body) = payload.split_at_mut(header_with_pn_len);
+    let mut body = EncoderBuffer::new(body);
+    body.advance_position(payload_len);
+    let mut body = scatter::Buffer::new(body);
+    key.encrypt(packet.packet_number.as_crypto_nonce(), header, &mut body)?;
+
+    Ok(EncryptedPayload::new(
+        packet.header_len,
+        packet.packet_number_len,
+        payload,
+    ))
+}
+
 /// Decrypts a `EncryptedPayload` into clear text
 #[inline]
 pub fn decrypt<'a, K: Key>(
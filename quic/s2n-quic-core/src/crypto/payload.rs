@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::packet::number::PacketNumberLen;
+use crate::packet::number::{PacketNumber, PacketNumberLen};
 use s2n_codec::{CheckedRange, DecoderBuffer, DecoderBufferMut, DecoderError};
 
 /// Type which restricts access to protected and encrypted payloads.
@@ -102,7 +102,8 @@ impl<'a> core::fmt::Debug for EncryptedPayload<'a> {
 }
 
 impl<'a> EncryptedPayload<'a> {
-    pub(crate) fn new(
+    /// Creates a new encrypted payload with a header_len and packet_number_len
+    pub fn new(
         header_len: usize,
         packet_number_len: PacketNumberLen,
         buffer: &'a mut [u8],
@@ -148,7 +149,7 @@ impl<'a> EncryptedPayload<'a> {
     }
 }
 
-fn header_protection_sample(
+pub(crate) fn header_protection_sample(
     buffer: DecoderBuffer,
     header_len: usize,
     sample_len: usize,
@@ -167,3 +168,16 @@ fn header_protection_sample(
 
     Ok(sample.into_less_safe_slice())
 }
+
+/// A packet which has been written to a buffer, but not encrypted yet
+///
+/// Space for the crypto tag is reserved at the end of the packet. The packet is encrypted in
+/// place with [`crate::crypto::seal`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsealedPacket {
+    pub packet_number: PacketNumber,
+    pub packet_number_len: PacketNumberLen,
+    pub header_len: usize,
+    /// The length of the packet, including the reserved crypto tag
+    pub len: usize,
+}
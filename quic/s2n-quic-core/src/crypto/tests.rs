@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    crypto::{packet_protection, scatter, HeaderKey, HeaderProtectionMask, Key, ProtectedPayload},
-    packet::number::{PacketNumber, PacketNumberSpace},
+    crypto::{
+        packet_protection, scatter, EncryptedPayload, HeaderKey, HeaderProtectionBatch,
+        HeaderProtectionMask, Key, ProtectedPayload, UnsealedPacket,
+    },
+    packet::number::{PacketNumber, PacketNumberLen, PacketNumberSpace},
     varint::VarInt,
 };
 use bolero::{check, generator::*};
@@ -40,6 +43,86 @@ fn round_trip() {
         });
 }
 
+#[test]
+#[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+fn batch_protect() {
+    check!()
+        .with_type::<Vec<(u8, u8, Vec<u8>)>>()
+        .for_each(|packets| {
+            let mut expected = vec![];
+            let mut actual = vec![];
+            let mut batch = HeaderProtectionBatch::default();
+
+            for (tag, header_len, payload) in packets {
+                let header_len = *header_len as usize % 20 + 1;
+                let packet_number_len =
+                    PacketNumberSpace::ApplicationData.new_packet_number_len(*tag);
+
+                // the packet needs to be long enough to sample from
+                let min_len =
+                    header_len + PacketNumberLen::MAX_LEN + size_of::<HeaderProtectionMask>();
+                if payload.len() < min_len {
+                    continue;
+                }
+
+                let mut packet = payload.clone();
+                let encrypted = EncryptedPayload::new(header_len, packet_number_len, &mut packet);
+                crate::crypto::protect(&FuzzCrypto, encrypted).unwrap();
+                expected.extend_from_slice(&packet);
+
+                let mut packet = payload.clone();
+                let encrypted = EncryptedPayload::new(header_len, packet_number_len, &mut packet);
+                batch.push(actual.len(), &encrypted);
+                actual.extend_from_slice(payload);
+            }
+
+            batch.protect(&FuzzCrypto, &mut actual).unwrap();
+
+            assert!(batch.is_empty());
+            assert_eq!(expected, actual);
+        });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+fn seal_encrypt_equivalence() {
+    check!()
+        .with_type::<(u32, u8, u8, Vec<u8>)>()
+        .for_each(|(packet_number, tag, header_len, payload)| {
+            let header_len = *header_len as usize % 20 + 1;
+            let space = PacketNumberSpace::ApplicationData;
+            let packet_number = space.new_packet_number(VarInt::from_u32(*packet_number));
+            let packet_number_len = space.new_packet_number_len(*tag);
+            if payload.len() <= header_len + packet_number_len.bytesize() {
+                return;
+            }
+
+            let mut expected = payload.clone();
+            let payload_len = expected.len();
+            let mut buffer = EncoderBuffer::new(&mut expected);
+            buffer.set_position(payload_len);
+            crate::crypto::encrypt(
+                &mut FuzzCrypto,
+                packet_number,
+                packet_number_len,
+                header_len,
+                scatter::Buffer::new(buffer),
+            )
+            .unwrap();
+
+            let mut actual = payload.clone();
+            let packet = UnsealedPacket {
+                packet_number,
+                packet_number_len,
+                header_len,
+                len: payload_len,
+            };
+            crate::crypto::seal(&mut FuzzCrypto, packet, &mut actual).unwrap();
+
+            assert_eq!(expected, actual);
+        });
+}
+
 fn fuzz_unprotect(
     input: &mut [u8],
     largest_packet_number: PacketNumber,
@@ -9,6 +9,9 @@ use zerocopy::{AsBytes, FromBytes, FromZeroes, Unaligned};
 mod error;
 pub use error::Error;
 
+mod secret;
+pub use secret::{OneRttSecrets, Secret, MAX_SECRET_LEN};
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
@@ -29,6 +32,12 @@ pub enum TlsExportError {
     Failure,
 }
 
+/// The maximum length of keying material which can be exported from a TLS session
+///
+/// The exporter expands a secret with HKDF, which produces at most 255 blocks of the hash output.
+/// The largest hash used by the TLS 1.3 cipher suites is SHA-384, with 48 byte blocks.
+pub const MAX_EXPORTED_KEYING_MATERIAL_LEN: usize = 255 * 48;
+
 impl TlsExportError {
     pub fn failure() -> Self {
         TlsExportError::Failure
@@ -95,6 +104,34 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_parameters: ApplicationParameters,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called with the secrets the 1-RTT keys are derived from, prior to `on_one_rtt_keys`
+    ///
+    /// The secrets are only needed to hand the connection off to another endpoint, which
+    /// derives the same keys from them.
+    ///
+    /// NOTE: This function is not currently supported
+    ///       for the `s2n-quic-rustls` provider
+    fn on_one_rtt_secrets(
+        &mut self,
+        secrets: &OneRttSecrets,
+    ) -> Result<(), crate::transport::Error> {
+        let _ = secrets;
+        Ok(())
+    }
+
+    /// Called on the client once the server has accepted or rejected the Encrypted Client Hello
+    /// offered in the handshake
+    ///
+    /// NOTE: This function is not currently supported
+    ///       for the `s2n-quic-tls` provider
+    fn on_encrypted_client_hello(
+        &mut self,
+        status: EchStatus,
+    ) -> Result<(), crate::transport::Error> {
+        let _ = status;
+        Ok(())
+    }
+
     fn on_server_name(
         &mut self,
         server_name: crate::application::ServerName,
@@ -164,6 +201,21 @@ pub trait Endpoint: 'static + Sized + Send {
 
     /// The maximum length of a tag for any algorithm that may be negotiated
     fn max_tag_length(&self) -> usize;
+
+    /// Creates the 1-RTT keys of a server connection which was handed off by another endpoint
+    ///
+    /// The default implementation doesn't support importing connections.
+    #[allow(clippy::type_complexity)]
+    fn new_server_one_rtt_keys(
+        &mut self,
+        secrets: &OneRttSecrets,
+    ) -> Option<(
+        <Self::Session as crate::crypto::CryptoSuite>::OneRttKey,
+        <Self::Session as crate::crypto::CryptoSuite>::OneRttHeaderKey,
+    )> {
+        let _ = secrets;
+        None
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -184,6 +236,19 @@ pub trait Session: crate::crypto::CryptoSuite + Sized + Send + Debug {
         true
     }
 
+    /// Exports keying material from the session once the handshake has completed
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc8446#section-7.5>. The default implementation
+    /// doesn't support exporting keying material.
+    fn export_keying_material(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), TlsExportError> {
+        Err(TlsExportError::failure())
+    }
+
     /// Parses a hello message of the provided type
     ///
     /// The default implementation of this function assumes TLS messages are being exchanged.
@@ -272,7 +337,7 @@ impl HelloOffsets {
     }
 }
 
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub enum CipherSuite {
     TLS_AES_128_GCM_SHA256,
@@ -303,6 +368,28 @@ impl crate::event::IntoEvent<crate::event::api::CipherSuite> for CipherSuite {
     }
 }
 
+/// The outcome of an Encrypted Client Hello (ECH) offer
+///
+/// See <https://datatracker.ietf.org/doc/draft-ietf-tls-esni/>.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EchStatus {
+    /// The server decrypted the inner ClientHello
+    Accepted,
+    /// The server didn't decrypt the inner ClientHello
+    Rejected,
+}
+
+impl crate::event::IntoEvent<crate::event::builder::EchStatus> for EchStatus {
+    #[inline]
+    fn into_event(self) -> crate::event::builder::EchStatus {
+        use crate::event::builder::EchStatus::*;
+        match self {
+            Self::Accepted => Accepted {},
+            Self::Rejected => Rejected {},
+        }
+    }
+}
+
 macro_rules! handshake_type {
     ($($variant:ident($value:literal)),* $(,)?) => {
         #[derive(Clone, Copy, Debug, PartialEq, Eq, AsBytes, Unaligned)]
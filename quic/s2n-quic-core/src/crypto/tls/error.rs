@@ -169,4 +169,6 @@ alert_descriptions!(
     UNKNOWN_PSK_IDENTITY = 115,
     CERTIFICATE_REQUIRED = 116,
     NO_APPLICATION_PROTOCOL = 120,
+    // https://datatracker.ietf.org/doc/html/draft-ietf-tls-esni#section-11.2
+    ECH_REQUIRED = 121,
 );
@@ -0,0 +1,74 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::CipherSuite;
+use core::fmt;
+use zeroize::Zeroize;
+
+/// The length of the largest traffic secret, which is produced by the SHA-384 cipher suites
+pub const MAX_SECRET_LEN: usize = 48;
+
+/// A TLS traffic secret
+///
+/// The bytes are zeroed when the secret is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret {
+    bytes: [u8; MAX_SECRET_LEN],
+    len: u8,
+}
+
+impl Secret {
+    /// Copies the secret, returning `None` if it's longer than [`MAX_SECRET_LEN`]
+    #[inline]
+    pub fn new(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > MAX_SECRET_LEN {
+            return None;
+        }
+
+        let mut secret = Self {
+            bytes: [0; MAX_SECRET_LEN],
+            len: bytes.len() as u8,
+        };
+        secret.bytes[..bytes.len()].copy_from_slice(bytes);
+        Some(secret)
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Secret").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+/// The traffic secrets the TLS handshake produced for the 1-RTT packet space
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OneRttSecrets {
+    pub cipher_suite: CipherSuite,
+    pub client: Secret,
+    pub server: Secret,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_len_test() {
+        let secret = Secret::new(&[1; 32]).unwrap();
+        assert_eq!(secret.as_bytes(), &[1; 32]);
+
+        assert!(Secret::new(&[1; MAX_SECRET_LEN]).is_some());
+        assert!(Secret::new(&[1; MAX_SECRET_LEN + 1]).is_none());
+    }
+}
@@ -336,6 +336,28 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
             self.server.context.state,
         );
 
+        // both peers should export the same keying material, if the sessions support it
+        let label = b"EXPERIMENTAL EXPORTER s2n-quic";
+        let mut client_key = [0; 32];
+        let mut server_key = [0; 32];
+        let client_result =
+            self.client
+                .session
+                .export_keying_material(label, b"context", &mut client_key);
+        let server_result =
+            self.server
+                .session
+                .export_keying_material(label, b"context", &mut server_key);
+        assert_eq!(
+            client_result.is_ok(),
+            server_result.is_ok(),
+            "only one of the sessions exported keying material"
+        );
+        if client_result.is_ok() {
+            assert_eq!(client_key, server_key);
+            assert_ne!(client_key, [0; 32]);
+        }
+
         // TODO check 0-rtt keys
     }
 }
@@ -399,6 +421,7 @@ where
     pub server_name: Option<Bytes>,
     pub application_protocol: Option<Bytes>,
     pub transport_parameters: Option<Bytes>,
+    pub encrypted_client_hello: Option<tls::EchStatus>,
     endpoint: endpoint::Type,
     pub state: State,
     waker: Waker,
@@ -439,6 +462,7 @@ where
             server_name: None,
             application_protocol: None,
             transport_parameters: None,
+            encrypted_client_hello: None,
             endpoint,
             state,
             waker,
@@ -721,6 +745,22 @@ where
         Ok(())
     }
 
+    fn on_encrypted_client_hello(
+        &mut self,
+        status: tls::EchStatus,
+    ) -> Result<(), transport::Error> {
+        assert!(
+            self.endpoint.is_client(),
+            "only clients can offer encrypted client hello"
+        );
+        assert!(
+            self.encrypted_client_hello.is_none(),
+            "encrypted client hello status should only be reported once"
+        );
+        self.encrypted_client_hello = Some(status);
+        Ok(())
+    }
+
     fn on_application_protocol(
         &mut self,
         application_protocol: Bytes,
@@ -0,0 +1,45 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    custom_frame::{ConnectionInfo, Endpoint, Handler, Packet, ReceiveContext},
+    transport,
+    varint::VarInt,
+};
+
+#[derive(Debug, Default)]
+pub struct Disabled(());
+
+impl Endpoint for Disabled {
+    type Handler = DisabledHandler;
+
+    fn create_connection(&mut self, _info: &ConnectionInfo) -> Self::Handler {
+        DisabledHandler(())
+    }
+}
+
+pub struct DisabledHandler(());
+
+impl Handler for DisabledHandler {
+    #[inline]
+    fn on_frame(
+        &mut self,
+        _context: &ReceiveContext<'_>,
+        _frame_type: VarInt,
+        _payload: &[u8],
+    ) -> Result<(), transport::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-12.4
+        //# An endpoint MUST treat the receipt of a frame of unknown type as a
+        //# connection error of type FRAME_ENCODING_ERROR.
+        Err(transport::Error::FRAME_ENCODING_ERROR.with_reason("unknown frame type"))
+    }
+
+    fn on_transmit<P: Packet>(&mut self, _packet: &mut P) {}
+
+    #[inline]
+    fn has_transmission_interest(&self) -> bool {
+        false
+    }
+
+    fn on_connection_error(&mut self, _error: crate::connection::Error) {}
+}
@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::task::Waker;
+
+use crate::{
+    connection,
+    event::{api::SocketAddress, IntoEvent},
+    inet, transport,
+    varint::VarInt,
+};
+
+/// The custom frame endpoint trait provides a way to send and receive extension frames
+/// with types that are not known to the transport.
+///
+/// The frame types used by the application are expected to be negotiated with the peer,
+/// for example with a custom transport parameter. Receiving a frame type that isn't
+/// accepted by the [`Handler`] results in a connection error.
+pub trait Endpoint: 'static + Send {
+    type Handler: Handler;
+
+    fn create_connection(&mut self, info: &ConnectionInfo) -> Self::Handler;
+}
+
+/// Information about the connection for which the Handler is being created.
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ConnectionInfo<'a> {
+    /// The `waker` associated with this connection. When woken, the connection will check the
+    /// interest in sending ([`Handler::has_transmission_interest`]), and send packets if necessary.
+    pub waker: Waker,
+
+    /// The address of the peer when the connection was created
+    pub remote_address: SocketAddress<'a>,
+}
+
+impl<'a> ConnectionInfo<'a> {
+    #[doc(hidden)]
+    pub fn new(waker: Waker, remote_address: &'a inet::SocketAddress) -> Self {
+        ConnectionInfo {
+            waker,
+            remote_address: remote_address.into_event(),
+        }
+    }
+}
+
+/// ReceiveContext contains information about the packet the frame was received in.
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ReceiveContext<'a> {
+    /// This is the current connection path this frame was received on.
+    pub path: crate::event::api::Path<'a>,
+
+    /// The number of the packet that carried the frame. The packet will be acknowledged
+    /// once all of its frames have been processed.
+    pub packet_number: u64,
+}
+
+impl<'a> ReceiveContext<'a> {
+    #[doc(hidden)]
+    pub fn new(path: crate::event::api::Path<'a>, packet_number: u64) -> Self {
+        ReceiveContext {
+            path,
+            packet_number,
+        }
+    }
+}
+
+/// Allows users to configure the behavior of sending and receiving custom frames.
+pub trait Handler: 'static + Send {
+    /// A callback that gives users direct access to custom frames as they are read off a packet
+    ///
+    /// Returning an error closes the connection with that error. Frame types that the
+    /// application has not negotiated should be rejected with a `FRAME_ENCODING_ERROR`.
+    fn on_frame(
+        &mut self,
+        context: &ReceiveContext<'_>,
+        frame_type: VarInt,
+        payload: &[u8],
+    ) -> Result<(), transport::Error>;
+
+    /// A callback that allows users to write custom frames directly to the packet
+    ///
+    /// Custom frames are not retransmitted by the transport if the packet is lost.
+    fn on_transmit<P: Packet>(&mut self, packet: &mut P);
+
+    /// A callback that checks if a user has custom frames ready to send
+    ///
+    /// Use method to trigger the on_transmit callback
+    fn has_transmission_interest(&self) -> bool;
+
+    /// A callback used to notify the application in the case of a connection error
+    fn on_connection_error(&mut self, error: connection::Error);
+}
+
+/// A packet will be available during the on_transmit callback. Use the methods
+/// defined here to interrogate the packet struct and write custom frames to the packet.
+pub trait Packet {
+    /// Returns the remaining space in the packet left to write frames
+    fn remaining_capacity(&self) -> usize;
+
+    /// Returns the RTT and congestion window of the path the packet is sent on
+    fn path_info(&self) -> connection::PathInfo;
+
+    /// Writes a single custom frame to a packet.
+    ///
+    /// The payload is prefixed with its length so the peer can skip the frame.
+    fn write_frame(&mut self, frame_type: VarInt, payload: &[u8]) -> Result<(), WriteError>;
+}
+
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum WriteError {
+    ExceedsPacketCapacity,
+    /// The frame type is handled by the transport and can't be used for custom frames
+    InvalidFrameType,
+}
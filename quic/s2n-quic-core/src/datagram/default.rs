@@ -5,7 +5,7 @@
 
 use crate::{
     connection,
-    datagram::{ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
+    datagram::{fec, ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
     transport::parameters::MaxDatagramFrameSize,
 };
 use alloc::collections::VecDeque;
@@ -27,6 +27,7 @@ use core::{
 pub struct Endpoint {
     send_queue_capacity: usize,
     recv_queue_capacity: usize,
+    fec: Option<fec::Scheme>,
 }
 
 impl Endpoint {
@@ -41,12 +42,14 @@ impl Endpoint {
 pub struct EndpointBuilder {
     send_queue_capacity: usize,
     recv_queue_capacity: usize,
+    fec: Option<fec::Scheme>,
 }
 
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum BuilderError {
     ZeroCapacity,
+    InvalidFecScheme,
 }
 
 #[cfg(feature = "std")]
@@ -58,6 +61,9 @@ impl fmt::Display for BuilderError {
             Self::ZeroCapacity { .. } => {
                 write!(f, "Cannot create a queue with zero capacity")
             }
+            Self::InvalidFecScheme { .. } => {
+                write!(f, "Invalid forward error correction scheme")
+            }
         }
     }
 }
@@ -80,10 +86,24 @@ impl EndpointBuilder {
         Ok(self)
     }
 
+    /// Protects datagrams with [forward error correction](fec) on connections where the peer
+    /// also supports it
+    ///
+    /// The largest datagram that can be sent is reduced by [`fec::MAX_OVERHEAD`] bytes. Parity
+    /// datagrams are sent as groups fill up or when [`Sender::finish_fec_group`] is called.
+    pub fn with_fec(mut self, scheme: fec::Scheme) -> Result<Self, BuilderError> {
+        if !scheme.is_valid() {
+            return Err(BuilderError::InvalidFecScheme);
+        }
+        self.fec = Some(scheme);
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Endpoint, core::convert::Infallible> {
         Ok(Endpoint {
             send_queue_capacity: self.send_queue_capacity,
             recv_queue_capacity: self.recv_queue_capacity,
+            fec: self.fec,
         })
     }
 }
@@ -93,15 +113,18 @@ impl super::Endpoint for Endpoint {
     type Receiver = Receiver;
 
     fn create_connection(&mut self, info: &ConnectionInfo) -> (Self::Sender, Self::Receiver) {
+        let fec = self.fec.filter(|_| info.peer_supports_fec);
         (
             Sender::builder()
                 .with_capacity(self.send_queue_capacity)
                 .with_connection_info(info)
+                .with_fec(fec)
                 .build()
                 .unwrap(),
             Receiver::builder()
                 .with_capacity(self.recv_queue_capacity)
                 .with_max_datagram_frame_size(MaxDatagramFrameSize::RECOMMENDED)
+                .with_fec(fec.is_some())
                 .build()
                 .unwrap(),
         )
@@ -110,6 +133,10 @@ impl super::Endpoint for Endpoint {
     fn max_datagram_frame_size(&self, _info: &PreConnectionInfo) -> u64 {
         MaxDatagramFrameSize::RECOMMENDED
     }
+
+    fn supports_fec(&self, _info: &PreConnectionInfo) -> bool {
+        self.fec.is_some()
+    }
 }
 
 /// Handles receiving unreliable datagrams.
@@ -123,6 +150,7 @@ pub struct Receiver {
     waker: Option<Waker>,
     max_datagram_frame_size: u64,
     error: Option<connection::Error>,
+    fec: Option<fec::Decoder>,
 }
 
 impl Receiver {
@@ -164,18 +192,35 @@ impl super::Receiver for Receiver {
         if datagram.len() as u64 > self.max_datagram_frame_size {
             return;
         }
-        // The oldest datagram on the queue is popped off if the queue is full.
-        // Configure this behavior by implementing a custom Receiver for datagrams.
-        if self.queue.len() == self.capacity {
-            self.queue.pop_front();
-        }
 
-        self.queue
-            .push_back(bytes::Bytes::copy_from_slice(datagram));
-        // Since a datagram was appended to the queue, wake the waker to inform
-        // the user that it can receive datagrams now.
-        if let Some(w) = self.waker.take() {
-            w.wake();
+        let Self {
+            queue,
+            capacity,
+            waker,
+            fec,
+            ..
+        } = self;
+
+        let mut on_datagram = |datagram: &[u8]| {
+            // The oldest datagram on the queue is popped off if the queue is full.
+            // Configure this behavior by implementing a custom Receiver for datagrams.
+            if queue.len() == *capacity {
+                queue.pop_front();
+            }
+
+            queue.push_back(bytes::Bytes::copy_from_slice(datagram));
+            // Since a datagram was appended to the queue, wake the waker to inform
+            // the user that it can receive datagrams now.
+            if let Some(w) = waker.take() {
+                w.wake();
+            }
+        };
+
+        if let Some(decoder) = fec {
+            // Malformed datagrams are dropped like any other unreliable datagram
+            let _ = decoder.decode(datagram, on_datagram);
+        } else {
+            on_datagram(datagram);
         }
     }
 
@@ -195,6 +240,7 @@ impl super::Receiver for Receiver {
 struct ReceiverBuilder {
     queue_capacity: usize,
     max_datagram_frame_size: u64,
+    fec: bool,
 }
 
 impl Default for ReceiverBuilder {
@@ -202,6 +248,7 @@ impl Default for ReceiverBuilder {
         Self {
             queue_capacity: 200,
             max_datagram_frame_size: MaxDatagramFrameSize::RECOMMENDED,
+            fec: false,
         }
     }
 }
@@ -218,6 +265,12 @@ impl ReceiverBuilder {
         self
     }
 
+    /// Decodes received datagrams with forward error correction
+    pub fn with_fec(mut self, enabled: bool) -> Self {
+        self.fec = enabled;
+        self
+    }
+
     /// Builds the datagram receiver
     pub fn build(self) -> Result<Receiver, core::convert::Infallible> {
         Ok(Receiver {
@@ -226,6 +279,7 @@ impl ReceiverBuilder {
             waker: None,
             max_datagram_frame_size: self.max_datagram_frame_size,
             error: None,
+            fec: self.fec.then(fec::Decoder::default),
         })
     }
 }
@@ -257,6 +311,11 @@ pub struct Sender {
     waker: Option<Waker>,
     max_datagram_payload: u64,
     error: Option<connection::Error>,
+    fec: Option<fec::Encoder>,
+    /// Parity datagrams for completed FEC groups waiting to be transmitted
+    parity: VecDeque<Bytes>,
+    /// Set when the application requests the current FEC group to be completed
+    flush_fec: bool,
 }
 
 #[non_exhaustive]
@@ -301,6 +360,33 @@ impl Sender {
         SenderBuilder::default()
     }
 
+    /// Polls the send queue for capacity
+    ///
+    /// This can be used to wait for space on the queue before producing a datagram, rather than
+    /// having [`send_datagram`](Self::send_datagram) return [`DatagramError::QueueAtCapacity`]
+    /// or [`send_datagram_forced`](Self::send_datagram_forced) drop the oldest datagram.
+    ///
+    /// # Return value
+    ///
+    /// - `Poll::Pending` if the datagram's send buffer capacity is currently exhausted. In this
+    ///   case, the caller should retry after the [`Waker`](core::task::Waker) on the provided
+    ///   [`Context`](core::task::Context) is notified.
+    /// - `Poll::Ready(Ok(available))` if the queue can currently accept `available` datagrams.
+    /// - `Poll::Ready(Err(DatagramError))` if a connection-level error occurred.
+    pub fn poll_send_ready(&mut self, cx: &mut Context) -> Poll<Result<usize, DatagramError>> {
+        if let Some(err) = self.error {
+            return Poll::Ready(Err(DatagramError::ConnectionError { error: err }));
+        }
+
+        let available = self.capacity.saturating_sub(self.queue.len());
+        if available == 0 {
+            self.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        Poll::Ready(Ok(available))
+    }
+
     /// Enqueues a datagram for sending it towards the peer.
     ///
     /// # Return value
@@ -404,6 +490,29 @@ impl Sender {
         Ok(())
     }
 
+    /// Completes the current forward error correction group
+    ///
+    /// Parity datagrams are sent once a group has as many datagrams as the configured
+    /// [`fec::Scheme`] allows. This can be used to protect a partial group without waiting for more
+    /// datagrams, for example at the end of a media frame. Datagrams still on the queue are included
+    /// in the group. Does nothing if FEC is not in use on the connection.
+    pub fn finish_fec_group(&mut self) {
+        self.flush_fec = self.fec.is_some();
+        if self.queue.is_empty() {
+            self.on_queue_drained();
+        }
+    }
+
+    fn on_queue_drained(&mut self) {
+        if !core::mem::take(&mut self.flush_fec) {
+            return;
+        }
+
+        if let Some(encoder) = self.fec.as_mut() {
+            self.parity.extend(encoder.finish_group());
+        }
+    }
+
     /// Filter through the datagrams in the send queue and only keep those that
     /// match a predicate
     pub fn retain_datagrams<F>(&mut self, f: F)
@@ -459,11 +568,39 @@ impl super::Sender for Sender {
         }
         self.record_capacity_stats(packet.remaining_capacity());
         let mut has_written = false;
+
+        // Parity datagrams for completed FEC groups are sent before any new datagrams
+        while let Some(parity) = self.parity.pop_front() {
+            if packet.remaining_capacity() >= parity.len() {
+                if packet.write_datagram(&parity).is_ok() {
+                    has_written = true;
+                }
+            } else if has_written {
+                self.parity.push_front(parity);
+                return;
+            }
+        }
+
         while packet.remaining_capacity() > 0 {
             if let Some(datagram) = self.queue.pop_front() {
+                let len = match &self.fec {
+                    Some(encoder) => encoder.encoded_len(datagram.data.len()),
+                    None => datagram.data.len(),
+                };
+
                 // Ensure there is enough space in the packet to send a datagram
-                if packet.remaining_capacity() >= datagram.data.len() {
-                    match packet.write_datagram(&datagram.data) {
+                if packet.remaining_capacity() >= len {
+                    let result = if let Some(encoder) = self.fec.as_mut() {
+                        let result = packet.write_datagram(&encoder.encode(&datagram.data));
+                        if encoder.is_group_complete() {
+                            self.parity.extend(encoder.finish_group());
+                        }
+                        result
+                    } else {
+                        packet.write_datagram(&datagram.data)
+                    };
+
+                    match result {
                         Ok(()) => has_written = true,
                         Err(_error) => {
                             continue;
@@ -485,14 +622,19 @@ impl super::Sender for Sender {
                 }
             } else {
                 // If there are no datagrams on the queue we return
+                self.on_queue_drained();
                 return;
             }
         }
+
+        if self.queue.is_empty() {
+            self.on_queue_drained();
+        }
     }
 
     #[inline]
     fn has_transmission_interest(&self) -> bool {
-        !self.queue.is_empty()
+        !self.queue.is_empty() || !self.parity.is_empty()
     }
 
     fn on_connection_error(&mut self, error: connection::Error) {
@@ -510,6 +652,7 @@ impl super::Sender for Sender {
 struct SenderBuilder {
     queue_capacity: usize,
     max_datagram_payload: u64,
+    fec: Option<fec::Scheme>,
 }
 
 impl Default for SenderBuilder {
@@ -517,6 +660,7 @@ impl Default for SenderBuilder {
         Self {
             queue_capacity: 200,
             max_datagram_payload: 0,
+            fec: None,
         }
     }
 }
@@ -534,17 +678,34 @@ impl SenderBuilder {
         self
     }
 
+    /// Encodes datagrams with the forward error correction scheme, if any
+    pub fn with_fec(mut self, scheme: Option<fec::Scheme>) -> Self {
+        self.fec = scheme;
+        self
+    }
+
     /// Builds the datagram sender into a provider
     pub fn build(self) -> Result<Sender, core::convert::Infallible> {
+        let mut max_datagram_payload = self.max_datagram_payload;
+        if self.fec.is_some() {
+            // make sure the parity datagrams fit within the peer's limits
+            max_datagram_payload = max_datagram_payload.saturating_sub(fec::MAX_OVERHEAD as u64);
+            // the payload length is encoded in 16 bits
+            max_datagram_payload = max_datagram_payload.min(u16::MAX as u64);
+        }
+
         Ok(Sender {
             queue: VecDeque::with_capacity(self.queue_capacity),
             capacity: self.queue_capacity,
-            max_datagram_payload: self.max_datagram_payload,
+            max_datagram_payload,
             max_packet_space: 0,
             min_packet_space: 0,
             smoothed_packet_size: 0.0,
             waker: None,
             error: None,
+            fec: self.fec.map(fec::Encoder::new),
+            parity: VecDeque::new(),
+            flush_fec: false,
         })
     }
 }
@@ -561,6 +722,7 @@ mod tests {
         let conn_info = ConnectionInfo {
             max_datagram_payload: 100,
             waker: noop_waker(),
+            peer_supports_fec: false,
         };
         // Create a default sender queue that only holds two elements
         let mut default_sender = Sender::builder()
@@ -600,6 +762,7 @@ mod tests {
         let conn_info = ConnectionInfo {
             max_datagram_payload: 100,
             waker: noop_waker(),
+            peer_supports_fec: false,
         };
         // Create a default sender queue that only holds two elements
         let mut default_sender = Sender::builder()
@@ -706,11 +869,115 @@ mod tests {
         assert!(default_sender.queue.is_empty());
     }
 
+    #[test]
+    fn poll_send_ready() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_capacity(2)
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+
+        let (waker, wake_count) = new_count_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(default_sender.poll_send_ready(&mut cx), Poll::Ready(Ok(2)));
+        default_sender
+            .send_datagram(bytes::Bytes::from_static(&[1, 2, 3]))
+            .unwrap();
+        assert_eq!(default_sender.poll_send_ready(&mut cx), Poll::Ready(Ok(1)));
+        default_sender
+            .send_datagram(bytes::Bytes::from_static(&[4, 5, 6]))
+            .unwrap();
+
+        // Queue is at capacity so the waker is stored
+        assert!(default_sender.waker.is_none());
+        assert_eq!(default_sender.poll_send_ready(&mut cx), Poll::Pending);
+        assert!(default_sender.waker.is_some());
+
+        let mut packet = MockPacket {
+            remaining_capacity: 3,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+
+        // Transmitting a datagram frees up space and wakes the application
+        assert_eq!(wake_count, 1);
+        assert_eq!(default_sender.poll_send_ready(&mut cx), Poll::Ready(Ok(1)));
+        assert!(default_sender
+            .send_datagram(bytes::Bytes::from_static(&[7, 8, 9]))
+            .is_ok());
+
+        // A pending readiness poll is woken on connection errors
+        assert_eq!(default_sender.poll_send_ready(&mut cx), Poll::Pending);
+        let conn_err = connection::Error::closed(crate::endpoint::Location::Remote);
+        crate::datagram::Sender::on_connection_error(&mut default_sender, conn_err);
+        assert_eq!(wake_count, 2);
+        assert_eq!(
+            default_sender.poll_send_ready(&mut cx),
+            Poll::Ready(Err(DatagramError::ConnectionError { error: conn_err }))
+        );
+    }
+
+    #[test]
+    fn fec_parity() {
+        let mut conn_info = ConnectionInfo::new(100, noop_waker());
+        conn_info.peer_supports_fec = true;
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .with_fec(Some(fec::Scheme::Xor { group_size: 2 }))
+            .build()
+            .unwrap();
+
+        // the FEC overhead is reserved from the peer's limit
+        assert_eq!(
+            default_sender.max_datagram_payload,
+            100 - fec::MAX_OVERHEAD as u64
+        );
+
+        for datagram in [&[1, 2, 3][..], &[4, 5, 6], &[7, 8, 9]] {
+            default_sender
+                .send_datagram(bytes::Bytes::copy_from_slice(datagram))
+                .unwrap();
+        }
+
+        let mut packet = MockPacket {
+            remaining_capacity: 100,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+
+        // one parity datagram for the full group
+        assert!(default_sender.queue.is_empty());
+        assert_eq!(default_sender.parity.len(), 1);
+
+        // the partial group is completed on request
+        default_sender.finish_fec_group();
+        assert_eq!(default_sender.parity.len(), 2);
+        assert!(crate::datagram::Sender::has_transmission_interest(
+            &default_sender
+        ));
+
+        let mut packet = MockPacket {
+            remaining_capacity: 100,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+        assert!(default_sender.parity.is_empty());
+        assert!(!crate::datagram::Sender::has_transmission_interest(
+            &default_sender
+        ));
+    }
+
     #[test]
     fn retain_datagrams() {
         let conn_info = ConnectionInfo {
             max_datagram_payload: 100,
             waker: noop_waker(),
+            peer_supports_fec: false,
         };
         let mut default_sender = Sender::builder()
             .with_capacity(3)
@@ -933,5 +1200,9 @@ mod tests {
         fn datagrams_prioritized(&self) -> bool {
             self.datagrams_prioritized
         }
+
+        fn packet_number(&self) -> u64 {
+            0
+        }
     }
 }
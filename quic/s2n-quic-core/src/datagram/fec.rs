@@ -0,0 +1,691 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Forward error correction for unreliable datagrams
+//!
+//! Outgoing datagrams are split into groups and each group is followed by one or more parity
+//! datagrams. A receiver can reconstruct as many missing datagrams in a group as parity datagrams
+//! it received for that group. Every datagram is prefixed with a header:
+//!
+//! ```text
+//! FEC Datagram {
+//!   Group (i),
+//!   Kind (8),
+//!   Index (8),
+//!   [Data Count (8)],
+//!   [Parity Count (8)],
+//!   Payload (..),
+//! }
+//! ```
+//!
+//! Data datagrams carry the application payload as-is so they can be delivered as soon as they
+//! arrive. Parity datagrams additionally carry the number of data and parity datagrams in the
+//! group. Their payload is computed over the data payloads, each prefixed with its 16-bit length and
+//! zero-padded to the longest one in the group.
+//!
+//! FEC is only used on a connection if both endpoints advertise support for it with the
+//! [`DatagramFecSupport`](crate::transport::parameters::DatagramFecSupport) transport parameter.
+
+use crate::varint::VarInt;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use bytes::Bytes;
+use s2n_codec::{
+    decoder_invariant, DecoderBuffer, DecoderError, Encoder as _, EncoderBuffer, EncoderValue,
+};
+
+/// The maximum number of bytes FEC adds on top of an application payload
+///
+/// Parity datagrams are the largest with a full header and a length prefix for the longest payload.
+pub const MAX_OVERHEAD: usize = MAX_HEADER_LEN + LEN_PREFIX;
+
+/// The group is encoded as a variable-length integer followed by up to 4 single-byte fields
+const MAX_HEADER_LEN: usize = 8 + 4;
+
+/// The number of groups a [`Decoder`] tracks by default
+pub const DEFAULT_MAX_GROUPS: usize = 8;
+
+const LEN_PREFIX: usize = core::mem::size_of::<u16>();
+
+/// The scheme used to compute parity datagrams
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Scheme {
+    /// A single parity datagram which is the XOR of the `group_size` datagrams in the group
+    ///
+    /// Recovers one lost datagram per group.
+    Xor { group_size: u8 },
+    /// `parity_shards` parity datagrams computed with a Cauchy Reed-Solomon code over groups of
+    /// `data_shards` datagrams
+    ///
+    /// Recovers up to `parity_shards` lost datagrams per group.
+    ReedSolomon { data_shards: u8, parity_shards: u8 },
+}
+
+impl Scheme {
+    /// Returns `true` if the scheme can be used to encode datagrams
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Self::Xor { group_size } => group_size > 0,
+            Self::ReedSolomon {
+                data_shards,
+                parity_shards,
+            } => {
+                // the evaluation points for data and parity shards need to be distinct
+                data_shards > 0
+                    && parity_shards > 0
+                    && data_shards as usize + parity_shards as usize <= 256
+            }
+        }
+    }
+
+    #[inline]
+    fn data_shards(&self) -> usize {
+        match *self {
+            Self::Xor { group_size } => group_size as usize,
+            Self::ReedSolomon { data_shards, .. } => data_shards as usize,
+        }
+    }
+
+    #[inline]
+    fn parity_kind(&self) -> Kind {
+        match self {
+            Self::Xor { .. } => Kind::XorParity,
+            Self::ReedSolomon { .. } => Kind::ReedSolomonParity,
+        }
+    }
+
+    #[inline]
+    fn parity_shards(&self) -> u8 {
+        match *self {
+            Self::Xor { .. } => 1,
+            Self::ReedSolomon { parity_shards, .. } => parity_shards,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Data,
+    XorParity,
+    ReedSolomonParity,
+}
+
+impl Kind {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Data),
+            1 => Some(Self::XorParity),
+            2 => Some(Self::ReedSolomonParity),
+            _ => None,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Self::Data => 0,
+            Self::XorParity => 1,
+            Self::ReedSolomonParity => 2,
+        }
+    }
+
+    /// Returns the coefficient applied to the data shard at `data_index` for the parity shard at
+    /// `parity_index`
+    #[inline]
+    fn coefficient(self, parity_index: u8, data_index: u8) -> u8 {
+        match self {
+            Self::Data | Self::XorParity => 1,
+            // Cauchy matrix with parity points counting down from 255 and data points counting up
+            // from 0, which keeps the coefficients independent of the group's data count
+            Self::ReedSolomonParity => gf256::inv((255 - parity_index) ^ data_index),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Header {
+    group: VarInt,
+    kind: Kind,
+    index: u8,
+    /// The number of data and parity shards in the group, which is only sent on parity shards
+    counts: Option<(u8, u8)>,
+}
+
+impl Header {
+    fn decode(buffer: DecoderBuffer) -> Result<(Self, DecoderBuffer), DecoderError> {
+        let (group, buffer) = buffer.decode::<VarInt>()?;
+        let (kind, buffer) = buffer.decode::<u8>()?;
+        let kind = Kind::from_u8(kind).ok_or(DecoderError::InvariantViolation(
+            "invalid FEC datagram kind",
+        ))?;
+        let (index, buffer) = buffer.decode::<u8>()?;
+
+        if kind == Kind::Data {
+            let header = Self {
+                group,
+                kind,
+                index,
+                counts: None,
+            };
+            return Ok((header, buffer));
+        }
+
+        let (data_count, buffer) = buffer.decode::<u8>()?;
+        let (parity_count, buffer) = buffer.decode::<u8>()?;
+
+        decoder_invariant!(data_count > 0, "FEC group without data");
+        decoder_invariant!(index < parity_count, "FEC parity index out of range");
+        if kind == Kind::ReedSolomonParity {
+            decoder_invariant!(
+                data_count as usize + parity_count as usize <= 256,
+                "FEC group too large"
+            );
+        }
+
+        let header = Self {
+            group,
+            kind,
+            index,
+            counts: Some((data_count, parity_count)),
+        };
+        Ok((header, buffer))
+    }
+}
+
+impl EncoderValue for Header {
+    fn encode<E: s2n_codec::Encoder>(&self, encoder: &mut E) {
+        encoder.encode(&self.group);
+        encoder.encode(&self.kind.as_u8());
+        encoder.encode(&self.index);
+        if let Some((data_count, parity_count)) = self.counts {
+            encoder.encode(&data_count);
+            encoder.encode(&parity_count);
+        }
+    }
+}
+
+fn encode_datagram(header: &Header, payload: &[u8]) -> Bytes {
+    let header_len = header.encoding_size();
+    let mut datagram = vec![0; header_len + payload.len()];
+    let mut buffer = EncoderBuffer::new(&mut datagram);
+    buffer.encode(header);
+    buffer.write_slice(payload);
+    datagram.into()
+}
+
+/// Encodes outgoing datagrams into groups followed by parity datagrams
+#[derive(Debug)]
+pub struct Encoder {
+    scheme: Scheme,
+    group: VarInt,
+    /// The length-prefixed payloads of the current group
+    symbols: Vec<Vec<u8>>,
+}
+
+impl Encoder {
+    /// Creates an encoder for the given scheme
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scheme is not [valid](Scheme::is_valid)
+    pub fn new(scheme: Scheme) -> Self {
+        assert!(scheme.is_valid(), "invalid FEC scheme: {scheme:?}");
+        Self {
+            scheme,
+            group: VarInt::ZERO,
+            symbols: Vec::with_capacity(scheme.data_shards()),
+        }
+    }
+
+    /// Returns the length of the datagram produced by [`Self::encode`] for a payload
+    #[inline]
+    pub fn encoded_len(&self, payload_len: usize) -> usize {
+        self.data_header().encoding_size() + payload_len
+    }
+
+    /// Encodes a payload into a data datagram belonging to the current group
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current group is complete or the payload is longer than `u16::MAX`
+    pub fn encode(&mut self, payload: &[u8]) -> Bytes {
+        assert!(!self.is_group_complete());
+        let len: u16 = payload.len().try_into().expect("payload too large for FEC");
+
+        let datagram = encode_datagram(&self.data_header(), payload);
+
+        let mut symbol = Vec::with_capacity(LEN_PREFIX + payload.len());
+        symbol.extend_from_slice(&len.to_be_bytes());
+        symbol.extend_from_slice(payload);
+        self.symbols.push(symbol);
+
+        datagram
+    }
+
+    /// Returns `true` if the current group has as many datagrams as the scheme allows
+    #[inline]
+    pub fn is_group_complete(&self) -> bool {
+        self.symbols.len() >= self.scheme.data_shards()
+    }
+
+    /// Returns `true` if datagrams have been encoded in the current group
+    #[inline]
+    pub fn has_pending_group(&self) -> bool {
+        !self.symbols.is_empty()
+    }
+
+    /// Completes the current group, which may contain fewer datagrams than the scheme allows,
+    /// and returns its parity datagrams
+    pub fn finish_group(&mut self) -> impl Iterator<Item = Bytes> {
+        let mut parity = Vec::new();
+
+        if !self.symbols.is_empty() {
+            let len = self.symbols.iter().map(Vec::len).max().unwrap_or_default();
+            let data_count = self.symbols.len() as u8;
+            let parity_count = self.scheme.parity_shards();
+            let kind = self.scheme.parity_kind();
+
+            for parity_index in 0..parity_count {
+                let mut symbol = vec![0; len];
+                for (data_index, data) in self.symbols.iter().enumerate() {
+                    let coefficient = kind.coefficient(parity_index, data_index as u8);
+                    gf256::mul_add(&mut symbol, data, coefficient);
+                }
+
+                let header = Header {
+                    group: self.group,
+                    kind,
+                    index: parity_index,
+                    counts: Some((data_count, parity_count)),
+                };
+                parity.push(encode_datagram(&header, &symbol));
+            }
+
+            self.symbols.clear();
+            self.group = self.group.checked_add_usize(1).unwrap_or(VarInt::ZERO);
+        }
+
+        parity.into_iter()
+    }
+
+    #[inline]
+    fn data_header(&self) -> Header {
+        Header {
+            group: self.group,
+            kind: Kind::Data,
+            index: self.symbols.len() as u8,
+            counts: None,
+        }
+    }
+}
+
+/// Decodes incoming datagrams and reconstructs the ones that were lost
+#[derive(Debug)]
+pub struct Decoder {
+    /// The groups being tracked, in ascending order
+    groups: VecDeque<Group>,
+    max_groups: usize,
+    /// Datagrams for groups below this value are no longer tracked and are dropped
+    min_group: u64,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_GROUPS)
+    }
+}
+
+impl Decoder {
+    /// Creates a decoder that tracks up to `max_groups` groups at a time
+    pub fn new(max_groups: usize) -> Self {
+        Self {
+            groups: VecDeque::with_capacity(max_groups),
+            max_groups: max_groups.max(1),
+            min_group: 0,
+        }
+    }
+
+    /// Decodes a received datagram
+    ///
+    /// `on_datagram` is called with the application payload of the datagram, if it carried one,
+    /// followed by any payloads that could be reconstructed with it. Each payload is delivered at
+    /// most once.
+    pub fn decode<F: FnMut(&[u8])>(
+        &mut self,
+        datagram: &[u8],
+        mut on_datagram: F,
+    ) -> Result<(), DecoderError> {
+        let (header, payload) = Header::decode(DecoderBuffer::new(datagram))?;
+        let payload = payload.into_less_safe_slice();
+
+        let group = header.group.as_u64();
+        if group < self.min_group {
+            // we no longer know if this was already delivered
+            return Ok(());
+        }
+
+        let group = self.group_mut(group);
+
+        if header.kind == Kind::Data {
+            if !group.on_data(header.index, payload) {
+                return Ok(());
+            }
+            on_datagram(payload);
+        } else {
+            group.on_parity(&header, payload);
+        }
+
+        group.recover(&mut on_datagram);
+
+        Ok(())
+    }
+
+    fn group_mut(&mut self, id: u64) -> &mut Group {
+        let mut index = self.groups.partition_point(|group| group.id < id);
+
+        if self.groups.get(index).map_or(true, |group| group.id != id) {
+            self.groups.insert(index, Group::new(id));
+        }
+
+        // evict the oldest groups, other than the one being accessed
+        while self.groups.len() > self.max_groups && index > 0 {
+            let evicted = self.groups.pop_front().expect("groups is not empty");
+            self.min_group = evicted.id + 1;
+            index -= 1;
+        }
+
+        &mut self.groups[index]
+    }
+}
+
+#[derive(Debug)]
+struct Group {
+    id: u64,
+    /// The length-prefixed data payloads received or reconstructed, indexed by their position
+    data: Vec<Option<Vec<u8>>>,
+    parity: Vec<Parity>,
+    data_count: Option<u8>,
+    /// Set once every data payload has been delivered or the group can't be recovered
+    is_complete: bool,
+}
+
+#[derive(Debug)]
+struct Parity {
+    kind: Kind,
+    index: u8,
+    symbol: Vec<u8>,
+}
+
+impl Group {
+    fn new(id: u64) -> Self {
+        Self {
+            id,
+            data: Vec::new(),
+            parity: Vec::new(),
+            data_count: None,
+            is_complete: false,
+        }
+    }
+
+    /// Records a data payload, returning `false` if it was already delivered
+    fn on_data(&mut self, index: u8, payload: &[u8]) -> bool {
+        let index = index as usize;
+        if self.data.len() <= index {
+            self.data.resize(index + 1, None);
+        } else if self.data[index].is_some() {
+            return false;
+        }
+
+        if self.is_complete {
+            // the payload is no longer needed for recovery but the slot prevents duplicates
+            self.data[index] = Some(Vec::new());
+            return true;
+        }
+
+        let mut symbol = Vec::with_capacity(LEN_PREFIX + payload.len());
+        symbol.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        symbol.extend_from_slice(payload);
+        self.data[index] = Some(symbol);
+
+        true
+    }
+
+    fn on_parity(&mut self, header: &Header, payload: &[u8]) {
+        if self.is_complete {
+            return;
+        }
+
+        let (data_count, _parity_count) = header.counts.expect("parity shards include counts");
+
+        match self.data_count {
+            Some(count) if count != data_count => {
+                // the peer is sending inconsistent groups so give up on recovering it
+                self.is_complete = true;
+                return;
+            }
+            _ => self.data_count = Some(data_count),
+        }
+
+        if let Some(parity) = self.parity.first() {
+            if parity.symbol.len() != payload.len() || parity.kind != header.kind {
+                self.is_complete = true;
+                return;
+            }
+        }
+
+        if self
+            .parity
+            .iter()
+            .any(|parity| parity.index == header.index)
+        {
+            return;
+        }
+
+        self.parity.push(Parity {
+            kind: header.kind,
+            index: header.index,
+            symbol: payload.to_vec(),
+        });
+    }
+
+    fn recover<F: FnMut(&[u8])>(&mut self, on_datagram: &mut F) {
+        if self.is_complete {
+            return;
+        }
+
+        let Some(data_count) = self.data_count else {
+            return;
+        };
+        let data_count = data_count as usize;
+        if self.data.len() < data_count {
+            self.data.resize(data_count, None);
+        }
+
+        let missing: Vec<usize> = (0..data_count)
+            .filter(|index| self.data[*index].is_none())
+            .collect();
+
+        if missing.is_empty() {
+            self.complete();
+            return;
+        }
+
+        if self.parity.len() < missing.len() {
+            return;
+        }
+
+        if let Some(recovered) = self.solve(&missing, data_count) {
+            for (index, symbol) in missing.into_iter().zip(recovered) {
+                let len = u16::from_be_bytes([symbol[0], symbol[1]]) as usize;
+                if let Some(payload) = symbol.get(LEN_PREFIX..LEN_PREFIX + len) {
+                    on_datagram(payload);
+                }
+                self.data[index] = Some(symbol);
+            }
+        }
+
+        self.complete();
+    }
+
+    /// Solves for the missing data symbols with the received parity symbols
+    fn solve(&self, missing: &[usize], data_count: usize) -> Option<Vec<Vec<u8>>> {
+        let len = self.parity[0].symbol.len();
+        if len < LEN_PREFIX {
+            return None;
+        }
+
+        let parity = &self.parity[..missing.len()];
+
+        // remove the contribution of the data symbols we already have from each parity symbol
+        let mut rhs = Vec::with_capacity(parity.len());
+        for parity in parity {
+            let mut symbol = parity.symbol.clone();
+            for (index, data) in self.data[..data_count].iter().enumerate() {
+                if let Some(data) = data {
+                    if data.len() > len {
+                        return None;
+                    }
+                    let coefficient = parity.kind.coefficient(parity.index, index as u8);
+                    gf256::mul_add(&mut symbol, data, coefficient);
+                }
+            }
+            rhs.push(symbol);
+        }
+
+        let mut matrix: Vec<Vec<u8>> = parity
+            .iter()
+            .map(|parity| {
+                missing
+                    .iter()
+                    .map(|index| parity.kind.coefficient(parity.index, *index as u8))
+                    .collect()
+            })
+            .collect();
+
+        // Gauss-Jordan elimination over GF(2^8)
+        let size = missing.len();
+        for column in 0..size {
+            let pivot = (column..size).find(|row| matrix[*row][column] != 0)?;
+            matrix.swap(column, pivot);
+            rhs.swap(column, pivot);
+
+            let scale = gf256::inv(matrix[column][column]);
+            for value in &mut matrix[column] {
+                *value = gf256::mul(*value, scale);
+            }
+            for value in &mut rhs[column] {
+                *value = gf256::mul(*value, scale);
+            }
+
+            for row in 0..size {
+                let factor = matrix[row][column];
+                if row == column || factor == 0 {
+                    continue;
+                }
+
+                let (pivot_row, target_row) = pick_rows(&mut matrix, column, row);
+                for (target, pivot) in target_row.iter_mut().zip(pivot_row.iter()) {
+                    *target ^= gf256::mul(*pivot, factor);
+                }
+
+                let (pivot_row, target_row) = pick_rows(&mut rhs, column, row);
+                gf256::mul_add(target_row, pivot_row, factor);
+            }
+        }
+
+        Some(rhs)
+    }
+
+    fn complete(&mut self) {
+        self.is_complete = true;
+        self.parity = Vec::new();
+        // keep the slots to detect duplicates but release the payloads
+        for data in self.data.iter_mut().flatten() {
+            *data = Vec::new();
+        }
+    }
+}
+
+/// Returns a shared reference to the `pivot` row and a mutable reference to the `target` row
+#[inline]
+fn pick_rows<T>(rows: &mut [T], pivot: usize, target: usize) -> (&T, &mut T) {
+    debug_assert_ne!(pivot, target);
+    if pivot < target {
+        let (head, tail) = rows.split_at_mut(target);
+        (&head[pivot], &mut tail[0])
+    } else {
+        let (head, tail) = rows.split_at_mut(pivot);
+        (&tail[0], &mut head[target])
+    }
+}
+
+/// Arithmetic in GF(2^8) with the 0x11d reduction polynomial
+mod gf256 {
+    struct Tables {
+        exp: [u8; 512],
+        log: [u8; 256],
+    }
+
+    static TABLES: Tables = Tables::new();
+
+    impl Tables {
+        const fn new() -> Self {
+            let mut exp = [0; 512];
+            let mut log = [0; 256];
+
+            let mut value: u16 = 1;
+            let mut i = 0;
+            while i < 255 {
+                exp[i] = value as u8;
+                log[value as usize] = i as u8;
+                value <<= 1;
+                if value & 0x100 != 0 {
+                    value ^= 0x11d;
+                }
+                i += 1;
+            }
+
+            // duplicate the table so the sum of two logarithms doesn't need to be reduced
+            while i < 512 {
+                exp[i] = exp[i - 255];
+                i += 1;
+            }
+
+            Self { exp, log }
+        }
+    }
+
+    #[inline]
+    pub fn mul(a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        TABLES.exp[TABLES.log[a as usize] as usize + TABLES.log[b as usize] as usize]
+    }
+
+    #[inline]
+    pub fn inv(a: u8) -> u8 {
+        debug_assert_ne!(a, 0, "zero has no inverse");
+        TABLES.exp[255 - TABLES.log[a as usize] as usize]
+    }
+
+    /// Computes `dst += src * coefficient`, treating `src` as zero-padded to the length of `dst`
+    #[inline]
+    pub fn mul_add(dst: &mut [u8], src: &[u8], coefficient: u8) {
+        match coefficient {
+            0 => {}
+            1 => {
+                for (dst, src) in dst.iter_mut().zip(src) {
+                    *dst ^= *src;
+                }
+            }
+            _ => {
+                for (dst, src) in dst.iter_mut().zip(src) {
+                    *dst ^= mul(*src, coefficient);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,261 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use bolero::check;
+
+/// Encodes the payloads with the given scheme and returns the data and parity datagrams in order
+fn encode(scheme: Scheme, payloads: &[Vec<u8>]) -> Vec<Bytes> {
+    let mut encoder = Encoder::new(scheme);
+    let mut datagrams = vec![];
+    for payload in payloads {
+        datagrams.push(encoder.encode(payload));
+        if encoder.is_group_complete() {
+            datagrams.extend(encoder.finish_group());
+        }
+    }
+    datagrams.extend(encoder.finish_group());
+    datagrams
+}
+
+/// Decodes the datagrams and returns the delivered payloads
+fn decode(datagrams: impl IntoIterator<Item = Bytes>) -> Vec<Vec<u8>> {
+    let mut decoder = Decoder::default();
+    let mut delivered = vec![];
+    for datagram in datagrams {
+        decoder
+            .decode(&datagram, |payload| delivered.push(payload.to_vec()))
+            .unwrap();
+    }
+    delivered
+}
+
+fn payloads(count: usize) -> Vec<Vec<u8>> {
+    (0..count).map(|i| vec![i as u8; 10 + i * 7 % 50]).collect()
+}
+
+#[test]
+fn gf256_inverse_test() {
+    for a in 1..=255u8 {
+        assert_eq!(gf256::mul(a, gf256::inv(a)), 1);
+    }
+}
+
+#[test]
+fn scheme_validation_test() {
+    assert!(Scheme::Xor { group_size: 4 }.is_valid());
+    assert!(!Scheme::Xor { group_size: 0 }.is_valid());
+    assert!(Scheme::ReedSolomon {
+        data_shards: 200,
+        parity_shards: 56
+    }
+    .is_valid());
+    assert!(!Scheme::ReedSolomon {
+        data_shards: 200,
+        parity_shards: 57
+    }
+    .is_valid());
+    assert!(!Scheme::ReedSolomon {
+        data_shards: 4,
+        parity_shards: 0
+    }
+    .is_valid());
+}
+
+#[test]
+fn overhead_test() {
+    let mut encoder = Encoder::new(Scheme::Xor { group_size: 1 });
+    let payload = [1; 100];
+    let encoded_len = encoder.encoded_len(payload.len());
+    let data = encoder.encode(&payload);
+    assert_eq!(data.len(), encoded_len);
+
+    let parity: Vec<_> = encoder.finish_group().collect();
+    assert_eq!(parity.len(), 1);
+    assert!(parity[0].len() <= payload.len() + MAX_OVERHEAD);
+}
+
+#[test]
+fn lossless_test() {
+    let payloads = payloads(10);
+    let datagrams = encode(Scheme::Xor { group_size: 4 }, &payloads);
+    // 3 groups, each with a single parity datagram
+    assert_eq!(datagrams.len(), 13);
+    assert_eq!(decode(datagrams), payloads);
+}
+
+#[test]
+fn xor_recovery_test() {
+    let payloads = payloads(8);
+    let datagrams = encode(Scheme::Xor { group_size: 4 }, &payloads);
+
+    // drop the second data datagram of each group
+    let received = datagrams
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| index % 5 != 1)
+        .map(|(_, datagram)| datagram);
+
+    let mut delivered = decode(received);
+    delivered.sort();
+    let mut expected = payloads;
+    expected.sort();
+    assert_eq!(delivered, expected);
+}
+
+#[test]
+fn xor_unrecoverable_test() {
+    let payloads = payloads(4);
+    let datagrams = encode(Scheme::Xor { group_size: 4 }, &payloads);
+
+    // two losses can't be recovered with a single parity datagram
+    let received = datagrams
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| *index != 0 && *index != 2)
+        .map(|(_, datagram)| datagram);
+
+    assert_eq!(
+        decode(received),
+        vec![payloads[1].clone(), payloads[3].clone()]
+    );
+}
+
+#[test]
+fn partial_group_test() {
+    let mut encoder = Encoder::new(Scheme::ReedSolomon {
+        data_shards: 8,
+        parity_shards: 2,
+    });
+    let first = encoder.encode(b"hello");
+    let second = encoder.encode(b"world!");
+    assert!(!encoder.is_group_complete());
+    assert!(encoder.has_pending_group());
+
+    let parity: Vec<_> = encoder.finish_group().collect();
+    assert_eq!(parity.len(), 2);
+    assert!(!encoder.has_pending_group());
+    assert_eq!(encoder.finish_group().count(), 0);
+
+    // both data datagrams are recovered from the parity datagrams
+    let mut delivered = decode(parity);
+    delivered.sort();
+    assert_eq!(delivered, vec![b"hello".to_vec(), b"world!".to_vec()]);
+
+    // late data datagrams aren't delivered again
+    let mut decoder = Decoder::default();
+    let mut delivered = vec![];
+    for datagram in encode(
+        Scheme::Xor { group_size: 2 },
+        &[b"hello".to_vec(), b"world!".to_vec()],
+    )
+    .into_iter()
+    .skip(1)
+    .chain([first, second])
+    {
+        decoder
+            .decode(&datagram, |payload| delivered.push(payload.to_vec()))
+            .unwrap();
+    }
+    assert_eq!(delivered, vec![b"world!".to_vec(), b"hello".to_vec()]);
+}
+
+#[test]
+fn duplicate_test() {
+    let payloads = payloads(2);
+    let datagrams = encode(Scheme::Xor { group_size: 2 }, &payloads);
+    let received = datagrams.iter().chain(datagrams.iter()).cloned();
+    assert_eq!(decode(received), payloads);
+}
+
+#[test]
+fn eviction_test() {
+    let mut decoder = Decoder::new(2);
+    let mut encoder = Encoder::new(Scheme::Xor { group_size: 2 });
+    let mut groups = vec![];
+    for i in 0..3u8 {
+        let data = [encoder.encode(&[i]), encoder.encode(&[i + 10])];
+        let parity: Vec<_> = encoder.finish_group().collect();
+        groups.push((data, parity));
+    }
+
+    let mut delivered = vec![];
+    let mut on_datagram = |payload: &[u8]| delivered.push(payload.to_vec());
+
+    // receive the first datagram of each group
+    for (data, _) in &groups {
+        decoder.decode(&data[0], &mut on_datagram).unwrap();
+    }
+
+    // the first group is no longer tracked so its parity is ignored
+    for (_, parity) in &groups {
+        decoder.decode(&parity[0], &mut on_datagram).unwrap();
+    }
+
+    assert_eq!(
+        delivered,
+        vec![vec![0], vec![1], vec![2], vec![11], vec![12]]
+    );
+}
+
+#[test]
+fn invalid_header_test() {
+    let mut decoder = Decoder::default();
+    let mut on_datagram = |_: &[u8]| panic!("nothing should be delivered");
+
+    // unknown kind
+    assert!(decoder.decode(&[0, 3, 0], &mut on_datagram).is_err());
+    // parity without counts
+    assert!(decoder.decode(&[0, 1, 0], &mut on_datagram).is_err());
+    // parity index out of range
+    assert!(decoder.decode(&[0, 1, 2, 1, 2], &mut on_datagram).is_err());
+    // group without data
+    assert!(decoder.decode(&[0, 2, 0, 0, 1], &mut on_datagram).is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reed_solomon_recovery_test() {
+    check!()
+        .with_type::<(u8, u8, Vec<Vec<u8>>, Vec<bool>)>()
+        .for_each(|(data_shards, parity_shards, payloads, losses)| {
+            let scheme = Scheme::ReedSolomon {
+                data_shards: data_shards % 16 + 1,
+                parity_shards: parity_shards % 4 + 1,
+            };
+            let parity_shards = (parity_shards % 4 + 1) as usize;
+            let payloads = &payloads[..payloads.len().min(64)];
+            let mut losses = losses.iter().copied().chain(core::iter::repeat(false));
+
+            let mut encoder = Encoder::new(scheme);
+            let mut received = vec![];
+            let mut expected = vec![];
+            let mut group = vec![];
+
+            for (index, payload) in payloads.iter().enumerate() {
+                let datagram = encoder.encode(payload);
+                let is_lost = losses.next().unwrap();
+                if !is_lost {
+                    received.push(datagram);
+                }
+                group.push((payload.clone(), is_lost));
+
+                if encoder.is_group_complete() || index == payloads.len() - 1 {
+                    received.extend(encoder.finish_group());
+
+                    let lost = group.iter().filter(|(_, is_lost)| *is_lost).count();
+                    for (payload, is_lost) in group.drain(..) {
+                        // every payload is delivered if there is enough parity
+                        if !is_lost || lost <= parity_shards {
+                            expected.push(payload);
+                        }
+                    }
+                }
+            }
+
+            let mut delivered = decode(received);
+            delivered.sort();
+            expected.sort();
+            assert_eq!(delivered, expected);
+        });
+}
@@ -4,6 +4,8 @@
 #[cfg(feature = "alloc")]
 pub mod default;
 pub mod disabled;
+#[cfg(feature = "alloc")]
+pub mod fec;
 pub mod traits;
 pub use disabled::*;
 pub use traits::*;
@@ -17,6 +17,17 @@ pub trait Endpoint: 'static + Send {
 
     /// Returns the maximum datagram frame size the provider is willing to accept
     fn max_datagram_frame_size(&self, info: &PreConnectionInfo) -> u64;
+
+    /// Returns `true` if the provider encodes and decodes datagrams with
+    /// [forward error correction](crate::datagram::fec)
+    ///
+    /// Support is advertised to the peer in the transport parameters and reported back in
+    /// [`ConnectionInfo::peer_supports_fec`].
+    #[inline]
+    fn supports_fec(&self, info: &PreConnectionInfo) -> bool {
+        let _ = info;
+        false
+    }
 }
 
 /// Information about the accepted connection for which the Sender/Receiver are being created.
@@ -34,6 +45,10 @@ pub struct ConnectionInfo {
     /// `datagram_mut`, perhaps because they don't have an available handle to the connection when
     /// enqueuing packets, or wish to avoid incurring the lock/unlock required by `datagram_mut`.
     pub waker: Waker,
+
+    /// Whether the peer advertised support for
+    /// [forward error correction](crate::datagram::fec) of datagrams
+    pub peer_supports_fec: bool,
 }
 
 impl ConnectionInfo {
@@ -42,6 +57,7 @@ impl ConnectionInfo {
         ConnectionInfo {
             max_datagram_payload,
             waker,
+            peer_supports_fec: false,
         }
     }
 }
@@ -95,6 +111,29 @@ pub trait Sender: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// A callback used to notify the application that a packet carrying datagrams was
+    /// acknowledged by the peer
+    ///
+    /// The `packet_number` matches the value returned by [`Packet::packet_number`] when the
+    /// datagrams were written in [`Sender::on_transmit`]. This is only called once per packet.
+    #[inline]
+    fn on_datagram_ack(&mut self, packet_number: u64) {
+        let _ = packet_number;
+    }
+
+    /// A callback used to notify the application that a packet carrying datagrams was
+    /// declared lost
+    ///
+    /// The `packet_number` matches the value returned by [`Packet::packet_number`] when the
+    /// datagrams were written in [`Sender::on_transmit`]. Datagrams are never retransmitted by the
+    /// connection so applications wishing to recover the data need to enqueue it again. Once a
+    /// packet is declared lost, [`Sender::on_datagram_ack`] will not be called for it, even if
+    /// the peer later acknowledges it.
+    #[inline]
+    fn on_datagram_loss(&mut self, packet_number: u64) {
+        let _ = packet_number;
+    }
 }
 
 /// A packet will be available during the on_transmit callback. Use the methods
@@ -121,6 +160,12 @@ pub trait Packet {
     /// Datagrams get prioritized every other packet, which gives the application the best
     /// chance to send a large datagram.
     fn datagrams_prioritized(&self) -> bool;
+
+    /// Returns the number of the packet being written
+    ///
+    /// Datagrams written to this packet will be reported with this value in
+    /// [`Sender::on_datagram_ack`] or [`Sender::on_datagram_loss`].
+    fn packet_number(&self) -> u64;
 }
 
 #[non_exhaustive]
@@ -0,0 +1,115 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Demultiplexes datagrams for other protocols sharing the endpoint's socket
+//!
+//! Protocols such as STUN may share the same 5-tuple as QUIC, which is common with ICE. Instead
+//! of dropping datagrams for those protocols, the endpoint hands them to a [`Demultiplexer`].
+
+use crate::{event::api::SocketAddress, time::Timestamp};
+
+/// Information about a datagram which doesn't belong to QUIC
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct Datagram<'a> {
+    pub remote_address: SocketAddress<'a>,
+    pub local_address: SocketAddress<'a>,
+    pub timestamp: Timestamp,
+}
+
+impl<'a> Datagram<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: SocketAddress<'a>,
+        local_address: SocketAddress<'a>,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            remote_address,
+            local_address,
+            timestamp,
+        }
+    }
+}
+
+/// Sends datagrams back to the peer of a demultiplexed datagram
+pub trait Responder {
+    /// Queues `payload` to be sent to the remote address of the received datagram
+    ///
+    /// The payload is sent from the endpoint's socket, along with the endpoint's QUIC packets.
+    /// It is dropped if too many responses are already waiting to be transmitted.
+    fn respond(&mut self, payload: &[u8]);
+}
+
+/// Hands datagrams which don't belong to QUIC to the application
+pub trait Demultiplexer: 'static + Send {
+    /// Returns `true` if the datagram should be processed by the QUIC endpoint
+    ///
+    /// By default, this follows the demultiplexing scheme in RFC 9443, where a QUIC packet
+    /// always has the second most significant bit of the first byte set.
+    #[inline]
+    fn is_quic(&mut self, payload: &[u8]) -> bool {
+        // RFC 9443 Section 7: if the value of the first byte is between 64 and 127 (inclusive)
+        // or between 192 and 255 (inclusive), the packet is a QUIC packet.
+        payload.first().is_some_and(|byte| byte & 0x40 == 0x40)
+    }
+
+    /// Called with a datagram which doesn't belong to QUIC
+    ///
+    /// This is called for datagrams rejected by [`Self::is_quic`], as well as datagrams which
+    /// failed to decode as QUIC packets. Returns `false` if the datagram wasn't recognized, in
+    /// which case it's dropped by the endpoint.
+    fn on_datagram(
+        &mut self,
+        datagram: &Datagram,
+        payload: &[u8],
+        responder: &mut dyn Responder,
+    ) -> bool;
+}
+
+/// A [`Demultiplexer`] which passes every datagram to the QUIC endpoint
+#[derive(Debug, Default)]
+pub struct Disabled(());
+
+impl Demultiplexer for Disabled {
+    #[inline]
+    fn is_quic(&mut self, _payload: &[u8]) -> bool {
+        true
+    }
+
+    #[inline]
+    fn on_datagram(
+        &mut self,
+        _datagram: &Datagram,
+        _payload: &[u8],
+        _responder: &mut dyn Responder,
+    ) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Rfc9443;
+
+    impl Demultiplexer for Rfc9443 {
+        fn on_datagram(&mut self, _: &Datagram, _: &[u8], _: &mut dyn Responder) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn is_quic_test() {
+        let mut demux = Rfc9443;
+
+        for byte in 0..=u8::MAX {
+            let expected = matches!(byte, 64..=127 | 192..=255);
+            assert_eq!(demux.is_quic(&[byte]), expected, "{byte}");
+        }
+
+        assert!(!demux.is_quic(&[]));
+        assert!(Disabled::default().is_quic(&[]));
+    }
+}
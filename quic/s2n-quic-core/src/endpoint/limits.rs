@@ -4,7 +4,20 @@
 use crate::{
     event::{api::SocketAddress, IntoEvent, Timestamp},
     inet,
+    recovery::K_GRANULARITY,
+    transport,
 };
+use core::time::Duration;
+
+/// The default granularity of the endpoint's connection timers
+pub const DEFAULT_TIMER_GRANULARITY: Duration = K_GRANULARITY;
+
+/// The default number of connections the endpoint preallocates storage for
+pub const DEFAULT_CONNECTION_CAPACITY: usize = 0;
+
+/// The default error used to close connections which are evicted from the endpoint
+pub const DEFAULT_EVICTION_ERROR: transport::Error =
+    transport::Error::CONNECTION_REFUSED.with_reason("connection evicted");
 
 /// Outcome describes how the library should proceed on a connection attempt. The implementor will
 /// use information from the ConnectionAttempt object to determine how the library should handle
@@ -35,6 +48,15 @@ pub enum Outcome {
     /// Use `Outcome::close()` to construct this variant
     #[non_exhaustive]
     Close,
+
+    /// Cleanly close the connection and ask the client to wait before connecting again
+    ///
+    /// Use `Outcome::close_with_retry_after()` to construct this variant
+    #[non_exhaustive]
+    CloseWithRetryAfter {
+        /// The amount of time the client is asked to wait before connecting again
+        retry_after: Duration,
+    },
 }
 
 impl Outcome {
@@ -57,6 +79,15 @@ impl Outcome {
     pub fn close() -> Self {
         Self::Close
     }
+
+    /// Cleanly close the connection and ask the client to wait before connecting again
+    ///
+    /// Overloaded servers can use this to shed load in a way clients can distinguish from a
+    /// network failure. The hint is available to the client application through
+    /// `connection::Error::retry_after`.
+    pub fn close_with_retry_after(retry_after: Duration) -> Self {
+        Self::CloseWithRetryAfter { retry_after }
+    }
 }
 
 /// A ConnectionAttempt holds information about the state of endpoint receiving a connect, along
@@ -65,7 +96,7 @@ impl Outcome {
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct ConnectionAttempt<'a> {
-    /// Number of handshakes the have begun but not completed
+    /// Number of handshakes that have begun but not completed
     pub inflight_handshakes: usize,
 
     /// Number of open connections
@@ -94,6 +125,338 @@ impl<'a> ConnectionAttempt<'a> {
     }
 }
 
+/// The number of connections on the endpoint at the time a limiter hook is invoked
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub struct ConnectionCounts {
+    /// Number of handshakes that have begun but not completed
+    pub inflight_handshakes: usize,
+
+    /// Number of open connections
+    pub connection_count: usize,
+}
+
+impl ConnectionCounts {
+    #[doc(hidden)]
+    pub fn new(inflight_handshakes: usize, connection_count: usize) -> Self {
+        Self {
+            inflight_handshakes,
+            connection_count,
+        }
+    }
+}
+
+/// An InitialPacket holds the fields of a peer's Initial packet which can be parsed without
+/// removing packet protection, along with information about the state of the endpoint. This can be
+/// used to filter Initial packets before spending any resources on cryptographic operations
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct InitialPacket<'a> {
+    /// The number of connections on the endpoint
+    pub counts: ConnectionCounts,
+
+    /// The unverified address of the peer
+    /// This address comes from the datagram
+    pub remote_address: SocketAddress<'a>,
+
+    /// The QUIC version of the packet
+    pub version: u32,
+
+    /// The destination connection ID chosen by the peer
+    pub destination_connection_id: &'a [u8],
+
+    /// The source connection ID chosen by the peer
+    pub source_connection_id: &'a [u8],
+
+    /// The address validation token included in the packet, if any
+    ///
+    /// The token has not been validated at this point.
+    pub token: &'a [u8],
+
+    /// The length of the datagram containing the packet
+    pub datagram_len: usize,
+    pub timestamp: Timestamp,
+}
+
+impl<'a> InitialPacket<'a> {
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        counts: ConnectionCounts,
+        remote_address: &'a inet::SocketAddress,
+        version: u32,
+        destination_connection_id: &'a [u8],
+        source_connection_id: &'a [u8],
+        token: &'a [u8],
+        datagram_len: usize,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            counts,
+            remote_address: remote_address.into_event(),
+            version,
+            destination_connection_id,
+            source_connection_id,
+            token,
+            datagram_len,
+            timestamp,
+        }
+    }
+}
+
+/// HandshakeOutcome describes how the library should proceed once the peer's ClientHello has been
+/// processed. The implementor will use information from the HandshakeAttempt object to determine
+/// how the library should handle the handshake
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HandshakeOutcome {
+    /// Allow the handshake to continue
+    ///
+    /// Use `HandshakeOutcome::allow()` to construct this variant
+    #[non_exhaustive]
+    Allow,
+
+    /// Close the connection with the provided transport error
+    ///
+    /// Use `HandshakeOutcome::close()` to construct this variant
+    #[non_exhaustive]
+    Close { error: transport::Error },
+}
+
+impl HandshakeOutcome {
+    /// Allow the handshake to continue
+    pub fn allow() -> Self {
+        Self::Allow
+    }
+
+    /// Close the connection with the provided transport error
+    pub fn close(error: transport::Error) -> Self {
+        Self::Close { error }
+    }
+}
+
+/// A HandshakeAttempt holds information about the state of the endpoint along with the
+/// parameters the peer offered in its ClientHello. This can be used to make decisions about the
+/// HandshakeOutcome of a connection before the handshake completes
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct HandshakeAttempt<'a> {
+    /// The number of connections on the endpoint
+    pub counts: ConnectionCounts,
+
+    /// The address of the connecting peer
+    pub remote_address: SocketAddress<'a>,
+
+    /// The server name the peer indicated, if any
+    pub server_name: Option<&'a str>,
+
+    /// The negotiated application protocol
+    pub application_protocol: &'a [u8],
+    pub timestamp: Timestamp,
+}
+
+impl<'a> HandshakeAttempt<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        counts: ConnectionCounts,
+        remote_address: &'a inet::SocketAddress,
+        server_name: Option<&'a str>,
+        application_protocol: &'a [u8],
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            counts,
+            remote_address: remote_address.into_event(),
+            server_name,
+            application_protocol,
+            timestamp,
+        }
+    }
+}
+
+/// The criteria used to select which connections are evicted from the endpoint
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EvictionCriteria {
+    /// Evict the connections which have gone the longest without receiving a datagram from the
+    /// peer
+    #[default]
+    IdleLongest,
+
+    /// Evict the connections with the lowest average throughput since they were created
+    LowestThroughput,
+
+    /// Evict the connections which were created first
+    Oldest,
+}
+
+/// EvictionOutcome describes how the library should proceed after checking the endpoint's
+/// resource usage. The implementor will use information from the EvictionCheck object to
+/// determine if any connections should be evicted
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EvictionOutcome {
+    /// Keep all of the connections open
+    ///
+    /// Use `EvictionOutcome::none()` to construct this variant
+    #[non_exhaustive]
+    None,
+
+    /// Close `count` connections selected by `criteria` with the provided transport error
+    ///
+    /// Use `EvictionOutcome::evict()` to construct this variant
+    #[non_exhaustive]
+    Evict {
+        count: usize,
+        criteria: EvictionCriteria,
+        error: transport::Error,
+    },
+}
+
+impl EvictionOutcome {
+    /// Keep all of the connections open
+    pub fn none() -> Self {
+        Self::None
+    }
+
+    /// Close `count` connections selected by `criteria` with the [`DEFAULT_EVICTION_ERROR`]
+    pub fn evict(count: usize, criteria: EvictionCriteria) -> Self {
+        Self::Evict {
+            count,
+            criteria,
+            error: DEFAULT_EVICTION_ERROR,
+        }
+    }
+
+    /// Sets the transport error the evicted connections are closed with
+    pub fn with_error(self, error: transport::Error) -> Self {
+        match self {
+            Self::Evict {
+                count, criteria, ..
+            } => Self::Evict {
+                count,
+                criteria,
+                error,
+            },
+            other => other,
+        }
+    }
+}
+
+/// An EvictionCheck holds information about the state of the endpoint. This can be used to
+/// decide if the endpoint is under resource pressure and connections should be evicted
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct EvictionCheck {
+    /// The number of connections on the endpoint
+    pub counts: ConnectionCounts,
+    pub timestamp: Timestamp,
+}
+
+impl EvictionCheck {
+    #[doc(hidden)]
+    pub fn new(counts: ConnectionCounts, timestamp: Timestamp) -> Self {
+        Self { counts, timestamp }
+    }
+}
+
+/// An RxOverflow reports that the platform dropped received datagrams because the endpoint didn't
+/// drain the receive queue fast enough
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct RxOverflow {
+    /// Number of datagrams dropped since the last report
+    pub dropped: usize,
+
+    /// The number of connections on the endpoint
+    pub counts: ConnectionCounts,
+    pub timestamp: Timestamp,
+}
+
+impl RxOverflow {
+    #[doc(hidden)]
+    pub fn new(dropped: usize, counts: ConnectionCounts, timestamp: Timestamp) -> Self {
+        Self {
+            dropped,
+            counts,
+            timestamp,
+        }
+    }
+}
+
+/// OverloadOutcome describes how the endpoint should shed work after its receive queue overflowed
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OverloadOutcome {
+    /// Keep processing all of the work as normal
+    ///
+    /// Use `OverloadOutcome::none()` to construct this variant
+    #[non_exhaustive]
+    None,
+
+    /// Shed non-essential work for `duration`
+    ///
+    /// Use `OverloadOutcome::shed()` to construct this variant
+    #[non_exhaustive]
+    Shed {
+        duration: Duration,
+        skip_events: bool,
+        defer_timers: bool,
+    },
+}
+
+impl OverloadOutcome {
+    /// Keep processing all of the work as normal
+    pub fn none() -> Self {
+        Self::None
+    }
+
+    /// Shed non-essential work for `duration`
+    ///
+    /// By default, the endpoint skips emitting events for dropped datagrams and defers
+    /// non-urgent timers, such as issuing new connection IDs and reconnecting clients. Connection
+    /// timers, like loss detection and idle timeouts, are never deferred.
+    pub fn shed(duration: Duration) -> Self {
+        Self::Shed {
+            duration,
+            skip_events: true,
+            defer_timers: true,
+        }
+    }
+
+    /// Sets if events for dropped datagrams are skipped while shedding work
+    pub fn with_skip_events(self, enabled: bool) -> Self {
+        match self {
+            Self::Shed {
+                duration,
+                defer_timers,
+                ..
+            } => Self::Shed {
+                duration,
+                skip_events: enabled,
+                defer_timers,
+            },
+            other => other,
+        }
+    }
+
+    /// Sets if non-urgent timers are deferred while shedding work
+    pub fn with_defer_timers(self, enabled: bool) -> Self {
+        match self {
+            Self::Shed {
+                duration,
+                skip_events,
+                ..
+            } => Self::Shed {
+                duration,
+                skip_events,
+                defer_timers: enabled,
+            },
+            other => other,
+        }
+    }
+}
+
 pub trait Limiter: 'static + Send {
     /// This trait is used to determine the outcome of connection attempts on an endpoint. The
     /// implementor returns an Outcome based on the ConnectionAttempt, or other information that the
@@ -119,4 +482,174 @@ pub trait Limiter: 'static + Send {
     /// }
     /// ```
     fn on_connection_attempt(&mut self, info: &ConnectionAttempt) -> Outcome;
+
+    /// Called on the server for each Initial packet which would start a new connection, before
+    /// the packet protection is removed or any address validation token is checked
+    ///
+    /// Since no cryptographic operations have been performed, this is the cheapest point at which
+    /// to shed unwanted connection attempts. The same packet is passed to `on_connection_attempt`
+    /// afterwards if it is allowed and doesn't carry a token.
+    ///
+    /// A server can't send a Retry in response to a packet which carries a token from a previous
+    /// Retry. Returning `Outcome::retry()` for a packet with a token will drop it instead.
+    ///
+    /// ```rust
+    /// # mod s2n_quic { pub mod provider { pub mod endpoint_limits { pub use s2n_quic_core::endpoint::limits::*; } } }
+    /// use s2n_quic::provider::endpoint_limits::{ConnectionAttempt, InitialPacket, Limiter, Outcome};
+    ///
+    /// struct MyEndpointLimits {
+    ///    handshake_limit: usize,
+    /// }
+    ///
+    /// impl Limiter for MyEndpointLimits {
+    ///    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+    ///        Outcome::allow()
+    ///    }
+    ///
+    ///    fn on_initial_packet(&mut self, info: &InitialPacket) -> Outcome {
+    ///        if info.counts.inflight_handshakes <= self.handshake_limit {
+    ///            Outcome::allow()
+    ///        } else if info.token.is_empty() {
+    ///            // ask the peer to prove it owns its address before spending any more resources
+    ///            Outcome::retry()
+    ///        } else {
+    ///            Outcome::drop()
+    ///        }
+    ///    }
+    /// }
+    /// ```
+    fn on_initial_packet(&mut self, info: &InitialPacket) -> Outcome {
+        let _ = info;
+        Outcome::allow()
+    }
+
+    /// Called on the server once the peer's ClientHello has been processed and before the
+    /// handshake completes
+    ///
+    /// At this point the server name and application protocol are known, which allows the
+    /// implementor to reject connections based on them. Since the peer's Initial packet has
+    /// already been consumed, a Retry can no longer be sent; use `on_connection_attempt` to defer
+    /// connections with a Retry packet.
+    ///
+    /// ```rust
+    /// # mod s2n_quic { pub mod provider { pub mod endpoint_limits { pub use s2n_quic_core::endpoint::limits::*; } } }
+    /// use s2n_quic::provider::endpoint_limits::{
+    ///     ConnectionAttempt, HandshakeAttempt, HandshakeOutcome, Limiter, Outcome,
+    /// };
+    /// use s2n_quic_core::transport;
+    ///
+    /// struct MyEndpointLimits;
+    ///
+    /// impl Limiter for MyEndpointLimits {
+    ///    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+    ///        Outcome::allow()
+    ///    }
+    ///
+    ///    fn on_handshake_attempt(&mut self, info: &HandshakeAttempt) -> HandshakeOutcome {
+    ///        match info.server_name {
+    ///            Some("localhost") => HandshakeOutcome::allow(),
+    ///            _ => HandshakeOutcome::close(transport::Error::CONNECTION_REFUSED),
+    ///        }
+    ///    }
+    /// }
+    /// ```
+    fn on_handshake_attempt(&mut self, info: &HandshakeAttempt) -> HandshakeOutcome {
+        let _ = info;
+        HandshakeOutcome::allow()
+    }
+
+    /// Called each time the endpoint processes its timers to check if connections should be
+    /// evicted
+    ///
+    /// This allows the implementor to shed connections when resource thresholds (e.g. memory
+    /// usage or open file descriptors) are exceeded, rather than letting a few misbehaving peers
+    /// exhaust the resources of the entire endpoint. Connections which are already closing are
+    /// never selected for eviction and count towards the number of evicted connections.
+    ///
+    /// ```rust
+    /// # mod s2n_quic { pub mod provider { pub mod endpoint_limits { pub use s2n_quic_core::endpoint::limits::*; } } }
+    /// use s2n_quic::provider::endpoint_limits::{
+    ///     ConnectionAttempt, EvictionCheck, EvictionCriteria, EvictionOutcome, Limiter, Outcome,
+    /// };
+    ///
+    /// struct MyEndpointLimits {
+    ///    max_connections: usize,
+    /// }
+    ///
+    /// impl Limiter for MyEndpointLimits {
+    ///    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+    ///        Outcome::allow()
+    ///    }
+    ///
+    ///    fn on_eviction_check(&mut self, info: &EvictionCheck) -> EvictionOutcome {
+    ///        match info.counts.connection_count.checked_sub(self.max_connections) {
+    ///            Some(count) if count > 0 => {
+    ///                EvictionOutcome::evict(count, EvictionCriteria::IdleLongest)
+    ///            }
+    ///            _ => EvictionOutcome::none(),
+    ///        }
+    ///    }
+    /// }
+    /// ```
+    fn on_eviction_check(&mut self, info: &EvictionCheck) -> EvictionOutcome {
+        let _ = info;
+        EvictionOutcome::none()
+    }
+
+    /// Called when the platform reports that received datagrams were dropped because the
+    /// endpoint didn't drain the receive queue fast enough
+    ///
+    /// Falling behind on the receive queue means the endpoint is spending more time processing
+    /// datagrams than it has available. Rather than falling further behind, the implementor can
+    /// choose to shed non-essential work for a period of time. Each report extends or replaces
+    /// the previous outcome.
+    ///
+    /// ```rust
+    /// # mod s2n_quic { pub mod provider { pub mod endpoint_limits { pub use s2n_quic_core::endpoint::limits::*; } } }
+    /// use s2n_quic::provider::endpoint_limits::{
+    ///     ConnectionAttempt, Limiter, Outcome, OverloadOutcome, RxOverflow,
+    /// };
+    /// use core::time::Duration;
+    ///
+    /// struct MyEndpointLimits;
+    ///
+    /// impl Limiter for MyEndpointLimits {
+    ///    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+    ///        Outcome::allow()
+    ///    }
+    ///
+    ///    fn on_rx_overflow(&mut self, info: &RxOverflow) -> OverloadOutcome {
+    ///        if info.dropped > 100 {
+    ///            OverloadOutcome::shed(Duration::from_millis(100))
+    ///        } else {
+    ///            OverloadOutcome::none()
+    ///        }
+    ///    }
+    /// }
+    /// ```
+    fn on_rx_overflow(&mut self, info: &RxOverflow) -> OverloadOutcome {
+        let _ = info;
+        OverloadOutcome::none()
+    }
+
+    /// Returns the granularity of the endpoint's connection timers
+    ///
+    /// The timeouts of all connections on the endpoint are tracked in a single timer wheel, with
+    /// expirations rounded to the configured granularity. A finer granularity results in more
+    /// precise timers at the cost of more frequent wakeups of the endpoint.
+    ///
+    /// This value is only queried when the endpoint is created.
+    fn timer_granularity(&self) -> Duration {
+        DEFAULT_TIMER_GRANULARITY
+    }
+
+    /// Returns the number of connections the endpoint preallocates storage for
+    ///
+    /// Connection storage grows as needed beyond this value. Preallocating storage avoids
+    /// reallocations while the endpoint ramps up to its expected number of concurrent connections.
+    ///
+    /// This value is only queried when the endpoint is created.
+    fn connection_capacity(&self) -> usize {
+        DEFAULT_CONNECTION_CAPACITY
+    }
 }
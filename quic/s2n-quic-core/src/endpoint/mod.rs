@@ -132,6 +132,14 @@ pub trait Endpoint: 'static + Send + Sized {
     /// Sets configuration for the maximum transmission unit (MTU) that can be sent on a path
     fn set_mtu_config(&mut self, mtu_config: mtu::Config);
 
+    /// Sets the maximum number of connection wakeups processed in a single call to `poll_wakeups`
+    ///
+    /// Any wakeups exceeding the limit are deferred to the next call.
+    #[inline]
+    fn set_max_wakeups(&mut self, max_wakeups: usize) {
+        let _ = max_wakeups;
+    }
+
     /// Returns the endpoint's event subscriber
     fn subscriber(&mut self) -> &mut Self::Subscriber;
 }
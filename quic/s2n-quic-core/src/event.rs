@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{connection, endpoint};
-use core::{ops::RangeInclusive, time::Duration};
+use core::{
+    ops::RangeInclusive,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
 
 mod generated;
 pub use generated::*;
@@ -10,6 +14,89 @@ pub use generated::*;
 /// All event types which can be emitted from this library.
 pub trait Event: core::fmt::Debug {
     const NAME: &'static str;
+
+    /// The severity of the event
+    const LEVEL: Level = Level::Debug;
+}
+
+/// The severity of an [`Event`]
+///
+/// Frequently emitted events, such as those for each packet, use the lower levels while events
+/// which indicate a problem use the higher levels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// The rate at which events of a given type are published to a [`Subscriber`]
+///
+/// See [`Subscriber::sample_rate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SampleRate(u32);
+
+impl Default for SampleRate {
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl SampleRate {
+    /// Publishes every event
+    pub const ALL: Self = Self(1);
+
+    /// Doesn't publish any events
+    pub const NONE: Self = Self(0);
+
+    /// Publishes one out of every `n` events
+    ///
+    /// A value of `0` doesn't publish any events.
+    #[inline]
+    pub const fn one_in(n: u32) -> Self {
+        Self(n)
+    }
+
+    /// Publishes every event of type `E` with at least the given severity
+    #[inline]
+    pub fn at_level<E: Event>(level: Level) -> Self {
+        if E::LEVEL >= level {
+            Self::ALL
+        } else {
+            Self::NONE
+        }
+    }
+
+    /// Returns `true` if at least some events are published
+    #[inline]
+    pub const fn is_enabled(self) -> bool {
+        self.0 > 0
+    }
+
+    /// Returns `true` if the current event should be published
+    ///
+    /// `sequence` counts the events of the type being sampled and is only updated for rates
+    /// which publish a subset of the events.
+    #[inline]
+    pub fn sample(self, sequence: &AtomicU32) -> bool {
+        match self.0 {
+            0 => false,
+            1 => true,
+            n => {
+                // Spread the sequence with a golden ratio hash rather than taking the modulus
+                // directly. Sequences are shared by all of the subscribers of a given event type
+                // and this keeps the sampling close to 1 in `n` for each of them, even when their
+                // events are interleaved.
+                let hash = sequence
+                    .fetch_add(1, Ordering::Relaxed)
+                    .wrapping_mul(0x9E37_79B9);
+                (hash as u64 * n as u64) >> 32 == 0
+            }
+        }
+    }
 }
 
 pub trait IntoEvent<Target> {
@@ -165,3 +252,48 @@ impl core::fmt::Debug for TlsSession<'_> {
         f.debug_struct("TlsSession").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_rate_test() {
+        let sequence = AtomicU32::new(0);
+        assert!((0..10).all(|_| SampleRate::ALL.sample(&sequence)));
+        assert!((0..10).all(|_| !SampleRate::NONE.sample(&sequence)));
+        assert_eq!(sequence.load(Ordering::Relaxed), 0);
+
+        for n in [2, 3, 4, 10, 100] {
+            let sampled = (0..10_000)
+                .filter(|_| SampleRate::one_in(n).sample(&sequence))
+                .count();
+            let expected = 10_000 / n as usize;
+            assert!(sampled.abs_diff(expected) <= 2, "{n} {sampled}");
+        }
+    }
+
+    #[test]
+    fn sample_rate_level_test() {
+        assert_eq!(
+            SampleRate::at_level::<api::PacketSent>(Level::Debug),
+            SampleRate::NONE
+        );
+        assert_eq!(
+            SampleRate::at_level::<api::PacketSent>(Level::Trace),
+            SampleRate::ALL
+        );
+        assert_eq!(
+            SampleRate::at_level::<api::ConnectionClosed>(Level::Info),
+            SampleRate::ALL
+        );
+        assert_eq!(
+            SampleRate::at_level::<api::PlatformRxError>(Level::Error),
+            SampleRate::ALL
+        );
+        assert_eq!(
+            SampleRate::at_level::<api::PathCreated>(Level::Warn),
+            SampleRate::NONE
+        );
+    }
+}
@@ -40,6 +40,7 @@ pub mod api {
         pub max_ack_delay: Duration,
         pub max_udp_payload_size: u64,
         pub active_connection_id_limit: u64,
+        pub initial_max_data: u64,
         pub initial_max_stream_data_bidi_local: u64,
         pub initial_max_stream_data_bidi_remote: u64,
         pub initial_max_stream_data_uni: u64,
@@ -47,6 +48,7 @@ pub mod api {
         pub initial_max_streams_uni: u64,
         pub max_datagram_frame_size: u64,
         pub dc_supported_versions: &'a [u32],
+        pub reset_stream_at_support: bool,
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -212,6 +214,17 @@ pub mod api {
         Datagram { len: u16 },
         #[non_exhaustive]
         DcStatelessResetTokens {},
+        #[non_exhaustive]
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
+        #[non_exhaustive]
+        Timestamp { timestamp: u64 },
+        #[non_exhaustive]
+        Custom { frame_type: u64, len: u16 },
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -223,6 +236,86 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " How long a closed connection retains its state before it is dropped"]
+    pub enum ConnectionCloseLinger {
+        #[non_exhaustive]
+        #[doc = " The connection remains in the closing or draining state for three times the probe"]
+        #[doc = " timeout (PTO), as recommended by RFC 9000"]
+        Default {},
+        #[non_exhaustive]
+        #[doc = " The connection remains in the closing state for the duration requested by the application"]
+        Duration { duration: Duration },
+        #[non_exhaustive]
+        #[doc = " The connection was aborted by the application"]
+        #[doc = ""]
+        #[doc = " No CONNECTION_CLOSE frame is sent and the connection state is dropped immediately. The"]
+        #[doc = " peer only learns about the closure through a stateless reset or its idle timeout."]
+        Abort {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    pub enum EvictionCriteria {
+        #[non_exhaustive]
+        #[doc = " The connection had gone the longest without receiving a datagram from the peer"]
+        IdleLongest {},
+        #[non_exhaustive]
+        #[doc = " The connection had the lowest average throughput since it was created"]
+        LowestThroughput {},
+        #[non_exhaustive]
+        #[doc = " The connection was the oldest on the endpoint"]
+        Oldest {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The class a connection is scheduled in when competing with other connections on the endpoint"]
+    pub enum PriorityClass {
+        #[non_exhaustive]
+        #[doc = " Latency sensitive connections which transmit before the other classes"]
+        Control {},
+        #[non_exhaustive]
+        #[doc = " The default class"]
+        Normal {},
+        #[non_exhaustive]
+        #[doc = " Throughput oriented connections which yield to the other classes"]
+        Bulk {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The action a client took after receiving a Version Negotiation packet"]
+    pub enum VersionNegotiationOutcome {
+        #[non_exhaustive]
+        #[doc = " The server doesn't support the version chosen by the client so the connection attempt"]
+        #[doc = " was abandoned"]
+        Abandoned {},
+        #[non_exhaustive]
+        #[doc = " The packet was discarded because it lists the version chosen by the client"]
+        ChosenVersionListed {},
+        #[non_exhaustive]
+        #[doc = " The packet was discarded because the client already processed another packet from the"]
+        #[doc = " server"]
+        AlreadyProcessedPacket {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    pub enum SocketError {
+        #[non_exhaustive]
+        #[doc = " The peer's host reported that nothing is listening on the destination port"]
+        PortUnreachable {},
+        #[non_exhaustive]
+        #[doc = " The peer's host or network could not be reached"]
+        HostUnreachable {},
+        #[non_exhaustive]
+        #[doc = " The datagram exceeded the MTU of a link on the path"]
+        PacketTooBig {
+            #[doc = " The reported MTU, including the IP and UDP headers"]
+            mtu: u16,
+        },
+        #[non_exhaustive]
+        #[doc = " The datagram couldn't be sent from the local address of its path"]
+        LocalAddressUnavailable {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub enum PacketHeader {
         #[non_exhaustive]
         Initial { number: u64, version: u32 },
@@ -291,6 +384,17 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The mechanism by which an address token was delivered to the peer"]
+    pub enum AddressTokenSource {
+        #[non_exhaustive]
+        #[doc = " The token was delivered in a Retry packet"]
+        RetryPacket {},
+        #[non_exhaustive]
+        #[doc = " The token was delivered in a NEW_TOKEN frame"]
+        NewTokenFrame {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub enum DatagramDropReason {
         #[non_exhaustive]
         #[doc = " There was an error while attempting to decode the datagram."]
@@ -339,6 +443,9 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds {},
+        #[non_exhaustive]
+        #[doc = " The maximum number of concurrent path validations was exceeded."]
+        PathValidationLimitExceeded {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -514,6 +621,18 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The outcome of an Encrypted Client Hello offer"]
+    pub enum EchStatus {
+        #[non_exhaustive]
+        #[doc = " The server decrypted the inner ClientHello, so the handshake was authenticated with the"]
+        #[doc = " protected server name"]
+        Accepted {},
+        #[non_exhaustive]
+        #[doc = " The server didn't decrypt the inner ClientHello and the client aborted the handshake"]
+        Rejected {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The source that caused a congestion event"]
     pub enum CongestionSource {
         #[non_exhaustive]
@@ -581,6 +700,49 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " An early packet using the configured InitialMtu was lost"]
         InitialMtuPacketLost {},
+        #[non_exhaustive]
+        #[doc = " The network reported that a packet exceeded the MTU of a link on the path"]
+        PacketTooBig {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The validation state of a path"]
+    pub enum PathValidation {
+        #[non_exhaustive]
+        #[doc = " The path has been validated"]
+        Validated {},
+        #[non_exhaustive]
+        #[doc = " A PATH_CHALLENGE has been sent on the path and is awaiting a response"]
+        Pending {},
+        #[non_exhaustive]
+        #[doc = " The path failed validation"]
+        Failed {},
+        #[non_exhaustive]
+        #[doc = " The path hasn't been validated and no PATH_CHALLENGE is in progress"]
+        NotValidated {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The phase of the congestion controller state machine"]
+    pub enum CongestionPhase {
+        #[non_exhaustive]
+        #[doc = " The congestion window is growing rapidly to find the capacity of the path"]
+        SlowStart {},
+        #[non_exhaustive]
+        #[doc = " The congestion window is growing after slow start has been exited"]
+        CongestionAvoidance {},
+        #[non_exhaustive]
+        #[doc = " The congestion controller is reacting to a congestion event"]
+        Recovery {},
+        #[non_exhaustive]
+        #[doc = " The congestion controller is draining the queue built up during slow start"]
+        Drain {},
+        #[non_exhaustive]
+        #[doc = " The congestion controller is probing for more bandwidth"]
+        ProbeBandwidth {},
+        #[non_exhaustive]
+        #[doc = " The congestion controller is probing for the minimum round trip time"]
+        ProbeRtt {},
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -620,6 +782,7 @@ pub mod api {
     }
     impl<'a> Event for ApplicationProtocolInformation<'a> {
         const NAME: &'static str = "transport:application_protocol_information";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -629,6 +792,19 @@ pub mod api {
     }
     impl<'a> Event for ServerNameInformation<'a> {
         const NAME: &'static str = "transport:server_name_information";
+        const LEVEL: Level = Level::Debug;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The server accepted or rejected the Encrypted Client Hello offered by the client"]
+    #[doc = ""]
+    #[doc = " The event is only emitted by client endpoints which were configured to offer ECH."]
+    pub struct EncryptedClientHello {
+        pub status: EchStatus,
+    }
+    impl Event for EncryptedClientHello {
+        const NAME: &'static str = "security:encrypted_client_hello";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -640,6 +816,7 @@ pub mod api {
     }
     impl Event for PacketSkipped {
         const NAME: &'static str = "transport:packet_skipped";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -650,6 +827,7 @@ pub mod api {
     }
     impl Event for PacketSent {
         const NAME: &'static str = "transport:packet_sent";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -659,6 +837,7 @@ pub mod api {
     }
     impl Event for PacketReceived {
         const NAME: &'static str = "transport:packet_received";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -669,6 +848,7 @@ pub mod api {
     }
     impl<'a> Event for ActivePathUpdated<'a> {
         const NAME: &'static str = "connectivity:active_path_updated";
+        const LEVEL: Level = Level::Info;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -679,6 +859,7 @@ pub mod api {
     }
     impl<'a> Event for PathCreated<'a> {
         const NAME: &'static str = "transport:path_created";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -690,6 +871,7 @@ pub mod api {
     }
     impl Event for FrameSent {
         const NAME: &'static str = "transport:frame_sent";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -701,6 +883,7 @@ pub mod api {
     }
     impl<'a> Event for FrameReceived<'a> {
         const NAME: &'static str = "transport:frame_received";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -713,6 +896,7 @@ pub mod api {
     }
     impl<'a> Event for PacketLost<'a> {
         const NAME: &'static str = "recovery:packet_lost";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -731,6 +915,7 @@ pub mod api {
     }
     impl<'a> Event for RecoveryMetrics<'a> {
         const NAME: &'static str = "recovery:metrics_updated";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -741,6 +926,7 @@ pub mod api {
     }
     impl<'a> Event for Congestion<'a> {
         const NAME: &'static str = "recovery:congestion";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -754,6 +940,7 @@ pub mod api {
     #[allow(deprecated)]
     impl<'a> Event for AckProcessed<'a> {
         const NAME: &'static str = "recovery:ack_processed";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -776,6 +963,7 @@ pub mod api {
     }
     impl<'a> Event for RxAckRangeDropped<'a> {
         const NAME: &'static str = "recovery:rx_ack_range_dropped";
+        const LEVEL: Level = Level::Warn;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -787,6 +975,7 @@ pub mod api {
     }
     impl<'a> Event for AckRangeReceived<'a> {
         const NAME: &'static str = "recovery:ack_range_received";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -798,6 +987,7 @@ pub mod api {
     }
     impl Event for AckRangeSent {
         const NAME: &'static str = "recovery:ack_range_sent";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -807,6 +997,7 @@ pub mod api {
     }
     impl<'a> Event for PacketDropped<'a> {
         const NAME: &'static str = "transport:packet_dropped";
+        const LEVEL: Level = Level::Warn;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -817,6 +1008,7 @@ pub mod api {
     }
     impl Event for KeyUpdate {
         const NAME: &'static str = "security:key_update";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -825,6 +1017,7 @@ pub mod api {
     }
     impl Event for KeySpaceDiscarded {
         const NAME: &'static str = "security:key_space_discarded";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -834,15 +1027,19 @@ pub mod api {
     }
     impl<'a> Event for ConnectionStarted<'a> {
         const NAME: &'static str = "connectivity:connection_started";
+        const LEVEL: Level = Level::Info;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
     #[doc = " Connection closed"]
     pub struct ConnectionClosed {
         pub error: crate::connection::Error,
+        #[doc = " How long the connection retains its state after closing"]
+        pub linger: ConnectionCloseLinger,
     }
     impl Event for ConnectionClosed {
         const NAME: &'static str = "connectivity:connection_closed";
+        const LEVEL: Level = Level::Info;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -854,6 +1051,7 @@ pub mod api {
     }
     impl<'a> Event for DuplicatePacket<'a> {
         const NAME: &'static str = "transport:duplicate_packet";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -863,6 +1061,7 @@ pub mod api {
     }
     impl<'a> Event for TransportParametersReceived<'a> {
         const NAME: &'static str = "transport:transport_parameters_received";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -879,6 +1078,22 @@ pub mod api {
     }
     impl Event for DatagramSent {
         const NAME: &'static str = "transport:datagram_sent";
+        const LEVEL: Level = Level::Trace;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The encrypted payload of a datagram sent by a connection"]
+    #[doc = ""]
+    #[doc = " The payload is emitted after the packet interceptor has been applied so it matches the bytes"]
+    #[doc = " handed off to the IO provider."]
+    pub struct DatagramPayloadSent<'a> {
+        pub local_addr: SocketAddress<'a>,
+        pub remote_addr: SocketAddress<'a>,
+        pub payload: &'a [u8],
+    }
+    impl<'a> Event for DatagramPayloadSent<'a> {
+        const NAME: &'static str = "transport:datagram_payload_sent";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -888,6 +1103,7 @@ pub mod api {
     }
     impl Event for DatagramReceived {
         const NAME: &'static str = "transport:datagram_received";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -898,6 +1114,7 @@ pub mod api {
     }
     impl Event for DatagramDropped {
         const NAME: &'static str = "transport:datagram_dropped";
+        const LEVEL: Level = Level::Warn;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -911,6 +1128,7 @@ pub mod api {
     }
     impl<'a> Event for ConnectionIdUpdated<'a> {
         const NAME: &'static str = "connectivity:connection_id_updated";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -920,6 +1138,7 @@ pub mod api {
     }
     impl<'a> Event for EcnStateChanged<'a> {
         const NAME: &'static str = "recovery:ecn_state_changed";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -928,6 +1147,7 @@ pub mod api {
     }
     impl Event for ConnectionMigrationDenied {
         const NAME: &'static str = "connectivity:connection_migration_denied";
+        const LEVEL: Level = Level::Warn;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -936,6 +1156,38 @@ pub mod api {
     }
     impl Event for HandshakeStatusUpdated {
         const NAME: &'static str = "connectivity:handshake_status_updated";
+        const LEVEL: Level = Level::Info;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " A breakdown of when each step of the handshake happened"]
+    #[doc = ""]
+    #[doc = " The event is emitted once the handshake is confirmed, which happens when the client receives"]
+    #[doc = " the HANDSHAKE_DONE frame or when the server completes the handshake. Each time is relative to"]
+    #[doc = " the creation of the connection."]
+    pub struct HandshakeTiming {
+        #[doc = " The first Initial packet was sent"]
+        pub initial_sent: Option<Duration>,
+        #[doc = " The first Initial packet was received"]
+        pub initial_received: Option<Duration>,
+        #[doc = " The handshake keys became available"]
+        pub handshake_keys_available: Option<Duration>,
+        #[doc = " The TLS handshake completed"]
+        #[doc = ""]
+        #[doc = " The TLS provider verifies the peer's certificate while processing its handshake flight,"]
+        #[doc = " so any time spent on verification falls between `handshake_keys_available` and this"]
+        #[doc = " value."]
+        pub handshake_complete: Duration,
+        #[doc = " The HANDSHAKE_DONE frame was received by the client or queued for sending by the server"]
+        pub handshake_done: Duration,
+        #[doc = " The client's address was validated with a Retry packet"]
+        pub retry: bool,
+        #[doc = " The client received a Version Negotiation packet during the handshake"]
+        pub version_negotiation: bool,
+    }
+    impl Event for HandshakeTiming {
+        const NAME: &'static str = "connectivity:handshake_timing";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -944,6 +1196,7 @@ pub mod api {
     }
     impl<'a> Event for TlsExporterReady<'a> {
         const NAME: &'static str = "connectivity:tls_exporter_ready";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -952,9 +1205,12 @@ pub mod api {
         pub path_challenge_status: PathChallengeStatus,
         pub path: Path<'a>,
         pub challenge_data: &'a [u8],
+        #[doc = " The number of PATH_CHALLENGE frames sent before the challenge was validated or abandoned"]
+        pub challenges_sent: u8,
     }
     impl<'a> Event for PathChallengeUpdated<'a> {
         const NAME: &'static str = "connectivity:path_challenge_updated";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -963,6 +1219,7 @@ pub mod api {
     }
     impl<'a> Event for TlsClientHello<'a> {
         const NAME: &'static str = "tls:client_hello";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -971,6 +1228,7 @@ pub mod api {
     }
     impl<'a> Event for TlsServerHello<'a> {
         const NAME: &'static str = "tls:server_hello";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -979,6 +1237,7 @@ pub mod api {
     }
     impl Event for RxStreamProgress {
         const NAME: &'static str = "transport:rx_stream_progress";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -987,6 +1246,7 @@ pub mod api {
     }
     impl Event for TxStreamProgress {
         const NAME: &'static str = "transport:tx_stream_progress";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -995,6 +1255,7 @@ pub mod api {
     }
     impl Event for KeepAliveTimerExpired {
         const NAME: &'static str = "connectivity::keep_alive_timer_expired";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1007,6 +1268,7 @@ pub mod api {
     }
     impl Event for MtuUpdated {
         const NAME: &'static str = "connectivity:mtu_updated";
+        const LEVEL: Level = Level::Info;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1018,6 +1280,7 @@ pub mod api {
     }
     impl Event for SlowStartExited {
         const NAME: &'static str = "recovery:slow_start_exited";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1030,6 +1293,7 @@ pub mod api {
     }
     impl Event for DeliveryRateSampled {
         const NAME: &'static str = "recovery:delivery_rate_sampled";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1042,6 +1306,57 @@ pub mod api {
     }
     impl Event for PacingRateUpdated {
         const NAME: &'static str = "recovery:pacing_rate_updated";
+        const LEVEL: Level = Level::Trace;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " A periodic snapshot of the congestion controller state"]
+    #[doc = ""]
+    #[doc = " The snapshot is supplied by every congestion controller, so the event has the same shape"]
+    #[doc = " regardless of which controller is selected. It is emitted at the interval configured with"]
+    #[doc = " `Limits::with_congestion_state_interval`."]
+    pub struct CongestionState {
+        pub path_id: u64,
+        pub congestion_window: u32,
+        pub bytes_in_flight: u32,
+        #[doc = " The pacing rate, if the congestion controller paces packets"]
+        pub pacing_rate_bytes_per_second: Option<u64>,
+        pub phase: CongestionPhase,
+        #[doc = " The number of times the congestion controller has entered recovery"]
+        pub loss_epoch: u64,
+    }
+    impl Event for CongestionState {
+        const NAME: &'static str = "recovery:congestion_state";
+        const LEVEL: Level = Level::Debug;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " A periodic snapshot of the statistics of a path"]
+    #[doc = ""]
+    #[doc = " The event is emitted for each of the connection's paths at the interval configured with"]
+    #[doc = " `Limits::with_path_statistics_interval`."]
+    pub struct PathStatistics<'a> {
+        pub path: Path<'a>,
+        pub validation: PathValidation,
+        pub smoothed_rtt: Duration,
+        pub min_rtt: Duration,
+        pub latest_rtt: Duration,
+        pub congestion_window: u32,
+        pub bytes_in_flight: u32,
+        #[doc = " The number of UDP payload bytes sent on the path"]
+        pub bytes_sent: u64,
+        #[doc = " The number of UDP payload bytes received on the path"]
+        pub bytes_received: u64,
+        #[doc = " The number of QUIC packets sent on the path"]
+        pub packets_sent: u64,
+        #[doc = " The number of QUIC packets sent on the path which were declared lost"]
+        pub packets_lost: u64,
+        #[doc = " The number of bytes sent on the path which were declared lost"]
+        pub bytes_lost: u64,
+    }
+    impl<'a> Event for PathStatistics<'a> {
+        const NAME: &'static str = "connectivity:path_statistics";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1052,6 +1367,7 @@ pub mod api {
     }
     impl Event for BbrStateChanged {
         const NAME: &'static str = "recovery:bbr_state_changed";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1061,6 +1377,93 @@ pub mod api {
     }
     impl Event for DcStateChanged {
         const NAME: &'static str = "transport:dc_state_changed";
+        const LEVEL: Level = Level::Debug;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The connection was selected for eviction by the endpoint limiter"]
+    #[doc = ""]
+    #[doc = " This is emitted before the connection is closed with the eviction error."]
+    pub struct ConnectionEvicted {
+        #[doc = " The criteria used to select the connection"]
+        pub criteria: EvictionCriteria,
+        #[doc = " The amount of time since the connection was created"]
+        pub age: Duration,
+        #[doc = " The amount of time since the connection last received a datagram from the peer"]
+        pub idle: Duration,
+        #[doc = " The total number of bytes sent and received by the connection"]
+        pub bytes: u64,
+    }
+    impl Event for ConnectionEvicted {
+        const NAME: &'static str = "connectivity:connection_evicted";
+        const LEVEL: Level = Level::Info;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The network reported an error for a datagram sent on the path"]
+    #[doc = ""]
+    #[doc = " These errors are typically derived from ICMP messages, which are not authenticated."]
+    pub struct SocketErrorReceived {
+        pub path_id: u64,
+        pub error: SocketError,
+    }
+    impl Event for SocketErrorReceived {
+        const NAME: &'static str = "connectivity:socket_error_received";
+        const LEVEL: Level = Level::Warn;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The local address of the path can no longer be used to send datagrams"]
+    #[doc = ""]
+    #[doc = " This typically happens when the interface the address was assigned to goes down, e.g. when"]
+    #[doc = " switching networks. The path stops pinning the address so the operating system can select a"]
+    #[doc = " new one, which the peer observes as a migration. The event is only emitted if the peer"]
+    #[doc = " provided a spare connection ID to use from the new address."]
+    pub struct LocalAddressLost<'a> {
+        #[doc = " The path, including the local address which was lost"]
+        pub path: Path<'a>,
+    }
+    impl<'a> Event for LocalAddressLost<'a> {
+        const NAME: &'static str = "connectivity:local_address_lost";
+        const LEVEL: Level = Level::Warn;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The connection transmitted its share of the endpoint's transmission queue"]
+    #[doc = ""]
+    #[doc = " Connections take turns transmitting in a deficit round robin, so a single connection can't"]
+    #[doc = " consume the entire queue."]
+    pub struct TransmitShare {
+        #[doc = " The number of datagrams transmitted by the connection in the round"]
+        pub datagrams: usize,
+        #[doc = " The number of bytes transmitted by the connection in the round"]
+        pub bytes: usize,
+        #[doc = " The remaining transmission credits, which is negative if the final datagram overshot the"]
+        #[doc = " connection's quantum"]
+        pub deficit: i64,
+        #[doc = " Set to true if the connection exhausted its quantum before it finished transmitting"]
+        pub is_limited: bool,
+        #[doc = " The class the connection was scheduled in"]
+        pub priority_class: PriorityClass,
+    }
+    impl Event for TransmitShare {
+        const NAME: &'static str = "transport:transmit_share";
+        const LEVEL: Level = Level::Trace;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The client received a Version Negotiation packet from the server"]
+    pub struct VersionNegotiationReceived<'a> {
+        #[doc = " The versions supported by the server"]
+        pub server_versions: &'a [u32],
+        #[doc = " The version chosen by the client for the connection attempt"]
+        pub chosen_version: u32,
+        #[doc = " The action taken by the client"]
+        pub outcome: VersionNegotiationOutcome,
+    }
+    impl<'a> Event for VersionNegotiationReceived<'a> {
+        const NAME: &'static str = "transport:version_negotiation_received";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1072,6 +1475,7 @@ pub mod api {
     }
     impl<'a> Event for VersionInformation<'a> {
         const NAME: &'static str = "transport::version_information";
+        const LEVEL: Level = Level::Info;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1081,6 +1485,7 @@ pub mod api {
     }
     impl Event for EndpointPacketSent {
         const NAME: &'static str = "transport:packet_sent";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1090,6 +1495,7 @@ pub mod api {
     }
     impl Event for EndpointPacketReceived {
         const NAME: &'static str = "transport:packet_received";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1106,6 +1512,7 @@ pub mod api {
     }
     impl Event for EndpointDatagramSent {
         const NAME: &'static str = "transport:datagram_sent";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1115,6 +1522,7 @@ pub mod api {
     }
     impl Event for EndpointDatagramReceived {
         const NAME: &'static str = "transport:datagram_received";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1125,6 +1533,24 @@ pub mod api {
     }
     impl Event for EndpointDatagramDropped {
         const NAME: &'static str = "transport:datagram_dropped";
+        const LEVEL: Level = Level::Warn;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " An address token was presented which the endpoint has already accepted"]
+    #[doc = ""]
+    #[doc = " Repeated occurrences may indicate that a peer is probing for token replay."]
+    #[doc = ""]
+    #[doc = " The event is only emitted for tokens which the address token provider reports as reused."]
+    #[doc = " The default provider only issues tokens in Retry packets, so with it the `source` is always"]
+    #[doc = " `RetryPacket`."]
+    pub struct EndpointAddressTokenReused<'a> {
+        pub remote_address: SocketAddress<'a>,
+        pub source: AddressTokenSource,
+    }
+    impl<'a> Event for EndpointAddressTokenReused<'a> {
+        const NAME: &'static str = "transport:address_token_reused";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1133,6 +1559,24 @@ pub mod api {
     }
     impl Event for EndpointConnectionAttemptFailed {
         const NAME: &'static str = "transport:connection_attempt_failed";
+        const LEVEL: Level = Level::Warn;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " A client connection attempt failed and another attempt has been scheduled"]
+    pub struct EndpointConnectionAttemptRetried<'a> {
+        #[doc = " The number of the scheduled attempt, starting at `2` for the first retry"]
+        pub attempt: u32,
+        #[doc = " The address the scheduled attempt will connect to"]
+        pub remote_address: SocketAddress<'a>,
+        #[doc = " The amount of time until the scheduled attempt is made"]
+        pub backoff: Duration,
+        #[doc = " The error which caused the previous attempt to fail"]
+        pub error: crate::connection::Error,
+    }
+    impl<'a> Event for EndpointConnectionAttemptRetried<'a> {
+        const NAME: &'static str = "transport:connection_attempt_retried";
+        const LEVEL: Level = Level::Debug;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1143,6 +1587,7 @@ pub mod api {
     }
     impl Event for PlatformTx {
         const NAME: &'static str = "platform:tx";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1153,6 +1598,7 @@ pub mod api {
     }
     impl Event for PlatformTxError {
         const NAME: &'static str = "platform:tx_error";
+        const LEVEL: Level = Level::Error;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1163,6 +1609,20 @@ pub mod api {
     }
     impl Event for PlatformRx {
         const NAME: &'static str = "platform:rx";
+        const LEVEL: Level = Level::Trace;
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " Emitted when the platform dropped received datagrams because the receive queue overflowed"]
+    #[doc = ""]
+    #[doc = " This indicates the endpoint isn't keeping up with the rate of incoming datagrams."]
+    pub struct PlatformRxOverflow {
+        #[doc = " The number of datagrams dropped since the last event"]
+        pub dropped: usize,
+    }
+    impl Event for PlatformRxOverflow {
+        const NAME: &'static str = "platform:rx_overflow";
+        const LEVEL: Level = Level::Warn;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1173,6 +1633,7 @@ pub mod api {
     }
     impl Event for PlatformRxError {
         const NAME: &'static str = "platform:rx_error";
+        const LEVEL: Level = Level::Error;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1182,6 +1643,7 @@ pub mod api {
     }
     impl Event for PlatformFeatureConfigured {
         const NAME: &'static str = "platform:feature_configured";
+        const LEVEL: Level = Level::Info;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1193,6 +1655,7 @@ pub mod api {
     }
     impl Event for PlatformEventLoopWakeup {
         const NAME: &'static str = "platform:event_loop_wakeup";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1204,6 +1667,7 @@ pub mod api {
     }
     impl Event for PlatformEventLoopSleep {
         const NAME: &'static str = "platform:event_loop_sleep";
+        const LEVEL: Level = Level::Trace;
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -1223,6 +1687,15 @@ pub mod api {
         #[doc = " Emitted when ECN support is configured"]
         Ecn { enabled: bool },
         #[non_exhaustive]
+        #[doc = " Emitted when receiving ICMP errors from the socket error queue is configured"]
+        IcmpErrors { enabled: bool },
+        #[non_exhaustive]
+        #[doc = " Emitted when reporting of datagrams dropped from the receive queue is configured"]
+        RxQueueOverflow { enabled: bool },
+        #[non_exhaustive]
+        #[doc = " Emitted when zero-copy transmission (MSG_ZEROCOPY) is configured"]
+        Zerocopy { enabled: bool },
+        #[non_exhaustive]
         #[doc = " Emitted when the base maximum transmission unit is configured"]
         BaseMtu { mtu: u16 },
         #[non_exhaustive]
@@ -1272,6 +1745,15 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<bool> for &crate::transport::parameters::ResetStreamAtSupport {
+        #[inline]
+        fn into_event(self) -> bool {
+            match self {
+                crate::transport::parameters::ResetStreamAtSupport::Enabled => true,
+                crate::transport::parameters::ResetStreamAtSupport::Disabled => false,
+            }
+        }
+    }
     impl<'a> core::fmt::Debug for ConnectionId<'a> {
         fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
             write!(f, "0x")?;
@@ -1282,7 +1764,7 @@ pub mod api {
         }
     }
     macro_rules! impl_conn_id {
-        ($name:ident) => {
+        ($ name : ident) => {
             impl<'a> IntoEvent<builder::ConnectionId<'a>> for &'a crate::connection::id::$name {
                 #[inline]
                 fn into_event(self) -> builder::ConnectionId<'a> {
@@ -1455,6 +1937,17 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ResetStreamAt {
+                id: self.stream_id.as_u64(),
+                error_code: self.application_error_code.as_u64(),
+                final_size: self.final_size.as_u64(),
+                reliable_size: self.reliable_size.as_u64(),
+            }
+        }
+    }
     impl IntoEvent<builder::Frame> for &crate::frame::StopSending {
         #[inline]
         fn into_event(self) -> builder::Frame {
@@ -1607,6 +2100,73 @@ pub mod api {
             builder::Frame::DcStatelessResetTokens {}
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::Timestamp {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::Timestamp {
+                timestamp: self.timestamp.as_u64(),
+            }
+        }
+    }
+    impl<Data> IntoEvent<builder::Frame> for &crate::frame::Custom<Data>
+    where
+        Data: s2n_codec::EncoderValue,
+    {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::Custom {
+                frame_type: self.frame_type.as_u64(),
+                len: self.data.encoding_size() as _,
+            }
+        }
+    }
+    impl IntoEvent<builder::ConnectionCloseLinger> for crate::connection::close::Linger {
+        #[inline]
+        fn into_event(self) -> builder::ConnectionCloseLinger {
+            use crate::connection::close::Linger;
+            match self {
+                Linger::Default => builder::ConnectionCloseLinger::Default,
+                Linger::Duration(duration) => builder::ConnectionCloseLinger::Duration { duration },
+                Linger::Abort => builder::ConnectionCloseLinger::Abort,
+            }
+        }
+    }
+    impl IntoEvent<builder::EvictionCriteria> for crate::endpoint::limits::EvictionCriteria {
+        #[inline]
+        fn into_event(self) -> builder::EvictionCriteria {
+            use crate::endpoint::limits::EvictionCriteria;
+            match self {
+                EvictionCriteria::IdleLongest => builder::EvictionCriteria::IdleLongest,
+                EvictionCriteria::LowestThroughput => builder::EvictionCriteria::LowestThroughput,
+                EvictionCriteria::Oldest => builder::EvictionCriteria::Oldest,
+            }
+        }
+    }
+    impl IntoEvent<builder::PriorityClass> for crate::connection::PriorityClass {
+        #[inline]
+        fn into_event(self) -> builder::PriorityClass {
+            use crate::connection::PriorityClass;
+            match self {
+                PriorityClass::Control => builder::PriorityClass::Control,
+                PriorityClass::Normal => builder::PriorityClass::Normal,
+                PriorityClass::Bulk => builder::PriorityClass::Bulk,
+            }
+        }
+    }
+    impl IntoEvent<builder::SocketError> for crate::io::rx::SocketError {
+        #[inline]
+        fn into_event(self) -> builder::SocketError {
+            use crate::io::rx::SocketError;
+            match self {
+                SocketError::PortUnreachable => builder::SocketError::PortUnreachable,
+                SocketError::HostUnreachable => builder::SocketError::HostUnreachable,
+                SocketError::PacketTooBig { mtu } => builder::SocketError::PacketTooBig { mtu },
+                SocketError::LocalAddressUnavailable => {
+                    builder::SocketError::LocalAddressUnavailable
+                }
+            }
+        }
+    }
     impl IntoEvent<builder::StreamType> for &crate::stream::StreamType {
         #[inline]
         fn into_event(self) -> builder::StreamType {
@@ -1665,6 +2225,15 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::AddressTokenSource> for crate::token::Source {
+        #[inline]
+        fn into_event(self) -> builder::AddressTokenSource {
+            match self {
+                Self::RetryPacket => builder::AddressTokenSource::RetryPacket,
+                Self::NewTokenFrame => builder::AddressTokenSource::NewTokenFrame,
+            }
+        }
+    }
     impl<'a> IntoEvent<builder::MtuConfig> for &'a crate::path::mtu::Config {
         #[inline]
         fn into_event(self) -> builder::MtuConfig {
@@ -1758,6 +2327,17 @@ pub mod tracing {
             tracing :: event ! (target : "server_name_information" , parent : id , tracing :: Level :: DEBUG , chosen_server_name = tracing :: field :: debug (chosen_server_name));
         }
         #[inline]
+        fn on_encrypted_client_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::EncryptedClientHello,
+        ) {
+            let id = context.id();
+            let api::EncryptedClientHello { status } = event;
+            tracing :: event ! (target : "encrypted_client_hello" , parent : id , tracing :: Level :: DEBUG , status = tracing :: field :: debug (status));
+        }
+        #[inline]
         fn on_packet_skipped(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2011,8 +2591,8 @@ pub mod tracing {
             event: &api::ConnectionClosed,
         ) {
             let id = context.id();
-            let api::ConnectionClosed { error } = event;
-            tracing :: event ! (target : "connection_closed" , parent : id , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error));
+            let api::ConnectionClosed { error, linger } = event;
+            tracing :: event ! (target : "connection_closed" , parent : id , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error) , linger = tracing :: field :: debug (linger));
         }
         #[inline]
         fn on_duplicate_packet(
@@ -2054,6 +2634,21 @@ pub mod tracing {
             tracing :: event ! (target : "datagram_sent" , parent : id , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , gso_offset = tracing :: field :: debug (gso_offset));
         }
         #[inline]
+        fn on_datagram_payload_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::DatagramPayloadSent,
+        ) {
+            let id = context.id();
+            let api::DatagramPayloadSent {
+                local_addr,
+                remote_addr,
+                payload,
+            } = event;
+            tracing :: event ! (target : "datagram_payload_sent" , parent : id , tracing :: Level :: DEBUG , local_addr = tracing :: field :: debug (local_addr) , remote_addr = tracing :: field :: debug (remote_addr) , payload = tracing :: field :: debug (payload));
+        }
+        #[inline]
         fn on_datagram_received(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2125,6 +2720,25 @@ pub mod tracing {
             tracing :: event ! (target : "handshake_status_updated" , parent : id , tracing :: Level :: DEBUG , status = tracing :: field :: debug (status));
         }
         #[inline]
+        fn on_handshake_timing(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::HandshakeTiming,
+        ) {
+            let id = context.id();
+            let api::HandshakeTiming {
+                initial_sent,
+                initial_received,
+                handshake_keys_available,
+                handshake_complete,
+                handshake_done,
+                retry,
+                version_negotiation,
+            } = event;
+            tracing :: event ! (target : "handshake_timing" , parent : id , tracing :: Level :: DEBUG , initial_sent = tracing :: field :: debug (initial_sent) , initial_received = tracing :: field :: debug (initial_received) , handshake_keys_available = tracing :: field :: debug (handshake_keys_available) , handshake_complete = tracing :: field :: debug (handshake_complete) , handshake_done = tracing :: field :: debug (handshake_done) , retry = tracing :: field :: debug (retry) , version_negotiation = tracing :: field :: debug (version_negotiation));
+        }
+        #[inline]
         fn on_tls_exporter_ready(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -2147,8 +2761,9 @@ pub mod tracing {
                 path_challenge_status,
                 path,
                 challenge_data,
+                challenges_sent,
             } = event;
-            tracing :: event ! (target : "path_challenge_updated" , parent : id , tracing :: Level :: DEBUG , path_challenge_status = tracing :: field :: debug (path_challenge_status) , path = tracing :: field :: debug (path) , challenge_data = tracing :: field :: debug (challenge_data));
+            tracing :: event ! (target : "path_challenge_updated" , parent : id , tracing :: Level :: DEBUG , path_challenge_status = tracing :: field :: debug (path_challenge_status) , path = tracing :: field :: debug (path) , challenge_data = tracing :: field :: debug (challenge_data) , challenges_sent = tracing :: field :: debug (challenges_sent));
         }
         #[inline]
         fn on_tls_client_hello(
@@ -2266,29 +2881,141 @@ pub mod tracing {
             tracing :: event ! (target : "pacing_rate_updated" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , bytes_per_second = tracing :: field :: debug (bytes_per_second) , burst_size = tracing :: field :: debug (burst_size) , pacing_gain = tracing :: field :: debug (pacing_gain));
         }
         #[inline]
-        fn on_bbr_state_changed(
+        fn on_congestion_state(
             &mut self,
             context: &mut Self::ConnectionContext,
             _meta: &api::ConnectionMeta,
-            event: &api::BbrStateChanged,
+            event: &api::CongestionState,
         ) {
             let id = context.id();
-            let api::BbrStateChanged { path_id, state } = event;
-            tracing :: event ! (target : "bbr_state_changed" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , state = tracing :: field :: debug (state));
+            let api::CongestionState {
+                path_id,
+                congestion_window,
+                bytes_in_flight,
+                pacing_rate_bytes_per_second,
+                phase,
+                loss_epoch,
+            } = event;
+            tracing :: event ! (target : "congestion_state" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , congestion_window = tracing :: field :: debug (congestion_window) , bytes_in_flight = tracing :: field :: debug (bytes_in_flight) , pacing_rate_bytes_per_second = tracing :: field :: debug (pacing_rate_bytes_per_second) , phase = tracing :: field :: debug (phase) , loss_epoch = tracing :: field :: debug (loss_epoch));
         }
         #[inline]
-        fn on_dc_state_changed(
+        fn on_path_statistics(
             &mut self,
             context: &mut Self::ConnectionContext,
             _meta: &api::ConnectionMeta,
-            event: &api::DcStateChanged,
+            event: &api::PathStatistics,
         ) {
             let id = context.id();
-            let api::DcStateChanged { state } = event;
-            tracing :: event ! (target : "dc_state_changed" , parent : id , tracing :: Level :: DEBUG , state = tracing :: field :: debug (state));
+            let api::PathStatistics {
+                path,
+                validation,
+                smoothed_rtt,
+                min_rtt,
+                latest_rtt,
+                congestion_window,
+                bytes_in_flight,
+                bytes_sent,
+                bytes_received,
+                packets_sent,
+                packets_lost,
+                bytes_lost,
+            } = event;
+            tracing :: event ! (target : "path_statistics" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , validation = tracing :: field :: debug (validation) , smoothed_rtt = tracing :: field :: debug (smoothed_rtt) , min_rtt = tracing :: field :: debug (min_rtt) , latest_rtt = tracing :: field :: debug (latest_rtt) , congestion_window = tracing :: field :: debug (congestion_window) , bytes_in_flight = tracing :: field :: debug (bytes_in_flight) , bytes_sent = tracing :: field :: debug (bytes_sent) , bytes_received = tracing :: field :: debug (bytes_received) , packets_sent = tracing :: field :: debug (packets_sent) , packets_lost = tracing :: field :: debug (packets_lost) , bytes_lost = tracing :: field :: debug (bytes_lost));
         }
         #[inline]
-        fn on_version_information(
+        fn on_bbr_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::BbrStateChanged,
+        ) {
+            let id = context.id();
+            let api::BbrStateChanged { path_id, state } = event;
+            tracing :: event ! (target : "bbr_state_changed" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , state = tracing :: field :: debug (state));
+        }
+        #[inline]
+        fn on_dc_state_changed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::DcStateChanged,
+        ) {
+            let id = context.id();
+            let api::DcStateChanged { state } = event;
+            tracing :: event ! (target : "dc_state_changed" , parent : id , tracing :: Level :: DEBUG , state = tracing :: field :: debug (state));
+        }
+        #[inline]
+        fn on_connection_evicted(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ConnectionEvicted,
+        ) {
+            let id = context.id();
+            let api::ConnectionEvicted {
+                criteria,
+                age,
+                idle,
+                bytes,
+            } = event;
+            tracing :: event ! (target : "connection_evicted" , parent : id , tracing :: Level :: DEBUG , criteria = tracing :: field :: debug (criteria) , age = tracing :: field :: debug (age) , idle = tracing :: field :: debug (idle) , bytes = tracing :: field :: debug (bytes));
+        }
+        #[inline]
+        fn on_socket_error_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::SocketErrorReceived,
+        ) {
+            let id = context.id();
+            let api::SocketErrorReceived { path_id, error } = event;
+            tracing :: event ! (target : "socket_error_received" , parent : id , tracing :: Level :: DEBUG , path_id = tracing :: field :: debug (path_id) , error = tracing :: field :: debug (error));
+        }
+        #[inline]
+        fn on_local_address_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::LocalAddressLost,
+        ) {
+            let id = context.id();
+            let api::LocalAddressLost { path } = event;
+            tracing :: event ! (target : "local_address_lost" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path));
+        }
+        #[inline]
+        fn on_transmit_share(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::TransmitShare,
+        ) {
+            let id = context.id();
+            let api::TransmitShare {
+                datagrams,
+                bytes,
+                deficit,
+                is_limited,
+                priority_class,
+            } = event;
+            tracing :: event ! (target : "transmit_share" , parent : id , tracing :: Level :: DEBUG , datagrams = tracing :: field :: debug (datagrams) , bytes = tracing :: field :: debug (bytes) , deficit = tracing :: field :: debug (deficit) , is_limited = tracing :: field :: debug (is_limited) , priority_class = tracing :: field :: debug (priority_class));
+        }
+        #[inline]
+        fn on_version_negotiation_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::VersionNegotiationReceived,
+        ) {
+            let id = context.id();
+            let api::VersionNegotiationReceived {
+                server_versions,
+                chosen_version,
+                outcome,
+            } = event;
+            tracing :: event ! (target : "version_negotiation_received" , parent : id , tracing :: Level :: DEBUG , server_versions = tracing :: field :: debug (server_versions) , chosen_version = tracing :: field :: debug (chosen_version) , outcome = tracing :: field :: debug (outcome));
+        }
+        #[inline]
+        fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
             event: &api::VersionInformation,
@@ -2370,6 +3097,22 @@ pub mod tracing {
             tracing :: event ! (target : "endpoint_datagram_dropped" , parent : parent , tracing :: Level :: DEBUG , len = tracing :: field :: debug (len) , reason = tracing :: field :: debug (reason));
         }
         #[inline]
+        fn on_endpoint_address_token_reused(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointAddressTokenReused,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::EndpointAddressTokenReused {
+                remote_address,
+                source,
+            } = event;
+            tracing :: event ! (target : "endpoint_address_token_reused" , parent : parent , tracing :: Level :: DEBUG , remote_address = tracing :: field :: debug (remote_address) , source = tracing :: field :: debug (source));
+        }
+        #[inline]
         fn on_endpoint_connection_attempt_failed(
             &mut self,
             meta: &api::EndpointMeta,
@@ -2383,6 +3126,24 @@ pub mod tracing {
             tracing :: event ! (target : "endpoint_connection_attempt_failed" , parent : parent , tracing :: Level :: DEBUG , error = tracing :: field :: debug (error));
         }
         #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptRetried,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::EndpointConnectionAttemptRetried {
+                attempt,
+                remote_address,
+                backoff,
+                error,
+            } = event;
+            tracing :: event ! (target : "endpoint_connection_attempt_retried" , parent : parent , tracing :: Level :: DEBUG , attempt = tracing :: field :: debug (attempt) , remote_address = tracing :: field :: debug (remote_address) , backoff = tracing :: field :: debug (backoff) , error = tracing :: field :: debug (error));
+        }
+        #[inline]
         fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
             let parent = match meta.endpoint_type {
                 api::EndpointType::Client {} => self.client.id(),
@@ -2410,6 +3171,19 @@ pub mod tracing {
             tracing :: event ! (target : "platform_rx" , parent : parent , tracing :: Level :: DEBUG , count = tracing :: field :: debug (count));
         }
         #[inline]
+        fn on_platform_rx_overflow(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformRxOverflow,
+        ) {
+            let parent = match meta.endpoint_type {
+                api::EndpointType::Client {} => self.client.id(),
+                api::EndpointType::Server {} => self.server.id(),
+            };
+            let api::PlatformRxOverflow { dropped } = event;
+            tracing :: event ! (target : "platform_rx_overflow" , parent : parent , tracing :: Level :: DEBUG , dropped = tracing :: field :: debug (dropped));
+        }
+        #[inline]
         fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
             let parent = match meta.endpoint_type {
                 api::EndpointType::Client {} => self.client.id(),
@@ -2530,6 +3304,7 @@ pub mod builder {
         pub max_ack_delay: Duration,
         pub max_udp_payload_size: u64,
         pub active_connection_id_limit: u64,
+        pub initial_max_data: u64,
         pub initial_max_stream_data_bidi_local: u64,
         pub initial_max_stream_data_bidi_remote: u64,
         pub initial_max_stream_data_uni: u64,
@@ -2537,6 +3312,7 @@ pub mod builder {
         pub initial_max_streams_uni: u64,
         pub max_datagram_frame_size: u64,
         pub dc_supported_versions: &'a [u32],
+        pub reset_stream_at_support: bool,
     }
     impl<'a> IntoEvent<api::TransportParameters<'a>> for TransportParameters<'a> {
         #[inline]
@@ -2553,6 +3329,7 @@ pub mod builder {
                 max_ack_delay,
                 max_udp_payload_size,
                 active_connection_id_limit,
+                initial_max_data,
                 initial_max_stream_data_bidi_local,
                 initial_max_stream_data_bidi_remote,
                 initial_max_stream_data_uni,
@@ -2560,6 +3337,7 @@ pub mod builder {
                 initial_max_streams_uni,
                 max_datagram_frame_size,
                 dc_supported_versions,
+                reset_stream_at_support,
             } = self;
             api::TransportParameters {
                 original_destination_connection_id: original_destination_connection_id.into_event(),
@@ -2573,6 +3351,7 @@ pub mod builder {
                 max_ack_delay: max_ack_delay.into_event(),
                 max_udp_payload_size: max_udp_payload_size.into_event(),
                 active_connection_id_limit: active_connection_id_limit.into_event(),
+                initial_max_data: initial_max_data.into_event(),
                 initial_max_stream_data_bidi_local: initial_max_stream_data_bidi_local.into_event(),
                 initial_max_stream_data_bidi_remote: initial_max_stream_data_bidi_remote
                     .into_event(),
@@ -2581,6 +3360,7 @@ pub mod builder {
                 initial_max_streams_uni: initial_max_streams_uni.into_event(),
                 max_datagram_frame_size: max_datagram_frame_size.into_event(),
                 dc_supported_versions: dc_supported_versions.into_event(),
+                reset_stream_at_support: reset_stream_at_support.into_event(),
             }
         }
     }
@@ -2860,6 +3640,19 @@ pub mod builder {
             len: u16,
         },
         DcStatelessResetTokens,
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
+        Timestamp {
+            timestamp: u64,
+        },
+        Custom {
+            frame_type: u64,
+            len: u16,
+        },
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -2955,6 +3748,24 @@ pub mod builder {
                     len: len.into_event(),
                 },
                 Self::DcStatelessResetTokens => DcStatelessResetTokens {},
+                Self::ResetStreamAt {
+                    id,
+                    error_code,
+                    final_size,
+                    reliable_size,
+                } => ResetStreamAt {
+                    id: id.into_event(),
+                    error_code: error_code.into_event(),
+                    final_size: final_size.into_event(),
+                    reliable_size: reliable_size.into_event(),
+                },
+                Self::Timestamp { timestamp } => Timestamp {
+                    timestamp: timestamp.into_event(),
+                },
+                Self::Custom { frame_type, len } => Custom {
+                    frame_type: frame_type.into_event(),
+                    len: len.into_event(),
+                },
             }
         }
     }
@@ -2974,6 +3785,125 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " How long a closed connection retains its state before it is dropped"]
+    pub enum ConnectionCloseLinger {
+        #[doc = " The connection remains in the closing or draining state for three times the probe"]
+        #[doc = " timeout (PTO), as recommended by RFC 9000"]
+        Default,
+        #[doc = " The connection remains in the closing state for the duration requested by the application"]
+        Duration { duration: Duration },
+        #[doc = " The connection was aborted by the application"]
+        #[doc = ""]
+        #[doc = " No CONNECTION_CLOSE frame is sent and the connection state is dropped immediately. The"]
+        #[doc = " peer only learns about the closure through a stateless reset or its idle timeout."]
+        Abort,
+    }
+    impl IntoEvent<api::ConnectionCloseLinger> for ConnectionCloseLinger {
+        #[inline]
+        fn into_event(self) -> api::ConnectionCloseLinger {
+            use api::ConnectionCloseLinger::*;
+            match self {
+                Self::Default => Default {},
+                Self::Duration { duration } => Duration {
+                    duration: duration.into_event(),
+                },
+                Self::Abort => Abort {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub enum EvictionCriteria {
+        #[doc = " The connection had gone the longest without receiving a datagram from the peer"]
+        IdleLongest,
+        #[doc = " The connection had the lowest average throughput since it was created"]
+        LowestThroughput,
+        #[doc = " The connection was the oldest on the endpoint"]
+        Oldest,
+    }
+    impl IntoEvent<api::EvictionCriteria> for EvictionCriteria {
+        #[inline]
+        fn into_event(self) -> api::EvictionCriteria {
+            use api::EvictionCriteria::*;
+            match self {
+                Self::IdleLongest => IdleLongest {},
+                Self::LowestThroughput => LowestThroughput {},
+                Self::Oldest => Oldest {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The class a connection is scheduled in when competing with other connections on the endpoint"]
+    pub enum PriorityClass {
+        #[doc = " Latency sensitive connections which transmit before the other classes"]
+        Control,
+        #[doc = " The default class"]
+        Normal,
+        #[doc = " Throughput oriented connections which yield to the other classes"]
+        Bulk,
+    }
+    impl IntoEvent<api::PriorityClass> for PriorityClass {
+        #[inline]
+        fn into_event(self) -> api::PriorityClass {
+            use api::PriorityClass::*;
+            match self {
+                Self::Control => Control {},
+                Self::Normal => Normal {},
+                Self::Bulk => Bulk {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The action a client took after receiving a Version Negotiation packet"]
+    pub enum VersionNegotiationOutcome {
+        #[doc = " The server doesn't support the version chosen by the client so the connection attempt"]
+        #[doc = " was abandoned"]
+        Abandoned,
+        #[doc = " The packet was discarded because it lists the version chosen by the client"]
+        ChosenVersionListed,
+        #[doc = " The packet was discarded because the client already processed another packet from the"]
+        #[doc = " server"]
+        AlreadyProcessedPacket,
+    }
+    impl IntoEvent<api::VersionNegotiationOutcome> for VersionNegotiationOutcome {
+        #[inline]
+        fn into_event(self) -> api::VersionNegotiationOutcome {
+            use api::VersionNegotiationOutcome::*;
+            match self {
+                Self::Abandoned => Abandoned {},
+                Self::ChosenVersionListed => ChosenVersionListed {},
+                Self::AlreadyProcessedPacket => AlreadyProcessedPacket {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    pub enum SocketError {
+        #[doc = " The peer's host reported that nothing is listening on the destination port"]
+        PortUnreachable,
+        #[doc = " The peer's host or network could not be reached"]
+        HostUnreachable,
+        #[doc = " The datagram exceeded the MTU of a link on the path"]
+        PacketTooBig {
+            #[doc = " The reported MTU, including the IP and UDP headers"]
+            mtu: u16,
+        },
+        #[doc = " The datagram couldn't be sent from the local address of its path"]
+        LocalAddressUnavailable,
+    }
+    impl IntoEvent<api::SocketError> for SocketError {
+        #[inline]
+        fn into_event(self) -> api::SocketError {
+            use api::SocketError::*;
+            match self {
+                Self::PortUnreachable => PortUnreachable {},
+                Self::HostUnreachable => HostUnreachable {},
+                Self::PacketTooBig { mtu } => PacketTooBig {
+                    mtu: mtu.into_event(),
+                },
+                Self::LocalAddressUnavailable => LocalAddressUnavailable {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum PacketHeader {
         Initial { number: u64, version: u32 },
         Handshake { number: u64, version: u32 },
@@ -3097,6 +4027,24 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The mechanism by which an address token was delivered to the peer"]
+    pub enum AddressTokenSource {
+        #[doc = " The token was delivered in a Retry packet"]
+        RetryPacket,
+        #[doc = " The token was delivered in a NEW_TOKEN frame"]
+        NewTokenFrame,
+    }
+    impl IntoEvent<api::AddressTokenSource> for AddressTokenSource {
+        #[inline]
+        fn into_event(self) -> api::AddressTokenSource {
+            use api::AddressTokenSource::*;
+            match self {
+                Self::RetryPacket => RetryPacket {},
+                Self::NewTokenFrame => NewTokenFrame {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum DatagramDropReason {
         #[doc = " There was an error while attempting to decode the datagram."]
         DecodingFailed,
@@ -3132,6 +4080,8 @@ pub mod builder {
         PathLimitExceeded,
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds,
+        #[doc = " The maximum number of concurrent path validations was exceeded."]
+        PathValidationLimitExceeded,
     }
     impl IntoEvent<api::DatagramDropReason> for DatagramDropReason {
         #[inline]
@@ -3155,6 +4105,7 @@ pub mod builder {
                 Self::RejectedConnectionMigration => RejectedConnectionMigration {},
                 Self::PathLimitExceeded => PathLimitExceeded {},
                 Self::InsufficientConnectionIds => InsufficientConnectionIds {},
+                Self::PathValidationLimitExceeded => PathValidationLimitExceeded {},
             }
         }
     }
@@ -3430,6 +4381,25 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The outcome of an Encrypted Client Hello offer"]
+    pub enum EchStatus {
+        #[doc = " The server decrypted the inner ClientHello, so the handshake was authenticated with the"]
+        #[doc = " protected server name"]
+        Accepted,
+        #[doc = " The server didn't decrypt the inner ClientHello and the client aborted the handshake"]
+        Rejected,
+    }
+    impl IntoEvent<api::EchStatus> for EchStatus {
+        #[inline]
+        fn into_event(self) -> api::EchStatus {
+            use api::EchStatus::*;
+            match self {
+                Self::Accepted => Accepted {},
+                Self::Rejected => Rejected {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The source that caused a congestion event"]
     pub enum CongestionSource {
         #[doc = " Explicit Congestion Notification"]
@@ -3521,6 +4491,8 @@ pub mod builder {
         Blackhole,
         #[doc = " An early packet using the configured InitialMtu was lost"]
         InitialMtuPacketLost,
+        #[doc = " The network reported that a packet exceeded the MTU of a link on the path"]
+        PacketTooBig,
     }
     impl IntoEvent<api::MtuUpdatedCause> for MtuUpdatedCause {
         #[inline]
@@ -3531,6 +4503,61 @@ pub mod builder {
                 Self::ProbeAcknowledged => ProbeAcknowledged {},
                 Self::Blackhole => Blackhole {},
                 Self::InitialMtuPacketLost => InitialMtuPacketLost {},
+                Self::PacketTooBig => PacketTooBig {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The validation state of a path"]
+    pub enum PathValidation {
+        #[doc = " The path has been validated"]
+        Validated,
+        #[doc = " A PATH_CHALLENGE has been sent on the path and is awaiting a response"]
+        Pending,
+        #[doc = " The path failed validation"]
+        Failed,
+        #[doc = " The path hasn't been validated and no PATH_CHALLENGE is in progress"]
+        NotValidated,
+    }
+    impl IntoEvent<api::PathValidation> for PathValidation {
+        #[inline]
+        fn into_event(self) -> api::PathValidation {
+            use api::PathValidation::*;
+            match self {
+                Self::Validated => Validated {},
+                Self::Pending => Pending {},
+                Self::Failed => Failed {},
+                Self::NotValidated => NotValidated {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The phase of the congestion controller state machine"]
+    pub enum CongestionPhase {
+        #[doc = " The congestion window is growing rapidly to find the capacity of the path"]
+        SlowStart,
+        #[doc = " The congestion window is growing after slow start has been exited"]
+        CongestionAvoidance,
+        #[doc = " The congestion controller is reacting to a congestion event"]
+        Recovery,
+        #[doc = " The congestion controller is draining the queue built up during slow start"]
+        Drain,
+        #[doc = " The congestion controller is probing for more bandwidth"]
+        ProbeBandwidth,
+        #[doc = " The congestion controller is probing for the minimum round trip time"]
+        ProbeRtt,
+    }
+    impl IntoEvent<api::CongestionPhase> for CongestionPhase {
+        #[inline]
+        fn into_event(self) -> api::CongestionPhase {
+            use api::CongestionPhase::*;
+            match self {
+                Self::SlowStart => SlowStart {},
+                Self::CongestionAvoidance => CongestionAvoidance {},
+                Self::Recovery => Recovery {},
+                Self::Drain => Drain {},
+                Self::ProbeBandwidth => ProbeBandwidth {},
+                Self::ProbeRtt => ProbeRtt {},
             }
         }
     }
@@ -3611,6 +4638,22 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The server accepted or rejected the Encrypted Client Hello offered by the client"]
+    #[doc = ""]
+    #[doc = " The event is only emitted by client endpoints which were configured to offer ECH."]
+    pub struct EncryptedClientHello {
+        pub status: EchStatus,
+    }
+    impl IntoEvent<api::EncryptedClientHello> for EncryptedClientHello {
+        #[inline]
+        fn into_event(self) -> api::EncryptedClientHello {
+            let EncryptedClientHello { status } = self;
+            api::EncryptedClientHello {
+                status: status.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Packet was skipped with a given reason"]
     pub struct PacketSkipped {
         pub number: u64,
@@ -3985,13 +5028,16 @@ pub mod builder {
     #[doc = " Connection closed"]
     pub struct ConnectionClosed {
         pub error: crate::connection::Error,
+        #[doc = " How long the connection retains its state after closing"]
+        pub linger: ConnectionCloseLinger,
     }
     impl IntoEvent<api::ConnectionClosed> for ConnectionClosed {
         #[inline]
         fn into_event(self) -> api::ConnectionClosed {
-            let ConnectionClosed { error } = self;
+            let ConnectionClosed { error, linger } = self;
             api::ConnectionClosed {
                 error: error.into_event(),
+                linger: linger.into_event(),
             }
         }
     }
@@ -4056,6 +5102,31 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The encrypted payload of a datagram sent by a connection"]
+    #[doc = ""]
+    #[doc = " The payload is emitted after the packet interceptor has been applied so it matches the bytes"]
+    #[doc = " handed off to the IO provider."]
+    pub struct DatagramPayloadSent<'a> {
+        pub local_addr: SocketAddress<'a>,
+        pub remote_addr: SocketAddress<'a>,
+        pub payload: &'a [u8],
+    }
+    impl<'a> IntoEvent<api::DatagramPayloadSent<'a>> for DatagramPayloadSent<'a> {
+        #[inline]
+        fn into_event(self) -> api::DatagramPayloadSent<'a> {
+            let DatagramPayloadSent {
+                local_addr,
+                remote_addr,
+                payload,
+            } = self;
+            api::DatagramPayloadSent {
+                local_addr: local_addr.into_event(),
+                remote_addr: remote_addr.into_event(),
+                payload: payload.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Datagram received by a connection"]
     pub struct DatagramReceived {
         pub len: u16,
@@ -4153,6 +5224,55 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A breakdown of when each step of the handshake happened"]
+    #[doc = ""]
+    #[doc = " The event is emitted once the handshake is confirmed, which happens when the client receives"]
+    #[doc = " the HANDSHAKE_DONE frame or when the server completes the handshake. Each time is relative to"]
+    #[doc = " the creation of the connection."]
+    pub struct HandshakeTiming {
+        #[doc = " The first Initial packet was sent"]
+        pub initial_sent: Option<Duration>,
+        #[doc = " The first Initial packet was received"]
+        pub initial_received: Option<Duration>,
+        #[doc = " The handshake keys became available"]
+        pub handshake_keys_available: Option<Duration>,
+        #[doc = " The TLS handshake completed"]
+        #[doc = ""]
+        #[doc = " The TLS provider verifies the peer's certificate while processing its handshake flight,"]
+        #[doc = " so any time spent on verification falls between `handshake_keys_available` and this"]
+        #[doc = " value."]
+        pub handshake_complete: Duration,
+        #[doc = " The HANDSHAKE_DONE frame was received by the client or queued for sending by the server"]
+        pub handshake_done: Duration,
+        #[doc = " The client's address was validated with a Retry packet"]
+        pub retry: bool,
+        #[doc = " The client received a Version Negotiation packet during the handshake"]
+        pub version_negotiation: bool,
+    }
+    impl IntoEvent<api::HandshakeTiming> for HandshakeTiming {
+        #[inline]
+        fn into_event(self) -> api::HandshakeTiming {
+            let HandshakeTiming {
+                initial_sent,
+                initial_received,
+                handshake_keys_available,
+                handshake_complete,
+                handshake_done,
+                retry,
+                version_negotiation,
+            } = self;
+            api::HandshakeTiming {
+                initial_sent: initial_sent.into_event(),
+                initial_received: initial_received.into_event(),
+                handshake_keys_available: handshake_keys_available.into_event(),
+                handshake_complete: handshake_complete.into_event(),
+                handshake_done: handshake_done.into_event(),
+                retry: retry.into_event(),
+                version_negotiation: version_negotiation.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct TlsExporterReady<'a> {
         pub session: crate::event::TlsSession<'a>,
     }
@@ -4171,6 +5291,8 @@ pub mod builder {
         pub path_challenge_status: PathChallengeStatus,
         pub path: Path<'a>,
         pub challenge_data: &'a [u8],
+        #[doc = " The number of PATH_CHALLENGE frames sent before the challenge was validated or abandoned"]
+        pub challenges_sent: u8,
     }
     impl<'a> IntoEvent<api::PathChallengeUpdated<'a>> for PathChallengeUpdated<'a> {
         #[inline]
@@ -4179,11 +5301,13 @@ pub mod builder {
                 path_challenge_status,
                 path,
                 challenge_data,
+                challenges_sent,
             } = self;
             api::PathChallengeUpdated {
                 path_challenge_status: path_challenge_status.into_event(),
                 path: path.into_event(),
                 challenge_data: challenge_data.into_event(),
+                challenges_sent: challenges_sent.into_event(),
             }
         }
     }
@@ -4344,43 +5468,268 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
-    #[doc = " The BBR state has changed"]
-    pub struct BbrStateChanged {
+    #[doc = " A periodic snapshot of the congestion controller state"]
+    #[doc = ""]
+    #[doc = " The snapshot is supplied by every congestion controller, so the event has the same shape"]
+    #[doc = " regardless of which controller is selected. It is emitted at the interval configured with"]
+    #[doc = " `Limits::with_congestion_state_interval`."]
+    pub struct CongestionState {
         pub path_id: u64,
-        pub state: BbrState,
+        pub congestion_window: u32,
+        pub bytes_in_flight: u32,
+        #[doc = " The pacing rate, if the congestion controller paces packets"]
+        pub pacing_rate_bytes_per_second: Option<u64>,
+        pub phase: CongestionPhase,
+        #[doc = " The number of times the congestion controller has entered recovery"]
+        pub loss_epoch: u64,
     }
-    impl IntoEvent<api::BbrStateChanged> for BbrStateChanged {
+    impl IntoEvent<api::CongestionState> for CongestionState {
         #[inline]
-        fn into_event(self) -> api::BbrStateChanged {
-            let BbrStateChanged { path_id, state } = self;
-            api::BbrStateChanged {
+        fn into_event(self) -> api::CongestionState {
+            let CongestionState {
+                path_id,
+                congestion_window,
+                bytes_in_flight,
+                pacing_rate_bytes_per_second,
+                phase,
+                loss_epoch,
+            } = self;
+            api::CongestionState {
                 path_id: path_id.into_event(),
-                state: state.into_event(),
-            }
-        }
-    }
-    #[derive(Clone, Debug)]
-    #[doc = " The DC state has changed"]
-    pub struct DcStateChanged {
-        pub state: DcState,
-    }
-    impl IntoEvent<api::DcStateChanged> for DcStateChanged {
-        #[inline]
-        fn into_event(self) -> api::DcStateChanged {
-            let DcStateChanged { state } = self;
-            api::DcStateChanged {
-                state: state.into_event(),
+                congestion_window: congestion_window.into_event(),
+                bytes_in_flight: bytes_in_flight.into_event(),
+                pacing_rate_bytes_per_second: pacing_rate_bytes_per_second.into_event(),
+                phase: phase.into_event(),
+                loss_epoch: loss_epoch.into_event(),
             }
         }
     }
     #[derive(Clone, Debug)]
-    #[doc = " QUIC version"]
-    pub struct VersionInformation<'a> {
-        pub server_versions: &'a [u32],
-        pub client_versions: &'a [u32],
-        pub chosen_version: Option<u32>,
-    }
-    impl<'a> IntoEvent<api::VersionInformation<'a>> for VersionInformation<'a> {
+    #[doc = " A periodic snapshot of the statistics of a path"]
+    #[doc = ""]
+    #[doc = " The event is emitted for each of the connection's paths at the interval configured with"]
+    #[doc = " `Limits::with_path_statistics_interval`."]
+    pub struct PathStatistics<'a> {
+        pub path: Path<'a>,
+        pub validation: PathValidation,
+        pub smoothed_rtt: Duration,
+        pub min_rtt: Duration,
+        pub latest_rtt: Duration,
+        pub congestion_window: u32,
+        pub bytes_in_flight: u32,
+        #[doc = " The number of UDP payload bytes sent on the path"]
+        pub bytes_sent: u64,
+        #[doc = " The number of UDP payload bytes received on the path"]
+        pub bytes_received: u64,
+        #[doc = " The number of QUIC packets sent on the path"]
+        pub packets_sent: u64,
+        #[doc = " The number of QUIC packets sent on the path which were declared lost"]
+        pub packets_lost: u64,
+        #[doc = " The number of bytes sent on the path which were declared lost"]
+        pub bytes_lost: u64,
+    }
+    impl<'a> IntoEvent<api::PathStatistics<'a>> for PathStatistics<'a> {
+        #[inline]
+        fn into_event(self) -> api::PathStatistics<'a> {
+            let PathStatistics {
+                path,
+                validation,
+                smoothed_rtt,
+                min_rtt,
+                latest_rtt,
+                congestion_window,
+                bytes_in_flight,
+                bytes_sent,
+                bytes_received,
+                packets_sent,
+                packets_lost,
+                bytes_lost,
+            } = self;
+            api::PathStatistics {
+                path: path.into_event(),
+                validation: validation.into_event(),
+                smoothed_rtt: smoothed_rtt.into_event(),
+                min_rtt: min_rtt.into_event(),
+                latest_rtt: latest_rtt.into_event(),
+                congestion_window: congestion_window.into_event(),
+                bytes_in_flight: bytes_in_flight.into_event(),
+                bytes_sent: bytes_sent.into_event(),
+                bytes_received: bytes_received.into_event(),
+                packets_sent: packets_sent.into_event(),
+                packets_lost: packets_lost.into_event(),
+                bytes_lost: bytes_lost.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The BBR state has changed"]
+    pub struct BbrStateChanged {
+        pub path_id: u64,
+        pub state: BbrState,
+    }
+    impl IntoEvent<api::BbrStateChanged> for BbrStateChanged {
+        #[inline]
+        fn into_event(self) -> api::BbrStateChanged {
+            let BbrStateChanged { path_id, state } = self;
+            api::BbrStateChanged {
+                path_id: path_id.into_event(),
+                state: state.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The DC state has changed"]
+    pub struct DcStateChanged {
+        pub state: DcState,
+    }
+    impl IntoEvent<api::DcStateChanged> for DcStateChanged {
+        #[inline]
+        fn into_event(self) -> api::DcStateChanged {
+            let DcStateChanged { state } = self;
+            api::DcStateChanged {
+                state: state.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The connection was selected for eviction by the endpoint limiter"]
+    #[doc = ""]
+    #[doc = " This is emitted before the connection is closed with the eviction error."]
+    pub struct ConnectionEvicted {
+        #[doc = " The criteria used to select the connection"]
+        pub criteria: EvictionCriteria,
+        #[doc = " The amount of time since the connection was created"]
+        pub age: Duration,
+        #[doc = " The amount of time since the connection last received a datagram from the peer"]
+        pub idle: Duration,
+        #[doc = " The total number of bytes sent and received by the connection"]
+        pub bytes: u64,
+    }
+    impl IntoEvent<api::ConnectionEvicted> for ConnectionEvicted {
+        #[inline]
+        fn into_event(self) -> api::ConnectionEvicted {
+            let ConnectionEvicted {
+                criteria,
+                age,
+                idle,
+                bytes,
+            } = self;
+            api::ConnectionEvicted {
+                criteria: criteria.into_event(),
+                age: age.into_event(),
+                idle: idle.into_event(),
+                bytes: bytes.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The network reported an error for a datagram sent on the path"]
+    #[doc = ""]
+    #[doc = " These errors are typically derived from ICMP messages, which are not authenticated."]
+    pub struct SocketErrorReceived {
+        pub path_id: u64,
+        pub error: SocketError,
+    }
+    impl IntoEvent<api::SocketErrorReceived> for SocketErrorReceived {
+        #[inline]
+        fn into_event(self) -> api::SocketErrorReceived {
+            let SocketErrorReceived { path_id, error } = self;
+            api::SocketErrorReceived {
+                path_id: path_id.into_event(),
+                error: error.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The local address of the path can no longer be used to send datagrams"]
+    #[doc = ""]
+    #[doc = " This typically happens when the interface the address was assigned to goes down, e.g. when"]
+    #[doc = " switching networks. The path stops pinning the address so the operating system can select a"]
+    #[doc = " new one, which the peer observes as a migration. The event is only emitted if the peer"]
+    #[doc = " provided a spare connection ID to use from the new address."]
+    pub struct LocalAddressLost<'a> {
+        #[doc = " The path, including the local address which was lost"]
+        pub path: Path<'a>,
+    }
+    impl<'a> IntoEvent<api::LocalAddressLost<'a>> for LocalAddressLost<'a> {
+        #[inline]
+        fn into_event(self) -> api::LocalAddressLost<'a> {
+            let LocalAddressLost { path } = self;
+            api::LocalAddressLost {
+                path: path.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The connection transmitted its share of the endpoint's transmission queue"]
+    #[doc = ""]
+    #[doc = " Connections take turns transmitting in a deficit round robin, so a single connection can't"]
+    #[doc = " consume the entire queue."]
+    pub struct TransmitShare {
+        #[doc = " The number of datagrams transmitted by the connection in the round"]
+        pub datagrams: usize,
+        #[doc = " The number of bytes transmitted by the connection in the round"]
+        pub bytes: usize,
+        #[doc = " The remaining transmission credits, which is negative if the final datagram overshot the"]
+        #[doc = " connection's quantum"]
+        pub deficit: i64,
+        #[doc = " Set to true if the connection exhausted its quantum before it finished transmitting"]
+        pub is_limited: bool,
+        #[doc = " The class the connection was scheduled in"]
+        pub priority_class: PriorityClass,
+    }
+    impl IntoEvent<api::TransmitShare> for TransmitShare {
+        #[inline]
+        fn into_event(self) -> api::TransmitShare {
+            let TransmitShare {
+                datagrams,
+                bytes,
+                deficit,
+                is_limited,
+                priority_class,
+            } = self;
+            api::TransmitShare {
+                datagrams: datagrams.into_event(),
+                bytes: bytes.into_event(),
+                deficit: deficit.into_event(),
+                is_limited: is_limited.into_event(),
+                priority_class: priority_class.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The client received a Version Negotiation packet from the server"]
+    pub struct VersionNegotiationReceived<'a> {
+        #[doc = " The versions supported by the server"]
+        pub server_versions: &'a [u32],
+        #[doc = " The version chosen by the client for the connection attempt"]
+        pub chosen_version: u32,
+        #[doc = " The action taken by the client"]
+        pub outcome: VersionNegotiationOutcome,
+    }
+    impl<'a> IntoEvent<api::VersionNegotiationReceived<'a>> for VersionNegotiationReceived<'a> {
+        #[inline]
+        fn into_event(self) -> api::VersionNegotiationReceived<'a> {
+            let VersionNegotiationReceived {
+                server_versions,
+                chosen_version,
+                outcome,
+            } = self;
+            api::VersionNegotiationReceived {
+                server_versions: server_versions.into_event(),
+                chosen_version: chosen_version.into_event(),
+                outcome: outcome.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " QUIC version"]
+    pub struct VersionInformation<'a> {
+        pub server_versions: &'a [u32],
+        pub client_versions: &'a [u32],
+        pub chosen_version: Option<u32>,
+    }
+    impl<'a> IntoEvent<api::VersionInformation<'a>> for VersionInformation<'a> {
         #[inline]
         fn into_event(self) -> api::VersionInformation<'a> {
             let VersionInformation {
@@ -4476,6 +5825,31 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " An address token was presented which the endpoint has already accepted"]
+    #[doc = ""]
+    #[doc = " Repeated occurrences may indicate that a peer is probing for token replay."]
+    #[doc = ""]
+    #[doc = " The event is only emitted for tokens which the address token provider reports as reused."]
+    #[doc = " The default provider only issues tokens in Retry packets, so with it the `source` is always"]
+    #[doc = " `RetryPacket`."]
+    pub struct EndpointAddressTokenReused<'a> {
+        pub remote_address: SocketAddress<'a>,
+        pub source: AddressTokenSource,
+    }
+    impl<'a> IntoEvent<api::EndpointAddressTokenReused<'a>> for EndpointAddressTokenReused<'a> {
+        #[inline]
+        fn into_event(self) -> api::EndpointAddressTokenReused<'a> {
+            let EndpointAddressTokenReused {
+                remote_address,
+                source,
+            } = self;
+            api::EndpointAddressTokenReused {
+                remote_address: remote_address.into_event(),
+                source: source.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct EndpointConnectionAttemptFailed {
         pub error: crate::connection::Error,
     }
@@ -4489,6 +5863,37 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A client connection attempt failed and another attempt has been scheduled"]
+    pub struct EndpointConnectionAttemptRetried<'a> {
+        #[doc = " The number of the scheduled attempt, starting at `2` for the first retry"]
+        pub attempt: u32,
+        #[doc = " The address the scheduled attempt will connect to"]
+        pub remote_address: SocketAddress<'a>,
+        #[doc = " The amount of time until the scheduled attempt is made"]
+        pub backoff: Duration,
+        #[doc = " The error which caused the previous attempt to fail"]
+        pub error: crate::connection::Error,
+    }
+    impl<'a> IntoEvent<api::EndpointConnectionAttemptRetried<'a>>
+        for EndpointConnectionAttemptRetried<'a>
+    {
+        #[inline]
+        fn into_event(self) -> api::EndpointConnectionAttemptRetried<'a> {
+            let EndpointConnectionAttemptRetried {
+                attempt,
+                remote_address,
+                backoff,
+                error,
+            } = self;
+            api::EndpointConnectionAttemptRetried {
+                attempt: attempt.into_event(),
+                remote_address: remote_address.into_event(),
+                backoff: backoff.into_event(),
+                error: error.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Emitted when the platform sends at least one packet"]
     pub struct PlatformTx {
         #[doc = " The number of packets sent"]
@@ -4534,6 +5939,23 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " Emitted when the platform dropped received datagrams because the receive queue overflowed"]
+    #[doc = ""]
+    #[doc = " This indicates the endpoint isn't keeping up with the rate of incoming datagrams."]
+    pub struct PlatformRxOverflow {
+        #[doc = " The number of datagrams dropped since the last event"]
+        pub dropped: usize,
+    }
+    impl IntoEvent<api::PlatformRxOverflow> for PlatformRxOverflow {
+        #[inline]
+        fn into_event(self) -> api::PlatformRxOverflow {
+            let PlatformRxOverflow { dropped } = self;
+            api::PlatformRxOverflow {
+                dropped: dropped.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Emitted when the platform returns an error while receiving datagrams"]
     pub struct PlatformRxError {
         #[doc = " The error code returned by the platform"]
@@ -4619,6 +6041,12 @@ pub mod builder {
         Gro { enabled: bool },
         #[doc = " Emitted when ECN support is configured"]
         Ecn { enabled: bool },
+        #[doc = " Emitted when receiving ICMP errors from the socket error queue is configured"]
+        IcmpErrors { enabled: bool },
+        #[doc = " Emitted when reporting of datagrams dropped from the receive queue is configured"]
+        RxQueueOverflow { enabled: bool },
+        #[doc = " Emitted when zero-copy transmission (MSG_ZEROCOPY) is configured"]
+        Zerocopy { enabled: bool },
         #[doc = " Emitted when the base maximum transmission unit is configured"]
         BaseMtu { mtu: u16 },
         #[doc = " Emitted when the initial maximum transmission unit is configured"]
@@ -4640,6 +6068,15 @@ pub mod builder {
                 Self::Ecn { enabled } => Ecn {
                     enabled: enabled.into_event(),
                 },
+                Self::IcmpErrors { enabled } => IcmpErrors {
+                    enabled: enabled.into_event(),
+                },
+                Self::RxQueueOverflow { enabled } => RxQueueOverflow {
+                    enabled: enabled.into_event(),
+                },
+                Self::Zerocopy { enabled } => Zerocopy {
+                    enabled: enabled.into_event(),
+                },
                 Self::BaseMtu { mtu } => BaseMtu {
                     mtu: mtu.into_event(),
                 },
@@ -4829,6 +6266,18 @@ mod traits {
         ) -> supervisor::Outcome {
             supervisor::Outcome::default()
         }
+        #[doc = r" The rate at which events of type `E` are published to the subscriber"]
+        #[doc = r""]
+        #[doc = r" The rate is queried before the event is constructed, which makes events"]
+        #[doc = r" that aren't published nearly free. Events can be filtered by severity with"]
+        #[doc = r" [`SampleRate::at_level`] or reduced to a fraction with [`SampleRate::one_in`]."]
+        #[doc = r""]
+        #[doc = r" If multiple `event::Subscriber`s are composed together, each `event::Subscriber`"]
+        #[doc = r" is sampled at its own rate."]
+        #[inline]
+        fn sample_rate<E: Event>(&self) -> SampleRate {
+            SampleRate::ALL
+        }
         #[doc = "Called when the `ApplicationProtocolInformation` event is triggered"]
         #[inline]
         fn on_application_protocol_information(
@@ -4853,6 +6302,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `EncryptedClientHello` event is triggered"]
+        #[inline]
+        fn on_encrypted_client_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &EncryptedClientHello,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `PacketSkipped` event is triggered"]
         #[inline]
         fn on_packet_skipped(
@@ -5119,6 +6580,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `DatagramPayloadSent` event is triggered"]
+        #[inline]
+        fn on_datagram_payload_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramPayloadSent,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `DatagramReceived` event is triggered"]
         #[inline]
         fn on_datagram_received(
@@ -5191,6 +6664,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `HandshakeTiming` event is triggered"]
+        #[inline]
+        fn on_handshake_timing(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeTiming,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `TlsExporterReady` event is triggered"]
         #[inline]
         fn on_tls_exporter_ready(
@@ -5323,6 +6808,30 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `CongestionState` event is triggered"]
+        #[inline]
+        fn on_congestion_state(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &CongestionState,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PathStatistics` event is triggered"]
+        #[inline]
+        fn on_path_statistics(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PathStatistics,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `BbrStateChanged` event is triggered"]
         #[inline]
         fn on_bbr_state_changed(
@@ -5347,6 +6856,66 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `ConnectionEvicted` event is triggered"]
+        #[inline]
+        fn on_connection_evicted(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionEvicted,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `SocketErrorReceived` event is triggered"]
+        #[inline]
+        fn on_socket_error_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SocketErrorReceived,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `LocalAddressLost` event is triggered"]
+        #[inline]
+        fn on_local_address_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &LocalAddressLost,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TransmitShare` event is triggered"]
+        #[inline]
+        fn on_transmit_share(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TransmitShare,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `VersionNegotiationReceived` event is triggered"]
+        #[inline]
+        fn on_version_negotiation_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &VersionNegotiationReceived,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `VersionInformation` event is triggered"]
         #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
@@ -5395,6 +6964,16 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `EndpointAddressTokenReused` event is triggered"]
+        #[inline]
+        fn on_endpoint_address_token_reused(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointAddressTokenReused,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `EndpointConnectionAttemptFailed` event is triggered"]
         #[inline]
         fn on_endpoint_connection_attempt_failed(
@@ -5405,6 +6984,16 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `EndpointConnectionAttemptRetried` event is triggered"]
+        #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRetried,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `PlatformTx` event is triggered"]
         #[inline]
         fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
@@ -5423,7 +7012,13 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
-        #[doc = "Called when the `PlatformRxError` event is triggered"]
+        #[doc = "Called when the `PlatformRxOverflow` event is triggered"]
+        #[inline]
+        fn on_platform_rx_overflow(&mut self, meta: &EndpointMeta, event: &PlatformRxOverflow) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PlatformRxError` event is triggered"]
         #[inline]
         fn on_platform_rx_error(&mut self, meta: &EndpointMeta, event: &PlatformRxError) {
             let _ = meta;
@@ -5557,6 +7152,21 @@ mod traits {
                 _ => supervisor::Outcome::Continue,
             }
         }
+        #[doc = r" Events are published to the tuple at the shared rate of both subscribers. If"]
+        #[doc = r" the rates differ, all events are published to the tuple and each subscriber"]
+        #[doc = r" is sampled separately in the event-specific methods."]
+        #[inline]
+        fn sample_rate<E: Event>(&self) -> SampleRate {
+            let rate_a = self.0.sample_rate::<E>();
+            let rate_b = self.1.sample_rate::<E>();
+            if rate_a == rate_b || !rate_b.is_enabled() {
+                rate_a
+            } else if !rate_a.is_enabled() {
+                rate_b
+            } else {
+                SampleRate::ALL
+            }
+        }
         #[inline]
         fn on_application_protocol_information(
             &mut self,
@@ -5564,8 +7174,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &ApplicationProtocolInformation,
         ) {
-            (self.0).on_application_protocol_information(&mut context.0, meta, event);
-            (self.1).on_application_protocol_information(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ApplicationProtocolInformation>();
+            let rate_a = self.0.sample_rate::<ApplicationProtocolInformation>();
+            if rate_a == rate {
+                (self.0).on_application_protocol_information(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_application_protocol_information(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ApplicationProtocolInformation>();
+            if rate_b == rate {
+                (self.1).on_application_protocol_information(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_application_protocol_information(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_server_name_information(
@@ -5574,8 +7200,50 @@ mod traits {
             meta: &ConnectionMeta,
             event: &ServerNameInformation,
         ) {
-            (self.0).on_server_name_information(&mut context.0, meta, event);
-            (self.1).on_server_name_information(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ServerNameInformation>();
+            let rate_a = self.0.sample_rate::<ServerNameInformation>();
+            if rate_a == rate {
+                (self.0).on_server_name_information(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_server_name_information(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ServerNameInformation>();
+            if rate_b == rate {
+                (self.1).on_server_name_information(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_server_name_information(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_encrypted_client_hello(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &EncryptedClientHello,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EncryptedClientHello>();
+            let rate_a = self.0.sample_rate::<EncryptedClientHello>();
+            if rate_a == rate {
+                (self.0).on_encrypted_client_hello(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_encrypted_client_hello(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EncryptedClientHello>();
+            if rate_b == rate {
+                (self.1).on_encrypted_client_hello(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_encrypted_client_hello(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_packet_skipped(
@@ -5584,8 +7252,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PacketSkipped,
         ) {
-            (self.0).on_packet_skipped(&mut context.0, meta, event);
-            (self.1).on_packet_skipped(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PacketSkipped>();
+            let rate_a = self.0.sample_rate::<PacketSkipped>();
+            if rate_a == rate {
+                (self.0).on_packet_skipped(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_packet_skipped(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PacketSkipped>();
+            if rate_b == rate {
+                (self.1).on_packet_skipped(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_packet_skipped(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_packet_sent(
@@ -5594,8 +7278,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PacketSent,
         ) {
-            (self.0).on_packet_sent(&mut context.0, meta, event);
-            (self.1).on_packet_sent(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PacketSent>();
+            let rate_a = self.0.sample_rate::<PacketSent>();
+            if rate_a == rate {
+                (self.0).on_packet_sent(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_packet_sent(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PacketSent>();
+            if rate_b == rate {
+                (self.1).on_packet_sent(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_packet_sent(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_packet_received(
@@ -5604,8 +7304,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PacketReceived,
         ) {
-            (self.0).on_packet_received(&mut context.0, meta, event);
-            (self.1).on_packet_received(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PacketReceived>();
+            let rate_a = self.0.sample_rate::<PacketReceived>();
+            if rate_a == rate {
+                (self.0).on_packet_received(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_packet_received(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PacketReceived>();
+            if rate_b == rate {
+                (self.1).on_packet_received(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_packet_received(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_active_path_updated(
@@ -5614,8 +7330,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &ActivePathUpdated,
         ) {
-            (self.0).on_active_path_updated(&mut context.0, meta, event);
-            (self.1).on_active_path_updated(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ActivePathUpdated>();
+            let rate_a = self.0.sample_rate::<ActivePathUpdated>();
+            if rate_a == rate {
+                (self.0).on_active_path_updated(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_active_path_updated(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ActivePathUpdated>();
+            if rate_b == rate {
+                (self.1).on_active_path_updated(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_active_path_updated(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_path_created(
@@ -5624,8 +7356,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PathCreated,
         ) {
-            (self.0).on_path_created(&mut context.0, meta, event);
-            (self.1).on_path_created(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PathCreated>();
+            let rate_a = self.0.sample_rate::<PathCreated>();
+            if rate_a == rate {
+                (self.0).on_path_created(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_path_created(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PathCreated>();
+            if rate_b == rate {
+                (self.1).on_path_created(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_path_created(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_frame_sent(
@@ -5634,8 +7382,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &FrameSent,
         ) {
-            (self.0).on_frame_sent(&mut context.0, meta, event);
-            (self.1).on_frame_sent(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<FrameSent>();
+            let rate_a = self.0.sample_rate::<FrameSent>();
+            if rate_a == rate {
+                (self.0).on_frame_sent(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_frame_sent(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<FrameSent>();
+            if rate_b == rate {
+                (self.1).on_frame_sent(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_frame_sent(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_frame_received(
@@ -5644,8 +7408,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &FrameReceived,
         ) {
-            (self.0).on_frame_received(&mut context.0, meta, event);
-            (self.1).on_frame_received(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<FrameReceived>();
+            let rate_a = self.0.sample_rate::<FrameReceived>();
+            if rate_a == rate {
+                (self.0).on_frame_received(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_frame_received(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<FrameReceived>();
+            if rate_b == rate {
+                (self.1).on_frame_received(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_frame_received(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_packet_lost(
@@ -5654,8 +7434,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PacketLost,
         ) {
-            (self.0).on_packet_lost(&mut context.0, meta, event);
-            (self.1).on_packet_lost(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PacketLost>();
+            let rate_a = self.0.sample_rate::<PacketLost>();
+            if rate_a == rate {
+                (self.0).on_packet_lost(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_packet_lost(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PacketLost>();
+            if rate_b == rate {
+                (self.1).on_packet_lost(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_packet_lost(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_recovery_metrics(
@@ -5664,8 +7460,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &RecoveryMetrics,
         ) {
-            (self.0).on_recovery_metrics(&mut context.0, meta, event);
-            (self.1).on_recovery_metrics(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<RecoveryMetrics>();
+            let rate_a = self.0.sample_rate::<RecoveryMetrics>();
+            if rate_a == rate {
+                (self.0).on_recovery_metrics(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_recovery_metrics(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<RecoveryMetrics>();
+            if rate_b == rate {
+                (self.1).on_recovery_metrics(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_recovery_metrics(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_congestion(
@@ -5674,8 +7486,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &Congestion,
         ) {
-            (self.0).on_congestion(&mut context.0, meta, event);
-            (self.1).on_congestion(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<Congestion>();
+            let rate_a = self.0.sample_rate::<Congestion>();
+            if rate_a == rate {
+                (self.0).on_congestion(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_congestion(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<Congestion>();
+            if rate_b == rate {
+                (self.1).on_congestion(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_congestion(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         #[allow(deprecated)]
@@ -5685,8 +7513,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &AckProcessed,
         ) {
-            (self.0).on_ack_processed(&mut context.0, meta, event);
-            (self.1).on_ack_processed(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<AckProcessed>();
+            let rate_a = self.0.sample_rate::<AckProcessed>();
+            if rate_a == rate {
+                (self.0).on_ack_processed(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_ack_processed(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<AckProcessed>();
+            if rate_b == rate {
+                (self.1).on_ack_processed(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_ack_processed(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_rx_ack_range_dropped(
@@ -5695,8 +7539,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &RxAckRangeDropped,
         ) {
-            (self.0).on_rx_ack_range_dropped(&mut context.0, meta, event);
-            (self.1).on_rx_ack_range_dropped(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<RxAckRangeDropped>();
+            let rate_a = self.0.sample_rate::<RxAckRangeDropped>();
+            if rate_a == rate {
+                (self.0).on_rx_ack_range_dropped(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_rx_ack_range_dropped(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<RxAckRangeDropped>();
+            if rate_b == rate {
+                (self.1).on_rx_ack_range_dropped(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_rx_ack_range_dropped(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_ack_range_received(
@@ -5705,8 +7565,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &AckRangeReceived,
         ) {
-            (self.0).on_ack_range_received(&mut context.0, meta, event);
-            (self.1).on_ack_range_received(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<AckRangeReceived>();
+            let rate_a = self.0.sample_rate::<AckRangeReceived>();
+            if rate_a == rate {
+                (self.0).on_ack_range_received(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_ack_range_received(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<AckRangeReceived>();
+            if rate_b == rate {
+                (self.1).on_ack_range_received(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_ack_range_received(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_ack_range_sent(
@@ -5715,8 +7591,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &AckRangeSent,
         ) {
-            (self.0).on_ack_range_sent(&mut context.0, meta, event);
-            (self.1).on_ack_range_sent(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<AckRangeSent>();
+            let rate_a = self.0.sample_rate::<AckRangeSent>();
+            if rate_a == rate {
+                (self.0).on_ack_range_sent(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_ack_range_sent(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<AckRangeSent>();
+            if rate_b == rate {
+                (self.1).on_ack_range_sent(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_ack_range_sent(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_packet_dropped(
@@ -5725,8 +7617,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PacketDropped,
         ) {
-            (self.0).on_packet_dropped(&mut context.0, meta, event);
-            (self.1).on_packet_dropped(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PacketDropped>();
+            let rate_a = self.0.sample_rate::<PacketDropped>();
+            if rate_a == rate {
+                (self.0).on_packet_dropped(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_packet_dropped(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PacketDropped>();
+            if rate_b == rate {
+                (self.1).on_packet_dropped(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_packet_dropped(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_key_update(
@@ -5735,8 +7643,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &KeyUpdate,
         ) {
-            (self.0).on_key_update(&mut context.0, meta, event);
-            (self.1).on_key_update(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<KeyUpdate>();
+            let rate_a = self.0.sample_rate::<KeyUpdate>();
+            if rate_a == rate {
+                (self.0).on_key_update(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_key_update(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<KeyUpdate>();
+            if rate_b == rate {
+                (self.1).on_key_update(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_key_update(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_key_space_discarded(
@@ -5745,8 +7669,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &KeySpaceDiscarded,
         ) {
-            (self.0).on_key_space_discarded(&mut context.0, meta, event);
-            (self.1).on_key_space_discarded(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<KeySpaceDiscarded>();
+            let rate_a = self.0.sample_rate::<KeySpaceDiscarded>();
+            if rate_a == rate {
+                (self.0).on_key_space_discarded(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_key_space_discarded(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<KeySpaceDiscarded>();
+            if rate_b == rate {
+                (self.1).on_key_space_discarded(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_key_space_discarded(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_connection_started(
@@ -5755,8 +7695,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &ConnectionStarted,
         ) {
-            (self.0).on_connection_started(&mut context.0, meta, event);
-            (self.1).on_connection_started(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ConnectionStarted>();
+            let rate_a = self.0.sample_rate::<ConnectionStarted>();
+            if rate_a == rate {
+                (self.0).on_connection_started(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_connection_started(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ConnectionStarted>();
+            if rate_b == rate {
+                (self.1).on_connection_started(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_connection_started(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_connection_closed(
@@ -5765,8 +7721,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &ConnectionClosed,
         ) {
-            (self.0).on_connection_closed(&mut context.0, meta, event);
-            (self.1).on_connection_closed(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ConnectionClosed>();
+            let rate_a = self.0.sample_rate::<ConnectionClosed>();
+            if rate_a == rate {
+                (self.0).on_connection_closed(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_connection_closed(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ConnectionClosed>();
+            if rate_b == rate {
+                (self.1).on_connection_closed(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_connection_closed(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_duplicate_packet(
@@ -5775,8 +7747,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &DuplicatePacket,
         ) {
-            (self.0).on_duplicate_packet(&mut context.0, meta, event);
-            (self.1).on_duplicate_packet(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<DuplicatePacket>();
+            let rate_a = self.0.sample_rate::<DuplicatePacket>();
+            if rate_a == rate {
+                (self.0).on_duplicate_packet(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_duplicate_packet(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<DuplicatePacket>();
+            if rate_b == rate {
+                (self.1).on_duplicate_packet(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_duplicate_packet(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_transport_parameters_received(
@@ -5785,8 +7773,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &TransportParametersReceived,
         ) {
-            (self.0).on_transport_parameters_received(&mut context.0, meta, event);
-            (self.1).on_transport_parameters_received(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<TransportParametersReceived>();
+            let rate_a = self.0.sample_rate::<TransportParametersReceived>();
+            if rate_a == rate {
+                (self.0).on_transport_parameters_received(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_transport_parameters_received(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<TransportParametersReceived>();
+            if rate_b == rate {
+                (self.1).on_transport_parameters_received(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_transport_parameters_received(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_datagram_sent(
@@ -5795,8 +7799,50 @@ mod traits {
             meta: &ConnectionMeta,
             event: &DatagramSent,
         ) {
-            (self.0).on_datagram_sent(&mut context.0, meta, event);
-            (self.1).on_datagram_sent(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<DatagramSent>();
+            let rate_a = self.0.sample_rate::<DatagramSent>();
+            if rate_a == rate {
+                (self.0).on_datagram_sent(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_datagram_sent(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<DatagramSent>();
+            if rate_b == rate {
+                (self.1).on_datagram_sent(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_datagram_sent(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_datagram_payload_sent(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &DatagramPayloadSent,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<DatagramPayloadSent>();
+            let rate_a = self.0.sample_rate::<DatagramPayloadSent>();
+            if rate_a == rate {
+                (self.0).on_datagram_payload_sent(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_datagram_payload_sent(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<DatagramPayloadSent>();
+            if rate_b == rate {
+                (self.1).on_datagram_payload_sent(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_datagram_payload_sent(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_datagram_received(
@@ -5805,8 +7851,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &DatagramReceived,
         ) {
-            (self.0).on_datagram_received(&mut context.0, meta, event);
-            (self.1).on_datagram_received(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<DatagramReceived>();
+            let rate_a = self.0.sample_rate::<DatagramReceived>();
+            if rate_a == rate {
+                (self.0).on_datagram_received(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_datagram_received(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<DatagramReceived>();
+            if rate_b == rate {
+                (self.1).on_datagram_received(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_datagram_received(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_datagram_dropped(
@@ -5815,8 +7877,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &DatagramDropped,
         ) {
-            (self.0).on_datagram_dropped(&mut context.0, meta, event);
-            (self.1).on_datagram_dropped(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<DatagramDropped>();
+            let rate_a = self.0.sample_rate::<DatagramDropped>();
+            if rate_a == rate {
+                (self.0).on_datagram_dropped(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_datagram_dropped(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<DatagramDropped>();
+            if rate_b == rate {
+                (self.1).on_datagram_dropped(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_datagram_dropped(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_connection_id_updated(
@@ -5825,8 +7903,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &ConnectionIdUpdated,
         ) {
-            (self.0).on_connection_id_updated(&mut context.0, meta, event);
-            (self.1).on_connection_id_updated(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ConnectionIdUpdated>();
+            let rate_a = self.0.sample_rate::<ConnectionIdUpdated>();
+            if rate_a == rate {
+                (self.0).on_connection_id_updated(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_connection_id_updated(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ConnectionIdUpdated>();
+            if rate_b == rate {
+                (self.1).on_connection_id_updated(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_connection_id_updated(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_ecn_state_changed(
@@ -5835,8 +7929,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &EcnStateChanged,
         ) {
-            (self.0).on_ecn_state_changed(&mut context.0, meta, event);
-            (self.1).on_ecn_state_changed(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EcnStateChanged>();
+            let rate_a = self.0.sample_rate::<EcnStateChanged>();
+            if rate_a == rate {
+                (self.0).on_ecn_state_changed(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_ecn_state_changed(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EcnStateChanged>();
+            if rate_b == rate {
+                (self.1).on_ecn_state_changed(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_ecn_state_changed(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_connection_migration_denied(
@@ -5845,8 +7955,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &ConnectionMigrationDenied,
         ) {
-            (self.0).on_connection_migration_denied(&mut context.0, meta, event);
-            (self.1).on_connection_migration_denied(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ConnectionMigrationDenied>();
+            let rate_a = self.0.sample_rate::<ConnectionMigrationDenied>();
+            if rate_a == rate {
+                (self.0).on_connection_migration_denied(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_connection_migration_denied(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ConnectionMigrationDenied>();
+            if rate_b == rate {
+                (self.1).on_connection_migration_denied(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_connection_migration_denied(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_handshake_status_updated(
@@ -5855,8 +7981,50 @@ mod traits {
             meta: &ConnectionMeta,
             event: &HandshakeStatusUpdated,
         ) {
-            (self.0).on_handshake_status_updated(&mut context.0, meta, event);
-            (self.1).on_handshake_status_updated(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<HandshakeStatusUpdated>();
+            let rate_a = self.0.sample_rate::<HandshakeStatusUpdated>();
+            if rate_a == rate {
+                (self.0).on_handshake_status_updated(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_handshake_status_updated(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<HandshakeStatusUpdated>();
+            if rate_b == rate {
+                (self.1).on_handshake_status_updated(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_handshake_status_updated(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_handshake_timing(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &HandshakeTiming,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<HandshakeTiming>();
+            let rate_a = self.0.sample_rate::<HandshakeTiming>();
+            if rate_a == rate {
+                (self.0).on_handshake_timing(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_handshake_timing(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<HandshakeTiming>();
+            if rate_b == rate {
+                (self.1).on_handshake_timing(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_handshake_timing(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_tls_exporter_ready(
@@ -5865,8 +8033,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &TlsExporterReady,
         ) {
-            (self.0).on_tls_exporter_ready(&mut context.0, meta, event);
-            (self.1).on_tls_exporter_ready(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<TlsExporterReady>();
+            let rate_a = self.0.sample_rate::<TlsExporterReady>();
+            if rate_a == rate {
+                (self.0).on_tls_exporter_ready(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_tls_exporter_ready(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<TlsExporterReady>();
+            if rate_b == rate {
+                (self.1).on_tls_exporter_ready(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_tls_exporter_ready(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_path_challenge_updated(
@@ -5875,8 +8059,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PathChallengeUpdated,
         ) {
-            (self.0).on_path_challenge_updated(&mut context.0, meta, event);
-            (self.1).on_path_challenge_updated(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PathChallengeUpdated>();
+            let rate_a = self.0.sample_rate::<PathChallengeUpdated>();
+            if rate_a == rate {
+                (self.0).on_path_challenge_updated(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_path_challenge_updated(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PathChallengeUpdated>();
+            if rate_b == rate {
+                (self.1).on_path_challenge_updated(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_path_challenge_updated(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_tls_client_hello(
@@ -5885,8 +8085,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &TlsClientHello,
         ) {
-            (self.0).on_tls_client_hello(&mut context.0, meta, event);
-            (self.1).on_tls_client_hello(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<TlsClientHello>();
+            let rate_a = self.0.sample_rate::<TlsClientHello>();
+            if rate_a == rate {
+                (self.0).on_tls_client_hello(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_tls_client_hello(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<TlsClientHello>();
+            if rate_b == rate {
+                (self.1).on_tls_client_hello(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_tls_client_hello(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_tls_server_hello(
@@ -5895,8 +8111,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &TlsServerHello,
         ) {
-            (self.0).on_tls_server_hello(&mut context.0, meta, event);
-            (self.1).on_tls_server_hello(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<TlsServerHello>();
+            let rate_a = self.0.sample_rate::<TlsServerHello>();
+            if rate_a == rate {
+                (self.0).on_tls_server_hello(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_tls_server_hello(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<TlsServerHello>();
+            if rate_b == rate {
+                (self.1).on_tls_server_hello(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_tls_server_hello(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_rx_stream_progress(
@@ -5905,8 +8137,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &RxStreamProgress,
         ) {
-            (self.0).on_rx_stream_progress(&mut context.0, meta, event);
-            (self.1).on_rx_stream_progress(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<RxStreamProgress>();
+            let rate_a = self.0.sample_rate::<RxStreamProgress>();
+            if rate_a == rate {
+                (self.0).on_rx_stream_progress(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_rx_stream_progress(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<RxStreamProgress>();
+            if rate_b == rate {
+                (self.1).on_rx_stream_progress(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_rx_stream_progress(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_tx_stream_progress(
@@ -5915,8 +8163,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &TxStreamProgress,
         ) {
-            (self.0).on_tx_stream_progress(&mut context.0, meta, event);
-            (self.1).on_tx_stream_progress(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<TxStreamProgress>();
+            let rate_a = self.0.sample_rate::<TxStreamProgress>();
+            if rate_a == rate {
+                (self.0).on_tx_stream_progress(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_tx_stream_progress(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<TxStreamProgress>();
+            if rate_b == rate {
+                (self.1).on_tx_stream_progress(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_tx_stream_progress(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_keep_alive_timer_expired(
@@ -5925,8 +8189,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &KeepAliveTimerExpired,
         ) {
-            (self.0).on_keep_alive_timer_expired(&mut context.0, meta, event);
-            (self.1).on_keep_alive_timer_expired(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<KeepAliveTimerExpired>();
+            let rate_a = self.0.sample_rate::<KeepAliveTimerExpired>();
+            if rate_a == rate {
+                (self.0).on_keep_alive_timer_expired(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_keep_alive_timer_expired(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<KeepAliveTimerExpired>();
+            if rate_b == rate {
+                (self.1).on_keep_alive_timer_expired(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_keep_alive_timer_expired(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_mtu_updated(
@@ -5935,8 +8215,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &MtuUpdated,
         ) {
-            (self.0).on_mtu_updated(&mut context.0, meta, event);
-            (self.1).on_mtu_updated(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<MtuUpdated>();
+            let rate_a = self.0.sample_rate::<MtuUpdated>();
+            if rate_a == rate {
+                (self.0).on_mtu_updated(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_mtu_updated(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<MtuUpdated>();
+            if rate_b == rate {
+                (self.1).on_mtu_updated(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_mtu_updated(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_slow_start_exited(
@@ -5945,8 +8241,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &SlowStartExited,
         ) {
-            (self.0).on_slow_start_exited(&mut context.0, meta, event);
-            (self.1).on_slow_start_exited(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<SlowStartExited>();
+            let rate_a = self.0.sample_rate::<SlowStartExited>();
+            if rate_a == rate {
+                (self.0).on_slow_start_exited(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_slow_start_exited(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<SlowStartExited>();
+            if rate_b == rate {
+                (self.1).on_slow_start_exited(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_slow_start_exited(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_delivery_rate_sampled(
@@ -5955,8 +8267,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &DeliveryRateSampled,
         ) {
-            (self.0).on_delivery_rate_sampled(&mut context.0, meta, event);
-            (self.1).on_delivery_rate_sampled(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<DeliveryRateSampled>();
+            let rate_a = self.0.sample_rate::<DeliveryRateSampled>();
+            if rate_a == rate {
+                (self.0).on_delivery_rate_sampled(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_delivery_rate_sampled(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<DeliveryRateSampled>();
+            if rate_b == rate {
+                (self.1).on_delivery_rate_sampled(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_delivery_rate_sampled(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_pacing_rate_updated(
@@ -5965,8 +8293,76 @@ mod traits {
             meta: &ConnectionMeta,
             event: &PacingRateUpdated,
         ) {
-            (self.0).on_pacing_rate_updated(&mut context.0, meta, event);
-            (self.1).on_pacing_rate_updated(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PacingRateUpdated>();
+            let rate_a = self.0.sample_rate::<PacingRateUpdated>();
+            if rate_a == rate {
+                (self.0).on_pacing_rate_updated(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_pacing_rate_updated(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PacingRateUpdated>();
+            if rate_b == rate {
+                (self.1).on_pacing_rate_updated(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_pacing_rate_updated(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_congestion_state(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &CongestionState,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<CongestionState>();
+            let rate_a = self.0.sample_rate::<CongestionState>();
+            if rate_a == rate {
+                (self.0).on_congestion_state(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_congestion_state(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<CongestionState>();
+            if rate_b == rate {
+                (self.1).on_congestion_state(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_congestion_state(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_path_statistics(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PathStatistics,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PathStatistics>();
+            let rate_a = self.0.sample_rate::<PathStatistics>();
+            if rate_a == rate {
+                (self.0).on_path_statistics(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_path_statistics(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PathStatistics>();
+            if rate_b == rate {
+                (self.1).on_path_statistics(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_path_statistics(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_bbr_state_changed(
@@ -5975,8 +8371,24 @@ mod traits {
             meta: &ConnectionMeta,
             event: &BbrStateChanged,
         ) {
-            (self.0).on_bbr_state_changed(&mut context.0, meta, event);
-            (self.1).on_bbr_state_changed(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<BbrStateChanged>();
+            let rate_a = self.0.sample_rate::<BbrStateChanged>();
+            if rate_a == rate {
+                (self.0).on_bbr_state_changed(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_bbr_state_changed(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<BbrStateChanged>();
+            if rate_b == rate {
+                (self.1).on_bbr_state_changed(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_bbr_state_changed(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_dc_state_changed(
@@ -5985,18 +8397,192 @@ mod traits {
             meta: &ConnectionMeta,
             event: &DcStateChanged,
         ) {
-            (self.0).on_dc_state_changed(&mut context.0, meta, event);
-            (self.1).on_dc_state_changed(&mut context.1, meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<DcStateChanged>();
+            let rate_a = self.0.sample_rate::<DcStateChanged>();
+            if rate_a == rate {
+                (self.0).on_dc_state_changed(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_dc_state_changed(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<DcStateChanged>();
+            if rate_b == rate {
+                (self.1).on_dc_state_changed(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_dc_state_changed(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_connection_evicted(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ConnectionEvicted,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<ConnectionEvicted>();
+            let rate_a = self.0.sample_rate::<ConnectionEvicted>();
+            if rate_a == rate {
+                (self.0).on_connection_evicted(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_connection_evicted(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<ConnectionEvicted>();
+            if rate_b == rate {
+                (self.1).on_connection_evicted(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_connection_evicted(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_socket_error_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &SocketErrorReceived,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<SocketErrorReceived>();
+            let rate_a = self.0.sample_rate::<SocketErrorReceived>();
+            if rate_a == rate {
+                (self.0).on_socket_error_received(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_socket_error_received(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<SocketErrorReceived>();
+            if rate_b == rate {
+                (self.1).on_socket_error_received(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_socket_error_received(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_local_address_lost(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &LocalAddressLost,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<LocalAddressLost>();
+            let rate_a = self.0.sample_rate::<LocalAddressLost>();
+            if rate_a == rate {
+                (self.0).on_local_address_lost(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_local_address_lost(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<LocalAddressLost>();
+            if rate_b == rate {
+                (self.1).on_local_address_lost(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_local_address_lost(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_transmit_share(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TransmitShare,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<TransmitShare>();
+            let rate_a = self.0.sample_rate::<TransmitShare>();
+            if rate_a == rate {
+                (self.0).on_transmit_share(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_transmit_share(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<TransmitShare>();
+            if rate_b == rate {
+                (self.1).on_transmit_share(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_transmit_share(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_version_negotiation_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &VersionNegotiationReceived,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<VersionNegotiationReceived>();
+            let rate_a = self.0.sample_rate::<VersionNegotiationReceived>();
+            if rate_a == rate {
+                (self.0).on_version_negotiation_received(&mut context.0, meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_version_negotiation_received(&mut context.0, meta, event);
+                (self.0).on_connection_event(&mut context.0, meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<VersionNegotiationReceived>();
+            if rate_b == rate {
+                (self.1).on_version_negotiation_received(&mut context.1, meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_version_negotiation_received(&mut context.1, meta, event);
+                (self.1).on_connection_event(&mut context.1, meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
-            (self.0).on_version_information(meta, event);
-            (self.1).on_version_information(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<VersionInformation>();
+            let rate_a = self.0.sample_rate::<VersionInformation>();
+            if rate_a == rate {
+                (self.0).on_version_information(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_version_information(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<VersionInformation>();
+            if rate_b == rate {
+                (self.1).on_version_information(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_version_information(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_endpoint_packet_sent(&mut self, meta: &EndpointMeta, event: &EndpointPacketSent) {
-            (self.0).on_endpoint_packet_sent(meta, event);
-            (self.1).on_endpoint_packet_sent(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointPacketSent>();
+            let rate_a = self.0.sample_rate::<EndpointPacketSent>();
+            if rate_a == rate {
+                (self.0).on_endpoint_packet_sent(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_packet_sent(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointPacketSent>();
+            if rate_b == rate {
+                (self.1).on_endpoint_packet_sent(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_packet_sent(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_endpoint_packet_received(
@@ -6004,13 +8590,41 @@ mod traits {
             meta: &EndpointMeta,
             event: &EndpointPacketReceived,
         ) {
-            (self.0).on_endpoint_packet_received(meta, event);
-            (self.1).on_endpoint_packet_received(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointPacketReceived>();
+            let rate_a = self.0.sample_rate::<EndpointPacketReceived>();
+            if rate_a == rate {
+                (self.0).on_endpoint_packet_received(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_packet_received(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointPacketReceived>();
+            if rate_b == rate {
+                (self.1).on_endpoint_packet_received(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_packet_received(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_endpoint_datagram_sent(&mut self, meta: &EndpointMeta, event: &EndpointDatagramSent) {
-            (self.0).on_endpoint_datagram_sent(meta, event);
-            (self.1).on_endpoint_datagram_sent(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointDatagramSent>();
+            let rate_a = self.0.sample_rate::<EndpointDatagramSent>();
+            if rate_a == rate {
+                (self.0).on_endpoint_datagram_sent(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_datagram_sent(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointDatagramSent>();
+            if rate_b == rate {
+                (self.1).on_endpoint_datagram_sent(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_datagram_sent(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_endpoint_datagram_received(
@@ -6018,8 +8632,22 @@ mod traits {
             meta: &EndpointMeta,
             event: &EndpointDatagramReceived,
         ) {
-            (self.0).on_endpoint_datagram_received(meta, event);
-            (self.1).on_endpoint_datagram_received(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointDatagramReceived>();
+            let rate_a = self.0.sample_rate::<EndpointDatagramReceived>();
+            if rate_a == rate {
+                (self.0).on_endpoint_datagram_received(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_datagram_received(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointDatagramReceived>();
+            if rate_b == rate {
+                (self.1).on_endpoint_datagram_received(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_datagram_received(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_endpoint_datagram_dropped(
@@ -6027,8 +8655,45 @@ mod traits {
             meta: &EndpointMeta,
             event: &EndpointDatagramDropped,
         ) {
-            (self.0).on_endpoint_datagram_dropped(meta, event);
-            (self.1).on_endpoint_datagram_dropped(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointDatagramDropped>();
+            let rate_a = self.0.sample_rate::<EndpointDatagramDropped>();
+            if rate_a == rate {
+                (self.0).on_endpoint_datagram_dropped(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_datagram_dropped(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointDatagramDropped>();
+            if rate_b == rate {
+                (self.1).on_endpoint_datagram_dropped(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_datagram_dropped(meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_address_token_reused(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointAddressTokenReused,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointAddressTokenReused>();
+            let rate_a = self.0.sample_rate::<EndpointAddressTokenReused>();
+            if rate_a == rate {
+                (self.0).on_endpoint_address_token_reused(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_address_token_reused(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointAddressTokenReused>();
+            if rate_b == rate {
+                (self.1).on_endpoint_address_token_reused(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_address_token_reused(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_endpoint_connection_attempt_failed(
@@ -6036,28 +8701,140 @@ mod traits {
             meta: &EndpointMeta,
             event: &EndpointConnectionAttemptFailed,
         ) {
-            (self.0).on_endpoint_connection_attempt_failed(meta, event);
-            (self.1).on_endpoint_connection_attempt_failed(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointConnectionAttemptFailed>();
+            let rate_a = self.0.sample_rate::<EndpointConnectionAttemptFailed>();
+            if rate_a == rate {
+                (self.0).on_endpoint_connection_attempt_failed(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_connection_attempt_failed(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointConnectionAttemptFailed>();
+            if rate_b == rate {
+                (self.1).on_endpoint_connection_attempt_failed(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_connection_attempt_failed(meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &EndpointMeta,
+            event: &EndpointConnectionAttemptRetried,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<EndpointConnectionAttemptRetried>();
+            let rate_a = self.0.sample_rate::<EndpointConnectionAttemptRetried>();
+            if rate_a == rate {
+                (self.0).on_endpoint_connection_attempt_retried(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_endpoint_connection_attempt_retried(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<EndpointConnectionAttemptRetried>();
+            if rate_b == rate {
+                (self.1).on_endpoint_connection_attempt_retried(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_endpoint_connection_attempt_retried(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_platform_tx(&mut self, meta: &EndpointMeta, event: &PlatformTx) {
-            (self.0).on_platform_tx(meta, event);
-            (self.1).on_platform_tx(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformTx>();
+            let rate_a = self.0.sample_rate::<PlatformTx>();
+            if rate_a == rate {
+                (self.0).on_platform_tx(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_tx(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformTx>();
+            if rate_b == rate {
+                (self.1).on_platform_tx(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_tx(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_platform_tx_error(&mut self, meta: &EndpointMeta, event: &PlatformTxError) {
-            (self.0).on_platform_tx_error(meta, event);
-            (self.1).on_platform_tx_error(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformTxError>();
+            let rate_a = self.0.sample_rate::<PlatformTxError>();
+            if rate_a == rate {
+                (self.0).on_platform_tx_error(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_tx_error(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformTxError>();
+            if rate_b == rate {
+                (self.1).on_platform_tx_error(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_tx_error(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_platform_rx(&mut self, meta: &EndpointMeta, event: &PlatformRx) {
-            (self.0).on_platform_rx(meta, event);
-            (self.1).on_platform_rx(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformRx>();
+            let rate_a = self.0.sample_rate::<PlatformRx>();
+            if rate_a == rate {
+                (self.0).on_platform_rx(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_rx(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformRx>();
+            if rate_b == rate {
+                (self.1).on_platform_rx(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_rx(meta, event);
+                (self.1).on_event(meta, event);
+            }
+        }
+        #[inline]
+        fn on_platform_rx_overflow(&mut self, meta: &EndpointMeta, event: &PlatformRxOverflow) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformRxOverflow>();
+            let rate_a = self.0.sample_rate::<PlatformRxOverflow>();
+            if rate_a == rate {
+                (self.0).on_platform_rx_overflow(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_rx_overflow(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformRxOverflow>();
+            if rate_b == rate {
+                (self.1).on_platform_rx_overflow(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_rx_overflow(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_platform_rx_error(&mut self, meta: &EndpointMeta, event: &PlatformRxError) {
-            (self.0).on_platform_rx_error(meta, event);
-            (self.1).on_platform_rx_error(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformRxError>();
+            let rate_a = self.0.sample_rate::<PlatformRxError>();
+            if rate_a == rate {
+                (self.0).on_platform_rx_error(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_rx_error(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformRxError>();
+            if rate_b == rate {
+                (self.1).on_platform_rx_error(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_rx_error(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_platform_feature_configured(
@@ -6065,8 +8842,22 @@ mod traits {
             meta: &EndpointMeta,
             event: &PlatformFeatureConfigured,
         ) {
-            (self.0).on_platform_feature_configured(meta, event);
-            (self.1).on_platform_feature_configured(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformFeatureConfigured>();
+            let rate_a = self.0.sample_rate::<PlatformFeatureConfigured>();
+            if rate_a == rate {
+                (self.0).on_platform_feature_configured(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_feature_configured(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformFeatureConfigured>();
+            if rate_b == rate {
+                (self.1).on_platform_feature_configured(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_feature_configured(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_platform_event_loop_wakeup(
@@ -6074,8 +8865,22 @@ mod traits {
             meta: &EndpointMeta,
             event: &PlatformEventLoopWakeup,
         ) {
-            (self.0).on_platform_event_loop_wakeup(meta, event);
-            (self.1).on_platform_event_loop_wakeup(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformEventLoopWakeup>();
+            let rate_a = self.0.sample_rate::<PlatformEventLoopWakeup>();
+            if rate_a == rate {
+                (self.0).on_platform_event_loop_wakeup(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_event_loop_wakeup(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformEventLoopWakeup>();
+            if rate_b == rate {
+                (self.1).on_platform_event_loop_wakeup(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_event_loop_wakeup(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_platform_event_loop_sleep(
@@ -6083,13 +8888,32 @@ mod traits {
             meta: &EndpointMeta,
             event: &PlatformEventLoopSleep,
         ) {
-            (self.0).on_platform_event_loop_sleep(meta, event);
-            (self.1).on_platform_event_loop_sleep(meta, event);
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let rate = self.sample_rate::<PlatformEventLoopSleep>();
+            let rate_a = self.0.sample_rate::<PlatformEventLoopSleep>();
+            if rate_a == rate {
+                (self.0).on_platform_event_loop_sleep(meta, event);
+            } else if rate_a.sample(&SEQUENCE) {
+                (self.0).on_platform_event_loop_sleep(meta, event);
+                (self.0).on_event(meta, event);
+            }
+            let rate_b = self.1.sample_rate::<PlatformEventLoopSleep>();
+            if rate_b == rate {
+                (self.1).on_platform_event_loop_sleep(meta, event);
+            } else if rate_b.sample(&SEQUENCE) {
+                (self.1).on_platform_event_loop_sleep(meta, event);
+                (self.1).on_event(meta, event);
+            }
         }
         #[inline]
         fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
-            self.0.on_event(meta, event);
-            self.1.on_event(meta, event);
+            let rate = self.sample_rate::<E>();
+            if self.0.sample_rate::<E>() == rate {
+                self.0.on_event(meta, event);
+            }
+            if self.1.sample_rate::<E>() == rate {
+                self.1.on_event(meta, event);
+            }
         }
         #[inline]
         fn on_connection_event<E: Event>(
@@ -6098,8 +8922,13 @@ mod traits {
             meta: &ConnectionMeta,
             event: &E,
         ) {
-            self.0.on_connection_event(&mut context.0, meta, event);
-            self.1.on_connection_event(&mut context.1, meta, event);
+            let rate = self.sample_rate::<E>();
+            if self.0.sample_rate::<E>() == rate {
+                self.0.on_connection_event(&mut context.0, meta, event);
+            }
+            if self.1.sample_rate::<E>() == rate {
+                self.1.on_connection_event(&mut context.1, meta, event);
+            }
         }
         #[inline]
         fn query(
@@ -6122,40 +8951,1270 @@ mod traits {
                 .and_then(|| B::query_mut(&mut context.1, query))
         }
     }
-    pub trait EndpointPublisher {
-        #[doc = "Publishes a `VersionInformation` event to the publisher's subscriber"]
-        fn on_version_information(&mut self, event: builder::VersionInformation);
-        #[doc = "Publishes a `EndpointPacketSent` event to the publisher's subscriber"]
-        fn on_endpoint_packet_sent(&mut self, event: builder::EndpointPacketSent);
-        #[doc = "Publishes a `EndpointPacketReceived` event to the publisher's subscriber"]
-        fn on_endpoint_packet_received(&mut self, event: builder::EndpointPacketReceived);
-        #[doc = "Publishes a `EndpointDatagramSent` event to the publisher's subscriber"]
-        fn on_endpoint_datagram_sent(&mut self, event: builder::EndpointDatagramSent);
-        #[doc = "Publishes a `EndpointDatagramReceived` event to the publisher's subscriber"]
-        fn on_endpoint_datagram_received(&mut self, event: builder::EndpointDatagramReceived);
-        #[doc = "Publishes a `EndpointDatagramDropped` event to the publisher's subscriber"]
-        fn on_endpoint_datagram_dropped(&mut self, event: builder::EndpointDatagramDropped);
-        #[doc = "Publishes a `EndpointConnectionAttemptFailed` event to the publisher's subscriber"]
-        fn on_endpoint_connection_attempt_failed(
+    #[doc = r" A [`Subscriber`] for the events of a single connection"]
+    #[doc = r""]
+    #[doc = r" Unlike the endpoint-wide [`Subscriber`], an `AttachedSubscriber` is attached to"]
+    #[doc = r" an individual connection and receives its events in addition to the endpoint's"]
+    #[doc = r" subscriber. This is useful for enabling verbose debugging for a single peer."]
+    #[doc = r""]
+    #[doc = r" Any [`Subscriber`] can be attached to a connection with [`Attached`]."]
+    pub trait AttachedSubscriber: 'static + Send {
+        #[doc = "Called when the `ApplicationProtocolInformation` event is triggered"]
+        #[inline]
+        fn on_application_protocol_information(
             &mut self,
-            event: builder::EndpointConnectionAttemptFailed,
-        );
-        #[doc = "Publishes a `PlatformTx` event to the publisher's subscriber"]
-        fn on_platform_tx(&mut self, event: builder::PlatformTx);
-        #[doc = "Publishes a `PlatformTxError` event to the publisher's subscriber"]
-        fn on_platform_tx_error(&mut self, event: builder::PlatformTxError);
-        #[doc = "Publishes a `PlatformRx` event to the publisher's subscriber"]
-        fn on_platform_rx(&mut self, event: builder::PlatformRx);
-        #[doc = "Publishes a `PlatformRxError` event to the publisher's subscriber"]
-        fn on_platform_rx_error(&mut self, event: builder::PlatformRxError);
-        #[doc = "Publishes a `PlatformFeatureConfigured` event to the publisher's subscriber"]
-        fn on_platform_feature_configured(&mut self, event: builder::PlatformFeatureConfigured);
-        #[doc = "Publishes a `PlatformEventLoopWakeup` event to the publisher's subscriber"]
-        fn on_platform_event_loop_wakeup(&mut self, event: builder::PlatformEventLoopWakeup);
-        #[doc = "Publishes a `PlatformEventLoopSleep` event to the publisher's subscriber"]
-        fn on_platform_event_loop_sleep(&mut self, event: builder::PlatformEventLoopSleep);
-        #[doc = r" Returns the QUIC version, if any"]
-        fn quic_version(&self) -> Option<u32>;
+            meta: &ConnectionMeta,
+            event: &ApplicationProtocolInformation,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ServerNameInformation` event is triggered"]
+        #[inline]
+        fn on_server_name_information(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &ServerNameInformation,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `EncryptedClientHello` event is triggered"]
+        #[inline]
+        fn on_encrypted_client_hello(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &EncryptedClientHello,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacketSkipped` event is triggered"]
+        #[inline]
+        fn on_packet_skipped(&mut self, meta: &ConnectionMeta, event: &PacketSkipped) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacketSent` event is triggered"]
+        #[inline]
+        fn on_packet_sent(&mut self, meta: &ConnectionMeta, event: &PacketSent) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacketReceived` event is triggered"]
+        #[inline]
+        fn on_packet_received(&mut self, meta: &ConnectionMeta, event: &PacketReceived) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ActivePathUpdated` event is triggered"]
+        #[inline]
+        fn on_active_path_updated(&mut self, meta: &ConnectionMeta, event: &ActivePathUpdated) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PathCreated` event is triggered"]
+        #[inline]
+        fn on_path_created(&mut self, meta: &ConnectionMeta, event: &PathCreated) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `FrameSent` event is triggered"]
+        #[inline]
+        fn on_frame_sent(&mut self, meta: &ConnectionMeta, event: &FrameSent) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `FrameReceived` event is triggered"]
+        #[inline]
+        fn on_frame_received(&mut self, meta: &ConnectionMeta, event: &FrameReceived) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacketLost` event is triggered"]
+        #[inline]
+        fn on_packet_lost(&mut self, meta: &ConnectionMeta, event: &PacketLost) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `RecoveryMetrics` event is triggered"]
+        #[inline]
+        fn on_recovery_metrics(&mut self, meta: &ConnectionMeta, event: &RecoveryMetrics) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `Congestion` event is triggered"]
+        #[inline]
+        fn on_congestion(&mut self, meta: &ConnectionMeta, event: &Congestion) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `AckProcessed` event is triggered"]
+        #[inline]
+        #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
+        #[allow(deprecated)]
+        fn on_ack_processed(&mut self, meta: &ConnectionMeta, event: &AckProcessed) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `RxAckRangeDropped` event is triggered"]
+        #[inline]
+        fn on_rx_ack_range_dropped(&mut self, meta: &ConnectionMeta, event: &RxAckRangeDropped) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `AckRangeReceived` event is triggered"]
+        #[inline]
+        fn on_ack_range_received(&mut self, meta: &ConnectionMeta, event: &AckRangeReceived) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `AckRangeSent` event is triggered"]
+        #[inline]
+        fn on_ack_range_sent(&mut self, meta: &ConnectionMeta, event: &AckRangeSent) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacketDropped` event is triggered"]
+        #[inline]
+        fn on_packet_dropped(&mut self, meta: &ConnectionMeta, event: &PacketDropped) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `KeyUpdate` event is triggered"]
+        #[inline]
+        fn on_key_update(&mut self, meta: &ConnectionMeta, event: &KeyUpdate) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `KeySpaceDiscarded` event is triggered"]
+        #[inline]
+        fn on_key_space_discarded(&mut self, meta: &ConnectionMeta, event: &KeySpaceDiscarded) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionStarted` event is triggered"]
+        #[inline]
+        fn on_connection_started(&mut self, meta: &ConnectionMeta, event: &ConnectionStarted) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionClosed` event is triggered"]
+        #[inline]
+        fn on_connection_closed(&mut self, meta: &ConnectionMeta, event: &ConnectionClosed) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DuplicatePacket` event is triggered"]
+        #[inline]
+        fn on_duplicate_packet(&mut self, meta: &ConnectionMeta, event: &DuplicatePacket) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TransportParametersReceived` event is triggered"]
+        #[inline]
+        fn on_transport_parameters_received(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &TransportParametersReceived,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DatagramSent` event is triggered"]
+        #[inline]
+        fn on_datagram_sent(&mut self, meta: &ConnectionMeta, event: &DatagramSent) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DatagramPayloadSent` event is triggered"]
+        #[inline]
+        fn on_datagram_payload_sent(&mut self, meta: &ConnectionMeta, event: &DatagramPayloadSent) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DatagramReceived` event is triggered"]
+        #[inline]
+        fn on_datagram_received(&mut self, meta: &ConnectionMeta, event: &DatagramReceived) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DatagramDropped` event is triggered"]
+        #[inline]
+        fn on_datagram_dropped(&mut self, meta: &ConnectionMeta, event: &DatagramDropped) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionIdUpdated` event is triggered"]
+        #[inline]
+        fn on_connection_id_updated(&mut self, meta: &ConnectionMeta, event: &ConnectionIdUpdated) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `EcnStateChanged` event is triggered"]
+        #[inline]
+        fn on_ecn_state_changed(&mut self, meta: &ConnectionMeta, event: &EcnStateChanged) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionMigrationDenied` event is triggered"]
+        #[inline]
+        fn on_connection_migration_denied(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &ConnectionMigrationDenied,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `HandshakeStatusUpdated` event is triggered"]
+        #[inline]
+        fn on_handshake_status_updated(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &HandshakeStatusUpdated,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `HandshakeTiming` event is triggered"]
+        #[inline]
+        fn on_handshake_timing(&mut self, meta: &ConnectionMeta, event: &HandshakeTiming) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TlsExporterReady` event is triggered"]
+        #[inline]
+        fn on_tls_exporter_ready(&mut self, meta: &ConnectionMeta, event: &TlsExporterReady) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PathChallengeUpdated` event is triggered"]
+        #[inline]
+        fn on_path_challenge_updated(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &PathChallengeUpdated,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TlsClientHello` event is triggered"]
+        #[inline]
+        fn on_tls_client_hello(&mut self, meta: &ConnectionMeta, event: &TlsClientHello) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TlsServerHello` event is triggered"]
+        #[inline]
+        fn on_tls_server_hello(&mut self, meta: &ConnectionMeta, event: &TlsServerHello) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `RxStreamProgress` event is triggered"]
+        #[inline]
+        fn on_rx_stream_progress(&mut self, meta: &ConnectionMeta, event: &RxStreamProgress) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TxStreamProgress` event is triggered"]
+        #[inline]
+        fn on_tx_stream_progress(&mut self, meta: &ConnectionMeta, event: &TxStreamProgress) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `KeepAliveTimerExpired` event is triggered"]
+        #[inline]
+        fn on_keep_alive_timer_expired(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &KeepAliveTimerExpired,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `MtuUpdated` event is triggered"]
+        #[inline]
+        fn on_mtu_updated(&mut self, meta: &ConnectionMeta, event: &MtuUpdated) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `SlowStartExited` event is triggered"]
+        #[inline]
+        fn on_slow_start_exited(&mut self, meta: &ConnectionMeta, event: &SlowStartExited) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DeliveryRateSampled` event is triggered"]
+        #[inline]
+        fn on_delivery_rate_sampled(&mut self, meta: &ConnectionMeta, event: &DeliveryRateSampled) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PacingRateUpdated` event is triggered"]
+        #[inline]
+        fn on_pacing_rate_updated(&mut self, meta: &ConnectionMeta, event: &PacingRateUpdated) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `CongestionState` event is triggered"]
+        #[inline]
+        fn on_congestion_state(&mut self, meta: &ConnectionMeta, event: &CongestionState) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PathStatistics` event is triggered"]
+        #[inline]
+        fn on_path_statistics(&mut self, meta: &ConnectionMeta, event: &PathStatistics) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `BbrStateChanged` event is triggered"]
+        #[inline]
+        fn on_bbr_state_changed(&mut self, meta: &ConnectionMeta, event: &BbrStateChanged) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DcStateChanged` event is triggered"]
+        #[inline]
+        fn on_dc_state_changed(&mut self, meta: &ConnectionMeta, event: &DcStateChanged) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionEvicted` event is triggered"]
+        #[inline]
+        fn on_connection_evicted(&mut self, meta: &ConnectionMeta, event: &ConnectionEvicted) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `SocketErrorReceived` event is triggered"]
+        #[inline]
+        fn on_socket_error_received(&mut self, meta: &ConnectionMeta, event: &SocketErrorReceived) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `LocalAddressLost` event is triggered"]
+        #[inline]
+        fn on_local_address_lost(&mut self, meta: &ConnectionMeta, event: &LocalAddressLost) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `TransmitShare` event is triggered"]
+        #[inline]
+        fn on_transmit_share(&mut self, meta: &ConnectionMeta, event: &TransmitShare) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `VersionNegotiationReceived` event is triggered"]
+        #[inline]
+        fn on_version_negotiation_received(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &VersionNegotiationReceived,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+    }
+    #[doc = r" Attaches a [`Subscriber`] to a single connection"]
+    #[doc = r""]
+    #[doc = r" The subscriber's connection context is created when the first event of the"]
+    #[doc = r" connection is published."]
+    pub struct Attached<Sub: Subscriber> {
+        subscriber: Sub,
+        context: Option<Sub::ConnectionContext>,
+    }
+    impl<Sub: Subscriber> fmt::Debug for Attached<Sub> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_struct("Attached").finish_non_exhaustive()
+        }
+    }
+    impl<Sub: Subscriber> Attached<Sub> {
+        #[inline]
+        pub fn new(subscriber: Sub) -> Self {
+            Self {
+                subscriber,
+                context: None,
+            }
+        }
+        #[inline]
+        fn context(&mut self, meta: &ConnectionMeta) -> (&mut Sub, &mut Sub::ConnectionContext) {
+            let subscriber = &mut self.subscriber;
+            let context = self.context.get_or_insert_with(|| {
+                subscriber.create_connection_context(meta, &builder::ConnectionInfo {}.into_event())
+            });
+            (subscriber, context)
+        }
+    }
+    impl<Sub: Subscriber> AttachedSubscriber for Attached<Sub> {
+        #[inline]
+        fn on_application_protocol_information(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &ApplicationProtocolInformation,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ApplicationProtocolInformation>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_application_protocol_information(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_server_name_information(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &ServerNameInformation,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ServerNameInformation>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_server_name_information(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_encrypted_client_hello(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &EncryptedClientHello,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<EncryptedClientHello>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_encrypted_client_hello(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_packet_skipped(&mut self, meta: &ConnectionMeta, event: &PacketSkipped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PacketSkipped>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_packet_skipped(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_packet_sent(&mut self, meta: &ConnectionMeta, event: &PacketSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PacketSent>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_packet_sent(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_packet_received(&mut self, meta: &ConnectionMeta, event: &PacketReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PacketReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_packet_received(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_active_path_updated(&mut self, meta: &ConnectionMeta, event: &ActivePathUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ActivePathUpdated>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_active_path_updated(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_path_created(&mut self, meta: &ConnectionMeta, event: &PathCreated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PathCreated>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_path_created(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_frame_sent(&mut self, meta: &ConnectionMeta, event: &FrameSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self.subscriber.sample_rate::<FrameSent>().sample(&SEQUENCE) {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_frame_sent(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_frame_received(&mut self, meta: &ConnectionMeta, event: &FrameReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<FrameReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_frame_received(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_packet_lost(&mut self, meta: &ConnectionMeta, event: &PacketLost) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PacketLost>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_packet_lost(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_recovery_metrics(&mut self, meta: &ConnectionMeta, event: &RecoveryMetrics) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<RecoveryMetrics>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_recovery_metrics(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_congestion(&mut self, meta: &ConnectionMeta, event: &Congestion) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<Congestion>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_congestion(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        #[allow(deprecated)]
+        fn on_ack_processed(&mut self, meta: &ConnectionMeta, event: &AckProcessed) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<AckProcessed>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_ack_processed(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_rx_ack_range_dropped(&mut self, meta: &ConnectionMeta, event: &RxAckRangeDropped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<RxAckRangeDropped>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_rx_ack_range_dropped(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_ack_range_received(&mut self, meta: &ConnectionMeta, event: &AckRangeReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<AckRangeReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_ack_range_received(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_ack_range_sent(&mut self, meta: &ConnectionMeta, event: &AckRangeSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<AckRangeSent>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_ack_range_sent(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_packet_dropped(&mut self, meta: &ConnectionMeta, event: &PacketDropped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PacketDropped>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_packet_dropped(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_key_update(&mut self, meta: &ConnectionMeta, event: &KeyUpdate) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self.subscriber.sample_rate::<KeyUpdate>().sample(&SEQUENCE) {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_key_update(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_key_space_discarded(&mut self, meta: &ConnectionMeta, event: &KeySpaceDiscarded) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<KeySpaceDiscarded>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_key_space_discarded(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_connection_started(&mut self, meta: &ConnectionMeta, event: &ConnectionStarted) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ConnectionStarted>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_connection_started(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_connection_closed(&mut self, meta: &ConnectionMeta, event: &ConnectionClosed) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ConnectionClosed>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_connection_closed(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_duplicate_packet(&mut self, meta: &ConnectionMeta, event: &DuplicatePacket) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<DuplicatePacket>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_duplicate_packet(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_transport_parameters_received(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &TransportParametersReceived,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<TransportParametersReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_transport_parameters_received(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_datagram_sent(&mut self, meta: &ConnectionMeta, event: &DatagramSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<DatagramSent>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_datagram_sent(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_datagram_payload_sent(&mut self, meta: &ConnectionMeta, event: &DatagramPayloadSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<DatagramPayloadSent>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_datagram_payload_sent(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_datagram_received(&mut self, meta: &ConnectionMeta, event: &DatagramReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<DatagramReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_datagram_received(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_datagram_dropped(&mut self, meta: &ConnectionMeta, event: &DatagramDropped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<DatagramDropped>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_datagram_dropped(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_connection_id_updated(&mut self, meta: &ConnectionMeta, event: &ConnectionIdUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ConnectionIdUpdated>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_connection_id_updated(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_ecn_state_changed(&mut self, meta: &ConnectionMeta, event: &EcnStateChanged) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<EcnStateChanged>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_ecn_state_changed(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_connection_migration_denied(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &ConnectionMigrationDenied,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ConnectionMigrationDenied>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_connection_migration_denied(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_handshake_status_updated(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &HandshakeStatusUpdated,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<HandshakeStatusUpdated>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_handshake_status_updated(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_handshake_timing(&mut self, meta: &ConnectionMeta, event: &HandshakeTiming) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<HandshakeTiming>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_handshake_timing(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_tls_exporter_ready(&mut self, meta: &ConnectionMeta, event: &TlsExporterReady) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<TlsExporterReady>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_tls_exporter_ready(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_path_challenge_updated(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &PathChallengeUpdated,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PathChallengeUpdated>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_path_challenge_updated(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_tls_client_hello(&mut self, meta: &ConnectionMeta, event: &TlsClientHello) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<TlsClientHello>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_tls_client_hello(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_tls_server_hello(&mut self, meta: &ConnectionMeta, event: &TlsServerHello) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<TlsServerHello>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_tls_server_hello(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_rx_stream_progress(&mut self, meta: &ConnectionMeta, event: &RxStreamProgress) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<RxStreamProgress>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_rx_stream_progress(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_tx_stream_progress(&mut self, meta: &ConnectionMeta, event: &TxStreamProgress) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<TxStreamProgress>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_tx_stream_progress(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_keep_alive_timer_expired(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &KeepAliveTimerExpired,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<KeepAliveTimerExpired>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_keep_alive_timer_expired(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_mtu_updated(&mut self, meta: &ConnectionMeta, event: &MtuUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<MtuUpdated>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_mtu_updated(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_slow_start_exited(&mut self, meta: &ConnectionMeta, event: &SlowStartExited) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<SlowStartExited>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_slow_start_exited(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_delivery_rate_sampled(&mut self, meta: &ConnectionMeta, event: &DeliveryRateSampled) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<DeliveryRateSampled>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_delivery_rate_sampled(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_pacing_rate_updated(&mut self, meta: &ConnectionMeta, event: &PacingRateUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PacingRateUpdated>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_pacing_rate_updated(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_congestion_state(&mut self, meta: &ConnectionMeta, event: &CongestionState) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<CongestionState>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_congestion_state(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_path_statistics(&mut self, meta: &ConnectionMeta, event: &PathStatistics) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<PathStatistics>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_path_statistics(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_bbr_state_changed(&mut self, meta: &ConnectionMeta, event: &BbrStateChanged) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<BbrStateChanged>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_bbr_state_changed(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_dc_state_changed(&mut self, meta: &ConnectionMeta, event: &DcStateChanged) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<DcStateChanged>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_dc_state_changed(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_connection_evicted(&mut self, meta: &ConnectionMeta, event: &ConnectionEvicted) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<ConnectionEvicted>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_connection_evicted(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_socket_error_received(&mut self, meta: &ConnectionMeta, event: &SocketErrorReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<SocketErrorReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_socket_error_received(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_local_address_lost(&mut self, meta: &ConnectionMeta, event: &LocalAddressLost) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<LocalAddressLost>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_local_address_lost(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_transmit_share(&mut self, meta: &ConnectionMeta, event: &TransmitShare) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<TransmitShare>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_transmit_share(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+        #[inline]
+        fn on_version_negotiation_received(
+            &mut self,
+            meta: &ConnectionMeta,
+            event: &VersionNegotiationReceived,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<VersionNegotiationReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let (subscriber, context) = self.context(meta);
+            subscriber.on_version_negotiation_received(context, meta, event);
+            subscriber.on_connection_event(context, meta, event);
+            subscriber.on_event(meta, event);
+        }
+    }
+    pub trait EndpointPublisher {
+        #[doc = "Publishes a `VersionInformation` event to the publisher's subscriber"]
+        fn on_version_information(&mut self, event: builder::VersionInformation);
+        #[doc = "Publishes a `EndpointPacketSent` event to the publisher's subscriber"]
+        fn on_endpoint_packet_sent(&mut self, event: builder::EndpointPacketSent);
+        #[doc = "Publishes a `EndpointPacketReceived` event to the publisher's subscriber"]
+        fn on_endpoint_packet_received(&mut self, event: builder::EndpointPacketReceived);
+        #[doc = "Publishes a `EndpointDatagramSent` event to the publisher's subscriber"]
+        fn on_endpoint_datagram_sent(&mut self, event: builder::EndpointDatagramSent);
+        #[doc = "Publishes a `EndpointDatagramReceived` event to the publisher's subscriber"]
+        fn on_endpoint_datagram_received(&mut self, event: builder::EndpointDatagramReceived);
+        #[doc = "Publishes a `EndpointDatagramDropped` event to the publisher's subscriber"]
+        fn on_endpoint_datagram_dropped(&mut self, event: builder::EndpointDatagramDropped);
+        #[doc = "Publishes a `EndpointAddressTokenReused` event to the publisher's subscriber"]
+        fn on_endpoint_address_token_reused(&mut self, event: builder::EndpointAddressTokenReused);
+        #[doc = "Publishes a `EndpointConnectionAttemptFailed` event to the publisher's subscriber"]
+        fn on_endpoint_connection_attempt_failed(
+            &mut self,
+            event: builder::EndpointConnectionAttemptFailed,
+        );
+        #[doc = "Publishes a `EndpointConnectionAttemptRetried` event to the publisher's subscriber"]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRetried,
+        );
+        #[doc = "Publishes a `PlatformTx` event to the publisher's subscriber"]
+        fn on_platform_tx(&mut self, event: builder::PlatformTx);
+        #[doc = "Publishes a `PlatformTxError` event to the publisher's subscriber"]
+        fn on_platform_tx_error(&mut self, event: builder::PlatformTxError);
+        #[doc = "Publishes a `PlatformRx` event to the publisher's subscriber"]
+        fn on_platform_rx(&mut self, event: builder::PlatformRx);
+        #[doc = "Publishes a `PlatformRxOverflow` event to the publisher's subscriber"]
+        fn on_platform_rx_overflow(&mut self, event: builder::PlatformRxOverflow);
+        #[doc = "Publishes a `PlatformRxError` event to the publisher's subscriber"]
+        fn on_platform_rx_error(&mut self, event: builder::PlatformRxError);
+        #[doc = "Publishes a `PlatformFeatureConfigured` event to the publisher's subscriber"]
+        fn on_platform_feature_configured(&mut self, event: builder::PlatformFeatureConfigured);
+        #[doc = "Publishes a `PlatformEventLoopWakeup` event to the publisher's subscriber"]
+        fn on_platform_event_loop_wakeup(&mut self, event: builder::PlatformEventLoopWakeup);
+        #[doc = "Publishes a `PlatformEventLoopSleep` event to the publisher's subscriber"]
+        fn on_platform_event_loop_sleep(&mut self, event: builder::PlatformEventLoopSleep);
+        #[doc = r" Returns the QUIC version, if any"]
+        fn quic_version(&self) -> Option<u32>;
     }
     pub struct EndpointPublisherSubscriber<'a, Sub: Subscriber> {
         meta: EndpointMeta,
@@ -6187,18 +10246,42 @@ mod traits {
     impl<'a, Sub: Subscriber> EndpointPublisher for EndpointPublisherSubscriber<'a, Sub> {
         #[inline]
         fn on_version_information(&mut self, event: builder::VersionInformation) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::VersionInformation>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber.on_version_information(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
         fn on_endpoint_packet_sent(&mut self, event: builder::EndpointPacketSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointPacketSent>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber.on_endpoint_packet_sent(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
         fn on_endpoint_packet_received(&mut self, event: builder::EndpointPacketReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointPacketReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_endpoint_packet_received(&self.meta, &event);
@@ -6206,6 +10289,14 @@ mod traits {
         }
         #[inline]
         fn on_endpoint_datagram_sent(&mut self, event: builder::EndpointDatagramSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointDatagramSent>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_endpoint_datagram_sent(&self.meta, &event);
@@ -6213,6 +10304,14 @@ mod traits {
         }
         #[inline]
         fn on_endpoint_datagram_received(&mut self, event: builder::EndpointDatagramReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointDatagramReceived>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_endpoint_datagram_received(&self.meta, &event);
@@ -6220,47 +10319,150 @@ mod traits {
         }
         #[inline]
         fn on_endpoint_datagram_dropped(&mut self, event: builder::EndpointDatagramDropped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointDatagramDropped>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_endpoint_datagram_dropped(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_endpoint_address_token_reused(&mut self, event: builder::EndpointAddressTokenReused) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointAddressTokenReused>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_endpoint_address_token_reused(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_endpoint_connection_attempt_failed(
             &mut self,
             event: builder::EndpointConnectionAttemptFailed,
         ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointConnectionAttemptFailed>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_endpoint_connection_attempt_failed(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRetried,
+        ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::EndpointConnectionAttemptRetried>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber
+                .on_endpoint_connection_attempt_retried(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_platform_tx(&mut self, event: builder::PlatformTx) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformTx>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber.on_platform_tx(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
         fn on_platform_tx_error(&mut self, event: builder::PlatformTxError) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformTxError>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber.on_platform_tx_error(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
         fn on_platform_rx(&mut self, event: builder::PlatformRx) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformRx>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber.on_platform_rx(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_platform_rx_overflow(&mut self, event: builder::PlatformRxOverflow) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformRxOverflow>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
+            let event = event.into_event();
+            self.subscriber.on_platform_rx_overflow(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_platform_rx_error(&mut self, event: builder::PlatformRxError) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformRxError>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber.on_platform_rx_error(&self.meta, &event);
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
         fn on_platform_feature_configured(&mut self, event: builder::PlatformFeatureConfigured) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformFeatureConfigured>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_platform_feature_configured(&self.meta, &event);
@@ -6268,6 +10470,14 @@ mod traits {
         }
         #[inline]
         fn on_platform_event_loop_wakeup(&mut self, event: builder::PlatformEventLoopWakeup) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformEventLoopWakeup>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_platform_event_loop_wakeup(&self.meta, &event);
@@ -6275,6 +10485,14 @@ mod traits {
         }
         #[inline]
         fn on_platform_event_loop_sleep(&mut self, event: builder::PlatformEventLoopSleep) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            if !self
+                .subscriber
+                .sample_rate::<api::PlatformEventLoopSleep>()
+                .sample(&SEQUENCE)
+            {
+                return;
+            }
             let event = event.into_event();
             self.subscriber
                 .on_platform_event_loop_sleep(&self.meta, &event);
@@ -6293,6 +10511,8 @@ mod traits {
         );
         #[doc = "Publishes a `ServerNameInformation` event to the publisher's subscriber"]
         fn on_server_name_information(&mut self, event: builder::ServerNameInformation);
+        #[doc = "Publishes a `EncryptedClientHello` event to the publisher's subscriber"]
+        fn on_encrypted_client_hello(&mut self, event: builder::EncryptedClientHello);
         #[doc = "Publishes a `PacketSkipped` event to the publisher's subscriber"]
         fn on_packet_skipped(&mut self, event: builder::PacketSkipped);
         #[doc = "Publishes a `PacketSent` event to the publisher's subscriber"]
@@ -6337,6 +10557,8 @@ mod traits {
         fn on_transport_parameters_received(&mut self, event: builder::TransportParametersReceived);
         #[doc = "Publishes a `DatagramSent` event to the publisher's subscriber"]
         fn on_datagram_sent(&mut self, event: builder::DatagramSent);
+        #[doc = "Publishes a `DatagramPayloadSent` event to the publisher's subscriber"]
+        fn on_datagram_payload_sent(&mut self, event: builder::DatagramPayloadSent);
         #[doc = "Publishes a `DatagramReceived` event to the publisher's subscriber"]
         fn on_datagram_received(&mut self, event: builder::DatagramReceived);
         #[doc = "Publishes a `DatagramDropped` event to the publisher's subscriber"]
@@ -6349,6 +10571,8 @@ mod traits {
         fn on_connection_migration_denied(&mut self, event: builder::ConnectionMigrationDenied);
         #[doc = "Publishes a `HandshakeStatusUpdated` event to the publisher's subscriber"]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated);
+        #[doc = "Publishes a `HandshakeTiming` event to the publisher's subscriber"]
+        fn on_handshake_timing(&mut self, event: builder::HandshakeTiming);
         #[doc = "Publishes a `TlsExporterReady` event to the publisher's subscriber"]
         fn on_tls_exporter_ready(&mut self, event: builder::TlsExporterReady);
         #[doc = "Publishes a `PathChallengeUpdated` event to the publisher's subscriber"]
@@ -6371,10 +10595,24 @@ mod traits {
         fn on_delivery_rate_sampled(&mut self, event: builder::DeliveryRateSampled);
         #[doc = "Publishes a `PacingRateUpdated` event to the publisher's subscriber"]
         fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated);
+        #[doc = "Publishes a `CongestionState` event to the publisher's subscriber"]
+        fn on_congestion_state(&mut self, event: builder::CongestionState);
+        #[doc = "Publishes a `PathStatistics` event to the publisher's subscriber"]
+        fn on_path_statistics(&mut self, event: builder::PathStatistics);
         #[doc = "Publishes a `BbrStateChanged` event to the publisher's subscriber"]
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged);
         #[doc = "Publishes a `DcStateChanged` event to the publisher's subscriber"]
         fn on_dc_state_changed(&mut self, event: builder::DcStateChanged);
+        #[doc = "Publishes a `ConnectionEvicted` event to the publisher's subscriber"]
+        fn on_connection_evicted(&mut self, event: builder::ConnectionEvicted);
+        #[doc = "Publishes a `SocketErrorReceived` event to the publisher's subscriber"]
+        fn on_socket_error_received(&mut self, event: builder::SocketErrorReceived);
+        #[doc = "Publishes a `LocalAddressLost` event to the publisher's subscriber"]
+        fn on_local_address_lost(&mut self, event: builder::LocalAddressLost);
+        #[doc = "Publishes a `TransmitShare` event to the publisher's subscriber"]
+        fn on_transmit_share(&mut self, event: builder::TransmitShare);
+        #[doc = "Publishes a `VersionNegotiationReceived` event to the publisher's subscriber"]
+        fn on_version_negotiation_received(&mut self, event: builder::VersionNegotiationReceived);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
         fn quic_version(&self) -> u32;
         #[doc = r" Returns the [`Subject`] for the current publisher"]
@@ -6385,6 +10623,7 @@ mod traits {
         quic_version: u32,
         subscriber: &'a mut Sub,
         context: &'a mut Sub::ConnectionContext,
+        attached: Option<&'a mut dyn AttachedSubscriber>,
     }
     impl<'a, Sub: Subscriber> fmt::Debug for ConnectionPublisherSubscriber<'a, Sub> {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -6407,8 +10646,15 @@ mod traits {
                 quic_version,
                 subscriber,
                 context,
+                attached: None,
             }
         }
+        #[doc = r" Also publishes events to the connection's [`AttachedSubscriber`], if any"]
+        #[inline]
+        pub fn with_attached(mut self, attached: Option<&'a mut dyn AttachedSubscriber>) -> Self {
+            self.attached = attached;
+            self
+        }
     }
     impl<'a, Sub: Subscriber> ConnectionPublisher for ConnectionPublisherSubscriber<'a, Sub> {
         #[inline]
@@ -6416,394 +10662,1176 @@ mod traits {
             &mut self,
             event: builder::ApplicationProtocolInformation,
         ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ApplicationProtocolInformation>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_application_protocol_information(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber.on_application_protocol_information(
+                    self.context,
+                    &self.meta,
+                    &event,
+                );
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_application_protocol_information(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_server_name_information(&mut self, event: builder::ServerNameInformation) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ServerNameInformation>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_server_name_information(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_server_name_information(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_server_name_information(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_encrypted_client_hello(&mut self, event: builder::EncryptedClientHello) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::EncryptedClientHello>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_encrypted_client_hello(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_encrypted_client_hello(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_packet_skipped(&mut self, event: builder::PacketSkipped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PacketSkipped>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_packet_skipped(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_packet_skipped(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_packet_skipped(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_packet_sent(&mut self, event: builder::PacketSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PacketSent>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_packet_sent(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_packet_sent(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_packet_sent(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_packet_received(&mut self, event: builder::PacketReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PacketReceived>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_packet_received(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_packet_received(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_packet_received(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_active_path_updated(&mut self, event: builder::ActivePathUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ActivePathUpdated>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_active_path_updated(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_active_path_updated(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_active_path_updated(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_path_created(&mut self, event: builder::PathCreated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PathCreated>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_path_created(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_path_created(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_path_created(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_frame_sent(&mut self, event: builder::FrameSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::FrameSent>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_frame_sent(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_frame_sent(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_frame_sent(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_frame_received(&mut self, event: builder::FrameReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::FrameReceived>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_frame_received(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_frame_received(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_frame_received(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_packet_lost(&mut self, event: builder::PacketLost) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PacketLost>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_packet_lost(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_packet_lost(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_packet_lost(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_recovery_metrics(&mut self, event: builder::RecoveryMetrics) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::RecoveryMetrics>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_recovery_metrics(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_recovery_metrics(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_recovery_metrics(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_congestion(&mut self, event: builder::Congestion) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::Congestion>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_congestion(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_congestion(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_congestion(&self.meta, &event);
+            }
         }
         #[inline]
         #[allow(deprecated)]
         fn on_ack_processed(&mut self, event: builder::AckProcessed) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::AckProcessed>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_ack_processed(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_ack_processed(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_ack_processed(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_rx_ack_range_dropped(&mut self, event: builder::RxAckRangeDropped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::RxAckRangeDropped>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_rx_ack_range_dropped(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_rx_ack_range_dropped(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_rx_ack_range_dropped(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_ack_range_received(&mut self, event: builder::AckRangeReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::AckRangeReceived>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_ack_range_received(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_ack_range_received(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_ack_range_received(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_ack_range_sent(&mut self, event: builder::AckRangeSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::AckRangeSent>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_ack_range_sent(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_ack_range_sent(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_ack_range_sent(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_packet_dropped(&mut self, event: builder::PacketDropped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PacketDropped>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_packet_dropped(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_packet_dropped(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_packet_dropped(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_key_update(&mut self, event: builder::KeyUpdate) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::KeyUpdate>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_key_update(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_key_update(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_key_update(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_key_space_discarded(&mut self, event: builder::KeySpaceDiscarded) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::KeySpaceDiscarded>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_key_space_discarded(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_key_space_discarded(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_key_space_discarded(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_connection_started(&mut self, event: builder::ConnectionStarted) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ConnectionStarted>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_connection_started(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_connection_started(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_connection_started(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ConnectionClosed>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_connection_closed(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_connection_closed(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_connection_closed(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_duplicate_packet(&mut self, event: builder::DuplicatePacket) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::DuplicatePacket>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_duplicate_packet(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_duplicate_packet(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_duplicate_packet(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_transport_parameters_received(
             &mut self,
             event: builder::TransportParametersReceived,
         ) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::TransportParametersReceived>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_transport_parameters_received(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_transport_parameters_received(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_transport_parameters_received(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_datagram_sent(&mut self, event: builder::DatagramSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::DatagramSent>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_datagram_sent(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_datagram_sent(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_datagram_sent(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_datagram_payload_sent(&mut self, event: builder::DatagramPayloadSent) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::DatagramPayloadSent>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_datagram_payload_sent(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_datagram_payload_sent(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_datagram_received(&mut self, event: builder::DatagramReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::DatagramReceived>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_datagram_received(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_datagram_received(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_datagram_received(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_datagram_dropped(&mut self, event: builder::DatagramDropped) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::DatagramDropped>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_datagram_dropped(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_datagram_dropped(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_datagram_dropped(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_connection_id_updated(&mut self, event: builder::ConnectionIdUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ConnectionIdUpdated>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_connection_id_updated(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_connection_id_updated(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_connection_id_updated(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_ecn_state_changed(&mut self, event: builder::EcnStateChanged) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::EcnStateChanged>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_ecn_state_changed(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_ecn_state_changed(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_ecn_state_changed(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_connection_migration_denied(&mut self, event: builder::ConnectionMigrationDenied) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ConnectionMigrationDenied>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_connection_migration_denied(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_connection_migration_denied(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_connection_migration_denied(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::HandshakeStatusUpdated>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_handshake_status_updated(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_handshake_status_updated(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_handshake_status_updated(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_handshake_timing(&mut self, event: builder::HandshakeTiming) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::HandshakeTiming>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_handshake_timing(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_handshake_timing(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_tls_exporter_ready(&mut self, event: builder::TlsExporterReady) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::TlsExporterReady>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_tls_exporter_ready(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_tls_exporter_ready(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_tls_exporter_ready(&self.meta, &event);
+            }
         }
         #[inline]
         fn on_path_challenge_updated(&mut self, event: builder::PathChallengeUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PathChallengeUpdated>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_path_challenge_updated(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_path_challenge_updated(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_tls_client_hello(&mut self, event: builder::TlsClientHello) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::TlsClientHello>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_tls_client_hello(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_tls_client_hello(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_tls_server_hello(&mut self, event: builder::TlsServerHello) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::TlsServerHello>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_tls_server_hello(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_tls_server_hello(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::RxStreamProgress>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_rx_stream_progress(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_rx_stream_progress(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_tx_stream_progress(&mut self, event: builder::TxStreamProgress) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::TxStreamProgress>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_tx_stream_progress(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_tx_stream_progress(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_keep_alive_timer_expired(&mut self, event: builder::KeepAliveTimerExpired) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::KeepAliveTimerExpired>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_keep_alive_timer_expired(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_keep_alive_timer_expired(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_mtu_updated(&mut self, event: builder::MtuUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::MtuUpdated>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
+            let event = event.into_event();
+            if is_sampled {
+                self.subscriber
+                    .on_mtu_updated(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_mtu_updated(&self.meta, &event);
+            }
+        }
+        #[inline]
+        fn on_slow_start_exited(&mut self, event: builder::SlowStartExited) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::SlowStartExited>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_path_challenge_updated(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_slow_start_exited(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_slow_start_exited(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_tls_client_hello(&mut self, event: builder::TlsClientHello) {
+        fn on_delivery_rate_sampled(&mut self, event: builder::DeliveryRateSampled) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::DeliveryRateSampled>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_tls_client_hello(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_delivery_rate_sampled(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_delivery_rate_sampled(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_tls_server_hello(&mut self, event: builder::TlsServerHello) {
+        fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PacingRateUpdated>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_tls_server_hello(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_pacing_rate_updated(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_pacing_rate_updated(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
+        fn on_congestion_state(&mut self, event: builder::CongestionState) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::CongestionState>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_rx_stream_progress(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_congestion_state(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_congestion_state(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_tx_stream_progress(&mut self, event: builder::TxStreamProgress) {
+        fn on_path_statistics(&mut self, event: builder::PathStatistics) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::PathStatistics>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_tx_stream_progress(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_path_statistics(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_path_statistics(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_keep_alive_timer_expired(&mut self, event: builder::KeepAliveTimerExpired) {
+        fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::BbrStateChanged>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_keep_alive_timer_expired(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_bbr_state_changed(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_bbr_state_changed(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_mtu_updated(&mut self, event: builder::MtuUpdated) {
+        fn on_dc_state_changed(&mut self, event: builder::DcStateChanged) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::DcStateChanged>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_mtu_updated(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_dc_state_changed(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_dc_state_changed(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_slow_start_exited(&mut self, event: builder::SlowStartExited) {
+        fn on_connection_evicted(&mut self, event: builder::ConnectionEvicted) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::ConnectionEvicted>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_slow_start_exited(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_connection_evicted(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_connection_evicted(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_delivery_rate_sampled(&mut self, event: builder::DeliveryRateSampled) {
+        fn on_socket_error_received(&mut self, event: builder::SocketErrorReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::SocketErrorReceived>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_delivery_rate_sampled(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_socket_error_received(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_socket_error_received(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_pacing_rate_updated(&mut self, event: builder::PacingRateUpdated) {
+        fn on_local_address_lost(&mut self, event: builder::LocalAddressLost) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::LocalAddressLost>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_pacing_rate_updated(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_local_address_lost(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_local_address_lost(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged) {
+        fn on_transmit_share(&mut self, event: builder::TransmitShare) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::TransmitShare>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_bbr_state_changed(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_transmit_share(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_transmit_share(&self.meta, &event);
+            }
         }
         #[inline]
-        fn on_dc_state_changed(&mut self, event: builder::DcStateChanged) {
+        fn on_version_negotiation_received(&mut self, event: builder::VersionNegotiationReceived) {
+            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+            let is_sampled = self
+                .subscriber
+                .sample_rate::<api::VersionNegotiationReceived>()
+                .sample(&SEQUENCE);
+            if !is_sampled && self.attached.is_none() {
+                return;
+            }
             let event = event.into_event();
-            self.subscriber
-                .on_dc_state_changed(self.context, &self.meta, &event);
-            self.subscriber
-                .on_connection_event(self.context, &self.meta, &event);
-            self.subscriber.on_event(&self.meta, &event);
+            if is_sampled {
+                self.subscriber
+                    .on_version_negotiation_received(self.context, &self.meta, &event);
+                self.subscriber
+                    .on_connection_event(self.context, &self.meta, &event);
+                self.subscriber.on_event(&self.meta, &event);
+            }
+            if let Some(attached) = self.attached.as_mut() {
+                attached.on_version_negotiation_received(&self.meta, &event);
+            }
         }
         #[inline]
         fn quic_version(&self) -> u32 {
@@ -6824,6 +11852,7 @@ pub mod testing {
         output: Vec<String>,
         pub application_protocol_information: u32,
         pub server_name_information: u32,
+        pub encrypted_client_hello: u32,
         pub packet_skipped: u32,
         pub packet_sent: u32,
         pub packet_received: u32,
@@ -6846,12 +11875,14 @@ pub mod testing {
         pub duplicate_packet: u32,
         pub transport_parameters_received: u32,
         pub datagram_sent: u32,
+        pub datagram_payload_sent: u32,
         pub datagram_received: u32,
         pub datagram_dropped: u32,
         pub connection_id_updated: u32,
         pub ecn_state_changed: u32,
         pub connection_migration_denied: u32,
         pub handshake_status_updated: u32,
+        pub handshake_timing: u32,
         pub tls_exporter_ready: u32,
         pub path_challenge_updated: u32,
         pub tls_client_hello: u32,
@@ -6863,18 +11894,28 @@ pub mod testing {
         pub slow_start_exited: u32,
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
+        pub congestion_state: u32,
+        pub path_statistics: u32,
         pub bbr_state_changed: u32,
         pub dc_state_changed: u32,
+        pub connection_evicted: u32,
+        pub socket_error_received: u32,
+        pub local_address_lost: u32,
+        pub transmit_share: u32,
+        pub version_negotiation_received: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
         pub endpoint_datagram_sent: u32,
         pub endpoint_datagram_received: u32,
         pub endpoint_datagram_dropped: u32,
+        pub endpoint_address_token_reused: u32,
         pub endpoint_connection_attempt_failed: u32,
+        pub endpoint_connection_attempt_retried: u32,
         pub platform_tx: u32,
         pub platform_tx_error: u32,
         pub platform_rx: u32,
+        pub platform_rx_overflow: u32,
         pub platform_rx_error: u32,
         pub platform_feature_configured: u32,
         pub platform_event_loop_wakeup: u32,
@@ -6905,6 +11946,7 @@ pub mod testing {
                 output: Default::default(),
                 application_protocol_information: 0,
                 server_name_information: 0,
+                encrypted_client_hello: 0,
                 packet_skipped: 0,
                 packet_sent: 0,
                 packet_received: 0,
@@ -6927,12 +11969,14 @@ pub mod testing {
                 duplicate_packet: 0,
                 transport_parameters_received: 0,
                 datagram_sent: 0,
+                datagram_payload_sent: 0,
                 datagram_received: 0,
                 datagram_dropped: 0,
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
                 handshake_status_updated: 0,
+                handshake_timing: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
@@ -6944,18 +11988,28 @@ pub mod testing {
                 slow_start_exited: 0,
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
+                congestion_state: 0,
+                path_statistics: 0,
                 bbr_state_changed: 0,
                 dc_state_changed: 0,
+                connection_evicted: 0,
+                socket_error_received: 0,
+                local_address_lost: 0,
+                transmit_share: 0,
+                version_negotiation_received: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
                 endpoint_datagram_sent: 0,
                 endpoint_datagram_received: 0,
                 endpoint_datagram_dropped: 0,
+                endpoint_address_token_reused: 0,
                 endpoint_connection_attempt_failed: 0,
+                endpoint_connection_attempt_retried: 0,
                 platform_tx: 0,
                 platform_tx_error: 0,
                 platform_rx: 0,
+                platform_rx_overflow: 0,
                 platform_rx_error: 0,
                 platform_feature_configured: 0,
                 platform_event_loop_wakeup: 0,
@@ -6993,6 +12047,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_encrypted_client_hello(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::EncryptedClientHello,
+        ) {
+            self.encrypted_client_hello += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_packet_skipped(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7236,6 +12301,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_datagram_payload_sent(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramPayloadSent,
+        ) {
+            self.datagram_payload_sent += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_datagram_received(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7302,6 +12378,17 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_handshake_timing(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::HandshakeTiming,
+        ) {
+            self.handshake_timing += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_tls_exporter_ready(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7423,6 +12510,28 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_congestion_state(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::CongestionState,
+        ) {
+            self.congestion_state += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_path_statistics(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PathStatistics,
+        ) {
+            self.path_statistics += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_bbr_state_changed(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -7445,6 +12554,61 @@ pub mod testing {
                 self.output.push(format!("{meta:?} {event:?}"));
             }
         }
+        fn on_connection_evicted(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionEvicted,
+        ) {
+            self.connection_evicted += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_socket_error_received(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::SocketErrorReceived,
+        ) {
+            self.socket_error_received += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_local_address_lost(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::LocalAddressLost,
+        ) {
+            self.local_address_lost += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_transmit_share(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TransmitShare,
+        ) {
+            self.transmit_share += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
+        fn on_version_negotiation_received(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::VersionNegotiationReceived,
+        ) {
+            self.version_negotiation_received += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{meta:?} {event:?}"));
+            }
+        }
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -7493,6 +12657,14 @@ pub mod testing {
             self.endpoint_datagram_dropped += 1;
             self.output.push(format!("{meta:?} {event:?}"));
         }
+        fn on_endpoint_address_token_reused(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointAddressTokenReused,
+        ) {
+            self.endpoint_address_token_reused += 1;
+            self.output.push(format!("{meta:?} {event:?}"));
+        }
         fn on_endpoint_connection_attempt_failed(
             &mut self,
             meta: &api::EndpointMeta,
@@ -7501,6 +12673,14 @@ pub mod testing {
             self.endpoint_connection_attempt_failed += 1;
             self.output.push(format!("{meta:?} {event:?}"));
         }
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointConnectionAttemptRetried,
+        ) {
+            self.endpoint_connection_attempt_retried += 1;
+            self.output.push(format!("{meta:?} {event:?}"));
+        }
         fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
             self.platform_tx += 1;
             self.output.push(format!("{meta:?} {event:?}"));
@@ -7513,6 +12693,14 @@ pub mod testing {
             self.platform_rx += 1;
             self.output.push(format!("{meta:?} {event:?}"));
         }
+        fn on_platform_rx_overflow(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformRxOverflow,
+        ) {
+            self.platform_rx_overflow += 1;
+            self.output.push(format!("{meta:?} {event:?}"));
+        }
         fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
             self.platform_rx_error += 1;
             self.output.push(format!("{meta:?} {event:?}"));
@@ -7548,6 +12736,7 @@ pub mod testing {
         output: Vec<String>,
         pub application_protocol_information: u32,
         pub server_name_information: u32,
+        pub encrypted_client_hello: u32,
         pub packet_skipped: u32,
         pub packet_sent: u32,
         pub packet_received: u32,
@@ -7570,12 +12759,14 @@ pub mod testing {
         pub duplicate_packet: u32,
         pub transport_parameters_received: u32,
         pub datagram_sent: u32,
+        pub datagram_payload_sent: u32,
         pub datagram_received: u32,
         pub datagram_dropped: u32,
         pub connection_id_updated: u32,
         pub ecn_state_changed: u32,
         pub connection_migration_denied: u32,
         pub handshake_status_updated: u32,
+        pub handshake_timing: u32,
         pub tls_exporter_ready: u32,
         pub path_challenge_updated: u32,
         pub tls_client_hello: u32,
@@ -7587,18 +12778,28 @@ pub mod testing {
         pub slow_start_exited: u32,
         pub delivery_rate_sampled: u32,
         pub pacing_rate_updated: u32,
+        pub congestion_state: u32,
+        pub path_statistics: u32,
         pub bbr_state_changed: u32,
         pub dc_state_changed: u32,
+        pub connection_evicted: u32,
+        pub socket_error_received: u32,
+        pub local_address_lost: u32,
+        pub transmit_share: u32,
+        pub version_negotiation_received: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
         pub endpoint_datagram_sent: u32,
         pub endpoint_datagram_received: u32,
         pub endpoint_datagram_dropped: u32,
+        pub endpoint_address_token_reused: u32,
         pub endpoint_connection_attempt_failed: u32,
+        pub endpoint_connection_attempt_retried: u32,
         pub platform_tx: u32,
         pub platform_tx_error: u32,
         pub platform_rx: u32,
+        pub platform_rx_overflow: u32,
         pub platform_rx_error: u32,
         pub platform_feature_configured: u32,
         pub platform_event_loop_wakeup: u32,
@@ -7619,6 +12820,7 @@ pub mod testing {
                 output: Default::default(),
                 application_protocol_information: 0,
                 server_name_information: 0,
+                encrypted_client_hello: 0,
                 packet_skipped: 0,
                 packet_sent: 0,
                 packet_received: 0,
@@ -7641,12 +12843,14 @@ pub mod testing {
                 duplicate_packet: 0,
                 transport_parameters_received: 0,
                 datagram_sent: 0,
+                datagram_payload_sent: 0,
                 datagram_received: 0,
                 datagram_dropped: 0,
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
                 handshake_status_updated: 0,
+                handshake_timing: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
@@ -7658,18 +12862,28 @@ pub mod testing {
                 slow_start_exited: 0,
                 delivery_rate_sampled: 0,
                 pacing_rate_updated: 0,
+                congestion_state: 0,
+                path_statistics: 0,
                 bbr_state_changed: 0,
                 dc_state_changed: 0,
+                connection_evicted: 0,
+                socket_error_received: 0,
+                local_address_lost: 0,
+                transmit_share: 0,
+                version_negotiation_received: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
                 endpoint_datagram_sent: 0,
                 endpoint_datagram_received: 0,
                 endpoint_datagram_dropped: 0,
+                endpoint_address_token_reused: 0,
                 endpoint_connection_attempt_failed: 0,
+                endpoint_connection_attempt_retried: 0,
                 platform_tx: 0,
                 platform_tx_error: 0,
                 platform_rx: 0,
+                platform_rx_overflow: 0,
                 platform_rx_error: 0,
                 platform_feature_configured: 0,
                 platform_event_loop_wakeup: 0,
@@ -7708,6 +12922,11 @@ pub mod testing {
             let event = event.into_event();
             self.output.push(format!("{event:?}"));
         }
+        fn on_endpoint_address_token_reused(&mut self, event: builder::EndpointAddressTokenReused) {
+            self.endpoint_address_token_reused += 1;
+            let event = event.into_event();
+            self.output.push(format!("{event:?}"));
+        }
         fn on_endpoint_connection_attempt_failed(
             &mut self,
             event: builder::EndpointConnectionAttemptFailed,
@@ -7716,6 +12935,14 @@ pub mod testing {
             let event = event.into_event();
             self.output.push(format!("{event:?}"));
         }
+        fn on_endpoint_connection_attempt_retried(
+            &mut self,
+            event: builder::EndpointConnectionAttemptRetried,
+        ) {
+            self.endpoint_connection_attempt_retried += 1;
+            let event = event.into_event();
+            self.output.push(format!("{event:?}"));
+        }
         fn on_platform_tx(&mut self, event: builder::PlatformTx) {
             self.platform_tx += 1;
             let event = event.into_event();
@@ -7731,6 +12958,11 @@ pub mod testing {
             let event = event.into_event();
             self.output.push(format!("{event:?}"));
         }
+        fn on_platform_rx_overflow(&mut self, event: builder::PlatformRxOverflow) {
+            self.platform_rx_overflow += 1;
+            let event = event.into_event();
+            self.output.push(format!("{event:?}"));
+        }
         fn on_platform_rx_error(&mut self, event: builder::PlatformRxError) {
             self.platform_rx_error += 1;
             let event = event.into_event();
@@ -7773,6 +13005,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_encrypted_client_hello(&mut self, event: builder::EncryptedClientHello) {
+            self.encrypted_client_hello += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_packet_skipped(&mut self, event: builder::PacketSkipped) {
             self.packet_skipped += 1;
             let event = event.into_event();
@@ -7931,6 +13170,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_datagram_payload_sent(&mut self, event: builder::DatagramPayloadSent) {
+            self.datagram_payload_sent += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_datagram_received(&mut self, event: builder::DatagramReceived) {
             self.datagram_received += 1;
             let event = event.into_event();
@@ -7973,6 +13219,13 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_handshake_timing(&mut self, event: builder::HandshakeTiming) {
+            self.handshake_timing += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_tls_exporter_ready(&mut self, event: builder::TlsExporterReady) {
             self.tls_exporter_ready += 1;
             let event = event.into_event();
@@ -8050,6 +13303,20 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_congestion_state(&mut self, event: builder::CongestionState) {
+            self.congestion_state += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_path_statistics(&mut self, event: builder::PathStatistics) {
+            self.path_statistics += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged) {
             self.bbr_state_changed += 1;
             let event = event.into_event();
@@ -8064,6 +13331,41 @@ pub mod testing {
                 self.output.push(format!("{event:?}"));
             }
         }
+        fn on_connection_evicted(&mut self, event: builder::ConnectionEvicted) {
+            self.connection_evicted += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_socket_error_received(&mut self, event: builder::SocketErrorReceived) {
+            self.socket_error_received += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_local_address_lost(&mut self, event: builder::LocalAddressLost) {
+            self.local_address_lost += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_transmit_share(&mut self, event: builder::TransmitShare) {
+            self.transmit_share += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
+        fn on_version_negotiation_received(&mut self, event: builder::VersionNegotiationReceived) {
+            self.version_negotiation_received += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{event:?}"));
+            }
+        }
         fn quic_version(&self) -> u32 {
             1
         }
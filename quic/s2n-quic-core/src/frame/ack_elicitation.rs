@@ -83,6 +83,7 @@ impl AckElicitable for crate::frame::DataBlocked {}
 //# Extension frames MUST be congestion controlled and MUST cause
 //# an ACK frame to be sent.
 impl AckElicitable for crate::frame::DcStatelessResetTokens<'_> {}
+impl<Data> AckElicitable for crate::frame::Custom<Data> {}
 impl AckElicitable for crate::frame::HandshakeDone {}
 impl AckElicitable for crate::frame::MaxData {}
 impl AckElicitable for crate::frame::MaxStreamData {}
@@ -99,8 +100,20 @@ impl AckElicitable for crate::frame::PathChallenge<'_> {}
 impl AckElicitable for crate::frame::PathResponse<'_> {}
 impl AckElicitable for crate::frame::Ping {}
 impl AckElicitable for crate::frame::ResetStream {}
+impl AckElicitable for crate::frame::ResetStreamAt {}
 impl AckElicitable for crate::frame::RetireConnectionId {}
 impl AckElicitable for crate::frame::StopSending {}
 impl<Data> AckElicitable for crate::frame::Stream<Data> {}
 impl AckElicitable for crate::frame::StreamDataBlocked {}
 impl AckElicitable for crate::frame::StreamsBlocked {}
+//= https://datatracker.ietf.org/doc/html/draft-huitema-quic-ts#section-3
+//# TIMESTAMP frames are not ack-eliciting.
+//
+// TIMESTAMP frames accompany ACK frames, so eliciting an acknowledgement for them would cause
+// every acknowledgement to elicit another one.
+impl AckElicitable for crate::frame::Timestamp {
+    #[inline]
+    fn ack_elicitation(&self) -> AckElicitation {
+        AckElicitation::NonEliciting
+    }
+}
@@ -29,6 +29,7 @@ impl CongestionControlled for crate::frame::DataBlocked {}
 //# Extension frames MUST be congestion controlled and MUST cause
 //# an ACK frame to be sent.
 impl CongestionControlled for crate::frame::DcStatelessResetTokens<'_> {}
+impl<Data> CongestionControlled for crate::frame::Custom<Data> {}
 impl CongestionControlled for crate::frame::HandshakeDone {}
 impl CongestionControlled for crate::frame::MaxData {}
 impl CongestionControlled for crate::frame::MaxStreamData {}
@@ -56,8 +57,16 @@ impl CongestionControlled for crate::frame::PathChallenge<'_> {}
 impl CongestionControlled for crate::frame::PathResponse<'_> {}
 impl CongestionControlled for crate::frame::Ping {}
 impl CongestionControlled for crate::frame::ResetStream {}
+impl CongestionControlled for crate::frame::ResetStreamAt {}
 impl CongestionControlled for crate::frame::RetireConnectionId {}
 impl CongestionControlled for crate::frame::StopSending {}
 impl CongestionControlled for crate::frame::StreamsBlocked {}
 impl CongestionControlled for crate::frame::StreamDataBlocked {}
+// TIMESTAMP frames are only sent alongside ACK frames, which aren't congestion controlled
+impl CongestionControlled for crate::frame::Timestamp {
+    #[inline]
+    fn is_congestion_controlled(&self) -> bool {
+        false
+    }
+}
 impl<Data> CongestionControlled for crate::frame::Stream<Data> {}
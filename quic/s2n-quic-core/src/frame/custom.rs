@@ -0,0 +1,97 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ExtensionTag, varint::VarInt};
+use s2n_codec::{
+    decoder_invariant, decoder_parameterized_value, DecoderBuffer, DecoderBufferMut, Encoder,
+    EncoderValue,
+};
+
+// CUSTOM Frame {
+//   Type (i),
+//   Length (i),
+//   Payload (..),
+// }
+//
+// CUSTOM frames carry frame types that are not known to the transport. The
+// payload is always prefixed with its length so the frame can be skipped
+// without understanding its contents.
+
+pub type CustomRef<'a> = Custom<&'a [u8]>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Custom<Data> {
+    /// The frame type, as negotiated by the application
+    pub frame_type: ExtensionTag,
+
+    /// The opaque payload of the frame
+    pub data: Data,
+}
+
+impl<Data> Custom<Data> {
+    #[inline]
+    pub fn tag(&self) -> ExtensionTag {
+        self.frame_type
+    }
+
+    /// Converts the frame data from one type to another
+    pub fn map_data<F: FnOnce(Data) -> Out, Out>(self, map: F) -> Custom<Out> {
+        Custom {
+            frame_type: self.frame_type,
+            data: map(self.data),
+        }
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a, Data> Custom<Data> {
+        fn decode(tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (data, buffer) = buffer.decode_with_len_prefix::<VarInt, Data>()?;
+
+            let frame = Custom {
+                frame_type: tag,
+                data,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl<Data: EncoderValue> EncoderValue for Custom<Data> {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.frame_type);
+        buffer.encode_with_len_prefix::<VarInt, _>(&self.data);
+    }
+}
+
+impl<'a> From<Custom<DecoderBuffer<'a>>> for CustomRef<'a> {
+    #[inline]
+    fn from(d: Custom<DecoderBuffer<'a>>) -> Self {
+        d.map_data(|data| data.into_less_safe_slice())
+    }
+}
+
+impl<'a> From<Custom<DecoderBufferMut<'a>>> for CustomRef<'a> {
+    #[inline]
+    fn from(d: Custom<DecoderBufferMut<'a>>) -> Self {
+        d.map_data(|data| &*data.into_less_safe_slice())
+    }
+}
+
+/// Ensures the frame type was encoded with the minimal number of bytes
+///
+/// Custom frames are decoded for any frame type not known to the transport so non-minimal
+/// encodings of known frame types must not be accepted as custom frames.
+#[inline]
+pub(crate) fn ensure_minimal_tag(
+    tag: ExtensionTag,
+    encoded_len: usize,
+) -> Result<(), s2n_codec::DecoderError> {
+    decoder_invariant!(
+        tag.encoding_size() == encoded_len,
+        "frame type is not minimally encoded"
+    );
+    Ok(())
+}
@@ -47,6 +47,33 @@ macro_rules! frames {
 
         pub type RemainingBuffer<'a> = Option<DecoderBufferMut<'a>>;
 
+        /// Returns `true` if the frame type is not handled by the transport and can be
+        /// carried in a [`Custom`] frame
+        #[inline]
+        pub fn is_custom_frame_type(frame_type: VarInt) -> bool {
+            let tag = frame_type.as_u64();
+
+            if tag < 0b0100_0000 {
+                match tag as Tag {
+                    $(
+                        $(
+                            $tag_macro!() => return false,
+                        )?
+                    )*
+                    _ => {}
+                }
+            }
+
+            match tag {
+                $(
+                    $(
+                        $extension_tag_macro!() => false,
+                    )?
+                )*
+                _ => true,
+            }
+        }
+
         #[derive(Debug, PartialEq, Eq)]
         pub enum Frame<'a, $ack, $data> {
             $(
@@ -143,7 +170,9 @@ macro_rules! frames {
 
             #[inline]
             fn handle_extension_frame(&mut self, buffer: DecoderBufferMut<'a>) -> DecoderBufferMutResult<'a, Self::Output> {
+                let len = buffer.len();
                 let (tag, buffer) = buffer.decode::<ExtensionTag>()?;
+                let tag_len = len - buffer.len();
 
                 match tag.as_u64() {
                     $(
@@ -156,8 +185,12 @@ macro_rules! frames {
                         )?
                     )*
                     _ => {
-                        let _ = buffer;
-                        Err(DecoderError::InvariantViolation("invalid frame"))
+                        // Frame types unknown to the transport are decoded as custom frames and
+                        // rejected by the connection unless an application handler accepts them
+                        custom::ensure_minimal_tag(tag, tag_len)?;
+                        let (frame, buffer) = buffer.decode_parameterized(tag)?;
+                        let output = self.handle_custom_frame(frame)?;
+                        Ok((output, buffer))
                     }
                 }
             }
@@ -263,7 +296,10 @@ frames! {
     [connection_close_tag] => connection_close, handle_connection_close_frame, ConnectionClose['a];
     [handshake_done_tag] => handshake_done, handle_handshake_done_frame, HandshakeDone;
     [datagram_tag] => datagram, handle_datagram_frame, Datagram[Data];
+    [reset_stream_at_tag] => reset_stream_at, handle_reset_stream_at_frame, ResetStreamAt;
     extension[dc_stateless_reset_tokens_tag] => dc_stateless_reset_tokens, handle_dc_stateless_reset_tokens_frame, DcStatelessResetTokens['a];
+    extension[timestamp_tag] => timestamp, handle_timestamp_frame, Timestamp;
+    => custom, handle_custom_frame, Custom[Data];
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -70,6 +70,7 @@ impl<Data> Probing for crate::frame::Crypto<Data> {}
 impl<Data> Probing for crate::frame::Datagram<Data> {}
 impl Probing for crate::frame::DataBlocked {}
 impl Probing for crate::frame::DcStatelessResetTokens<'_> {}
+impl<Data> Probing for crate::frame::Custom<Data> {}
 impl Probing for crate::frame::HandshakeDone {}
 impl Probing for crate::frame::MaxData {}
 impl Probing for crate::frame::MaxStreamData {}
@@ -101,11 +102,13 @@ impl Probing for crate::frame::PathResponse<'_> {
 }
 impl Probing for crate::frame::Ping {}
 impl Probing for crate::frame::ResetStream {}
+impl Probing for crate::frame::ResetStreamAt {}
 impl Probing for crate::frame::RetireConnectionId {}
 impl Probing for crate::frame::StopSending {}
 impl<Data> Probing for crate::frame::Stream<Data> {}
 impl Probing for crate::frame::StreamDataBlocked {}
 impl Probing for crate::frame::StreamsBlocked {}
+impl Probing for crate::frame::Timestamp {}
 
 //= https://www.rfc-editor.org/rfc/rfc9000#section-9.1
 //= type=test
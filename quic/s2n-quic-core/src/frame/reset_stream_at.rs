@@ -0,0 +1,119 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::varint::VarInt;
+use s2n_codec::decoder_invariant;
+
+//# https://datatracker.ietf.org/doc/html/draft-ietf-quic-reliable-stream-reset#section-4
+//# The RESET_STREAM_AT frame (type=0x24) is used to abruptly terminate
+//# the sending part of a stream, while guaranteeing delivery of stream
+//# data up to a certain byte offset.
+
+macro_rules! reset_stream_at_tag {
+    () => {
+        0x24u8
+    };
+}
+
+//# RESET_STREAM_AT Frame {
+//#   Type (i) = 0x24,
+//#   Stream ID (i),
+//#   Application Protocol Error Code (i),
+//#   Final Size (i),
+//#   Reliable Size (i),
+//# }
+
+//# In addition to the common Stream ID, Application Protocol Error Code
+//# and Final Size fields, RESET_STREAM_AT frames contain the following
+//# field:
+//#
+//# Reliable Size:  A variable-length integer encoding the number of bytes
+//# of stream data that the sender will deliver reliably.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ResetStreamAt {
+    /// A variable-length integer encoding of the Stream ID of the
+    /// stream being terminated.
+    pub stream_id: VarInt,
+
+    /// A variable-length integer containing the application protocol
+    /// error code which indicates why the stream is being closed.
+    pub application_error_code: VarInt,
+
+    /// A variable-length integer indicating the final size of
+    /// the stream by the RESET_STREAM_AT sender, in unit of bytes.
+    pub final_size: VarInt,
+
+    /// A variable-length integer indicating the amount of stream data
+    /// which will be delivered reliably, in unit of bytes.
+    pub reliable_size: VarInt,
+}
+
+impl ResetStreamAt {
+    pub const fn tag(&self) -> u8 {
+        reset_stream_at_tag!()
+    }
+}
+
+s2n_codec::decoder_parameterized_value!(
+    impl<'a> ResetStreamAt {
+        fn decode(_tag: crate::frame::Tag, buffer: Buffer) -> Result<Self> {
+            let (stream_id, buffer) = buffer.decode()?;
+            let (application_error_code, buffer) = buffer.decode()?;
+            let (final_size, buffer) = buffer.decode()?;
+            let (reliable_size, buffer) = buffer.decode()?;
+
+            //# If the Reliable Size is larger than the Final Size, the receiver
+            //# MUST close the connection with a connection error of type
+            //# FRAME_ENCODING_ERROR.
+            decoder_invariant!(
+                reliable_size <= final_size,
+                "reliable_size cannot exceed the final_size"
+            );
+
+            let frame = ResetStreamAt {
+                stream_id,
+                application_error_code,
+                final_size,
+                reliable_size,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl s2n_codec::EncoderValue for ResetStreamAt {
+    #[inline]
+    fn encode<E: s2n_codec::Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&reset_stream_at_tag!());
+        buffer.encode(&self.stream_id);
+        buffer.encode(&self.application_error_code);
+        buffer.encode(&self.final_size);
+        buffer.encode(&self.reliable_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::FrameMut;
+    use s2n_codec::{DecoderBufferMut, EncoderBuffer, EncoderValue};
+
+    #[test]
+    fn reliable_size_exceeding_final_size_test() {
+        let frame = ResetStreamAt {
+            stream_id: VarInt::from_u8(1),
+            application_error_code: VarInt::from_u8(2),
+            final_size: VarInt::from_u8(3),
+            reliable_size: VarInt::from_u8(4),
+        };
+
+        let mut bytes = [0u8; 16];
+        let len = frame.encoding_size();
+        frame.encode(&mut EncoderBuffer::new(&mut bytes[..len]));
+
+        let buffer = DecoderBufferMut::new(&mut bytes[..len]);
+        assert!(buffer.decode::<FrameMut>().is_err());
+    }
+}
@@ -20,3 +20,41 @@ fn round_trip() {
         }
     });
 }
+
+#[test]
+fn custom_frame_types() {
+    use crate::{frame::is_custom_frame_type, varint::VarInt};
+
+    for known in [
+        0x00u64, 0x01, 0x02, 0x08, 0x0f, 0x1e, 0x30, 0x31, 0x24, 0xdc0000,
+    ] {
+        assert!(
+            !is_custom_frame_type(VarInt::new(known).unwrap()),
+            "{known:#x}"
+        );
+    }
+
+    for custom in [0x1fu64, 0x3f, 0x40, 0x2ab2, 0xdc0001] {
+        assert!(
+            is_custom_frame_type(VarInt::new(custom).unwrap()),
+            "{custom:#x}"
+        );
+    }
+}
+
+#[test]
+fn custom_frame_non_minimal_tag() {
+    use crate::frame::FrameMut;
+    use s2n_codec::DecoderBufferMut;
+
+    // 0x1f encoded in 2 bytes instead of 1
+    let mut bytes = [0x40, 0x1f, 0x00];
+    assert!(DecoderBufferMut::new(&mut bytes)
+        .decode::<FrameMut>()
+        .is_err());
+
+    let mut bytes = [0x1f, 0x00];
+    assert!(DecoderBufferMut::new(&mut bytes)
+        .decode::<FrameMut>()
+        .is_ok());
+}
@@ -0,0 +1,59 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ExtensionTag, varint::VarInt};
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+//# https://datatracker.ietf.org/doc/html/draft-huitema-quic-ts#section-3
+//# TIMESTAMP frames are used to carry the time at which a packet was
+//# sent, as measured by the sender's clock.
+
+const TAG: VarInt = VarInt::from_u32(0x02f5);
+
+macro_rules! timestamp_tag {
+    () => {
+        0x02f5u64
+    };
+}
+
+//# TIMESTAMP Frame {
+//#   Type (i) = 0x2f5,
+//#   Timestamp (i),
+//# }
+
+//# TIMESTAMP frames contain the following fields:
+//#
+//# Timestamp:  A variable-length integer representing the time at which
+//#    the packet was sent, in microseconds since the start of the
+//#    connection, divided by 2 to the power of the ack_delay_exponent
+//#    transport parameter sent by the sender of the frame.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Timestamp {
+    /// The scaled time at which the packet was sent
+    pub timestamp: VarInt,
+}
+
+impl Timestamp {
+    pub const fn tag(&self) -> ExtensionTag {
+        TAG
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> Timestamp {
+        fn decode(_tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (timestamp, buffer) = buffer.decode()?;
+            let frame = Timestamp { timestamp };
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for Timestamp {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&TAG);
+        buffer.encode(&self.timestamp);
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-#![cfg_attr(kani, allow(dead_code))]
+// the optimized implementations are only probed when `once_cell` is available to cache the result
+#![cfg_attr(any(kani, not(feature = "once_cell")), allow(dead_code))]
 
 use super::{Accumulator, LargeWriteFn, State, LARGE_WRITE_LEN};
 
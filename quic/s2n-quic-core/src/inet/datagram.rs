@@ -36,4 +36,7 @@ pub struct AncillaryData {
     pub local_interface: Option<u32>,
     /// Set when the packet buffer is an aggregate of multiple received packets
     pub segment_size: u16,
+    /// The cumulative number of datagrams the OS dropped because the socket's receive queue was
+    /// full, if reported with the datagram
+    pub rx_queue_overflow: Option<u32>,
 }
@@ -5,7 +5,7 @@ use crate::{
     endpoint::Endpoint,
     event::{self, EndpointPublisher},
     io::{rx::Rx, tx::Tx},
-    task::cooldown::Cooldown,
+    task::{budget::Budget, cooldown::Cooldown},
     time::clock::{ClockWithTimer, Timer},
 };
 use core::pin::Pin;
@@ -19,6 +19,7 @@ pub struct EventLoop<E, C, R, T> {
     pub rx: R,
     pub tx: T,
     pub cooldown: Cooldown,
+    pub budget: Budget,
 }
 
 impl<E, C, R, T> EventLoop<E, C, R, T>
@@ -36,6 +37,7 @@ where
             mut rx,
             mut tx,
             mut cooldown,
+            mut budget,
         } = self;
 
         /// Creates a event publisher with the endpoint's subscriber
@@ -83,6 +85,9 @@ where
 
             let select = cooldown.wrap(select);
 
+            // Yield to the runtime if the endpoint has been busy for too many iterations
+            let select = budget.wrap(select);
+
             let select::Outcome {
                 rx_result,
                 tx_result,
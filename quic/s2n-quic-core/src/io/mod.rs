@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod event_loop;
+#[cfg(feature = "std")]
+pub mod pcapng;
 pub mod rx;
 pub mod tx;
 
@@ -0,0 +1,275 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes captured datagrams to the [pcapng](https://www.ietf.org/archive/id/draft-ietf-opsawg-pcapng-02.html)
+//! file format
+//!
+//! Datagrams are written with synthetic IP and UDP headers so the resulting file can be opened
+//! directly by tools like Wireshark.
+
+use crate::inet::checksum::checksum;
+use core::time::Duration;
+use std::{
+    io::{self, Write},
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+};
+
+const SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+const ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+const DECRYPTION_SECRETS_BLOCK: u32 = 0x0000_000A;
+
+/// Secrets in the [NSS key log format](https://developer.mozilla.org/en-US/docs/Mozilla/Projects/NSS/Key_Log_Format)
+const SECRETS_TLS_KEY_LOG: u32 = 0x544C_534B;
+
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// Raw IP packets, starting with either an IPv4 or IPv6 header
+const LINKTYPE_RAW: u16 = 101;
+
+const OPT_END: u16 = 0;
+const OPT_COMMENT: u16 = 1;
+const SHB_USERAPPL: u16 = 4;
+const IF_TSRESOL: u16 = 9;
+const EPB_FLAGS: u16 = 2;
+
+/// Timestamps are written with nanosecond resolution
+const TSRESOL_NANOS: u8 = 9;
+
+const IPV4_HEADER_LEN: usize = 20;
+const IPV6_HEADER_LEN: usize = 40;
+const UDP_HEADER_LEN: usize = 8;
+const UDP_PROTOCOL: u8 = 17;
+const DEFAULT_TTL: u8 = 64;
+
+/// The direction of a captured datagram, relative to the local endpoint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+impl Direction {
+    #[inline]
+    fn flags(self) -> u32 {
+        match self {
+            Self::Inbound => 0b01,
+            Self::Outbound => 0b10,
+        }
+    }
+}
+
+/// A datagram to be written to the capture
+#[derive(Clone, Copy, Debug)]
+pub struct Datagram<'a> {
+    /// The time at which the datagram was captured, relative to the UNIX epoch
+    pub timestamp: Duration,
+    pub direction: Direction,
+    pub local_address: SocketAddr,
+    pub remote_address: SocketAddr,
+    /// The captured UDP payload, which may be truncated
+    pub payload: &'a [u8],
+    /// The length of the UDP payload before it was truncated
+    pub payload_len: usize,
+    /// An optional comment attached to the datagram
+    pub comment: Option<&'a str>,
+}
+
+/// Writes pcapng blocks to the underlying output
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    output: W,
+    block: Vec<u8>,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new writer and writes the section header and interface description blocks
+    pub fn new(output: W) -> io::Result<Self> {
+        let mut writer = Self {
+            output,
+            block: Vec::new(),
+        };
+
+        writer.write_block(SECTION_HEADER_BLOCK, |block| {
+            put_u32(block, BYTE_ORDER_MAGIC);
+            // major and minor versions
+            put_u16(block, 1);
+            put_u16(block, 0);
+            // the section length isn't known ahead of time
+            block.extend_from_slice(&(-1i64).to_le_bytes());
+            put_option(block, SHB_USERAPPL, b"s2n-quic");
+            put_option(block, OPT_END, &[]);
+        })?;
+
+        writer.write_block(INTERFACE_DESCRIPTION_BLOCK, |block| {
+            put_u16(block, LINKTYPE_RAW);
+            // reserved
+            put_u16(block, 0);
+            // the snap length is unlimited
+            put_u32(block, 0);
+            put_option(block, IF_TSRESOL, &[TSRESOL_NANOS]);
+            put_option(block, OPT_END, &[]);
+        })?;
+
+        Ok(writer)
+    }
+
+    /// Writes a datagram as an enhanced packet block
+    pub fn write_datagram(&mut self, datagram: &Datagram) -> io::Result<()> {
+        let (source, destination) = match datagram.direction {
+            Direction::Inbound => (datagram.remote_address, datagram.local_address),
+            Direction::Outbound => (datagram.local_address, datagram.remote_address),
+        };
+
+        let mut headers = [0u8; IPV6_HEADER_LEN + UDP_HEADER_LEN];
+        let headers = encode_headers(&mut headers, source, destination, datagram.payload_len);
+
+        let captured_len = headers.len() + datagram.payload.len();
+        let original_len = headers.len() + datagram.payload_len;
+        let timestamp = datagram.timestamp.as_nanos() as u64;
+
+        self.write_block(ENHANCED_PACKET_BLOCK, |block| {
+            // the capture only contains a single interface
+            put_u32(block, 0);
+            put_u32(block, (timestamp >> 32) as u32);
+            put_u32(block, timestamp as u32);
+            put_u32(block, captured_len as u32);
+            put_u32(block, original_len as u32);
+            block.extend_from_slice(headers);
+            block.extend_from_slice(datagram.payload);
+            pad(block);
+            put_option(block, EPB_FLAGS, &datagram.direction.flags().to_le_bytes());
+            if let Some(comment) = datagram.comment {
+                put_option(block, OPT_COMMENT, comment.as_bytes());
+            }
+            put_option(block, OPT_END, &[]);
+        })
+    }
+
+    /// Writes TLS secrets in the NSS key log format as a decryption secrets block
+    ///
+    /// Secrets need to be written before any of the datagrams that are protected by them for
+    /// tools to decrypt the capture.
+    pub fn write_key_log(&mut self, key_log: &[u8]) -> io::Result<()> {
+        if key_log.is_empty() {
+            return Ok(());
+        }
+
+        self.write_block(DECRYPTION_SECRETS_BLOCK, |block| {
+            put_u32(block, SECRETS_TLS_KEY_LOG);
+            put_u32(block, key_log.len() as u32);
+            block.extend_from_slice(key_log);
+            pad(block);
+        })
+    }
+
+    /// Flushes the underlying output
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+
+    /// Returns the underlying output
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_block(&mut self, block_type: u32, body: impl FnOnce(&mut Vec<u8>)) -> io::Result<()> {
+        let block = &mut self.block;
+        block.clear();
+        put_u32(block, block_type);
+        // reserve space for the total length, which is filled in after the body is written
+        put_u32(block, 0);
+        body(block);
+        debug_assert_eq!(block.len() % 4, 0, "blocks must be 32-bit aligned");
+
+        // the total length is written both before and after the body
+        let len = (block.len() + 4) as u32;
+        block[4..8].copy_from_slice(&len.to_le_bytes());
+        put_u32(block, len);
+
+        self.output.write_all(block)
+    }
+}
+
+/// Writes the IP and UDP headers for a datagram into `buffer` and returns the written portion
+fn encode_headers(
+    buffer: &mut [u8; IPV6_HEADER_LEN + UDP_HEADER_LEN],
+    source: SocketAddr,
+    destination: SocketAddr,
+    payload_len: usize,
+) -> &[u8] {
+    let udp_len = (UDP_HEADER_LEN + payload_len) as u16;
+
+    let ip_len = match (source.ip(), destination.ip()) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            let header = &mut buffer[..IPV4_HEADER_LEN];
+            // version 4 with a 5-word header
+            header[0] = 0x45;
+            header[2..4].copy_from_slice(&(IPV4_HEADER_LEN as u16 + udp_len).to_be_bytes());
+            // don't fragment
+            header[6] = 0x40;
+            header[8] = DEFAULT_TTL;
+            header[9] = UDP_PROTOCOL;
+            header[12..16].copy_from_slice(&source.octets());
+            header[16..20].copy_from_slice(&destination.octets());
+            let checksum = checksum(header);
+            header[10..12].copy_from_slice(&checksum.to_be_bytes());
+            IPV4_HEADER_LEN
+        }
+        (source, destination) => {
+            // if either of the addresses is IPv6 then both need to be mapped
+            let to_ipv6 = |ip: IpAddr| -> Ipv6Addr {
+                match ip {
+                    IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+                    IpAddr::V6(ip) => ip,
+                }
+            };
+
+            let header = &mut buffer[..IPV6_HEADER_LEN];
+            header[0] = 0x60;
+            header[4..6].copy_from_slice(&udp_len.to_be_bytes());
+            header[6] = UDP_PROTOCOL;
+            header[7] = DEFAULT_TTL;
+            header[8..24].copy_from_slice(&to_ipv6(source).octets());
+            header[24..40].copy_from_slice(&to_ipv6(destination).octets());
+            IPV6_HEADER_LEN
+        }
+    };
+
+    let header = &mut buffer[ip_len..ip_len + UDP_HEADER_LEN];
+    header[0..2].copy_from_slice(&source.port().to_be_bytes());
+    header[2..4].copy_from_slice(&destination.port().to_be_bytes());
+    header[4..6].copy_from_slice(&udp_len.to_be_bytes());
+    // the payload may be truncated so the UDP checksum is omitted
+    header[6..8].copy_from_slice(&[0, 0]);
+
+    &buffer[..ip_len + UDP_HEADER_LEN]
+}
+
+#[inline]
+fn put_u16(block: &mut Vec<u8>, value: u16) {
+    block.extend_from_slice(&value.to_le_bytes());
+}
+
+#[inline]
+fn put_u32(block: &mut Vec<u8>, value: u32) {
+    block.extend_from_slice(&value.to_le_bytes());
+}
+
+#[inline]
+fn put_option(block: &mut Vec<u8>, code: u16, value: &[u8]) {
+    put_u16(block, code);
+    put_u16(block, value.len() as u16);
+    block.extend_from_slice(value);
+    pad(block);
+}
+
+/// Pads the block to a 32-bit boundary
+#[inline]
+fn pad(block: &mut Vec<u8>) {
+    let len = (block.len() + 3) & !3;
+    block.resize(len, 0);
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,137 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Splits the capture into `(block_type, body)` pairs, checking the framing of each block
+fn blocks(mut capture: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut blocks = vec![];
+    while !capture.is_empty() {
+        let block_type = u32::from_le_bytes(capture[0..4].try_into().unwrap());
+        let len = u32::from_le_bytes(capture[4..8].try_into().unwrap()) as usize;
+        assert_eq!(len % 4, 0);
+        let trailer = u32::from_le_bytes(capture[len - 4..len].try_into().unwrap()) as usize;
+        assert_eq!(len, trailer);
+        blocks.push((block_type, &capture[8..len - 4]));
+        capture = &capture[len..];
+    }
+    blocks
+}
+
+fn datagram<'a>(local: &str, remote: &str, payload: &'a [u8], payload_len: usize) -> Datagram<'a> {
+    Datagram {
+        timestamp: Duration::from_secs(1),
+        direction: Direction::Outbound,
+        local_address: local.parse().unwrap(),
+        remote_address: remote.parse().unwrap(),
+        payload,
+        payload_len,
+        comment: Some("OneRtt { number: 1 }"),
+    }
+}
+
+#[test]
+fn header_blocks() {
+    let writer = Writer::new(vec![]).unwrap();
+    let capture = writer.into_inner();
+    let blocks = blocks(&capture);
+
+    assert_eq!(blocks.len(), 2);
+    let (block_type, body) = blocks[0];
+    assert_eq!(block_type, SECTION_HEADER_BLOCK);
+    assert_eq!(&body[0..4], &BYTE_ORDER_MAGIC.to_le_bytes());
+    let (block_type, body) = blocks[1];
+    assert_eq!(block_type, INTERFACE_DESCRIPTION_BLOCK);
+    assert_eq!(&body[0..2], &LINKTYPE_RAW.to_le_bytes());
+}
+
+#[test]
+fn ipv4_datagram() {
+    let mut writer = Writer::new(vec![]).unwrap();
+    let payload = [1u8; 13];
+    writer
+        .write_datagram(&datagram(
+            "10.0.0.1:4433",
+            "10.0.0.2:443",
+            &payload,
+            payload.len(),
+        ))
+        .unwrap();
+    let capture = writer.into_inner();
+    let blocks = blocks(&capture);
+
+    let (block_type, body) = blocks[2];
+    assert_eq!(block_type, ENHANCED_PACKET_BLOCK);
+    let captured_len = u32::from_le_bytes(body[12..16].try_into().unwrap()) as usize;
+    let original_len = u32::from_le_bytes(body[16..20].try_into().unwrap()) as usize;
+    assert_eq!(
+        captured_len,
+        IPV4_HEADER_LEN + UDP_HEADER_LEN + payload.len()
+    );
+    assert_eq!(captured_len, original_len);
+
+    let packet = &body[20..20 + captured_len];
+    let (ip, rest) = packet.split_at(IPV4_HEADER_LEN);
+    assert_eq!(ip[0], 0x45);
+    assert_eq!(&ip[12..16], &[10, 0, 0, 1]);
+    assert_eq!(&ip[16..20], &[10, 0, 0, 2]);
+    // a valid header checksums to zero
+    assert_eq!(checksum(ip), 0xffff);
+
+    let (udp, rest) = rest.split_at(UDP_HEADER_LEN);
+    assert_eq!(&udp[0..2], &4433u16.to_be_bytes());
+    assert_eq!(&udp[2..4], &443u16.to_be_bytes());
+    assert_eq!(rest, &payload);
+}
+
+#[test]
+fn truncated_ipv6_datagram() {
+    let mut writer = Writer::new(vec![]).unwrap();
+    let payload = [1u8; 16];
+    writer
+        .write_datagram(&datagram("[::1]:4433", "127.0.0.1:443", &payload, 1200))
+        .unwrap();
+    let capture = writer.into_inner();
+    let blocks = blocks(&capture);
+
+    let (_, body) = blocks[2];
+    let captured_len = u32::from_le_bytes(body[12..16].try_into().unwrap()) as usize;
+    let original_len = u32::from_le_bytes(body[16..20].try_into().unwrap()) as usize;
+    assert_eq!(
+        captured_len,
+        IPV6_HEADER_LEN + UDP_HEADER_LEN + payload.len()
+    );
+    assert_eq!(original_len, IPV6_HEADER_LEN + UDP_HEADER_LEN + 1200);
+
+    let packet = &body[20..20 + captured_len];
+    assert_eq!(packet[0] >> 4, 6);
+    // the headers reflect the original length of the datagram
+    assert_eq!(&packet[4..6], &(1208u16).to_be_bytes());
+    // the IPv4 address is mapped
+    let destination: [u8; 16] = packet[24..40].try_into().unwrap();
+    assert_eq!(
+        Ipv6Addr::from(destination),
+        "127.0.0.1"
+            .parse::<std::net::Ipv4Addr>()
+            .unwrap()
+            .to_ipv6_mapped()
+    );
+}
+
+#[test]
+fn key_log() {
+    let mut writer = Writer::new(vec![]).unwrap();
+    let key_log = b"CLIENT_HANDSHAKE_TRAFFIC_SECRET 00 01\n";
+    writer.write_key_log(key_log).unwrap();
+    // empty key logs aren't written
+    writer.write_key_log(&[]).unwrap();
+    let capture = writer.into_inner();
+    let blocks = blocks(&capture);
+
+    assert_eq!(blocks.len(), 3);
+    let (block_type, body) = blocks[2];
+    assert_eq!(block_type, DECRYPTION_SECRETS_BLOCK);
+    assert_eq!(&body[0..4], &SECRETS_TLS_KEY_LOG.to_le_bytes());
+    let len = u32::from_le_bytes(body[4..8].try_into().unwrap()) as usize;
+    assert_eq!(&body[8..8 + len], key_log);
+}
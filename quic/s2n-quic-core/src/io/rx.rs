@@ -1,7 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{event, inet::datagram, path};
+use crate::{
+    event,
+    inet::datagram,
+    path::{self, RemoteAddress},
+};
 use core::task::{Context, Poll};
 
 pub mod pair;
@@ -54,6 +58,54 @@ pub trait Queue {
     /// Iterates over all of the packets in the receive queue and processes them
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, on_packet: F);
 
+    /// Iterates over all of the errors reported by the network for previously transmitted datagrams
+    ///
+    /// The callback is provided with the remote address the datagram was sent to, the reported
+    /// error, and the prefix of the datagram that was quoted in the error. Queues which don't
+    /// receive errors from the network can rely on the default implementation.
+    #[inline]
+    fn for_each_error<F: FnMut(RemoteAddress, SocketError, &[u8])>(&mut self, on_error: F) {
+        let _ = on_error;
+    }
+
+    /// Returns the number of datagrams the platform dropped since the last call because the
+    /// receive queue overflowed
+    ///
+    /// Queues which aren't able to detect dropped datagrams can rely on the default
+    /// implementation.
+    #[inline]
+    fn take_dropped_datagrams(&mut self) -> usize {
+        0
+    }
+
     /// Returns if there are items in the queue or not
     fn is_empty(&self) -> bool;
 }
+
+/// An error reported by the network for a previously transmitted datagram
+///
+/// These are typically derived from ICMP messages, which are not authenticated. As such, they
+/// should only be treated as hints about the state of the path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SocketError {
+    /// The peer's host reported that nothing is listening on the destination port
+    PortUnreachable,
+
+    /// The peer's host or network could not be reached
+    HostUnreachable,
+
+    /// The datagram exceeded the MTU of a link on the path
+    ///
+    /// The reported `mtu` includes the IP and UDP headers.
+    PacketTooBig { mtu: u16 },
+
+    /// The datagram couldn't be sent from the local address of its path
+    ///
+    /// This is reported by the local host rather than the network, typically because the address
+    /// was removed from the interface it was assigned to.
+    ///
+    /// Only sockets bound to a wildcard address report this error, since the path can't be moved
+    /// to another local address otherwise.
+    LocalAddressUnavailable,
+}
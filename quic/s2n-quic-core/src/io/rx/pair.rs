@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::Rx;
-use crate::{event, inet::datagram};
+use crate::{event, inet::datagram, path::RemoteAddress};
 use core::task::{Context, Poll};
 
 /// A pair of Rx channels that feed into the same endpoint
@@ -125,6 +125,21 @@ where
         self.b.for_each(&mut on_packet);
     }
 
+    #[inline]
+    fn for_each_error<F: FnMut(RemoteAddress, super::SocketError, &[u8])>(
+        &mut self,
+        mut on_error: F,
+    ) {
+        // drain both of the channels
+        self.a.for_each_error(&mut on_error);
+        self.b.for_each_error(&mut on_error);
+    }
+
+    #[inline]
+    fn take_dropped_datagrams(&mut self) -> usize {
+        self.a.take_dropped_datagrams() + self.b.take_dropped_datagrams()
+    }
+
     #[inline]
     fn is_empty(&self) -> bool {
         self.a.is_empty() && self.b.is_empty()
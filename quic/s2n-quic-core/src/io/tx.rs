@@ -85,6 +85,10 @@ pub trait Queue {
     /// Set to true if the queue supports setting IPv6 flow labels
     const SUPPORTS_FLOW_LABELS: bool = false;
 
+    /// Set to true if the queue calls [`Message::finish_payload`] with the complete payload of
+    /// every message, including all of its GSO segments, before it is transmitted
+    const SUPPORTS_FINISH_PAYLOAD: bool = false;
+
     /// Pushes a message into the transmission queue
     ///
     /// The index of the message is returned to enable further operations to be
@@ -99,6 +103,17 @@ pub trait Queue {
         // default as no-op
     }
 
+    /// Calls `f` with the payload of the message which is still waiting on more GSO segments, if any
+    ///
+    /// Queues which set `SUPPORTS_FINISH_PAYLOAD` call [`Message::finish_payload`] on their own
+    /// when a message is complete. This allows the last writer of a message to finish it before
+    /// the queue is used by another writer.
+    #[inline]
+    fn finish_payload<F: FnOnce(&mut [u8])>(&mut self, f: F) {
+        // default as no-op
+        let _ = f;
+    }
+
     /// Returns the number of remaining datagrams that can be transmitted
     fn capacity(&self) -> usize;
 
@@ -154,6 +169,57 @@ pub trait Message {
 
     /// Writes the payload of the message to an output buffer
     fn write_payload(&mut self, buffer: PayloadBuffer, gso_offset: usize) -> Result<usize, Error>;
+
+    /// Finishes the complete payload of a message before it is transmitted
+    ///
+    /// This is only called by queues which set [`Queue::SUPPORTS_FINISH_PAYLOAD`]. The payload
+    /// contains all of the GSO segments written to the message, which may include segments
+    /// written by previous messages of the same writer. Messages can use this to defer work
+    /// which is more efficient to perform on multiple datagrams at once.
+    #[inline]
+    fn finish_payload(&mut self, payload: &mut [u8]) {
+        // default as no-op
+        let _ = payload;
+    }
+}
+
+impl<M: Message> Message for &mut M {
+    type Handle = M::Handle;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        (**self).path_handle()
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        (**self).ecn()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> Duration {
+        (**self).delay()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        (**self).ipv6_flow_label()
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool {
+        (**self).can_gso(segment_len, segment_count)
+    }
+
+    #[inline]
+    fn write_payload(&mut self, buffer: PayloadBuffer, gso_offset: usize) -> Result<usize, Error> {
+        (**self).write_payload(buffer, gso_offset)
+    }
+
+    #[inline]
+    fn finish_payload(&mut self, payload: &mut [u8]) {
+        (**self).finish_payload(payload)
+    }
 }
 
 #[derive(Debug)]
@@ -93,6 +93,7 @@ where
     const SUPPORTS_ECN: bool = Tx::SUPPORTS_ECN;
     const SUPPORTS_PACING: bool = Tx::SUPPORTS_PACING;
     const SUPPORTS_FLOW_LABELS: bool = Tx::SUPPORTS_FLOW_LABELS;
+    const SUPPORTS_FINISH_PAYLOAD: bool = Tx::SUPPORTS_FINISH_PAYLOAD;
 
     #[inline]
     fn push<M: tx::Message<Handle = Self::Handle>>(
@@ -104,6 +105,11 @@ where
         self.tx.push(message)
     }
 
+    #[inline]
+    fn finish_payload<F: FnOnce(&mut [u8])>(&mut self, f: F) {
+        self.tx.finish_payload(f)
+    }
+
     #[inline]
     fn capacity(&self) -> usize {
         self.tx.capacity()
@@ -161,6 +167,11 @@ where
     ) -> Result<usize, tx::Error> {
         self.inner.write_payload(buffer, gso_offset)
     }
+
+    #[inline]
+    fn finish_payload(&mut self, payload: &mut [u8]) {
+        self.inner.finish_payload(payload)
+    }
 }
 
 #[cfg(test)]
@@ -22,9 +22,11 @@ pub mod connection;
 pub mod counter;
 pub mod crypto;
 pub mod ct;
+pub mod custom_frame;
 pub mod datagram;
 #[cfg(feature = "alloc")]
 pub mod dc;
+pub mod demux;
 pub mod endpoint;
 pub mod event;
 pub mod frame;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    crypto::{HeaderKey, Key as CryptoKey, ProtectedPayload},
+    crypto::{EncryptedPayload, HeaderKey, Key as CryptoKey, ProtectedPayload, UnsealedPacket},
     packet::{
         number::{PacketNumber, PacketNumberLen},
         stateless_reset,
@@ -113,13 +113,112 @@ pub trait PacketEncoder<K: CryptoKey, H: HeaderKey, Payload: PacketPayloadEncode
 
     // Encodes, encrypts, and header-protects a packet into a buffer
     fn encode_packet<'a>(
-        mut self,
+        self,
         key: &mut K,
         header_key: &H,
         largest_acknowledged_packet_number: PacketNumber,
         min_packet_len: Option<usize>,
-        mut buffer: EncoderBuffer<'a>,
+        buffer: EncoderBuffer<'a>,
     ) -> Result<(ProtectedPayload<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+        let (encrypted_payload, remaining) = self.encode_sealed_packet(
+            key,
+            header_key,
+            largest_acknowledged_packet_number,
+            min_packet_len,
+            buffer,
+        )?;
+
+        // Protect the packet
+        let protected_payload = crate::crypto::protect(header_key, encrypted_payload)
+            .expect("header protection should always work");
+
+        // SUCCESS!!!
+
+        Ok((protected_payload, remaining))
+    }
+
+    // Encodes and encrypts a packet into a buffer, leaving header protection to the caller
+    //
+    // This allows the header protection of multiple packets to be applied at once with a
+    // `HeaderProtectionBatch`.
+    fn encode_sealed_packet<'a>(
+        self,
+        key: &mut K,
+        header_key: &H,
+        largest_acknowledged_packet_number: PacketNumber,
+        min_packet_len: Option<usize>,
+        buffer: EncoderBuffer<'a>,
+    ) -> Result<(EncryptedPayload<'a>, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+        let (packet, buffer) = self.write_packet(
+            key,
+            header_key,
+            largest_acknowledged_packet_number,
+            min_packet_len,
+            buffer,
+        )?;
+
+        // Encrypt the written payload. Note that the tag is appended to the
+        // buffer in the `encrypt` function.
+        let (encrypted_payload, remaining) = crate::crypto::encrypt(
+            key,
+            packet.packet_number,
+            packet.packet_number_len,
+            packet.header_len,
+            buffer,
+        )
+        .expect("encryption should always work");
+
+        Ok((encrypted_payload, remaining))
+    }
+
+    // Encodes a packet into a buffer, leaving encryption and header protection to the caller
+    //
+    // Space for the crypto tag is reserved after the payload. This allows all of the packets in a
+    // buffer to be sealed with `crate::crypto::seal` once the buffer has been filled.
+    fn encode_unsealed_packet<'a>(
+        self,
+        key: &K,
+        header_key: &H,
+        largest_acknowledged_packet_number: PacketNumber,
+        min_packet_len: Option<usize>,
+        buffer: EncoderBuffer<'a>,
+    ) -> Result<(UnsealedPacket, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+        let (mut packet, mut buffer) = self.write_packet(
+            key,
+            header_key,
+            largest_acknowledged_packet_number,
+            min_packet_len,
+            buffer,
+        )?;
+
+        // The extra chunk can't be held until the packet is sealed so it's copied inline
+        let (mut buffer, _extra) = {
+            buffer.flatten();
+            buffer.into_inner()
+        };
+
+        // reserve bytes for the tag
+        buffer.advance_position(key.tag_len());
+        packet.len = buffer.len();
+
+        let (_packet, remaining) = buffer.split_off();
+
+        Ok((packet, EncoderBuffer::new(remaining)))
+    }
+
+    // Writes the header and payload of a packet into a buffer
+    //
+    // The returned buffer contains the packet, without the crypto tag. The payload may end with an
+    // extra chunk which hasn't been copied into the buffer yet. The `len` of the returned packet
+    // isn't set.
+    fn write_packet<'a>(
+        mut self,
+        key: &K,
+        header_key: &H,
+        largest_acknowledged_packet_number: PacketNumber,
+        min_packet_len: Option<usize>,
+        mut buffer: EncoderBuffer<'a>,
+    ) -> Result<(UnsealedPacket, scatter::Buffer<'a>), PacketEncodingError<'a>> {
         let packet_number = self.packet_number();
 
         // Truncate the packet number from the largest_acknowledged_packet_number.
@@ -268,18 +367,13 @@ pub trait PacketEncoder<K: CryptoKey, H: HeaderKey, Payload: PacketPayloadEncode
         buffer.advance_position(inline_len);
         let buffer = scatter::Buffer::new_with_extra(buffer, extra);
 
-        // Encrypt the written payload. Note that the tag is appended to the
-        // buffer in the `encrypt` function.
-        let (encrypted_payload, remaining) =
-            crate::crypto::encrypt(key, packet_number, packet_number_len, header_len, buffer)
-                .expect("encryption should always work");
-
-        // Protect the packet
-        let protected_payload = crate::crypto::protect(header_key, encrypted_payload)
-            .expect("header protection should always work");
-
-        // SUCCESS!!!
+        let packet = UnsealedPacket {
+            packet_number,
+            packet_number_len,
+            header_len,
+            len: 0,
+        };
 
-        Ok((protected_payload, remaining))
+        Ok((packet, buffer))
     }
 }
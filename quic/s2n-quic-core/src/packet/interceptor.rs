@@ -38,6 +38,12 @@ pub trait Ack {
 
 /// Trait which enables an application to intercept packets that are transmitted and received
 pub trait Interceptor: 'static + Send {
+    /// Set to false if the interceptor never modifies transmitted datagrams
+    ///
+    /// Intercepted datagrams need to be in their final form, so header protection can't be
+    /// deferred and applied to multiple datagrams at once if this is set.
+    const INTERCEPTS_TX_DATAGRAMS: bool = true;
+
     #[inline(always)]
     fn intercept_rx_ack<A: Ack>(&mut self, subject: &Subject, ack: &mut A) {
         let _ = subject;
@@ -102,13 +108,17 @@ pub trait Interceptor: 'static + Send {
 #[derive(Debug, Default)]
 pub struct Disabled(());
 
-impl Interceptor for Disabled {}
+impl Interceptor for Disabled {
+    const INTERCEPTS_TX_DATAGRAMS: bool = false;
+}
 
 impl<X, Y> Interceptor for (X, Y)
 where
     X: Interceptor,
     Y: Interceptor,
 {
+    const INTERCEPTS_TX_DATAGRAMS: bool = X::INTERCEPTS_TX_DATAGRAMS || Y::INTERCEPTS_TX_DATAGRAMS;
+
     #[inline(always)]
     fn intercept_rx_ack<A: Ack>(&mut self, subject: &Subject, ack: &mut A) {
         self.0.intercept_rx_ack(subject, ack);
@@ -228,6 +238,8 @@ where
 }
 
 impl<T: Interceptor> Interceptor for Option<T> {
+    const INTERCEPTS_TX_DATAGRAMS: bool = T::INTERCEPTS_TX_DATAGRAMS;
+
     #[inline]
     fn intercept_rx_remote_port(&mut self, subject: &Subject, port: &mut u16) {
         if let Some(inner) = self.as_mut() {
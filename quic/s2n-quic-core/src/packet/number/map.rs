@@ -1,8 +1,14 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace};
-use alloc::{boxed::Box, vec::Vec};
+use crate::{
+    packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace},
+    varint::VarInt,
+};
+use alloc::{
+    boxed::Box,
+    collections::{vec_deque, VecDeque},
+};
 use core::fmt;
 
 /// A data structure for tracking packets that are pending acknowledgement
@@ -13,45 +19,61 @@ use core::fmt;
 /// * Packet numbers will mostly be removed in ranges
 /// * Packet numbers that are deemed lost will also be removed and retransmitted
 ///
-/// This is implemented as a buffer ring with a moving range for the lower and upper bound of
-/// contained packet numbers. The following example illustrates how each field tracks state:
+/// The values are stored in blocks which each cover a fixed range of [`BLOCK_LEN`] consecutive
+/// packet numbers. A block is allocated when the first packet number in its range is inserted and
+/// released once the last one is removed. The memory used is proportional to the number of ranges
+/// with packets still pending, rather than the distance between the smallest and largest pending
+/// packet numbers. This prevents a few unacknowledged packets from retaining a slot for every
+/// packet sent after them, e.g. when a lossy peer leaves gaps in its ACK ranges.
 ///
 /// ```ignore
-/// packets = [ PN(2), None, PN(0), PN(1) ]
-///                           ^ index = 2
-/// start = PN(0)
-/// end = PN(2)
+/// blocks = [ Some([_, .., PN(30), PN(31)]), None, Some([PN(64), _, PN(66), _, ..]) ]
+/// base = 0
+/// start = PN(30)
+/// end = PN(66)
 /// ```
 ///
-/// Upon inserting `PN(3)` the state is now:
+/// Upon removing `PN(30)` and `PN(31)` the first block is released and the state is now:
 ///
 /// ```ignore
-/// packets = [ PN(2), PN(3), PN(0), PN(1) ]
-///                           ^ index = 2
-/// start = PN(0)
-/// end = PN(3)
-/// ```
-///
-/// Upon removing `PN(0)` the state is now:
-///
-/// ```ignore
-/// packets = [ PN(2), PN(3), None, PN(1) ]
-///                                 ^ index = 3
-/// start = PN(1)
-/// end = PN(3)
+/// blocks = [ Some([PN(64), _, PN(66), _, ..]) ]
+/// base = 2
+/// start = PN(64)
+/// end = PN(66)
 /// ```
 #[derive(Clone)]
 pub struct Map<V> {
-    /// The stored values for each packet number
-    values: Box<[Option<V>]>,
+    /// The blocks for each range of packet numbers, starting with the range at `base`
+    ///
+    /// The first and last blocks are always allocated, unless the map is empty.
+    blocks: VecDeque<Option<Box<Block<V>>>>,
+    /// The index of the packet number range covered by the first block
+    base: u64,
     /// The smallest contained inclusive packet number in the map
     start: PacketNumber,
     /// The largest contained inclusive packet number in the map
     end: PacketNumber,
-    /// The starting index of the first occupied packet
-    ///
-    /// This field will be set to the `packets.len()` if the map is empty
-    index: usize,
+    /// A released block which is reused for the next range, rather than allocating a new one
+    spare: Option<Box<Block<V>>>,
+}
+
+/// The number of consecutive packet numbers covered by each block
+const BLOCK_LEN: usize = 32;
+
+#[derive(Clone)]
+struct Block<V> {
+    values: [Option<V>; BLOCK_LEN],
+    /// The number of occupied values in the block
+    len: usize,
+}
+
+impl<V> Block<V> {
+    fn new() -> Box<Self> {
+        Box::new(Self {
+            values: core::array::from_fn(|_| None),
+            len: 0,
+        })
+    }
 }
 
 impl<V: fmt::Debug> fmt::Debug for Map<V> {
@@ -60,31 +82,18 @@ impl<V: fmt::Debug> fmt::Debug for Map<V> {
     }
 }
 
-/// Start with 8 sent packets at a time
-///
-/// Capacity will grow exponentially as more packet number entries are added
-const DEFAULT_CAPACITY: usize = 8;
-
 impl<V> Default for Map<V> {
     fn default() -> Self {
         // we use the Initial packet number space as a filler until an actual
         // packet number is inserted
         let base = PacketNumberSpace::Initial.new_packet_number(0u8.into());
 
-        let mut values = Vec::with_capacity(DEFAULT_CAPACITY);
-        while values.len() != values.capacity() {
-            values.push(None);
-        }
-        let values = values.into_boxed_slice();
-
-        // Set the index to the len (OOB) to indicate that it's empty
-        let index = values.len();
-
         Self {
-            values,
+            blocks: VecDeque::new(),
+            base: 0,
             start: base,
             end: base,
-            index,
+            spare: None,
         }
     }
 }
@@ -93,35 +102,21 @@ impl<V> Map<V> {
     /// Inserts the given `value`
     pub fn insert(&mut self, packet_number: PacketNumber, value: V) {
         if self.is_empty() {
-            self.start = packet_number;
-            self.end = packet_number;
-            self.values[0] = Some(value);
-            self.index = 0;
-            return;
-        }
-
-        // The implementation assumes monotonicity of insertion
-        debug_assert!(
-            packet_number > self.start && packet_number > self.end,
-            "packet numbers should be monotonic: {:?} > {:?} && {:?}",
-            packet_number,
-            self.start,
-            self.end
-        );
-
-        // check if we need to increase capacity
-        let distance = (packet_number.as_u64() - self.start.as_u64()) as usize;
-
-        let index = if distance >= self.values.len() {
-            self.resize(distance);
-
-            // use the distance as the index since we've already resized beyond it
-            distance
+            self.reset(packet_number);
         } else {
-            (self.index + distance) % self.values.len()
-        };
+            // The implementation assumes monotonicity of insertion
+            debug_assert!(
+                packet_number > self.start && packet_number > self.end,
+                "packet numbers should be monotonic: {:?} > {:?} && {:?}",
+                packet_number,
+                self.start,
+                self.end
+            );
+        }
 
-        self.values[index] = Some(value);
+        let entry = self.entry(packet_number);
+        debug_assert!(entry.is_none());
+        *entry = Some(value);
         self.end = packet_number;
     }
 
@@ -133,34 +128,18 @@ impl<V> Map<V> {
         update: F,
     ) {
         if self.is_empty() {
-            self.start = packet_number;
-            self.end = packet_number;
-            self.values[0] = Some(value);
-            self.index = 0;
-            return;
-        }
-
-        // The implementation assumes insertion is not lower than the start
-        debug_assert!(
-            packet_number >= self.start,
-            "packet numbers should be monotonic: {:?} > {:?}",
-            packet_number,
-            self.start,
-        );
-
-        // check if we need to increase capacity
-        let distance = (packet_number.as_u64() - self.start.as_u64()) as usize;
-
-        let index = if distance >= self.values.len() {
-            self.resize(distance);
-
-            // use the distance as the index since we've already resized beyond it
-            distance
+            self.reset(packet_number);
         } else {
-            (self.index + distance) % self.values.len()
-        };
+            // The implementation assumes insertion is not lower than the start
+            debug_assert!(
+                packet_number >= self.start,
+                "packet numbers should be monotonic: {:?} > {:?}",
+                packet_number,
+                self.start,
+            );
+        }
 
-        let entry = &mut self.values[index];
+        let entry = self.entry(packet_number);
 
         if let Some(prev) = entry.as_mut() {
             update(prev);
@@ -174,15 +153,14 @@ impl<V> Map<V> {
     /// Returns a reference to the `V` associated with the given `packet_number`
     #[inline]
     pub fn get(&self, packet_number: PacketNumber) -> Option<&V> {
-        let index = self.pn_index(packet_number)?;
-        self.values[index].as_ref()
+        let (index, offset) = self.position(packet_number)?;
+        self.blocks[index].as_ref()?.values[offset].as_ref()
     }
 
     /// Removes the value associated with the given `packet_number`
     /// and returns the value if it was present
     pub fn remove(&mut self, packet_number: PacketNumber) -> Option<V> {
-        let index = self.pn_index(packet_number)?;
-        let info = self.values[index].take()?;
+        let info = self.take(packet_number)?;
 
         // update the bounds
         match (self.start == packet_number, self.end == packet_number) {
@@ -240,31 +218,142 @@ impl<V> Map<V> {
     /// Returns true if there are no entries
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.index == self.values.len()
+        self.blocks.is_empty()
+    }
+
+    /// Returns the number of packet numbers between the smallest and largest contained packet
+    /// numbers
+    ///
+    /// This includes packet numbers which have been removed from the middle of the map.
+    #[inline]
+    pub fn span(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        (self.end.as_u64() - self.start.as_u64()) as usize + 1
+    }
+
+    /// Returns the number of slots currently allocated for values
+    ///
+    /// This doesn't include the block retained for reuse.
+    pub fn capacity(&self) -> usize {
+        self.blocks.iter().filter(|block| block.is_some()).count() * BLOCK_LEN
+    }
+
+    /// Reduces the memory used for tracking the blocks if the map has become mostly unoccupied
+    ///
+    /// The blocks themselves are released as they're emptied, so this only shrinks the list of
+    /// blocks while its length fits in a quarter of the allocated capacity.
+    pub fn compact(&mut self) {
+        if self.blocks.len().saturating_mul(4) <= self.blocks.capacity() {
+            self.blocks.shrink_to_fit();
+        }
     }
 
     /// Clears all of the packet information in the sent
     #[inline]
     pub fn clear(&mut self) {
-        self.index = self.values.len();
+        self.blocks.clear();
     }
 
+    /// Returns the range index and the offset within the range for a packet number
     #[inline]
-    fn pn_index(&self, packet_number: PacketNumber) -> Option<usize> {
+    fn locate(packet_number: PacketNumber) -> (u64, usize) {
+        let packet_number = packet_number.as_u64();
+        let block_len = BLOCK_LEN as u64;
+        (
+            packet_number / block_len,
+            (packet_number % block_len) as usize,
+        )
+    }
+
+    /// Returns the packet number at the offset of the range index
+    #[inline]
+    fn packet_number(&self, range: u64, offset: usize) -> PacketNumber {
+        let value = range * BLOCK_LEN as u64 + offset as u64;
+        // the value is between two contained packet numbers so it's always a valid VarInt
+        let value = VarInt::new(value).expect("packet number should be a valid VarInt");
+        PacketNumber::from_varint(value, self.start.space())
+    }
+
+    /// Returns the block index and the offset within the block for a contained packet number
+    #[inline]
+    fn position(&self, packet_number: PacketNumber) -> Option<(usize, usize)> {
         // the map is empty so there are no valid entries
         if self.is_empty() {
             return None;
         }
 
         // make sure it's within the inserted packet numbers
-        if packet_number > self.end {
+        if packet_number > self.end || packet_number < self.start {
             return None;
         }
 
-        let offset = packet_number.checked_distance(self.start)?;
-        let index = self.index.checked_add(offset as usize)?;
-        let index = index % self.values.len();
-        Some(index)
+        let (range, offset) = Self::locate(packet_number);
+        Some(((range - self.base) as usize, offset))
+    }
+
+    /// Resets the empty map to start at the given packet number
+    #[inline]
+    fn reset(&mut self, packet_number: PacketNumber) {
+        debug_assert!(self.is_empty());
+        self.base = Self::locate(packet_number).0;
+        self.start = packet_number;
+        self.end = packet_number;
+    }
+
+    /// Returns the entry for the packet number, allocating its block if needed
+    #[inline]
+    fn entry(&mut self, packet_number: PacketNumber) -> &mut Option<V> {
+        let (range, offset) = Self::locate(packet_number);
+        let index = (range - self.base) as usize;
+
+        if index >= self.blocks.len() {
+            self.blocks.resize_with(index + 1, || None);
+        }
+
+        let spare = &mut self.spare;
+        let block =
+            self.blocks[index].get_or_insert_with(|| spare.take().unwrap_or_else(Block::new));
+
+        let entry = &mut block.values[offset];
+        if entry.is_none() {
+            block.len += 1;
+        }
+        entry
+    }
+
+    /// Takes the value for the packet number, releasing its block if it's now empty
+    ///
+    /// The bounds of the map are not updated.
+    #[inline]
+    fn take(&mut self, packet_number: PacketNumber) -> Option<V> {
+        let (index, offset) = self.position(packet_number)?;
+        let slot = &mut self.blocks[index];
+        let block = slot.as_mut()?;
+        let value = block.values[offset].take()?;
+
+        block.len -= 1;
+        if block.len == 0 {
+            let block = slot.take();
+            if self.spare.is_none() {
+                self.spare = block;
+            }
+        }
+
+        Some(value)
+    }
+
+    /// Updates the bounds of the map after the contained `range` was removed
+    #[inline]
+    fn on_range_removed(&mut self, range: PacketNumberRange) {
+        match (range.start() == self.start, range.end() == self.end) {
+            (true, true) => self.clear(),
+            (true, false) => self.set_start(range.end().next().unwrap()),
+            (false, true) => self.set_end(range.start().prev().unwrap()),
+            (false, false) => {}
+        }
     }
 
     #[inline]
@@ -274,19 +363,21 @@ impl<V> Map<V> {
         debug_assert!(packet_number >= self.start);
         debug_assert!(packet_number <= self.end);
 
-        // find the next occupied slot
-        for packet_number in PacketNumberRange::new(packet_number, self.end) {
-            if self.get(packet_number).is_some() {
-                let index = self
-                    .pn_index(packet_number)
-                    .expect("packet should be in bounds");
-
-                self.index = index;
-                self.start = packet_number;
-                debug_assert!(self.start <= self.end);
-                debug_assert_eq!(self.pn_index(packet_number), Some(index));
-                return;
+        // find the next occupied slot, skipping the released blocks
+        let (range, mut offset) = Self::locate(packet_number);
+        for index in (range - self.base) as usize..self.blocks.len() {
+            if let Some(block) = self.blocks[index].as_ref() {
+                if let Some(found) = (offset..BLOCK_LEN).find(|&i| block.values[i].is_some()) {
+                    self.start = self.packet_number(self.base + index as u64, found);
+                    debug_assert!(self.start <= self.end);
+
+                    // release the tracking for the blocks before the new start
+                    self.blocks.drain(..index);
+                    self.base += index as u64;
+                    return;
+                }
             }
+            offset = 0;
         }
 
         unreachable!("could not find an occupied entry; map should be empty");
@@ -299,84 +390,58 @@ impl<V> Map<V> {
         debug_assert!(packet_number >= self.start);
         debug_assert!(packet_number <= self.end);
 
-        // find the next occupied slot
-        for packet_number in PacketNumberRange::new(self.start, packet_number).rev() {
-            if self.get(packet_number).is_some() {
-                self.end = packet_number;
-                debug_assert!(self.start <= self.end);
-                return;
+        // find the previous occupied slot, skipping the released blocks
+        let (range, mut offset) = Self::locate(packet_number);
+        for index in (0..=(range - self.base) as usize).rev() {
+            if let Some(block) = self.blocks[index].as_ref() {
+                if let Some(found) = (0..=offset).rev().find(|&i| block.values[i].is_some()) {
+                    self.end = self.packet_number(self.base + index as u64, found);
+                    debug_assert!(self.start <= self.end);
+
+                    // release the tracking for the blocks after the new end
+                    self.blocks.truncate(index + 1);
+                    return;
+                }
             }
+            offset = BLOCK_LEN - 1;
         }
 
         unreachable!("could not find an occupied entry; map should be empty");
     }
-
-    fn resize(&mut self, len: usize) {
-        let mut new_len = self.values.len();
-
-        // grow capacity until we can fit the inserted PN
-        loop {
-            new_len *= 2;
-            if len < new_len {
-                break;
-            }
-        }
-
-        // allocate a new packet buffer and copy the previous values
-        let mut values = Vec::with_capacity(new_len);
-        // The packets are stored in a ring so we copy from the index
-        // to the end, then from the start to the index
-        values.extend(self.values[self.index..].iter_mut().map(|v| v.take()));
-        values.extend(self.values[..self.index].iter_mut().map(|v| v.take()));
-        while values.len() != values.capacity() {
-            values.push(None);
-        }
-
-        // reset the index to the beginning of the buffer
-        self.index = 0;
-        self.values = values.into_boxed_slice();
-    }
 }
 
 /// An iterator over all of the contained packet numbers
 ///
-/// This iterator is optimized to reduce the amount of bounds checks being performed
-#[derive(Debug)]
+/// Blocks which have been released are skipped without checking each of their packet numbers
 pub struct Iter<'a, V> {
-    packets: &'a Map<V>,
-    packet_number: Option<PacketNumber>,
-    index: usize,
-    remaining: usize,
+    blocks: vec_deque::Iter<'a, Option<Box<Block<V>>>>,
+    block: Option<&'a Block<V>>,
+    /// The range index of `block`
+    range: u64,
+    offset: usize,
+    space: PacketNumberSpace,
+}
+
+impl<'a, V> fmt::Debug for Iter<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("range", &self.range)
+            .field("offset", &self.offset)
+            .finish()
+    }
 }
 
 impl<'a, V> Iter<'a, V> {
     #[inline]
     fn new(packets: &'a Map<V>) -> Self {
-        let start = packets.start;
-        let end = packets.end;
-        let index = packets.index;
-
-        let mut iter = Self {
-            packets,
-            packet_number: Some(start),
-            index,
-            // start with an empty iterator
-            remaining: 0,
-        };
-
-        // make sure we have at least one packet
-        if iter.packets.is_empty() {
-            return iter;
+        Self {
+            blocks: packets.blocks.iter(),
+            block: None,
+            // the first call to `next` advances to the first block
+            range: packets.base.wrapping_sub(1),
+            offset: BLOCK_LEN,
+            space: packets.start.space(),
         }
-
-        // set the number of remaining entries based on the bounded range
-        iter.remaining = (end.as_u64() - start.as_u64()) as usize;
-        // we always have at least 1 items since the range is inclusive
-        iter.remaining += 1;
-
-        debug_assert!(iter.remaining <= iter.packets.values.len());
-
-        iter
     }
 }
 
@@ -385,49 +450,48 @@ impl<'a, V> Iterator for Iter<'a, V> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while self.remaining > 0 {
-            self.remaining -= 1;
-
-            let packet_number = self.packet_number?;
-            self.packet_number = packet_number.next();
-
-            let index = self.index;
-            self.index = (index + 1) % self.packets.values.len();
-
-            if let Some(info) = self.packets.values[index].as_ref() {
-                return Some((packet_number, info));
+        loop {
+            if let Some(block) = self.block {
+                while self.offset < BLOCK_LEN {
+                    let offset = self.offset;
+                    self.offset += 1;
+
+                    if let Some(info) = block.values[offset].as_ref() {
+                        let value = self.range * BLOCK_LEN as u64 + offset as u64;
+                        let value =
+                            VarInt::new(value).expect("packet number should be a valid VarInt");
+                        let packet_number = PacketNumber::from_varint(value, self.space);
+                        return Some((packet_number, info));
+                    }
+                }
             }
-        }
 
-        None
+            self.block = self.blocks.next()?.as_deref();
+            self.range = self.range.wrapping_add(1);
+            self.offset = 0;
+        }
     }
 }
 
 /// An iterator which removes a set of packet numbers in a range
 ///
-/// This iterator is optimized to reduce the amount of bounds checks being performed
+/// Blocks which have been released are skipped without checking each of their packet numbers
 #[derive(Debug)]
 pub struct RemoveIter<'a, V> {
     packets: &'a mut Map<V>,
+    /// The inclusive bounds of the contained range being removed, or `None` once it has been
+    /// removed
+    range: Option<(PacketNumber, PacketNumber)>,
     packet_number: Option<PacketNumber>,
-    index: usize,
-    remaining: usize,
 }
 
 impl<'a, V> RemoveIter<'a, V> {
     #[inline]
     fn new(packets: &'a mut Map<V>, range: PacketNumberRange) -> Self {
-        let mut start = packets.start;
-        let mut end = packets.end;
-
-        let index = packets.index;
-
         let mut iter = Self {
             packets,
+            range: None,
             packet_number: None,
-            index,
-            // start with an empty iterator
-            remaining: 0,
         };
 
         // make sure we have at least one packet
@@ -436,60 +500,14 @@ impl<'a, V> RemoveIter<'a, V> {
         }
 
         // ensure the range overlaps with the contained items
-        if range.end() < start || range.start() > end {
+        let start = range.start().max(iter.packets.start);
+        let end = range.end().min(iter.packets.end);
+        if start > end {
             return iter;
         }
 
-        use core::cmp::Ordering::*;
-
-        match (range.start().cmp(&start), range.end().cmp(&end)) {
-            (Less, Equal) | (Less, Greater) | (Equal, Greater) | (Equal, Equal) => {
-                // deleting all entries
-
-                // clear the sent packets
-                //
-                // NOTE: this doesn't actually delete anything in the buffer
-                iter.packets.clear();
-
-                // no need to update index as it's already set to the lower bound
-            }
-            (Less, Less) | (Equal, Less) => {
-                // deleting start
-                end = range.end();
-
-                iter.packets.set_start(end.next().unwrap());
-            }
-            (Greater, Greater) | (Greater, Equal) => {
-                // deleting end
-                start = range.start();
-
-                iter.index = iter
-                    .packets
-                    .pn_index(start)
-                    .expect("packet number bounds have already been checked");
-
-                iter.packets.set_end(start.prev().unwrap());
-            }
-            (Greater, Less) => {
-                // deleting middle part
-                start = range.start();
-                end = range.end();
-
-                iter.index = iter
-                    .packets
-                    .pn_index(start)
-                    .expect("packet number bounds have already been checked");
-            }
-        }
-
-        // Update the starting packet number
+        iter.range = Some((start, end));
         iter.packet_number = Some(start);
-        // set the number of remaining entries based on the bounded range
-        iter.remaining = (end.as_u64() - start.as_u64()) as usize;
-        // we always have at least 1 items since the range is inclusive
-        iter.remaining += 1;
-
-        debug_assert!(iter.remaining <= iter.packets.values.len());
 
         iter
     }
@@ -500,27 +518,42 @@ impl<'a, V> Iterator for RemoveIter<'a, V> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while self.remaining > 0 {
-            self.remaining -= 1;
+        let (start, end) = self.range?;
+
+        while let Some(packet_number) = self.packet_number.filter(|pn| *pn <= end) {
+            let (index, offset) = self
+                .packets
+                .position(packet_number)
+                .expect("packet number bounds have already been checked");
+
+            if self.packets.blocks[index].is_none() {
+                // skip the rest of the released block
+                let next = packet_number.as_u64() + (BLOCK_LEN - offset) as u64;
+                self.packet_number = VarInt::new(next)
+                    .ok()
+                    .map(|next| PacketNumber::from_varint(next, packet_number.space()));
+                continue;
+            }
 
-            let packet_number = self.packet_number?;
             self.packet_number = packet_number.next();
 
-            let index = self.index;
-            self.index = (index + 1) % self.packets.values.len();
-
-            if let Some(info) = self.packets.values[index].take() {
+            if let Some(info) = self.packets.take(packet_number) {
                 return Some((packet_number, info));
             }
         }
 
+        // all of the values have been taken so update the bounds
+        self.range = None;
+        self.packets
+            .on_range_removed(PacketNumberRange::new(start, end));
+
         None
     }
 }
 
 impl<'a, V> Drop for RemoveIter<'a, V> {
     fn drop(&mut self) {
-        // make sure the iterator is drained, otherwise the entries might dangle
+        // make sure the iterator is drained, otherwise the bounds would include removed entries
         while self.next().is_some() {}
     }
 }
@@ -657,6 +690,8 @@ mod tests {
         Remove(VarInt),
         // Removes a range of packet numbers
         RemoveRange(VarInt, VarInt),
+        // Reduces the capacity of the map
+        Compact,
     }
 
     fn model(ops: &[Operation]) {
@@ -714,7 +749,22 @@ mod tests {
                 self.check_consistency();
             }
 
+            pub fn compact(&mut self) {
+                self.subject.compact();
+                self.check_consistency();
+            }
+
             fn check_consistency(&self) {
+                // only the blocks for ranges with a contained packet number are allocated
+                let mut ranges: Vec<_> = self
+                    .oracle
+                    .keys()
+                    .map(|pn| pn.as_u64() / BLOCK_LEN as u64)
+                    .collect();
+                ranges.dedup();
+                assert_eq!(self.subject.capacity(), ranges.len() * BLOCK_LEN);
+                assert_eq!(self.subject.is_empty(), self.oracle.is_empty());
+
                 let mut subject = self.subject.iter();
                 let mut oracle = self.oracle.iter();
                 loop {
@@ -759,10 +809,62 @@ mod tests {
 
                     model.remove_range(range);
                 }
+                Operation::Compact => {
+                    model.compact();
+                }
             }
         }
     }
 
+    #[test]
+    fn release_blocks_test() {
+        let space = PacketNumberSpace::ApplicationData;
+        let mut map = TestMap::default();
+        assert_eq!(map.capacity(), 0);
+
+        for pn in 0u32..1000 {
+            map.insert(space.new_packet_number(pn.into()), pn as u64);
+        }
+        assert_eq!(map.span(), 1000);
+        assert_eq!(map.capacity(), 1024);
+
+        // a packet which is never acknowledged only retains its own block, along with the blocks
+        // for 990..=991 and 992..=999
+        let start = space.new_packet_number(1u8.into());
+        let end = space.new_packet_number(989u32.into());
+        map.remove_range(PacketNumberRange::new(start, end))
+            .for_each(|_| ());
+        assert_eq!(map.span(), 1000);
+        assert_eq!(map.capacity(), 3 * BLOCK_LEN);
+        assert_eq!(map.iter().count(), 11);
+
+        // the map can still grow after releasing the blocks
+        for pn in 1000u32..1100 {
+            map.insert(space.new_packet_number(pn.into()), pn as u64);
+        }
+        assert_eq!(map.span(), 1100);
+        assert_eq!(map.iter().count(), 111);
+        assert_eq!(map.capacity(), 6 * BLOCK_LEN);
+        for pn in (990u32..1100).chain([0]) {
+            assert_eq!(
+                map.get(space.new_packet_number(pn.into())),
+                Some(&(pn as u64))
+            );
+        }
+
+        // removing the first packet releases the tracking for the blocks before the next one
+        assert_eq!(map.remove(space.new_packet_number(0u8.into())), Some(0));
+        assert_eq!(map.span(), 110);
+        map.compact();
+        assert!(map.blocks.capacity() < 32);
+
+        // empty maps release all of the blocks
+        map.remove_range(map.get_range()).for_each(|_| ());
+        map.compact();
+        assert!(map.is_empty());
+        assert_eq!(map.capacity(), 0);
+    }
+
     #[test]
     fn differential_test() {
         check!()
@@ -366,6 +366,41 @@ impl Config {
     pub fn is_valid(&self) -> bool {
         self.base_mtu.0 <= self.initial_mtu.0 && self.initial_mtu.0 <= self.max_mtu.0
     }
+
+    /// Clamps the configuration so that datagrams sent to the given peer do not exceed
+    /// `max_udp_payload_size`
+    ///
+    /// The `initial_mtu` and `base_mtu` are lowered as needed to remain within the clamped `max_mtu`.
+    #[inline]
+    pub fn clamp_max_udp_payload_size(
+        mut self,
+        max_udp_payload_size: u16,
+        peer_socket_address: &inet::SocketAddress,
+    ) -> Self {
+        let min_ip_header_len = match peer_socket_address {
+            inet::SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
+            inet::SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
+        };
+        let max_mtu = max_udp_payload_size
+            .saturating_add(UDP_HEADER_LEN + min_ip_header_len)
+            .max(MINIMUM_MTU);
+
+        if max_mtu >= self.max_mtu.0.get() {
+            return self;
+        }
+
+        let max_mtu = max_mtu
+            .try_into()
+            .expect("max_mtu is greater than or equal to MINIMUM_MTU");
+
+        self.max_mtu = MaxMtu(max_mtu);
+        self.initial_mtu = InitialMtu(self.initial_mtu.0.min(max_mtu));
+        self.base_mtu = BaseMtu(self.base_mtu.0.min(max_mtu));
+
+        debug_assert!(self.is_valid());
+
+        self
+    }
 }
 
 #[derive(Debug, Default)]
@@ -702,6 +737,63 @@ impl Controller {
         }
     }
 
+    /// This method gets called when the network reports that a packet exceeded the MTU of a
+    /// link on the path
+    ///
+    /// The reported `mtu` includes the size of the UDP and IP headers. Since these reports are
+    /// not authenticated, they are only used to reduce the sizes the controller will use and
+    /// never reduce the PLPMTU below the BASE_PLPMTU.
+    #[inline]
+    pub fn on_packet_too_big<CC: CongestionController, Pub: event::ConnectionPublisher>(
+        &mut self,
+        mtu: u16,
+        peer_socket_address: &inet::SocketAddress,
+        now: Timestamp,
+        congestion_controller: &mut CC,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) {
+        let min_ip_header_len = match peer_socket_address {
+            inet::SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
+            inet::SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
+        };
+        let max_datagram_size = mtu
+            .saturating_sub(UDP_HEADER_LEN + min_ip_header_len)
+            .max(self.base_plpmtu);
+
+        // A report larger than anything we would send doesn't tell us anything new
+        ensure!(max_datagram_size < self.max_probe_size);
+
+        if max_datagram_size < self.plpmtu {
+            self.black_hole_counter = Default::default();
+            self.largest_acked_mtu_sized_packet = None;
+            self.plpmtu = max_datagram_size;
+            congestion_controller.on_mtu_update(
+                self.plpmtu,
+                &mut congestion_controller::PathPublisher::new(publisher, path_id),
+            );
+
+            publisher.on_mtu_updated(event::builder::MtuUpdated {
+                path_id: path_id.into_event(),
+                mtu: self.plpmtu,
+                cause: MtuUpdatedCause::PacketTooBig,
+            });
+        }
+
+        // Probing hasn't been enabled yet, so leave the max probe size for the initial search
+        ensure!(!self.state.is_disabled());
+
+        let is_probe_too_big = self.probed_size > max_datagram_size;
+        self.max_probe_size = max_datagram_size;
+        self.update_probed_size();
+
+        // The current probe is known to be too large, so restart the search with the reduced
+        // max probe size
+        if is_probe_too_big && matches!(self.state, State::Searching(..) | State::SearchRequested) {
+            self.request_new_search(Some(now));
+        }
+    }
+
     /// Gets the currently validated maximum QUIC datagram size
     ///
     /// This does not include the size of UDP and IP headers.
@@ -121,6 +121,47 @@ fn mtu_config_builder() {
     assert_eq!(Some(MtuError), result.err());
 }
 
+#[test]
+fn mtu_config_clamp_max_udp_payload_size() {
+    let ipv4 =
+        inet::SocketAddress::IpV4(SocketAddressV4::new(IpV4Address::new([127, 0, 0, 1]), 443));
+    let ipv6 = inet::SocketAddress::IpV6(Default::default());
+    let config = mtu::Config::builder()
+        .with_initial_mtu(1500)
+        .unwrap()
+        .with_base_mtu(1400)
+        .unwrap()
+        .with_max_mtu(9001)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    // A payload size larger than the configured max MTU does not change the config
+    let clamped = config.clamp_max_udp_payload_size(u16::MAX, &ipv4);
+    assert_eq!(9001_u16, u16::from(clamped.max_mtu));
+    assert_eq!(1500_u16, u16::from(clamped.initial_mtu));
+    assert_eq!(1400_u16, u16::from(clamped.base_mtu));
+
+    let clamped = config.clamp_max_udp_payload_size(1452, &ipv4);
+    assert!(clamped.is_valid());
+    assert_eq!(1480_u16, u16::from(clamped.max_mtu));
+    assert_eq!(1480_u16, u16::from(clamped.initial_mtu));
+    assert_eq!(1400_u16, u16::from(clamped.base_mtu));
+    assert_eq!(1452, clamped.max_mtu.max_datagram_size(&ipv4));
+
+    // The IP header length depends on the address family
+    let clamped = config.clamp_max_udp_payload_size(1200, &ipv6);
+    assert!(clamped.is_valid());
+    assert_eq!(1248_u16, u16::from(clamped.max_mtu));
+    assert_eq!(1248_u16, u16::from(clamped.base_mtu));
+    assert_eq!(1200, clamped.max_mtu.max_datagram_size(&ipv6));
+
+    // The MTU is never clamped below the minimum
+    let clamped = mtu::Config::default().clamp_max_udp_payload_size(1000, &ipv4);
+    assert!(clamped.is_valid());
+    assert_eq!(MINIMUM_MTU, u16::from(clamped.max_mtu));
+}
+
 #[test]
 fn mtu_manager() {
     let remote = inet::SocketAddress::default();
@@ -831,6 +872,126 @@ fn on_packet_loss_initial_mtu_configured() {
 //# When used with an
 //# acknowledged PL (e.g., SCTP), DPLPMTUD SHOULD NOT continue to
 //# generate PLPMTU probes in this state.
+/// Returns the IPv4 address used by `new_controller`
+fn ipv4_peer() -> inet::SocketAddress {
+    SocketAddressV4::new(IpV4Address::new([127, 0, 0, 1]), 443).into()
+}
+
+#[test]
+fn on_packet_too_big_reduces_probe_size() {
+    let mut controller = new_controller(9000);
+    let mut cc = CongestionController::default();
+    let now = now();
+    let mut publisher = Publisher::snapshot();
+    controller.enable();
+    let plpmtu = controller.plpmtu;
+    assert_eq!(1472, controller.probed_size);
+
+    // the link MTU includes the UDP and IPv4 headers
+    controller.on_packet_too_big(
+        1400,
+        &ipv4_peer(),
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(plpmtu, controller.plpmtu);
+    assert_eq!(1372, controller.max_probe_size);
+    assert_eq!(plpmtu + (1372 - plpmtu) / 2, controller.probed_size);
+    assert_eq!(State::SearchRequested, controller.state);
+    assert_eq!(0, cc.on_mtu_update);
+}
+
+#[test]
+fn on_packet_too_big_reduces_plpmtu() {
+    let mut controller = new_controller(9000);
+    let mut cc = CongestionController::default();
+    let now = now();
+    let mut publisher = Publisher::snapshot();
+    controller.plpmtu = 1472;
+    controller.enable();
+    controller.state = State::Searching(pn(1), now);
+
+    controller.on_packet_too_big(
+        1300,
+        &ipv4_peer(),
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(1272, controller.plpmtu);
+    assert_eq!(1, cc.on_mtu_update);
+    assert_eq!(State::SearchComplete, controller.state);
+    assert_eq!(
+        Some(now + PMTU_RAISE_TIMER_DURATION),
+        controller.pmtu_raise_timer.next_expiration()
+    );
+
+    // the PLPMTU is never reduced below the BASE_PLPMTU
+    controller.on_packet_too_big(
+        576,
+        &ipv4_peer(),
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(controller.base_plpmtu, controller.plpmtu);
+    assert_eq!(2, cc.on_mtu_update);
+}
+
+#[test]
+fn on_packet_too_big_larger_than_max_probe_size() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::snapshot();
+    controller.enable();
+    let plpmtu = controller.plpmtu;
+    let probed_size = controller.probed_size;
+
+    controller.on_packet_too_big(
+        9000,
+        &ipv4_peer(),
+        now(),
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    assert_eq!(plpmtu, controller.plpmtu);
+    assert_eq!(probed_size, controller.probed_size);
+    assert_eq!(controller.max_udp_payload, controller.max_probe_size);
+    assert_eq!(0, cc.on_mtu_update);
+}
+
+#[test]
+fn on_packet_too_big_disabled_controller() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::snapshot();
+    controller.plpmtu = 1472;
+
+    controller.on_packet_too_big(
+        1300,
+        &ipv4_peer(),
+        now(),
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    // the initial MTU is reduced, but the max probe size is left for the initial search
+    assert_eq!(1272, controller.plpmtu);
+    assert_eq!(controller.max_udp_payload, controller.max_probe_size);
+    assert_eq!(State::Disabled, controller.state);
+    assert_eq!(1, cc.on_mtu_update);
+}
+
 #[test]
 fn on_transmit_search_not_requested() {
     let mut controller = new_controller(1500);
@@ -0,0 +1,116 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn bandwidth() {
+    let result = Bandwidth::new(1000, Duration::from_secs(10));
+
+    // 10 seconds = 10^10 nanoseconds, nanos_per_byte = 10^10 / 1000 = 10_000_000
+    // nanos_per_kibibyte = 10_000_000 * 1024 =
+    assert_eq!(10_240_000_000, result.nanos_per_kibibyte);
+}
+
+#[test]
+fn bandwidth_saturating() {
+    let result = Bandwidth::new(u64::MAX, Duration::from_secs(1));
+
+    assert_eq!(Bandwidth::INFINITY, result);
+}
+
+#[test]
+fn bandwidth_zero_interval() {
+    let result = Bandwidth::new(500, Duration::ZERO);
+
+    assert_eq!(Bandwidth::ZERO, result);
+}
+/// Test that the maximum supported interval (~104 days) does not panic
+#[test]
+fn bandwidth_max_interval() {
+    let result = Bandwidth::new(1, Duration::from_nanos(1 << 53));
+
+    assert!(result > Bandwidth::ZERO);
+}
+
+/// Test that a value larger than the maximum supported interval does not panic
+#[test]
+fn bandwidth_interval_too_long() {
+    let result = Bandwidth::new(1, Duration::from_nanos(1 << 54));
+
+    assert_eq!(Bandwidth::ZERO, result);
+}
+
+#[test]
+fn bandwidth_mul_ratio() {
+    let bandwidth = Bandwidth::new(4000, Duration::from_secs(1));
+
+    let result = bandwidth * Ratio::new(3, 4);
+
+    assert_eq!(result, Bandwidth::new(3000, Duration::from_secs(1)));
+}
+
+#[test]
+fn bandwidth_zero_mul_ratio() {
+    assert_eq!(Bandwidth::ZERO, Bandwidth::ZERO * Ratio::new(3, 7));
+}
+
+#[test]
+fn bandwidth_mul_duration() {
+    let bandwidth = Bandwidth::new(7000, Duration::from_secs(2));
+
+    let result = bandwidth * Duration::from_secs(10);
+
+    assert_eq!(result, 35000);
+}
+
+#[test]
+fn bandwidth_mul_duration_too_long() {
+    let bandwidth = Bandwidth::new(1, Duration::from_nanos(1));
+
+    let result = bandwidth * Duration::from_nanos(1 << 53);
+
+    assert!(result < u64::MAX);
+
+    let result = bandwidth * Duration::from_nanos(1 << 54);
+
+    assert_eq!(result, u64::MAX);
+}
+
+#[test]
+fn bandwidth_mul_saturation() {
+    let bandwidth = Bandwidth::INFINITY;
+
+    let result = bandwidth * Duration::from_secs(10);
+
+    assert_eq!(result, u64::MAX);
+}
+
+#[test]
+fn u64_div_bandwidth() {
+    let bandwidth = Bandwidth::new(10_000, Duration::from_secs(1));
+    let bytes = 200_000;
+    assert_eq!(bytes / bandwidth, Duration::from_secs(20));
+
+    let bandwidth = Bandwidth::new(10_000, Duration::from_secs(1));
+    let bytes = 2_000;
+    assert_eq!(bytes / bandwidth, Duration::from_millis(200));
+}
+
+#[test]
+fn bandwidth_ordering() {
+    let low = Bandwidth::new(10_000, Duration::from_secs(1));
+    let high = Bandwidth::new(20_000, Duration::from_secs(1));
+
+    assert!(high > low);
+    assert_eq!(high, low.max(high));
+}
+
+#[test]
+fn as_bytes_per_second() {
+    let bandwidth = Bandwidth::new(10_000, Duration::from_secs(1));
+
+    assert_eq!(10_000, bandwidth.as_bytes_per_second());
+    assert_eq!(0, Bandwidth::ZERO.as_bytes_per_second());
+    assert_eq!(u64::MAX, Bandwidth::INFINITY.as_bytes_per_second());
+}
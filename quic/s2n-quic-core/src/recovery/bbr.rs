@@ -7,14 +7,15 @@ use crate::{
     event::IntoEvent,
     random,
     recovery::{
-        bandwidth,
-        bandwidth::{Bandwidth, RateSample},
+        bandwidth::Bandwidth,
         bbr::{
             pacing::Pacer,
             probe_bw::{CyclePhase, PROBE_BW_FULL_LOSS_COUNT},
         },
         congestion_controller,
-        congestion_controller::Publisher,
+        congestion_controller::{CongestionState, Publisher},
+        delivery_rate,
+        delivery_rate::RateSample,
         CongestionController, RttEstimator,
     },
     time::Timestamp,
@@ -97,7 +98,9 @@ impl State {
     /// The dynamic gain factor used to scale BBR.bw to produce BBR.pacing_rate
     fn pacing_gain(&self, app_settings: &ApplicationSettings) -> Ratio<u64> {
         match self {
-            State::Startup => startup::PACING_GAIN,
+            State::Startup => app_settings
+                .startup_pacing_gain()
+                .unwrap_or(startup::PACING_GAIN),
             State::Drain => drain::PACING_GAIN,
             State::ProbeBw(probe_bw_state) => {
                 probe_bw_state.cycle_phase().pacing_gain(app_settings)
@@ -219,12 +222,26 @@ impl IntoEvent<event::builder::BbrState> for &State {
 #[derive(Default, Debug, Clone, Copy)]
 pub struct ApplicationSettings {
     initial_congestion_window: Option<u32>,
+    initial_congestion_window_packets: Option<u32>,
+    startup_pacing_gain: Option<u32>,
     probe_bw_cwnd_gain: Option<u32>,
     probe_bw_up_pacing_gain: Option<u32>,
     loss_threshold: Option<u32>,
 }
 
 impl ApplicationSettings {
+    fn initial_congestion_window(&self, max_datagram_size: u16) -> Option<u32> {
+        self.initial_congestion_window.or_else(|| {
+            self.initial_congestion_window_packets
+                .map(|packets| packets.saturating_mul(max_datagram_size as u32))
+        })
+    }
+
+    fn startup_pacing_gain(&self) -> Option<Ratio<u64>> {
+        self.startup_pacing_gain
+            .map(|pacing_gain| Ratio::new_raw(pacing_gain as u64, 100))
+    }
+
     fn probe_bw_cwnd_gain(&self) -> Option<Ratio<u64>> {
         self.probe_bw_cwnd_gain
             .map(|cwnd_gain| Ratio::new_raw(cwnd_gain as u64, 100))
@@ -250,7 +267,7 @@ impl ApplicationSettings {
 pub struct BbrCongestionController {
     state: State,
     round_counter: round::Counter,
-    bw_estimator: bandwidth::Estimator,
+    bw_estimator: delivery_rate::Estimator,
     full_pipe_estimator: full_pipe::Estimator,
     //= https://www.rfc-editor.org/rfc/rfc9002#appendix-B.2
     //# The sum of the size in bytes of all sent packets
@@ -282,12 +299,14 @@ pub struct BbrCongestionController {
     //# True if the connection has fully utilized its cwnd at any point in the last packet-timed round trip.
     cwnd_limited_in_round: bool,
     app_settings: ApplicationSettings,
+    /// The number of times recovery has been entered
+    loss_epoch: u64,
 }
 
 type BytesInFlight = Counter<u32>;
 
 impl CongestionController for BbrCongestionController {
-    type PacketInfo = bandwidth::PacketInfo;
+    type PacketInfo = delivery_rate::PacketInfo;
 
     #[inline]
     fn congestion_window(&self) -> u32 {
@@ -496,7 +515,9 @@ impl CongestionController for BbrCongestionController {
 
         self.bytes_in_flight -= lost_bytes;
         self.bw_estimator.on_loss(lost_bytes as usize);
-        self.recovery_state.on_congestion_event(timestamp);
+        if self.recovery_state.on_congestion_event(timestamp) {
+            self.loss_epoch += 1;
+        }
         self.congestion_state
             .on_packet_lost(self.bw_estimator.delivered_bytes(), new_loss_burst);
 
@@ -522,7 +543,9 @@ impl CongestionController for BbrCongestionController {
         self.bw_estimator.on_explicit_congestion(ce_count);
         self.ecn_state.on_explicit_congestion(ce_count);
         self.congestion_state.on_explicit_congestion();
-        self.recovery_state.on_congestion_event(event_time);
+        if self.recovery_state.on_congestion_event(event_time) {
+            self.loss_epoch += 1;
+        }
     }
 
     //= https://www.rfc-editor.org/rfc/rfc8899#section-3
@@ -564,6 +587,41 @@ impl CongestionController for BbrCongestionController {
     fn send_quantum(&self) -> Option<usize> {
         Some(self.pacer.send_quantum())
     }
+
+    #[inline]
+    fn bandwidth_estimate(&self) -> Option<Bandwidth> {
+        let max_bw = self.data_rate_model.max_bw();
+        (max_bw != Bandwidth::ZERO).then_some(max_bw)
+    }
+
+    #[inline]
+    fn delivery_rate_sample(&self) -> Option<RateSample> {
+        Some(self.bw_estimator.rate_sample())
+    }
+
+    #[inline]
+    fn congestion_state(&self) -> CongestionState {
+        use event::builder::CongestionPhase;
+
+        let phase = if self.recovery_state.in_recovery() {
+            CongestionPhase::Recovery
+        } else {
+            match self.state {
+                State::Startup => CongestionPhase::SlowStart,
+                State::Drain => CongestionPhase::Drain,
+                State::ProbeBw(_) => CongestionPhase::ProbeBandwidth,
+                State::ProbeRtt(_) => CongestionPhase::ProbeRtt,
+            }
+        };
+
+        CongestionState {
+            congestion_window: self.congestion_window(),
+            bytes_in_flight: self.bytes_in_flight(),
+            pacing_rate: Some(self.pacing_rate()),
+            phase,
+            loss_epoch: self.loss_epoch,
+        }
+    }
 }
 
 impl BbrCongestionController {
@@ -615,6 +673,7 @@ impl BbrCongestionController {
             try_fast_path: false,
             cwnd_limited_in_round: false,
             app_settings,
+            loss_epoch: 0,
         }
     }
 
@@ -819,7 +878,9 @@ impl BbrCongestionController {
             10 * max_datagram_size as u32,
             max(INITIAL_WINDOW_LIMIT, 2 * max_datagram_size as u32),
         );
-        let initial_window = app_settings.initial_congestion_window.unwrap_or(default);
+        let initial_window = app_settings
+            .initial_congestion_window(max_datagram_size)
+            .unwrap_or(default);
 
         max(initial_window, Self::minimum_window(max_datagram_size))
     }
@@ -1161,6 +1222,8 @@ pub mod builder {
     #[derive(Debug, Default)]
     pub struct Builder {
         initial_congestion_window: Option<u32>,
+        initial_congestion_window_packets: Option<u32>,
+        startup_pacing_gain: Option<u32>,
         probe_bw_cwnd_gain: Option<u32>,
         probe_bw_up_pacing_gain: Option<u32>,
         loss_threshold: Option<u32>,
@@ -1168,8 +1231,34 @@ pub mod builder {
 
     impl Builder {
         /// Set the initial congestion window in bytes.
+        ///
+        /// Overrides any value set with `with_initial_congestion_window_packets`.
         pub fn with_initial_congestion_window(mut self, initial_congestion_window: u32) -> Self {
             self.initial_congestion_window = Some(initial_congestion_window);
+            self.initial_congestion_window_packets = None;
+            self
+        }
+
+        /// Set the initial congestion window in packets of the current max datagram size
+        /// (default: 10).
+        ///
+        /// Overrides any value set with `with_initial_congestion_window`.
+        pub fn with_initial_congestion_window_packets(
+            mut self,
+            initial_congestion_window_packets: u32,
+        ) -> Self {
+            self.initial_congestion_window_packets = Some(initial_congestion_window_packets);
+            self.initial_congestion_window = None;
+            self
+        }
+
+        #[cfg(feature = "unstable-congestion-controller")]
+        /// Set the gain factor used during the Startup phase of the BBR
+        /// algorithm (default: 277/100).
+        ///
+        /// The gain value is calculated as the ratio: `startup_pacing_gain / 100`
+        pub fn with_startup_pacing_gain(mut self, startup_pacing_gain: u32) -> Self {
+            self.startup_pacing_gain = Some(startup_pacing_gain);
             self
         }
 
@@ -1206,6 +1295,8 @@ pub mod builder {
         pub fn build(self) -> Endpoint {
             let app_settings = ApplicationSettings {
                 initial_congestion_window: self.initial_congestion_window,
+                initial_congestion_window_packets: self.initial_congestion_window_packets,
+                startup_pacing_gain: self.startup_pacing_gain,
                 probe_bw_cwnd_gain: self.probe_bw_cwnd_gain,
                 probe_bw_up_pacing_gain: self.probe_bw_up_pacing_gain,
                 loss_threshold: self.loss_threshold,
@@ -4,8 +4,9 @@
 use crate::{
     counter::{Counter, Saturating},
     recovery::{
-        bandwidth::{Bandwidth, PacketInfo, RateSample},
+        bandwidth::Bandwidth,
         bbr::{data_rate, data_volume, round, BbrCongestionController},
+        delivery_rate::{PacketInfo, RateSample},
     },
 };
 
@@ -194,8 +195,9 @@ impl BbrCongestionController {
 pub mod testing {
     use crate::{
         recovery::{
-            bandwidth::{Bandwidth, PacketInfo, RateSample},
+            bandwidth::Bandwidth,
             bbr::{congestion, data_rate, data_volume},
+            delivery_rate::{PacketInfo, RateSample},
         },
         time::{Clock, NoopClock},
     };
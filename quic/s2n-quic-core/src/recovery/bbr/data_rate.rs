@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::recovery::{
-    bandwidth::{Bandwidth, RateSample},
+    bandwidth::Bandwidth,
     bbr::{windowed_filter::WindowedMaxFilter, BETA},
+    delivery_rate::RateSample,
 };
 
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#2.9.1
@@ -80,8 +80,8 @@ mod tests {
         path::MINIMUM_MAX_DATAGRAM_SIZE,
         random,
         recovery::{
-            bandwidth::RateSample, bbr::BbrCongestionController,
-            congestion_controller::PathPublisher,
+            bbr::BbrCongestionController, congestion_controller::PathPublisher,
+            delivery_rate::RateSample,
         },
         time::{Clock, NoopClock},
     };
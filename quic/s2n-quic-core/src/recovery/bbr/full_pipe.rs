@@ -4,9 +4,9 @@
 use crate::{
     counter::{Counter, Saturating},
     recovery::{
-        bandwidth,
         bandwidth::Bandwidth,
         bbr::{ApplicationSettings, BbrCongestionController},
+        delivery_rate,
     },
 };
 use num_rational::Ratio;
@@ -45,7 +45,7 @@ impl Estimator {
     #[inline]
     pub fn on_round_start(
         &mut self,
-        rate_sample: bandwidth::RateSample,
+        rate_sample: delivery_rate::RateSample,
         max_bw: Bandwidth,
         ecn_ce_count_too_high: bool,
     ) {
@@ -66,7 +66,7 @@ impl Estimator {
     #[inline]
     pub fn on_loss_round_start(
         &mut self,
-        rate_sample: bandwidth::RateSample,
+        rate_sample: delivery_rate::RateSample,
         loss_bursts_in_round: u8,
         max_datagram_size: u16,
         app_settings: &ApplicationSettings,
@@ -90,7 +90,7 @@ impl Estimator {
     #[inline]
     fn bandwidth_plateaued(
         &mut self,
-        rate_sample: bandwidth::RateSample,
+        rate_sample: delivery_rate::RateSample,
         max_bw: Bandwidth,
     ) -> bool {
         //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.3.1.2
@@ -141,7 +141,7 @@ impl Estimator {
     #[inline]
     fn excessive_inflight(
         &mut self,
-        rate_sample: bandwidth::RateSample,
+        rate_sample: delivery_rate::RateSample,
         loss_bursts_in_round: u8,
         max_datagram_size: u16,
         app_settings: &ApplicationSettings,
@@ -220,7 +220,7 @@ mod tests {
     use super::*;
     use crate::{
         path::MINIMUM_MAX_DATAGRAM_SIZE,
-        recovery::{bandwidth::RateSample, bbr::full_pipe},
+        recovery::{bbr::full_pipe, delivery_rate::RateSample},
     };
     use core::time::Duration;
 
@@ -7,12 +7,12 @@ use crate::{
     event::IntoEvent,
     random,
     recovery::{
-        bandwidth::RateSample,
         bbr,
         bbr::{
             congestion, data_rate, data_volume, round, ApplicationSettings, BbrCongestionController,
         },
         congestion_controller::Publisher,
+        delivery_rate::RateSample,
     },
     time::Timestamp,
 };
@@ -813,8 +813,7 @@ mod tests {
         path,
         path::MINIMUM_MAX_DATAGRAM_SIZE,
         recovery::{
-            bandwidth::{Bandwidth, PacketInfo},
-            congestion_controller::PathPublisher,
+            bandwidth::Bandwidth, congestion_controller::PathPublisher, delivery_rate::PacketInfo,
         },
         time::{Clock, NoopClock},
     };
@@ -4,9 +4,10 @@
 use crate::{
     random,
     recovery::{
-        bandwidth, bbr,
+        bbr,
         bbr::{probe_rtt, round, BbrCongestionController},
         congestion_controller::Publisher,
+        delivery_rate,
     },
     time::{Timer, Timestamp},
 };
@@ -38,7 +39,7 @@ impl State {
     /// Keeps BBR in the `ProbeRTT` state for max of (PROBE_RTT_DURATION, 1 round)
     fn handle_probe_rtt(
         &mut self,
-        bw_estimator: &mut bandwidth::Estimator,
+        bw_estimator: &mut delivery_rate::Estimator,
         round_counter: &mut round::Counter,
         probe_rtt_cwnd: u32,
         bytes_in_flight: u32,
@@ -226,9 +227,10 @@ mod tests {
         event, path,
         path::MINIMUM_MAX_DATAGRAM_SIZE,
         recovery::{
-            bandwidth::{Bandwidth, PacketInfo, RateSample},
+            bandwidth::Bandwidth,
             bbr::windowed_filter::PROBE_RTT_INTERVAL,
             congestion_controller::PathPublisher,
+            delivery_rate::{PacketInfo, RateSample},
         },
         time::{Clock, NoopClock},
     };
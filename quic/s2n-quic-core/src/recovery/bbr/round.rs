@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::recovery::bandwidth::PacketInfo;
+use crate::recovery::delivery_rate::PacketInfo;
 
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.5.1
 //# Several aspects of the BBR algorithm depend on counting the progress of "packet-timed" round
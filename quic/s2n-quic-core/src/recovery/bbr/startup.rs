@@ -81,7 +81,9 @@ mod tests {
     use crate::{
         event, path,
         path::MINIMUM_MAX_DATAGRAM_SIZE,
-        recovery::{bandwidth::PacketInfo, bbr::probe_rtt, congestion_controller::PathPublisher},
+        recovery::{
+            bbr::probe_rtt, congestion_controller::PathPublisher, delivery_rate::PacketInfo,
+        },
         time::{Clock, NoopClock},
     };
 
@@ -8,12 +8,14 @@ use crate::{
     path::MINIMUM_MAX_DATAGRAM_SIZE,
     random,
     recovery::{
-        bandwidth::{Bandwidth, PacketInfo, RateSample},
+        bandwidth::Bandwidth,
         bbr,
         bbr::{
-            probe_bw, probe_bw::CyclePhase, probe_rtt, BbrCongestionController, State, LOSS_THRESH,
+            drain, probe_bw, probe_bw::CyclePhase, probe_rtt, startup, ApplicationSettings,
+            BbrCongestionController, State, LOSS_THRESH,
         },
         congestion_controller::{PathPublisher, Publisher},
+        delivery_rate::{PacketInfo, RateSample},
         CongestionController,
     },
     time::{Clock, NoopClock},
@@ -46,6 +48,44 @@ fn initial_window_with_app_settings() {
     );
 }
 
+// ApplicationSettings.initial_congestion_window_packets
+#[test]
+fn initial_window_packets_with_app_settings() {
+    let mut app_settings = ApplicationSettings {
+        initial_congestion_window_packets: Some(32),
+        ..Default::default()
+    };
+
+    let max_datagram_size = 1350;
+    assert_eq!(
+        max_datagram_size as u32 * 32,
+        BbrCongestionController::initial_window(max_datagram_size, &app_settings)
+    );
+
+    app_settings.initial_congestion_window_packets = Some(0);
+    assert_eq!(
+        BbrCongestionController::minimum_window(max_datagram_size),
+        BbrCongestionController::initial_window(max_datagram_size, &app_settings)
+    );
+}
+
+// ApplicationSettings.startup_pacing_gain
+#[test]
+fn startup_pacing_gain_with_app_settings() {
+    let mut app_settings = Default::default();
+    assert_eq!(
+        startup::PACING_GAIN,
+        State::Startup.pacing_gain(&app_settings)
+    );
+
+    app_settings.startup_pacing_gain = Some(400);
+    assert_eq!(
+        Ratio::new_raw(400, 100),
+        State::Startup.pacing_gain(&app_settings)
+    );
+    assert_eq!(drain::PACING_GAIN, State::Drain.pacing_gain(&app_settings));
+}
+
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.5.6.3
 //= type=test
 //# When not explicitly accelerating to probe for bandwidth (Drain, ProbeRTT,
@@ -339,6 +379,27 @@ fn new() {
     assert!(bbr.state.is_startup());
 }
 
+#[test]
+fn bandwidth_estimate() {
+    let mut bbr = BbrCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    // No bandwidth samples yet
+    assert_eq!(None, bbr.bandwidth_estimate());
+
+    let rate_sample = RateSample {
+        delivered_bytes: 1000,
+        interval: Duration::from_millis(1),
+        ..Default::default()
+    };
+    bbr.data_rate_model.update_max_bw(rate_sample);
+
+    let estimate = bbr.bandwidth_estimate();
+    assert_eq!(Some(bbr.data_rate_model.max_bw()), estimate);
+    // 1000 bytes/ms is ~1MB/s; allow for rounding in the kibibyte-based representation
+    let bytes_per_second = estimate.unwrap().as_bytes_per_second();
+    assert!((999_000..=1_001_000).contains(&bytes_per_second));
+}
+
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.6.4.2
 //= type=test
 //# BBRBDPMultiple(gain):
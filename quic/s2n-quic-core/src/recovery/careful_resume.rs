@@ -0,0 +1,481 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An implementation of Careful Resume, as specified in
+//! <https://datatracker.ietf.org/doc/html/draft-ietf-tsvwg-careful-resume>
+//!
+//! Careful Resume allows a sender to reuse the congestion window and RTT observed by a previous
+//! connection over the same path, rather than growing the congestion window from the initial
+//! window. The saved parameters are only trusted after the current path has been confirmed to
+//! behave like the saved one, and the sender retreats to a safe window if they turn out to be
+//! wrong.
+
+use crate::{connection, event::api::SocketAddress, time::Timestamp};
+use core::time::Duration;
+
+/// The saved parameters are rejected if the current RTT is less than the saved RTT divided by
+/// this value...
+const MIN_RTT_DIVISOR: u32 = 2;
+/// ...or more than the saved RTT multiplied by this value
+const MAX_RTT_MULTIPLIER: u32 = 10;
+
+/// The congestion window used in the unvalidated phase is the saved window divided by this value
+const JUMP_WINDOW_DIVISOR: u32 = 2;
+
+/// The congestion window used in the safe retreat phase is the validated capacity of the path
+/// divided by this value
+const SAFE_RETREAT_DIVISOR: u32 = 2;
+
+/// The path characteristics observed by a previous connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SavedPath {
+    /// The congestion window, in bytes
+    pub congestion_window: u32,
+    /// The minimum round-trip time
+    pub rtt: Duration,
+}
+
+impl SavedPath {
+    #[inline]
+    pub fn new(congestion_window: u32, rtt: Duration) -> Self {
+        Self {
+            congestion_window,
+            rtt,
+        }
+    }
+}
+
+impl From<connection::PathInfo> for SavedPath {
+    #[inline]
+    fn from(info: connection::PathInfo) -> Self {
+        Self::new(info.congestion_window, info.min_rtt)
+    }
+}
+
+/// Loads the saved characteristics of the path to a peer
+///
+/// The store is queried for each new path once the sender has more data to send than the initial
+/// window allows, so parameters learned during the handshake, such as those echoed back by the
+/// peer in a BDP frame, are taken into account.
+pub trait Store: 'static + Send + Sync + core::fmt::Debug {
+    /// Returns the parameters saved for the path to `remote_address`, if any
+    fn load(&self, remote_address: &SocketAddress) -> Option<SavedPath>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Store> Store for alloc::sync::Arc<T> {
+    #[inline]
+    fn load(&self, remote_address: &SocketAddress) -> Option<SavedPath> {
+        self.as_ref().load(remote_address)
+    }
+}
+
+/// A query of a [`Store`] for the path to a peer which hasn't been made yet
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct Lookup {
+    store: alloc::sync::Arc<dyn Store>,
+    remote_address: crate::path::RemoteAddress,
+}
+
+#[cfg(feature = "alloc")]
+impl Lookup {
+    #[inline]
+    pub fn new(
+        store: alloc::sync::Arc<dyn Store>,
+        remote_address: crate::path::RemoteAddress,
+    ) -> Self {
+        Self {
+            store,
+            remote_address,
+        }
+    }
+
+    /// Returns the parameters currently saved for the path
+    #[inline]
+    pub fn load(&self) -> Option<SavedPath> {
+        use crate::event::IntoEvent;
+        self.store.load(&(&self.remote_address.0).into_event())
+    }
+}
+
+#[cfg(feature = "std")]
+mod cache {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        net::{IpAddr, SocketAddr},
+        sync::Mutex,
+    };
+
+    /// A [`Store`] which keeps the saved parameters in memory, keyed by the IP address of the peer
+    ///
+    /// The port is ignored since clients usually bind a new ephemeral port for each connection.
+    #[derive(Debug, Default)]
+    pub struct Cache {
+        paths: Mutex<HashMap<IpAddr, SavedPath>>,
+    }
+
+    impl Cache {
+        /// Saves the parameters of the path to `remote_address`, replacing any previous value
+        pub fn insert(&self, remote_address: SocketAddr, path: SavedPath) {
+            self.paths.lock().unwrap().insert(remote_address.ip(), path);
+        }
+
+        /// Removes the parameters saved for the path to `remote_address`
+        pub fn remove(&self, remote_address: SocketAddr) -> Option<SavedPath> {
+            self.paths.lock().unwrap().remove(&remote_address.ip())
+        }
+    }
+
+    impl Store for Cache {
+        #[inline]
+        fn load(&self, remote_address: &SocketAddress) -> Option<SavedPath> {
+            let remote_address: SocketAddr = remote_address.into();
+            self.paths
+                .lock()
+                .unwrap()
+                .get(&remote_address.ip())
+                .copied()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use cache::Cache;
+
+/// The phases a sender using Careful Resume progresses through
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// The sender uses the initial window while confirming the path matches the saved one
+    Reconnaissance,
+    /// The congestion window was raised to the jump window and hasn't been confirmed yet
+    Unvalidated,
+    /// The packets sent in the unvalidated phase are being acknowledged
+    Validating,
+    /// Congestion was detected while the jump window was unconfirmed
+    SafeRetreat,
+    /// The congestion controller operates without any further influence from Careful Resume
+    Normal,
+}
+
+#[derive(Clone, Debug)]
+pub struct CarefulResume {
+    saved: SavedPath,
+    phase: Phase,
+    /// The time the unvalidated phase was entered
+    unvalidated_start: Option<Timestamp>,
+    /// The time the last packet of the unvalidated phase was sent
+    last_unvalidated: Option<Timestamp>,
+    /// The capacity of the path confirmed by acknowledgements, in bytes
+    pipe_size: u32,
+}
+
+impl CarefulResume {
+    #[inline]
+    pub fn new(saved: SavedPath) -> Self {
+        Self {
+            saved,
+            phase: Phase::Reconnaissance,
+            unvalidated_start: None,
+            last_unvalidated: None,
+            pipe_size: 0,
+        }
+    }
+
+    #[inline]
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Returns true if Careful Resume controls the congestion window
+    ///
+    /// The congestion controller should not grow the congestion window itself while this is true.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.phase,
+            Phase::Unvalidated | Phase::Validating | Phase::SafeRetreat
+        )
+    }
+
+    /// Called when an acknowledgement is received
+    ///
+    /// `is_congestion_limited` is true if the sender was limited by the congestion window rather
+    /// than the application. Returns the new congestion window, if it should change.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn on_ack(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        congestion_window: u32,
+        is_congestion_limited: bool,
+        rtt: Duration,
+        time_of_last_sent_packet: Timestamp,
+        now: Timestamp,
+    ) -> Option<u32> {
+        let bytes_acknowledged = bytes_acknowledged.try_into().unwrap_or(u32::MAX);
+
+        match self.phase {
+            Phase::Reconnaissance => {
+                // The saved window is only used once the initial window has been acknowledged
+                // and the sender has more data to send than it allows
+                if !is_congestion_limited {
+                    return None;
+                }
+
+                let saved_rtt = self.saved.rtt;
+                if rtt < saved_rtt / MIN_RTT_DIVISOR || rtt > saved_rtt * MAX_RTT_MULTIPLIER {
+                    self.phase = Phase::Normal;
+                    return None;
+                }
+
+                let jump_window = self.saved.congestion_window / JUMP_WINDOW_DIVISOR;
+                if jump_window <= congestion_window {
+                    // the current window already exceeds what the saved parameters would allow
+                    self.phase = Phase::Normal;
+                    return None;
+                }
+
+                self.phase = Phase::Unvalidated;
+                self.unvalidated_start = Some(now);
+                self.pipe_size = congestion_window;
+                Some(jump_window)
+            }
+            Phase::Unvalidated => {
+                self.pipe_size = self.pipe_size.saturating_add(bytes_acknowledged);
+
+                // Wait for the first packet sent with the jump window to be acknowledged
+                if Some(newest_acked_time_sent) < self.unvalidated_start {
+                    return None;
+                }
+
+                self.phase = Phase::Validating;
+                self.last_unvalidated = Some(time_of_last_sent_packet);
+
+                // The acknowledged packet may also have been the last one sent
+                if newest_acked_time_sent >= time_of_last_sent_packet {
+                    self.phase = Phase::Normal;
+                }
+
+                Some(self.pipe_size)
+            }
+            Phase::Validating => {
+                self.pipe_size = self.pipe_size.saturating_add(bytes_acknowledged);
+
+                // The window is validated once the last packet sent with the jump window is
+                // acknowledged
+                if Some(newest_acked_time_sent) >= self.last_unvalidated {
+                    self.phase = Phase::Normal;
+                }
+
+                Some(self.pipe_size)
+            }
+            Phase::SafeRetreat => {
+                if Some(newest_acked_time_sent) >= self.last_unvalidated {
+                    self.phase = Phase::Normal;
+                }
+
+                None
+            }
+            Phase::Normal => None,
+        }
+    }
+
+    /// Called when a packet is lost or the ECN-CE count reported by the peer increases
+    ///
+    /// Returns the new congestion window, if the congestion controller's own response should be
+    /// overridden.
+    #[inline]
+    pub fn on_congestion_event(
+        &mut self,
+        time_of_last_sent_packet: Option<Timestamp>,
+        minimum_window: u32,
+    ) -> Option<u32> {
+        match self.phase {
+            Phase::Reconnaissance => {
+                // The saved parameters are abandoned and the congestion controller responds as
+                // usual
+                self.phase = Phase::Normal;
+                None
+            }
+            Phase::Unvalidated | Phase::Validating => {
+                // The jump window turned out to be too large, so retreat to a fraction of the
+                // capacity which was actually confirmed
+                self.phase = Phase::SafeRetreat;
+                self.last_unvalidated = self.last_unvalidated.or(time_of_last_sent_packet);
+                Some((self.pipe_size / SAFE_RETREAT_DIVISOR).max(minimum_window))
+            }
+            // Losses of packets sent in the unvalidated phase were already accounted for
+            Phase::SafeRetreat | Phase::Normal => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{Clock, NoopClock};
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn saved() -> SavedPath {
+        SavedPath::new(1_000_000, 100 * MS)
+    }
+
+    #[test]
+    fn resume_test() {
+        let mut cr = CarefulResume::new(saved());
+        let start = NoopClock.get_time();
+
+        // the sender isn't using the initial window yet
+        assert_eq!(
+            cr.on_ack(
+                start,
+                1_200,
+                12_000,
+                false,
+                100 * MS,
+                start,
+                start + 100 * MS
+            ),
+            None
+        );
+        assert_eq!(cr.phase(), Phase::Reconnaissance);
+
+        // jump to half of the saved window
+        let jump = start + 200 * MS;
+        assert_eq!(
+            cr.on_ack(start, 1_200, 12_000, true, 100 * MS, start, jump),
+            Some(500_000)
+        );
+        assert_eq!(cr.phase(), Phase::Unvalidated);
+        assert!(cr.is_active());
+
+        // packets sent before the jump don't validate the window
+        assert_eq!(
+            cr.on_ack(start, 12_000, 500_000, true, 100 * MS, jump, jump + MS),
+            None
+        );
+
+        // the first unvalidated packet is acknowledged
+        let last_sent = jump + 50 * MS;
+        assert_eq!(
+            cr.on_ack(
+                jump,
+                100_000,
+                500_000,
+                true,
+                100 * MS,
+                last_sent,
+                jump + 100 * MS
+            ),
+            Some(124_000)
+        );
+        assert_eq!(cr.phase(), Phase::Validating);
+
+        assert_eq!(
+            cr.on_ack(
+                jump + MS,
+                100_000,
+                124_000,
+                true,
+                100 * MS,
+                last_sent,
+                jump + 101 * MS
+            ),
+            Some(224_000)
+        );
+        assert_eq!(cr.phase(), Phase::Validating);
+
+        // the last unvalidated packet is acknowledged
+        assert_eq!(
+            cr.on_ack(
+                last_sent,
+                100_000,
+                224_000,
+                true,
+                100 * MS,
+                last_sent,
+                jump + 150 * MS
+            ),
+            Some(324_000)
+        );
+        assert_eq!(cr.phase(), Phase::Normal);
+        assert!(!cr.is_active());
+    }
+
+    #[test]
+    fn rtt_mismatch_test() {
+        let start = NoopClock.get_time();
+
+        for rtt in [49 * MS, 1001 * MS] {
+            let mut cr = CarefulResume::new(saved());
+            assert_eq!(
+                cr.on_ack(start, 1_200, 12_000, true, rtt, start, start),
+                None
+            );
+            assert_eq!(cr.phase(), Phase::Normal);
+        }
+
+        // the current window is already larger than the jump window
+        let mut cr = CarefulResume::new(saved());
+        assert_eq!(
+            cr.on_ack(start, 1_200, 600_000, true, 100 * MS, start, start),
+            None
+        );
+        assert_eq!(cr.phase(), Phase::Normal);
+    }
+
+    #[test]
+    fn safe_retreat_test() {
+        let mut cr = CarefulResume::new(saved());
+        let start = NoopClock.get_time();
+
+        let jump = start + MS;
+        cr.on_ack(start, 1_200, 12_000, true, 100 * MS, start, jump);
+        assert_eq!(cr.phase(), Phase::Unvalidated);
+        cr.on_ack(start, 20_000, 500_000, true, 100 * MS, jump, jump);
+        assert_eq!(cr.phase(), Phase::Unvalidated);
+
+        let last_sent = start + 10 * MS;
+        assert_eq!(cr.on_congestion_event(Some(last_sent), 2_400), Some(16_000));
+        assert_eq!(cr.phase(), Phase::SafeRetreat);
+        assert!(cr.is_active());
+
+        // further losses don't reduce the window again
+        assert_eq!(cr.on_congestion_event(Some(last_sent), 2_400), None);
+
+        assert_eq!(
+            cr.on_ack(
+                start + MS,
+                1_200,
+                16_000,
+                true,
+                100 * MS,
+                last_sent,
+                last_sent
+            ),
+            None
+        );
+        assert_eq!(cr.phase(), Phase::SafeRetreat);
+        cr.on_ack(
+            last_sent,
+            1_200,
+            16_000,
+            true,
+            100 * MS,
+            last_sent,
+            last_sent,
+        );
+        assert_eq!(cr.phase(), Phase::Normal);
+    }
+
+    #[test]
+    fn reconnaissance_congestion_test() {
+        let mut cr = CarefulResume::new(saved());
+        assert_eq!(cr.on_congestion_event(None, 2_400), None);
+        assert_eq!(cr.phase(), Phase::Normal);
+    }
+}
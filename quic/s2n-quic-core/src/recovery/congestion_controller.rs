@@ -5,15 +5,14 @@ use crate::{
     event,
     event::{
         api::SocketAddress,
-        builder::{BbrState, SlowStartExitCause},
+        builder::{BbrState, CongestionPhase, SlowStartExitCause},
         IntoEvent,
     },
     inet, path,
     path::Config,
     random,
     recovery::{
-        bandwidth::{Bandwidth, RateSample},
-        RttEstimator,
+        bandwidth::Bandwidth, delivery_rate::RateSample, one_way_delay::OneWayDelay, RttEstimator,
     },
     time::Timestamp,
 };
@@ -62,6 +61,24 @@ pub trait Publisher {
     fn on_bbr_state_changed(&mut self, state: BbrState);
 }
 
+/// A snapshot of the state of a congestion controller
+///
+/// Every congestion controller supplies the same snapshot so the state can be reported
+/// regardless of which controller is used.
+#[derive(Clone, Debug)]
+pub struct CongestionState {
+    /// The size of the congestion window in bytes
+    pub congestion_window: u32,
+    /// The number of bytes in flight
+    pub bytes_in_flight: u32,
+    /// The rate at which packets are paced, if the controller paces packets
+    pub pacing_rate: Option<Bandwidth>,
+    /// The phase of the controller's state machine
+    pub phase: CongestionPhase,
+    /// The number of times the controller has entered recovery
+    pub loss_epoch: u64,
+}
+
 /// Wrapper around a `ConnectionPublisher` that forwards congestion control related
 /// events to the inner publisher with the necessary context.
 pub struct PathPublisher<'a, Pub: event::ConnectionPublisher> {
@@ -74,6 +91,22 @@ impl<'a, Pub: event::ConnectionPublisher> PathPublisher<'a, Pub> {
     pub fn new(publisher: &'a mut Pub, path_id: path::Id) -> PathPublisher<Pub> {
         Self { publisher, path_id }
     }
+
+    /// Publishes the snapshot of the congestion controller state
+    #[inline]
+    pub fn on_congestion_state(&mut self, state: CongestionState) {
+        self.publisher
+            .on_congestion_state(event::builder::CongestionState {
+                path_id: self.path_id.into_event(),
+                congestion_window: state.congestion_window,
+                bytes_in_flight: state.bytes_in_flight,
+                pacing_rate_bytes_per_second: state
+                    .pacing_rate
+                    .map(|rate| rate.as_bytes_per_second()),
+                phase: state.phase,
+                loss_epoch: state.loss_epoch,
+            })
+    }
 }
 
 impl<'a, Pub: event::ConnectionPublisher> Publisher for PathPublisher<'a, Pub> {
@@ -180,6 +213,19 @@ pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed
         publisher: &mut Pub,
     );
 
+    /// Invoked when a one-way delay sample is computed from a TIMESTAMP frame sent by the peer
+    ///
+    /// Samples are only produced when the peer sends TIMESTAMP frames alongside its ACK frames,
+    /// which requires both endpoints to negotiate the timestamps extension. Delay-based
+    /// controllers can use the forward queuing delay to detect congestion before packets are lost.
+    fn on_one_way_delay_update<Pub: Publisher>(
+        &mut self,
+        _sample: OneWayDelay,
+        _now: Timestamp,
+        _publisher: &mut Pub,
+    ) {
+    }
+
     /// Invoked when an acknowledgement of one or more previously unacknowledged packets is received
     ///
     /// Generally the `bytes_acknowledged` value is aggregated over all newly acknowledged packets, though
@@ -243,6 +289,26 @@ pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed
     fn send_quantum(&self) -> Option<usize> {
         None
     }
+
+    /// The estimated bottleneck bandwidth of the path
+    ///
+    /// If the value is `None`, the congestion controller does not estimate bandwidth or has not
+    /// yet collected enough samples to do so.
+    fn bandwidth_estimate(&self) -> Option<Bandwidth> {
+        None
+    }
+
+    /// The most recent delivery rate sample of the path
+    ///
+    /// If the value is `None`, the congestion controller does not sample the delivery rate. See
+    /// [`delivery_rate::Estimator`](crate::recovery::delivery_rate::Estimator) for an estimator
+    /// which can be embedded in custom congestion controllers.
+    fn delivery_rate_sample(&self) -> Option<RateSample> {
+        None
+    }
+
+    /// Returns a snapshot of the state of the congestion controller
+    fn congestion_state(&self) -> CongestionState;
 }
 
 // Prevent implementation of the `CongestionController` trait if the
@@ -378,6 +444,16 @@ pub mod testing {
             fn earliest_departure_time(&self) -> Option<Timestamp> {
                 None
             }
+
+            fn congestion_state(&self) -> CongestionState {
+                CongestionState {
+                    congestion_window: self.congestion_window(),
+                    bytes_in_flight: self.bytes_in_flight(),
+                    pacing_rate: None,
+                    phase: CongestionPhase::CongestionAvoidance,
+                    loss_epoch: 0,
+                }
+            }
         }
     }
 
@@ -420,6 +496,7 @@ pub mod testing {
             pub app_limited: Option<bool>,
             pub slow_start: bool,
             pub remote_address: RemoteAddress,
+            pub one_way_delay: Option<OneWayDelay>,
         }
 
         impl Default for CongestionController {
@@ -439,6 +516,7 @@ pub mod testing {
                     app_limited: None,
                     slow_start: true,
                     remote_address: RemoteAddress::default(),
+                    one_way_delay: None,
                 }
             }
         }
@@ -497,6 +575,15 @@ pub mod testing {
                 self.on_rtt_update += 1
             }
 
+            fn on_one_way_delay_update<Pub: Publisher>(
+                &mut self,
+                sample: OneWayDelay,
+                _now: Timestamp,
+                _publisher: &mut Pub,
+            ) {
+                self.one_way_delay = Some(sample);
+            }
+
             fn on_ack<Pub: Publisher>(
                 &mut self,
                 _newest_acked_time_sent: Timestamp,
@@ -560,6 +647,24 @@ pub mod testing {
             fn earliest_departure_time(&self) -> Option<Timestamp> {
                 None
             }
+
+            fn congestion_state(&self) -> CongestionState {
+                let phase = if self.requires_fast_retransmission {
+                    CongestionPhase::Recovery
+                } else if self.slow_start {
+                    CongestionPhase::SlowStart
+                } else {
+                    CongestionPhase::CongestionAvoidance
+                };
+
+                CongestionState {
+                    congestion_window: self.congestion_window,
+                    bytes_in_flight: self.bytes_in_flight,
+                    pacing_rate: None,
+                    phase,
+                    loss_epoch: self.congestion_events as u64,
+                }
+            }
         }
     }
 }
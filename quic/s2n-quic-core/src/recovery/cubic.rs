@@ -3,11 +3,14 @@
 
 use crate::{
     counter::Counter,
-    event::builder::SlowStartExitCause,
+    event::builder::{CongestionPhase, SlowStartExitCause},
     random,
     recovery::{
-        congestion_controller::{self, CongestionController, Publisher},
+        careful_resume::{self, CarefulResume},
+        congestion_controller::{self, CongestionController, CongestionState, Publisher},
         cubic::{FastRetransmission::*, State::*},
+        delivery_rate,
+        delivery_rate::RateSample,
         hybrid_slow_start::HybridSlowStart,
         pacing::Pacer,
         RttEstimator,
@@ -18,6 +21,7 @@ use core::{
     cmp::{max, min},
     time::Duration,
 };
+use num_rational::Ratio;
 #[cfg(not(feature = "std"))]
 use num_traits::Float as _;
 
@@ -170,12 +174,21 @@ pub struct CubicCongestionController {
     // The highest number of bytes in flight seen when an ACK was received,
     // since the last congestion event.
     bytes_in_flight_hi: BytesInFlight,
+    // The number of times a recovery period has been entered
+    loss_epoch: u64,
+    // Samples the delivery rate so it can be reported, CUBIC itself doesn't use it
+    delivery_rate_estimator: delivery_rate::Estimator,
+    // Resumes the congestion window of a previous connection over the same path, if any
+    careful_resume: Option<CarefulResume>,
+    // Queries the saved parameters of the path once the initial window is in use
+    #[cfg(feature = "alloc")]
+    careful_resume_lookup: Option<careful_resume::Lookup>,
 }
 
 type BytesInFlight = Counter<u32>;
 
 impl CongestionController for CubicCongestionController {
-    type PacketInfo = ();
+    type PacketInfo = delivery_rate::PacketInfo;
 
     #[inline]
     fn congestion_window(&self) -> u32 {
@@ -208,10 +221,17 @@ impl CongestionController for CubicCongestionController {
         app_limited: Option<bool>,
         rtt_estimator: &RttEstimator,
         publisher: &mut Pub,
-    ) {
+    ) -> Self::PacketInfo {
+        let packet_info = self.delivery_rate_estimator.on_packet_sent(
+            *self.bytes_in_flight,
+            bytes_sent,
+            app_limited,
+            time_sent,
+        );
+
         if bytes_sent == 0 {
             // Packet was not congestion controlled
-            return;
+            return packet_info;
         }
 
         self.bytes_in_flight
@@ -248,6 +268,8 @@ impl CongestionController for CubicCongestionController {
             self.state.is_slow_start(),
             publisher,
         );
+
+        packet_info
     }
 
     #[inline]
@@ -288,17 +310,78 @@ impl CongestionController for CubicCongestionController {
         &mut self,
         newest_acked_time_sent: Timestamp,
         bytes_acknowledged: usize,
-        _newest_acked_packet_info: Self::PacketInfo,
+        newest_acked_packet_info: Self::PacketInfo,
         rtt_estimator: &RttEstimator,
         _random_generator: &mut dyn random::Generator,
         ack_receive_time: Timestamp,
         publisher: &mut Pub,
     ) {
+        self.delivery_rate_estimator.on_ack(
+            bytes_acknowledged,
+            newest_acked_time_sent,
+            newest_acked_packet_info,
+            ack_receive_time,
+            publisher,
+        );
+
         self.bytes_in_flight_hi = self.bytes_in_flight_hi.max(self.bytes_in_flight);
         self.bytes_in_flight
             .try_sub(bytes_acknowledged)
             .expect("bytes_acknowledged should not exceed u32::MAX");
 
+        #[cfg(feature = "alloc")]
+        if !self.under_utilized {
+            // The saved parameters are only needed once the sender is limited by the initial
+            // window, by which time any parameters echoed during the handshake have been saved
+            if let Some(saved) = self
+                .careful_resume_lookup
+                .take()
+                .and_then(|lookup| lookup.load())
+            {
+                self.careful_resume = Some(CarefulResume::new(saved));
+            }
+        }
+
+        if let Some(careful_resume) = self.careful_resume.as_mut() {
+            let was_active = careful_resume.is_active();
+
+            if let Some(congestion_window) = careful_resume.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                self.congestion_window as u32,
+                !self.under_utilized,
+                rtt_estimator.min_rtt(),
+                self.time_of_last_sent_packet
+                    .expect("At least one packet must be sent to receive an ACK"),
+                ack_receive_time,
+            ) {
+                self.congestion_window = congestion_window as f32;
+            }
+
+            if careful_resume.is_active() {
+                // Careful Resume controls the window until the resumed window is validated
+                return;
+            }
+
+            if careful_resume.phase() == careful_resume::Phase::Normal {
+                // Careful Resume has ended, so the window is no longer influenced by it
+                self.careful_resume = None;
+            }
+
+            if was_active {
+                if self.state.is_slow_start() {
+                    // The resumed window has been validated, so continue from it in congestion
+                    // avoidance rather than growing it further in slow start
+                    publisher
+                        .on_slow_start_exited(SlowStartExitCause::Other, self.congestion_window());
+                    self.state = State::congestion_avoidance(ack_receive_time);
+                    self.cubic.on_slow_start_exit(self.congestion_window);
+                }
+
+                return;
+            }
+        }
+
         if self.under_utilized {
             self.state.on_app_limited(ack_receive_time);
 
@@ -400,6 +483,7 @@ impl CongestionController for CubicCongestionController {
         debug_assert!(lost_bytes > 0);
 
         self.bytes_in_flight -= lost_bytes;
+        self.delivery_rate_estimator.on_loss(lost_bytes as usize);
 
         if self.state.is_slow_start() && !persistent_congestion {
             publisher
@@ -417,16 +501,24 @@ impl CongestionController for CubicCongestionController {
             self.congestion_window = self.cubic.minimum_window();
             self.state = State::SlowStart;
             self.cubic.reset();
+            self.careful_resume = None;
+            #[cfg(feature = "alloc")]
+            {
+                self.careful_resume_lookup = None;
+            }
         }
     }
 
     #[inline]
     fn on_explicit_congestion<Pub: Publisher>(
         &mut self,
-        _ce_count: u64,
+        ce_count: u64,
         event_time: Timestamp,
         publisher: &mut Pub,
     ) {
+        self.delivery_rate_estimator
+            .on_explicit_congestion(ce_count);
+
         if self.state.is_slow_start() {
             publisher.on_slow_start_exited(SlowStartExitCause::Ecn, self.congestion_window());
         }
@@ -478,6 +570,7 @@ impl CongestionController for CubicCongestionController {
         self.bytes_in_flight
             .try_sub(bytes_sent)
             .expect("bytes sent should not exceed u32::MAX");
+        self.delivery_rate_estimator.on_packet_discarded(bytes_sent);
 
         if let Recovery(recovery_start_time, RequiresTransmission) = self.state {
             // If any of the discarded packets were lost, they will no longer be retransmitted
@@ -491,6 +584,28 @@ impl CongestionController for CubicCongestionController {
     fn earliest_departure_time(&self) -> Option<Timestamp> {
         self.pacer.earliest_departure_time()
     }
+
+    #[inline]
+    fn delivery_rate_sample(&self) -> Option<RateSample> {
+        Some(self.delivery_rate_estimator.rate_sample())
+    }
+
+    #[inline]
+    fn congestion_state(&self) -> CongestionState {
+        let phase = match self.state {
+            SlowStart => CongestionPhase::SlowStart,
+            Recovery(..) => CongestionPhase::Recovery,
+            CongestionAvoidance(_) => CongestionPhase::CongestionAvoidance,
+        };
+
+        CongestionState {
+            congestion_window: self.congestion_window(),
+            bytes_in_flight: self.bytes_in_flight(),
+            pacing_rate: self.pacer.current_pacing_rate(),
+            phase,
+            loss_epoch: self.loss_epoch,
+        }
+    }
 }
 
 impl CubicCongestionController {
@@ -505,7 +620,10 @@ impl CubicCongestionController {
         Self {
             cubic,
             slow_start: HybridSlowStart::new(max_datagram_size),
-            pacer: Pacer::default(),
+            pacer: match app_settings.slow_start_pacing_gain() {
+                Some(gain) => Pacer::default().with_slow_start_gain(gain),
+                None => Pacer::default(),
+            },
             max_datagram_size,
             congestion_window,
             state: SlowStart,
@@ -513,9 +631,30 @@ impl CubicCongestionController {
             time_of_last_sent_packet: None,
             under_utilized: true,
             bytes_in_flight_hi: Counter::new(0),
+            loss_epoch: 0,
+            delivery_rate_estimator: Default::default(),
+            careful_resume: None,
+            #[cfg(feature = "alloc")]
+            careful_resume_lookup: None,
         }
     }
 
+    /// Resumes the congestion window of a previous connection over the same path using
+    /// Careful Resume
+    pub fn with_careful_resume(mut self, saved: careful_resume::SavedPath) -> Self {
+        self.careful_resume = Some(CarefulResume::new(saved));
+        self
+    }
+
+    /// Resumes the congestion window of a previous connection over the same path using
+    /// Careful Resume, if the parameters of the path are saved by the time the initial window
+    /// is in use
+    #[cfg(feature = "alloc")]
+    pub fn with_careful_resume_lookup(mut self, lookup: careful_resume::Lookup) -> Self {
+        self.careful_resume_lookup = Some(lookup);
+        self
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
     //# Endpoints SHOULD use an initial congestion
     //# window of ten times the maximum datagram size (max_datagram_size),
@@ -536,7 +675,9 @@ impl CubicCongestionController {
             10 * max_datagram_size as u32,
             max(INITIAL_WINDOW_LIMIT, 2 * max_datagram_size as u32),
         );
-        let initial_window = app_settings.initial_congestion_window.unwrap_or(default);
+        let initial_window = app_settings
+            .initial_congestion_window(max_datagram_size)
+            .unwrap_or(default);
 
         max(initial_window, cubic.minimum_window() as u32)
     }
@@ -627,6 +768,7 @@ impl CubicCongestionController {
         }
 
         // Enter recovery period.
+        self.loss_epoch += 1;
 
         //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
         //# The sender MUST exit slow start and enter a recovery period when a
@@ -652,6 +794,22 @@ impl CubicCongestionController {
         //# reported ECN-CE count, or persistent congestion.
         self.congestion_window = self.cubic.multiplicative_decrease(self.congestion_window);
 
+        // Congestion before the saved parameters are used rules them out, as in the
+        // reconnaissance phase
+        #[cfg(feature = "alloc")]
+        {
+            self.careful_resume_lookup = None;
+        }
+
+        if let Some(careful_resume) = self.careful_resume.as_mut() {
+            if let Some(congestion_window) = careful_resume.on_congestion_event(
+                self.time_of_last_sent_packet,
+                self.cubic.minimum_window() as u32,
+            ) {
+                self.congestion_window = congestion_window as f32;
+            }
+        }
+
         // Update Hybrid Slow Start with the decreased congestion window.
         self.slow_start.on_congestion_event(self.congestion_window);
     }
@@ -691,6 +849,22 @@ impl CubicCongestionController {
 #[derive(Default, Debug, Clone, Copy)]
 pub struct ApplicationSettings {
     initial_congestion_window: Option<u32>,
+    initial_congestion_window_packets: Option<u32>,
+    slow_start_pacing_gain: Option<u32>,
+}
+
+impl ApplicationSettings {
+    fn initial_congestion_window(&self, max_datagram_size: u16) -> Option<u32> {
+        self.initial_congestion_window.or_else(|| {
+            self.initial_congestion_window_packets
+                .map(|packets| packets.saturating_mul(max_datagram_size as u32))
+        })
+    }
+
+    fn slow_start_pacing_gain(&self) -> Option<Ratio<u64>> {
+        self.slow_start_pacing_gain
+            .map(|pacing_gain| Ratio::new_raw(pacing_gain as u64, 100))
+    }
 }
 
 /// Core functions of "CUBIC for Fast Long-Distance Networks" as specified in
@@ -883,6 +1057,8 @@ impl Cubic {
 #[derive(Debug, Default)]
 pub struct Endpoint {
     app_settings: ApplicationSettings,
+    #[cfg(feature = "alloc")]
+    saved_paths: Option<alloc::sync::Arc<dyn careful_resume::Store>>,
 }
 
 impl congestion_controller::Endpoint for Endpoint {
@@ -892,31 +1068,98 @@ impl congestion_controller::Endpoint for Endpoint {
         &mut self,
         path_info: congestion_controller::PathInfo,
     ) -> Self::CongestionController {
-        CubicCongestionController::new(path_info.max_datagram_size, self.app_settings)
+        #[cfg(feature = "alloc")]
+        {
+            let controller =
+                CubicCongestionController::new(path_info.max_datagram_size, self.app_settings);
+
+            match self.saved_paths.as_ref() {
+                Some(store) => {
+                    let lookup =
+                        careful_resume::Lookup::new(store.clone(), path_info.remote_address.into());
+                    controller.with_careful_resume_lookup(lookup)
+                }
+                None => controller,
+            }
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            CubicCongestionController::new(path_info.max_datagram_size, self.app_settings)
+        }
     }
 }
 
 pub mod builder {
     use super::{ApplicationSettings, Endpoint};
+    #[cfg(feature = "alloc")]
+    use crate::recovery::careful_resume;
 
     /// Build the congestion controller endpoint with application provided overrides
     #[derive(Default)]
     pub struct Builder {
         initial_congestion_window: Option<u32>,
+        initial_congestion_window_packets: Option<u32>,
+        slow_start_pacing_gain: Option<u32>,
+        #[cfg(feature = "alloc")]
+        saved_paths: Option<alloc::sync::Arc<dyn careful_resume::Store>>,
     }
 
     impl Builder {
         /// Set the initial congestion window in bytes.
+        ///
+        /// Overrides any value set with `with_initial_congestion_window_packets`.
         pub fn with_initial_congestion_window(mut self, initial_congestion_window: u32) -> Self {
             self.initial_congestion_window = Some(initial_congestion_window);
+            self.initial_congestion_window_packets = None;
+            self
+        }
+
+        /// Set the initial congestion window in packets of the current max datagram size
+        /// (default: 10).
+        ///
+        /// Overrides any value set with `with_initial_congestion_window`.
+        pub fn with_initial_congestion_window_packets(
+            mut self,
+            initial_congestion_window_packets: u32,
+        ) -> Self {
+            self.initial_congestion_window_packets = Some(initial_congestion_window_packets);
+            self.initial_congestion_window = None;
+            self
+        }
+
+        #[cfg(feature = "unstable-congestion-controller")]
+        /// Set the factor applied to the congestion window to produce the pacing rate
+        /// while in slow start (default: 2).
+        ///
+        /// The gain value is calculated as the ratio: `slow_start_pacing_gain / 100`
+        pub fn with_slow_start_pacing_gain(mut self, slow_start_pacing_gain: u32) -> Self {
+            self.slow_start_pacing_gain = Some(slow_start_pacing_gain);
+            self
+        }
+
+        /// Resume the congestion window of previous connections using Careful Resume
+        ///
+        /// The `store` is queried for the parameters saved for each new path once the initial
+        /// window is in use. The saved window is only used after the path has been confirmed to
+        /// have a similar RTT, and the window is reduced if it turns out to cause congestion.
+        #[cfg(feature = "alloc")]
+        pub fn with_careful_resume<S: careful_resume::Store>(mut self, store: S) -> Self {
+            self.saved_paths = Some(alloc::sync::Arc::new(store));
             self
         }
 
         pub fn build(self) -> Endpoint {
             let app_settings = ApplicationSettings {
                 initial_congestion_window: self.initial_congestion_window,
+                initial_congestion_window_packets: self.initial_congestion_window_packets,
+                slow_start_pacing_gain: self.slow_start_pacing_gain,
             };
-            Endpoint { app_settings }
+            Endpoint {
+                app_settings,
+                #[cfg(feature = "alloc")]
+                saved_paths: self.saved_paths,
+            }
         }
     }
 }
@@ -4,11 +4,12 @@ use super::*;
 use crate::{
     event,
     packet::number::PacketNumberSpace,
-    path,
+    path::{self, MINIMUM_MAX_DATAGRAM_SIZE},
     recovery::congestion_controller::PathPublisher,
     time::{Clock, NoopClock},
 };
 use core::time::Duration;
+use num_rational::Ratio;
 
 #[macro_export]
 macro_rules! assert_delta {
@@ -23,6 +24,19 @@ macro_rules! assert_delta {
     };
 }
 
+fn packet_info() -> delivery_rate::PacketInfo {
+    let now = NoopClock.get_time();
+    delivery_rate::PacketInfo {
+        delivered_bytes: 0,
+        delivered_time: now,
+        lost_bytes: 0,
+        ecn_ce_count: 0,
+        first_sent_time: now,
+        bytes_in_flight: 0,
+        is_app_limited: false,
+    }
+}
+
 fn bytes_to_packets(bytes: f32, max_datagram_size: u16) -> f32 {
     bytes / max_datagram_size as f32
 }
@@ -222,6 +236,76 @@ fn initial_window_with_app_settings() {
     );
 }
 
+// ApplicationSettings.initial_congestion_window_packets
+#[test]
+fn initial_window_packets_with_app_settings() {
+    let mut app_settings = ApplicationSettings {
+        initial_congestion_window_packets: Some(32),
+        ..Default::default()
+    };
+
+    let max_datagram_size = 1350;
+    let cubic = Cubic::new(max_datagram_size);
+    assert_eq!(
+        max_datagram_size as u32 * 32,
+        CubicCongestionController::initial_window(&cubic, max_datagram_size, &app_settings)
+    );
+
+    // the window is recalculated with the new max datagram size
+    let max_datagram_size = 1500;
+    let cubic = Cubic::new(max_datagram_size);
+    assert_eq!(
+        max_datagram_size as u32 * 32,
+        CubicCongestionController::initial_window(&cubic, max_datagram_size, &app_settings)
+    );
+
+    app_settings.initial_congestion_window_packets = Some(0);
+    assert_eq!(
+        cubic.minimum_window() as u32,
+        CubicCongestionController::initial_window(&cubic, max_datagram_size, &app_settings)
+    );
+}
+
+// ApplicationSettings.slow_start_pacing_gain
+#[test]
+fn slow_start_pacing_gain_with_app_settings() {
+    let app_settings = ApplicationSettings {
+        slow_start_pacing_gain: Some(400),
+        ..Default::default()
+    };
+    let mut default_cc =
+        CubicCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+    let mut cc = CubicCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, app_settings);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::new(Duration::from_millis(100));
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+    // send enough bursts of packets to move beyond the initial interval
+    for _ in 0..30 {
+        default_cc.on_packet_sent(
+            now,
+            MINIMUM_MAX_DATAGRAM_SIZE as usize,
+            None,
+            &rtt_estimator,
+            &mut publisher,
+        );
+        cc.on_packet_sent(
+            now,
+            MINIMUM_MAX_DATAGRAM_SIZE as usize,
+            None,
+            &rtt_estimator,
+            &mut publisher,
+        );
+    }
+
+    // the larger gain doubles the pacing rate used in slow start
+    assert_eq!(
+        default_cc.pacer.current_pacing_rate().unwrap() * Ratio::new(2, 1),
+        cc.pacer.current_pacing_rate().unwrap()
+    );
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
 //= type=test
 //# The RECOMMENDED
@@ -490,7 +574,7 @@ fn congestion_avoidance_after_idle_period() {
     cc.on_ack(
         now,
         1000,
-        (),
+        packet_info(),
         rtt_estimator,
         random,
         now + Duration::from_secs(16),
@@ -525,7 +609,7 @@ fn congestion_avoidance_after_idle_period() {
     cc.on_ack(
         now,
         1000,
-        (),
+        packet_info(),
         rtt_estimator,
         random,
         now + Duration::from_secs(25),
@@ -563,7 +647,15 @@ fn congestion_avoidance_after_fast_convergence() {
     cc.congestion_window = 80_000.0;
     cc.cubic.w_last_max = bytes_to_packets(100_000.0, max_datagram_size);
 
-    cc.on_packet_lost(100, (), false, false, random, now, &mut publisher);
+    cc.on_packet_lost(
+        100,
+        packet_info(),
+        false,
+        false,
+        random,
+        now,
+        &mut publisher,
+    );
     assert_delta!(cc.congestion_window, 80_000.0 * BETA_CUBIC, 0.001);
 
     // Window max was less than the last max, so fast convergence applies
@@ -678,7 +770,7 @@ fn on_packet_lost() {
 
     cc.on_packet_lost(
         100,
-        (),
+        packet_info(),
         false,
         false,
         random,
@@ -719,7 +811,7 @@ fn on_packet_lost_below_minimum_window() {
 
     cc.on_packet_lost(
         100,
-        (),
+        packet_info(),
         false,
         false,
         random,
@@ -743,13 +835,74 @@ fn on_packet_lost_already_in_recovery() {
 
     // break up on_packet_loss into two call to confirm double call
     // behavior is valid (50 + 50 = 100 lost bytes)
-    cc.on_packet_lost(50, (), false, false, random, now, &mut publisher);
-    cc.on_packet_lost(50, (), false, false, random, now, &mut publisher);
+    cc.on_packet_lost(50, packet_info(), false, false, random, now, &mut publisher);
+    cc.on_packet_lost(50, packet_info(), false, false, random, now, &mut publisher);
 
     // No change to the congestion window
     assert_delta!(cc.congestion_window, 10000.0, 0.001);
 }
 
+#[test]
+fn congestion_state() {
+    let mut cc = CubicCongestionController::new(1000, Default::default());
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+    cc.congestion_window = 100_000.0;
+    cc.bytes_in_flight = BytesInFlight::new(10_000);
+
+    let state = cc.congestion_state();
+    assert!(matches!(state.phase, CongestionPhase::SlowStart));
+    assert_eq!(state.congestion_window, 100_000);
+    assert_eq!(state.bytes_in_flight, 10_000);
+    assert_eq!(state.loss_epoch, 0);
+
+    cc.on_packet_lost(
+        100,
+        packet_info(),
+        false,
+        false,
+        random,
+        now,
+        &mut publisher,
+    );
+
+    let state = cc.congestion_state();
+    assert!(matches!(state.phase, CongestionPhase::Recovery));
+    assert_eq!(state.loss_epoch, 1);
+
+    // Additional losses in the same recovery period don't start a new epoch
+    cc.on_packet_lost(
+        100,
+        packet_info(),
+        false,
+        false,
+        random,
+        now,
+        &mut publisher,
+    );
+    assert_eq!(cc.congestion_state().loss_epoch, 1);
+
+    cc.state = State::congestion_avoidance(now);
+    assert!(matches!(
+        cc.congestion_state().phase,
+        CongestionPhase::CongestionAvoidance
+    ));
+
+    // A loss after leaving recovery starts a new epoch
+    cc.on_packet_lost(
+        100,
+        packet_info(),
+        false,
+        false,
+        random,
+        now + Duration::from_secs(1),
+        &mut publisher,
+    );
+    assert_eq!(cc.congestion_state().loss_epoch, 2);
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
 //= type=test
 //# When persistent congestion is declared, the sender's congestion
@@ -767,7 +920,7 @@ fn on_packet_lost_persistent_congestion() {
     cc.bytes_in_flight = BytesInFlight::new(1000);
     cc.state = Recovery(now, Idle);
 
-    cc.on_packet_lost(100, (), true, false, random, now, &mut publisher);
+    cc.on_packet_lost(100, packet_info(), true, false, random, now, &mut publisher);
 
     assert!(cc.state.is_slow_start());
     assert_eq!(cc.state, SlowStart);
@@ -834,6 +987,35 @@ fn on_packet_discarded() {
     assert_eq!(Recovery(now, FastRetransmission::Idle), cc.state);
 }
 
+#[test]
+fn delivery_rate_sample() {
+    let mut cc = CubicCongestionController::new(1000, Default::default());
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    let packet_info = cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    assert_eq!(packet_info.bytes_in_flight, 1000);
+
+    let ack_time = now + Duration::from_millis(100);
+    cc.on_ack(
+        now,
+        1000,
+        packet_info,
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+
+    let rate_sample = cc.delivery_rate_sample().unwrap();
+    assert_eq!(rate_sample.delivered_bytes, 1000);
+    assert_eq!(rate_sample.interval, Duration::from_millis(100));
+    assert_eq!(rate_sample.delivery_rate().as_bytes_per_second(), 10_000);
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
 //= type=test
 //# When bytes in flight is smaller than the congestion window and
@@ -856,7 +1038,7 @@ fn on_packet_ack_limited() {
     cc.on_ack(
         now,
         1,
-        (),
+        packet_info(),
         &RttEstimator::default(),
         random,
         now,
@@ -870,7 +1052,7 @@ fn on_packet_ack_limited() {
     cc.on_ack(
         now,
         1,
-        (),
+        packet_info(),
         &RttEstimator::default(),
         random,
         now,
@@ -894,7 +1076,15 @@ fn on_packet_ack_timestamp_regression() {
     cc.under_utilized = true;
     cc.state = State::congestion_avoidance(now);
 
-    cc.on_ack(now, 1, (), &rtt_estimator, random, now, &mut publisher);
+    cc.on_ack(
+        now,
+        1,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
 
     assert_eq!(
         State::CongestionAvoidance(CongestionAvoidanceTiming {
@@ -908,7 +1098,7 @@ fn on_packet_ack_timestamp_regression() {
     cc.on_ack(
         now,
         1,
-        (),
+        packet_info(),
         &rtt_estimator,
         random,
         now - Duration::from_secs(1),
@@ -935,7 +1125,15 @@ fn on_packet_ack_utilized_then_under_utilized() {
     cc.state = SlowStart;
 
     cc.on_packet_sent(now, 60_000, Some(true), &rtt_estimator, &mut publisher);
-    cc.on_ack(now, 10_000, (), &rtt_estimator, random, now, &mut publisher);
+    cc.on_ack(
+        now,
+        10_000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
     let cwnd = cc.congestion_window();
 
     assert!(!cc.under_utilized);
@@ -946,7 +1144,7 @@ fn on_packet_ack_utilized_then_under_utilized() {
     cc.on_ack(
         now,
         1200,
-        (),
+        packet_info(),
         &rtt_estimator,
         random,
         now + Duration::from_millis(100),
@@ -958,7 +1156,7 @@ fn on_packet_ack_utilized_then_under_utilized() {
     cc.on_ack(
         now,
         40_000,
-        (),
+        packet_info(),
         &rtt_estimator,
         random,
         now + Duration::from_millis(100),
@@ -976,7 +1174,7 @@ fn on_packet_ack_utilized_then_under_utilized() {
     cc.on_ack(
         now,
         1200,
-        (),
+        packet_info(),
         &rtt_estimator,
         random,
         now + Duration::from_millis(201),
@@ -1005,7 +1203,15 @@ fn on_packet_ack_congestion_avoidance_max_cwnd() {
     cc.cubic.w_max = 100_000.0;
 
     cc.on_packet_sent(now, 60_000, Some(false), &rtt_estimator, &mut publisher);
-    cc.on_ack(now, 60_000, (), &rtt_estimator, random, now, &mut publisher);
+    cc.on_ack(
+        now,
+        60_000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
 
     // 60_000 is the highest bytes in flight * 1.5 for the congestion avoidance max_cwnd multiplier
     assert_eq!(90_000, cc.congestion_window());
@@ -1029,7 +1235,7 @@ fn on_packet_ack_recovery_to_congestion_avoidance() {
     cc.on_ack(
         now + Duration::from_millis(1),
         1,
-        (),
+        packet_info(),
         &RttEstimator::default(),
         random,
         now + Duration::from_millis(2),
@@ -1061,7 +1267,7 @@ fn on_packet_ack_slow_start_to_congestion_avoidance() {
     cc.on_ack(
         now,
         100,
-        (),
+        packet_info(),
         &RttEstimator::default(),
         random,
         now + Duration::from_millis(2),
@@ -1096,7 +1302,7 @@ fn on_packet_ack_recovery() {
     cc.on_ack(
         now,
         100,
-        (),
+        packet_info(),
         &RttEstimator::default(),
         random,
         now + Duration::from_millis(2),
@@ -1140,7 +1346,7 @@ fn on_packet_ack_congestion_avoidance() {
     cc.on_ack(
         now,
         1000,
-        (),
+        packet_info(),
         &rtt_estimator,
         random,
         now + Duration::from_millis(4750),
@@ -1256,3 +1462,240 @@ fn on_packet_ack_congestion_avoidance_too_large_increase() {
     assert!(cc.cubic.w_cubic(t) > cc.cubic.w_est(t, rtt));
     assert_delta!(cc.congestion_window, 3_600_000.0 + 1000.0 / 2.0, 0.001);
 }
+
+#[test]
+fn careful_resume_validated() {
+    let saved = careful_resume::SavedPath::new(200_000, Duration::from_millis(100));
+    let mut cc =
+        CubicCongestionController::new(1000, Default::default()).with_careful_resume(saved);
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let mut rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+    let initial_window = cc.congestion_window;
+
+    // the saved window isn't used while the sender is application limited
+    cc.on_packet_sent(now, 1000, Some(true), &rtt_estimator, &mut publisher);
+    cc.on_ack(
+        now,
+        1000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, initial_window, 0.001);
+    assert!(cc.careful_resume.is_some());
+
+    // fill the initial window
+    cc.on_packet_sent(
+        now,
+        initial_window as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    assert!(cc.is_congestion_limited());
+
+    let ack_time = now + Duration::from_millis(100);
+    rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(100),
+        ack_time,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+
+    // the first acknowledgement jumps to half of the saved window
+    cc.on_ack(
+        now,
+        1000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, 100_000.0, 0.001);
+    assert!(cc.state.is_slow_start());
+
+    let jump_sent = ack_time + Duration::from_millis(1);
+    cc.on_packet_sent(
+        jump_sent,
+        50_000,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+
+    // the window doesn't grow until the first jump packet is acknowledged
+    cc.on_ack(
+        now,
+        1000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, 100_000.0, 0.001);
+
+    // the window is reduced to the confirmed capacity
+    let validate_time = jump_sent + Duration::from_millis(100);
+    cc.on_ack(
+        jump_sent,
+        50_000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        validate_time,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, initial_window + 51_000.0, 0.001);
+
+    // careful resume exits to congestion avoidance
+    assert!(cc.careful_resume.is_none());
+    assert_eq!(cc.state, State::congestion_avoidance(validate_time));
+}
+
+#[test]
+fn careful_resume_safe_retreat() {
+    let saved = careful_resume::SavedPath::new(200_000, Duration::from_millis(100));
+    let mut cc =
+        CubicCongestionController::new(1000, Default::default()).with_careful_resume(saved);
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let mut rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+    let initial_window = cc.congestion_window;
+
+    cc.on_packet_sent(
+        now,
+        initial_window as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    let ack_time = now + Duration::from_millis(100);
+    rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(100),
+        ack_time,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    cc.on_ack(
+        now,
+        1000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, 100_000.0, 0.001);
+
+    let jump_sent = ack_time + Duration::from_millis(1);
+    cc.on_packet_sent(
+        jump_sent,
+        90_000,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+
+    // a loss during the unvalidated phase retreats to half of the confirmed capacity
+    cc.on_packet_lost(
+        1000,
+        packet_info(),
+        false,
+        false,
+        random,
+        jump_sent,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, initial_window / 2.0, 0.001);
+    assert!(matches!(cc.state, Recovery(..)));
+
+    // the window doesn't grow until the last jump packet is acknowledged
+    cc.on_ack(
+        jump_sent,
+        1000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        jump_sent,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, initial_window / 2.0, 0.001);
+    assert!(cc.careful_resume.is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn careful_resume_lookup() {
+    let store = std::sync::Arc::new(careful_resume::Cache::default());
+    let remote_address: std::net::SocketAddr = "127.0.0.1:443".parse().unwrap();
+    let lookup = careful_resume::Lookup::new(
+        store.clone(),
+        crate::inet::SocketAddress::from(remote_address).into(),
+    );
+    let mut cc =
+        CubicCongestionController::new(1000, Default::default()).with_careful_resume_lookup(lookup);
+    let mut publisher = event::testing::Publisher::snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let mut rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+    let initial_window = cc.congestion_window;
+
+    // the store isn't queried while the sender is application limited
+    cc.on_packet_sent(now, 1000, Some(true), &rtt_estimator, &mut publisher);
+    cc.on_ack(
+        now,
+        1000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
+    assert!(cc.careful_resume.is_none());
+    assert!(cc.careful_resume_lookup.is_some());
+
+    // parameters saved after the controller was created are used
+    store.insert(
+        remote_address,
+        careful_resume::SavedPath::new(200_000, Duration::from_millis(100)),
+    );
+
+    cc.on_packet_sent(
+        now,
+        initial_window as usize,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+    let ack_time = now + Duration::from_millis(100);
+    rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(100),
+        ack_time,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    cc.on_ack(
+        now,
+        1000,
+        packet_info(),
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_delta!(cc.congestion_window, 100_000.0, 0.001);
+    assert!(cc.careful_resume_lookup.is_none());
+}
@@ -1,13 +1,19 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{event, event::IntoEvent, recovery::congestion_controller::Publisher, time::Timestamp};
-use core::{
-    cmp::{max, Ordering},
-    time::Duration,
+//! Delivery rate estimation
+//!
+//! The [`Estimator`] samples the rate at which data is delivered over a path. It is used by BBR to
+//! model the bottleneck bandwidth, but doesn't depend on any particular congestion controller so
+//! other controllers can consume the [`RateSample`]s as well.
+
+use crate::{
+    event,
+    event::IntoEvent,
+    recovery::{bandwidth::Bandwidth, congestion_controller::Publisher},
+    time::Timestamp,
 };
-use num_rational::Ratio;
-use num_traits::Inv;
+use core::{cmp::max, time::Duration};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 /// Bandwidth-related data tracked for each sent packet
@@ -30,143 +36,6 @@ pub struct PacketInfo {
     pub is_app_limited: bool,
 }
 
-/// Represents a rate at which data is transferred
-///
-/// While bandwidth is typically thought of as an amount of data over a fixed
-/// amount of time (bytes per second, for example), in this case we internally
-/// represent bandwidth as the inverse: an amount of time to send a fixed amount
-/// of data (nanoseconds per kibibyte or 1024 bytes, in this case). This allows for
-/// some of the math operations needed on `Bandwidth` to avoid division, while
-/// reducing the likelihood of panicking due to overflow.
-///
-/// The maximum (non-infinite) value that can be represented is ~1 TB/second.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct Bandwidth {
-    nanos_per_kibibyte: u64,
-}
-
-// 2^10 = 1024 bytes in kibibyte
-const KIBIBYTE_SHIFT: u8 = 10;
-
-impl Bandwidth {
-    pub const ZERO: Bandwidth = Bandwidth {
-        nanos_per_kibibyte: u64::MAX,
-    };
-
-    pub const INFINITY: Bandwidth = Bandwidth {
-        nanos_per_kibibyte: 0,
-    };
-
-    /// Constructs a new `Bandwidth` with the given bytes per interval
-    pub const fn new(bytes: u64, interval: Duration) -> Self {
-        let interval = (interval.as_nanos() as u64) << KIBIBYTE_SHIFT;
-        if interval == 0 || bytes == 0 {
-            Bandwidth::ZERO
-        } else {
-            Self {
-                nanos_per_kibibyte: interval / bytes,
-            }
-        }
-    }
-
-    #[inline]
-    pub fn serialize(self) -> u64 {
-        self.nanos_per_kibibyte
-    }
-
-    #[inline]
-    pub fn deserialize(value: u64) -> Self {
-        Self {
-            nanos_per_kibibyte: value,
-        }
-    }
-
-    /// Represents the bandwidth as bytes per second
-    pub fn as_bytes_per_second(&self) -> u64 {
-        const ONE_SECOND_IN_NANOS: u64 = Duration::from_secs(1).as_nanos() as u64;
-
-        if *self == Bandwidth::INFINITY {
-            return u64::MAX;
-        }
-
-        (ONE_SECOND_IN_NANOS << KIBIBYTE_SHIFT) / self.nanos_per_kibibyte
-    }
-}
-
-impl Default for Bandwidth {
-    fn default() -> Self {
-        Bandwidth::ZERO
-    }
-}
-
-impl core::cmp::PartialOrd for Bandwidth {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl core::cmp::Ord for Bandwidth {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // The higher the nanos_per_kibibyte, the lower the bandwidth,
-        // so reverse the ordering when comparing
-        self.nanos_per_kibibyte
-            .cmp(&other.nanos_per_kibibyte)
-            .reverse()
-    }
-}
-
-impl core::ops::Mul<Ratio<u64>> for Bandwidth {
-    type Output = Bandwidth;
-
-    fn mul(self, rhs: Ratio<u64>) -> Self::Output {
-        if self == Bandwidth::ZERO {
-            return Bandwidth::ZERO;
-        }
-
-        Bandwidth {
-            // Since `Bandwidth` is represented as time/byte and not bytes/time, we should divide
-            // by the given ratio to result in a higher nanos_per_kibibyte value (lower bandwidth).
-            // To avoid division, we can multiply by the inverse of the ratio instead
-            nanos_per_kibibyte: (rhs.inv() * self.nanos_per_kibibyte).to_integer(),
-        }
-    }
-}
-
-impl core::ops::Mul<Duration> for Bandwidth {
-    type Output = u64;
-
-    fn mul(self, rhs: Duration) -> Self::Output {
-        if self == Bandwidth::INFINITY {
-            return u64::MAX;
-        } else if rhs.is_zero() {
-            return 0;
-        }
-
-        let interval = (rhs.as_nanos() as u64) << KIBIBYTE_SHIFT;
-
-        if interval == 0 {
-            return u64::MAX;
-        }
-
-        interval / self.nanos_per_kibibyte
-    }
-}
-
-/// Divides a count of bytes represented as a u64 by the given `Bandwidth`
-///
-/// Since `Bandwidth` is a rate of bytes over a time period, this division
-/// results in a `Duration` being returned, representing how long a path
-/// with the given `Bandwidth` would take to transmit the given number of
-/// bytes.
-impl core::ops::Div<Bandwidth> for u64 {
-    type Output = Duration;
-
-    fn div(self, rhs: Bandwidth) -> Self::Output {
-        #[allow(clippy::suspicious_arithmetic_impl)]
-        Duration::from_nanos(rhs.nanos_per_kibibyte.saturating_mul(self) >> KIBIBYTE_SHIFT)
-    }
-}
-
 #[derive(Clone, Copy, Debug, Default)]
 /// A bandwidth delivery rate estimate with associated metadata
 pub struct RateSample {
@@ -301,7 +170,10 @@ impl Estimator {
         //#   P.delivered_time  = C.delivered_time
         //#   P.delivered       = C.delivered
         //#   P.is_app_limited  = (C.app_limited != 0)
-        if prior_bytes_in_flight == 0 {
+        //
+        // The estimator may also be attached to a controller that already has bytes in flight,
+        // in which case the interval is started with the first packet it observes.
+        if prior_bytes_in_flight == 0 || self.first_sent_time.is_none() {
             self.first_sent_time = Some(now);
             self.delivered_time = Some(now);
         }
@@ -9,10 +9,13 @@ pub use sent_packets::*;
 
 pub mod bandwidth;
 pub mod bbr;
+pub mod careful_resume;
 pub mod congestion_controller;
 pub mod cubic;
+pub mod delivery_rate;
 mod hybrid_slow_start;
 pub mod loss;
+pub mod one_way_delay;
 mod pacing;
 pub mod persistent_congestion;
 mod pto;
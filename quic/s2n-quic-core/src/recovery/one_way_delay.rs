@@ -0,0 +1,265 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Estimates one-way delays from the TIMESTAMP frames sent by the peer
+//!
+//! A TIMESTAMP frame carries the time at which the peer sent the packet containing it, measured
+//! by the peer's clock. When that packet also carries an ACK frame which produces an RTT sample,
+//! the peer's timestamp splits the round trip into the time spent reaching the peer and the time
+//! spent returning from it.
+//!
+//! Since the clocks of the endpoints are not synchronized, the absolute delays are unknown. The
+//! estimator instead tracks the smallest delay observed in each direction and reports the delay
+//! of each sample in excess of it, which is the queuing delay experienced by the packets.
+
+use crate::{ack, packet::number::PacketNumber, time::Timestamp, varint::VarInt};
+use core::time::Duration;
+
+/// The queuing delays observed in a one-way delay sample
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OneWayDelay {
+    /// The delay experienced by the acknowledged packet on the way to the peer, in excess of the
+    /// smallest delay observed on the path
+    pub forward: Duration,
+    /// The delay experienced by the acknowledgement on the way from the peer, in excess of the
+    /// smallest delay observed on the path
+    pub reverse: Duration,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+enum Pending {
+    #[default]
+    None,
+    /// An ACK frame produced an RTT sample but the TIMESTAMP frame in the same packet hasn't
+    /// been processed yet
+    RttSample {
+        packet_number: PacketNumber,
+        time_sent: Timestamp,
+        ack_delay: VarInt,
+        now: Timestamp,
+    },
+    /// A TIMESTAMP frame was processed but the ACK frame in the same packet hasn't been
+    /// processed yet
+    Timestamp {
+        packet_number: PacketNumber,
+        timestamp: VarInt,
+        peer_settings: ack::Settings,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Estimator {
+    /// The local time which local timestamps are measured from
+    epoch: Option<Timestamp>,
+    /// The smallest forward delay observed, including the offset between the clocks
+    min_forward: Option<i64>,
+    /// The smallest reverse delay observed, including the offset between the clocks
+    min_reverse: Option<i64>,
+    pending: Pending,
+    latest: Option<OneWayDelay>,
+}
+
+impl Estimator {
+    /// Returns the most recent one-way delay sample, if any
+    #[inline]
+    pub fn latest(&self) -> Option<OneWayDelay> {
+        self.latest
+    }
+
+    /// Called when an ACK frame in the packet `packet_number` produced an RTT sample
+    ///
+    /// `time_sent` is the time the largest acknowledged packet was sent, `ack_delay` is the
+    /// encoded `Ack Delay` field of the ACK frame and `now` is the time the ACK frame was received.
+    ///
+    /// Returns a sample if the TIMESTAMP frame in the same packet was already processed.
+    #[inline]
+    pub fn on_rtt_sample(
+        &mut self,
+        packet_number: PacketNumber,
+        time_sent: Timestamp,
+        ack_delay: VarInt,
+        now: Timestamp,
+    ) -> Option<OneWayDelay> {
+        match core::mem::take(&mut self.pending) {
+            Pending::Timestamp {
+                packet_number: pn,
+                timestamp,
+                peer_settings,
+            } if pn == packet_number => {
+                let ack_delay = peer_settings.decode_ack_delay(ack_delay);
+                let peer_time = peer_settings.decode_ack_delay(timestamp);
+                Some(self.on_sample(time_sent, ack_delay, peer_time, now))
+            }
+            _ => {
+                self.pending = Pending::RttSample {
+                    packet_number,
+                    time_sent,
+                    ack_delay,
+                    now,
+                };
+                None
+            }
+        }
+    }
+
+    /// Called when a TIMESTAMP frame is received in the packet `packet_number`
+    ///
+    /// `timestamp` is the encoded `Timestamp` field of the frame, and `peer_settings` contains
+    /// the `ack_delay_exponent` used by the peer to encode it and its `Ack Delay` fields.
+    ///
+    /// Returns a sample if the ACK frame in the same packet was already processed.
+    #[inline]
+    pub fn on_timestamp(
+        &mut self,
+        packet_number: PacketNumber,
+        timestamp: VarInt,
+        peer_settings: ack::Settings,
+    ) -> Option<OneWayDelay> {
+        match core::mem::take(&mut self.pending) {
+            Pending::RttSample {
+                packet_number: pn,
+                time_sent,
+                ack_delay,
+                now,
+            } if pn == packet_number => {
+                let ack_delay = peer_settings.decode_ack_delay(ack_delay);
+                let peer_time = peer_settings.decode_ack_delay(timestamp);
+                Some(self.on_sample(time_sent, ack_delay, peer_time, now))
+            }
+            _ => {
+                self.pending = Pending::Timestamp {
+                    packet_number,
+                    timestamp,
+                    peer_settings,
+                };
+                None
+            }
+        }
+    }
+
+    fn on_sample(
+        &mut self,
+        time_sent: Timestamp,
+        ack_delay: Duration,
+        peer_time: Duration,
+        now: Timestamp,
+    ) -> OneWayDelay {
+        let epoch = *self.epoch.get_or_insert(time_sent);
+        let local_time = |time: Timestamp| -> i64 {
+            if time >= epoch {
+                micros(time - epoch)
+            } else {
+                -micros(epoch - time)
+            }
+        };
+
+        let peer_sent = micros(peer_time);
+        // the peer received the packet `ack_delay` before sending the acknowledgement
+        let peer_received = peer_sent.saturating_sub(micros(ack_delay));
+
+        let forward = peer_received.saturating_sub(local_time(time_sent));
+        let reverse = local_time(now).saturating_sub(peer_sent);
+
+        let excess = |min: &mut Option<i64>, value: i64| -> Duration {
+            let min = min.get_or_insert(value);
+            *min = (*min).min(value);
+            Duration::from_micros(value.saturating_sub(*min) as u64)
+        };
+
+        let sample = OneWayDelay {
+            forward: excess(&mut self.min_forward, forward),
+            reverse: excess(&mut self.min_reverse, reverse),
+        };
+        self.latest = Some(sample);
+        sample
+    }
+}
+
+#[inline]
+fn micros(duration: Duration) -> i64 {
+    duration.as_micros().try_into().unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        packet::number::PacketNumberSpace,
+        time::{Clock, NoopClock},
+    };
+
+    fn pn(value: u32) -> PacketNumber {
+        PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u32(value))
+    }
+
+    #[test]
+    fn queuing_delay_test() {
+        let mut estimator = Estimator::default();
+        let settings = ack::Settings::RECOMMENDED;
+        let start = NoopClock.get_time();
+        let ms = Duration::from_millis;
+        let encode = |duration| settings.encode_ack_delay(duration);
+
+        // the peer's clock is ahead by an unknown amount
+        let offset = ms(5_000);
+
+        // 10ms to reach the peer, 5ms ack delay, 10ms to return
+        assert_eq!(
+            estimator.on_rtt_sample(pn(1), start, encode(ms(5)), start + ms(25)),
+            None
+        );
+        assert_eq!(
+            estimator.on_timestamp(pn(1), encode(offset + ms(15)), settings),
+            Some(OneWayDelay::default())
+        );
+
+        // 30ms to reach the peer, 10ms to return
+        let sent = start + ms(100);
+        assert_eq!(
+            estimator.on_timestamp(pn(2), encode(offset + ms(130)), settings),
+            None
+        );
+        assert_eq!(
+            estimator.on_rtt_sample(pn(2), sent, encode(ms(0)), sent + ms(40)),
+            Some(OneWayDelay {
+                forward: ms(20),
+                reverse: ms(0),
+            })
+        );
+
+        // 5ms to reach the peer, which lowers the minimum, and 25ms to return
+        let sent = start + ms(200);
+        estimator.on_rtt_sample(pn(3), sent, encode(ms(0)), sent + ms(30));
+        let expected = OneWayDelay {
+            forward: ms(0),
+            reverse: ms(15),
+        };
+        assert_eq!(
+            estimator.on_timestamp(pn(3), encode(offset + ms(205)), settings),
+            Some(expected)
+        );
+        assert_eq!(estimator.latest(), Some(expected));
+    }
+
+    #[test]
+    fn mismatched_packets_test() {
+        let mut estimator = Estimator::default();
+        let settings = ack::Settings::RECOMMENDED;
+        let start = NoopClock.get_time();
+
+        // frames from different packets are never combined
+        assert_eq!(
+            estimator.on_timestamp(pn(1), VarInt::from_u8(10), settings),
+            None
+        );
+        assert_eq!(
+            estimator.on_rtt_sample(pn(2), start, VarInt::from_u8(0), start),
+            None
+        );
+        assert_eq!(
+            estimator.on_timestamp(pn(3), VarInt::from_u8(10), settings),
+            None
+        );
+        assert_eq!(estimator.latest(), None);
+    }
+}
@@ -39,9 +39,20 @@ pub struct Pacer {
     capacity: Counter<u32, Saturating>,
     // The time the next packet should be transmitted
     next_packet_departure_time: Option<Timestamp>,
+    // The most recently calculated pacing rate
+    pacing_rate: Option<Bandwidth>,
+    // Overrides `SLOW_START_N` while in slow start
+    slow_start_gain: Option<Ratio<u64>>,
 }
 
 impl Pacer {
+    /// Sets the value of `N` used while in slow start (default: 2)
+    #[inline]
+    pub fn with_slow_start_gain(mut self, slow_start_gain: Ratio<u64>) -> Self {
+        self.slow_start_gain = Some(slow_start_gain);
+        self
+    }
+
     /// Called when each packet has been written
     #[inline]
     pub fn on_packet_sent<Pub: Publisher>(
@@ -60,15 +71,21 @@ impl Pacer {
 
         if self.capacity == 0 {
             if let Some(next_packet_departure_time) = self.next_packet_departure_time {
+                let n = self.gain(slow_start);
                 let interval = Self::interval(
                     rtt_estimator.smoothed_rtt(),
                     congestion_window,
                     max_datagram_size,
-                    slow_start,
+                    n,
                     publisher,
                 );
                 self.next_packet_departure_time =
                     Some((next_packet_departure_time + interval).max(now));
+                self.pacing_rate = Some(Self::pacing_rate(
+                    rtt_estimator.smoothed_rtt(),
+                    congestion_window,
+                    n,
+                ));
             } else {
                 self.next_packet_departure_time = Some(now + INITIAL_INTERVAL);
             }
@@ -85,19 +102,47 @@ impl Pacer {
         self.next_packet_departure_time
     }
 
+    /// Returns the most recently calculated pacing rate
+    ///
+    /// The value is `None` until packets have been paced.
+    pub fn current_pacing_rate(&self) -> Option<Bandwidth> {
+        self.pacing_rate
+    }
+
+    // Returns the value of `N` for the current phase
+    #[inline]
+    fn gain(&self, slow_start: bool) -> Ratio<u64> {
+        if slow_start {
+            self.slow_start_gain.unwrap_or(SLOW_START_N)
+        } else {
+            N
+        }
+    }
+
     // Recalculate the interval between bursts of paced packets
     #[inline]
     fn interval<Pub: Publisher>(
         rtt: Duration,
         congestion_window: u32,
         max_datagram_size: u16,
-        slow_start: bool,
+        n: Ratio<u64>,
         publisher: &mut Pub,
     ) -> Duration {
         debug_assert_ne!(congestion_window, 0);
 
-        let n = if slow_start { SLOW_START_N } else { N };
+        let pacing_rate = Self::pacing_rate(rtt, congestion_window, n);
 
+        // `MAX_BURST_PACKETS` is incorporated into the formula since we are trying to spread
+        // bursts of packets evenly over time.
+        let packet_size = MAX_BURST_PACKETS * max_datagram_size as u32;
+
+        publisher.on_pacing_rate_updated(pacing_rate, packet_size, n);
+
+        packet_size as u64 / pacing_rate
+    }
+
+    #[inline]
+    fn pacing_rate(rtt: Duration, congestion_window: u32, n: Ratio<u64>) -> Bandwidth {
         //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
         //# A perfectly paced sender spreads packets exactly evenly over time.
         //# For a window-based congestion controller, such as the one in this
@@ -106,15 +151,7 @@ impl Pacer {
         //# where congestion_window is in bytes:
         //#
         //# rate = N * congestion_window / smoothed_rtt
-        let pacing_rate = Bandwidth::new(congestion_window as u64, rtt) * n;
-
-        // `MAX_BURST_PACKETS` is incorporated into the formula since we are trying to spread
-        // bursts of packets evenly over time.
-        let packet_size = MAX_BURST_PACKETS * max_datagram_size as u32;
-
-        publisher.on_pacing_rate_updated(pacing_rate, packet_size, n);
-
-        packet_size as u64 / pacing_rate
+        Bandwidth::new(congestion_window as u64, rtt) * n
     }
 }
 
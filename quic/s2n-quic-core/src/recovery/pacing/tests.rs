@@ -285,7 +285,7 @@ fn interval_differential_test() {
                 rtt,
                 congestion_window,
                 max_datagram_size,
-                slow_start,
+                if slow_start { SLOW_START_N } else { N },
                 &mut publisher,
             );
 
@@ -47,6 +47,9 @@ pub struct RttEstimator {
     max_ack_delay: Duration,
     /// The time that the first RTT sample was obtained
     first_rtt_sample: Option<Timestamp>,
+    /// The PTO period used for the Initial and Handshake packet number spaces until the first
+    /// RTT sample is obtained, overriding the period derived from the initial RTT
+    handshake_pto: Option<Duration>,
 }
 
 impl Default for RttEstimator {
@@ -92,9 +95,18 @@ impl RttEstimator {
             rttvar,
             max_ack_delay,
             first_rtt_sample: None,
+            handshake_pto: None,
         }
     }
 
+    /// Overrides the PTO period of the Initial and Handshake packet number spaces until the
+    /// first RTT sample is obtained
+    #[inline]
+    pub fn with_handshake_pto(mut self, handshake_pto: Option<Duration>) -> Self {
+        self.handshake_pto = handshake_pto.map(|pto| pto.max(K_GRANULARITY));
+        self
+    }
+
     /// Creates a new RTT Estimator with the `max_ack_delay` from the current instance
     pub fn for_new_path(&self, initial_rtt: Duration) -> Self {
         Self::new_with_max_ack_delay(self.max_ack_delay, initial_rtt)
@@ -144,6 +156,11 @@ impl RttEstimator {
         // We operate on microseconds rather than `Duration` to improve efficiency.
         // See https://godbolt.org/z/osEd9rj9a
 
+        if let Some(handshake_pto) = self.handshake_pto.filter(|_| !space.is_application_data()) {
+            // The configured period still backs off exponentially on consecutive timeouts
+            return handshake_pto.saturating_mul(pto_backoff);
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
         //# When an ack-eliciting packet is transmitted, the sender schedules a
         //# timer for the PTO period as follows:
@@ -201,6 +218,8 @@ impl RttEstimator {
 
         if self.first_rtt_sample.is_none() {
             self.first_rtt_sample = Some(timestamp);
+            // The handshake PTO is only used until the path's RTT has been measured
+            self.handshake_pto = None;
             //= https://www.rfc-editor.org/rfc/rfc9002#section-5.2
             //# min_rtt MUST be set to the latest_rtt on the first RTT sample.
             self.min_rtt = self.latest_rtt;
@@ -435,6 +454,41 @@ mod test {
         );
     }
 
+    #[test]
+    fn handshake_pto() {
+        let handshake_pto = Duration::from_millis(100);
+        let mut rtt_estimator =
+            RttEstimator::new(DEFAULT_INITIAL_RTT).with_handshake_pto(Some(handshake_pto));
+
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::Initial),
+            handshake_pto
+        );
+        assert_eq!(
+            rtt_estimator.pto_period(2, PacketNumberSpace::Handshake),
+            handshake_pto * 2
+        );
+        // The application data space isn't affected
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData),
+            RttEstimator::new(DEFAULT_INITIAL_RTT)
+                .pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData)
+        );
+
+        // The PTO is derived from the RTT once the first sample is obtained
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(500),
+            NoopClock.get_time(),
+            false,
+            PacketNumberSpace::Initial,
+        );
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::Initial),
+            Duration::from_millis(500 + 4 * 250)
+        );
+    }
+
     #[test]
     fn for_new_path() {
         let mut rtt_estimator = RttEstimator::default();
@@ -114,6 +114,20 @@ pub trait Generator: 'static + Send {
     /// To enable stateless reset functionality, the stateless reset token must
     /// be generated the same for a given `local_connection_id` before and after loss of state.
     fn generate(&mut self, local_connection_id: &[u8]) -> Token;
+
+    /// Generates a stateless reset token using the endpoint's random provider
+    ///
+    /// The endpoint calls this method instead of [`Self::generate`]. Generators which produce
+    /// random tokens should draw them from `random`. The default implementation calls
+    /// [`Self::generate`].
+    #[inline]
+    fn generate_with_random(
+        &mut self,
+        local_connection_id: &[u8],
+        _random: &mut dyn crate::random::Generator,
+    ) -> Token {
+        self.generate(local_connection_id)
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
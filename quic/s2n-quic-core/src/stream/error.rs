@@ -66,6 +66,18 @@ pub enum StreamError {
     NonEmptyOutput {
         source: &'static panic::Location<'static>,
     },
+    /// The stream was reset with a reliable size that exceeds the amount of data
+    /// which was written to the stream.
+    #[non_exhaustive]
+    InvalidReliableSize {
+        source: &'static panic::Location<'static>,
+    },
+    /// The stream was reset with a reliable size but the peer does not support
+    /// reliable stream resets.
+    #[non_exhaustive]
+    ReliableResetUnsupported {
+        source: &'static panic::Location<'static>,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -98,6 +110,13 @@ impl fmt::Display for StreamError {
                 f,
                 "The stream was provided a non-empty placeholder buffer for receiving data."
             ),
+            Self::InvalidReliableSize { .. } => write!(
+                f,
+                "The reliable size exceeds the amount of data written to the stream"
+            ),
+            Self::ReliableResetUnsupported { .. } => {
+                write!(f, "The peer does not support reliable stream resets")
+            }
         }
     }
 }
@@ -115,6 +134,8 @@ impl StreamError {
             StreamError::NonWritable { source } => source,
             StreamError::SendingBlocked { source } => source,
             StreamError::NonEmptyOutput { source } => source,
+            StreamError::InvalidReliableSize { source } => source,
+            StreamError::ReliableResetUnsupported { source } => source,
         }
     }
 
@@ -181,14 +202,30 @@ impl StreamError {
         let source = panic::Location::caller();
         StreamError::NonEmptyOutput { source }
     }
+
+    #[track_caller]
+    #[inline]
+    #[doc(hidden)]
+    pub fn invalid_reliable_size() -> StreamError {
+        let source = panic::Location::caller();
+        StreamError::InvalidReliableSize { source }
+    }
+
+    #[track_caller]
+    #[inline]
+    #[doc(hidden)]
+    pub fn reliable_reset_unsupported() -> StreamError {
+        let source = panic::Location::caller();
+        StreamError::ReliableResetUnsupported { source }
+    }
 }
 
 impl application::error::TryInto for StreamError {
     fn application_error(&self) -> Option<application::Error> {
-        if let StreamError::ConnectionError { error, .. } = self {
-            error.application_error()
-        } else {
-            None
+        match self {
+            StreamError::StreamReset { error, .. } => Some(*error),
+            StreamError::ConnectionError { error, .. } => error.application_error(),
+            _ => None,
         }
     }
 }
@@ -237,6 +274,8 @@ impl From<StreamError> for std::io::ErrorKind {
             StreamError::NonWritable { .. } => ErrorKind::Other,
             StreamError::SendingBlocked { .. } => ErrorKind::WouldBlock,
             StreamError::NonEmptyOutput { .. } => ErrorKind::InvalidInput,
+            StreamError::InvalidReliableSize { .. } => ErrorKind::InvalidInput,
+            StreamError::ReliableResetUnsupported { .. } => ErrorKind::Unsupported,
         }
     }
 }
@@ -0,0 +1,37 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+/// A named group of streams which share an aggregate send budget
+///
+/// All of the streams on a connection which are assigned to the same group `id` are limited to
+/// buffering `max_send_buffer_size` bytes in total. This can be used by applications which
+/// multiplex several tenants over a single connection to bound how much of the connection one
+/// tenant's streams can use relative to the others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Group {
+    id: u64,
+    max_send_buffer_size: u32,
+}
+
+impl Group {
+    /// Creates a new group with the given identifier and aggregate send buffer size
+    #[inline]
+    pub const fn new(id: u64, max_send_buffer_size: u32) -> Self {
+        Self {
+            id,
+            max_send_buffer_size,
+        }
+    }
+
+    /// Returns the identifier of the group
+    #[inline]
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the maximum number of bytes all of the streams in the group can buffer
+    #[inline]
+    pub const fn max_send_buffer_size(&self) -> u32 {
+        self.max_send_buffer_size
+    }
+}
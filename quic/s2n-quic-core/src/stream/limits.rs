@@ -30,6 +30,16 @@ pub struct Limits {
     /// is not communicated to the peer, it is only used for limiting
     /// concurrent streams opened locally by the application.
     pub max_open_local_bidirectional_streams: LocalBidirectional,
+    /// The maximum number of peer-initiated bidirectional streams which
+    /// may be waiting to be accepted by the application. Once the limit is
+    /// reached, no additional bidirectional stream credits are issued to the
+    /// peer until the application accepts some of the pending streams.
+    pub max_accept_queue_bidirectional_streams: AcceptQueue,
+    /// The maximum number of peer-initiated unidirectional streams which
+    /// may be waiting to be accepted by the application. Once the limit is
+    /// reached, no additional unidirectional stream credits are issued to the
+    /// peer until the application accepts some of the pending streams.
+    pub max_accept_queue_unidirectional_streams: AcceptQueue,
 }
 
 impl Default for Limits {
@@ -43,6 +53,8 @@ impl Limits {
         max_send_buffer_size: MaxSendBufferSize::RECOMMENDED,
         max_open_local_unidirectional_streams: LocalUnidirectional::RECOMMENDED,
         max_open_local_bidirectional_streams: LocalBidirectional::RECOMMENDED,
+        max_accept_queue_bidirectional_streams: AcceptQueue::RECOMMENDED,
+        max_accept_queue_unidirectional_streams: AcceptQueue::RECOMMENDED,
     };
 }
 
@@ -109,6 +121,13 @@ impl LocalBidirectional {
     pub const RECOMMENDED: Self = Self(InitialMaxStreamsBidi::RECOMMENDED.as_varint());
 }
 
+varint_local_limits!(AcceptQueue(VarInt));
+
+impl AcceptQueue {
+    /// By default, the accept queue is only bounded by the stream concurrency limits
+    pub const RECOMMENDED: Self = Self(VarInt::MAX);
+}
+
 impl From<InitialMaxStreamsBidi> for LocalBidirectional {
     fn from(value: InitialMaxStreamsBidi) -> Self {
         Self(value.as_varint())
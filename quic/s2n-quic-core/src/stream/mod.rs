@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod error;
+mod group;
 mod id;
 pub mod iter;
 pub mod limits;
@@ -11,6 +12,7 @@ pub mod state;
 mod type_;
 
 pub use error::*;
+pub use group::Group;
 pub use id::*;
 pub use limits::Limits;
 pub use type_::*;
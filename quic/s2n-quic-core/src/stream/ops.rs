@@ -54,6 +54,15 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Resets the tx stream with an error code, while still delivering the first `reliable_size`
+    /// bytes of the stream to the peer
+    pub fn reset_at(&mut self, error: application::Error, reliable_size: u64) -> &mut Self {
+        let tx = self.tx_mut();
+        tx.reset = Some(error);
+        tx.reliable_size = reliable_size;
+        self
+    }
+
     /// Flushes any pending tx data to be ACKed before unblocking
     pub fn flush(&mut self) -> &mut Self {
         self.tx_mut().flush = true;
@@ -66,6 +75,19 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Waits for the peer to either acknowledge the finished tx stream or request it to stop
+    /// sending data
+    pub fn stopped(&mut self) -> &mut Self {
+        self.tx_mut().stopped = true;
+        self
+    }
+
+    /// Assigns the tx stream to a group which shares an aggregate send budget
+    pub fn with_group(&mut self, group: stream::Group) -> &mut Self {
+        self.tx_mut().group = Some(group);
+        self
+    }
+
     /// Requests data on the rx stream to be received into the provided slice of chunks
     pub fn receive(&mut self, chunks: &'a mut [bytes::Bytes]) -> &mut Self {
         self.rx_mut().chunks = Some(chunks);
@@ -187,6 +209,11 @@ pub mod tx {
         /// Optionally reset the stream with an error
         pub reset: Option<application::Error>,
 
+        /// The amount of data which is still delivered to the peer when resetting the stream
+        ///
+        /// A value of `0` resets the stream without delivering any outstanding data.
+        pub reliable_size: u64,
+
         /// Waits for an ACK on resets and finishes
         pub flush: bool,
 
@@ -196,6 +223,14 @@ pub mod tx {
         /// Marks the tx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
+
+        /// Waits for the peer to either acknowledge the finished stream or request the stream to
+        /// stop sending with a `STOP_SENDING` frame
+        pub stopped: bool,
+
+        /// Optionally assigns the stream to a group which limits the amount of data that all of
+        /// the streams in the group can buffer
+        pub group: Option<stream::Group>,
     }
 
     /// The result of a tx request
@@ -213,6 +248,11 @@ pub mod tx {
 
         /// The current status of the stream
         pub status: Status,
+
+        /// The error code the peer provided when requesting the stream to stop sending
+        ///
+        /// This is only populated for requests that set the `stopped` flag.
+        pub stop_sending: Option<application::Error>,
     }
 
     impl Default for Response {
@@ -222,6 +262,7 @@ pub mod tx {
                 chunks: Chunks::default(),
                 will_wake: false,
                 status: Status::Open,
+                stop_sending: None,
             }
         }
     }
@@ -498,6 +539,8 @@ mod tests {
             .send(&mut send_chunks)
             .finish()
             .flush()
+            .stopped()
+            .with_group(stream::Group::new(3, 100))
             .reset(application::Error::new(1).unwrap())
             .receive(&mut receive_chunks)
             .with_watermark(5, 10)
@@ -511,7 +554,10 @@ mod tests {
                     finish: true,
                     flush: true,
                     reset: Some(reset),
+                    reliable_size: 0,
                     detached: false,
+                    stopped: true,
+                    group: Some(group),
                 }),
                 rx: Some(rx::Request {
                     chunks: Some(rx_chunks),
@@ -522,6 +568,7 @@ mod tests {
                 })
             } if reset == application::Error::new(1).unwrap()
               && stop_sending == application::Error::new(2).unwrap()
+              && group == stream::Group::new(3, 100)
               && tx_chunks.len() == 1
               && rx_chunks.len() == 2
         ));
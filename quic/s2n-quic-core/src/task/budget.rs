@@ -0,0 +1,196 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use pin_project_lite::pin_project;
+
+/// Limits the number of consecutive iterations a task can perform without yielding to the runtime
+///
+/// A task that is continuously woken with work (e.g. a burst of received packets or a single busy
+/// connection) never returns `Pending` on its own. Without an explicit yield, it would monopolize
+/// the worker thread and starve any other tasks scheduled on it.
+#[derive(Clone, Debug, Default)]
+pub struct Budget {
+    credits: u16,
+    limit: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The task can continue processing work
+    Continue,
+    /// The task should wake itself and return Pending to let other tasks make progress
+    Yield,
+}
+
+impl Outcome {
+    #[inline]
+    pub fn is_continue(&self) -> bool {
+        matches!(self, Self::Continue)
+    }
+
+    #[inline]
+    pub fn is_yield(&self) -> bool {
+        matches!(self, Self::Yield)
+    }
+}
+
+impl Budget {
+    /// Creates a budget allowing `limit` consecutive ready iterations before yielding
+    ///
+    /// A `limit` of `0` disables the budget and the task never yields on its own.
+    #[inline]
+    pub fn new(limit: u16) -> Self {
+        Self {
+            limit,
+            credits: limit,
+        }
+    }
+
+    #[inline]
+    pub fn state(&self) -> Outcome {
+        if self.limit > 0 && self.credits == 0 {
+            Outcome::Yield
+        } else {
+            Outcome::Continue
+        }
+    }
+
+    /// Notifies the budget that the poll operation was ready without yielding
+    ///
+    /// This consumes a credit until they are exhausted, at which point the task should yield.
+    #[inline]
+    pub fn on_ready(&mut self) -> Outcome {
+        self.credits = self.credits.saturating_sub(1);
+        self.state()
+    }
+
+    /// Notifies the budget that the task yielded to the runtime
+    ///
+    /// This restores the credits for the next set of iterations.
+    #[inline]
+    pub fn on_pending(&mut self) {
+        self.credits = self.limit;
+    }
+
+    #[inline]
+    pub async fn wrap<F>(&mut self, fut: F) -> F::Output
+    where
+        F: Future,
+    {
+        Wrapped {
+            fut,
+            budget: self,
+            is_first_poll: true,
+        }
+        .await
+    }
+}
+
+pin_project!(
+    struct Wrapped<'a, F>
+    where
+        F: core::future::Future,
+    {
+        #[pin]
+        fut: F,
+        budget: &'a mut Budget,
+        is_first_poll: bool,
+    }
+);
+
+impl<'a, F> Future for Wrapped<'a, F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.project();
+
+        // if the budget was exhausted by the previous iterations, yield before doing any more work
+        if core::mem::take(this.is_first_poll) && this.budget.state().is_yield() {
+            this.budget.on_pending();
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        match this.fut.poll(cx) {
+            Poll::Ready(v) => {
+                this.budget.on_ready();
+                Poll::Ready(v)
+            }
+            Poll::Pending => {
+                this.budget.on_pending();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_test::task::new_count_waker;
+
+    #[test]
+    fn budget_test() {
+        let mut budget = Budget::new(2);
+
+        assert_eq!(budget.on_ready(), Outcome::Continue);
+        assert_eq!(budget.on_ready(), Outcome::Yield);
+        assert_eq!(budget.on_ready(), Outcome::Yield);
+
+        // yielding restores the credits
+        budget.on_pending();
+
+        assert_eq!(budget.on_ready(), Outcome::Continue);
+        budget.on_pending();
+        assert_eq!(budget.on_ready(), Outcome::Continue);
+        assert_eq!(budget.on_ready(), Outcome::Yield);
+    }
+
+    #[test]
+    fn disabled_test() {
+        let mut budget = Budget::new(0);
+
+        // with the budget disabled, it should never yield
+        for _ in 0..100 {
+            assert_eq!(budget.on_ready(), Outcome::Continue);
+        }
+    }
+
+    #[test]
+    fn wrap_test() {
+        let (waker, count) = new_count_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut budget = Budget::new(2);
+
+        macro_rules! poll {
+            () => {{
+                let fut = budget.wrap(core::future::ready(()));
+                futures::pin_mut!(fut);
+                fut.as_mut().poll(&mut cx)
+            }};
+        }
+
+        assert!(poll!().is_ready());
+        assert!(poll!().is_ready());
+        assert_eq!(count, 0);
+
+        // the budget is exhausted so the task should wake itself and yield
+        assert!(poll!().is_pending());
+        assert_eq!(count, 1);
+
+        // the credits are restored after yielding
+        assert!(poll!().is_ready());
+        assert!(poll!().is_ready());
+        assert!(poll!().is_pending());
+        assert_eq!(count, 2);
+    }
+}
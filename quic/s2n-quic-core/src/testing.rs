@@ -3,6 +3,8 @@
 
 use bolero_generator::TypeGenerator;
 
+pub mod corpus;
+
 #[derive(Clone, Copy, Debug, TypeGenerator)]
 pub struct InlineVec<T, const LEN: usize> {
     values: [T; LEN],
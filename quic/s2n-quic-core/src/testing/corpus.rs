@@ -0,0 +1,461 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Regression testing for the wire decoders using a corpus of datagrams
+//!
+//! Each corpus entry is a text file containing a single datagram, as received by an endpoint,
+//! along with the outcome the endpoint is expected to reach when processing it:
+//!
+//! ```text
+//! # ACK frame with more ranges than the payload can carry
+//! endpoint: server
+//! expect: error PROTOCOL_VIOLATION
+//! 40 0102030405060708 00
+//! 02 00 00 05 00
+//! ```
+//!
+//! Lines starting with `#` are comments. The `endpoint` (`server` or `client`, defaulting to
+//! `server`) and `dcid-len` (the length of locally issued connection IDs, defaulting to `8`)
+//! headers are optional. `expect` is one of `processed`, `dropped` or `error <CODE>`, where
+//! `<CODE>` is the name of a transport error code. Every other line is hex, with any whitespace
+//! ignored.
+//!
+//! Datagrams are replayed through the packet decoders, header protection, packet protection and
+//! the frame decoders, followed by the stateless checks performed by the transport: which frames
+//! are permitted in each packet type and by each endpoint. Packets are protected with the null
+//! keys from [`crate::crypto::key::testing`], so crafted entries are written in cleartext.
+//! Checks which depend on connection state, such as flow control, are not covered.
+//!
+//! The corpus shipped with this crate lives in [`CORPUS_DIR`]. Downstream forks can replay it,
+//! or their own corpora, with [`check`].
+
+use crate::{
+    connection::{self, id::ConnectionInfo, ProcessingError},
+    crypto::key::testing::{HeaderKey, Key},
+    endpoint,
+    frame::{Frame, FrameMut},
+    inet::SocketAddress,
+    packet::{number::PacketNumberSpace, ProtectedPacket},
+    transport::{self, error::Code},
+    varint::VarInt,
+};
+use s2n_codec::DecoderBufferMut;
+use std::{
+    fmt, fs,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+/// The corpus of datagrams shipped with this crate
+pub const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/corpus/wire");
+
+/// The QUIC version processed by the endpoint
+const SUPPORTED_VERSION: u32 = 0x1;
+
+const MAX_CONNECTION_ID_LEN: usize = 20;
+
+/// The outcome of replaying a datagram
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The datagram was discarded without affecting the connection
+    Dropped,
+    /// At least one packet in the datagram was processed
+    Processed,
+    /// A packet in the datagram closed the connection with the given error
+    Error(Code),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Dropped => write!(f, "dropped"),
+            Self::Processed => write!(f, "processed"),
+            Self::Error(code) => write!(f, "error {code}"),
+        }
+    }
+}
+
+/// A datagram in the corpus, along with its expected outcome
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub path: PathBuf,
+    pub endpoint: endpoint::Type,
+    pub destination_connection_id_len: usize,
+    pub expected: Outcome,
+    pub datagram: Vec<u8>,
+}
+
+impl Entry {
+    /// Parses a corpus entry
+    pub fn parse(path: PathBuf, contents: &str) -> Result<Self, String> {
+        let mut endpoint = endpoint::Type::Server;
+        let mut destination_connection_id_len = 8;
+        let mut expected = None;
+        let mut hex = String::new();
+
+        for line in contents.lines() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                hex.extend(line.chars().filter(|c| !c.is_whitespace()));
+                continue;
+            };
+
+            let value = value.trim();
+            match key.trim() {
+                "endpoint" => {
+                    endpoint = match value {
+                        "server" => endpoint::Type::Server,
+                        "client" => endpoint::Type::Client,
+                        _ => return Err(format!("invalid endpoint: {value}")),
+                    };
+                }
+                "dcid-len" => {
+                    destination_connection_id_len = value
+                        .parse()
+                        .map_err(|_| format!("invalid dcid-len: {value}"))?;
+                }
+                "expect" => {
+                    expected = Some(parse_outcome(value)?);
+                }
+                _ => return Err(format!("invalid header: {key}")),
+            }
+        }
+
+        let expected = expected.ok_or("missing expect header")?;
+
+        if hex.len() % 2 != 0 {
+            return Err("odd number of hex digits".to_string());
+        }
+
+        let datagram = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<_, _>>()
+            .map_err(|err| format!("invalid hex: {err}"))?;
+
+        Ok(Self {
+            path,
+            endpoint,
+            destination_connection_id_len,
+            expected,
+            datagram,
+        })
+    }
+
+    /// Replays the datagram and returns the outcome
+    pub fn replay(&self) -> Outcome {
+        let mut datagram = self.datagram.clone();
+        replay(
+            self.endpoint,
+            self.destination_connection_id_len,
+            &mut datagram,
+        )
+    }
+}
+
+fn parse_outcome(value: &str) -> Result<Outcome, String> {
+    match value {
+        "processed" => return Ok(Outcome::Processed),
+        "dropped" => return Ok(Outcome::Dropped),
+        _ => {}
+    }
+
+    let name = value
+        .strip_prefix("error ")
+        .ok_or_else(|| format!("invalid outcome: {value}"))?
+        .trim();
+
+    // transport error codes are at most 0x1ff, besides application-defined codes
+    (0..0x200u32)
+        .map(|code| Code::new(VarInt::from_u32(code)))
+        .find(|code| code.description() == Some(name))
+        .map(Outcome::Error)
+        .ok_or_else(|| format!("unknown error code: {name}"))
+}
+
+/// Loads all of the entries in a corpus directory
+pub fn load<P: AsRef<Path>>(dir: P) -> Result<Vec<Entry>, String> {
+    let dir = dir.as_ref();
+    let mut paths = fs::read_dir(dir)
+        .map_err(|err| format!("could not read {}: {err}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("could not read {}: {err}", dir.display()))?;
+
+    // replay the entries in a stable order
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter(|path| path.extension().map_or(false, |ext| ext == "hex"))
+        .map(|path| {
+            let contents = fs::read_to_string(&path)
+                .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+            Entry::parse(path.clone(), &contents)
+                .map_err(|err| format!("{}: {err}", path.display()))
+        })
+        .collect()
+}
+
+/// Replays every entry in the corpus directory
+///
+/// Panics with a summary of every entry which panicked or didn't reach its expected outcome.
+pub fn check<P: AsRef<Path>>(dir: P) {
+    let entries = load(dir).unwrap();
+    assert!(!entries.is_empty(), "the corpus is empty");
+
+    let mut failures = vec![];
+
+    for entry in &entries {
+        match catch_unwind(AssertUnwindSafe(|| entry.replay())) {
+            Ok(actual) if actual == entry.expected => {}
+            Ok(actual) => failures.push(format!(
+                "{}: expected {}, got {actual}",
+                entry.path.display(),
+                entry.expected
+            )),
+            Err(_) => failures.push(format!("{}: panicked", entry.path.display())),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} corpus entries failed:\n{}",
+        failures.len(),
+        entries.len(),
+        failures.join("\n")
+    );
+}
+
+/// Replays a single datagram as received by the given endpoint type
+pub fn replay(
+    endpoint: endpoint::Type,
+    destination_connection_id_len: usize,
+    datagram: &mut [u8],
+) -> Outcome {
+    let remote_address = SocketAddress::default();
+    let connection_info = ConnectionInfo::new(&remote_address);
+
+    let mut buffer = DecoderBufferMut::new(datagram);
+    let mut outcome = Outcome::Dropped;
+
+    while !buffer.is_empty() {
+        // any bytes which can't be decoded as a packet are discarded, along with the rest of the
+        // datagram
+        let Ok((packet, remaining)) =
+            ProtectedPacket::decode(buffer, &connection_info, &destination_connection_id_len)
+        else {
+            break;
+        };
+        buffer = remaining;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+        //# Endpoints that receive a version 1 long header with a value
+        //# larger than 20 MUST drop the packet.
+        if packet
+            .version()
+            .map_or(false, |version| version != SUPPORTED_VERSION)
+            || packet.destination_connection_id().len() > MAX_CONNECTION_ID_LEN
+            || packet
+                .source_connection_id()
+                .map_or(false, |id| id.len() > MAX_CONNECTION_ID_LEN)
+        {
+            continue;
+        }
+
+        match on_packet(endpoint, packet) {
+            Ok(true) => outcome = Outcome::Processed,
+            Ok(false) => {}
+            Err(code) => return Outcome::Error(code),
+        }
+    }
+
+    outcome
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Space {
+    Initial,
+    Handshake,
+    ZeroRtt,
+    ApplicationData,
+}
+
+/// Processes a single packet, returning `true` if it wasn't discarded
+fn on_packet(endpoint: endpoint::Type, packet: ProtectedPacket) -> Result<bool, Code> {
+    macro_rules! on_payload {
+        ($packet:ident, $space:expr, $number_space:ident) => {{
+            let Ok(packet) = $packet.unprotect(
+                        &HeaderKey::new(),
+                        PacketNumberSpace::$number_space.new_packet_number(Default::default()),
+                    ) else {
+                        return Ok(false);
+                    };
+
+            // only some packet types report connection errors while decrypting
+            let packet = match packet.decrypt(&Key::new()).map_err(ProcessingError::from) {
+                Ok(packet) => packet,
+                Err(ProcessingError::ConnectionError(connection::Error::Transport {
+                    code,
+                    ..
+                })) => return Err(code),
+                Err(_) => return Ok(false),
+            };
+
+            on_payload(endpoint, $space, packet.payload)
+                .map_err(|error| error.code)
+                .map(|_| true)
+        }};
+    }
+
+    match packet {
+        ProtectedPacket::Initial(packet) => on_payload!(packet, Space::Initial, Initial),
+        ProtectedPacket::Handshake(packet) => on_payload!(packet, Space::Handshake, Handshake),
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.3
+        //# A client MUST NOT send 0-RTT packets once it starts processing 1-RTT
+        //# packets from the server.
+        ProtectedPacket::ZeroRtt(_) if endpoint.is_client() => Ok(false),
+        ProtectedPacket::ZeroRtt(packet) => on_payload!(packet, Space::ZeroRtt, ApplicationData),
+        ProtectedPacket::Short(packet) => {
+            on_payload!(packet, Space::ApplicationData, ApplicationData)
+        }
+        // only clients process Retry and Version Negotiation packets
+        ProtectedPacket::Retry(_) | ProtectedPacket::VersionNegotiation(_)
+            if endpoint.is_server() =>
+        {
+            Ok(false)
+        }
+        ProtectedPacket::Retry(packet) => {
+            let _ = packet.destination_connection_id();
+            let _ = packet.source_connection_id();
+            Ok(true)
+        }
+        ProtectedPacket::VersionNegotiation(packet) => {
+            let _: Vec<_> = packet.iter().collect();
+            Ok(true)
+        }
+    }
+}
+
+fn on_payload(
+    endpoint: endpoint::Type,
+    space: Space,
+    mut payload: DecoderBufferMut,
+) -> Result<(), transport::Error> {
+    let mut frames = 0;
+
+    while !payload.is_empty() {
+        let frame_type = payload
+            .peek()
+            .decode::<VarInt>()
+            .map_or(VarInt::default(), |(frame_type, _)| frame_type);
+
+        let (frame, remaining) = payload
+            .decode::<FrameMut>()
+            .map_err(transport::Error::from)?;
+
+        on_frame(endpoint, space, &frame).map_err(|err| err.with_frame_type(frame_type))?;
+
+        frames += 1;
+        payload = remaining;
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-12.4
+    //# An endpoint MUST treat receipt of a packet containing no frames as a
+    //# connection error of type PROTOCOL_VIOLATION.
+    if frames == 0 {
+        return Err(transport::Error::PROTOCOL_VIOLATION.with_reason("packet contained no frames"));
+    }
+
+    Ok(())
+}
+
+fn on_frame(
+    endpoint: endpoint::Type,
+    space: Space,
+    frame: &FrameMut,
+) -> Result<(), transport::Error> {
+    use Frame::*;
+
+    let is_permitted = match (space, frame) {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-12.4
+        //# An endpoint MUST treat the receipt of a frame of unknown type as a
+        //# connection error of type FRAME_ENCODING_ERROR.
+        (_, Custom(_)) => {
+            return Err(transport::Error::FRAME_ENCODING_ERROR.with_reason("unknown frame type"))
+        }
+        (_, Padding(_) | Ping(_)) => true,
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.2
+        //# CONNECTION_CLOSE frames of type 0x1c are also
+        //# permitted.
+        (Space::Initial | Space::Handshake, ConnectionClose(frame)) => frame.tag() == 0x1c,
+        (Space::Initial | Space::Handshake, Ack(_) | Crypto(_)) => true,
+        (Space::Initial | Space::Handshake, _) => false,
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-12.5
+        //# Note that it is not possible to send the following frames in 0-RTT
+        //# packets for various reasons: ACK, CRYPTO, HANDSHAKE_DONE, NEW_TOKEN,
+        //# PATH_RESPONSE, and RETIRE_CONNECTION_ID.
+        (
+            Space::ZeroRtt,
+            Ack(_)
+            | Crypto(_)
+            | HandshakeDone(_)
+            | NewToken(_)
+            | PathResponse(_)
+            | RetireConnectionId(_),
+        ) => false,
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-19.7
+        //# A server MUST treat receipt
+        //# of a NEW_TOKEN frame as a connection error of type
+        //# PROTOCOL_VIOLATION.
+        //
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-19.20
+        //# A server MUST
+        //# treat receipt of a HANDSHAKE_DONE frame as a connection error of type
+        //# PROTOCOL_VIOLATION.
+        (_, NewToken(_) | HandshakeDone(_)) => endpoint.is_client(),
+        // extension frames are only permitted once the extension has been negotiated
+        (_, DcStatelessResetTokens(_) | Timestamp(_)) => false,
+        _ => true,
+    };
+
+    if !is_permitted {
+        return Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason("frame is not permitted in the packet"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corpus_test() {
+        check(CORPUS_DIR);
+    }
+
+    #[test]
+    fn parse_test() {
+        let entry = Entry::parse(
+            PathBuf::from("test.hex"),
+            "# comment\nendpoint: client\ndcid-len: 4\nexpect: error FRAME_ENCODING_ERROR\n40 01 02\n0304\n",
+        )
+        .unwrap();
+
+        assert_eq!(entry.endpoint, endpoint::Type::Client);
+        assert_eq!(entry.destination_connection_id_len, 4);
+        assert_eq!(entry.expected, Outcome::Error(Code::FRAME_ENCODING_ERROR));
+        assert_eq!(entry.datagram, [0x40, 0x01, 0x02, 0x03, 0x04]);
+
+        assert!(Entry::parse(PathBuf::new(), "40").is_err());
+        assert!(Entry::parse(PathBuf::new(), "expect: error NOT_A_CODE\n40").is_err());
+        assert!(Entry::parse(PathBuf::new(), "expect: dropped\n4").is_err());
+    }
+}
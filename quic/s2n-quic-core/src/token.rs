@@ -7,11 +7,15 @@ use crate::{
     inet, random,
 };
 
+#[cfg(feature = "alloc")]
+pub mod cache;
+
 #[non_exhaustive]
 pub struct Context<'a> {
     pub remote_address: SocketAddress<'a>,
     pub peer_connection_id: &'a [u8],
     pub random: &'a mut dyn random::Generator,
+    reused_token: Option<Source>,
 }
 
 impl<'a> Context<'a> {
@@ -26,8 +30,27 @@ impl<'a> Context<'a> {
             remote_address: remote_address.into_event(),
             peer_connection_id: peer_connection_id.as_bytes(),
             random,
+            reused_token: None,
         }
     }
+
+    /// Reports that the token being validated has already been accepted by the endpoint
+    ///
+    /// Formats should call this when their replay protection rejects a token. The endpoint
+    /// will emit an `EndpointAddressTokenReused` event, which can be used to detect peers
+    /// probing for token replay. Formats which issue NEW_TOKEN tokens are responsible for
+    /// tracking and reporting their reuse with [`Source::NewTokenFrame`].
+    #[inline]
+    pub fn on_token_reused(&mut self, source: Source) {
+        self.reused_token = Some(source);
+    }
+
+    /// Returns the source of the token if it was reported as reused
+    #[inline]
+    #[doc(hidden)]
+    pub fn reused_token(&self) -> Option<Source> {
+        self.reused_token
+    }
 }
 
 pub trait Format: 'static + Send {
@@ -52,7 +75,8 @@ pub trait Format: 'static + Send {
 
     /// Return the original destination connection id of a valid token.
     /// If the token is invalid, return None.
-    /// Callers should detect duplicate tokens and treat them as invalid.
+    /// Callers should detect duplicate tokens and treat them as invalid, reporting them with
+    /// [`Context::on_token_reused`].
     fn validate_token(
         &mut self,
         context: &mut Context<'_>,
@@ -0,0 +1,64 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stores address validation tokens which servers deliver to clients in NEW_TOKEN frames
+//!
+//! Presenting a token in the first Initial packet of a later connection allows the server to
+//! skip address validation, which saves the round trip of a Retry packet.
+
+use crate::{
+    application::ServerName,
+    event::{api::SocketAddress, IntoEvent},
+    inet,
+};
+use bytes::Bytes;
+
+/// The server which issued a token
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct Server<'a> {
+    /// The name the client used to connect to the server
+    pub server_name: Option<&'a ServerName>,
+
+    /// The address the client used to connect to the server
+    pub remote_address: SocketAddress<'a>,
+}
+
+impl<'a> Server<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        server_name: Option<&'a ServerName>,
+        remote_address: &'a inet::SocketAddress,
+    ) -> Self {
+        Self {
+            server_name,
+            remote_address: remote_address.into_event(),
+        }
+    }
+}
+
+/// Stores the tokens a client received from each server
+pub trait Cache: 'static + Send {
+    /// Called when a server delivers a token in a NEW_TOKEN frame
+    fn on_new_token(&mut self, server: &Server, token: &[u8]);
+
+    /// Returns a token to present to the server when opening a new connection
+    ///
+    /// The returned token should be removed from the cache, since reusing a token allows
+    /// observers to link connections.
+    fn take_token(&mut self, server: &Server) -> Option<Bytes>;
+}
+
+/// A [`Cache`] which doesn't store any tokens
+#[derive(Debug, Default)]
+pub struct Disabled(());
+
+impl Cache for Disabled {
+    #[inline]
+    fn on_new_token(&mut self, _server: &Server, _token: &[u8]) {}
+
+    #[inline]
+    fn take_token(&mut self, _server: &Server) -> Option<Bytes> {
+        None
+    }
+}
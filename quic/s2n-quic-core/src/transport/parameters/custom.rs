@@ -0,0 +1,297 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for application-defined transport parameters
+//!
+//! Custom parameters are appended to the transport parameters extension sent by the endpoint.
+//! Parameters sent by the peer which are not known to the transport are made available to the
+//! application instead of being ignored.
+//!
+//! Application settings can also be exchanged for each application protocol, similar to the
+//! TLS ALPS extension. Each endpoint sends its settings for every protocol it supports and only
+//! the settings for the negotiated protocol are used, which avoids an extra round-trip after the
+//! handshake to exchange them.
+
+use super::{
+    ServerTransportParameters, TransportParameterId, TransportParameterLength, ValidationError,
+};
+use crate::{event::api::SocketAddress, event::IntoEvent, inet, varint::VarInt};
+use alloc::{collections::BTreeSet, vec::Vec};
+use bytes::Bytes;
+use s2n_codec::{DecoderBuffer, DecoderError, Encoder, EncoderValue};
+
+/// The ID of the transport parameter carrying the application settings
+///
+/// The value matches the codepoint of the TLS ALPS extension. It isn't registered as a QUIC
+/// transport parameter so both peers need to agree on its use.
+pub const APPLICATION_SETTINGS_ID: VarInt = VarInt::from_u16(0x4469);
+
+/// Information about the connection the custom transport parameters are created for
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ConnectionInfo<'a> {
+    pub remote_address: SocketAddress<'a>,
+}
+
+impl<'a> ConnectionInfo<'a> {
+    #[inline]
+    #[doc(hidden)]
+    pub fn new(remote_address: &'a inet::SocketAddress) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+        }
+    }
+}
+
+pub trait Endpoint: 'static + Send {
+    /// Set to `false` if the endpoint doesn't send or receive custom transport parameters
+    ///
+    /// When disabled, unknown parameters sent by the peer are ignored.
+    const ENABLED: bool = true;
+
+    /// Returns the custom transport parameters to send to the peer on a new connection
+    fn on_connection(&mut self, info: &ConnectionInfo) -> Parameters;
+}
+
+/// Custom transport parameters are disabled
+#[derive(Debug, Default)]
+pub struct Disabled(());
+
+impl Endpoint for Disabled {
+    const ENABLED: bool = false;
+
+    #[inline]
+    fn on_connection(&mut self, _info: &ConnectionInfo) -> Parameters {
+        Parameters::default()
+    }
+}
+
+/// A set of transport parameters with application-defined IDs and opaque values
+///
+/// The same set of parameters is sent on every connection when used as an [`Endpoint`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Parameters {
+    entries: Vec<(VarInt, Bytes)>,
+}
+
+impl Parameters {
+    /// Adds a parameter to the set
+    ///
+    /// Returns an error if the ID is used by a transport parameter known to the transport, is
+    /// [`APPLICATION_SETTINGS_ID`], or if the ID was already added.
+    pub fn insert<V: Into<Bytes>>(&mut self, id: VarInt, value: V) -> Result<(), ValidationError> {
+        if ServerTransportParameters::is_known_id(id) {
+            return Err(ValidationError(
+                "transport parameter ID is reserved by the transport",
+            ));
+        }
+
+        if id == APPLICATION_SETTINGS_ID {
+            return Err(ValidationError(
+                "application settings must be added with `insert_application_settings`",
+            ));
+        }
+
+        if self.get(id).is_some() {
+            return Err(ValidationError("duplicate transport parameter ID"));
+        }
+
+        self.entries.push((id, value.into()));
+        Ok(())
+    }
+
+    /// Adds a parameter to the set and returns it
+    pub fn with<V: Into<Bytes>>(mut self, id: VarInt, value: V) -> Result<Self, ValidationError> {
+        self.insert(id, value)?;
+        Ok(self)
+    }
+
+    /// Adds application settings which are used if `protocol` is negotiated
+    ///
+    /// Returns an error if the protocol isn't a valid ALPN identifier or if settings were
+    /// already added for the protocol.
+    pub fn insert_application_settings(
+        &mut self,
+        protocol: &[u8],
+        settings: &[u8],
+    ) -> Result<(), ValidationError> {
+        //= https://www.rfc-editor.org/rfc/rfc7301#section-3.1
+        //# Empty strings
+        //# MUST NOT be included and byte strings MUST NOT be truncated.
+        if protocol.is_empty() || protocol.len() > u8::MAX as usize {
+            return Err(ValidationError("invalid application protocol length"));
+        }
+
+        if self.application_settings(protocol).is_some() {
+            return Err(ValidationError(
+                "duplicate application settings for the protocol",
+            ));
+        }
+
+        let settings_len = VarInt::try_from(settings.len())
+            .map_err(|_| ValidationError("application settings are too large"))?;
+
+        let mut value = self
+            .get(APPLICATION_SETTINGS_ID)
+            .map(|value| value.to_vec())
+            .unwrap_or_default();
+        value.push(protocol.len() as u8);
+        value.extend_from_slice(protocol);
+        value.extend_from_slice(&settings_len.encode_to_vec());
+        value.extend_from_slice(settings);
+
+        if let Some((_, entry)) = self
+            .entries
+            .iter_mut()
+            .find(|(id, _)| *id == APPLICATION_SETTINGS_ID)
+        {
+            *entry = value.into();
+        } else {
+            self.entries.push((APPLICATION_SETTINGS_ID, value.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Adds application settings for `protocol` and returns the set
+    pub fn with_application_settings(
+        mut self,
+        protocol: &[u8],
+        settings: &[u8],
+    ) -> Result<Self, ValidationError> {
+        self.insert_application_settings(protocol, settings)?;
+        Ok(self)
+    }
+
+    /// Returns the application settings for the given protocol
+    #[inline]
+    pub fn application_settings(&self, protocol: &[u8]) -> Option<Bytes> {
+        let value = self.get(APPLICATION_SETTINGS_ID)?;
+        decode_application_settings(value, protocol).ok()?
+    }
+
+    /// Returns the value of the parameter with the given ID
+    #[inline]
+    pub fn get(&self, id: VarInt) -> Option<&Bytes> {
+        self.entries
+            .iter()
+            .find_map(|(entry_id, value)| (*entry_id == id).then_some(value))
+    }
+
+    /// Returns an iterator over all of the parameters in the set
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (VarInt, &Bytes)> {
+        self.entries.iter().map(|(id, value)| (*id, value))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Decodes all of the parameters unknown to the transport from an encoded transport
+    /// parameters extension
+    #[doc(hidden)]
+    pub fn decode_unknown(mut buffer: DecoderBuffer) -> Result<Self, DecoderError> {
+        let mut parameters = Self::default();
+        // the IDs are tracked in a set since the peer controls the number of parameters
+        let mut seen_ids = BTreeSet::new();
+
+        while !buffer.is_empty() {
+            let (id, remaining) = buffer.decode::<TransportParameterId>()?;
+            let (value, remaining) =
+                remaining.decode_slice_with_len_prefix::<TransportParameterLength>()?;
+            buffer = remaining;
+
+            if ServerTransportParameters::is_known_id(id) {
+                continue;
+            }
+
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4
+            //# An endpoint MUST NOT send a parameter more than once in a given
+            //# transport parameters extension.
+            s2n_codec::decoder_invariant!(
+                seen_ids.insert(id),
+                "duplicate value for custom transport parameter"
+            );
+
+            let value = Bytes::copy_from_slice(value.into_less_safe_slice());
+
+            if id == APPLICATION_SETTINGS_ID {
+                // make sure the settings are well-formed before they are made available
+                decode_application_settings(&value, &[])?;
+            }
+
+            parameters.entries.push((id, value));
+        }
+
+        Ok(parameters)
+    }
+}
+
+/// Finds the settings for `protocol` in an encoded application settings parameter
+///
+/// Each entry is the protocol, prefixed by a `u8` length, followed by the settings, prefixed by a
+/// variable-length integer.
+#[doc(hidden)]
+pub fn decode_application_settings(
+    value: &Bytes,
+    protocol: &[u8],
+) -> Result<Option<Bytes>, DecoderError> {
+    let mut buffer = DecoderBuffer::new(value);
+    let mut settings_for_protocol = None;
+
+    while !buffer.is_empty() {
+        let (entry_protocol, remaining) = buffer.decode_slice_with_len_prefix::<u8>()?;
+        let (settings, remaining) = remaining.decode_slice_with_len_prefix::<VarInt>()?;
+        buffer = remaining;
+
+        s2n_codec::decoder_invariant!(
+            !entry_protocol.is_empty(),
+            "empty application protocol in application settings"
+        );
+
+        if settings_for_protocol.is_none() && entry_protocol.as_less_safe_slice() == protocol {
+            let settings = settings.into_less_safe_slice();
+            // the settings are a subslice of the value so avoid a copy
+            let offset = settings.as_ptr() as usize - value.as_ptr() as usize;
+            settings_for_protocol = Some(value.slice(offset..offset + settings.len()));
+        }
+    }
+
+    Ok(settings_for_protocol)
+}
+
+impl Endpoint for Parameters {
+    #[inline]
+    fn on_connection(&mut self, _info: &ConnectionInfo) -> Parameters {
+        self.clone()
+    }
+}
+
+impl EncoderValue for Parameters {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        for (id, value) in self.entries.iter() {
+            buffer.encode(id);
+            buffer.encode_with_len_prefix::<TransportParameterLength, _>(&value.as_ref());
+        }
+    }
+}
+
+/// Encodes the transport parameters followed by the custom parameters
+#[derive(Debug)]
+#[doc(hidden)]
+pub struct WithCustom<'a, T>(pub &'a T, pub &'a Parameters);
+
+impl<T: EncoderValue> EncoderValue for WithCustom<'_, T> {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(self.0);
+        buffer.encode(self.1);
+    }
+}
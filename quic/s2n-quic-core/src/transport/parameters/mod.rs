@@ -10,10 +10,11 @@ use crate::{
     varint::VarInt,
 };
 use core::{mem::size_of, time::Duration};
+#[cfg(feature = "alloc")]
+use s2n_codec::EncoderBuffer;
 use s2n_codec::{
     decoder_invariant, decoder_value, DecoderBuffer, DecoderBufferMut, DecoderBufferMutResult,
-    DecoderBufferResult, DecoderError, DecoderValue, DecoderValueMut, Encoder, EncoderBuffer,
-    EncoderValue,
+    DecoderBufferResult, DecoderError, DecoderValue, DecoderValueMut, Encoder, EncoderValue,
 };
 
 #[cfg(test)]
@@ -589,6 +590,10 @@ impl TransportParameterValidator for stateless_reset::Token {}
 
 transport_parameter!(MaxUdpPayloadSize(VarInt), 0x03, VarInt::from_u16(65527));
 
+impl MaxUdpPayloadSize {
+    pub const DEFAULT: Self = Self(VarInt::from_u16(65527));
+}
+
 impl TransportParameterValidator for MaxUdpPayloadSize {
     fn validate(self) -> Result<Self, DecoderError> {
         decoder_invariant!(
@@ -973,6 +978,16 @@ impl TransportParameterValidator for PreferredAddress {
             !self.is_unspecified(),
             "at least one address needs to be specified"
         );
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# Similarly, a server MUST NOT include a zero-
+        //# length connection ID in this transport parameter.  A client MUST
+        //# treat a violation of these requirements as a connection error of
+        //# type TRANSPORT_PARAMETER_ERROR.
+        decoder_invariant!(
+            !self.connection_id.is_empty(),
+            "the connection ID must not be empty"
+        );
         Ok(self)
     }
 }
@@ -1209,6 +1224,150 @@ impl<'a> IntoEvent<&'a [u32]> for &'a DcSupportedVersions {
     }
 }
 
+//# https://datatracker.ietf.org/doc/html/draft-ietf-quic-reliable-stream-reset#section-3
+//# Endpoints advertise their support of the extension described in this
+//# document by sending the reset_stream_at (0x17f7586d2cb571) transport
+//# parameter (Section 7.4 of [RFC9000]) with an empty value.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ResetStreamAtSupport {
+    Enabled,
+    #[default]
+    Disabled,
+}
+
+impl ResetStreamAtSupport {
+    pub const RECOMMENDED: Self = Self::Disabled;
+}
+
+impl TransportParameter for ResetStreamAtSupport {
+    type CodecValue = ();
+
+    // Safety: the value is less than 2^62
+    const ID: TransportParameterId = unsafe { VarInt::new_unchecked(0x17f7586d2cb571) };
+
+    fn from_codec_value(_value: ()) -> Self {
+        ResetStreamAtSupport::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if let ResetStreamAtSupport::Enabled = self {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for ResetStreamAtSupport {}
+
+/// Endpoints advertise that they encode and decode unreliable datagrams with forward error
+/// correction by sending the datagram_fec transport parameter with an empty value.
+///
+/// FEC is only used when both endpoints send the parameter. See [`crate::datagram::fec`].
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DatagramFecSupport {
+    Enabled,
+    #[default]
+    Disabled,
+}
+
+impl DatagramFecSupport {
+    pub const RECOMMENDED: Self = Self::Disabled;
+}
+
+impl TransportParameter for DatagramFecSupport {
+    type CodecValue = ();
+
+    // A private-use value which is not reserved for greasing
+    const ID: TransportParameterId = TransportParameterId::from_u16(0x5fec);
+
+    fn from_codec_value(_value: ()) -> Self {
+        DatagramFecSupport::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if let DatagramFecSupport::Enabled = self {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for DatagramFecSupport {}
+
+//# https://datatracker.ietf.org/doc/html/draft-huitema-quic-ts#section-2
+//# The support of the TIMESTAMP frame is negotiated with the
+//# enable_timestamp (0x7158) transport parameter.  The value is a
+//# variable-length integer: 1 if the endpoint wants to receive
+//# TIMESTAMP frames, 2 if it is able to send them, and 3 for both.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TimestampSupport(VarInt);
+
+impl TimestampSupport {
+    pub const DISABLED: Self = Self(VarInt::from_u8(0));
+    pub const RECEIVE: Self = Self(VarInt::from_u8(1));
+    pub const SEND: Self = Self(VarInt::from_u8(2));
+    pub const SEND_AND_RECEIVE: Self = Self(VarInt::from_u8(3));
+
+    pub const RECOMMENDED: Self = Self::DISABLED;
+
+    /// Returns `true` if the endpoint is able to send TIMESTAMP frames
+    #[inline]
+    pub fn can_send(&self) -> bool {
+        self.0.as_u64() & 0b10 != 0
+    }
+
+    /// Returns `true` if the endpoint wants to receive TIMESTAMP frames
+    #[inline]
+    pub fn can_receive(&self) -> bool {
+        self.0.as_u64() & 0b01 != 0
+    }
+}
+
+impl TransportParameter for TimestampSupport {
+    type CodecValue = VarInt;
+
+    const ID: TransportParameterId = TransportParameterId::from_u16(0x7158);
+
+    fn from_codec_value(value: VarInt) -> Self {
+        Self(value)
+    }
+
+    fn try_into_codec_value(&self) -> Option<&VarInt> {
+        if *self == Self::DISABLED {
+            None
+        } else {
+            Some(&self.0)
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for TimestampSupport {
+    fn validate(self) -> Result<Self, DecoderError> {
+        decoder_invariant!(
+            (1..=3).contains(&self.0.as_u64()),
+            "enable_timestamp must be 1, 2 or 3"
+        );
+        Ok(self)
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# If present, transport parameters that set initial per-stream flow
 //# control limits (initial_max_stream_data_bidi_local,
@@ -1251,6 +1410,7 @@ impl InitialStreamLimits {
 
 pub struct DatagramLimits {
     pub max_datagram_payload: u64,
+    pub peer_supports_fec: bool,
 }
 
 impl<
@@ -1321,6 +1481,7 @@ impl<
         let max_udp_payload = self.max_udp_payload_size.as_u64();
         DatagramLimits {
             max_datagram_payload: max_datagram_payload.min(max_udp_payload),
+            peer_supports_fec: self.datagram_fec_support == DatagramFecSupport::Enabled,
         }
     }
 }
@@ -1335,6 +1496,9 @@ impl<
 mod disabled_parameter;
 pub use disabled_parameter::DisabledParameter;
 
+#[cfg(feature = "alloc")]
+pub mod custom;
+
 /// Specific TransportParameters sent by the client endpoint
 pub type ClientTransportParameters = TransportParameters<
     DisabledParameter<OriginalDestinationConnectionId>,
@@ -1380,6 +1544,7 @@ impl<'a> IntoEvent<event::builder::TransportParameters<'a>> for &'a ServerTransp
             ack_delay_exponent: self.ack_delay_exponent.into_event(),
             max_ack_delay: Duration::from(self.max_ack_delay),
             active_connection_id_limit: self.active_connection_id_limit.into_event(),
+            initial_max_data: self.initial_max_data.into_event(),
             initial_max_stream_data_bidi_local: self
                 .initial_max_stream_data_bidi_local
                 .into_event(),
@@ -1391,6 +1556,7 @@ impl<'a> IntoEvent<event::builder::TransportParameters<'a>> for &'a ServerTransp
             initial_max_streams_uni: self.initial_max_streams_uni.into_event(),
             max_datagram_frame_size: self.max_datagram_frame_size.into_event(),
             dc_supported_versions: self.dc_supported_versions.into_event(),
+            reset_stream_at_support: self.reset_stream_at_support.into_event(),
         }
     }
 }
@@ -1412,6 +1578,7 @@ impl<'a> IntoEvent<event::builder::TransportParameters<'a>> for &'a ClientTransp
             ack_delay_exponent: self.ack_delay_exponent.into_event(),
             max_ack_delay: Duration::from(self.max_ack_delay),
             active_connection_id_limit: self.active_connection_id_limit.into_event(),
+            initial_max_data: self.initial_max_data.into_event(),
             initial_max_stream_data_bidi_local: self
                 .initial_max_stream_data_bidi_local
                 .into_event(),
@@ -1423,6 +1590,7 @@ impl<'a> IntoEvent<event::builder::TransportParameters<'a>> for &'a ClientTransp
             initial_max_streams_uni: self.initial_max_streams_uni.into_event(),
             max_datagram_frame_size: self.max_datagram_frame_size.into_event(),
             dc_supported_versions: self.dc_supported_versions.into_event(),
+            reset_stream_at_support: self.reset_stream_at_support.into_event(),
         }
     }
 }
@@ -1455,6 +1623,21 @@ macro_rules! impl_transport_parameters {
             }
         }
 
+        impl<$($server_param),*> TransportParameters<$($server_param),*>
+        where
+            $(
+                $server_param: TransportParameter,
+            )*
+        {
+            /// Returns `true` if the ID is used by a transport parameter known to this implementation
+            #[cfg(feature = "alloc")]
+            pub(crate) fn is_known_id(id: TransportParameterId) -> bool {
+                $(
+                    id == <$field_ty>::ID ||
+                )* false
+            }
+        }
+
         impl<$($server_param),*> EncoderValue for TransportParameters<$($server_param),*>
         where
             $(
@@ -1568,6 +1751,9 @@ impl_transport_parameters!(
         initial_source_connection_id: Option<InitialSourceConnectionId>,
         retry_source_connection_id: RetrySourceConnectionId,
         dc_supported_versions: DcSupportedVersions,
+        reset_stream_at_support: ResetStreamAtSupport,
+        datagram_fec_support: DatagramFecSupport,
+        timestamp_support: TimestampSupport,
     }
 );
 
@@ -1615,6 +1801,9 @@ impl<
         load!(ack_delay_exponent, ack_delay_exponent);
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
+        load!(max_udp_payload_size, max_udp_payload_size);
         load!(migration_support, migration_support);
+        load!(reset_stream_at_support, reset_stream_at_support);
+        load!(timestamp_support, timestamp_support);
     }
 }
@@ -69,6 +69,9 @@ fn server_transport_parameters() -> ServerTransportParameters {
         ack_delay_exponent: 2u8.try_into().unwrap(),
         max_ack_delay: integer_value.try_into().unwrap(),
         migration_support: MigrationSupport::Disabled,
+        reset_stream_at_support: ResetStreamAtSupport::Enabled,
+        datagram_fec_support: DatagramFecSupport::Enabled,
+        timestamp_support: TimestampSupport::SEND_AND_RECEIVE,
         active_connection_id_limit: integer_value.try_into().unwrap(),
         original_destination_connection_id: Some([1, 2, 3, 4, 5, 6, 7, 8][..].try_into().unwrap()),
         stateless_reset_token: Some([2; 16].into()),
@@ -115,6 +118,9 @@ fn client_transport_parameters() -> ClientTransportParameters {
         ack_delay_exponent: 2u8.try_into().unwrap(),
         max_ack_delay: integer_value.try_into().unwrap(),
         migration_support: MigrationSupport::Disabled,
+        reset_stream_at_support: ResetStreamAtSupport::Enabled,
+        datagram_fec_support: DatagramFecSupport::Enabled,
+        timestamp_support: TimestampSupport::SEND_AND_RECEIVE,
         active_connection_id_limit: integer_value.try_into().unwrap(),
         original_destination_connection_id: Default::default(),
         stateless_reset_token: Default::default(),
@@ -188,6 +194,26 @@ fn ignore_unknown_parameter() {
     assert_eq!(0, remaining.len());
 }
 
+//= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+//= type=test
+//# Similarly, a server MUST NOT include a zero-
+//# length connection ID in this transport parameter.
+#[test]
+fn empty_preferred_address_connection_id() {
+    use s2n_codec::EncoderBuffer;
+
+    let mut value = server_transport_parameters();
+    value.preferred_address.as_mut().unwrap().connection_id = [][..].try_into().unwrap();
+
+    let mut buffer = vec![0; 32 * 1024];
+    let mut encoder = EncoderBuffer::new(&mut buffer);
+    encoder.encode(&value);
+
+    let (encoded, _) = encoder.split_off();
+    let decoder = DecoderBuffer::new(encoded);
+    assert!(ServerTransportParameters::decode(decoder).is_err());
+}
+
 #[test]
 fn compute_data_window_test() {
     assert_eq!(
@@ -334,3 +360,157 @@ fn dc_selected_version() {
         .selected_version()
         .is_err());
 }
+
+#[test]
+fn custom_parameters() {
+    use bytes::Bytes;
+    use custom::{Parameters, WithCustom};
+
+    let custom = Parameters::default()
+        .with(VarInt::from_u16(0x2ab2), &b"hello"[..])
+        .unwrap()
+        .with(VarInt::from_u32(0xff_0000), Bytes::new())
+        .unwrap();
+
+    // parameters known to the transport can't be overridden
+    assert!(Parameters::default()
+        .with(MaxIdleTimeout::ID, &b"oops"[..])
+        .is_err());
+    assert!(Parameters::default()
+        .with(DcSupportedVersions::ID, &b"oops"[..])
+        .is_err());
+    // duplicate IDs are rejected
+    assert!(custom
+        .clone()
+        .with(VarInt::from_u16(0x2ab2), &b"again"[..])
+        .is_err());
+
+    let value = client_transport_parameters();
+    let buffer = WithCustom(&value, &custom).encode_to_vec();
+
+    // the transport ignores the custom parameters
+    let (decoded_params, remaining) =
+        ClientTransportParameters::decode(DecoderBuffer::new(&buffer)).unwrap();
+    assert_eq!(value, decoded_params);
+    assert!(remaining.is_empty());
+
+    // only the unknown parameters are decoded
+    let decoded_custom = Parameters::decode_unknown(DecoderBuffer::new(&buffer)).unwrap();
+    assert_eq!(custom, decoded_custom);
+    assert_eq!(
+        Some(&Bytes::from_static(b"hello")),
+        decoded_custom.get(VarInt::from_u16(0x2ab2))
+    );
+    assert_eq!(None, decoded_custom.get(VarInt::from_u16(0x2ab3)));
+
+    // duplicate custom parameters are rejected
+    let buffer = WithCustom(&custom, &custom).encode_to_vec();
+    assert!(Parameters::decode_unknown(DecoderBuffer::new(&buffer)).is_err());
+}
+
+#[test]
+fn custom_parameters_many_entries() {
+    use custom::Parameters;
+
+    const LEN: u32 = 20_000;
+
+    // a large extension filled with empty parameters decodes without a quadratic cost
+    let mut buffer = vec![];
+    for id in 0x10_0000..0x10_0000 + LEN {
+        buffer.extend_from_slice(&VarInt::from_u32(id).encode_to_vec());
+        buffer.push(0);
+    }
+    let decoded_custom = Parameters::decode_unknown(DecoderBuffer::new(&buffer)).unwrap();
+    assert_eq!(LEN as usize, decoded_custom.len());
+
+    // a duplicate after all of the other entries is still rejected
+    buffer.extend_from_slice(&VarInt::from_u32(0x10_0000).encode_to_vec());
+    buffer.push(0);
+    assert!(Parameters::decode_unknown(DecoderBuffer::new(&buffer)).is_err());
+}
+
+#[test]
+fn custom_application_settings() {
+    use bytes::Bytes;
+    use custom::{Parameters, WithCustom, APPLICATION_SETTINGS_ID};
+
+    let custom = Parameters::default()
+        .with_application_settings(b"h3", b"h3 settings")
+        .unwrap()
+        .with(VarInt::from_u16(0x2ab2), &b"hello"[..])
+        .unwrap()
+        .with_application_settings(b"hq-interop", b"")
+        .unwrap();
+
+    // settings are kept in a single parameter
+    assert_eq!(custom.len(), 2);
+    assert_eq!(
+        Some(Bytes::from_static(b"h3 settings")),
+        custom.application_settings(b"h3")
+    );
+    assert_eq!(
+        Some(Bytes::new()),
+        custom.application_settings(b"hq-interop")
+    );
+    assert_eq!(None, custom.application_settings(b"h2"));
+
+    // invalid protocols and duplicate settings are rejected
+    assert!(custom.clone().with_application_settings(b"", b"").is_err());
+    assert!(custom
+        .clone()
+        .with_application_settings(&[1; 256], b"")
+        .is_err());
+    assert!(custom
+        .clone()
+        .with_application_settings(b"h3", b"again")
+        .is_err());
+    // the settings parameter can't be set directly
+    assert!(Parameters::default()
+        .with(APPLICATION_SETTINGS_ID, &b"oops"[..])
+        .is_err());
+
+    let value = client_transport_parameters();
+    let buffer = WithCustom(&value, &custom).encode_to_vec();
+    let decoded_custom = Parameters::decode_unknown(DecoderBuffer::new(&buffer)).unwrap();
+    assert_eq!(custom, decoded_custom);
+    assert_eq!(
+        Some(Bytes::from_static(b"h3 settings")),
+        decoded_custom.application_settings(b"h3")
+    );
+
+    // malformed settings are rejected
+    let mut buffer = value.encode_to_vec();
+    buffer.extend_from_slice(&APPLICATION_SETTINGS_ID.encode_to_vec());
+    // the settings length exceeds the parameter value
+    buffer.extend_from_slice(&[4, 2, b'h', b'3', 5]);
+    assert!(Parameters::decode_unknown(DecoderBuffer::new(&buffer)).is_err());
+}
+
+#[test]
+fn timestamp_support() {
+    use s2n_codec::EncoderBuffer;
+
+    for (value, is_valid) in [(0u8, false), (1, true), (2, true), (3, true), (4, false)] {
+        let mut params = client_transport_parameters();
+        params.timestamp_support = TimestampSupport::DISABLED;
+
+        let mut buffer = vec![0; 32 * 1024];
+        let mut encoder = EncoderBuffer::new(&mut buffer);
+        encoder.encode(&params);
+        encoder.encode(&TimestampSupport::ID);
+        encoder.encode_with_len_prefix::<TransportParameterLength, _>(&VarInt::from_u8(value));
+
+        let (encoded, _) = encoder.split_off();
+        let result = ClientTransportParameters::decode(DecoderBuffer::new(encoded));
+        assert_eq!(is_valid, result.is_ok(), "value: {value}");
+    }
+
+    assert!(!TimestampSupport::DISABLED.can_send());
+    assert!(!TimestampSupport::DISABLED.can_receive());
+    assert!(TimestampSupport::RECEIVE.can_receive());
+    assert!(!TimestampSupport::RECEIVE.can_send());
+    assert!(TimestampSupport::SEND.can_send());
+    assert!(!TimestampSupport::SEND.can_receive());
+    assert!(TimestampSupport::SEND_AND_RECEIVE.can_send());
+    assert!(TimestampSupport::SEND_AND_RECEIVE.can_receive());
+}
@@ -94,6 +94,24 @@ macro_rules! impl_cipher_suite {
                     key
                 }
 
+                /// Exposes the AEAD key and IV for the duration of `f` so they can be installed
+                /// on a crypto offload device
+                ///
+                /// The key is re-derived from the secret into a zeroizing buffer, since the
+                /// platform key doesn't allow reading it back.
+                pub fn with_key_material<R>(
+                    &self,
+                    f: impl FnOnce(&crate::offload::KeyMaterial) -> R,
+                ) -> R {
+                    let key = Self::new_key_secret(&self.secret);
+                    let key_material = crate::offload::KeyMaterial {
+                        cipher_suite: s2n_quic_core::crypto::tls::CipherSuite::$name,
+                        key: &*key,
+                        iv: self.iv.as_bytes(),
+                    };
+                    f(&key_material)
+                }
+
                 fn new_iv(secret: &hkdf::Prk) -> iv::Iv {
                     iv::Iv::new(secret, &$iv_label)
                 }
@@ -71,6 +71,11 @@ impl NegotiatedCipherSuite {
     pub fn update(&self) -> Self {
         dispatch!(self, |cipher| cipher.update().into())
     }
+
+    /// Exposes the AEAD key material for the duration of `f`
+    pub fn with_key_material<R>(&self, f: impl FnOnce(&crate::offload::KeyMaterial) -> R) -> R {
+        dispatch!(self, |cipher| cipher.with_key_material(f))
+    }
 }
 
 impl crypto::Key for NegotiatedCipherSuite {
@@ -5,7 +5,11 @@ use crate::{hkdf, ring_aead as aead};
 use core::fmt;
 use s2n_quic_core::crypto::{self, HeaderProtectionMask};
 
-pub struct HeaderKey(pub(crate) aead::quic::HeaderProtectionKey);
+pub struct HeaderKey {
+    key: aead::quic::HeaderProtectionKey,
+    /// Derives the masks of AES-based keys for multiple samples with a single call
+    batch: Option<batch::Key>,
+}
 
 impl crypto::HeaderKey for HeaderKey {
     #[inline]
@@ -15,7 +19,7 @@ impl crypto::HeaderKey for HeaderKey {
 
     #[inline]
     fn opening_sample_len(&self) -> usize {
-        self.0.algorithm().sample_len()
+        self.key.algorithm().sample_len()
     }
 
     #[inline]
@@ -23,9 +27,24 @@ impl crypto::HeaderKey for HeaderKey {
         self.header_protection_mask(sample)
     }
 
+    #[inline]
+    fn sealing_header_protection_masks(
+        &self,
+        samples: &mut [u8],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        if let Some(batch) = self.batch.as_ref() {
+            batch.masks(samples, masks);
+        } else {
+            for (sample, mask) in samples.chunks_exact(self.sealing_sample_len()).zip(masks) {
+                *mask = self.header_protection_mask(sample);
+            }
+        }
+    }
+
     #[inline]
     fn sealing_sample_len(&self) -> usize {
-        self.0.algorithm().sample_len()
+        self.key.algorithm().sample_len()
     }
 }
 
@@ -45,12 +64,13 @@ impl HeaderKey {
 
         let key = aead::quic::HeaderProtectionKey::new(alg, bytes.as_ref())
             .expect("header secret length already checked");
-        Self(key)
+        let batch = batch::Key::new(alg, bytes.as_ref());
+        Self { key, batch }
     }
 
     #[inline]
     fn header_protection_mask(&self, sample: &[u8]) -> HeaderProtectionMask {
-        self.0
+        self.key
             .new_mask(sample)
             .expect("sample length already checked")
     }
@@ -64,7 +84,69 @@ impl fmt::Debug for HeaderKey {
 
 impl From<aead::quic::HeaderProtectionKey> for HeaderKey {
     fn from(key: aead::quic::HeaderProtectionKey) -> Self {
-        Self(key)
+        Self { key, batch: None }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod batch {
+    use crate::{ring::cipher, ring_aead::quic};
+    use s2n_quic_core::crypto::{HeaderProtectionMask, HEADER_PROTECTION_MASK_LEN};
+
+    /// An AES key in ECB mode
+    ///
+    /// The AES header protection mask is the encrypted sample, so encrypting all of the
+    /// samples as consecutive blocks derives all of the masks at once.
+    //= https://www.rfc-editor.org/rfc/rfc9001#section-5.4.3
+    //# mask = AES-ECB(hp_key, sample)
+    pub struct Key(cipher::EncryptingKey);
+
+    impl Key {
+        pub fn new(alg: &'static quic::Algorithm, key: &[u8]) -> Option<Self> {
+            let alg = if alg == &quic::AES_128 {
+                &cipher::AES_128
+            } else if alg == &quic::AES_256 {
+                &cipher::AES_256
+            } else {
+                return None;
+            };
+
+            let key = cipher::UnboundCipherKey::new(alg, key).ok()?;
+            let key = cipher::EncryptingKey::ecb(key).ok()?;
+            Some(Self(key))
+        }
+
+        #[inline]
+        pub fn masks(&self, samples: &mut [u8], masks: &mut [HeaderProtectionMask]) {
+            self.0
+                .encrypt(samples)
+                .expect("samples are a multiple of the block length");
+
+            let block_len = self.0.algorithm().block_len();
+            for (block, mask) in samples.chunks_exact(block_len).zip(masks) {
+                mask.copy_from_slice(&block[..HEADER_PROTECTION_MASK_LEN]);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod batch {
+    use crate::ring_aead::quic;
+    use s2n_quic_core::crypto::HeaderProtectionMask;
+
+    /// The platform library doesn't expose AES-ECB so masks are derived one sample at a time
+    pub enum Key {}
+
+    impl Key {
+        pub fn new(_alg: &'static quic::Algorithm, _key: &[u8]) -> Option<Self> {
+            None
+        }
+
+        #[inline]
+        pub fn masks(&self, _samples: &mut [u8], _masks: &mut [HeaderProtectionMask]) {
+            match *self {}
+        }
     }
 }
 
@@ -90,6 +172,15 @@ impl crypto::HeaderKey for HeaderKeyPair {
         self.sealer.sealing_header_protection_mask(sample)
     }
 
+    #[inline]
+    fn sealing_header_protection_masks(
+        &self,
+        samples: &mut [u8],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        self.sealer.sealing_header_protection_masks(samples, masks)
+    }
+
     #[inline]
     fn sealing_sample_len(&self) -> usize {
         self.sealer.sealing_sample_len()
@@ -123,6 +214,15 @@ macro_rules! header_key {
                 self.0.sealing_header_protection_mask(sample)
             }
 
+            #[inline]
+            fn sealing_header_protection_masks(
+                &self,
+                samples: &mut [u8],
+                masks: &mut [s2n_quic_core::crypto::HeaderProtectionMask],
+            ) {
+                self.0.sealing_header_protection_masks(samples, masks)
+            }
+
             #[inline]
             fn sealing_sample_len(&self) -> usize {
                 self.0.sealing_sample_len()
@@ -136,3 +236,34 @@ macro_rules! header_key {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::crypto::HeaderKey as _;
+
+    fn check<const KEY_LEN: usize>(alg: &'static aead::quic::Algorithm) {
+        let secret = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, &[1; 32]);
+        let key = HeaderKey::new::<KEY_LEN>(&secret, b"quic hp", alg);
+
+        let sample_len = key.sealing_sample_len();
+        let samples: Vec<u8> = (0..sample_len * 10).map(|i| i as u8).collect();
+
+        let expected: Vec<_> = samples
+            .chunks_exact(sample_len)
+            .map(|sample| key.sealing_header_protection_mask(sample))
+            .collect();
+
+        let mut actual = vec![HeaderProtectionMask::default(); expected.len()];
+        key.sealing_header_protection_masks(&mut samples.clone(), &mut actual);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn batch_masks_test() {
+        check::<16>(&aead::quic::AES_128);
+        check::<32>(&aead::quic::AES_256);
+        check::<32>(&aead::quic::CHACHA20);
+    }
+}
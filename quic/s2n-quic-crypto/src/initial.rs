@@ -1,7 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{cipher_suite::TLS_AES_128_GCM_SHA256 as CipherSuite, header_key::HeaderKeyPair, hkdf};
+use crate::{
+    cipher_suite::TLS_AES_128_GCM_SHA256 as CipherSuite, header_key::HeaderKeyPair, hkdf, hmac,
+};
 use s2n_quic_core::{
     crypto::{
         self,
@@ -22,24 +24,49 @@ pub struct InitialKey {
 }
 
 lazy_static::lazy_static! {
-    /// Compute the Initial salt once, as the seed is constant
-    static ref INITIAL_SIGNING_KEY: hkdf::Salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &INITIAL_SALT);
+    /// Key the HMAC used for extracting the Initial secret once, as the salt is constant
+    static ref INITIAL_SALT_KEY: hmac::Key = hmac::Key::new(hmac::HMAC_SHA256, &INITIAL_SALT);
+}
+
+/// The client and server secrets derived from a client's destination connection ID
+struct InitialSecrets {
+    client: hmac::Tag,
+    server: hmac::Tag,
+}
+
+impl InitialSecrets {
+    fn new(connection_id: &[u8]) -> Self {
+        // initial_secret = HKDF-Extract(initial_salt, client_dst_connection_id)
+        let initial_secret = hmac::sign(&INITIAL_SALT_KEY, connection_id);
+
+        // Both secrets are expanded from the Initial secret, so it's only extracted and keyed
+        // once rather than for every expansion.
+        let initial_secret = hmac::Key::new(hmac::HMAC_SHA256, initial_secret.as_ref());
+
+        Self {
+            client: expand_secret(&initial_secret, &CLIENT_IN),
+            server: expand_secret(&initial_secret, &SERVER_IN),
+        }
+    }
+}
+
+/// Performs HKDF-Expand for an output the size of the digest
+///
+/// Only the first block is needed, which is `T(1) = HMAC-Hash(PRK, info | 0x01)`.
+#[inline]
+fn expand_secret(secret: &hmac::Key, label: &[u8]) -> hmac::Tag {
+    let mut context = hmac::Context::with_key(secret);
+    context.update(label);
+    context.update(&[1]);
+    context.sign()
 }
 
 impl InitialKey {
     fn new(endpoint: endpoint::Type, connection_id: &[u8]) -> (Self, InitialHeaderKey) {
-        let initial_secret = INITIAL_SIGNING_KEY.extract(connection_id);
-        let digest = INITIAL_SIGNING_KEY.algorithm();
+        let secrets = InitialSecrets::new(connection_id);
 
-        let client_secret = initial_secret
-            .expand(&[&CLIENT_IN], digest)
-            .expect("label size verified")
-            .into();
-
-        let server_secret = initial_secret
-            .expand(&[&SERVER_IN], digest)
-            .expect("label size verified")
-            .into();
+        let client_secret = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, secrets.client.as_ref());
+        let server_secret = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, secrets.server.as_ref());
 
         let (sealer, opener) = match endpoint {
             endpoint::Type::Client => (
@@ -130,8 +157,8 @@ mod tests {
         crypto::{
             initial::{
                 EXAMPLE_CLIENT_INITIAL_PAYLOAD, EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET,
-                EXAMPLE_DCID, EXAMPLE_SERVER_INITIAL_PAYLOAD,
-                EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
+                EXAMPLE_CLIENT_INITIAL_SECRET, EXAMPLE_DCID, EXAMPLE_SERVER_INITIAL_PAYLOAD,
+                EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET, EXAMPLE_SERVER_INITIAL_SECRET,
             },
             InitialKey as _,
         },
@@ -139,6 +166,14 @@ mod tests {
         packet::{encoding::PacketEncoder, initial::CleartextInitial, ProtectedPacket},
     };
 
+    #[test]
+    fn rfc_example_secrets_test() {
+        let secrets = InitialSecrets::new(&EXAMPLE_DCID);
+
+        assert_eq!(secrets.client.as_ref(), EXAMPLE_CLIENT_INITIAL_SECRET);
+        assert_eq!(secrets.server.as_ref(), EXAMPLE_SERVER_INITIAL_SECRET);
+    }
+
     #[test]
     fn rfc_example_server_test() {
         test_round_trip(
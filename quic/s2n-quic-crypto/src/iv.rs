@@ -23,6 +23,11 @@ impl Iv {
         Self(bytes)
     }
 
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8; NONCE_LEN] {
+        &self.0
+    }
+
     #[inline]
     pub fn nonce(&self, packet_number: u64) -> [u8; NONCE_LEN] {
         let mut nonce = [0; NONCE_LEN];
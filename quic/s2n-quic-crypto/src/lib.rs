@@ -29,6 +29,7 @@ pub struct SecretPair {
 
 pub mod handshake;
 pub mod initial;
+pub mod offload;
 pub mod one_rtt;
 pub mod retry;
 pub mod zero_rtt;
@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    cipher_suite::NegotiatedCipherSuite as CipherSuite, header_key::HeaderKeyPair,
-    ring_aead::Algorithm, SecretPair,
+    cipher_suite::NegotiatedCipherSuite as CipherSuite,
+    header_key::HeaderKeyPair,
+    offload::{self, Device},
+    ring_aead::Algorithm,
+    SecretPair,
 };
+use core::fmt;
 use s2n_quic_core::{
     crypto::{packet_protection, scatter, Key},
     endpoint,
@@ -14,6 +18,35 @@ use s2n_quic_core::{
 pub struct KeyPair {
     pub(crate) sealer: CipherSuite,
     pub(crate) opener: CipherSuite,
+    offload: Option<Offload>,
+}
+
+/// Device-backed keys for the current key phase
+struct Offload {
+    device: Device,
+    sealer: Option<Box<dyn offload::Sealer>>,
+    opener: Option<Box<dyn offload::Opener>>,
+}
+
+impl Offload {
+    fn new(device: Device, sealer: &CipherSuite, opener: &CipherSuite) -> Self {
+        let sealer = sealer.with_key_material(|key| device.sealer(key));
+        let opener = opener.with_key_material(|key| device.opener(key));
+        Self {
+            device,
+            sealer,
+            opener,
+        }
+    }
+}
+
+impl fmt::Debug for Offload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Offload")
+            .field("sealer", &self.sealer.is_some())
+            .field("opener", &self.opener.is_some())
+            .finish()
+    }
 }
 
 impl KeyPair {
@@ -30,7 +63,11 @@ impl KeyPair {
         let (sealer, header_sealer) = CipherSuite::new(algorithm, sealer_secret)?;
         let (opener, header_opener) = CipherSuite::new(algorithm, opener_secret)?;
 
-        let key = Self { sealer, opener };
+        let key = Self {
+            sealer,
+            opener,
+            offload: None,
+        };
         let header_key = HeaderKeyPair {
             sealer: header_sealer,
             opener: header_opener,
@@ -43,11 +80,27 @@ impl KeyPair {
     /// <https://www.rfc-editor.org/rfc/rfc9001#section-6>
     #[inline]
     pub fn update(&self) -> Self {
+        let sealer = self.sealer.update();
+        let opener = self.opener.update();
+        // keys for the next phase are installed on the same device as the current phase
+        let offload = self
+            .offload
+            .as_ref()
+            .map(|offload| Offload::new(offload.device.clone(), &sealer, &opener));
+
         Self {
-            sealer: self.sealer.update(),
-            opener: self.opener.update(),
+            sealer,
+            opener,
+            offload,
         }
     }
+
+    /// Installs the keys on a crypto offload device
+    ///
+    /// Packets the device declines are protected in software.
+    pub fn with_offload(&mut self, device: Device) {
+        self.offload = Some(Offload::new(device, &self.sealer, &self.opener));
+    }
 }
 
 impl Key for KeyPair {
@@ -58,6 +111,14 @@ impl Key for KeyPair {
         header: &[u8],
         payload: &mut [u8],
     ) -> Result<(), packet_protection::Error> {
+        if let Some(opener) = self.offload.as_ref().and_then(|o| o.opener.as_ref()) {
+            match opener.open(packet_number, header, payload) {
+                Ok(()) => return Ok(()),
+                Err(offload::Error::Crypto(error)) => return Err(error),
+                Err(offload::Error::Unavailable) => {}
+            }
+        }
+
         self.opener.decrypt(packet_number, header, payload)
     }
 
@@ -68,6 +129,14 @@ impl Key for KeyPair {
         header: &[u8],
         payload: &mut scatter::Buffer,
     ) -> Result<(), packet_protection::Error> {
+        if let Some(sealer) = self.offload.as_mut().and_then(|o| o.sealer.as_mut()) {
+            match sealer.seal(packet_number, header, payload) {
+                Ok(()) => return Ok(()),
+                Err(offload::Error::Crypto(error)) => return Err(error),
+                Err(offload::Error::Unavailable) => {}
+            }
+        }
+
         self.sealer.encrypt(packet_number, header, payload)
     }
 
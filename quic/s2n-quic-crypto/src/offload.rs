@@ -0,0 +1,295 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Delegation of 1-RTT packet protection to hardware crypto engines
+//!
+//! An [`Offload`] implementation is handed the AEAD key material for each 1-RTT key phase and
+//! may return a [`Sealer`] and/or [`Opener`] bound to a device, such as a NIC with inline
+//! crypto or a lookaside accelerator. Packets are routed to the device first; any packet it
+//! declines with [`Error::Unavailable`] is protected in software with the same keys, so the
+//! device can shed load or refuse packets it can't handle at any time.
+//!
+//! Header protection is always applied in software.
+
+use core::fmt;
+use s2n_quic_core::crypto::{packet_protection, scatter, tls::CipherSuite};
+use std::sync::Arc;
+
+/// AEAD key material for a single direction of a key phase
+///
+/// The referenced bytes are only valid for the duration of the [`Offload`] call and are
+/// zeroized immediately afterwards. Implementations should copy them directly into device
+/// memory and not retain them anywhere else.
+#[non_exhaustive]
+pub struct KeyMaterial<'a> {
+    pub cipher_suite: CipherSuite,
+    pub key: &'a [u8],
+    pub iv: &'a [u8],
+}
+
+impl fmt::Debug for KeyMaterial<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyMaterial")
+            .field("cipher_suite", &self.cipher_suite)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The device can't process the packet right now
+    ///
+    /// The payload MUST be left unmodified so it can be protected in software instead.
+    Unavailable,
+    /// The device processed the packet and the operation failed
+    Crypto(packet_protection::Error),
+}
+
+impl From<packet_protection::Error> for Error {
+    fn from(error: packet_protection::Error) -> Self {
+        Self::Crypto(error)
+    }
+}
+
+/// Encrypts outgoing packets with a key installed on a device
+pub trait Sealer: 'static + Send {
+    /// Encrypts the payload in place and writes the authentication tag after it, with the
+    /// same layout as [`s2n_quic_core::crypto::Key::encrypt`]
+    fn seal(
+        &mut self,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), Error>;
+}
+
+/// Decrypts incoming packets with a key installed on a device
+pub trait Opener: 'static + Send {
+    /// Decrypts the payload in place, with the same layout as
+    /// [`s2n_quic_core::crypto::Key::decrypt`]
+    fn open(&self, packet_number: u64, header: &[u8], payload: &mut [u8]) -> Result<(), Error>;
+}
+
+/// Installs 1-RTT keys on a crypto offload device
+///
+/// Each method is called once per key phase and direction. Returning `None` keeps that
+/// direction in software for the lifetime of the key phase.
+pub trait Offload: 'static + Send + Sync {
+    fn sealer(&self, key: &KeyMaterial) -> Option<Box<dyn Sealer>>;
+
+    fn opener(&self, key: &KeyMaterial) -> Option<Box<dyn Opener>>;
+}
+
+/// A shared handle to an [`Offload`] implementation
+#[derive(Clone)]
+pub struct Device(Arc<dyn Offload>);
+
+impl Device {
+    pub fn new<O: Offload>(offload: O) -> Self {
+        Self(Arc::new(offload))
+    }
+}
+
+impl From<Arc<dyn Offload>> for Device {
+    fn from(offload: Arc<dyn Offload>) -> Self {
+        Self(offload)
+    }
+}
+
+impl fmt::Debug for Device {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Device").finish()
+    }
+}
+
+impl core::ops::Deref for Device {
+    type Target = dyn Offload;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aead::Aead, hkdf, one_rtt::OneRttKey, ring_aead as aead, SecretPair, MAX_TAG_LEN};
+    use s2n_codec::{Encoder, EncoderBuffer};
+    use s2n_quic_core::crypto::{Key, OneRttKey as _};
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    #[derive(Default)]
+    struct State {
+        installs: AtomicUsize,
+        seals: AtomicUsize,
+        opens: AtomicUsize,
+        unavailable: AtomicBool,
+    }
+
+    /// Software stand-in for a device, built only from the exported key material
+    #[derive(Clone, Default)]
+    struct Mock(Arc<State>);
+
+    struct MockKey {
+        key: Mutex<aead::LessSafeKey>,
+        iv: [u8; aead::NONCE_LEN],
+        state: Arc<State>,
+    }
+
+    impl MockKey {
+        fn new(key: &KeyMaterial, state: &Arc<State>) -> Self {
+            assert_eq!(key.cipher_suite, CipherSuite::TLS_CHACHA20_POLY1305_SHA256);
+            state.installs.fetch_add(1, Ordering::Relaxed);
+            let unbound = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, key.key).unwrap();
+            Self {
+                key: Mutex::new(aead::LessSafeKey::new(unbound)),
+                iv: key.iv.try_into().unwrap(),
+                state: state.clone(),
+            }
+        }
+
+        fn nonce(&self, packet_number: u64) -> [u8; aead::NONCE_LEN] {
+            let mut nonce = self.iv;
+            for (a, b) in nonce[4..].iter_mut().zip(packet_number.to_be_bytes()) {
+                *a ^= b;
+            }
+            nonce
+        }
+    }
+
+    impl Sealer for MockKey {
+        fn seal(
+            &mut self,
+            packet_number: u64,
+            header: &[u8],
+            payload: &mut scatter::Buffer,
+        ) -> Result<(), Error> {
+            if self.state.unavailable.load(Ordering::Relaxed) {
+                return Err(Error::Unavailable);
+            }
+            self.state.seals.fetch_add(1, Ordering::Relaxed);
+            let nonce = self.nonce(packet_number);
+            self.key
+                .get_mut()
+                .unwrap()
+                .encrypt(&nonce, header, payload)?;
+            Ok(())
+        }
+    }
+
+    impl Opener for MockKey {
+        fn open(&self, packet_number: u64, header: &[u8], payload: &mut [u8]) -> Result<(), Error> {
+            if self.state.unavailable.load(Ordering::Relaxed) {
+                return Err(Error::Unavailable);
+            }
+            self.state.opens.fetch_add(1, Ordering::Relaxed);
+            let nonce = aead::Nonce::assume_unique_for_key(self.nonce(packet_number));
+            self.key
+                .lock()
+                .unwrap()
+                .open_in_place(nonce, aead::Aad::from(header), payload)
+                .map_err(|_| packet_protection::Error::DECRYPT_ERROR)?;
+            Ok(())
+        }
+    }
+
+    impl Offload for Mock {
+        fn sealer(&self, key: &KeyMaterial) -> Option<Box<dyn Sealer>> {
+            Some(Box::new(MockKey::new(key, &self.0)))
+        }
+
+        fn opener(&self, key: &KeyMaterial) -> Option<Box<dyn Opener>> {
+            Some(Box::new(MockKey::new(key, &self.0)))
+        }
+    }
+
+    const HEADER: &[u8] = &[1, 2, 3];
+    const PAYLOAD: &[u8] = &[42; 16];
+
+    fn keys() -> (OneRttKey, OneRttKey) {
+        let secrets = || SecretPair {
+            server: hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, &[1; 32]),
+            client: hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, &[2; 32]),
+        };
+        let (server, _) = OneRttKey::new_server(&aead::CHACHA20_POLY1305, secrets()).unwrap();
+        let (client, _) = OneRttKey::new_client(&aead::CHACHA20_POLY1305, secrets()).unwrap();
+        (server, client)
+    }
+
+    /// Seals a packet with `sealer` and checks that `opener` recovers it
+    fn round_trip(sealer: &mut OneRttKey, opener: &OneRttKey, packet_number: u64) {
+        let mut buffer = [0u8; PAYLOAD.len() + MAX_TAG_LEN];
+        {
+            let mut encoder = EncoderBuffer::new(&mut buffer);
+            encoder.write_slice(PAYLOAD);
+            let mut payload = scatter::Buffer::new(encoder);
+            sealer.encrypt(packet_number, HEADER, &mut payload).unwrap();
+        }
+        assert_ne!(&buffer[..PAYLOAD.len()], PAYLOAD);
+
+        opener.decrypt(packet_number, HEADER, &mut buffer).unwrap();
+        assert_eq!(&buffer[..PAYLOAD.len()], PAYLOAD);
+    }
+
+    #[test]
+    fn offload_matches_software_test() {
+        let mock = Mock::default();
+        let (server, mut client) = keys();
+        let mut server = server.with_offload(Device::new(mock.clone()));
+        assert_eq!(mock.0.installs.load(Ordering::Relaxed), 2);
+
+        round_trip(&mut server, &client, 1);
+        round_trip(&mut client, &server, 2);
+        assert_eq!(mock.0.seals.load(Ordering::Relaxed), 1);
+        assert_eq!(mock.0.opens.load(Ordering::Relaxed), 1);
+
+        // the device is reused for the next key phase
+        let mut server = server.derive_next_key();
+        let mut client = client.derive_next_key();
+        assert_eq!(mock.0.installs.load(Ordering::Relaxed), 4);
+
+        round_trip(&mut server, &client, 3);
+        round_trip(&mut client, &server, 4);
+        assert_eq!(mock.0.seals.load(Ordering::Relaxed), 2);
+        assert_eq!(mock.0.opens.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn offload_fallback_test() {
+        let mock = Mock::default();
+        mock.0.unavailable.store(true, Ordering::Relaxed);
+        let (server, mut client) = keys();
+        let mut server = server.with_offload(Device::new(mock.clone()));
+
+        round_trip(&mut server, &client, 1);
+        round_trip(&mut client, &server, 2);
+        assert_eq!(mock.0.seals.load(Ordering::Relaxed), 0);
+        assert_eq!(mock.0.opens.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn offload_decrypt_error_test() {
+        let (server, mut client) = keys();
+        let server = server.with_offload(Device::new(Mock::default()));
+
+        let mut buffer = [0u8; PAYLOAD.len() + MAX_TAG_LEN];
+        {
+            let mut encoder = EncoderBuffer::new(&mut buffer);
+            encoder.write_slice(PAYLOAD);
+            let mut payload = scatter::Buffer::new(encoder);
+            client.encrypt(1, HEADER, &mut payload).unwrap();
+        }
+        buffer[0] ^= 1;
+
+        // device failures are reported rather than retried in software
+        assert_eq!(
+            server.decrypt(1, HEADER, &mut buffer),
+            Err(packet_protection::Error::DECRYPT_ERROR)
+        );
+    }
+}
@@ -1,11 +1,24 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::offload::Device;
 use s2n_quic_core::crypto;
 
 header_key!(OneRttHeaderKey);
 negotiated_crypto!(OneRttKey, OneRttHeaderKey);
 
+impl OneRttKey {
+    /// Delegates packet protection to a crypto offload device, falling back to software for
+    /// any packets it declines
+    ///
+    /// The device is kept across key updates.
+    #[must_use]
+    pub fn with_offload(mut self, device: Device) -> Self {
+        self.0.with_offload(device);
+        self
+    }
+}
+
 impl crypto::OneRttKey for OneRttKey {
     #[inline]
     #[must_use]
@@ -72,6 +72,14 @@ impl crypto::HeaderKey for ZeroRttHeaderKey {
         self.0.sealing_header_protection_mask(sample)
     }
 
+    fn sealing_header_protection_masks(
+        &self,
+        samples: &mut [u8],
+        masks: &mut [HeaderProtectionMask],
+    ) {
+        self.0.sealing_header_protection_masks(samples, masks)
+    }
+
     fn sealing_sample_len(&self) -> usize {
         self.0.sealing_sample_len()
     }
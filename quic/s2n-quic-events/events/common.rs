@@ -34,6 +34,7 @@ struct TransportParameters<'a> {
     max_ack_delay: Duration,
     max_udp_payload_size: u64,
     active_connection_id_limit: u64,
+    initial_max_data: u64,
     initial_max_stream_data_bidi_local: u64,
     initial_max_stream_data_bidi_remote: u64,
     initial_max_stream_data_uni: u64,
@@ -41,6 +42,7 @@ struct TransportParameters<'a> {
     initial_max_streams_uni: u64,
     max_datagram_frame_size: u64,
     dc_supported_versions: &'a [u32],
+    reset_stream_at_support: bool,
 }
 
 struct PreferredAddress<'a> {
@@ -94,6 +96,16 @@ impl IntoEvent<bool> for &crate::transport::parameters::MigrationSupport {
     }
 }
 
+impl IntoEvent<bool> for &crate::transport::parameters::ResetStreamAtSupport {
+    #[inline]
+    fn into_event(self) -> bool {
+        match self {
+            crate::transport::parameters::ResetStreamAtSupport::Enabled => true,
+            crate::transport::parameters::ResetStreamAtSupport::Disabled => false,
+        }
+    }
+}
+
 #[builder_derive(derive(Copy))]
 struct Path<'a> {
     local_addr: SocketAddress<'a>,
@@ -371,6 +383,19 @@ enum Frame {
         len: u16,
     },
     DcStatelessResetTokens,
+    ResetStreamAt {
+        id: u64,
+        error_code: u64,
+        final_size: u64,
+        reliable_size: u64,
+    },
+    Timestamp {
+        timestamp: u64,
+    },
+    Custom {
+        frame_type: u64,
+        len: u16,
+    },
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -411,6 +436,18 @@ impl IntoEvent<builder::Frame> for &crate::frame::ResetStream {
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ResetStreamAt {
+            id: self.stream_id.as_u64(),
+            error_code: self.application_error_code.as_u64(),
+            final_size: self.final_size.as_u64(),
+            reliable_size: self.reliable_size.as_u64(),
+        }
+    }
+}
+
 impl IntoEvent<builder::Frame> for &crate::frame::StopSending {
     #[inline]
     fn into_event(self) -> builder::Frame {
@@ -581,11 +618,141 @@ impl<'a> IntoEvent<builder::Frame> for &crate::frame::DcStatelessResetTokens<'a>
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::Timestamp {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::Timestamp {
+            timestamp: self.timestamp.as_u64(),
+        }
+    }
+}
+
+impl<Data> IntoEvent<builder::Frame> for &crate::frame::Custom<Data>
+where
+    Data: s2n_codec::EncoderValue,
+{
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::Custom {
+            frame_type: self.frame_type.as_u64(),
+            len: self.data.encoding_size() as _,
+        }
+    }
+}
+
 enum StreamType {
     Bidirectional,
     Unidirectional,
 }
 
+/// How long a closed connection retains its state before it is dropped
+enum ConnectionCloseLinger {
+    /// The connection remains in the closing or draining state for three times the probe
+    /// timeout (PTO), as recommended by RFC 9000
+    Default,
+    /// The connection remains in the closing state for the duration requested by the application
+    Duration { duration: Duration },
+    /// The connection was aborted by the application
+    ///
+    /// No CONNECTION_CLOSE frame is sent and the connection state is dropped immediately. The
+    /// peer only learns about the closure through a stateless reset or its idle timeout.
+    Abort,
+}
+
+impl IntoEvent<builder::ConnectionCloseLinger> for crate::connection::close::Linger {
+    #[inline]
+    fn into_event(self) -> builder::ConnectionCloseLinger {
+        use crate::connection::close::Linger;
+        match self {
+            Linger::Default => builder::ConnectionCloseLinger::Default,
+            Linger::Duration(duration) => builder::ConnectionCloseLinger::Duration { duration },
+            Linger::Abort => builder::ConnectionCloseLinger::Abort,
+        }
+    }
+}
+
+enum EvictionCriteria {
+    /// The connection had gone the longest without receiving a datagram from the peer
+    IdleLongest,
+    /// The connection had the lowest average throughput since it was created
+    LowestThroughput,
+    /// The connection was the oldest on the endpoint
+    Oldest,
+}
+
+impl IntoEvent<builder::EvictionCriteria> for crate::endpoint::limits::EvictionCriteria {
+    #[inline]
+    fn into_event(self) -> builder::EvictionCriteria {
+        use crate::endpoint::limits::EvictionCriteria;
+        match self {
+            EvictionCriteria::IdleLongest => builder::EvictionCriteria::IdleLongest,
+            EvictionCriteria::LowestThroughput => builder::EvictionCriteria::LowestThroughput,
+            EvictionCriteria::Oldest => builder::EvictionCriteria::Oldest,
+        }
+    }
+}
+
+/// The class a connection is scheduled in when competing with other connections on the endpoint
+enum PriorityClass {
+    /// Latency sensitive connections which transmit before the other classes
+    Control,
+    /// The default class
+    Normal,
+    /// Throughput oriented connections which yield to the other classes
+    Bulk,
+}
+
+/// The action a client took after receiving a Version Negotiation packet
+enum VersionNegotiationOutcome {
+    /// The server doesn't support the version chosen by the client so the connection attempt
+    /// was abandoned
+    Abandoned,
+    /// The packet was discarded because it lists the version chosen by the client
+    ChosenVersionListed,
+    /// The packet was discarded because the client already processed another packet from the
+    /// server
+    AlreadyProcessedPacket,
+}
+
+impl IntoEvent<builder::PriorityClass> for crate::connection::PriorityClass {
+    #[inline]
+    fn into_event(self) -> builder::PriorityClass {
+        use crate::connection::PriorityClass;
+        match self {
+            PriorityClass::Control => builder::PriorityClass::Control,
+            PriorityClass::Normal => builder::PriorityClass::Normal,
+            PriorityClass::Bulk => builder::PriorityClass::Bulk,
+        }
+    }
+}
+
+enum SocketError {
+    /// The peer's host reported that nothing is listening on the destination port
+    PortUnreachable,
+    /// The peer's host or network could not be reached
+    HostUnreachable,
+    /// The datagram exceeded the MTU of a link on the path
+    PacketTooBig {
+        /// The reported MTU, including the IP and UDP headers
+        mtu: u16,
+    },
+    /// The datagram couldn't be sent from the local address of its path
+    LocalAddressUnavailable,
+}
+
+impl IntoEvent<builder::SocketError> for crate::io::rx::SocketError {
+    #[inline]
+    fn into_event(self) -> builder::SocketError {
+        use crate::io::rx::SocketError;
+        match self {
+            SocketError::PortUnreachable => builder::SocketError::PortUnreachable,
+            SocketError::HostUnreachable => builder::SocketError::HostUnreachable,
+            SocketError::PacketTooBig { mtu } => builder::SocketError::PacketTooBig { mtu },
+            SocketError::LocalAddressUnavailable => builder::SocketError::LocalAddressUnavailable,
+        }
+    }
+}
+
 impl IntoEvent<builder::StreamType> for &crate::stream::StreamType {
     #[inline]
     fn into_event(self) -> builder::StreamType {
@@ -704,6 +871,24 @@ impl IntoEvent<builder::EndpointType> for crate::endpoint::Type {
     }
 }
 
+/// The mechanism by which an address token was delivered to the peer
+enum AddressTokenSource {
+    /// The token was delivered in a Retry packet
+    RetryPacket,
+    /// The token was delivered in a NEW_TOKEN frame
+    NewTokenFrame,
+}
+
+impl IntoEvent<builder::AddressTokenSource> for crate::token::Source {
+    #[inline]
+    fn into_event(self) -> builder::AddressTokenSource {
+        match self {
+            Self::RetryPacket => builder::AddressTokenSource::RetryPacket,
+            Self::NewTokenFrame => builder::AddressTokenSource::NewTokenFrame,
+        }
+    }
+}
+
 enum DatagramDropReason {
     /// There was an error while attempting to decode the datagram.
     DecodingFailed,
@@ -744,6 +929,8 @@ enum DatagramDropReason {
     PathLimitExceeded,
     /// The peer initiated a connection migration without supplying enough connection IDs to use.
     InsufficientConnectionIds,
+    /// The maximum number of concurrent path validations was exceeded.
+    PathValidationLimitExceeded,
 }
 
 struct MtuConfig {
@@ -903,6 +1090,15 @@ enum HandshakeStatus {
     HandshakeDoneLost,
 }
 
+/// The outcome of an Encrypted Client Hello offer
+enum EchStatus {
+    /// The server decrypted the inner ClientHello, so the handshake was authenticated with the
+    /// protected server name
+    Accepted,
+    /// The server didn't decrypt the inner ClientHello and the client aborted the handshake
+    Rejected,
+}
+
 /// The source that caused a congestion event
 enum CongestionSource {
     /// Explicit Congestion Notification
@@ -963,6 +1159,8 @@ enum MtuUpdatedCause {
     Blackhole,
     /// An early packet using the configured InitialMtu was lost
     InitialMtuPacketLost,
+    /// The network reported that a packet exceeded the MTU of a link on the path
+    PacketTooBig,
 }
 
 /// A bandwidth delivery rate estimate with associated metadata
@@ -989,6 +1187,34 @@ struct RateSample {
     delivery_rate_bytes_per_second: u64,
 }
 
+/// The validation state of a path
+enum PathValidation {
+    /// The path has been validated
+    Validated,
+    /// A PATH_CHALLENGE has been sent on the path and is awaiting a response
+    Pending,
+    /// The path failed validation
+    Failed,
+    /// The path hasn't been validated and no PATH_CHALLENGE is in progress
+    NotValidated,
+}
+
+/// The phase of the congestion controller state machine
+enum CongestionPhase {
+    /// The congestion window is growing rapidly to find the capacity of the path
+    SlowStart,
+    /// The congestion window is growing after slow start has been exited
+    CongestionAvoidance,
+    /// The congestion controller is reacting to a congestion event
+    Recovery,
+    /// The congestion controller is draining the queue built up during slow start
+    Drain,
+    /// The congestion controller is probing for more bandwidth
+    ProbeBandwidth,
+    /// The congestion controller is probing for the minimum round trip time
+    ProbeRtt,
+}
+
 // The BBR congestion controller State
 enum BbrState {
     Startup,
@@ -16,6 +16,14 @@ struct ServerNameInformation<'a> {
     chosen_server_name: &'a str,
 }
 
+#[event("security:encrypted_client_hello")]
+/// The server accepted or rejected the Encrypted Client Hello offered by the client
+///
+/// The event is only emitted by client endpoints which were configured to offer ECH.
+struct EncryptedClientHello {
+    status: EchStatus,
+}
+
 #[event("transport:packet_skipped")]
 /// Packet was skipped with a given reason
 struct PacketSkipped {
@@ -25,6 +33,7 @@ struct PacketSkipped {
 }
 
 #[event("transport:packet_sent")]
+#[level(trace)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.5
 /// Packet was sent by a connection
 struct PacketSent {
@@ -33,6 +42,7 @@ struct PacketSent {
 }
 
 #[event("transport:packet_received")]
+#[level(trace)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.6
 /// Packet was received by a connection
 struct PacketReceived {
@@ -40,6 +50,7 @@ struct PacketReceived {
 }
 
 #[event("connectivity:active_path_updated")]
+#[level(info)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.1.8
 /// Active path was updated
 struct ActivePathUpdated<'a> {
@@ -58,6 +69,7 @@ struct PathCreated<'a> {
 }
 
 #[event("transport:frame_sent")]
+#[level(trace)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.5
 // This diverges a bit from the qlog spec, which prefers to log data as part of the
 // packet events.
@@ -69,6 +81,7 @@ struct FrameSent {
 }
 
 #[event("transport:frame_received")]
+#[level(trace)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.6
 // This diverges a bit from the qlog spec, which prefers to log data as part of the
 // packet events.
@@ -90,6 +103,7 @@ struct PacketLost<'a> {
 }
 
 #[event("recovery:metrics_updated")]
+#[level(trace)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.4.2
 /// Recovery metrics updated
 struct RecoveryMetrics<'a> {
@@ -113,6 +127,7 @@ struct Congestion<'a> {
 }
 
 #[event("recovery:ack_processed")]
+#[level(trace)]
 #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
 /// Events related to ACK processing
 struct AckProcessed<'a> {
@@ -121,6 +136,7 @@ struct AckProcessed<'a> {
 }
 
 #[event("recovery:rx_ack_range_dropped")]
+#[level(warn)]
 /// Ack range for received packets was dropped due to space constraints
 ///
 /// For the purpose of processing Acks, RX packet numbers are stored as
@@ -140,6 +156,7 @@ struct RxAckRangeDropped<'a> {
 }
 
 #[event("recovery:ack_range_received")]
+#[level(trace)]
 /// ACK range was received
 struct AckRangeReceived<'a> {
     packet_header: PacketHeader,
@@ -148,6 +165,7 @@ struct AckRangeReceived<'a> {
 }
 
 #[event("recovery:ack_range_sent")]
+#[level(trace)]
 /// ACK range was sent
 struct AckRangeSent {
     packet_header: PacketHeader,
@@ -156,6 +174,7 @@ struct AckRangeSent {
 }
 
 #[event("transport:packet_dropped")]
+#[level(warn)]
 /// Packet was dropped with the given reason
 struct PacketDropped<'a> {
     reason: PacketDropReason<'a>,
@@ -175,6 +194,7 @@ struct KeySpaceDiscarded {
 }
 
 #[event("connectivity:connection_started")]
+#[level(info)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.1.2
 /// Connection started
 struct ConnectionStarted<'a> {
@@ -182,10 +202,13 @@ struct ConnectionStarted<'a> {
 }
 
 #[event("connectivity:connection_closed")]
+#[level(info)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.1.3
 /// Connection closed
 struct ConnectionClosed {
     error: crate::connection::Error,
+    /// How long the connection retains its state after closing
+    linger: ConnectionCloseLinger,
 }
 
 #[event("transport:duplicate_packet")]
@@ -203,6 +226,7 @@ struct TransportParametersReceived<'a> {
 }
 
 #[event("transport:datagram_sent")]
+#[level(trace)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.10
 /// Datagram sent by a connection
 struct DatagramSent {
@@ -216,7 +240,19 @@ struct DatagramSent {
     gso_offset: usize,
 }
 
+#[event("transport:datagram_payload_sent")]
+/// The encrypted payload of a datagram sent by a connection
+///
+/// The payload is emitted after the packet interceptor has been applied so it matches the bytes
+/// handed off to the IO provider.
+struct DatagramPayloadSent<'a> {
+    local_addr: SocketAddress<'a>,
+    remote_addr: SocketAddress<'a>,
+    payload: &'a [u8],
+}
+
 #[event("transport:datagram_received")]
+#[level(trace)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.11
 /// Datagram received by a connection
 struct DatagramReceived {
@@ -224,6 +260,7 @@ struct DatagramReceived {
 }
 
 #[event("transport:datagram_dropped")]
+#[level(warn)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.12
 /// Datagram dropped by a connection
 struct DatagramDropped {
@@ -249,15 +286,44 @@ struct EcnStateChanged<'a> {
 }
 
 #[event("connectivity:connection_migration_denied")]
+#[level(warn)]
 struct ConnectionMigrationDenied {
     reason: MigrationDenyReason,
 }
 
 #[event("connectivity:handshake_status_updated")]
+#[level(info)]
 struct HandshakeStatusUpdated {
     status: HandshakeStatus,
 }
 
+#[event("connectivity:handshake_timing")]
+/// A breakdown of when each step of the handshake happened
+///
+/// The event is emitted once the handshake is confirmed, which happens when the client receives
+/// the HANDSHAKE_DONE frame or when the server completes the handshake. Each time is relative to
+/// the creation of the connection.
+struct HandshakeTiming {
+    /// The first Initial packet was sent
+    initial_sent: Option<Duration>,
+    /// The first Initial packet was received
+    initial_received: Option<Duration>,
+    /// The handshake keys became available
+    handshake_keys_available: Option<Duration>,
+    /// The TLS handshake completed
+    ///
+    /// The TLS provider verifies the peer's certificate while processing its handshake flight,
+    /// so any time spent on verification falls between `handshake_keys_available` and this
+    /// value.
+    handshake_complete: Duration,
+    /// The HANDSHAKE_DONE frame was received by the client or queued for sending by the server
+    handshake_done: Duration,
+    /// The client's address was validated with a Retry packet
+    retry: bool,
+    /// The client received a Version Negotiation packet during the handshake
+    version_negotiation: bool,
+}
+
 #[event("connectivity:tls_exporter_ready")]
 struct TlsExporterReady<'a> {
     session: crate::event::TlsSession<'a>,
@@ -269,6 +335,8 @@ struct PathChallengeUpdated<'a> {
     path_challenge_status: PathChallengeStatus,
     path: Path<'a>,
     challenge_data: &'a [u8],
+    /// The number of PATH_CHALLENGE frames sent before the challenge was validated or abandoned
+    challenges_sent: u8,
 }
 
 #[event("tls:client_hello")]
@@ -282,11 +350,13 @@ struct TlsServerHello<'a> {
 }
 
 #[event("transport:rx_stream_progress")]
+#[level(trace)]
 struct RxStreamProgress {
     bytes: usize,
 }
 
 #[event("transport:tx_stream_progress")]
+#[level(trace)]
 struct TxStreamProgress {
     bytes: usize,
 }
@@ -297,6 +367,7 @@ pub struct KeepAliveTimerExpired {
 }
 
 #[event("connectivity:mtu_updated")]
+#[level(info)]
 /// The maximum transmission unit (MTU) for the path has changed
 struct MtuUpdated {
     path_id: u64,
@@ -314,6 +385,7 @@ struct SlowStartExited {
 }
 
 #[event("recovery:delivery_rate_sampled")]
+#[level(trace)]
 /// A new delivery rate sample has been generated
 /// Note: This event is only recorded for congestion controllers that support
 ///       bandwidth estimates, such as BBR
@@ -323,6 +395,7 @@ struct DeliveryRateSampled {
 }
 
 #[event("recovery:pacing_rate_updated")]
+#[level(trace)]
 /// The pacing rate has been updated
 struct PacingRateUpdated {
     path_id: u64,
@@ -331,6 +404,48 @@ struct PacingRateUpdated {
     pacing_gain: f32,
 }
 
+#[event("recovery:congestion_state")]
+/// A periodic snapshot of the congestion controller state
+///
+/// The snapshot is supplied by every congestion controller, so the event has the same shape
+/// regardless of which controller is selected. It is emitted at the interval configured with
+/// `Limits::with_congestion_state_interval`.
+struct CongestionState {
+    path_id: u64,
+    congestion_window: u32,
+    bytes_in_flight: u32,
+    /// The pacing rate, if the congestion controller paces packets
+    pacing_rate_bytes_per_second: Option<u64>,
+    phase: CongestionPhase,
+    /// The number of times the congestion controller has entered recovery
+    loss_epoch: u64,
+}
+
+#[event("connectivity:path_statistics")]
+/// A periodic snapshot of the statistics of a path
+///
+/// The event is emitted for each of the connection's paths at the interval configured with
+/// `Limits::with_path_statistics_interval`.
+struct PathStatistics<'a> {
+    path: Path<'a>,
+    validation: PathValidation,
+    smoothed_rtt: Duration,
+    min_rtt: Duration,
+    latest_rtt: Duration,
+    congestion_window: u32,
+    bytes_in_flight: u32,
+    /// The number of UDP payload bytes sent on the path
+    bytes_sent: u64,
+    /// The number of UDP payload bytes received on the path
+    bytes_received: u64,
+    /// The number of QUIC packets sent on the path
+    packets_sent: u64,
+    /// The number of QUIC packets sent on the path which were declared lost
+    packets_lost: u64,
+    /// The number of bytes sent on the path which were declared lost
+    bytes_lost: u64,
+}
+
 #[event("recovery:bbr_state_changed")]
 /// The BBR state has changed
 struct BbrStateChanged {
@@ -343,3 +458,73 @@ struct BbrStateChanged {
 struct DcStateChanged {
     state: DcState,
 }
+
+#[event("connectivity:connection_evicted")]
+#[level(info)]
+/// The connection was selected for eviction by the endpoint limiter
+///
+/// This is emitted before the connection is closed with the eviction error.
+struct ConnectionEvicted {
+    /// The criteria used to select the connection
+    criteria: EvictionCriteria,
+    /// The amount of time since the connection was created
+    age: Duration,
+    /// The amount of time since the connection last received a datagram from the peer
+    idle: Duration,
+    /// The total number of bytes sent and received by the connection
+    bytes: u64,
+}
+
+#[event("connectivity:socket_error_received")]
+#[level(warn)]
+/// The network reported an error for a datagram sent on the path
+///
+/// These errors are typically derived from ICMP messages, which are not authenticated.
+struct SocketErrorReceived {
+    path_id: u64,
+    error: SocketError,
+}
+
+#[event("connectivity:local_address_lost")]
+#[level(warn)]
+/// The local address of the path can no longer be used to send datagrams
+///
+/// This typically happens when the interface the address was assigned to goes down, e.g. when
+/// switching networks. The path stops pinning the address so the operating system can select a
+/// new one, which the peer observes as a migration. The event is only emitted if the peer
+/// provided a spare connection ID to use from the new address.
+struct LocalAddressLost<'a> {
+    /// The path, including the local address which was lost
+    path: Path<'a>,
+}
+
+#[event("transport:transmit_share")]
+#[level(trace)]
+/// The connection transmitted its share of the endpoint's transmission queue
+///
+/// Connections take turns transmitting in a deficit round robin, so a single connection can't
+/// consume the entire queue.
+struct TransmitShare {
+    /// The number of datagrams transmitted by the connection in the round
+    datagrams: usize,
+    /// The number of bytes transmitted by the connection in the round
+    bytes: usize,
+    /// The remaining transmission credits, which is negative if the final datagram overshot the
+    /// connection's quantum
+    deficit: i64,
+    /// Set to true if the connection exhausted its quantum before it finished transmitting
+    is_limited: bool,
+    /// The class the connection was scheduled in
+    priority_class: PriorityClass,
+}
+
+#[event("transport:version_negotiation_received")]
+/// The client received a Version Negotiation packet from the server
+struct VersionNegotiationReceived<'a> {
+    /// The versions supported by the server
+    server_versions: &'a [u32],
+    /// The version chosen by the client for the connection attempt
+    chosen_version: u32,
+    /// The action taken by the client
+    outcome: VersionNegotiationOutcome,
+}
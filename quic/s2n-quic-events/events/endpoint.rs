@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #[event("transport::version_information")]
+#[level(info)]
 #[subject(endpoint)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.1
 //# QUIC endpoints each have their own list of of QUIC versions they
@@ -14,6 +15,7 @@ struct VersionInformation<'a> {
 }
 
 #[event("transport:packet_sent")]
+#[level(trace)]
 #[subject(endpoint)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.5
 /// Packet was sent by the endpoint
@@ -22,6 +24,7 @@ struct EndpointPacketSent {
 }
 
 #[event("transport:packet_received")]
+#[level(trace)]
 #[subject(endpoint)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.6
 /// Packet was received by the endpoint
@@ -30,6 +33,7 @@ struct EndpointPacketReceived {
 }
 
 #[event("transport:datagram_sent")]
+#[level(trace)]
 #[subject(endpoint)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.10
 /// Datagram sent by the endpoint
@@ -45,6 +49,7 @@ struct EndpointDatagramSent {
 }
 
 #[event("transport:datagram_received")]
+#[level(trace)]
 #[subject(endpoint)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.11
 /// Datagram received by the endpoint
@@ -53,6 +58,7 @@ struct EndpointDatagramReceived {
 }
 
 #[event("transport:datagram_dropped")]
+#[level(warn)]
 #[subject(endpoint)]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.12
 /// Datagram dropped by the endpoint
@@ -61,8 +67,37 @@ struct EndpointDatagramDropped {
     reason: DatagramDropReason,
 }
 
+#[event("transport:address_token_reused")]
+#[subject(endpoint)]
+/// An address token was presented which the endpoint has already accepted
+///
+/// Repeated occurrences may indicate that a peer is probing for token replay.
+///
+/// The event is only emitted for tokens which the address token provider reports as reused.
+/// The default provider only issues tokens in Retry packets, so with it the `source` is always
+/// `RetryPacket`.
+struct EndpointAddressTokenReused<'a> {
+    remote_address: SocketAddress<'a>,
+    source: AddressTokenSource,
+}
+
 #[event("transport:connection_attempt_failed")]
+#[level(warn)]
 #[subject(endpoint)]
 struct EndpointConnectionAttemptFailed {
     error: crate::connection::Error,
 }
+
+#[event("transport:connection_attempt_retried")]
+#[subject(endpoint)]
+/// A client connection attempt failed and another attempt has been scheduled
+struct EndpointConnectionAttemptRetried<'a> {
+    /// The number of the scheduled attempt, starting at `2` for the first retry
+    attempt: u32,
+    /// The address the scheduled attempt will connect to
+    remote_address: SocketAddress<'a>,
+    /// The amount of time until the scheduled attempt is made
+    backoff: Duration,
+    /// The error which caused the previous attempt to fail
+    error: crate::connection::Error,
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #[event("platform:tx")]
+#[level(trace)]
 #[subject(endpoint)]
 /// Emitted when the platform sends at least one packet
 struct PlatformTx {
@@ -10,6 +11,7 @@ struct PlatformTx {
 }
 
 #[event("platform:tx_error")]
+#[level(error)]
 #[subject(endpoint)]
 /// Emitted when the platform returns an error while sending datagrams
 struct PlatformTxError {
@@ -25,6 +27,7 @@ impl From<PlatformTxError> for std::io::Error {
 }
 
 #[event("platform:rx")]
+#[level(trace)]
 #[subject(endpoint)]
 /// Emitted when the platform receives at least one packet
 struct PlatformRx {
@@ -32,7 +35,19 @@ struct PlatformRx {
     count: usize,
 }
 
+#[event("platform:rx_overflow")]
+#[level(warn)]
+#[subject(endpoint)]
+/// Emitted when the platform dropped received datagrams because the receive queue overflowed
+///
+/// This indicates the endpoint isn't keeping up with the rate of incoming datagrams.
+struct PlatformRxOverflow {
+    /// The number of datagrams dropped since the last event
+    dropped: usize,
+}
+
 #[event("platform:rx_error")]
+#[level(error)]
 #[subject(endpoint)]
 /// Emitted when the platform returns an error while receiving datagrams
 struct PlatformRxError {
@@ -48,6 +63,7 @@ impl From<PlatformRxError> for std::io::Error {
 }
 
 #[event("platform:feature_configured")]
+#[level(info)]
 #[subject(endpoint)]
 /// Emitted when a platform feature is configured
 struct PlatformFeatureConfigured {
@@ -66,6 +82,12 @@ enum PlatformFeatureConfiguration {
     Gro { enabled: bool },
     /// Emitted when ECN support is configured
     Ecn { enabled: bool },
+    /// Emitted when receiving ICMP errors from the socket error queue is configured
+    IcmpErrors { enabled: bool },
+    /// Emitted when reporting of datagrams dropped from the receive queue is configured
+    RxQueueOverflow { enabled: bool },
+    /// Emitted when zero-copy transmission (MSG_ZEROCOPY) is configured
+    Zerocopy { enabled: bool },
     /// Emitted when the base maximum transmission unit is configured
     BaseMtu { mtu: u16 },
     /// Emitted when the initial maximum transmission unit is configured
@@ -75,6 +97,7 @@ enum PlatformFeatureConfiguration {
 }
 
 #[event("platform:event_loop_wakeup")]
+#[level(trace)]
 #[subject(endpoint)]
 struct PlatformEventLoopWakeup {
     timeout_expired: bool,
@@ -84,6 +107,7 @@ struct PlatformEventLoopWakeup {
 }
 
 #[event("platform:event_loop_sleep")]
+#[level(trace)]
 #[subject(endpoint)]
 struct PlatformEventLoopSleep {
     /// The next time at which the event loop will wake
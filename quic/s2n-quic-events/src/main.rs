@@ -16,6 +16,8 @@ struct Output {
     pub endpoint_publisher_subscriber: TokenStream,
     pub connection_publisher: TokenStream,
     pub connection_publisher_subscriber: TokenStream,
+    pub attached_subscriber: TokenStream,
+    pub attached_subscriber_impl: TokenStream,
     pub tuple_subscriber: TokenStream,
     pub tracing_subscriber: TokenStream,
     pub builders: TokenStream,
@@ -36,6 +38,8 @@ impl ToTokens for Output {
             endpoint_publisher_subscriber,
             connection_publisher,
             connection_publisher_subscriber,
+            attached_subscriber,
+            attached_subscriber_impl,
             tuple_subscriber,
             tracing_subscriber,
             builders,
@@ -300,6 +304,19 @@ impl ToTokens for Output {
                         supervisor::Outcome::default()
                     }
 
+                    /// The rate at which events of type `E` are published to the subscriber
+                    ///
+                    /// The rate is queried before the event is constructed, which makes events
+                    /// that aren't published nearly free. Events can be filtered by severity with
+                    /// [`SampleRate::at_level`] or reduced to a fraction with [`SampleRate::one_in`].
+                    ///
+                    /// If multiple `event::Subscriber`s are composed together, each `event::Subscriber`
+                    /// is sampled at its own rate.
+                    #[inline]
+                    fn sample_rate<E: Event>(&self) -> SampleRate {
+                        SampleRate::ALL
+                    }
+
                     #subscriber
 
                     /// Called for each event that relates to the endpoint and all connections
@@ -366,18 +383,46 @@ impl ToTokens for Output {
                         }
                     }
 
+                    /// Events are published to the tuple at the shared rate of both subscribers. If
+                    /// the rates differ, all events are published to the tuple and each subscriber
+                    /// is sampled separately in the event-specific methods.
+                    #[inline]
+                    fn sample_rate<E: Event>(&self) -> SampleRate {
+                        let rate_a = self.0.sample_rate::<E>();
+                        let rate_b = self.1.sample_rate::<E>();
+                        if rate_a == rate_b || !rate_b.is_enabled() {
+                            rate_a
+                        } else if !rate_a.is_enabled() {
+                            rate_b
+                        } else {
+                            SampleRate::ALL
+                        }
+                    }
+
                     #tuple_subscriber
 
                     #[inline]
                     fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
-                        self.0.on_event(meta, event);
-                        self.1.on_event(meta, event);
+                        let rate = self.sample_rate::<E>();
+                        // subscribers sampled at a different rate are notified in the event-specific method
+                        if self.0.sample_rate::<E>() == rate {
+                            self.0.on_event(meta, event);
+                        }
+                        if self.1.sample_rate::<E>() == rate {
+                            self.1.on_event(meta, event);
+                        }
                     }
 
                     #[inline]
                     fn on_connection_event<E: Event>(&mut self, context: &mut Self::ConnectionContext, meta: &ConnectionMeta, event: &E) {
-                        self.0.on_connection_event(&mut context.0, meta, event);
-                        self.1.on_connection_event(&mut context.1, meta, event);
+                        let rate = self.sample_rate::<E>();
+                        // subscribers sampled at a different rate are notified in the event-specific method
+                        if self.0.sample_rate::<E>() == rate {
+                            self.0.on_connection_event(&mut context.0, meta, event);
+                        }
+                        if self.1.sample_rate::<E>() == rate {
+                            self.1.on_connection_event(&mut context.1, meta, event);
+                        }
                     }
 
                     #[inline]
@@ -395,6 +440,55 @@ impl ToTokens for Output {
                     }
                 }
 
+                /// A [`Subscriber`] for the events of a single connection
+                ///
+                /// Unlike the endpoint-wide [`Subscriber`], an `AttachedSubscriber` is attached to
+                /// an individual connection and receives its events in addition to the endpoint's
+                /// subscriber. This is useful for enabling verbose debugging for a single peer.
+                ///
+                /// Any [`Subscriber`] can be attached to a connection with [`Attached`].
+                pub trait AttachedSubscriber: 'static + Send {
+                    #attached_subscriber
+                }
+
+                /// Attaches a [`Subscriber`] to a single connection
+                ///
+                /// The subscriber's connection context is created when the first event of the
+                /// connection is published.
+                pub struct Attached<Sub: Subscriber> {
+                    subscriber: Sub,
+                    context: Option<Sub::ConnectionContext>,
+                }
+
+                impl<Sub: Subscriber> fmt::Debug for Attached<Sub> {
+                    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.debug_struct("Attached").finish_non_exhaustive()
+                    }
+                }
+
+                impl<Sub: Subscriber> Attached<Sub> {
+                    #[inline]
+                    pub fn new(subscriber: Sub) -> Self {
+                        Self {
+                            subscriber,
+                            context: None,
+                        }
+                    }
+
+                    #[inline]
+                    fn context(&mut self, meta: &ConnectionMeta) -> (&mut Sub, &mut Sub::ConnectionContext) {
+                        let subscriber = &mut self.subscriber;
+                        let context = self.context.get_or_insert_with(|| {
+                            subscriber.create_connection_context(meta, &builder::ConnectionInfo {}.into_event())
+                        });
+                        (subscriber, context)
+                    }
+                }
+
+                impl<Sub: Subscriber> AttachedSubscriber for Attached<Sub> {
+                    #attached_subscriber_impl
+                }
+
                 pub trait EndpointPublisher {
                     #endpoint_publisher
 
@@ -456,6 +550,7 @@ impl ToTokens for Output {
                     quic_version: u32,
                     subscriber: &'a mut Sub,
                     context: &'a mut Sub::ConnectionContext,
+                    attached: Option<&'a mut dyn AttachedSubscriber>,
                 }
 
                 impl<'a, Sub: Subscriber> fmt::Debug for ConnectionPublisherSubscriber<'a, Sub> {
@@ -480,8 +575,16 @@ impl ToTokens for Output {
                             quic_version,
                             subscriber,
                             context,
+                            attached: None,
                         }
                     }
+
+                    /// Also publishes events to the connection's [`AttachedSubscriber`], if any
+                    #[inline]
+                    pub fn with_attached(mut self, attached: Option<&'a mut dyn AttachedSubscriber>) -> Self {
+                        self.attached = attached;
+                        self
+                    }
                 }
 
                 impl<'a, Sub: Subscriber> ConnectionPublisher for ConnectionPublisherSubscriber<'a, Sub> {
@@ -131,10 +131,12 @@ impl Struct {
         ));
 
         if let Some(event_name) = attrs.event_name.as_ref() {
+            let level = &attrs.level;
             output.api.extend(quote!(
                 #allow_deprecated
                 impl #generics Event for #ident #generics {
                     const NAME: &'static str = #event_name;
+                    const LEVEL: Level = #level;
                 }
             ));
 
@@ -173,8 +175,24 @@ impl Struct {
                         #[inline]
                         #allow_deprecated
                         fn #function(&mut self, meta: &EndpointMeta, event: &#ident) {
-                            (self.0).#function(meta, event);
-                            (self.1).#function(meta, event);
+                            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+                            let rate = self.sample_rate::<#ident>();
+
+                            let rate_a = self.0.sample_rate::<#ident>();
+                            if rate_a == rate {
+                                (self.0).#function(meta, event);
+                            } else if rate_a.sample(&SEQUENCE) {
+                                (self.0).#function(meta, event);
+                                (self.0).on_event(meta, event);
+                            }
+
+                            let rate_b = self.1.sample_rate::<#ident>();
+                            if rate_b == rate {
+                                (self.1).#function(meta, event);
+                            } else if rate_b.sample(&SEQUENCE) {
+                                (self.1).#function(meta, event);
+                                (self.1).on_event(meta, event);
+                            }
                         }
                     ));
 
@@ -204,6 +222,10 @@ impl Struct {
                         #[inline]
                         #allow_deprecated
                         fn #function(&mut self, event: builder::#ident) {
+                            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+                            if !self.subscriber.sample_rate::<api::#ident>().sample(&SEQUENCE) {
+                                return;
+                            }
                             let event = event.into_event();
                             self.subscriber.#function(&self.meta, &event);
                             self.subscriber.on_event(&self.meta, &event);
@@ -244,8 +266,26 @@ impl Struct {
                         #[inline]
                         #allow_deprecated
                         fn #function(&mut self, context: &mut Self::ConnectionContext, meta: &ConnectionMeta, event: &#ident) {
-                            (self.0).#function(&mut context.0, meta, event);
-                            (self.1).#function(&mut context.1, meta, event);
+                            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+                            let rate = self.sample_rate::<#ident>();
+
+                            let rate_a = self.0.sample_rate::<#ident>();
+                            if rate_a == rate {
+                                (self.0).#function(&mut context.0, meta, event);
+                            } else if rate_a.sample(&SEQUENCE) {
+                                (self.0).#function(&mut context.0, meta, event);
+                                (self.0).on_connection_event(&mut context.0, meta, event);
+                                (self.0).on_event(meta, event);
+                            }
+
+                            let rate_b = self.1.sample_rate::<#ident>();
+                            if rate_b == rate {
+                                (self.1).#function(&mut context.1, meta, event);
+                            } else if rate_b.sample(&SEQUENCE) {
+                                (self.1).#function(&mut context.1, meta, event);
+                                (self.1).on_connection_event(&mut context.1, meta, event);
+                                (self.1).on_event(meta, event);
+                            }
                         }
                     ));
 
@@ -268,10 +308,46 @@ impl Struct {
                         #[inline]
                         #allow_deprecated
                         fn #function(&mut self, event: builder::#ident) {
+                            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+                            let is_sampled = self.subscriber.sample_rate::<api::#ident>().sample(&SEQUENCE);
+                            if !is_sampled && self.attached.is_none() {
+                                return;
+                            }
                             let event = event.into_event();
-                            self.subscriber.#function(self.context, &self.meta, &event);
-                            self.subscriber.on_connection_event(self.context, &self.meta, &event);
-                            self.subscriber.on_event(&self.meta, &event);
+                            if is_sampled {
+                                self.subscriber.#function(self.context, &self.meta, &event);
+                                self.subscriber.on_connection_event(self.context, &self.meta, &event);
+                                self.subscriber.on_event(&self.meta, &event);
+                            }
+                            if let Some(attached) = self.attached.as_mut() {
+                                attached.#function(&self.meta, &event);
+                            }
+                        }
+                    ));
+
+                    output.attached_subscriber.extend(quote!(
+                        #[doc = #subscriber_doc]
+                        #[inline]
+                        #deprecated
+                        #allow_deprecated
+                        fn #function(&mut self, meta: &ConnectionMeta, event: &#ident) {
+                            let _ = meta;
+                            let _ = event;
+                        }
+                    ));
+
+                    output.attached_subscriber_impl.extend(quote!(
+                        #[inline]
+                        #allow_deprecated
+                        fn #function(&mut self, meta: &ConnectionMeta, event: &#ident) {
+                            static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+                            if !self.subscriber.sample_rate::<#ident>().sample(&SEQUENCE) {
+                                return;
+                            }
+                            let (subscriber, context) = self.context(meta);
+                            subscriber.#function(context, meta, event);
+                            subscriber.on_connection_event(context, meta, event);
+                            subscriber.on_event(meta, event);
                         }
                     ));
 
@@ -394,6 +470,7 @@ struct ContainerAttrs {
     deprecated: TokenStream,
     allow_deprecated: TokenStream,
     subject: Subject,
+    level: Level,
     exhaustive: bool,
     derive: bool,
     derive_attrs: TokenStream,
@@ -411,6 +488,7 @@ impl ContainerAttrs {
             allow_deprecated: TokenStream::default(),
             // most event subjects relate to actual connections so make that the default
             subject: Subject::Connection,
+            level: Level::Debug,
             // default to #[non_exhaustive]
             exhaustive: false,
             derive: true,
@@ -432,6 +510,8 @@ impl ContainerAttrs {
                 }
             } else if path.is_ident("subject") {
                 v.subject = attr.parse_args().unwrap();
+            } else if path.is_ident("level") {
+                v.level = attr.parse_args().unwrap();
             } else if path.is_ident("exhaustive") {
                 v.exhaustive = true;
             } else if path.is_ident("derive") {
@@ -471,6 +551,45 @@ impl Parse for Subject {
     }
 }
 
+#[derive(Debug)]
+enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Parse for Level {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let name: Ident = input.parse()?;
+        match name.to_string().as_str() {
+            "trace" => Ok(Self::Trace),
+            "debug" => Ok(Self::Debug),
+            "info" => Ok(Self::Info),
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            name => Err(syn::parse::Error::new(
+                input.span(),
+                format!("invalid event level: {name}, expected trace, debug, info, warn or error"),
+            )),
+        }
+    }
+}
+
+impl ToTokens for Level {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let variant = match self {
+            Self::Trace => quote!(Trace),
+            Self::Debug => quote!(Debug),
+            Self::Info => quote!(Info),
+            Self::Warn => quote!(Warn),
+            Self::Error => quote!(Error),
+        };
+        tokens.extend(quote!(Level::#variant));
+    }
+}
+
 #[derive(Debug)]
 struct Field {
     attrs: FieldAttrs,
@@ -0,0 +1,131 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+#include <sys/socket.h>
+#include <sys/types.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Functions returning an `int` return S2N_QUIC_SUCCESS or S2N_QUIC_FAILURE. Functions returning
+ * a pointer return NULL on failure. In both cases, `s2n_quic_last_error` describes the error.
+ *
+ * Functions which wait on the network block the calling thread.
+ *
+ * Endpoints are driven by a multi-threaded runtime which the library starts in the background the
+ * first time an endpoint is created. `s2n_quic_runtime_init` configures it ahead of time.
+ */
+#define S2N_QUIC_SUCCESS 0
+#define S2N_QUIC_FAILURE -1
+
+typedef struct s2n_quic_server s2n_quic_server;
+typedef struct s2n_quic_client s2n_quic_client;
+typedef struct s2n_quic_connection s2n_quic_connection;
+typedef struct s2n_quic_stream s2n_quic_stream;
+typedef struct s2n_quic_send_stream s2n_quic_send_stream;
+typedef struct s2n_quic_receive_stream s2n_quic_receive_stream;
+typedef struct s2n_quic_io s2n_quic_io;
+
+typedef enum {
+    /* The connection was created */
+    S2N_QUIC_EVENT_CONNECTION_STARTED = 0,
+    /* The handshake completed */
+    S2N_QUIC_EVENT_HANDSHAKE_COMPLETE = 1,
+    /* The connection was closed */
+    S2N_QUIC_EVENT_CONNECTION_CLOSED = 2,
+} s2n_quic_event_type;
+
+/*
+ * Called with the context passed when creating the endpoint, the type of event, and the ID of
+ * the connection.
+ *
+ * Callbacks are invoked on the library's threads. The context must be safe to use from those
+ * threads and callbacks must not call any blocking functions.
+ */
+typedef void (*s2n_quic_event_callback)(void *context, s2n_quic_event_type event, uint64_t connection_id);
+
+/*
+ * Called once the application should call `s2n_quic_io_poll_wakeups` again.
+ *
+ * Callbacks may be invoked on any thread and must not call any blocking functions.
+ */
+typedef void (*s2n_quic_wakeup_callback)(void *context);
+
+/* Returns a description of the last error on the calling thread, valid until the next call */
+const char *s2n_quic_last_error(void);
+
+/*
+ * Sets the number of threads used by the runtime, with 0 selecting one per CPU core. Fails if an
+ * endpoint has already been created.
+ */
+int s2n_quic_runtime_init(size_t worker_threads);
+
+/* Servers */
+s2n_quic_server *s2n_quic_server_new(const char *address, const char *cert_pem, const char *key_pem,
+                                     s2n_quic_event_callback callback, void *context);
+uint16_t s2n_quic_server_local_port(s2n_quic_server *server);
+s2n_quic_connection *s2n_quic_server_accept(s2n_quic_server *server);
+void s2n_quic_server_free(s2n_quic_server *server);
+s2n_quic_server *s2n_quic_server_new_with_io(s2n_quic_io *io, const char *cert_pem, const char *key_pem,
+                                             s2n_quic_event_callback callback, void *context);
+
+/* Clients */
+s2n_quic_client *s2n_quic_client_new(const char *address, const char *ca_pem, s2n_quic_event_callback callback,
+                                     void *context);
+s2n_quic_connection *s2n_quic_client_connect(s2n_quic_client *client, const char *address, const char *server_name);
+void s2n_quic_client_free(s2n_quic_client *client);
+s2n_quic_client *s2n_quic_client_new_with_io(s2n_quic_io *io, const char *ca_pem, s2n_quic_event_callback callback,
+                                             void *context);
+
+/* Connections */
+uint64_t s2n_quic_connection_id(s2n_quic_connection *connection);
+s2n_quic_stream *s2n_quic_connection_open_bidirectional_stream(s2n_quic_connection *connection);
+s2n_quic_stream *s2n_quic_connection_accept_bidirectional_stream(s2n_quic_connection *connection);
+s2n_quic_send_stream *s2n_quic_connection_open_send_stream(s2n_quic_connection *connection);
+s2n_quic_receive_stream *s2n_quic_connection_accept_receive_stream(s2n_quic_connection *connection);
+int s2n_quic_connection_close(s2n_quic_connection *connection, uint64_t error_code);
+void s2n_quic_connection_free(s2n_quic_connection *connection);
+
+/* Streams */
+ssize_t s2n_quic_stream_send(s2n_quic_stream *stream, const uint8_t *data, size_t len);
+ssize_t s2n_quic_stream_receive(s2n_quic_stream *stream, uint8_t *data, size_t len);
+int s2n_quic_stream_finish(s2n_quic_stream *stream);
+void s2n_quic_stream_free(s2n_quic_stream *stream);
+
+/* Unidirectional streams */
+ssize_t s2n_quic_send_stream_send(s2n_quic_send_stream *stream, const uint8_t *data, size_t len);
+int s2n_quic_send_stream_finish(s2n_quic_send_stream *stream);
+void s2n_quic_send_stream_free(s2n_quic_send_stream *stream);
+ssize_t s2n_quic_receive_stream_receive(s2n_quic_receive_stream *stream, uint8_t *data, size_t len);
+void s2n_quic_receive_stream_free(s2n_quic_receive_stream *stream);
+
+/*
+ * Application-driven IO
+ *
+ * Endpoints created with an IO handle don't open a socket. Instead, the application passes it
+ * received datagrams, sends the datagrams returned by `s2n_quic_io_poll_transmit`, and calls
+ * back into it once `s2n_quic_io_timeout` milliseconds have elapsed or the wakeup callback fires.
+ * Blocking functions on the endpoint's connections and streams must not be called from the thread
+ * driving the IO handle. Freeing the IO handle shuts down the endpoint.
+ */
+s2n_quic_io *s2n_quic_io_new(const char *local_address);
+size_t s2n_quic_io_max_mtu(s2n_quic_io *io);
+int s2n_quic_io_receive(s2n_quic_io *io, const struct sockaddr *remote_address, socklen_t remote_address_len,
+                        uint8_t *payload, size_t len);
+ssize_t s2n_quic_io_poll_transmit(s2n_quic_io *io, uint8_t *buffer, size_t len,
+                                  struct sockaddr_storage *remote_address, socklen_t *remote_address_len);
+int64_t s2n_quic_io_timeout(s2n_quic_io *io);
+ssize_t s2n_quic_io_poll_wakeups(s2n_quic_io *io, s2n_quic_wakeup_callback callback, void *context);
+void s2n_quic_io_free(s2n_quic_io *io);
+
+#ifdef __cplusplus
+}
+#endif
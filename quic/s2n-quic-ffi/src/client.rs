@@ -0,0 +1,139 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection::s2n_quic_connection,
+    error::{call, free, handle_arg, str_arg, Result},
+    event::{s2n_quic_event_callback, Subscriber},
+    io::s2n_quic_io,
+    runtime,
+};
+use core::{
+    ffi::{c_char, c_void},
+    ptr,
+};
+use s2n_quic::{client::Connect, Client};
+use std::net::SocketAddr;
+
+/// A QUIC client endpoint
+pub struct s2n_quic_client {
+    client: Client,
+}
+
+/// Creates a client bound to `address` (e.g. `"0.0.0.0:0"`)
+///
+/// `ca_pem` contains the PEM-encoded certificate used to authenticate servers.
+/// `callback` may be `NULL` if the application isn't interested in events.
+///
+/// # Safety
+///
+/// `address` and `ca_pem` must each be `NULL` or point to a NUL-terminated string.
+///
+/// `context` is passed to `callback` from the library's threads, so it must remain valid and be
+/// safe to use from any thread for as long as the endpoint exists.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_client_new(
+    address: *const c_char,
+    ca_pem: *const c_char,
+    callback: Option<s2n_quic_event_callback>,
+    context: *mut c_void,
+) -> *mut s2n_quic_client {
+    call(ptr::null_mut(), || {
+        let address = str_arg(address, "address")?;
+        let ca_pem = str_arg(ca_pem, "ca_pem")?;
+
+        let client = runtime::enter(|| -> Result<_> {
+            let endpoint = Client::builder()
+                .with_tls(ca_pem)?
+                .with_io(address)?
+                .with_event(Subscriber::new(callback, context))?
+                .start()?;
+            Ok(endpoint)
+        })?;
+
+        Ok(Box::into_raw(Box::new(s2n_quic_client { client })))
+    })
+}
+
+/// Creates a client which is driven by the application through `io`
+///
+/// `ca_pem` contains the PEM-encoded certificate used to authenticate servers.
+/// `callback` may be `NULL` if the application isn't interested in events. Each IO handle can only
+/// be used by a single endpoint.
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used by another thread for the duration of the call.
+///
+/// `ca_pem` must be `NULL` or point to a NUL-terminated string.
+///
+/// `context` is passed to `callback` from the library's threads, so it must remain valid and be
+/// safe to use from any thread for as long as the endpoint exists.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_client_new_with_io(
+    io: *mut s2n_quic_io,
+    ca_pem: *const c_char,
+    callback: Option<s2n_quic_event_callback>,
+    context: *mut c_void,
+) -> *mut s2n_quic_client {
+    call(ptr::null_mut(), || {
+        let io = handle_arg(io, "io")?;
+        let ca_pem = str_arg(ca_pem, "ca_pem")?;
+        let provider = io.take_provider()?;
+
+        let client = runtime::enter(|| -> Result<_> {
+            let endpoint = Client::builder()
+                .with_tls(ca_pem)?
+                .with_io(provider)?
+                .with_event(Subscriber::new(callback, context))?
+                .start()?;
+            Ok(endpoint)
+        })?;
+
+        Ok(Box::into_raw(Box::new(s2n_quic_client { client })))
+    })
+}
+
+/// Blocks until a connection to the server at `address` is established
+///
+/// `server_name` is used for SNI and to authenticate the server's certificate.
+///
+/// # Safety
+///
+/// `client` must be `NULL` or a pointer returned by `s2n_quic_client_new` or
+/// `s2n_quic_client_new_with_io` which hasn't been freed. It must not be used by another thread for
+/// the duration of the call.
+///
+/// `address` and `server_name` must each be `NULL` or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_client_connect(
+    client: *mut s2n_quic_client,
+    address: *const c_char,
+    server_name: *const c_char,
+) -> *mut s2n_quic_connection {
+    call(ptr::null_mut(), || {
+        let client = handle_arg(client, "client")?;
+        let address: SocketAddr = str_arg(address, "address")?.parse()?;
+        let server_name = str_arg(server_name, "server_name")?;
+
+        let connect = Connect::new(address).with_server_name(server_name);
+        let connection = runtime::block_on(client.client.connect(connect))?;
+
+        Ok(s2n_quic_connection::new(connection))
+    })
+}
+
+/// Closes the client and frees its resources
+///
+/// Connections which were previously established remain valid and must be freed separately.
+///
+/// # Safety
+///
+/// `client` must be `NULL` or a pointer returned by `s2n_quic_client_new` or
+/// `s2n_quic_client_new_with_io` which hasn't been freed. It must not be used again after this
+/// call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_client_free(client: *mut s2n_quic_client) {
+    free(client)
+}
@@ -0,0 +1,148 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::{call, free, handle_arg},
+    runtime,
+    stream::{s2n_quic_receive_stream, s2n_quic_send_stream, s2n_quic_stream},
+    S2N_QUIC_FAILURE, S2N_QUIC_SUCCESS,
+};
+use core::{ffi::c_int, ptr};
+use s2n_quic::{application, Connection};
+
+/// An established QUIC connection
+pub struct s2n_quic_connection {
+    connection: Connection,
+}
+
+impl s2n_quic_connection {
+    pub(crate) fn new(connection: Connection) -> *mut Self {
+        Box::into_raw(Box::new(Self { connection }))
+    }
+}
+
+/// Returns the ID of the connection, matching the ID passed to event callbacks
+///
+/// # Safety
+///
+/// `connection` must be `NULL` or a pointer returned by `s2n_quic_server_accept` or
+/// `s2n_quic_client_connect` which hasn't been freed. It must not be used by another thread for the
+/// duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_connection_id(connection: *mut s2n_quic_connection) -> u64 {
+    call(0, || {
+        let connection = handle_arg(connection, "connection")?;
+        Ok(connection.connection.id())
+    })
+}
+
+/// Blocks until a new bidirectional stream is opened
+///
+/// # Safety
+///
+/// `connection` must be `NULL` or a pointer returned by `s2n_quic_server_accept` or
+/// `s2n_quic_client_connect` which hasn't been freed. It must not be used by another thread for the
+/// duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_connection_open_bidirectional_stream(
+    connection: *mut s2n_quic_connection,
+) -> *mut s2n_quic_stream {
+    call(ptr::null_mut(), || {
+        let connection = handle_arg(connection, "connection")?;
+        let stream = runtime::block_on(connection.connection.open_bidirectional_stream())?;
+        Ok(s2n_quic_stream::new(stream))
+    })
+}
+
+/// Blocks until the peer opens a bidirectional stream
+///
+/// Returns `NULL` once the connection has been closed.
+///
+/// # Safety
+///
+/// `connection` must be `NULL` or a pointer returned by `s2n_quic_server_accept` or
+/// `s2n_quic_client_connect` which hasn't been freed. It must not be used by another thread for the
+/// duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_connection_accept_bidirectional_stream(
+    connection: *mut s2n_quic_connection,
+) -> *mut s2n_quic_stream {
+    call(ptr::null_mut(), || {
+        let connection = handle_arg(connection, "connection")?;
+        let stream = runtime::block_on(connection.connection.accept_bidirectional_stream())?
+            .ok_or("the connection is closed")?;
+        Ok(s2n_quic_stream::new(stream))
+    })
+}
+
+/// Blocks until a new unidirectional stream is opened
+///
+/// # Safety
+///
+/// `connection` must be `NULL` or a pointer returned by `s2n_quic_server_accept` or
+/// `s2n_quic_client_connect` which hasn't been freed. It must not be used by another thread for the
+/// duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_connection_open_send_stream(
+    connection: *mut s2n_quic_connection,
+) -> *mut s2n_quic_send_stream {
+    call(ptr::null_mut(), || {
+        let connection = handle_arg(connection, "connection")?;
+        let stream = runtime::block_on(connection.connection.open_send_stream())?;
+        Ok(s2n_quic_send_stream::new(stream))
+    })
+}
+
+/// Blocks until the peer opens a unidirectional stream
+///
+/// Returns `NULL` once the connection has been closed.
+///
+/// # Safety
+///
+/// `connection` must be `NULL` or a pointer returned by `s2n_quic_server_accept` or
+/// `s2n_quic_client_connect` which hasn't been freed. It must not be used by another thread for the
+/// duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_connection_accept_receive_stream(
+    connection: *mut s2n_quic_connection,
+) -> *mut s2n_quic_receive_stream {
+    call(ptr::null_mut(), || {
+        let connection = handle_arg(connection, "connection")?;
+        let stream = runtime::block_on(connection.connection.accept_receive_stream())?
+            .ok_or("the connection is closed")?;
+        Ok(s2n_quic_receive_stream::new(stream))
+    })
+}
+
+/// Closes the connection with the given application error code
+///
+/// # Safety
+///
+/// `connection` must be `NULL` or a pointer returned by `s2n_quic_server_accept` or
+/// `s2n_quic_client_connect` which hasn't been freed. It must not be used by another thread for the
+/// duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_connection_close(
+    connection: *mut s2n_quic_connection,
+    error_code: u64,
+) -> c_int {
+    call(S2N_QUIC_FAILURE, || {
+        let connection = handle_arg(connection, "connection")?;
+        let error_code = application::Error::new(error_code)?;
+        connection.connection.close(error_code);
+        Ok(S2N_QUIC_SUCCESS)
+    })
+}
+
+/// Frees the connection
+///
+/// Streams which were previously opened or accepted remain valid and must be freed separately.
+///
+/// # Safety
+///
+/// `connection` must be `NULL` or a pointer returned by `s2n_quic_server_accept` or
+/// `s2n_quic_client_connect` which hasn't been freed. It must not be used again after this call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_connection_free(connection: *mut s2n_quic_connection) {
+    free(connection)
+}
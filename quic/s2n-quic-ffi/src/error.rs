@@ -0,0 +1,80 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::{ffi::c_char, fmt};
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+pub(crate) type Error = Box<dyn std::error::Error + Send + Sync>;
+pub(crate) type Result<T, E = Error> = core::result::Result<T, E>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Returns a description of the last error which occurred on the calling thread
+///
+/// The returned string is valid until the next call into the library on the same thread.
+#[no_mangle]
+pub extern "C" fn s2n_quic_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ptr())
+}
+
+fn set_last_error(error: impl fmt::Display) {
+    // interior NUL bytes can't be represented in a C string
+    let message = error.to_string().replace('\0', " ");
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = message);
+}
+
+/// Calls `f`, recording any errors and returning `on_error` in their place
+///
+/// Panics are caught to avoid unwinding across the FFI boundary.
+pub(crate) fn call<T>(on_error: T, f: impl FnOnce() -> Result<T>) -> T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(error)) => {
+            set_last_error(error);
+            on_error
+        }
+        Err(_) => {
+            set_last_error("the library panicked");
+            on_error
+        }
+    }
+}
+
+/// Frees a handle which was returned to the application as a boxed pointer
+///
+/// Panics while dropping the handle are caught like in [`call`].
+pub(crate) unsafe fn free<T>(value: *mut T) {
+    call((), || {
+        if !value.is_null() {
+            drop(Box::from_raw(value));
+        }
+        Ok(())
+    })
+}
+
+/// Converts a NUL-terminated C string argument into a `&str`
+pub(crate) unsafe fn str_arg<'a>(value: *const c_char, name: &str) -> Result<&'a str> {
+    if value.is_null() {
+        return Err(format!("`{name}` must not be NULL").into());
+    }
+
+    let value = CStr::from_ptr(value)
+        .to_str()
+        .map_err(|_| format!("`{name}` must be valid UTF-8"))?;
+
+    Ok(value)
+}
+
+/// Converts a handle argument into a reference
+pub(crate) unsafe fn handle_arg<'a, T>(value: *mut T, name: &str) -> Result<&'a mut T> {
+    value
+        .as_mut()
+        .ok_or_else(|| format!("`{name}` must not be NULL").into())
+}
@@ -0,0 +1,88 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::ffi::c_void;
+use s2n_quic::provider::event::{events, ConnectionInfo, ConnectionMeta};
+
+/// Connection-level events which are reported to the application
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum s2n_quic_event_type {
+    /// The connection was created
+    S2N_QUIC_EVENT_CONNECTION_STARTED = 0,
+    /// The handshake completed
+    S2N_QUIC_EVENT_HANDSHAKE_COMPLETE = 1,
+    /// The connection was closed
+    S2N_QUIC_EVENT_CONNECTION_CLOSED = 2,
+}
+
+pub use s2n_quic_event_type::*;
+
+/// Called with the application-provided context, the type of event, and the ID of the connection
+pub type s2n_quic_event_callback =
+    extern "C" fn(context: *mut c_void, event: s2n_quic_event_type, connection_id: u64);
+
+/// Forwards connection events to an application-provided callback
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Subscriber {
+    callback: Option<s2n_quic_event_callback>,
+    context: *mut c_void,
+}
+
+/// Safety: the application is responsible for the context being safe to use from the runtime's
+/// threads, as documented in the header
+unsafe impl Send for Subscriber {}
+unsafe impl Sync for Subscriber {}
+
+impl Subscriber {
+    pub fn new(callback: Option<s2n_quic_event_callback>, context: *mut c_void) -> Self {
+        Self { callback, context }
+    }
+
+    #[inline]
+    fn emit(&self, event: s2n_quic_event_type, meta: &ConnectionMeta) {
+        if let Some(callback) = self.callback {
+            callback(self.context, event, meta.id);
+        }
+    }
+}
+
+impl s2n_quic::provider::event::Subscriber for Subscriber {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_connection_started(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        _event: &events::ConnectionStarted,
+    ) {
+        self.emit(S2N_QUIC_EVENT_CONNECTION_STARTED, meta);
+    }
+
+    fn on_handshake_status_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        event: &events::HandshakeStatusUpdated,
+    ) {
+        if matches!(event.status, events::HandshakeStatus::Complete { .. }) {
+            self.emit(S2N_QUIC_EVENT_HANDSHAKE_COMPLETE, meta);
+        }
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &ConnectionMeta,
+        _event: &events::ConnectionClosed,
+    ) {
+        self.emit(S2N_QUIC_EVENT_CONNECTION_CLOSED, meta);
+    }
+}
@@ -0,0 +1,306 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::{call, free, handle_arg, str_arg, Result},
+    S2N_QUIC_FAILURE, S2N_QUIC_SUCCESS,
+};
+use core::{
+    ffi::{c_char, c_int, c_void},
+    mem::size_of,
+    ptr,
+    task::{Context, Poll},
+};
+use libc::{sockaddr, sockaddr_in, sockaddr_in6, sockaddr_storage, socklen_t};
+use s2n_quic::provider::io::detached;
+use std::{
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    sync::Arc,
+    task::{Wake, Waker},
+    time::Instant,
+};
+
+/// Drives an endpoint from an application-owned event loop
+///
+/// The application owns the socket. It passes received datagrams to the endpoint, sends the
+/// datagrams the endpoint produces, and calls back into it once the timeout expires or the
+/// wakeup callback fires.
+pub struct s2n_quic_io {
+    handle: detached::Handle,
+    /// The provider is handed to the endpoint once it's created
+    provider: Option<detached::Provider>,
+}
+
+impl s2n_quic_io {
+    /// Takes the provider for an endpoint which is driven by this IO handle
+    pub(crate) fn take_provider(&mut self) -> Result<detached::Provider> {
+        self.provider
+            .take()
+            .ok_or_else(|| "the IO handle is already in use by an endpoint".into())
+    }
+}
+
+/// Called once the application should call `s2n_quic_io_poll_wakeups` again
+///
+/// The callback may be invoked on any thread, including the library's threads, and must not call
+/// any blocking functions.
+pub type s2n_quic_wakeup_callback = extern "C" fn(context: *mut c_void);
+
+/// Creates an IO handle for an endpoint with the given local address (e.g. `"0.0.0.0:443"`)
+///
+/// The handle is passed to `s2n_quic_server_new_with_io` or `s2n_quic_client_new_with_io`. The
+/// endpoint is owned by the handle, so it keeps running until the handle is freed.
+///
+/// # Safety
+///
+/// `local_address` must be `NULL` or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_io_new(local_address: *const c_char) -> *mut s2n_quic_io {
+    call(ptr::null_mut(), || {
+        let local_address: SocketAddr = str_arg(local_address, "local_address")?.parse()?;
+
+        let provider = detached::Provider::builder()
+            .with_local_address(local_address)?
+            .build()?;
+        let handle = provider.handle();
+
+        Ok(Box::into_raw(Box::new(s2n_quic_io {
+            handle,
+            provider: Some(provider),
+        })))
+    })
+}
+
+/// Returns the minimum size of the buffer passed to `s2n_quic_io_poll_transmit`, or 0 on failure
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used by another thread for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_io_max_mtu(io: *mut s2n_quic_io) -> usize {
+    call(0, || {
+        let io = handle_arg(io, "io")?;
+        Ok(io.handle.max_mtu())
+    })
+}
+
+/// Processes a datagram received from `remote_address`
+///
+/// The payload is decrypted in place.
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used by another thread for the duration of the call.
+///
+/// `remote_address` must be `NULL` or valid for reads of `remote_address_len` bytes.
+///
+/// `payload` must be `NULL` or valid for reads and writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_io_receive(
+    io: *mut s2n_quic_io,
+    remote_address: *const sockaddr,
+    remote_address_len: socklen_t,
+    payload: *mut u8,
+    len: usize,
+) -> c_int {
+    call(S2N_QUIC_FAILURE, || {
+        let io = handle_arg(io, "io")?;
+        let remote_address = sockaddr_arg(remote_address, remote_address_len)?;
+
+        if payload.is_null() {
+            return Err("`payload` must not be NULL".into());
+        }
+        let payload = core::slice::from_raw_parts_mut(payload, len);
+
+        io.handle.receive(
+            Instant::now(),
+            remote_address.into(),
+            Default::default(),
+            payload,
+        );
+
+        Ok(S2N_QUIC_SUCCESS)
+    })
+}
+
+/// Writes the next datagram to be sent into `buffer` and its destination into `remote_address`
+///
+/// `len` must be at least `s2n_quic_io_max_mtu`. Returns the length of the datagram, 0 if there
+/// is nothing to send, or -1 on failure. This should be called until it returns 0 after receiving
+/// datagrams, after the timeout has expired, and after polling for wakeups.
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used by another thread for the duration of the call.
+///
+/// `buffer` must be `NULL` or valid for writes of `len` bytes.
+///
+/// `remote_address` and `remote_address_len` must each be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_io_poll_transmit(
+    io: *mut s2n_quic_io,
+    buffer: *mut u8,
+    len: usize,
+    remote_address: *mut sockaddr_storage,
+    remote_address_len: *mut socklen_t,
+) -> isize {
+    call(S2N_QUIC_FAILURE as isize, || {
+        let io = handle_arg(io, "io")?;
+        let remote_address = handle_arg(remote_address, "remote_address")?;
+        let remote_address_len = handle_arg(remote_address_len, "remote_address_len")?;
+
+        if buffer.is_null() {
+            return Err("`buffer` must not be NULL".into());
+        }
+        if len < io.handle.max_mtu() {
+            return Err(format!("`len` must be at least {}", io.handle.max_mtu()).into());
+        }
+        let buffer = core::slice::from_raw_parts_mut(buffer, len);
+
+        let Some(transmit) = io.handle.poll_transmit(Instant::now(), buffer) else {
+            return Ok(0);
+        };
+
+        *remote_address_len = write_sockaddr(transmit.remote_address.into(), remote_address);
+
+        Ok(transmit.len as isize)
+    })
+}
+
+/// Returns the number of milliseconds until `s2n_quic_io_poll_transmit` should be called, or -1
+/// if the endpoint doesn't have a timeout
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used by another thread for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_io_timeout(io: *mut s2n_quic_io) -> i64 {
+    call(-1, || {
+        let io = handle_arg(io, "io")?;
+
+        let Some(timeout) = io.handle.timeout() else {
+            return Ok(-1);
+        };
+
+        // round up so the application doesn't wake up before the timeout has expired
+        let duration = timeout.saturating_duration_since(Instant::now());
+        let millis = (duration.as_micros() + 999) / 1000;
+        Ok(millis.min(i64::MAX as u128) as i64)
+    })
+}
+
+/// Processes wakeups from the application, e.g. new data written to a stream
+///
+/// Returns the number of wakeups which were processed, or -1 once the endpoint has shut down and
+/// no longer needs to be driven. If there were no wakeups, `callback` is invoked with `context`
+/// once there are.
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used by another thread for the duration of the call.
+///
+/// `context` is passed to `callback`, which may be invoked from any thread, so it must remain valid
+/// and be safe to use from any thread until the callback is invoked or the handle is freed.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_io_poll_wakeups(
+    io: *mut s2n_quic_io,
+    callback: s2n_quic_wakeup_callback,
+    context: *mut c_void,
+) -> isize {
+    call(S2N_QUIC_FAILURE as isize, || {
+        let io = handle_arg(io, "io")?;
+
+        let waker = Waker::from(Arc::new(Wakeup { callback, context }));
+        let mut cx = Context::from_waker(&waker);
+
+        match io.handle.poll_wakeups(&mut cx, Instant::now()) {
+            Poll::Ready(Ok(count)) => Ok(count as isize),
+            Poll::Ready(Err(_)) => Err("the endpoint has shut down".into()),
+            Poll::Pending => Ok(0),
+        }
+    })
+}
+
+/// Frees the IO handle
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used again after this call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_io_free(io: *mut s2n_quic_io) {
+    free(io)
+}
+
+/// Invokes the application's callback when the endpoint is woken up
+struct Wakeup {
+    callback: s2n_quic_wakeup_callback,
+    context: *mut c_void,
+}
+
+/// Safety: the application is responsible for the context being safe to use from any thread, as
+/// documented in the header
+unsafe impl Send for Wakeup {}
+unsafe impl Sync for Wakeup {}
+
+impl Wake for Wakeup {
+    fn wake(self: Arc<Self>) {
+        (self.callback)(self.context);
+    }
+}
+
+/// Converts a C socket address argument into a [`SocketAddr`]
+unsafe fn sockaddr_arg(address: *const sockaddr, len: socklen_t) -> Result<SocketAddr> {
+    let address = address
+        .as_ref()
+        .ok_or("`remote_address` must not be NULL")?;
+    let len = len as usize;
+
+    match address.sa_family as c_int {
+        libc::AF_INET if len >= size_of::<sockaddr_in>() => {
+            let address = &*(address as *const sockaddr as *const sockaddr_in);
+            let ip = Ipv4Addr::from(u32::from_be(address.sin_addr.s_addr));
+            let port = u16::from_be(address.sin_port);
+            Ok(SocketAddrV4::new(ip, port).into())
+        }
+        libc::AF_INET6 if len >= size_of::<sockaddr_in6>() => {
+            let address = &*(address as *const sockaddr as *const sockaddr_in6);
+            let ip = Ipv6Addr::from(address.sin6_addr.s6_addr);
+            let port = u16::from_be(address.sin6_port);
+            Ok(SocketAddrV6::new(ip, port, address.sin6_flowinfo, address.sin6_scope_id).into())
+        }
+        _ => Err("`remote_address` must be a valid IPv4 or IPv6 address".into()),
+    }
+}
+
+/// Writes `address` into a C socket address and returns its length
+pub(crate) fn write_sockaddr(address: SocketAddr, storage: &mut sockaddr_storage) -> socklen_t {
+    // Safety: all-zero is a valid representation for the socket address structs
+    *storage = unsafe { core::mem::zeroed() };
+
+    match address {
+        SocketAddr::V4(address) => {
+            // Safety: `sockaddr_storage` is large enough and aligned for any address family
+            let storage = unsafe { &mut *(storage as *mut sockaddr_storage as *mut sockaddr_in) };
+            storage.sin_family = libc::AF_INET as _;
+            storage.sin_port = address.port().to_be();
+            storage.sin_addr.s_addr = u32::from(*address.ip()).to_be();
+            size_of::<sockaddr_in>() as _
+        }
+        SocketAddr::V6(address) => {
+            // Safety: `sockaddr_storage` is large enough and aligned for any address family
+            let storage = unsafe { &mut *(storage as *mut sockaddr_storage as *mut sockaddr_in6) };
+            storage.sin6_family = libc::AF_INET6 as _;
+            storage.sin6_port = address.port().to_be();
+            storage.sin6_addr.s6_addr = address.ip().octets();
+            storage.sin6_flowinfo = address.flowinfo();
+            storage.sin6_scope_id = address.scope_id();
+            size_of::<sockaddr_in6>() as _
+        }
+    }
+}
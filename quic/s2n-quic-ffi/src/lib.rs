@@ -0,0 +1,52 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! C bindings for embedding s2n-quic in non-Rust applications
+//!
+//! The API is declared in `include/s2n_quic.h` and covers the lifecycle of servers, clients,
+//! connections, and bidirectional and unidirectional streams, along with a callback for
+//! connection-level events.
+//!
+//! Endpoints are driven by a global multi-threaded tokio runtime which the library starts the
+//! first time an endpoint is created. By default it runs one worker thread per CPU core, which
+//! can be changed with [`s2n_quic_runtime_init`] before creating any endpoints. Functions which
+//! wait on the network (e.g. accepting connections or receiving stream data) block the calling
+//! thread. Event callbacks are invoked on the runtime's threads and must not call any blocking
+//! functions.
+//!
+//! Functions returning an `int` return [`S2N_QUIC_SUCCESS`] or [`S2N_QUIC_FAILURE`]. Functions
+//! returning a pointer return `NULL` on failure. In both cases, a description of the error can be
+//! retrieved with `s2n_quic_last_error`.
+//!
+//! Endpoints can also be driven from an application-owned event loop with application-owned
+//! sockets by creating them with an [`s2n_quic_io`] handle. The application passes received
+//! datagrams to the handle, sends the datagrams it produces, and calls back into it once its
+//! timeout expires or its wakeup callback fires.
+
+#![allow(non_camel_case_types)]
+
+mod client;
+mod connection;
+mod error;
+mod event;
+mod io;
+mod runtime;
+mod server;
+mod stream;
+
+pub use client::*;
+pub use connection::*;
+pub use error::*;
+pub use event::*;
+pub use io::*;
+pub use runtime::s2n_quic_runtime_init;
+pub use server::*;
+pub use stream::*;
+
+/// Returned by functions which complete successfully
+pub const S2N_QUIC_SUCCESS: i32 = 0;
+/// Returned by functions which fail. The reason can be retrieved with `s2n_quic_last_error`.
+pub const S2N_QUIC_FAILURE: i32 = -1;
+
+#[cfg(test)]
+mod tests;
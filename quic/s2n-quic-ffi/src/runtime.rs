@@ -0,0 +1,55 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::call, S2N_QUIC_FAILURE, S2N_QUIC_SUCCESS};
+use core::{ffi::c_int, future::Future};
+use once_cell::sync::OnceCell;
+use tokio::runtime::{self, Runtime};
+
+/// The runtime which drives all of the endpoints created through the C API
+///
+/// The runtime is created with the default configuration the first time it's used, unless the
+/// application called `s2n_quic_runtime_init` beforehand.
+static RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+fn build(worker_threads: usize) -> std::io::Result<Runtime> {
+    let mut builder = runtime::Builder::new_multi_thread();
+    builder.enable_all().thread_name("s2n-quic-ffi");
+    if worker_threads > 0 {
+        builder.worker_threads(worker_threads);
+    }
+    builder.build()
+}
+
+fn get() -> &'static Runtime {
+    RUNTIME.get_or_init(|| build(0).expect("failed to create the s2n-quic runtime"))
+}
+
+/// Configures the runtime which drives the library's endpoints
+///
+/// The library runs a multi-threaded runtime in the background. By default, it starts one worker
+/// thread per CPU core the first time an endpoint is created. `worker_threads` overrides the
+/// number of threads, with 0 selecting the default.
+///
+/// This must be called before any endpoint is created, otherwise it fails.
+#[no_mangle]
+pub extern "C" fn s2n_quic_runtime_init(worker_threads: usize) -> c_int {
+    call(S2N_QUIC_FAILURE, || {
+        let runtime = build(worker_threads)?;
+        RUNTIME
+            .set(runtime)
+            .map_err(|_| "the runtime has already been started")?;
+        Ok(S2N_QUIC_SUCCESS)
+    })
+}
+
+/// Blocks the calling thread until the future completes
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    get().block_on(future)
+}
+
+/// Calls `f` in the context of the runtime so it can spawn tasks
+pub(crate) fn enter<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = get().enter();
+    f()
+}
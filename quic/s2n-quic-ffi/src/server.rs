@@ -0,0 +1,148 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection::s2n_quic_connection,
+    error::{call, free, handle_arg, str_arg, Result},
+    event::{s2n_quic_event_callback, Subscriber},
+    io::s2n_quic_io,
+    runtime,
+};
+use core::{
+    ffi::{c_char, c_void},
+    ptr,
+};
+use s2n_quic::Server;
+
+/// A QUIC server endpoint
+pub struct s2n_quic_server {
+    server: Server,
+}
+
+/// Creates a server listening on `address` (e.g. `"0.0.0.0:443"`)
+///
+/// `cert_pem` and `key_pem` contain the PEM-encoded certificate chain and private key.
+/// `callback` may be `NULL` if the application isn't interested in events.
+///
+/// # Safety
+///
+/// `address`, `cert_pem` and `key_pem` must each be `NULL` or point to a NUL-terminated string.
+///
+/// `context` is passed to `callback` from the library's threads, so it must remain valid and be
+/// safe to use from any thread for as long as the endpoint exists.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_server_new(
+    address: *const c_char,
+    cert_pem: *const c_char,
+    key_pem: *const c_char,
+    callback: Option<s2n_quic_event_callback>,
+    context: *mut c_void,
+) -> *mut s2n_quic_server {
+    call(ptr::null_mut(), || {
+        let address = str_arg(address, "address")?;
+        let cert_pem = str_arg(cert_pem, "cert_pem")?;
+        let key_pem = str_arg(key_pem, "key_pem")?;
+
+        let server = runtime::enter(|| -> Result<_> {
+            let endpoint = Server::builder()
+                .with_tls((cert_pem, key_pem))?
+                .with_io(address)?
+                .with_event(Subscriber::new(callback, context))?
+                .start()?;
+            Ok(endpoint)
+        })?;
+
+        Ok(Box::into_raw(Box::new(s2n_quic_server { server })))
+    })
+}
+
+/// Creates a server which is driven by the application through `io`
+///
+/// `cert_pem` and `key_pem` contain the PEM-encoded certificate chain and private key.
+/// `callback` may be `NULL` if the application isn't interested in events. Each IO handle can only
+/// be used by a single endpoint.
+///
+/// # Safety
+///
+/// `io` must be `NULL` or a pointer returned by `s2n_quic_io_new` which hasn't been freed. It must
+/// not be used by another thread for the duration of the call.
+///
+/// `cert_pem` and `key_pem` must each be `NULL` or point to a NUL-terminated string.
+///
+/// `context` is passed to `callback` from the library's threads, so it must remain valid and be
+/// safe to use from any thread for as long as the endpoint exists.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_server_new_with_io(
+    io: *mut s2n_quic_io,
+    cert_pem: *const c_char,
+    key_pem: *const c_char,
+    callback: Option<s2n_quic_event_callback>,
+    context: *mut c_void,
+) -> *mut s2n_quic_server {
+    call(ptr::null_mut(), || {
+        let io = handle_arg(io, "io")?;
+        let cert_pem = str_arg(cert_pem, "cert_pem")?;
+        let key_pem = str_arg(key_pem, "key_pem")?;
+        let provider = io.take_provider()?;
+
+        let server = runtime::enter(|| -> Result<_> {
+            let endpoint = Server::builder()
+                .with_tls((cert_pem, key_pem))?
+                .with_io(provider)?
+                .with_event(Subscriber::new(callback, context))?
+                .start()?;
+            Ok(endpoint)
+        })?;
+
+        Ok(Box::into_raw(Box::new(s2n_quic_server { server })))
+    })
+}
+
+/// Returns the UDP port the server is bound to, or 0 on failure
+///
+/// # Safety
+///
+/// `server` must be `NULL` or a pointer returned by `s2n_quic_server_new` or
+/// `s2n_quic_server_new_with_io` which hasn't been freed. It must not be used by another thread for
+/// the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_server_local_port(server: *mut s2n_quic_server) -> u16 {
+    call(0, || {
+        let server = handle_arg(server, "server")?;
+        Ok(server.server.local_addr()?.port())
+    })
+}
+
+/// Blocks until a new connection is accepted
+///
+/// Returns `NULL` once the server has been closed.
+///
+/// # Safety
+///
+/// `server` must be `NULL` or a pointer returned by `s2n_quic_server_new` or
+/// `s2n_quic_server_new_with_io` which hasn't been freed. It must not be used by another thread for
+/// the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_server_accept(
+    server: *mut s2n_quic_server,
+) -> *mut s2n_quic_connection {
+    call(ptr::null_mut(), || {
+        let server = handle_arg(server, "server")?;
+        let connection = runtime::block_on(server.server.accept()).ok_or("the server is closed")?;
+        Ok(s2n_quic_connection::new(connection))
+    })
+}
+
+/// Closes the server and frees its resources
+///
+/// Connections which were previously accepted remain valid and must be freed separately.
+///
+/// # Safety
+///
+/// `server` must be `NULL` or a pointer returned by `s2n_quic_server_new` or
+/// `s2n_quic_server_new_with_io` which hasn't been freed. It must not be used again after this
+/// call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_server_free(server: *mut s2n_quic_server) {
+    free(server)
+}
@@ -0,0 +1,269 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::{call, free, handle_arg, Result},
+    runtime, S2N_QUIC_FAILURE, S2N_QUIC_SUCCESS,
+};
+use bytes::{Buf, Bytes};
+use core::{
+    ffi::c_int,
+    future::{self, Future},
+    slice,
+    task::{Context, Poll},
+};
+use s2n_quic::stream::{self, BidirectionalStream, ReceiveStream, SendStream};
+
+/// A bidirectional QUIC stream
+pub struct s2n_quic_stream {
+    stream: BidirectionalStream,
+    /// Data which was received but didn't fit in the application's buffer
+    pending: Bytes,
+}
+
+impl s2n_quic_stream {
+    pub(crate) fn new(stream: BidirectionalStream) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            stream,
+            pending: Bytes::new(),
+        }))
+    }
+}
+
+/// The sending half of a unidirectional QUIC stream
+pub struct s2n_quic_send_stream {
+    stream: SendStream,
+}
+
+impl s2n_quic_send_stream {
+    pub(crate) fn new(stream: SendStream) -> *mut Self {
+        Box::into_raw(Box::new(Self { stream }))
+    }
+}
+
+/// The receiving half of a unidirectional QUIC stream
+pub struct s2n_quic_receive_stream {
+    stream: ReceiveStream,
+    /// Data which was received but didn't fit in the application's buffer
+    pending: Bytes,
+}
+
+impl s2n_quic_receive_stream {
+    pub(crate) fn new(stream: ReceiveStream) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            stream,
+            pending: Bytes::new(),
+        }))
+    }
+}
+
+/// Copies `len` bytes from `data` and blocks until `send` has buffered them
+unsafe fn send<F, Fut>(data: *const u8, len: usize, send: F) -> Result<isize>
+where
+    F: FnOnce(Bytes) -> Fut,
+    Fut: Future<Output = stream::Result<()>>,
+{
+    if len == 0 {
+        return Ok(0);
+    }
+    if data.is_null() {
+        return Err("`data` must not be NULL".into());
+    }
+
+    let data = Bytes::copy_from_slice(slice::from_raw_parts(data, len));
+    runtime::block_on(send(data))?;
+
+    Ok(len as isize)
+}
+
+/// Copies at most `len` bytes into `data`, blocking on `poll_receive` if nothing is pending
+unsafe fn receive<F>(
+    pending: &mut Bytes,
+    data: *mut u8,
+    len: usize,
+    mut poll_receive: F,
+) -> Result<isize>
+where
+    F: FnMut(&mut Context) -> Poll<stream::Result<Option<Bytes>>>,
+{
+    if len == 0 {
+        return Ok(0);
+    }
+    if data.is_null() {
+        return Err("`data` must not be NULL".into());
+    }
+
+    while pending.is_empty() {
+        match runtime::block_on(future::poll_fn(&mut poll_receive))? {
+            Some(chunk) => *pending = chunk,
+            None => return Ok(0),
+        }
+    }
+
+    let len = len.min(pending.len());
+    slice::from_raw_parts_mut(data, len).copy_from_slice(&pending[..len]);
+    pending.advance(len);
+
+    Ok(len as isize)
+}
+
+/// Blocks until `len` bytes from `data` have been buffered for sending
+///
+/// Returns the number of bytes sent, or -1 on failure.
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_open_bidirectional_stream`
+/// or `s2n_quic_connection_accept_bidirectional_stream` which hasn't been freed. It must not be
+/// used by another thread for the duration of the call.
+///
+/// `data` must be `NULL` or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_stream_send(
+    stream: *mut s2n_quic_stream,
+    data: *const u8,
+    len: usize,
+) -> isize {
+    call(S2N_QUIC_FAILURE as isize, || {
+        let stream = handle_arg(stream, "stream")?;
+        send(data, len, |data| stream.stream.send(data))
+    })
+}
+
+/// Blocks until data is received on the stream and copies at most `len` bytes into `data`
+///
+/// Returns the number of bytes received, 0 once the peer has finished the stream, or -1 on
+/// failure.
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_open_bidirectional_stream`
+/// or `s2n_quic_connection_accept_bidirectional_stream` which hasn't been freed. It must not be
+/// used by another thread for the duration of the call.
+///
+/// `data` must be `NULL` or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_stream_receive(
+    stream: *mut s2n_quic_stream,
+    data: *mut u8,
+    len: usize,
+) -> isize {
+    call(S2N_QUIC_FAILURE as isize, || {
+        let stream = handle_arg(stream, "stream")?;
+        receive(&mut stream.pending, data, len, |cx| {
+            stream.stream.poll_receive(cx)
+        })
+    })
+}
+
+/// Finishes the sending side of the stream and blocks until all of the data has been flushed
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_open_bidirectional_stream`
+/// or `s2n_quic_connection_accept_bidirectional_stream` which hasn't been freed. It must not be
+/// used by another thread for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_stream_finish(stream: *mut s2n_quic_stream) -> c_int {
+    call(S2N_QUIC_FAILURE, || {
+        let stream = handle_arg(stream, "stream")?;
+        runtime::block_on(stream.stream.close())?;
+        Ok(S2N_QUIC_SUCCESS)
+    })
+}
+
+/// Frees the stream
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_open_bidirectional_stream`
+/// or `s2n_quic_connection_accept_bidirectional_stream` which hasn't been freed. It must not be
+/// used again after this call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_stream_free(stream: *mut s2n_quic_stream) {
+    free(stream)
+}
+
+/// Blocks until `len` bytes from `data` have been buffered for sending
+///
+/// Returns the number of bytes sent, or -1 on failure.
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_open_send_stream` which
+/// hasn't been freed. It must not be used by another thread for the duration of the call.
+///
+/// `data` must be `NULL` or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_send_stream_send(
+    stream: *mut s2n_quic_send_stream,
+    data: *const u8,
+    len: usize,
+) -> isize {
+    call(S2N_QUIC_FAILURE as isize, || {
+        let stream = handle_arg(stream, "stream")?;
+        send(data, len, |data| stream.stream.send(data))
+    })
+}
+
+/// Finishes the stream and blocks until all of the data has been flushed
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_open_send_stream` which
+/// hasn't been freed. It must not be used by another thread for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_send_stream_finish(stream: *mut s2n_quic_send_stream) -> c_int {
+    call(S2N_QUIC_FAILURE, || {
+        let stream = handle_arg(stream, "stream")?;
+        runtime::block_on(stream.stream.close())?;
+        Ok(S2N_QUIC_SUCCESS)
+    })
+}
+
+/// Frees the stream
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_open_send_stream` which
+/// hasn't been freed. It must not be used again after this call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_send_stream_free(stream: *mut s2n_quic_send_stream) {
+    free(stream)
+}
+
+/// Blocks until data is received on the stream and copies at most `len` bytes into `data`
+///
+/// Returns the number of bytes received, 0 once the peer has finished the stream, or -1 on
+/// failure.
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_accept_receive_stream`
+/// which hasn't been freed. It must not be used by another thread for the duration of the call.
+///
+/// `data` must be `NULL` or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_receive_stream_receive(
+    stream: *mut s2n_quic_receive_stream,
+    data: *mut u8,
+    len: usize,
+) -> isize {
+    call(S2N_QUIC_FAILURE as isize, || {
+        let stream = handle_arg(stream, "stream")?;
+        receive(&mut stream.pending, data, len, |cx| {
+            stream.stream.poll_receive(cx)
+        })
+    })
+}
+
+/// Frees the stream
+///
+/// # Safety
+///
+/// `stream` must be `NULL` or a pointer returned by `s2n_quic_connection_accept_receive_stream`
+/// which hasn't been freed. It must not be used again after this call.
+#[no_mangle]
+pub unsafe extern "C" fn s2n_quic_receive_stream_free(stream: *mut s2n_quic_receive_stream) {
+    free(stream)
+}
@@ -0,0 +1,407 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::{
+    ffi::c_void,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    time::Duration,
+};
+use s2n_quic_core::crypto::tls::testing::certificates::{CERT_PEM, KEY_PEM};
+use std::{
+    ffi::{CStr, CString},
+    thread,
+};
+
+const MESSAGE: &[u8] = b"hello from the C API";
+
+fn cstring(value: &str) -> CString {
+    CString::new(value).unwrap()
+}
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(s2n_quic_last_error()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[derive(Debug, Default)]
+struct Events {
+    connection_id: AtomicU64,
+    started: AtomicUsize,
+    handshake_complete: AtomicUsize,
+}
+
+extern "C" fn on_event(context: *mut c_void, event: s2n_quic_event_type, connection_id: u64) {
+    let events = unsafe { &*(context as *const Events) };
+    events.connection_id.store(connection_id, Ordering::Relaxed);
+    match event {
+        S2N_QUIC_EVENT_CONNECTION_STARTED => events.started.fetch_add(1, Ordering::Relaxed),
+        S2N_QUIC_EVENT_HANDSHAKE_COMPLETE => {
+            events.handshake_complete.fetch_add(1, Ordering::Relaxed)
+        }
+        S2N_QUIC_EVENT_CONNECTION_CLOSED => 0,
+    };
+}
+
+#[test]
+fn echo_test() {
+    unsafe {
+        let server = s2n_quic_server_new(
+            cstring("127.0.0.1:0").as_ptr(),
+            cstring(CERT_PEM).as_ptr(),
+            cstring(KEY_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(!server.is_null(), "{}", last_error());
+
+        let port = s2n_quic_server_local_port(server);
+        assert_ne!(port, 0, "{}", last_error());
+
+        // raw pointers aren't `Send` so pass the addresses instead
+        let server_addr = server as usize;
+        let echo = thread::spawn(move || {
+            let server = server_addr as *mut s2n_quic_server;
+            let connection = s2n_quic_server_accept(server);
+            assert!(!connection.is_null(), "{}", last_error());
+
+            let stream = s2n_quic_connection_accept_bidirectional_stream(connection);
+            assert!(!stream.is_null(), "{}", last_error());
+
+            // use a small buffer so chunks are split across multiple calls
+            let mut buffer = [0u8; 4];
+            loop {
+                let len = s2n_quic_stream_receive(stream, buffer.as_mut_ptr(), buffer.len());
+                assert!(len >= 0, "{}", last_error());
+                if len == 0 {
+                    break;
+                }
+                let sent = s2n_quic_stream_send(stream, buffer.as_ptr(), len as usize);
+                assert_eq!(sent, len, "{}", last_error());
+            }
+
+            assert_eq!(s2n_quic_stream_finish(stream), S2N_QUIC_SUCCESS);
+            s2n_quic_stream_free(stream);
+
+            // keep the connection open until the client has received everything
+            connection as usize
+        });
+
+        let events = Events::default();
+        let client = s2n_quic_client_new(
+            cstring("0.0.0.0:0").as_ptr(),
+            cstring(CERT_PEM).as_ptr(),
+            Some(on_event),
+            &events as *const Events as *mut c_void,
+        );
+        assert!(!client.is_null(), "{}", last_error());
+
+        let connection = s2n_quic_client_connect(
+            client,
+            cstring(&format!("127.0.0.1:{port}")).as_ptr(),
+            cstring("localhost").as_ptr(),
+        );
+        assert!(!connection.is_null(), "{}", last_error());
+        assert_eq!(
+            s2n_quic_connection_id(connection),
+            events.connection_id.load(Ordering::Relaxed)
+        );
+
+        let stream = s2n_quic_connection_open_bidirectional_stream(connection);
+        assert!(!stream.is_null(), "{}", last_error());
+
+        let sent = s2n_quic_stream_send(stream, MESSAGE.as_ptr(), MESSAGE.len());
+        assert_eq!(sent, MESSAGE.len() as isize, "{}", last_error());
+        assert_eq!(s2n_quic_stream_finish(stream), S2N_QUIC_SUCCESS);
+
+        let mut received = vec![];
+        let mut buffer = [0u8; 64];
+        loop {
+            let len = s2n_quic_stream_receive(stream, buffer.as_mut_ptr(), buffer.len());
+            assert!(len >= 0, "{}", last_error());
+            if len == 0 {
+                break;
+            }
+            received.extend_from_slice(&buffer[..len as usize]);
+        }
+        assert_eq!(received, MESSAGE);
+
+        // wait for the server to finish its side of the stream before closing the connection,
+        // otherwise the server may not receive the acknowledgement for its data
+        let server_connection = echo.join().unwrap() as *mut s2n_quic_connection;
+
+        assert_eq!(
+            s2n_quic_connection_close(connection, 0),
+            S2N_QUIC_SUCCESS,
+            "{}",
+            last_error()
+        );
+        s2n_quic_stream_free(stream);
+        s2n_quic_connection_free(connection);
+        s2n_quic_connection_free(server_connection);
+
+        s2n_quic_client_free(client);
+        s2n_quic_server_free(server);
+
+        assert_eq!(events.started.load(Ordering::Relaxed), 1);
+        assert_eq!(events.handshake_complete.load(Ordering::Relaxed), 1);
+    }
+}
+
+/// Sends [`MESSAGE`] over a unidirectional stream from the client to the server
+unsafe fn send_unidirectional(
+    server: *mut s2n_quic_server,
+    client: *mut s2n_quic_client,
+    server_addr: &str,
+) {
+    // raw pointers aren't `Send` so pass the addresses instead
+    let server_addr_ptr = server as usize;
+    let receiver = thread::spawn(move || {
+        let server = server_addr_ptr as *mut s2n_quic_server;
+        let connection = s2n_quic_server_accept(server);
+        assert!(!connection.is_null(), "{}", last_error());
+
+        let stream = s2n_quic_connection_accept_receive_stream(connection);
+        assert!(!stream.is_null(), "{}", last_error());
+
+        let mut received = vec![];
+        let mut buffer = [0u8; 4];
+        loop {
+            let len = s2n_quic_receive_stream_receive(stream, buffer.as_mut_ptr(), buffer.len());
+            assert!(len >= 0, "{}", last_error());
+            if len == 0 {
+                break;
+            }
+            received.extend_from_slice(&buffer[..len as usize]);
+        }
+        s2n_quic_receive_stream_free(stream);
+
+        (received, connection as usize)
+    });
+
+    let connection = s2n_quic_client_connect(
+        client,
+        cstring(server_addr).as_ptr(),
+        cstring("localhost").as_ptr(),
+    );
+    assert!(!connection.is_null(), "{}", last_error());
+
+    let stream = s2n_quic_connection_open_send_stream(connection);
+    assert!(!stream.is_null(), "{}", last_error());
+
+    let sent = s2n_quic_send_stream_send(stream, MESSAGE.as_ptr(), MESSAGE.len());
+    assert_eq!(sent, MESSAGE.len() as isize, "{}", last_error());
+    assert_eq!(
+        s2n_quic_send_stream_finish(stream),
+        S2N_QUIC_SUCCESS,
+        "{}",
+        last_error()
+    );
+
+    let (received, server_connection) = receiver.join().unwrap();
+    assert_eq!(received, MESSAGE);
+
+    s2n_quic_send_stream_free(stream);
+    s2n_quic_connection_free(connection);
+    s2n_quic_connection_free(server_connection as *mut s2n_quic_connection);
+}
+
+#[test]
+fn unidirectional_stream_test() {
+    unsafe {
+        let server = s2n_quic_server_new(
+            cstring("127.0.0.1:0").as_ptr(),
+            cstring(CERT_PEM).as_ptr(),
+            cstring(KEY_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(!server.is_null(), "{}", last_error());
+        let port = s2n_quic_server_local_port(server);
+
+        let client = s2n_quic_client_new(
+            cstring("0.0.0.0:0").as_ptr(),
+            cstring(CERT_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(!client.is_null(), "{}", last_error());
+
+        send_unidirectional(server, client, &format!("127.0.0.1:{port}"));
+
+        s2n_quic_client_free(client);
+        s2n_quic_server_free(server);
+    }
+}
+
+extern "C" fn on_wakeup(_context: *mut c_void) {}
+
+/// Exchanges datagrams between IO handles in memory, standing in for the application's event loop
+unsafe fn drive(endpoints: &[(*mut s2n_quic_io, &str)], stop: &AtomicBool) {
+    let mut buffer = vec![0u8; s2n_quic_io_max_mtu(endpoints[0].0)];
+
+    while !stop.load(Ordering::Relaxed) {
+        for (io, local_address) in endpoints {
+            let local_address = local_address.parse().unwrap();
+            let mut source: libc::sockaddr_storage = core::mem::zeroed();
+            let source_len = io::write_sockaddr(local_address, &mut source);
+
+            assert!(s2n_quic_io_poll_wakeups(*io, on_wakeup, ptr::null_mut()) >= 0);
+
+            loop {
+                let mut destination: libc::sockaddr_storage = core::mem::zeroed();
+                let mut destination_len = 0;
+                let len = s2n_quic_io_poll_transmit(
+                    *io,
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                    &mut destination,
+                    &mut destination_len,
+                );
+                assert!(len >= 0, "{}", last_error());
+                if len == 0 {
+                    break;
+                }
+
+                // the handles are only addressed by port in this test
+                let destination = &*(&destination as *const _ as *const libc::sockaddr_in);
+                let port = u16::from_be(destination.sin_port);
+                let (peer, _) = endpoints
+                    .iter()
+                    .find(|(_, address)| address.ends_with(&format!(":{port}")))
+                    .expect("datagram sent to an unknown address");
+
+                let status = s2n_quic_io_receive(
+                    *peer,
+                    &source as *const _ as *const libc::sockaddr,
+                    source_len,
+                    buffer.as_mut_ptr(),
+                    len as usize,
+                );
+                assert_eq!(status, S2N_QUIC_SUCCESS, "{}", last_error());
+            }
+        }
+
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn detached_io_test() {
+    const SERVER_ADDR: &str = "127.0.0.1:4433";
+    const CLIENT_ADDR: &str = "127.0.0.1:4434";
+
+    unsafe {
+        let server_io = s2n_quic_io_new(cstring(SERVER_ADDR).as_ptr());
+        assert!(!server_io.is_null(), "{}", last_error());
+        let server = s2n_quic_server_new_with_io(
+            server_io,
+            cstring(CERT_PEM).as_ptr(),
+            cstring(KEY_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(!server.is_null(), "{}", last_error());
+
+        let client_io = s2n_quic_io_new(cstring(CLIENT_ADDR).as_ptr());
+        assert!(!client_io.is_null(), "{}", last_error());
+        let client = s2n_quic_client_new_with_io(
+            client_io,
+            cstring(CERT_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(!client.is_null(), "{}", last_error());
+
+        // each IO handle can only drive a single endpoint
+        let other = s2n_quic_client_new_with_io(
+            client_io,
+            cstring(CERT_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(other.is_null());
+        assert!(last_error().contains("in use"), "{}", last_error());
+
+        let stop = std::sync::Arc::new(AtomicBool::new(false));
+        let (server_io_ptr, client_io_ptr) = (server_io as usize, client_io as usize);
+        let driver = thread::spawn({
+            let stop = stop.clone();
+            move || {
+                let endpoints = [
+                    (server_io_ptr as *mut s2n_quic_io, SERVER_ADDR),
+                    (client_io_ptr as *mut s2n_quic_io, CLIENT_ADDR),
+                ];
+                drive(&endpoints, &stop);
+            }
+        });
+
+        send_unidirectional(server, client, SERVER_ADDR);
+
+        s2n_quic_client_free(client);
+        s2n_quic_server_free(server);
+
+        stop.store(true, Ordering::Relaxed);
+        driver.join().unwrap();
+
+        s2n_quic_io_free(client_io);
+        s2n_quic_io_free(server_io);
+    }
+}
+
+#[test]
+fn runtime_init_test() {
+    // the runtime is started by the first endpoint so configuring it afterwards fails
+    let server = unsafe {
+        s2n_quic_server_new(
+            cstring("127.0.0.1:0").as_ptr(),
+            cstring(CERT_PEM).as_ptr(),
+            cstring(KEY_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        )
+    };
+    assert!(!server.is_null(), "{}", last_error());
+    assert_eq!(s2n_quic_runtime_init(1), S2N_QUIC_FAILURE);
+    assert!(last_error().contains("already"), "{}", last_error());
+    unsafe { s2n_quic_server_free(server) };
+}
+
+#[test]
+fn invalid_argument_test() {
+    unsafe {
+        let server = s2n_quic_server_new(
+            ptr::null(),
+            cstring(CERT_PEM).as_ptr(),
+            cstring(KEY_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(server.is_null());
+        assert!(last_error().contains("address"), "{}", last_error());
+
+        let connection = s2n_quic_server_accept(ptr::null_mut());
+        assert!(connection.is_null());
+        assert!(last_error().contains("server"), "{}", last_error());
+
+        let len = s2n_quic_stream_receive(ptr::null_mut(), ptr::null_mut(), 1);
+        assert_eq!(len, S2N_QUIC_FAILURE as isize);
+
+        let client = s2n_quic_client_new(
+            cstring("not an address").as_ptr(),
+            cstring(CERT_PEM).as_ptr(),
+            None,
+            ptr::null_mut(),
+        );
+        assert!(client.is_null());
+        assert!(!last_error().is_empty());
+
+        // freeing NULL handles is a no-op
+        s2n_quic_server_free(ptr::null_mut());
+        s2n_quic_client_free(ptr::null_mut());
+        s2n_quic_connection_free(ptr::null_mut());
+        s2n_quic_stream_free(ptr::null_mut());
+    }
+}
@@ -44,6 +44,9 @@ fn main() -> Result<(), Error> {
             features.insert("gro");
             features.insert("pktinfo");
             features.insert("tos");
+            features.insert("recverr");
+            features.insert("rxq_ovfl");
+            features.insert("zerocopy");
         }
         "macos" => {
             // miri doesn't support the way we detect syscall support so override it
@@ -58,6 +61,7 @@ fn main() -> Result<(), Error> {
             features.insert("mtu_disc");
             features.insert("pktinfo");
             features.insert("tos");
+            features.insert("recverr");
         }
         _ => {
             // TODO others
@@ -80,7 +84,12 @@ impl Features {
         }
 
         // the following features only make sense if cmsg is supported
-        if ["gso", "gro", "pktinfo", "tos"].contains(&name) && !self.supports("cmsg") {
+        if [
+            "gso", "gro", "pktinfo", "tos", "recverr", "rxq_ovfl", "zerocopy",
+        ]
+        .contains(&name)
+            && !self.supports("cmsg")
+        {
             return;
         }
 
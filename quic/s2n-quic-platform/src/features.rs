@@ -9,8 +9,11 @@ pub mod gso;
 pub mod pktinfo;
 pub mod pktinfo_v4;
 pub mod pktinfo_v6;
+pub mod recverr;
+pub mod rxq_ovfl;
 pub mod tos;
 pub mod tos_v4;
 pub mod tos_v6;
+pub mod zerocopy;
 
 pub use gso::Gso;
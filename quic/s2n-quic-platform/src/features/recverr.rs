@@ -0,0 +1,152 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::c_int;
+use s2n_quic_core::io::rx::SocketError;
+
+#[cfg(s2n_quic_platform_recverr)]
+mod recverr_enabled {
+    use super::*;
+    use libc::{IPPROTO_IP, IPPROTO_IPV6, IPV6_RECVERR, IP_RECVERR};
+
+    pub const SOCKOPT_V4: Option<(c_int, c_int)> = Some((IPPROTO_IP as _, IP_RECVERR as _));
+    pub const SOCKOPT_V6: Option<(c_int, c_int)> = Some((IPPROTO_IPV6 as _, IPV6_RECVERR as _));
+    pub const EMSGSIZE: Option<u32> = Some(libc::EMSGSIZE as _);
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        (level == IPPROTO_IP as c_int && ty == IP_RECVERR as c_int)
+            || (level == IPPROTO_IPV6 as c_int && ty == IPV6_RECVERR as c_int)
+    }
+}
+
+#[cfg(any(not(s2n_quic_platform_recverr), test))]
+mod recverr_disabled {
+    #![cfg_attr(test, allow(dead_code))]
+    use super::*;
+
+    pub const SOCKOPT_V4: Option<(c_int, c_int)> = None;
+    pub const SOCKOPT_V6: Option<(c_int, c_int)> = None;
+    pub const EMSGSIZE: Option<u32> = None;
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        let _ = level;
+        let _ = ty;
+        false
+    }
+}
+
+mod recverr_impl {
+    #[cfg(not(s2n_quic_platform_recverr))]
+    pub use super::recverr_disabled::*;
+    #[cfg(s2n_quic_platform_recverr)]
+    pub use super::recverr_enabled::*;
+}
+
+pub use recverr_impl::*;
+
+pub const IS_SUPPORTED: bool = cfg!(s2n_quic_platform_recverr);
+
+// The origins of extended socket errors
+// see https://man7.org/linux/man-pages/man7/ip.7.html
+const ORIGIN_LOCAL: u8 = 1;
+const ORIGIN_ICMP: u8 = 2;
+const ORIGIN_ICMP6: u8 = 3;
+
+const ICMP_DEST_UNREACH: u8 = 3;
+const ICMP6_DST_UNREACH: u8 = 1;
+const ICMP6_PACKET_TOO_BIG: u8 = 2;
+
+/// Decodes a `sock_extended_err` control message into a [`SocketError`]
+///
+/// Errors which don't indicate a problem with the path, such as an expired TTL, are ignored.
+#[inline]
+pub fn decode(bytes: &[u8]) -> Option<SocketError> {
+    // struct sock_extended_err {
+    //     __u32 ee_errno;
+    //     __u8  ee_origin;
+    //     __u8  ee_type;
+    //     __u8  ee_code;
+    //     __u8  ee_pad;
+    //     __u32 ee_info;
+    //     __u32 ee_data;
+    // };
+    let errno = u32::from_ne_bytes(bytes.get(0..4)?.try_into().ok()?);
+    let origin = *bytes.get(4)?;
+    let ty = *bytes.get(5)?;
+    let code = *bytes.get(6)?;
+    let info = u32::from_ne_bytes(bytes.get(8..12)?.try_into().ok()?);
+
+    // `ee_info` contains the MTU for packet too big errors
+    let mtu = info.min(u16::MAX as u32) as u16;
+
+    let error = match (origin, ty, code) {
+        // the packet exceeded the MTU of the local interface
+        (ORIGIN_LOCAL, _, _) if Some(errno) == EMSGSIZE => SocketError::PacketTooBig { mtu },
+        (ORIGIN_ICMP, ICMP_DEST_UNREACH, 3) => SocketError::PortUnreachable,
+        (ORIGIN_ICMP, ICMP_DEST_UNREACH, 4) => SocketError::PacketTooBig { mtu },
+        // network, host, or administratively prohibited
+        (ORIGIN_ICMP, ICMP_DEST_UNREACH, 0 | 1 | 9 | 10 | 13) => SocketError::HostUnreachable,
+        (ORIGIN_ICMP6, ICMP6_DST_UNREACH, 4) => SocketError::PortUnreachable,
+        // no route, administratively prohibited, or address unreachable
+        (ORIGIN_ICMP6, ICMP6_DST_UNREACH, 0 | 1 | 3) => SocketError::HostUnreachable,
+        (ORIGIN_ICMP6, ICMP6_PACKET_TOO_BIG, _) => SocketError::PacketTooBig { mtu },
+        _ => return None,
+    };
+
+    Some(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(errno: u32, origin: u8, ty: u8, code: u8, info: u32) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[0..4].copy_from_slice(&errno.to_ne_bytes());
+        bytes[4] = origin;
+        bytes[5] = ty;
+        bytes[6] = code;
+        bytes[8..12].copy_from_slice(&info.to_ne_bytes());
+        bytes
+    }
+
+    #[test]
+    fn decode_test() {
+        let port_unreachable = Some(SocketError::PortUnreachable);
+        let host_unreachable = Some(SocketError::HostUnreachable);
+
+        assert_eq!(port_unreachable, decode(&encode(111, ORIGIN_ICMP, 3, 3, 0)));
+        assert_eq!(
+            port_unreachable,
+            decode(&encode(111, ORIGIN_ICMP6, 1, 4, 0))
+        );
+        assert_eq!(host_unreachable, decode(&encode(113, ORIGIN_ICMP, 3, 1, 0)));
+        assert_eq!(
+            host_unreachable,
+            decode(&encode(113, ORIGIN_ICMP6, 1, 3, 0))
+        );
+
+        assert_eq!(
+            Some(SocketError::PacketTooBig { mtu: 1400 }),
+            decode(&encode(90, ORIGIN_ICMP, 3, 4, 1400))
+        );
+        assert_eq!(
+            Some(SocketError::PacketTooBig { mtu: 1280 }),
+            decode(&encode(90, ORIGIN_ICMP6, 2, 0, 1280))
+        );
+
+        if let Some(errno) = EMSGSIZE {
+            assert_eq!(
+                Some(SocketError::PacketTooBig { mtu: 1500 }),
+                decode(&encode(errno, ORIGIN_LOCAL, 0, 0, 1500))
+            );
+        }
+
+        // ICMP time exceeded doesn't say anything about the peer
+        assert_eq!(None, decode(&encode(113, ORIGIN_ICMP, 11, 0, 0)));
+        // truncated messages are ignored
+        assert_eq!(None, decode(&encode(111, ORIGIN_ICMP, 3, 3, 0)[..8]));
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::c_int;
+
+#[cfg(s2n_quic_platform_zerocopy)]
+mod zerocopy_enabled {
+    use super::*;
+
+    // `SO_ZEROCOPY` isn't exported by libc for all targets
+    // see https://github.com/torvalds/linux/blob/v5.0/include/uapi/asm-generic/socket.h#L116
+    const SO_ZEROCOPY: c_int = 60;
+
+    pub const SOCKOPT: Option<(c_int, c_int)> = Some((libc::SOL_SOCKET as _, SO_ZEROCOPY));
+    pub const SEND_FLAG: c_int = libc::MSG_ZEROCOPY as _;
+}
+
+#[cfg(any(not(s2n_quic_platform_zerocopy), test))]
+mod zerocopy_disabled {
+    #![cfg_attr(test, allow(dead_code))]
+    use super::*;
+
+    pub const SOCKOPT: Option<(c_int, c_int)> = None;
+    pub const SEND_FLAG: c_int = 0;
+}
+
+mod zerocopy_impl {
+    #[cfg(not(s2n_quic_platform_zerocopy))]
+    pub use super::zerocopy_disabled::*;
+    #[cfg(s2n_quic_platform_zerocopy)]
+    pub use super::zerocopy_enabled::*;
+}
+
+pub use zerocopy_impl::*;
+
+pub const IS_SUPPORTED: bool = cfg!(s2n_quic_platform_zerocopy);
+
+// see https://www.kernel.org/doc/html/latest/networking/msg_zerocopy.html#notification-parsing
+const ORIGIN_ZEROCOPY: u8 = 5;
+const CODE_ZEROCOPY_COPIED: u8 = 1;
+
+/// A range of completed zero-copy sends, read from the socket error queue
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Completion {
+    /// The id of the first completed send
+    pub start: u32,
+    /// The id of the last completed send, inclusive
+    pub end: u32,
+    /// The kernel fell back to copying the payloads
+    pub copied: bool,
+}
+
+/// Decodes a `sock_extended_err` control message into a zero-copy [`Completion`]
+///
+/// Returns `None` if the error didn't originate from a zero-copy notification.
+#[inline]
+pub fn decode(bytes: &[u8]) -> Option<Completion> {
+    // see `recverr::decode` for the layout of `sock_extended_err`
+    let origin = *bytes.get(4)?;
+    let code = *bytes.get(6)?;
+
+    if origin != ORIGIN_ZEROCOPY {
+        return None;
+    }
+
+    let start = u32::from_ne_bytes(bytes.get(8..12)?.try_into().ok()?);
+    let end = u32::from_ne_bytes(bytes.get(12..16)?.try_into().ok()?);
+    let copied = code & CODE_ZEROCOPY_COPIED != 0;
+
+    Some(Completion { start, end, copied })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(origin: u8, code: u8, info: u32, data: u32) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[4] = origin;
+        bytes[6] = code;
+        bytes[8..12].copy_from_slice(&info.to_ne_bytes());
+        bytes[12..16].copy_from_slice(&data.to_ne_bytes());
+        bytes
+    }
+
+    #[test]
+    fn decode_test() {
+        assert_eq!(
+            Some(Completion {
+                start: 3,
+                end: 7,
+                copied: false
+            }),
+            decode(&encode(ORIGIN_ZEROCOPY, 0, 3, 7))
+        );
+        assert_eq!(
+            Some(Completion {
+                start: 8,
+                end: 8,
+                copied: true
+            }),
+            decode(&encode(ORIGIN_ZEROCOPY, CODE_ZEROCOPY_COPIED, 8, 8))
+        );
+
+        // ICMP errors aren't completions
+        assert_eq!(None, decode(&encode(2, 3, 0, 0)));
+        // truncated messages are ignored
+        assert_eq!(None, decode(&encode(ORIGIN_ZEROCOPY, 0, 3, 7)[..12]));
+    }
+}
@@ -1,6 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "std")]
+pub mod detached;
+
+#[cfg(feature = "std")]
+pub mod runtime;
+
+#[cfg(feature = "std")]
+mod setup;
+
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
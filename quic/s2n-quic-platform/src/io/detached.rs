@@ -0,0 +1,302 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An IO provider which is driven by the application rather than a runtime
+//!
+//! Instead of owning a socket and spawning a task, the endpoint is stored in a [`Handle`].
+//! The application is then responsible for feeding it received datagrams, polling it for
+//! datagrams to transmit, and calling back into it once the next timeout has expired. This
+//! makes it possible to embed an endpoint in a custom event loop, a userspace networking
+//! stack, or another language's runtime.
+
+use core::task::{Context, Poll};
+use s2n_quic_core::{
+    endpoint::{CloseError, Endpoint},
+    inet::{datagram, ExplicitCongestionNotification, SocketAddress},
+    io::{rx, tx},
+    path::{self, mtu},
+    time::{self, Timestamp},
+};
+use std::{
+    io::{self, ErrorKind},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+mod builder;
+
+pub use builder::Builder;
+pub type PathHandle = path::Tuple;
+
+pub struct Io {
+    mtu_config: mtu::Config,
+    handle: Handle,
+}
+
+impl Io {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns a handle for driving the endpoint once it has been started
+    pub fn handle(&self) -> Handle {
+        self.handle.clone()
+    }
+
+    pub fn start<E: Endpoint<PathHandle = PathHandle>>(
+        self,
+        mut endpoint: E,
+    ) -> io::Result<SocketAddress> {
+        endpoint.set_mtu_config(self.mtu_config);
+
+        let mut slot = self
+            .handle
+            .state
+            .endpoint
+            .lock()
+            .map_err(|_| io::Error::new(ErrorKind::Other, "endpoint lock was poisoned"))?;
+        debug_assert!(slot.is_none(), "endpoint has already been started");
+        *slot = Some(Box::new(endpoint));
+
+        Ok(self.handle.state.local_address)
+    }
+}
+
+/// A datagram written by [`Handle::poll_transmit`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transmit {
+    /// The address to which the datagram should be sent
+    pub remote_address: SocketAddress,
+    /// The ECN markings to set on the datagram
+    pub ecn: ExplicitCongestionNotification,
+    /// The number of bytes written to the provided buffer
+    pub len: usize,
+}
+
+/// Drives an endpoint which was started with the detached [`Io`] provider
+///
+/// All of the methods take the current time, which must be monotonic across calls. Calls made
+/// before the endpoint has started are ignored.
+#[derive(Clone)]
+pub struct Handle {
+    state: Arc<State>,
+}
+
+struct State {
+    epoch: Instant,
+    local_address: SocketAddress,
+    max_mtu: usize,
+    endpoint: Mutex<Option<Box<dyn Driver>>>,
+}
+
+impl Handle {
+    fn new(local_address: SocketAddress, max_mtu: usize) -> Self {
+        let state = State {
+            epoch: Instant::now(),
+            local_address,
+            max_mtu,
+            endpoint: Mutex::new(None),
+        };
+        Self {
+            state: Arc::new(state),
+        }
+    }
+
+    /// Returns the minimum size of the buffer passed to [`Self::poll_transmit`]
+    pub fn max_mtu(&self) -> usize {
+        self.state.max_mtu
+    }
+
+    /// Processes a datagram received from `remote_address`
+    ///
+    /// The payload is decrypted in place.
+    pub fn receive(
+        &self,
+        now: Instant,
+        remote_address: SocketAddress,
+        ecn: ExplicitCongestionNotification,
+        payload: &mut [u8],
+    ) {
+        let header = datagram::Header {
+            path: PathHandle {
+                remote_address: remote_address.into(),
+                local_address: self.state.local_address.into(),
+            },
+            ecn,
+        };
+        let clock = self.clock(now);
+        self.with_endpoint(|endpoint| endpoint.receive(&clock, header, payload));
+    }
+
+    /// Writes the next datagram to be transmitted into `buffer`
+    ///
+    /// This should be called until it returns `None` after receiving datagrams, after the
+    /// timeout has expired, and after the application has been woken up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is smaller than [`Self::max_mtu`]
+    pub fn poll_transmit(&self, now: Instant, buffer: &mut [u8]) -> Option<Transmit> {
+        assert!(
+            buffer.len() >= self.state.max_mtu,
+            "transmit buffer must be at least {} bytes",
+            self.state.max_mtu
+        );
+        let buffer = &mut buffer[..self.state.max_mtu];
+        let clock = self.clock(now);
+        self.with_endpoint(|endpoint| endpoint.transmit(&clock, buffer))?
+    }
+
+    /// Returns the time at which [`Self::poll_transmit`] should next be called
+    pub fn timeout(&self) -> Option<Instant> {
+        let timeout = self.with_endpoint(|endpoint| endpoint.timeout())??;
+        let timeout = unsafe {
+            // Safety: the timestamp was derived from the handle's epoch
+            timeout.as_duration()
+        };
+        Some(self.state.epoch + timeout)
+    }
+
+    /// Polls for wakeups from the application, e.g. new data written to a stream
+    ///
+    /// When successful, the number of wakeups is returned. An error is returned once the endpoint
+    /// has shut down and no longer needs to be driven.
+    pub fn poll_wakeups(&self, cx: &mut Context, now: Instant) -> Poll<Result<usize, CloseError>> {
+        let clock = self.clock(now);
+        self.with_endpoint(|endpoint| endpoint.poll_wakeups(cx, &clock))
+            .unwrap_or(Poll::Pending)
+    }
+
+    #[inline]
+    fn clock(&self, now: Instant) -> Clock {
+        let elapsed = now.saturating_duration_since(self.state.epoch);
+        Clock(unsafe {
+            // Safety: time duration is only derived from a single `Instant`
+            Timestamp::from_duration(elapsed)
+        })
+    }
+
+    #[inline]
+    fn with_endpoint<F: FnOnce(&mut dyn Driver) -> R, R>(&self, f: F) -> Option<R> {
+        let mut endpoint = self.state.endpoint.lock().ok()?;
+        let endpoint = endpoint.as_mut()?;
+        Some(f(&mut **endpoint))
+    }
+}
+
+struct Clock(Timestamp);
+
+impl time::Clock for Clock {
+    #[inline]
+    fn get_time(&self) -> Timestamp {
+        self.0
+    }
+}
+
+/// An object-safe wrapper around [`Endpoint`]
+trait Driver: Send {
+    fn receive(&mut self, clock: &Clock, header: datagram::Header<PathHandle>, payload: &mut [u8]);
+
+    fn transmit(&mut self, clock: &Clock, buffer: &mut [u8]) -> Option<Transmit>;
+
+    fn timeout(&self) -> Option<Timestamp>;
+
+    fn poll_wakeups(&mut self, cx: &mut Context, clock: &Clock) -> Poll<Result<usize, CloseError>>;
+}
+
+impl<E: Endpoint<PathHandle = PathHandle>> Driver for E {
+    #[inline]
+    fn receive(&mut self, clock: &Clock, header: datagram::Header<PathHandle>, payload: &mut [u8]) {
+        let mut queue = RxQueue {
+            header,
+            payload: Some(payload),
+        };
+        Endpoint::receive(self, &mut queue, clock);
+    }
+
+    #[inline]
+    fn transmit(&mut self, clock: &Clock, buffer: &mut [u8]) -> Option<Transmit> {
+        let mut queue = TxQueue {
+            buffer,
+            transmit: None,
+        };
+        Endpoint::transmit(self, &mut queue, clock);
+        queue.transmit
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Timestamp> {
+        Endpoint::timeout(self)
+    }
+
+    #[inline]
+    fn poll_wakeups(&mut self, cx: &mut Context, clock: &Clock) -> Poll<Result<usize, CloseError>> {
+        Endpoint::poll_wakeups(self, cx, clock)
+    }
+}
+
+/// A receive queue containing a single datagram
+struct RxQueue<'a> {
+    header: datagram::Header<PathHandle>,
+    payload: Option<&'a mut [u8]>,
+}
+
+impl<'a> rx::Queue for RxQueue<'a> {
+    type Handle = PathHandle;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        if let Some(payload) = self.payload.take() {
+            on_packet(self.header, payload);
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.payload.is_none()
+    }
+}
+
+/// A transmit queue with capacity for a single datagram
+struct TxQueue<'a> {
+    buffer: &'a mut [u8],
+    transmit: Option<Transmit>,
+}
+
+impl<'a> tx::Queue for TxQueue<'a> {
+    type Handle = PathHandle;
+
+    const SUPPORTS_ECN: bool = true;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        if self.transmit.is_some() {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        let remote_address = message.path_handle().remote_address.0;
+        let ecn = message.ecn();
+        let buffer = tx::PayloadBuffer::new(self.buffer);
+        let len = message.write_payload(buffer, 0)?;
+
+        self.transmit = Some(Transmit {
+            remote_address,
+            ecn,
+            len,
+        });
+
+        Ok(tx::Outcome { len, index: 0 })
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        if self.transmit.is_some() {
+            0
+        } else {
+            1
+        }
+    }
+}
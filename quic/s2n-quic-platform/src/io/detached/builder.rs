@@ -0,0 +1,44 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic_core::inet::SocketAddressV4;
+
+#[derive(Default)]
+pub struct Builder {
+    local_address: Option<SocketAddress>,
+    mtu_config_builder: mtu::Builder,
+}
+
+impl Builder {
+    /// Sets the local address of the socket the application is using for the endpoint
+    ///
+    /// Defaults to the unspecified address.
+    pub fn with_local_address<A: Into<SocketAddress>>(mut self, addr: A) -> io::Result<Self> {
+        self.local_address = Some(addr.into());
+        Ok(self)
+    }
+
+    /// Sets the largest maximum transmission unit (MTU) that can be sent on a path
+    pub fn with_max_mtu(mut self, max_mtu: u16) -> io::Result<Self> {
+        self.mtu_config_builder = self
+            .mtu_config_builder
+            .with_max_mtu(max_mtu)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        Ok(self)
+    }
+
+    pub fn build(self) -> io::Result<Io> {
+        let mtu_config = self
+            .mtu_config_builder
+            .build()
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        let local_address = self
+            .local_address
+            .unwrap_or_else(|| SocketAddressV4::UNSPECIFIED.into());
+        let max_mtu: u16 = mtu_config.max_mtu().into();
+        let handle = Handle::new(local_address, max_mtu as usize);
+
+        Ok(Io { mtu_config, handle })
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A socket-based IO provider which can be driven by any async runtime
+//!
+//! The runtime is abstracted behind the [`Runtime`] trait, which is responsible for spawning the
+//! socket and endpoint tasks, providing a clock with timers, and registering sockets with its IO
+//! reactor. Implementations are provided for tokio and for runtimes built on `async-io`, such as
+//! async-std and smol.
+
+use crate::{features::Gso, io::setup, message::default as message, socket};
+use core::{
+    future::Future,
+    task::{Context, Poll},
+};
+use s2n_quic_core::{
+    endpoint::Endpoint,
+    event::{self, EndpointPublisher as _},
+    inet::{self, SocketAddress},
+    io::event_loop::EventLoop,
+    path::MaxMtu,
+    task::{budget::Budget, cooldown::Cooldown},
+    time::{Clock as _, ClockWithTimer},
+};
+use std::io::{self, ErrorKind};
+
+#[cfg(feature = "async-io-runtime")]
+pub mod async_io;
+mod builder;
+mod task;
+#[cfg(test)]
+mod tests;
+#[cfg(all(feature = "tokio-runtime", unix))]
+pub mod tokio;
+
+pub type PathHandle = message::Handle;
+pub use builder::Builder;
+
+/// An async runtime which can drive an endpoint
+pub trait Runtime: 'static + Send + Clone {
+    /// The clock used by the endpoint for timestamps and timers
+    type Clock: 'static + ClockWithTimer + Send + Sync;
+
+    /// A UDP socket registered with the runtime's IO reactor
+    type Socket: Socket;
+
+    /// Returns a new clock for an endpoint
+    fn clock(&self) -> Self::Clock;
+
+    /// Spawns a task which runs in the background until completion
+    fn spawn<F: 'static + Send + Future<Output = ()>>(&self, task: F);
+
+    /// Registers a non-blocking UDP socket with the runtime
+    fn register(&self, socket: std::net::UdpSocket) -> io::Result<Self::Socket>;
+}
+
+/// A UDP socket which can notify a task when it becomes ready
+pub trait Socket: 'static + Send + Unpin {
+    /// Returns the underlying socket
+    fn get_ref(&self) -> &std::net::UdpSocket;
+
+    /// Polls for read readiness after a receive operation returned `WouldBlock`
+    ///
+    /// Returns `Ready` if the operation should be retried.
+    fn poll_read_ready(&mut self, cx: &mut Context) -> Poll<io::Result<()>>;
+
+    /// Polls for write readiness after a send operation returned `WouldBlock`
+    ///
+    /// Returns `Ready` if the operation should be retried.
+    fn poll_write_ready(&mut self, cx: &mut Context) -> Poll<io::Result<()>>;
+}
+
+pub struct Io<R: Runtime> {
+    builder: Builder<R>,
+}
+
+impl<R: Runtime> Io<R> {
+    pub fn builder(runtime: R) -> Builder<R> {
+        Builder::new(runtime)
+    }
+
+    pub fn new<A: std::net::ToSocketAddrs>(runtime: R, addr: A) -> io::Result<Self> {
+        let address = addr.to_socket_addrs()?.next().expect("missing address");
+        let builder = Builder::new(runtime).with_receive_address(address)?;
+        Ok(Self { builder })
+    }
+
+    pub fn start<E: Endpoint<PathHandle = PathHandle>>(
+        self,
+        mut endpoint: E,
+    ) -> io::Result<SocketAddress>
+    where
+        <R::Clock as ClockWithTimer>::Timer: Send,
+    {
+        let Builder { runtime, config } = self.builder;
+
+        let mut publisher = event::EndpointPublisherSubscriber::new(
+            event::builder::EndpointMeta {
+                endpoint_type: E::ENDPOINT_TYPE,
+                timestamp: runtime.clock().get_time(),
+            },
+            None,
+            endpoint.subscriber(),
+        );
+
+        let gso = Gso::default();
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::Gso {
+                max_segments: gso.max_segments(),
+            },
+        });
+
+        let setup::Sockets {
+            rx_socket,
+            tx_socket,
+            rx_addr,
+            mtu_config,
+            rx_config,
+        } = config.bind(&mut publisher)?;
+
+        let rx = {
+            let payload_len = rx_config.payload_len();
+
+            let (producer, consumer) =
+                socket::ring::pair::<message::Message>(entries(8 << 20, payload_len), payload_len);
+
+            let socket = register(&runtime, rx_socket)?;
+            runtime.spawn(async move {
+                let _ = task::rx(socket, producer, cooldown()).await;
+            });
+
+            let max_mtu = MaxMtu::try_from(payload_len as u16).unwrap();
+            let addr: inet::SocketAddress = rx_addr.into();
+            socket::io::rx::Rx::new(vec![consumer], max_mtu, addr.into())
+        };
+
+        let tx = {
+            // compute the payload size for each message from the number of GSO segments we can
+            // fill
+            let payload_len = {
+                let max_mtu: u16 = mtu_config.max_mtu().into();
+                (max_mtu as u32 * gso.max_segments() as u32).min(u16::MAX as u32)
+            };
+
+            let (producer, consumer) = socket::ring::pair::<message::Message>(
+                entries(128 * 1024, payload_len),
+                payload_len,
+            );
+
+            let socket = register(&runtime, tx_socket)?;
+            let task_gso = gso.clone();
+            runtime.spawn(async move {
+                let _ = task::tx(socket, consumer, task_gso, cooldown()).await;
+            });
+
+            socket::io::tx::Tx::new(vec![producer], gso, mtu_config.max_mtu())
+        };
+
+        // Notify the endpoint of the MTU that we chose
+        endpoint.set_mtu_config(mtu_config);
+
+        let event_loop = EventLoop {
+            endpoint,
+            clock: runtime.clock(),
+            rx,
+            tx,
+            cooldown: cooldown(),
+            budget: budget(),
+        }
+        .start();
+
+        runtime.spawn(event_loop);
+
+        Ok(rx_addr.into())
+    }
+}
+
+fn register<R: Runtime>(runtime: &R, socket: socket2::Socket) -> io::Result<R::Socket> {
+    let socket: std::net::UdpSocket = socket.into();
+    socket.set_nonblocking(true)?;
+    runtime.register(socket)
+}
+
+/// Returns the number of ring entries needed to fill the buffer size, rounded up to the next power
+/// of two
+fn entries(buffer_size: u32, payload_len: u32) -> u32 {
+    (buffer_size / payload_len).max(1).next_power_of_two()
+}
+
+fn cooldown() -> Cooldown {
+    // these tasks don't spin before waiting on the runtime
+    Cooldown::new(0)
+}
+
+fn budget() -> Budget {
+    // periodically yield so a busy endpoint doesn't starve other tasks sharing the runtime
+    Budget::new(32)
+}
@@ -0,0 +1,171 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drives the endpoint on a runtime built on [`async-io`](https://docs.rs/async-io), such as
+//! async-std or smol
+//!
+//! `async-io` doesn't include an executor so the application provides a function for spawning
+//! tasks:
+//!
+//! ```rust,ignore
+//! let runtime = AsyncIo::new(|task| smol::spawn(task).detach());
+//! let runtime = AsyncIo::new(|task| drop(async_std::task::spawn(task)));
+//! ```
+
+use async_io::{Async, Timer as Sleep};
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use s2n_quic_core::time::{self, Timestamp};
+use std::{
+    io,
+    net::UdpSocket,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A task to be spawned by the application's executor
+pub type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+#[derive(Clone)]
+pub struct AsyncIo {
+    spawn: Arc<dyn Fn(Task) + Send + Sync>,
+}
+
+impl AsyncIo {
+    pub fn new<F: 'static + Fn(Task) + Send + Sync>(spawn: F) -> Self {
+        Self {
+            spawn: Arc::new(spawn),
+        }
+    }
+}
+
+impl fmt::Debug for AsyncIo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncIo").finish_non_exhaustive()
+    }
+}
+
+impl super::Runtime for AsyncIo {
+    type Clock = Clock;
+    type Socket = Async<UdpSocket>;
+
+    #[inline]
+    fn clock(&self) -> Self::Clock {
+        Clock::default()
+    }
+
+    #[inline]
+    fn spawn<F: 'static + Send + Future<Output = ()>>(&self, task: F) {
+        (self.spawn)(Box::pin(task))
+    }
+
+    #[inline]
+    fn register(&self, socket: UdpSocket) -> io::Result<Self::Socket> {
+        Async::new(socket)
+    }
+}
+
+impl super::Socket for Async<UdpSocket> {
+    #[inline]
+    fn get_ref(&self) -> &UdpSocket {
+        Async::get_ref(self)
+    }
+
+    #[inline]
+    fn poll_read_ready(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_readable(cx)
+    }
+
+    #[inline]
+    fn poll_write_ready(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_writable(cx)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Clock(Instant);
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self(Instant::now())
+    }
+}
+
+impl time::Clock for Clock {
+    #[inline]
+    fn get_time(&self) -> Timestamp {
+        let duration = self.0.elapsed();
+        unsafe {
+            // Safety: time duration is only derived from a single `Instant`
+            Timestamp::from_duration(duration)
+        }
+    }
+}
+
+impl time::ClockWithTimer for Clock {
+    type Timer = Timer;
+
+    #[inline]
+    fn timer(&self) -> Timer {
+        Timer {
+            clock: *self,
+            target: None,
+            sleep: Sleep::never(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Timer {
+    /// A reference to the current clock
+    clock: Clock,
+    /// The `Instant` at which the timer should expire
+    target: Option<Instant>,
+    /// The timer registered with the `async-io` reactor
+    sleep: Sleep,
+}
+
+impl time::clock::Timer for Timer {
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<()> {
+        // Only poll the inner timer if we have a target set
+        if self.target.is_none() {
+            return Poll::Pending;
+        }
+
+        let res = Pin::new(&mut self.sleep).poll(cx);
+
+        if res.is_ready() {
+            // clear the target after it fires, otherwise we'll endlessly wake up the task
+            self.target = None;
+        }
+
+        res.map(|_| ())
+    }
+
+    #[inline]
+    fn update(&mut self, timestamp: Timestamp) {
+        let delay = unsafe {
+            // Safety: the same clock epoch is being used
+            timestamp.as_duration()
+        };
+
+        // floor the delay to milliseconds to reduce timer churn
+        let delay = Duration::from_millis(delay.as_millis() as u64);
+
+        // add the delay to the clock's epoch
+        let next_time = self.clock.0 + delay;
+
+        // If the target hasn't changed then don't do anything
+        if Some(next_time) == self.target {
+            return;
+        }
+
+        self.sleep.set_at(next_time);
+        self.target = Some(next_time);
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+pub struct Builder<R: Runtime> {
+    pub(super) runtime: R,
+    pub(super) config: setup::Config,
+}
+
+impl<R: Runtime> Builder<R> {
+    pub(super) fn new(runtime: R) -> Self {
+        Self {
+            runtime,
+            config: Default::default(),
+        }
+    }
+
+    /// Sets the local address for the runtime to listen on
+    ///
+    /// NOTE: this method is mutually exclusive with `with_socket`
+    pub fn with_receive_address(mut self, addr: std::net::SocketAddr) -> io::Result<Self> {
+        debug_assert!(
+            self.config.rx_socket.is_none(),
+            "socket has already been set"
+        );
+        self.config.recv_addr = Some(addr);
+        Ok(self)
+    }
+
+    /// Sets the socket used for sending and receiving for the runtime
+    ///
+    /// NOTE: this method is mutually exclusive with `with_receive_address`
+    pub fn with_socket(mut self, socket: std::net::UdpSocket) -> io::Result<Self> {
+        debug_assert!(
+            self.config.recv_addr.is_none(),
+            "recv address has already been set"
+        );
+        self.config.rx_socket = Some(socket.into());
+        Ok(self)
+    }
+
+    /// Sets the size of the operating system’s send buffer associated with the socket
+    pub fn with_send_buffer_size(mut self, send_buffer_size: usize) -> io::Result<Self> {
+        self.config.socket_send_buffer_size = Some(send_buffer_size);
+        Ok(self)
+    }
+
+    /// Sets the size of the operating system’s receive buffer associated with the socket
+    pub fn with_recv_buffer_size(mut self, recv_buffer_size: usize) -> io::Result<Self> {
+        self.config.socket_recv_buffer_size = Some(recv_buffer_size);
+        Ok(self)
+    }
+
+    /// Sets the largest maximum transmission unit (MTU) that can be sent on a path (default: 1500)
+    pub fn with_max_mtu(mut self, max_mtu: u16) -> io::Result<Self> {
+        self.config.mtu_config_builder = self
+            .config
+            .mtu_config_builder
+            .with_max_mtu(max_mtu)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        Ok(self)
+    }
+
+    /// Configures Generic Receive Offload (GRO)
+    ///
+    /// By default, GRO will be used unless the platform does not support it. If it is known that
+    /// GRO is not available, set this option to explicitly disable it.
+    pub fn with_gro(mut self, enabled: bool) -> io::Result<Self> {
+        self.config.gro_enabled = Some(enabled);
+        Ok(self)
+    }
+
+    /// Enables the address reuse (SO_REUSEADDR) socket option
+    pub fn with_reuse_address(mut self, enabled: bool) -> io::Result<Self> {
+        self.config.reuse_address = enabled;
+        Ok(self)
+    }
+
+    /// Enables the port reuse (SO_REUSEPORT) socket option
+    pub fn with_reuse_port(mut self) -> io::Result<Self> {
+        if !cfg!(unix) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reuse_port is not supported on the current platform",
+            ));
+        }
+        self.config.reuse_port = true;
+        Ok(self)
+    }
+
+    pub fn build(self) -> io::Result<Io<R>> {
+        Ok(Io { builder: self })
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::Socket;
+use crate::{
+    features::Gso,
+    message::Message,
+    socket::{
+        ring,
+        task::{rx, tx},
+    },
+};
+use core::task::{Context, Poll};
+use s2n_quic_core::task::cooldown::Cooldown;
+use std::io;
+
+/// Wraps a runtime socket to implement the socket task traits
+pub struct Registered<S>(S);
+
+pub async fn rx<S, M>(socket: S, producer: ring::Producer<M>, cooldown: Cooldown) -> io::Result<()>
+where
+    S: Socket,
+    M: Message + Unpin,
+    Registered<S>: rx::Socket<M, Error = io::Error>,
+{
+    let result = rx::Receiver::new(producer, Registered(socket), cooldown).await;
+    if let Some(err) = result {
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn tx<S, M>(
+    socket: S,
+    consumer: ring::Consumer<M>,
+    gso: Gso,
+    cooldown: Cooldown,
+) -> io::Result<()>
+where
+    S: Socket,
+    M: Message + Unpin,
+    Registered<S>: tx::Socket<M, Error = io::Error>,
+{
+    let result = tx::Sender::new(consumer, Registered(socket), gso, cooldown).await;
+    if let Some(err) = result {
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use crate::syscall::{SocketType, UnixMessage};
+    use std::os::unix::io::AsRawFd;
+
+    impl<S: Socket, M: UnixMessage> tx::Socket<M> for Registered<S> {
+        type Error = io::Error;
+
+        #[inline]
+        fn send(
+            &mut self,
+            cx: &mut Context,
+            entries: &mut [M],
+            events: &mut tx::Events,
+        ) -> io::Result<()> {
+            let flags = events.send_flags();
+            M::send(self.0.get_ref().as_raw_fd(), entries, flags, events);
+
+            // yield back if we weren't blocked
+            if !events.is_blocked() {
+                return Ok(());
+            }
+
+            // if the socket became ready in the meantime, have the caller try again
+            if let Poll::Ready(result) = self.0.poll_write_ready(cx) {
+                result?;
+                events.take_blocked();
+            }
+
+            Ok(())
+        }
+    }
+
+    impl<S: Socket, M: UnixMessage> rx::Socket<M> for Registered<S> {
+        type Error = io::Error;
+
+        #[inline]
+        fn recv(
+            &mut self,
+            cx: &mut Context,
+            entries: &mut [M],
+            events: &mut rx::Events,
+        ) -> io::Result<()> {
+            M::recv(
+                self.0.get_ref().as_raw_fd(),
+                SocketType::NonBlocking,
+                entries,
+                events,
+            );
+
+            // yield back if we weren't blocked
+            if !events.is_blocked() {
+                return Ok(());
+            }
+
+            // if the socket became ready in the meantime, have the caller try again
+            if let Poll::Ready(result) = self.0.poll_read_ready(cx) {
+                result?;
+                events.take_blocked();
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod simple {
+    use super::*;
+    use crate::{
+        message::{simple::Message, Message as _},
+        syscall::SocketEvents,
+    };
+
+    impl<S: Socket> tx::Socket<Message> for Registered<S> {
+        type Error = io::Error;
+
+        #[inline]
+        fn send(
+            &mut self,
+            cx: &mut Context,
+            entries: &mut [Message],
+            events: &mut tx::Events,
+        ) -> io::Result<()> {
+            for entry in entries {
+                let target: std::net::SocketAddr = (*entry.remote_address()).into();
+                let payload = entry.payload_mut();
+                match self.0.get_ref().send_to(payload, target) {
+                    Ok(_) => {
+                        if events.on_complete(1).is_break() {
+                            return Ok(());
+                        }
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        events.blocked();
+                        if let Poll::Ready(result) = self.0.poll_write_ready(cx) {
+                            result?;
+                            events.take_blocked();
+                        }
+                        break;
+                    }
+                    Err(err) => {
+                        if events.on_error(err).is_break() {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl<S: Socket> rx::Socket<Message> for Registered<S> {
+        type Error = io::Error;
+
+        #[inline]
+        fn recv(
+            &mut self,
+            cx: &mut Context,
+            entries: &mut [Message],
+            events: &mut rx::Events,
+        ) -> io::Result<()> {
+            for entry in entries {
+                let payload = entry.payload_mut();
+                match self.0.get_ref().recv_from(payload) {
+                    Ok((len, addr)) => {
+                        unsafe {
+                            entry.set_payload_len(len);
+                        }
+                        entry.set_remote_address(&(addr.into()));
+
+                        if events.on_complete(1).is_break() {
+                            return Ok(());
+                        }
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        events.blocked();
+                        if let Poll::Ready(result) = self.0.poll_read_ready(cx) {
+                            result?;
+                            events.take_blocked();
+                        }
+                        break;
+                    }
+                    Err(err) => {
+                        if events.on_error(err).is_break() {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Io, PathHandle, Runtime};
+use crate::io::tokio::tests::TestEndpoint;
+use core::future::Future;
+use s2n_quic_core::{inet::SocketAddress, path::Handle as _, time::ClockWithTimer};
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+/// Starts a client and server on the runtime, returning once the client has received all of its
+/// messages
+fn start<R: Runtime>(runtime: R) -> io::Result<()>
+where
+    <R::Clock as ClockWithTimer>::Timer: Send,
+{
+    let addr: std::net::SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+    let server_socket = std::net::UdpSocket::bind(addr)?;
+    let server_addr: SocketAddress = server_socket.local_addr()?.into();
+    let client_socket = std::net::UdpSocket::bind(addr)?;
+    let client_addr: SocketAddress = client_socket.local_addr()?.into();
+
+    let server_endpoint = {
+        let mut handle = PathHandle::from_remote_address(client_addr.into());
+        handle.local_address = server_addr.into();
+        TestEndpoint::<true>::new(handle)
+    };
+
+    let client_endpoint = {
+        let mut handle = PathHandle::from_remote_address(server_addr.into());
+        handle.local_address = client_addr.into();
+        TestEndpoint::<false>::new(handle)
+    };
+
+    let server = Io::builder(runtime.clone())
+        .with_socket(server_socket)?
+        .build()?;
+    assert_eq!(server.start(server_endpoint)?, server_addr);
+
+    let client = Io::builder(runtime).with_socket(client_socket)?.build()?;
+    assert_eq!(client.start(client_endpoint)?, client_addr);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+mod tokio_runtime {
+    use super::*;
+    use crate::io::runtime::tokio::Tokio;
+    use tokio::task::JoinHandle;
+
+    /// Wraps the tokio runtime to keep track of the spawned tasks
+    #[derive(Clone)]
+    struct Tracked {
+        runtime: Tokio,
+        tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    }
+
+    impl Runtime for Tracked {
+        type Clock = <Tokio as Runtime>::Clock;
+        type Socket = <Tokio as Runtime>::Socket;
+
+        fn clock(&self) -> Self::Clock {
+            self.runtime.clock()
+        }
+
+        fn spawn<F: 'static + Send + Future<Output = ()>>(&self, task: F) {
+            let task = tokio::spawn(task);
+            self.tasks.lock().unwrap().push(task);
+        }
+
+        fn register(&self, socket: std::net::UdpSocket) -> io::Result<Self::Socket> {
+            self.runtime.register(socket)
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn tokio_test() -> io::Result<()> {
+        let runtime = Tracked {
+            runtime: Tokio::current()?,
+            tasks: Default::default(),
+        };
+
+        start(runtime.clone())?;
+
+        // the last task spawned is the client's endpoint, which finishes once all of its messages
+        // have been acknowledged
+        let client = runtime.tasks.lock().unwrap().pop().unwrap();
+        tokio::time::timeout(core::time::Duration::from_secs(60), client).await??;
+
+        for task in runtime.tasks.lock().unwrap().drain(..) {
+            task.abort();
+        }
+
+        Ok(())
+    }
+
+    /// Ensures the runtime binds its socket with the same options as the tokio provider
+    #[tokio::test]
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(miri, ignore)]
+    async fn reuse_port_test() -> io::Result<()> {
+        use crate::{io::setup::convert_addr_to_std, syscall};
+
+        let addr: std::net::SocketAddr = "127.0.0.1:0".parse().unwrap();
+
+        let client_socket = std::net::UdpSocket::bind(addr)?;
+        let client_addr: SocketAddress = client_socket.local_addr()?.into();
+        let endpoint =
+            TestEndpoint::<true>::new(PathHandle::from_remote_address(client_addr.into()));
+
+        let server = Io::builder(Tokio::current()?)
+            .with_receive_address(addr)?
+            .with_reuse_port()?
+            .with_gro(true)?
+            .build()?;
+        let server_addr: std::net::SocketAddr = server.start(endpoint)?.into();
+        assert_ne!(server_addr.port(), 0);
+
+        // binding another socket to the port only succeeds if the runtime enabled SO_REUSEPORT
+        let shard = syscall::bind_udp(server_addr, false, true)?;
+        assert_eq!(server_addr, convert_addr_to_std(shard.local_addr()?)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async-io-runtime")]
+mod async_io_runtime {
+    use super::*;
+    use crate::io::runtime::async_io::AsyncIo;
+    use std::{sync::mpsc, thread};
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn async_io_test() -> io::Result<()> {
+        let tasks = Arc::new(Mutex::new(vec![]));
+
+        // run each task on its own thread, as `async-io` doesn't provide an executor
+        let runtime = AsyncIo::new({
+            let tasks = tasks.clone();
+            move |task| {
+                let (done, on_done) = mpsc::channel();
+                thread::spawn(move || {
+                    async_io::block_on(task);
+                    let _ = done.send(());
+                });
+                tasks.lock().unwrap().push(on_done);
+            }
+        });
+
+        start(runtime)?;
+
+        // the last task spawned is the client's endpoint, which finishes once all of its messages
+        // have been acknowledged
+        let client = tasks.lock().unwrap().pop().unwrap();
+        client
+            .recv_timeout(core::time::Duration::from_secs(60))
+            .map_err(|err| io::Error::new(io::ErrorKind::TimedOut, err))
+    }
+}
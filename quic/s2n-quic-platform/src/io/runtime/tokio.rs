@@ -0,0 +1,77 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drives the endpoint on a tokio runtime
+
+use crate::io::tokio::Clock;
+use core::{
+    future::Future,
+    task::{ready, Context, Poll},
+};
+use std::{io, net::UdpSocket};
+use tokio::{io::unix::AsyncFd, runtime::Handle};
+
+#[derive(Clone, Debug)]
+pub struct Tokio {
+    handle: Handle,
+}
+
+impl Tokio {
+    pub fn new(handle: Handle) -> Self {
+        Self { handle }
+    }
+
+    /// Uses the tokio runtime in the current scope
+    pub fn current() -> io::Result<Self> {
+        let handle =
+            Handle::try_current().map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        Ok(Self::new(handle))
+    }
+}
+
+impl super::Runtime for Tokio {
+    type Clock = Clock;
+    type Socket = AsyncFd<UdpSocket>;
+
+    #[inline]
+    fn clock(&self) -> Self::Clock {
+        // the clock reads tokio's time source, which requires the runtime context
+        let _guard = self.handle.enter();
+        Clock::default()
+    }
+
+    #[inline]
+    fn spawn<F: 'static + Send + Future<Output = ()>>(&self, task: F) {
+        self.handle.spawn(task);
+    }
+
+    #[inline]
+    fn register(&self, socket: UdpSocket) -> io::Result<Self::Socket> {
+        let _guard = self.handle.enter();
+        AsyncFd::new(socket)
+    }
+}
+
+impl super::Socket for AsyncFd<UdpSocket> {
+    #[inline]
+    fn get_ref(&self) -> &UdpSocket {
+        AsyncFd::get_ref(self)
+    }
+
+    #[inline]
+    fn poll_read_ready(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        // The first poll returns the stale readiness from before the `WouldBlock`, so clear it
+        // before polling again to register the waker. If the socket is ready on the second poll,
+        // the caller should try again.
+        ready!(AsyncFd::poll_read_ready(self, cx))?.clear_ready();
+        ready!(AsyncFd::poll_read_ready(self, cx))?.retain_ready();
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_write_ready(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        ready!(AsyncFd::poll_write_ready(self, cx))?.clear_ready();
+        ready!(AsyncFd::poll_write_ready(self, cx))?.retain_ready();
+        Poll::Ready(Ok(()))
+    }
+}
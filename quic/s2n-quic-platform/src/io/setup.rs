@@ -0,0 +1,223 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Socket setup shared by the socket-based IO providers
+
+use crate::syscall;
+use s2n_quic_core::{
+    event::{self, EndpointPublisher},
+    path::{mtu, MaxMtu},
+};
+use std::io::{self, ErrorKind};
+
+/// The socket options requested by an IO provider builder
+#[derive(Debug, Default)]
+pub(crate) struct Config {
+    pub rx_socket: Option<socket2::Socket>,
+    pub tx_socket: Option<socket2::Socket>,
+    pub recv_addr: Option<std::net::SocketAddr>,
+    pub send_addr: Option<std::net::SocketAddr>,
+    pub socket_recv_buffer_size: Option<usize>,
+    pub socket_send_buffer_size: Option<usize>,
+    pub mtu_config_builder: mtu::Builder,
+    pub gro_enabled: Option<bool>,
+    pub reuse_address: bool,
+    pub reuse_port: bool,
+}
+
+/// The bound and configured sockets for an IO provider
+#[derive(Debug)]
+pub(crate) struct Sockets {
+    pub rx_socket: socket2::Socket,
+    pub tx_socket: socket2::Socket,
+    pub rx_addr: std::net::SocketAddr,
+    pub mtu_config: mtu::Config,
+    pub rx_config: RxConfig,
+}
+
+/// The configuration applied to the receive sockets
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "tokio"), allow(dead_code))] // only the tokio provider shards sockets
+pub(crate) struct RxConfig {
+    /// The configured max MTU, before it was reduced due to `configure_mtu_disc` failing
+    pub original_max_mtu: MaxMtu,
+    pub gro_enabled: bool,
+    pub rxq_ovfl_enabled: bool,
+    socket_recv_buffer_size: Option<usize>,
+    reuse_address: bool,
+    reuse_port: bool,
+}
+
+impl Config {
+    /// Binds the sockets, if needed, and configures the platform features on them
+    ///
+    /// The outcome of each platform feature is published as a `PlatformFeatureConfigured` event.
+    pub fn bind<P: EndpointPublisher>(self, publisher: &mut P) -> io::Result<Sockets> {
+        let Self {
+            rx_socket,
+            tx_socket,
+            recv_addr,
+            send_addr,
+            socket_recv_buffer_size,
+            socket_send_buffer_size,
+            mtu_config_builder,
+            gro_enabled,
+            reuse_address,
+            reuse_port,
+        } = self;
+
+        let rx_socket = if let Some(rx_socket) = rx_socket {
+            rx_socket
+        } else if let Some(recv_addr) = recv_addr {
+            syscall::bind_udp(recv_addr, reuse_address, reuse_port)?
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "missing bind address",
+            ));
+        };
+
+        let rx_addr = convert_addr_to_std(rx_socket.local_addr()?)?;
+
+        let tx_socket = if let Some(tx_socket) = tx_socket {
+            tx_socket
+        } else if let Some(send_addr) = send_addr {
+            syscall::bind_udp(send_addr, reuse_address, reuse_port)?
+        } else {
+            // No tx_socket or send address was specified, so the tx socket
+            // will be a handle to the rx socket.
+            rx_socket.try_clone()?
+        };
+
+        if let Some(size) = socket_send_buffer_size {
+            tx_socket.set_send_buffer_size(size)?;
+        }
+
+        if let Some(size) = socket_recv_buffer_size {
+            rx_socket.set_recv_buffer_size(size)?;
+        }
+
+        let mut mtu_config = mtu_config_builder
+            .build()
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        let original_max_mtu = mtu_config.max_mtu();
+
+        // Configure MTU discovery
+        if !syscall::configure_mtu_disc(&tx_socket) {
+            // disable MTU probing if we can't prevent fragmentation
+            mtu_config = mtu::Config::MIN;
+        }
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::BaseMtu {
+                mtu: mtu_config.base_mtu().into(),
+            },
+        });
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::InitialMtu {
+                mtu: mtu_config.initial_mtu().into(),
+            },
+        });
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::MaxMtu {
+                mtu: mtu_config.max_mtu().into(),
+            },
+        });
+
+        // Configure the socket with GRO
+        let gro_enabled = gro_enabled.unwrap_or(true) && syscall::configure_gro(&rx_socket);
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::Gro {
+                enabled: gro_enabled,
+            },
+        });
+
+        // Configure packet info CMSG
+        syscall::configure_pktinfo(&rx_socket);
+
+        // Configure reporting of datagrams dropped from the receive queue
+        let rxq_ovfl_enabled = syscall::configure_rxq_ovfl(&rx_socket);
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::RxQueueOverflow {
+                enabled: rxq_ovfl_enabled,
+            },
+        });
+
+        // Configure TOS/ECN
+        let tos_enabled = syscall::configure_tos(&rx_socket);
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::Ecn {
+                enabled: tos_enabled,
+            },
+        });
+
+        Ok(Sockets {
+            rx_socket,
+            tx_socket,
+            rx_addr,
+            mtu_config,
+            rx_config: RxConfig {
+                original_max_mtu,
+                gro_enabled,
+                rxq_ovfl_enabled,
+                socket_recv_buffer_size,
+                reuse_address,
+                reuse_port,
+            },
+        })
+    }
+}
+
+impl RxConfig {
+    /// Returns the size of the buffers needed to receive a single message
+    pub fn payload_len(&self) -> u32 {
+        if self.gro_enabled {
+            // if GRO is enabled, then we need to provide the syscall with the maximum size buffer
+            u16::MAX as u32
+        } else {
+            // Use the originally configured MTU to allow larger packets to be received
+            // even if the tx MTU has been reduced due to configure_mtu_disc failing
+            let max_mtu: u16 = self.original_max_mtu.into();
+            max_mtu as u32
+        }
+    }
+
+    /// Binds an additional receive socket to the same address, with the same configuration
+    ///
+    /// The port reuse (SO_REUSEPORT) socket option must be enabled so the operating system shards
+    /// incoming flows between the sockets.
+    #[cfg(feature = "tokio")]
+    pub fn bind_shard(&self, rx_addr: std::net::SocketAddr) -> io::Result<socket2::Socket> {
+        debug_assert!(self.reuse_port);
+        // `rx_addr` is read back from the first socket, so an ephemeral port has
+        // already been resolved and all of the sockets join the same port group
+        debug_assert_ne!(rx_addr.port(), 0);
+
+        let socket = syscall::bind_udp(rx_addr, self.reuse_address, self.reuse_port)?;
+
+        if let Some(size) = self.socket_recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+
+        if self.gro_enabled {
+            syscall::configure_gro(&socket);
+        }
+        syscall::configure_pktinfo(&socket);
+        syscall::configure_tos(&socket);
+        if self.rxq_ovfl_enabled {
+            syscall::configure_rxq_ovfl(&socket);
+        }
+
+        Ok(socket)
+    }
+}
+
+pub(crate) fn convert_addr_to_std(addr: socket2::SockAddr) -> io::Result<std::net::SocketAddr> {
+    addr.as_socket()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid domain for socket"))
+}
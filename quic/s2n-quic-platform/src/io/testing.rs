@@ -16,7 +16,7 @@ pub mod network;
 mod socket;
 pub mod time;
 
-pub use model::{Model, TxRecorder};
+pub use model::{Model, QueueDiscipline, TxRecorder};
 pub use network::{Network, PathHandle};
 pub use socket::Socket;
 pub use time::now;
@@ -358,6 +358,7 @@ impl Io {
             tx,
             rx,
             cooldown: Default::default(),
+            budget: Default::default(),
         };
         let join = executor.spawn(event_loop.start());
         Ok((join, handle))
@@ -3,11 +3,11 @@
 
 use super::network::{Buffers, Network, Packet};
 use core::time::Duration;
-use s2n_quic_core::{havoc, path::MaxMtu};
+use s2n_quic_core::{havoc, path::MaxMtu, time::Timestamp};
 use std::{
     borrow::Cow,
     sync::{
-        atomic::{AtomicU16, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering},
         Arc, Mutex,
     },
 };
@@ -180,6 +180,241 @@ impl Model {
             .store(value, Ordering::SeqCst);
         self
     }
+
+    pub fn bandwidth(&self) -> u64 {
+        self.0.bandwidth.load(Ordering::SeqCst)
+    }
+
+    /// Sets the rate, in bytes per second, at which the network's bottleneck link transmits
+    /// packets
+    ///
+    /// Packets which arrive faster than the link can transmit them are held in a queue, which
+    /// increases their delay. By default, the bandwidth is unlimited.
+    pub fn set_bandwidth(&self, value: u64) -> &Self {
+        self.0.bandwidth.store(value, Ordering::SeqCst);
+        self
+    }
+
+    pub fn burst(&self) -> u64 {
+        self.0.burst.load(Ordering::SeqCst)
+    }
+
+    /// Sets the number of bytes the bottleneck link can transmit at once after being idle
+    ///
+    /// The link is modeled as a token bucket which is refilled at the configured bandwidth and
+    /// holds at most this many bytes worth of tokens.
+    pub fn set_burst(&self, value: u64) -> &Self {
+        self.0.burst.store(value, Ordering::SeqCst);
+        self
+    }
+
+    pub fn queue_limit(&self) -> u64 {
+        self.0.queue_limit.load(Ordering::SeqCst)
+    }
+
+    /// Sets the maximum number of bytes which can be queued at the bottleneck link
+    ///
+    /// Packets which would exceed the limit are dropped. By default, the queue is unbounded.
+    pub fn set_queue_limit(&self, value: u64) -> &Self {
+        self.0.queue_limit.store(value, Ordering::SeqCst);
+        self
+    }
+
+    pub fn queue_discipline(&self) -> QueueDiscipline {
+        self.0.bottleneck.lock().unwrap().discipline
+    }
+
+    /// Sets the active queue management algorithm used by the bottleneck link
+    pub fn set_queue_discipline(&self, value: QueueDiscipline) -> &Self {
+        let mut bottleneck = self.0.bottleneck.lock().unwrap();
+        bottleneck.discipline = value;
+        bottleneck.codel = Default::default();
+        drop(bottleneck);
+        self
+    }
+
+    pub fn port_unreachable(&self) -> bool {
+        self.0.port_unreachable.load(Ordering::SeqCst)
+    }
+
+    /// Sets whether the network reports port unreachable errors
+    ///
+    /// When enabled, the sender of a packet addressed to an unbound address will be notified,
+    /// similar to an ICMP port unreachable message.
+    pub fn set_port_unreachable(&self, value: bool) -> &Self {
+        self.0.port_unreachable.store(value, Ordering::SeqCst);
+        self
+    }
+}
+
+/// The queue management algorithm used by the bottleneck link
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueueDiscipline {
+    /// Packets are dropped once the queue limit is reached
+    #[default]
+    TailDrop,
+    /// Packets are dropped once they've been queued for longer than `target` for at least
+    /// `interval`, as specified in [RFC 8289](https://www.rfc-editor.org/rfc/rfc8289)
+    ///
+    /// Packets are also dropped once the queue limit is reached.
+    CoDel {
+        target: Duration,
+        interval: Duration,
+    },
+}
+
+impl QueueDiscipline {
+    /// Returns the CoDel discipline with the parameters recommended by RFC 8289
+    pub const fn codel() -> Self {
+        Self::CoDel {
+            target: Duration::from_millis(5),
+            interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// The state of the network's bottleneck link
+#[derive(Debug, Default)]
+struct Bottleneck {
+    discipline: QueueDiscipline,
+    /// The time at which the link will have transmitted every queued packet
+    ///
+    /// This is the theoretical arrival time of the generic cell rate algorithm, which is
+    /// equivalent to a token bucket.
+    tat: Option<Timestamp>,
+    codel: CoDel,
+}
+
+impl Bottleneck {
+    /// Returns the amount of time a packet is queued before it's transmitted, or `None` if
+    /// the packet is dropped
+    fn enqueue(
+        &mut self,
+        now: Timestamp,
+        len: usize,
+        bandwidth: u64,
+        burst: u64,
+        queue_limit: u64,
+    ) -> Option<Duration> {
+        if bandwidth == u64::MAX {
+            return Some(Duration::ZERO);
+        }
+
+        let len = len as u64;
+        let tat = self.tat.map_or(now, |tat| tat.max(now));
+        let tolerance = transmission_time(burst, bandwidth);
+        let departure = tat
+            .checked_sub(tolerance)
+            .map_or(now, |departure| departure.max(now));
+        let sojourn = departure.saturating_duration_since(now);
+
+        // the number of bytes which are queued ahead of the packet
+        let queued = (sojourn.as_nanos() * bandwidth as u128 / 1_000_000_000) as u64;
+
+        if queued.saturating_add(len) > queue_limit {
+            debug!("model::drop::queue_limit queued={queued}");
+            return None;
+        }
+
+        if let QueueDiscipline::CoDel { target, interval } = self.discipline {
+            // the queue is FIFO so the packet's dequeue time is already known
+            if self
+                .codel
+                .should_drop(departure, sojourn, queued, len, target, interval)
+            {
+                debug!("model::drop::codel sojourn={sojourn:?}");
+                return None;
+            }
+        }
+
+        self.tat = Some(tat + transmission_time(len, bandwidth));
+
+        Some(sojourn)
+    }
+}
+
+fn transmission_time(bytes: u64, bandwidth: u64) -> Duration {
+    if bandwidth == 0 {
+        return Duration::MAX;
+    }
+
+    let nanos = bytes as u128 * 1_000_000_000 / bandwidth as u128;
+    Duration::from_nanos(nanos.min(u64::MAX as u128) as u64)
+}
+
+/// The dequeue state of the CoDel algorithm
+///
+/// See <https://www.rfc-editor.org/rfc/rfc8289#section-5>
+#[derive(Debug, Default)]
+struct CoDel {
+    first_above_time: Option<Timestamp>,
+    drop_next: Option<Timestamp>,
+    count: u32,
+    last_count: u32,
+    dropping: bool,
+}
+
+impl CoDel {
+    fn should_drop(
+        &mut self,
+        now: Timestamp,
+        sojourn: Duration,
+        queued: u64,
+        len: u64,
+        target: Duration,
+        interval: Duration,
+    ) -> bool {
+        let ok_to_drop = if sojourn < target || queued <= len {
+            self.first_above_time = None;
+            false
+        } else if let Some(first_above_time) = self.first_above_time {
+            now >= first_above_time
+        } else {
+            self.first_above_time = Some(now + interval);
+            false
+        };
+
+        if self.dropping {
+            if !ok_to_drop {
+                self.dropping = false;
+                return false;
+            }
+
+            let drop_next = self.drop_next.unwrap_or(now);
+            if now < drop_next {
+                return false;
+            }
+
+            self.count += 1;
+            self.drop_next = Some(control_law(drop_next, interval, self.count));
+            return true;
+        }
+
+        if !ok_to_drop {
+            return false;
+        }
+
+        self.dropping = true;
+
+        // start at the previous drop rate if the queue was controlled recently
+        let delta = self.count.saturating_sub(self.last_count);
+        self.count = match self.drop_next {
+            Some(drop_next)
+                if delta > 1 && now.saturating_duration_since(drop_next) < interval * 16 =>
+            {
+                delta
+            }
+            _ => 1,
+        };
+        self.drop_next = Some(control_law(now, interval, self.count));
+        self.last_count = self.count;
+
+        true
+    }
+}
+
+fn control_law(time: Timestamp, interval: Duration, count: u32) -> Timestamp {
+    time + interval.div_f64((count as f64).sqrt())
 }
 
 fn rate_to_u64(rate: f64) -> u64 {
@@ -201,6 +436,11 @@ struct State {
     inflight_delay: AtomicU64,
     inflight_delay_threshold: AtomicU64,
     current_inflight: AtomicU64,
+    bandwidth: AtomicU64,
+    burst: AtomicU64,
+    queue_limit: AtomicU64,
+    bottleneck: Mutex<Bottleneck>,
+    port_unreachable: AtomicBool,
 }
 
 impl Default for State {
@@ -218,6 +458,11 @@ impl Default for State {
             inflight_delay: AtomicU64::new(0),
             inflight_delay_threshold: AtomicU64::new(u64::MAX),
             current_inflight: AtomicU64::new(0),
+            bandwidth: AtomicU64::new(u64::MAX),
+            burst: AtomicU64::new(0),
+            queue_limit: AtomicU64::new(u64::MAX),
+            bottleneck: Mutex::new(Bottleneck::default()),
+            port_unreachable: AtomicBool::new(false),
         }
     }
 }
@@ -233,6 +478,12 @@ impl Network for Model {
         let max_udp_payload = self.max_udp_payload() as usize;
         let inflight_delay = self.inflight_delay();
         let inflight_delay_threshold = self.inflight_delay_threshold();
+        let port_unreachable = self.port_unreachable();
+        let bandwidth = self.bandwidth();
+        let burst = self.burst();
+        let queue_limit = self.queue_limit();
+        let bottleneck = self.0.clone();
+        let mut bottleneck = bottleneck.bottleneck.lock().unwrap();
 
         let now = super::time::now();
         let mut transmit_time = now + self.delay();
@@ -264,6 +515,13 @@ impl Network for Model {
                 return 0;
             }
 
+            // hold the packet in the bottleneck queue until the link is able to transmit it
+            let Some(queue_delay) =
+                bottleneck.enqueue(now, packet.payload.len(), bandwidth, burst, queue_limit)
+            else {
+                return 0;
+            };
+
             let mut packet = packet.into_owned();
 
             if !packet.payload.is_empty() && gen_rate(corrupt_rate) {
@@ -287,7 +545,7 @@ impl Network for Model {
             }
 
             // copy the transmit time for this packet
-            let mut transmit_time = *transmit_time;
+            let mut transmit_time = *transmit_time + queue_delay;
 
             if !network_jitter.is_zero() {
                 transmit_time += gen_jitter(network_jitter);
@@ -314,10 +572,18 @@ impl Network for Model {
                     super::time::delay_until(transmit_time).await;
                 }
 
-                buffers.rx(*packet.path.local_address, |queue| {
+                let local_address = *packet.path.local_address;
+                let mut packet = Some(packet);
+
+                buffers.rx(local_address, |queue| {
                     model.0.current_inflight.fetch_sub(1, Ordering::SeqCst);
-                    queue.enqueue(packet);
+                    queue.enqueue(packet.take().unwrap());
                 });
+
+                // the packet wasn't delivered so notify the sender, if enabled
+                if let Some(packet) = packet.filter(|_| port_unreachable) {
+                    buffers.port_unreachable(&packet);
+                }
             });
 
             1
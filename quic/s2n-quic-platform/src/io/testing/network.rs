@@ -1,10 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::message::Message as _;
+use crate::{message::Message as _, socket::error_queue};
 use core::task::{Context, Waker};
 use s2n_quic_core::{
     inet::{ExplicitCongestionNotification, SocketAddress},
+    io::rx::SocketError,
     path::{LocalAddress, MaxMtu, Tuple},
 };
 use std::{
@@ -110,6 +111,25 @@ impl Buffers {
         }
     }
 
+    /// Moves the host to a new address as if the interface its previous address was assigned to
+    /// went down
+    ///
+    /// Unlike [`Self::rebind`], which models a NAT rebinding the host is unaware of, datagrams
+    /// which are still sent from the previous address are rejected with
+    /// [`SocketError::LocalAddressUnavailable`]. This models a socket bound to a wildcard
+    /// address, since it's the only kind of socket which reports the error.
+    pub fn change_interface(&self, host: HostId, addr: std::net::SocketAddr) {
+        self.rebind(host, addr);
+
+        if let Ok(mut lock) = self.inner.lock() {
+            let state = &mut *lock;
+            let errors = state.errors.get(&host).cloned();
+            let queue = state.tx.get_mut(&host).unwrap();
+            queue.is_local_address_checked = true;
+            queue.errors = errors;
+        }
+    }
+
     pub fn tx<F: FnOnce(&mut Queue)>(&self, handle: SocketAddress, f: F) {
         if let Ok(mut lock) = self.inner.lock() {
             let lock = &mut *lock;
@@ -174,6 +194,34 @@ impl Buffers {
         Ok(())
     }
 
+    /// Registers the queue used to report errors back to the host
+    pub fn set_error_queue(&self, host: HostId, producer: error_queue::Producer) {
+        if let Ok(mut lock) = self.inner.lock() {
+            lock.errors.insert(host, producer);
+        }
+    }
+
+    /// Notifies the sender of a packet that its destination isn't bound to a host
+    ///
+    /// The packet is expected to be addressed from the perspective of the receiver.
+    pub fn port_unreachable(&self, packet: &Packet) {
+        if let Ok(lock) = self.inner.lock() {
+            let sender = lock
+                .addr_to_host
+                .get(&*packet.path.remote_address)
+                .and_then(|host| lock.errors.get(host));
+
+            if let Some(errors) = sender {
+                trace!("packet::port_unreachable");
+                errors.push(
+                    *packet.path.local_address,
+                    SocketError::PortUnreachable,
+                    &packet.payload,
+                );
+            }
+        }
+    }
+
     pub fn pending_transmission<F: FnMut(&Packet)>(&self, mut f: F) {
         if let Ok(lock) = self.inner.lock() {
             for queue in lock.tx.values() {
@@ -289,6 +337,7 @@ struct State {
     host_to_addr: HashMap<HostId, Vec<SocketAddress>>,
     tx: HashMap<HostId, Queue>,
     rx: HashMap<HostId, Queue>,
+    errors: HashMap<HostId, error_queue::Producer>,
 }
 
 impl Default for State {
@@ -300,6 +349,7 @@ impl Default for State {
             host_to_addr: Default::default(),
             tx: Default::default(),
             rx: Default::default(),
+            errors: Default::default(),
         }
     }
 }
@@ -319,6 +369,7 @@ impl State {
         }
 
         self.rx.remove(&host);
+        self.errors.remove(&host);
 
         if let Some(addrs) = self.host_to_addr.remove(&host) {
             for addr in addrs {
@@ -337,6 +388,11 @@ pub struct Queue {
     local_address: LocalAddress,
     waker: Option<Waker>,
     is_open: bool,
+    /// Rejects datagrams which are sent from an address other than the queue's, like a socket
+    /// which sets the source address of each datagram
+    is_local_address_checked: bool,
+    /// Used to report datagrams rejected due to their local address
+    errors: Option<error_queue::Producer>,
 }
 
 impl Queue {
@@ -349,6 +405,8 @@ impl Queue {
             local_address,
             waker: None,
             is_open: true,
+            is_local_address_checked: false,
+            errors: None,
         }
     }
 
@@ -422,6 +480,25 @@ impl Queue {
     }
 
     pub fn send_packet(&mut self, mut packet: Packet) {
+        // datagrams which don't specify a local address are sent from the queue's
+        if self.is_local_address_checked
+            && packet.path.local_address.port() != 0
+            && packet.path.local_address.unmap() != self.local_address.unmap()
+        {
+            trace!(
+                "packet::send::local_address_unavailable src={}",
+                packet.path.local_address.0
+            );
+            if let Some(errors) = self.errors.as_ref() {
+                errors.push(
+                    *packet.path.remote_address,
+                    SocketError::LocalAddressUnavailable,
+                    &packet.payload,
+                );
+            }
+            return;
+        }
+
         // update the path with the latest address
         packet.path.local_address = self.local_address;
 
@@ -80,6 +80,13 @@ impl Socket {
         self.0.buffers.rebind(self.0.host, addr);
     }
 
+    /// Moves the socket to a new address as if the interface of its previous address went down
+    ///
+    /// Datagrams which are still sent from the previous address are rejected.
+    pub fn change_interface(&self, addr: std::net::SocketAddr) {
+        self.0.buffers.change_interface(self.0.host, addr);
+    }
+
     /// Sends a packet to the provided destination
     pub fn send_to(
         &self,
@@ -196,7 +203,13 @@ impl Socket {
         let max_mtu = MaxMtu::try_from(payload_len as u16).unwrap();
         let handle = self.local_addr().unwrap();
         let handle = SocketAddress::from(handle);
+
+        // register a queue for the network to report errors for transmitted packets
+        let (error_producer, error_consumer) = crate::socket::error_queue::pair();
+        self.0.buffers.set_error_queue(self.0.host, error_producer);
+
         crate::socket::io::rx::Rx::new(consumers, max_mtu, handle.into())
+            .with_error_queue(error_consumer)
     }
 
     pub fn tx_task(
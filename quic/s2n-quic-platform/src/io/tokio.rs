@@ -1,29 +1,36 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{features::gso, message::default as message, socket, syscall};
+use crate::{features::gso, io::setup, message::default as message, socket, syscall};
 use s2n_quic_core::{
     endpoint::Endpoint,
     event::{self, EndpointPublisher as _},
     inet::{self, SocketAddress},
     io::event_loop::EventLoop,
     path::{mtu, MaxMtu},
-    task::cooldown::Cooldown,
+    task::{budget::Budget, cooldown::Cooldown},
     time::Clock as ClockTrait,
 };
 use std::{convert::TryInto, io, io::ErrorKind};
 use tokio::runtime::Handle;
 
+mod affinity;
 mod builder;
 mod clock;
 pub(crate) mod task;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
 pub type PathHandle = message::Handle;
 pub use builder::Builder;
 pub(crate) use clock::Clock;
 
+/// The number of consecutive busy iterations the endpoint performs before yielding to the runtime
+const DEFAULT_BUDGET: u16 = 32;
+
+/// The number of connection wakeups the endpoint processes in a single iteration
+const DEFAULT_MAX_WAKEUPS: usize = 1024;
+
 #[derive(Debug, Default)]
 pub struct Io {
     builder: Builder,
@@ -57,8 +64,14 @@ impl Io {
             mtu_config_builder,
             max_segments,
             gro_enabled,
+            icmp_errors_enabled,
+            zerocopy_enabled,
+            zerocopy_threshold,
             reuse_address,
             reuse_port,
+            core_affinity,
+            max_endpoint_wakeups,
+            endpoint_budget,
         } = self.builder;
 
         let clock = Clock::default();
@@ -88,96 +101,84 @@ impl Io {
 
         let guard = handle.enter();
 
-        let rx_socket = if let Some(rx_socket) = rx_socket {
-            rx_socket
-        } else if let Some(recv_addr) = recv_addr {
-            syscall::bind_udp(recv_addr, reuse_address, reuse_port)?
-        } else {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "missing bind address",
-            ));
-        };
-
-        let rx_addr = convert_addr_to_std(rx_socket.local_addr()?)?;
+        let mut spawner = core_affinity.map(affinity::Spawner::new);
 
-        let tx_socket = if let Some(tx_socket) = tx_socket {
-            tx_socket
-        } else if let Some(send_addr) = send_addr {
-            syscall::bind_udp(send_addr, reuse_address, reuse_port)?
-        } else {
-            // No tx_socket or send address was specified, so the tx socket
-            // will be a handle to the rx socket.
-            rx_socket.try_clone()?
-        };
+        // only shard the receive socket across cores if we're the ones binding it
+        let shard_rx = reuse_port && spawner.is_some() && rx_socket.is_none();
 
-        if let Some(size) = socket_send_buffer_size {
-            tx_socket.set_send_buffer_size(size)?;
-        }
-
-        if let Some(size) = socket_recv_buffer_size {
-            rx_socket.set_recv_buffer_size(size)?;
-        }
-
-        let mut mtu_config = mtu_config_builder
-            .build()
-            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
-        let original_max_mtu = mtu_config.max_mtu();
-
-        // Configure MTU discovery
-        if !syscall::configure_mtu_disc(&tx_socket) {
-            // disable MTU probing if we can't prevent fragmentation
-            mtu_config = mtu::Config::MIN;
+        let setup::Sockets {
+            rx_socket,
+            tx_socket,
+            rx_addr,
+            mtu_config,
+            rx_config,
+        } = setup::Config {
+            rx_socket,
+            tx_socket,
+            recv_addr,
+            send_addr,
+            socket_recv_buffer_size,
+            socket_send_buffer_size,
+            mtu_config_builder,
+            gro_enabled,
+            reuse_address,
+            reuse_port,
         }
+        .bind(&mut publisher)?;
 
-        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
-            configuration: event::builder::PlatformFeatureConfiguration::BaseMtu {
-                mtu: mtu_config.base_mtu().into(),
-            },
-        });
+        // Configure the tx socket to queue ICMP errors
+        let icmp_errors_enabled =
+            icmp_errors_enabled.unwrap_or(true) && syscall::configure_recverr(&tx_socket);
 
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
-            configuration: event::builder::PlatformFeatureConfiguration::InitialMtu {
-                mtu: mtu_config.initial_mtu().into(),
+            configuration: event::builder::PlatformFeatureConfiguration::IcmpErrors {
+                enabled: icmp_errors_enabled,
             },
         });
 
-        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
-            configuration: event::builder::PlatformFeatureConfiguration::MaxMtu {
-                mtu: mtu_config.max_mtu().into(),
-            },
-        });
+        let tx_socket_count = parse_env("S2N_QUIC_UNSTABLE_TX_SOCKET_COUNT").unwrap_or(1);
 
-        // Configure the socket with GRO
-        let gro_enabled = gro_enabled.unwrap_or(true) && syscall::configure_gro(&rx_socket);
+        // Configure the tx socket to transmit with zero-copy
+        //
+        // The kernel numbers zero-copy sends per socket, so completions can only be attributed
+        // when a single task transmits on it.
+        let zerocopy_enabled =
+            zerocopy_enabled && tx_socket_count == 1 && syscall::configure_zerocopy(&tx_socket);
 
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
-            configuration: event::builder::PlatformFeatureConfiguration::Gro {
-                enabled: gro_enabled,
+            configuration: event::builder::PlatformFeatureConfiguration::Zerocopy {
+                enabled: zerocopy_enabled,
             },
         });
 
-        // Configure packet info CMSG
-        syscall::configure_pktinfo(&rx_socket);
-
-        // Configure TOS/ECN
-        let tos_enabled = syscall::configure_tos(&rx_socket);
+        let (zerocopy_notifier, zerocopy_tracker) = if zerocopy_enabled {
+            let threshold = zerocopy_threshold.unwrap_or(socket::zerocopy::DEFAULT_THRESHOLD);
+            let (notifier, tracker) = socket::zerocopy::pair(threshold);
+            (Some(notifier), Some(tracker))
+        } else {
+            (None, None)
+        };
 
-        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
-            configuration: event::builder::PlatformFeatureConfiguration::Ecn {
-                enabled: tos_enabled,
-            },
-        });
+        // the tx tasks also report datagrams which couldn't be sent from their local address, so
+        // the queue is always created
+        let (error_producer, error_queue) = socket::error_queue::pair();
+
+        // Moving a path off of a local address which became unavailable relies on the operating
+        // system selecting a new source address, which is only possible if the tx socket is bound
+        // to a wildcard address. A socket bound to a specific address can't send from any other
+        // one, so those errors aren't reported.
+        let local_address_errors = is_wildcard_bound(&tx_socket)?.then(|| error_producer.clone());
+
+        // errors and zero-copy completions are queued on the socket that transmitted the
+        // datagram, so read them from the tx socket
+        if icmp_errors_enabled || zerocopy_enabled {
+            let producer = icmp_errors_enabled.then(|| error_producer.clone());
+            let task = task::errors(tx_socket.try_clone()?, producer, zerocopy_notifier);
+            handle.spawn(task);
+        }
 
         let rx = {
-            // if GRO is enabled, then we need to provide the syscall with the maximum size buffer
-            let payload_len = if gro_enabled {
-                u16::MAX
-            } else {
-                // Use the originally configured MTU to allow larger packets to be received
-                // even if the tx MTU has been reduced due to configure_mtu_disc failing
-                original_max_mtu.into()
-            } as u32;
+            let payload_len = rx_config.payload_len();
 
             let rx_buffer_size = queue_recv_buffer_size.unwrap_or(8 * (1 << 20));
             let entries = rx_buffer_size / payload_len;
@@ -190,7 +191,12 @@ impl Io {
 
             let mut consumers = vec![];
 
-            let rx_socket_count = parse_env("S2N_QUIC_UNSTABLE_RX_SOCKET_COUNT").unwrap_or(1);
+            let rx_socket_count =
+                parse_env("S2N_QUIC_UNSTABLE_RX_SOCKET_COUNT").unwrap_or(match &spawner {
+                    // open a socket per core so the OS can shard flows between them
+                    Some(spawner) if shard_rx => spawner.len(),
+                    _ => 1,
+                });
 
             // configure the number of self-wakes before "cooling down" and waiting for epoll to
             // complete
@@ -202,18 +208,33 @@ impl Io {
 
                 // spawn a task that actually reads from the socket into the ring buffer
                 if idx + 1 == rx_socket_count {
-                    handle.spawn(task::rx(rx_socket, producer, rx_cooldown));
+                    let task = task::rx(rx_socket, producer, rx_cooldown);
+                    spawn(&handle, &mut spawner, "rx", task)?;
                     break;
                 } else {
-                    let rx_socket = rx_socket.try_clone()?;
-                    handle.spawn(task::rx(rx_socket, producer, rx_cooldown.clone()));
+                    let rx_socket = if shard_rx {
+                        rx_config.bind_shard(rx_addr)?
+                    } else {
+                        rx_socket.try_clone()?
+                    };
+                    let task = task::rx(rx_socket, producer, rx_cooldown.clone());
+                    spawn(&handle, &mut spawner, "rx", task)?;
                 }
             }
 
             // construct the RX side for the endpoint event loop
             let max_mtu = MaxMtu::try_from(payload_len as u16).unwrap();
             let addr: inet::SocketAddress = rx_addr.into();
-            socket::io::rx::Rx::new(consumers, max_mtu, addr.into())
+            let rx = socket::io::rx::Rx::new(consumers, max_mtu, addr.into());
+
+            // unless the sockets are sharded, the channels read from clones of the same socket
+            let rx = if shard_rx {
+                rx
+            } else {
+                rx.with_shared_socket()
+            };
+
+            rx.with_error_queue(error_queue)
         };
 
         let tx = {
@@ -237,8 +258,6 @@ impl Io {
 
             let mut producers = vec![];
 
-            let tx_socket_count = parse_env("S2N_QUIC_UNSTABLE_TX_SOCKET_COUNT").unwrap_or(1);
-
             // configure the number of self-wakes before "cooling down" and waiting for epoll to
             // complete
             let tx_cooldown = cooldown("TX");
@@ -249,16 +268,27 @@ impl Io {
 
                 // spawn a task that actually flushes the ring buffer to the socket
                 if idx + 1 == tx_socket_count {
-                    handle.spawn(task::tx(tx_socket, consumer, gso.clone(), tx_cooldown));
+                    let task = task::tx(
+                        tx_socket,
+                        consumer,
+                        gso.clone(),
+                        tx_cooldown,
+                        zerocopy_tracker,
+                        local_address_errors,
+                    );
+                    spawn(&handle, &mut spawner, "tx", task)?;
                     break;
                 } else {
                     let tx_socket = tx_socket.try_clone()?;
-                    handle.spawn(task::tx(
+                    let task = task::tx(
                         tx_socket,
                         consumer,
                         gso.clone(),
                         tx_cooldown.clone(),
-                    ));
+                        None,
+                        local_address_errors.clone(),
+                    );
+                    spawn(&handle, &mut spawner, "tx", task)?;
                 }
             }
 
@@ -269,16 +299,24 @@ impl Io {
         // Notify the endpoint of the MTU that we chose
         endpoint.set_mtu_config(mtu_config);
 
-        let task = handle.spawn(
-            EventLoop {
-                endpoint,
-                clock,
-                rx,
-                tx,
-                cooldown: cooldown("ENDPOINT"),
-            }
-            .start(),
-        );
+        // Bound the amount of work the endpoint performs between yields to the runtime
+        endpoint.set_max_wakeups(max_endpoint_wakeups.unwrap_or_else(max_wakeups));
+
+        let event_loop = EventLoop {
+            endpoint,
+            clock,
+            rx,
+            tx,
+            cooldown: cooldown("ENDPOINT"),
+            budget: endpoint_budget.map_or_else(|| budget("ENDPOINT"), Budget::new),
+        }
+        .start();
+
+        let task = if let Some(spawner) = spawner.as_mut() {
+            spawner.spawn_with_handle("endpoint", event_loop)?
+        } else {
+            handle.spawn(event_loop)
+        };
 
         drop(guard);
 
@@ -286,9 +324,29 @@ impl Io {
     }
 }
 
-fn convert_addr_to_std(addr: socket2::SockAddr) -> io::Result<std::net::SocketAddr> {
-    addr.as_socket()
-        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid domain for socket"))
+/// Spawns a task on a pinned thread, if configured, otherwise on the provided runtime handle
+fn spawn<F>(
+    handle: &Handle,
+    spawner: &mut Option<affinity::Spawner>,
+    name: &str,
+    task: F,
+) -> io::Result<()>
+where
+    F: 'static + Send + core::future::Future,
+    F::Output: 'static + Send,
+{
+    if let Some(spawner) = spawner.as_mut() {
+        spawner.spawn(name, task)
+    } else {
+        handle.spawn(task);
+        Ok(())
+    }
+}
+
+/// Returns `true` if the socket is bound to the unspecified address of its family
+fn is_wildcard_bound(socket: &socket2::Socket) -> io::Result<bool> {
+    let addr = setup::convert_addr_to_std(socket.local_addr()?)?;
+    Ok(addr.ip().is_unspecified())
 }
 
 fn parse_env<T: core::str::FromStr>(name: &str) -> Option<T> {
@@ -300,3 +358,13 @@ pub fn cooldown(direction: &str) -> Cooldown {
     let limit = parse_env(&name).unwrap_or(0);
     Cooldown::new(limit)
 }
+
+pub fn budget(task: &str) -> Budget {
+    let name = format!("S2N_QUIC_UNSTABLE_BUDGET_{task}");
+    let limit = parse_env(&name).unwrap_or(DEFAULT_BUDGET);
+    Budget::new(limit)
+}
+
+pub fn max_wakeups() -> usize {
+    parse_env("S2N_QUIC_UNSTABLE_MAX_WAKEUPS").unwrap_or(DEFAULT_MAX_WAKEUPS)
+}
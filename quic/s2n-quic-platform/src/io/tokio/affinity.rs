@@ -0,0 +1,125 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::syscall;
+use core::future::Future;
+use std::io;
+use tokio::{runtime, sync::watch, task::JoinHandle};
+
+/// Spawns tasks onto dedicated threads, each pinned to a core in the configured set
+///
+/// Cores are assigned in a round-robin fashion so that consecutive tasks are spread across
+/// the set.
+///
+/// All of the threads exit once the task returned by [`Self::spawn_with_handle`] completes or is
+/// aborted, or if the spawner is dropped before it's called.
+#[derive(Debug)]
+pub(crate) struct Spawner {
+    cores: Vec<usize>,
+    next: usize,
+    /// Dropping the sender signals the threads to shut down
+    shutdown: Option<watch::Sender<()>>,
+    on_shutdown: watch::Receiver<()>,
+}
+
+impl Spawner {
+    pub fn new(cores: Vec<usize>) -> Self {
+        debug_assert!(!cores.is_empty(), "core set cannot be empty");
+        let (shutdown, on_shutdown) = watch::channel(());
+        Self {
+            cores,
+            next: 0,
+            shutdown: Some(shutdown),
+            on_shutdown,
+        }
+    }
+
+    /// Returns the number of cores in the set
+    pub fn len(&self) -> usize {
+        self.cores.len()
+    }
+
+    /// Spawns a task onto a pinned thread, running it until it completes or the spawner shuts
+    /// down
+    pub fn spawn<F>(&mut self, name: &str, task: F) -> io::Result<()>
+    where
+        F: 'static + Send + Future,
+    {
+        let runtime = Self::runtime()?;
+        let mut on_shutdown = self.on_shutdown.clone();
+        self.spawn_thread(name, move || {
+            runtime.block_on(async move {
+                tokio::select! {
+                    _ = task => {}
+                    // the sender is never used to send a value so this only returns once it's
+                    // dropped
+                    _ = on_shutdown.changed() => {}
+                }
+            });
+        })
+    }
+
+    /// Spawns a task onto a pinned thread and returns a handle to it
+    ///
+    /// All of the threads started by the spawner exit once the task completes or the returned
+    /// handle is aborted.
+    pub fn spawn_with_handle<F>(&mut self, name: &str, task: F) -> io::Result<JoinHandle<()>>
+    where
+        F: 'static + Send + Future<Output = ()>,
+    {
+        let runtime = Self::runtime()?;
+        let shutdown = self.shutdown.take().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "only one task can be spawned with a handle",
+            )
+        })?;
+
+        let task = runtime.spawn(async move {
+            // the sender is moved into the task so the threads are notified even if the task is
+            // aborted
+            let _shutdown = shutdown;
+            task.await
+        });
+
+        let mut on_shutdown = self.on_shutdown.clone();
+        self.spawn_thread(name, move || {
+            runtime.block_on(async move {
+                let _ = on_shutdown.changed().await;
+            });
+        })?;
+
+        Ok(task)
+    }
+
+    fn runtime() -> io::Result<runtime::Runtime> {
+        runtime::Builder::new_current_thread().enable_all().build()
+    }
+
+    fn spawn_thread<F: 'static + Send + FnOnce()>(&mut self, name: &str, f: F) -> io::Result<()> {
+        let core = self.cores[self.next % self.cores.len()];
+        self.next += 1;
+
+        let (result_tx, result_rx) = std::sync::mpsc::sync_channel(1);
+
+        std::thread::Builder::new()
+            .name(format!("s2n-quic-{name}-{core}"))
+            .spawn(move || {
+                let result = syscall::set_cpu_affinity(&[core]);
+                let is_ok = result.is_ok();
+                let _ = result_tx.send(result);
+
+                if is_ok {
+                    f();
+                }
+            })?;
+
+        // wait for the thread to report if pinning was successful
+        result_rx.recv().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "pinned thread exited before reporting its status",
+            )
+        })?
+    }
+}
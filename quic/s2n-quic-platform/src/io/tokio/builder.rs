@@ -3,6 +3,12 @@
 
 use super::*;
 
+/// Configures the sockets and tasks of the tokio IO provider
+///
+/// Client connections are moved to a new local address when their current one is removed from the
+/// host. This requires the transmit socket to be bound to a wildcard address, such as `0.0.0.0` or
+/// `[::]`, so the operating system can select the new source address. Connections on sockets bound
+/// to a specific address stay on their path.
 #[derive(Debug, Default)]
 pub struct Builder {
     pub(super) handle: Option<Handle>,
@@ -17,8 +23,14 @@ pub struct Builder {
     pub(super) mtu_config_builder: mtu::Builder,
     pub(super) max_segments: gso::MaxSegments,
     pub(super) gro_enabled: Option<bool>,
+    pub(super) icmp_errors_enabled: Option<bool>,
+    pub(super) zerocopy_enabled: bool,
+    pub(super) zerocopy_threshold: Option<u16>,
     pub(super) reuse_address: bool,
     pub(super) reuse_port: bool,
+    pub(super) core_affinity: Option<Vec<usize>>,
+    pub(super) max_endpoint_wakeups: Option<usize>,
+    pub(super) endpoint_budget: Option<u16>,
 }
 
 impl Builder {
@@ -218,6 +230,42 @@ impl Builder {
         }
     }
 
+    /// Configures reading ICMP errors from the socket error queue
+    ///
+    /// When enabled, errors reported by the network for transmitted datagrams are passed to the
+    /// affected connections. A port or host unreachable error will close a connection which
+    /// hasn't completed its handshake, rather than waiting for it to time out, and a packet too
+    /// big error will reduce the MTU used on the path.
+    ///
+    /// By default, ICMP errors will be read unless the platform does not support it.
+    pub fn with_icmp_errors(mut self, enabled: bool) -> io::Result<Self> {
+        self.icmp_errors_enabled = Some(enabled);
+        Ok(self)
+    }
+
+    /// Configures transmitting with zero-copy (MSG_ZEROCOPY)
+    ///
+    /// When enabled, the kernel reads large payloads directly from the transmit queue rather than
+    /// copying them, which reduces CPU usage for bulk transfers. Payloads smaller than the
+    /// [threshold](Self::with_zerocopy_threshold) are still copied, since pinning the pages costs
+    /// more than the copy. If the kernel reports copying the payloads anyway, for example when
+    /// routing over the loopback interface, zero-copy is disabled for the remainder of the runtime.
+    ///
+    /// By default, zero-copy is disabled. It is only used on Linux 5.0+ with a single tx socket.
+    pub fn with_zerocopy(mut self, enabled: bool) -> io::Result<Self> {
+        self.zerocopy_enabled = enabled;
+        Ok(self)
+    }
+
+    /// Sets the minimum payload size, in bytes, for a datagram to be transmitted with zero-copy
+    /// (default: 10240)
+    ///
+    /// When GSO is enabled, this is compared against the combined size of the segments.
+    pub fn with_zerocopy_threshold(mut self, threshold: u16) -> io::Result<Self> {
+        self.zerocopy_threshold = Some(threshold);
+        Ok(self)
+    }
+
     /// Enables the address reuse (SO_REUSEADDR) socket option
     pub fn with_reuse_address(mut self, enabled: bool) -> io::Result<Self> {
         self.reuse_address = enabled;
@@ -236,6 +284,67 @@ impl Builder {
         Ok(self)
     }
 
+    /// Pins the IO and endpoint tasks to the provided set of CPU cores
+    ///
+    /// Each task is spawned onto a dedicated thread with its own single-threaded runtime, which is
+    /// pinned to one of the cores in the set in a round-robin fashion. This avoids tasks migrating
+    /// between cores and bouncing cachelines across NUMA nodes on large hosts.
+    ///
+    /// If the port reuse (SO_REUSEPORT) socket option is also enabled and the runtime is binding
+    /// to a receive address, a receive socket is opened per core, allowing the operating system to
+    /// shard incoming flows across them.
+    ///
+    /// NOTE: this option is currently only supported on Linux
+    pub fn with_core_affinity<I: IntoIterator<Item = usize>>(
+        mut self,
+        cores: I,
+    ) -> io::Result<Self> {
+        if !cfg!(target_os = "linux") {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "core_affinity is not supported on the current platform",
+            ));
+        }
+
+        let mut cores: Vec<usize> = cores.into_iter().collect();
+        cores.sort_unstable();
+        cores.dedup();
+
+        if cores.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "core_affinity requires at least one core",
+            ));
+        }
+
+        self.core_affinity = Some(cores);
+        Ok(self)
+    }
+
+    /// Sets the maximum number of connection wakeups the endpoint processes per iteration
+    ///
+    /// Any remaining wakeups are deferred to the next iteration, which bounds the amount of work
+    /// performed between yields to the runtime.
+    pub fn with_max_endpoint_wakeups(mut self, max_wakeups: usize) -> io::Result<Self> {
+        if max_wakeups == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "max_endpoint_wakeups must be at least 1",
+            ));
+        }
+        self.max_endpoint_wakeups = Some(max_wakeups);
+        Ok(self)
+    }
+
+    /// Sets the number of consecutive busy iterations the endpoint task performs before yielding
+    ///
+    /// A value of `0` disables cooperative yielding, which allows a busy endpoint to monopolize
+    /// the worker thread it's running on.
+    pub fn with_endpoint_budget(mut self, iterations: u16) -> io::Result<Self> {
+        self.endpoint_budget = Some(iterations);
+        Ok(self)
+    }
+
     pub fn build(self) -> io::Result<Io> {
         Ok(Io { builder: self })
     }
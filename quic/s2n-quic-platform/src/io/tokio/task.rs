@@ -23,7 +23,11 @@ macro_rules! libc_msg {
         #[cfg($cfg)]
         mod $message {
             use super::unix;
-            use crate::{features::Gso, message::$message::Message, socket::ring};
+            use crate::{
+                features::Gso,
+                message::$message::Message,
+                socket::{error_queue, ring, zerocopy},
+            };
             use s2n_quic_core::task::cooldown::Cooldown;
 
             pub async fn rx<S: Into<std::net::UdpSocket>>(
@@ -39,12 +43,29 @@ macro_rules! libc_msg {
                 consumer: ring::Consumer<Message>,
                 gso: Gso,
                 cooldown: Cooldown,
+                zerocopy: Option<zerocopy::Tracker>,
+                errors: Option<error_queue::Producer>,
             ) -> std::io::Result<()> {
-                unix::tx(socket, consumer, gso, cooldown).await
+                unix::tx(socket, consumer, gso, cooldown, zerocopy, errors).await
             }
         }
     };
 }
 
+cfg_if::cfg_if! {
+    if #[cfg(all(s2n_quic_platform_recverr, s2n_quic_platform_socket_msg))] {
+        pub use unix::errors;
+    } else {
+        /// Reading from the socket error queue isn't supported on the current platform
+        pub async fn errors<S>(
+            _socket: S,
+            _producer: Option<crate::socket::error_queue::Producer>,
+            _zerocopy: Option<crate::socket::zerocopy::Notifier>,
+        ) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+}
+
 libc_msg!(msg, s2n_quic_platform_socket_msg);
 libc_msg!(mmsg, s2n_quic_platform_socket_mmsg);
@@ -7,6 +7,7 @@ use crate::{
     socket::{
         ring, task,
         task::{rx, tx},
+        zerocopy,
     },
     syscall::SocketEvents,
 };
@@ -36,6 +37,10 @@ pub async fn tx<S: Into<std::net::UdpSocket>>(
     consumer: ring::Consumer<Message>,
     gso: Gso,
     cooldown: Cooldown,
+    // zero-copy isn't supported on the simple socket
+    _zerocopy: Option<zerocopy::Tracker>,
+    // the simple socket doesn't set the local address of datagrams so it can't become unusable
+    _errors: Option<crate::socket::error_queue::Producer>,
 ) -> io::Result<()> {
     let socket = socket.into();
     socket.set_nonblocking(true).unwrap();
@@ -4,8 +4,9 @@
 use crate::{
     features::Gso,
     socket::{
-        ring,
+        error_queue, ring,
         task::{rx, tx},
+        zerocopy,
     },
     syscall::{SocketType, UnixMessage},
 };
@@ -36,12 +37,21 @@ pub async fn tx<S: Into<std::net::UdpSocket>, M: UnixMessage + Unpin>(
     consumer: ring::Consumer<M>,
     gso: Gso,
     cooldown: Cooldown,
+    zerocopy: Option<zerocopy::Tracker>,
+    errors: Option<error_queue::Producer>,
 ) -> io::Result<()> {
     let socket = socket.into();
     socket.set_nonblocking(true).unwrap();
 
     let socket = AsyncFd::new(socket).unwrap();
-    let result = tx::Sender::new(consumer, socket, gso, cooldown).await;
+    let mut sender = tx::Sender::new(consumer, socket, gso, cooldown);
+    if let Some(zerocopy) = zerocopy {
+        sender = sender.with_zerocopy(zerocopy);
+    }
+    if let Some(errors) = errors {
+        sender = sender.with_error_queue(errors);
+    }
+    let result = sender.await;
     if let Some(err) = result {
         Err(err)
     } else {
@@ -49,6 +59,80 @@ pub async fn tx<S: Into<std::net::UdpSocket>, M: UnixMessage + Unpin>(
     }
 }
 
+/// Reads the socket error queue and passes errors reported by the network to the endpoint and
+/// zero-copy completions to the transmit task
+#[cfg(all(s2n_quic_platform_recverr, s2n_quic_platform_socket_msg))]
+pub async fn errors<S: Into<std::net::UdpSocket>>(
+    socket: S,
+    producer: Option<crate::socket::error_queue::Producer>,
+    zerocopy: Option<crate::socket::zerocopy::Notifier>,
+) -> io::Result<()> {
+    use crate::{
+        socket::error_queue::MAX_QUOTE_LEN,
+        syscall::msg::{recv_error, QueuedError},
+    };
+    use tokio::io::Interest;
+
+    let socket = socket.into();
+    socket.set_nonblocking(true)?;
+
+    // errors are always reported by epoll so only register interest in those
+    let socket = AsyncFd::with_interest(socket, Interest::ERROR)?;
+    let mut quote = [0; MAX_QUOTE_LEN];
+
+    // shut down the task once nothing is left to notify
+    let closed = || {
+        core::future::poll_fn(|cx| {
+            let is_producer_closed = producer
+                .as_ref()
+                .map_or(true, |producer| producer.poll_closed(cx).is_ready());
+            let is_zerocopy_closed = zerocopy
+                .as_ref()
+                .map_or(true, |zerocopy| zerocopy.poll_closed(cx).is_ready());
+
+            if is_producer_closed && is_zerocopy_closed {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+    };
+
+    loop {
+        let mut guard = tokio::select! {
+            guard = socket.ready(Interest::ERROR) => guard?,
+            _ = closed() => return Ok(()),
+        };
+
+        // drain the error queue
+        loop {
+            match recv_error(guard.get_inner(), &mut quote) {
+                Ok(Some(QueuedError::Path {
+                    remote_address,
+                    error,
+                    len,
+                })) => {
+                    if let Some(producer) = producer.as_ref() {
+                        producer.push(remote_address, error, &quote[..len]);
+                    }
+                }
+                Ok(Some(QueuedError::Zerocopy(completion))) => {
+                    if let Some(zerocopy) = zerocopy.as_ref() {
+                        zerocopy.on_completion(completion);
+                    }
+                }
+                Ok(None) => continue,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    guard.clear_ready();
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
 impl<S: AsRawFd, M: UnixMessage> tx::Socket<M> for AsyncFd<S> {
     type Error = io::Error;
 
@@ -65,7 +149,15 @@ impl<S: AsRawFd, M: UnixMessage> tx::Socket<M> for AsyncFd<S> {
         //       assume the socket is ready in the general case and then fall back to querying
         //       socket readiness if it's not. This can avoid some things like having to construct
         //       a `std::io::Error` with `WouldBlock` and dereferencing the registration.
-        M::send(self.get_ref().as_raw_fd(), entries, events);
+        M::send(
+            self.get_ref().as_raw_fd(),
+            entries,
+            events.send_flags(),
+            events,
+        );
+
+        // the endpoint can move its connections off of any local addresses that went away
+        events.report_local_address_errors(entries, M::destination_address);
 
         // yield back if we weren't blocked
         if !events.is_blocked() {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::io::setup::convert_addr_to_std;
 use core::{
     convert::TryInto,
     task::{Context, Poll},
@@ -16,7 +17,7 @@ use s2n_quic_core::{
 };
 use std::{collections::BTreeMap, net::ToSocketAddrs};
 
-struct TestEndpoint<const IS_SERVER: bool> {
+pub(crate) struct TestEndpoint<const IS_SERVER: bool> {
     handle: PathHandle,
     messages: BTreeMap<u32, Option<Timestamp>>,
     now: Option<Timestamp>,
@@ -24,7 +25,7 @@ struct TestEndpoint<const IS_SERVER: bool> {
 }
 
 impl<const IS_SERVER: bool> TestEndpoint<IS_SERVER> {
-    fn new(handle: PathHandle) -> Self {
+    pub(crate) fn new(handle: PathHandle) -> Self {
         let messages = if IS_SERVER { 0 } else { 30 };
         let messages = (0..messages).map(|id| (id, None)).collect();
         Self {
@@ -37,7 +38,7 @@ impl<const IS_SERVER: bool> TestEndpoint<IS_SERVER> {
 }
 
 #[derive(Debug, Default)]
-struct NoopSubscriber;
+pub(crate) struct NoopSubscriber;
 
 impl event::Subscriber for NoopSubscriber {
     type ConnectionContext = ();
@@ -181,6 +182,15 @@ async fn test<A: ToSocketAddrs>(
     let (server_io, server_addr) = runtime(server_rx_addr, server_tx_addr).await?;
     let (client_io, client_addr) = runtime(client_rx_addr, client_tx_addr).await?;
 
+    run(server_io, server_addr, client_io, client_addr).await
+}
+
+async fn run(
+    server_io: super::Io,
+    server_addr: SocketAddress,
+    client_io: super::Io,
+    client_addr: SocketAddress,
+) -> io::Result<()> {
     let server_endpoint = {
         let mut handle = PathHandle::from_remote_address(client_addr.into());
         handle.local_address = server_addr.into();
@@ -260,3 +270,196 @@ async fn ipv6_two_socket_test() -> io::Result<()> {
         other => other,
     }
 }
+
+/// Ensures the endpoints make progress with a minimal budget and the runtime stays responsive
+#[tokio::test(flavor = "current_thread")]
+#[cfg_attr(miri, ignore)]
+async fn endpoint_budget_test() -> io::Result<()> {
+    let mut endpoints = vec![];
+    for _ in 0..2 {
+        let socket = syscall::bind_udp(IPV4_LOCALHOST, false, false)?;
+        socket.set_nonblocking(true)?;
+        let socket: std::net::UdpSocket = socket.into();
+        let addr = socket.local_addr()?;
+
+        let io = Io::builder()
+            .with_rx_socket(socket)?
+            .with_max_endpoint_wakeups(1)?
+            .with_endpoint_budget(1)?
+            .build()?;
+
+        endpoints.push((io, SocketAddress::from(addr)));
+    }
+
+    let (client_io, client_addr) = endpoints.pop().unwrap();
+    let (server_io, server_addr) = endpoints.pop().unwrap();
+
+    // a task sharing the single-threaded runtime should keep making progress
+    let ticker = tokio::spawn(async {
+        let mut ticks = 0u64;
+        loop {
+            tokio::task::yield_now().await;
+            ticks += 1;
+            if ticks == 100 {
+                return ticks;
+            }
+        }
+    });
+
+    run(server_io, server_addr, client_io, client_addr).await?;
+
+    assert_eq!(ticker.await.unwrap(), 100);
+
+    Ok(())
+}
+
+#[test]
+fn endpoint_budget_config_test() {
+    assert!(Io::builder().with_max_endpoint_wakeups(0).is_err());
+    assert!(Io::builder().with_max_endpoint_wakeups(1).is_ok());
+    assert!(Io::builder().with_endpoint_budget(0).is_ok());
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+#[cfg_attr(miri, ignore)]
+async fn core_affinity_test() -> io::Result<()> {
+    // spread the tasks across the cores we're allowed to run on, which may not start at 0 when
+    // the process is restricted to a subset of cores (e.g. with `taskset` or cgroups)
+    let cores: Vec<usize> = syscall::get_cpu_affinity()?.into_iter().take(4).collect();
+
+    let mut endpoints = vec![];
+    for _ in 0..2 {
+        // resolve an ephemeral port up front so the endpoints know each other's addresses
+        let addr = {
+            let socket = syscall::bind_udp(IPV4_LOCALHOST, false, false)?;
+            convert_addr_to_std(socket.local_addr()?)?
+        };
+
+        let io = Io::builder()
+            .with_receive_address(addr)?
+            .with_reuse_port()?
+            .with_core_affinity(cores.iter().copied())?
+            .build()?;
+
+        endpoints.push((io, SocketAddress::from(addr)));
+    }
+
+    let (client_io, client_addr) = endpoints.pop().unwrap();
+    let (server_io, server_addr) = endpoints.pop().unwrap();
+
+    run(server_io, server_addr, client_io, client_addr).await
+}
+
+/// Ensures the pinned threads exit once the endpoint task is aborted
+#[tokio::test]
+#[cfg(target_os = "linux")]
+#[cfg_attr(miri, ignore)]
+async fn core_affinity_shutdown_test() -> io::Result<()> {
+    use std::sync::mpsc;
+
+    /// Reports when the task it was moved into is dropped
+    struct OnDrop(mpsc::Sender<()>);
+
+    impl Drop for OnDrop {
+        fn drop(&mut self) {
+            let _ = self.0.send(());
+        }
+    }
+
+    let cores: Vec<usize> = syscall::get_cpu_affinity()?.into_iter().take(2).collect();
+    let mut spawner = affinity::Spawner::new(cores);
+    let (dropped_tx, dropped_rx) = mpsc::channel();
+
+    for name in ["rx", "tx"] {
+        let guard = OnDrop(dropped_tx.clone());
+        spawner.spawn(name, async move {
+            let _guard = guard;
+            core::future::pending::<()>().await
+        })?;
+    }
+
+    let guard = OnDrop(dropped_tx);
+    let task = spawner.spawn_with_handle("endpoint", async move {
+        let _guard = guard;
+        core::future::pending::<()>().await
+    })?;
+
+    // nothing is dropped while the endpoint task is running
+    let timeout = core::time::Duration::from_millis(100);
+    assert!(dropped_rx.recv_timeout(timeout).is_err());
+
+    task.abort();
+
+    let timeout = core::time::Duration::from_secs(5);
+    for _ in 0..3 {
+        dropped_rx.recv_timeout(timeout).unwrap();
+    }
+
+    Ok(())
+}
+
+/// Ensures sockets sharded with `SO_REUSEPORT` share the port resolved by the first socket when
+/// binding to port 0
+#[test]
+#[cfg(target_os = "linux")]
+#[cfg_attr(miri, ignore)]
+fn reuse_port_ephemeral_test() -> io::Result<()> {
+    let socket = syscall::bind_udp(IPV4_LOCALHOST, false, true)?;
+    let addr = convert_addr_to_std(socket.local_addr()?)?;
+    assert_ne!(addr.port(), 0);
+
+    let sharded = syscall::bind_udp(addr, false, true)?;
+    assert_eq!(addr, convert_addr_to_std(sharded.local_addr()?)?);
+
+    Ok(())
+}
+
+/// Ensures only sockets bound to a wildcard address report datagrams which couldn't be sent from
+/// their local address
+#[test]
+#[cfg_attr(miri, ignore)]
+fn wildcard_bound_test() -> io::Result<()> {
+    let socket = syscall::bind_udp(IPV4_LOCALHOST, false, false)?;
+    assert!(!is_wildcard_bound(&socket)?);
+
+    let socket = syscall::bind_udp("0.0.0.0:0", false, false)?;
+    assert!(is_wildcard_bound(&socket)?);
+
+    // IPv6 may not be available in the test environment
+    if let Ok(socket) = syscall::bind_udp("[::]:0", false, false) {
+        assert!(is_wildcard_bound(&socket)?);
+    }
+
+    Ok(())
+}
+
+/// Ensures the endpoints make progress when transmitting with zero-copy
+///
+/// The loopback interface copies the payloads, so this also exercises falling back to copying
+/// once the first completions are read.
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn zerocopy_test() -> io::Result<()> {
+    let mut endpoints = vec![];
+    for _ in 0..2 {
+        let socket = syscall::bind_udp(IPV4_LOCALHOST, false, false)?;
+        socket.set_nonblocking(true)?;
+        let socket: std::net::UdpSocket = socket.into();
+        let addr = socket.local_addr()?;
+
+        let io = Io::builder()
+            .with_rx_socket(socket)?
+            .with_zerocopy(true)?
+            // send every datagram with zero-copy, since the test payloads are tiny
+            .with_zerocopy_threshold(0)?
+            .build()?;
+
+        endpoints.push((io, SocketAddress::from(addr)));
+    }
+
+    let (client_io, client_addr) = endpoints.pop().unwrap();
+    let (server_io, server_addr) = endpoints.pop().unwrap();
+
+    run(server_io, server_addr, client_io, client_addr).await
+}
@@ -120,6 +120,7 @@ impl Io {
             tx,
             endpoint,
             cooldown: Default::default(),
+            budget: Default::default(),
         }
         .start();
 
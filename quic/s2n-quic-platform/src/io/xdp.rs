@@ -77,7 +77,7 @@ pub mod tx {
 
         // spawn a task that actually flushes the ring buffer to the socket
         let cooldown = s2n_quic_core::task::cooldown::Cooldown::default();
-        let task = crate::io::tokio::task::tx(socket, consumer, gso.clone(), cooldown);
+        let task = crate::io::tokio::task::tx(socket, consumer, gso.clone(), cooldown, None);
 
         // construct the TX side for the endpoint event loop
         let io = crate::socket::io::tx::Tx::new(producers, gso, max_mtu);
@@ -149,6 +149,7 @@ where
 
         // tell the endpoint what our MTU is
         endpoint.set_mtu_config(mtu_config);
+        endpoint.set_max_wakeups(crate::io::tokio::max_wakeups());
 
         // create a tokio clock
         let clock = Clock::new();
@@ -160,6 +161,7 @@ where
             rx,
             tx,
             cooldown: crate::io::tokio::cooldown("ENDPOINT"),
+            budget: crate::io::tokio::budget("ENDPOINT"),
         };
 
         // spawn the event loop on to the tokio handle
@@ -134,26 +134,77 @@ pub trait Message: 'static + Copy {
 pub struct RxMessage<'a, Handle: Copy> {
     /// The received header for the message
     pub header: datagram::Header<Handle>,
-    /// The number of segments inside the message
+    /// The size of each segment inside the message
+    ///
+    /// When the message was coalesced with GRO, all of the segments are this size, except for the
+    /// last one, which can be smaller.
     pub segment_size: usize,
     /// The full payload of the message
     pub payload: &'a mut [u8],
+    /// The cumulative number of datagrams the OS dropped from the socket's receive queue, if
+    /// reported with the message
+    pub rx_queue_overflow: Option<u32>,
 }
 
 impl<'a, Handle: Copy> RxMessage<'a, Handle> {
     #[inline]
     pub fn for_each<F: FnMut(datagram::Header<Handle>, &mut [u8])>(self, mut on_packet: F) {
-        // `chunks_mut` doesn't know what to do with zero-sized segments so return early
-        if self.segment_size == 0 {
-            return;
+        let header = self.header;
+        for segment in self.segments() {
+            on_packet(header, segment);
         }
+    }
 
-        for segment in self.payload.chunks_mut(self.segment_size) {
-            on_packet(self.header, segment);
+    /// Returns an iterator over the segments in the message
+    ///
+    /// The segments borrow from the payload so no copies are made.
+    #[inline]
+    pub fn segments(self) -> Segments<'a> {
+        Segments {
+            payload: self.payload,
+            segment_size: self.segment_size,
         }
     }
 }
 
+/// Splits a coalesced payload into segments
+pub struct Segments<'a> {
+    payload: &'a mut [u8],
+    segment_size: usize,
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = &'a mut [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // zero-sized segments can't make progress
+        if self.payload.is_empty() || self.segment_size == 0 {
+            return None;
+        }
+
+        let payload = core::mem::take(&mut self.payload);
+        // the last segment is allowed to be smaller than the others
+        let len = self.segment_size.min(payload.len());
+        let (segment, remaining) = payload.split_at_mut(len);
+        self.payload = remaining;
+
+        Some(segment)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.segment_size == 0 {
+            0
+        } else {
+            (self.payload.len() + self.segment_size - 1) / self.segment_size
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Segments<'a> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,14 +228,37 @@ mod tests {
                     header: datagram::Header { path, ecn },
                     segment_size,
                     payload: &mut payload,
+                    rx_queue_overflow: None,
+                };
+
+                let expected_segments = if segment_size == 0 {
+                    0
+                } else {
+                    (payload_len + segment_size - 1) / segment_size
                 };
+                let mut segments = 0;
+                let mut total_len = 0;
 
                 rx_message.for_each(|header, segment| {
                     assert_eq!(header.path, path);
                     assert_eq!(header.ecn, ecn);
                     assert!(segment.len() <= payload_len);
                     assert!(segment.len() <= segment_size);
-                })
+                    assert!(!segment.is_empty());
+
+                    // only the last segment can be smaller than the segment size
+                    if segment.len() < segment_size {
+                        assert_eq!(total_len + segment.len(), payload_len);
+                    }
+
+                    segments += 1;
+                    total_len += segment.len();
+                });
+
+                assert_eq!(segments, expected_segments);
+                if segment_size > 0 {
+                    assert_eq!(total_len, payload_len);
+                }
             })
     }
 }
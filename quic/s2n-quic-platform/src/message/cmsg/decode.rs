@@ -90,11 +90,23 @@ unsafe fn collect_item(data: &mut AncillaryData, cmsg: &cmsghdr, value: &[u8]) {
         }
         (level, ty) if features::gro::is_match(level, ty) => {
             if let Some(segment_size) = value_from_bytes::<features::gro::Cmsg>(value) {
-                data.segment_size = segment_size as _;
+                // the kernel only coalesces non-empty datagrams so anything else is invalid
+                if let Ok(segment_size @ 1..=u16::MAX) = u16::try_from(segment_size) {
+                    data.segment_size = segment_size as _;
+                } else {
+                    decode_error!("invalid gro segment size");
+                }
             } else {
                 decode_error!("invalid gro value");
             }
         }
+        (level, ty) if features::rxq_ovfl::is_match(level, ty) => {
+            if let Some(dropped) = value_from_bytes::<features::rxq_ovfl::Cmsg>(value) {
+                data.rx_queue_overflow = Some(dropped);
+            } else {
+                decode_error!("invalid rxq_ovfl value");
+            }
+        }
         _ => {
             decode_error!("unexpected cmsghdr");
         }
@@ -151,16 +151,18 @@ impl MessageTrait for msghdr {
 
         let payload = self.payload_mut();
 
+        // messages that weren't coalesced are a single segment
         let segment_size = if cmsg.segment_size == 0 {
             payload.len()
         } else {
-            cmsg.segment_size as _
+            (cmsg.segment_size as usize).min(payload.len())
         };
 
         let message = crate::message::RxMessage {
             header,
             segment_size,
             payload,
+            rx_queue_overflow: cmsg.rx_queue_overflow,
         };
 
         Some(message)
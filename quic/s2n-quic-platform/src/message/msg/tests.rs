@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::*;
+use super::{ext::MsghdrEncoder, *};
 use bolero::check;
 use core::mem::zeroed;
 use s2n_quic_core::inet::{SocketAddress, Unspecified};
@@ -109,3 +109,68 @@ fn handle_get_set_test() {
             });
         });
 }
+
+/// Ensures coalesced payloads are split at the GRO segment boundaries without copying,
+/// regardless of the layout of the control messages
+#[test]
+#[cfg_attr(kani, kani::proof, kani::solver(minisat), kani::unwind(17))]
+fn rx_gro_segments_test() {
+    const PAYLOAD_LEN: i32 = 16;
+
+    let (Some(gro_level), Some(gro_type)) = (features::gro::LEVEL, features::gro::TYPE) else {
+        return;
+    };
+
+    check!()
+        .with_generator((
+            gen::<SocketAddress>(),
+            -2..=PAYLOAD_LEN + 2,
+            0..=PAYLOAD_LEN as usize,
+            gen::<Option<bool>>(),
+        ))
+        .cloned()
+        .for_each(|(addr, gro, payload_len, tos)| {
+            test_msghdr(|message| {
+                message.set_remote_address(&addr);
+
+                // write the control messages like the kernel would on receive
+                message.msg_controllen = 0;
+                let tos_cmsg = features::tos_v4::LEVEL.zip(features::tos_v4::TYPE);
+                let mut encoder = message.cmsg_encoder();
+                let encode_tos = |encoder: &mut MsghdrEncoder| {
+                    if let Some((level, ty)) = tos_cmsg {
+                        let ecn: features::tos_v4::Cmsg = 0b10;
+                        encoder.encode_cmsg(level, ty, ecn).unwrap();
+                    }
+                };
+                // `Some(true)` places the TOS value before the GRO value
+                if tos == Some(true) {
+                    encode_tos(&mut encoder);
+                }
+                encoder.encode_cmsg(gro_level, gro_type, gro).unwrap();
+                if tos == Some(false) {
+                    encode_tos(&mut encoder);
+                }
+
+                unsafe {
+                    message.set_payload_len(payload_len);
+                }
+                let payload_ptr = message.payload_ptr_mut() as usize;
+
+                let local_address = SocketAddress::default().into();
+                let rx_message = message.rx_read(&local_address).unwrap();
+
+                // invalid segment sizes are treated as a single datagram
+                let segment_size = if gro > 0 { gro as usize } else { payload_len };
+
+                let mut offset = 0;
+                for segment in rx_message.segments() {
+                    // segments point into the received payload
+                    assert_eq!(segment.as_ptr() as usize, payload_ptr + offset);
+                    assert_eq!(segment.len(), segment_size.min(payload_len - offset));
+                    offset += segment.len();
+                }
+                assert_eq!(offset, payload_len);
+            });
+        });
+}
@@ -91,6 +91,7 @@ impl MessageTrait for Message {
             header,
             segment_size: payload.len(),
             payload,
+            rx_queue_overflow: None,
         };
 
         Some(message)
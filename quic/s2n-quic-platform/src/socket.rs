@@ -1,7 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod error_queue;
 pub mod io;
 pub mod options;
 pub mod ring;
 pub mod task;
+pub mod zerocopy;
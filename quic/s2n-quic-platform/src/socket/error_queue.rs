@@ -0,0 +1,195 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structure for passing errors reported by the network from the socket to the endpoint
+//!
+//! The queue is bounded, since each error may require the endpoint to search through its
+//! connections. Errors received while the queue is full are dropped.
+
+use core::task::{Context, Poll, Waker};
+use s2n_quic_core::{inet::SocketAddress, io::rx::SocketError};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// The maximum number of errors that are buffered for the endpoint
+const CAPACITY: usize = 32;
+
+/// The maximum number of bytes kept from each quoted datagram
+///
+/// This is enough to hold a long packet header with the maximum connection ID lengths.
+pub const MAX_QUOTE_LEN: usize = 64;
+
+/// Creates a new error queue
+pub fn pair() -> (Producer, Consumer) {
+    let state = Arc::new(Mutex::new(State::new()));
+    let producer = Producer(state.clone());
+    let consumer = Consumer {
+        state,
+        entries: VecDeque::with_capacity(CAPACITY),
+    };
+    (producer, consumer)
+}
+
+#[derive(Debug)]
+struct Entry {
+    remote_address: SocketAddress,
+    error: SocketError,
+    quote: [u8; MAX_QUOTE_LEN],
+    quote_len: usize,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: VecDeque<Entry>,
+    consumer_waker: Option<Waker>,
+    producer_waker: Option<Waker>,
+    is_consumer_open: bool,
+}
+
+impl State {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            is_consumer_open: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// The half of the queue owned by the tasks which report errors for a socket
+#[derive(Clone, Debug)]
+pub struct Producer(Arc<Mutex<State>>);
+
+impl Producer {
+    /// Pushes an error into the queue and wakes up the endpoint
+    ///
+    /// Only the first [`MAX_QUOTE_LEN`] bytes of the quoted datagram are kept.
+    #[inline]
+    pub fn push(&self, remote_address: SocketAddress, error: SocketError, quoted: &[u8]) {
+        let mut state = self.0.lock().unwrap();
+
+        if state.entries.len() >= CAPACITY {
+            return;
+        }
+
+        let quote_len = quoted.len().min(MAX_QUOTE_LEN);
+        let mut quote = [0; MAX_QUOTE_LEN];
+        quote[..quote_len].copy_from_slice(&quoted[..quote_len]);
+
+        state.entries.push_back(Entry {
+            remote_address,
+            error,
+            quote,
+            quote_len,
+        });
+
+        if let Some(waker) = state.consumer_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Polls for the consumer to be dropped
+    #[inline]
+    pub fn poll_closed(&self, cx: &mut Context) -> Poll<()> {
+        let mut state = self.0.lock().unwrap();
+
+        if !state.is_consumer_open {
+            return Poll::Ready(());
+        }
+
+        state.producer_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// The half of the queue owned by the endpoint
+#[derive(Debug)]
+pub struct Consumer {
+    state: Arc<Mutex<State>>,
+    /// Entries taken out of the shared state so the lock isn't held while they're processed
+    entries: VecDeque<Entry>,
+}
+
+impl Consumer {
+    /// Returns `true` if there are errors in the queue, otherwise registers the waker to be
+    /// notified of new errors
+    #[inline]
+    pub fn poll_ready(&mut self, cx: &mut Context) -> bool {
+        let mut state = self.state.lock().unwrap();
+
+        if !state.entries.is_empty() {
+            return true;
+        }
+
+        state.consumer_waker = Some(cx.waker().clone());
+        false
+    }
+
+    /// Calls `on_error` for each error in the queue
+    #[inline]
+    pub fn for_each<F: FnMut(SocketAddress, SocketError, &[u8])>(&mut self, mut on_error: F) {
+        core::mem::swap(&mut self.state.lock().unwrap().entries, &mut self.entries);
+
+        for entry in self.entries.drain(..) {
+            on_error(
+                entry.remote_address,
+                entry.error,
+                &entry.quote[..entry.quote_len],
+            );
+        }
+    }
+}
+
+impl Drop for Consumer {
+    #[inline]
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.is_consumer_open = false;
+            if let Some(waker) = state.producer_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{inet::SocketAddressV4, task::waker};
+
+    #[test]
+    fn error_queue_test() {
+        let (producer, mut consumer) = pair();
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        let addr: SocketAddress = SocketAddressV4::new([127, 0, 0, 1], 443).into();
+
+        assert!(!consumer.poll_ready(&mut cx));
+        assert!(producer.poll_closed(&mut cx).is_pending());
+
+        // quotes are truncated to the max length
+        producer.push(addr, SocketError::PortUnreachable, &[1; MAX_QUOTE_LEN + 1]);
+        // errors are dropped once the queue is full
+        for _ in 1..CAPACITY + 1 {
+            producer.push(addr, SocketError::HostUnreachable, &[2]);
+        }
+
+        assert!(consumer.poll_ready(&mut cx));
+
+        let mut errors = vec![];
+        consumer.for_each(|remote_address, error, quote| {
+            assert_eq!(addr, remote_address);
+            errors.push((error, quote.len()));
+        });
+
+        assert_eq!(CAPACITY, errors.len());
+        assert_eq!((SocketError::PortUnreachable, MAX_QUOTE_LEN), errors[0]);
+        assert_eq!((SocketError::HostUnreachable, 1), errors[1]);
+        assert!(!consumer.poll_ready(&mut cx));
+
+        drop(consumer);
+        assert!(producer.poll_closed(&mut cx).is_ready());
+    }
+}
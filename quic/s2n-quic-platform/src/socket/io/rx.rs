@@ -1,13 +1,16 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{message::Message, socket::ring::Consumer};
+use crate::{
+    message::Message,
+    socket::{error_queue, ring::Consumer},
+};
 use core::task::{Context, Poll};
 use s2n_quic_core::{
     event,
     inet::datagram,
     io::rx,
-    path::{LocalAddress, MaxMtu},
+    path::{LocalAddress, MaxMtu, RemoteAddress},
     task::waker,
 };
 
@@ -16,17 +19,39 @@ pub struct Rx<T: Message> {
     channels: Vec<Consumer<T>>,
     max_mtu: MaxMtu,
     local_address: LocalAddress,
+    errors: Option<error_queue::Consumer>,
+    overflow: Overflow,
 }
 
 impl<T: Message> Rx<T> {
     #[inline]
     pub fn new(channels: Vec<Consumer<T>>, max_mtu: MaxMtu, local_address: LocalAddress) -> Self {
+        let overflow = Overflow::new(channels.len());
         Self {
             channels,
             max_mtu,
             local_address,
+            errors: None,
+            overflow,
         }
     }
+
+    /// Sets the queue of errors reported by the network for transmitted datagrams
+    #[inline]
+    pub fn with_error_queue(mut self, errors: error_queue::Consumer) -> Self {
+        self.errors = Some(errors);
+        self
+    }
+
+    /// Indicates that all of the channels read from the same socket
+    ///
+    /// By default, each channel is assumed to read from its own socket with its own counter of
+    /// dropped datagrams.
+    #[inline]
+    pub fn with_shared_socket(mut self) -> Self {
+        self.overflow = Overflow::new(1);
+        self
+    }
 }
 
 impl<T: Message> rx::Rx for Rx<T> {
@@ -53,6 +78,10 @@ impl<T: Message> rx::Rx for Rx<T> {
                 }
             }
 
+            if let Some(errors) = self.errors.as_mut() {
+                is_any_ready |= errors.poll_ready(cx);
+            }
+
             // if all of the channels are closed then shut down the task
             if is_all_closed {
                 return Err(()).into();
@@ -90,6 +119,8 @@ impl<T: Message> rx::Rx for Rx<T> {
             channels: &mut this.channels,
             max_mtu: this.max_mtu,
             local_address: &this.local_address,
+            errors: this.errors.as_mut(),
+            overflow: &mut this.overflow,
         };
 
         f(&mut queue);
@@ -107,6 +138,8 @@ pub struct RxQueue<'a, T: Message> {
     channels: &'a mut [Consumer<T>],
     max_mtu: MaxMtu,
     local_address: &'a LocalAddress,
+    errors: Option<&'a mut error_queue::Consumer>,
+    overflow: &'a mut Overflow,
 }
 
 impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
@@ -114,7 +147,7 @@ impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
 
     #[inline]
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
-        for channel in self.channels.iter_mut() {
+        for (idx, channel) in self.channels.iter_mut().enumerate() {
             // one last effort to acquire items if some were received since we last polled
             let len = channel.acquire(u32::MAX);
 
@@ -127,6 +160,10 @@ impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
                 // NOTE: it's important that we process all of the messages in the queue as the
                 //       channel is completely drained here.
                 if let Some(message) = message.rx_read(self.local_address) {
+                    if let Some(counter) = message.rx_queue_overflow {
+                        self.overflow.on_counter(idx, counter);
+                    }
+
                     message.for_each(&mut on_packet);
                 }
 
@@ -141,8 +178,101 @@ impl<'a, T: Message> rx::Queue for RxQueue<'a, T> {
         }
     }
 
+    #[inline]
+    fn for_each_error<F: FnMut(RemoteAddress, rx::SocketError, &[u8])>(&mut self, mut on_error: F) {
+        if let Some(errors) = self.errors.as_mut() {
+            errors.for_each(|remote_address, error, quote| {
+                on_error(remote_address.into(), error, quote)
+            });
+        }
+    }
+
+    #[inline]
+    fn take_dropped_datagrams(&mut self) -> usize {
+        core::mem::take(&mut self.overflow.dropped)
+    }
+
     #[inline]
     fn is_empty(&self) -> bool {
         false
     }
 }
+
+/// Tracks the number of datagrams the OS dropped from the socket receive queues
+///
+/// The OS reports a cumulative counter with each message received after a drop, so the number of
+/// new drops is the difference from the last counter observed on the same socket.
+#[derive(Debug)]
+struct Overflow {
+    counters: Vec<u32>,
+    dropped: usize,
+}
+
+impl Overflow {
+    #[inline]
+    fn new(sockets: usize) -> Self {
+        Self {
+            counters: vec![0; sockets.max(1)],
+            dropped: 0,
+        }
+    }
+
+    #[inline]
+    fn on_counter(&mut self, channel: usize, counter: u32) {
+        // channels beyond the number of counters share the last socket
+        let idx = channel.min(self.counters.len() - 1);
+        let prev = &mut self.counters[idx];
+        let delta = counter.wrapping_sub(*prev);
+
+        // A socket can be shared between multiple channels so messages aren't necessarily read in
+        // the order they were received. Ignore any counters that are behind the one we've seen.
+        if delta == 0 || delta > u32::MAX / 2 {
+            return;
+        }
+
+        *prev = counter;
+        self.dropped += delta as usize;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_test() {
+        let mut overflow = Overflow::new(2);
+
+        overflow.on_counter(0, 3);
+        assert_eq!(overflow.dropped, 3);
+
+        // stale counters are ignored
+        overflow.on_counter(0, 2);
+        overflow.on_counter(0, 3);
+        assert_eq!(overflow.dropped, 3);
+
+        // each socket has its own counter
+        overflow.on_counter(1, 4);
+        assert_eq!(overflow.dropped, 7);
+
+        overflow.on_counter(0, 10);
+        assert_eq!(overflow.dropped, 14);
+
+        // the counter wraps around
+        overflow.counters[0] = u32::MAX - 1;
+        overflow.dropped = 0;
+        overflow.on_counter(0, 1);
+        assert_eq!(overflow.dropped, 3);
+    }
+
+    #[test]
+    fn shared_overflow_test() {
+        let mut overflow = Overflow::new(1);
+
+        overflow.on_counter(0, 3);
+        overflow.on_counter(1, 3);
+        overflow.on_counter(1, 5);
+        overflow.on_counter(0, 4);
+        assert_eq!(overflow.dropped, 5);
+    }
+}
@@ -231,6 +231,8 @@ impl<'a, T: Message> TxQueue<'a, T> {
 
         // if we can't use GSO then flush the current message
         if !can_gso {
+            // the current payload is complete so give the writer a chance to finish it
+            message.finish_payload(prev_message.payload_mut());
             self.flush_gso();
             return Ok(Err(message));
         }
@@ -294,6 +296,7 @@ impl<'a, T: Message> TxQueue<'a, T> {
 
         // if we've hit any limits, then flush the GSO information to the message
         if size_mismatch || at_segment_limit || at_payload_limit {
+            message.finish_payload(prev_message.payload_mut());
             self.flush_gso();
         }
 
@@ -387,6 +390,7 @@ impl<'a, T: Message> tx::Queue for TxQueue<'a, T> {
 
     const SUPPORTS_ECN: bool = T::SUPPORTS_ECN;
     const SUPPORTS_FLOW_LABELS: bool = T::SUPPORTS_FLOW_LABELS;
+    const SUPPORTS_FINISH_PAYLOAD: bool = true;
 
     #[inline]
     fn push<M>(&mut self, message: M) -> Result<tx::Outcome, tx::Error>
@@ -427,7 +431,7 @@ impl<'a, T: Message> tx::Queue for TxQueue<'a, T> {
         let can_gso = message.can_gso(self.max_mtu, 0);
 
         // write the message to the entry
-        let payload_len = entry.tx_write(message)?;
+        let payload_len = entry.tx_write(&mut message)?;
 
         // if GSO is supported and we are allowed to have additional segments, store the GSO state
         // for another potential message to be written later
@@ -439,7 +443,8 @@ impl<'a, T: Message> tx::Queue for TxQueue<'a, T> {
                 size: payload_len,
             });
         } else {
-            // otherwise, release the message to the consumer
+            // otherwise, finish and release the message to the consumer
+            message.finish_payload(entry.payload_mut());
             self.release_message();
         }
 
@@ -452,6 +457,13 @@ impl<'a, T: Message> tx::Queue for TxQueue<'a, T> {
         Ok(outcome)
     }
 
+    #[inline]
+    fn finish_payload<F: FnOnce(&mut [u8])>(&mut self, f: F) {
+        if let Some((message, _gso)) = self.gso_message() {
+            f(message.payload_mut());
+        }
+    }
+
     #[inline]
     fn flush(&mut self) {
         // flush GSO segments between connections
@@ -473,3 +485,118 @@ impl<'a, T: Message> Drop for TxQueue<'a, T> {
         self.flush_channel();
     }
 }
+
+#[cfg(all(test, s2n_quic_platform_gso))]
+mod tests {
+    use super::*;
+    use crate::{features::gso::MaxSegments, message::msg::Handle, socket::ring};
+    use core::time::Duration;
+    use s2n_quic_core::{
+        io::tx::{Queue as _, Tx as _},
+        path::RemoteAddress,
+    };
+
+    /// A datagram which records the payloads it was asked to finish
+    struct Datagram<'a> {
+        handle: Handle,
+        len: usize,
+        value: u8,
+        finished: &'a mut Vec<Vec<u8>>,
+    }
+
+    impl<'a> tx::Message for Datagram<'a> {
+        type Handle = Handle;
+
+        fn path_handle(&self) -> &Self::Handle {
+            &self.handle
+        }
+
+        fn ecn(&mut self) -> ExplicitCongestionNotification {
+            Default::default()
+        }
+
+        fn delay(&mut self) -> Duration {
+            Default::default()
+        }
+
+        fn ipv6_flow_label(&mut self) -> u32 {
+            0
+        }
+
+        fn can_gso(&self, segment_len: usize, _segment_count: usize) -> bool {
+            self.len <= segment_len
+        }
+
+        fn write_payload(
+            &mut self,
+            buffer: tx::PayloadBuffer,
+            _gso_offset: usize,
+        ) -> Result<usize, tx::Error> {
+            let buffer = unsafe { buffer.into_mut_slice() };
+            buffer[..self.len].fill(self.value);
+            Ok(self.len)
+        }
+
+        fn finish_payload(&mut self, payload: &mut [u8]) {
+            self.finished.push(payload.to_vec());
+        }
+    }
+
+    #[test]
+    fn finish_payload_test() {
+        let max_mtu = MaxMtu::try_from(1500).unwrap();
+        let gso = Gso::from(MaxSegments::try_from(3).unwrap());
+        let (producer, mut consumer) = ring::pair::<libc::msghdr>(16, 1500 * 3);
+        let mut tx = Tx::new(vec![producer], gso, max_mtu);
+
+        let handle = Handle::from_remote_address(RemoteAddress::default());
+        let mut finished = vec![];
+
+        tx.queue(|queue| {
+            let mut push = |len: usize, value: u8, finished: &mut Vec<Vec<u8>>| {
+                queue
+                    .push(Datagram {
+                        handle,
+                        len,
+                        value,
+                        finished,
+                    })
+                    .unwrap();
+            };
+
+            // the payload is finished once it reaches the segment limit
+            push(100, 0, &mut finished);
+            push(100, 1, &mut finished);
+            push(100, 2, &mut finished);
+            // the payload is finished once a smaller segment is written
+            push(100, 3, &mut finished);
+            push(50, 4, &mut finished);
+            // the payload is finished before a segment which doesn't fit is written
+            push(100, 5, &mut finished);
+            push(200, 6, &mut finished);
+
+            // the writer finishes the payload which is still waiting on segments
+            queue.finish_payload(|payload| finished.push(payload.to_vec()));
+        });
+
+        let payload = |segments: &[(usize, u8)]| {
+            segments
+                .iter()
+                .flat_map(|(len, value)| core::iter::repeat(*value).take(*len))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            finished,
+            [
+                payload(&[(100, 0), (100, 1), (100, 2)]),
+                payload(&[(100, 3), (50, 4)]),
+                payload(&[(100, 5)]),
+                payload(&[(200, 6)]),
+            ]
+        );
+
+        // all of the payloads were released to the socket task
+        assert_eq!(consumer.acquire(u32::MAX), 4);
+    }
+}
@@ -153,6 +153,34 @@ impl<T: Message> Consumer<T> {
         Poll::Pending
     }
 
+    /// Polls for more than `held` ready-to-consume messages from the producer
+    ///
+    /// This is used by consumers that hold on to messages at the front of the ring after reading
+    /// them, which would otherwise cause [`Self::poll_acquire`] to always be ready.
+    #[inline]
+    pub fn poll_acquire_beyond(&mut self, held: u32, cx: &mut Context) -> Poll<u32> {
+        macro_rules! try_acquire {
+            () => {{
+                let count = self.acquire(u32::MAX);
+
+                if count > held {
+                    return Poll::Ready(count);
+                }
+            }};
+        }
+
+        // first try to acquire some messages
+        try_acquire!();
+
+        // if we couldn't acquire anything register our waker
+        self.wakers.register(cx.waker());
+
+        // try to acquire some messages in case we got some concurrently to waker registration
+        try_acquire!();
+
+        Poll::Pending
+    }
+
     /// Releases consumed messages to the producer
     #[inline]
     pub fn release(&mut self, release_len: u32) {
@@ -1,14 +1,23 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::features::Gso;
+use crate::{features::Gso, message::Message, socket::error_queue};
 use core::ops::ControlFlow;
+use s2n_quic_core::{inet::SocketAddress, io::rx::SocketError};
 
 #[derive(Debug)]
 pub struct TxEvents {
     count: usize,
     is_blocked: bool,
     gso: Gso,
+    is_zerocopy: bool,
+    zerocopy_count: usize,
+    is_zerocopy_exhausted: bool,
+    /// Used to report datagrams which couldn't be sent from their local address to the endpoint
+    errors: Option<error_queue::Producer>,
+    /// The indexes of the messages in the current send call which couldn't be sent from their
+    /// local address
+    local_address_errors: Vec<usize>,
 }
 
 impl TxEvents {
@@ -18,9 +27,77 @@ impl TxEvents {
             count: 0,
             is_blocked: false,
             gso,
+            is_zerocopy: false,
+            zerocopy_count: 0,
+            is_zerocopy_exhausted: false,
+            errors: None,
+            local_address_errors: Vec::new(),
         }
     }
 
+    /// Sets the queue used to report errors to the endpoint
+    #[inline]
+    pub fn set_error_queue(&mut self, errors: error_queue::Producer) {
+        self.errors = Some(errors);
+    }
+
+    /// Reports the messages in `entries` which couldn't be sent from their local address
+    ///
+    /// This typically means the interface the address was assigned to went down, which the
+    /// endpoint can recover from by moving its connections to a new local address.
+    #[inline]
+    pub fn report_local_address_errors<M, F>(&mut self, entries: &mut [M], remote_address: F)
+    where
+        M: Message,
+        F: Fn(&M) -> Option<SocketAddress>,
+    {
+        let Some(errors) = self.errors.as_ref() else {
+            self.local_address_errors.clear();
+            return;
+        };
+
+        for index in self.local_address_errors.drain(..) {
+            let Some(entry) = entries.get_mut(index) else {
+                continue;
+            };
+            if let Some(remote_address) = remote_address(entry) {
+                errors.push(
+                    remote_address,
+                    SocketError::LocalAddressUnavailable,
+                    entry.payload_mut(),
+                );
+            }
+        }
+    }
+
+    /// Sets whether the next send call uses zero-copy
+    #[inline]
+    pub fn set_zerocopy(&mut self, enabled: bool) {
+        self.is_zerocopy = enabled;
+    }
+
+    /// Returns the flags to pass to the send syscall
+    #[inline]
+    pub fn send_flags(&self) -> std::os::raw::c_int {
+        if self.is_zerocopy {
+            crate::features::zerocopy::SEND_FLAG
+        } else {
+            0
+        }
+    }
+
+    /// Returns and resets the number of messages successfully sent with zero-copy
+    #[inline]
+    pub fn take_zerocopy_count(&mut self) -> usize {
+        core::mem::take(&mut self.zerocopy_count)
+    }
+
+    /// Returns if the socket ran out of memory for zero-copy sends and resets the value
+    #[inline]
+    pub fn take_zerocopy_exhausted(&mut self) -> bool {
+        core::mem::take(&mut self.is_zerocopy_exhausted)
+    }
+
     /// Returns if the task is blocked
     #[inline]
     pub fn is_blocked(&self) -> bool {
@@ -51,6 +128,9 @@ impl crate::syscall::SocketEvents for TxEvents {
     fn on_complete(&mut self, count: usize) -> ControlFlow<(), ()> {
         // increment the total sent packets and reset our blocked status
         self.count += count;
+        if self.is_zerocopy {
+            self.zerocopy_count += count;
+        }
         self.is_blocked = false;
         ControlFlow::Continue(())
     }
@@ -69,6 +149,24 @@ impl crate::syscall::SocketEvents for TxEvents {
                 // if we got interrupted break and have the task try again
                 ControlFlow::Break(())
             }
+            #[cfg(unix)]
+            _ if self.is_zerocopy && error.raw_os_error() == Some(libc::ENOBUFS) => {
+                // the socket ran out of memory for pinning pages so have the task retry the
+                // message with a copy
+                self.is_zerocopy_exhausted = true;
+                ControlFlow::Break(())
+            }
+            #[cfg(unix)]
+            _ if error.raw_os_error() == Some(libc::EADDRNOTAVAIL) => {
+                // the local address of the message was removed so record it to be reported to the
+                // endpoint and discard the packet
+                //
+                // `ENETDOWN` isn't included since the interface may come back up with the same
+                // address, which doesn't warrant moving the connection
+                self.local_address_errors.push(self.count);
+                self.count += 1;
+                ControlFlow::Continue(())
+            }
             _ => {
                 // let the GSO settings know there was an error on the socket, which may indicate
                 // it needs to be disabled
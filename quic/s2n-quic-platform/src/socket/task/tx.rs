@@ -4,7 +4,7 @@
 use crate::{
     features::Gso,
     message::Message,
-    socket::{ring::Consumer, task::events},
+    socket::{error_queue, ring::Consumer, task::events, zerocopy},
 };
 use core::{
     future::Future,
@@ -33,6 +33,8 @@ pub struct Sender<T: Message, S: Socket<T>> {
     events: Events,
     ring_cooldown: Cooldown,
     io_cooldown: Cooldown,
+    /// Tracks slots sent with zero-copy, which can't be released until the kernel is done with them
+    zerocopy: Option<zerocopy::Tracker>,
 }
 
 impl<T, S> Sender<T, S>
@@ -48,18 +50,42 @@ where
             events: Events::new(gso),
             ring_cooldown: cooldown.clone(),
             io_cooldown: cooldown,
+            zerocopy: None,
         }
     }
 
+    /// Reports datagrams which couldn't be sent from their local address to the endpoint
+    #[inline]
+    pub fn with_error_queue(mut self, errors: error_queue::Producer) -> Self {
+        self.events.set_error_queue(errors);
+        self
+    }
+
+    /// Sends messages with zero-copy, when they meet the tracker's threshold
+    #[inline]
+    pub fn with_zerocopy(mut self, tracker: zerocopy::Tracker) -> Self {
+        self.zerocopy = Some(tracker);
+        self
+    }
+
     #[inline]
     fn poll_ring(&mut self, watermark: u32, cx: &mut Context) -> Poll<Result<(), ()>> {
+        // skip over any messages that are still waiting on zero-copy completions
+        let held = self.zerocopy.as_ref().map_or(0, |z| z.in_flight());
+
         loop {
             let is_loop = self.ring_cooldown.state().is_loop();
 
             let count = if is_loop {
                 self.ring.acquire(watermark)
             } else {
-                match self.ring.poll_acquire(watermark, cx) {
+                let result = if held > 0 {
+                    self.ring.poll_acquire_beyond(held, cx)
+                } else {
+                    self.ring.poll_acquire(watermark, cx)
+                };
+
+                match result {
                     Poll::Ready(count) => count,
                     Poll::Pending if !self.ring.is_open() => return Err(()).into(),
                     Poll::Pending => 0,
@@ -67,7 +93,7 @@ where
             };
 
             // if the number of free slots increased since last time then yield
-            if count > 0 {
+            if count > held {
                 self.ring_cooldown.on_ready();
                 return Ok(()).into();
             }
@@ -95,6 +121,15 @@ where
         let mut pending_wake = false;
 
         while !this.events.take_blocked() {
+            // hand back any zero-copy messages the kernel is done with
+            if let Some(zerocopy) = this.zerocopy.as_mut() {
+                let count = zerocopy.poll_release(cx);
+                if count > 0 {
+                    this.ring.release_no_wake(count);
+                    pending_wake = true;
+                }
+            }
+
             match this.poll_ring(u32::MAX, cx) {
                 Poll::Ready(Ok(_)) => {}
                 Poll::Ready(Err(_)) => return None.into(),
@@ -109,13 +144,41 @@ where
             // slice the ring data by the number of items we've already received
             let entries = this.ring.data();
 
+            let entries = if let Some(zerocopy) = this.zerocopy.as_ref() {
+                let entries = &mut entries[zerocopy.in_flight() as usize..];
+
+                // send the longest run of messages that agree on using zero-copy, since the flag
+                // applies to the entire syscall
+                let is_zerocopy = zerocopy.should_send(entries[0].payload_len());
+                let len = entries
+                    .iter()
+                    .take_while(|entry| zerocopy.should_send(entry.payload_len()) == is_zerocopy)
+                    .count();
+                this.events.set_zerocopy(is_zerocopy);
+
+                &mut entries[..len]
+            } else {
+                entries
+            };
+
             // perform the send syscall
             match this.tx.send(cx, entries, &mut this.events) {
                 Ok(_) => {
                     // increment the number of received messages
                     let count = this.events.take_count() as u32;
 
-                    if count > 0 {
+                    if let Some(zerocopy) = this.zerocopy.as_mut() {
+                        let zerocopy_count = this.events.take_zerocopy_count() as u32;
+                        zerocopy.on_send(count, zerocopy_count);
+
+                        if this.events.take_zerocopy_exhausted() {
+                            zerocopy.on_no_buffers();
+                        }
+
+                        if count > 0 {
+                            this.io_cooldown.on_ready();
+                        }
+                    } else if count > 0 {
                         this.ring.release_no_wake(count);
                         this.io_cooldown.on_ready();
                         pending_wake = true;
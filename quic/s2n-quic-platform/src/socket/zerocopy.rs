@@ -0,0 +1,322 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Completion tracking for zero-copy (MSG_ZEROCOPY) transmissions
+//!
+//! When a message is sent with `MSG_ZEROCOPY`, the kernel pins the pages of the payload rather
+//! than copying them and may continue reading from them after the send call returns. The slots in
+//! the transmit ring can't be handed back to the endpoint until the kernel reports the send
+//! as completed on the socket error queue.
+//!
+//! The kernel assigns each successful zero-copy send on a socket an incrementing 32-bit id and
+//! reports completions as inclusive ranges of those ids. The [`Notifier`] is owned by the task
+//! reading the error queue and the [`Tracker`] by the task transmitting on the socket.
+
+use crate::features::zerocopy::Completion;
+use core::task::{Context, Poll, Waker};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// The default minimum payload size for a message to be sent with zero-copy
+///
+/// Pinning pages and processing completions has a fixed cost which is only recovered for larger
+/// payloads. See <https://www.kernel.org/doc/html/latest/networking/msg_zerocopy.html>.
+pub const DEFAULT_THRESHOLD: u16 = 10 * 1024;
+
+/// Creates a new completion tracker for a socket
+pub fn pair(threshold: u16) -> (Notifier, Tracker) {
+    let state = Arc::new(Mutex::new(State::new()));
+    let notifier = Notifier(state.clone());
+    let tracker = Tracker {
+        state,
+        threshold,
+        is_enabled: true,
+        is_suspended: false,
+        batches: VecDeque::new(),
+        in_flight: 0,
+        next_id: 0,
+    };
+    (notifier, tracker)
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// All ids before this one have completed
+    completed: u32,
+    /// Completions received out of order
+    pending: Vec<(u32, u32)>,
+    /// The kernel copied the payload for at least one send
+    copied: bool,
+    tracker_waker: Option<Waker>,
+    notifier_waker: Option<Waker>,
+    is_tracker_open: bool,
+}
+
+impl State {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            is_tracker_open: true,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    fn on_completion(&mut self, completion: Completion) {
+        self.copied |= completion.copied;
+        self.pending.push((completion.start, completion.end));
+
+        // advance the watermark through any contiguous ranges
+        while let Some(idx) = self
+            .pending
+            .iter()
+            .position(|(start, _)| *start == self.completed)
+        {
+            let (_, end) = self.pending.swap_remove(idx);
+            self.completed = end.wrapping_add(1);
+        }
+    }
+
+    #[inline]
+    fn is_complete(&self, id: u32) -> bool {
+        // ids wrap around so compare the distance from the watermark
+        (id.wrapping_sub(self.completed) as i32) < 0
+    }
+}
+
+/// The half of the tracker owned by the task reading from the socket error queue
+#[derive(Debug)]
+pub struct Notifier(Arc<Mutex<State>>);
+
+impl Notifier {
+    /// Records completed sends and wakes up the transmit task
+    #[inline]
+    pub fn on_completion(&self, completion: Completion) {
+        let mut state = self.0.lock().unwrap();
+        state.on_completion(completion);
+
+        if let Some(waker) = state.tracker_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Polls for the tracker to be dropped
+    #[inline]
+    pub fn poll_closed(&self, cx: &mut Context) -> Poll<()> {
+        let mut state = self.0.lock().unwrap();
+
+        if !state.is_tracker_open {
+            return Poll::Ready(());
+        }
+
+        state.notifier_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// A contiguous run of ring slots transmitted in a single send call
+#[derive(Debug)]
+struct Batch {
+    slots: u32,
+    /// The id of the last zero-copy send in the batch, if any
+    last_id: Option<u32>,
+}
+
+/// The half of the tracker owned by the transmit task
+#[derive(Debug)]
+pub struct Tracker {
+    state: Arc<Mutex<State>>,
+    threshold: u16,
+    /// Cleared once the kernel reports copying payloads, since zero-copy is only overhead then
+    is_enabled: bool,
+    /// Set when the kernel runs out of memory for pinning pages, until in-flight sends complete
+    is_suspended: bool,
+    batches: VecDeque<Batch>,
+    in_flight: u32,
+    next_id: u32,
+}
+
+impl Tracker {
+    /// Returns `true` if a message with the given payload length should be sent with zero-copy
+    #[inline]
+    pub fn should_send(&self, payload_len: usize) -> bool {
+        self.is_enabled && !self.is_suspended && payload_len >= self.threshold as usize
+    }
+
+    /// Returns the number of slots at the front of the ring that were transmitted but haven't
+    /// completed yet
+    #[inline]
+    pub fn in_flight(&self) -> u32 {
+        self.in_flight
+    }
+
+    /// Records a send call which transmitted `slots` messages, of which `zerocopy_sends`
+    /// completed successfully with zero-copy
+    #[inline]
+    pub fn on_send(&mut self, slots: u32, zerocopy_sends: u32) {
+        if slots == 0 {
+            return;
+        }
+
+        let last_id = if zerocopy_sends > 0 {
+            self.next_id = self.next_id.wrapping_add(zerocopy_sends);
+            Some(self.next_id.wrapping_sub(1))
+        } else {
+            None
+        };
+
+        self.batches.push_back(Batch { slots, last_id });
+        self.in_flight += slots;
+    }
+
+    /// Stops using zero-copy until all of the in-flight sends complete
+    #[inline]
+    pub fn on_no_buffers(&mut self) {
+        self.is_suspended = true;
+    }
+
+    /// Returns the number of slots that can be released back to the ring
+    ///
+    /// If any slots are still in flight, the waker is notified on the next completion.
+    #[inline]
+    pub fn poll_release(&mut self, cx: &mut Context) -> u32 {
+        if self.batches.is_empty() {
+            return 0;
+        }
+
+        let mut state = self.state.lock().unwrap();
+
+        if state.copied {
+            self.is_enabled = false;
+        }
+
+        let mut released = 0;
+
+        // slots need to be released in order so stop at the first incomplete batch
+        while let Some(batch) = self.batches.front() {
+            if let Some(id) = batch.last_id {
+                if !state.is_complete(id) {
+                    break;
+                }
+            }
+
+            released += batch.slots;
+            self.batches.pop_front();
+        }
+
+        self.in_flight -= released;
+
+        if self.in_flight == 0 {
+            self.is_suspended = false;
+        } else {
+            state.tracker_waker = Some(cx.waker().clone());
+        }
+
+        released
+    }
+}
+
+impl Drop for Tracker {
+    #[inline]
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.is_tracker_open = false;
+            if let Some(waker) = state.notifier_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::task::waker;
+
+    fn completion(start: u32, end: u32) -> Completion {
+        Completion {
+            start,
+            end,
+            copied: false,
+        }
+    }
+
+    #[test]
+    fn release_test() {
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        let (notifier, mut tracker) = pair(1000);
+
+        assert!(!tracker.should_send(999));
+        assert!(tracker.should_send(1000));
+
+        // ids 0..=2
+        tracker.on_send(3, 3);
+        // a copied batch behind the zero-copy batch
+        tracker.on_send(2, 0);
+        // ids 3..=4
+        tracker.on_send(2, 2);
+        assert_eq!(tracker.in_flight(), 7);
+        assert_eq!(tracker.poll_release(&mut cx), 0);
+
+        // the batch isn't complete until its last id is
+        notifier.on_completion(completion(0, 1));
+        assert_eq!(tracker.poll_release(&mut cx), 0);
+
+        // completions can arrive out of order
+        notifier.on_completion(completion(3, 4));
+        assert_eq!(tracker.poll_release(&mut cx), 0);
+
+        notifier.on_completion(completion(2, 2));
+        assert_eq!(tracker.poll_release(&mut cx), 7);
+        assert_eq!(tracker.in_flight(), 0);
+    }
+
+    #[test]
+    fn wrapping_test() {
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        let (notifier, mut tracker) = pair(0);
+        tracker.next_id = u32::MAX - 1;
+        notifier.0.lock().unwrap().completed = u32::MAX - 1;
+
+        tracker.on_send(4, 4);
+        notifier.on_completion(completion(u32::MAX - 1, 0));
+        assert_eq!(tracker.poll_release(&mut cx), 0);
+        notifier.on_completion(completion(1, 1));
+        assert_eq!(tracker.poll_release(&mut cx), 4);
+    }
+
+    #[test]
+    fn fallback_test() {
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        let (notifier, mut tracker) = pair(0);
+
+        tracker.on_send(1, 1);
+        tracker.on_no_buffers();
+        assert!(!tracker.should_send(100));
+
+        notifier.on_completion(Completion {
+            start: 0,
+            end: 0,
+            copied: true,
+        });
+        assert_eq!(tracker.poll_release(&mut cx), 1);
+
+        // the kernel copied the payload so zero-copy is disabled for good
+        assert!(!tracker.should_send(100));
+    }
+
+    #[test]
+    fn closed_test() {
+        let (notifier, tracker) = pair(0);
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        assert!(notifier.poll_closed(&mut cx).is_pending());
+        drop(tracker);
+        assert!(notifier.poll_closed(&mut cx).is_ready());
+    }
+}
@@ -50,13 +50,20 @@ pub trait SocketEvents {
 
 #[cfg(unix)]
 pub trait UnixMessage: crate::message::Message {
-    fn send<E: SocketEvents>(fd: std::os::unix::io::RawFd, entries: &mut [Self], events: &mut E);
+    fn send<E: SocketEvents>(
+        fd: std::os::unix::io::RawFd,
+        entries: &mut [Self],
+        flags: libc::c_int,
+        events: &mut E,
+    );
     fn recv<E: SocketEvents>(
         fd: std::os::unix::io::RawFd,
         ty: SocketType,
         entries: &mut [Self],
         events: &mut E,
     );
+    /// Returns the remote address the message is sent to
+    fn destination_address(&self) -> Option<s2n_quic_core::inet::SocketAddress>;
 }
 
 pub fn udp_socket(addr: std::net::SocketAddr) -> io::Result<Socket> {
@@ -201,6 +208,70 @@ pub fn configure_tos(rx_socket: &Socket) -> bool {
     success
 }
 
+/// Configures the socket to queue errors reported by the network, such as ICMP messages, so they
+/// can be read from the socket error queue
+pub fn configure_recverr(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        if let Some((level, ty)) = crate::features::recverr::SOCKOPT_V4 {
+            success |= libc!(setsockopt(
+                tx_socket.as_raw_fd(),
+                level,
+                ty,
+                &enabled as *const _ as _,
+                core::mem::size_of_val(&enabled) as _,
+            ))
+            .is_ok();
+        }
+
+        if let Some((level, ty)) = crate::features::recverr::SOCKOPT_V6 {
+            success |= libc!(setsockopt(
+                tx_socket.as_raw_fd(),
+                level,
+                ty,
+                &enabled as *const _ as _,
+                core::mem::size_of_val(&enabled) as _,
+            ))
+            .is_ok();
+        }
+    }
+
+    success
+}
+
+/// Configures the socket to allow transmitting with zero-copy (MSG_ZEROCOPY)
+///
+/// Kernels which don't support zero-copy on UDP sockets (before Linux 5.0) reject the option.
+pub fn configure_zerocopy(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        if let Some((level, ty)) = crate::features::zerocopy::SOCKOPT {
+            success = libc!(setsockopt(
+                tx_socket.as_raw_fd(),
+                level,
+                ty,
+                &enabled as *const _ as _,
+                core::mem::size_of_val(&enabled) as _,
+            ))
+            .is_ok();
+        }
+    }
+
+    let _ = tx_socket;
+
+    success
+}
+
 /// Configures the socket to return local address and interface information as part of the
 /// ancillary data
 pub fn configure_pktinfo(rx_socket: &Socket) -> bool {
@@ -258,3 +329,89 @@ pub fn configure_gro(rx_socket: &Socket) -> bool {
 
     success
 }
+
+pub fn configure_rxq_ovfl(rx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    if let Some((level, ty)) = crate::features::rxq_ovfl::SOCKOPT {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        success |= libc!(setsockopt(
+            rx_socket.as_raw_fd(),
+            level as _,
+            ty as _,
+            &enabled as *const _ as _,
+            core::mem::size_of_val(&enabled) as _
+        ))
+        .is_ok();
+    }
+
+    success
+}
+
+/// Returns the set of CPU cores the calling thread is allowed to run on
+pub fn get_cpu_affinity() -> io::Result<Vec<usize>> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+
+        // a pid of 0 refers to the calling thread
+        libc!(sched_getaffinity(
+            0,
+            core::mem::size_of::<libc::cpu_set_t>(),
+            &mut set
+        ))?;
+
+        let cores = (0..libc::CPU_SETSIZE as usize)
+            .filter(|core| unsafe { libc::CPU_ISSET(*core, &set) })
+            .collect();
+
+        Ok(cores)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cpu affinity is not supported on the current platform",
+        ))
+    }
+}
+
+/// Pins the calling thread to the provided set of CPU cores
+pub fn set_cpu_affinity(cores: &[usize]) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+
+        for core in cores.iter().copied() {
+            if core >= libc::CPU_SETSIZE as usize {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("cpu core {core} is out of range"),
+                ));
+            }
+
+            unsafe { libc::CPU_SET(core, &mut set) };
+        }
+
+        // a pid of 0 refers to the calling thread
+        libc!(sched_setaffinity(
+            0,
+            core::mem::size_of::<libc::cpu_set_t>(),
+            &set
+        ))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cpu affinity is not supported on the current platform",
+        ))
+    }
+}
@@ -7,20 +7,26 @@ use std::os::unix::io::{AsRawFd, RawFd};
 
 impl UnixMessage for mmsghdr {
     #[inline]
-    fn send<E: SocketEvents>(fd: RawFd, entries: &mut [Self], events: &mut E) {
-        send(&fd, entries, events)
+    fn send<E: SocketEvents>(fd: RawFd, entries: &mut [Self], flags: libc::c_int, events: &mut E) {
+        send(&fd, entries, flags, events)
     }
 
     #[inline]
     fn recv<E: SocketEvents>(fd: RawFd, ty: SocketType, entries: &mut [Self], events: &mut E) {
         recv(&fd, ty, entries, events)
     }
+
+    #[inline]
+    fn destination_address(&self) -> Option<s2n_quic_core::inet::SocketAddress> {
+        crate::message::msg::Ext::remote_address(&self.msg_hdr)
+    }
 }
 
 #[inline]
 pub fn send<Sock: AsRawFd, E: SocketEvents>(
     socket: &Sock,
     packets: &mut [mmsghdr],
+    flags: libc::c_int,
     events: &mut E,
 ) {
     if packets.is_empty() {
@@ -49,8 +55,7 @@ pub fn send<Sock: AsRawFd, E: SocketEvents>(
 
     // > The flags argument contains flags ORed together.
     //
-    // No flags are currently set
-    let flags = Default::default();
+    // The only flag we set is MSG_ZEROCOPY, when enabled by the caller
 
     // > The sendmmsg() system call is an extension of sendmsg(2) that allows
     // > the caller to transmit multiple messages on a socket using a single
@@ -6,22 +6,31 @@ use crate::message::Message as _;
 use libc::msghdr;
 use std::os::unix::io::{AsRawFd, RawFd};
 
+#[cfg(s2n_quic_platform_recverr)]
+use s2n_quic_core::{inet::SocketAddress, io::rx::SocketError};
+
 impl UnixMessage for msghdr {
     #[inline]
-    fn send<E: SocketEvents>(fd: RawFd, entries: &mut [Self], events: &mut E) {
-        send(&fd, entries, events)
+    fn send<E: SocketEvents>(fd: RawFd, entries: &mut [Self], flags: libc::c_int, events: &mut E) {
+        send(&fd, entries, flags, events)
     }
 
     #[inline]
     fn recv<E: SocketEvents>(fd: RawFd, ty: SocketType, entries: &mut [Self], events: &mut E) {
         recv(&fd, ty, entries, events)
     }
+
+    #[inline]
+    fn destination_address(&self) -> Option<s2n_quic_core::inet::SocketAddress> {
+        crate::message::msg::Ext::remote_address(self)
+    }
 }
 
 #[inline]
 pub fn send<'a, Sock: AsRawFd, P: IntoIterator<Item = &'a mut msghdr>, E: SocketEvents>(
     socket: &Sock,
     packets: P,
+    flags: libc::c_int,
     events: &mut E,
 ) {
     for packet in packets {
@@ -60,9 +69,7 @@ pub fn send<'a, Sock: AsRawFd, P: IntoIterator<Item = &'a mut msghdr>, E: Socket
 
         // > The flags argument is the bitwise OR of zero or more flags.
         //
-        // No flags are currently set
-        let flags = Default::default();
-
+        // The only flag we set is MSG_ZEROCOPY, when enabled by the caller
         // > On success, these calls return the number of characters sent.
         // > On error, -1 is returned, and errno is set appropriately.
         let result = libc!(sendmsg(sockfd, msg, flags));
@@ -168,3 +175,90 @@ pub fn recv<'a, Sock: AsRawFd, P: IntoIterator<Item = &'a mut msghdr>, E: Socket
         flags = libc::MSG_DONTWAIT;
     }
 }
+
+/// An entry read from the socket error queue
+#[cfg(s2n_quic_platform_recverr)]
+#[derive(Debug)]
+pub enum QueuedError {
+    /// An error reported by the network for a transmitted datagram
+    ///
+    /// The number of bytes of the quoted datagram written to the payload is included.
+    Path {
+        remote_address: SocketAddress,
+        error: SocketError,
+        len: usize,
+    },
+    /// A range of zero-copy sends was completed
+    Zerocopy(crate::features::zerocopy::Completion),
+}
+
+/// Receives a single entry from the socket error queue
+///
+/// The prefix of the datagram quoted in a path error is written to `payload`. `Ok(None)` is
+/// returned for errors that don't indicate a problem with the path.
+#[cfg(s2n_quic_platform_recverr)]
+pub fn recv_error<Sock: AsRawFd>(
+    socket: &Sock,
+    payload: &mut [u8],
+) -> std::io::Result<Option<QueuedError>> {
+    use crate::{
+        features::{recverr, zerocopy},
+        message::{cmsg, msg::Ext as _},
+    };
+
+    let mut remote_address: libc::sockaddr_in6 = unsafe { core::mem::zeroed() };
+    // leave room for the extended error, the address of the node that reported it and any other
+    // control messages enabled on the socket
+    let mut control = cmsg::Storage::<256>::default();
+    let mut iovec = libc::iovec {
+        iov_base: payload.as_mut_ptr() as _,
+        iov_len: payload.len(),
+    };
+
+    let mut msg: msghdr = unsafe { core::mem::zeroed() };
+    msg.msg_name = &mut remote_address as *mut _ as _;
+    msg.msg_namelen = core::mem::size_of_val(&remote_address) as _;
+    msg.msg_iov = &mut iovec;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as _;
+    msg.msg_controllen = control.len() as _;
+
+    // Safety: all of the pointers in the msghdr point to buffers that outlive the call
+    //
+    // > MSG_ERRQUEUE: This flag specifies that queued errors should be received from the socket
+    // > error queue.
+    let len = libc!(recvmsg(
+        socket.as_raw_fd(),
+        &mut msg,
+        libc::MSG_ERRQUEUE | libc::MSG_DONTWAIT
+    ))?;
+
+    // Safety: the control buffer is aligned to cmsghdr
+    let mut errors = unsafe { cmsg::decode::Iter::from_msghdr(&msg) }
+        .filter(|(cmsg, _)| recverr::is_match(cmsg.cmsg_level, cmsg.cmsg_type))
+        .map(|(_, value)| value);
+
+    let value = if let Some(value) = errors.next() {
+        value
+    } else {
+        return Ok(None);
+    };
+
+    // zero-copy completions don't quote a datagram so check them first
+    if let Some(completion) = zerocopy::decode(value) {
+        return Ok(Some(QueuedError::Zerocopy(completion)));
+    }
+
+    let remote_address = if let Some(remote_address) = msg.remote_address() {
+        remote_address
+    } else {
+        return Ok(None);
+    };
+
+    let len = (len as usize).min(payload.len());
+    Ok(recverr::decode(value).map(|error| QueuedError::Path {
+        remote_address,
+        error,
+        len,
+    }))
+}
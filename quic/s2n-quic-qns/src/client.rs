@@ -52,6 +52,8 @@ pub fn build(
             (Rustls, Bbr) => build!(build_rustls, Bbr, alpns),
             (Null, Cubic) => build!(build_null, Cubic),
             (Null, Bbr) => build!(build_null, Bbr),
+            (External, Cubic) => build!(build_external, Cubic),
+            (External, Bbr) => build!(build_external, Bbr),
         },
     )
 }
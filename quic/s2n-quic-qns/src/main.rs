@@ -4,6 +4,7 @@
 #![allow(unexpected_cfgs)]
 
 use structopt::StructOpt;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 
 pub type Error = Box<dyn 'static + std::error::Error + Send + Sync>;
 pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -33,18 +34,28 @@ const CRASH_ERROR_MESSAGE: &str = "The s2n-quic-qns application shut down unexpe
 static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 fn main() {
+    let args = Arguments::from_args_safe();
+
     let format = tracing_subscriber::fmt::format()
         .with_level(false) // don't include levels in formatted output
         .with_timer(tracing_subscriber::fmt::time::uptime())
         .with_ansi(false)
         .compact(); // Use a less verbose output format.
 
+    // the TLS shim exchanges frames over stdout so any logs need to go to stderr instead
+    let writer = if matches!(args, Ok(Arguments::TlsShim(_))) {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
+
     tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .event_format(format)
+        .with_writer(writer)
         .init();
 
-    match Arguments::from_args_safe() {
+    match args {
         Ok(args) => {
             if let Err(error) = args.run() {
                 eprintln!("Error: {error:?}");
@@ -69,6 +80,7 @@ fn main() {
 enum Arguments {
     Interop(Interop),
     Perf(Perf),
+    TlsShim(tls::external::shim::Shim),
 }
 
 impl Arguments {
@@ -76,6 +88,7 @@ impl Arguments {
         match self {
             Self::Interop(subject) => subject.run(),
             Self::Perf(subject) => subject.run(),
+            Self::TlsShim(subject) => subject.run(),
         }
     }
 }
@@ -8,6 +8,8 @@ use s2n_quic::provider::tls::rustls::rustls as rustls_crate;
 use std::{path::PathBuf, str::FromStr};
 use structopt::StructOpt;
 
+pub mod external;
+
 #[derive(Debug, StructOpt)]
 pub struct Server {
     #[structopt(long)]
@@ -24,6 +26,10 @@ pub struct Server {
     /// Must be at least 16 bytes
     #[structopt(long)]
     pub ticket_key: Option<String>,
+
+    /// The command to run for each connection with the external tls provider
+    #[structopt(long)]
+    pub tls_command: Option<String>,
 }
 
 impl Server {
@@ -80,6 +86,10 @@ impl Server {
     pub fn build_null(&self) -> Result<null::Provider> {
         Ok(null::Provider)
     }
+
+    pub fn build_external(&self) -> Result<external::Provider> {
+        external::Provider::new(self.tls_command.as_deref().unwrap_or_default())
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -93,6 +103,10 @@ pub struct Client {
     /// disable verification of the server certificate (rustls only)
     #[structopt(long)]
     pub disable_cert_verification: bool,
+
+    /// The command to run for each connection with the external tls provider
+    #[structopt(long)]
+    pub tls_command: Option<String>,
 }
 
 impl Client {
@@ -141,6 +155,10 @@ impl Client {
     pub fn build_null(&self) -> Result<null::Provider> {
         Ok(null::Provider)
     }
+
+    pub fn build_external(&self) -> Result<external::Provider> {
+        external::Provider::new(self.tls_command.as_deref().unwrap_or_default())
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -152,6 +170,8 @@ pub enum TlsProviders {
     Rustls,
     /// Use the null tls provider
     Null,
+    /// Proxy the handshake to the process started with `--tls-command`
+    External,
 }
 
 impl Default for TlsProviders {
@@ -170,6 +190,7 @@ impl core::fmt::Display for TlsProviders {
             TlsProviders::S2N => String::from("s2n-tls"),
             TlsProviders::Rustls => String::from("rustls"),
             TlsProviders::Null => String::from("null"),
+            TlsProviders::External => String::from("external"),
         };
         write!(f, "{}", str)
     }
@@ -182,6 +203,7 @@ impl FromStr for TlsProviders {
         Ok(match s {
             "rustls" => Self::Rustls,
             "null" => Self::Null,
+            "external" => Self::External,
             #[cfg(unix)]
             "s2n-tls" => Self::S2N,
             _ => {
@@ -0,0 +1,680 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A TLS provider which proxies the handshake to an external process
+//!
+//! Each connection spawns the configured command and exchanges length-prefixed frames with it
+//! over its stdin and stdout. The external process owns the TLS state machine and reports the
+//! traffic secrets it derives, from which the packet protection keys are derived in-process.
+//! This allows the QUIC crypto plumbing to be tested against any TLS implementation which can
+//! be wrapped in a small shim.
+//!
+//! Each frame is encoded as a 1 byte type, followed by a 4 byte big-endian length and the frame
+//! payload. Encryption levels are encoded as `0` for Initial, `1` for Handshake and `2` for
+//! Application.
+//!
+//! Frames sent to the process:
+//!
+//! * `START` (`0x01`): `is_server: u8, server_name_len: u16, server_name, transport_parameters`
+//!   is sent once, before any other frame.
+//! * `CRYPTO` (`0x02`): `level: u8, data` carries handshake data received from the peer.
+//!
+//! Frames sent by the process:
+//!
+//! * `CRYPTO` (`0x02`): `level: u8, data` carries handshake data to send to the peer.
+//! * `SECRETS` (`0x03`): `level: u8, cipher_suite: u16, client_secret_len: u8, client_secret,
+//!   server_secret` installs the traffic secrets for the Handshake or Application level.
+//! * `TRANSPORT_PARAMETERS` (`0x04`): the peer's encoded transport parameters, which must be sent
+//!   before the Application secrets.
+//! * `APPLICATION_PROTOCOL` (`0x05`): the negotiated application protocol.
+//! * `SERVER_NAME` (`0x06`): the server name requested by the client, sent by servers only.
+//! * `COMPLETE` (`0x07`): the handshake is complete.
+//! * `ALERT` (`0x08`): `description: u8` aborts the handshake with a TLS alert.
+//!
+//! Since the transport parameters are provided to the process up front, servers can't append
+//! parameters once the client's parameters are known. 0-RTT is not supported.
+
+use crate::Result;
+use bytes::Bytes;
+use s2n_codec::EncoderValue;
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::{
+        tls::{self, ApplicationParameters, CipherSuite, TlsExportError, TlsSession},
+        CryptoSuite,
+    },
+    endpoint, transport,
+};
+use s2n_quic_crypto::{
+    handshake::HandshakeKey, hkdf, one_rtt::OneRttKey, ring_aead as aead, Prk, SecretPair, Suite,
+};
+use std::{
+    fmt,
+    io::{self, BufReader, Read, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    task::{Poll, Waker},
+};
+
+pub mod shim;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Initial,
+    Handshake,
+    Application,
+}
+
+impl Level {
+    fn from_u8(value: u8) -> io::Result<Self> {
+        Ok(match value {
+            0 => Self::Initial,
+            1 => Self::Handshake,
+            2 => Self::Application,
+            _ => return Err(invalid_data("invalid encryption level")),
+        })
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Self::Initial => 0,
+            Self::Handshake => 1,
+            Self::Application => 2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Frame {
+    Start {
+        is_server: bool,
+        server_name: Vec<u8>,
+        transport_parameters: Vec<u8>,
+    },
+    Crypto {
+        level: Level,
+        data: Vec<u8>,
+    },
+    Secrets {
+        level: Level,
+        cipher_suite: u16,
+        client: Vec<u8>,
+        server: Vec<u8>,
+    },
+    TransportParameters(Vec<u8>),
+    ApplicationProtocol(Vec<u8>),
+    ServerName(Vec<u8>),
+    Complete,
+    Alert(u8),
+}
+
+impl Frame {
+    /// Writes the frame to the given stream
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut payload = vec![];
+
+        let tag = match self {
+            Self::Start {
+                is_server,
+                server_name,
+                transport_parameters,
+            } => {
+                let server_name_len: u16 = server_name
+                    .len()
+                    .try_into()
+                    .map_err(|_| invalid_data("server name too long"))?;
+                payload.push(*is_server as u8);
+                payload.extend_from_slice(&server_name_len.to_be_bytes());
+                payload.extend_from_slice(server_name);
+                payload.extend_from_slice(transport_parameters);
+                0x01
+            }
+            Self::Crypto { level, data } => {
+                payload.push(level.as_u8());
+                payload.extend_from_slice(data);
+                0x02
+            }
+            Self::Secrets {
+                level,
+                cipher_suite,
+                client,
+                server,
+            } => {
+                let client_len: u8 = client
+                    .len()
+                    .try_into()
+                    .map_err(|_| invalid_data("secret too long"))?;
+                payload.push(level.as_u8());
+                payload.extend_from_slice(&cipher_suite.to_be_bytes());
+                payload.push(client_len);
+                payload.extend_from_slice(client);
+                payload.extend_from_slice(server);
+                0x03
+            }
+            Self::TransportParameters(value) => {
+                payload.extend_from_slice(value);
+                0x04
+            }
+            Self::ApplicationProtocol(value) => {
+                payload.extend_from_slice(value);
+                0x05
+            }
+            Self::ServerName(value) => {
+                payload.extend_from_slice(value);
+                0x06
+            }
+            Self::Complete => 0x07,
+            Self::Alert(description) => {
+                payload.push(*description);
+                0x08
+            }
+        };
+
+        let len: u32 = payload
+            .len()
+            .try_into()
+            .map_err(|_| invalid_data("frame too long"))?;
+        w.write_all(&[tag])?;
+        w.write_all(&len.to_be_bytes())?;
+        w.write_all(&payload)?;
+        w.flush()
+    }
+
+    /// Reads a frame from the given stream
+    ///
+    /// Returns `None` if the stream was closed before the start of a frame.
+    pub fn read<R: Read>(r: &mut R) -> io::Result<Option<Self>> {
+        let mut header = [0u8; 5];
+        match r.read_exact(&mut header[..1]) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        r.read_exact(&mut header[1..])?;
+
+        let tag = header[0];
+        let len = u32::from_be_bytes(header[1..].try_into().unwrap()) as usize;
+        let mut payload = vec![0; len];
+        r.read_exact(&mut payload)?;
+
+        fn split(payload: &mut Vec<u8>, len: usize) -> io::Result<Vec<u8>> {
+            if payload.len() < len {
+                return Err(invalid_data("frame too short"));
+            }
+            let rest = payload.split_off(len);
+            Ok(core::mem::replace(payload, rest))
+        }
+
+        fn split_u8(payload: &mut Vec<u8>) -> io::Result<u8> {
+            Ok(split(payload, 1)?[0])
+        }
+
+        fn split_u16(payload: &mut Vec<u8>) -> io::Result<u16> {
+            let value = split(payload, 2)?;
+            Ok(u16::from_be_bytes([value[0], value[1]]))
+        }
+
+        let frame = match tag {
+            0x01 => {
+                let is_server = split_u8(&mut payload)? != 0;
+                let server_name_len = split_u16(&mut payload)? as usize;
+                let server_name = split(&mut payload, server_name_len)?;
+                Self::Start {
+                    is_server,
+                    server_name,
+                    transport_parameters: payload,
+                }
+            }
+            0x02 => {
+                let level = Level::from_u8(split_u8(&mut payload)?)?;
+                Self::Crypto {
+                    level,
+                    data: payload,
+                }
+            }
+            0x03 => {
+                let level = Level::from_u8(split_u8(&mut payload)?)?;
+                let cipher_suite = split_u16(&mut payload)?;
+                let client_len = split_u8(&mut payload)? as usize;
+                let client = split(&mut payload, client_len)?;
+                Self::Secrets {
+                    level,
+                    cipher_suite,
+                    client,
+                    server: payload,
+                }
+            }
+            0x04 => Self::TransportParameters(payload),
+            0x05 => Self::ApplicationProtocol(payload),
+            0x06 => Self::ServerName(payload),
+            0x07 => Self::Complete,
+            0x08 => Self::Alert(split_u8(&mut payload)?),
+            _ => return Err(invalid_data("invalid frame type")),
+        };
+
+        Ok(Some(frame))
+    }
+}
+
+fn invalid_data(reason: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+/// Runs the TLS handshake in an external process
+#[derive(Clone, Debug)]
+pub struct Provider {
+    command: Arc<[String]>,
+}
+
+impl Provider {
+    /// Creates a provider which spawns the given command, split on whitespace, for each
+    /// connection
+    pub fn new(command: &str) -> Result<Self> {
+        let command: Arc<[String]> = command.split_whitespace().map(String::from).collect();
+
+        if command.is_empty() {
+            return Err("the external TLS command is empty".into());
+        }
+
+        Ok(Self { command })
+    }
+}
+
+impl s2n_quic::provider::tls::Provider for Provider {
+    type Server = Endpoint;
+    type Client = Endpoint;
+    type Error = String;
+
+    fn start_server(self) -> Result<Self::Server, Self::Error> {
+        Ok(Endpoint {
+            command: self.command,
+        })
+    }
+
+    fn start_client(self) -> Result<Self::Client, Self::Error> {
+        Ok(Endpoint {
+            command: self.command,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Endpoint {
+    command: Arc<[String]>,
+}
+
+impl tls::Endpoint for Endpoint {
+    type Session = Session;
+
+    fn new_server_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        let start = Frame::Start {
+            is_server: true,
+            server_name: vec![],
+            transport_parameters: transport_parameters.encode_to_vec(),
+        };
+        Session::new(&self.command, endpoint::Type::Server, start)
+    }
+
+    fn new_client_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+        server_name: ServerName,
+    ) -> Self::Session {
+        let start = Frame::Start {
+            is_server: false,
+            server_name: server_name.as_bytes().to_vec(),
+            transport_parameters: transport_parameters.encode_to_vec(),
+        };
+        Session::new(&self.command, endpoint::Type::Client, start)
+    }
+
+    fn max_tag_length(&self) -> usize {
+        s2n_quic_crypto::MAX_TAG_LEN
+    }
+}
+
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    frames: mpsc::Receiver<io::Result<Frame>>,
+}
+
+impl Process {
+    fn spawn(command: &[String], waker: Arc<Mutex<Option<Waker>>>) -> io::Result<Self> {
+        let mut child = Command::new(&command[0])
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, frames) = mpsc::channel();
+
+        // the session is polled without blocking so frames are read on a separate thread, which
+        // wakes the connection for each frame
+        std::thread::spawn(move || {
+            let mut stdout = BufReader::new(stdout);
+
+            loop {
+                let frame = Frame::read(&mut stdout).transpose();
+                let is_done = !matches!(frame, Some(Ok(_)));
+
+                if let Some(frame) = frame {
+                    if sender.send(frame).is_err() {
+                        break;
+                    }
+                }
+
+                if let Some(waker) = waker.lock().unwrap().as_ref() {
+                    waker.wake_by_ref();
+                }
+
+                if is_done {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            frames,
+        })
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+pub struct Session {
+    endpoint: endpoint::Type,
+    process: io::Result<Process>,
+    waker: Arc<Mutex<Option<Waker>>>,
+    start: Option<Frame>,
+    peer_parameters: Option<Vec<u8>>,
+    cipher_suite: CipherSuite,
+    has_one_rtt_keys: bool,
+    is_complete: bool,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("endpoint", &self.endpoint)
+            .field("cipher_suite", &self.cipher_suite)
+            .field("has_one_rtt_keys", &self.has_one_rtt_keys)
+            .field("is_complete", &self.is_complete)
+            .finish()
+    }
+}
+
+impl Session {
+    fn new(command: &[String], endpoint: endpoint::Type, start: Frame) -> Self {
+        let waker = Arc::new(Mutex::new(None));
+        let process = Process::spawn(command, waker.clone());
+
+        Self {
+            endpoint,
+            process,
+            waker,
+            start: Some(start),
+            peer_parameters: None,
+            cipher_suite: CipherSuite::Unknown,
+            has_one_rtt_keys: false,
+            is_complete: false,
+        }
+    }
+
+    fn send(&mut self, frame: &Frame) -> Result<(), transport::Error> {
+        let process = self.process.as_mut().map_err(|_| {
+            transport::Error::INTERNAL_ERROR.with_reason("could not spawn the external TLS process")
+        })?;
+
+        frame.write(&mut process.stdin).map_err(|_| {
+            transport::Error::INTERNAL_ERROR
+                .with_reason("could not write to the external TLS process")
+        })
+    }
+
+    fn on_frame<C: tls::Context<Self>>(
+        &mut self,
+        frame: Frame,
+        context: &mut C,
+    ) -> Result<(), transport::Error> {
+        match frame {
+            Frame::Crypto { level, data } => {
+                let data = Bytes::from(data);
+                match level {
+                    Level::Initial => context.send_initial(data),
+                    Level::Handshake => context.send_handshake(data),
+                    Level::Application => context.send_application(data),
+                }
+            }
+            Frame::Secrets {
+                level,
+                cipher_suite,
+                client,
+                server,
+            } => {
+                let (prk_algo, aead_algo, cipher_suite) = algorithms(cipher_suite)
+                    .ok_or(tls::Error::HANDSHAKE_FAILURE.with_reason("unsupported cipher suite"))?;
+                self.cipher_suite = cipher_suite;
+
+                let pair = SecretPair {
+                    client: Prk::new_less_safe(prk_algo, &client),
+                    server: Prk::new_less_safe(prk_algo, &server),
+                };
+
+                match level {
+                    Level::Handshake => {
+                        let (key, header_key) = HandshakeKey::new(self.endpoint, aead_algo, pair)
+                            .ok_or(tls::Error::INTERNAL_ERROR)?;
+                        context.on_handshake_keys(key, header_key)?;
+                    }
+                    Level::Application => {
+                        let transport_parameters =
+                            self.peer_parameters.as_deref().ok_or_else(|| {
+                                tls::Error::MISSING_EXTENSION
+                                    .with_reason("missing peer transport parameters")
+                            })?;
+
+                        let (key, header_key) = OneRttKey::new(self.endpoint, aead_algo, pair)
+                            .ok_or(tls::Error::INTERNAL_ERROR)?;
+                        context.on_one_rtt_keys(
+                            key,
+                            header_key,
+                            ApplicationParameters {
+                                transport_parameters,
+                            },
+                        )?;
+                        self.has_one_rtt_keys = true;
+                    }
+                    Level::Initial => {
+                        return Err(transport::Error::PROTOCOL_VIOLATION
+                            .with_reason("initial secrets are derived by the transport"));
+                    }
+                }
+            }
+            Frame::TransportParameters(transport_parameters) => {
+                if self.endpoint.is_server() {
+                    let mut server_params = vec![];
+                    context.on_client_application_params(
+                        ApplicationParameters {
+                            transport_parameters: &transport_parameters,
+                        },
+                        &mut server_params,
+                    )?;
+
+                    if !server_params.is_empty() {
+                        return Err(transport::Error::INTERNAL_ERROR.with_reason(
+                            "the external TLS provider can't append transport parameters",
+                        ));
+                    }
+                }
+
+                self.peer_parameters = Some(transport_parameters);
+            }
+            Frame::ApplicationProtocol(protocol) => {
+                context.on_application_protocol(Bytes::from(protocol))?;
+            }
+            Frame::ServerName(server_name) => {
+                let server_name = String::from_utf8(server_name)
+                    .map_err(|_| tls::Error::DECODE_ERROR.with_reason("invalid server name"))?;
+                context.on_server_name(server_name.into())?;
+            }
+            Frame::Complete => {
+                if !self.has_one_rtt_keys {
+                    return Err(transport::Error::PROTOCOL_VIOLATION
+                        .with_reason("the handshake completed without application secrets"));
+                }
+
+                context.on_tls_exporter_ready(&Exporter(self.cipher_suite))?;
+                context.on_handshake_complete()?;
+                self.is_complete = true;
+            }
+            Frame::Alert(description) => {
+                return Err(tls::Error::new(description).into());
+            }
+            Frame::Start { .. } => {
+                return Err(transport::Error::PROTOCOL_VIOLATION
+                    .with_reason("unexpected frame from the external TLS process"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl tls::Session for Session {
+    fn poll<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Poll<Result<(), transport::Error>> {
+        if let Some(start) = self.start.take() {
+            self.send(&start)?;
+        }
+
+        while let Some(data) = context.receive_initial(None) {
+            self.send(&Frame::Crypto {
+                level: Level::Initial,
+                data: data.to_vec(),
+            })?;
+        }
+
+        while let Some(data) = context.receive_handshake(None) {
+            self.send(&Frame::Crypto {
+                level: Level::Handshake,
+                data: data.to_vec(),
+            })?;
+        }
+
+        while let Some(data) = context.receive_application(None) {
+            self.send(&Frame::Crypto {
+                level: Level::Application,
+                data: data.to_vec(),
+            })?;
+        }
+
+        // register the waker before draining the frames so a frame read in the meantime isn't
+        // missed
+        *self.waker.lock().unwrap() = Some(context.waker().clone());
+
+        loop {
+            let Ok(process) = self.process.as_ref() else {
+                break;
+            };
+
+            match process.frames.try_recv() {
+                Ok(Ok(frame)) => self.on_frame(frame, context)?,
+                Ok(Err(_)) => {
+                    return Err(transport::Error::INTERNAL_ERROR
+                        .with_reason("could not read from the external TLS process"))
+                    .into();
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    if self.is_complete {
+                        break;
+                    }
+
+                    return Err(transport::Error::INTERNAL_ERROR
+                        .with_reason("the external TLS process exited"))
+                    .into();
+                }
+            }
+        }
+
+        if self.is_complete {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl CryptoSuite for Session {
+    type HandshakeKey = <Suite as CryptoSuite>::HandshakeKey;
+    type HandshakeHeaderKey = <Suite as CryptoSuite>::HandshakeHeaderKey;
+    type InitialKey = <Suite as CryptoSuite>::InitialKey;
+    type InitialHeaderKey = <Suite as CryptoSuite>::InitialHeaderKey;
+    type OneRttKey = <Suite as CryptoSuite>::OneRttKey;
+    type OneRttHeaderKey = <Suite as CryptoSuite>::OneRttHeaderKey;
+    type ZeroRttKey = <Suite as CryptoSuite>::ZeroRttKey;
+    type ZeroRttHeaderKey = <Suite as CryptoSuite>::ZeroRttHeaderKey;
+    type RetryKey = <Suite as CryptoSuite>::RetryKey;
+}
+
+/// Exposes the negotiated cipher suite to the connection
+///
+/// The secrets needed to export keying material are held by the external process, so exporting
+/// is not supported.
+struct Exporter(CipherSuite);
+
+impl TlsSession for Exporter {
+    fn tls_exporter(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), TlsExportError> {
+        Err(TlsExportError::failure())
+    }
+
+    fn cipher_suite(&self) -> CipherSuite {
+        self.0
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc8446#appendix-B.4
+//# This specification defines the following cipher suites for use with
+//# TLS 1.3.
+fn algorithms(
+    cipher_suite: u16,
+) -> Option<(hkdf::Algorithm, &'static aead::Algorithm, CipherSuite)> {
+    Some(match cipher_suite {
+        0x1301 => (
+            hkdf::HKDF_SHA256,
+            &aead::AES_128_GCM,
+            CipherSuite::TLS_AES_128_GCM_SHA256,
+        ),
+        0x1302 => (
+            hkdf::HKDF_SHA384,
+            &aead::AES_256_GCM,
+            CipherSuite::TLS_AES_256_GCM_SHA384,
+        ),
+        0x1303 => (
+            hkdf::HKDF_SHA256,
+            &aead::CHACHA20_POLY1305,
+            CipherSuite::TLS_CHACHA20_POLY1305_SHA256,
+        ),
+        _ => return None,
+    })
+}
@@ -0,0 +1,241 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A reference implementation of the external TLS process, backed by rustls
+//!
+//! This can be used to check the external provider end-to-end, and as a starting point for
+//! wrapping other TLS stacks.
+
+use super::{Frame, Level};
+#[allow(deprecated)]
+use crate::{tls::rustls_crate, Result};
+use rustls_crate::{
+    crypto::{aws_lc_rs, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName},
+    quic::{self, KeyChange, Version},
+    version, ClientConfig, KeyLog, RootCertStore, ServerConfig,
+};
+use s2n_quic_core::crypto::tls::testing::certificates::{CERT_DER, KEY_DER};
+use std::{
+    collections::HashMap,
+    io::{self, BufReader, Write},
+    sync::{Arc, Mutex},
+};
+use structopt::StructOpt;
+
+/// Runs a TLS handshake over stdin and stdout for the external TLS provider
+///
+/// The testing certificates are used by both the server and the client.
+#[derive(Debug, StructOpt)]
+pub struct Shim {
+    /// The application protocols to offer or accept
+    #[structopt(long, default_value = "hq-interop")]
+    alpn: Vec<String>,
+}
+
+impl Shim {
+    pub fn run(&self) -> Result<()> {
+        let mut input = BufReader::new(io::stdin().lock());
+        let mut output = io::stdout().lock();
+
+        let Some(Frame::Start {
+            is_server,
+            server_name,
+            transport_parameters,
+        }) = Frame::read(&mut input)?
+        else {
+            return Err("expected a START frame".into());
+        };
+
+        let secrets = Arc::new(Secrets::default());
+        let alpn: Vec<Vec<u8>> = self.alpn.iter().map(|p| p.as_bytes().to_vec()).collect();
+
+        let mut connection: quic::Connection = if is_server {
+            let mut config = ServerConfig::builder_with_provider(provider())
+                .with_protocol_versions(&[&version::TLS13])?
+                .with_no_client_auth()
+                .with_single_cert(
+                    vec![CertificateDer::from(CERT_DER.to_vec())],
+                    PrivateKeyDer::Pkcs8(KEY_DER.to_vec().into()),
+                )?;
+            config.alpn_protocols = alpn;
+            config.key_log = secrets.clone();
+
+            quic::ServerConnection::new(Arc::new(config), Version::V1, transport_parameters)?.into()
+        } else {
+            let mut roots = RootCertStore::empty();
+            roots.add(CertificateDer::from(CERT_DER.to_vec()))?;
+
+            let mut config = ClientConfig::builder_with_provider(provider())
+                .with_protocol_versions(&[&version::TLS13])?
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+            config.alpn_protocols = alpn;
+            config.key_log = secrets.clone();
+
+            let server_name = ServerName::try_from(String::from_utf8(server_name)?)?;
+
+            quic::ClientConnection::new(
+                Arc::new(config),
+                Version::V1,
+                server_name,
+                transport_parameters,
+            )?
+            .into()
+        };
+
+        let mut state = State {
+            level: Level::Initial,
+            secrets,
+            has_transport_parameters: false,
+            has_application_protocol: false,
+            has_server_name: false,
+            is_complete: false,
+        };
+
+        loop {
+            state.flush(&mut connection, &mut output)?;
+
+            let Some(frame) = Frame::read(&mut input)? else {
+                // the connection closed the session
+                return Ok(());
+            };
+
+            let Frame::Crypto { data, .. } = frame else {
+                return Err("unexpected frame from the connection".into());
+            };
+
+            if let Err(error) = connection.read_hs(&data) {
+                // fall back to an internal_error alert if rustls didn't select one
+                let description = connection.alert().map_or(80, u8::from);
+                Frame::Alert(description).write(&mut output)?;
+                return Err(error.into());
+            }
+        }
+    }
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(aws_lc_rs::default_provider())
+}
+
+struct State {
+    level: Level,
+    secrets: Arc<Secrets>,
+    has_transport_parameters: bool,
+    has_application_protocol: bool,
+    has_server_name: bool,
+    is_complete: bool,
+}
+
+impl State {
+    /// Writes any pending handshake data, secrets and events to the connection
+    fn flush<W: Write>(&mut self, connection: &mut quic::Connection, output: &mut W) -> Result<()> {
+        loop {
+            // the peer's transport parameters need to be sent prior to the application secrets
+            self.emit_events(connection, output)?;
+
+            let mut data = vec![];
+            let key_change = connection.write_hs(&mut data);
+
+            if !data.is_empty() {
+                Frame::Crypto {
+                    level: self.level,
+                    data,
+                }
+                .write(output)?;
+            }
+
+            let (level, client, server) = match key_change {
+                Some(KeyChange::Handshake { .. }) => (
+                    Level::Handshake,
+                    "CLIENT_HANDSHAKE_TRAFFIC_SECRET",
+                    "SERVER_HANDSHAKE_TRAFFIC_SECRET",
+                ),
+                Some(KeyChange::OneRtt { .. }) => (
+                    Level::Application,
+                    "CLIENT_TRAFFIC_SECRET_0",
+                    "SERVER_TRAFFIC_SECRET_0",
+                ),
+                None => break,
+            };
+
+            let cipher_suite = connection
+                .negotiated_cipher_suite()
+                .ok_or("the cipher suite was not negotiated")?
+                .suite();
+
+            Frame::Secrets {
+                level,
+                cipher_suite: cipher_suite.into(),
+                client: self.secrets.take(client)?,
+                server: self.secrets.take(server)?,
+            }
+            .write(output)?;
+
+            self.level = level;
+        }
+
+        if !self.is_complete && self.level == Level::Application && !connection.is_handshaking() {
+            self.emit_events(connection, output)?;
+            Frame::Complete.write(output)?;
+            self.is_complete = true;
+        }
+
+        Ok(())
+    }
+
+    fn emit_events<W: Write>(
+        &mut self,
+        connection: &quic::Connection,
+        output: &mut W,
+    ) -> Result<()> {
+        if !self.has_transport_parameters {
+            if let Some(params) = connection.quic_transport_parameters() {
+                Frame::TransportParameters(params.to_vec()).write(output)?;
+                self.has_transport_parameters = true;
+            }
+        }
+
+        if !self.has_application_protocol {
+            if let Some(protocol) = connection.alpn_protocol() {
+                Frame::ApplicationProtocol(protocol.to_vec()).write(output)?;
+                self.has_application_protocol = true;
+            }
+        }
+
+        if !self.has_server_name {
+            if let quic::Connection::Server(server) = connection {
+                if let Some(server_name) = server.server_name() {
+                    Frame::ServerName(server_name.as_bytes().to_vec()).write(output)?;
+                    self.has_server_name = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Captures the traffic secrets derived by rustls
+#[derive(Debug, Default)]
+struct Secrets(Mutex<HashMap<String, Vec<u8>>>);
+
+impl Secrets {
+    fn take(&self, label: &str) -> Result<Vec<u8>> {
+        self.0
+            .lock()
+            .unwrap()
+            .remove(label)
+            .ok_or_else(|| format!("missing {label}").into())
+    }
+}
+
+impl KeyLog for Secrets {
+    fn log(&self, label: &str, _client_random: &[u8], secret: &[u8]) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(label.to_string(), secret.to_vec());
+    }
+}
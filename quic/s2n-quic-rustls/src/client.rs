@@ -1,25 +1,43 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, cipher_suite::default_crypto_provider, session::Session, Error};
+use crate::{
+    certificate, cipher_suite::default_crypto_provider, pinned::PinnedCertificateVerifier,
+    session::Session, Error,
+};
 use core::convert::TryFrom;
-use rustls::{ClientConfig, ConfigBuilder, WantsVerifier};
+use rustls::{
+    client::{EchConfig, EchMode},
+    pki_types::EchConfigListBytes,
+    ClientConfig, ConfigBuilder, WantsVerifier,
+};
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls};
 use std::sync::Arc;
 
 /// Create a QUIC client specific [rustls::ConfigBuilder].
 ///
-/// Uses aws_lc_rs as the crypto provider and sets QUIC specific protocol versions.
-fn default_config_builder() -> Result<ConfigBuilder<ClientConfig, WantsVerifier>, rustls::Error> {
+/// Uses aws_lc_rs as the crypto provider and sets QUIC specific protocol versions. If an
+/// [EchMode] is provided, the ClientHello is encrypted with it.
+fn default_config_builder(
+    ech_mode: Option<EchMode>,
+) -> Result<ConfigBuilder<ClientConfig, WantsVerifier>, rustls::Error> {
     let tls13_cipher_suite_crypto_provider = default_crypto_provider()?;
-    ClientConfig::builder_with_provider(tls13_cipher_suite_crypto_provider.into())
-        .with_protocol_versions(crate::PROTOCOL_VERSIONS)
+    let builder = ClientConfig::builder_with_provider(tls13_cipher_suite_crypto_provider.into());
+
+    if let Some(ech_mode) = ech_mode {
+        // ECH implies TLS 1.3, which is the only version supported by QUIC
+        builder.with_ech(ech_mode)
+    } else {
+        builder.with_protocol_versions(crate::PROTOCOL_VERSIONS)
+    }
 }
 
 #[derive(Clone)]
 pub struct Client {
     config: Arc<ClientConfig>,
+    /// Set if the config was built with an ECH configuration
+    ech_offered: bool,
 }
 
 impl Client {
@@ -34,6 +52,7 @@ impl Client {
     pub fn new(config: ClientConfig) -> Self {
         Self {
             config: Arc::new(config),
+            ech_offered: false,
         }
     }
 
@@ -61,7 +80,10 @@ impl From<ClientConfig> for Client {
 // TODO this should be removed after removing deprecated re-exports
 impl From<Arc<ClientConfig>> for Client {
     fn from(config: Arc<ClientConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            ech_offered: false,
+        }
     }
 }
 
@@ -96,6 +118,7 @@ impl tls::Endpoint for Client {
         .expect("could not create rustls client session");
 
         Session::new(session.into(), Some(server_name))
+            .with_encrypted_client_hello(self.ech_offered)
     }
 
     fn max_tag_length(&self) -> usize {
@@ -107,6 +130,8 @@ pub struct Builder {
     cert_store: rustls::RootCertStore,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    ech_config_list: Option<Vec<u8>>,
+    pinned_certificates: Vec<rustls::pki_types::CertificateDer<'static>>,
 }
 
 impl Default for Builder {
@@ -121,6 +146,8 @@ impl Builder {
             cert_store: rustls::RootCertStore::empty(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            ech_config_list: None,
+            pinned_certificates: vec![],
         }
     }
 
@@ -138,6 +165,26 @@ impl Builder {
         Ok(self)
     }
 
+    /// Pins the server to the end-entity certificate of the provided chain
+    ///
+    /// Once a certificate is pinned, the server is only accepted if it presents exactly one of
+    /// the pinned certificates. Trust anchors configured with `with_certificate`, certificate
+    /// validity periods, and the server name are no longer checked, which makes pinning
+    /// suitable for self-signed certificates on device-to-device and internal links.
+    ///
+    /// This method can be called multiple times to accept any one of several certificates.
+    pub fn with_pinned_certificate<C: certificate::IntoCertificate>(
+        mut self,
+        certificate: C,
+    ) -> Result<Self, Error> {
+        let certificates = certificate.into_certificate()?;
+        let end_entity = certificates.0.into_iter().next().ok_or_else(|| {
+            rustls::Error::General("Certificate chain needs to have at least one entry".to_string())
+        })?;
+        self.pinned_certificates.push(end_entity);
+        Ok(self)
+    }
+
     pub fn with_max_cert_chain_depth(self, len: u16) -> Result<Self, Error> {
         // TODO is there a way to configure this?
         let _ = len;
@@ -157,9 +204,23 @@ impl Builder {
         Ok(self)
     }
 
+    /// Encrypts the ClientHello with one of the configurations in the encoded `ECHConfigList`
+    ///
+    /// The configuration list is typically fetched from the `ech` parameter of the server's
+    /// HTTPS DNS record. The outer ClientHello carries the public name of the selected
+    /// configuration, so the server name of the connection is hidden on the wire. The
+    /// `EncryptedClientHello` event reports whether the server accepted the offer.
+    ///
+    /// Since the configuration is specific to the servers publishing it, a separate client
+    /// should be built for connecting to servers with a different configuration.
+    pub fn with_encrypted_client_hello(mut self, ech_config_list: &[u8]) -> Result<Self, Error> {
+        self.ech_config_list = Some(ech_config_list.to_vec());
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, Error> {
         // TODO load system root store?
-        if self.cert_store.is_empty() {
+        if self.cert_store.is_empty() && self.pinned_certificates.is_empty() {
             //= https://www.rfc-editor.org/rfc/rfc9001#section-4.4
             //# A client MUST authenticate the identity of the server.
             return Err(
@@ -167,9 +228,32 @@ impl Builder {
             );
         }
 
-        let mut config = default_config_builder()?
-            .with_root_certificates(self.cert_store)
-            .with_no_client_auth();
+        let ech_offered = self.ech_config_list.is_some();
+        let ech_mode = if let Some(ech_config_list) = self.ech_config_list {
+            let ech_config = EchConfig::new(
+                EchConfigListBytes::from(ech_config_list),
+                rustls::crypto::aws_lc_rs::hpke::ALL_SUPPORTED_SUITES,
+            )?;
+            Some(ech_config.into())
+        } else {
+            None
+        };
+
+        let builder = default_config_builder(ech_mode)?;
+        let mut config = if self.pinned_certificates.is_empty() {
+            builder
+                .with_root_certificates(self.cert_store)
+                .with_no_client_auth()
+        } else {
+            let verifier = PinnedCertificateVerifier::new(
+                self.pinned_certificates,
+                builder.crypto_provider().signature_verification_algorithms,
+            );
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(verifier))
+                .with_no_client_auth()
+        };
 
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
@@ -178,7 +262,9 @@ impl Builder {
             config.key_log = key_log;
         }
 
-        #[allow(deprecated)]
-        Ok(Client::new(config))
+        Ok(Client {
+            config: Arc::new(config),
+            ech_offered,
+        })
     }
 }
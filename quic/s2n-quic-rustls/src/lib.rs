@@ -25,6 +25,7 @@ type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 mod cipher_suite;
 mod error;
+mod pinned;
 mod session;
 
 pub mod certificate;
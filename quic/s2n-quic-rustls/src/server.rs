@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, cipher_suite::default_crypto_provider, session::Session, Error};
+use crate::{
+    certificate, cipher_suite::default_crypto_provider, pinned::PinnedCertificateVerifier,
+    session::Session, Error,
+};
 use rustls::{crypto::aws_lc_rs, ConfigBuilder, ServerConfig, WantsVerifier};
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls};
@@ -102,6 +105,7 @@ pub struct Builder {
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
     prefer_server_cipher_suite_order: bool,
+    pinned_client_certificates: Vec<rustls::pki_types::CertificateDer<'static>>,
 }
 
 impl Default for Builder {
@@ -117,6 +121,7 @@ impl Builder {
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
             prefer_server_cipher_suite_order: true,
+            pinned_client_certificates: vec![],
         }
     }
 
@@ -162,8 +167,37 @@ impl Builder {
         Ok(self)
     }
 
+    /// Requires clients to authenticate with the end-entity certificate of the provided chain
+    ///
+    /// Once a certificate is pinned, clients must present exactly one of the pinned
+    /// certificates. Chain building and certificate validity periods are not checked, which
+    /// makes pinning suitable for self-signed certificates on device-to-device and internal
+    /// links.
+    ///
+    /// This method can be called multiple times to accept any one of several certificates.
+    pub fn with_pinned_client_certificate<C: certificate::IntoCertificate>(
+        mut self,
+        certificate: C,
+    ) -> Result<Self, Error> {
+        let certificates = certificate.into_certificate()?;
+        let end_entity = certificates.0.into_iter().next().ok_or_else(|| {
+            rustls::Error::General("Certificate chain needs to have at least one entry".to_string())
+        })?;
+        self.pinned_client_certificates.push(end_entity);
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Server, Error> {
-        let builder = default_config_builder()?.with_no_client_auth();
+        let builder = default_config_builder()?;
+        let builder = if self.pinned_client_certificates.is_empty() {
+            builder.with_no_client_auth()
+        } else {
+            let verifier = PinnedCertificateVerifier::new(
+                self.pinned_client_certificates,
+                builder.crypto_provider().signature_verification_algorithms,
+            );
+            builder.with_client_cert_verifier(Arc::new(verifier))
+        };
 
         let mut config = if let Some(cert_resolver) = self.cert_resolver {
             builder.with_cert_resolver(cert_resolver)
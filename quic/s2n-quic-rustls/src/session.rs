@@ -21,6 +21,11 @@ pub struct Session {
     emitted_handshake_complete: bool,
     emitted_server_name: bool,
     emitted_application_protocol: bool,
+    emitted_encrypted_client_hello: bool,
+    /// Set if the client offered ECH in its ClientHello
+    ech_offered: bool,
+    /// Set if the server rejected the ECH offer
+    ech_rejected: bool,
     server_name: Option<ServerName>,
 }
 
@@ -79,14 +84,32 @@ impl Session {
             emitted_handshake_complete: false,
             emitted_server_name: false,
             emitted_application_protocol: false,
+            emitted_encrypted_client_hello: false,
+            ech_offered: false,
+            ech_rejected: false,
             server_name,
         }
     }
 
+    /// Marks that the client offered Encrypted Client Hello in its ClientHello
+    pub(crate) fn with_encrypted_client_hello(mut self, offered: bool) -> Self {
+        self.ech_offered = offered;
+        self
+    }
+
     fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
         self.connection
             .read_hs(crypto_data)
-            .map_err(crate::error::reason)
+            .map_err(|error| {
+                // note the rejection so it's reported in the event
+                if let rustls::Error::PeerIncompatible(
+                    rustls::PeerIncompatible::ServerRejectedEncryptedClientHello(_),
+                ) = &error
+                {
+                    self.ech_rejected = true;
+                }
+                crate::error::reason(error)
+            })
             .map_err(|reason| {
                 //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
                 //# QUIC is only able to convey an alert level of "fatal".  In TLS 1.3,
@@ -319,6 +342,21 @@ impl Session {
                 self.emitted_application_protocol = true;
             }
         }
+        if self.ech_offered && !self.emitted_encrypted_client_hello {
+            // rustls aborts the handshake when the offer is rejected, so completing the handshake
+            // means the server accepted it
+            let status = if self.ech_rejected {
+                Some(tls::EchStatus::Rejected)
+            } else if !self.connection.is_handshaking() {
+                Some(tls::EchStatus::Accepted)
+            } else {
+                None
+            };
+            if let Some(status) = status {
+                context.on_encrypted_client_hello(status)?;
+                self.emitted_encrypted_client_hello = true;
+            }
+        }
 
         Ok(())
     }
@@ -347,6 +385,15 @@ impl tls::Session for Session {
         self.emit_events(context)?;
         result
     }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), tls::TlsExportError> {
+        tls::TlsSession::tls_exporter(self, label, context, output)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
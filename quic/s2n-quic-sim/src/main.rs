@@ -10,6 +10,7 @@ mod batch;
 mod query;
 mod report;
 mod run;
+mod scenario;
 mod stats;
 
 #[derive(Debug, StructOpt)]
@@ -18,6 +19,7 @@ enum Args {
     Run(Box<run::Run>),
     Report(report::Report),
     Batch(batch::Batch),
+    Scenario(scenario::Scenario),
 }
 
 fn main() -> Result {
@@ -37,6 +39,7 @@ fn main() -> Result {
         Args::Run(args) => args.run(),
         Args::Report(args) => args.run(),
         Args::Batch(args) => args.run(),
+        Args::Scenario(args) => args.run(),
     }
 }
 
@@ -4,7 +4,7 @@
 use crate::{stats, Result};
 use indicatif::{ParallelProgressIterator, ProgressBar};
 use rayon::prelude::*;
-use s2n_quic::provider::io::testing::{test_seed, Model};
+use s2n_quic::provider::io::testing::{test_seed, Model, QueueDiscipline};
 use structopt::StructOpt;
 
 mod config;
@@ -14,7 +14,7 @@ mod endpoint;
 mod events;
 
 mod range;
-use range::CliRange;
+pub use range::CliRange;
 
 #[derive(Debug, StructOpt)]
 pub struct Run {
@@ -168,6 +168,17 @@ impl Run {
         zero_param!(transmit_rate, set_transmit_rate);
         zero_param!(max_inflight, set_max_inflight);
         zero_param!(inflight_delay_threshold, set_inflight_delay_threshold);
+        zero_param!(bandwidth, set_bandwidth);
+        zero_param!(queue_limit, set_queue_limit);
+
+        let codel_target = self.codel_target.gen_duration();
+        if !codel_target.is_zero() {
+            model.set_queue_discipline(QueueDiscipline::CoDel {
+                target: codel_target,
+                interval: core::time::Duration::from_millis(100),
+            });
+        }
+        events.codel_target = Some(codel_target.into());
 
         events.into()
     }
@@ -91,6 +91,18 @@ config!(
         #[default = "0"]
         inflight_delay_threshold: CliRange<u64>,
 
+        #[name = "bandwidth"]
+        #[default = "0"]
+        bandwidth: CliRange<u64>,
+
+        #[name = "queue_limit"]
+        #[default = "0"]
+        queue_limit: CliRange<u64>,
+
+        #[name = "codel_target"]
+        #[default = "0ms"]
+        codel_target: CliRange<Duration>,
+
         #[name = "clients"]
         #[default = "1"]
         clients: CliRange<u32>,
@@ -6,7 +6,7 @@ use s2n_quic::{
     client::Connect,
     provider::{
         event::tracing::Subscriber as Tracing,
-        io::testing::{primary, rand, spawn, time, Handle, Result},
+        io::testing::{primary, rand, spawn, time, Handle, Random, Result},
     },
     Client, Server,
 };
@@ -17,6 +17,7 @@ pub fn server(handle: &Handle, events: events::Events) -> Result<SocketAddr> {
     let mut server = Server::builder()
         .with_io(handle.builder().build().unwrap())?
         .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+        .with_random(Random)?
         .with_event((events, Tracing::default()))?
         .start()?;
     let server_addr = server.local_addr()?;
@@ -51,6 +52,7 @@ pub fn client(
     let client = Client::builder()
         .with_io(handle.builder().build().unwrap())?
         .with_tls(certificates::CERT_PEM)?
+        .with_random(Random)?
         .with_event((events, Tracing::default()))?
         .start()?;
 
@@ -0,0 +1,576 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{run::CliRange, Result};
+use anyhow::anyhow;
+use s2n_quic::{
+    client::Connect,
+    provider::{
+        event,
+        io::testing::{
+            primary, rand, spawn, test_seed, time, Handle, Model, QueueDiscipline, Random,
+        },
+    },
+    Client, Server,
+};
+use s2n_quic_core::{crypto::tls::testing::certificates, stream::testing::Data};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use structopt::StructOpt;
+
+/// Runs scripted scenarios and checks their outcomes against the scenario's assertions
+#[derive(Debug, StructOpt)]
+pub struct Scenario {
+    plans: Vec<Plan>,
+
+    /// Runs the scenarios with the given seeds instead of the ones in the plan
+    #[structopt(long)]
+    seed: Vec<u64>,
+
+    /// Writes the report to the given path instead of stdout
+    #[structopt(short, long)]
+    out: Option<PathBuf>,
+}
+
+impl Scenario {
+    pub fn run(&self) -> Result {
+        let mut reports = vec![];
+
+        for plan in &self.plans {
+            let seeds = if self.seed.is_empty() {
+                &plan.seeds
+            } else {
+                &self.seed
+            };
+
+            reports.push(plan.run(seeds)?);
+        }
+
+        if let Some(out) = self.out.as_ref() {
+            serde_json::to_writer_pretty(fs::File::create(out)?, &reports)?;
+        } else {
+            serde_json::to_writer_pretty(io::stdout().lock(), &reports)?;
+            println!();
+        }
+
+        let failed = reports.iter().filter(|report| !report.passed).count();
+        if failed > 0 {
+            return Err(anyhow!("{failed} scenario(s) did not pass"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Plan {
+    #[serde(default)]
+    name: Option<String>,
+
+    /// The seeds to run the scenario with
+    #[serde(default = "default_seeds")]
+    seeds: Vec<u64>,
+
+    /// The number of servers in the topology
+    #[serde(default = "default_servers")]
+    servers: usize,
+
+    /// The link properties at the start of the scenario
+    #[serde(default)]
+    network: Link,
+
+    /// Groups of clients which connect to the servers
+    #[serde(default, rename = "client")]
+    clients: Vec<ClientGroup>,
+
+    /// Changes to the link properties over the course of the scenario
+    #[serde(default, rename = "change")]
+    changes: Vec<Change>,
+
+    /// Bounds on the outcome of each run
+    #[serde(default, rename = "assert")]
+    assertions: Assertions,
+}
+
+fn default_seeds() -> Vec<u64> {
+    vec![1]
+}
+
+fn default_servers() -> usize {
+    1
+}
+
+impl Plan {
+    fn run(&self, seeds: &[u64]) -> Result<Report> {
+        let name = self.name.clone().unwrap_or_default();
+        eprintln!("     Running {name}");
+
+        if self.servers == 0 {
+            return Err(anyhow!("{name}: at least one server is required"));
+        }
+
+        for group in &self.clients {
+            if let Some(server) = group.server {
+                if server >= self.servers {
+                    return Err(anyhow!("{name}: server {server} does not exist"));
+                }
+            }
+        }
+
+        let mut runs = vec![];
+        for seed in seeds.iter().copied() {
+            runs.push(self.run_seed(seed)?);
+        }
+
+        let passed = runs.iter().all(|run| run.violations.is_empty());
+
+        Ok(Report { name, passed, runs })
+    }
+
+    fn run_seed(&self, seed: u64) -> Result<Run> {
+        let network = Model::default();
+        let outcome = Arc::new(Mutex::new(Outcome::default()));
+
+        let duration = test_seed(network.clone(), seed, |handle| {
+            self.network.apply(&network);
+
+            for change in &self.changes {
+                let network = network.clone();
+                let change = change.clone();
+                spawn(async move {
+                    time::delay(change.at.gen_duration()).await;
+                    change.link.apply(&network);
+                });
+            }
+
+            let mut servers = vec![];
+            for _ in 0..self.servers {
+                servers.push(server(handle, &outcome)?);
+            }
+
+            for group in &self.clients {
+                for _ in 0..group.count {
+                    client(handle, &outcome, &servers, group)?;
+                }
+            }
+
+            Ok(())
+        })
+        .map_err(|err| anyhow!("{err}"))?;
+
+        let outcome = outcome.lock().unwrap();
+        let goodput = Goodput::new(&outcome.goodput);
+
+        let mut run = Run {
+            seed,
+            duration: humantime::format_duration(duration).to_string(),
+            connections: outcome.connections,
+            handshakes: outcome.handshakes,
+            failures: outcome.failures,
+            goodput,
+            violations: vec![],
+        };
+
+        self.assertions.check(&mut run);
+
+        Ok(run)
+    }
+}
+
+impl FromStr for Plan {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file = fs::read_to_string(s)?;
+        let mut plan: Self =
+            toml::from_str(&file).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        if plan.name.is_none() {
+            plan.name = Some(
+                Path::new(s.trim_end_matches(".toml"))
+                    .file_name()
+                    .and_then(|v| v.to_str())
+                    .unwrap_or(s)
+                    .to_owned(),
+            );
+        }
+
+        Ok(plan)
+    }
+}
+
+macro_rules! link {
+    ($(#[doc = $doc:literal] $field:ident: $ty:ty => $set:ident($gen:ident)),* $(,)?) => {
+        /// Properties of the simulated network
+        ///
+        /// Properties which aren't specified are left unchanged. Each property can either be a
+        /// single value or a range, from which a value is picked for each run.
+        #[derive(Clone, Debug, Default, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Link {
+            $(
+                #[doc = $doc]
+                #[serde(default)]
+                $field: Option<CliRange<$ty>>,
+            )*
+
+            /// Enables CoDel on the bottleneck queue with the parameters recommended by RFC 8289
+            #[serde(default)]
+            codel: Option<bool>,
+        }
+
+        impl Link {
+            fn apply(&self, model: &Model) {
+                $(
+                    if let Some(value) = self.$field.as_ref() {
+                        model.$set(value.$gen());
+                    }
+                )*
+
+                if let Some(codel) = self.codel {
+                    model.set_queue_discipline(if codel {
+                        QueueDiscipline::codel()
+                    } else {
+                        QueueDiscipline::TailDrop
+                    });
+                }
+            }
+        }
+    };
+}
+
+link!(
+    /// The probability of a packet being dropped
+    drop_rate: f64 => set_drop_rate(gen),
+    /// The probability of a packet being corrupted
+    corrupt_rate: f64 => set_corrupt_rate(gen),
+    /// The amount of jitter added to each packet
+    jitter: humantime::Duration => set_jitter(gen_duration),
+    /// The amount of jitter added to the network as a whole
+    network_jitter: humantime::Duration => set_network_jitter(gen_duration),
+    /// The one-way delay of the network
+    delay: humantime::Duration => set_delay(gen_duration),
+    /// The number of packets which can be transmitted per round
+    transmit_rate: u64 => set_transmit_rate(gen),
+    /// The probability of a packet being delivered twice
+    retransmit_rate: f64 => set_retransmit_rate(gen),
+    /// The maximum size of a UDP payload
+    max_udp_payload: u16 => set_max_udp_payload(gen),
+    /// The maximum number of packets in flight before packets are dropped
+    max_inflight: u64 => set_max_inflight(gen),
+    /// The additional delay applied once the inflight threshold is exceeded
+    inflight_delay: humantime::Duration => set_inflight_delay(gen_duration),
+    /// The number of packets in flight at which the inflight delay is applied
+    inflight_delay_threshold: u64 => set_inflight_delay_threshold(gen),
+    /// The rate, in bytes per second, of the bottleneck link
+    bandwidth: u64 => set_bandwidth(gen),
+    /// The number of bytes the bottleneck link can transmit at once after being idle
+    burst: u64 => set_burst(gen),
+    /// The maximum number of bytes queued at the bottleneck link
+    queue_limit: u64 => set_queue_limit(gen),
+);
+
+#[derive(Clone, Debug, Deserialize)]
+struct Change {
+    /// The time at which the change is applied, relative to the start of the scenario
+    at: CliRange<humantime::Duration>,
+
+    #[serde(flatten)]
+    link: Link,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClientGroup {
+    /// The number of client endpoints in the group
+    #[serde(default = "default_count")]
+    count: u32,
+
+    /// The number of connections opened by each client
+    #[serde(default = "default_count")]
+    connections: u32,
+
+    /// The delay between each of a client's connections
+    #[serde(default)]
+    connect_delay: CliRange<humantime::Duration>,
+
+    /// The number of streams opened on each connection
+    #[serde(default = "default_count")]
+    streams: u32,
+
+    /// The number of bytes sent on each stream
+    #[serde(default = "default_stream_data")]
+    stream_data: u64,
+
+    /// The index of the server to connect to, otherwise a random server is picked for each
+    /// connection
+    #[serde(default)]
+    server: Option<usize>,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_stream_data() -> u64 {
+    4096
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Assertions {
+    /// The number of client connections which completed the handshake
+    #[serde(default)]
+    handshakes: Bound<u64>,
+
+    /// The number of client connections which failed
+    #[serde(default)]
+    failures: Bound<u64>,
+
+    /// The rate at which each server connection received stream data, in bytes per second
+    #[serde(default)]
+    goodput: Bound<f64>,
+}
+
+impl Assertions {
+    fn check(&self, run: &mut Run) {
+        self.handshakes
+            .check("handshakes", run.handshakes, &mut run.violations);
+        self.failures
+            .check("failures", run.failures, &mut run.violations);
+
+        if let Some(goodput) = run.goodput.as_ref() {
+            self.goodput
+                .check("goodput.min", goodput.min, &mut run.violations);
+            self.goodput
+                .check("goodput.max", goodput.max, &mut run.violations);
+        } else if self.goodput.min.is_some() {
+            run.violations
+                .push("goodput: no stream data was received".to_owned());
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Bound<T> {
+    #[serde(default)]
+    min: Option<T>,
+    #[serde(default)]
+    max: Option<T>,
+}
+
+impl<T: Copy + PartialOrd + core::fmt::Display> Bound<T> {
+    fn check(&self, name: &str, value: T, violations: &mut Vec<String>) {
+        if let Some(min) = self.min {
+            if value < min {
+                violations.push(format!("{name}: {value} is less than {min}"));
+            }
+        }
+
+        if let Some(max) = self.max {
+            if value > max {
+                violations.push(format!("{name}: {value} is greater than {max}"));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    name: String,
+    passed: bool,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+struct Run {
+    seed: u64,
+    duration: String,
+    connections: u64,
+    handshakes: u64,
+    failures: u64,
+    goodput: Option<Goodput>,
+    violations: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Goodput {
+    min: f64,
+    mean: f64,
+    max: f64,
+}
+
+impl Goodput {
+    fn new(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = samples.iter().copied().fold(0.0, f64::max);
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+
+        Some(Self { min, mean, max })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Outcome {
+    connections: u64,
+    handshakes: u64,
+    failures: u64,
+    goodput: Vec<f64>,
+}
+
+type SharedOutcome = Arc<Mutex<Outcome>>;
+
+/// Records the rate at which each server connection receives stream data
+#[derive(Clone, Debug)]
+struct Observer {
+    outcome: SharedOutcome,
+}
+
+struct Progress {
+    outcome: SharedOutcome,
+    start: event::Timestamp,
+    end: Option<event::Timestamp>,
+    bytes: u64,
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        let Some(end) = self.end else {
+            return;
+        };
+
+        let elapsed = end.saturating_duration_since(self.start).as_secs_f64();
+        if elapsed > 0.0 {
+            let goodput = self.bytes as f64 / elapsed;
+            self.outcome.lock().unwrap().goodput.push(goodput);
+        }
+    }
+}
+
+impl event::Subscriber for Observer {
+    type ConnectionContext = Progress;
+
+    fn create_connection_context(
+        &mut self,
+        meta: &event::ConnectionMeta,
+        _info: &event::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        Progress {
+            outcome: self.outcome.clone(),
+            start: meta.timestamp,
+            end: None,
+            bytes: 0,
+        }
+    }
+
+    #[inline]
+    fn on_rx_stream_progress(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::RxStreamProgress,
+    ) {
+        context.bytes += event.bytes as u64;
+        context.end = Some(meta.timestamp);
+    }
+}
+
+fn server(handle: &Handle, outcome: &SharedOutcome) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+        .with_random(Random)?
+        .with_event(Observer {
+            outcome: outcome.clone(),
+        })?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    // accept connections and drain the streams
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            primary::spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    primary::spawn(async move {
+                        while let Ok(Some(chunk)) = stream.receive().await {
+                            let _ = chunk;
+                        }
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+fn client(
+    handle: &Handle,
+    outcome: &SharedOutcome,
+    servers: &[SocketAddr],
+    group: &ClientGroup,
+) -> Result {
+    let client = Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(certificates::CERT_PEM)?
+        .with_random(Random)?
+        .start()?;
+
+    for index in 0..group.connections {
+        let server_addr = match group.server {
+            Some(server) => servers[server],
+            None => *rand::one_of(servers),
+        };
+        let delay = group.connect_delay.gen_duration() * index;
+        let streams = group.streams;
+        let stream_data = group.stream_data;
+        let client = client.clone();
+        let outcome = outcome.clone();
+
+        outcome.lock().unwrap().connections += 1;
+
+        primary::spawn(async move {
+            if !delay.is_zero() {
+                time::delay(delay).await;
+            }
+
+            let result = async {
+                let connect = Connect::new(server_addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await?;
+                outcome.lock().unwrap().handshakes += 1;
+
+                for _ in 0..streams {
+                    let mut stream = connection.open_bidirectional_stream().await?;
+                    let mut data = Data::new(stream_data);
+
+                    while let Some(chunk) = data.send_one(usize::MAX) {
+                        stream.send(chunk).await?;
+                    }
+
+                    stream.close().await?;
+                }
+
+                Result::<()>::Ok(())
+            }
+            .await;
+
+            if result.is_err() {
+                outcome.lock().unwrap().failures += 1;
+            }
+        });
+    }
+
+    Ok(())
+}
@@ -155,6 +155,12 @@ pub struct Parameters {
     pub inflight_delay: Option<Duration>,
     #[prost(uint64, tag = "15")]
     pub inflight_delay_threshold: u64,
+    #[prost(uint64, tag = "16")]
+    pub bandwidth: u64,
+    #[prost(uint64, tag = "17")]
+    pub queue_limit: u64,
+    #[prost(message, tag = "18")]
+    pub codel_target: Option<Duration>,
 }
 
 impl From<Parameters> for Stats {
@@ -630,6 +636,21 @@ static QUERIES: &[(&str, Type, Q)] = &[
                 .as_secs_f64(),
         )
     }),
+    ("net.bandwidth", I, |params, _conn, _conns| {
+        Some(params.bandwidth as f64)
+    }),
+    ("net.queue-limit", I, |params, _conn, _conns| {
+        Some(params.queue_limit as f64)
+    }),
+    ("net.codel-target", T, |params, _conn, _conns| {
+        Some(
+            params
+                .codel_target
+                .unwrap_or_default()
+                .as_duration()
+                .as_secs_f64(),
+        )
+    }),
 ];
 
 pub static QUERY_NAMES: Lazy<Vec<&'static str>> =
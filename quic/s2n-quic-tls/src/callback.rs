@@ -9,7 +9,8 @@ use s2n_quic_core::{
     endpoint, transport,
 };
 use s2n_quic_crypto::{
-    handshake::HandshakeKey, hkdf, one_rtt::OneRttKey, ring_aead as aead, Prk, SecretPair, Suite,
+    handshake::HandshakeKey, hkdf, offload, one_rtt::OneRttKey, ring_aead as aead, Prk, SecretPair,
+    Suite,
 };
 use s2n_tls::{connection::Connection, error::Fallible, ffi::*};
 
@@ -30,6 +31,7 @@ pub struct Callback<'a, T, C> {
     pub emitted_server_name: &'a mut bool,
     pub server_name: &'a Option<ServerName>,
     pub server_params: &'a mut Vec<u8>,
+    pub offload: &'a Option<offload::Device>,
 }
 
 impl<'a, T, C> Callback<'a, T, C>
@@ -167,8 +169,9 @@ where
 
                 let (prk_algo, _aead, cipher_suite) =
                     get_algo_type(conn).ok_or(tls::Error::INTERNAL_ERROR)?;
+                let raw = tls::Secret::new(secret).ok_or(tls::Error::INTERNAL_ERROR)?;
                 let secret = Prk::new_less_safe(prk_algo, secret);
-                self.state.secrets = Secrets::Half { secret, id };
+                self.state.secrets = Secrets::Half { secret, raw, id };
                 self.state.cipher_suite = cipher_suite;
 
                 Ok(())
@@ -176,12 +179,14 @@ where
             Secrets::Half {
                 id: other_id,
                 secret: other_secret,
+                raw: other_raw,
             } => {
                 let (prk_algo, aead_algo, cipher_suite) =
                     get_algo_type(conn).ok_or(tls::Error::INTERNAL_ERROR)?;
+                let raw = tls::Secret::new(secret).ok_or(tls::Error::INTERNAL_ERROR)?;
                 let secret = Prk::new_less_safe(prk_algo, secret);
                 self.state.cipher_suite = cipher_suite;
-                let pair = match (id, other_id) {
+                let (pair, raw_client, raw_server) = match (id, other_id) {
                     (
                         s2n_secret_type_t::CLIENT_HANDSHAKE_TRAFFIC_SECRET,
                         s2n_secret_type_t::SERVER_HANDSHAKE_TRAFFIC_SECRET,
@@ -189,10 +194,14 @@ where
                     | (
                         s2n_secret_type_t::CLIENT_APPLICATION_TRAFFIC_SECRET,
                         s2n_secret_type_t::SERVER_APPLICATION_TRAFFIC_SECRET,
-                    ) => SecretPair {
-                        client: secret,
-                        server: other_secret,
-                    },
+                    ) => (
+                        SecretPair {
+                            client: secret,
+                            server: other_secret,
+                        },
+                        raw,
+                        other_raw,
+                    ),
                     (
                         s2n_secret_type_t::SERVER_HANDSHAKE_TRAFFIC_SECRET,
                         s2n_secret_type_t::CLIENT_HANDSHAKE_TRAFFIC_SECRET,
@@ -200,10 +209,14 @@ where
                     | (
                         s2n_secret_type_t::SERVER_APPLICATION_TRAFFIC_SECRET,
                         s2n_secret_type_t::CLIENT_APPLICATION_TRAFFIC_SECRET,
-                    ) => SecretPair {
-                        server: secret,
-                        client: other_secret,
-                    },
+                    ) => (
+                        SecretPair {
+                            server: secret,
+                            client: other_secret,
+                        },
+                        other_raw,
+                        raw,
+                    ),
                     _ => {
                         debug_assert!(false, "invalid key phase");
                         return Err(transport::Error::INTERNAL_ERROR);
@@ -253,8 +266,17 @@ where
                         self.state.rx_phase.transition();
                     }
                     _ => {
-                        let (key, header_key) =
+                        self.context.on_one_rtt_secrets(&tls::OneRttSecrets {
+                            cipher_suite,
+                            client: raw_client,
+                            server: raw_server,
+                        })?;
+
+                        let (mut key, header_key) =
                             OneRttKey::new(self.endpoint, aead_algo, pair).expect("invalid cipher");
+                        if let Some(device) = self.offload {
+                            key = key.with_offload(device.clone());
+                        }
                         // At this point the server is done writing Handshake messages
                         if self.endpoint.is_server() {
                             self.state.tx_phase.transition();
@@ -423,6 +445,7 @@ enum Secrets {
     Waiting,
     Half {
         secret: Prk,
+        raw: tls::Secret,
         id: s2n_secret_type_t::Type,
     },
 }
@@ -471,23 +494,28 @@ fn get_algo_type(
     //# QUIC can use any of the cipher suites defined in [TLS13] with the
     //# exception of TLS_AES_128_CCM_8_SHA256.
 
-    match cipher {
-        TLS_AES_128_GCM_SHA256 => Some((
-            hkdf::HKDF_SHA256,
-            &aead::AES_128_GCM,
-            CipherSuite::TLS_AES_128_GCM_SHA256,
-        )),
-        TLS_AES_256_GCM_SHA384 => Some((
-            hkdf::HKDF_SHA384,
-            &aead::AES_256_GCM,
-            CipherSuite::TLS_AES_256_GCM_SHA384,
-        )),
-        TLS_CHACHA20_POLY1305_SHA256 => Some((
-            hkdf::HKDF_SHA256,
-            &aead::CHACHA20_POLY1305,
-            CipherSuite::TLS_CHACHA20_POLY1305_SHA256,
-        )),
-        _ => None,
+    let cipher_suite = match cipher {
+        TLS_AES_128_GCM_SHA256 => CipherSuite::TLS_AES_128_GCM_SHA256,
+        TLS_AES_256_GCM_SHA384 => CipherSuite::TLS_AES_256_GCM_SHA384,
+        TLS_CHACHA20_POLY1305_SHA256 => CipherSuite::TLS_CHACHA20_POLY1305_SHA256,
+        _ => return None,
+    };
+
+    let (prk_algo, aead_algo) = get_algorithms(cipher_suite)?;
+    Some((prk_algo, aead_algo, cipher_suite))
+}
+
+/// Returns the HKDF and AEAD algorithms of a negotiated cipher suite
+pub(crate) fn get_algorithms(
+    cipher_suite: CipherSuite,
+) -> Option<(hkdf::Algorithm, &'static aead::Algorithm)> {
+    match cipher_suite {
+        CipherSuite::TLS_AES_128_GCM_SHA256 => Some((hkdf::HKDF_SHA256, &aead::AES_128_GCM)),
+        CipherSuite::TLS_AES_256_GCM_SHA384 => Some((hkdf::HKDF_SHA384, &aead::AES_256_GCM)),
+        CipherSuite::TLS_CHACHA20_POLY1305_SHA256 => {
+            Some((hkdf::HKDF_SHA256, &aead::CHACHA20_POLY1305))
+        }
+        CipherSuite::Unknown => None,
     }
 }
 
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use bytes::Bytes;
-use s2n_tls::error::Error;
+use s2n_tls::{callbacks::VerifyHostNameCallback, error::Error};
 
 impl Format {
     pub fn as_pem(&self) -> Option<&[u8]> {
@@ -111,3 +111,15 @@ cert_type!(Certificate, IntoCertificate, into_certificate);
 
 #[cfg(any(test, feature = "unstable_private_key"))]
 pub const OFFLOAD_PRIVATE_KEY: PrivateKey = PrivateKey(Format::None);
+
+/// Accepts any host name presented by a pinned peer
+///
+/// When a certificate is pinned, it is the identity of the peer so the names it carries are
+/// not checked.
+pub(crate) struct AnyHostName;
+
+impl VerifyHostNameCallback for AnyHostName {
+    fn verify_host_name(&self, _host_name: &str) -> bool {
+        true
+    }
+}
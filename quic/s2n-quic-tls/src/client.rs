@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    certificate::{IntoCertificate, IntoPrivateKey},
+    certificate::{AnyHostName, IntoCertificate, IntoPrivateKey},
     keylog::KeyLogHandle,
     params::Params,
     session::Session,
@@ -10,6 +10,7 @@ use crate::{
 };
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
+use s2n_quic_crypto::offload;
 use s2n_tls::{
     callbacks::VerifyHostNameCallback,
     config::{self, Config},
@@ -23,6 +24,7 @@ pub struct Client<L: ConfigLoader = Config> {
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    offload: Option<offload::Device>,
 }
 
 impl Client {
@@ -44,6 +46,7 @@ impl<L: ConfigLoader> Client<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            offload: None,
         }
     }
 }
@@ -66,6 +69,8 @@ impl<L: ConfigLoader> ConfigLoader for Client<L> {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    pinned: bool,
+    offload: Option<offload::Device>,
 }
 
 impl Default for Builder {
@@ -79,6 +84,8 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            pinned: false,
+            offload: None,
         }
     }
 }
@@ -106,6 +113,27 @@ impl Builder {
         Ok(self)
     }
 
+    /// Pins the server to the provided certificate
+    ///
+    /// The first call clears the trust store and disables server name verification, so only
+    /// servers presenting a pinned certificate are accepted. This is suitable for self-signed
+    /// certificates on device-to-device and internal links. The pinned certificate acts as a
+    /// trust anchor: its validity period is still checked, and if it is a CA certificate, any
+    /// certificate it issued is accepted as well.
+    ///
+    /// This method can be called multiple times to accept any one of several certificates.
+    pub fn with_pinned_certificate<C: IntoCertificate>(
+        mut self,
+        certificate: C,
+    ) -> Result<Self, Error> {
+        if !self.pinned {
+            self.config.wipe_trust_store()?;
+            self.config.set_verify_host_callback(AnyHostName)?;
+            self.pinned = true;
+        }
+        self.with_certificate(certificate)
+    }
+
     /// Clears the default trust store for this client
     ///
     /// By default, the trust store is initialized with common
@@ -185,6 +213,15 @@ impl Builder {
         Ok(self)
     }
 
+    /// Delegates 1-RTT packet protection to a crypto offload device
+    ///
+    /// Packets the device declines are protected in software.
+    #[cfg(any(test, feature = "unstable_crypto_offload"))]
+    pub fn with_crypto_offload<O: offload::Offload>(mut self, offload: O) -> Result<Self, Error> {
+        self.offload = Some(offload::Device::new(offload));
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, Error> {
         #[cfg(feature = "fips")]
         assert!(s2n_tls::init::fips_mode()?.is_enabled());
@@ -193,6 +230,7 @@ impl Builder {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            offload: self.offload,
         })
     }
 }
@@ -213,7 +251,9 @@ impl<L: ConfigLoader> tls::Endpoint for Client<L> {
             server_name: Some(&server_name),
         });
         self.params.with(params, |params| {
-            Session::new(endpoint::Type::Client, config, params, Some(server_name)).unwrap()
+            Session::new(endpoint::Type::Client, config, params, Some(server_name))
+                .unwrap()
+                .with_crypto_offload(self.offload.clone())
         })
     }
 
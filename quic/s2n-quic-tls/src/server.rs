@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    certificate::{Format, IntoCertificate, IntoPrivateKey},
+    certificate::{AnyHostName, Format, IntoCertificate, IntoPrivateKey},
     keylog::KeyLogHandle,
     params::Params,
     session::Session,
@@ -10,6 +10,11 @@ use crate::{
 };
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
+use s2n_quic_crypto::{
+    offload,
+    one_rtt::{OneRttHeaderKey, OneRttKey},
+    Prk, SecretPair,
+};
 #[cfg(any(test, feature = "unstable_client_hello"))]
 use s2n_tls::callbacks::ClientHelloCallback;
 #[cfg(any(test, feature = "unstable_private_key"))]
@@ -27,6 +32,7 @@ pub struct Server<L: ConfigLoader = Config> {
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    offload: Option<offload::Device>,
 }
 
 impl Server {
@@ -48,6 +54,7 @@ impl<L: ConfigLoader> Server<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            offload: None,
         }
     }
 }
@@ -76,6 +83,8 @@ impl<L: ConfigLoader> From<Server<L>> for Config {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    pinned: bool,
+    offload: Option<offload::Device>,
 }
 
 impl Default for Builder {
@@ -89,6 +98,8 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            pinned: false,
+            offload: None,
         }
     }
 }
@@ -175,6 +186,28 @@ impl Builder {
         Ok(self)
     }
 
+    /// Requires clients to authenticate with the provided certificate
+    ///
+    /// The first call clears the trust store, enables client authentication and disables
+    /// host name verification, so only clients presenting a pinned certificate are accepted.
+    /// This is suitable for self-signed certificates on device-to-device and internal links.
+    /// The pinned certificate acts as a trust anchor: its validity period is still checked, and
+    /// if it is a CA certificate, any certificate it issued is accepted as well.
+    ///
+    /// This method can be called multiple times to accept any one of several certificates.
+    pub fn with_pinned_client_certificate<C: IntoCertificate>(
+        mut self,
+        certificate: C,
+    ) -> Result<Self, Error> {
+        if !self.pinned {
+            self.config.wipe_trust_store()?;
+            self.config.set_client_auth_type(ClientAuthType::Required)?;
+            self.config.set_verify_host_callback(AnyHostName)?;
+            self.pinned = true;
+        }
+        self.with_trusted_certificate(certificate)
+    }
+
     /// Set the application level certificate verification handler which will be invoked on this
     /// server instance when a client certificate is presented during the mutual TLS handshake.
     #[deprecated(note = "use `with_verify_host_name_callback` instead")]
@@ -218,6 +251,15 @@ impl Builder {
         Ok(self)
     }
 
+    /// Delegates 1-RTT packet protection to a crypto offload device
+    ///
+    /// Packets the device declines are protected in software.
+    #[cfg(any(test, feature = "unstable_crypto_offload"))]
+    pub fn with_crypto_offload<O: offload::Offload>(mut self, offload: O) -> Result<Self, Error> {
+        self.offload = Some(offload::Device::new(offload));
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Server, Error> {
         #[cfg(feature = "fips")]
         assert!(s2n_tls::init::fips_mode()?.is_enabled());
@@ -226,6 +268,7 @@ impl Builder {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            offload: self.offload,
         })
     }
 }
@@ -238,7 +281,9 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
             .loader
             .load(crate::ConnectionContext { server_name: None });
         self.params.with(params, |params| {
-            Session::new(endpoint::Type::Server, config, params, None).unwrap()
+            Session::new(endpoint::Type::Server, config, params, None)
+                .unwrap()
+                .with_crypto_offload(self.offload.clone())
         })
     }
 
@@ -253,4 +298,20 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn new_server_one_rtt_keys(
+        &mut self,
+        secrets: &tls::OneRttSecrets,
+    ) -> Option<(OneRttKey, OneRttHeaderKey)> {
+        let (prk_algo, aead_algo) = crate::callback::get_algorithms(secrets.cipher_suite)?;
+        let pair = SecretPair {
+            client: Prk::new_less_safe(prk_algo, secrets.client.as_bytes()),
+            server: Prk::new_less_safe(prk_algo, secrets.server.as_bytes()),
+        };
+        let (mut key, header_key) = OneRttKey::new(endpoint::Type::Server, aead_algo, pair)?;
+        if let Some(device) = self.offload.as_ref() {
+            key = key.with_offload(device.clone());
+        }
+        Some((key, header_key))
+    }
 }
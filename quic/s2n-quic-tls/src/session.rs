@@ -9,7 +9,7 @@ use s2n_quic_core::{
     crypto::{tls, tls::CipherSuite, CryptoSuite},
     endpoint, ensure, transport,
 };
-use s2n_quic_crypto::Suite;
+use s2n_quic_crypto::{offload, Suite};
 use s2n_tls::{
     config::Config,
     connection::Connection,
@@ -29,6 +29,7 @@ pub struct Session {
     server_name: Option<ServerName>,
     received_ticket: bool,
     server_params: Vec<u8>,
+    offload: Option<offload::Device>,
 }
 
 impl Session {
@@ -76,8 +77,15 @@ impl Session {
             server_name,
             received_ticket: false,
             server_params,
+            offload: None,
         })
     }
+
+    /// Installs the session's 1-RTT keys on a crypto offload device
+    pub(crate) fn with_crypto_offload(mut self, offload: Option<offload::Device>) -> Self {
+        self.offload = offload;
+        self
+    }
 }
 
 impl CryptoSuite for Session {
@@ -124,6 +132,7 @@ impl tls::Session for Session {
             emitted_server_name: &mut self.emitted_server_name,
             server_name: &self.server_name,
             server_params: &mut self.server_params,
+            offload: &self.offload,
         };
 
         unsafe {
@@ -170,6 +179,7 @@ impl tls::Session for Session {
             emitted_server_name: &mut self.emitted_server_name,
             server_name: &self.server_name,
             server_params: &mut self.server_params,
+            offload: &self.offload,
         };
 
         unsafe {
@@ -216,4 +226,13 @@ impl tls::Session for Session {
         // Discard the session once a ticket is received
         self.received_ticket
     }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), tls::TlsExportError> {
+        tls::TlsSession::tls_exporter(self, label, context, output)
+    }
 }
@@ -338,6 +338,53 @@ fn s2n_client_s2n_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_crypto_offload_test() {
+    use s2n_quic_crypto::offload;
+
+    /// Records installed keys and leaves all packets to software
+    #[derive(Clone, Default)]
+    struct Offload(Arc<AtomicU8>);
+
+    impl offload::Offload for Offload {
+        fn sealer(&self, key: &offload::KeyMaterial) -> Option<Box<dyn offload::Sealer>> {
+            assert!(!key.key.is_empty());
+            self.0.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+
+        fn opener(&self, key: &offload::KeyMaterial) -> Option<Box<dyn offload::Opener>> {
+            assert!(!key.key.is_empty());
+            self.0.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+
+    let client_offload = Offload::default();
+    let server_offload = Offload::default();
+    let mut client_endpoint = client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_crypto_offload(client_offload.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_crypto_offload(server_offload.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+
+    // only the 1-RTT sealer and opener are installed on the device
+    assert_eq!(client_offload.0.load(Ordering::Relaxed), 2);
+    assert_eq!(server_offload.0.load(Ordering::Relaxed), 2);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_s2n_server_resumption_test() {
@@ -373,6 +420,78 @@ fn rustls_client_s2n_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+/// Ensures the rustls client reports a rejected ECH offer when the server doesn't support ECH
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rustls_client_ech_s2n_server_rejected_test() {
+    let mut client_endpoint = s2n_quic_rustls::client::Builder::default()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_encrypted_client_hello(&ech_config_list("localhost"))
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = s2n_server();
+
+    let mut pair = tls::testing::Pair::new(
+        &mut server_endpoint,
+        &mut client_endpoint,
+        "localhost".into(),
+    );
+
+    // the client aborts the handshake once it authenticates the server with the public name
+    let error = loop {
+        if let Err(error) = pair.poll(None) {
+            break error;
+        }
+    };
+
+    assert_eq!(
+        error.try_into_tls_error().unwrap().code,
+        tls::Error::ECH_REQUIRED.code
+    );
+    assert_eq!(
+        pair.client.context.encrypted_client_hello,
+        Some(tls::EchStatus::Rejected)
+    );
+}
+
+/// Returns an encoded `ECHConfigList` with a single X25519 configuration
+fn ech_config_list(public_name: &str) -> Vec<u8> {
+    let mut contents = vec![];
+    // config_id
+    contents.push(1);
+    // kem_id: DHKEM(X25519, HKDF-SHA256)
+    contents.extend_from_slice(&0x0020u16.to_be_bytes());
+    // public_key: the X25519 base point
+    let mut public_key = [0u8; 32];
+    public_key[0] = 9;
+    contents.extend_from_slice(&(public_key.len() as u16).to_be_bytes());
+    contents.extend_from_slice(&public_key);
+    // cipher_suites: HKDF-SHA256 with AES-128-GCM
+    contents.extend_from_slice(&4u16.to_be_bytes());
+    contents.extend_from_slice(&0x0001u16.to_be_bytes());
+    contents.extend_from_slice(&0x0001u16.to_be_bytes());
+    // maximum_name_length
+    contents.push(0);
+    // public_name
+    contents.push(public_name.len() as u8);
+    contents.extend_from_slice(public_name.as_bytes());
+    // extensions
+    contents.extend_from_slice(&0u16.to_be_bytes());
+
+    let mut config = vec![];
+    // version: draft-ietf-tls-esni-18
+    config.extend_from_slice(&0xfe0du16.to_be_bytes());
+    config.extend_from_slice(&(contents.len() as u16).to_be_bytes());
+    config.extend_from_slice(&contents);
+
+    let mut list = vec![];
+    list.extend_from_slice(&(config.len() as u16).to_be_bytes());
+    list.extend_from_slice(&config);
+    list
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_rustls_server_test() {
@@ -474,6 +593,129 @@ fn s2n_client_with_custom_hostname_auth_accepts_server_name() {
     run_result(&mut server_endpoint, &mut client_endpoint, None).unwrap();
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_pinned_s2n_server_client_auth_pinned_test() {
+    let mut client_endpoint = client::Builder::default()
+        .with_pinned_certificate(CERT_PEM)
+        .unwrap()
+        .with_client_identity(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_pinned_client_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_pinned_s2n_server_rejects_untrusted_client_test() {
+    let mut client_endpoint = client::Builder::default()
+        .with_pinned_certificate(CERT_PEM)
+        .unwrap()
+        .with_client_identity(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_pinned_client_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+
+    // The handshake should fail because the client certificate is not pinned by the server
+    assert!(test_result.is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_pinned_rejects_untrusted_server_test() {
+    let mut client_endpoint = client::Builder::default()
+        .with_pinned_certificate(UNTRUSTED_CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = s2n_server();
+
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+
+    // The handshake should fail because the server certificate is not pinned by the client
+    assert!(test_result.is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rustls_client_pinned_s2n_server_test() {
+    let mut client_endpoint = s2n_quic_rustls::client::Builder::default()
+        .with_pinned_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = s2n_server();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rustls_client_pinned_rejects_untrusted_server_test() {
+    let mut client_endpoint = s2n_quic_rustls::client::Builder::default()
+        .with_pinned_certificate(UNTRUSTED_CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut server_endpoint = s2n_server();
+
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+
+    // The handshake should fail because the server certificate is not pinned by the client
+    assert!(test_result.is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_client_auth_rustls_server_pinned_test() {
+    let mut client_endpoint = s2n_client_with_client_auth().unwrap();
+    let mut server_endpoint = s2n_quic_rustls::server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_pinned_client_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_with_untrusted_client_auth_rustls_server_pinned_test() {
+    let mut client_endpoint = s2n_client_with_untrusted_client_auth().unwrap();
+    let mut server_endpoint = s2n_quic_rustls::server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_pinned_client_certificate(CERT_PEM)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+
+    // The handshake should fail because the client certificate is not pinned by the server
+    assert!(test_result.is_err());
+}
+
 /// Executes the handshake to completion
 fn run_result<S: Endpoint, C: Endpoint>(
     server: &mut S,
@@ -13,12 +13,15 @@ use s2n_quic_core::{
         builder::{AckAction, AckProcessed},
         IntoEvent as _,
     },
-    frame::{ack::EcnCounts, Ack, Ping},
+    frame::{ack::EcnCounts, Ping, Timestamp as TimestampFrame},
     packet::number::{PacketNumber, PacketNumberSpace},
     time::{timer, Timer, Timestamp},
     varint::VarInt,
 };
 
+/// ACK frames are limited to this fraction of the remaining packet capacity
+const ACK_FRAME_CAPACITY_DIVISOR: usize = 2;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2
 //# Endpoints acknowledge all packets they receive and process.  However,
 //# only ack-eliciting packets cause an ACK frame to be sent within the
@@ -55,7 +58,7 @@ pub struct AckManager {
     /// The time at which we received the largest pn
     largest_received_packet_number_at: Option<Timestamp>,
 
-    /// The number of processed packets since transmission
+    /// The number of processed ack-eliciting packets since transmission
     processed_packets_since_transmission: Counter<u8, Saturating>,
 
     /// The number of transmissions since the last ACK-eliciting packet was sent
@@ -66,6 +69,12 @@ pub struct AckManager {
 
     /// Explicit Congestion Notification counts from processed packets
     ecn_counts: EcnCounts,
+
+    /// The largest processed packet number which was received with an ECN codepoint
+    largest_ecn_marked_packet_number: Option<PacketNumber>,
+
+    /// The time TIMESTAMP frames are measured from, if they are sent to the peer
+    timestamp_epoch: Option<Timestamp>,
 }
 
 impl AckManager {
@@ -82,9 +91,17 @@ impl AckManager {
             transmissions_since_elicitation: Counter::new(0),
             transmission_state: AckTransmissionState::default(),
             ecn_counts: EcnCounts::default(),
+            largest_ecn_marked_packet_number: None,
+            timestamp_epoch: None,
         }
     }
 
+    /// Sends a TIMESTAMP frame measured from `epoch` alongside each ACK frame
+    pub fn with_timestamps(mut self, epoch: Timestamp) -> Self {
+        self.timestamp_epoch = Some(epoch);
+        self
+    }
+
     /// Called when an outgoing packet is being assembled
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> bool {
         let constraint = context.transmission_constraint();
@@ -104,13 +121,31 @@ impl AckManager {
         //# Even if an endpoint does not set an ECT field on packets it sends,
         //# the endpoint MUST provide feedback about ECN markings it receives, if
         //# these are accessible.
-        context
-            .write_ack_frame(&Ack {
-                ack_delay,
-                ack_ranges: &self.ack_ranges,
-                ecn_counts: self.ecn_counts.as_option(),
-            })
-            .is_some()
+        let ecn_counts = self.ecn_counts();
+
+        // Limit the ACK frame to a portion of the packet so a large number of ranges after a loss
+        // episode doesn't crowd out other frames, unless that isn't enough for the largest range.
+        let capacity = context.remaining_capacity();
+        let frame = self
+            .ack_ranges
+            .fit(ack_delay, ecn_counts, capacity / ACK_FRAME_CAPACITY_DIVISOR)
+            .or_else(|| self.ack_ranges.fit(ack_delay, ecn_counts, capacity));
+
+        let did_send_ack = frame.map_or(false, |frame| context.write_ack_frame(&frame).is_some());
+
+        if did_send_ack {
+            if let Some(epoch) = self.timestamp_epoch {
+                //= https://datatracker.ietf.org/doc/html/draft-huitema-quic-ts#section-3
+                //# The timestamp is encoded using the same ack_delay_exponent as
+                //# the ACK Delay field of ACK frames.
+                let timestamp = context.current_time().saturating_duration_since(epoch);
+                let timestamp = self.ack_settings.encode_ack_delay(timestamp);
+                // the peer can still use the ACK frame if the TIMESTAMP frame doesn't fit
+                let _ = context.write_frame(&TimestampFrame { timestamp });
+            }
+        }
+
+        did_send_ack
     }
 
     /// Called after an outgoing packet is assembled and `on_transmit` returned `true`
@@ -268,9 +303,14 @@ impl AckManager {
         //# relevant security concerns.
         self.ecn_counts.increment(processed_packet.datagram.ecn);
 
+        if processed_packet.datagram.ecn.using_ecn() {
+            self.largest_ecn_marked_packet_number = self
+                .largest_ecn_marked_packet_number
+                .max(Some(packet_number));
+        }
+
         // Notify the state that the ack_ranges have changed
         self.transmission_state.on_update(&self.ack_ranges);
-        self.processed_packets_since_transmission += 1;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.5
         //# An endpoint measures the delays intentionally introduced between the
@@ -286,6 +326,7 @@ impl AckManager {
 
         if processed_packet.is_ack_eliciting() {
             let mut should_activate = false;
+            self.processed_packets_since_transmission += 1;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# In order to assist loss detection at the sender, an endpoint SHOULD
@@ -294,15 +335,16 @@ impl AckManager {
             //#
             //# *  when the received packet has a packet number less than another
             //#    ack-eliciting packet that has been received, or
-
-            should_activate |= !is_largest;
-
+            //
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# *  when the packet has a packet number larger than the highest-
             //#    numbered ack-eliciting packet that has been received and there are
             //#    missing packets between that packet and this packet.
 
-            should_activate |= !is_ordered;
+            // Applications can opt out of this behavior on paths which frequently reorder packets
+            if self.ack_settings.ack_on_reordering {
+                should_activate |= !is_largest || !is_ordered;
+            }
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# Similarly, packets marked with the ECN Congestion Experienced (CE)
@@ -310,15 +352,10 @@ impl AckManager {
             //# reduce the peer's response time to congestion events.
             should_activate |= processed_packet.datagram.ecn.congestion_experienced();
 
-            // TODO update to draft link after published
-            // https://github.com/quicwg/base-drafts/pull/3623
-            // An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
-
             // TODO support delayed ack proposal
             // https://tools.ietf.org/html/draft-iyengar-quic-delayed-ack-00
-            let packet_tolerance = 10;
-
-            should_activate |= self.processed_packets_since_transmission >= packet_tolerance;
+            should_activate |= self.processed_packets_since_transmission
+                >= self.ack_settings.ack_eliciting_threshold;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3.3
             //# An endpoint that receives a PATH_CHALLENGE on an active path SHOULD
@@ -360,6 +397,26 @@ impl AckManager {
         self.largest_received_packet_number_acked
     }
 
+    /// Returns the ECN counts to include in an ACK frame
+    ///
+    /// The counts are only needed while the ACK frame reports packets which were received with an
+    /// ECN codepoint. Once those packets are no longer being acknowledged, the counts haven't
+    /// changed since an ACK frame which the peer has already processed.
+    fn ecn_counts(&self) -> Option<EcnCounts> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
+        //# If an ACK frame newly acknowledges a packet that the endpoint sent
+        //# with either the ECT(0) or ECT(1) codepoint set, ECN validation fails
+        //# if the corresponding ECN counts are not present in the ACK frame.
+        let largest_ecn_marked = self.largest_ecn_marked_packet_number?;
+        let smallest_acked = self.ack_ranges.min_value()?;
+
+        if largest_ecn_marked < smallest_acked {
+            return None;
+        }
+
+        self.ecn_counts.as_option()
+    }
+
     /// Computes the ack_delay field for the current state
     fn ack_delay(&self, now: Timestamp) -> VarInt {
         let ack_delay = self
@@ -519,6 +576,190 @@ mod tests {
         assert_eq!(1, manager.ecn_counts.ce_count.as_u64());
     }
 
+    #[test]
+    fn ack_eliciting_threshold() {
+        let mut publisher = Publisher::snapshot();
+
+        for threshold in [1u8, 2, 10] {
+            let mut manager = AckManager::new(
+                PacketNumberSpace::ApplicationData,
+                ack::Settings {
+                    ack_eliciting_threshold: threshold,
+                    ..Default::default()
+                },
+            );
+            let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+            let path = helper_path_server();
+            let path_id = path::Id::test_id();
+
+            for pn in 1..=threshold {
+                assert!(!manager.transmission_state.is_active());
+
+                let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+                let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+                processed_packet.ack_elicitation = AckElicitation::Eliciting;
+                manager.on_processed_packet(
+                    &processed_packet,
+                    path_event!(path, path_id),
+                    &mut publisher,
+                );
+            }
+
+            // the ACK is sent once the threshold is reached
+            assert!(manager.transmission_state.is_active(), "{threshold}");
+        }
+    }
+
+    #[test]
+    fn ack_on_reordering() {
+        let mut publisher = Publisher::snapshot();
+
+        for ack_on_reordering in [true, false] {
+            let mut manager = AckManager::new(
+                PacketNumberSpace::ApplicationData,
+                ack::Settings {
+                    ack_on_reordering,
+                    ..Default::default()
+                },
+            );
+            let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+            let path = helper_path_server();
+            let path_id = path::Id::test_id();
+
+            // skip packet number 2 so packet 3 is received out of order
+            for pn in [1, 3] {
+                let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+                let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+                processed_packet.ack_elicitation = AckElicitation::Eliciting;
+                manager.on_processed_packet(
+                    &processed_packet,
+                    path_event!(path, path_id),
+                    &mut publisher,
+                );
+            }
+
+            assert_eq!(manager.transmission_state.is_active(), ack_on_reordering);
+            // the ACK should still be scheduled within the max_ack_delay
+            assert!(manager.ack_delay_timer.is_armed() || ack_on_reordering);
+        }
+    }
+
+    #[test]
+    fn ack_frame_capacity() {
+        let mut manager = AckManager::new(
+            PacketNumberSpace::ApplicationData,
+            ack::Settings {
+                ack_ranges_limit: u8::MAX,
+                ..Default::default()
+            },
+        );
+
+        // skip every other packet number to create the maximum number of ranges
+        for pn in (0..1000u32).step_by(2) {
+            let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u32(pn));
+            let _ = manager.ack_ranges.insert_packet_number(pn);
+        }
+        let largest = manager.ack_ranges.max_value().unwrap();
+
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        frame_buffer.set_max_packet_size(Some(200));
+        let mut write_context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+        manager.transmission_state = AckTransmissionState::Active { retransmissions: 0 };
+
+        assert!(manager.on_transmit(&mut write_context));
+
+        let mut frame = write_context.frame_buffer.pop_front().unwrap();
+        // the frame should only take up half of the packet
+        assert!(frame.data.len() <= 100);
+        let Frame::Ack(ack) = frame.as_frame() else {
+            panic!("expected an ACK frame");
+        };
+
+        // the most recent ranges are reported
+        assert_eq!(ack.largest_acknowledged(), PacketNumber::as_varint(largest));
+        let ranges = ack.ack_ranges().count();
+        assert!(ranges > 1);
+        assert!(ranges < manager.ack_ranges.interval_len());
+
+        // nothing is written if the largest range doesn't fit
+        write_context.frame_buffer.set_max_packet_size(Some(4));
+        assert!(!manager.on_transmit(&mut write_context));
+        assert!(write_context.frame_buffer.is_empty());
+    }
+
+    #[test]
+    fn ecn_counts_omitted_once_acknowledged() {
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        let mut publisher = Publisher::no_snapshot();
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        frame_buffer.set_max_packet_size(Some(1200));
+        let mut write_context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+
+        let mut receive = |manager: &mut AckManager, pn: u8, ecn| {
+            let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+            let datagram = helper_datagram_info(ecn);
+            let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+            processed_packet.ack_elicitation = AckElicitation::Eliciting;
+            manager.on_processed_packet(
+                &processed_packet,
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+        };
+
+        let mut transmit = |manager: &mut AckManager| {
+            // bundle the ACK with a PING so the transmission is tracked
+            write_context.write_frame(&Ping).unwrap();
+            manager.transmission_state = AckTransmissionState::Active { retransmissions: 0 };
+            assert!(manager.on_transmit(&mut write_context));
+            manager.on_transmit_complete(&mut write_context);
+            let packet_number = write_context.packet_number();
+
+            write_context.frame_buffer.pop_front().unwrap();
+            let mut frame = write_context.frame_buffer.pop_front().unwrap();
+            write_context.frame_buffer.flush();
+            let Frame::Ack(ack) = frame.as_frame() else {
+                panic!("expected an ACK frame");
+            };
+            (packet_number, ack.ecn_counts)
+        };
+
+        receive(&mut manager, 1, ExplicitCongestionNotification::Ect0);
+        let (packet_number, ecn_counts) = transmit(&mut manager);
+        assert_eq!(ecn_counts.unwrap().ect_0_count, VarInt::from_u8(1));
+
+        // the peer received the counts
+        manager.on_packet_ack(time::now(), &packet_number);
+
+        // the counts haven't changed so they don't need to be sent
+        receive(&mut manager, 2, ExplicitCongestionNotification::NotEct);
+        let (packet_number, ecn_counts) = transmit(&mut manager);
+        assert_eq!(ecn_counts, None);
+        manager.on_packet_ack(time::now(), &packet_number);
+
+        // the counts are sent as soon as they change
+        receive(&mut manager, 3, ExplicitCongestionNotification::Ce);
+        let (_packet_number, ecn_counts) = transmit(&mut manager);
+        let ecn_counts = ecn_counts.unwrap();
+        assert_eq!(ecn_counts.ect_0_count, VarInt::from_u8(1));
+        assert_eq!(ecn_counts.ce_count, VarInt::from_u8(1));
+    }
+
     /// Helper function to construct `DatagramInfo` with the given `ExplicitCongestionNotification`
     fn helper_datagram_info(ecn: ExplicitCongestionNotification) -> DatagramInfo {
         DatagramInfo {
@@ -0,0 +1,115 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the activity of a connection, which is used to select connections for eviction
+
+use core::time::Duration;
+use s2n_quic_core::{endpoint::limits::EvictionCriteria, time::Timestamp};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Activity {
+    /// The time the connection was created
+    created_at: Timestamp,
+    /// The last time the connection received a datagram from the peer
+    last_received: Timestamp,
+    /// The total number of bytes sent and received by the connection
+    bytes: u64,
+}
+
+impl Activity {
+    #[inline]
+    pub fn new(timestamp: Timestamp) -> Self {
+        Self {
+            created_at: timestamp,
+            last_received: timestamp,
+            bytes: 0,
+        }
+    }
+
+    /// Called when the connection receives a datagram from the peer
+    #[inline]
+    pub fn on_datagram_received(&mut self, len: usize, timestamp: Timestamp) {
+        self.bytes = self.bytes.saturating_add(len as u64);
+        self.last_received = self.last_received.max(timestamp);
+    }
+
+    /// Called when the connection transmits bytes to the peer
+    #[inline]
+    pub fn on_transmit(&mut self, len: usize) {
+        self.bytes = self.bytes.saturating_add(len as u64);
+    }
+
+    /// Returns the amount of time since the connection was created
+    #[inline]
+    pub fn age(&self, now: Timestamp) -> Duration {
+        now.saturating_duration_since(self.created_at)
+    }
+
+    /// Returns the amount of time since the connection last received a datagram
+    #[inline]
+    pub fn idle(&self, now: Timestamp) -> Duration {
+        now.saturating_duration_since(self.last_received)
+    }
+
+    /// Returns the total number of bytes sent and received by the connection
+    #[inline]
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the eviction priority of the connection for the given criteria
+    ///
+    /// Connections with a higher priority are evicted first.
+    #[inline]
+    pub fn eviction_priority(&self, criteria: EvictionCriteria, now: Timestamp) -> u64 {
+        match criteria {
+            EvictionCriteria::IdleLongest => self.idle(now).as_micros() as u64,
+            EvictionCriteria::LowestThroughput => {
+                // compute the bytes per second, rounding the age up to a millisecond to avoid
+                // dividing by zero for brand new connections
+                let age = self.age(now).as_millis().max(1) as u64;
+                let throughput = self.bytes.saturating_mul(1000) / age;
+                u64::MAX - throughput
+            }
+            EvictionCriteria::Oldest => self.age(now).as_micros() as u64,
+            // EvictionCriteria is non_exhaustive so don't prioritize criteria we don't understand
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{testing::Clock, Clock as _};
+
+    #[test]
+    fn eviction_priority_test() {
+        let start = Clock::default().get_time();
+
+        let mut old_idle = Activity::new(start);
+        old_idle.on_datagram_received(100, start);
+
+        let mut busy = Activity::new(start + Duration::from_secs(1));
+
+        let now = start + Duration::from_secs(2);
+        busy.on_datagram_received(1_000_000, now);
+        busy.on_transmit(1_000_000);
+
+        assert_eq!(old_idle.age(now), Duration::from_secs(2));
+        assert_eq!(old_idle.idle(now), Duration::from_secs(2));
+        assert!(busy.idle(now) < Duration::from_millis(1));
+        assert_eq!(busy.bytes(), 2_000_000);
+
+        for criteria in [
+            EvictionCriteria::IdleLongest,
+            EvictionCriteria::LowestThroughput,
+            EvictionCriteria::Oldest,
+        ] {
+            assert!(
+                old_idle.eviction_priority(criteria, now) > busy.eviction_priority(criteria, now),
+                "{criteria:?}"
+            );
+        }
+    }
+}
@@ -12,10 +12,13 @@ use core::{
     fmt,
     sync::atomic::{self, Ordering},
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
     application,
     application::ServerName,
+    connection::{close, handoff, PriorityClass},
+    event,
     inet::SocketAddress,
     query::{Query, QueryMut},
     stream::StreamType,
@@ -63,8 +66,9 @@ impl Drop for Connection {
         // deletion of the data.
         // https://github.com/rust-lang/rust/blob/e012a191d768adeda1ee36a99ef8b92d51920154/library/alloc/src/sync.rs#L1637
 
-        // If the connection wasn't closed before, close it now to make sure
-        // all Streams terminate.
+        // If the connection wasn't closed before, apply the configured drop
+        // behavior. By default, this closes the connection once all Streams
+        // are flushed.
         //
         // Only close the connection if this is the last application handle.
         // Otherwise, just drop `api`, which decrements the strong count.
@@ -78,7 +82,7 @@ impl Drop for Connection {
         }
 
         atomic::fence(Ordering::Acquire);
-        self.api.close_connection(None);
+        self.api.drop_connection();
     }
 }
 
@@ -144,7 +148,27 @@ impl Connection {
         context: &Context,
     ) -> Poll<Result<Stream, connection::Error>> {
         self.api
-            .poll_open_stream(&self.api, stream_type, &mut self.open_token, context)
+            .poll_open_stream(&self.api, stream_type, None, &mut self.open_token, context)
+    }
+
+    /// Opens a [`Stream`] of the given type only if it's assigned the provided ID
+    ///
+    /// The method will return an `InvalidConfiguration` error, without opening a stream, if the
+    /// next stream of the given type would be assigned a different ID.
+    #[inline]
+    pub fn poll_open_stream_with_id_hint(
+        &mut self,
+        stream_type: StreamType,
+        id: StreamId,
+        context: &Context,
+    ) -> Poll<Result<Stream, connection::Error>> {
+        self.api.poll_open_stream(
+            &self.api,
+            stream_type,
+            Some(id),
+            &mut self.open_token,
+            context,
+        )
     }
 
     #[inline]
@@ -162,7 +186,39 @@ impl Connection {
     /// This will immediately terminate all outstanding streams.
     #[inline]
     pub fn close(&self, error_code: application::Error) {
-        self.api.close_connection(Some(error_code));
+        self.api
+            .close_connection(Some(error_code), close::Linger::Default);
+    }
+
+    /// Closes the Connection with the provided error code, retaining the closing state for
+    /// `linger` instead of the default of three times the PTO
+    #[inline]
+    pub fn close_with_linger(&self, error_code: application::Error, linger: Duration) {
+        self.api
+            .close_connection(Some(error_code), close::Linger::Duration(linger));
+    }
+
+    /// Aborts the Connection with the provided error code
+    ///
+    /// Unlike `close`, no CONNECTION_CLOSE frame is sent and the connection state is dropped
+    /// immediately.
+    #[inline]
+    pub fn abort(&self, error_code: application::Error) {
+        self.api
+            .close_connection(Some(error_code), close::Linger::Abort);
+    }
+
+    #[inline]
+    pub fn set_drop_behavior(
+        &self,
+        behavior: close::DropBehavior,
+    ) -> Result<(), connection::Error> {
+        self.api.set_drop_behavior(behavior)
+    }
+
+    #[inline]
+    pub fn set_priority_class(&self, class: PriorityClass) -> Result<(), connection::Error> {
+        self.api.set_priority_class(class)
     }
 
     #[inline]
@@ -175,6 +231,26 @@ impl Connection {
         self.api.application_protocol()
     }
 
+    #[inline]
+    pub fn peer_transport_parameter(&self, id: u64) -> Result<Option<Bytes>, connection::Error> {
+        self.api.peer_transport_parameter(id)
+    }
+
+    #[inline]
+    pub fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api.export_keying_material(label, context, output)
+    }
+
+    #[inline]
+    pub fn export_handoff(&self) -> Result<handoff::State, connection::Error> {
+        self.api.export_handoff()
+    }
+
     #[inline]
     pub fn id(&self) -> u64 {
         self.api.id()
@@ -185,10 +261,60 @@ impl Connection {
         self.api.ping()
     }
 
+    #[inline]
+    pub fn poll_ping(&self, context: &Context) -> Poll<Result<Duration, connection::Error>> {
+        self.api.poll_ping(context)
+    }
+
+    /// Polls for the connection to be usable by the application
+    ///
+    /// This is always ready for connections which weren't accepted early.
+    #[inline]
+    pub fn poll_established(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api.poll_established(context)
+    }
+
+    #[inline]
+    pub fn poll_flush(
+        &self,
+        mode: connection::FlushMode,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.api.poll_flush(mode, context)
+    }
+
     pub fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error> {
         self.api.keep_alive(enabled)
     }
 
+    #[inline]
+    pub fn attach_event_subscriber<Sub: event::Subscriber>(
+        &self,
+        subscriber: Sub,
+    ) -> Result<(), connection::Error> {
+        self.api
+            .attach_event_subscriber(Box::new(event::Attached::new(subscriber)))
+    }
+
+    #[inline]
+    pub fn idle_timeout(&self) -> Result<Option<Duration>, connection::Error> {
+        self.api.idle_timeout()
+    }
+
+    #[inline]
+    pub fn set_idle_timeout(&self, timeout: Duration) -> Result<(), connection::Error> {
+        self.api.set_idle_timeout(timeout)
+    }
+
+    #[inline]
+    pub fn set_inactivity_timeout(
+        &self,
+        timeout: Duration,
+        error: application::Error,
+    ) -> Result<(), connection::Error> {
+        self.api.set_inactivity_timeout(timeout, error)
+    }
+
     #[inline]
     pub fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.local_address()
@@ -199,6 +325,40 @@ impl Connection {
         self.api.remote_address()
     }
 
+    #[inline]
+    pub fn max_datagram_size(&self) -> Result<usize, connection::Error> {
+        self.api.max_datagram_size()
+    }
+
+    #[inline]
+    pub fn max_datagram_payload(&self) -> Result<usize, connection::Error> {
+        self.api.max_datagram_payload()
+    }
+
+    #[inline]
+    pub fn poll_max_datagram_payload(
+        &self,
+        previous: usize,
+        context: &Context,
+    ) -> Poll<Result<usize, connection::Error>> {
+        self.api.poll_max_datagram_payload(previous, context)
+    }
+
+    #[inline]
+    pub fn statistics(&self) -> Result<connection::Statistics, connection::Error> {
+        self.api.statistics()
+    }
+
+    #[inline]
+    pub fn path_info(&self) -> Result<connection::PathInfo, connection::Error> {
+        self.api.path_info()
+    }
+
+    #[inline]
+    pub fn path_statistics(&self) -> Result<Vec<connection::PathStatistics>, connection::Error> {
+        self.api.path_statistics()
+    }
+
     #[inline]
     pub fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api.query_event_context(query)
@@ -13,10 +13,13 @@ use bytes::Bytes;
 use core::{
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
     application,
     application::ServerName,
+    connection::{close, handoff, PriorityClass},
+    event,
     inet::SocketAddress,
     query::{Query, QueryMut},
     stream::{ops, StreamId, StreamType},
@@ -48,26 +51,85 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
         &self,
         arc_self: &Arc<dyn ConnectionApiProvider>,
         stream_type: StreamType,
+        id_hint: Option<StreamId>,
         open_token: &mut connection::OpenToken,
         context: &Context,
     ) -> Poll<Result<Stream, connection::Error>>;
 
-    fn close_connection(&self, code: Option<application::Error>);
+    fn close_connection(&self, code: Option<application::Error>, linger: close::Linger);
+
+    fn drop_connection(&self);
+
+    fn set_drop_behavior(&self, behavior: close::DropBehavior) -> Result<(), connection::Error>;
+
+    fn set_priority_class(&self, class: PriorityClass) -> Result<(), connection::Error>;
 
     fn server_name(&self) -> Result<Option<ServerName>, connection::Error>;
 
     fn application_protocol(&self) -> Result<Bytes, connection::Error>;
 
+    fn peer_transport_parameter(&self, id: u64) -> Result<Option<Bytes>, connection::Error>;
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
+    fn export_handoff(&self) -> Result<handoff::State, connection::Error>;
+
     fn id(&self) -> u64;
 
     fn ping(&self) -> Result<(), connection::Error>;
 
+    fn poll_ping(&self, context: &Context) -> Poll<Result<Duration, connection::Error>>;
+
+    fn poll_established(&self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    fn poll_flush(
+        &self,
+        mode: connection::FlushMode,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>>;
+
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn attach_event_subscriber(
+        &self,
+        subscriber: Box<dyn event::AttachedSubscriber>,
+    ) -> Result<(), connection::Error>;
+
+    fn idle_timeout(&self) -> Result<Option<Duration>, connection::Error>;
+
+    fn set_idle_timeout(&self, timeout: Duration) -> Result<(), connection::Error>;
+
+    fn set_inactivity_timeout(
+        &self,
+        timeout: Duration,
+        error: application::Error,
+    ) -> Result<(), connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn max_datagram_size(&self) -> Result<usize, connection::Error>;
+
+    fn max_datagram_payload(&self) -> Result<usize, connection::Error>;
+
+    fn poll_max_datagram_payload(
+        &self,
+        previous: usize,
+        context: &Context,
+    ) -> Poll<Result<usize, connection::Error>>;
+
+    fn statistics(&self) -> Result<connection::Statistics, connection::Error>;
+
+    fn path_info(&self) -> Result<connection::PathInfo, connection::Error>;
+
+    fn path_statistics(&self) -> Result<Vec<connection::PathStatistics>, connection::Error>;
+
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
 
     fn query_event_context_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
@@ -14,10 +14,11 @@ use crate::{
         self,
         connect::{self, ConnectionSender},
         handle::{AcceptorSender, ConnectorReceiver},
+        manager,
     },
     stream,
 };
-use alloc::{collections::BTreeMap, sync::Arc};
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
 use bytes::Bytes;
 use core::{
     cell::Cell,
@@ -26,21 +27,27 @@ use core::{
     pin::Pin,
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
-use intrusive_collections::{
-    intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
-};
+use intrusive_collections::{intrusive_adapter, LinkedList, LinkedListLink};
 use s2n_quic_core::{
     application,
     application::ServerName,
-    event::supervisor,
+    connection::{close, handoff, PriorityClass},
+    endpoint::limits::{ConnectionCounts, EvictionCriteria},
+    event::{self, supervisor},
     inet::SocketAddress,
     query::{Query, QueryMut},
     recovery::K_GRANULARITY,
     time::Timestamp,
     transport,
 };
+use slab::Slab;
 use smallvec::SmallVec;
+use timer_wheel::TimerWheel;
+
+mod slab;
+mod timer_wheel;
 
 // Intrusive list adapter for managing the list of `done` connections
 intrusive_adapter!(DoneConnectionsAdapter<C, L> = Arc<ConnectionNode<C, L>>: ConnectionNode<C, L> {
@@ -59,15 +66,9 @@ intrusive_adapter!(WaitingForConnectionIdAdapter<C, L> = Arc<ConnectionNode<C, L
     waiting_for_connection_id_link: LinkedListLink
 } where C: connection::Trait, L: connection::Lock<C>);
 
-// Intrusive red black tree adapter for managing a list of `waiting_for_timeout` connections
+// Intrusive list adapter for managing the timer wheel of `waiting_for_timeout` connections
 intrusive_adapter!(WaitingForTimeoutAdapter<C, L> = Arc<ConnectionNode<C, L>>: ConnectionNode<C, L> {
-    waiting_for_timeout_link: RBTreeLink
-} where C: connection::Trait, L: connection::Lock<C>);
-
-// Intrusive red black tree adapter for managing all connections in a tree for
-// lookup by Connection ID
-intrusive_adapter!(ConnectionTreeAdapter<C, L> = Arc<ConnectionNode<C, L>>: ConnectionNode<C, L> {
-    tree_link: RBTreeLink
+    waiting_for_timeout_link: LinkedListLink
 } where C: connection::Trait, L: connection::Lock<C>);
 
 /// A wrapper around a `Connection` implementation which allows to insert the
@@ -78,8 +79,6 @@ struct ConnectionNode<C: connection::Trait, L: connection::Lock<C>> {
     inner: L,
     /// The connection id pertaining to the stored connection
     internal_connection_id: InternalConnectionId,
-    /// Allows the Connection to be part of the `connection_map` collection
-    tree_link: RBTreeLink,
     /// Allows the Connection to be part of the `done_connections` collection
     done_connections_link: LinkedListLink,
     /// Allows the Connection to be part of the `waiting_for_transmission` collection
@@ -87,9 +86,15 @@ struct ConnectionNode<C: connection::Trait, L: connection::Lock<C>> {
     /// Allows the Connection to be part of the `waiting_for_connection_id` collection
     waiting_for_connection_id_link: LinkedListLink,
     /// Allows the Connection to be part of the `waiting_for_timeout` collection
-    waiting_for_timeout_link: RBTreeLink,
+    waiting_for_timeout_link: LinkedListLink,
     /// The cached time at which the connection will timeout next
     timeout: Cell<Option<Timestamp>>,
+    /// The cached class of the connection
+    priority_class: Cell<PriorityClass>,
+    /// The cached lane of the connection, which selects its `waiting_for_transmission` list
+    transmission_lane: Cell<usize>,
+    /// The slot in the `waiting_for_timeout` timer wheel the connection is stored in
+    timer_wheel_slot: Cell<usize>,
     /// The count of outstanding application handles
     application_handle_count: AtomicUsize,
     /// The inner connection type
@@ -105,12 +110,14 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionNode<C, L> {
         ConnectionNode {
             inner: connection_impl,
             internal_connection_id,
-            tree_link: RBTreeLink::new(),
             done_connections_link: LinkedListLink::new(),
             waiting_for_transmission_link: LinkedListLink::new(),
             waiting_for_connection_id_link: LinkedListLink::new(),
-            waiting_for_timeout_link: RBTreeLink::new(),
+            waiting_for_timeout_link: LinkedListLink::new(),
             timeout: Cell::new(None),
+            priority_class: Cell::new(PriorityClass::default()),
+            transmission_lane: Cell::new(0),
+            timer_wheel_slot: Cell::new(0),
             application_handle_count: AtomicUsize::new(0),
             _connection: PhantomData,
         }
@@ -199,19 +206,15 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionNode<C, L> {
     }
 }
 
-impl<'a, C: connection::Trait, L: connection::Lock<C>> KeyAdapter<'a>
-    for WaitingForTimeoutAdapter<C, L>
-{
-    type Key = Timestamp;
-
-    fn get_key(&self, node: &'a ConnectionNode<C, L>) -> Timestamp {
-        if let Some(timeout) = node.timeout.get() {
+impl<C: connection::Trait, L: connection::Lock<C>> timer_wheel::Entry for ConnectionNode<C, L> {
+    fn expiration(&self) -> Timestamp {
+        if let Some(timeout) = self.timeout.get() {
             timeout
         } else if cfg!(debug_assertions) {
             panic!("node was queried for timeout but none was set")
         } else {
             unsafe {
-                // Safety: this will simply move the connection to the beginning of the queue
+                // Safety: this will simply expire the connection on the next iteration
                 // to ensure the timeout value is properly updated.
                 //
                 // Assuming everything is tested properly, this should never be reached
@@ -219,17 +222,9 @@ impl<'a, C: connection::Trait, L: connection::Lock<C>> KeyAdapter<'a>
             }
         }
     }
-}
 
-// This is required to build an intrusive `RBTree` of `ConnectionNode`s which
-// utilizes `ConnectionId`s as a key.
-impl<'a, C: connection::Trait, L: connection::Lock<C>> KeyAdapter<'a>
-    for ConnectionTreeAdapter<C, L>
-{
-    type Key = InternalConnectionId;
-
-    fn get_key(&self, node: &'a ConnectionNode<C, L>) -> InternalConnectionId {
-        node.internal_connection_id
+    fn wheel_slot(&self) -> &Cell<usize> {
+        &self.timer_wheel_slot
     }
 }
 
@@ -276,11 +271,12 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         &self,
         arc_self: &ConnectionApi,
         stream_type: stream::StreamType,
+        id_hint: Option<stream::StreamId>,
         open_token: &mut connection::OpenToken,
         context: &Context,
     ) -> Poll<Result<stream::Stream, connection::Error>> {
-        let response =
-            self.api_poll_call(|conn| conn.poll_open_stream(stream_type, open_token, context));
+        let response = self
+            .api_poll_call(|conn| conn.poll_open_stream(stream_type, id_hint, open_token, context));
 
         match response {
             Poll::Pending => Poll::Pending,
@@ -295,13 +291,28 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         }
     }
 
-    fn close_connection(&self, error: Option<application::Error>) {
+    fn close_connection(&self, error: Option<application::Error>, linger: close::Linger) {
+        let _: Result<(), connection::Error> = self.api_write_call(|conn| {
+            conn.application_close(error, linger);
+            Ok(())
+        });
+    }
+
+    fn drop_connection(&self) {
         let _: Result<(), connection::Error> = self.api_write_call(|conn| {
-            conn.application_close(error);
+            conn.application_drop();
             Ok(())
         });
     }
 
+    fn set_drop_behavior(&self, behavior: close::DropBehavior) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_drop_behavior(behavior))
+    }
+
+    fn set_priority_class(&self, class: PriorityClass) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_priority_class(class))
+    }
+
     fn server_name(&self) -> Result<Option<ServerName>, connection::Error> {
         self.api_read_call(|conn| Ok(conn.server_name()))
     }
@@ -310,6 +321,23 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| Ok(conn.application_protocol()))
     }
 
+    fn peer_transport_parameter(&self, id: u64) -> Result<Option<Bytes>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.peer_transport_parameter(id)))
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api_read_call(|conn| conn.export_keying_material(label, context, output))
+    }
+
+    fn export_handoff(&self) -> Result<handoff::State, connection::Error> {
+        self.api_write_call(|conn| conn.export_handoff())
+    }
+
     fn id(&self) -> u64 {
         self.internal_connection_id.into()
     }
@@ -318,10 +346,49 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.ping())
     }
 
+    fn poll_ping(&self, context: &Context) -> Poll<Result<Duration, connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_ping(context))
+    }
+
+    fn poll_established(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_established(context))
+    }
+
+    fn poll_flush(
+        &self,
+        mode: connection::FlushMode,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_flush(mode, context))
+    }
+
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error> {
         self.api_write_call(|conn| conn.keep_alive(enabled))
     }
 
+    fn attach_event_subscriber(
+        &self,
+        subscriber: Box<dyn event::AttachedSubscriber>,
+    ) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.attach_event_subscriber(subscriber))
+    }
+
+    fn idle_timeout(&self) -> Result<Option<Duration>, connection::Error> {
+        self.api_read_call(|conn| conn.idle_timeout())
+    }
+
+    fn set_idle_timeout(&self, timeout: Duration) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_idle_timeout(timeout))
+    }
+
+    fn set_inactivity_timeout(
+        &self,
+        timeout: Duration,
+        error: application::Error,
+    ) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_inactivity_timeout(timeout, error))
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.local_address())
     }
@@ -330,6 +397,34 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| conn.remote_address())
     }
 
+    fn max_datagram_size(&self) -> Result<usize, connection::Error> {
+        self.api_read_call(|conn| conn.max_datagram_size())
+    }
+
+    fn max_datagram_payload(&self) -> Result<usize, connection::Error> {
+        self.api_read_call(|conn| conn.max_datagram_payload())
+    }
+
+    fn poll_max_datagram_payload(
+        &self,
+        previous: usize,
+        context: &Context,
+    ) -> Poll<Result<usize, connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_max_datagram_payload(previous, context))
+    }
+
+    fn statistics(&self) -> Result<connection::Statistics, connection::Error> {
+        self.api_read_call(|conn| conn.statistics())
+    }
+
+    fn path_info(&self) -> Result<connection::PathInfo, connection::Error> {
+        self.api_read_call(|conn| conn.path_info())
+    }
+
+    fn path_statistics(&self) -> Result<Vec<connection::PathStatistics>, connection::Error> {
+        self.api_read_call(|conn| conn.path_statistics())
+    }
+
     #[inline]
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api_read_call(|conn| {
@@ -355,6 +450,31 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
     }
 }
 
+/// The number of `waiting_for_transmission` lists
+///
+/// The first lane holds connections with time-critical frames to send, which are visited ahead of
+/// the lanes for each of the priority classes.
+const TRANSMISSION_LANES: usize = PriorityClass::ALL.len() + 1;
+
+/// The `waiting_for_transmission` list for connections with time-critical frames to send
+const URGENT_TRANSMISSION_LANE: usize = 0;
+
+/// Returns the `waiting_for_transmission` list for connections in the given priority class
+#[inline]
+fn class_transmission_lane(class: PriorityClass) -> usize {
+    URGENT_TRANSMISSION_LANE + 1 + class.index()
+}
+
+/// Returns the `waiting_for_transmission` list a connection with the given interests is placed in
+#[inline]
+fn transmission_lane(interests: &ConnectionInterests) -> usize {
+    if interests.urgent_transmission {
+        URGENT_TRANSMISSION_LANE
+    } else {
+        class_transmission_lane(interests.priority_class)
+    }
+}
+
 /// Contains all secondary lists of Connections.
 ///
 /// A Connection can be a member in any of those, in addition to being a member of
@@ -362,12 +482,12 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
 struct InterestLists<C: connection::Trait, L: connection::Lock<C>> {
     /// Connections which have been finalized
     done_connections: LinkedList<DoneConnectionsAdapter<C, L>>,
-    /// Connections which need to transmit data
-    waiting_for_transmission: LinkedList<WaitingForTransmissionAdapter<C, L>>,
+    /// Connections which need to transmit data, with a list for each transmission lane
+    waiting_for_transmission: [LinkedList<WaitingForTransmissionAdapter<C, L>>; TRANSMISSION_LANES],
     /// Connections which need a new connection ID
     waiting_for_connection_id: LinkedList<WaitingForConnectionIdAdapter<C, L>>,
     /// Connections which are waiting for a timeout to occur
-    waiting_for_timeout: RBTree<WaitingForTimeoutAdapter<C, L>>,
+    waiting_for_timeout: TimerWheel<WaitingForTimeoutAdapter<C, L>>,
     /// Connections which are waiting for a handshake to complete.
     ///
     /// The senders are a vector to allow multiple tasks to register interest in the same
@@ -380,12 +500,14 @@ struct InterestLists<C: connection::Trait, L: connection::Lock<C>> {
 }
 
 impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
-    fn new() -> Self {
+    fn new(timer_granularity: Duration) -> Self {
         Self {
             done_connections: LinkedList::new(DoneConnectionsAdapter::new()),
-            waiting_for_transmission: LinkedList::new(WaitingForTransmissionAdapter::new()),
+            waiting_for_transmission: core::array::from_fn(|_| {
+                LinkedList::new(WaitingForTransmissionAdapter::new())
+            }),
             waiting_for_connection_id: LinkedList::new(WaitingForConnectionIdAdapter::new()),
-            waiting_for_timeout: RBTree::new(WaitingForTimeoutAdapter::new()),
+            waiting_for_timeout: TimerWheel::new(timer_granularity),
             waiting_for_open: BTreeMap::new(),
             handshake_connections: 0,
             connection_count: 0,
@@ -454,10 +576,38 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             };
         }
 
-        sync_interests_list!(
+        // A connection which changed its lane while waiting for transmission is moved to the
+        // back of the list for its new lane
+        let lane = transmission_lane(&interests);
+        if node.waiting_for_transmission_link.is_linked()
+            && (!interests.transmission || node.transmission_lane.get() != lane)
+        {
+            let mut cursor = unsafe {
+                // Safety: We know that the node is only ever part of the list for its cached lane
+                self.waiting_for_transmission[node.transmission_lane.get()]
+                    .cursor_mut_from_ptr(node as *const ConnectionNode<C, L>)
+            };
+            cursor.remove();
+        }
+        node.priority_class.set(interests.priority_class);
+        node.transmission_lane.set(lane);
+
+        if interests.transmission && !node.waiting_for_transmission_link.is_linked() {
+            let list = &mut self.waiting_for_transmission[lane];
+            let node = unsafe {
+                // Safety: We know that all of our ConnectionNode's are stored in
+                // reference counted pointers.
+                node.arc_from_ref()
+            };
+            if matches!(result, ConnectionContainerIterationResult::Continue) {
+                list.push_back(node);
+            } else {
+                list.push_front(node);
+            }
+        }
+        debug_assert_eq!(
             interests.transmission,
-            waiting_for_transmission_link,
-            waiting_for_transmission
+            node.waiting_for_transmission_link.is_linked()
         );
 
         sync_interests_list!(
@@ -470,7 +620,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
         if node.timeout.get() != interests.timeout {
             // remove the connection if it's currently linked
             if node.waiting_for_timeout_link.is_linked() {
-                remove_interest!(waiting_for_timeout);
+                unsafe {
+                    // Safety: We know that the node is only ever part of this wheel
+                    self.waiting_for_timeout.remove(node);
+                }
             }
             // set the new timeout value
             node.timeout.set(interests.timeout);
@@ -507,63 +660,61 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             };
             let handle = crate::connection::api::Connection::new(handle);
 
-            match <C::Config as endpoint::Config>::ENDPOINT_TYPE {
-                endpoint::Type::Server => {
-                    if let Err(error) = accept_queue.unbounded_send(handle) {
-                        error.into_inner().api.close_connection(None);
+            // Client connections and server connections imported from another endpoint are
+            // returned to the task waiting for them rather than the accept queue
+            if let Some(mut senders) = self.waiting_for_open.remove(&id) {
+                let mut any_interest = false;
+                let last = senders.pop();
+                for sender in senders {
+                    if let Err(Ok(_handle)) = sender.send(Ok(handle.clone())) {
+                        // This particular handle is not interested anymore, but maybe one
+                        // of the others will be.
+                    } else {
+                        any_interest = true;
                     }
                 }
-                endpoint::Type::Client => {
-                    if let Some(mut senders) = self.waiting_for_open.remove(&id) {
-                        let mut any_interest = false;
-                        let last = senders.pop();
-                        for sender in senders {
-                            if let Err(Ok(_handle)) = sender.send(Ok(handle.clone())) {
-                                // This particular handle is not interested anymore, but maybe one
-                                // of the others will be.
-                            } else {
-                                any_interest = true;
-                            }
+                if let Some(sender) = last {
+                    if let Err(Ok(handle)) = sender.send(Ok(handle)) {
+                        if !any_interest {
+                            // close the connection if the application is no longer waiting for the handshake
+                            handle.api.close_connection(None, Default::default());
                         }
-                        if let Some(sender) = last {
-                            if let Err(Ok(handle)) = sender.send(Ok(handle)) {
-                                if !any_interest {
-                                    // close the connection if the application is no longer waiting for the handshake
-                                    handle.api.close_connection(None);
-                                }
-                            }
-                        }
-                    } else {
-                        debug_assert!(false, "client connection tried to open more than once");
                     }
                 }
+            } else if <C::Config as endpoint::Config>::ENDPOINT_TYPE.is_server() {
+                if let Err(error) = accept_queue.unbounded_send(handle) {
+                    error
+                        .into_inner()
+                        .api
+                        .close_connection(None, Default::default());
+                }
+            } else {
+                debug_assert!(false, "client connection tried to open more than once");
             }
         }
 
         if interests.finalization != node.done_connections_link.is_linked() {
             if interests.finalization {
-                if <C::Config as endpoint::Config>::ENDPOINT_TYPE.is_client() {
-                    if let Some(senders) = self.waiting_for_open.remove(&id) {
-                        let err = node.inner.read(|conn| conn.error());
-                        let err = match err {
-                            Ok(Some(err)) => {
-                                // error from connection
-                                err
-                            }
-                            Ok(None) => {
-                                // connection expressed finalization without error
-                                transport::Error::NO_ERROR.into()
-                            }
-                            Err(_err) => {
-                                // error acquiring a lock
-                                transport::Error::INTERNAL_ERROR
-                                    .with_reason("failed to acquire connection lock")
-                                    .into()
-                            }
-                        };
-                        for sender in senders {
-                            let _ = sender.send(Err(err));
+                if let Some(senders) = self.waiting_for_open.remove(&id) {
+                    let err = node.inner.read(|conn| conn.error());
+                    let err = match err {
+                        Ok(Some(err)) => {
+                            // error from connection
+                            err
+                        }
+                        Ok(None) => {
+                            // connection expressed finalization without error
+                            transport::Error::NO_ERROR.into()
                         }
+                        Err(_err) => {
+                            // error acquiring a lock
+                            transport::Error::INTERNAL_ERROR
+                                .with_reason("failed to acquire connection lock")
+                                .into()
+                        }
+                    };
+                    for sender in senders {
+                        let _ = sender.send(Err(err));
                     }
                 }
 
@@ -591,13 +742,13 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
         let connection_ptr = connection as *const ConnectionNode<C, L>;
 
         macro_rules! remove_connection_from_list {
-            ($list_name:ident, $link_name:ident) => {
+            ($list_name:ident $([$index:expr])?, $link_name:ident) => {
                 if connection.$link_name.is_linked() {
                     let mut cursor = unsafe {
                         // Safety: We know that the Connection is part of the list,
                         // because it is linked, and we never place Connections in
                         // other lists when `finalize_done_connections` is called.
-                        self.$list_name.cursor_mut_from_ptr(connection_ptr)
+                        self.$list_name$([$index])?.cursor_mut_from_ptr(connection_ptr)
                     };
                     let remove_result = cursor.remove();
                     debug_assert!(remove_result.is_some());
@@ -605,9 +756,18 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             };
         }
 
-        remove_connection_from_list!(waiting_for_transmission, waiting_for_transmission_link);
+        remove_connection_from_list!(
+            waiting_for_transmission[connection.transmission_lane.get()],
+            waiting_for_transmission_link
+        );
         remove_connection_from_list!(waiting_for_connection_id, waiting_for_connection_id_link);
-        remove_connection_from_list!(waiting_for_timeout, waiting_for_timeout_link);
+
+        if connection.waiting_for_timeout_link.is_linked() {
+            unsafe {
+                // Safety: We know that the Connection is part of the wheel, because it is linked
+                self.waiting_for_timeout.remove(connection);
+            }
+        }
 
         self.connection_count -= 1;
     }
@@ -625,8 +785,8 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
 /// - There exist a variety of iteration methods, which allow to iterate over
 ///   all or a subset of connections in each interest list.
 pub struct ConnectionContainer<C: connection::Trait, L: connection::Lock<C>> {
-    /// Connections organized as a tree, for lookup by Connection ID
-    connection_map: RBTree<ConnectionTreeAdapter<C, L>>,
+    /// Connections stored in a slab, for lookup by Connection ID
+    connection_map: Slab<Arc<ConnectionNode<C, L>>>,
     /// Additional interest lists in which Connections will be placed dynamically
     interest_lists: InterestLists<C, L>,
     /// The synchronized queue of accepted connections
@@ -637,11 +797,22 @@ pub struct ConnectionContainer<C: connection::Trait, L: connection::Lock<C>> {
     ///
     /// This is only used by clients
     connector_receiver: ConnectorReceiver,
+    /// The connections visible to management handles
+    registry: manager::Registry,
 }
 
 macro_rules! iterate_interruptible {
-    ($sel:ident, $list_name:ident, $link_name:ident, $func:expr) => {
-        let mut extracted_list = $sel.interest_lists.$list_name.take();
+    ($sel:ident, $list_name:ident $([$index:expr])?, $link_name:ident, $func:expr) => {
+        iterate_interruptible!(
+            $sel,
+            $list_name $([$index])?,
+            $link_name,
+            $func,
+            |_interests: &mut ConnectionInterests, _result| {}
+        );
+    };
+    ($sel:ident, $list_name:ident $([$index:expr])?, $link_name:ident, $func:expr, $on_interests:expr) => {
+        let mut extracted_list = $sel.interest_lists.$list_name$([$index])?.take();
         let mut cursor = extracted_list.front_mut();
 
         while let Some(connection) = cursor.remove() {
@@ -652,7 +823,8 @@ macro_rules! iterate_interruptible {
 
             let (result, interests) = match connection.inner.write(|conn| {
                 let result = $func(conn);
-                let interests = conn.interests();
+                let mut interests = conn.interests();
+                $on_interests(&mut interests, result);
                 (result, interests)
             }) {
                 Ok(result) => result,
@@ -675,7 +847,7 @@ macro_rules! iterate_interruptible {
             match result {
                 ConnectionContainerIterationResult::BreakAndInsertAtFront => {
                     $sel.interest_lists
-                        .$list_name
+                        .$list_name$([$index])?
                         .front_mut()
                         .splice_after(extracted_list);
                     break;
@@ -690,15 +862,26 @@ macro_rules! iterate_interruptible {
 
 impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
     /// Creates a new `ConnectionContainer`
-    pub(crate) fn new(accept_queue: AcceptorSender, connector_receiver: ConnectorReceiver) -> Self {
+    pub(crate) fn new(
+        accept_queue: AcceptorSender,
+        connector_receiver: ConnectorReceiver,
+        timer_granularity: Duration,
+        connection_capacity: usize,
+    ) -> Self {
         Self {
-            connection_map: RBTree::new(ConnectionTreeAdapter::new()),
-            interest_lists: InterestLists::new(),
+            connection_map: Slab::with_capacity(connection_capacity),
+            interest_lists: InterestLists::new(timer_granularity),
             accept_queue,
             connector_receiver,
+            registry: manager::Registry::default(),
         }
     }
 
+    /// Returns a handle for inspecting and closing the connections in the container
+    pub fn manager(&self) -> manager::Manager {
+        manager::Manager::new(self.registry.clone())
+    }
+
     /// Returns `true` if the endpoint can accept new connections
     pub fn can_accept(&self) -> bool {
         debug_assert!(<C::Config as endpoint::Config>::ENDPOINT_TYPE.is_server());
@@ -720,6 +903,15 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         self.connection_map.is_empty()
     }
 
+    /// Reserves an ID for a new connection
+    ///
+    /// The returned ID should be passed to `insert_server_connection` or
+    /// `insert_client_connection`. If the connection fails to be created, the ID will be
+    /// released on the next reservation.
+    pub fn reserve_id(&mut self) -> InternalConnectionId {
+        self.connection_map.reserve_id()
+    }
+
     /// Stop accepting new connection attempts and close pending connection requests
     ///
     /// Drains any requests in the connector_receiver queue and notifies the application
@@ -759,15 +951,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
     }
 
     /// Returns the next `Timestamp` at which any contained connections will expire
+    ///
+    /// Note that this is rounded to the granularity of the timer wheel.
     pub fn next_expiration(&self) -> Option<Timestamp> {
-        let cursor = self.interest_lists.waiting_for_timeout.front();
-        let node = cursor.get()?;
-        let timeout = node.timeout.get();
-        debug_assert!(
-            timeout.is_some(),
-            "a connection should only be in the timeout list when the timeout field is set"
-        );
-        timeout
+        self.interest_lists.waiting_for_timeout.next_expiration()
     }
 
     /// Insert a new server Connection into the container
@@ -799,6 +986,25 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         self.insert_connection(connection, internal_connection_id)
     }
 
+    /// Insert a server Connection handed off by another endpoint into the container
+    ///
+    /// The connection is returned over `connection_sender` instead of the accept queue.
+    pub fn insert_imported_connection(
+        &mut self,
+        connection: C,
+        internal_connection_id: InternalConnectionId,
+        connection_sender: ConnectionSender,
+    ) {
+        debug_assert!(<C::Config as endpoint::Config>::ENDPOINT_TYPE.is_server());
+
+        self.interest_lists.waiting_for_open.insert(
+            internal_connection_id,
+            smallvec::smallvec![connection_sender],
+        );
+
+        self.insert_connection(connection, internal_connection_id)
+    }
+
     /// Potentially register a sender with an existing client Connection
     pub fn register_sender_for_client_connection(
         &mut self,
@@ -837,6 +1043,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         let connection = L::new(connection);
         let connection = Arc::new(ConnectionNode::new(connection, internal_connection_id));
 
+        // Increment the inflight handshakes before updating the interests, since servers which
+        // allow 0.5-RTT data may hand the new connection over to the application right away
+        self.interest_lists.handshake_connections += 1;
+
         if self
             .interest_lists
             .update_interests(
@@ -847,11 +1057,15 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
             )
             .is_ok()
         {
-            self.connection_map.insert(connection);
-            // Increment the inflight handshakes and total connection counter because we have accepted a new connection
-            self.interest_lists.handshake_connections += 1;
+            let api: ConnectionApi = connection.clone();
+            self.registry.insert(internal_connection_id, &api);
+            self.connection_map
+                .insert(internal_connection_id, connection);
+            // Increment the total connection counter because we have accepted a new connection
             self.interest_lists.connection_count += 1;
             self.ensure_counter_consistency();
+        } else {
+            self.interest_lists.handshake_connections = self.count_handshaking_connections();
         }
     }
 
@@ -864,21 +1078,17 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         self.interest_lists.connection_count
     }
 
+    /// Returns the number of connections reported to the endpoint limiter
+    pub fn counts(&self) -> ConnectionCounts {
+        ConnectionCounts::new(self.handshake_connections(), self.len())
+    }
+
     pub fn get_connection_handle(
         &mut self,
         id: &InternalConnectionId,
     ) -> Option<crate::connection::api::Connection> {
-        let cursor = self.connection_map.find(id);
-        let node = cursor.get()?;
-        let handle = unsafe {
-            // We have to obtain an `Arc<ConnectionNode>` in order to be able to
-            // perform interest updates later on. However the intrusive tree
-            // API only provides us a raw reference.
-            // Safety: We know that all of our ConnectionNode's are stored in
-            // reference counted pointers.
-            node.arc_from_ref()
-        };
-        Some(crate::connection::api::Connection::new(handle))
+        let node = self.connection_map.get(*id)?;
+        Some(crate::connection::api::Connection::new(node.clone()))
     }
 
     /// Looks up the `Connection` with the given ID and executes the provided function
@@ -898,8 +1108,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
     where
         F: FnOnce(&mut C) -> R,
     {
-        let cursor = self.connection_map.find(&connection_id);
-        let node = cursor.get()?;
+        let node = self.connection_map.get(connection_id)?;
 
         let on_write = |conn: &mut C| {
             let result = func(conn);
@@ -941,6 +1150,63 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         Some((result, interests))
     }
 
+    /// Returns the IDs of up to `count` open connections, ordered by their eviction priority
+    /// for the given criteria
+    ///
+    /// Connections in less urgent priority classes are always selected before connections in
+    /// more urgent classes.
+    ///
+    /// Connections which are already closing are never selected and count towards `count`, since
+    /// their resources are released once they finish closing.
+    pub fn eviction_candidates(
+        &self,
+        count: usize,
+        criteria: EvictionCriteria,
+        now: Timestamp,
+    ) -> Vec<InternalConnectionId> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let mut closing = 0;
+        let mut candidates = Vec::new();
+
+        for node in self.connection_map.iter() {
+            let _ = node.inner.read(|conn| {
+                if conn.error().is_some() {
+                    closing += 1;
+                } else {
+                    let priority = conn.activity().eviction_priority(criteria, now);
+                    candidates.push((
+                        node.priority_class.get(),
+                        priority,
+                        node.internal_connection_id,
+                    ));
+                }
+            });
+        }
+
+        // order by the least urgent class, then by the highest priority and fall back to the
+        // connection id so the selection is deterministic
+        candidates.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+        candidates.truncate(count.saturating_sub(closing));
+        candidates.into_iter().map(|(_, _, id)| id).collect()
+    }
+
+    /// Returns the ID of the first connection for which `predicate` returns `true`
+    ///
+    /// This requires scanning all of the connections, so it should only be used when the
+    /// connection can't be looked up by its connection ID.
+    pub fn find_connection<F>(&self, mut predicate: F) -> Option<InternalConnectionId>
+    where
+        F: FnMut(&C) -> bool,
+    {
+        self.connection_map
+            .iter()
+            .find(|node| node.inner.read(|conn| predicate(conn)).unwrap_or(false))
+            .map(|node| node.internal_connection_id)
+    }
+
     /// Removes all Connections in the `done` state from the `ConnectionContainer`.
     fn finalize_done_connections(&mut self) {
         debug_assert_eq!(
@@ -984,24 +1250,71 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         if cfg!(debug_assertions) {
             let expected = self.count_handshaking_connections();
             assert_eq!(expected, self.interest_lists.handshake_connections);
-            assert_eq!(self.len(), self.connection_map.iter().count());
+            assert_eq!(self.len(), self.connection_map.len());
         }
     }
 
-    /// Iterates over all `Connection`s which are waiting for transmission,
+    /// Iterates over all `Connection`s which have time-critical frames to transmit,
     /// and executes the given function on each `Connection`
-    pub fn iterate_transmission_list<F>(&mut self, mut func: F)
+    ///
+    /// A connection which was visited is placed in the list for its priority class afterwards, so
+    /// the rest of its data is transmitted in the same order as the other connections in its
+    /// class.
+    pub fn iterate_urgent_transmission_list<F>(&mut self, mut func: F)
     where
         F: FnMut(&mut C) -> ConnectionContainerIterationResult,
     {
         iterate_interruptible!(
             self,
-            waiting_for_transmission,
+            waiting_for_transmission[URGENT_TRANSMISSION_LANE],
             waiting_for_transmission_link,
-            func
+            func,
+            |interests: &mut ConnectionInterests, result| {
+                // A connection which wasn't visited keeps its place at the front of the lane
+                if matches!(result, ConnectionContainerIterationResult::Continue) {
+                    interests.urgent_transmission = false;
+                }
+            }
         );
     }
 
+    /// Iterates over all `Connection`s which are waiting for transmission,
+    /// and executes the given function on each `Connection`
+    ///
+    /// Connections in more urgent priority classes are visited first. Once `func` interrupts
+    /// the iteration, no connections in less urgent classes are visited. Connections with
+    /// time-critical frames are only visited by `iterate_urgent_transmission_list`.
+    pub fn iterate_transmission_list<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut C) -> ConnectionContainerIterationResult,
+    {
+        let mut is_interrupted = false;
+
+        for class in PriorityClass::ALL {
+            let mut func = |conn: &mut C| {
+                let result = func(conn);
+                if matches!(
+                    result,
+                    ConnectionContainerIterationResult::BreakAndInsertAtFront
+                ) {
+                    is_interrupted = true;
+                }
+                result
+            };
+
+            iterate_interruptible!(
+                self,
+                waiting_for_transmission[class_transmission_lane(class)],
+                waiting_for_transmission_link,
+                func
+            );
+
+            if is_interrupted {
+                break;
+            }
+        }
+    }
+
     /// Iterates over all `Connection`s which are waiting for new connection Ids,
     /// and executes the given function on each `Connection`
     pub fn iterate_new_connection_id_list<F>(&mut self, mut func: F)
@@ -1022,30 +1335,12 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
     where
         F: FnMut(&mut C, &supervisor::Context),
     {
-        loop {
-            let mut cursor = self.interest_lists.waiting_for_timeout.front_mut();
-            let connection = if let Some(connection) = cursor.get() {
-                connection
-            } else {
-                break;
-            };
-
-            match connection.timeout.get() {
-                Some(v) if !v.has_elapsed(now) => break,
-                Some(_) => {}
-                None => {
-                    debug_assert!(false, "connection was inserted without a timeout specified");
-
-                    let conn = cursor.remove().unwrap();
-                    conn.timeout.set(None);
-                    continue;
-                }
+        while let Some(connection) = self.interest_lists.waiting_for_timeout.pop_expired(now) {
+            if connection.timeout.get().is_none() {
+                debug_assert!(false, "connection was inserted without a timeout specified");
+                continue;
             }
 
-            let connection = cursor
-                .remove()
-                .expect("list capacity was already checked in the `while` condition");
-
             // Note that while we iterate over the intrusive lists here
             // `Connection` is part of no list anymore, since it also got dropped
             // from list that is described by the `cursor`.
@@ -1110,9 +1405,9 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
 
     fn remove_node_by_id(&mut self, connection_id: InternalConnectionId) {
         // Remove the Connection from `connection_map`
-        let mut cursor = self.connection_map.find_mut(&connection_id);
-        let remove_result = cursor.remove();
+        let remove_result = self.connection_map.remove(connection_id);
         debug_assert!(remove_result.is_some());
+        self.registry.remove(connection_id);
 
         if let Some(connection) = remove_result {
             self.interest_lists.remove_node(&connection);
@@ -1130,11 +1425,11 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
 
     fn remove_node(&mut self, connection: &ConnectionNode<C, L>) {
         // Remove the Connection from `connection_map`
-        let mut cursor = self
+        let remove_result = self
             .connection_map
-            .find_mut(&connection.internal_connection_id);
-        let remove_result = cursor.remove();
+            .remove(connection.internal_connection_id);
         debug_assert!(remove_result.is_some());
+        self.registry.remove(connection.internal_connection_id);
 
         self.interest_lists.remove_node(connection);
     }
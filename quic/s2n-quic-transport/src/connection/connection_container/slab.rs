@@ -0,0 +1,140 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A slab of connections indexed by generational [`InternalConnectionId`]s
+//!
+//! Connections are stored in a contiguous vector and looked up directly by the index encoded in
+//! their ID. Vacant entries are reused for new connections, with the generation of the entry
+//! incremented to invalidate any stale IDs that may still refer to the previous occupant.
+
+use crate::connection::InternalConnectionId;
+use alloc::vec::Vec;
+
+#[derive(Debug)]
+struct Entry<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+#[derive(Debug)]
+pub struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    /// Indices of the vacant entries
+    vacant: Vec<u32>,
+    /// An ID which has been handed out by `reserve_id` but has not been inserted yet
+    reserved: Option<InternalConnectionId>,
+    /// The number of occupied entries
+    len: usize,
+}
+
+impl<T> Slab<T> {
+    /// Creates a slab with space preallocated for `capacity` entries
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            vacant: Vec::with_capacity(capacity),
+            reserved: None,
+            len: 0,
+        }
+    }
+
+    /// Reserves an ID for a connection which will be inserted into the slab
+    ///
+    /// Only a single reservation is outstanding at a time. If the previously reserved ID was never
+    /// inserted (e.g. the connection failed to be created), the entry is released and its
+    /// generation is incremented.
+    pub fn reserve_id(&mut self) -> InternalConnectionId {
+        if let Some(id) = self.reserved.take() {
+            self.release(id.index());
+        }
+
+        let index = if let Some(index) = self.vacant.pop() {
+            index
+        } else {
+            let index = self.entries.len();
+            let index = u32::try_from(index).expect("connection index overflow");
+            self.entries.push(Entry {
+                generation: 0,
+                value: None,
+            });
+            index
+        };
+
+        let entry = &self.entries[index as usize];
+        debug_assert!(entry.value.is_none());
+
+        let id = InternalConnectionId::new(index, entry.generation);
+        self.reserved = Some(id);
+        id
+    }
+
+    /// Inserts a value for a previously reserved ID
+    pub fn insert(&mut self, id: InternalConnectionId, value: T) {
+        debug_assert_eq!(
+            self.reserved,
+            Some(id),
+            "connections can only be inserted with the last reserved id"
+        );
+        self.reserved = None;
+
+        let entry = &mut self.entries[id.index() as usize];
+        debug_assert_eq!(entry.generation, id.generation());
+        debug_assert!(entry.value.is_none());
+        entry.value = Some(value);
+        self.len += 1;
+    }
+
+    /// Returns the value for the given ID, if it is still present
+    #[inline]
+    pub fn get(&self, id: InternalConnectionId) -> Option<&T> {
+        let entry = self.entries.get(id.index() as usize)?;
+
+        if entry.generation != id.generation() {
+            return None;
+        }
+
+        entry.value.as_ref()
+    }
+
+    /// Removes and returns the value for the given ID, if it is still present
+    pub fn remove(&mut self, id: InternalConnectionId) -> Option<T> {
+        let entry = self.entries.get_mut(id.index() as usize)?;
+
+        if entry.generation != id.generation() {
+            return None;
+        }
+
+        let value = entry.value.take()?;
+        self.len -= 1;
+        self.release(id.index());
+        Some(value)
+    }
+
+    /// Returns the number of values in the slab
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no values in the slab
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterates over all of the values in the slab in index order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter().filter_map(|entry| entry.value.as_ref())
+    }
+
+    fn release(&mut self, index: u32) {
+        let entry = &mut self.entries[index as usize];
+        debug_assert!(entry.value.is_none());
+        // invalidate any IDs that are still referring to this entry
+        entry.generation = entry.generation.wrapping_add(1);
+        self.vacant.push(index);
+    }
+}
+
+#[cfg(test)]
+mod tests;
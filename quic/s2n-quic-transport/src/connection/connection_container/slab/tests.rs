@@ -0,0 +1,98 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use alloc::collections::BTreeMap;
+use bolero::{check, generator::*};
+
+#[derive(Clone, Copy, Debug, TypeGenerator)]
+enum Operation {
+    /// Reserves an ID and optionally inserts a value for it
+    Reserve { insert: bool },
+    /// Removes the value at the given index of the active IDs
+    Remove(u16),
+    /// Removes a value that was previously removed
+    RemoveStale(u16),
+}
+
+#[test]
+fn model_test() {
+    check!().with_type::<Vec<Operation>>().for_each(|ops| {
+        let mut slab = Slab::with_capacity(4);
+        let mut model = BTreeMap::new();
+        let mut removed = vec![];
+        let mut next_value = 0u64;
+
+        for op in ops.iter().copied() {
+            match op {
+                Operation::Reserve { insert } => {
+                    let id = slab.reserve_id();
+                    assert!(slab.get(id).is_none());
+                    assert!(!model.contains_key(&id), "ids should be unique");
+                    assert!(!removed.contains(&id), "ids should be unique");
+
+                    if insert {
+                        slab.insert(id, next_value);
+                        model.insert(id, next_value);
+                        next_value += 1;
+                    }
+                }
+                Operation::Remove(index) => {
+                    if model.is_empty() {
+                        continue;
+                    }
+                    let index = index as usize % model.len();
+                    let id = *model.keys().nth(index).unwrap();
+                    let expected = model.remove(&id);
+                    assert_eq!(slab.remove(id), expected);
+                    removed.push(id);
+                }
+                Operation::RemoveStale(index) => {
+                    if removed.is_empty() {
+                        continue;
+                    }
+                    let id = removed[index as usize % removed.len()];
+                    assert!(slab.get(id).is_none());
+                    assert!(slab.remove(id).is_none());
+                }
+            }
+
+            assert_eq!(slab.len(), model.len());
+            assert_eq!(slab.is_empty(), model.is_empty());
+
+            for (id, value) in model.iter() {
+                assert_eq!(slab.get(*id), Some(value));
+            }
+
+            let mut values: Vec<_> = slab.iter().copied().collect();
+            values.sort_unstable();
+            let mut expected: Vec<_> = model.values().copied().collect();
+            expected.sort_unstable();
+            assert_eq!(values, expected);
+        }
+    });
+}
+
+#[test]
+fn reuse_test() {
+    let mut slab = Slab::with_capacity(1);
+
+    let first = slab.reserve_id();
+    slab.insert(first, 1);
+    assert_eq!(slab.remove(first), Some(1));
+
+    // the entry should be reused with a new generation
+    let second = slab.reserve_id();
+    assert_eq!(first.index(), second.index());
+    assert_ne!(first.generation(), second.generation());
+    slab.insert(second, 2);
+
+    assert!(slab.get(first).is_none());
+    assert_eq!(slab.get(second), Some(&2));
+
+    // an unused reservation should be released on the next reservation
+    let third = slab.reserve_id();
+    let fourth = slab.reserve_id();
+    assert_eq!(third.index(), fourth.index());
+    assert_ne!(third, fourth);
+}
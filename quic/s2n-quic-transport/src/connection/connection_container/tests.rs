@@ -5,8 +5,7 @@ use super::*;
 use crate::{
     connection::{
         self, connection_impl::AcceptState, connection_interests::ConnectionInterests,
-        internal_connection_id::InternalConnectionId, InternalConnectionIdGenerator,
-        ProcessingError, Trait,
+        internal_connection_id::InternalConnectionId, ProcessingError, Trait,
     },
     endpoint, path, stream,
 };
@@ -17,10 +16,13 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    application, event,
+    application,
+    connection::{close, handoff, PriorityClass},
+    endpoint::limits::{EvictionCriteria, DEFAULT_TIMER_GRANULARITY},
+    event,
     event::builder::DatagramDropReason,
     inet::{DatagramInfo, SocketAddress},
-    io::tx,
+    io::{rx::SocketError, tx},
     packet::{
         handshake::ProtectedHandshake,
         initial::{CleartextInitial, ProtectedInitial},
@@ -29,7 +31,7 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::mtu,
+    path::{mtu, RemoteAddress},
     query,
     time::{Timer, Timestamp},
 };
@@ -75,6 +77,26 @@ impl connection::Trait for TestConnection {
         self.accept_state == AcceptState::Active
     }
 
+    fn poll_handshake_attempt(&mut self) -> bool {
+        false
+    }
+
+    fn take_new_tokens(&mut self) -> Vec<Bytes> {
+        Vec::new()
+    }
+
+    fn activity(&self) -> connection::Activity {
+        connection::Activity::new(unsafe { Timestamp::from_duration(Duration::ZERO) })
+    }
+
+    fn on_evicted(
+        &mut self,
+        _criteria: EvictionCriteria,
+        _timestamp: Timestamp,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) {
+    }
+
     fn close(
         &mut self,
         _error: connection::Error,
@@ -99,6 +121,7 @@ impl connection::Trait for TestConnection {
         &mut self,
         _connection_id_format: &mut <Self::Config as endpoint::Config>::ConnectionIdFormat,
         _stateless_reset_token_generator: &mut <Self::Config as endpoint::Config>::StatelessResetTokenGenerator,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _timestamp: Timestamp,
     ) -> Result<(), connection::local_id_registry::LocalIdRegistrationError> {
         Ok(())
@@ -113,6 +136,15 @@ impl connection::Trait for TestConnection {
     ) -> Result<(), crate::contexts::ConnectionOnTransmitError> {
         Ok(())
     }
+    fn on_urgent_transmit<Tx: tx::Queue>(
+        &mut self,
+        _queue: &mut Tx,
+        _timestamp: Timestamp,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        _packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
+    ) -> Result<(), crate::contexts::ConnectionOnTransmitError> {
+        Ok(())
+    }
 
     fn on_timeout(
         &mut self,
@@ -128,6 +160,18 @@ impl connection::Trait for TestConnection {
         Ok(())
     }
 
+    fn on_imported(
+        &mut self,
+        _state: &handoff::State,
+        _tls_endpoint: &mut <Self::Config as endpoint::Config>::TLSEndpoint,
+        _timestamp: Timestamp,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        _datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
+        _dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn on_wakeup(
         &mut self,
         _timestamp: Timestamp,
@@ -246,6 +290,28 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn is_socket_error_target(
+        &self,
+        _remote_address: &RemoteAddress,
+        _destination_connection_id: &[u8],
+    ) -> bool {
+        false
+    }
+
+    fn on_socket_error(
+        &mut self,
+        _remote_address: &RemoteAddress,
+        _error: SocketError,
+        _destination_connection_id: &[u8],
+        _timestamp: Timestamp,
+        _congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        _mtu: &mut mtu::Manager<<Self::Config as endpoint::Config>::Mtu>,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> Result<(), connection::Error> {
+        Ok(())
+    }
+
     /// Returns the Connections interests
     fn interests(&self) -> ConnectionInterests {
         self.interests
@@ -276,16 +342,33 @@ impl connection::Trait for TestConnection {
     fn poll_open_stream(
         &mut self,
         _stream_type: stream::StreamType,
+        _id_hint: Option<stream::StreamId>,
         _token: &mut connection::OpenToken,
         _context: &Context,
     ) -> Poll<Result<stream::StreamId, connection::Error>> {
         todo!()
     }
 
-    fn application_close(&mut self, _error: Option<application::Error>) {
+    fn application_close(&mut self, _error: Option<application::Error>, _linger: close::Linger) {
         // no-op
     }
 
+    fn application_drop(&mut self) {
+        // no-op
+    }
+
+    fn set_drop_behavior(
+        &mut self,
+        _behavior: close::DropBehavior,
+    ) -> Result<(), connection::Error> {
+        Ok(())
+    }
+
+    fn set_priority_class(&mut self, class: PriorityClass) -> Result<(), connection::Error> {
+        self.interests.priority_class = class;
+        Ok(())
+    }
+
     fn server_name(&self) -> Option<ServerName> {
         todo!()
     }
@@ -294,14 +377,70 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn peer_transport_parameter(&self, _id: u64) -> Option<Bytes> {
+        todo!()
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn export_handoff(&mut self) -> Result<handoff::State, connection::Error> {
+        todo!()
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         todo!()
     }
 
+    fn poll_ping(&mut self, _context: &Context) -> Poll<Result<Duration, connection::Error>> {
+        todo!()
+    }
+
+    fn poll_established(&mut self, _context: &Context) -> Poll<Result<(), connection::Error>> {
+        todo!()
+    }
+
+    fn poll_flush(
+        &mut self,
+        _mode: connection::FlushMode,
+        _context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        todo!()
+    }
+
+    fn idle_timeout(&self) -> Result<Option<Duration>, connection::Error> {
+        Ok(None)
+    }
+
+    fn set_idle_timeout(&mut self, _timeout: Duration) -> Result<(), connection::Error> {
+        Ok(())
+    }
+
+    fn set_inactivity_timeout(
+        &mut self,
+        _timeout: Duration,
+        _error: application::Error,
+    ) -> Result<(), connection::Error> {
+        Ok(())
+    }
+
     fn keep_alive(&mut self, _enabled: bool) -> Result<(), connection::Error> {
         todo!()
     }
 
+    fn attach_event_subscriber(
+        &mut self,
+        _subscriber: Box<dyn event::AttachedSubscriber>,
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         todo!()
     }
@@ -310,6 +449,34 @@ impl connection::Trait for TestConnection {
         Ok(SocketAddress::default())
     }
 
+    fn max_datagram_size(&self) -> Result<usize, connection::Error> {
+        Ok(1200)
+    }
+
+    fn max_datagram_payload(&self) -> Result<usize, connection::Error> {
+        todo!()
+    }
+
+    fn poll_max_datagram_payload(
+        &mut self,
+        _previous: usize,
+        _context: &Context,
+    ) -> Poll<Result<usize, connection::Error>> {
+        todo!()
+    }
+
+    fn statistics(&self) -> Result<connection::Statistics, connection::Error> {
+        Ok(Default::default())
+    }
+
+    fn path_info(&self) -> Result<connection::PathInfo, connection::Error> {
+        Ok(Default::default())
+    }
+
+    fn path_statistics(&self) -> Result<Vec<connection::PathStatistics>, connection::Error> {
+        Ok(Default::default())
+    }
+
     fn error(&self) -> Option<connection::Error> {
         None
     }
@@ -395,8 +562,10 @@ enum Operation {
         closing: bool,
         accept: bool,
         transmission: bool,
+        urgent_transmission: bool,
         new_connection_id: bool,
         timeout: Option<u16>,
+        priority_class: u8,
     },
     CloseApp,
     HandshakeCompleted {
@@ -416,7 +585,6 @@ fn container_test() {
     use core::time::Duration;
 
     check!().with_type::<Vec<Operation>>().for_each(|ops| {
-        let mut id_gen = InternalConnectionIdGenerator::new();
         let mut connections = vec![];
         let (handle, acceptor, connector, _close_handle) = endpoint::handle::Handle::new(100);
         let (waker, _wake_count) = futures_test::task::new_count_waker();
@@ -424,12 +592,12 @@ fn container_test() {
 
         let mut handle = Some(handle);
         let mut container: ConnectionContainer<TestConnection, TestLock> =
-            ConnectionContainer::new(acceptor, connector);
+            ConnectionContainer::new(acceptor, connector, DEFAULT_TIMER_GRANULARITY, 0);
 
         for op in ops.iter() {
             match op {
                 Operation::Insert => {
-                    let id = id_gen.generate_id();
+                    let id = container.reserve_id();
                     let connection = TestConnection::default();
                     container.insert_connection(connection, id);
                     connections.push(id);
@@ -440,8 +608,10 @@ fn container_test() {
                     closing,
                     accept,
                     transmission,
+                    urgent_transmission,
                     new_connection_id,
                     timeout,
+                    priority_class,
                 } => {
                     if connections.is_empty() {
                         continue;
@@ -469,8 +639,11 @@ fn container_test() {
                             i.accept = *accept;
                         }
                         i.transmission = *transmission;
+                        i.urgent_transmission = *transmission && *urgent_transmission;
                         i.new_connection_id = *new_connection_id;
                         i.timeout = timeout.map(|ms| now + Duration::from_millis(ms as _));
+                        i.priority_class =
+                            PriorityClass::ALL[*priority_class as usize % PriorityClass::ALL.len()];
 
                         // we need to express at least one interest to ensure progress
                         if !(i.transmission || i.new_connection_id || i.timeout.is_some()) {
@@ -544,9 +717,32 @@ fn container_test() {
                 }
                 Operation::Transmit(count) => {
                     let mut count = *count;
+                    let mut is_interrupted = false;
+                    container.iterate_urgent_transmission_list(|conn| {
+                        assert!(conn.interests.transmission);
+                        assert!(conn.interests.urgent_transmission);
+
+                        if count == 0 {
+                            is_interrupted = true;
+                            ConnectionContainerIterationResult::BreakAndInsertAtFront
+                        } else {
+                            count -= 1;
+                            ConnectionContainerIterationResult::Continue
+                        }
+                    });
+
+                    if is_interrupted {
+                        continue;
+                    }
+
+                    let mut prev_class = PriorityClass::ALL[0];
                     container.iterate_transmission_list(|conn| {
                         assert!(conn.interests.transmission);
 
+                        // connections should be visited from the most to the least urgent class
+                        assert!(prev_class <= conn.interests.priority_class);
+                        prev_class = conn.interests.priority_class;
+
                         if count == 0 {
                             ConnectionContainerIterationResult::BreakAndInsertAtFront
                         } else {
@@ -578,7 +774,7 @@ fn container_test() {
                     let index = index % connections.len();
                     let id = connections[index];
 
-                    let node = container.connection_map.find(&id).get().unwrap();
+                    let node = container.connection_map.get(id).unwrap();
                     node.inner.poison();
 
                     let mut was_called = false;
@@ -593,14 +789,14 @@ fn container_test() {
 
         container.finalize_done_connections();
 
-        let mut connections = connections.drain(..);
-        let mut cursor = container.connection_map.front();
-
-        while let Some(conn) = cursor.get() {
-            assert_eq!(Some(conn.internal_connection_id), connections.next());
-            cursor.move_next();
-        }
-
-        assert!(connections.next().is_none());
+        // connections are stored by slot rather than by insertion order
+        let mut stored: Vec<_> = container
+            .connection_map
+            .iter()
+            .map(|conn| conn.internal_connection_id)
+            .collect();
+        stored.sort();
+        connections.sort();
+        assert_eq!(stored, connections);
     });
 }
@@ -0,0 +1,285 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A hierarchical timer wheel for tracking connection timeouts
+//!
+//! Timestamps are quantized into ticks of a configurable granularity. The wheel is made up of
+//! [`LEVELS`] levels, each containing [`SLOTS`] slots. Each slot in level `N` covers `SLOTS^N`
+//! ticks. Entries are placed in the lowest level which can represent the distance between the
+//! current tick and the entry's deadline and are cascaded down to lower levels as time advances.
+//!
+//! Inserting and removing entries is `O(1)`, which avoids the rebalancing costs of an ordered tree
+//! for endpoints with a large number of mostly-idle connections.
+
+use alloc::vec::Vec;
+use core::{cell::Cell, time::Duration};
+use intrusive_collections::{linked_list::LinkedListOps, Adapter, LinkedList, PointerOps};
+use s2n_quic_core::{recovery::K_GRANULARITY, time::Timestamp};
+
+/// The number of bits used to index a slot in a level
+const SLOT_BITS: u32 = 6;
+/// The number of slots in each level
+const SLOTS: usize = 1 << SLOT_BITS;
+/// The number of levels in the wheel
+const LEVELS: usize = 6;
+/// The maximum number of ticks an entry can be scheduled in the future
+const MAX_TICKS: u64 = (1 << (SLOT_BITS as usize * LEVELS)) - 1;
+/// The slot value for entries which have expired and are waiting to be returned
+const PENDING_SLOT: usize = SLOTS * LEVELS;
+
+/// An entry that can be stored in the [`TimerWheel`]
+pub trait Entry {
+    /// Returns the time at which the entry expires
+    fn expiration(&self) -> Timestamp;
+
+    /// Returns the cell used to track which slot the entry is stored in
+    fn wheel_slot(&self) -> &Cell<usize>;
+}
+
+type Value<A> = <<A as Adapter>::PointerOps as PointerOps>::Value;
+type Pointer<A> = <<A as Adapter>::PointerOps as PointerOps>::Pointer;
+
+pub struct TimerWheel<A>
+where
+    A: Adapter,
+    A::LinkOps: LinkedListOps,
+{
+    /// All of the slots across each level
+    slots: Vec<LinkedList<A>>,
+    /// Bitsets of non-empty slots for each level
+    occupied: [u64; LEVELS],
+    /// Entries which have expired and are waiting to be returned from `pop_expired`
+    pending: LinkedList<A>,
+    /// The adapter used to convert entry pointers
+    adapter: A,
+    /// The tick up to which all entries have been processed
+    elapsed: u64,
+    /// The duration of each tick in microseconds
+    granularity: u64,
+}
+
+impl<A> TimerWheel<A>
+where
+    A: Adapter + Clone + Default,
+    A::LinkOps: LinkedListOps,
+    Value<A>: Entry,
+{
+    pub fn new(granularity: Duration) -> Self {
+        // ticks can't be any more precise than the resolution of `Timestamp`
+        let granularity = (granularity.as_micros() as u64).max(1);
+
+        Self {
+            slots: (0..SLOTS * LEVELS)
+                .map(|_| LinkedList::new(A::default()))
+                .collect(),
+            occupied: [0; LEVELS],
+            pending: LinkedList::new(A::default()),
+            adapter: A::default(),
+            elapsed: 0,
+            granularity,
+        }
+    }
+
+    /// Returns `true` if there are no entries in the wheel
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.occupied.iter().all(|level| *level == 0)
+    }
+
+    /// Inserts an entry into the wheel
+    ///
+    /// The entry will be returned from `pop_expired` once its expiration would be considered
+    /// elapsed, within one tick of granularity.
+    pub fn insert(&mut self, entry: Pointer<A>) {
+        let pointer_ops = self.adapter.pointer_ops();
+        let raw = pointer_ops.into_raw(entry);
+        let (expiration, entry) = unsafe {
+            // Safety: the raw pointer was just created from a valid entry
+            ((*raw).expiration(), pointer_ops.from_raw(raw))
+        };
+        // entries at or before the current tick are scheduled for the next tick
+        let when = self.deadline_tick(expiration).max(self.elapsed + 1);
+        self.insert_at(entry, when);
+    }
+
+    /// Removes an entry from the wheel
+    ///
+    /// # Safety
+    ///
+    /// The entry must currently be stored in this wheel
+    pub unsafe fn remove(&mut self, entry: &Value<A>) {
+        let slot = entry.wheel_slot().get();
+        let ptr = entry as *const Value<A>;
+
+        if slot == PENDING_SLOT {
+            let removed = self.pending.cursor_mut_from_ptr(ptr).remove();
+            debug_assert!(removed.is_some());
+            return;
+        }
+
+        let list = &mut self.slots[slot];
+        let removed = list.cursor_mut_from_ptr(ptr).remove();
+        debug_assert!(removed.is_some());
+
+        if list.is_empty() {
+            self.occupied[slot / SLOTS] &= !(1 << (slot % SLOTS));
+        }
+    }
+
+    /// Returns the time at which the next entry in the wheel will expire
+    ///
+    /// Note that this may return a time before the expiration of the earliest entry, in which case
+    /// the wheel will cascade entries down to lower levels when polled.
+    pub fn next_expiration(&self) -> Option<Timestamp> {
+        let tick = if self.pending.is_empty() {
+            self.next_expiration_slot()?.1
+        } else {
+            self.elapsed
+        };
+
+        Some(self.tick_to_timestamp(tick))
+    }
+
+    /// Removes and returns the next entry which has expired at the current time
+    pub fn pop_expired(&mut self, now: Timestamp) -> Option<Pointer<A>> {
+        let now = self.now_tick(now);
+
+        loop {
+            if let Some(entry) = self.pending.pop_front() {
+                return Some(entry);
+            }
+
+            let (slot, deadline) = match self.next_expiration_slot() {
+                Some((slot, deadline)) if deadline <= now => (slot, deadline),
+                _ => {
+                    // nothing else is ready so advance to the current time
+                    self.elapsed = self.elapsed.max(now);
+                    return None;
+                }
+            };
+
+            self.elapsed = deadline;
+
+            let mut list = self.slots[slot].take();
+            self.occupied[slot / SLOTS] &= !(1 << (slot % SLOTS));
+
+            if slot < SLOTS {
+                // entries in the first level have expired
+                for entry in list.iter() {
+                    entry.wheel_slot().set(PENDING_SLOT);
+                }
+                self.pending = list;
+            } else {
+                // cascade entries in higher levels down to the lower levels
+                while let Some(expiration) = list.front().get().map(|entry| entry.expiration()) {
+                    let entry = list.pop_front().unwrap();
+                    // entries can't expire before the start of the slot they were stored in
+                    let when = self.deadline_tick(expiration).max(self.elapsed);
+                    self.insert_at(entry, when);
+                }
+            }
+        }
+    }
+
+    /// Converts a timestamp into the tick at which it should be considered elapsed
+    ///
+    /// Timestamps that are less than [`K_GRANULARITY`] in the future are considered elapsed (see
+    /// [`Timestamp::has_elapsed`]), so the deadline is shifted back by that amount and rounded up
+    /// to the next tick.
+    #[inline]
+    fn deadline_tick(&self, expiration: Timestamp) -> u64 {
+        let micros = unsafe {
+            // Safety: the timestamp is only converted back with the same epoch
+            expiration.as_duration().as_micros() as u64
+        };
+        let micros = micros.saturating_sub(K_GRANULARITY.as_micros() as u64);
+        micros / self.granularity + 1
+    }
+
+    #[inline]
+    fn now_tick(&self, now: Timestamp) -> u64 {
+        let micros = unsafe {
+            // Safety: the timestamp is only converted back with the same epoch
+            now.as_duration().as_micros() as u64
+        };
+        micros / self.granularity
+    }
+
+    #[inline]
+    fn tick_to_timestamp(&self, tick: u64) -> Timestamp {
+        let micros = tick.saturating_mul(self.granularity);
+        unsafe {
+            // Safety: the tick was derived from timestamps with the same epoch
+            Timestamp::from_duration(Duration::from_micros(micros))
+        }
+    }
+
+    fn insert_at(&mut self, entry: Pointer<A>, when: u64) {
+        debug_assert!(when >= self.elapsed);
+        // entries too far in the future are clamped to the maximum range of the wheel
+        let when = when.min(self.elapsed + MAX_TICKS);
+
+        let level = level_for(self.elapsed, when);
+        let slot = level * SLOTS + slot_for(level, when);
+
+        let list = &mut self.slots[slot];
+        list.push_back(entry);
+        if let Some(entry) = list.back().get() {
+            entry.wheel_slot().set(slot);
+        }
+        self.occupied[level] |= 1 << (slot % SLOTS);
+    }
+
+    /// Returns the earliest occupied slot and the tick at which it expires
+    fn next_expiration_slot(&self) -> Option<(usize, u64)> {
+        for level in 0..LEVELS {
+            let occupied = self.occupied[level];
+            if occupied == 0 {
+                continue;
+            }
+
+            let slot_range = slot_range(level);
+            let level_range = slot_range * SLOTS as u64;
+            let current = slot_for(level, self.elapsed) as u32;
+
+            // find the next occupied slot, starting at the current slot
+            let offset = occupied.rotate_right(current).trailing_zeros();
+            let slot = ((current + offset) as usize) % SLOTS;
+
+            let level_start = self.elapsed & !(level_range - 1);
+            let mut deadline = level_start + slot as u64 * slot_range;
+
+            if deadline <= self.elapsed && level > 0 {
+                // the slot has wrapped around into the next rotation of the level
+                deadline += level_range;
+            }
+
+            return Some((level * SLOTS + slot, deadline));
+        }
+
+        None
+    }
+}
+
+/// Returns the number of ticks covered by a single slot in the given level
+#[inline]
+fn slot_range(level: usize) -> u64 {
+    1 << (SLOT_BITS as usize * level)
+}
+
+#[inline]
+fn slot_for(level: usize, tick: u64) -> usize {
+    ((tick >> (SLOT_BITS as usize * level)) as usize) & (SLOTS - 1)
+}
+
+/// Returns the level for a deadline based on the highest bit that differs from the current tick
+#[inline]
+fn level_for(elapsed: u64, when: u64) -> usize {
+    let masked = (elapsed ^ when) | (SLOTS as u64 - 1);
+    let masked = masked.min(MAX_TICKS);
+    let significant = 63 - masked.leading_zeros() as usize;
+    significant / SLOT_BITS as usize
+}
+
+#[cfg(test)]
+mod tests;
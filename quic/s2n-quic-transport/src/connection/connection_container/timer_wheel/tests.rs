@@ -0,0 +1,158 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use alloc::rc::Rc;
+use bolero::{check, generator::*};
+use intrusive_collections::{intrusive_adapter, LinkedListLink};
+
+struct Node {
+    id: usize,
+    expiration: Timestamp,
+    slot: Cell<usize>,
+    link: LinkedListLink,
+}
+
+impl Entry for Node {
+    fn expiration(&self) -> Timestamp {
+        self.expiration
+    }
+
+    fn wheel_slot(&self) -> &Cell<usize> {
+        &self.slot
+    }
+}
+
+intrusive_adapter!(NodeAdapter = Rc<Node>: Node { link: LinkedListLink });
+
+#[derive(Clone, Copy, Debug, TypeGenerator)]
+enum Operation {
+    /// Inserts an entry that expires in the given number of microseconds
+    Insert(u32),
+    /// Removes the entry at the given index
+    Remove(u16),
+    /// Advances the clock by the given number of microseconds
+    Advance(u32),
+}
+
+fn timestamp(micros: u64) -> Timestamp {
+    unsafe { Timestamp::from_duration(Duration::from_micros(micros)) }
+}
+
+struct Model {
+    granularity: u64,
+    elapsed: u64,
+    /// Active entries with the tick at which they're expected to expire
+    entries: Vec<(Rc<Node>, u64)>,
+}
+
+impl Model {
+    fn due_tick(&self, micros: u64) -> u64 {
+        let tick = micros.saturating_sub(K_GRANULARITY.as_micros() as u64) / self.granularity + 1;
+        tick.max(self.elapsed + 1)
+    }
+}
+
+fn check_wheel(granularity: u64, ops: &[Operation]) {
+    let mut wheel = TimerWheel::<NodeAdapter>::new(Duration::from_micros(granularity));
+    let mut model = Model {
+        granularity,
+        elapsed: 0,
+        entries: vec![],
+    };
+    let mut now = 1u64;
+    let mut next_id = 0;
+
+    for op in ops.iter().copied() {
+        match op {
+            Operation::Insert(offset) => {
+                let expiration = now + offset as u64;
+                let node = Rc::new(Node {
+                    id: next_id,
+                    expiration: timestamp(expiration),
+                    slot: Cell::new(0),
+                    link: LinkedListLink::new(),
+                });
+                next_id += 1;
+                let tick = model.due_tick(expiration);
+                model.entries.push((node.clone(), tick));
+                wheel.insert(node);
+            }
+            Operation::Remove(index) => {
+                if model.entries.is_empty() {
+                    continue;
+                }
+                let index = index as usize % model.entries.len();
+                let (node, _) = model.entries.swap_remove(index);
+                unsafe { wheel.remove(&node) };
+                assert!(!node.link.is_linked());
+            }
+            Operation::Advance(amount) => {
+                now += amount as u64;
+                let now_tick = now / granularity;
+                let now_ts = timestamp(now);
+
+                if let Some(next) = wheel.next_expiration() {
+                    let earliest = model.entries.iter().map(|(_, tick)| *tick).min().unwrap();
+                    assert!(next <= timestamp(earliest * granularity));
+                } else {
+                    assert!(model.entries.is_empty());
+                }
+
+                let mut expired = vec![];
+                while let Some(node) = wheel.pop_expired(now_ts) {
+                    assert!(node.expiration.has_elapsed(now_ts));
+                    expired.push(node.id);
+                }
+                expired.sort_unstable();
+
+                let mut expected = vec![];
+                model.entries.retain(|(node, tick)| {
+                    if *tick <= now_tick {
+                        expected.push(node.id);
+                        false
+                    } else {
+                        true
+                    }
+                });
+                expected.sort_unstable();
+
+                assert_eq!(expired, expected);
+                model.elapsed = model.elapsed.max(now_tick);
+            }
+        }
+    }
+
+    assert_eq!(wheel.is_empty(), model.entries.is_empty());
+
+    // drain the remaining entries so they're unlinked before being dropped
+    for (node, _) in model.entries.drain(..) {
+        unsafe { wheel.remove(&node) };
+    }
+    assert!(wheel.is_empty());
+}
+
+#[test]
+fn model_test() {
+    check!()
+        .with_type::<(u8, Vec<Operation>)>()
+        .for_each(|(granularity, ops)| {
+            let granularity = (*granularity as u64 + 1) * 10;
+            check_wheel(granularity, ops)
+        });
+}
+
+#[test]
+fn long_timeout_test() {
+    let ops = [
+        Operation::Insert(u32::MAX),
+        Operation::Insert(30_000_000),
+        Operation::Advance(29_999_000),
+        Operation::Advance(1_000),
+        Operation::Advance(u32::MAX),
+    ];
+
+    for granularity in [1, 250, 1_000] {
+        check_wheel(granularity, &ops);
+    }
+}
@@ -4,7 +4,10 @@
 //! Maps from external connection IDs to internal connection IDs
 
 use crate::connection::{local_id_registry::LocalIdRegistry, InternalConnectionId, PeerIdRegistry};
-use core::{convert::TryFrom as _, hash::BuildHasher};
+use core::{
+    convert::TryFrom as _,
+    hash::{BuildHasher, Hasher},
+};
 use hashbrown::hash_map::{Entry, HashMap};
 use s2n_quic_core::{connection, endpoint, random, stateless_reset, time::Timestamp};
 use siphasher::sip::SipHasher13;
@@ -50,6 +53,82 @@ impl BuildHasher for HashState {
     }
 }
 
+/// The hash state for the local connection ID map
+#[derive(Debug)]
+pub enum LocalIdHashState {
+    /// Hashes the IDs with SipHash13 using random keys
+    Keyed(HashState),
+    /// Uses the ID bytes as the hash value
+    ///
+    /// Combined with the open-addressing map, which stores a tag derived from the top bits of
+    /// each hash, this turns a lookup into a probe of the tag table without hashing the ID.
+    ///
+    /// This is only used when the local connection ID format generates uniformly random IDs. The
+    /// map only contains locally-generated IDs, so a peer is unable to insert values which
+    /// result in poor bucketing.
+    Random,
+}
+
+impl BuildHasher for LocalIdHashState {
+    type Hasher = LocalIdHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        match self {
+            Self::Keyed(state) => LocalIdHasher::Keyed(state.build_hasher()),
+            Self::Random => LocalIdHasher::Random(0),
+        }
+    }
+}
+
+/// A hasher for local connection IDs, built by the [`LocalIdHashState`]
+pub enum LocalIdHasher {
+    Keyed(SipHasher13),
+    Random(u64),
+}
+
+impl Hasher for LocalIdHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Keyed(hasher) => hasher.write(bytes),
+            Self::Random(hash) => {
+                // The IDs are uniformly random so the leading bytes are already well-distributed
+                const LEN: usize = core::mem::size_of::<u64>();
+                let mut value = [0u8; LEN];
+                if bytes.len() >= LEN {
+                    value.copy_from_slice(&bytes[..LEN]);
+                } else if !bytes.is_empty() {
+                    // The map derives the bucket from the low bits of the hash and the control
+                    // tag from the high bits, so IDs shorter than the hash are repeated to fill
+                    // all of them. Otherwise, every short ID would share the same tag.
+                    for (dest, src) in value.iter_mut().zip(bytes.iter().cycle()) {
+                        *dest = *src;
+                    }
+                }
+                *hash ^= u64::from_ne_bytes(value);
+            }
+        }
+    }
+
+    #[inline]
+    fn write_usize(&mut self, value: usize) {
+        match self {
+            Self::Keyed(hasher) => hasher.write_usize(value),
+            // mix in the length prefix so IDs of different lengths don't trivially collide
+            Self::Random(hash) => *hash = hash.rotate_left(5) ^ value as u64,
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        match self {
+            Self::Keyed(hasher) => hasher.finish(),
+            Self::Random(hash) => *hash,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct StatelessResetMap {
     /// Maps from a hash of peer stateless reset token to internal connection IDs
@@ -87,12 +166,12 @@ impl StatelessResetMap {
 #[derive(Debug)]
 pub(crate) struct LocalIdMap {
     /// Maps from external to internal connection IDs
-    map: HashMap<connection::LocalId, InternalConnectionId, HashState>,
+    map: HashMap<connection::LocalId, InternalConnectionId, LocalIdHashState>,
 }
 
 impl LocalIdMap {
     /// Constructs a new `LocalIdMap`
-    fn new(hash_state: HashState) -> Self {
+    fn new(hash_state: LocalIdHashState) -> Self {
         Self {
             map: HashMap::with_hasher(hash_state),
         }
@@ -218,9 +297,15 @@ pub(crate) struct ConnectionIdMapperState {
 }
 
 impl ConnectionIdMapperState {
-    fn new(random_generator: &mut dyn random::Generator) -> Self {
+    fn new(random_generator: &mut dyn random::Generator, random_local_ids: bool) -> Self {
+        let local_id_hash_state = if random_local_ids {
+            LocalIdHashState::Random
+        } else {
+            LocalIdHashState::Keyed(HashState::new(random_generator))
+        };
+
         Self {
-            local_id_map: LocalIdMap::new(HashState::new(random_generator)),
+            local_id_map: LocalIdMap::new(local_id_hash_state),
             stateless_reset_map: StatelessResetMap::new(HashState::new(random_generator)),
             initial_id_map: InitialIdMap::new(
                 HashState::new(random_generator),
@@ -241,12 +326,19 @@ pub struct ConnectionIdMapper {
 
 impl ConnectionIdMapper {
     /// Creates a new `ConnectionIdMapper`
+    ///
+    /// If `random_local_ids` is set, the local connection ID format generates uniformly random
+    /// IDs and their lookups can skip hashing the ID bytes.
     pub fn new(
         random_generator: &mut dyn random::Generator,
         endpoint_type: endpoint::Type,
+        random_local_ids: bool,
     ) -> Self {
         Self {
-            state: Arc::new(Mutex::new(ConnectionIdMapperState::new(random_generator))),
+            state: Arc::new(Mutex::new(ConnectionIdMapperState::new(
+                random_generator,
+                random_local_ids,
+            ))),
             endpoint_type,
         }
     }
@@ -444,7 +536,8 @@ mod tests {
     #[test]
     fn remove_internal_connection_id_by_stateless_reset_token_test() {
         let mut random_generator = random::testing::Generator(123);
-        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+        let mut mapper =
+            ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
         let internal_id = InternalConnectionIdGenerator::new().generate_id();
         let peer_id = id(b"id01");
 
@@ -485,7 +578,8 @@ mod tests {
     #[test]
     fn initial_id_map() {
         let mut random_generator = random::testing::Generator(123);
-        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+        let mut mapper =
+            ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
         let internal_id = InternalConnectionIdGenerator::new().generate_id();
         let local_id = connection::LocalId::try_from_bytes(b"id000001").unwrap();
         let initial_id = connection::InitialId::try_from(local_id).unwrap();
@@ -513,7 +607,8 @@ mod tests {
     #[should_panic]
     fn initial_id_map_client_insert() {
         let mut random_generator = random::testing::Generator(123);
-        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client);
+        let mut mapper =
+            ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client, false);
         let internal_id = InternalConnectionIdGenerator::new().generate_id();
         let local_id = connection::LocalId::try_from_bytes(b"id000001").unwrap();
         let initial_id = connection::InitialId::try_from(local_id).unwrap();
@@ -527,7 +622,8 @@ mod tests {
     #[should_panic]
     fn initial_id_map_client_remove() {
         let mut random_generator = random::testing::Generator(123);
-        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client);
+        let mut mapper =
+            ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client, false);
         let internal_id = InternalConnectionIdGenerator::new().generate_id();
 
         mapper.remove_initial_id(&internal_id);
@@ -536,9 +632,73 @@ mod tests {
     #[test]
     fn initial_id_map_client_lookup() {
         let mut random_generator = random::testing::Generator(123);
-        let mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client);
+        let mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client, false);
         let local_id = connection::LocalId::try_from_bytes(b"id000001").unwrap();
 
         assert_eq!(None, mapper.lookup_internal_connection_id(&local_id));
     }
+
+    #[test]
+    fn random_local_id_map() {
+        let mut map = LocalIdMap::new(LocalIdHashState::Random);
+        let mut generator = InternalConnectionIdGenerator::new();
+
+        let ids = [
+            &b"id01"[..],
+            &b"id000001"[..],
+            &b"id0000001"[..],
+            &b"id000001id000001"[..],
+        ]
+        .map(|bytes| {
+            let local_id = connection::LocalId::try_from_bytes(bytes).unwrap();
+            let internal_id = generator.generate_id();
+            assert!(map.try_insert(&local_id, internal_id).is_ok());
+            assert!(map.try_insert(&local_id, internal_id).is_err());
+            (local_id, internal_id)
+        });
+
+        for (local_id, internal_id) in ids {
+            assert_eq!(Some(internal_id), map.get(&local_id));
+        }
+
+        let missing = connection::LocalId::try_from_bytes(b"id000002").unwrap();
+        assert_eq!(None, map.get(&missing));
+
+        let (local_id, internal_id) = ids[1];
+        assert_eq!(Some(internal_id), map.remove(&local_id));
+        assert_eq!(None, map.get(&local_id));
+    }
+
+    #[test]
+    fn random_short_local_id_hash() {
+        use std::collections::HashSet;
+
+        let mut random_generator = random::testing::Generator(123);
+        let mut map = LocalIdMap::new(LocalIdHashState::Random);
+        let mut generator = InternalConnectionIdGenerator::new();
+        let mut tags = HashSet::new();
+        let mut ids = vec![];
+
+        // use a 4 byte ID format, which is the shortest supported length
+        for _ in 0..1000 {
+            let mut bytes = [0u8; 4];
+            random::Generator::public_random_fill(&mut random_generator, &mut bytes);
+            let local_id = connection::LocalId::try_from_bytes(&bytes).unwrap();
+
+            // the map uses the top 7 bits of the hash as the control tag for each entry
+            tags.insert(map.map.hasher().hash_one(local_id) >> 57);
+
+            let internal_id = generator.generate_id();
+            if map.try_insert(&local_id, internal_id).is_ok() {
+                ids.push((local_id, internal_id));
+            }
+        }
+
+        // the random bytes should be spread across all of the tags
+        assert_eq!(tags.len(), 128);
+
+        for (local_id, internal_id) in ids {
+            assert_eq!(Some(internal_id), map.get(&local_id));
+        }
+    }
 }
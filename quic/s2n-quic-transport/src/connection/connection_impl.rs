@@ -35,17 +35,24 @@ use core::{
 use s2n_quic_core::{
     application,
     application::ServerName,
-    connection::{error::Error, id::Generator as _, InitialId, PeerId},
-    crypto::{tls, CryptoSuite},
+    connection::{
+        close, error::Error, handoff, id::Generator as _, InitialId, PeerId, PriorityClass,
+    },
+    crypto::{tls, tls::Endpoint as _, CryptoSuite},
+    custom_frame::{self, Endpoint as _, Handler as _},
     datagram::{Receiver, Sender},
+    endpoint::limits::EvictionCriteria,
     event::{
         self,
-        builder::{DatagramDropReason, MtuUpdatedCause, RxStreamProgress, TxStreamProgress},
+        builder::{
+            DatagramDropReason, MtuUpdatedCause, RxStreamProgress, TransmitShare, TxStreamProgress,
+        },
         supervisor, ConnectionPublisher as _, IntoEvent as _, Subscriber,
     },
     inet::{DatagramInfo, SocketAddress},
-    io::tx,
+    io::{rx::SocketError, tx, tx::Queue as _},
     packet::{
+        self,
         handshake::ProtectedHandshake,
         initial::{CleartextInitial, ProtectedInitial},
         number::PacketNumberSpace,
@@ -54,7 +61,7 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::{mtu, Handle as _},
+    path::{mtu, Handle as _, RemoteAddress},
     query,
     recovery::CongestionController,
     stateless_reset::token::Generator as _,
@@ -71,6 +78,10 @@ pub(crate) enum AcceptState {
     Handshaking,
     /// The connection has completed the handshake but hasn't been handed over
     /// to the application yet.
+    ///
+    /// Servers which allow 0.5-RTT data enter this state as soon as the 1-RTT
+    /// keys are available, and servers which accept connections early as soon
+    /// as the client's first flight is processed.
     HandshakeCompleted,
     /// The connection has been handed over to the application and can be
     /// actively utilized from there.
@@ -161,16 +172,38 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     timers: ConnectionTimers,
     /// Describes whether the connection is known to be accepted by the application
     accept_state: AcceptState,
+    /// Set once the handshake attempt has been reported to the endpoint limiter
+    handshake_attempt_reported: bool,
+    /// Notified once the connection is established or closes
+    ///
+    /// This is only used by connections which were accepted early.
+    established_waker: Option<Waker>,
     /// The current state of the connection
     state: ConnectionState,
     /// Manage the paths that the connection could use
     path_manager: path::Manager<Config>,
     /// The limits applied to the current connection
     limits: Limits,
+    /// The remaining transmission credits for the connection's share of the TX queue
+    transmit_deficit: transmission::deficit::Deficit,
+    /// Tracks the activity of the connection for eviction decisions
+    activity: connection::Activity,
+    /// The idle timeout requested by the application
+    ///
+    /// The effective idle timeout is the lower of this and the negotiated value.
+    local_idle_timeout: Option<Duration>,
+    /// Closes the connection if the application's streams stop making progress
+    inactivity: connection::Inactivity,
     /// The error set on the connection
     ///
     /// This is stored so future calls from the application return the same error
     error: Result<(), connection::Error>,
+    /// How long the connection retains its state after being closed by the application
+    close_linger: close::Linger,
+    /// What happens to the connection once the application drops its last handle
+    drop_behavior: close::DropBehavior,
+    /// The class the connection is scheduled in on the endpoint
+    priority_class: PriorityClass,
     /// Sends CONNECTION_CLOSE close frames after the connection is closed
     close_sender: CloseSender,
     /// Manages all of the different packet spaces and their respective components
@@ -191,6 +224,9 @@ struct EventContext<Config: endpoint::Config> {
 
     /// Holds the event context associated with the connection
     context: <Config::EventSubscriber as event::Subscriber>::ConnectionContext,
+
+    /// The subscriber attached to the connection, if any
+    attached: Option<Box<dyn event::AttachedSubscriber>>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for EventContext<Config> {
@@ -219,6 +255,7 @@ impl<Config: endpoint::Config> EventContext<Config> {
             subscriber,
             &mut self.context,
         )
+        .with_attached(self.attached.as_deref_mut())
     }
 }
 
@@ -258,7 +295,11 @@ macro_rules! transmission_context {
             outcome: $outcome,
             ecn,
             min_packet_len: None,
+            initial_padding: $self.limits.initial_padding(),
+            min_datagram_size: $self.limits.min_datagram_size() as usize,
             transmission_mode: $transmission_mode,
+            urgent_only: false,
+            defer_header_protection: false,
             publisher: &mut $self.event_context.publisher($timestamp, $subscriber),
             packet_interceptor: $packet_interceptor,
         }
@@ -287,7 +328,44 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             dc,
         ) {
             Poll::Ready(res) => res?,
-            Poll::Pending => return Ok(()),
+            Poll::Pending => {
+                // Servers can optionally hand over the connection once the 1-RTT keys are
+                // available so the application can send 0.5-RTT data
+                if Config::ENDPOINT_TYPE.is_server()
+                    && self.limits.half_rtt_data()
+                    && self.accept_state == AcceptState::Handshaking
+                    && space_manager.application().is_some()
+                {
+                    self.accept_state = AcceptState::HandshakeCompleted;
+                }
+
+                // Servers can also hand over the connection as soon as the ClientHello has been
+                // processed, so the server name and application protocol are known
+                if Config::ENDPOINT_TYPE.is_server()
+                    && self.limits.early_accept()
+                    && self.accept_state == AcceptState::Handshaking
+                    && space_manager.handshake().is_some()
+                {
+                    self.accept_state = AcceptState::HandshakeCompleted;
+                }
+
+                if self.is_established() {
+                    if let Some(waker) = self.established_waker.take() {
+                        waker.wake();
+                    }
+                }
+
+                return Ok(());
+            }
+        }
+
+        // TLS providers which complete the handshake asynchronously may confirm it outside of
+        // packet processing, so the handshake keys need to be discarded here as well
+        if space_manager.handshake().is_some() && space_manager.is_handshake_confirmed() {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.2
+            //# An endpoint MUST discard its handshake keys when the TLS handshake is
+            //# confirmed (Section 4.1.2).
+            space_manager.discard_handshake(&mut self.path_manager, &mut publisher);
         }
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.1
@@ -316,11 +394,18 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             && space_manager.is_handshake_complete()
         {
             // Move into the HandshakeCompleted state. This will signal the
-            // necessary interest to hand over the connection to the application.
-            self.accept_state = AcceptState::HandshakeCompleted;
+            // necessary interest to hand over the connection to the application,
+            // unless it was already handed over for 0.5-RTT data.
+            if self.accept_state == AcceptState::Handshaking {
+                self.accept_state = AcceptState::HandshakeCompleted;
+            }
             // Move the connection into the active state.
             self.state = ConnectionState::Active;
 
+            if let Some(waker) = self.established_waker.take() {
+                waker.wake();
+            }
+
             // Cancel the max handshake duration timer as the handshake has completed in time
             self.timers.max_handshake_duration_timer.cancel();
 
@@ -337,6 +422,36 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         Ok(())
     }
 
+    /// Returns true if the application can use the connection
+    fn is_established(&self) -> bool {
+        if self.space_manager.is_handshake_complete() {
+            return true;
+        }
+
+        // servers allowing 0.5-RTT data can use the connection as soon as the 1-RTT keys are
+        // available
+        Config::ENDPOINT_TYPE.is_server()
+            && self.limits.half_rtt_data()
+            && self.space_manager.application().is_some()
+    }
+
+    /// Returns the idle timeout negotiated with the peer, lowered by the application if requested
+    fn effective_idle_timeout(&self) -> Option<Duration> {
+        match (self.limits.max_idle_timeout(), self.local_idle_timeout) {
+            (Some(negotiated), Some(local)) => Some(negotiated.min(local)),
+            (negotiated, local) => negotiated.or(local),
+        }
+    }
+
+    /// Returns true if the application lowered the idle timeout below the negotiated value
+    fn is_idle_timeout_lowered(&self) -> bool {
+        self.local_idle_timeout.map_or(false, |local| {
+            self.limits
+                .max_idle_timeout()
+                .map_or(true, |negotiated| local < negotiated)
+        })
+    }
+
     /// Returns the idle timeout based on transport parameters of both peers
     fn get_idle_timer_duration(&self) -> Option<Duration> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
@@ -347,7 +462,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         //# commits to initiating an immediate close (Section 10.2) if it
         //# abandons the connection prior to the effective value.
 
-        let mut duration = self.limits.max_idle_timeout()?.as_millis() as u64;
+        let mut duration = self.effective_idle_timeout()?.as_millis() as u64;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
         //# To avoid excessively small idle timeout periods, endpoints MUST
@@ -380,6 +495,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             .publisher(packet.datagram.timestamp, subscriber);
 
         if packet.bytes_progressed > 0 {
+            self.inactivity.on_progress(packet.datagram.timestamp);
             publisher.on_rx_stream_progress(RxStreamProgress {
                 bytes: packet.bytes_progressed,
             })
@@ -393,6 +509,15 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                 .discard_handshake(&mut self.path_manager, &mut publisher);
         }
 
+        if self.space_manager.is_handshake_confirmed() {
+            self.space_manager
+                .handshake_timing
+                .on_handshake_done(packet.datagram.timestamp, &mut publisher);
+        }
+
+        // acknowledged MTU probes and path migrations can change the datagram payload
+        self.on_max_datagram_payload_update();
+
         // check to see if we're flushing and should now close the connection
         if self.poll_flush().is_ready() {
             self.error?;
@@ -401,6 +526,27 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         Ok(())
     }
 
+    /// Returns the largest datagram payload which can currently be sent on the active path
+    fn current_max_datagram_payload(&self) -> usize {
+        let path = self.path_manager.active_path();
+        self.space_manager.application().map_or(0, |space| {
+            space.max_datagram_payload(
+                path.mtu_controller.max_datagram_size(),
+                path.peer_connection_id.len(),
+            )
+        })
+    }
+
+    /// Notifies the application if the maximum datagram payload changed
+    fn on_max_datagram_payload_update(&mut self) {
+        let current = self.current_max_datagram_payload();
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space
+                .datagram_manager
+                .on_max_datagram_payload_update(current);
+        }
+    }
+
     fn on_ack_eliciting_packet_sent(&mut self, timestamp: Timestamp) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
         //# An endpoint also restarts its
@@ -427,7 +573,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
     ///
     /// Since non-probing frames can only be sent on the active path, a separate
     /// transmission context with Mode::PathValidationOnly is used to send on
-    /// other paths.
+    /// other paths. The active path is included if `include_active_path` is set.
     fn path_validation_only_transmission<'a, Tx: tx::Queue<Handle = Config::PathHandle>>(
         &mut self,
         queue: &mut Tx,
@@ -435,14 +581,17 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         outcome: &'a mut transmission::Outcome,
         subscriber: &mut Config::EventSubscriber,
         packet_interceptor: &'a mut Config::PacketInterceptor,
+        include_active_path: bool,
     ) -> usize {
+        let defer_header_protection = Self::defer_header_protection::<Tx>();
         let mut count = 0;
         let mut pending_paths = self.path_manager.paths_pending_validation();
         while let Some((path_id, path_manager)) = pending_paths.next_path() {
             // It is more efficient to coalesce path validation and other
             // frames for the active path so we skip PathValidationOnly
-            // and handle transmission for the active path separately.
-            if path_id == path_manager.active_path_id()
+            // and handle transmission for the active path separately, unless
+            // the transmission is limited to time-critical frames.
+            if (path_id == path_manager.active_path_id() && !include_active_path)
                 || !path_manager[path_id].can_transmit(timestamp)
             {
                 continue;
@@ -463,8 +612,12 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                         local_id_registry: &mut self.local_id_registry,
                         outcome,
                         min_packet_len: None,
+                        initial_padding: self.limits.initial_padding(),
+                        min_datagram_size: self.limits.min_datagram_size() as usize,
                         ecn,
                         transmission_mode,
+                        urgent_only: false,
+                        defer_header_protection,
                         publisher: &mut self.event_context.publisher(timestamp, subscriber),
                         packet_interceptor,
                     },
@@ -479,6 +632,250 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         count
     }
 
+    /// Returns `true` if the encryption and header protection of 1-RTT packets can be deferred
+    /// until the queue finishes the payload
+    ///
+    /// The datagrams passed to the packet interceptor need to be in their final form, so the
+    /// protection is only deferred if the interceptor doesn't look at them.
+    #[inline]
+    fn defer_header_protection<Tx: tx::Queue>() -> bool {
+        Tx::SUPPORTS_FINISH_PAYLOAD
+            && !<Config::PacketInterceptor as packet::interceptor::Interceptor>::INTERCEPTS_TX_DATAGRAMS
+    }
+
+    /// Queries the connection for outgoing packets
+    ///
+    /// If `urgent_only` is set, only time-critical frames are transmitted. Those transmissions
+    /// aren't limited to the connection's share of the queue, since they are small and shouldn't
+    /// wait for the next transmission round.
+    fn transmit<Tx: tx::Queue<Handle = Config::PathHandle>>(
+        &mut self,
+        queue: &mut Tx,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+        packet_interceptor: &mut Config::PacketInterceptor,
+        urgent_only: bool,
+    ) -> Result<(), ConnectionOnTransmitError> {
+        // Limit the connection to its share of the queue for the current transmission round
+        let mut deficit = self.transmit_deficit;
+        let quantum = if urgent_only {
+            u32::MAX
+        } else {
+            self.limits.transmit_quantum()
+        };
+        deficit.on_round(quantum);
+        let queue = &mut transmission::deficit::Queue::new(queue, &mut deficit);
+
+        let defer_header_protection = Self::defer_header_protection::<Tx>();
+        let mut count = 0;
+
+        debug_assert!(
+            !self.path_manager.is_amplification_limited(),
+            "connection should not express transmission interest if amplification limited"
+        );
+
+        match self.state {
+            ConnectionState::Handshaking | ConnectionState::Active | ConnectionState::Flushing => {
+                let mut outcome = transmission::Outcome::default();
+                let path_id = self.path_manager.active_path_id();
+
+                // Send an MTU probe if necessary and the handshake has completed
+                // MTU probes are prioritized over other data so they are not blocked by the
+                // congestion controller, as they are critical to achieving maximum throughput.
+                if !urgent_only
+                    && self.state == ConnectionState::Active
+                    && self.path_manager.active_path().can_transmit(timestamp)
+                    && self
+                        .path_manager
+                        .active_path()
+                        .mtu_controller
+                        .can_transmit(self.path_manager.active_path().transmission_constraint())
+                    && queue
+                        .push(ConnectionTransmission {
+                            context: ConnectionTransmissionContext {
+                                defer_header_protection,
+                                ..transmission_context!(
+                                    self,
+                                    &mut outcome,
+                                    path_id,
+                                    timestamp,
+                                    transmission::Mode::MtuProbing,
+                                    subscriber,
+                                    packet_interceptor,
+                                )
+                            },
+                            space_manager: &mut self.space_manager,
+                        })
+                        .is_ok()
+                {
+                    count += 1;
+                }
+
+                // Send all other data for the active path
+                while self.path_manager.active_path().can_transmit(timestamp)
+                    && queue
+                        .push(ConnectionTransmission {
+                            context: ConnectionTransmissionContext {
+                                urgent_only,
+                                defer_header_protection,
+                                ..transmission_context!(
+                                    self,
+                                    &mut outcome,
+                                    path_id,
+                                    timestamp,
+                                    transmission::Mode::Normal,
+                                    subscriber,
+                                    packet_interceptor,
+                                )
+                            },
+                            space_manager: &mut self.space_manager,
+                        })
+                        .is_ok()
+                {
+                    count += 1;
+                }
+
+                if outcome.ack_elicitation.is_ack_eliciting() {
+                    self.on_ack_eliciting_packet_sent(timestamp);
+                }
+
+                if let Some(edt) = self
+                    .path_manager
+                    .active_path()
+                    .congestion_controller
+                    .earliest_departure_time()
+                {
+                    if !edt.has_elapsed(timestamp) {
+                        // We can't transmit more until a future time, so arm the pacing
+                        // timer to pause transmission until the earliest departure time.
+
+                        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
+                        //# A sender SHOULD pace sending of all in-flight packets based on input
+                        //# from the congestion controller.
+
+                        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
+                        //# Senders MUST either use pacing or limit such bursts.
+                        self.timers.pacing_timer.set(edt);
+                    }
+                }
+
+                let meta = event::builder::ConnectionMeta {
+                    endpoint_type: Config::ENDPOINT_TYPE,
+                    id: self.event_context.internal_connection_id.into(),
+                    timestamp,
+                };
+                let path_id = self.path_manager.active_path_id().as_u8();
+                let path = self.path_manager.active_path();
+                subscriber.on_recovery_metrics(
+                    &mut self.event_context.context,
+                    &meta.into_event(),
+                    &recovery_event!(path_id, path).into_event(),
+                );
+
+                // PathValidationOnly handles transmission on non-active paths. Transmission
+                // on the active path should be handled prior to this.
+                //
+                // Transmissions limited to time-critical frames don't carry any other frames to
+                // coalesce with PATH_CHALLENGE and PATH_RESPONSE frames for the active path, so
+                // those are sent separately as well.
+                count += self.path_validation_only_transmission(
+                    queue,
+                    timestamp,
+                    &mut outcome,
+                    subscriber,
+                    packet_interceptor,
+                    urgent_only,
+                );
+
+                let mut publisher = self.event_context.publisher(timestamp, subscriber);
+
+                // Seal the packets in the last payload before the queue is
+                // flushed for the next connection
+                if defer_header_protection {
+                    queue.finish_payload(|payload| {
+                        connection::finish_payload(
+                            &mut self.space_manager,
+                            &self.path_manager,
+                            &mut publisher,
+                            payload,
+                        )
+                    });
+                }
+
+                // If anything was transmitted, notify the space manager
+                // that a burst of packets has completed transmission
+                if count > 0 {
+                    self.space_manager
+                        .on_transmit_burst_complete(self.path_manager.active_path(), timestamp);
+                }
+
+                if outcome.bytes_progressed > 0 {
+                    self.inactivity.on_progress(timestamp);
+                    publisher.on_tx_stream_progress(TxStreamProgress {
+                        bytes: outcome.bytes_progressed,
+                    })
+                }
+
+                // check to see if we are flushing and should close
+                if self.poll_flush().is_ready() {
+                    // trigger a wake up so we can close
+                    self.wakeup_handle.wakeup();
+                }
+            }
+            ConnectionState::Closing => {
+                let mut publisher = self.event_context.publisher(timestamp, subscriber);
+                let path = self.path_manager.active_path_mut();
+
+                if queue
+                    .push(
+                        self.close_sender
+                            .transmission(path, timestamp, &mut publisher),
+                    )
+                    .is_ok()
+                {
+                    count += 1;
+                }
+            }
+            ConnectionState::Draining | ConnectionState::Finished => {
+                // We are not allowed to send any data in this states
+            }
+        }
+
+        let is_limited = queue.is_limited();
+        let datagrams = queue.datagrams();
+        let bytes = queue.bytes();
+        self.activity.on_transmit(bytes);
+
+        if urgent_only {
+            return if datagrams == 0 {
+                Err(ConnectionOnTransmitError::NoDatagram)
+            } else {
+                Ok(())
+            };
+        }
+
+        self.transmit_deficit = deficit;
+
+        if datagrams > 0 {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+            publisher.on_transmit_share(TransmitShare {
+                datagrams,
+                bytes,
+                deficit: deficit.credits(),
+                is_limited,
+                priority_class: self.priority_class.into_event(),
+            });
+        }
+
+        if is_limited {
+            Err(ConnectionOnTransmitError::QuantumExhausted)
+        } else if count == 0 {
+            Err(ConnectionOnTransmitError::NoDatagram)
+        } else {
+            Ok(())
+        }
+    }
+
     fn on_supervisor_timeout(
         &mut self,
         timestamp: Timestamp,
@@ -579,15 +976,38 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.accept_state == AcceptState::Active
     }
 
+    fn poll_handshake_attempt(&mut self) -> bool {
+        if !Config::ENDPOINT_TYPE.is_server() || self.handshake_attempt_reported {
+            return false;
+        }
+
+        // The handshake space is only created once the server has processed the ClientHello
+        if self.space_manager.handshake().is_none() && self.space_manager.application().is_none() {
+            return false;
+        }
+
+        self.handshake_attempt_reported = true;
+        true
+    }
+
+    fn take_new_tokens(&mut self) -> Vec<Bytes> {
+        self.space_manager
+            .application_mut()
+            .map(|(space, _handshake_status)| space.take_new_tokens())
+            .unwrap_or_default()
+    }
+
     /// Creates a new `Connection` instance with the given configuration
     fn new(parameters: ConnectionParameters<Self::Config>) -> Result<Self, connection::Error> {
         let mut event_context = EventContext {
             context: parameters.event_context,
+            attached: parameters.attached_subscriber,
             internal_connection_id: parameters.internal_connection_id,
             quic_version: parameters.quic_version,
         };
 
-        let rtt_estimator = RttEstimator::new(parameters.limits.initial_round_trip_time());
+        let rtt_estimator = RttEstimator::new(parameters.limits.initial_round_trip_time())
+            .with_handshake_pto(parameters.limits.handshake_probe_timeout());
         // Assume clients validate the server's address implicitly.
         let peer_validated = Self::Config::ENDPOINT_TYPE.is_server();
         // The path manager always starts with a single path containing the known peer and local
@@ -603,7 +1023,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             parameters.limits.anti_amplification_multiplier(),
         );
 
-        let path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+        let path_manager = path::Manager::new(initial_path, parameters.peer_id_registry)
+            .with_challenge_config((&parameters.limits).into());
 
         let mut publisher =
             event_context.publisher(parameters.timestamp, parameters.event_subscriber);
@@ -630,17 +1051,42 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         let wakeup_handle = Arc::from(parameters.wakeup_handle);
         let waker = Waker::from(wakeup_handle.clone());
+
+        let mut space_manager = parameters.space_manager;
+        let remote_address = parameters.path_handle.remote_address();
+        let custom_frame_info = custom_frame::ConnectionInfo::new(waker.clone(), &remote_address);
+        space_manager.custom_frame_handler = Some(
+            parameters
+                .custom_frame_endpoint
+                .create_connection(&custom_frame_info),
+        );
+
+        if parameters.limits.duplicate_first_flight() {
+            if let Some((space, _handshake_status)) = space_manager.initial_mut() {
+                space.enable_duplicate_first_flight();
+            }
+        }
+
         let mut connection = Self {
             local_id_registry: parameters.local_id_registry,
             open_registry: parameters.open_registry,
             timers: Default::default(),
             accept_state: AcceptState::Handshaking,
+            handshake_attempt_reported: false,
+            established_waker: None,
             state: ConnectionState::Handshaking,
             path_manager,
             limits: parameters.limits,
+            transmit_deficit: Default::default(),
+            activity: connection::Activity::new(parameters.timestamp),
+            local_idle_timeout: None,
+            inactivity: Default::default(),
             error: Ok(()),
+            close_linger: Default::default(),
+            drop_behavior: parameters.limits.drop_behavior(),
+            priority_class: parameters.limits.priority_class(),
             close_sender: CloseSender::default(),
-            space_manager: parameters.space_manager,
+            space_manager,
             wakeup_handle,
             waker,
             event_context,
@@ -661,7 +1107,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                         use s2n_quic_core::event::{
                             builder::ConnectionClosed, ConnectionPublisher,
                         };
-                        publisher.on_connection_closed(ConnectionClosed { error });
+                        publisher.on_connection_closed(ConnectionClosed {
+                            error,
+                            linger: close::Linger::Default.into_event(),
+                        });
                     },
                 );
                 return Err(error);
@@ -724,14 +1173,22 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         let mut publisher = self.event_context.publisher(timestamp, subscriber);
 
-        publisher.on_connection_closed(event::builder::ConnectionClosed { error });
+        publisher.on_connection_closed(event::builder::ConnectionClosed {
+            error,
+            linger: self.close_linger.into_event(),
+        });
 
         // We don't need any timers anymore
         self.timers.cancel();
+        self.inactivity.cancel();
         // Update the connection state based on the type of error
         self.state = error.into();
         self.error = Err(error);
 
+        if let Some(waker) = self.established_waker.take() {
+            waker.wake();
+        }
+
         // Disable access to the connection from concurrent open attempts.
         self.open_registry = None;
 
@@ -743,7 +1200,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         let close_context = s2n_quic_core::connection::close::Context::new(&remote_address);
         let active_path_id = self.path_manager.active_path_id();
 
-        if let Some((early_connection_close, connection_close)) =
+        if self.close_linger.is_abort() {
+            // The application aborted the connection so skip notifying the peer and drop the
+            // connection state immediately
+            self.state = ConnectionState::Finished;
+        } else if let Some((early_connection_close, connection_close)) =
             s2n_quic_core::connection::error::as_frame(error, close_formatter, &close_context)
         {
             let mut outcome = transmission::Outcome::default();
@@ -768,7 +1229,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 //# connections close cleanly and that delayed or reordered packets are
                 //# properly discarded.  These states SHOULD persist for at least three
                 //# times the current PTO interval as defined in [QUIC-RECOVERY].
-                let timeout = 3 * self.current_pto();
+                let timeout = match self.close_linger {
+                    close::Linger::Duration(linger) => linger,
+                    _ => 3 * self.current_pto(),
+                };
 
                 self.close_sender.close(packet, timeout, timestamp);
             } else if cfg!(debug_assertions) {
@@ -799,6 +1263,16 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         if let Some((space, _)) = self.space_manager.application_mut() {
             space.datagram_manager.sender.on_connection_error(error);
             space.datagram_manager.receiver.on_connection_error(error);
+            space.custom_frame_manager.on_connection_error(error);
+            // Let any task waiting on a flush observe the connection error
+            space.wake_flush_waiter();
+            space.wake_ping_waiter();
+            space.datagram_manager.wake_payload_waiter();
+        }
+
+        // Notify the custom frame handler if the application space was never created
+        if let Some(handler) = self.space_manager.custom_frame_handler.as_mut() {
+            handler.on_connection_error(error);
         }
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.2.1
@@ -820,6 +1294,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         &mut self,
         connection_id_format: &mut Config::ConnectionIdFormat,
         stateless_reset_token_generator: &mut Config::StatelessResetTokenGenerator,
+        random_generator: &mut Config::RandomGenerator,
         timestamp: Timestamp,
     ) -> Result<(), LocalIdRegistrationError> {
         match self.local_id_registry.connection_id_interest() {
@@ -828,12 +1303,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 let connection_info = ConnectionInfo::new(&remote_address);
 
                 while count > 0 {
-                    let id = connection_id_format.generate(&connection_info);
+                    let id = connection_id_format
+                        .generate_with_random(&connection_info, random_generator);
                     let expiration = connection_id_format
                         .lifetime()
                         .map(|duration| timestamp + duration);
-                    let stateless_reset_token =
-                        stateless_reset_token_generator.generate(id.as_bytes());
+                    let stateless_reset_token = stateless_reset_token_generator
+                        .generate_with_random(id.as_bytes(), random_generator);
                     self.local_id_registry.register_connection_id(
                         &id,
                         expiration,
@@ -855,157 +1331,17 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         subscriber: &mut Config::EventSubscriber,
         packet_interceptor: &mut Config::PacketInterceptor,
     ) -> Result<(), ConnectionOnTransmitError> {
-        let mut count = 0;
-
-        debug_assert!(
-            !self.path_manager.is_amplification_limited(),
-            "connection should not express transmission interest if amplification limited"
-        );
-
-        match self.state {
-            ConnectionState::Handshaking | ConnectionState::Active | ConnectionState::Flushing => {
-                let mut outcome = transmission::Outcome::default();
-                let path_id = self.path_manager.active_path_id();
-
-                // Send an MTU probe if necessary and the handshake has completed
-                // MTU probes are prioritized over other data so they are not blocked by the
-                // congestion controller, as they are critical to achieving maximum throughput.
-                if self.state == ConnectionState::Active
-                    && self.path_manager.active_path().can_transmit(timestamp)
-                    && self
-                        .path_manager
-                        .active_path()
-                        .mtu_controller
-                        .can_transmit(self.path_manager.active_path().transmission_constraint())
-                    && queue
-                        .push(ConnectionTransmission {
-                            context: transmission_context!(
-                                self,
-                                &mut outcome,
-                                path_id,
-                                timestamp,
-                                transmission::Mode::MtuProbing,
-                                subscriber,
-                                packet_interceptor,
-                            ),
-                            space_manager: &mut self.space_manager,
-                        })
-                        .is_ok()
-                {
-                    count += 1;
-                }
-
-                // Send all other data for the active path
-                while self.path_manager.active_path().can_transmit(timestamp)
-                    && queue
-                        .push(ConnectionTransmission {
-                            context: transmission_context!(
-                                self,
-                                &mut outcome,
-                                path_id,
-                                timestamp,
-                                transmission::Mode::Normal,
-                                subscriber,
-                                packet_interceptor,
-                            ),
-                            space_manager: &mut self.space_manager,
-                        })
-                        .is_ok()
-                {
-                    count += 1;
-                }
-
-                if outcome.ack_elicitation.is_ack_eliciting() {
-                    self.on_ack_eliciting_packet_sent(timestamp);
-                }
-
-                if let Some(edt) = self
-                    .path_manager
-                    .active_path()
-                    .congestion_controller
-                    .earliest_departure_time()
-                {
-                    if !edt.has_elapsed(timestamp) {
-                        // We can't transmit more until a future time, so arm the pacing
-                        // timer to pause transmission until the earliest departure time.
-
-                        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
-                        //# A sender SHOULD pace sending of all in-flight packets based on input
-                        //# from the congestion controller.
-
-                        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
-                        //# Senders MUST either use pacing or limit such bursts.
-                        self.timers.pacing_timer.set(edt);
-                    }
-                }
-
-                let meta = event::builder::ConnectionMeta {
-                    endpoint_type: Config::ENDPOINT_TYPE,
-                    id: self.internal_connection_id().into(),
-                    timestamp,
-                };
-                let path_id = self.path_manager.active_path_id().as_u8();
-                let path = self.path_manager.active_path();
-                subscriber.on_recovery_metrics(
-                    &mut self.event_context.context,
-                    &meta.into_event(),
-                    &recovery_event!(path_id, path).into_event(),
-                );
-
-                // PathValidationOnly handles transmission on non-active paths. Transmission
-                // on the active path should be handled prior to this.
-                count += self.path_validation_only_transmission(
-                    queue,
-                    timestamp,
-                    &mut outcome,
-                    subscriber,
-                    packet_interceptor,
-                );
-
-                // If anything was transmitted, notify the space manager
-                // that a burst of packets has completed transmission
-                if count > 0 {
-                    self.space_manager
-                        .on_transmit_burst_complete(self.path_manager.active_path(), timestamp);
-                }
-
-                let mut publisher = self.event_context.publisher(timestamp, subscriber);
-                if outcome.bytes_progressed > 0 {
-                    publisher.on_tx_stream_progress(TxStreamProgress {
-                        bytes: outcome.bytes_progressed,
-                    })
-                }
-
-                // check to see if we are flushing and should close
-                if self.poll_flush().is_ready() {
-                    // trigger a wake up so we can close
-                    self.wakeup_handle.wakeup();
-                }
-            }
-            ConnectionState::Closing => {
-                let mut publisher = self.event_context.publisher(timestamp, subscriber);
-                let path = self.path_manager.active_path_mut();
-
-                if queue
-                    .push(
-                        self.close_sender
-                            .transmission(path, timestamp, &mut publisher),
-                    )
-                    .is_ok()
-                {
-                    count += 1;
-                }
-            }
-            ConnectionState::Draining | ConnectionState::Finished => {
-                // We are not allowed to send any data in this states
-            }
-        }
+        self.transmit(queue, timestamp, subscriber, packet_interceptor, false)
+    }
 
-        if count == 0 {
-            Err(ConnectionOnTransmitError::NoDatagram)
-        } else {
-            Ok(())
-        }
+    fn on_urgent_transmit<Tx: tx::Queue<Handle = Config::PathHandle>>(
+        &mut self,
+        queue: &mut Tx,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+        packet_interceptor: &mut Config::PacketInterceptor,
+    ) -> Result<(), ConnectionOnTransmitError> {
+        self.transmit(queue, timestamp, subscriber, packet_interceptor, true)
     }
 
     /// Handles all timeouts on the `Connection`.
@@ -1074,9 +1410,23 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             .poll_expiration(timestamp)
             .is_ready()
         {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
+            //# By announcing a max_idle_timeout, an endpoint
+            //# commits to initiating an immediate close (Section 10.2) if it
+            //# abandons the connection prior to the effective value.
+            if self.is_idle_timeout_lowered() {
+                return Err(transport::Error::NO_ERROR
+                    .with_reason("idle timeout lowered by the application")
+                    .into());
+            }
+
             return Err(connection::Error::idle_timer_expired());
         }
 
+        if let Poll::Ready(error) = self.inactivity.poll_expiration(timestamp) {
+            return Err(connection::Error::application(error));
+        }
+
         if self
             .timers
             .supervisor_timer
@@ -1086,6 +1436,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             self.on_supervisor_timeout(timestamp, subscriber, supervisor_context)?;
         }
 
+        // lost MTU probes and black holes can change the datagram payload
+        self.on_max_datagram_payload_update();
+
         // check to see if we're flushing the connection
         if self.poll_flush().is_ready() {
             return self.error;
@@ -1115,6 +1468,44 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
     }
 
     /// Handles all external wakeups on the [`Connection`].
+    fn on_imported(
+        &mut self,
+        state: &handoff::State,
+        tls_endpoint: &mut Config::TLSEndpoint,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+        datagram: &mut Config::DatagramEndpoint,
+        dc: &mut Config::DcEndpoint,
+    ) -> Result<(), connection::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        let (key, header_key) = tls_endpoint.new_server_one_rtt_keys(&state.secrets).ok_or(
+            connection::Error::invalid_configuration(
+                "the TLS provider doesn't support connection handoff",
+            ),
+        )?;
+
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+        self.space_manager.on_imported(
+            state,
+            key,
+            header_key,
+            &mut self.path_manager,
+            &mut self.local_id_registry,
+            &mut self.limits,
+            timestamp,
+            &self.waker,
+            &mut publisher,
+            datagram,
+            dc,
+        )?;
+
+        // The peer's address was validated by the handshake on the other endpoint
+        self.path_manager.active_path_mut().on_handshake_packet();
+
+        self.update_crypto_state(timestamp, subscriber, datagram, dc)
+    }
+
     fn on_wakeup(
         &mut self,
         timestamp: Timestamp,
@@ -1125,6 +1516,18 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // reset the queued state first so that new wakeup request are not missed
         self.wakeup_handle.wakeup_handled();
 
+        self.inactivity.on_wakeup(timestamp);
+
+        if core::mem::take(&mut self.timers.rearm_peer_idle_timer) {
+            if let Some(duration) = self.get_idle_timer_duration() {
+                // account for the time the connection has already been idle
+                let remaining = duration.saturating_sub(self.activity.idle(timestamp));
+                self.timers.peer_idle_timer.set(timestamp + remaining);
+            } else {
+                self.timers.peer_idle_timer.cancel();
+            }
+        }
+
         // check if crypto progress can be made
         self.update_crypto_state(timestamp, subscriber, datagram, dc)?;
 
@@ -1174,6 +1577,12 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             len: datagram.payload_len as u16,
         });
 
+        self.path_manager
+            .publish_statistics(datagram.timestamp, &self.limits, &mut publisher);
+
+        self.activity
+            .on_datagram_received(datagram.payload_len, datagram.timestamp);
+
         if amplification_outcome.is_active_path_unblocked() {
             //= https://www.rfc-editor.org/rfc/rfc9002#appendix-A.6
             //# When a server is blocked by anti-amplification limits, receiving a
@@ -1326,6 +1735,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 packet_interceptor,
             )?;
 
+            self.space_manager
+                .handshake_timing
+                .on_initial_received(datagram.timestamp);
+
             // try to move the crypto state machine forward
             self.update_crypto_state(
                 datagram.timestamp,
@@ -1568,7 +1981,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         &mut self,
         datagram: &DatagramInfo,
         path_id: path::Id,
-        _packet: ProtectedVersionNegotiation,
+        packet: ProtectedVersionNegotiation,
         subscriber: &mut Config::EventSubscriber,
         _packet_interceptor: &mut Config::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
@@ -1577,29 +1990,6 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         publisher.on_packet_received(event::builder::PacketReceived {
             packet_header: event::builder::PacketHeader::VersionNegotiation {},
         });
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
-        //= type=TODO
-        //= feature=Version negotiation handler
-        //= tracking-issue=349
-        //# A client that supports only this version of QUIC MUST abandon the
-        //# current connection attempt if it receives a Version Negotiation
-        //# packet, with the following two exceptions.
-
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
-        //= type=TODO
-        //= feature=Version negotiation handler
-        //= tracking-issue=349
-        //# A client MUST discard any
-        //# Version Negotiation packet if it has received and successfully
-        //# processed any other packet, including an earlier Version Negotiation
-        //# packet.
-
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
-        //= type=TODO
-        //= feature=Version negotiation handler
-        //= tracking-issue=349
-        //# A client MUST discard a Version Negotiation packet that
-        //# lists the QUIC version selected by the client.
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-21.2
         //# Except for Initial and Stateless Resets, an endpoint only accepts
@@ -1619,6 +2009,48 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             return Err(ProcessingError::Other);
         }
 
+        // Only clients act on Version Negotiation packets
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Ok(());
+        }
+
+        let already_processed_packet = self.space_manager.handshake_timing.has_received_packet();
+        self.space_manager.handshake_timing.on_version_negotiation();
+
+        let chosen_version = publisher.quic_version();
+        let server_versions: Vec<u32> = packet.iter().collect();
+        let is_chosen_version_listed = server_versions.contains(&chosen_version);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
+        //# A client MUST discard any
+        //# Version Negotiation packet if it has received and successfully
+        //# processed any other packet, including an earlier Version Negotiation
+        //# packet.
+        let outcome = if already_processed_packet {
+            event::builder::VersionNegotiationOutcome::AlreadyProcessedPacket
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
+        //# A client MUST discard a Version Negotiation packet that
+        //# lists the QUIC version selected by the client.
+        } else if is_chosen_version_listed {
+            event::builder::VersionNegotiationOutcome::ChosenVersionListed
+        } else {
+            event::builder::VersionNegotiationOutcome::Abandoned
+        };
+
+        publisher.on_version_negotiation_received(event::builder::VersionNegotiationReceived {
+            server_versions: &server_versions,
+            chosen_version,
+            outcome,
+        });
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-6.2
+        //# A client that supports only this version of QUIC MUST abandon the
+        //# current connection attempt if it receives a Version Negotiation
+        //# packet, with the following two exceptions.
+        if !already_processed_packet && !is_chosen_version_listed {
+            return Err(connection::Error::unsupported_version().into());
+        }
+
         Ok(())
     }
 
@@ -1778,6 +2210,112 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn activity(&self) -> connection::Activity {
+        self.activity
+    }
+
+    fn on_evicted(
+        &mut self,
+        criteria: EvictionCriteria,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+    ) {
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+        publisher.on_connection_evicted(event::builder::ConnectionEvicted {
+            criteria: criteria.into_event(),
+            age: self.activity.age(timestamp),
+            idle: self.activity.idle(timestamp),
+            bytes: self.activity.bytes(),
+        });
+    }
+
+    fn is_socket_error_target(
+        &self,
+        remote_address: &RemoteAddress,
+        destination_connection_id: &[u8],
+    ) -> bool {
+        self.path_manager
+            .path_for_socket_error(remote_address, destination_connection_id)
+            .is_some()
+    }
+
+    fn on_socket_error(
+        &mut self,
+        remote_address: &RemoteAddress,
+        error: SocketError,
+        destination_connection_id: &[u8],
+        timestamp: Timestamp,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+        random_generator: &mut Config::RandomGenerator,
+        subscriber: &mut Config::EventSubscriber,
+    ) -> Result<(), connection::Error> {
+        let path_id = if let Some(path_id) = self
+            .path_manager
+            .path_for_socket_error(remote_address, destination_connection_id)
+        {
+            path_id
+        } else {
+            return Ok(());
+        };
+
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+        publisher.on_socket_error_received(event::builder::SocketErrorReceived {
+            path_id: path_id.into_event(),
+            error: error.into_event(),
+        });
+
+        match error {
+            SocketError::PortUnreachable | SocketError::HostUnreachable => {
+                // The errors aren't authenticated, so they are only trusted until the handshake is
+                // confirmed. After that, the connection relies on its idle timer to detect that
+                // the peer has gone away.
+                if path_id == self.path_manager.active_path_id()
+                    && !self.space_manager.is_handshake_confirmed()
+                {
+                    return Err(connection::Error::unreachable());
+                }
+            }
+            SocketError::PacketTooBig { mtu } => {
+                let path = &mut self.path_manager[path_id];
+                let remote_address = path.remote_address();
+                path.mtu_controller.on_packet_too_big(
+                    mtu,
+                    &remote_address,
+                    timestamp,
+                    &mut path.congestion_controller,
+                    path_id,
+                    &mut publisher,
+                );
+                self.on_max_datagram_payload_update();
+            }
+            SocketError::LocalAddressUnavailable => {
+                let is_moved = self.path_manager.on_local_address_unavailable(
+                    path_id,
+                    self.space_manager.is_handshake_confirmed(),
+                    congestion_controller_endpoint,
+                    mtu,
+                    &self.limits,
+                    random_generator,
+                    &mut publisher,
+                );
+
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+                //# An endpoint can migrate a connection to a new local address by
+                //# sending packets containing non-probing frames from that address.
+                if is_moved {
+                    if let Some((space, _)) = self.space_manager.application_mut() {
+                        space.ping();
+                    }
+                    self.on_max_datagram_payload_update();
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     fn mark_as_accepted(&mut self) {
         debug_assert!(
             self.accept_state == AcceptState::HandshakeCompleted,
@@ -1802,6 +2340,15 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
                 interests.transmission = self.can_transmit(constraint);
 
+                // ACK frames and loss recovery probes are forced through any constraint, which
+                // makes them time-critical along with path validation. Handshake packets are
+                // left to the normal pass so their coalescing isn't split across two passes.
+                interests.urgent_transmission = interests.transmission
+                    && self.space_manager.is_handshake_confirmed()
+                    && (self.space_manager.get_transmission_interest()
+                        == transmission::Interest::Forced
+                        || self.path_manager.has_path_validation_interest());
+
                 interests.new_connection_id =
                     // Only issue new Connection Ids to the peer when we know they won't be used
                     // for Initial or Handshake packets.
@@ -1816,6 +2363,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 let constraint = self.path_manager.active_path().transmission_constraint();
                 interests.closing = true;
                 interests.transmission = self.close_sender.can_transmit(constraint);
+                interests.urgent_transmission = interests.transmission;
                 interests.finalization = self.close_sender.finalization_status().is_final();
             }
             ConnectionState::Draining | ConnectionState::Finished => {
@@ -1837,6 +2385,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             };
         } else {
             interests.timeout = self.next_expiration();
+            interests.priority_class = self.priority_class;
         }
 
         interests
@@ -1883,12 +2432,23 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             .application_mut()
             .ok_or_else(connection::Error::unspecified)?;
 
-        space.stream_manager.poll_accept(stream_type, context)
+        let result = space.stream_manager.poll_accept(stream_type, context);
+
+        // Accepting a stream drains the accept queue, which may allow the stream manager to
+        // issue additional MAX_STREAMS credits to the peer
+        if matches!(result, Poll::Ready(Ok(Some(_))))
+            && space.stream_manager.has_transmission_interest()
+        {
+            self.wakeup_handle.wakeup();
+        }
+
+        result
     }
 
     fn poll_open_stream(
         &mut self,
         stream_type: stream::StreamType,
+        id_hint: Option<stream::StreamId>,
         open_token: &mut connection::OpenToken,
         context: &Context,
     ) -> Poll<Result<stream::StreamId, connection::Error>> {
@@ -1899,6 +2459,22 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             .application_mut()
             .ok_or_else(connection::Error::unspecified)?;
 
+        // The stream is assigned the next ID once it's opened and the connection is locked for
+        // the duration of the call, so checking the hint up front can't race with other opens.
+        if let Some(id) = id_hint {
+            let next_id = space
+                .stream_manager
+                .next_local_stream_id(stream_type)
+                .ok_or_else(connection::Error::stream_id_exhausted)?;
+
+            if next_id != id {
+                return Err(connection::Error::invalid_configuration(
+                    "the stream ID hint does not match the next stream ID",
+                ))
+                .into();
+            }
+        }
+
         let mut api_context = ConnectionApiCallContext::from_wakeup_handle(&self.wakeup_handle);
 
         space.stream_manager.poll_open_local_stream(
@@ -1909,7 +2485,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         )
     }
 
-    fn application_close(&mut self, error: Option<application::Error>) {
+    fn application_close(&mut self, error: Option<application::Error>, linger: close::Linger) {
         if self.error.is_err() {
             return;
         }
@@ -1919,6 +2495,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         if let Some(error) = error {
             self.error = Err(connection::Error::application(error));
+            self.close_linger = linger;
         } else {
             // give the connection some time to flush all outstanding streams
             self.state = ConnectionState::Flushing;
@@ -1929,6 +2506,51 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.wakeup_handle.wakeup();
     }
 
+    fn application_drop(&mut self) {
+        match self.drop_behavior {
+            close::DropBehavior::Close(error) => {
+                self.application_close(Some(error), Default::default())
+            }
+            close::DropBehavior::KeepAlive => {
+                if self.error.is_err() {
+                    return;
+                }
+
+                // Disable access to the connection from concurrent open attempts.
+                self.open_registry = None;
+
+                // stop sending PINGs so the connection can reach the idle timeout
+                if let Some((space, _)) = self.space_manager.application_mut() {
+                    space.keep_alive(false);
+                }
+
+                self.wakeup_handle.wakeup();
+            }
+            _ => self.application_close(None, Default::default()),
+        }
+    }
+
+    fn set_drop_behavior(
+        &mut self,
+        behavior: close::DropBehavior,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.drop_behavior = behavior;
+
+        Ok(())
+    }
+
+    fn set_priority_class(&mut self, class: PriorityClass) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.priority_class = class;
+        // wake up the endpoint so the connection is moved to the new class
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
     fn server_name(&self) -> Option<ServerName> {
         self.space_manager.server_name.clone()
     }
@@ -1937,6 +2559,104 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.space_manager.application_protocol.clone()
     }
 
+    fn peer_transport_parameter(&self, id: u64) -> Option<Bytes> {
+        let id = s2n_quic_core::varint::VarInt::new(id).ok()?;
+        self.space_manager
+            .peer_transport_parameters
+            .get(id)
+            .cloned()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        if !self.limits.keying_material_export() {
+            return Err(connection::Error::invalid_configuration(
+                "keying material export is not enabled for the connection",
+            ));
+        }
+
+        if !self.space_manager.is_handshake_complete() {
+            return Err(connection::Error::handshake_not_complete());
+        }
+
+        self.space_manager
+            .export_keying_material(label, context, output)
+            .map_err(|_| {
+                connection::Error::keying_material_export(
+                    "the TLS session failed to export keying material",
+                )
+            })
+    }
+
+    fn export_handoff(&mut self) -> Result<handoff::State, connection::Error> {
+        self.error?;
+
+        if !self.limits.connection_handoff() {
+            return Err(connection::Error::invalid_configuration(
+                "connection handoff is not enabled for the connection",
+            ));
+        }
+
+        if !self.space_manager.is_handshake_complete() {
+            return Err(connection::Error::handshake_not_complete());
+        }
+
+        let pending = self.space_manager.handoff.as_deref().ok_or(
+            connection::Error::invalid_configuration(
+                "the TLS provider doesn't support connection handoff",
+            ),
+        )?;
+        let peer_transport_parameters = pending
+            .peer_transport_parameters
+            .clone()
+            .ok_or_else(connection::Error::handshake_not_complete)?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# The sequence number of the initial connection ID is 0.
+        let (local_connection_id, stateless_reset_token) = self
+            .local_id_registry
+            .registered_id(0)
+            .ok_or_else(connection::Error::unspecified)?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport
+        //# parameter is sent, the sequence number of the supplied connection ID
+        //# is 1.
+        let preferred_address_connection_id = self.local_id_registry.registered_id(1);
+
+        let path = self.path_manager.active_path();
+        let state = handoff::State {
+            quic_version: self.quic_version(),
+            secrets: pending.secrets.clone(),
+            original_destination_connection_id: pending.original_destination_connection_id,
+            local_connection_id,
+            stateless_reset_token,
+            preferred_address_connection_id,
+            peer_connection_id: path.peer_connection_id,
+            remote_address: *path.remote_address(),
+            local_address: *path.local_address(),
+            smoothed_rtt: path.rtt_estimator.smoothed_rtt(),
+            peer_transport_parameters,
+            application_protocol: self.space_manager.application_protocol.clone(),
+            server_name: self.space_manager.server_name.clone(),
+        };
+
+        // The peer continues the connection with the other endpoint, so the state is dropped
+        // without notifying it
+        self.open_registry = None;
+        self.error = Err(connection::Error::handed_off());
+        self.close_linger = close::Linger::Abort;
+        self.wakeup_handle.wakeup();
+
+        Ok(state)
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1955,6 +2675,63 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn poll_flush(
+        &mut self,
+        mode: connection::FlushMode,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.error?;
+
+        let (space, _) = self
+            .space_manager
+            .application_mut()
+            .ok_or_else(connection::Error::unspecified)?;
+
+        space.poll_flush(mode, context.waker()).map(Ok)
+    }
+
+    fn poll_ping(&mut self, context: &Context) -> Poll<Result<Duration, connection::Error>> {
+        self.error?;
+
+        let (space, _) = self
+            .space_manager
+            .application_mut()
+            .ok_or_else(connection::Error::unspecified)?;
+
+        if space.poll_ping_ack(context.waker()).is_pending() {
+            self.wakeup_handle.wakeup();
+            return Poll::Pending;
+        }
+
+        // the RTT estimator is updated with the sample from the ACK that acknowledged the PING
+        let rtt = self.path_manager.active_path().rtt_estimator.latest_rtt();
+
+        Poll::Ready(Ok(rtt))
+    }
+
+    fn poll_established(&mut self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        if self.is_established() {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.error?;
+
+        self.established_waker = Some(context.waker().clone());
+
+        Poll::Pending
+    }
+
+    fn attach_event_subscriber(
+        &mut self,
+        subscriber: Box<dyn event::AttachedSubscriber>,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.event_context.attached = Some(subscriber);
+
+        Ok(())
+    }
+
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1973,6 +2750,35 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn idle_timeout(&self) -> Result<Option<Duration>, connection::Error> {
+        self.error?;
+
+        Ok(self.effective_idle_timeout())
+    }
+
+    fn set_idle_timeout(&mut self, timeout: Duration) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.local_idle_timeout = (!timeout.is_zero()).then_some(timeout);
+        self.timers.rearm_peer_idle_timer = true;
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
+    fn set_inactivity_timeout(
+        &mut self,
+        timeout: Duration,
+        error: application::Error,
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.inactivity.set_timeout(timeout, error);
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
@@ -1981,6 +2787,81 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(*self.path_manager.active_path().handle.remote_address())
     }
 
+    fn max_datagram_size(&self) -> Result<usize, connection::Error> {
+        Ok(self
+            .path_manager
+            .active_path()
+            .mtu_controller
+            .max_datagram_size())
+    }
+
+    fn max_datagram_payload(&self) -> Result<usize, connection::Error> {
+        self.error?;
+
+        Ok(self.current_max_datagram_payload())
+    }
+
+    fn poll_max_datagram_payload(
+        &mut self,
+        previous: usize,
+        context: &Context,
+    ) -> Poll<Result<usize, connection::Error>> {
+        self.error?;
+
+        let current = self.current_max_datagram_payload();
+        let (space, _) = self
+            .space_manager
+            .application_mut()
+            .ok_or_else(connection::Error::unspecified)?;
+
+        space
+            .datagram_manager
+            .poll_max_datagram_payload(current, previous, context.waker())
+            .map(Ok)
+    }
+
+    fn statistics(&self) -> Result<connection::Statistics, connection::Error> {
+        let mut statistics = connection::Statistics::default();
+
+        let path = self.path_manager.active_path();
+        statistics.smoothed_rtt = path.rtt_estimator.smoothed_rtt();
+        statistics.min_rtt = path.rtt_estimator.min_rtt();
+        statistics.latest_rtt = path.rtt_estimator.latest_rtt();
+        statistics.congestion_window = path.congestion_controller.congestion_window();
+        statistics.bytes_in_flight = path.congestion_controller.bytes_in_flight();
+        statistics.delivery_rate = path
+            .congestion_controller
+            .delivery_rate_sample()
+            .filter(|sample| !sample.interval.is_zero())
+            .map(|sample| sample.delivery_rate().as_bytes_per_second());
+        statistics.paths = self.path_manager.path_count();
+
+        if let Some(space) = self.space_manager.application() {
+            statistics.streams_opened = space.stream_manager.streams_opened();
+            statistics.send_buffer_occupancy = space.stream_manager.send_buffer_occupancy();
+        }
+
+        // The packet counters are only available if the endpoint registered the statistics
+        // subscriber
+        let mut query = query::Once::new(|counters: &connection::statistics::Counters| {
+            counters.write(&mut statistics)
+        });
+        <Config::EventSubscriber as event::Subscriber>::query(
+            &self.event_context.context,
+            &mut query,
+        );
+
+        Ok(statistics)
+    }
+
+    fn path_info(&self) -> Result<connection::PathInfo, connection::Error> {
+        Ok(self.path_manager.active_path().info())
+    }
+
+    fn path_statistics(&self) -> Result<Vec<connection::PathStatistics>, connection::Error> {
+        Ok(self.path_manager.statistics().collect())
+    }
+
     fn error(&self) -> Option<connection::Error> {
         self.error.err()
     }
@@ -2036,6 +2917,7 @@ impl<Config: endpoint::Config> timer::Provider for ConnectionImpl<Config> {
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         // find the earliest armed timer
         self.timers.timers(query)?;
+        self.inactivity.timers(query)?;
         self.close_sender.timers(query)?;
         self.local_id_registry.timers(query)?;
         self.path_manager.timers(query)?;
@@ -3,7 +3,7 @@
 
 //! A collection of a all the interactions a `Connection` is interested in
 
-use s2n_quic_core::time::Timestamp;
+use s2n_quic_core::{connection::PriorityClass, time::Timestamp};
 
 /// A collection of a all the interactions a `Connection` is interested in
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -19,12 +19,17 @@ pub struct ConnectionInterests {
     pub accept: bool,
     /// Is `true` if a `Connection` wants to send data
     pub transmission: bool,
+    /// Is `true` if a `Connection` wants to send time-critical frames, such as ACK,
+    /// PATH_CHALLENGE, PATH_RESPONSE or CONNECTION_CLOSE frames, ahead of other connections
+    pub urgent_transmission: bool,
     /// Is `true` if a `Connection` needs a new connection id
     pub new_connection_id: bool,
     /// Is `true` if a `Connection` should attempt to receive or send ACKs
     pub ack: bool,
     /// Is `Some(Timestamp)` if the connection needs to be woken up at the specified time
     pub timeout: Option<Timestamp>,
+    /// The class the `Connection` should be scheduled in
+    pub priority_class: PriorityClass,
 }
 
 impl ConnectionInterests {
@@ -39,12 +44,15 @@ impl ConnectionInterests {
     ///
     /// The `finalization` interest is the exception. A `Connection` can only
     /// be finalized if all parts are interested in finalization.
+    ///
+    /// The `priority_class` is the most urgent class of the two collections.
     pub fn merge(self, other: ConnectionInterests) -> ConnectionInterests {
         ConnectionInterests {
             finalization: self.finalization && other.finalization,
             closing: self.closing && other.closing,
             accept: self.accept || other.accept,
             transmission: self.transmission || other.transmission,
+            urgent_transmission: self.urgent_transmission || other.urgent_transmission,
             new_connection_id: self.new_connection_id || other.new_connection_id,
             ack: self.ack || other.ack,
             timeout: match (self.timeout, other.timeout) {
@@ -53,6 +61,7 @@ impl ConnectionInterests {
                 (None, Some(b)) => Some(b),
                 (None, None) => None,
             },
+            priority_class: self.priority_class.min(other.priority_class),
         }
     }
 }
@@ -83,45 +92,53 @@ mod tests {
     fn test_merge_connection_interests() {
         let a = ConnectionInterests {
             transmission: false,
+            urgent_transmission: false,
             accept: true,
             finalization: true,
             closing: true,
             new_connection_id: false,
             ack: false,
             timeout: None,
+            priority_class: PriorityClass::Bulk,
         };
 
         let b_time = unsafe { Timestamp::from_duration(Duration::from_secs(123)) };
         let b = ConnectionInterests {
             transmission: true,
+            urgent_transmission: true,
             accept: false,
             finalization: false,
             closing: false,
             new_connection_id: true,
             ack: true,
             timeout: Some(b_time),
+            priority_class: PriorityClass::Normal,
         };
 
         let c_time = unsafe { Timestamp::from_duration(Duration::from_secs(456)) };
         let c = ConnectionInterests {
             transmission: false,
+            urgent_transmission: false,
             accept: false,
             finalization: true,
             closing: true,
             new_connection_id: false,
             ack: false,
             timeout: Some(c_time),
+            priority_class: PriorityClass::Control,
         };
 
         assert_eq!(
             ConnectionInterests {
                 transmission: true,
+                urgent_transmission: true,
                 accept: true,
                 finalization: false,
                 closing: false,
                 new_connection_id: true,
                 ack: true,
                 timeout: Some(b_time),
+                priority_class: PriorityClass::Normal,
             },
             a + b
         );
@@ -129,12 +146,14 @@ mod tests {
         assert_eq!(
             ConnectionInterests {
                 transmission: false,
+                urgent_transmission: false,
                 accept: true,
                 finalization: true,
                 closing: true,
                 new_connection_id: false,
                 ack: false,
                 timeout: Some(c_time),
+                priority_class: PriorityClass::Control,
             },
             a + c
         );
@@ -142,12 +161,14 @@ mod tests {
         assert_eq!(
             ConnectionInterests {
                 transmission: true,
+                urgent_transmission: true,
                 accept: false,
                 finalization: false,
                 closing: false,
                 new_connection_id: true,
                 ack: true,
                 timeout: Some(b_time),
+                priority_class: PriorityClass::Control,
             },
             b + c
         );
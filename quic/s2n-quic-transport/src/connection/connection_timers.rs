@@ -17,6 +17,9 @@ pub struct ConnectionTimers {
     //# eliciting packets have been sent since last receiving and processing
     //# a packet.
     pub reset_peer_idle_timer_on_send: bool,
+    /// Stores if the peer idle timer should be rearmed after the application changed the idle
+    /// timeout
+    pub rearm_peer_idle_timer: bool,
     /// The timer which is used to send packets to the peer before the idle
     /// timeout expires
     pub local_idle_timer: Timer,
@@ -15,14 +15,19 @@ use crate::{
     stream,
 };
 use bytes::Bytes;
-use core::task::{Context, Poll};
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application,
     application::ServerName,
+    connection::{close, handoff, PriorityClass},
+    endpoint::limits::EvictionCriteria,
     event::{self, builder::DatagramDropReason, supervisor, ConnectionPublisher, IntoEvent},
     inet::{DatagramInfo, SocketAddress},
-    io::tx,
+    io::{rx::SocketError, tx},
     packet::{
         handshake::ProtectedHandshake,
         initial::{CleartextInitial, ProtectedInitial},
@@ -32,7 +37,7 @@ use s2n_quic_core::{
         zero_rtt::ProtectedZeroRtt,
         ProtectedPacket,
     },
-    path::{mtu, Handle as _},
+    path::{mtu, Handle as _, RemoteAddress},
     query,
     time::Timestamp,
 };
@@ -55,6 +60,13 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     /// has been handed off to the application
     fn is_accepted(&self) -> bool;
 
+    /// Returns `true` exactly once on the server, after the peer's ClientHello has been
+    /// processed and the handshake attempt should be reported to the endpoint limiter
+    fn poll_handshake_attempt(&mut self) -> bool;
+
+    /// Returns the tokens the client received in NEW_TOKEN frames since the last call
+    fn take_new_tokens(&mut self) -> Vec<Bytes>;
+
     /// Initiates closing the connection as described in
     /// https://www.rfc-editor.org/rfc/rfc9000#section-10
     fn close(
@@ -67,6 +79,19 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     );
 
+    /// Returns the activity of the connection, which is used to select connections for eviction
+    fn activity(&self) -> connection::Activity;
+
+    /// Notifies the connection that it was selected for eviction by the endpoint limiter
+    ///
+    /// The caller is expected to close the connection after this call.
+    fn on_evicted(
+        &mut self,
+        criteria: EvictionCriteria,
+        timestamp: Timestamp,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    );
+
     /// Marks a connection which advertised itself as having completed the handshake
     /// (via [`ConnectionInterests`]) as accepted. After this call the `accept` interest should
     /// no longer be signalled.
@@ -78,6 +103,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         &mut self,
         connection_id_format: &mut <Self::Config as endpoint::Config>::ConnectionIdFormat,
         stateless_reset_token_generator: &mut <Self::Config as endpoint::Config>::StatelessResetTokenGenerator,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         timestamp: Timestamp,
     ) -> Result<(), LocalIdRegistrationError>;
 
@@ -92,6 +118,21 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     where
         Tx: tx::Queue<Handle = <Self::Config as endpoint::Config>::PathHandle>;
 
+    /// Queries the connection for outgoing packets which carry time-critical frames
+    ///
+    /// Only ACK frames, loss recovery probes, PATH_CHALLENGE and PATH_RESPONSE frames and
+    /// CONNECTION_CLOSE frames are transmitted. The rest of the connection's data is transmitted
+    /// by `on_transmit`.
+    fn on_urgent_transmit<Tx>(
+        &mut self,
+        queue: &mut Tx,
+        timestamp: Timestamp,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
+    ) -> Result<(), ConnectionOnTransmitError>
+    where
+        Tx: tx::Queue<Handle = <Self::Config as endpoint::Config>::PathHandle>;
+
     /// Handles all timeouts on the `Connection`.
     ///
     /// `timestamp` passes the current time.
@@ -113,6 +154,20 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
     ) -> Result<(), connection::Error>;
 
+    /// Installs the state of a connection handed off by another endpoint
+    ///
+    /// Is called once on a connection created for the state, instead of handling an initial
+    /// packet.
+    fn on_imported(
+        &mut self,
+        state: &handoff::State,
+        tls_endpoint: &mut <Self::Config as endpoint::Config>::TLSEndpoint,
+        timestamp: Timestamp,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+        datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
+        dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
+    ) -> Result<(), connection::Error>;
+
     // Packet handling
 
     /// Is called when an initial packet had been received
@@ -208,6 +263,28 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
     ) -> Result<path::Id, DatagramDropReason>;
 
+    /// Returns `true` if the datagram quoted in a socket error was sent by the connection
+    fn is_socket_error_target(
+        &self,
+        remote_address: &RemoteAddress,
+        destination_connection_id: &[u8],
+    ) -> bool;
+
+    /// Notifies a connection that the network reported an error for a datagram it sent
+    ///
+    /// An error is returned if the connection should be closed as a result.
+    fn on_socket_error(
+        &mut self,
+        remote_address: &RemoteAddress,
+        error: SocketError,
+        destination_connection_id: &[u8],
+        timestamp: Timestamp,
+        congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<<Self::Config as endpoint::Config>::Mtu>,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> Result<(), connection::Error>;
+
     /// Returns the Connections interests
     fn interests(&self) -> ConnectionInterests;
 
@@ -463,27 +540,120 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         context: &Context,
     ) -> Poll<Result<Option<stream::StreamId>, connection::Error>>;
 
+    /// Opens the next local initiated stream of a certain type
+    ///
+    /// If an `id_hint` is provided, the stream is only opened if it would be assigned that ID.
     fn poll_open_stream(
         &mut self,
         stream_type: stream::StreamType,
+        id_hint: Option<stream::StreamId>,
         open_token: &mut connection::OpenToken,
         context: &Context,
     ) -> Poll<Result<stream::StreamId, connection::Error>>;
 
-    fn application_close(&mut self, error: Option<application::Error>);
+    /// Closes the connection on behalf of the application
+    ///
+    /// If no error is provided, the connection is gracefully closed once all outstanding streams
+    /// have been flushed and `linger` is ignored.
+    fn application_close(&mut self, error: Option<application::Error>, linger: close::Linger);
+
+    /// Called once the application dropped its last handle to the connection
+    ///
+    /// The connection is closed or kept open according to the configured
+    /// [`close::DropBehavior`].
+    fn application_drop(&mut self);
+
+    /// Sets what happens to the connection once the application drops its last handle
+    fn set_drop_behavior(&mut self, behavior: close::DropBehavior)
+        -> Result<(), connection::Error>;
+
+    /// Sets the class the connection is scheduled in on the endpoint
+    fn set_priority_class(&mut self, class: PriorityClass) -> Result<(), connection::Error>;
 
     fn server_name(&self) -> Option<ServerName>;
 
     fn application_protocol(&self) -> Bytes;
 
+    fn peer_transport_parameter(&self, id: u64) -> Option<Bytes>;
+
+    /// Exports keying material from the TLS session into `output`
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
+    /// Exports the state of a server connection so it can be resumed on another endpoint
+    ///
+    /// The connection is closed without notifying the peer once the state is exported.
+    fn export_handoff(&mut self) -> Result<handoff::State, connection::Error>;
+
     fn ping(&mut self) -> Result<(), connection::Error>;
 
+    /// Sends a PING to the peer and polls for it to be acknowledged
+    ///
+    /// Once acknowledged, the latest RTT sample of the active path is returned.
+    fn poll_ping(&mut self, context: &Context) -> Poll<Result<Duration, connection::Error>>;
+
+    /// Polls for the connection to be usable by the application
+    ///
+    /// This is once the handshake has completed or, for servers allowing 0.5-RTT data, once the
+    /// 1-RTT keys are available. Servers which accept connections early use this to wait for
+    /// the connection to be usable.
+    fn poll_established(&mut self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    fn poll_flush(
+        &mut self,
+        mode: connection::FlushMode,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>>;
+
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
+    /// Attaches an event subscriber to the connection, replacing any previously attached one
+    fn attach_event_subscriber(
+        &mut self,
+        subscriber: Box<dyn event::AttachedSubscriber>,
+    ) -> Result<(), connection::Error>;
+
+    /// Returns the effective idle timeout of the connection
+    fn idle_timeout(&self) -> Result<Option<Duration>, connection::Error>;
+
+    /// Lowers the idle timeout of the connection below the negotiated value
+    ///
+    /// A `timeout` of zero restores the negotiated value.
+    fn set_idle_timeout(&mut self, timeout: Duration) -> Result<(), connection::Error>;
+
+    /// Closes the connection with `error` if stream data doesn't make progress within `timeout`
+    ///
+    /// A `timeout` of zero disables the timer.
+    fn set_inactivity_timeout(
+        &mut self,
+        timeout: Duration,
+        error: application::Error,
+    ) -> Result<(), connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn max_datagram_size(&self) -> Result<usize, connection::Error>;
+
+    fn max_datagram_payload(&self) -> Result<usize, connection::Error>;
+
+    fn poll_max_datagram_payload(
+        &mut self,
+        previous: usize,
+        context: &Context,
+    ) -> Poll<Result<usize, connection::Error>>;
+
+    fn statistics(&self) -> Result<connection::Statistics, connection::Error>;
+
+    fn path_info(&self) -> Result<connection::PathInfo, connection::Error>;
+
+    fn path_statistics(&self) -> Result<Vec<connection::PathStatistics>, connection::Error>;
+
     fn error(&self) -> Option<connection::Error>;
 
     fn query_event_context(&self, query: &mut dyn query::Query);
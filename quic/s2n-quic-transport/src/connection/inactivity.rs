@@ -0,0 +1,121 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Closes the connection on behalf of the application if stream data stops making progress
+//!
+//! Unlike the idle timer, the inactivity timer isn't restarted by packets which don't carry any
+//! stream data, such as keep-alive PINGs or ACKs.
+
+use core::{task::Poll, time::Duration};
+use s2n_quic_core::{
+    application,
+    time::{timer, Timer, Timestamp},
+};
+
+#[derive(Debug, Default)]
+pub struct Inactivity {
+    /// The configured timeout and the error used to close the connection when it expires
+    timeout: Option<(Duration, application::Error)>,
+    timer: Timer,
+    /// Set when the timeout changes so the timer is armed with the next timestamp
+    needs_arm: bool,
+}
+
+impl Inactivity {
+    /// Configures the inactivity timeout
+    ///
+    /// A `timeout` of zero disables the timer.
+    #[inline]
+    pub fn set_timeout(&mut self, timeout: Duration, error: application::Error) {
+        self.timer.cancel();
+
+        if timeout.is_zero() {
+            self.timeout = None;
+            self.needs_arm = false;
+        } else {
+            self.timeout = Some((timeout, error));
+            self.needs_arm = true;
+        }
+    }
+
+    /// Arms the timer if the timeout was changed since the last call
+    #[inline]
+    pub fn on_wakeup(&mut self, timestamp: Timestamp) {
+        if core::mem::take(&mut self.needs_arm) {
+            self.on_progress(timestamp);
+        }
+    }
+
+    /// Called when stream data is sent or received
+    #[inline]
+    pub fn on_progress(&mut self, timestamp: Timestamp) {
+        if let Some((timeout, _)) = self.timeout {
+            self.timer.set(timestamp + timeout);
+        }
+    }
+
+    /// Returns the error to close the connection with if the timer expired
+    #[inline]
+    pub fn poll_expiration(&mut self, timestamp: Timestamp) -> Poll<application::Error> {
+        if self.timer.poll_expiration(timestamp).is_ready() {
+            if let Some((_, error)) = self.timeout {
+                return Poll::Ready(error);
+            }
+        }
+
+        Poll::Pending
+    }
+
+    #[inline]
+    pub fn cancel(&mut self) {
+        self.timer.cancel();
+    }
+}
+
+impl timer::Provider for Inactivity {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::{testing::Clock, Clock as _};
+
+    #[test]
+    fn inactivity_test() {
+        let start = Clock::default().get_time();
+        let timeout = Duration::from_secs(1);
+        let error = application::Error::new(42).unwrap();
+
+        let mut inactivity = Inactivity::default();
+        assert!(!inactivity.timer.is_armed());
+
+        inactivity.set_timeout(timeout, error);
+        // the timer isn't armed until the connection wakes up
+        assert!(!inactivity.timer.is_armed());
+        inactivity.on_wakeup(start);
+        assert!(inactivity.timer.is_armed());
+
+        // progress pushes the expiration out
+        let now = start + Duration::from_millis(800);
+        inactivity.on_progress(now);
+        assert!(inactivity.poll_expiration(start + timeout).is_pending());
+
+        assert_eq!(
+            inactivity.poll_expiration(now + timeout),
+            Poll::Ready(error)
+        );
+        assert!(!inactivity.timer.is_armed());
+
+        // a zero timeout disables the timer
+        inactivity.set_timeout(timeout, error);
+        inactivity.on_wakeup(now);
+        inactivity.set_timeout(Duration::ZERO, error);
+        inactivity.on_wakeup(now);
+        inactivity.on_progress(now);
+        assert!(!inactivity.timer.is_armed());
+    }
+}
@@ -5,16 +5,43 @@
 
 /// A connection ID which is stable and internally identifies a connection over
 /// the whole lifetime of an endpoint.
+///
+/// The lower 32 bits contain the index of the connection in the endpoint's connection storage and
+/// the upper 32 bits contain the generation of that index. The generation is incremented each time
+/// the index is reused, which prevents stale IDs from referring to newer connections.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct InternalConnectionId(u64);
 
-/// Generates internal connection IDs.
+impl InternalConnectionId {
+    /// Creates an ID from a storage index and generation
+    #[inline]
+    pub(crate) fn new(index: u32, generation: u32) -> Self {
+        Self(((generation as u64) << 32) | index as u64)
+    }
+
+    /// Returns the index of the connection in the endpoint's connection storage
+    #[inline]
+    pub(crate) fn index(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// Returns the generation of the storage index
+    #[inline]
+    pub(crate) fn generation(self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+}
+
+/// Generates sequential internal connection IDs.
 ///
-/// Connection IDs are only internal if they are generated by the same generator.
+/// The endpoint allocates IDs from its connection storage. This generator is used by tests which
+/// need unique IDs without a connection container.
+#[cfg(test)]
 pub struct InternalConnectionIdGenerator {
     next: u64,
 }
 
+#[cfg(test)]
 impl InternalConnectionIdGenerator {
     /// Returns a new InternalConnectionIdGenerator
     pub fn new() -> Self {
@@ -46,6 +73,18 @@ impl From<InternalConnectionId> for u64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn index_generation_test() {
+        let id = InternalConnectionId::new(123, 456);
+        assert_eq!(id.index(), 123);
+        assert_eq!(id.generation(), 456);
+
+        let id = InternalConnectionId::new(u32::MAX, u32::MAX);
+        assert_eq!(id.index(), u32::MAX);
+        assert_eq!(id.generation(), u32::MAX);
+        assert_eq!(u64::from(id), u64::MAX);
+    }
+
     #[test]
     fn connection_id_generator_test() {
         let mut id_generator = InternalConnectionIdGenerator::new();
@@ -11,7 +11,7 @@ use crate::{
 };
 use core::convert::TryInto;
 use s2n_quic_core::{
-    ack, connection, frame,
+    ack, connection, ensure, frame,
     memo::Memo,
     packet::number::PacketNumber,
     stateless_reset,
@@ -317,6 +317,18 @@ impl LocalIdRegistry {
         self.internal_id
     }
 
+    /// Returns the connection ID and stateless reset token registered with the given sequence
+    /// number, if it hasn't been removed
+    pub fn registered_id(
+        &self,
+        sequence_number: u32,
+    ) -> Option<(connection::LocalId, stateless_reset::Token)> {
+        self.registered_ids
+            .iter()
+            .find(|id_info| id_info.sequence_number == sequence_number)
+            .map(|id_info| (id_info.id, id_info.stateless_reset_token))
+    }
+
     /// Sets the active connection id limit
     pub fn set_active_connection_id_limit(&mut self, active_connection_id_limit: u64) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
@@ -385,6 +397,47 @@ impl LocalIdRegistry {
         Ok(())
     }
 
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+    //# If the preferred_address transport
+    //# parameter is sent, the sequence number of the supplied connection ID
+    //# is 1.
+    /// Registers the connection ID sent in the server's `preferred_address` transport parameter
+    ///
+    /// The connection ID is communicated to the peer in the transport parameters rather than a
+    /// NEW_CONNECTION_ID frame, so it starts in the `Active` status. This must be called before
+    /// any other connection IDs are registered.
+    pub fn register_preferred_address_connection_id(
+        &mut self,
+        id: &connection::LocalId,
+        expiration: Option<Timestamp>,
+        stateless_reset_token: stateless_reset::Token,
+    ) -> Result<(), LocalIdRegistrationError> {
+        ensure!(
+            self.next_sequence_number == 1,
+            Err(LocalIdRegistrationError::InvalidSequenceNumber)
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# The value of the
+        //# active_connection_id_limit parameter MUST be at least 2.
+        //
+        // The peer's limit isn't known until its transport parameters are received, but it is
+        // guaranteed to have room for the preferred address connection ID.
+        self.active_connection_id_limit = self.active_connection_id_limit.max(2);
+
+        self.register_connection_id(id, expiration, stateless_reset_token)?;
+
+        if let Some(id_info) = self.registered_ids.last_mut() {
+            debug_assert_eq!(id_info.sequence_number, 1);
+            id_info.status = Active;
+        }
+        self.transmission_interest.clear();
+
+        self.check_consistency();
+
+        Ok(())
+    }
+
     /// Unregisters connection IDs that have expired
     fn unregister_expired_ids(&mut self, timestamp: Timestamp) {
         {
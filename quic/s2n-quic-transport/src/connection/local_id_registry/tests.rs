@@ -52,7 +52,7 @@ fn mapper(
 ) -> (ConnectionIdMapper, LocalIdRegistry) {
     let mut random_generator = random::testing::Generator(123);
 
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     let registry = mapper.create_local_id_registry(
         InternalConnectionIdGenerator::new().generate_id(),
         &handshake_id,
@@ -131,11 +131,50 @@ fn sequence_number_must_increase_by_one() {
     assert_eq!(1, seq_num_2 - seq_num_1);
 }
 
+//= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+//= type=test
+//# If the preferred_address transport
+//# parameter is sent, the sequence number of the supplied connection ID
+//# is 1.
+#[test]
+fn preferred_address_connection_id() {
+    let ext_id_1 = id(b"id01");
+    let ext_id_2 = id(b"id02");
+    let ext_id_3 = id(b"id03");
+
+    // the connection ID is registered before the peer's active_connection_id_limit is known
+    let (mapper, mut reg) = mapper(ext_id_1, None, TEST_TOKEN_1);
+    reg.register_preferred_address_connection_id(&ext_id_2, None, TEST_TOKEN_2)
+        .unwrap();
+
+    let id_info = reg.get_connection_id_info(&ext_id_2).unwrap();
+    assert_eq!(1, id_info.sequence_number);
+    // the connection ID was sent in the transport parameters so it doesn't need to be issued
+    assert_eq!(Active, id_info.status);
+    assert_eq!(
+        transmission::Interest::None,
+        reg.get_transmission_interest()
+    );
+    assert_eq!(
+        Some((
+            reg.internal_connection_id(),
+            connection::id::Classification::Local
+        )),
+        mapper.lookup_internal_connection_id(&ext_id_2)
+    );
+
+    // the connection ID must be registered immediately after the handshake connection ID
+    assert_eq!(
+        Err(LocalIdRegistrationError::InvalidSequenceNumber),
+        reg.register_preferred_address_connection_id(&ext_id_3, None, TEST_TOKEN_3)
+    );
+}
+
 #[test]
 fn connection_mapper_test() {
     let mut id_generator = InternalConnectionIdGenerator::new();
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
 
     let id1 = id_generator.generate_id();
     let id2 = id_generator.generate_id();
@@ -9,6 +9,7 @@ use crate::{
 };
 use s2n_quic_core::{connection, event, event::supervisor, path::mtu, time::Timestamp};
 
+mod activity;
 mod api;
 mod api_provider;
 mod close_sender;
@@ -19,22 +20,29 @@ mod connection_interests;
 mod connection_timers;
 mod connection_trait;
 pub(crate) mod finalization;
+mod inactivity;
 mod internal_connection_id;
 pub(crate) mod local_id_registry;
 pub(crate) mod open_token;
 pub(crate) mod peer_id_registry;
 pub(crate) mod transmission;
 
+pub(crate) use activity::Activity;
 pub(crate) use api_provider::{ConnectionApi, ConnectionApiProvider};
 pub(crate) use connection_container::{ConnectionContainer, ConnectionContainerIterationResult};
 pub(crate) use connection_id_mapper::{ConnectionIdMapper, OpenRegistry};
 pub(crate) use connection_interests::ConnectionInterests;
 pub(crate) use connection_timers::ConnectionTimers;
 pub(crate) use connection_trait::ConnectionTrait as Trait;
-pub(crate) use internal_connection_id::{InternalConnectionId, InternalConnectionIdGenerator};
+pub(crate) use inactivity::Inactivity;
+pub(crate) use internal_connection_id::InternalConnectionId;
+#[cfg(test)]
+pub(crate) use internal_connection_id::InternalConnectionIdGenerator;
 pub(crate) use local_id_registry::LocalIdRegistry;
 pub(crate) use peer_id_registry::PeerIdRegistry;
-pub(crate) use transmission::{ConnectionTransmission, ConnectionTransmissionContext};
+pub(crate) use transmission::{
+    finish_payload, ConnectionTransmission, ConnectionTransmissionContext,
+};
 
 pub use api::Connection;
 pub use connection_impl::ConnectionImpl as Implementation;
@@ -79,12 +87,16 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub mtu_config: mtu::Config,
     /// The context that should be passed to all related connection events
     pub event_context: <Cfg::EventSubscriber as event::Subscriber>::ConnectionContext,
+    /// The subscriber attached to the connection, in addition to the endpoint's subscriber
+    pub attached_subscriber: Option<Box<dyn event::AttachedSubscriber>>,
     /// The context passed to the connection supervisor
     pub supervisor_context: &'a supervisor::Context<'a>,
     /// The datagram provider for the endpoint
     pub datagram_endpoint: &'a mut Cfg::DatagramEndpoint,
     /// The dc provider for the endpoint
     pub dc_endpoint: &'a mut Cfg::DcEndpoint,
+    /// The custom frame provider for the endpoint
+    pub custom_frame_endpoint: &'a mut Cfg::CustomFrameEndpoint,
     /// The event subscriber for the endpoint
     pub event_subscriber: &'a mut Cfg::EventSubscriber,
 }
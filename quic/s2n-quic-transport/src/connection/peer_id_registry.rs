@@ -703,11 +703,12 @@ pub mod testing {
     ) -> PeerIdRegistry {
         let mut random_generator = random::testing::Generator(123);
 
-        let mut registry = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server)
-            .create_client_peer_id_registry(
-                InternalConnectionIdGenerator::new().generate_id(),
-                true,
-            );
+        let mut registry =
+            ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false)
+                .create_client_peer_id_registry(
+                    InternalConnectionIdGenerator::new().generate_id(),
+                    true,
+                );
         registry.register_initial_connection_id(initial_id);
         if let Some(token) = stateless_reset_token {
             registry.register_initial_stateless_reset_token(token);
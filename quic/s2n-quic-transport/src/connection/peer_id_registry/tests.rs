@@ -237,7 +237,7 @@ fn ignore_retire_prior_to_that_does_not_increase() {
 fn retire_connection_id_when_retire_prior_to_increases() {
     let id_1 = id(b"id01");
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     // Even if `rotate_handshake_connection_id` is false, the handshake connection ID should be retired if the peer requests it
     let mut reg = mapper
         .create_client_peer_id_registry(InternalConnectionIdGenerator::new().generate_id(), false);
@@ -369,7 +369,7 @@ fn retire_initial_id_when_new_connection_id_available_rotate_handshake_connectio
     let id_1 = id(b"id01");
     let mut random_generator = random::testing::Generator(123);
 
-    let mut reg = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server)
+    let mut reg = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false)
         .create_client_peer_id_registry(InternalConnectionIdGenerator::new().generate_id(), false);
     reg.register_initial_connection_id(id_1);
     assert!(!reg.rotate_handshake_connection_id);
@@ -386,7 +386,7 @@ fn retire_initial_id_when_new_connection_id_available_rotate_handshake_connectio
 pub fn initial_id_is_active() {
     let id_1 = id(b"id01");
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     let reg = mapper.create_server_peer_id_registry(
         InternalConnectionIdGenerator::new().generate_id(),
         id_1,
@@ -400,7 +400,7 @@ pub fn initial_id_is_active() {
 pub fn retired_id_is_not_active() {
     let id_1 = id(b"id01");
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     let mut reg = mapper.create_server_peer_id_registry(
         InternalConnectionIdGenerator::new().generate_id(),
         id_1,
@@ -416,7 +416,7 @@ pub fn retired_id_is_not_active() {
 pub fn unknown_id_is_not_active() {
     let id_1 = id(b"id01");
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     let reg = mapper.create_server_peer_id_registry(
         InternalConnectionIdGenerator::new().generate_id(),
         id_1,
@@ -432,7 +432,7 @@ pub fn unknown_id_is_not_active() {
 pub fn consume_new_id_should_return_id() {
     let id_1 = id(b"id01");
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     let mut reg = mapper.create_server_peer_id_registry(
         InternalConnectionIdGenerator::new().generate_id(),
         id_1,
@@ -466,7 +466,7 @@ pub fn consume_new_id_should_return_id() {
 pub fn consume_new_id_should_error_if_no_ids_are_available() {
     let id_1 = id(b"id01");
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     let mut reg = mapper.create_server_peer_id_registry(
         InternalConnectionIdGenerator::new().generate_id(),
         id_1,
@@ -522,7 +522,7 @@ fn error_conversion() {
 #[test]
 pub fn client_peer_id_registry_should_not_register_cid() {
     let mut random_generator = random::testing::Generator(123);
-    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+    let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false);
     let reg = mapper
         .create_client_peer_id_registry(InternalConnectionIdGenerator::new().generate_id(), true);
 
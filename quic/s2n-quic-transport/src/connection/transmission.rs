@@ -8,6 +8,7 @@ use crate::{
 use core::time::Duration;
 use s2n_codec::{Encoder, EncoderBuffer};
 use s2n_quic_core::{
+    connection::limits::InitialPadding,
     event::{self, ConnectionPublisher as _},
     frame::ack_elicitation::AckElicitable,
     inet::ExplicitCongestionNotification,
@@ -27,7 +28,13 @@ pub struct ConnectionTransmissionContext<'a, 'sub, Config: endpoint::Config> {
     pub outcome: &'a mut transmission::Outcome,
     pub ecn: ExplicitCongestionNotification,
     pub min_packet_len: Option<usize>,
+    pub initial_padding: InitialPadding,
+    pub min_datagram_size: usize,
     pub transmission_mode: transmission::Mode,
+    /// Only ACK frames and loss recovery probes are written
+    pub urgent_only: bool,
+    /// Sealing of 1-RTT packets is deferred until the TX queue finishes the payload
+    pub defer_header_protection: bool,
     pub publisher: &'a mut event::ConnectionPublisherSubscriber<'sub, Config::EventSubscriber>,
     pub packet_interceptor: &'a mut Config::PacketInterceptor,
 }
@@ -135,6 +142,13 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 //# Probe packets MUST NOT be blocked by the congestion controller.
                 self.context.transmission_mode = transmission::Mode::LossRecoveryProbing;
                 transmission::Constraint::None
+            } else if self.context.urgent_only {
+                // ACK frames are the only frames written while congestion limited, so the
+                // constraint restricts the packets to them
+                self.context
+                    .path()
+                    .transmission_constraint()
+                    .max(transmission::Constraint::CongestionLimited)
             } else {
                 self.context.path().transmission_constraint()
             };
@@ -150,6 +164,16 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
 
         // limit the number of retries to the MAX_BURST_PACKETS
         for _ in 0..MAX_BURST_PACKETS {
+            let defer_header_protection = self.context.defer_header_protection
+                && space_manager
+                    .application_mut()
+                    .map(|(space, _)| {
+                        space
+                            .deferred_header_protection
+                            .on_datagram_start(gso_offset)
+                    })
+                    .is_some();
+
             let encoder = EncoderBuffer::new(&mut buffer[..max_datagram_size]);
             let initial_capacity = encoder.capacity();
 
@@ -171,31 +195,54 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             //# eliciting Initial packets to at least the smallest allowed maximum
             //# datagram size of 1200 bytes.
 
-            // If the transmission contains an Initial packet, it must be padded. However, we should
-            // add padding only if necessary, after packets from all packet number spaces have been
-            // coalesced. Therefore, after confirming there will be an Initial packet, we first check
-            // if there will be an ApplicationData packet, since those packets come at the end of the
-            // datagram. If there is no ApplicationData packet, the Handshake packet will come at the
-            // end, so we check that next. Finally, if there is no ApplicationData or Handshake packet
-            // to transmit, the Initial packet itself will be padded.
+            // If the transmission contains an Initial packet, it must be padded. However, unless
+            // the Initial packet was configured to carry the padding, we should add padding only if
+            // necessary, after packets from all packet number spaces have been coalesced. Therefore,
+            // after confirming there will be an Initial packet, we first check if there will be an
+            // ApplicationData packet, since those packets come at the end of the datagram. If there
+            // is no ApplicationData packet, the Handshake packet will come at the end, so we check
+            // that next. Finally, if there is no ApplicationData or Handshake packet to transmit,
+            // the Initial packet itself will be padded.
+            //
+            // Datagrams without an Initial packet are padded in the same way up to the configured
+            // minimum datagram size.
+            let has_initial = has_transmission(space_manager.initial(), transmission_constraint);
+            let min_datagram_len = if has_initial {
+                max_datagram_size
+            } else {
+                self.context.min_datagram_size
+            };
             let mut pn_space_to_pad = {
-                let needs_padding =
-                    has_transmission(space_manager.initial(), transmission_constraint);
-
-                if !needs_padding {
-                    // There is no Initial packet, so no padding is needed
+                if min_datagram_len == 0 {
+                    // There is no Initial packet or minimum size, so no padding is needed
                     None
+                } else if has_initial && self.context.initial_padding == InitialPadding::Initial {
+                    Some(PacketNumberSpace::Initial)
                 } else if has_transmission(space_manager.application(), transmission_constraint) {
                     Some(PacketNumberSpace::ApplicationData)
                 } else if has_transmission(space_manager.handshake(), transmission_constraint) {
                     Some(PacketNumberSpace::Handshake)
-                } else {
+                } else if has_initial {
                     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9
                     //# These packets MAY also include PADDING frames.
                     Some(PacketNumberSpace::Initial)
+                } else {
+                    // The ApplicationData space can still write a packet, even if it didn't
+                    // report interest under the current transmission constraint, so it carries
+                    // the padding if nothing else is written
+                    Some(PacketNumberSpace::ApplicationData)
                 }
             };
 
+            // Returns the length the padded packet needs to be for the datagram to reach the
+            // minimum length
+            let padded_len = |encoder: &EncoderBuffer| {
+                let datagram_len = initial_capacity - encoder.capacity();
+                min_datagram_len
+                    .saturating_sub(datagram_len)
+                    .min(encoder.capacity())
+            };
+
             //= https://www.rfc-editor.org/rfc/rfc9001#section-4
             //# When packets of different types need to be sent,
             //# endpoints SHOULD use coalesced packets to send them in the same UDP
@@ -204,6 +251,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
 
             let is_mtu_probing = self.context.transmission_mode.is_mtu_probing();
 
+            let mut initial_sent = false;
             let encoder = if let Some((space, handshake_status)) = space_manager
                 .initial_mut()
                 // MTU probes are only sent in the Application Space
@@ -211,7 +259,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             {
                 self.context.min_packet_len = pn_space_to_pad
                     .filter(|pn_space| pn_space.is_initial())
-                    .map(|_| encoder.capacity());
+                    .map(|_| padded_len(&encoder));
 
                 match space.on_transmit(
                     &mut self.context,
@@ -233,6 +281,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                             pn_space_to_pad = None;
                         }
                         *self.context.outcome += outcome;
+                        initial_sent = true;
                         encoder
                     }
                     Err(PacketEncodingError::PacketNumberTruncationError(encoder)) => {
@@ -256,6 +305,12 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                 encoder
             };
 
+            if initial_sent {
+                space_manager
+                    .handshake_timing
+                    .on_initial_sent(self.context.timestamp);
+            }
+
             let encoder = if let Some((space, handshake_status)) = space_manager
                 .handshake_mut()
                 // MTU probes are only sent in the Application Space
@@ -263,7 +318,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             {
                 self.context.min_packet_len = pn_space_to_pad
                     .filter(|pn_space| pn_space.is_handshake())
-                    .map(|_| encoder.capacity());
+                    .map(|_| padded_len(&encoder));
 
                 let encoder = match space.on_transmit(
                     &mut self.context,
@@ -326,7 +381,7 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
             let encoder = if let Some((space, handshake_status)) = space_manager.application_mut() {
                 self.context.min_packet_len = pn_space_to_pad
                     .filter(|pn_space| pn_space.is_application_data())
-                    .map(|_| encoder.capacity());
+                    .map(|_| padded_len(&encoder));
 
                 // Pad the packet when sending path validation frames so that MTU is also validated.
                 let path = &self.context.path_manager[self.context.path_id];
@@ -350,6 +405,12 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
                     self.context.min_packet_len = Some(encoder.capacity());
                 }
 
+                if defer_header_protection {
+                    space
+                        .deferred_header_protection
+                        .on_packet_start(initial_capacity - encoder.capacity());
+                }
+
                 match space.on_transmit(
                     &mut self.context,
                     transmission_constraint,
@@ -428,9 +489,34 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
 
                 // if the packet interceptor cleared the encoder buffer, then try again
                 if encoder.is_empty() {
+                    debug_assert!(
+                        !defer_header_protection,
+                        "datagrams with deferred header protection can't be modified by the interceptor"
+                    );
                     continue;
                 }
 
+                let is_deferred = defer_header_protection
+                    && space_manager.application_mut().map_or(false, |(space, _)| {
+                        space.deferred_header_protection.on_datagram_end(
+                            gso_offset,
+                            encoder.len(),
+                            self.context.path_id,
+                        )
+                    });
+
+                // the payload of a datagram with deferred packets is published once the
+                // packets are protected
+                if !is_deferred {
+                    self.context.publisher.on_datagram_payload_sent(
+                        event::builder::DatagramPayloadSent {
+                            local_addr: local_address.into_event(),
+                            remote_addr: remote_address.into_event(),
+                            payload: encoder.as_mut_slice(),
+                        },
+                    );
+                }
+
                 encoder.len()
             };
 
@@ -439,6 +525,40 @@ impl<'a, 'sub, Config: endpoint::Config> tx::Message for ConnectionTransmission<
 
         Err(tx::Error::EmptyPayload)
     }
+
+    #[inline]
+    fn finish_payload(&mut self, payload: &mut [u8]) {
+        if self.context.defer_header_protection {
+            finish_payload(
+                self.space_manager,
+                self.context.path_manager,
+                self.context.publisher,
+                payload,
+            );
+        }
+    }
+}
+
+/// Seals the deferred packets in a finished GSO `payload`
+#[inline]
+pub fn finish_payload<Config: endpoint::Config, Pub: event::ConnectionPublisher>(
+    space_manager: &mut PacketSpaceManager<Config>,
+    path_manager: &path::Manager<Config>,
+    publisher: &mut Pub,
+    payload: &mut [u8],
+) {
+    use s2n_quic_core::event::IntoEvent;
+
+    if let Some((space, _)) = space_manager.application_mut() {
+        space.finish_payload(payload, |path_id, datagram| {
+            let path = &path_manager[path_id];
+            publisher.on_datagram_payload_sent(event::builder::DatagramPayloadSent {
+                local_addr: path.local_address().into_event(),
+                remote_addr: path.remote_address().into_event(),
+                payload: datagram,
+            });
+        });
+    }
 }
 
 fn has_transmission<P: transmission::interest::Provider>(
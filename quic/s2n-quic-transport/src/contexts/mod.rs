@@ -22,6 +22,8 @@ pub enum OnTransmitError {
 pub enum ConnectionOnTransmitError {
     /// It was not possible to obtain a datagram to write into
     NoDatagram,
+    /// The connection exhausted its transmission quantum before it finished transmitting
+    QuantumExhausted,
 }
 
 /// The context parameter which is passed from all external API calls
@@ -5,8 +5,8 @@
 
 use crate::{connection, stream};
 use s2n_quic_core::{
-    crypto::tls, datagram, dc, endpoint, event, packet, path, path::mtu, random,
-    recovery::congestion_controller, stateless_reset,
+    crypto::tls, custom_frame, datagram, dc, demux, endpoint, event, packet, path, path::mtu,
+    random, recovery::congestion_controller, stateless_reset, transport,
 };
 
 /// Configuration parameters for a QUIC endpoint
@@ -26,6 +26,8 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type RandomGenerator: random::Generator;
     /// The validation token format
     type TokenFormat: s2n_quic_core::token::Format;
+    /// The cache of tokens received from servers
+    type TokenCache: s2n_quic_core::token::cache::Cache;
     /// The endpoint limits
     type EndpointLimits: endpoint::Limiter;
     /// The connection limits
@@ -48,6 +50,12 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type DatagramEndpoint: datagram::Endpoint;
     /// The dc implementation for the endpoint
     type DcEndpoint: dc::Endpoint;
+    /// The custom transport parameters implementation for the endpoint
+    type CustomTransportParameters: transport::parameters::custom::Endpoint;
+    /// The custom frame implementation for the endpoint
+    type CustomFrameEndpoint: custom_frame::Endpoint;
+    /// The demultiplexer for datagrams of other protocols sharing the endpoint's socket
+    type Demultiplexer: demux::Demultiplexer;
 
     /// The type of the local endpoint
     const ENDPOINT_TYPE: endpoint::Type;
@@ -79,6 +87,9 @@ pub struct Context<'a, Cfg: Config> {
     /// Token generator / validator
     pub token: &'a mut Cfg::TokenFormat,
 
+    /// Stores tokens received from servers
+    pub token_cache: &'a mut Cfg::TokenCache,
+
     /// The connection limits
     pub connection_limits: &'a mut Cfg::ConnectionLimits,
 
@@ -97,4 +108,10 @@ pub struct Context<'a, Cfg: Config> {
     pub datagram: &'a mut Cfg::DatagramEndpoint,
 
     pub dc: &'a mut Cfg::DcEndpoint,
+
+    pub custom_transport_parameters: &'a mut Cfg::CustomTransportParameters,
+
+    pub custom_frame: &'a mut Cfg::CustomFrameEndpoint,
+
+    pub demux: &'a mut Cfg::Demultiplexer,
 }
@@ -5,14 +5,20 @@ use crate::{
     connection::{self, Connection},
     endpoint::handle::ConnectorSender,
 };
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::{
     fmt,
     future::Future,
+    hash::{Hash, Hasher},
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 use futures_channel::oneshot;
-use s2n_quic_core::{application::ServerName, inet::SocketAddress, path::RemoteAddress};
+use s2n_quic_core::{
+    application::ServerName, connection::PriorityClass, event, inet::SocketAddress,
+    path::RemoteAddress,
+};
 
 /// Held by connection Attempt future. Used to receive the actual connection.
 pub(crate) type ConnectionReceiver = oneshot::Receiver<Result<Connection, connection::Error>>;
@@ -26,6 +32,10 @@ pub struct Connect {
     pub(crate) remote_address: RemoteAddress,
     pub(crate) server_name: Option<ServerName>,
     pub(crate) deduplicate: bool,
+    pub(crate) alternate_addresses: Vec<RemoteAddress>,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) priority_class: Option<PriorityClass>,
+    pub(crate) event_subscriber: Option<AttachedSubscriber>,
 }
 
 impl fmt::Display for Connect {
@@ -51,6 +61,10 @@ impl Connect {
             remote_address: addr.into().into(),
             server_name: None,
             deduplicate: false,
+            alternate_addresses: Vec::new(),
+            retry_policy: RetryPolicy::default(),
+            priority_class: None,
+            event_subscriber: None,
         }
     }
 
@@ -80,6 +94,236 @@ impl Connect {
     }
 }
 
+impl Connect {
+    /// Adds an address which is tried if an attempt to the previous address fails
+    ///
+    /// Addresses are tried in the order they were added, starting with the address the `Connect`
+    /// was created with, and wrap around once all of them have been attempted. Alternate
+    /// addresses are only used if the [`RetryPolicy`] allows more than one attempt.
+    #[must_use]
+    pub fn with_alternate_address<Addr: Into<SocketAddress>>(mut self, addr: Addr) -> Self {
+        self.alternate_addresses.push(addr.into().into());
+        self
+    }
+
+    /// Specifies how the connection attempt is retried if the handshake fails to complete
+    #[must_use]
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy,
+            ..self
+        }
+    }
+
+    /// Specifies the class the connection is scheduled in on the endpoint
+    ///
+    /// This overrides the class configured with the endpoint's limits.
+    #[must_use]
+    pub fn with_priority_class(self, priority_class: PriorityClass) -> Self {
+        Self {
+            priority_class: Some(priority_class),
+            ..self
+        }
+    }
+
+    /// Attaches an event subscriber to the connection, in addition to the endpoint's subscriber
+    ///
+    /// The subscriber only receives the events of this connection, which makes it possible to
+    /// enable verbose debugging for a single peer. Each connection attempt is given its own
+    /// clone of the subscriber.
+    #[must_use]
+    pub fn with_event_subscriber<Sub>(self, subscriber: Sub) -> Self
+    where
+        Sub: event::Subscriber + Clone + Sync,
+    {
+        Self {
+            event_subscriber: Some(AttachedSubscriber::new(subscriber)),
+            ..self
+        }
+    }
+
+    /// Moves the attempt on to the next address in the list
+    pub(crate) fn rotate_address(&mut self) {
+        if self.alternate_addresses.is_empty() {
+            return;
+        }
+
+        let next = self.alternate_addresses.remove(0);
+        let previous = core::mem::replace(&mut self.remote_address, next);
+        self.alternate_addresses.push(previous);
+    }
+}
+
+/// Creates an [`event::AttachedSubscriber`] for each attempt of a [`Connect`]
+#[derive(Clone)]
+pub(crate) struct AttachedSubscriber(
+    Arc<dyn Fn() -> Box<dyn event::AttachedSubscriber> + Send + Sync>,
+);
+
+impl AttachedSubscriber {
+    fn new<Sub>(subscriber: Sub) -> Self
+    where
+        Sub: event::Subscriber + Clone + Sync,
+    {
+        Self(Arc::new(move || {
+            Box::new(event::Attached::new(subscriber.clone()))
+        }))
+    }
+
+    #[inline]
+    pub(crate) fn create(&self) -> Box<dyn event::AttachedSubscriber> {
+        (self.0)()
+    }
+
+    /// Returns the address of the factory, which identifies the subscriber
+    #[inline]
+    fn addr(&self) -> *const () {
+        Arc::as_ptr(&self.0) as *const ()
+    }
+}
+
+impl fmt::Debug for AttachedSubscriber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AttachedSubscriber").finish()
+    }
+}
+
+impl PartialEq for AttachedSubscriber {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl Eq for AttachedSubscriber {}
+
+impl Hash for AttachedSubscriber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state);
+    }
+}
+
+/// Controls how a client retries a connection attempt
+///
+/// An attempt is retried when the handshake times out or the peer is found to be unreachable.
+/// Each retry waits for an exponentially increasing backoff, starting at
+/// [`initial_backoff`](Self::with_initial_backoff) and doubling up to
+/// [`max_backoff`](Self::with_max_backoff).
+///
+/// Attempts can also be [raced](Self::with_race_delay) against each other, in which case the
+/// next address is tried while the previous attempt is still in progress and the first handshake
+/// to complete wins.
+///
+/// Note that Retry packets sent by the server are always handled transparently as part of a
+/// single attempt.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    attempt_timeout: Option<Duration>,
+    race_delay: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl RetryPolicy {
+    pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+    pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+    /// Creates a policy which makes at most `max_attempts` connection attempts
+    ///
+    /// A value of `1`, which is the default, disables retries.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: Self::DEFAULT_INITIAL_BACKOFF,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            attempt_timeout: None,
+            race_delay: None,
+        }
+    }
+
+    /// Sets the amount of time to wait before the first retry
+    #[must_use]
+    pub fn with_initial_backoff(self, initial_backoff: Duration) -> Self {
+        Self {
+            initial_backoff,
+            ..self
+        }
+    }
+
+    /// Sets the maximum amount of time to wait between attempts
+    #[must_use]
+    pub fn with_max_backoff(self, max_backoff: Duration) -> Self {
+        Self {
+            max_backoff,
+            ..self
+        }
+    }
+
+    /// Sets how long each attempt waits for its handshake to complete before it fails
+    ///
+    /// This overrides the `max_handshake_duration` of the endpoint's limits so a single
+    /// unresponsive address doesn't hold up the attempts to the remaining addresses.
+    #[must_use]
+    pub fn with_attempt_timeout(self, attempt_timeout: Duration) -> Self {
+        Self {
+            attempt_timeout: Some(attempt_timeout),
+            ..self
+        }
+    }
+
+    /// Starts an attempt to the next address if the current one hasn't completed within `delay`
+    ///
+    /// The earlier attempts keep going, so several addresses race to complete a handshake. The
+    /// first connection to be established is returned and the others are closed once their
+    /// handshakes complete. An attempt which fails before the next address has joined the race
+    /// hands over to it immediately, without waiting for a backoff.
+    ///
+    /// Every raced attempt counts towards [`max_attempts`](Self::max_attempts).
+    #[must_use]
+    pub fn with_race_delay(self, delay: Duration) -> Self {
+        Self {
+            race_delay: Some(delay),
+            ..self
+        }
+    }
+
+    /// Returns the maximum number of connection attempts
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the maximum amount of time to wait between attempts
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Returns how long each attempt waits for its handshake, if overridden
+    pub fn attempt_timeout(&self) -> Option<Duration> {
+        self.attempt_timeout
+    }
+
+    /// Returns the delay after which the next address joins the race, if racing is enabled
+    pub fn race_delay(&self) -> Option<Duration> {
+        self.race_delay
+    }
+
+    /// Returns the amount of time to wait before the given retry
+    ///
+    /// `retry` starts at `1` for the first retry.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31);
+        self.initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff)
+    }
+}
+
 /// Make it easy for applications to create a connection attempt without importing the `Connect` struct
 impl<T: Into<SocketAddress>> From<T> for Connect {
     fn from(addr: T) -> Self {
@@ -92,6 +336,11 @@ impl<T: Into<SocketAddress>> From<T> for Connect {
 pub(crate) struct Request {
     pub connect: Connect,
     pub sender: ConnectionSender,
+    /// The number of attempts which have already been made for this request
+    pub attempt: u32,
+    /// Set if the attempt races earlier attempts of the same request, which already track its
+    /// outcome
+    pub raced: bool,
 }
 
 #[must_use = "futures do nothing unless you `.await` or poll them"]
@@ -116,6 +365,8 @@ impl Attempt {
         let request = Request {
             connect,
             sender: response,
+            attempt: 0,
+            raced: false,
         };
         Self {
             state: AttemptState::Connect(request, opener.clone(), receiver),
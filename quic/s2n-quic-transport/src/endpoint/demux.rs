@@ -0,0 +1,133 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Queues responses to datagrams which were handed to the application's demultiplexer
+
+use crate::endpoint;
+use alloc::{collections::VecDeque, vec::Vec};
+use s2n_quic_core::{demux, inet::ExplicitCongestionNotification, io::tx, path, time};
+
+#[derive(Debug)]
+pub struct Dispatch<Path: path::Handle> {
+    transmissions: VecDeque<Transmission<Path>>,
+    max_transmissions: usize,
+}
+
+impl<Path: path::Handle> Default for Dispatch<Path> {
+    fn default() -> Self {
+        Self::new(endpoint::DEFAULT_MAX_PEERS)
+    }
+}
+
+impl<Path: path::Handle> Dispatch<Path> {
+    pub fn new(max_transmissions: usize) -> Self {
+        Self {
+            transmissions: VecDeque::new(),
+            max_transmissions,
+        }
+    }
+
+    /// Returns a responder which queues datagrams to be sent on `path`
+    pub fn responder(&mut self, path: Path) -> Responder<Path> {
+        Responder {
+            dispatch: self,
+            path,
+        }
+    }
+
+    pub fn on_transmit<Tx: tx::Queue<Handle = Path>>(&mut self, queue: &mut Tx) {
+        while let Some(transmission) = self.transmissions.pop_front() {
+            if queue.push(&transmission).is_err() {
+                self.transmissions.push_front(transmission);
+                return;
+            }
+        }
+    }
+}
+
+pub struct Responder<'a, Path: path::Handle> {
+    dispatch: &'a mut Dispatch<Path>,
+    path: Path,
+}
+
+impl<Path: path::Handle> demux::Responder for Responder<'_, Path> {
+    #[inline]
+    fn respond(&mut self, payload: &[u8]) {
+        if self.dispatch.transmissions.len() >= self.dispatch.max_transmissions {
+            return;
+        }
+
+        self.dispatch.transmissions.push_back(Transmission {
+            path: self.path,
+            payload: payload.to_vec(),
+        });
+    }
+}
+
+#[derive(Debug)]
+struct Transmission<Path: path::Handle> {
+    path: Path,
+    payload: Vec<u8>,
+}
+
+impl<Path: path::Handle> tx::Message for &Transmission<Path> {
+    type Handle = Path;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        &self.path
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        Default::default()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> time::Duration {
+        Default::default()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, _segment_count: usize) -> bool {
+        segment_len >= self.payload.len()
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(&self.payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{demux::Responder as _, inet::SocketAddress, path::RemoteAddress};
+
+    #[test]
+    fn responder_limit_test() {
+        let mut dispatch = Dispatch::new(2);
+        let path = RemoteAddress::from(SocketAddress::default());
+
+        let mut responder = dispatch.responder(path);
+        for _ in 0..3 {
+            responder.respond(b"response");
+        }
+
+        // responses beyond the limit are dropped
+        assert_eq!(dispatch.transmissions.len(), 2);
+        assert!(dispatch
+            .transmissions
+            .iter()
+            .all(|transmission| transmission.payload == b"response"));
+    }
+}
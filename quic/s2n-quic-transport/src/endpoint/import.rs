@@ -0,0 +1,268 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows server applications to resume connections handed off by another endpoint
+
+use crate::{
+    connection::{
+        self,
+        limits::{ConnectionInfo as LimitsInfo, Limiter as _},
+        Connection, InternalConnectionId, Trait as _,
+    },
+    endpoint::{
+        self,
+        connect::{ConnectionReceiver, ConnectionSender},
+    },
+    recovery::congestion_controller::{self, Endpoint as _},
+    space::PacketSpaceManager,
+};
+use alloc::boxed::Box;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_channel::{mpsc, oneshot};
+use s2n_quic_core::{
+    connection::{close, handoff, id::Generator as _},
+    event::{self, supervisor, ConnectionPublisher as _, IntoEvent as _, Subscriber as _},
+    path::{Handle as _, RemoteAddress},
+    time::Timestamp,
+};
+
+/// Held by library. Used to receive the connections to import from the application.
+pub(crate) type ImportReceiver = mpsc::UnboundedReceiver<Request>;
+/// Held by application. Used to submit the connections to import to the library.
+pub(crate) type ImportSender = mpsc::UnboundedSender<Request>;
+
+#[derive(Debug)]
+pub(crate) struct Request {
+    pub state: Box<handoff::State>,
+    pub sender: ConnectionSender,
+}
+
+/// Imports connections handed off by another endpoint into a server endpoint
+#[derive(Clone, Debug)]
+pub struct Importer {
+    sender: ImportSender,
+}
+
+impl Importer {
+    pub(crate) fn new(sender: ImportSender) -> Self {
+        Self { sender }
+    }
+
+    /// Resumes the connection on the endpoint and returns a future to be awaited
+    ///
+    /// The future resolves once the connection is ready to be used by the application.
+    pub fn import(&self, state: handoff::State) -> Import {
+        let (sender, receiver) = oneshot::channel();
+        let request = Request {
+            state: Box::new(state),
+            sender,
+        };
+
+        // If the endpoint has closed, the sender is dropped with the request and the receiver
+        // reports the error
+        let _ = self.sender.unbounded_send(request);
+
+        Import { receiver }
+    }
+}
+
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Import {
+    receiver: ConnectionReceiver,
+}
+
+impl Future for Import {
+    type Output = Result<Connection, connection::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(Ok(res)) => Poll::Ready(res),
+            // The endpoint has closed
+            Poll::Ready(Err(_)) => Poll::Ready(Err(connection::Error::unspecified())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<Cfg: endpoint::Config> endpoint::Endpoint<Cfg> {
+    /// Creates a connection for the state handed off by another endpoint
+    pub(super) fn create_imported_connection(&mut self, request: Request, timestamp: Timestamp) {
+        debug_assert!(
+            Cfg::ENDPOINT_TYPE.is_server(),
+            "only servers can import connections"
+        );
+
+        let Request { state, sender } = request;
+
+        match self.import_connection(&state, timestamp) {
+            Ok((connection, internal_connection_id)) => {
+                self.connections.insert_imported_connection(
+                    connection,
+                    internal_connection_id,
+                    sender,
+                );
+            }
+            Err(error) => {
+                let _ = sender.send(Err(error));
+            }
+        }
+    }
+
+    fn import_connection(
+        &mut self,
+        state: &handoff::State,
+        timestamp: Timestamp,
+    ) -> Result<(Cfg::Connection, InternalConnectionId), connection::Error> {
+        let remote_address = RemoteAddress::from(state.remote_address);
+        let internal_connection_id = self.connections.reserve_id();
+
+        let context = self.config.context();
+        let expiration = context
+            .connection_id_format
+            .lifetime()
+            .map(|duration| timestamp + duration);
+        let rotate_handshake_connection_id = context
+            .connection_id_format
+            .rotate_handshake_connection_id();
+
+        // The connection IDs were issued by the other endpoint, so they keep their sequence
+        // numbers and stateless reset tokens
+        let mut local_id_registry = self.connection_id_mapper.create_local_id_registry(
+            internal_connection_id,
+            &state.local_connection_id,
+            expiration,
+            state.stateless_reset_token,
+            rotate_handshake_connection_id,
+        );
+
+        if let Some((connection_id, stateless_reset_token)) =
+            state.preferred_address_connection_id.as_ref()
+        {
+            local_id_registry
+                .register_preferred_address_connection_id(
+                    connection_id,
+                    expiration,
+                    *stateless_reset_token,
+                )
+                .map_err(|_| {
+                    connection::Error::invalid_configuration(
+                        "the preferred address connection ID is already in use",
+                    )
+                })?;
+        }
+
+        let peer_id_registry = self.connection_id_mapper.create_server_peer_id_registry(
+            internal_connection_id,
+            state.peer_connection_id,
+            rotate_handshake_connection_id,
+        );
+
+        let wakeup_handle = self
+            .wakeup_queue
+            .create_wakeup_handle(internal_connection_id);
+
+        let context = self.config.context();
+
+        let limits = context
+            .connection_limits
+            .on_connection(&LimitsInfo::new(&remote_address));
+        // Start from the RTT measured during the handshake rather than the configured default
+        let limits = limits
+            .with_initial_round_trip_time(state.smoothed_rtt)
+            .unwrap_or(limits);
+
+        let mtu_config = context
+            .mtu
+            .config(&remote_address)
+            .map_err(|_err| {
+                connection::Error::invalid_configuration(
+                    "MTU provider produced an invalid MTU configuration",
+                )
+            })?
+            .clamp_max_udp_payload_size(limits.max_udp_payload_size(), &remote_address);
+
+        let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+        let congestion_controller = context
+            .congestion_controller
+            .new_congestion_controller(path_info);
+
+        let meta = event::builder::ConnectionMeta {
+            endpoint_type: Cfg::ENDPOINT_TYPE,
+            id: internal_connection_id.into(),
+            timestamp,
+        };
+
+        let supervisor_context = supervisor::Context::new(
+            self.connections.handshake_connections(),
+            self.connections.len(),
+            &remote_address,
+            true,
+        );
+
+        let event_context = context.event_subscriber.create_connection_context(
+            &meta.clone().into_event(),
+            &event::builder::ConnectionInfo {}.into_event(),
+        );
+
+        let mut space_manager = PacketSpaceManager::new_imported(timestamp);
+        space_manager.receive_buffer_pool = self.receive_buffer_pools.get(&limits);
+
+        let connection_parameters = connection::Parameters {
+            internal_connection_id,
+            local_id_registry,
+            peer_id_registry,
+            space_manager,
+            wakeup_handle,
+            peer_connection_id: state.peer_connection_id,
+            local_connection_id: state.local_connection_id,
+            // The local address is filled in once the first datagram is received on this
+            // endpoint
+            path_handle: Cfg::PathHandle::from_remote_address(remote_address),
+            congestion_controller,
+            timestamp,
+            quic_version: state.quic_version,
+            limits,
+            mtu_config,
+            event_context,
+            attached_subscriber: None,
+            supervisor_context: &supervisor_context,
+            event_subscriber: context.event_subscriber,
+            datagram_endpoint: context.datagram,
+            dc_endpoint: context.dc,
+            custom_frame_endpoint: context.custom_frame,
+            open_registry: None,
+        };
+
+        let mut connection = Cfg::Connection::new(connection_parameters)?;
+
+        let context = self.config.context();
+        if let Err(error) = connection.on_imported(
+            state,
+            context.tls,
+            timestamp,
+            context.event_subscriber,
+            context.datagram,
+            context.dc,
+        ) {
+            connection.with_event_publisher(
+                timestamp,
+                None,
+                context.event_subscriber,
+                |publisher, _path| {
+                    publisher.on_connection_closed(event::builder::ConnectionClosed {
+                        error,
+                        linger: close::Linger::Default.into_event(),
+                    });
+                },
+            );
+
+            return Err(error);
+        }
+
+        Ok((connection, internal_connection_id))
+    }
+}
@@ -15,6 +15,7 @@ use crate::{
 use core::convert::TryInto;
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
+    connection::close,
     crypto::{tls, tls::Endpoint as TLSEndpoint, CryptoSuite, InitialKey},
     datagram::{Endpoint, PreConnectionInfo},
     event::{self, supervisor, ConnectionPublisher, EndpointPublisher, IntoEvent, Subscriber as _},
@@ -22,7 +23,13 @@ use s2n_quic_core::{
     packet::initial::ProtectedInitial,
     path::Handle as _,
     stateless_reset::token::Generator as _,
-    transport::{self, parameters::ServerTransportParameters},
+    transport::{
+        self,
+        parameters::{
+            custom, custom::Endpoint as _, DatagramFecSupport, PreferredAddress,
+            ServerTransportParameters,
+        },
+    },
 };
 
 impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
@@ -86,11 +93,10 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             // The destination connection ID on the packet was randomly generated by the client
             // so we'll generate a new initial_connection_id.
             let connection_info = ConnectionInfo::new(&remote_address);
-            initial_connection_id = self
-                .config
-                .context()
+            let context = self.config.context();
+            initial_connection_id = context
                 .connection_id_format
-                .generate(&connection_info);
+                .generate_with_random(&connection_info, context.random_generator);
         }
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
@@ -123,7 +129,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         // TODO handle token with stateless retry
 
-        let internal_connection_id = self.connection_id_generator.generate_id();
+        let internal_connection_id = self.connections.reserve_id();
 
         let initial_connection_id_expiration_time = self
             .config
@@ -132,13 +138,12 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .lifetime()
             .map(|duration| datagram.timestamp + duration);
 
-        let stateless_reset_token = self
-            .config
-            .context()
+        let context = self.config.context();
+        let stateless_reset_token = context
             .stateless_reset_token_generator
-            .generate(initial_connection_id.as_bytes());
+            .generate_with_random(initial_connection_id.as_bytes(), context.random_generator);
 
-        let local_id_registry = self.connection_id_mapper.create_local_id_registry(
+        let mut local_id_registry = self.connection_id_mapper.create_local_id_registry(
             internal_connection_id,
             &initial_connection_id,
             initial_connection_id_expiration_time,
@@ -172,6 +177,41 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         transport_parameters.load_limits(&limits);
 
+        let (ipv4_address, ipv6_address) = limits.preferred_address();
+        if ipv4_address.is_some() || ipv6_address.is_some() {
+            let context = self.config.context();
+            let connection_id = context.connection_id_format.generate_with_random(
+                &ConnectionInfo::new(&remote_address),
+                context.random_generator,
+            );
+            let expiration = context
+                .connection_id_format
+                .lifetime()
+                .map(|duration| datagram.timestamp + duration);
+            let stateless_reset_token = context
+                .stateless_reset_token_generator
+                .generate_with_random(connection_id.as_bytes(), context.random_generator);
+
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+            //# If the preferred_address transport
+            //# parameter is sent, the sequence number of the supplied connection ID
+            //# is 1.
+            local_id_registry
+                .register_preferred_address_connection_id(
+                    &connection_id,
+                    expiration,
+                    stateless_reset_token,
+                )
+                .expect("the preferred address connection ID is registered first");
+
+            transport_parameters.preferred_address = Some(PreferredAddress {
+                ipv4_address,
+                ipv6_address,
+                connection_id: connection_id.into(),
+                stateless_reset_token,
+            });
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
         //# A server includes the Destination Connection ID field from the first
         //# Initial packet it received from the client in the
@@ -226,9 +266,21 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .try_into()
             .expect("Failed to convert max_datagram_frame_size");
 
-        let tls_session = endpoint_context
-            .tls
-            .new_server_session(&transport_parameters);
+        if endpoint_context
+            .datagram
+            .supports_fec(&PreConnectionInfo::new())
+        {
+            transport_parameters.datagram_fec_support = DatagramFecSupport::Enabled;
+        }
+
+        let custom_transport_parameters = endpoint_context
+            .custom_transport_parameters
+            .on_connection(&custom::ConnectionInfo::new(&remote_address));
+
+        let tls_session = endpoint_context.tls.new_server_session(&custom::WithCustom(
+            &transport_parameters,
+            &custom_transport_parameters,
+        ));
 
         let quic_version = packet.version;
 
@@ -269,7 +321,8 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                     event::builder::EndpointConnectionAttemptFailed { error },
                 );
                 error
-            })?;
+            })?
+            .clamp_max_udp_payload_size(limits.max_udp_payload_size(), &remote_address);
 
         let mut publisher = event::ConnectionPublisherSubscriber::new(
             meta,
@@ -283,7 +336,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .congestion_controller
             .new_congestion_controller(path_info);
 
-        let space_manager = PacketSpaceManager::new(
+        let mut space_manager = PacketSpaceManager::new(
             original_destination_connection_id,
             tls_session,
             initial_key,
@@ -291,6 +344,11 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             datagram.timestamp,
             &mut publisher,
         );
+        space_manager.receive_buffer_pool = self.receive_buffer_pools.get(&limits);
+
+        if retry_token_dcid.is_some() {
+            space_manager.handshake_timing.on_retry();
+        }
 
         let connection_parameters = connection::Parameters {
             internal_connection_id,
@@ -310,10 +368,12 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             limits,
             mtu_config,
             event_context,
+            attached_subscriber: None,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
             datagram_endpoint: endpoint_context.datagram,
             dc_endpoint: endpoint_context.dc,
+            custom_frame_endpoint: endpoint_context.custom_frame,
             open_registry: None,
         };
 
@@ -350,6 +410,20 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                     connection::Error::unspecified()
                 })?;
 
+                connection.with_event_publisher(
+                    datagram.timestamp,
+                    Some(path_id),
+                    endpoint_context.event_subscriber,
+                    |publisher, _path| {
+                        publisher.on_packet_received(event::builder::PacketReceived {
+                            packet_header: event::builder::PacketHeader::new(
+                                packet.packet_number,
+                                packet.version,
+                            ),
+                        });
+                    },
+                );
+
                 connection
                     .handle_cleartext_initial_packet(
                         datagram,
@@ -399,13 +473,35 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                 endpoint_context.event_subscriber,
                 |publisher, _path| {
                     use s2n_quic_core::event::builder::ConnectionClosed;
-                    publisher.on_connection_closed(ConnectionClosed { error });
+                    publisher.on_connection_closed(ConnectionClosed {
+                        error,
+                        linger: close::Linger::Default.into_event(),
+                    });
                 },
             );
 
             return Err(error);
         }
 
+        let endpoint_context = self.config.context();
+        if let Err(error) = Self::on_handshake_attempt(
+            &mut connection,
+            endpoint_context.endpoint_limits,
+            self.connections.counts(),
+            datagram.timestamp,
+        ) {
+            // The connection is still persisted below so the CONNECTION_CLOSE frame is sent to
+            // the peer before the connection is finalized
+            connection.close(
+                error,
+                endpoint_context.connection_close_formatter,
+                &mut self.close_packet_buffer,
+                datagram.timestamp,
+                endpoint_context.event_subscriber,
+                endpoint_context.packet_interceptor,
+            );
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9001#section-4.3
         //= type=TODO
         //= tracking-issue=299
@@ -0,0 +1,161 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows applications to inspect and close the connections on an endpoint without holding
+//! their handles
+
+use crate::connection::{self, ConnectionApi, ConnectionApiProvider, InternalConnectionId};
+use alloc::{
+    collections::BTreeMap,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{fmt, time::Duration};
+use s2n_quic_core::{application, connection::close, inet::SocketAddress};
+use std::sync::Mutex;
+
+type Entries = BTreeMap<u64, Weak<dyn ConnectionApiProvider>>;
+
+/// Tracks the active connections on an endpoint
+///
+/// Only weak references are held so the registry doesn't extend the lifetime of any
+/// connection.
+#[derive(Clone, Default)]
+pub(crate) struct Registry(Arc<Mutex<Entries>>);
+
+impl Registry {
+    #[inline]
+    pub fn insert(&self, id: InternalConnectionId, connection: &ConnectionApi) {
+        if let Ok(mut entries) = self.0.lock() {
+            entries.insert(id.into(), Arc::downgrade(connection));
+        }
+    }
+
+    #[inline]
+    pub fn remove(&self, id: InternalConnectionId) {
+        if let Ok(mut entries) = self.0.lock() {
+            entries.remove(&id.into());
+        }
+    }
+
+    #[inline]
+    fn get(&self, id: u64) -> Option<ConnectionApi> {
+        self.0.lock().ok()?.get(&id)?.upgrade()
+    }
+
+    /// Returns strong references to all of the connections
+    ///
+    /// The lock is released before returning so callers don't hold it while acquiring the
+    /// connection locks.
+    #[inline]
+    fn snapshot(&self) -> Vec<(u64, ConnectionApi)> {
+        let entries = match self.0.lock() {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        entries
+            .iter()
+            .filter_map(|(id, connection)| Some((*id, connection.upgrade()?)))
+            .collect()
+    }
+}
+
+/// A summary of an active connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Summary {
+    /// The endpoint-unique identifier of the connection, as returned by `Connection::id`
+    pub id: u64,
+    /// The local address of the active path
+    pub local_address: SocketAddress,
+    /// The peer's address on the active path
+    pub remote_address: SocketAddress,
+}
+
+/// A management handle for the connections on an endpoint
+///
+/// Connections are identified by the value returned by `Connection::id`. Requests for
+/// connections which have already closed are ignored.
+#[derive(Clone)]
+pub struct Manager {
+    registry: Registry,
+}
+
+impl fmt::Debug for Manager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Manager").finish_non_exhaustive()
+    }
+}
+
+impl Manager {
+    pub(crate) fn new(registry: Registry) -> Self {
+        Self { registry }
+    }
+
+    /// Returns a summary of each of the active connections, ordered by id
+    pub fn connections(&self) -> Vec<Summary> {
+        self.registry
+            .snapshot()
+            .into_iter()
+            .filter_map(|(id, connection)| {
+                let local_address = connection.local_address().ok()?;
+                let remote_address = connection.remote_address().ok()?;
+                Some(Summary {
+                    id,
+                    local_address,
+                    remote_address,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns a snapshot of the statistics for a connection
+    ///
+    /// Returns `None` if the connection is no longer active.
+    pub fn statistics(&self, id: u64) -> Option<connection::Statistics> {
+        self.registry.get(id)?.statistics().ok()
+    }
+
+    /// Closes a connection with the provided error code
+    ///
+    /// Returns `false` if the connection is no longer active.
+    pub fn close(&self, id: u64, error_code: application::Error) -> bool {
+        self.close_connection(id, error_code, close::Linger::Default)
+    }
+
+    /// Closes a connection with the provided error code, retaining the closing state for
+    /// `linger` instead of the default of three times the PTO
+    ///
+    /// Returns `false` if the connection is no longer active.
+    pub fn close_with_linger(
+        &self,
+        id: u64,
+        error_code: application::Error,
+        linger: Duration,
+    ) -> bool {
+        self.close_connection(id, error_code, close::Linger::Duration(linger))
+    }
+
+    /// Aborts a connection with the provided error code, without notifying the peer
+    ///
+    /// Returns `false` if the connection is no longer active.
+    pub fn abort(&self, id: u64, error_code: application::Error) -> bool {
+        self.close_connection(id, error_code, close::Linger::Abort)
+    }
+
+    #[inline]
+    fn close_connection(
+        &self,
+        id: u64,
+        error_code: application::Error,
+        linger: close::Linger,
+    ) -> bool {
+        if let Some(connection) = self.registry.get(id) {
+            connection.close_connection(Some(error_code), linger);
+            true
+        } else {
+            false
+        }
+    }
+}
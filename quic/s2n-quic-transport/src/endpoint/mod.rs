@@ -8,8 +8,9 @@ use crate::{
         self,
         limits::{ConnectionInfo as LimitsInfo, Limiter as _},
         ConnectionContainer, ConnectionContainerIterationResult, ConnectionIdMapper,
-        InternalConnectionId, InternalConnectionIdGenerator, Trait as _,
+        InternalConnectionId, Trait as _,
     },
+    contexts::ConnectionOnTransmitError,
     endpoint,
     endpoint::close::CloseHandle,
     recovery::congestion_controller::{self, Endpoint as _},
@@ -31,7 +32,14 @@ use s2n_quic_core::{
     datagram::{Endpoint as DatagramEndpoint, PreConnectionInfo},
     dc,
     dc::Endpoint as _,
-    endpoint::{limits::Outcome, Limiter as _},
+    demux::Demultiplexer as _,
+    endpoint::{
+        limits::{
+            ConnectionCounts, EvictionCheck, EvictionOutcome, HandshakeAttempt, HandshakeOutcome,
+            Outcome, RxOverflow,
+        },
+        Limiter as _,
+    },
     event::{
         self, supervisor, ConnectionPublisher, EndpointPublisher as _, IntoEvent, Subscriber as _,
     },
@@ -39,20 +47,30 @@ use s2n_quic_core::{
     io::{rx, tx},
     packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket},
     path,
-    path::{mtu, Handle as _},
+    path::{mtu, Handle as _, RemoteAddress},
     random::Generator as _,
     stateless_reset::token::{Generator as _, LEN as StatelessResetTokenLen},
     time::{Clock, Timestamp},
-    token::{self, Format},
-    transport::parameters::{ClientTransportParameters, DcSupportedVersions},
+    token::{self, cache::Cache as _, Format},
+    transport::parameters::{
+        custom, custom::Endpoint as _, ClientTransportParameters, DatagramFecSupport,
+        DcSupportedVersions,
+    },
 };
 
 pub mod close;
 mod config;
 pub mod connect;
+mod demux;
 pub mod handle;
+pub mod import;
 mod initial;
+pub mod manager;
+mod overload;
 mod packet_buffer;
+mod receive_buffer;
+mod reconnect;
+mod refuse;
 mod retry;
 mod stateless_reset;
 mod version;
@@ -70,8 +88,6 @@ pub struct Endpoint<Cfg: Config> {
     config: Cfg,
     /// Contains all active connections
     connections: ConnectionContainer<Cfg::Connection, Cfg::ConnectionLock>,
-    /// Creates internal IDs for new connections
-    connection_id_generator: InternalConnectionIdGenerator,
     /// Maps from external to internal connection IDs
     connection_id_mapper: ConnectionIdMapper,
     /// Allows to wakeup the endpoint task which might be blocked on waiting for packets
@@ -83,9 +99,26 @@ pub struct Endpoint<Cfg: Config> {
     /// This is not a local variable in order to reuse the allocated queue capacity in between
     /// [`Endpoint`] interactions.
     dequeued_wakeups: VecDeque<InternalConnectionId>,
+    /// The maximum number of wakeups processed in a single call to `poll_wakeups`. Any remaining
+    /// wakeups stay in [`Self::dequeued_wakeups`] until the next call.
+    max_wakeups: usize,
     version_negotiator: version::Negotiator<Cfg>,
     retry_dispatch: retry::Dispatch<Cfg::PathHandle>,
+    /// Queues CONNECTION_CLOSE packets for connection attempts refused by the endpoint limits
+    refuse_dispatch: refuse::Dispatch<Cfg::PathHandle>,
     stateless_reset_dispatch: stateless_reset::Dispatch<Cfg::PathHandle>,
+    /// Queues responses to datagrams which were handed to the demultiplexer
+    demux_dispatch: demux::Dispatch<Cfg::PathHandle>,
+    /// Tracks client connection attempts which may be retried
+    reconnect_scheduler: reconnect::Scheduler,
+    /// Held so [`import::Importer`]s can be created for server endpoints
+    import_sender: import::ImportSender,
+    /// Receives the connections handed off to a server endpoint by the application
+    import_receiver: import::ImportReceiver,
+    /// Tracks the work being shed after the receive queue overflowed
+    overload: overload::State,
+    /// The pools that stream receive buffers are allocated from
+    receive_buffer_pools: receive_buffer::Pools,
     close_packet_buffer: packet_buffer::Buffer,
 }
 
@@ -113,6 +146,17 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
             self.receive_datagram(&mut header, payload, timestamp)
         });
+
+        queue.for_each_error(|remote_address, error, payload| {
+            let timestamp = *now.get_or_insert_with(|| clock.get_time());
+            self.on_socket_error(&remote_address, error, payload, timestamp)
+        });
+
+        let dropped = queue.take_dropped_datagrams();
+        if dropped > 0 {
+            let timestamp = *now.get_or_insert_with(|| clock.get_time());
+            self.on_rx_overflow(dropped, timestamp);
+        }
     }
 
     fn transmit<Tx, C>(&mut self, queue: &mut Tx, clock: &C)
@@ -132,26 +176,59 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
         let timestamp = clock.get_time();
 
-        self.connections.iterate_transmission_list(|connection| {
-            // if we no longer have capacity, then put the connection at the front of the queue for
-            // next time
-            if !queue.has_capacity() {
-                return ConnectionContainerIterationResult::BreakAndInsertAtFront;
-            }
+        // Connections with time-critical frames send them ahead of the rest of the data in the
+        // queue. Afterwards, they transmit the rest of their data from their priority class.
+        self.connections
+            .iterate_urgent_transmission_list(|connection| {
+                if !queue.has_capacity() {
+                    return ConnectionContainerIterationResult::BreakAndInsertAtFront;
+                }
 
-            // ignore the transmission error and just query the queue capacity instead
-            let _ = connection.on_transmit(
-                queue,
-                timestamp,
-                endpoint_context.event_subscriber,
-                endpoint_context.packet_interceptor,
-            );
+                let _ = connection.on_urgent_transmit(
+                    queue,
+                    timestamp,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
+                );
 
-            // flush the TX queue between connections
-            queue.flush();
+                queue.flush();
 
-            ConnectionContainerIterationResult::Continue
-        });
+                ConnectionContainerIterationResult::Continue
+            });
+
+        // Connections transmit in rounds, each limited to its quantum. Any connection that
+        // exhausts its quantum is moved to the back of the list, so keep going until either the
+        // queue is full or every connection has finished transmitting.
+        loop {
+            let mut is_limited = false;
+
+            self.connections.iterate_transmission_list(|connection| {
+                // if we no longer have capacity, then put the connection at the front of the queue for
+                // next time
+                if !queue.has_capacity() {
+                    return ConnectionContainerIterationResult::BreakAndInsertAtFront;
+                }
+
+                // ignore any other transmission errors and just query the queue capacity instead
+                if let Err(ConnectionOnTransmitError::QuantumExhausted) = connection.on_transmit(
+                    queue,
+                    timestamp,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
+                ) {
+                    is_limited = true;
+                }
+
+                // flush the TX queue between connections
+                queue.flush();
+
+                ConnectionContainerIterationResult::Continue
+            });
+
+            if !is_limited || !queue.has_capacity() {
+                break;
+            }
+        }
 
         if queue.has_capacity() {
             let mut publisher = event::EndpointPublisherSubscriber::new(
@@ -164,8 +241,10 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             );
             self.version_negotiator.on_transmit(queue, &mut publisher);
             self.retry_dispatch.on_transmit(queue, &mut publisher);
+            self.refuse_dispatch.on_transmit(queue, &mut publisher);
             self.stateless_reset_dispatch
                 .on_transmit(queue, &mut publisher);
+            self.demux_dispatch.on_transmit(queue);
         }
     }
 
@@ -190,15 +269,18 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             return Poll::Ready(Err(s2n_quic_core::endpoint::CloseError));
         }
 
-        self.wakeup_queue
-            .poll_pending_wakeups(&mut self.dequeued_wakeups, cx);
+        // Only swap in new wakeups once the previously dequeued batch has been fully processed
+        if self.dequeued_wakeups.is_empty() {
+            self.wakeup_queue
+                .poll_pending_wakeups(&mut self.dequeued_wakeups, cx);
+        }
 
         let mut now: Option<Timestamp> = None;
-        let mut wakeup_count = self.dequeued_wakeups.len();
+        let mut wakeup_count = self.dequeued_wakeups.len().min(self.max_wakeups);
         let close_packet_buffer = &mut self.close_packet_buffer;
         let endpoint_context = self.config.context();
 
-        for internal_id in self.dequeued_wakeups.drain(..) {
+        for internal_id in self.dequeued_wakeups.drain(..wakeup_count) {
             self.connections.with_connection(internal_id, |conn| {
                 let timestamp = match now {
                     Some(now) => now,
@@ -229,18 +311,26 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
         // try to open connection requests from the application
         if Cfg::ENDPOINT_TYPE.is_client() {
-            loop {
+            let timestamp = *now.get_or_insert_with(|| clock.get_time());
+            let mut publisher = event::EndpointPublisherSubscriber::new(
+                event::builder::EndpointMeta {
+                    endpoint_type: Cfg::ENDPOINT_TYPE,
+                    timestamp,
+                },
+                None,
+                self.config.context().event_subscriber,
+            );
+            wakeup_count += self.reconnect_scheduler.poll(cx, timestamp, &mut publisher);
+
+            // any requests exceeding the budget are picked up on the next call
+            while wakeup_count < self.max_wakeups {
                 match self.connections.poll_connection_request(cx) {
                     Poll::Pending => break,
                     Poll::Ready(Some(request)) => {
                         wakeup_count += 1;
 
                         let time = clock.get_time();
-                        if let Err(err) = self.create_client_connection(request, time) {
-                            // TODO report that the connection was not successfully created
-                            // TODO emit event
-                            dbg!(err);
-                        }
+                        self.create_client_connection(request, time);
                     }
                     Poll::Ready(None) => {
                         // the client handle has been dropped so break from loop
@@ -250,6 +340,24 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             }
         }
 
+        // resume the connections handed off to the endpoint
+        if Cfg::ENDPOINT_TYPE.is_server() {
+            while wakeup_count < self.max_wakeups {
+                match futures_core::Stream::poll_next(
+                    core::pin::Pin::new(&mut self.import_receiver),
+                    cx,
+                ) {
+                    Poll::Ready(Some(request)) => {
+                        wakeup_count += 1;
+
+                        let time = clock.get_time();
+                        self.create_imported_connection(request, time);
+                    }
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+        }
+
         if wakeup_count > 0 {
             Poll::Ready(Ok(wakeup_count))
         } else {
@@ -259,7 +367,19 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
     #[inline]
     fn timeout(&self) -> Option<Timestamp> {
-        self.connections.next_expiration()
+        let mut timeout = self.connections.next_expiration();
+
+        for other in [
+            self.reconnect_scheduler.next_expiration(),
+            self.overload.next_expiration(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            timeout = Some(timeout.map_or(other, |timeout| timeout.min(other)));
+        }
+
+        timeout
     }
 
     #[inline]
@@ -267,6 +387,12 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
         self.config.context().mtu.set_endpoint_config(mtu_config);
     }
 
+    #[inline]
+    fn set_max_wakeups(&mut self, max_wakeups: usize) {
+        // always make progress on at least one wakeup
+        self.max_wakeups = max_wakeups.max(1);
+    }
+
     #[inline]
     fn subscriber(&mut self) -> &mut Self::Subscriber {
         self.config.context().event_subscriber
@@ -274,6 +400,58 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 }
 
 impl<Cfg: Config> Endpoint<Cfg> {
+    /// Consults the endpoint limiter once the peer's ClientHello has been processed
+    ///
+    /// Returns an error if the limiter rejected the connection.
+    fn on_handshake_attempt(
+        connection: &mut Cfg::Connection,
+        endpoint_limits: &mut Cfg::EndpointLimits,
+        connection_counts: ConnectionCounts,
+        timestamp: Timestamp,
+    ) -> Result<(), connection::Error> {
+        if !connection.poll_handshake_attempt() {
+            return Ok(());
+        }
+
+        let remote_address = connection.remote_address()?;
+        let server_name = connection.server_name();
+        let application_protocol = connection.application_protocol();
+
+        let attempt = HandshakeAttempt::new(
+            connection_counts,
+            &remote_address,
+            server_name.as_deref(),
+            &application_protocol,
+            timestamp.into_event(),
+        );
+
+        match endpoint_limits.on_handshake_attempt(&attempt) {
+            HandshakeOutcome::Allow { .. } => Ok(()),
+            HandshakeOutcome::Close { error, .. } => Err(error.into()),
+            // HandshakeOutcome is non_exhaustive so allow on things we don't understand
+            _ => Ok(()),
+        }
+    }
+
+    /// Stores the tokens the connection received in NEW_TOKEN frames
+    fn on_new_tokens(connection: &mut Cfg::Connection, token_cache: &mut Cfg::TokenCache) {
+        let tokens = connection.take_new_tokens();
+        if tokens.is_empty() {
+            return;
+        }
+
+        let Ok(remote_address) = connection.remote_address() else {
+            return;
+        };
+        let remote_address = remote_address.unmap();
+        let server_name = connection.server_name();
+        let server = token::cache::Server::new(server_name.as_ref(), &remote_address);
+
+        for token in tokens {
+            token_cache.on_new_token(&server, &token);
+        }
+    }
+
     /// Creates a new QUIC server endpoint using the given configuration
     pub fn new_server(config: Cfg) -> (Self, handle::Acceptor) {
         assert!(
@@ -294,26 +472,62 @@ impl<Cfg: Config> Endpoint<Cfg> {
         (endpoint, handle.connector)
     }
 
+    /// Returns a handle for inspecting and closing the endpoint's connections
+    pub fn manager(&self) -> manager::Manager {
+        self.connections.manager()
+    }
+
+    /// Returns a handle for resuming connections handed off by another server endpoint
+    pub fn importer(&self) -> import::Importer {
+        debug_assert!(
+            Cfg::ENDPOINT_TYPE.is_server(),
+            "only servers can import connections"
+        );
+        import::Importer::new(self.import_sender.clone())
+    }
+
     fn new(mut config: Cfg) -> (Self, handle::Handle) {
         // TODO make this limit configurable
         let max_opening_connections = 1000;
         let (handle, acceptor_sender, connector_receiver, close_handle) =
             handle::Handle::new(max_opening_connections);
 
-        let connection_id_mapper =
-            ConnectionIdMapper::new(config.context().random_generator, Cfg::ENDPOINT_TYPE);
+        let context = config.context();
+        let random_local_ids = context.connection_id_format.generates_random_ids();
+        let connection_id_mapper = ConnectionIdMapper::new(
+            context.random_generator,
+            Cfg::ENDPOINT_TYPE,
+            random_local_ids,
+        );
+
+        let (import_sender, import_receiver) = futures_channel::mpsc::unbounded();
+
+        let timer_granularity = config.context().endpoint_limits.timer_granularity();
+        let connection_capacity = config.context().endpoint_limits.connection_capacity();
 
         let endpoint = Self {
             config,
-            connections: ConnectionContainer::new(acceptor_sender, connector_receiver),
-            connection_id_generator: InternalConnectionIdGenerator::new(),
+            connections: ConnectionContainer::new(
+                acceptor_sender,
+                connector_receiver,
+                timer_granularity,
+                connection_capacity,
+            ),
             connection_id_mapper,
             wakeup_queue: WakeupQueue::new(),
             close_handle,
             dequeued_wakeups: VecDeque::new(),
+            max_wakeups: usize::MAX,
             version_negotiator: version::Negotiator::default(),
             retry_dispatch: retry::Dispatch::default(),
+            refuse_dispatch: refuse::Dispatch::default(),
             stateless_reset_dispatch: stateless_reset::Dispatch::default(),
+            demux_dispatch: demux::Dispatch::default(),
+            reconnect_scheduler: reconnect::Scheduler::default(),
+            import_sender,
+            import_receiver,
+            overload: overload::State::default(),
+            receive_buffer_pools: receive_buffer::Pools::default(),
             close_packet_buffer: Default::default(),
         };
 
@@ -341,8 +555,65 @@ impl<Cfg: Config> Endpoint<Cfg> {
             timestamp.into_event(),
         );
 
+        let outcome = self
+            .config
+            .context()
+            .endpoint_limits
+            .on_connection_attempt(&attempt);
+
+        self.on_limits_outcome(outcome, header, packet, payload_len, timestamp)
+    }
+
+    /// Filters Initial packets which would start a new connection before any cryptographic
+    /// operations are performed
+    fn initial_packet_allowed(
+        &mut self,
+        header: &datagram::Header<Cfg::PathHandle>,
+        packet: &ProtectedInitial,
+        payload_len: usize,
+        timestamp: Timestamp,
+    ) -> Option<()> {
+        let remote_address = header.path.remote_address();
+
+        let info = s2n_quic_core::endpoint::limits::InitialPacket::new(
+            self.connections.counts(),
+            &remote_address,
+            packet.version,
+            packet.destination_connection_id(),
+            packet.source_connection_id(),
+            packet.token(),
+            payload_len,
+            timestamp.into_event(),
+        );
+
+        let mut outcome = self
+            .config
+            .context()
+            .endpoint_limits
+            .on_initial_packet(&info);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
+        //# In response to processing an Initial packet containing a token that
+        //# was provided in a Retry packet, a server cannot send another Retry
+        //# packet; it can only refuse the connection or permit it to proceed.
+        if matches!(outcome, Outcome::Retry { .. }) && !packet.token().is_empty() {
+            outcome = Outcome::drop();
+        }
+
+        self.on_limits_outcome(outcome, header, packet, payload_len, timestamp)
+    }
+
+    /// Applies the outcome returned by the endpoint limiter to a peer's Initial packet
+    fn on_limits_outcome(
+        &mut self,
+        outcome: Outcome,
+        header: &datagram::Header<Cfg::PathHandle>,
+        packet: &ProtectedInitial,
+        payload_len: usize,
+        timestamp: Timestamp,
+    ) -> Option<()> {
+        let remote_address = header.path.remote_address();
         let context = self.config.context();
-        let outcome = context.endpoint_limits.on_connection_attempt(&attempt);
         let mut publisher = event::EndpointPublisherSubscriber::new(
             event::builder::EndpointMeta {
                 endpoint_type: Cfg::ENDPOINT_TYPE,
@@ -366,7 +637,9 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
                 let connection_info = ConnectionInfo::new(&remote_address);
 
-                let local_connection_id = context.connection_id_format.generate(&connection_info);
+                let local_connection_id = context
+                    .connection_id_format
+                    .generate_with_random(&connection_info, context.random_generator);
 
                 self.retry_dispatch.queue::<
                     _,
@@ -381,39 +654,96 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
                 None
             }
-            Outcome::Close { .. } => {
-                //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
-                //= type=TODO
-                //= tracking-issue=270
-                //# If a server refuses to accept a new connection, it SHOULD send an
-                //# Initial packet containing a CONNECTION_CLOSE frame with error code
-                //# CONNECTION_REFUSED.
+            Outcome::Close { .. } | Outcome::CloseWithRetryAfter { .. } => {
+                let retry_after = match outcome {
+                    Outcome::CloseWithRetryAfter { retry_after, .. } => Some(retry_after),
+                    _ => None,
+                };
 
-                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
-                    len: payload_len as u16,
-                    reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
-                });
+                self.refuse_dispatch
+                    .queue::<<<Cfg as Config>::TLSEndpoint as tls::Endpoint>::Session>(
+                        header.path,
+                        packet,
+                        retry_after,
+                    );
+
+                self.overload.on_datagram_dropped(
+                    &mut publisher,
+                    event::builder::EndpointDatagramDropped {
+                        len: payload_len as u16,
+                        reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
+                    },
+                );
 
                 None
             }
             Outcome::Drop { .. } => {
-                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
-                    len: payload_len as u16,
-                    reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
-                });
+                self.overload.on_datagram_dropped(
+                    &mut publisher,
+                    event::builder::EndpointDatagramDropped {
+                        len: payload_len as u16,
+                        reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
+                    },
+                );
                 None
             }
             _ => {
-                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
-                    len: payload_len as u16,
-                    reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
-                });
+                self.overload.on_datagram_dropped(
+                    &mut publisher,
+                    event::builder::EndpointDatagramDropped {
+                        len: payload_len as u16,
+                        reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
+                    },
+                );
                 // Outcome is non_exhaustive so drop on things we don't understand
                 None
             }
         }
     }
 
+    /// Hands a datagram which doesn't belong to QUIC to the demultiplexer
+    ///
+    /// The datagram is dropped if the demultiplexer doesn't recognize it.
+    fn on_non_quic_datagram(
+        &mut self,
+        header: &datagram::Header<Cfg::PathHandle>,
+        payload: &[u8],
+        timestamp: Timestamp,
+    ) {
+        let endpoint_context = self.config.context();
+        let remote_address = header.path.remote_address();
+        let local_address = header.path.local_address();
+        let datagram = s2n_quic_core::demux::Datagram::new(
+            remote_address.into_event(),
+            local_address.into_event(),
+            timestamp,
+        );
+        let mut responder = self.demux_dispatch.responder(header.path);
+
+        if endpoint_context
+            .demux
+            .on_datagram(&datagram, payload, &mut responder)
+        {
+            return;
+        }
+
+        let mut publisher = event::EndpointPublisherSubscriber::new(
+            event::builder::EndpointMeta {
+                endpoint_type: Cfg::ENDPOINT_TYPE,
+                timestamp,
+            },
+            None,
+            endpoint_context.event_subscriber,
+        );
+        self.overload.on_datagram_dropped(
+            &mut publisher,
+            event::builder::EndpointDatagramDropped {
+                len: payload.len() as u16,
+                reason: event::builder::DatagramDropReason::DecodingFailed,
+            },
+        );
+    }
+
     /// Ingests a single datagram
     fn receive_datagram(
         &mut self,
@@ -423,6 +753,12 @@ impl<Cfg: Config> Endpoint<Cfg> {
     ) {
         let endpoint_context = self.config.context();
 
+        // Hand datagrams for other protocols sharing the socket to the application
+        if !endpoint_context.demux.is_quic(payload) {
+            self.on_non_quic_datagram(header, payload, timestamp);
+            return;
+        }
+
         // Try to decode the first packet in the datagram
         let payload_len = payload.len();
         let buffer = DecoderBufferMut::new(payload);
@@ -471,19 +807,9 @@ impl<Cfg: Config> Endpoint<Cfg> {
             let internal_connection_id = self.close_on_matching_stateless_reset(payload, timestamp);
 
             if internal_connection_id.is_none() {
-                // The packet didn't contain a valid stateless token
-                let mut publisher = event::EndpointPublisherSubscriber::new(
-                    event::builder::EndpointMeta {
-                        endpoint_type: Cfg::ENDPOINT_TYPE,
-                        timestamp,
-                    },
-                    None,
-                    self.config.context().event_subscriber,
-                );
-                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
-                    len: payload_len as u16,
-                    reason: event::builder::DatagramDropReason::DecodingFailed,
-                });
+                // The packet didn't contain a valid stateless token so it may belong to another
+                // protocol sharing the socket
+                self.on_non_quic_datagram(header, payload, timestamp);
             };
 
             return;
@@ -506,10 +832,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .on_packet(&header.path, payload_len, &packet, &mut publisher)
             .is_err()
         {
-            publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
-                len: payload_len as u16,
-                reason: event::builder::DatagramDropReason::UnsupportedVersion,
-            });
+            self.overload.on_datagram_dropped(
+                &mut publisher,
+                event::builder::EndpointDatagramDropped {
+                    len: payload_len as u16,
+                    reason: event::builder::DatagramDropReason::UnsupportedVersion,
+                },
+            );
             return;
         }
 
@@ -518,7 +847,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 Some(connection_id) => connection_id,
                 None => {
                     // Ignore the datagram
-                    publisher.on_endpoint_datagram_dropped(
+                    self.overload.on_datagram_dropped(
+                        &mut publisher,
                         event::builder::EndpointDatagramDropped {
                             len: payload_len as u16,
                             reason:
@@ -544,6 +874,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         // TODO validate the connection ID before looking up the connection in the map
         let close_packet_buffer = &mut self.close_packet_buffer;
+        let connection_counts = self.connections.counts();
 
         // Try to lookup the internal connection ID and dispatch the packet
         // to the Connection
@@ -633,6 +964,27 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     return Err(());
                 }
 
+                if let Err(err) = Self::on_handshake_attempt(
+                    conn,
+                    endpoint_context.endpoint_limits,
+                    connection_counts,
+                    datagram.timestamp,
+                ) {
+                    conn.close(
+                        err,
+                        endpoint_context.connection_close_formatter,
+                        close_packet_buffer,
+                        datagram.timestamp,
+                        endpoint_context.event_subscriber,
+                        endpoint_context.packet_interceptor,
+                    );
+                    return Err(());
+                }
+
+                if Cfg::ENDPOINT_TYPE.is_client() {
+                    Self::on_new_tokens(conn, endpoint_context.token_cache);
+                }
+
                 Ok(())
             });
 
@@ -645,17 +997,36 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         match (Cfg::ENDPOINT_TYPE, packet) {
             (s2n_quic_core::endpoint::Type::Server, ProtectedPacket::Initial(packet)) => {
+                if self
+                    .initial_packet_allowed(header, &packet, payload_len, timestamp)
+                    .is_none()
+                {
+                    return;
+                }
+
+                // the filter borrows the endpoint context so it needs to be reacquired
+                let endpoint_context = self.config.context();
+                let mut publisher = event::EndpointPublisherSubscriber::new(
+                    event::builder::EndpointMeta {
+                        endpoint_type: Cfg::ENDPOINT_TYPE,
+                        timestamp,
+                    },
+                    Some(packet.version),
+                    endpoint_context.event_subscriber,
+                );
+
                 let source_connection_id =
                     match connection::PeerId::try_from_bytes(packet.source_connection_id()) {
                         Some(connection_id) => connection_id,
                         None => {
-                            publisher.on_endpoint_datagram_dropped(
-                           event::builder::EndpointDatagramDropped {
-                               len: payload_len as u16,
-                               reason:
-                                   event::builder::DatagramDropReason::InvalidSourceConnectionId,
-                           },
-                       );
+                            self.overload.on_datagram_dropped(
+                            &mut publisher,
+                            event::builder::EndpointDatagramDropped {
+                                len: payload_len as u16,
+                                reason:
+                                    event::builder::DatagramDropReason::InvalidSourceConnectionId,
+                            },
+                        );
                             return;
                         }
                     };
@@ -683,6 +1054,15 @@ impl<Cfg: Config> Endpoint<Cfg> {
                         .validate_token(&mut context, packet.token());
 
                     if outcome.is_none() {
+                        if let Some(source) = context.reused_token() {
+                            publisher.on_endpoint_address_token_reused(
+                                event::builder::EndpointAddressTokenReused {
+                                    remote_address: remote_address.into_event(),
+                                    source: source.into_event(),
+                                },
+                            );
+                        }
+
                         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
                         //= type=TODO
                         //= tracking-issue=344
@@ -696,7 +1076,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
                         //# Instead, the
                         //# server SHOULD immediately close (Section 10.2) the connection with an
                         //# INVALID_TOKEN error.
-                        publisher.on_endpoint_datagram_dropped(
+                        self.overload.on_datagram_dropped(
+                            &mut publisher,
                             event::builder::EndpointDatagramDropped {
                                 len: payload_len as u16,
                                 reason: event::builder::DatagramDropReason::InvalidRetryToken,
@@ -767,10 +1148,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     return;
                 }
 
-                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
-                    len: payload_len as u16,
-                    reason: event::builder::DatagramDropReason::UnknownDestinationConnectionId,
-                });
+                self.overload.on_datagram_dropped(
+                    &mut publisher,
+                    event::builder::EndpointDatagramDropped {
+                        len: payload_len as u16,
+                        reason: event::builder::DatagramDropReason::UnknownDestinationConnectionId,
+                    },
+                );
 
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.1
                 //# Endpoints MAY skip this check if any packet from a datagram is
@@ -818,11 +1202,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
         datagram: &DatagramInfo,
         destination_connection_id: &LocalId,
     ) {
-        let token = self
-            .config
-            .context()
+        let context = self.config.context();
+        let token = context
             .stateless_reset_token_generator
-            .generate(destination_connection_id.as_bytes());
+            .generate_with_random(
+                destination_connection_id.as_bytes(),
+                context.random_generator,
+            );
         let max_tag_length = self.config.context().tls.max_tag_length();
         // The datagram payload length is used as the packet length since
         // a stateless reset is only sent if the first packet in a datagram is
@@ -841,6 +1227,67 @@ impl<Cfg: Config> Endpoint<Cfg> {
     /// Checks if the given payload contains a stateless reset token matching a known token.
     /// If there is a match, the matching connection will be closed and the `InternalConnectionId`
     /// will be returned.
+    /// Dispatches an error reported by the network to the connection that sent the quoted datagram
+    fn on_socket_error(
+        &mut self,
+        remote_address: &RemoteAddress,
+        error: rx::SocketError,
+        payload: &[u8],
+        timestamp: Timestamp,
+    ) {
+        let (destination_connection_id, source_connection_id) =
+            if let Some(ids) = quoted_connection_ids(payload) {
+                ids
+            } else {
+                return;
+            };
+
+        let internal_id = if let Some(source_connection_id) = source_connection_id {
+            // Long header packets contain our own connection ID so the connection can be looked up
+            // directly
+            LocalId::try_from_bytes(source_connection_id)
+                .and_then(|id| self.connection_id_mapper.lookup_internal_connection_id(&id))
+                .map(|(internal_id, _)| internal_id)
+        } else {
+            // Short header packets only contain the peer's connection ID, so the connections need
+            // to be searched. The number of errors is bounded by the IO provider's queue.
+            self.connections.find_connection(|conn| {
+                conn.is_socket_error_target(remote_address, destination_connection_id)
+            })
+        };
+
+        let internal_id = if let Some(internal_id) = internal_id {
+            internal_id
+        } else {
+            return;
+        };
+
+        let endpoint_context = self.config.context();
+        let close_packet_buffer = &mut self.close_packet_buffer;
+
+        self.connections.with_connection(internal_id, |conn| {
+            if let Err(err) = conn.on_socket_error(
+                remote_address,
+                error,
+                destination_connection_id,
+                timestamp,
+                endpoint_context.congestion_controller,
+                endpoint_context.mtu,
+                endpoint_context.random_generator,
+                endpoint_context.event_subscriber,
+            ) {
+                conn.close(
+                    err,
+                    endpoint_context.connection_close_formatter,
+                    close_packet_buffer,
+                    timestamp,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
+                );
+            }
+        });
+    }
+
     fn close_on_matching_stateless_reset(
         &mut self,
         payload: &[u8],
@@ -896,6 +1343,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
     }
 
     fn on_timeout(&mut self, timestamp: Timestamp) {
+        self.overload.on_timeout(timestamp);
+
         let connection_id_mapper = &mut self.connection_id_mapper;
         let close_packet_buffer = &mut self.close_packet_buffer;
         let endpoint_context = self.config.context();
@@ -920,45 +1369,136 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 }
             });
 
+        // the remaining timers aren't urgent so they can wait while the endpoint is overloaded
+        let defer_timers = self.overload.defer_timers();
+
         // allow connections to generate a new connection id
-        self.connections
-            .iterate_new_connection_id_list(|connection| {
-                let result = connection.on_new_connection_id(
-                    endpoint_context.connection_id_format,
-                    endpoint_context.stateless_reset_token_generator,
+        if !defer_timers {
+            self.connections
+                .iterate_new_connection_id_list(|connection| {
+                    let result = connection.on_new_connection_id(
+                        endpoint_context.connection_id_format,
+                        endpoint_context.stateless_reset_token_generator,
+                        endpoint_context.random_generator,
+                        timestamp,
+                    );
+                    if result.is_ok() {
+                        ConnectionContainerIterationResult::Continue
+                    } else {
+                        // The provided Connection ID generator must never generate the same
+                        // connection ID twice. If this happens, it is unlikely we could recover
+                        // from it.
+                        panic!("Generated connection ID was already in use");
+                    }
+                });
+        }
+
+        self.on_eviction_check(timestamp);
+
+        if defer_timers {
+            return;
+        }
+
+        while let Some(request) = self.reconnect_scheduler.pop_expired(timestamp) {
+            self.create_client_connection(request, timestamp);
+        }
+    }
+
+    /// Reports datagrams dropped from the receive queue and consults the endpoint limiter on
+    /// whether work should be shed
+    fn on_rx_overflow(&mut self, dropped: usize, timestamp: Timestamp) {
+        let endpoint_context = self.config.context();
+
+        let mut publisher = event::EndpointPublisherSubscriber::new(
+            event::builder::EndpointMeta {
+                endpoint_type: Cfg::ENDPOINT_TYPE,
+                timestamp,
+            },
+            None,
+            endpoint_context.event_subscriber,
+        );
+        publisher.on_platform_rx_overflow(event::builder::PlatformRxOverflow { dropped });
+
+        let info = RxOverflow::new(dropped, self.connections.counts(), timestamp.into_event());
+        let outcome = endpoint_context.endpoint_limits.on_rx_overflow(&info);
+        self.overload.on_outcome(outcome, timestamp);
+    }
+
+    /// Consults the endpoint limiter and evicts connections if it reports resource pressure
+    fn on_eviction_check(&mut self, timestamp: Timestamp) {
+        let endpoint_context = self.config.context();
+
+        let check = EvictionCheck::new(self.connections.counts(), timestamp.into_event());
+
+        let (count, criteria, error) =
+            match endpoint_context.endpoint_limits.on_eviction_check(&check) {
+                EvictionOutcome::Evict {
+                    count,
+                    criteria,
+                    error,
+                    ..
+                } => (count, criteria, error),
+                // EvictionOutcome is non_exhaustive so keep connections on things we don't understand
+                _ => return,
+            };
+
+        let close_packet_buffer = &mut self.close_packet_buffer;
+
+        for id in self
+            .connections
+            .eviction_candidates(count, criteria, timestamp)
+        {
+            self.connections.with_connection(id, |conn| {
+                conn.on_evicted(criteria, timestamp, endpoint_context.event_subscriber);
+                conn.close(
+                    error.into(),
+                    endpoint_context.connection_close_formatter,
+                    close_packet_buffer,
                     timestamp,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
                 );
-                if result.is_ok() {
-                    ConnectionContainerIterationResult::Continue
-                } else {
-                    // The provided Connection ID generator must never generate the same connection
-                    // ID twice. If this happens, it is unlikely we could recover from it.
-                    panic!("Generated connection ID was already in use");
-                }
             });
+        }
     }
 
+    /// Creates a connection for the request
+    ///
+    /// If the connection can't be created, the error is reported to the application through the
+    /// request's sender.
     fn create_client_connection(
         &mut self,
         request: endpoint::connect::Request,
         timestamp: Timestamp,
-    ) -> Result<(), connection::Error> {
+    ) {
         assert!(
             Cfg::ENDPOINT_TYPE.is_client(),
             "only client endpoints can be created with client configurations"
         );
 
         let endpoint::connect::Request {
-            connect:
-                endpoint::connect::Connect {
-                    remote_address,
-                    server_name: hostname,
-                    deduplicate,
-                },
+            connect,
             sender,
+            attempt,
+            raced,
         } = request;
 
-        let internal_connection_id = self.connection_id_generator.generate_id();
+        // keep the request around if the attempt can be retried, unless an earlier attempt
+        // is already tracking it
+        let retry =
+            (!raced && attempt + 1 < connect.retry_policy.max_attempts()).then(|| connect.clone());
+
+        let endpoint::connect::Connect {
+            remote_address,
+            server_name: hostname,
+            deduplicate,
+            retry_policy,
+            priority_class,
+            event_subscriber,
+            ..
+        } = connect;
+
+        let internal_connection_id = self.connections.reserve_id();
 
         if deduplicate && !Cfg::DcEndpoint::ENABLED {
             // FIXME: Deduplication causes us to return full `Connection` handles to application
@@ -967,9 +1507,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
             // places internally).
             //
             // Just prevent that configuration for now.
-            return Err(connection::Error::invalid_configuration(
+            let error = connection::Error::invalid_configuration(
                 "Requested connection deduplication which is not supported without (unstable) dc provider configured",
-            ));
+            );
+            let _ = sender.send(Err(error));
+            return;
         }
 
         let open_registry = if deduplicate {
@@ -979,6 +1521,10 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     remote_address,
                     server_name: hostname.clone(),
                     deduplicate,
+                    alternate_addresses: Default::default(),
+                    retry_policy: Default::default(),
+                    priority_class,
+                    event_subscriber: None,
                 },
             ) {
                 Ok(existing) => {
@@ -995,10 +1541,10 @@ impl<Cfg: Config> Endpoint<Cfg> {
                             .get_connection_handle(&existing)
                             .expect("handle exists for active connection");
                         let _ = sender.send(Ok(handle));
-                        return Ok(());
+                        return;
                     } else {
                         // Done, will get notified with a Result<handle> once the connection opens.
-                        return Ok(());
+                        return;
                     }
                 }
                 Err(registry) => Some(registry),
@@ -1007,11 +1553,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
             None
         };
 
-        let local_connection_id = self
-            .config
-            .context()
-            .connection_id_format
-            .generate(&ConnectionInfo::new(&remote_address));
+        let context = self.config.context();
+        let local_connection_id = context.connection_id_format.generate_with_random(
+            &ConnectionInfo::new(&remote_address),
+            context.random_generator,
+        );
 
         let local_connection_id_expiration_time = self
             .config
@@ -1030,11 +1576,10 @@ impl<Cfg: Config> Endpoint<Cfg> {
             // TODO: the client currently generates a random stateless_reset_token but doesnt
             // transmit it. Refactor `create_local_id_registry` to instead accept None for
             // stateless_reset_token.
-            let stateless_reset_token = self
-                .config
-                .context()
+            let context = self.config.context();
+            let stateless_reset_token = context
                 .stateless_reset_token_generator
-                .generate(local_connection_id.as_bytes());
+                .generate_with_random(local_connection_id.as_bytes(), context.random_generator);
             self.connection_id_mapper.create_local_id_registry(
                 internal_connection_id,
                 &local_connection_id,
@@ -1096,9 +1641,17 @@ impl<Cfg: Config> Endpoint<Cfg> {
             initial_source_connection_id: Some(local_connection_id.into()),
             ..Default::default()
         };
-        let limits = endpoint_context
+        let mut limits = endpoint_context
             .connection_limits
             .on_connection(&LimitsInfo::new(&remote_address));
+        if let Some(priority_class) = priority_class {
+            limits = limits.with_priority_class(priority_class).unwrap_or(limits);
+        }
+        if let Some(attempt_timeout) = retry_policy.attempt_timeout() {
+            limits = limits
+                .with_max_handshake_duration(attempt_timeout)
+                .unwrap_or(limits);
+        }
 
         let mut endpoint_publisher = event::EndpointPublisherSubscriber::new(
             event::builder::EndpointMeta {
@@ -1109,25 +1662,30 @@ impl<Cfg: Config> Endpoint<Cfg> {
             endpoint_context.event_subscriber,
         );
 
-        let mtu_config = endpoint_context
-            .mtu
-            .config(&remote_address)
-            .map_err(|_err| {
+        let mtu_config = match endpoint_context.mtu.config(&remote_address) {
+            Ok(mtu_config) => mtu_config
+                .clamp_max_udp_payload_size(limits.max_udp_payload_size(), &remote_address),
+            Err(_err) => {
                 let error = connection::Error::invalid_configuration(
                     "MTU provider produced an invalid MTU configuration",
                 );
                 endpoint_publisher.on_endpoint_connection_attempt_failed(
                     event::builder::EndpointConnectionAttemptFailed { error },
                 );
-                error
-            })?;
+                let _ = sender.send(Err(error));
+                return;
+            }
+        };
+
+        let mut attached_subscriber = event_subscriber.map(|subscriber| subscriber.create());
 
         let mut publisher = event::ConnectionPublisherSubscriber::new(
             meta,
             quic_version,
             endpoint_context.event_subscriber,
             &mut event_context,
-        );
+        )
+        .with_attached(attached_subscriber.as_deref_mut());
 
         let congestion_controller = {
             let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
@@ -1144,6 +1702,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .try_into()
             .expect("Failed to convert max_datagram_frame_size");
 
+        if endpoint_context
+            .datagram
+            .supports_fec(&PreConnectionInfo::new())
+        {
+            transport_parameters.datagram_fec_support = DatagramFecSupport::Enabled;
+        }
+
         transport_parameters.active_connection_id_limit = s2n_quic_core::varint::VarInt::from(
             connection::peer_id_registry::ACTIVE_CONNECTION_ID_LIMIT,
         )
@@ -1166,15 +1731,21 @@ impl<Cfg: Config> Endpoint<Cfg> {
             <<Cfg::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
                 original_destination_connection_id.as_bytes(),
             );
+        let custom_transport_parameters = endpoint_context
+            .custom_transport_parameters
+            .on_connection(&custom::ConnectionInfo::new(&remote_address));
+
         let tls_session = endpoint_context
             .tls
             // TODO should SNI be optional? rustls expects a SNI but other tls providers dont seem
             // to require this value.
             .new_client_session(
-                &transport_parameters,
-                hostname.expect("application should provide a valid server name"),
+                &custom::WithCustom(&transport_parameters, &custom_transport_parameters),
+                hostname
+                    .clone()
+                    .expect("application should provide a valid server name"),
             );
-        let space_manager = PacketSpaceManager::new(
+        let mut space_manager = PacketSpaceManager::new(
             original_destination_connection_id,
             tls_session,
             initial_key,
@@ -1182,6 +1753,19 @@ impl<Cfg: Config> Endpoint<Cfg> {
             timestamp,
             &mut publisher,
         );
+        space_manager.receive_buffer_pool = self.receive_buffer_pools.get(&limits);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# When connecting to a server for
+        //# which the client retains an applicable and unused token, it SHOULD
+        //# include that token in the Token field of its Initial packet.
+        let server_address = remote_address.unmap();
+        let server = token::cache::Server::new(hostname.as_ref(), &server_address);
+        if let Some(token) = endpoint_context.token_cache.take_token(&server) {
+            if let Some((space, _handshake_status)) = space_manager.initial_mut() {
+                space.on_cached_token(&token);
+            }
+        }
 
         let wakeup_handle = self
             .wakeup_queue
@@ -1207,16 +1791,85 @@ impl<Cfg: Config> Endpoint<Cfg> {
             limits,
             mtu_config,
             event_context,
+            attached_subscriber,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
             datagram_endpoint: endpoint_context.datagram,
             dc_endpoint: endpoint_context.dc,
+            custom_frame_endpoint: endpoint_context.custom_frame,
             open_registry,
         };
-        let connection = <Cfg as crate::endpoint::Config>::Connection::new(connection_parameters)?;
+        let connection =
+            match <Cfg as crate::endpoint::Config>::Connection::new(connection_parameters) {
+                Ok(connection) => connection,
+                Err(error) => {
+                    let _ = sender.send(Err(error));
+                    return;
+                }
+            };
+        let sender = match retry {
+            Some(connect) => self.reconnect_scheduler.on_attempt(
+                endpoint::connect::Request {
+                    connect,
+                    sender,
+                    attempt,
+                    raced,
+                },
+                timestamp,
+            ),
+            None => sender,
+        };
         self.connections
             .insert_client_connection(connection, internal_connection_id, sender);
-        Ok(())
+    }
+}
+
+/// Returns the destination and source connection IDs of a datagram quoted in a socket error
+///
+/// Quoted datagrams may be truncated, so only the packet header is decoded. Short header packets
+/// don't encode the length of the destination connection ID, so the rest of the datagram is
+/// returned in its place.
+fn quoted_connection_ids(payload: &[u8]) -> Option<(&[u8], Option<&[u8]>)> {
+    let buffer = DecoderBuffer::new(payload);
+    let (tag, buffer) = buffer.decode::<u8>().ok()?;
+
+    if tag & 0x80 == 0 {
+        return Some((buffer.into_less_safe_slice(), None));
+    }
+
+    let (_version, buffer) = buffer.decode::<u32>().ok()?;
+    let (destination_connection_id, buffer) = buffer.decode_slice_with_len_prefix::<u8>().ok()?;
+    let (source_connection_id, _) = buffer.decode_slice_with_len_prefix::<u8>().ok()?;
+
+    Some((
+        destination_connection_id.into_less_safe_slice(),
+        Some(source_connection_id.into_less_safe_slice()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_connection_ids_test() {
+        // long header packets contain both connection IDs
+        let long = [0xc0, 0, 0, 0, 1, 2, 0xaa, 0xbb, 1, 0xcc, 0xdd, 0xee];
+        assert_eq!(
+            Some((&[0xaa, 0xbb][..], Some(&[0xcc][..]))),
+            quoted_connection_ids(&long)
+        );
+
+        // short header packets return the rest of the datagram
+        let short = [0x40, 0xaa, 0xbb, 0xcc];
+        assert_eq!(
+            Some((&[0xaa, 0xbb, 0xcc][..], None)),
+            quoted_connection_ids(&short)
+        );
+
+        // truncated long headers can't be routed
+        assert_eq!(None, quoted_connection_ids(&long[..7]));
+        assert_eq!(None, quoted_connection_ids(&[]));
     }
 }
 
@@ -1240,6 +1893,7 @@ pub mod testing {
         type StatelessResetTokenGenerator = stateless_reset::token::testing::Generator;
         type RandomGenerator = random::testing::Generator;
         type TokenFormat = s2n_quic_core::token::testing::Format;
+        type TokenCache = s2n_quic_core::token::cache::Disabled;
         type ConnectionLimits = s2n_quic_core::connection::limits::Limits;
         type Mtu = s2n_quic_core::path::mtu::Config;
         type StreamManager = crate::stream::DefaultStreamManager;
@@ -1249,6 +1903,9 @@ pub mod testing {
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
+        type CustomTransportParameters = s2n_quic_core::transport::parameters::custom::Disabled;
+        type CustomFrameEndpoint = s2n_quic_core::custom_frame::Disabled;
+        type Demultiplexer = s2n_quic_core::demux::Disabled;
 
         fn context(&mut self) -> super::Context<Self> {
             todo!()
@@ -1272,6 +1929,7 @@ pub mod testing {
         type StatelessResetTokenGenerator = stateless_reset::token::testing::Generator;
         type RandomGenerator = random::testing::Generator;
         type TokenFormat = s2n_quic_core::token::testing::Format;
+        type TokenCache = s2n_quic_core::token::cache::Disabled;
         type ConnectionLimits = s2n_quic_core::connection::limits::Limits;
         type Mtu = s2n_quic_core::path::mtu::Config;
         type StreamManager = crate::stream::DefaultStreamManager;
@@ -1281,6 +1939,9 @@ pub mod testing {
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
+        type CustomTransportParameters = s2n_quic_core::transport::parameters::custom::Disabled;
+        type CustomFrameEndpoint = s2n_quic_core::custom_frame::Disabled;
+        type Demultiplexer = s2n_quic_core::demux::Disabled;
 
         fn context(&mut self) -> super::Context<Self> {
             todo!()
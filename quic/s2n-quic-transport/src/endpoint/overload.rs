@@ -0,0 +1,127 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the work the endpoint sheds after its receive queue overflowed
+
+use s2n_quic_core::{
+    endpoint::limits::OverloadOutcome,
+    event::{self, EndpointPublisher},
+    time::Timestamp,
+};
+
+#[derive(Debug, Default)]
+pub(crate) struct State {
+    /// The time at which the endpoint stops shedding work
+    deadline: Option<Timestamp>,
+    skip_events: bool,
+    defer_timers: bool,
+}
+
+impl State {
+    /// Applies the outcome returned by the endpoint limiter for a receive queue overflow
+    #[inline]
+    pub fn on_outcome(&mut self, outcome: OverloadOutcome, now: Timestamp) {
+        *self = match outcome {
+            OverloadOutcome::Shed {
+                duration,
+                skip_events,
+                defer_timers,
+                ..
+            } => Self {
+                deadline: Some(now + duration),
+                skip_events,
+                defer_timers,
+            },
+            // OverloadOutcome is non_exhaustive so keep processing on things we don't understand
+            _ => Self::default(),
+        };
+    }
+
+    /// Stops shedding work once the deadline has passed
+    #[inline]
+    pub fn on_timeout(&mut self, now: Timestamp) {
+        if self.deadline.map_or(false, |deadline| deadline <= now) {
+            *self = Self::default();
+        }
+    }
+
+    /// Returns `true` if non-urgent timers should be deferred
+    #[inline]
+    pub fn defer_timers(&self) -> bool {
+        self.defer_timers
+    }
+
+    /// Returns the time at which the deferred timers should be processed
+    #[inline]
+    pub fn next_expiration(&self) -> Option<Timestamp> {
+        self.deadline.filter(|_| self.defer_timers)
+    }
+
+    /// Publishes the datagram dropped event, unless events are being skipped
+    #[inline]
+    pub fn on_datagram_dropped<P: EndpointPublisher>(
+        &self,
+        publisher: &mut P,
+        event: event::builder::EndpointDatagramDropped,
+    ) {
+        if !self.skip_events {
+            publisher.on_endpoint_datagram_dropped(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use s2n_quic_core::time::{testing::Clock, Clock as _};
+
+    #[test]
+    fn shed_test() {
+        let mut clock = Clock::default();
+        let mut state = State::default();
+        assert!(!state.defer_timers());
+        assert_eq!(state.next_expiration(), None);
+
+        let now = clock.get_time();
+        state.on_outcome(OverloadOutcome::shed(Duration::from_millis(10)), now);
+        assert!(state.defer_timers());
+        assert!(state.skip_events);
+        assert_eq!(
+            state.next_expiration(),
+            Some(now + Duration::from_millis(10))
+        );
+
+        clock.inc_by(Duration::from_millis(5));
+        state.on_timeout(clock.get_time());
+        assert!(state.defer_timers());
+
+        clock.inc_by(Duration::from_millis(5));
+        state.on_timeout(clock.get_time());
+        assert!(!state.defer_timers());
+        assert!(!state.skip_events);
+        assert_eq!(state.next_expiration(), None);
+    }
+
+    #[test]
+    fn outcome_options_test() {
+        let now = Clock::default().get_time();
+        let mut state = State::default();
+
+        let outcome = OverloadOutcome::shed(Duration::from_millis(10)).with_defer_timers(false);
+        state.on_outcome(outcome, now);
+        assert!(state.skip_events);
+        assert!(!state.defer_timers());
+        assert_eq!(state.next_expiration(), None);
+
+        let outcome = OverloadOutcome::shed(Duration::from_millis(10)).with_skip_events(false);
+        state.on_outcome(outcome, now);
+        assert!(!state.skip_events);
+        assert!(state.defer_timers());
+
+        // a report without shedding clears the previous outcome
+        state.on_outcome(OverloadOutcome::none(), now);
+        assert!(!state.skip_events);
+        assert!(!state.defer_timers());
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Holds the pools that stream receive buffers are allocated from
+
+use alloc::vec::Vec;
+use s2n_quic_core::{buffer::reassembler::Pool, connection::Limits};
+
+/// The receive buffer pools for an endpoint
+///
+/// Connections with the same pool settings share a pool. In practice the connection limits are
+/// the same for all connections, so there is only ever a single pool.
+#[derive(Debug, Default)]
+pub(crate) struct Pools {
+    pools: Vec<(u32, u64, Pool)>,
+}
+
+impl Pools {
+    /// Returns the pool for the connection limits, if enabled
+    #[inline]
+    pub fn get(&mut self, limits: &Limits) -> Option<Pool> {
+        let (chunk_size, max_size) = limits.receive_buffer_pool()?;
+
+        if let Some((_, _, pool)) = self
+            .pools
+            .iter()
+            .find(|(c, m, _)| *c == chunk_size && *m == max_size)
+        {
+            return Some(pool.clone());
+        }
+
+        let pool = Pool::new(chunk_size, max_size);
+        self.pools.push((chunk_size, max_size, pool.clone()));
+        Some(pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_pool_test() {
+        let mut pools = Pools::default();
+        assert!(pools.get(&Limits::default()).is_none());
+
+        let limits = Limits::default()
+            .with_receive_buffer_pool(4096, 1 << 20)
+            .unwrap();
+        let a = pools.get(&limits).unwrap();
+        let b = pools.get(&limits).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.chunk_size(), 4096);
+
+        let limits = limits.with_receive_buffer_pool(8192, 1 << 20).unwrap();
+        assert_ne!(pools.get(&limits).unwrap(), a);
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retries client connection attempts which fail before the handshake completes
+
+use crate::{
+    connection,
+    endpoint::connect::{ConnectionReceiver, ConnectionSender, Request},
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_channel::oneshot;
+use s2n_quic_core::{
+    event::{self, EndpointPublisher, IntoEvent},
+    time::Timestamp,
+};
+
+#[derive(Debug, Default)]
+pub(crate) struct Scheduler {
+    /// Attempts which are waiting on the outcome of a handshake
+    inflight: Vec<Inflight>,
+    /// Attempts which are waiting for their backoff to elapse
+    scheduled: Vec<Scheduled>,
+}
+
+#[derive(Debug)]
+struct Inflight {
+    request: Request,
+    /// The outcomes of the attempts which are racing to complete a handshake
+    receivers: Vec<ConnectionReceiver>,
+    /// The time at which an attempt to the next address joins the race
+    race_deadline: Option<Timestamp>,
+}
+
+impl Inflight {
+    /// Schedules the next address to join the race, if the request allows it
+    #[inline]
+    fn schedule_race(&mut self, timestamp: Timestamp) {
+        let retry_policy = &self.request.connect.retry_policy;
+        self.race_deadline = if self.request.attempt + 1 < retry_policy.max_attempts() {
+            retry_policy.race_delay().map(|delay| timestamp + delay)
+        } else {
+            None
+        };
+    }
+
+    /// Starts an attempt to the next address, which races the attempts already in progress
+    fn race(&mut self, timestamp: Timestamp) -> Request {
+        self.request.attempt += 1;
+        self.request.connect.rotate_address();
+
+        let (sender, receiver) = oneshot::channel();
+        self.receivers.push(receiver);
+        self.schedule_race(timestamp);
+
+        Request {
+            connect: self.request.connect.clone(),
+            sender,
+            attempt: self.request.attempt,
+            raced: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Scheduled {
+    request: Request,
+    deadline: Timestamp,
+}
+
+impl Scheduler {
+    /// Intercepts the outcome of an attempt so it can be retried
+    ///
+    /// The returned sender should be used in place of the request's sender.
+    pub fn on_attempt(&mut self, request: Request, timestamp: Timestamp) -> ConnectionSender {
+        let (sender, receiver) = oneshot::channel();
+        let mut inflight = Inflight {
+            request,
+            receivers: vec![receiver],
+            race_deadline: None,
+        };
+        inflight.schedule_race(timestamp);
+        self.inflight.push(inflight);
+        sender
+    }
+
+    /// Polls all of the attempts which are waiting on a handshake
+    ///
+    /// Attempts which fail with a retryable error are scheduled to be made again once their
+    /// backoff elapses. Everything else is forwarded to the application. Returns the number of
+    /// attempts which made progress.
+    pub fn poll<Pub: EndpointPublisher>(
+        &mut self,
+        cx: &mut Context,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) -> usize {
+        let mut count = 0;
+
+        // stop tracking any requests the application is no longer interested in
+        self.scheduled
+            .retain_mut(|scheduled| scheduled.request.sender.poll_canceled(cx).is_pending());
+
+        let mut index = 0;
+        while let Some(inflight) = self.inflight.get_mut(index) {
+            // dropping the receivers causes the connections to be closed once the handshake
+            // completes
+            if inflight.request.sender.poll_canceled(cx).is_ready() {
+                self.inflight.swap_remove(index);
+                continue;
+            }
+
+            let mut outcome = None;
+            let mut is_closing = false;
+            inflight.receivers.retain_mut(|receiver| {
+                if outcome.is_some() {
+                    return true;
+                }
+
+                match Pin::new(receiver).poll(cx) {
+                    Poll::Ready(Ok(result)) => {
+                        outcome = Some(result);
+                        false
+                    }
+                    Poll::Ready(Err(_)) => {
+                        is_closing = true;
+                        false
+                    }
+                    Poll::Pending => true,
+                }
+            });
+
+            // the endpoint is closing so drop the request
+            if is_closing {
+                self.inflight.swap_remove(index);
+                continue;
+            }
+
+            let Some(result) = outcome else {
+                index += 1;
+                continue;
+            };
+
+            count += 1;
+
+            let error = match result {
+                Ok(connection) => {
+                    // the remaining attempts lost the race and are closed once their receivers
+                    // are dropped
+                    let Inflight { request, .. } = self.inflight.swap_remove(index);
+                    // if the application is no longer waiting for the handshake, the returned
+                    // handle is dropped, which closes the connection
+                    let _ = request.sender.send(Ok(connection));
+                    continue;
+                }
+                Err(error) => error,
+            };
+
+            // the next address was going to join the race anyway, so start it straight away
+            if is_retryable(&error) && inflight.race_deadline.is_some() {
+                inflight.race_deadline = Some(timestamp);
+                continue;
+            }
+
+            // other attempts are still racing, so poll them again in case they've also completed
+            if !inflight.receivers.is_empty() {
+                continue;
+            }
+
+            let Inflight { mut request, .. } = self.inflight.swap_remove(index);
+
+            request.attempt += 1;
+
+            if !is_retryable(&error)
+                || request.attempt >= request.connect.retry_policy.max_attempts()
+            {
+                let _ = request.sender.send(Err(error));
+                continue;
+            }
+
+            request.connect.rotate_address();
+            let retry_policy = &request.connect.retry_policy;
+            let mut backoff = retry_policy.backoff(request.attempt);
+
+            // wait at least as long as a server which is shedding load asked, up to the
+            // policy's maximum backoff. The hint isn't authenticated, so it isn't trusted to
+            // delay the attempt any further.
+            if let Some(retry_after) = error.retry_after() {
+                backoff = backoff.max(retry_after.min(retry_policy.max_backoff()));
+            }
+
+            let Some(deadline) = timestamp.checked_add(backoff) else {
+                let _ = request.sender.send(Err(error));
+                continue;
+            };
+
+            publisher.on_endpoint_connection_attempt_retried(
+                event::builder::EndpointConnectionAttemptRetried {
+                    attempt: request.attempt + 1,
+                    remote_address: request.connect.remote_address.into_event(),
+                    backoff,
+                    error,
+                },
+            );
+
+            self.scheduled.push(Scheduled { request, deadline });
+        }
+
+        count
+    }
+
+    /// Returns the next request which is ready to be attempted
+    ///
+    /// This includes both the retries whose backoff has elapsed and the attempts which join a
+    /// race.
+    pub fn pop_expired(&mut self, timestamp: Timestamp) -> Option<Request> {
+        if let Some(index) = self
+            .scheduled
+            .iter()
+            .position(|scheduled| scheduled.deadline <= timestamp)
+        {
+            return Some(self.scheduled.swap_remove(index).request);
+        }
+
+        let inflight = self.inflight.iter_mut().find(|inflight| {
+            inflight
+                .race_deadline
+                .map_or(false, |deadline| deadline <= timestamp)
+        })?;
+        Some(inflight.race(timestamp))
+    }
+
+    /// Returns the time at which the next scheduled attempt should be made
+    pub fn next_expiration(&self) -> Option<Timestamp> {
+        let scheduled = self.scheduled.iter().map(|scheduled| scheduled.deadline);
+        let raced = self
+            .inflight
+            .iter()
+            .filter_map(|inflight| inflight.race_deadline);
+        scheduled.chain(raced).min()
+    }
+}
+
+/// Returns `true` if a new attempt could succeed where the previous one failed
+///
+/// Refused connections are only retried if the server indicated when it would be able to accept
+/// a new one. Since the refusal can be forged, the wait for the hint is bounded by the retry
+/// policy's maximum backoff.
+fn is_retryable(error: &connection::Error) -> bool {
+    matches!(
+        error,
+        connection::Error::MaxHandshakeDurationExceeded { .. }
+            | connection::Error::IdleTimerExpired { .. }
+            | connection::Error::Unreachable { .. }
+    ) || error.retry_after().is_some()
+}
@@ -0,0 +1,225 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Refuses connection attempts without allocating any connection state
+
+use crate::endpoint;
+use alloc::collections::VecDeque;
+use core::{ops::Range, time::Duration};
+use s2n_codec::{encoder::scatter, Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic_core::{
+    connection::close::RetryAfter,
+    crypto::{CryptoSuite, InitialKey},
+    event,
+    frame::{ConnectionClose, Padding},
+    inet::ExplicitCongestionNotification,
+    io::tx,
+    packet::{
+        encoding::{PacketEncoder, PacketPayloadEncoder},
+        initial::{Initial, ProtectedInitial},
+        number::PacketNumberSpace,
+    },
+    path::{self, MINIMUM_MAX_DATAGRAM_SIZE},
+    time, transport,
+    varint::VarInt,
+};
+
+#[derive(Debug)]
+pub struct Dispatch<Path: path::Handle> {
+    // TODO: Find a better datastructure capable of handling delays in transmission
+    // https://github.com/aws/s2n-quic/issues/280
+    transmissions: VecDeque<Transmission<Path>>,
+}
+
+impl<Path: path::Handle> Default for Dispatch<Path> {
+    fn default() -> Self {
+        Self::new(endpoint::DEFAULT_MAX_PEERS)
+    }
+}
+
+impl<Path: path::Handle> Dispatch<Path> {
+    pub fn new(max_peers: usize) -> Self {
+        Self {
+            transmissions: VecDeque::with_capacity(max_peers),
+        }
+    }
+
+    pub fn queue<C: CryptoSuite>(
+        &mut self,
+        path_handle: Path,
+        packet: &ProtectedInitial,
+        retry_after: Option<Duration>,
+    ) {
+        if let Some(transmission) = Transmission::new::<C>(path_handle, packet, retry_after) {
+            self.transmissions.push_back(transmission);
+        }
+    }
+
+    pub fn on_transmit<Tx: tx::Queue<Handle = Path>, Pub: event::EndpointPublisher>(
+        &mut self,
+        queue: &mut Tx,
+        publisher: &mut Pub,
+    ) {
+        while let Some(transmission) = self.transmissions.pop_front() {
+            match queue.push(&transmission) {
+                Ok(tx::Outcome { len, .. }) => {
+                    publisher.on_endpoint_packet_sent(event::builder::EndpointPacketSent {
+                        packet_header: event::builder::PacketHeader::Initial {
+                            number: 0,
+                            version: transmission.version,
+                        },
+                    });
+
+                    publisher.on_endpoint_datagram_sent(event::builder::EndpointDatagramSent {
+                        len: len as u16,
+                        gso_offset: 0,
+                    });
+                }
+                Err(_) => {
+                    self.transmissions.push_front(transmission);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+pub struct Transmission<Path: path::Handle> {
+    path: Path,
+    packet: [u8; MINIMUM_MAX_DATAGRAM_SIZE as usize],
+    packet_range: Range<usize>,
+    version: u32,
+}
+
+impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Transmission")
+            .field("remote_address", &self.path.remote_address())
+            .field("local_address", &self.path.local_address())
+            .field("packet", &&self.packet[self.packet_range.clone()])
+            .finish()
+    }
+}
+
+impl<Path: path::Handle> Transmission<Path> {
+    pub fn new<C: CryptoSuite>(
+        path: Path,
+        packet: &ProtectedInitial,
+        retry_after: Option<Duration>,
+    ) -> Option<Self> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
+        //# If a server refuses to accept a new connection, it SHOULD send an
+        //# Initial packet containing a CONNECTION_CLOSE frame with error code
+        //# CONNECTION_REFUSED.
+
+        // The server doesn't have any connection state so the packet is protected with the keys
+        // derived from the client's original destination connection ID.
+        let (mut key, header_key) = C::InitialKey::new_server(packet.destination_connection_id());
+
+        let retry_after = retry_after.map(RetryAfter::new);
+        let mut connection_close: ConnectionClose = transport::Error::CONNECTION_REFUSED.into();
+        connection_close.reason = retry_after.as_ref().map(RetryAfter::as_bytes);
+
+        let packet_number = PacketNumberSpace::Initial.new_packet_number(VarInt::ZERO);
+
+        let initial = Initial {
+            version: packet.version,
+            destination_connection_id: packet.source_connection_id(),
+            // reuse the client's chosen connection ID since this connection will never be
+            // addressed again
+            source_connection_id: packet.destination_connection_id(),
+            token: &[0u8; 0][..],
+            packet_number,
+            payload: Payload { connection_close },
+        };
+
+        let mut packet_buf = [0u8; MINIMUM_MAX_DATAGRAM_SIZE as usize];
+        let (protected_packet, _buffer) = initial
+            .encode_packet(
+                &mut key,
+                &header_key,
+                packet_number,
+                None,
+                EncoderBuffer::new(&mut packet_buf),
+            )
+            .ok()?;
+        let packet_range = 0..protected_packet.len();
+
+        Some(Self {
+            path,
+            packet: packet_buf,
+            packet_range,
+            version: packet.version,
+        })
+    }
+}
+
+impl<Path: path::Handle> AsRef<[u8]> for Transmission<Path> {
+    fn as_ref(&self) -> &[u8] {
+        &self.packet[self.packet_range.clone()]
+    }
+}
+
+impl<Path: path::Handle> tx::Message for &Transmission<Path> {
+    type Handle = Path;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        &self.path
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        Default::default()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> time::Duration {
+        Default::default()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, _segment_count: usize) -> bool {
+        segment_len >= self.as_ref().len()
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(self.as_ref())
+    }
+}
+
+/// Writes a CONNECTION_CLOSE frame followed by enough padding to satisfy the minimum packet length
+struct Payload<'a> {
+    connection_close: ConnectionClose<'a>,
+}
+
+impl<'a> PacketPayloadEncoder for Payload<'a> {
+    fn encoding_size_hint<E: Encoder>(&mut self, _encoder: &E, minimum_len: usize) -> usize {
+        self.connection_close.encoding_size().max(minimum_len)
+    }
+
+    fn encode(
+        &mut self,
+        buffer: &mut scatter::Buffer,
+        minimum_len: usize,
+        _header_len: usize,
+        _tag_len: usize,
+    ) {
+        buffer.encode(&self.connection_close);
+
+        let length = minimum_len.saturating_sub(buffer.len());
+        if length > 0 {
+            buffer.encode(&Padding { length });
+        }
+    }
+}
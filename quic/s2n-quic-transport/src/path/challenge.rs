@@ -3,6 +3,7 @@
 
 use crate::{contexts::WriteContext, transmission};
 use s2n_quic_core::{
+    connection::Limits,
     ct::ConstantTimeEq,
     event, frame,
     time::{timer, Duration, Timer, Timestamp},
@@ -11,11 +12,52 @@ use s2n_quic_core::{
 pub type Data = [u8; frame::path_challenge::DATA_LEN];
 const DISABLED_DATA: Data = [0; frame::path_challenge::DATA_LEN];
 
+//= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.1
+//# An endpoint MAY send multiple PATH_CHALLENGE frames to guard against
+//# packet loss.
+
+// Re-transmitting twice guards against packet loss, while remaining
+// below the amplification limit of 3.
+const DEFAULT_TRANSMISSIONS: u8 = 2;
+
+/// Configures how paths are validated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The number of PATH_CHALLENGE frames sent for each validation
+    pub transmissions: u8,
+    /// The minimum amount of time between PATH_CHALLENGE frames
+    pub interval: Duration,
+    /// Overrides the RFC recommended amount of time before abandoning a validation
+    pub abandon_duration: Option<Duration>,
+    /// The maximum number of validations pending at the same time
+    pub max_pending: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::from(&Limits::default())
+    }
+}
+
+impl From<&Limits> for Config {
+    fn from(limits: &Limits) -> Self {
+        Self {
+            transmissions: limits.path_challenge_transmissions(),
+            interval: limits.path_challenge_interval(),
+            abandon_duration: limits.path_validation_timeout(),
+            max_pending: limits.max_pending_path_validations(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Challenge {
     state: State,
     abandon_duration: Duration,
     abandon_timer: Timer,
+    sent: u8,
+    retransmit_interval: Duration,
+    retransmit_timer: Timer,
     data: Data,
 }
 
@@ -57,31 +99,43 @@ impl Challenge {
             //# An endpoint SHOULD NOT probe a new path with packets containing a
             //# PATH_CHALLENGE frame more frequently than it would send an Initial
             //# packet.
-
-            //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.1
-            //# An endpoint MAY send multiple PATH_CHALLENGE frames to guard against
-            //# packet loss.
-
-            // Re-transmitting twice guards against packet loss, while remaining
-            // below the amplification limit of 3.
-            state: State::RequiresTransmission(2),
+            state: State::RequiresTransmission(DEFAULT_TRANSMISSIONS),
             abandon_duration,
             abandon_timer: Timer::default(),
+            sent: 0,
+            retransmit_interval: Duration::ZERO,
+            retransmit_timer: Timer::default(),
             data,
         }
     }
 
+    /// Sets the number of PATH_CHALLENGE frames to send and the minimum interval between them
+    pub fn with_transmissions(mut self, transmissions: u8, interval: Duration) -> Self {
+        debug_assert!(transmissions > 0, "at least one challenge must be sent");
+        self.state = State::RequiresTransmission(transmissions);
+        self.retransmit_interval = interval;
+        self
+    }
+
     pub fn disabled() -> Self {
         Self {
             state: State::InitialPathDisabled,
             abandon_duration: Duration::ZERO,
             abandon_timer: Timer::default(),
+            sent: 0,
+            retransmit_interval: Duration::ZERO,
+            retransmit_timer: Timer::default(),
             data: DISABLED_DATA,
         }
     }
 
     /// When a PATH_CHALLENGE is transmitted this handles any internal state operations.
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        // wait for the retransmission interval to elapse before sending another challenge
+        if self.retransmit_timer.is_armed() {
+            return;
+        }
+
         match self.state {
             State::RequiresTransmission(0) => self.state = State::PendingResponse,
             State::RequiresTransmission(remaining) => {
@@ -93,11 +147,17 @@ impl Challenge {
                 if context.write_frame(&frame).is_some() {
                     let remaining = remaining - 1;
                     self.state = State::RequiresTransmission(remaining);
+                    self.sent += 1;
 
                     if !self.abandon_timer.is_armed() {
                         self.abandon_timer
                             .set(context.current_time() + self.abandon_duration);
                     }
+
+                    if remaining > 0 && !self.retransmit_interval.is_zero() {
+                        self.retransmit_timer
+                            .set(context.current_time() + self.retransmit_interval);
+                    }
                 }
             }
             _ => {}
@@ -110,6 +170,9 @@ impl Challenge {
         publisher: &mut Pub,
         path: event::builder::Path,
     ) {
+        // the next challenge is sent once the timer is no longer armed
+        let _ = self.retransmit_timer.poll_expiration(timestamp);
+
         if self.abandon_timer.poll_expiration(timestamp).is_ready() {
             self.abandon(publisher, path);
         }
@@ -123,10 +186,12 @@ impl Challenge {
         if self.is_pending() {
             self.state = State::Abandoned;
             self.abandon_timer.cancel();
+            self.retransmit_timer.cancel();
             publisher.on_path_challenge_updated(event::builder::PathChallengeUpdated {
                 path_challenge_status: event::builder::PathChallengeStatus::Abandoned,
                 path,
                 challenge_data: self.challenge_data(),
+                challenges_sent: self.sent,
             });
         }
     }
@@ -145,6 +210,7 @@ impl Challenge {
     pub fn on_validated(&mut self, data: &[u8]) -> bool {
         if self.is_pending() && ConstantTimeEq::ct_eq(&self.data[..], data).into() {
             self.state = State::Validated;
+            self.retransmit_timer.cancel();
             true
         } else {
             false
@@ -154,12 +220,18 @@ impl Challenge {
     pub fn challenge_data(&self) -> &[u8] {
         &self.data
     }
+
+    /// Returns the number of PATH_CHALLENGE frames sent for the challenge
+    pub fn challenges_sent(&self) -> u8 {
+        self.sent
+    }
 }
 
 impl timer::Provider for Challenge {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.abandon_timer.timers(query)?;
+        self.retransmit_timer.timers(query)?;
 
         Ok(())
     }
@@ -171,6 +243,10 @@ impl transmission::interest::Provider for Challenge {
         &self,
         query: &mut Q,
     ) -> transmission::interest::Result {
+        if self.retransmit_timer.is_armed() {
+            return Ok(());
+        }
+
         self.state.transmission_interest(query)
     }
 }
@@ -211,6 +287,7 @@ mod tests {
     use s2n_quic_core::{
         endpoint,
         time::{Clock, Duration, NoopClock},
+        transmission::interest::Provider as _,
     };
     use testing::*;
 
@@ -288,6 +365,86 @@ mod tests {
         assert_eq!(context.frame_buffer.len(), 0);
     }
 
+    #[test]
+    fn retransmissions_are_spaced_by_the_interval() {
+        // Setup:
+        let mut helper = helper_challenge();
+        let interval = Duration::from_millis(100);
+        helper.challenge = helper.challenge.with_transmissions(3, interval);
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            helper.now,
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+        let mut publisher = event::testing::Publisher::snapshot();
+        assert_eq!(helper.challenge.state, State::RequiresTransmission(3));
+
+        // Trigger:
+        helper.challenge.on_transmit(&mut context);
+
+        // Expectation:
+        assert_eq!(context.frame_buffer.len(), 1);
+        assert_eq!(helper.challenge.challenges_sent(), 1);
+        assert!(!helper.challenge.has_transmission_interest());
+
+        // Trigger:
+        helper.challenge.on_transmit(&mut context);
+
+        // Expectation:
+        assert_eq!(context.frame_buffer.len(), 1);
+        assert_eq!(helper.challenge.state, State::RequiresTransmission(2));
+
+        for expected in 2..=3 {
+            // Trigger:
+            helper.challenge.on_timeout(
+                helper.now + interval * (expected - 1),
+                &mut publisher,
+                event::builder::Path::test(),
+            );
+            context.current_time = helper.now + interval * (expected - 1);
+            assert!(helper.challenge.has_transmission_interest());
+            helper.challenge.on_transmit(&mut context);
+
+            // Expectation:
+            assert_eq!(context.frame_buffer.len(), expected as usize);
+            assert_eq!(helper.challenge.challenges_sent(), expected as u8);
+        }
+
+        // the final challenge doesn't wait for the interval before awaiting a response
+        assert!(helper.challenge.has_transmission_interest());
+        helper.challenge.on_transmit(&mut context);
+        assert_eq!(helper.challenge.state, State::PendingResponse);
+        assert_eq!(context.frame_buffer.len(), 3);
+    }
+
+    #[test]
+    fn challenges_sent_is_reported_on_abandon() {
+        let mut helper = helper_challenge();
+        helper.challenge = helper.challenge.with_transmissions(1, Duration::ZERO);
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            helper.now,
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+        helper.challenge.on_transmit(&mut context);
+        helper.challenge.on_transmit(&mut context);
+        assert_eq!(helper.challenge.state, State::PendingResponse);
+
+        let mut publisher = event::testing::Publisher::snapshot();
+        helper
+            .challenge
+            .abandon(&mut publisher, event::builder::Path::test());
+
+        assert_eq!(helper.challenge.state, State::Abandoned);
+        assert_eq!(helper.challenge.challenges_sent(), 1);
+    }
+
     #[test]
     fn successful_on_transmit_arms_the_timer() {
         // Setup:
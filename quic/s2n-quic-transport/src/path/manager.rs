@@ -24,13 +24,13 @@ use s2n_quic_core::{
     packet::number::PacketNumberSpace,
     path::{
         migration::{self, Validator as _},
-        mtu, Handle as _, Id,
+        mtu, Handle as _, Id, RemoteAddress,
     },
     random,
     recovery::congestion_controller::{self, Endpoint as _},
     stateless_reset,
     time::{timer, Timestamp},
-    transport,
+    transport::{self, parameters::MigrationSupport},
 };
 use smallvec::SmallVec;
 
@@ -70,6 +70,15 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// The earliest time the next `PathStatistics` events are published
+    next_path_statistics: Option<Timestamp>,
+
+    /// Controls how new paths are validated
+    challenge_config: challenge::Config,
+
+    /// Indicates if the peer supports active migration on the address used during the handshake
+    peer_migration_support: MigrationSupport,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -80,12 +89,21 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            next_path_statistics: None,
+            challenge_config: challenge::Config::default(),
+            peer_migration_support: MigrationSupport::default(),
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
         manager
     }
 
+    /// Sets the configuration used when validating paths
+    pub fn with_challenge_config(mut self, config: challenge::Config) -> Self {
+        self.challenge_config = config;
+        self
+    }
+
     /// Update the active path
     fn update_active_path<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -153,6 +171,62 @@ impl<Config: endpoint::Config> Manager<Config> {
         &mut self.paths[self.active as usize]
     }
 
+    /// Returns the number of paths the connection is tracking, including the active path
+    #[inline]
+    pub fn path_count(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns a snapshot of the statistics of each path the connection is tracking
+    #[inline]
+    pub fn statistics(&self) -> impl Iterator<Item = connection::PathStatistics> + '_ {
+        self.paths
+            .iter()
+            .enumerate()
+            .map(|(id, path)| path.statistics(path_id(id as u8)))
+    }
+
+    /// Publishes a `PathStatistics` event for each path if the interval has elapsed
+    #[inline]
+    pub fn publish_statistics<Pub: event::ConnectionPublisher>(
+        &mut self,
+        timestamp: Timestamp,
+        limits: &Limits,
+        publisher: &mut Pub,
+    ) {
+        let Some(interval) = limits.path_statistics_interval() else {
+            return;
+        };
+
+        if self
+            .next_path_statistics
+            .map_or(false, |next| !next.has_elapsed(timestamp))
+        {
+            return;
+        }
+
+        self.next_path_statistics = Some(timestamp + interval);
+
+        for (id, path) in self.paths.iter().enumerate() {
+            let path_id = path_id(id as u8);
+            let statistics = path.statistics(path_id);
+            publisher.on_path_statistics(event::builder::PathStatistics {
+                path: path_event!(path, path_id),
+                validation: statistics.validation.into_event(),
+                smoothed_rtt: statistics.smoothed_rtt,
+                min_rtt: statistics.min_rtt,
+                latest_rtt: statistics.latest_rtt,
+                congestion_window: statistics.congestion_window,
+                bytes_in_flight: statistics.bytes_in_flight,
+                bytes_sent: statistics.bytes_sent,
+                bytes_received: statistics.bytes_received,
+                packets_sent: statistics.packets_sent,
+                packets_lost: statistics.packets_lost,
+                bytes_lost: statistics.bytes_lost,
+            });
+        }
+    }
+
     /// Return the Id of the active path
     #[inline]
     pub fn active_path_id(&self) -> Id {
@@ -205,21 +279,193 @@ impl<Config: endpoint::Config> Manager<Config> {
     /// Returns the Path for the provided address if the PathManager knows about it
     #[inline]
     pub fn path(&self, handle: &Config::PathHandle) -> Option<(Id, &Path<Config>)> {
-        self.paths
-            .iter()
-            .enumerate()
-            .find(|(_id, path)| Path::eq_by_handle(path, handle))
-            .map(|(id, path)| (path_id(id as u8), path))
+        let id = self.path_id_by_handle(handle)?;
+        Some((id, &self[id]))
     }
 
     /// Returns the Path for the provided address if the PathManager knows about it
     #[inline]
     pub fn path_mut(&mut self, handle: &Config::PathHandle) -> Option<(Id, &mut Path<Config>)> {
+        let id = self.path_id_by_handle(handle)?;
+        Some((id, &mut self[id]))
+    }
+
+    /// Returns the Id of the Path for the provided address, preferring the active path
+    ///
+    /// Clients match paths by the remote address only, so after moving to a new local address
+    /// the previous path matches the same handles as the active path.
+    #[inline]
+    fn path_id_by_handle(&self, handle: &Config::PathHandle) -> Option<Id> {
+        if Path::eq_by_handle(self.active_path(), handle) {
+            return Some(self.active_path_id());
+        }
+
         self.paths
-            .iter_mut()
-            .enumerate()
-            .find(|(_id, path)| Path::eq_by_handle(path, handle))
-            .map(|(id, path)| (path_id(id as u8), path))
+            .iter()
+            .position(|path| Path::eq_by_handle(path, handle))
+            .map(|id| path_id(id as u8))
+    }
+
+    /// Returns the Id of the Path a datagram quoted in a socket error was sent on, if the
+    /// PathManager knows about it
+    ///
+    /// In addition to being sent to the path's remote address, the quoted datagram must start with
+    /// the path's peer connection ID. This makes it harder for an off-path attacker to spoof
+    /// errors, since they aren't authenticated.
+    #[inline]
+    pub fn path_for_socket_error(
+        &self,
+        remote_address: &RemoteAddress,
+        destination_connection_id: &[u8],
+    ) -> Option<Id> {
+        let remote_address = remote_address.unmap();
+        self.paths
+            .iter()
+            .position(|path| {
+                path.remote_address().unmap() == remote_address
+                    && destination_connection_id.starts_with(path.peer_connection_id.as_bytes())
+            })
+            .map(|id| path_id(id as u8))
+    }
+
+    /// Records whether the peer supports active migration, as indicated by its transport parameters
+    #[inline]
+    pub fn on_peer_migration_support(&mut self, migration_support: MigrationSupport) {
+        self.peer_migration_support = migration_support;
+    }
+
+    /// Called when the local address of a path can no longer be used to send datagrams
+    ///
+    /// The connection moves to a new path which doesn't pin the local address, which lets the
+    /// operating system select the source address of the following datagrams. The peer then
+    /// observes the new address as a migration. Returns `true` if the connection was moved to a
+    /// new path, which requires a spare connection ID from the peer.
+    #[allow(clippy::too_many_arguments)]
+    pub fn on_local_address_unavailable<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_id: Id,
+        handshake_confirmed: bool,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+        limits: &Limits,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) -> bool {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# Clients are responsible for initiating all migrations.
+        ensure!(Config::ENDPOINT_TYPE.is_client(), false);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate connection migration before the
+        //# handshake is confirmed, as defined in Section 4.1.2 of [QUIC-TLS].
+        ensure!(handshake_confirmed, false);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# If the peer sent the disable_active_migration transport parameter, an
+        //# endpoint also MUST NOT send packets (including probing packets; see
+        //# Section 9.1) from a different local address to the address the peer
+        //# used during the handshake, unless the endpoint has acted on a
+        //# preferred_address transport parameter from the peer.
+        ensure!(
+            self.peer_migration_support == MigrationSupport::Enabled,
+            false
+        );
+
+        // errors for datagrams which were sent on previous paths are ignored
+        ensure!(path_id == self.active_path_id(), false);
+
+        // the path has already been moved off of its address
+        ensure!(self[path_id].handle.local_address().port() != 0, false);
+
+        ensure!(self.paths.len() < MAX_ALLOWED_PATHS, false);
+
+        let remote_address = self[path_id].remote_address();
+        let Ok(mtu_config) = mtu.config(&remote_address) else {
+            return false;
+        };
+        let mtu_config =
+            mtu_config.clamp_max_udp_payload_size(limits.max_udp_payload_size(), &remote_address);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.5
+        //# An endpoint MUST NOT reuse a connection ID when sending from more
+        //# than one local address -- for example, when initiating connection
+        //# migration as described in Section 9.2 or when probing a new network
+        //# path as described in Section 9.1.
+        //
+        // If the peer hasn't provided a spare connection ID, the connection stays on its current
+        // path.
+        let Some(peer_connection_id) = self.peer_id_registry.consume_new_id_for_new_path() else {
+            return false;
+        };
+
+        let path = &self[path_id];
+        publisher.on_local_address_lost(event::builder::LocalAddressLost {
+            path: path_event!(path, path_id),
+        });
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+        //# When migrating, the new path might not support the endpoint's current
+        //# sending rate.  Therefore, the endpoint resets its congestion
+        //# controller and RTT estimate, as described in Section 9.4.
+        //
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.4
+        //# Packets sent on the old path MUST NOT contribute to
+        //# congestion control or RTT estimation for the new path.
+        //
+        // The new path starts with its own congestion controller, round-trip time estimator and
+        // MTU controller, like the paths created when the peer migrates. Packets sent from the
+        // previous address are still acknowledged on the previous path.
+        let rtt = path
+            .rtt_estimator
+            .for_new_path(limits.initial_round_trip_time());
+        let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+        let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+
+        let path = Path::new(
+            Config::PathHandle::from_remote_address(remote_address),
+            peer_connection_id,
+            path.local_connection_id,
+            rtt,
+            cc,
+            false,
+            mtu_config,
+            limits.anti_amplification_multiplier(),
+        );
+
+        let new_path_id = self::path_id(self.paths.len() as u8);
+
+        let active_path = self.active_path();
+        publisher.on_path_created(event::builder::PathCreated {
+            active: path_event!(active_path, path_id),
+            new: path_event!(path, new_path_id),
+        });
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: new_path_id.into_event(),
+            mtu: path.mtu_controller.max_datagram_size() as u16,
+            cause: MtuUpdatedCause::NewPath,
+        });
+
+        self.paths.push(path);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+        //# To establish reachability on the new path, an endpoint initiates path
+        //# validation (Section 8.2) on the new path.
+        self.set_challenge(new_path_id, random_generator);
+        // clients aren't limited by amplification so the outcome is always unchanged
+        let _ = self.activate_path(publisher, path_id, new_path_id);
+
+        true
+    }
+
+    /// Returns `true` if any path is waiting to send a PATH_CHALLENGE or PATH_RESPONSE frame
+    #[inline]
+    pub fn has_path_validation_interest(&self) -> bool {
+        use transmission::interest::Provider as _;
+
+        self.paths
+            .iter()
+            .any(|path| path.has_transmission_interest())
     }
 
     /// Returns an iterator over all paths pending path_challenge or path_response
@@ -336,8 +582,12 @@ impl<Config: endpoint::Config> Manager<Config> {
             self.active_path().local_connection_id != datagram.destination_connection_id;
 
         if active_migration {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+            //# This transport
+            //#    parameter does not prohibit connection migration after a client
+            //#    has acted on a preferred_address transport parameter.
             ensure!(
-                limits.active_migration_enabled(),
+                limits.active_migration_enabled() || limits.is_preferred_address(&local_address),
                 Err(DatagramDropReason::RejectedConnectionMigration)
             )
         }
@@ -374,6 +624,22 @@ impl<Config: endpoint::Config> Manager<Config> {
             }
         }
 
+        // Bound the number of validations in flight so rapid address changes don't result in
+        // an excessive amount of probing traffic. The path pending packet authentication is
+        // excluded since it is replaced by the new path.
+        let pending_validations = self
+            .paths
+            .iter()
+            .enumerate()
+            .filter(|(idx, path)| {
+                Some(*idx as u8) != self.pending_packet_authentication
+                    && path.is_challenge_pending()
+            })
+            .count();
+        if pending_validations >= self.challenge_config.max_pending as usize {
+            return Err(DatagramDropReason::PathValidationLimitExceeded);
+        }
+
         // Determine which index will be used for the newly created path
         //
         // If a previously allocated path failed to contain an authenticated packet, we
@@ -417,11 +683,14 @@ impl<Config: endpoint::Config> Manager<Config> {
             .rtt_estimator
             .for_new_path(limits.initial_round_trip_time());
 
-        let mtu_config = mtu.config(&remote_address).map_err(|_err| {
-            event::builder::DatagramDropReason::InvalidMtuConfiguration {
-                endpoint_mtu_config: mtu.endpoint_config().into_event(),
-            }
-        })?;
+        let mtu_config = mtu
+            .config(&remote_address)
+            .map_err(
+                |_err| event::builder::DatagramDropReason::InvalidMtuConfiguration {
+                    endpoint_mtu_config: mtu.endpoint_config().into_event(),
+                },
+            )?
+            .clamp_max_udp_payload_size(limits.max_udp_payload_size(), &remote_address);
 
         let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
         let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
@@ -511,11 +780,15 @@ impl<Config: endpoint::Config> Manager<Config> {
         //# could have a longer round-trip time than the original.  A value of
         //# three times the larger of the current PTO or the PTO for the new path
         //# (using kInitialRtt, as defined in [QUIC-RECOVERY]) is RECOMMENDED.
-        let abandon_duration = self[path_id].pto_period(PacketNumberSpace::ApplicationData);
-        let abandon_duration = 3 * abandon_duration.max(
-            self.active_path()
-                .pto_period(PacketNumberSpace::ApplicationData),
-        );
+        //
+        // The application can override the recommended value through the connection limits.
+        let abandon_duration = self.challenge_config.abandon_duration.unwrap_or_else(|| {
+            let abandon_duration = self[path_id].pto_period(PacketNumberSpace::ApplicationData);
+            3 * abandon_duration.max(
+                self.active_path()
+                    .pto_period(PacketNumberSpace::ApplicationData),
+            )
+        });
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-9
         //# An endpoint MUST
@@ -525,7 +798,10 @@ impl<Config: endpoint::Config> Manager<Config> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.3
         //# Servers SHOULD initiate path validation to the client's new address
         //# upon receiving a probe packet from a different address.
-        let challenge = challenge::Challenge::new(abandon_duration, data);
+        let challenge = challenge::Challenge::new(abandon_duration, data).with_transmissions(
+            self.challenge_config.transmissions,
+            self.challenge_config.interval,
+        );
         self[path_id].set_challenge(challenge);
     }
 
@@ -611,6 +887,7 @@ impl<Config: endpoint::Config> Manager<Config> {
                     path_challenge_status: event::builder::PathChallengeStatus::Validated,
                     path: path_event!(path, id),
                     challenge_data: path.challenge.challenge_data().into_event(),
+                    challenges_sent: path.challenge.challenges_sent(),
                 });
                 // A path was validated so check if it becomes the new
                 // last_known_active_validated_path
@@ -88,7 +88,7 @@ impl Model {
             let zero_path = helper_path(zero_conn_id);
             let mut random_generator = random::testing::Generator(123);
             let peer_id_registry =
-                ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server)
+                ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false)
                     .create_server_peer_id_registry(
                         InternalConnectionIdGenerator::new().generate_id(),
                         zero_path.peer_connection_id,
@@ -198,6 +198,7 @@ impl Model {
                     DatagramDropReason::InsufficientConnectionIds => {}
                     DatagramDropReason::RejectedConnectionMigration => {}
                     DatagramDropReason::PathLimitExceeded => {}
+                    DatagramDropReason::PathValidationLimitExceeded => {}
                     datagram_drop_reason => panic!("{:?}", datagram_drop_reason),
                 };
             }
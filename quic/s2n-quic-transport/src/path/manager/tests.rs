@@ -32,20 +32,25 @@ type ClientPath = super::Path<Client>;
 // Helper function to easily create a PathManager as a Server
 fn manager_server(first_path: ServerPath) -> ServerManager {
     let mut random_generator = random::testing::Generator(123);
-    let peer_id_registry = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server)
-        .create_server_peer_id_registry(
-            InternalConnectionIdGenerator::new().generate_id(),
-            first_path.peer_connection_id,
-            true,
-        );
+    let peer_id_registry =
+        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false)
+            .create_server_peer_id_registry(
+                InternalConnectionIdGenerator::new().generate_id(),
+                first_path.peer_connection_id,
+                true,
+            );
     ServerManager::new(first_path, peer_id_registry)
 }
 
 // Helper function to easily create a PathManager as a Client
 fn manager_client(first_path: ClientPath) -> ClientManager {
     let mut random_generator = random::testing::Generator(123);
-    let peer_id_registry = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client)
-        .create_client_peer_id_registry(InternalConnectionIdGenerator::new().generate_id(), true);
+    let peer_id_registry =
+        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client, false)
+            .create_client_peer_id_registry(
+                InternalConnectionIdGenerator::new().generate_id(),
+                true,
+            );
     ClientManager::new(first_path, peer_id_registry)
 }
 
@@ -982,6 +987,75 @@ fn limit_number_of_connection_migrations() {
     assert_eq!(total_paths, MAX_ALLOWED_PATHS);
 }
 
+#[test]
+fn limit_number_of_pending_path_validations() {
+    // Setup:
+    let mut publisher = Publisher::snapshot();
+    let new_addr: SocketAddr = "127.0.0.1:1".parse().unwrap();
+    let new_addr = SocketAddress::from(new_addr);
+    let new_addr = RemoteAddress::from(new_addr);
+    let first_path = ServerPath::new(
+        new_addr,
+        connection::PeerId::try_from_bytes(&[1]).unwrap(),
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        false,
+        mtu::Config::default(),
+        ANTI_AMPLIFICATION_MULTIPLIER,
+    );
+    let config = challenge::Config {
+        max_pending: 2,
+        ..Default::default()
+    };
+    let mut manager = manager_server(first_path).with_challenge_config(config);
+    let mut results = vec![];
+
+    for i in 1..=3 {
+        let new_addr: SocketAddr = format!("127.0.0.2:{}", i).parse().unwrap();
+        let new_addr = SocketAddress::from(new_addr);
+        let new_addr = RemoteAddress::from(new_addr);
+        let now = NoopClock {}.get_time();
+        let datagram = DatagramInfo {
+            timestamp: now,
+            payload_len: 0,
+            ecn: ExplicitCongestionNotification::default(),
+            destination_connection_id: connection::LocalId::TEST_ID,
+            destination_connection_id_classification: connection::id::Classification::Local,
+            source_connection_id: None,
+        };
+
+        // Trigger:
+        let res = manager.handle_connection_migration(
+            &new_addr,
+            &datagram,
+            &mut Default::default(),
+            &mut migration::allow_all::Validator,
+            &mut mtu::Manager::new(mtu::Config::default()),
+            &Limits::default(),
+            &mut publisher,
+        );
+        if let Ok((id, _)) = res {
+            let _ = manager.on_processed_packet(
+                id,
+                None,
+                path_validation::Probe::Probing,
+                &mut random::testing::Generator(123),
+                &mut publisher,
+            );
+        }
+        results.push(res);
+    }
+
+    // Expectation:
+    assert!(results[0].is_ok());
+    assert!(results[1].is_ok());
+    assert!(matches!(
+        results[2],
+        Err(DatagramDropReason::PathValidationLimitExceeded)
+    ));
+}
+
 #[test]
 fn active_connection_migration_disabled() {
     // Setup:
@@ -1709,7 +1783,7 @@ fn last_known_validated_path_should_update_on_path_response() {
 
     let mut random_generator = random::testing::Generator(123);
     let mut peer_id_registry =
-        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server)
+        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false)
             .create_server_peer_id_registry(
                 InternalConnectionIdGenerator::new().generate_id(),
                 zero_path.peer_connection_id,
@@ -1876,7 +1950,7 @@ pub fn helper_manager_with_paths_base(
 
     let mut random_generator = random::testing::Generator(123);
     let mut peer_id_registry =
-        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server)
+        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false)
             .create_server_peer_id_registry(
                 InternalConnectionIdGenerator::new().generate_id(),
                 zero_path.peer_connection_id,
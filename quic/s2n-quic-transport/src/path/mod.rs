@@ -12,9 +12,11 @@ use crate::{
     transmission::{self, Mode},
 };
 use s2n_quic_core::{
+    connection::{statistics::PathCounters, PathInfo, PathStatistics, PathValidation},
     counter::{Counter, Saturating},
     event::{self, IntoEvent},
     frame, packet, random,
+    recovery::one_way_delay,
     time::{timer, Timestamp},
 };
 
@@ -58,6 +60,10 @@ pub struct Path<Config: endpoint::Config> {
     pub mtu_controller: mtu::Controller,
     /// Controller for determining the ECN capability of the path
     pub ecn_controller: ecn::Controller,
+    /// Estimates the one-way delays of the path from the TIMESTAMP frames sent by the peer
+    pub one_way_delay: one_way_delay::Estimator,
+    /// The packet and byte counters of the path
+    pub counters: PathCounters,
 
     /// True if the path has been validated by the peer
     peer_validated: bool,
@@ -93,6 +99,8 @@ impl<Config: endpoint::Config> Clone for Path<Config> {
             state: self.state,
             mtu_controller: self.mtu_controller.clone(),
             ecn_controller: self.ecn_controller.clone(),
+            one_way_delay: self.one_way_delay,
+            counters: self.counters,
             peer_validated: self.peer_validated,
             challenge: self.challenge.clone(),
             response_data: self.response_data,
@@ -141,6 +149,8 @@ impl<Config: endpoint::Config> Path<Config> {
             state,
             mtu_controller: mtu::Controller::new(mtu_config, &peer_socket_address),
             ecn_controller: ecn::Controller::default(),
+            one_way_delay: one_way_delay::Estimator::default(),
+            counters: PathCounters::default(),
             peer_validated,
             challenge: Challenge::disabled(),
             response_data: None,
@@ -193,6 +203,8 @@ impl<Config: endpoint::Config> Path<Config> {
             "path should not transmit when amplification limited; tried to transmit {bytes}"
         );
 
+        self.counters.bytes_sent += bytes as u64;
+
         if let State::AmplificationLimited { tx_allowance, .. } = &mut self.state {
             *tx_allowance -= bytes as u32
         }
@@ -205,6 +217,8 @@ impl<Config: endpoint::Config> Path<Config> {
     pub fn on_bytes_received(&mut self, bytes: usize) -> AmplificationOutcome {
         let was_at_amplification_limit = self.at_amplification_limit();
 
+        self.counters.bytes_received += bytes as u64;
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1
         //# For the purposes of
         //# avoiding amplification prior to address validation, servers MUST
@@ -377,6 +391,52 @@ impl<Config: endpoint::Config> Path<Config> {
         self.state == State::Validated
     }
 
+    /// Returns the validation state of the path
+    #[inline]
+    pub fn validation(&self) -> PathValidation {
+        if self.is_validated() {
+            PathValidation::Validated
+        } else if self.is_challenge_pending() {
+            PathValidation::Pending
+        } else if self.failed_validation() {
+            PathValidation::Failed
+        } else {
+            PathValidation::NotValidated
+        }
+    }
+
+    /// Returns a snapshot of the statistics of the path
+    #[inline]
+    pub fn statistics(&self, id: Id) -> PathStatistics {
+        let mut statistics = PathStatistics::default();
+        statistics.id = id.as_u8() as u64;
+        statistics.local_address = *self.local_address();
+        statistics.remote_address = *self.remote_address();
+        statistics.is_active = self.is_active();
+        statistics.validation = self.validation();
+        statistics.smoothed_rtt = self.rtt_estimator.smoothed_rtt();
+        statistics.min_rtt = self.rtt_estimator.min_rtt();
+        statistics.latest_rtt = self.rtt_estimator.latest_rtt();
+        statistics.congestion_window = self.congestion_controller.congestion_window();
+        statistics.bytes_in_flight = self.congestion_controller.bytes_in_flight();
+        self.counters.write(&mut statistics);
+        statistics
+    }
+
+    /// Returns the RTT and congestion window of the path
+    #[inline]
+    pub fn info(&self) -> PathInfo {
+        let mut info = PathInfo::default();
+        info.smoothed_rtt = self.rtt_estimator.smoothed_rtt();
+        info.min_rtt = self.rtt_estimator.min_rtt();
+        info.bandwidth_estimate = self
+            .congestion_controller
+            .bandwidth_estimate()
+            .map(|bw| bw.as_bytes_per_second());
+        info.congestion_window = self.congestion_controller.congestion_window();
+        info
+    }
+
     /// The path received a non-path-validation-probing packet so mark it as activated.
     #[inline]
     pub fn on_activated(&mut self) {
@@ -907,6 +967,36 @@ mod tests {
         assert!(path.challenge.is_pending());
     }
 
+    #[test]
+    fn statistics_test() {
+        // Setup:
+        let mut path = testing::helper_path_server();
+        let id = path::Id::test_id();
+
+        let statistics = path.statistics(id);
+        assert_eq!(statistics.validation, PathValidation::NotValidated);
+        assert_eq!(statistics.bytes_sent, 0);
+        assert_eq!(statistics.bytes_received, 0);
+
+        // Trigger:
+        let _ = path.on_bytes_received(100);
+        path.on_bytes_transmitted(200);
+        path.set_challenge(helper_challenge().challenge);
+
+        // Expectation:
+        let statistics = path.statistics(id);
+        assert_eq!(statistics.validation, PathValidation::Pending);
+        assert_eq!(statistics.bytes_received, 100);
+        assert_eq!(statistics.bytes_sent, 200);
+        assert_eq!(statistics.remote_address, *path.remote_address());
+
+        // Trigger:
+        path.on_validated();
+
+        // Expectation:
+        assert_eq!(path.statistics(id).validation, PathValidation::Validated);
+    }
+
     #[test]
     fn on_validated_when_already_validated_does_nothing() {
         // Setup:
@@ -18,6 +18,7 @@ use s2n_quic_core::{
     recovery::{congestion_controller, persistent_congestion, CongestionController, Pto},
     time::{timer, timer::Provider, Timer, Timestamp},
     transport,
+    varint::VarInt,
 };
 use smallvec::SmallVec;
 
@@ -40,6 +41,10 @@ pub struct Manager<Config: endpoint::Config> {
     //  These are packets that are pending acknowledgement.
     sent_packets: SentPackets<<<Config::CongestionControllerEndpoint as congestion_controller::Endpoint>::CongestionController as congestion_controller::CongestionController>::PacketInfo>,
 
+    // The maximum span of packet numbers tracked in `sent_packets` before the oldest packets
+    // are evicted
+    max_sent_packets: usize,
+
     // Timer set when packets may be declared lost at a time in the future
     loss_timer: Timer,
 
@@ -66,6 +71,12 @@ pub struct Manager<Config: endpoint::Config> {
     //
     // Used for updating the PTO timer at the end of a transmission burst.
     pto_update_pending: bool,
+
+    // The interval at which snapshots of the congestion controller state are published
+    congestion_state_interval: Option<Duration>,
+
+    // The earliest time the next congestion controller snapshot is published
+    next_congestion_state: Option<Timestamp>,
 }
 
 /// Initial capacity of the SmallVec used for keeping track of packets
@@ -118,15 +129,31 @@ impl<Config: endpoint::Config> Manager<Config> {
             space,
             largest_acked_packet: None,
             sent_packets: SentPackets::default(),
+            max_sent_packets: usize::MAX,
             loss_timer: Timer::default(),
             pto: Pto::default(),
             time_of_last_ack_eliciting_packet: None,
             baseline_ecn_counts: EcnCounts::default(),
             sent_packet_ecn_counts: EcnCounts::default(),
             pto_update_pending: false,
+            congestion_state_interval: None,
+            next_congestion_state: None,
         }
     }
 
+    /// Limits the span of packet numbers tracked while waiting for acknowledgement
+    pub fn with_max_sent_packets(mut self, max_sent_packets: usize) -> Self {
+        debug_assert!(max_sent_packets > 0);
+        self.max_sent_packets = max_sent_packets;
+        self
+    }
+
+    /// Publishes a snapshot of the congestion controller state at most once per `interval`
+    pub fn with_congestion_state_interval(mut self, interval: Option<Duration>) -> Self {
+        self.congestion_state_interval = interval;
+        self
+    }
+
     /// Invoked when the Client processes a Retry packet.
     ///
     /// Reset congestion controller state by discarding sent bytes and replacing recovery
@@ -239,6 +266,7 @@ impl<Config: endpoint::Config> Manager<Config> {
 
         let path_id = context.path_id();
         let path = context.path_mut();
+        path.counters.packets_sent += 1;
         let cc_packet_info = path.congestion_controller.on_packet_sent(
             time_sent,
             congestion_controlled_bytes,
@@ -397,7 +425,7 @@ impl<Config: endpoint::Config> Manager<Config> {
                 PacketNumberRange::new(space.new_packet_number(start), space.new_packet_number(end))
             }),
             largest_acked_packet_number,
-            frame.ack_delay(),
+            frame.ack_delay,
             frame.ecn_counts,
             packet_number,
             random_generator,
@@ -416,7 +444,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         timestamp: Timestamp,
         ranges: impl Iterator<Item = PacketNumberRange>,
         largest_acked_packet_number: PacketNumber,
-        ack_delay: Duration,
+        ack_delay: VarInt,
         ecn_counts: Option<EcnCounts>,
         packet_number: PacketNumber,
         random_generator: &mut Config::RandomGenerator,
@@ -457,6 +485,7 @@ impl<Config: endpoint::Config> Manager<Config> {
                 includes_ack_eliciting,
                 timestamp,
                 ack_delay,
+                packet_number,
                 context,
                 publisher,
             );
@@ -472,13 +501,45 @@ impl<Config: endpoint::Config> Manager<Config> {
             );
         }
 
+        self.evict_sent_packets(context, publisher);
+        // release any memory that is no longer needed after removing the acked packets
+        self.sent_packets.compact();
+
         let path_id = context.path_id().as_u8();
         let path = context.path_mut();
         publisher.on_recovery_metrics(recovery_event!(path_id, path));
 
+        self.publish_congestion_state(timestamp, context, publisher);
+
         Ok(())
     }
 
+    /// Publishes a snapshot of the congestion controller state if the interval has elapsed
+    #[inline]
+    fn publish_congestion_state<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        timestamp: Timestamp,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        let Some(interval) = self.congestion_state_interval else {
+            return;
+        };
+
+        if self
+            .next_congestion_state
+            .map_or(false, |next| !next.has_elapsed(timestamp))
+        {
+            return;
+        }
+
+        self.next_congestion_state = Some(timestamp + interval);
+
+        let path_id = context.path_id();
+        let state = context.path().congestion_controller.congestion_state();
+        congestion_controller::PathPublisher::new(publisher, path_id).on_congestion_state(state);
+    }
+
     // Process ack_range and return largest_newly_acked and if the packet is ack eliciting.
     fn process_ack_range<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
@@ -563,7 +624,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         largest_acked_packet_number: PacketNumber,
         includes_ack_eliciting: bool,
         timestamp: Timestamp,
-        ack_delay: Duration,
+        ack_delay: VarInt,
+        packet_number: PacketNumber,
         context: &mut Ctx,
         publisher: &mut Pub,
     ) {
@@ -591,7 +653,7 @@ impl<Config: endpoint::Config> Manager<Config> {
             let latest_rtt = timestamp - largest_newly_acked_info.time_sent;
             let path = context.path_mut_by_id(largest_newly_acked_info.path_id);
             path.rtt_estimator.update_rtt(
-                ack_delay,
+                Duration::from_micros(ack_delay.as_u64()),
                 latest_rtt,
                 timestamp,
                 is_handshake_confirmed,
@@ -609,6 +671,23 @@ impl<Config: endpoint::Config> Manager<Config> {
                 ),
             );
 
+            // Combine the RTT sample with the TIMESTAMP frame sent by the peer in the same packet
+            if let Some(sample) = path.one_way_delay.on_rtt_sample(
+                packet_number,
+                largest_newly_acked_info.time_sent,
+                ack_delay,
+                timestamp,
+            ) {
+                path.congestion_controller.on_one_way_delay_update(
+                    sample,
+                    timestamp,
+                    &mut congestion_controller::PathPublisher::new(
+                        publisher,
+                        largest_newly_acked_info.path_id,
+                    ),
+                );
+            }
+
             // Notify components the RTT estimate was updated
             context.on_rtt_update(timestamp);
         }
@@ -772,12 +851,26 @@ impl<Config: endpoint::Config> Manager<Config> {
         self.sent_packet_ecn_counts -= newly_acked_ecn_counts;
     }
 
+    /// Requests a probe packet to be sent without waiting for the PTO timer to expire
+    #[inline]
+    pub fn force_probe(&mut self) {
+        self.pto.force_transmit()
+    }
+
     /// Returns `true` if the recovery manager requires a probe packet to be sent.
     #[inline]
     pub fn requires_probe(&self) -> bool {
         self.pto.has_transmission_interest()
     }
 
+    /// Returns `true` if any ack-eliciting packets are waiting to be acknowledged or declared lost
+    #[inline]
+    pub fn has_ack_eliciting_packets_in_flight(&self) -> bool {
+        self.sent_packets
+            .iter()
+            .any(|(_, info)| info.ack_elicitation.is_ack_eliciting())
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#appendix-B.9
     //# When Initial or Handshake keys are discarded, packets sent in that
     //# space no longer count toward bytes in flight.
@@ -929,6 +1022,49 @@ impl<Config: endpoint::Config> Manager<Config> {
         )
     }
 
+    /// Stops tracking the oldest packets if the span of tracked packet numbers exceeds the limit
+    ///
+    /// The data in the evicted packets is retransmitted the same as any other lost packet, so
+    /// this bounds the memory used for sent packet metadata without affecting reliability.
+    fn evict_sent_packets<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        let span = self.sent_packets.span();
+        if span <= self.max_sent_packets {
+            return;
+        }
+
+        let excess = (span - self.max_sent_packets) as u64;
+        let start = self.sent_packets.get_range().start();
+        let end = VarInt::new(start.as_u64() + excess - 1)
+            .expect("the end is bounded by the largest sent packet number");
+        let end = start.space().new_packet_number(end);
+        let evicted_packets = PacketNumberRange::new(start, end);
+
+        for (packet_number, _) in self.sent_packets.iter() {
+            if packet_number > end {
+                // sent_packets is ordered by packet number, so all remaining packets will be larger
+                break;
+            }
+
+            // only notify for the tracked packets, since the gaps have already been acknowledged
+            let range = PacketNumberRange::new(packet_number, packet_number);
+            context.on_packet_loss(&range, publisher);
+        }
+
+        // The packets weren't lost due to congestion, so they're removed from flight without a
+        // congestion controller reaction
+        for (_, sent_info) in self.sent_packets.remove_range(evicted_packets) {
+            let path = context.path_mut_by_id(sent_info.path_id);
+            path.congestion_controller.on_packet_discarded(
+                sent_info.sent_bytes as usize,
+                &mut congestion_controller::PathPublisher::new(publisher, sent_info.path_id),
+            );
+        }
+    }
+
     fn remove_lost_packets<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
         now: Timestamp,
@@ -945,6 +1081,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         // Remove the lost packets and account for the bytes on the proper congestion controller
         for (packet_number, sent_info) in self.sent_packets.remove_range(lost_packets) {
             let path = context.path_mut_by_id(sent_info.path_id);
+            path.counters.packets_lost += 1;
+            path.counters.bytes_lost += sent_info.sent_bytes as u64;
 
             //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
             //# A sender that does not have state for all packet
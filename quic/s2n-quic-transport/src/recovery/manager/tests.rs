@@ -3196,6 +3196,95 @@ fn ack_packets(
     )
 }
 
+#[test]
+fn evict_sent_packets() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut publisher = Publisher::no_snapshot();
+    let mut manager = ServerManager::new(space).with_max_sent_packets(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let now = now();
+
+    for packet_number in 0u8..20 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 100,
+                bytes_progressed: 0,
+            },
+            now,
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+    assert_eq!(manager.sent_packets.span(), 20);
+
+    // Packets 0 through 2 are lost due to the packet threshold, and packets 3 through 9 are
+    // evicted to get back within the limit
+    ack_packets(5..=5, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(manager.sent_packets.span(), 10);
+    assert_eq!(
+        manager.sent_packets.get_range().start(),
+        space.new_packet_number(VarInt::from_u8(10))
+    );
+    // packet 5 was acknowledged so it shouldn't be declared lost
+    assert_eq!(context.on_packet_loss_count, 9);
+    assert!(!context
+        .lost_packets
+        .contains(&space.new_packet_number(VarInt::from_u8(5))));
+    // only the packets lost due to the packet threshold are reported to the congestion controller
+    assert_eq!(context.path().congestion_controller.lost_bytes, 300);
+}
+
+#[test]
+fn evict_sent_packets_does_not_reduce_congestion_window() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut publisher = Publisher::no_snapshot();
+    let mut manager = ServerManager::new(space).with_max_sent_packets(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let now = now();
+    let congestion_window = context.path().congestion_controller.congestion_window;
+
+    for packet_number in 0u8..20 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 100,
+                bytes_progressed: 0,
+            },
+            now,
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Acknowledging packet 2 doesn't declare any packets lost, but packets 0 through 9 are
+    // evicted to get back within the limit
+    ack_packets(2..=2, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(manager.sent_packets.span(), 10);
+    // the evicted data is still retransmitted
+    assert_eq!(context.on_packet_loss_count, 9);
+
+    let congestion_controller = &context.path().congestion_controller;
+    assert_eq!(congestion_controller.on_packets_lost, 0);
+    assert_eq!(congestion_controller.lost_bytes, 0);
+    assert_eq!(congestion_controller.congestion_window, congestion_window);
+    assert!(!congestion_controller.requires_fast_retransmission);
+    // the evicted packets are no longer in flight
+    assert_eq!(congestion_controller.bytes_in_flight, 1100);
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.5
 //= type=test
 //# A sender MUST however count these packets as being additionally in
@@ -3417,7 +3506,7 @@ fn helper_generate_path_manager_with_first_addr(
 ) -> path::Manager<ServerConfig> {
     let mut random_generator = random::testing::Generator(123);
 
-    let registry = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server)
+    let registry = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server, false)
         .create_server_peer_id_registry(
             InternalConnectionIdGenerator::new().generate_id(),
             connection::PeerId::TEST_ID,
@@ -3446,7 +3535,7 @@ fn helper_generate_client_path_manager(
 ) -> path::Manager<ClientConfig> {
     let mut random_generator = random::testing::Generator(123);
 
-    let registry = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client)
+    let registry = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client, false)
         .create_client_peer_id_registry(InternalConnectionIdGenerator::new().generate_id(), true);
     let mut rtt_estimator = RttEstimator::default();
     rtt_estimator.on_max_ack_delay(max_ack_delay.try_into().unwrap());
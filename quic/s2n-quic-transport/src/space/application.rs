@@ -10,7 +10,7 @@ use crate::{
     recovery,
     recovery::CongestionController,
     space::{
-        datagram, keep_alive::KeepAlive, CryptoStream, HandshakeStatus, PacketSpace,
+        custom_frame, datagram, keep_alive::KeepAlive, CryptoStream, HandshakeStatus, PacketSpace,
         TxPacketNumbers,
     },
     stream::Manager as _,
@@ -18,29 +18,42 @@ use crate::{
     transmission,
     transmission::interest::Provider,
 };
-use core::{convert::TryInto, fmt, marker::PhantomData};
-use s2n_codec::EncoderBuffer;
+use alloc::vec::Vec;
+use bytes::Bytes;
+use core::{
+    convert::TryInto,
+    fmt,
+    marker::PhantomData,
+    task::{Poll, Waker},
+};
+use s2n_codec::{EncoderBuffer, EncoderValue as _};
 use s2n_quic_core::{
+    ack,
     counter::{Counter, Saturating},
-    crypto::{application::KeySet, limited, tls, CryptoSuite},
+    crypto::{self, application::KeySet, limited, tls, CryptoSuite, Key as _},
+    datagram::Sender as _,
     dc::Endpoint as _,
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
-        ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        self, ack::AckRanges, crypto::CryptoRef, custom::CustomRef, datagram::DatagramRef,
+        stream::StreamRef, Ack, ConnectionClose, DataBlocked, DcStatelessResetTokens,
+        HandshakeDone, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken,
+        PathChallenge, PathResponse, ResetStream, ResetStreamAt, RetireConnectionId, StopSending,
+        StreamDataBlocked, StreamsBlocked, Timestamp as TimestampFrame,
     },
     inet::DatagramInfo,
     packet::{
         encoding::{PacketEncoder, PacketEncodingError},
-        number::{PacketNumber, PacketNumberRange, PacketNumberSpace, SlidingWindow},
+        number::{
+            PacketNumber, PacketNumberLen, PacketNumberRange, PacketNumberSpace, SlidingWindow,
+        },
         short::{CleartextShort, ProtectedShort, Short, SpinBit},
     },
     random::Generator,
     recovery::MAX_BURST_PACKETS,
     time::{timer, Timestamp},
     transport,
+    varint::VarInt,
 };
 
 // Ensure there is a gap between skipped packet numbers
@@ -68,18 +81,31 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     //# the handshake (Section 4.1.2).
     key_set: KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
     header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+    /// The packets in the current GSO payload which are waiting on header protection
+    pub deferred_header_protection: transmission::header_protection::Deferred,
 
     ping: flag::Ping,
     keep_alive: KeepAlive,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
     pub datagram_manager: datagram::Manager<Config>,
+    pub custom_frame_manager: custom_frame::Manager<Config>,
     pub dc_manager: dc::Manager<Config>,
     /// Counter used for detecting an Optimistic Ack attack
     skip_counter: Option<Counter<u32, Saturating>>,
+    /// Whether packet numbers are skipped for detecting an Optimistic Ack attack
+    packet_number_skipping: bool,
+    /// The settings for decoding TIMESTAMP frames sent by the peer, if they are accepted
+    peer_timestamp_settings: Option<ack::Settings>,
     /// Keeps track of if the TLS session still exists. If it does, we buffer
     /// the crypto frames received. If not there's no chance that these messages will be read.
     pub buffer_crypto_frames: bool,
+    /// The task waiting for the buffered application data to be flushed
+    flush_waiter: Option<(connection::FlushMode, Waker)>,
+    /// The PING requested by the application, if any
+    ping_probe: PingProbe,
+    /// Tokens received in NEW_TOKEN frames which haven't been stored by the endpoint yet
+    new_tokens: Vec<Bytes>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for ApplicationSpace<Config> {
@@ -102,8 +128,10 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         now: Timestamp,
         stream_manager: Config::StreamManager,
         ack_manager: AckManager,
+        recovery_manager: recovery::Manager<Config>,
         keep_alive: KeepAlive,
         datagram_manager: datagram::Manager<Config>,
+        custom_frame_manager: custom_frame::Manager<Config>,
         dc_manager: dc::Manager<Config>,
     ) -> Self {
         let key_set = KeySet::new(key, Self::key_limits());
@@ -116,17 +144,70 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             crypto_stream: CryptoStream::new(),
             key_set,
             header_key,
+            deferred_header_protection: Default::default(),
             ping: flag::Ping::default(),
             keep_alive,
             processed_packet_numbers: SlidingWindow::default(),
-            recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
+            recovery_manager,
             datagram_manager,
+            custom_frame_manager,
             dc_manager,
             skip_counter: None,
+            packet_number_skipping: true,
+            peer_timestamp_settings: None,
             buffer_crypto_frames: Config::ENDPOINT_TYPE.is_client(),
+            flush_waiter: None,
+            ping_probe: PingProbe::default(),
+            new_tokens: Vec::new(),
         }
     }
 
+    /// Sets whether packet numbers are skipped for detecting an Optimistic Ack attack
+    pub fn with_packet_number_skipping(mut self, enabled: bool) -> Self {
+        self.packet_number_skipping = enabled;
+        self
+    }
+
+    /// Sets the settings for decoding TIMESTAMP frames sent by the peer
+    ///
+    /// TIMESTAMP frames are rejected if `None`.
+    pub fn with_peer_timestamps(mut self, settings: Option<ack::Settings>) -> Self {
+        self.peer_timestamp_settings = settings;
+        self
+    }
+
+    /// Returns the largest DATAGRAM frame payload which fits in a single 1-RTT packet
+    ///
+    /// This accounts for the short packet header, the largest packet number encoding, the AEAD
+    /// tag and the DATAGRAM frame header, and is further limited by the peer's
+    /// `max_datagram_frame_size` transport parameter.
+    pub fn max_datagram_payload(
+        &self,
+        max_datagram_size: usize,
+        peer_connection_id_len: usize,
+    ) -> usize {
+        let header_len = 1 + peer_connection_id_len + PacketNumberLen::MAX_LEN;
+        let tag_len = self.key_set.active_key().key().tag_len();
+        let frame_len = frame::datagram::DATAGRAM_TAG.encoding_size()
+            + VarInt::try_from(max_datagram_size)
+                .unwrap_or(VarInt::MAX)
+                .encoding_size();
+
+        let payload_len = max_datagram_size.saturating_sub(header_len + tag_len + frame_len);
+        let peer_limit = self
+            .datagram_manager
+            .peer_max_datagram_payload()
+            .try_into()
+            .unwrap_or(usize::MAX);
+
+        payload_len.min(peer_limit)
+    }
+
+    /// Returns the tokens received in NEW_TOKEN frames since the last call
+    pub fn take_new_tokens(&mut self) -> Vec<Bytes> {
+        core::mem::take(&mut self.new_tokens)
+    }
+
     /// Returns true if the packet number has already been processed
     pub fn is_duplicate<Pub: event::ConnectionPublisher>(
         &self,
@@ -223,6 +304,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 &mut self.recovery_manager,
                 &mut self.crypto_stream,
                 &mut self.datagram_manager,
+                &mut self.custom_frame_manager,
                 &mut self.dc_manager,
             ),
             timestamp: context.timestamp,
@@ -236,24 +318,57 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         let spin_bit = self.spin_bit;
         let header_key = &self.header_key;
-        let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
-                    let packet = Short {
-                        spin_bit,
-                        key_phase,
-                        destination_connection_id,
-                        packet_number,
-                        payload,
-                    };
-                    packet.encode_packet(
-                        key,
-                        header_key,
-                        packet_number_encoder,
-                        min_packet_len,
-                        buffer,
-                    )
-                })?;
+        let buffer = if context.defer_header_protection {
+            // The packet is counted against the limits of the key here, but is only sealed once
+            // the payload is finished
+            let ((packet, key_phase), buffer) =
+                self.key_set
+                    .encrypt_packet(buffer, |buffer, key, key_phase| {
+                        let packet = Short {
+                            spin_bit,
+                            key_phase,
+                            destination_connection_id,
+                            packet_number,
+                            payload,
+                        };
+                        let (packet, buffer) = packet.encode_unsealed_packet(
+                            key,
+                            header_key,
+                            packet_number_encoder,
+                            min_packet_len,
+                            buffer,
+                        )?;
+                        Ok(((packet, key_phase), buffer))
+                    })?;
+
+            self.deferred_header_protection.on_packet(packet, key_phase);
+
+            buffer
+        } else {
+            let (encrypted_packet, buffer) =
+                self.key_set
+                    .encrypt_packet(buffer, |buffer, key, key_phase| {
+                        let packet = Short {
+                            spin_bit,
+                            key_phase,
+                            destination_connection_id,
+                            packet_number,
+                            payload,
+                        };
+                        packet.encode_sealed_packet(
+                            key,
+                            header_key,
+                            packet_number_encoder,
+                            min_packet_len,
+                            buffer,
+                        )
+                    })?;
+
+            crypto::protect(header_key, encrypted_packet)
+                .expect("header protection should always work");
+
+            buffer
+        };
 
         outcome.bytes_progressed +=
             (self.stream_manager.outgoing_bytes_progressed() - bytes_progressed).as_u64() as usize;
@@ -266,6 +381,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             skipped_packet_number,
         );
 
+        self.on_flush_progress();
+
         Ok((outcome, buffer))
     }
 
@@ -349,6 +466,22 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             });
     }
 
+    /// Seals the deferred packets in a finished GSO payload
+    ///
+    /// `on_datagram` is called for each datagram in the payload which contained a deferred packet.
+    pub fn finish_payload<F: FnMut(path::Id, &mut [u8])>(
+        &mut self,
+        payload: &mut [u8],
+        on_datagram: F,
+    ) {
+        self.deferred_header_protection.finish(
+            &mut self.key_set,
+            &self.header_key,
+            payload,
+            on_datagram,
+        );
+    }
+
     pub(super) fn on_transmit_burst_complete(
         &mut self,
         active_path: &Path<Config>,
@@ -481,6 +614,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         );
 
         match self.skip_counter {
+            // the counter is never armed when skipping is disabled
+            _ if !self.packet_number_skipping => (),
             Some(skip_counter) if skip_counter == 0 => {
                 if self.tx_packet_numbers.should_skip_packet_number() {
                     Self::arm_skip_counter(
@@ -500,6 +635,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         self.stream_manager.on_timeout(timestamp);
 
+        // packets declared lost no longer hold the connection from being idle
+        self.on_flush_progress();
+
         if self.keep_alive.on_timeout(timestamp).is_ready() {
             publisher.on_keep_alive_timer_expired(event::builder::KeepAliveTimerExpired {
                 timeout: self.keep_alive.period(),
@@ -540,10 +678,80 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.ping.send()
     }
 
+    /// Polls for a PING to be acknowledged by the peer
+    ///
+    /// A PING is sent if the task isn't already waiting on one. Only the most recently registered
+    /// waker is notified.
+    pub fn poll_ping_ack(&mut self, waker: &Waker) -> Poll<()> {
+        if core::mem::take(&mut self.ping_probe.is_acked) {
+            self.ping_probe.waiter = None;
+            return Poll::Ready(());
+        }
+
+        if self.ping_probe.waiter.is_none() {
+            self.ping.send();
+        }
+
+        self.ping_probe.waiter = Some(waker.clone());
+
+        Poll::Pending
+    }
+
+    /// Notifies the task waiting on a PING, if any, so it can observe the latest connection state
+    pub fn wake_ping_waiter(&mut self) {
+        if let Some(waker) = self.ping_probe.waiter.take() {
+            waker.wake();
+        }
+    }
+
     pub fn keep_alive(&mut self, enabled: bool) {
         self.keep_alive.update(enabled);
     }
 
+    /// Polls for all of the currently buffered application data to be flushed
+    ///
+    /// The waker is notified once the data has been transmitted or, depending on the `mode`,
+    /// acknowledged by the peer. Only the most recently registered waker is notified.
+    pub fn poll_flush(&mut self, mode: connection::FlushMode, waker: &Waker) -> Poll<()> {
+        if self.is_flushed(mode) {
+            self.flush_waiter = None;
+            return Poll::Ready(());
+        }
+
+        // only the most recent task is notified, similar to accepting streams
+        self.flush_waiter = Some((mode, waker.clone()));
+
+        Poll::Pending
+    }
+
+    /// Notifies the flush waiter if the application data has been flushed
+    fn on_flush_progress(&mut self) {
+        if let Some((mode, _)) = self.flush_waiter {
+            if self.is_flushed(mode) {
+                self.wake_flush_waiter();
+            }
+        }
+    }
+
+    /// Notifies the flush waiter, if any, so it can observe the latest connection state
+    pub fn wake_flush_waiter(&mut self) {
+        if let Some((_, waker)) = self.flush_waiter.take() {
+            waker.wake();
+        }
+    }
+
+    fn is_flushed(&self, mode: connection::FlushMode) -> bool {
+        self.stream_manager.is_flushed(mode)
+            && !self.datagram_manager.sender.has_transmission_interest()
+            && !(mode.is_idle() && self.is_draining())
+    }
+
+    /// Returns `true` if any frames are waiting to be transmitted or acknowledged
+    fn is_draining(&self) -> bool {
+        self.has_transmission_interest()
+            || self.recovery_manager.has_ack_eliciting_packets_in_flight()
+    }
+
     /// Returns the Packet Number to be used when encoding outgoing packets
     fn packet_number_encoder(&self) -> PacketNumber {
         self.tx_packet_numbers.largest_sent_packet_number_acked()
@@ -572,6 +780,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 path_manager,
                 tx_packet_numbers: &mut self.tx_packet_numbers,
                 dc_manager: &mut self.dc_manager,
+                datagram_manager: &mut self.datagram_manager,
+                ping_probe: &mut self.ping_probe,
             },
         )
     }
@@ -695,6 +905,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ApplicationS
         self.recovery_manager.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
+        self.custom_frame_manager.transmission_interest(query)?;
         self.dc_manager.transmission_interest(query)?;
         Ok(())
     }
@@ -706,6 +917,25 @@ impl<Config: endpoint::Config> connection::finalization::Provider for Applicatio
     }
 }
 
+/// Tracks a PING requested by the application
+#[derive(Debug, Default)]
+struct PingProbe {
+    /// The task waiting for the PING to be acknowledged
+    waiter: Option<Waker>,
+    /// Set once the PING is acknowledged, until the waiting task observes it
+    is_acked: bool,
+}
+
+impl PingProbe {
+    #[inline]
+    fn on_ack(&mut self) {
+        if let Some(waker) = self.waiter.take() {
+            self.is_acked = true;
+            waker.wake();
+        }
+    }
+}
+
 struct RecoveryContext<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
     handshake_status: &'a mut HandshakeStatus,
@@ -717,6 +947,8 @@ struct RecoveryContext<'a, Config: endpoint::Config> {
     path_manager: &'a mut path::Manager<Config>,
     tx_packet_numbers: &'a mut TxPacketNumbers,
     dc_manager: &'a mut dc::Manager<Config>,
+    datagram_manager: &'a mut datagram::Manager<Config>,
+    ping_probe: &'a mut PingProbe,
 }
 
 impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'a, Config> {
@@ -777,10 +1009,13 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.dc_manager
             .on_packet_ack(packet_number_range, publisher);
         self.crypto_stream.on_packet_ack(packet_number_range);
-        self.ping.on_packet_ack(packet_number_range);
+        if self.ping.on_packet_ack(packet_number_range) {
+            self.ping_probe.on_ack();
+        }
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
+        self.datagram_manager.on_packet_ack(packet_number_range);
     }
 
     fn on_packet_ack(&mut self, timestamp: Timestamp, packet_number_range: &PacketNumberRange) {
@@ -802,6 +1037,7 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.stream_manager.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
+        self.datagram_manager.on_packet_loss(packet_number_range);
     }
 
     fn on_rtt_update(&mut self, now: Timestamp) {
@@ -872,14 +1108,18 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         let (recovery_manager, mut context) =
             self.recovery(handshake_status, local_id_registry, path_id, path_manager);
 
-        recovery_manager.on_ack_frame(
+        let result = recovery_manager.on_ack_frame(
             timestamp,
             frame,
             packet_number,
             random_generator,
             &mut context,
             publisher,
-        )
+        );
+
+        self.on_flush_progress();
+
+        result
     }
 
     fn handle_connection_close_frame(
@@ -915,6 +1155,16 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_custom_frame(
+        &mut self,
+        path: s2n_quic_core::event::api::Path<'_>,
+        packet_number: PacketNumber,
+        frame: CustomRef,
+    ) -> Result<(), transport::Error> {
+        self.custom_frame_manager
+            .on_custom_frame(path, packet_number, frame)
+    }
+
     fn handle_data_blocked_frame(&mut self, frame: DataBlocked) -> Result<(), transport::Error> {
         self.stream_manager.on_data_blocked(frame)
     }
@@ -938,6 +1188,13 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         self.stream_manager.on_reset_stream(&frame)
     }
 
+    fn handle_reset_stream_at_frame(
+        &mut self,
+        frame: ResetStreamAt,
+    ) -> Result<(), transport::Error> {
+        self.stream_manager.on_reset_stream_at(&frame)
+    }
+
     fn handle_stop_sending_frame(&mut self, frame: StopSending) -> Result<(), transport::Error> {
         self.stream_manager.on_stop_sending(&frame)
     }
@@ -966,7 +1223,9 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
                 .with_reason(Self::INVALID_FRAME_ERROR)
                 .with_frame_type(frame.tag().into()));
         }
-        // TODO add support for the NEW_TOKEN_FRAME on the client
+
+        // the endpoint stores the token in its cache once the packet has been processed
+        self.new_tokens.push(Bytes::copy_from_slice(frame.token));
         Ok(())
     }
 
@@ -1121,6 +1380,36 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_timestamp_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: TimestampFrame,
+        packet_number: PacketNumber,
+        path_id: path::Id,
+        path: &mut Path<Config>,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        // the peer can only send TIMESTAMP frames if the local endpoint advertised it accepts them
+        let Some(settings) = self.peer_timestamp_settings else {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("TIMESTAMP frames were not negotiated")
+                .with_frame_type(frame.tag()));
+        };
+
+        if let Some(sample) =
+            path.one_way_delay
+                .on_timestamp(packet_number, frame.timestamp, settings)
+        {
+            path.congestion_controller.on_one_way_delay_update(
+                sample,
+                timestamp,
+                &mut recovery::congestion_controller::PathPublisher::new(publisher, path_id),
+            );
+        }
+
+        Ok(())
+    }
+
     fn on_processed_packet<Pub: event::ConnectionPublisher>(
         &mut self,
         processed_packet: ProcessedPacket,
@@ -3,7 +3,7 @@
 
 use crate::{
     sync::data_sender::{self, DataSender, OutgoingDataFlowController},
-    transmission,
+    transmission::{self, interest::Provider as _},
 };
 use s2n_quic_core::{
     ack, buffer::Reassembler, frame::crypto::CryptoRef, transport, varint::VarInt,
@@ -123,6 +123,21 @@ impl CryptoStream {
         self.tx.on_packet_loss(ack_set);
     }
 
+    /// This method gets called when the packet space requires a PTO probe
+    pub fn on_probe(&mut self) {
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
+        //# An endpoint SHOULD include new data in packets that are sent on PTO
+        //# expiration.  Previously sent data MAY be sent if no new data can be
+        //# sent.
+
+        // If the handshake flight was lost, sending a PING would only elicit an ACK and delay the
+        // retransmission for another round trip. Instead, the unacknowledged CRYPTO data is
+        // resent in the probe so the peer can make progress.
+        if !self.tx.has_transmission_interest() && self.tx.is_inflight() {
+            self.tx.on_all_lost();
+        }
+    }
+
     /// This method gets called when a Retry packet is processed.
     pub fn on_retry_packet(&mut self) {
         self.tx.on_all_lost();
@@ -138,3 +153,67 @@ impl transmission::interest::Provider for CryptoStream {
         self.tx.transmission_interest(query)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::testing::{MockWriteContext, OutgoingFrameBuffer};
+    use bytes::Bytes;
+    use s2n_quic_core::{
+        endpoint, frame::Frame, packet::number::PacketNumberSpace, time::clock::testing as time,
+        transmission::interest::Interest,
+    };
+
+    /// Transmits the pending data and returns the offset of each written CRYPTO frame
+    fn transmit(stream: &mut CryptoStream, frame_buffer: &mut OutgoingFrameBuffer) -> Vec<u64> {
+        let mut context = MockWriteContext::new(
+            time::now(),
+            frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+        stream.tx.on_transmit((), &mut context).unwrap();
+        frame_buffer.flush();
+
+        let mut offsets = vec![];
+        while let Some(mut frame) = frame_buffer.pop_front() {
+            if let Frame::Crypto(frame) = frame.as_frame() {
+                offsets.push(frame.offset.as_u64());
+            }
+        }
+        offsets
+    }
+
+    #[test]
+    fn probe_retransmits_inflight_data_test() {
+        let mut stream = CryptoStream::new();
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+
+        // probing without any data is a no-op
+        stream.on_probe();
+        assert_eq!(stream.get_transmission_interest(), Interest::None);
+
+        stream.tx.push(Bytes::from_static(&[1; 100]));
+        assert_eq!(transmit(&mut stream, &mut frame_buffer), [0]);
+        assert_eq!(stream.get_transmission_interest(), Interest::None);
+
+        // new data is preferred over resending inflight data
+        stream.tx.push(Bytes::from_static(&[2; 100]));
+        stream.on_probe();
+        assert_eq!(stream.get_transmission_interest(), Interest::NewData);
+        assert_eq!(transmit(&mut stream, &mut frame_buffer), [100]);
+
+        // the inflight data is resent once there isn't any new data
+        stream.on_probe();
+        assert_eq!(stream.get_transmission_interest(), Interest::LostData);
+        assert_eq!(transmit(&mut stream, &mut frame_buffer), [0]);
+
+        // acknowledged data is not resent
+        stream.on_packet_ack(
+            &PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(2)),
+        );
+        stream.on_probe();
+        assert_eq!(stream.get_transmission_interest(), Interest::None);
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    endpoint, path,
+    transmission::{interest, WriteContext},
+};
+use s2n_quic_core::{
+    connection,
+    custom_frame::{Endpoint, Handler, ReceiveContext, WriteError},
+    frame::{self, custom::CustomRef},
+    packet::number::PacketNumber,
+    transport,
+    varint::VarInt,
+};
+
+type ConfigHandler<Config> =
+    <<Config as endpoint::Config>::CustomFrameEndpoint as Endpoint>::Handler;
+
+// Contains the custom frame handler for the connection.
+//
+// Used to call custom frame callbacks during packet transmission and
+// packet processing.
+pub struct Manager<Config: endpoint::Config> {
+    handler: Option<ConfigHandler<Config>>,
+}
+
+impl<Config: endpoint::Config> Manager<Config> {
+    pub fn new(handler: Option<ConfigHandler<Config>>) -> Self {
+        Self { handler }
+    }
+
+    /// A callback that allows users to write custom frames directly to the packet.
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W, path: &path::Path<Config>) {
+        if let Some(handler) = self.handler.as_mut() {
+            let mut packet = Packet { context, path };
+            handler.on_transmit(&mut packet);
+        }
+    }
+
+    // A callback that allows users to access custom frames directly after they are
+    // received.
+    pub fn on_custom_frame(
+        &mut self,
+        path: s2n_quic_core::event::api::Path<'_>,
+        packet_number: PacketNumber,
+        frame: CustomRef,
+    ) -> Result<(), transport::Error> {
+        let handler = self.handler.as_mut().ok_or(
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-12.4
+            //# An endpoint MUST treat the receipt of a frame of unknown type as a
+            //# connection error of type FRAME_ENCODING_ERROR.
+            transport::Error::FRAME_ENCODING_ERROR.with_reason("unknown frame type"),
+        )?;
+        let context = ReceiveContext::new(path, packet_number.as_u64());
+        handler.on_frame(&context, frame.frame_type, frame.data)
+    }
+
+    pub fn on_connection_error(&mut self, error: connection::Error) {
+        if let Some(handler) = self.handler.as_mut() {
+            handler.on_connection_error(error);
+        }
+    }
+}
+
+impl<Config: endpoint::Config> interest::Provider for Manager<Config> {
+    #[inline]
+    fn transmission_interest<Q: interest::Query>(&self, query: &mut Q) -> interest::Result {
+        if let Some(handler) = self.handler.as_ref() {
+            if handler.has_transmission_interest() {
+                query.on_new_data()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Packet<'a, C: WriteContext, Config: endpoint::Config> {
+    context: &'a mut C,
+    path: &'a path::Path<Config>,
+}
+
+impl<'a, C: WriteContext, Config: endpoint::Config> s2n_quic_core::custom_frame::Packet
+    for Packet<'a, C, Config>
+{
+    /// Returns the remaining space in the packet
+    fn remaining_capacity(&self) -> usize {
+        self.context.remaining_capacity()
+    }
+
+    /// Returns the RTT and congestion window of the path
+    fn path_info(&self) -> connection::PathInfo {
+        self.path.info()
+    }
+
+    /// Writes a single custom frame to a packet
+    fn write_frame(&mut self, frame_type: VarInt, payload: &[u8]) -> Result<(), WriteError> {
+        if !frame::is_custom_frame_type(frame_type) {
+            return Err(WriteError::InvalidFrameType);
+        }
+
+        let frame = frame::Custom {
+            frame_type,
+            data: payload,
+        };
+        self.context
+            .write_frame(&frame)
+            .ok_or(WriteError::ExceedsPacketCapacity)?;
+
+        Ok(())
+    }
+}
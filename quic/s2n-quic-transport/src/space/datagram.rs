@@ -9,11 +9,13 @@ use crate::{
         WriteContext,
     },
 };
-use core::task::Poll;
+use alloc::collections::VecDeque;
+use core::task::{Poll, Waker};
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{
     datagram::{Endpoint, ReceiveContext, Receiver, Sender, WriteError},
     frame::{self, datagram::DatagramRef},
+    packet::number::{PacketNumber, PacketNumberRange},
     query,
     varint::VarInt,
 };
@@ -26,6 +28,11 @@ pub struct Manager<Config: endpoint::Config> {
     pub sender: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Sender,
     pub receiver: <<Config as endpoint::Config>::DatagramEndpoint as Endpoint>::Receiver,
     max_datagram_payload: u64,
+    /// The task waiting for the maximum datagram payload to change from the value it observed
+    payload_waiter: Option<(usize, Waker)>,
+    /// Packets carrying datagrams which have not been acknowledged or declared lost, in
+    /// ascending order
+    pending_packets: VecDeque<PacketNumber>,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -38,6 +45,49 @@ impl<Config: endpoint::Config> Manager<Config> {
             sender,
             receiver,
             max_datagram_payload,
+            payload_waiter: None,
+            pending_packets: VecDeque::new(),
+        }
+    }
+
+    /// Returns the largest datagram payload the peer is willing to accept
+    #[inline]
+    pub fn peer_max_datagram_payload(&self) -> u64 {
+        self.max_datagram_payload
+    }
+
+    /// Polls for the maximum datagram payload to change from the `previous` value
+    ///
+    /// Only the most recent task is notified.
+    pub fn poll_max_datagram_payload(
+        &mut self,
+        current: usize,
+        previous: usize,
+        waker: &Waker,
+    ) -> Poll<usize> {
+        if current != previous {
+            self.payload_waiter = None;
+            return Poll::Ready(current);
+        }
+
+        self.payload_waiter = Some((previous, waker.clone()));
+
+        Poll::Pending
+    }
+
+    /// Notifies the waiting task if the maximum datagram payload changed
+    pub fn on_max_datagram_payload_update(&mut self, current: usize) {
+        if let Some((previous, _)) = self.payload_waiter {
+            if previous != current {
+                self.wake_payload_waiter();
+            }
+        }
+    }
+
+    /// Notifies the waiting task, if any, so it can observe the latest connection state
+    pub fn wake_payload_waiter(&mut self) {
+        if let Some((_, waker)) = self.payload_waiter.take() {
+            waker.wake();
         }
     }
 
@@ -53,8 +103,45 @@ impl<Config: endpoint::Config> Manager<Config> {
             has_pending_streams: stream_manager.has_pending_streams(),
             datagrams_prioritized,
             max_datagram_payload: self.max_datagram_payload,
+            has_written: false,
         };
         self.sender.on_transmit(&mut packet);
+
+        if packet.has_written {
+            let packet_number = packet.context.packet_number();
+            debug_assert!(self
+                .pending_packets
+                .back()
+                .map_or(true, |last| *last < packet_number));
+            self.pending_packets.push_back(packet_number);
+        }
+    }
+
+    /// Notifies the sender of any acknowledged packets that carried datagrams
+    pub fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        let pending = self.pending_range(packet_number_range);
+        for packet_number in self.pending_packets.drain(pending) {
+            self.sender.on_datagram_ack(packet_number.as_u64());
+        }
+    }
+
+    /// Notifies the sender of any lost packets that carried datagrams
+    pub fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        let pending = self.pending_range(packet_number_range);
+        for packet_number in self.pending_packets.drain(pending) {
+            self.sender.on_datagram_loss(packet_number.as_u64());
+        }
+    }
+
+    /// Returns the indices of the pending packets contained in the range
+    fn pending_range(&self, packet_number_range: &PacketNumberRange) -> core::ops::Range<usize> {
+        let start = self
+            .pending_packets
+            .partition_point(|pn| *pn < packet_number_range.start());
+        let end = self
+            .pending_packets
+            .partition_point(|pn| *pn <= packet_number_range.end());
+        start..end
     }
 
     // A callback that allows users to access datagrams directly after they are
@@ -100,6 +187,7 @@ struct Packet<'a, C: WriteContext> {
     has_pending_streams: bool,
     datagrams_prioritized: bool,
     max_datagram_payload: u64,
+    has_written: bool,
 }
 
 impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
@@ -137,6 +225,7 @@ impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
         self.context
             .write_frame(&frame)
             .ok_or(WriteError::ExceedsPacketCapacity)?;
+        self.has_written = true;
 
         Ok(())
     }
@@ -150,4 +239,9 @@ impl<'a, C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'a, C> {
     fn datagrams_prioritized(&self) -> bool {
         self.datagrams_prioritized
     }
+
+    /// Returns the number of the packet being written
+    fn packet_number(&self) -> u64 {
+        self.context.packet_number().as_u64()
+    }
 }
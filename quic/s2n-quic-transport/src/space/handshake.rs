@@ -281,6 +281,10 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
             &mut context,
             publisher,
         );
+
+        if self.recovery_manager.requires_probe() {
+            self.crypto_stream.on_probe();
+        }
     }
 
     /// Called before the Handshake packet space is discarded
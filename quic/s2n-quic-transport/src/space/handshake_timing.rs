@@ -0,0 +1,100 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic_core::{
+    event::{self, ConnectionPublisher},
+    time::Timestamp,
+};
+
+/// Records when each step of the handshake happened so it can be reported in a single event
+#[derive(Debug)]
+pub struct HandshakeTiming {
+    start: Timestamp,
+    initial_sent: Option<Timestamp>,
+    initial_received: Option<Timestamp>,
+    handshake_keys_available: Option<Timestamp>,
+    handshake_complete: Option<Timestamp>,
+    retry: bool,
+    version_negotiation: bool,
+    is_published: bool,
+}
+
+impl HandshakeTiming {
+    pub fn new(start: Timestamp) -> Self {
+        Self {
+            start,
+            initial_sent: None,
+            initial_received: None,
+            handshake_keys_available: None,
+            handshake_complete: None,
+            retry: false,
+            version_negotiation: false,
+            is_published: false,
+        }
+    }
+
+    #[inline]
+    pub fn on_initial_sent(&mut self, timestamp: Timestamp) {
+        self.initial_sent.get_or_insert(timestamp);
+    }
+
+    #[inline]
+    pub fn on_initial_received(&mut self, timestamp: Timestamp) {
+        self.initial_received.get_or_insert(timestamp);
+    }
+
+    #[inline]
+    pub fn on_handshake_keys_available(&mut self, timestamp: Timestamp) {
+        self.handshake_keys_available.get_or_insert(timestamp);
+    }
+
+    #[inline]
+    pub fn on_handshake_complete(&mut self, timestamp: Timestamp) {
+        self.handshake_complete.get_or_insert(timestamp);
+    }
+
+    #[inline]
+    pub fn on_retry(&mut self) {
+        self.retry = true;
+    }
+
+    /// Returns `true` if any packet was received from the peer during the handshake
+    #[inline]
+    pub fn has_received_packet(&self) -> bool {
+        self.initial_received.is_some() || self.retry || self.version_negotiation
+    }
+
+    #[inline]
+    pub fn on_version_negotiation(&mut self) {
+        // only packets which arrive before the handshake completes are relevant
+        if self.handshake_complete.is_none() {
+            self.version_negotiation = true;
+        }
+    }
+
+    /// Publishes the timing event, if it hasn't been already
+    ///
+    /// This should be called once the handshake is done.
+    pub fn on_handshake_done<Pub: ConnectionPublisher>(
+        &mut self,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        if self.is_published {
+            return;
+        }
+        self.is_published = true;
+
+        let elapsed = |time: Timestamp| time.saturating_duration_since(self.start);
+
+        publisher.on_handshake_timing(event::builder::HandshakeTiming {
+            initial_sent: self.initial_sent.map(elapsed),
+            initial_received: self.initial_received.map(elapsed),
+            handshake_keys_available: self.handshake_keys_available.map(elapsed),
+            handshake_complete: elapsed(self.handshake_complete.unwrap_or(timestamp)),
+            handshake_done: elapsed(timestamp),
+            retry: self.retry,
+            version_negotiation: self.version_negotiation,
+        });
+    }
+}
@@ -47,9 +47,11 @@ pub struct InitialSpace<Config: endpoint::Config> {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.3
     //# Subsequent Initial packets from the client include the connection ID
     //# and token values from the Retry packet.
-    retry_token: Vec<u8>,
+    token: Vec<u8>,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
+    /// Set if an immediate probe should follow the first ack-eliciting packet
+    duplicate_first_flight: bool,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for InitialSpace<Config> {
@@ -77,12 +79,24 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             crypto_stream: CryptoStream::new(),
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Initial, now),
             received_hello_message: false,
-            retry_token: Vec::new(),
+            token: Vec::new(),
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::Initial),
+            duplicate_first_flight: false,
         }
     }
 
+    /// Sends the first flight twice by probing immediately after the first ack-eliciting packet
+    pub fn enable_duplicate_first_flight(&mut self) {
+        self.duplicate_first_flight = true;
+    }
+
+    /// Includes a token from a NEW_TOKEN frame of a previous connection in the Initial packets
+    pub fn on_cached_token(&mut self, token: &[u8]) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.token = token.to_vec();
+    }
+
     /// This method gets called when a Retry packet is processed.
     ///
     /// Reset the TLS stack and recover state when the first Retry packet is processed.
@@ -96,7 +110,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         publisher: &mut Pub,
     ) {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
-        self.retry_token = retry_token.to_vec();
+        self.token = retry_token.to_vec();
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.2
         //# Changing the Destination Connection ID field also results in
@@ -199,7 +213,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             version: context.quic_version,
             destination_connection_id,
             source_connection_id: context.path_manager[context.path_id].local_connection_id,
-            token: self.retry_token.as_slice(),
+            token: self.token.as_slice(),
             packet_number,
             payload,
         };
@@ -237,6 +251,19 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
                 packet_len: outcome.bytes_sent,
             });
 
+        if self.duplicate_first_flight
+            && outcome.ack_elicitation.is_ack_eliciting()
+            && context.transmission_mode.is_normal()
+        {
+            self.duplicate_first_flight = false;
+
+            // Servers skip the duplicate if the probe can't be sent without exceeding the
+            // anti-amplification limit
+            if !context.path().at_amplification_limit() {
+                self.recovery_manager.force_probe();
+            }
+        }
+
         Ok((outcome, buffer))
     }
 
@@ -331,6 +358,10 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             &mut context,
             publisher,
         );
+
+        if self.recovery_manager.requires_probe() {
+            self.crypto_stream.on_probe();
+        }
     }
 
     /// Called before the Initial packet space is discarded
@@ -16,30 +16,34 @@ use core::{
     ops::RangeInclusive,
     task::{Poll, Waker},
 };
-use s2n_codec::DecoderBufferMut;
+use s2n_codec::{DecoderBufferMut, Encoder as _};
 use s2n_quic_core::{
     application::ServerName,
-    connection::{limits::Limits, InitialId, PeerId},
-    crypto::{tls, tls::Session, CryptoSuite, Key},
+    buffer::reassembler,
+    connection::{handoff, limits::Limits, InitialId, PeerId},
+    crypto::{tls, tls::Context as _, tls::Session, CryptoSuite, Key},
     event::{self, IntoEvent},
     frame::{
-        ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        ack::AckRanges, crypto::CryptoRef, custom::CustomRef, datagram::DatagramRef,
+        stream::StreamRef, Ack, ConnectionClose, DataBlocked, DcStatelessResetTokens,
+        HandshakeDone, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken,
+        PathChallenge, PathResponse, ResetStream, ResetStreamAt, RetireConnectionId, StopSending,
+        StreamDataBlocked, StreamsBlocked, Timestamp as TimestampFrame,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
     time::{timer, Timestamp},
-    transport,
+    transport::{self, parameters::custom},
     varint::VarInt,
 };
 
 mod application;
 mod crypto_stream;
+pub(crate) mod custom_frame;
 pub(crate) mod datagram;
 mod handshake;
 mod handshake_status;
+mod handshake_timing;
 mod initial;
 mod keep_alive;
 mod session_context;
@@ -49,10 +53,23 @@ pub(crate) use application::ApplicationSpace;
 pub(crate) use crypto_stream::CryptoStream;
 pub(crate) use handshake::HandshakeSpace;
 pub(crate) use handshake_status::HandshakeStatus;
+pub(crate) use handshake_timing::HandshakeTiming;
 pub(crate) use initial::InitialSpace;
 pub(crate) use session_context::SessionContext;
 pub(crate) use tx_packet_numbers::TxPacketNumbers;
 
+/// The state a server retains until the connection is handed off to another endpoint
+///
+/// The 1-RTT keys are only derived on the endpoint the connection is handed off to, so the
+/// application space is never created while this is pending.
+#[derive(Debug)]
+pub struct PendingHandoff {
+    pub secrets: tls::OneRttSecrets,
+    pub original_destination_connection_id: InitialId,
+    /// The encoded transport parameters of the client, set once the 1-RTT keys are available
+    pub peer_transport_parameters: Option<Bytes>,
+}
+
 struct SessionInfo<Config: endpoint::Config> {
     session: <Config::TLSEndpoint as tls::Endpoint>::Session,
     initial_cid: InitialId,
@@ -60,6 +77,8 @@ struct SessionInfo<Config: endpoint::Config> {
 
 pub struct PacketSpaceManager<Config: endpoint::Config> {
     session_info: Option<SessionInfo<Config>>,
+    /// The TLS session, retained after the handshake so keying material can be exported
+    retained_session: Option<<Config::TLSEndpoint as tls::Endpoint>::Session>,
     retry_cid: Option<Box<PeerId>>,
     initial: Option<Box<InitialSpace<Config>>>,
     handshake: Option<Box<HandshakeSpace<Config>>>,
@@ -77,6 +96,18 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     //# another mechanism is used for agreeing on an application protocol,
     //# endpoints MUST use ALPN for this purpose.
     pub application_protocol: Bytes,
+    /// Transport parameters sent by the peer that are unknown to the transport
+    pub peer_transport_parameters: custom::Parameters,
+    /// The custom frame handler, held until the application space is created
+    pub custom_frame_handler:
+        Option<<Config::CustomFrameEndpoint as s2n_quic_core::custom_frame::Endpoint>::Handler>,
+    /// Records when each step of the handshake happened
+    pub handshake_timing: HandshakeTiming,
+    /// The endpoint's pool for stream receive buffers, if enabled
+    pub receive_buffer_pool: Option<reassembler::Pool>,
+    /// Set on servers which hand the connection off to another endpoint once the handshake
+    /// completes
+    pub handoff: Option<Box<PendingHandoff>>,
 }
 
 impl<Config: endpoint::Config> fmt::Debug for PacketSpaceManager<Config> {
@@ -124,6 +155,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 session,
                 initial_cid,
             }),
+            retained_session: None,
             retry_cid: None,
             initial: Some(Box::new(InitialSpace::new(
                 initial_key,
@@ -137,6 +169,35 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             handshake_status: HandshakeStatus::default(),
             server_name: None,
             application_protocol: Bytes::new(),
+            peer_transport_parameters: custom::Parameters::default(),
+            custom_frame_handler: None,
+            handshake_timing: HandshakeTiming::new(now),
+            receive_buffer_pool: None,
+            handoff: None,
+        }
+    }
+
+    /// Creates a space manager for a connection handed off by another endpoint
+    ///
+    /// The handshake was completed by the other endpoint so only the application space is
+    /// created, once the 1-RTT keys are installed with [`Self::on_imported`].
+    pub fn new_imported(now: Timestamp) -> Self {
+        Self {
+            session_info: None,
+            retained_session: None,
+            retry_cid: None,
+            initial: None,
+            handshake: None,
+            application: None,
+            zero_rtt_crypto: None,
+            handshake_status: HandshakeStatus::default(),
+            server_name: None,
+            application_protocol: Bytes::new(),
+            peer_transport_parameters: custom::Parameters::default(),
+            custom_frame_handler: None,
+            handshake_timing: HandshakeTiming::new(now),
+            receive_buffer_pool: None,
+            handoff: None,
         }
     }
 
@@ -264,16 +325,29 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 limits,
                 server_name: &mut self.server_name,
                 application_protocol: &mut self.application_protocol,
+                peer_transport_parameters: &mut self.peer_transport_parameters,
+                custom_frame_handler: &mut self.custom_frame_handler,
                 waker,
                 publisher,
                 datagram,
                 dc,
+                receive_buffer_pool: &self.receive_buffer_pool,
+                handoff: &mut self.handoff,
             };
 
-            match session_info.session.poll(&mut context)? {
+            let result = session_info.session.poll(&mut context)?;
+
+            if self.handshake.is_some() {
+                self.handshake_timing.on_handshake_keys_available(now);
+            }
+            if self.handshake_status.is_complete() {
+                self.handshake_timing.on_handshake_complete(now);
+            }
+
+            match result {
                 Poll::Ready(_success) => {
                     if session_info.session.should_discard_session() {
-                        self.discard_session();
+                        self.discard_session(limits.keying_material_export());
                     }
 
                     self.retry_cid = None;
@@ -311,25 +385,92 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 limits,
                 server_name: &mut self.server_name,
                 application_protocol: &mut self.application_protocol,
+                peer_transport_parameters: &mut self.peer_transport_parameters,
+                custom_frame_handler: &mut self.custom_frame_handler,
                 waker,
                 publisher,
                 datagram,
                 dc,
+                receive_buffer_pool: &self.receive_buffer_pool,
+                handoff: &mut self.handoff,
             };
 
             session_info
                 .session
                 .process_post_handshake_message(&mut context)?;
             if session_info.session.should_discard_session() {
-                self.discard_session();
+                self.discard_session(limits.keying_material_export());
             }
         }
 
         Ok(())
     }
 
-    fn discard_session(&mut self) {
-        self.session_info = None;
+    /// Replays the outcome of the handshake of a connection handed off by another endpoint
+    pub fn on_imported<Pub: event::ConnectionPublisher>(
+        &mut self,
+        state: &handoff::State,
+        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+        path_manager: &mut path::Manager<Config>,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        limits: &mut Limits,
+        now: Timestamp,
+        waker: &Waker,
+        publisher: &mut Pub,
+        datagram: &mut Config::DatagramEndpoint,
+        dc: &mut Config::DcEndpoint,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+        debug_assert!(self.session_info.is_none());
+
+        let mut context: SessionContext<Config, Pub> = SessionContext {
+            now,
+            initial_cid: &state.original_destination_connection_id,
+            retry_cid: None,
+            initial: &mut self.initial,
+            handshake: &mut self.handshake,
+            application: &mut self.application,
+            zero_rtt_crypto: &mut self.zero_rtt_crypto,
+            path_manager,
+            handshake_status: &mut self.handshake_status,
+            local_id_registry,
+            limits,
+            server_name: &mut self.server_name,
+            application_protocol: &mut self.application_protocol,
+            peer_transport_parameters: &mut self.peer_transport_parameters,
+            custom_frame_handler: &mut self.custom_frame_handler,
+            waker,
+            publisher,
+            datagram,
+            dc,
+            receive_buffer_pool: &self.receive_buffer_pool,
+            handoff: &mut self.handoff,
+        };
+
+        if let Some(server_name) = state.server_name.clone() {
+            context.on_server_name(server_name)?;
+        }
+        context.on_application_protocol(state.application_protocol.clone())?;
+        context.on_one_rtt_keys(
+            key,
+            header_key,
+            tls::ApplicationParameters {
+                transport_parameters: &state.peer_transport_parameters,
+            },
+        )?;
+        context.on_handshake_complete()?;
+
+        self.handshake_timing.on_handshake_complete(now);
+
+        Ok(())
+    }
+
+    fn discard_session(&mut self, retain: bool) {
+        let session_info = self.session_info.take();
+        if retain {
+            self.retained_session = session_info.map(|info| info.session);
+        }
         if let Some((application_space, _status)) = self.application_mut() {
             application_space.crypto_stream.rx.reset();
             application_space.buffer_crypto_frames = false;
@@ -465,6 +606,28 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         self.handshake_status.is_complete()
     }
 
+    /// Exports keying material from the TLS session
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc8446#section-7.5>
+    pub fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), tls::TlsExportError> {
+        if !self.is_handshake_complete() {
+            return Err(tls::TlsExportError::failure());
+        }
+
+        let session = self
+            .retained_session
+            .as_ref()
+            .or_else(|| self.session_info.as_ref().map(|info| &info.session))
+            .ok_or_else(tls::TlsExportError::failure)?;
+
+        session.export_keying_material(label, context, output)
+    }
+
     pub(crate) fn on_transmit_close(
         &mut self,
         early_connection_close: &ConnectionClose,
@@ -549,6 +712,17 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 can_send_handshake,
                 early_connection_close
             );
+
+            // pad the 1-RTT packet up to the configured minimum datagram size
+            if context.min_datagram_size > 0 {
+                context.min_packet_len = Some(
+                    context
+                        .min_datagram_size
+                        .saturating_sub(buffer.len())
+                        .min(buffer.remaining_capacity()),
+                );
+            }
+
             write_packet!(
                 buffer,
                 application_mut,
@@ -566,6 +740,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         publisher: &mut Pub,
     ) {
         self.session_info = None;
+        self.retained_session = None;
         self.retry_cid = None;
         self.discard_initial(path_manager, now, publisher);
         self.discard_handshake(path_manager, publisher);
@@ -586,6 +761,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
     pub fn on_retry_packet(&mut self, retry_source_connection_id: PeerId) {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
         self.retry_cid = Some(Box::new(retry_source_connection_id));
+        self.handshake_timing.on_retry();
     }
 
     pub fn retry_cid(&self) -> Option<&PeerId> {
@@ -786,6 +962,20 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
             .with_frame_type(frame.tag().into()))
     }
 
+    fn handle_custom_frame(
+        &mut self,
+        _path: s2n_quic_core::event::api::Path<'_>,
+        _packet_number: PacketNumber,
+        frame: CustomRef,
+    ) -> Result<(), transport::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-12.4
+        //# An endpoint MUST treat the receipt of a frame of unknown type as a
+        //# connection error of type FRAME_ENCODING_ERROR.
+        Err(transport::Error::FRAME_ENCODING_ERROR
+            .with_reason("unknown frame type")
+            .with_frame_type(frame.tag()))
+    }
+
     fn handle_dc_stateless_reset_tokens_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         frame: DcStatelessResetTokens,
@@ -796,11 +986,26 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
             .with_frame_type(frame.tag()))
     }
 
+    fn handle_timestamp_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: TimestampFrame,
+        _packet_number: PacketNumber,
+        _path_id: path::Id,
+        _path: &mut Path<Config>,
+        _timestamp: Timestamp,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag()))
+    }
+
     default_frame_handler!(handle_data_blocked_frame, DataBlocked);
     default_frame_handler!(handle_max_data_frame, MaxData);
     default_frame_handler!(handle_max_stream_data_frame, MaxStreamData);
     default_frame_handler!(handle_max_streams_frame, MaxStreams);
     default_frame_handler!(handle_reset_stream_frame, ResetStream);
+    default_frame_handler!(handle_reset_stream_at_frame, ResetStreamAt);
     default_frame_handler!(handle_stop_sending_frame, StopSending);
     default_frame_handler!(handle_stream_data_blocked_frame, StreamDataBlocked);
     default_frame_handler!(handle_streams_blocked_frame, StreamsBlocked);
@@ -983,6 +1188,10 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     let on_error = on_frame_processed!(frame);
                     self.handle_reset_stream_frame(frame).map_err(on_error)?;
                 }
+                Frame::ResetStreamAt(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_reset_stream_at_frame(frame).map_err(on_error)?;
+                }
                 Frame::StopSending(frame) => {
                     let on_error = on_frame_processed!(frame);
                     self.handle_stop_sending_frame(frame).map_err(on_error)?;
@@ -1056,6 +1265,27 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     self.handle_dc_stateless_reset_tokens_frame(frame, publisher)
                         .map_err(on_error)?;
                 }
+                Frame::Timestamp(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_timestamp_frame(
+                        frame,
+                        packet_number,
+                        path_id,
+                        &mut path_manager[path_id],
+                        datagram.timestamp,
+                        publisher,
+                    )
+                    .map_err(on_error)?;
+                }
+                Frame::Custom(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_custom_frame(
+                        path_event!(path, path_id).into_event(),
+                        packet_number,
+                        frame.into(),
+                    )
+                    .map_err(on_error)?;
+                }
             }
 
             payload = remaining;
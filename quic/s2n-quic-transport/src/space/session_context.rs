@@ -4,10 +4,10 @@
 use crate::{
     ack::AckManager,
     connection::{self, limits::Limits},
-    endpoint, path,
+    endpoint, path, recovery,
     space::{
-        datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
-        InitialSpace,
+        custom_frame, datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace,
+        HandshakeStatus, InitialSpace, PendingHandoff,
     },
     stream,
 };
@@ -17,6 +17,7 @@ use s2n_codec::{DecoderBuffer, DecoderValue};
 use s2n_quic_core::{
     ack,
     application::ServerName,
+    buffer::reassembler,
     connection::{InitialId, PeerId},
     crypto,
     crypto::{tls, tls::ApplicationParameters, CryptoSuite, Key},
@@ -34,9 +35,10 @@ use s2n_quic_core::{
     transport::{
         self,
         parameters::{
-            ActiveConnectionIdLimit, ClientTransportParameters, DatagramLimits,
-            DcSupportedVersions, InitialFlowControlLimits, InitialSourceConnectionId, MaxAckDelay,
-            ServerTransportParameters, TransportParameter as _,
+            custom, custom::Endpoint as _, ActiveConnectionIdLimit, ClientTransportParameters,
+            DatagramLimits, DcSupportedVersions, InitialFlowControlLimits,
+            InitialSourceConnectionId, MaxAckDelay, ServerTransportParameters,
+            TransportParameter as _,
         },
         Error,
     },
@@ -58,10 +60,16 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
     pub limits: &'a mut Limits,
     pub server_name: &'a mut Option<ServerName>,
     pub application_protocol: &'a mut Bytes,
+    pub peer_transport_parameters: &'a mut custom::Parameters,
+    pub custom_frame_handler: &'a mut Option<
+        <Config::CustomFrameEndpoint as s2n_quic_core::custom_frame::Endpoint>::Handler,
+    >,
     pub waker: &'a Waker,
     pub publisher: &'a mut Pub,
     pub datagram: &'a mut Config::DatagramEndpoint,
     pub dc: &'a mut Config::DcEndpoint,
+    pub receive_buffer_pool: &'a Option<reassembler::Pool>,
+    pub handoff: &'a mut Option<Box<PendingHandoff>>,
 }
 
 impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
@@ -184,6 +192,32 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .register_initial_stateless_reset_token(stateless_reset_token);
         }
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport
+        //# parameter is sent, the sequence number of the supplied connection ID
+        //# is 1.
+        //
+        // The client doesn't migrate to the preferred address but the connection ID can still be
+        // used on the current path like any other connection ID issued by the server.
+        if let Some(preferred_address) = &peer_parameters.preferred_address {
+            let connection_id = PeerId::try_from_bytes(preferred_address.connection_id.as_bytes())
+                .ok_or_else(|| {
+                    transport::Error::TRANSPORT_PARAMETER_ERROR
+                        .with_reason("invalid preferred_address connection id")
+                })?;
+
+            self.path_manager.on_new_connection_id(
+                &connection_id,
+                1,
+                0,
+                &preferred_address.stateless_reset_token,
+                self.publisher,
+            )?;
+        }
+
+        self.path_manager
+            .on_peer_migration_support(peer_parameters.migration_support);
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -392,6 +426,21 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .with_reason("application keys initialized more than once"));
         }
 
+        if self.handoff.is_some() {
+            // The parameters are still validated so an invalid set fails the handshake, but the
+            // application space is only created by the endpoint the connection is handed off to
+            self.on_client_params(DecoderBuffer::new(
+                application_parameters.transport_parameters,
+            ))?;
+            if let Some(handoff) = self.handoff.as_mut() {
+                handoff.peer_transport_parameters = Some(Bytes::copy_from_slice(
+                    application_parameters.transport_parameters,
+                ));
+            }
+
+            return Ok(());
+        }
+
         if Config::ENDPOINT_TYPE.is_client() {
             //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
             //# Therefore, a client SHOULD discard 0-RTT keys as soon as it installs
@@ -413,6 +462,15 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             endpoint::Type::Server => self.on_client_params(param_decoder)?,
         };
 
+        if Config::CustomTransportParameters::ENABLED {
+            let param_decoder = DecoderBuffer::new(application_parameters.transport_parameters);
+            *self.peer_transport_parameters = custom::Parameters::decode_unknown(param_decoder)
+                .map_err(|_| {
+                    transport::Error::TRANSPORT_PARAMETER_ERROR
+                        .with_reason("Invalid custom transport parameters")
+                })?;
+        }
+
         self.local_id_registry
             .set_active_connection_id_limit(active_connection_id_limit.as_u64());
 
@@ -422,20 +480,29 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             self.limits.initial_flow_control_limits(),
             peer_flow_control_limits,
             self.path_manager.active_path().rtt_estimator.min_rtt(),
+            self.receive_buffer_pool.clone(),
         );
 
-        let ack_manager = AckManager::new(
+        let mut ack_manager = AckManager::new(
             PacketNumberSpace::ApplicationData,
             self.limits.ack_settings(),
         );
+        if self.limits.send_timestamps() {
+            ack_manager = ack_manager.with_timestamps(self.now);
+        }
+
+        let recovery_manager = recovery::Manager::new(PacketNumberSpace::ApplicationData)
+            .with_max_sent_packets(self.limits.max_sent_packets() as usize)
+            .with_congestion_state_interval(self.limits.congestion_state_interval());
 
         let keep_alive = KeepAlive::new(
             self.limits.max_idle_timeout(),
             self.limits.max_keep_alive_period(),
         );
 
-        let conn_info =
+        let mut conn_info =
             ConnectionInfo::new(datagram_limits.max_datagram_payload, self.waker.clone());
+        conn_info.peer_supports_fec = datagram_limits.peer_supports_fec;
         let (datagram_sender, datagram_receiver) = self.datagram.create_connection(&conn_info);
         let datagram_manager = datagram::Manager::new(
             datagram_sender,
@@ -476,16 +543,22 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             .on_max_ack_delay(max_ack_delay);
 
         let cipher_suite = key.cipher_suite().into_event();
-        *self.application = Some(Box::new(ApplicationSpace::new(
-            key,
-            header_key,
-            self.now,
-            stream_manager,
-            ack_manager,
-            keep_alive,
-            datagram_manager,
-            dc_manager,
-        )));
+        *self.application = Some(Box::new(
+            ApplicationSpace::new(
+                key,
+                header_key,
+                self.now,
+                stream_manager,
+                ack_manager,
+                recovery_manager,
+                keep_alive,
+                datagram_manager,
+                custom_frame::Manager::new(self.custom_frame_handler.take()),
+                dc_manager,
+            )
+            .with_packet_number_skipping(self.limits.packet_number_skipping())
+            .with_peer_timestamps(self.limits.peer_timestamp_settings()),
+        ));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::OneRtt { generation: 0 },
             cipher_suite,
@@ -494,6 +567,30 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_one_rtt_secrets(&mut self, secrets: &tls::OneRttSecrets) -> Result<(), transport::Error> {
+        if Config::ENDPOINT_TYPE.is_server() && self.limits.connection_handoff() {
+            *self.handoff = Some(Box::new(PendingHandoff {
+                secrets: secrets.clone(),
+                original_destination_connection_id: *self.initial_cid,
+                peer_transport_parameters: None,
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn on_encrypted_client_hello(
+        &mut self,
+        status: tls::EchStatus,
+    ) -> Result<(), transport::Error> {
+        self.publisher
+            .on_encrypted_client_hello(event::builder::EncryptedClientHello {
+                status: status.into_event(),
+            });
+
+        Ok(())
+    }
+
     fn on_server_name(&mut self, server_name: ServerName) -> Result<(), transport::Error> {
         self.publisher
             .on_server_name_information(event::builder::ServerNameInformation {
@@ -522,11 +619,16 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         &mut self,
         session: &impl tls::TlsSession,
     ) -> Result<(), transport::Error> {
-        self.application
-            .as_mut()
-            .expect("application keys should be ready before the tls exporter")
-            .dc_manager
-            .on_path_secrets_ready(session, self.publisher)?;
+        if let Some(application) = self.application.as_mut() {
+            application
+                .dc_manager
+                .on_path_secrets_ready(session, self.publisher)?;
+        } else {
+            debug_assert!(
+                self.handoff.is_some(),
+                "application keys should be ready before the tls exporter"
+            );
+        }
 
         self.publisher
             .on_tls_exporter_ready(event::builder::TlsExporterReady {
@@ -577,6 +679,9 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 );
             }
             Ok(())
+        } else if self.handoff.is_some() {
+            // The connection is confirmed by the endpoint it's handed off to
+            Ok(())
         } else {
             Err(transport::Error::INTERNAL_ERROR
                 .with_reason("handshake cannot be completed without application keys"))
@@ -657,12 +762,15 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
 
     fn send_application(&mut self, transmission: Bytes) {
         if cfg!(any(test, feature = "unstable_resumption")) {
-            self.application
-                .as_mut()
-                .expect("can_send_application should be called before sending")
-                .crypto_stream
-                .tx
-                .push(transmission);
+            if let Some(application) = self.application.as_mut() {
+                application.crypto_stream.tx.push(transmission);
+            } else {
+                // session tickets aren't sent for connections which are handed off
+                debug_assert!(
+                    self.handoff.is_some(),
+                    "can_send_application should be called before sending"
+                );
+            }
         }
     }
 
@@ -13,7 +13,7 @@ use core::{
 };
 pub use s2n_quic_core::{
     application,
-    stream::{ops, StreamError, StreamId, StreamType},
+    stream::{ops, Group, StreamError, StreamId, StreamType},
 };
 
 #[derive(Clone)]
@@ -208,6 +208,25 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.finish().flush().poll(Some(cx))?.into()
         }
 
+        /// Waits for the peer to either acknowledge the stream being finished or request the
+        /// stream to stop sending with a `STOP_SENDING` frame.
+        ///
+        /// The method will return:
+        /// - `Poll::Ready(Ok(Some(error_code)))` if the peer requested the stream to stop sending
+        ///   with the provided `error_code`.
+        /// - `Poll::Ready(Ok(None))` if the stream was finished and all of the data was
+        ///   acknowledged by the peer.
+        /// - `Poll::Ready(Err(stream_error))` if the stream had previously entered an error state.
+        /// - `Poll::Pending` if the stream is still sending data. In this case, the
+        ///   caller should retry after the `Waker` on the provided `Context` is notified.
+        pub fn poll_stopped(
+            &mut self,
+            cx: &mut Context,
+        ) -> Poll<Result<Option<application::Error>, StreamError>> {
+            let response = ready!(self.tx_request()?.stopped().poll(Some(cx))?.into_poll());
+            Ok(response.stop_sending).into()
+        }
+
         /// Initiates a `RESET` on the stream.
         ///
         /// This will close the stream and notify the peer of the provided `error_code`.
@@ -215,6 +234,30 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Initiates a reliable `RESET` on the stream.
+        ///
+        /// This will close the stream and notify the peer of the provided `error_code`, while
+        /// still delivering the first `reliable_size` bytes of the stream to the peer.
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: u64,
+        ) -> Result<(), StreamError> {
+            self.tx_request()?
+                .reset_at(error_code, reliable_size)
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Assigns the stream to a group which shares an aggregate send budget.
+        ///
+        /// All of the streams in the group are limited to buffering the group's
+        /// `max_send_buffer_size` in total.
+        pub fn set_group(&mut self, group: Group) -> Result<(), StreamError> {
+            self.tx_request()?.with_group(group).poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -481,10 +524,29 @@ macro_rules! tx_request_apis {
             self
         }
 
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: u64,
+        ) -> &mut Self {
+            self.request.reset_at(error_code, reliable_size);
+            self
+        }
+
         pub fn flush(&mut self) -> &mut Self {
             self.request.flush();
             self
         }
+
+        pub fn stopped(&mut self) -> &mut Self {
+            self.request.stopped();
+            self
+        }
+
+        pub fn with_group(&mut self, group: Group) -> &mut Self {
+            self.request.with_group(group);
+            self
+        }
     };
 }
 
@@ -62,8 +62,10 @@ pub mod tx {
     pub struct Request {
         chunks: Option<Vec<Bytes>>,
         reset: Option<application::Error>,
+        reliable_size: u64,
         flush: bool,
         finish: bool,
+        stopped: bool,
     }
 
     impl<'a> From<&'a ops::tx::Request<'a>> for Request {
@@ -71,8 +73,10 @@ pub mod tx {
             Self {
                 chunks: tx.chunks.as_ref().map(|chunks| chunks.to_vec()),
                 reset: tx.reset,
+                reliable_size: tx.reliable_size,
                 flush: tx.flush,
                 finish: tx.finish,
+                stopped: tx.stopped,
             }
         }
     }
@@ -94,6 +98,17 @@ pub mod tx {
                     );
                 }
 
+                if response.stop_sending.is_some() {
+                    assert!(
+                        self.stopped,
+                        "stop_sending should only be reported when requested"
+                    );
+                    assert!(
+                        !response.will_wake,
+                        "stopped streams should not store the waker"
+                    );
+                }
+
                 if response.is_finished() {
                     assert_eq!(
                         response.bytes.available, 0,
@@ -106,6 +121,21 @@ pub mod tx {
                 }
             }
 
+            // reliable resets can be rejected if the size is invalid or the peer doesn't support them
+            if self.reset.is_some() && self.reliable_size > 0 {
+                if let Err(error) = response {
+                    assert!(
+                        matches!(
+                            error,
+                            StreamError::InvalidReliableSize { .. }
+                                | StreamError::ReliableResetUnsupported { .. }
+                        ),
+                        "reliable reset failed with an unexpected error: {error:?}"
+                    );
+                    return;
+                }
+            }
+
             // resetting takes priority
             if self.reset.is_some() || response.map_or(false, |res| res.is_reset()) {
                 let response = response.expect("reset should never fail");
@@ -143,6 +173,30 @@ pub mod tx {
                 return;
             }
 
+            // the request is only interested in the stream being finished or stopped
+            if self.stopped && self.chunks.is_none() && !self.finish && !self.flush {
+                if let Ok(response) = response {
+                    assert_eq!(
+                        response.bytes.consumed, 0,
+                        "stopped queries should never consume bytes"
+                    );
+                    assert_eq!(
+                        response.chunks.consumed, 0,
+                        "stopped queries should never consume chunks"
+                    );
+
+                    if context.is_some() && !response.will_wake {
+                        assert!(
+                            response.is_finished(),
+                            "stopped queries should only complete once the stream is finished"
+                        );
+                    }
+                }
+
+                // none of the other checks apply so return early
+                return;
+            }
+
             // the request is interested in push availability
             if self
                 .chunks
@@ -238,6 +292,8 @@ pub mod tx {
                         should_wake |= response.chunks.consumed < chunks.len();
                         // the request wanted all of the chunks to be flushed
                         should_wake |= self.flush;
+                        // the request wanted to be notified when the stream is stopped
+                        should_wake |= self.stopped;
                         assert!(
                             should_wake,
                             concat!(
@@ -269,7 +325,9 @@ pub mod tx {
                             response.status
                         );
                             assert!(
-                                response.chunks.consumed < self.chunks_len() || self.flush,
+                                response.chunks.consumed < self.chunks_len()
+                                    || self.flush
+                                    || self.stopped,
                                 concat!(
                                     "waker should only wake when not all of the provided chunks were consumed ",
                                     "or when a flush was requested",
@@ -18,7 +18,7 @@ use core::{
 use s2n_quic_core::{
     ack, endpoint,
     frame::MaxStreams,
-    stream::{self, iter::StreamIter, StreamId, StreamType},
+    stream::{self, iter::StreamIter, limits::LocalLimits as _, StreamId, StreamType},
     time::{timer, Timestamp},
     transport,
     transport::parameters::InitialFlowControlLimits,
@@ -76,6 +76,9 @@ impl Controller {
             ),
             remote_bidi_controller: RemoteInitiated::new(
                 initial_local_limits.max_open_remote_bidirectional_streams,
+                stream_limits
+                    .max_accept_queue_bidirectional_streams
+                    .as_varint(),
                 min_rtt,
             ),
             local_uni_controller: LocalInitiated::new(
@@ -84,6 +87,9 @@ impl Controller {
             ),
             remote_uni_controller: RemoteInitiated::new(
                 initial_local_limits.max_open_remote_unidirectional_streams,
+                stream_limits
+                    .max_accept_queue_unidirectional_streams
+                    .as_varint(),
                 min_rtt,
             ),
         }
@@ -186,6 +192,17 @@ impl Controller {
         }
     }
 
+    /// This method is called when the application accepts a remote initiated stream.
+    ///
+    /// Accepting streams drains the accept queue, which may allow additional
+    /// stream credits to be issued to the peer.
+    pub fn on_accept_stream(&mut self, stream_type: StreamType) {
+        match stream_type {
+            StreamType::Bidirectional => self.remote_bidi_controller.on_accept_stream(),
+            StreamType::Unidirectional => self.remote_uni_controller.on_accept_stream(),
+        }
+    }
+
     /// This method is called whenever a stream is closed.
     pub fn on_close_stream(&mut self, stream_id: StreamId) {
         match self.direction(stream_id) {
@@ -47,11 +47,16 @@ pub(super) struct RemoteInitiated {
     max_streams_sync: IncrementalValueSync<VarInt, MaxStreamsToFrameWriter>,
     opened_streams: VarInt,
     closed_streams: VarInt,
+    /// The number of opened streams which have been accepted by the application
+    accepted_streams: VarInt,
+    /// The maximum number of opened streams which may be waiting to be accepted
+    /// before additional credits are withheld from the peer
+    max_accept_queue: VarInt,
     rtt_refill: TokenBucket,
 }
 
 impl RemoteInitiated {
-    pub fn new(max_local_limit: VarInt, min_rtt: Duration) -> Self {
+    pub fn new(max_local_limit: VarInt, max_accept_queue: VarInt, min_rtt: Duration) -> Self {
         Self {
             max_local_limit,
             max_streams_sync: IncrementalValueSync::new(
@@ -61,6 +66,8 @@ impl RemoteInitiated {
             ),
             opened_streams: VarInt::from_u8(0),
             closed_streams: VarInt::from_u8(0),
+            accepted_streams: VarInt::from_u8(0),
+            max_accept_queue,
             rtt_refill: TokenBucket::builder()
                 .with_max(max_local_limit.as_u64())
                 .with_refill_interval(min_rtt)
@@ -110,6 +117,19 @@ impl RemoteInitiated {
         self.check_integrity();
     }
 
+    #[inline]
+    pub fn on_accept_stream(&mut self) {
+        self.accepted_streams += 1;
+        self.check_integrity();
+    }
+
+    /// Returns `true` if the application has not accepted enough of the opened
+    /// streams to allow the peer to open additional streams
+    #[inline]
+    pub fn is_accept_queue_full(&self) -> bool {
+        self.opened_streams - self.accepted_streams >= self.max_accept_queue
+    }
+
     /// Returns the number of streams currently open
     #[inline]
     pub fn open_stream_count(&self) -> VarInt {
@@ -143,6 +163,11 @@ impl RemoteInitiated {
 
     #[inline]
     pub fn on_timeout(&mut self, now: Timestamp) {
+        // push back on the peer until the application catches up with accepting streams
+        if self.is_accept_queue_full() {
+            return;
+        }
+
         let synced_closed_streams = self.synced_closed_streams();
 
         let refill = self.closed_streams - synced_closed_streams;
@@ -185,6 +210,10 @@ impl RemoteInitiated {
                 self.closed_streams <= self.opened_streams,
                 "Cannot close more streams than previously opened"
             );
+            assert!(
+                self.accepted_streams <= self.opened_streams,
+                "Cannot accept more streams than previously opened"
+            );
             assert!(
                 self.open_stream_count() <= self.max_local_limit,
                 "Cannot have more incoming streams open concurrently than
@@ -227,6 +256,7 @@ impl transmission::interest::Provider for RemoteInitiated {
         if self.closed_streams > self.synced_closed_streams()
             && !self.rtt_refill.is_armed()
             && !self.max_streams_sync.is_cancelled()
+            && !self.is_accept_queue_full()
         {
             query.on_new_data()?;
         }
@@ -11,6 +11,7 @@ use crate::{
         self,
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
+        send_group_budget::SendGroupBudget,
         stream_container::{StreamContainer, StreamContainerIterationResult},
         stream_events::StreamEvents,
         stream_impl::StreamConfig,
@@ -22,13 +23,15 @@ use core::{
     task::{ready, Context, Poll, Waker},
     time::Duration,
 };
+use hashbrown::HashMap;
 use s2n_quic_core::{
     ack,
+    buffer::reassembler,
     connection::error::Error,
     endpoint,
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::PacketNumberSpace,
     stream::{iter::StreamIter, ops, StreamId, StreamType},
@@ -70,6 +73,43 @@ impl StreamIdSet {
         }
     }
 
+    /// Returns the number of streams which have been opened across all of the types
+    pub fn opened(&self) -> u64 {
+        [
+            self.server_initiated_unidirectional,
+            self.client_initiated_unidirectional,
+            self.server_initiated_bidirectional,
+            self.client_initiated_bidirectional,
+        ]
+        .iter()
+        .map(|next| match next {
+            // The lower 2 bits encode the initiator and type so the remaining bits are the index
+            // of the next stream, which is equal to the number of streams opened
+            Some(id) => id.as_varint().as_u64() >> 2,
+            // All of the streams for the type have been opened
+            None => (VarInt::MAX.as_u64() >> 2) + 1,
+        })
+        .sum()
+    }
+
+    /// Returns the `StreamId` inside the set for the given initiator and stream type
+    pub fn get(&self, initiator: endpoint::Type, stream_type: StreamType) -> Option<StreamId> {
+        match (initiator, stream_type) {
+            (endpoint::Type::Server, StreamType::Unidirectional) => {
+                self.server_initiated_unidirectional
+            }
+            (endpoint::Type::Client, StreamType::Unidirectional) => {
+                self.client_initiated_unidirectional
+            }
+            (endpoint::Type::Server, StreamType::Bidirectional) => {
+                self.server_initiated_bidirectional
+            }
+            (endpoint::Type::Client, StreamType::Bidirectional) => {
+                self.client_initiated_bidirectional
+            }
+        }
+    }
+
     /// Returns the reference to the `StreamId` inside the set for the given
     /// initiator and stream type
     pub fn get_mut(
@@ -187,6 +227,16 @@ pub struct StreamManagerState<S> {
     /// Limits for the Stream manager. Since only Stream limits are utilized at
     /// the moment we only store those
     stream_limits: stream::Limits,
+    /// Whether the local endpoint accepts `RESET_STREAM_AT` frames from the peer
+    reset_stream_at_enabled: bool,
+    /// Whether the peer accepts `RESET_STREAM_AT` frames from the local endpoint
+    peer_reset_stream_at_enabled: bool,
+    /// The aggregate send budgets for each of the stream groups, keyed by the group identifier
+    send_groups: HashMap<u64, SendGroupBudget>,
+    /// The send budget which is shared by all of the streams on the connection
+    connection_send_budget: SendGroupBudget,
+    /// The pool which receive buffers are allocated from, if any
+    receive_buffer_pool: Option<reassembler::Pool>,
 }
 
 impl<S: StreamTrait> StreamManagerState<S> {
@@ -247,6 +297,8 @@ impl<S: StreamTrait> StreamManagerState<S> {
             desired_flow_control_window: initial_receive_window.as_u64() as u32,
             initial_send_window,
             max_send_buffer_size: self.stream_limits.max_send_buffer_size.as_u32(),
+            connection_send_budget: self.connection_send_budget.clone(),
+            receive_buffer_pool: self.receive_buffer_pool.clone(),
         }));
     }
 
@@ -442,6 +494,7 @@ impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
         if self.inner.streams.contains(next_id_to_accept) {
             *self.inner.accept_state.next_stream_mut(stream_type) =
                 next_id_to_accept.next_of_type();
+            self.inner.stream_controller.on_accept_stream(stream_type);
             Ok(Some(next_id_to_accept))
         } else {
             Ok(None)
@@ -565,6 +618,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         initial_local_limits: InitialFlowControlLimits,
         initial_peer_limits: InitialFlowControlLimits,
         min_rtt: Duration,
+        receive_buffer_pool: Option<reassembler::Pool>,
     ) -> Self {
         // We limit the initial data limit to u32::MAX (4GB), which far
         // exceeds the reasonable amount of data a connection is
@@ -601,6 +655,14 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 close_reason: None,
                 accept_state: AcceptState::new(local_endpoint_type),
                 stream_limits: connection_limits.stream_limits(),
+                reset_stream_at_enabled: connection_limits.reset_stream_at_enabled(),
+                peer_reset_stream_at_enabled: connection_limits.peer_reset_stream_at_enabled(),
+                send_groups: HashMap::new(),
+                connection_send_budget: {
+                    let (high, low) = connection_limits.connection_send_buffer_watermarks();
+                    SendGroupBudget::with_watermarks(high, low)
+                },
+                receive_buffer_pool,
             },
             last_blocked_sync_period: Duration::ZERO,
             last_min_rtt: min_rtt,
@@ -619,6 +681,20 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             .acquired_window()
     }
 
+    fn streams_opened(&self) -> u64 {
+        self.inner.next_stream_ids.opened()
+    }
+
+    fn send_buffer_occupancy(&self) -> u64 {
+        self.inner.connection_send_budget.buffered()
+    }
+
+    fn next_local_stream_id(&self, stream_type: StreamType) -> Option<StreamId> {
+        self.inner
+            .next_stream_ids
+            .get(self.inner.local_endpoint_type, stream_type)
+    }
+
     fn poll_accept(
         &mut self,
         stream_type: Option<StreamType>,
@@ -912,6 +988,21 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         self.handle_stream_frame(stream_id, |stream, events| stream.on_reset(frame, events))
     }
 
+    fn on_reset_stream_at(&mut self, frame: &ResetStreamAt) -> Result<(), transport::Error> {
+        // The peer is only allowed to send RESET_STREAM_AT frames if we advertised support for
+        // the extension via the `reset_stream_at` transport parameter
+        if !self.inner.reset_stream_at_enabled {
+            return Err(transport::Error::FRAME_ENCODING_ERROR
+                .with_reason("RESET_STREAM_AT support was not advertised")
+                .with_frame_type(frame.tag().into()));
+        }
+
+        let stream_id = StreamId::from_varint(frame.stream_id);
+        self.handle_stream_frame(stream_id, |stream, events| {
+            stream.on_reset_at(frame, events)
+        })
+    }
+
     fn on_max_stream_data(&mut self, frame: &MaxStreamData) -> Result<(), transport::Error> {
         let stream_id = StreamId::from_varint(frame.stream_id);
         self.handle_stream_frame(stream_id, |stream, events| {
@@ -982,17 +1073,45 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
+        let mut send_group = None;
+
+        if let Some(tx) = request.tx.as_ref() {
+            if tx.reliable_size > 0 && !self.inner.peer_reset_stream_at_enabled {
+                return Err(StreamError::reliable_reset_unsupported());
+            }
+
+            if let Some(group) = tx.group {
+                let budget = self
+                    .inner
+                    .send_groups
+                    .entry(group.id())
+                    .or_insert_with(|| SendGroupBudget::new(group.max_send_buffer_size()));
+                // the most recently provided limit for the group takes precedence
+                budget.set_max_buffer_size(group.max_send_buffer_size());
+                send_group = Some(budget.clone());
+            }
+        }
+
         self.perform_api_call(
             stream_id,
             Err(StreamError::invalid_stream()),
             api_call_context,
-            |stream| stream.poll_request(request, context),
+            |stream| {
+                if let Some(group) = send_group {
+                    stream.set_send_group(group);
+                }
+                stream.poll_request(request, context)
+            },
         )
     }
 
     fn has_pending_streams(&self) -> bool {
         self.inner.streams.has_pending_streams()
     }
+
+    fn is_flushed(&self, mode: connection::FlushMode) -> bool {
+        self.inner.streams.is_flushed(mode)
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
@@ -15,6 +15,7 @@ use crate::{
     stream::{
         controller::MAX_STREAMS_SYNC_FRACTION,
         manager_api::Manager as _,
+        send_group_budget::SendGroupBudget,
         stream_impl::StreamConfig,
         stream_interests::{StreamInterestProvider, StreamInterests},
         testing::*,
@@ -37,7 +38,7 @@ use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     frame::{
         stream::StreamRef, DataBlocked, Frame, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::{PacketNumberRange, PacketNumberSpace},
     stream::{ops, StreamId, StreamType},
@@ -75,6 +76,7 @@ struct MockStream {
     on_transmit_limit: Option<usize>,
     on_data_count: usize,
     on_reset_count: usize,
+    on_reset_at_count: usize,
     on_stream_data_blocked_count: usize,
     on_stop_sending_count: usize,
     on_max_stream_data_count: usize,
@@ -88,6 +90,7 @@ struct MockStream {
     poll_push_count: usize,
     poll_finish_count: usize,
     reset_count: usize,
+    send_group: Option<SendGroupBudget>,
 }
 
 impl MockStream {
@@ -143,6 +146,7 @@ impl StreamTrait for MockStream {
             on_internal_reset_count: 0,
             on_data_count: 0,
             on_reset_count: 0,
+            on_reset_at_count: 0,
             on_stream_data_blocked_count: 0,
             on_stop_sending_count: 0,
             on_max_stream_data_count: 0,
@@ -161,6 +165,7 @@ impl StreamTrait for MockStream {
             poll_push_count: 0,
             poll_finish_count: 0,
             reset_count: 0,
+            send_group: None,
         }
     }
 
@@ -212,6 +217,20 @@ impl StreamTrait for MockStream {
         Ok(())
     }
 
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), TransportError> {
+        assert_eq!(self.stream_id(), StreamId::from_varint(frame.stream_id));
+        self.on_reset_at_count += 1;
+        self.store_wakers(events);
+        if let Some(err) = self.next_packet_error {
+            return Err(err);
+        };
+        Ok(())
+    }
+
     fn on_max_stream_data(
         &mut self,
         frame: &MaxStreamData,
@@ -303,6 +322,10 @@ impl StreamTrait for MockStream {
         self.on_connection_window_available_retrieve_window -= Into::<u64>::into(acquired_window);
     }
 
+    fn set_send_group(&mut self, group: SendGroupBudget) {
+        self.send_group = Some(group);
+    }
+
     fn poll_request(
         &mut self,
         request: &mut ops::Request,
@@ -406,6 +429,7 @@ fn create_stream_manager(local_ep_type: endpoint::Type) -> AbstractStreamManager
         initial_local_limits,
         initial_peer_limits,
         DEFAULT_INITIAL_RTT,
+        None,
     )
 }
 
@@ -513,7 +537,9 @@ fn opens_locally_initiated_streams() {
             let mut manager = create_stream_manager(local_ep_type);
 
             for n in 0..8 {
+                let next_id = manager.next_local_stream_id(stream_type);
                 let stream_id: StreamId = try_open(&mut manager, stream_type).unwrap();
+                assert_eq!(next_id, Some(stream_id));
                 assert!(manager.active_streams().contains(&stream_id));
                 assert_eq!(
                     StreamId::nth(local_ep_type, stream_type, n).unwrap(),
@@ -526,6 +552,7 @@ fn opens_locally_initiated_streams() {
                 .inner
                 .next_stream_ids
                 .get_mut(local_ep_type, stream_type) = None;
+            assert_eq!(manager.next_local_stream_id(stream_type), None);
 
             assert_matches!(
                 try_open(&mut manager, stream_type),
@@ -827,6 +854,73 @@ fn peer_closing_streams_transmits_max_streams() {
     }
 }
 
+#[test]
+fn full_accept_queue_withholds_max_streams() {
+    for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+        let initial_local_limits = create_default_initial_flow_control_limits();
+        let max_streams = match stream_type {
+            StreamType::Bidirectional => initial_local_limits.max_open_remote_bidirectional_streams,
+            StreamType::Unidirectional => {
+                initial_local_limits.max_open_remote_unidirectional_streams
+            }
+        };
+
+        // only bound the accept queue of the tested stream type
+        let limits = match stream_type {
+            StreamType::Bidirectional => ConnectionLimits::default()
+                .with_max_accept_queue_bidirectional_streams(max_streams.as_u64()),
+            StreamType::Unidirectional => ConnectionLimits::default()
+                .with_max_accept_queue_unidirectional_streams(max_streams.as_u64()),
+        }
+        .unwrap();
+
+        let mut manager = AbstractStreamManager::<MockStream>::new(
+            &limits,
+            endpoint::Type::Server,
+            initial_local_limits,
+            create_default_initial_flow_control_limits(),
+            DEFAULT_INITIAL_RTT,
+            None,
+        );
+
+        // The peer opens up to the current max streams limit
+        for i in 0..*max_streams {
+            let stream_id = StreamId::nth(endpoint::Type::Client, stream_type, i).unwrap();
+            assert_eq!(
+                Ok(()),
+                manager.on_data(&stream_data(stream_id, VarInt::from_u32(0), &[], false))
+            );
+        }
+
+        // The peer closes the most recent streams before the application accepts any of them
+        let streams_to_close = max_streams / MAX_STREAMS_SYNC_FRACTION;
+        for i in (*max_streams - *streams_to_close)..*max_streams {
+            let stream_id = StreamId::nth(endpoint::Type::Client, stream_type, i).unwrap();
+            manager.with_asserted_stream(stream_id, |stream| {
+                stream.interests.retained = false;
+            });
+        }
+
+        // The application hasn't accepted any streams so no additional credits are issued
+        assert_eq!(
+            transmission::Interest::None,
+            manager.get_transmission_interest()
+        );
+
+        let (accept_waker, _accept_wake_counter) = new_count_waker();
+        assert!(matches!(
+            manager.poll_accept(Some(stream_type), &Context::from_waker(&accept_waker)),
+            Poll::Ready(Ok(Some(_)))
+        ));
+
+        // Accepting a stream makes room in the queue so the credits are released
+        assert_eq!(
+            transmission::Interest::NewData,
+            manager.get_transmission_interest()
+        );
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
 //= type=test
 //# An endpoint that is unable to open a new stream due to the peer's
@@ -1402,6 +1496,7 @@ fn asymmetric_stream_limits_remote_initiated() {
                     initial_local_limits,
                     initial_peer_limits,
                     DEFAULT_INITIAL_RTT,
+                    None,
                 );
 
                 // The peer opens streams up to the limit we have given them
@@ -1482,6 +1577,7 @@ fn asymmetric_stream_limits_local_initiated() {
                     initial_local_limits,
                     initial_peer_limits,
                     DEFAULT_INITIAL_RTT,
+                    None,
                 );
 
                 // Local endpoint opens streams up to the limit
@@ -1569,6 +1665,7 @@ fn rate_limited_stream_credits() {
                 initial_local_limits,
                 initial_peer_limits,
                 DEFAULT_INITIAL_RTT,
+                None,
             );
 
             let mut stream_ids =
@@ -2938,6 +3035,58 @@ fn forwards_on_reset() {
     assert_eq!(write_wake_counter, 2);
 }
 
+#[test]
+fn forwards_on_reset_at() {
+    let frame = |stream_id: StreamId| ResetStreamAt {
+        stream_id: stream_id.into(),
+        application_error_code: VarInt::from_u32(3_928_776),
+        final_size: VarInt::from_u32(1_000),
+        reliable_size: VarInt::from_u32(500),
+    };
+
+    // RESET_STREAM_AT frames are rejected if support was not advertised
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    assert_is_transport_error(
+        manager.on_reset_stream_at(&frame(stream_1)),
+        TransportError::FRAME_ENCODING_ERROR,
+    );
+
+    let limits = ConnectionLimits::default()
+        .with_max_open_local_bidirectional_streams(1000)
+        .unwrap()
+        .with_reset_stream_at(true)
+        .unwrap();
+    let mut manager = AbstractStreamManager::<MockStream>::new(
+        &limits,
+        endpoint::Type::Server,
+        create_default_initial_flow_control_limits(),
+        create_default_initial_flow_control_limits(),
+        DEFAULT_INITIAL_RTT,
+        None,
+    );
+    let (read_waker, read_wake_counter) = new_count_waker();
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        stream.read_waker_to_return = Some(read_waker);
+    });
+
+    assert_eq!(Ok(()), manager.on_reset_stream_at(&frame(stream_1)));
+    assert_eq!(read_wake_counter, 1);
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert_eq!(stream.on_reset_at_count, 1);
+        assert_eq!(stream.on_reset_count, 0);
+    });
+
+    // Check invalid stream ID
+    assert_is_transport_error(
+        manager.on_reset_stream_at(&frame(invalid_stream_id(endpoint::Type::Server))),
+        TransportError::STREAM_STATE_ERROR,
+    );
+}
+
 #[test]
 fn forwards_poll_pop() {
     let (mut wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
@@ -3202,6 +3351,46 @@ fn forwards_reset() {
     );
 }
 
+#[test]
+fn forwards_send_group() {
+    let (_wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let stream_2 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let stream_3 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    for (stream_id, group) in [
+        (stream_1, s2n_quic_core::stream::Group::new(1, 1000)),
+        (stream_2, s2n_quic_core::stream::Group::new(1, 2000)),
+        (stream_3, s2n_quic_core::stream::Group::new(2, 1000)),
+    ] {
+        assert!(manager
+            .poll_request(
+                stream_id,
+                &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+                ops::Request::default().with_group(group),
+                None,
+            )
+            .is_ok());
+    }
+
+    let group_1 = manager
+        .with_asserted_stream(stream_1, |stream| stream.send_group.clone())
+        .expect("stream should be assigned to a group");
+
+    // the most recently provided limit is applied to the group
+    assert_eq!(group_1.available_buffer_space(), 2000);
+
+    manager.with_asserted_stream(stream_2, |stream| {
+        assert!(stream.send_group.as_ref().unwrap().ptr_eq(&group_1));
+    });
+
+    manager.with_asserted_stream(stream_3, |stream| {
+        assert!(!stream.send_group.as_ref().unwrap().ptr_eq(&group_1));
+    });
+}
+
 #[test]
 fn stream_transmission_fairness_test() {
     for concurrent_streams in 2..=5 {
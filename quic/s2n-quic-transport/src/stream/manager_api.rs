@@ -13,10 +13,12 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    ack, endpoint,
+    ack,
+    buffer::reassembler,
+    endpoint,
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     stream::{ops, StreamId, StreamType},
     time::{timer, Timestamp},
@@ -39,6 +41,7 @@ pub trait Manager:
         initial_local_limits: InitialFlowControlLimits,
         initial_peer_limits: InitialFlowControlLimits,
         min_rtt: Duration,
+        receive_buffer_pool: Option<reassembler::Pool>,
     ) -> Self;
 
     /// The number of bytes of forward progress the peer has made on incoming streams
@@ -47,6 +50,12 @@ pub trait Manager:
     /// The number of bytes of forward progress the local endpoint has made on outgoing streams
     fn outgoing_bytes_progressed(&self) -> VarInt;
 
+    /// The number of streams which have been opened by both endpoints
+    fn streams_opened(&self) -> u64;
+
+    /// The number of bytes buffered by all of the streams which have not been acknowledged yet
+    fn send_buffer_occupancy(&self) -> u64;
+
     /// Accepts the next incoming stream of a given type
     fn poll_accept(
         &mut self,
@@ -54,6 +63,11 @@ pub trait Manager:
         context: &Context,
     ) -> Poll<Result<Option<StreamId>, connection::Error>>;
 
+    /// Returns the ID of the next local initiated stream of a certain type
+    ///
+    /// `None` is returned if all of the stream IDs for the type have been used.
+    fn next_local_stream_id(&self, stream_type: StreamType) -> Option<StreamId>;
+
     /// Opens the next local initiated stream of a certain type
     fn poll_open_local_stream(
         &mut self,
@@ -113,6 +127,10 @@ pub trait Manager:
     /// a stream
     fn on_reset_stream(&mut self, frame: &ResetStream) -> Result<(), transport::Error>;
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// a stream
+    fn on_reset_stream_at(&mut self, frame: &ResetStreamAt) -> Result<(), transport::Error>;
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// a stream
     fn on_max_stream_data(&mut self, frame: &MaxStreamData) -> Result<(), transport::Error>;
@@ -142,4 +160,8 @@ pub trait Manager:
 
     /// Returns whether or not streams have data to send
     fn has_pending_streams(&self) -> bool;
+
+    /// Returns whether or not all of the buffered stream data has been flushed, as
+    /// determined by the provided `mode`
+    fn is_flushed(&self, mode: connection::FlushMode) -> bool;
 }
@@ -10,6 +10,7 @@ mod manager;
 mod manager_api;
 mod outgoing_connection_flow_controller;
 mod receive_stream;
+mod send_group_budget;
 mod send_stream;
 mod stream_container;
 mod stream_events;
@@ -18,8 +18,11 @@ use core::{
 };
 use s2n_quic_core::{
     ack, application,
-    buffer::{self, Reassembler},
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    buffer::{self, reassembler, Reassembler},
+    frame::{
+        stream::StreamRef, MaxStreamData, ResetStream, ResetStreamAt, StopSending,
+        StreamDataBlocked,
+    },
     packet::number::PacketNumber,
     stream::{ops, StreamId},
     transport,
@@ -84,6 +87,16 @@ pub(super) enum ReceiveStreamState {
     Reset(StreamError),
 }
 
+/// Keeps track of a reliable reset which was received via a `RESET_STREAM_AT` frame
+///
+/// The stream keeps delivering data to the application until `reliable_size` bytes have been
+/// consumed, after which it transitions into the `Reset` state.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(super) struct ReliableReset {
+    error: StreamError,
+    reliable_size: u64,
+}
+
 /// Keeps track of any missing data in the `Stopping` state
 #[derive(PartialEq, Debug, Clone)]
 pub(super) struct MissingData {
@@ -343,6 +356,8 @@ pub struct ReceiveStream {
     /// The handle of a task that is currently waiting on new incoming data, along with the low
     /// watermark value.
     pub(super) read_waiter: Option<(Waker, usize)>,
+    /// A pending reliable reset from the peer, if one was received
+    pub(super) reliable_reset: Option<ReliableReset>,
     /// Whether the final state had already been observed by the application
     final_state_observed: bool,
     /// Marks the stream as detached from the application
@@ -355,6 +370,7 @@ impl ReceiveStream {
         connection_flow_controller: IncomingConnectionFlowController,
         initial_window: VarInt,
         desired_flow_control_window: u32,
+        receive_buffer_pool: Option<reassembler::Pool>,
     ) -> ReceiveStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -366,7 +382,8 @@ impl ReceiveStream {
 
         let mut result = ReceiveStream {
             state,
-            receive_buffer: Reassembler::new(),
+            receive_buffer: receive_buffer_pool
+                .map_or_else(Reassembler::new, Reassembler::with_pool),
             flow_controller: ReceiveStreamFlowController::new(
                 connection_flow_controller,
                 initial_window,
@@ -374,6 +391,7 @@ impl ReceiveStream {
             ),
             stop_sending_sync: OnceSync::new(),
             read_waiter: None,
+            reliable_reset: None,
             final_state_observed: is_closed,
             detached: is_closed,
         };
@@ -515,6 +533,12 @@ impl ReceiveStream {
                     self.flow_controller.stop_sync();
                 }
 
+                // wake the waiter once all of the reliably delivered data is available
+                if let Some(reliable_reset) = self.reliable_reset.as_ref() {
+                    should_wake |= self.read_waiter.is_some()
+                        && self.receive_buffer.total_received_len() >= reliable_reset.reliable_size;
+                }
+
                 if let Some(total_size) = self.receive_buffer.final_size() {
                     // If we already have received all the data, there is no point
                     // in transmitting STOP_SENDING anymore.
@@ -532,7 +556,10 @@ impl ReceiveStream {
                     // If the frame with the FIN contained no new data all
                     // buffered data might already have been consumed. In this
                     // case we directly go into [`ReceiveStreamState::DataRead`]
-                    if frame.is_fin && self.receive_buffer.consumed_len() == total_size {
+                    if frame.is_fin
+                        && self.reliable_reset.is_none()
+                        && self.receive_buffer.consumed_len() == total_size
+                    {
                         self.receive_buffer.reset();
                         self.state = ReceiveStreamState::DataRead;
                     }
@@ -596,6 +623,81 @@ impl ReceiveStream {
         Ok(())
     }
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    pub fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        let error = StreamError::stream_reset(frame.application_error_code.into());
+        let reliable_size = frame.reliable_size.as_u64();
+
+        match self.state {
+            // Data which has already been delivered or discarded can't be recovered, so a
+            // reliable reset is treated in the same way as a regular reset. This is also the
+            // case if all of the data had already been received, in which case the reset is
+            // ignored.
+            ReceiveStreamState::Reset(_)
+            | ReceiveStreamState::DataRead
+            | ReceiveStreamState::Stopping { .. } => {}
+            ReceiveStreamState::Receiving
+                if reliable_size == 0 || self.receive_buffer.is_writing_complete() => {}
+            ReceiveStreamState::Receiving => {
+                if self.receive_buffer.final_size().is_none() {
+                    self.flow_controller
+                        .acquire_window_up_to(frame.final_size, Some(frame.tag()))?;
+                }
+
+                // Record the final size in the receive buffer so any data beyond it is
+                // rejected. As with RESET_STREAM, a change in the final size is treated as a
+                // FINAL_SIZE_ERROR.
+                self.receive_buffer
+                    .write_at_fin(frame.final_size, &[])
+                    .map_err(|_| {
+                        transport::Error::FINAL_SIZE_ERROR
+                            .with_reason("Final size in reset frame did not match received data")
+                            .with_frame_type(frame.tag().into())
+                    })?;
+
+                // The reliable size can only be reduced by subsequent frames
+                let reliable_size = self
+                    .reliable_reset
+                    .map_or(reliable_size, |prev| prev.reliable_size.min(reliable_size));
+
+                self.reliable_reset = Some(ReliableReset {
+                    error,
+                    reliable_size,
+                });
+
+                // The final size is known so we don't need to send any more flow control
+                // updates. The peer has also reset the stream so STOP_SENDING isn't needed.
+                self.flow_controller.stop_sync();
+                self.stop_sending_sync.stop_sync();
+
+                // If the application has already consumed all of the reliable data then the
+                // stream can be reset right away
+                if self.receive_buffer.consumed_len() >= reliable_size {
+                    self.reset_state(error);
+                }
+
+                self.wake(events);
+
+                return Ok(());
+            }
+        }
+
+        self.init_reset(error, Some(frame.final_size), Some(frame.tag()))?;
+
+        // We don't have to send `STOP_SENDING` anymore since the stream was reset by the peer
+        self.stop_sending_sync.stop_sync();
+
+        // Return the waker to wake up potential users of the stream
+        self.wake(events);
+
+        Ok(())
+    }
+
     /// Starts the reset procedure if the Stream has not been in a RESET state
     /// before.
     fn init_reset(
@@ -659,6 +761,13 @@ impl ReceiveStream {
             }
         }
 
+        self.reset_state(error);
+
+        Ok(())
+    }
+
+    /// Discards any buffered data and transitions into the `Reset` state
+    fn reset_state(&mut self, error: StreamError) {
         // If the stream was reset by the peer we don't actually have to retransmit
         // outgoing flow control window anymore.
         self.flow_controller.stop_sync();
@@ -677,8 +786,6 @@ impl ReceiveStream {
         self.flow_controller.release_outstanding_window();
 
         self.state = ReceiveStreamState::Reset(error);
-
-        Ok(())
     }
 
     /// This method gets called when a packet delivery got acknowledged
@@ -828,15 +935,45 @@ impl ReceiveStream {
                 response.status = ops::Status::Finished;
                 return Ok(response);
             }
+            // a pending reliable reset takes precedence over the end of the stream
+            ReceiveStreamState::Receiving if self.reliable_reset.is_some() => None,
             ReceiveStreamState::Receiving => self.receive_buffer.final_size(),
         };
 
+        // The number of bytes that can still be delivered before a reliable reset takes effect
+        let mut reliable_remaining = usize::MAX;
+
+        if let Some(ReliableReset {
+            error,
+            reliable_size,
+        }) = self.reliable_reset
+        {
+            let consumed_len = self.receive_buffer.consumed_len();
+
+            if consumed_len >= reliable_size {
+                // All of the reliable data has been consumed so finish resetting the stream
+                self.reset_state(error);
+                self.final_state_observed = true;
+                self.read_waiter = None;
+                return Err(error);
+            }
+
+            reliable_remaining =
+                usize::try_from(reliable_size - consumed_len).unwrap_or(usize::MAX);
+        }
+
         let low_watermark = &mut request.low_watermark;
         let high_watermark = &mut request.high_watermark;
         let mut should_wake = false;
 
         // ensure the number of available bytes is at least the requested low watermark
-        if self.receive_buffer.len() >= self.flow_controller.watermark().min(*low_watermark) {
+        if self.receive_buffer.len()
+            >= self
+                .flow_controller
+                .watermark()
+                .min(*low_watermark)
+                .min(reliable_remaining)
+        {
             if let Some(chunks) = request.chunks.as_mut().filter(|chunks| !chunks.is_empty()) {
                 // Make sure all of the placeholder chunks are empty. If it's not, it could lead to
                 // replacing a chunk that was received in a previous request.
@@ -848,8 +985,12 @@ impl ReceiveStream {
                 }
 
                 while response.chunks.consumed < chunks.len() {
-                    if let Some(data) = self.receive_buffer.pop_watermarked(*high_watermark) {
+                    if let Some(data) = self
+                        .receive_buffer
+                        .pop_watermarked((*high_watermark).min(reliable_remaining))
+                    {
                         let data_len = data.len();
+                        reliable_remaining = reliable_remaining.saturating_sub(data_len);
                         // Release the flow control window for the consumed chunk
                         self.flow_controller.release_window(
                             VarInt::try_from(data_len)
@@ -12,7 +12,7 @@ use core::{convert::TryFrom, task::Poll};
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     connection, endpoint,
-    frame::{Frame, MaxData, MaxStreamData, ResetStream, StopSending},
+    frame::{Frame, MaxData, MaxStreamData, ResetStream, ResetStreamAt, StopSending},
     stream::{ops, StreamError, StreamType},
     transport::Error as TransportError,
     varint::VarInt,
@@ -527,6 +527,152 @@ fn reset_stream() {
     }
 }
 
+#[test]
+fn reliable_reset_delivers_data_up_to_reliable_size() {
+    let mut test_env = setup_receive_only_test_env();
+
+    test_env.feed_data(VarInt::from_u32(0), 300);
+    assert_eq!(300, test_env.consume_all_data());
+
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u32(1000),
+        reliable_size: VarInt::from_u32(500),
+    };
+
+    let mut events = StreamEvents::new();
+    assert!(test_env
+        .stream
+        .on_reset_at(&reset_frame, &mut events)
+        .is_ok());
+    events.wake_all();
+    assert_eq!(test_env.wake_counter, 1);
+
+    // The stream should wait for the remaining reliable data
+    test_env.assert_no_read_data();
+
+    // Data beyond the final size is rejected
+    let mut events = StreamEvents::new();
+    assert_is_transport_error(
+        test_env.stream.on_data(
+            &stream_data(
+                test_env.stream.stream_id,
+                VarInt::from_u32(1000),
+                &[0, 1],
+                false,
+            ),
+            &mut events,
+        ),
+        TransportError::FINAL_SIZE_ERROR,
+    );
+
+    // Receiving data past the reliable size wakes the reader
+    test_env.feed_data(VarInt::from_u32(300), 500);
+    assert_eq!(test_env.wake_counter, 2);
+
+    // Only the data up to the reliable size is delivered
+    let mut received = 0;
+    while let Poll::Ready(Ok(Some(chunk))) = test_env.poll_pop() {
+        received += chunk.len();
+    }
+    assert_eq!(200, received);
+
+    test_env.assert_pop_error();
+    assert_eq!(
+        stream_interests(&["fin"]),
+        test_env.stream.get_stream_interests()
+    );
+}
+
+#[test]
+fn reliable_reset_is_observed_if_all_data_is_reliable() {
+    let mut test_env = setup_receive_only_test_env();
+
+    test_env.feed_data(VarInt::from_u32(0), 500);
+
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u32(500),
+        reliable_size: VarInt::from_u32(500),
+    };
+
+    let mut events = StreamEvents::new();
+    assert!(test_env
+        .stream
+        .on_reset_at(&reset_frame, &mut events)
+        .is_ok());
+
+    // The stream should deliver all of the data and then report the reset instead of finishing
+    let mut received = 0;
+    while let Poll::Ready(Ok(Some(chunk))) = test_env.poll_pop() {
+        received += chunk.len();
+    }
+    assert_eq!(500, received);
+    test_env.assert_pop_error();
+}
+
+#[test]
+fn reliable_reset_behaves_like_reset_if_reliable_data_was_consumed() {
+    for reliable_size in [0, 100, 300] {
+        let mut test_env = setup_receive_only_test_env();
+
+        test_env.feed_data(VarInt::from_u32(0), 300);
+        assert_eq!(300, test_env.consume_all_data());
+
+        let reset_frame = ResetStreamAt {
+            stream_id: test_env.stream.stream_id.into(),
+            application_error_code: VarInt::from_u8(1),
+            final_size: VarInt::from_u32(1000),
+            reliable_size: VarInt::from_u32(reliable_size),
+        };
+
+        let mut events = StreamEvents::new();
+        assert!(test_env
+            .stream
+            .on_reset_at(&reset_frame, &mut events)
+            .is_ok());
+        events.wake_all();
+
+        assert_eq!(
+            stream_interests(&[]),
+            test_env.stream.get_stream_interests()
+        );
+        test_env.assert_pop_error();
+    }
+}
+
+#[test]
+fn reliable_reset_errors_if_final_size_contradicts_fin_size() {
+    for final_size in [0, 400, 799, 801] {
+        let mut test_env = setup_receive_only_test_env();
+
+        test_env.feed_data(VarInt::from_u32(0), 100);
+        let mut events = StreamEvents::new();
+        assert!(test_env
+            .stream
+            .on_data(
+                &stream_data(test_env.stream.stream_id, VarInt::from_u32(800), &[], true),
+                &mut events
+            )
+            .is_ok());
+
+        let reset_frame = ResetStreamAt {
+            stream_id: test_env.stream.stream_id.into(),
+            application_error_code: VarInt::from_u8(0),
+            final_size: VarInt::from_u32(final_size),
+            reliable_size: VarInt::from_u32(final_size.min(200)),
+        };
+
+        let mut events = StreamEvents::new();
+        assert_is_transport_error(
+            test_env.stream.on_reset_at(&reset_frame, &mut events),
+            TransportError::FINAL_SIZE_ERROR,
+        );
+    }
+}
+
 #[test]
 fn reset_errors_if_final_size_contradicts_fin_size() {
     for final_size in &[0, 400, 799, 801] {
@@ -0,0 +1,220 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{rc::Rc, vec::Vec};
+use core::{cell::RefCell, task::Waker};
+
+/// The actual implementation/state of a send budget which is shared between
+/// all `Stream`s in a group
+#[derive(Debug)]
+struct SendGroupBudgetImpl {
+    /// The amount of buffered bytes at which the streams in the group stop accepting data
+    high_watermark: u64,
+    /// The amount of buffered bytes the group needs to drain to before accepting data again
+    low_watermark: u64,
+    /// The amount of bytes that are currently buffered by streams in the group
+    buffered: u64,
+    /// Set once the group reaches the high watermark until it drains to the low watermark
+    blocked: bool,
+    /// Tasks which are waiting on the group to release buffer space
+    waiters: Vec<Waker>,
+}
+
+impl SendGroupBudgetImpl {
+    /// Updates the blocked state of the group and returns the waiters if it was unblocked
+    fn update_blocked(&mut self) -> Option<Vec<Waker>> {
+        if self.buffered >= self.high_watermark {
+            self.blocked = true;
+        } else if self.blocked && self.buffered <= self.low_watermark {
+            self.blocked = false;
+            return Some(core::mem::take(&mut self.waiters));
+        }
+
+        None
+    }
+}
+
+/// Manages the aggregate send buffer budget for a group of `Stream`s.
+///
+/// Each `Stream` in the group acquires budget when the application enqueues
+/// data and releases it once the data has been acknowledged or discarded.
+///
+/// The same mechanism is used to limit the total amount of data buffered by
+/// all of the `Stream`s on a connection.
+#[derive(Clone, Debug)]
+pub struct SendGroupBudget {
+    inner: Rc<RefCell<SendGroupBudgetImpl>>,
+}
+
+impl SendGroupBudget {
+    /// Creates a new `SendGroupBudget` with the given maximum buffer size
+    pub fn new(max_buffer_size: u32) -> Self {
+        Self::with_watermarks(max_buffer_size as u64, max_buffer_size as u64)
+    }
+
+    /// Creates a new `SendGroupBudget` which stops accepting data once `high_watermark` bytes
+    /// are buffered, until the buffered amount drains to `low_watermark` bytes
+    pub fn with_watermarks(high_watermark: u64, low_watermark: u64) -> Self {
+        debug_assert!(low_watermark <= high_watermark);
+
+        Self {
+            inner: Rc::new(RefCell::new(SendGroupBudgetImpl {
+                high_watermark,
+                low_watermark,
+                buffered: 0,
+                blocked: false,
+                waiters: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns `true` if both budgets refer to the same group
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Updates the maximum buffer size of the group
+    ///
+    /// Raising the limit wakes up all of the tasks waiting on the group.
+    pub fn set_max_buffer_size(&mut self, max_buffer_size: u32) {
+        let max_buffer_size = max_buffer_size as u64;
+        let mut inner = self.inner.borrow_mut();
+        inner.high_watermark = max_buffer_size;
+        inner.low_watermark = max_buffer_size;
+
+        if let Some(waiters) = inner.update_blocked() {
+            drop(inner);
+            wake_all(waiters);
+        }
+    }
+
+    /// Returns the amount of bytes that can be additionally buffered by the group
+    pub fn available_buffer_space(&self) -> usize {
+        let inner = self.inner.borrow();
+
+        if inner.blocked {
+            return 0;
+        }
+
+        inner
+            .high_watermark
+            .saturating_sub(inner.buffered)
+            .try_into()
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Returns the amount of bytes that are currently buffered by the group
+    pub fn buffered(&self) -> u64 {
+        self.inner.borrow().buffered
+    }
+
+    /// Records `len` bytes as buffered by a stream in the group
+    pub fn acquire(&mut self, len: u64) {
+        let mut inner = self.inner.borrow_mut();
+        inner.buffered += len;
+        let _ = inner.update_blocked();
+    }
+
+    /// Releases `len` previously acquired bytes
+    ///
+    /// All of the tasks waiting on the group are woken up once it drains to the low
+    /// watermark, since they might be able to make progress now.
+    pub fn release(&mut self, len: u64) {
+        if len == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.borrow_mut();
+        debug_assert!(inner.buffered >= len, "released more than was acquired");
+        inner.buffered = inner.buffered.saturating_sub(len);
+
+        if let Some(waiters) = inner.update_blocked() {
+            drop(inner);
+            wake_all(waiters);
+        }
+    }
+
+    /// Registers a task which is blocked on the group's budget
+    pub fn register_waker(&mut self, waker: &Waker) {
+        let mut inner = self.inner.borrow_mut();
+        if !inner.waiters.iter().any(|w| w.will_wake(waker)) {
+            inner.waiters.push(waker.clone());
+        }
+    }
+}
+
+#[inline]
+fn wake_all(waiters: Vec<Waker>) {
+    for waker in waiters {
+        waker.wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_test::task::new_count_waker;
+
+    #[test]
+    fn budget_test() {
+        let mut budget = SendGroupBudget::new(1000);
+        let mut other = budget.clone();
+        assert!(budget.ptr_eq(&other));
+        assert!(!budget.ptr_eq(&SendGroupBudget::new(1000)));
+
+        budget.acquire(600);
+        other.acquire(500);
+        assert_eq!(budget.buffered(), 1100);
+        assert_eq!(other.available_buffer_space(), 0);
+
+        let (waker, wake_counter) = new_count_waker();
+        other.register_waker(&waker);
+        // registering the same waker twice only wakes it once
+        other.register_waker(&waker);
+
+        budget.release(500);
+        assert_eq!(wake_counter, 1);
+        assert_eq!(other.available_buffer_space(), 400);
+
+        // waiters are only woken once
+        budget.release(100);
+        assert_eq!(wake_counter, 1);
+
+        other.register_waker(&waker);
+        budget.set_max_buffer_size(100);
+        assert_eq!(budget.available_buffer_space(), 0);
+        assert_eq!(wake_counter, 1);
+
+        budget.set_max_buffer_size(2000);
+        assert_eq!(budget.available_buffer_space(), 1500);
+        assert_eq!(wake_counter, 2);
+    }
+
+    #[test]
+    fn watermark_test() {
+        let mut budget = SendGroupBudget::with_watermarks(1000, 400);
+        assert_eq!(budget.available_buffer_space(), 1000);
+
+        budget.acquire(1200);
+        assert_eq!(budget.available_buffer_space(), 0);
+
+        let (waker, wake_counter) = new_count_waker();
+        budget.register_waker(&waker);
+
+        // the budget stays blocked until it drains to the low watermark
+        budget.release(500);
+        assert_eq!(budget.buffered(), 700);
+        assert_eq!(budget.available_buffer_space(), 0);
+        assert_eq!(wake_counter, 0);
+
+        budget.release(300);
+        assert_eq!(budget.available_buffer_space(), 600);
+        assert_eq!(wake_counter, 1);
+
+        // the budget isn't blocked again until it reaches the high watermark
+        budget.acquire(500);
+        assert_eq!(budget.available_buffer_space(), 100);
+        budget.acquire(100);
+        assert_eq!(budget.available_buffer_space(), 0);
+    }
+}
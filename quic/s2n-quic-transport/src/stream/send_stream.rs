@@ -5,6 +5,7 @@ use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
+        send_group_budget::SendGroupBudget,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
         StreamError,
@@ -24,7 +25,7 @@ use core::{
 };
 use s2n_quic_core::{
     ack, application,
-    frame::{MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{MaxStreamData, ResetStream, ResetStreamAt, StopSending, StreamDataBlocked},
     packet::number::PacketNumber,
     stream::{ops, StreamId},
     time::{timer, Timestamp},
@@ -95,6 +96,11 @@ pub struct OutgoingResetData {
     final_size: VarInt,
     /// The error code which should get transmitted in the RESET frame
     application_error_code: application::Error,
+    /// The amount of data which is delivered reliably before the stream is reset
+    ///
+    /// If the value is non-zero, a `RESET_STREAM_AT` frame is transmitted instead of a
+    /// `RESET_STREAM` frame.
+    reliable_size: VarInt,
 }
 
 /// Writes the `RESET` frames based on the streams flow control window.
@@ -108,6 +114,15 @@ impl ValueToFrameWriter<OutgoingResetData> for ResetStreamToFrameWriter {
         stream_id: StreamId,
         context: &mut W,
     ) -> Option<PacketNumber> {
+        if value.reliable_size > VarInt::from_u8(0) {
+            return context.write_frame(&ResetStreamAt {
+                stream_id: stream_id.into(),
+                application_error_code: value.application_error_code.into(),
+                final_size: value.final_size,
+                reliable_size: value.reliable_size,
+            });
+        }
+
         context.write_frame(&ResetStream {
             stream_id: stream_id.into(),
             application_error_code: value.application_error_code.into(),
@@ -261,6 +276,26 @@ impl StreamFlowController {
         }
     }
 
+    /// Limits the connection window the stream will acquire to the given offset
+    ///
+    /// This is called when the stream is reset with a reliable size, since the data after the
+    /// reliable size will no longer be transmitted.
+    pub fn truncate(&mut self, offset: VarInt) {
+        if self.state == StreamFlowControllerState::Finished {
+            return;
+        }
+
+        let offset = offset.max(self.acquired_connection_flow_controller_window);
+        self.highest_requested_connection_flow_control_window = self
+            .highest_requested_connection_flow_control_window
+            .min(offset);
+
+        // The remaining data might not be blocked on flow control any longer. The blocked state
+        // will be recomputed on the next transmission attempt.
+        self.state = StreamFlowControllerState::Ready;
+        self.stream_data_blocked_sync.stop_sync();
+    }
+
     /// Returns the window/offset up to which data can be written
     fn available_window(&self) -> VarInt {
         core::cmp::min(
@@ -457,6 +492,17 @@ pub struct SendStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// The error code of the `STOP_SENDING` frame which caused the stream to be reset
+    stop_sending: Option<application::Error>,
+    /// A reliable reset which is waiting for the connection flow control window to cover
+    /// the reliable size before being transmitted
+    pending_reset: Option<OutgoingResetData>,
+    /// The group which limits the aggregate amount of data buffered across multiple streams
+    group: Option<SendGroupBudget>,
+    /// The budget which limits the total amount of data buffered by all streams on the connection
+    connection_budget: SendGroupBudget,
+    /// The amount of buffered bytes which have been acquired from each of the stream's budgets
+    budget_acquired: u64,
 }
 
 impl SendStream {
@@ -465,6 +511,7 @@ impl SendStream {
         is_closed: bool,
         initial_window: VarInt,
         max_buffer_capacity: u32,
+        connection_budget: SendGroupBudget,
     ) -> SendStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -485,6 +532,11 @@ impl SendStream {
             write_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            stop_sending: None,
+            pending_reset: None,
+            group: None,
+            connection_budget,
+            budget_acquired: 0,
         };
 
         if is_closed {
@@ -509,7 +561,7 @@ impl SendStream {
         // The reason for this is that we allow users to enqueue more data than
         // the maximum flow control window.
 
-        if let SendStreamState::Sending = self.state {
+        if self.is_transmitting_data() {
             self.data_sender
                 .flow_controller_mut()
                 .set_max_stream_data(frame.maximum_stream_data);
@@ -565,10 +617,14 @@ impl SendStream {
         //# An endpoint SHOULD copy the error code from the STOP_SENDING frame to
         //# the RESET_STREAM frame it sends, but it can use any application error
         //# code.
-        let error = StreamError::stream_reset(frame.application_error_code.into());
+        let error_code = frame.application_error_code.into();
+        let error = StreamError::stream_reset(error_code);
 
         if self.init_reset(ResetSource::StopSendingFrame, error) == InitResetResult::ResetInitiated
         {
+            // Remember the error code so it can be reported to the application
+            self.stop_sending = Some(error_code);
+
             // Return the waker to wake up potential users of the stream.
             // If the Stream got reset, then blocked writers need to get woken up.
             self.wake(events);
@@ -580,6 +636,7 @@ impl SendStream {
     /// This method gets called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A, events: &mut StreamEvents) {
         self.data_sender.on_packet_ack(ack_set);
+        self.release_budgets();
         self.data_sender
             .flow_controller_mut()
             .on_packet_ack(ack_set);
@@ -621,7 +678,14 @@ impl SendStream {
                 }
             }
             SendStreamState::ResetSent(error_code) => {
-                if self.reset_sync.on_packet_ack(ack_set).is_ready() {
+                let _ = self.reset_sync.on_packet_ack(ack_set);
+
+                // A reliable reset additionally requires all of the data up to the reliable size
+                // to be acknowledged before the stream is done.
+                let is_data_acknowledged =
+                    !matches!(self.data_sender.state(), data_sender::State::Finishing(_));
+
+                if self.reset_sync.is_delivered() && is_data_acknowledged {
                     // A reset had been acknowledged. Enter the terminal state.
                     self.state = SendStreamState::ResetAcknowledged(error_code);
 
@@ -652,8 +716,11 @@ impl SendStream {
         stream_id: StreamId,
         context: &mut W,
     ) -> Result<(), OnTransmitError> {
+        self.try_request_reset_delivery();
         self.reset_sync.on_transmit(stream_id, context)?;
         self.data_sender.on_transmit(stream_id.into(), context)?;
+        // transmitting data may have acquired the window needed for a pending reliable reset
+        self.try_request_reset_delivery();
         self.data_sender
             .flow_controller_mut()
             .on_transmit(stream_id, context)
@@ -701,10 +768,86 @@ impl SendStream {
     pub fn on_connection_window_available(&mut self) {
         // Outstanding flow control requests are only fulfilled if the Stream
         // was still trying to send data.
-        if let SendStreamState::Sending = self.state {
+        if self.is_transmitting_data() {
             self.data_sender
                 .flow_controller_mut()
                 .try_acquire_connection_window();
+            self.try_request_reset_delivery();
+        }
+    }
+
+    /// Assigns the stream to a group which shares an aggregate send budget
+    ///
+    /// Any data which is currently buffered by the stream is moved from the budget of the
+    /// previous group to the new one.
+    pub fn set_group(&mut self, mut group: SendGroupBudget) {
+        if self
+            .group
+            .as_ref()
+            .map_or(false, |current| current.ptr_eq(&group))
+        {
+            return;
+        }
+
+        // make sure the acquired budget reflects the currently buffered data
+        self.release_budgets();
+
+        if let Some(mut previous) = self.group.take() {
+            previous.release(self.budget_acquired);
+        }
+
+        group.acquire(self.budget_acquired);
+        self.group = Some(group);
+    }
+
+    /// Returns the budgets which limit the amount of data buffered by the stream
+    fn budgets_mut(&mut self) -> impl Iterator<Item = &mut SendGroupBudget> {
+        self.group
+            .iter_mut()
+            .chain(core::iter::once(&mut self.connection_budget))
+    }
+
+    /// Returns any budget to the group and connection for data which is no longer buffered
+    fn release_budgets(&mut self) {
+        let enqueued_len = self.data_sender.enqueued_len().as_u64();
+        if let Some(released) = self.budget_acquired.checked_sub(enqueued_len) {
+            self.budget_acquired = enqueued_len;
+            for budget in self.budgets_mut() {
+                budget.release(released);
+            }
+        }
+    }
+
+    /// Returns `true` if the stream is still transmitting data to the peer
+    ///
+    /// This is the case when the stream is in the `Sending` state, or if it was reset with a
+    /// reliable size and the reliable data has not been acknowledged yet.
+    fn is_transmitting_data(&self) -> bool {
+        match self.state {
+            SendStreamState::Sending => true,
+            SendStreamState::ResetSent(_) => {
+                matches!(self.data_sender.state(), data_sender::State::Finishing(_))
+            }
+            SendStreamState::ResetAcknowledged(_) => false,
+        }
+    }
+
+    /// Requests the delivery of a pending reliable reset once enough connection flow control
+    /// window has been acquired to transmit all of the reliable data.
+    fn try_request_reset_delivery(&mut self) {
+        if let Some(reset) = self.pending_reset {
+            let acquired_window = self
+                .data_sender
+                .flow_controller()
+                .acquired_connection_flow_controller_window();
+
+            if acquired_window >= reset.reliable_size {
+                self.pending_reset = None;
+                self.reset_sync.request_delivery(OutgoingResetData {
+                    final_size: acquired_window,
+                    ..reset
+                });
+            }
         }
     }
 
@@ -773,11 +916,15 @@ impl SendStream {
         }
 
         if let Some(error_code) = request.reset {
-            // reset is a best effort operation so ignore the result
-            let _ = self.init_reset(
-                ResetSource::LocalApplication,
-                StreamError::stream_reset(error_code),
-            );
+            let error = StreamError::stream_reset(error_code);
+
+            // reset is a best effort operation so ignore the result, unless the reliable size
+            // was invalid
+            if request.reliable_size > 0 {
+                let _ = self.init_reliable_reset(error, request.reliable_size)?;
+            } else {
+                let _ = self.init_reset(ResetSource::LocalApplication, error);
+            }
 
             // mark the stream as resetting
             response.status = ops::Status::Resetting;
@@ -805,6 +952,14 @@ impl SendStream {
                 // The reset is now known to have been read by the client.
                 self.final_state_observed = true;
                 self.write_waiter = None;
+
+                // Report the peer's error code to callers waiting for the stream to be stopped
+                if let Some(error_code) = self.stop_sending.filter(|_| request.stopped) {
+                    response.stop_sending = Some(error_code);
+                    response.status = ops::Status::Reset(error);
+                    return Ok(response);
+                }
+
                 return Err(error);
             }
             SendStreamState::Sending => {
@@ -824,6 +979,7 @@ impl SendStream {
 
                 if !self.can_push() {
                     store_waker!(false);
+                    self.register_budget_waker(context);
 
                     // no more progress can be made on the operation
                     return Ok(response);
@@ -832,10 +988,15 @@ impl SendStream {
                 response.bytes.consumed += chunk.len();
                 response.chunks.consumed += 1;
 
+                self.budget_acquired += chunk.len() as u64;
+                for budget in self.budgets_mut() {
+                    budget.acquire(chunk.len() as u64);
+                }
+
                 self.data_sender
                     .push(core::mem::replace(chunk, Bytes::new()));
             }
-        } else if !request.finish && !request.flush && context.is_some() {
+        } else if !request.finish && !request.flush && !request.stopped && context.is_some() {
             // if `chunks` are `None` or `Some(&[])` and we're not ending or flushing the stream,
             // the caller is only interested in notifications of state changes.
 
@@ -845,6 +1006,7 @@ impl SendStream {
             // store the waker if we currently can't push
             if !self.can_push() {
                 store_waker!(false);
+                self.register_budget_waker(context);
 
                 return Ok(response);
             }
@@ -881,10 +1043,21 @@ impl SendStream {
             store_waker!(true);
         }
 
+        if request.stopped {
+            if self.data_sender.state() == data_sender::State::Finished {
+                // The peer acknowledged all of the data, so it can no longer stop the stream
+                self.final_state_observed = true;
+                self.write_waiter = None;
+            } else {
+                // notify callers once the stream is either finished or stopped
+                store_waker!(false);
+            }
+        }
+
         match self.data_sender.state() {
             data_sender::State::Sending => {
                 // inform the caller of the available space to send
-                response.bytes.available = self.data_sender.available_buffer_space();
+                response.bytes.available = self.available_buffer_space();
                 // assume chunks are 1 bytes
                 response.chunks.available = response.bytes.available;
             }
@@ -925,7 +1098,37 @@ impl SendStream {
     fn can_push(&self) -> bool {
         // We accept the data if there is at least 1 byte of space
         // available in the flow control window.
-        self.data_sender.available_buffer_space() > 0
+        self.available_buffer_space() > 0
+    }
+
+    /// Returns the amount of data that can be additionally buffered on the stream
+    ///
+    /// This is further limited by the remaining budget of the connection and, if the stream is
+    /// part of a group, the remaining budget of the group.
+    fn available_buffer_space(&self) -> usize {
+        let available = self
+            .data_sender
+            .available_buffer_space()
+            .min(self.connection_budget.available_buffer_space());
+
+        if let Some(group) = self.group.as_ref() {
+            available.min(group.available_buffer_space())
+        } else {
+            available
+        }
+    }
+
+    /// Registers the caller with each budget which the stream is currently blocked on
+    ///
+    /// Releasing budget on any of the other streams sharing the budget will wake up the caller.
+    fn register_budget_waker(&mut self, context: Option<&Context>) {
+        if let Some(context) = context {
+            for budget in self.budgets_mut() {
+                if budget.available_buffer_space() == 0 {
+                    budget.register_waker(context.waker());
+                }
+            }
+        }
     }
 
     /// Ensures a potential push operation would be valid
@@ -984,6 +1187,7 @@ impl SendStream {
         // Clear the send buffer. Since we initiated a RESET, there is no need
         // to send or resend the remaining data.
         self.data_sender.stop_sending(error);
+        self.release_budgets();
 
         // For an internal reset (which provides no error_code) we do not need
         // to transmit the reset frame
@@ -1003,6 +1207,7 @@ impl SendStream {
                         .data_sender
                         .flow_controller()
                         .acquired_connection_flow_controller_window(),
+                    reliable_size: VarInt::from_u8(0),
                 });
             }
             (false, _) => {
@@ -1015,6 +1220,76 @@ impl SendStream {
 
         InitResetResult::ResetInitiated
     }
+
+    /// Starts the reset procedure while continuing to deliver all data up to `reliable_size`
+    ///
+    /// Returns an error if `reliable_size` exceeds the amount of data written to the stream.
+    fn init_reliable_reset(
+        &mut self,
+        error: StreamError,
+        reliable_size: u64,
+    ) -> Result<InitResetResult, StreamError> {
+        match self.state {
+            SendStreamState::ResetSent(_) | SendStreamState::ResetAcknowledged(_) => {
+                return Ok(InitResetResult::ResetNotNecessary)
+            }
+            SendStreamState::Sending
+                if self.data_sender.state() == data_sender::State::Finished =>
+            {
+                return Ok(InitResetResult::ResetNotNecessary)
+            }
+            SendStreamState::Sending => {}
+        }
+
+        let reliable_size = VarInt::try_from(reliable_size)
+            .ok()
+            .filter(|size| *size <= self.data_sender.total_enqueued_len())
+            .ok_or_else(StreamError::invalid_reliable_size)?;
+
+        let application_error_code = match error {
+            StreamError::StreamReset { error, .. } => error,
+            _ => unreachable!("reliable resets must be accommodated by an error code"),
+        };
+
+        self.state = SendStreamState::ResetSent(error);
+
+        // The application initiated the reset so it is aware of the final state
+        self.final_state_observed = true;
+
+        //# https://datatracker.ietf.org/doc/html/draft-ietf-quic-reliable-stream-reset#section-4
+        //# When resetting a stream without the intent to deliver any data to the
+        //# receiver, the sender uses a RESET_STREAM frame (Section 19.4 of
+        //# [RFC9000]).  When resetting a stream with the intent to deliver data
+        //# up to a certain offset, the sender uses a RESET_STREAM_AT frame.
+
+        // Discard any data after the reliable size and keep transmitting the rest
+        self.data_sender.reset_at(reliable_size);
+        self.data_sender
+            .flow_controller_mut()
+            .truncate(reliable_size);
+        self.release_budgets();
+
+        // The final size of the stream needs to include all of the reliable data, so the frame is
+        // held back until the stream has acquired enough of the connection window.
+        self.pending_reset = Some(OutgoingResetData {
+            application_error_code,
+            final_size: reliable_size,
+            reliable_size,
+        });
+        self.try_request_reset_delivery();
+
+        Ok(InitResetResult::ResetInitiated)
+    }
+}
+
+impl Drop for SendStream {
+    fn drop(&mut self) {
+        // return any outstanding budget so the other streams sharing it can make progress
+        let acquired = self.budget_acquired;
+        for budget in self.budgets_mut() {
+            budget.release(acquired);
+        }
+    }
 }
 
 impl timer::Provider for SendStream {
@@ -1044,9 +1319,12 @@ impl StreamInterestProvider for SendStream {
             //# A sender MUST NOT send a STREAM or
             //# STREAM_DATA_BLOCKED frame for a stream in the "Reset Sent" state or
             //# any terminal state -- that is, after sending a RESET_STREAM frame.
-            SendStreamState::ResetSent(_) => {
-                interests.with_transmission(|query| self.reset_sync.transmission_interest(query))
-            }
+            SendStreamState::ResetSent(_) => interests.with_transmission(|query| {
+                // reliable resets continue to transmit data up to the reliable size
+                self.data_sender.transmission_interest(query)?;
+                self.reset_sync.transmission_interest(query)?;
+                Ok(())
+            }),
             _ => interests.with_transmission(|query| {
                 self.data_sender.transmission_interest(query)?;
                 self.data_sender
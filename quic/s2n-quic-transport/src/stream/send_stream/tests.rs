@@ -3,6 +3,7 @@
 
 use super::*;
 use crate::stream::{
+    send_group_budget::SendGroupBudget,
     stream_interests::{StreamInterestProvider, StreamInterests},
     testing::*,
     StreamError, StreamEvents, StreamTrait,
@@ -89,6 +90,8 @@ enum Instruction {
     Finish(bool),
     /// Initiates a `RESET` with the given error code
     Reset(ApplicationErrorCode, bool),
+    /// Initiates a reliable `RESET` with the given error code and reliable size
+    ResetAt(ApplicationErrorCode, u64, bool),
     /// Ingest a `MAX_DATA` frame which indicates the given window.
     SetMaxData(VarInt),
     /// Ingest a `MAX_STREAM_DATA` frame which indicates the given window.
@@ -112,6 +115,8 @@ enum Instruction {
     ),
     /// Checks whether a reset frame is transmitted
     CheckResetTx(ApplicationErrorCode, PacketNumber, VarInt),
+    /// Checks whether a reset frame with a reliable size is transmitted
+    CheckResetAtTx(ApplicationErrorCode, PacketNumber, VarInt, VarInt),
     /// Checks whether a Stream is interested in the given interactions
     CheckInterests(StreamInterests),
     /// Checks that no outgoing data is written in an `on_transmit` call and
@@ -155,6 +160,14 @@ fn execute_instructions(test_env: &mut TestEnvironment, instructions: &[Instruct
 
                 assert_eq!(*expect_success, result.is_ok(), "Unexpected reset result");
             }
+            Instruction::ResetAt(error_code, reliable_size, expect_success) => {
+                let result = test_env.stream.poll_request(
+                    ops::Request::default().reset_at(*error_code, *reliable_size),
+                    None,
+                );
+
+                assert_eq!(*expect_success, result.is_ok(), "Unexpected reset result");
+            }
             Instruction::SetMaxData(max_data) => {
                 let was_waiting_for_connection_window = test_env
                     .stream
@@ -243,6 +256,19 @@ fn execute_instructions(test_env: &mut TestEnvironment, instructions: &[Instruct
                     *expected_final_size,
                 );
             }
+            Instruction::CheckResetAtTx(
+                expected_error_code,
+                expected_packet_number,
+                expected_final_size,
+                expected_reliable_size,
+            ) => {
+                test_env.assert_write_reset_at_frame(
+                    *expected_error_code,
+                    *expected_packet_number,
+                    *expected_final_size,
+                    *expected_reliable_size,
+                );
+            }
             Instruction::CheckInterests(expected_interests) => {
                 assert_eq!(*expected_interests, test_env.stream.get_stream_interests());
             }
@@ -2103,6 +2129,88 @@ fn stop_sending_does_not_cause_an_action_if_stream_is_finished_and_acknowledged(
     }
 }
 
+#[test]
+fn stopped_returns_the_stop_sending_error_code() {
+    let mut test_env = setup_send_only_test_env();
+    let error_code = ApplicationErrorCode::new(0x1234_5678).unwrap();
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u32(0), 500, true),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 500, false, false, pn(0)),
+        ],
+    );
+
+    // The stream is still open so the caller should wait
+    assert_eq!(test_env.poll_stopped(), Poll::Pending);
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            // The peer requests the stream to stop, which should wake up the waiter
+            Instruction::StopSending(error_code, ExpectWakeup(Some(true))),
+            Instruction::CheckResetTx(error_code, pn(1), VarInt::from_u32(500)),
+        ],
+    );
+
+    assert_eq!(test_env.poll_stopped(), Poll::Ready(Ok(Some(error_code))));
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::AckPacket(pn(1), ExpectWakeup(Some(false))),
+            Instruction::CheckInterests(stream_interests(&["fin"])),
+        ],
+    );
+
+    // The error code should still be available after the reset was acknowledged
+    assert_eq!(test_env.poll_stopped(), Poll::Ready(Ok(Some(error_code))));
+}
+
+#[test]
+fn stopped_returns_none_once_the_stream_is_finished_and_acknowledged() {
+    let mut test_env = setup_send_only_test_env();
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u32(0), 500, true),
+            Instruction::Finish(false),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 500, true, false, pn(0)),
+        ],
+    );
+
+    assert_eq!(test_env.poll_stopped(), Poll::Pending);
+
+    execute_instructions(
+        &mut test_env,
+        &[Instruction::AckPacket(pn(0), ExpectWakeup(Some(true)))],
+    );
+
+    assert_eq!(test_env.poll_stopped(), Poll::Ready(Ok(None)));
+
+    // Observing the finished stream should allow it to be finalized
+    execute_instructions(
+        &mut test_env,
+        &[Instruction::CheckInterests(stream_interests(&["fin"]))],
+    );
+}
+
+#[test]
+fn stopped_after_stream_is_reset_locally() {
+    let mut test_env = setup_send_only_test_env();
+    let error_code = ApplicationErrorCode::new(0x1234_5678).unwrap();
+
+    execute_instructions(&mut test_env, &[Instruction::Reset(error_code, true)]);
+
+    // A local reset was not requested by the peer, so it's reported as an error
+    assert_matches!(
+        test_env.poll_stopped(),
+        Poll::Ready(Err(StreamError::StreamReset { .. })),
+    );
+}
+
 #[test]
 fn reset_does_not_cause_an_action_if_stream_is_already_reset() {
     for is_internal_reset in &[true, false] {
@@ -2365,6 +2473,55 @@ fn resetting_the_stream_does_does_trigger_a_reset_frame_and_reset_errors() {
     }
 }
 
+#[test]
+fn reliable_reset_transmits_data_up_to_reliable_size() {
+    let mut test_env = setup_send_only_test_env();
+    let error_code = ApplicationErrorCode::new(0x3333_4444).unwrap();
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u32(0), 500, true),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 500, false, false, pn(0)),
+            Instruction::EnqueueData(VarInt::from_u32(500), 500, true),
+            // The reliable size can't exceed the amount of enqueued data
+            Instruction::ResetAt(error_code, 1001, false),
+            Instruction::ResetAt(error_code, 700, true),
+            Instruction::CheckInterests(stream_interests(&["ack", "tx"])),
+            // Only the data up to the reliable size is transmitted
+            Instruction::CheckDataTx(VarInt::from_u32(500), 200, false, false, pn(1)),
+            Instruction::CheckResetAtTx(
+                error_code,
+                pn(2),
+                VarInt::from_u32(700),
+                VarInt::from_u32(700),
+            ),
+            Instruction::CheckNoTx,
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+        ],
+    );
+
+    // Accessing the stream should lead to the reset error
+    assert_matches!(
+        test_env.poll_finish(),
+        Poll::Ready(Err(StreamError::StreamReset { .. })),
+    );
+
+    // The reset is only complete once all of the reliable data is acknowledged
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::AckPacket(pn(1), ExpectWakeup(Some(false))),
+            Instruction::AckPacket(pn(2), ExpectWakeup(Some(false))),
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+            Instruction::NackPacket(pn(0)),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 500, false, false, pn(3)),
+            Instruction::AckPacket(pn(3), ExpectWakeup(Some(false))),
+            Instruction::CheckInterests(stream_interests(&["fin"])),
+        ],
+    );
+}
+
 #[test]
 fn stream_does_not_try_to_acquire_connection_flow_control_credits_after_reset() {
     #[derive(Copy, Clone, Debug, PartialEq)]
@@ -2728,6 +2885,7 @@ fn can_send_multiple_chunks() {
                                     ops::Status::Open
                                 },
                                 will_wake,
+                                stop_sending: None,
                             }),
                             rx: None,
                         }),
@@ -2847,6 +3005,7 @@ fn can_query_stream_readiness() {
                         },
                         status: ops::Status::Open,
                         will_wake: with_context && expected_buffer_size == 0,
+                        stop_sending: None,
                     }),
                     rx: None,
                 }),
@@ -2854,3 +3013,40 @@ fn can_query_stream_readiness() {
         }
     }
 }
+
+#[test]
+fn group_budget_is_shared_between_streams() {
+    let mut group = SendGroupBudget::new(1000);
+    let mut stream_1 = setup_send_only_test_env();
+    let mut stream_2 = setup_send_only_test_env();
+    stream_1.stream.set_send_group(group.clone());
+    stream_2.stream.set_send_group(group.clone());
+
+    assert_eq!(
+        Poll::Ready(Ok(())),
+        stream_1.poll_push(Bytes::from_static(&[0; 1000]))
+    );
+    assert_eq!(group.buffered(), 1000);
+
+    // the second stream is blocked on the group's budget, even though it has buffer space
+    assert_eq!(Poll::Pending, stream_2.poll_push(Bytes::from_static(b"1")));
+
+    // acknowledging the data on the first stream frees up the budget and wakes the second stream
+    stream_1.assert_write_frames(1);
+    stream_1.ack_packet(pn(0), ExpectWakeup(Some(false)));
+    assert_eq!(group.buffered(), 0);
+    assert_eq!(stream_2.wake_counter, 1);
+    assert_eq!(
+        Poll::Ready(Ok(())),
+        stream_2.poll_push(Bytes::from_static(b"1"))
+    );
+    assert_eq!(group.buffered(), 1);
+
+    // dropping a stream returns its budget to the group
+    drop(stream_2);
+    assert_eq!(group.buffered(), 0);
+
+    // raising the limit makes the budget available immediately
+    group.set_max_buffer_size(2000);
+    assert_eq!(group.available_buffer_space(), 2000);
+}
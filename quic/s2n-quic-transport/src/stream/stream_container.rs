@@ -8,7 +8,7 @@
 #![allow(unknown_lints, clippy::non_send_fields_in_send_ty)]
 
 use crate::{
-    stream,
+    connection, stream,
     stream::{stream_impl::StreamTrait, stream_interests::StreamInterests},
     transmission,
 };
@@ -620,6 +620,23 @@ impl<S: StreamTrait> StreamContainer<S> {
         !self.interest_lists.waiting_for_transmission.is_empty()
             || !self.interest_lists.waiting_for_retransmission.is_empty()
     }
+
+    /// Returns `true` if no stream has buffered data left to transmit and, if required by the
+    /// `mode`, no stream is waiting on acknowledgements for transmitted data
+    pub fn is_flushed(&self, mode: connection::FlushMode) -> bool {
+        let lists = &self.interest_lists;
+
+        // streams blocked on flow control still have data buffered
+        let is_transmitted = !self.has_pending_streams()
+            && lists.waiting_for_connection_flow_control_credits.is_empty()
+            && lists.waiting_for_stream_flow_control_credits.is_empty();
+
+        if !mode.is_acknowledged() {
+            return is_transmitted;
+        }
+
+        is_transmitted && lists.waiting_for_frame_delivery.is_empty()
+    }
 }
 
 impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
@@ -7,6 +7,7 @@ use crate::{
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         receive_stream::ReceiveStream,
+        send_group_budget::SendGroupBudget,
         send_stream::SendStream,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
@@ -15,8 +16,13 @@ use crate::{
 };
 use core::{task::Context, time::Duration};
 use s2n_quic_core::{
-    ack, endpoint,
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    ack,
+    buffer::reassembler,
+    endpoint,
+    frame::{
+        stream::StreamRef, MaxStreamData, ResetStream, ResetStreamAt, StopSending,
+        StreamDataBlocked,
+    },
     stream::{ops, StreamId},
     time::{timer, Timestamp},
     transport,
@@ -38,10 +44,14 @@ pub struct StreamConfig {
     pub initial_receive_window: VarInt,
     /// The desired flow control window that we want to maintain on the receiving side
     pub desired_flow_control_window: u32,
+    /// The pool which receive buffers are allocated from, if any
+    pub receive_buffer_pool: Option<reassembler::Pool>,
     /// The initial flow control window for sending data
     pub initial_send_window: VarInt,
     /// The maximum buffered amount of data on the sending side
     pub max_send_buffer_size: u32,
+    /// The connection-wide budget for data buffered on the sending side
+    pub connection_send_budget: SendGroupBudget,
 }
 
 /// A trait which represents an internally used `Stream`
@@ -78,6 +88,14 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error>;
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error>;
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// this stream
     fn on_max_stream_data(
@@ -124,6 +142,9 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
 
     // These functions are called from the client API
 
+    /// Assigns the sending half of the stream to a group which shares an aggregate send budget
+    fn set_send_group(&mut self, group: SendGroupBudget);
+
     fn poll_request(
         &mut self,
         request: &mut ops::Request,
@@ -204,6 +225,7 @@ impl StreamTrait for StreamImpl {
                 config.incoming_connection_flow_controller,
                 config.initial_receive_window,
                 config.desired_flow_control_window,
+                config.receive_buffer_pool,
             ),
             has_send: !send_is_closed,
             send_stream: SendStream::new(
@@ -211,6 +233,7 @@ impl StreamTrait for StreamImpl {
                 send_is_closed,
                 config.initial_send_window,
                 config.max_send_buffer_size,
+                config.connection_send_budget,
             ),
         }
     }
@@ -249,6 +272,15 @@ impl StreamTrait for StreamImpl {
         self.receive_stream.on_reset(frame, events)
     }
 
+    #[inline]
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        self.receive_stream.on_reset_at(frame, events)
+    }
+
     #[inline]
     fn on_max_stream_data(
         &mut self,
@@ -329,6 +361,13 @@ impl StreamTrait for StreamImpl {
 
     // These functions are called from the client API
 
+    #[inline]
+    fn set_send_group(&mut self, group: SendGroupBudget) {
+        if self.has_send {
+            self.send_stream.set_group(group);
+        }
+    }
+
     fn poll_request(
         &mut self,
         request: &mut ops::Request,
@@ -6,8 +6,8 @@ use crate::{
     stream::{
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
-        stream_impl::StreamConfig, stream_interests::StreamInterests, StreamEvents, StreamImpl,
-        StreamTrait,
+        send_group_budget::SendGroupBudget, stream_impl::StreamConfig,
+        stream_interests::StreamInterests, StreamEvents, StreamImpl, StreamTrait,
     },
     transmission,
 };
@@ -17,7 +17,7 @@ use futures_test::task::{new_count_waker, AwokenCount};
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     endpoint,
-    frame::{stream::Stream as StreamFrame, Frame, ResetStream, StreamDataBlocked},
+    frame::{stream::Stream as StreamFrame, Frame, ResetStream, ResetStreamAt, StreamDataBlocked},
     packet::number::{PacketNumber, PacketNumberSpace},
     stream::{ops, StreamError, StreamId, StreamType},
     time::{clock::testing as time, Timestamp},
@@ -355,6 +355,31 @@ impl TestEnvironment {
         );
     }
 
+    /// Asserts that a RESET_STREAM_AT frame was transmitted
+    pub fn assert_write_reset_at_frame(
+        &mut self,
+        expected_error_code: ApplicationErrorCode,
+        expected_packet_number: PacketNumber,
+        expected_final_size: VarInt,
+        expected_reliable_size: VarInt,
+    ) {
+        let mut sent_frame = self.transmit().expect("no frame was written");
+        assert_eq!(
+            expected_packet_number, sent_frame.packet_nr,
+            "packet number mismatch"
+        );
+
+        assert_eq!(
+            Frame::ResetStreamAt(ResetStreamAt {
+                stream_id: self.stream.stream_id.into(),
+                application_error_code: expected_error_code.into(),
+                final_size: expected_final_size,
+                reliable_size: expected_reliable_size,
+            }),
+            sent_frame.as_frame()
+        );
+    }
+
     /// Asserts that a STREAM_DATA_BLOCKED frame was transmitted
     pub fn assert_write_stream_data_blocked_frame(
         &mut self,
@@ -444,6 +469,18 @@ impl TestEnvironment {
         }
     }
 
+    pub fn poll_stopped(&mut self) -> Poll<Result<Option<ApplicationErrorCode>, StreamError>> {
+        match self
+            .run_request(ops::Request::default().stopped(), true)?
+            .into_poll()
+        {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(response) => {
+                Poll::Ready(Ok(response.tx().expect("invalid response").stop_sending))
+            }
+        }
+    }
+
     pub fn poll_pop(&mut self) -> Poll<Result<Option<Bytes>, StreamError>> {
         let mut chunks = [Bytes::new()];
         match self
@@ -544,6 +581,8 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         desired_flow_control_window: config.desired_flow_control_window,
         initial_send_window: VarInt::new(config.initial_send_window).unwrap(),
         max_send_buffer_size: config.max_send_buffer_size as u32,
+        connection_send_budget: SendGroupBudget::with_watermarks(u64::MAX, u64::MAX),
+        receive_buffer_pool: None,
     });
 
     let (waker, wake_counter) = new_count_waker();
@@ -190,12 +190,59 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.check_integrity();
     }
 
+    /// Stops sending outgoing data beyond the given offset.
+    ///
+    /// Any data up to `reliable_size` will continue to be transmitted until it has been
+    /// acknowledged, after which the sender enters the `Finished` state. Unlike
+    /// [`Self::finish()`], the final size of the stream is not transmitted with a `FIN` flag,
+    /// since it will be communicated to the peer via a `RESET_STREAM_AT` frame.
+    pub fn reset_at(&mut self, reliable_size: VarInt) {
+        if matches!(self.state, State::Finished | State::Cancelled(_)) {
+            return;
+        }
+
+        self.buffer.truncate(reliable_size);
+        let total_len = self.buffer.total_len();
+
+        // Drop any tracking information for the discarded data. Transmissions which are still in
+        // flight are ignored as their ranges will no longer be contained in `pending`.
+        let discarded = total_len..=VarInt::MAX;
+        self.pending
+            .remove(discarded.clone())
+            .expect("pending should not have a limit");
+        self.lost
+            .remove(discarded)
+            .expect("lost should not have a limit");
+        self.transmission_offset = self.transmission_offset.min(total_len);
+
+        if self.pending.is_empty() {
+            // We don't need to track transmissions for discarded or already acked ranges
+            self.transmissions.clear();
+        }
+
+        self.state = State::Finishing(FinState::Acknowledged);
+
+        if self.is_idle() {
+            self.state = State::Finished;
+            self.flow_controller_mut().finish();
+            self.buffer.release_all();
+        }
+
+        self.check_integrity();
+    }
+
     /// Returns the amount of bytes that have ever been enqueued for writing on
     /// this Stream. This equals the offset of the highest enqueued byte + 1.
     pub fn total_enqueued_len(&self) -> VarInt {
         self.buffer.total_len()
     }
 
+    /// Returns the amount of bytes which are currently buffered for transmission or
+    /// retransmission
+    pub fn enqueued_len(&self) -> VarInt {
+        self.buffer.enqueued_len()
+    }
+
     /// Returns true if the data sender doesn't have any data enqueued for sending
     pub fn is_empty(&self) -> bool {
         self.buffer.is_empty()
@@ -126,6 +126,33 @@ impl Buffer {
         self.check_integrity();
     }
 
+    /// Removes all of the chunks after the provided offset in the buffer
+    ///
+    /// Chunks which have already been released are not affected, so the buffer will never be
+    /// truncated to a length less than its head.
+    pub fn truncate(&mut self, len: VarInt) {
+        let len = len.max(self.head);
+
+        while self.total_len() > len {
+            let mut chunk = self.chunks.pop_back().expect("buffer should not be empty");
+            let chunk_len = VarInt::try_from(chunk.len()).unwrap();
+            let start = self.total_len() - chunk_len;
+
+            if start < len {
+                // only part of the chunk is removed
+                let remaining = len - start;
+                chunk.data.truncate(remaining.try_into().unwrap());
+                self.pending_len -= chunk_len - remaining;
+                self.chunks.push_back(chunk);
+                break;
+            }
+
+            self.pending_len -= chunk_len;
+        }
+
+        self.check_integrity();
+    }
+
     /// Releases all of the currently enqueued chunks
     pub fn release_all(&mut self) {
         self.chunks.clear();
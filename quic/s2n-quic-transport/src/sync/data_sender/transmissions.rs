@@ -158,7 +158,10 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         }
 
         let packet_number = context.packet_number();
-        let mut view = viewer.next_view(interval, matches!(state, State::Finishing(_)));
+        // An acknowledged FIN doesn't need to be sent again. This also covers senders that finish
+        // without transmitting a FIN after a reliable reset.
+        let has_fin = matches!(state, State::Finishing(fin) if !fin.is_acknowledged());
+        let mut view = viewer.next_view(interval, has_fin);
 
         self.writer
             .write_chunk(
@@ -8,7 +8,7 @@ use crate::{
     dc, endpoint, path,
     path::mtu,
     recovery,
-    space::{datagram, CryptoStream, HandshakeStatus},
+    space::{custom_frame, datagram, CryptoStream, HandshakeStatus},
     stream::Manager as _,
     sync::{flag, flag::Ping},
     transmission::{self, Mode, Provider as _},
@@ -37,6 +37,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         recovery_manager: &'a mut recovery::Manager<Config>,
         crypto_stream: &'a mut CryptoStream,
         datagram_manager: &'a mut datagram::Manager<Config>,
+        custom_frame_manager: &'a mut custom_frame::Manager<Config>,
         dc_manager: &'a mut dc::Manager<Config>,
     ) -> Self {
         if transmission_mode != Mode::PathValidationOnly {
@@ -55,6 +56,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
                     recovery_manager,
                     crypto_stream,
                     datagram_manager,
+                    custom_frame_manager,
                     dc_manager,
                     prioritize_datagrams: false,
                 })
@@ -109,6 +111,7 @@ pub struct Normal<'a, Config: endpoint::Config> {
     recovery_manager: &'a mut recovery::Manager<Config>,
     crypto_stream: &'a mut CryptoStream,
     datagram_manager: &'a mut datagram::Manager<Config>,
+    custom_frame_manager: &'a mut custom_frame::Manager<Config>,
     dc_manager: &'a mut dc::Manager<Config>,
     prioritize_datagrams: bool,
 }
@@ -138,6 +141,11 @@ impl<'a, Config: endpoint::Config> Normal<'a, Config> {
         if can_transmit {
             self.transmit_control_data(context);
 
+            // Custom frames are sent after the control data so they don't delay any
+            // frames required by the transport
+            self.custom_frame_manager
+                .on_transmit(context, self.path_manager.active_path());
+
             // If we did not prioritize datagrams in this packet, we send them just
             // before we send stream data.
             if !self.prioritize_datagrams {
@@ -201,6 +209,7 @@ impl<'a, Config: endpoint::Config> transmission::interest::Provider for Normal<'
         self.handshake_status.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
+        self.custom_frame_manager.transmission_interest(query)?;
         self.local_id_registry.transmission_interest(query)?;
         self.path_manager.transmission_interest(query)?;
         self.crypto_stream.transmission_interest(query)?;
@@ -6,7 +6,12 @@ use core::marker::PhantomData;
 use s2n_codec::{encoder::scatter, Encoder, EncoderValue};
 use s2n_quic_core::{
     event::{self, ConnectionPublisher as _, IntoEvent},
-    frame::{ack::AckRanges as AckRangesTrait, ack_elicitation::AckElicitation, Ack, FrameTrait},
+    frame::{
+        ack::AckRanges as AckRangesTrait,
+        ack_elicitation::{AckElicitable as _, AckElicitation},
+        congestion_controlled::CongestionControlled as _,
+        Ack, FrameTrait, Padding,
+    },
     packet::number::PacketNumber,
     time::Timestamp,
 };
@@ -59,6 +64,38 @@ impl<'a, 'b, 'sub, Config: endpoint::Config> Context<'a, 'b, 'sub, Config> {
             transmission::Constraint::None => {}
         }
     }
+
+    /// Writes a PADDING frame to the packet
+    ///
+    /// If the payload ends with stream data which is deferred until encryption, the padding is
+    /// inserted in front of the other frames instead. PADDING frames can appear anywhere in the
+    /// payload so this avoids copying the stream data into the packet buffer before it's sealed.
+    #[inline]
+    pub fn write_padding_frame(&mut self, frame: &Padding) -> Option<PacketNumber> {
+        if !self.buffer.has_extra() {
+            return self.write_frame(frame);
+        }
+
+        self.check_frame_constraint(frame);
+
+        if frame.encoding_size() > self.buffer.remaining_capacity() {
+            return None;
+        }
+
+        self.buffer.prepend_repeated(frame.length, 0);
+        self.outcome.ack_elicitation |= frame.ack_elicitation();
+        self.outcome.is_congestion_controlled |= frame.is_congestion_controlled();
+
+        self.publisher.on_frame_sent(event::builder::FrameSent {
+            packet_header: event::builder::PacketHeader::new(
+                self.packet_number,
+                self.publisher.quic_version(),
+            ),
+            path_id: self.path_id.into_event(),
+            frame: frame.into_event(),
+        });
+        Some(self.packet_number)
+    }
 }
 
 impl<'a, 'b, 'sub, Config: endpoint::Config> WriteContext for Context<'a, 'b, 'sub, Config> {
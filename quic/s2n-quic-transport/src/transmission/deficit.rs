@@ -0,0 +1,203 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deficit round robin scheduling of connection transmissions
+//!
+//! Each time a connection is visited by the endpoint's transmission round, it is granted a
+//! quantum of bytes it may push into the TX queue. Once the credits are exhausted, the connection
+//! is moved to the back of the transmission list so other connections get a chance to fill the
+//! remaining queue capacity. Any overshoot from the final datagram is carried over to the next
+//! round.
+
+use s2n_quic_core::io::tx;
+
+/// The transmission credits for a single connection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Deficit {
+    credits: i64,
+}
+
+impl Deficit {
+    /// Grants the connection its quantum for a new transmission round
+    ///
+    /// Unused credits are capped at the quantum so an idle connection can't accumulate a burst.
+    #[inline]
+    pub fn on_round(&mut self, quantum: u32) {
+        let quantum = quantum as i64;
+        self.credits = (self.credits + quantum).min(quantum);
+    }
+
+    /// Returns the remaining credits, which is negative if the connection overshot its quantum
+    #[inline]
+    pub fn credits(&self) -> i64 {
+        self.credits
+    }
+}
+
+/// A transmission queue which limits pushes to the connection's remaining credits
+pub struct Queue<'a, Tx> {
+    inner: &'a mut Tx,
+    deficit: &'a mut Deficit,
+    datagrams: usize,
+    bytes: usize,
+    is_limited: bool,
+}
+
+impl<'a, Tx: tx::Queue> Queue<'a, Tx> {
+    #[inline]
+    pub fn new(inner: &'a mut Tx, deficit: &'a mut Deficit) -> Self {
+        Self {
+            inner,
+            deficit,
+            datagrams: 0,
+            bytes: 0,
+            is_limited: false,
+        }
+    }
+
+    /// Returns the number of datagrams pushed into the queue
+    #[inline]
+    pub fn datagrams(&self) -> usize {
+        self.datagrams
+    }
+
+    /// Returns the number of bytes pushed into the queue
+    #[inline]
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Returns `true` if a push was rejected because the credits were exhausted
+    #[inline]
+    pub fn is_limited(&self) -> bool {
+        self.is_limited
+    }
+}
+
+impl<'a, Tx: tx::Queue> tx::Queue for Queue<'a, Tx> {
+    type Handle = Tx::Handle;
+
+    const SUPPORTS_ECN: bool = Tx::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Tx::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Tx::SUPPORTS_FLOW_LABELS;
+    const SUPPORTS_FINISH_PAYLOAD: bool = Tx::SUPPORTS_FINISH_PAYLOAD;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        if self.deficit.credits <= 0 {
+            self.is_limited = true;
+            return Err(tx::Error::AtCapacity);
+        }
+
+        let outcome = self.inner.push(message)?;
+        self.datagrams += 1;
+        self.bytes += outcome.len;
+        self.deficit.credits -= outcome.len as i64;
+
+        Ok(outcome)
+    }
+
+    #[inline]
+    fn finish_payload<F: FnOnce(&mut [u8])>(&mut self, f: F) {
+        self.inner.finish_payload(f)
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.inner.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        if self.deficit.credits <= 0 {
+            0
+        } else {
+            self.inner.capacity()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        io::tx::{Message, Queue as _},
+        path::{self, Handle as _},
+    };
+
+    struct TestQueue {
+        capacity: usize,
+    }
+
+    impl tx::Queue for TestQueue {
+        type Handle = path::RemoteAddress;
+
+        fn push<M: Message<Handle = Self::Handle>>(
+            &mut self,
+            mut message: M,
+        ) -> Result<tx::Outcome, tx::Error> {
+            if self.capacity == 0 {
+                return Err(tx::Error::AtCapacity);
+            }
+            self.capacity -= 1;
+
+            let mut buffer = [0u8; 1500];
+            let len = message.write_payload(tx::PayloadBuffer::new(&mut buffer), 0)?;
+            Ok(tx::Outcome { len, index: 0 })
+        }
+
+        fn capacity(&self) -> usize {
+            self.capacity
+        }
+    }
+
+    fn message(len: usize) -> (path::RemoteAddress, Vec<u8>) {
+        let handle = path::RemoteAddress::from_remote_address(Default::default());
+        (handle, vec![1; len])
+    }
+
+    #[test]
+    fn quantum_test() {
+        let mut inner = TestQueue { capacity: 100 };
+        let mut deficit = Deficit::default();
+        deficit.on_round(2000);
+
+        let mut queue = Queue::new(&mut inner, &mut deficit);
+        assert!(queue.push(message(1200)).is_ok());
+        // the credits are still positive so the datagram is allowed to overshoot
+        assert!(queue.push(message(1200)).is_ok());
+        assert!(!queue.has_capacity());
+        assert!(queue.push(message(1200)).is_err());
+
+        assert_eq!(queue.datagrams(), 2);
+        assert_eq!(queue.bytes(), 2400);
+        assert!(queue.is_limited());
+        assert_eq!(deficit.credits(), -400);
+
+        // the overshoot is deducted from the next round
+        deficit.on_round(2000);
+        assert_eq!(deficit.credits(), 1600);
+
+        // unused credits are capped at the quantum
+        deficit.on_round(2000);
+        assert_eq!(deficit.credits(), 2000);
+    }
+
+    #[test]
+    fn inner_capacity_test() {
+        let mut inner = TestQueue { capacity: 1 };
+        let mut deficit = Deficit::default();
+        deficit.on_round(10_000);
+
+        let mut queue = Queue::new(&mut inner, &mut deficit);
+        assert!(queue.push(message(1200)).is_ok());
+        assert!(queue.push(message(1200)).is_err());
+
+        // the connection was limited by the queue, not its credits
+        assert!(!queue.is_limited());
+        assert_eq!(deficit.credits(), 8800);
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::path;
+use s2n_quic_core::{
+    crypto::{
+        self, application::KeySet, HeaderKey, HeaderProtectionBatch, OneRttKey, UnsealedPacket,
+    },
+    packet::KeyPhase,
+};
+
+/// Tracks the packets in the current GSO payload which are waiting to be sealed
+///
+/// The packets are encrypted and header protection is applied once the TX queue finishes the
+/// payload, which allows the masks for all of the packets in the payload to be derived at once.
+#[derive(Debug, Default)]
+pub struct Deferred {
+    /// The packets in the current payload which haven't been encrypted yet
+    packets: Vec<Packet>,
+    batch: HeaderProtectionBatch,
+    /// The size of each segment in the current payload
+    ///
+    /// All of the segments, except for the last one, are the same size as the first one.
+    segment_len: usize,
+    /// The offset of the datagram currently being written in the payload
+    datagram_offset: usize,
+    /// The number of packets in the batch before the current datagram was written
+    datagram_packets: usize,
+    /// The offset of the next packet in the payload
+    packet_offset: usize,
+    /// The datagrams with deferred packets, which are reported once they are protected
+    datagrams: Vec<Datagram>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Packet {
+    offset: usize,
+    packet: UnsealedPacket,
+    key_phase: KeyPhase,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Datagram {
+    offset: usize,
+    len: usize,
+    path_id: path::Id,
+}
+
+impl Deferred {
+    /// Called before a datagram is written to the segment at `gso_offset`
+    #[inline]
+    pub fn on_datagram_start(&mut self, gso_offset: usize) {
+        if gso_offset == 0 {
+            debug_assert!(
+                self.packets.is_empty() && self.datagrams.is_empty(),
+                "the previous payload should be finished before a new one is started"
+            );
+            self.segment_len = 0;
+        }
+
+        self.datagram_offset = gso_offset * self.segment_len;
+        self.datagram_packets = self.packets.len();
+    }
+
+    /// Called before a packet is written at `position` in the current datagram
+    #[inline]
+    pub fn on_packet_start(&mut self, position: usize) {
+        self.packet_offset = self.datagram_offset + position;
+    }
+
+    /// Records the unsealed packet which was written after the last call to `on_packet_start`
+    ///
+    /// `key_phase` is the phase of the key the packet was encoded for.
+    #[inline]
+    pub fn on_packet(&mut self, packet: UnsealedPacket, key_phase: KeyPhase) {
+        self.packets.push(Packet {
+            offset: self.packet_offset,
+            packet,
+            key_phase,
+        });
+    }
+
+    /// Called after a datagram of `len` bytes is written to the segment at `gso_offset`
+    ///
+    /// Returns `true` if the datagram contains packets which are waiting to be sealed.
+    #[inline]
+    pub fn on_datagram_end(&mut self, gso_offset: usize, len: usize, path_id: path::Id) -> bool {
+        if gso_offset == 0 {
+            self.segment_len = len;
+        }
+
+        if self.packets.len() == self.datagram_packets {
+            return false;
+        }
+
+        self.datagrams.push(Datagram {
+            offset: self.datagram_offset,
+            len,
+            path_id,
+        });
+
+        true
+    }
+
+    /// Encrypts and applies header protection to all of the recorded packets in the finished
+    /// `payload`
+    ///
+    /// `on_datagram` is called for each datagram which contained a recorded packet.
+    #[inline]
+    pub fn finish<K: OneRttKey, H: HeaderKey, F: FnMut(path::Id, &mut [u8])>(
+        &mut self,
+        key_set: &mut KeySet<K>,
+        header_key: &H,
+        payload: &mut [u8],
+        mut on_datagram: F,
+    ) {
+        for packet in self.packets.drain(..) {
+            debug_assert!(packet.offset + packet.packet.len <= payload.len());
+            let key = key_set.key_mut(packet.key_phase).key_mut();
+            let encrypted_payload = crypto::seal(key, packet.packet, &mut payload[packet.offset..])
+                .expect("encryption should always work");
+            self.batch.push(packet.offset, &encrypted_payload);
+        }
+
+        self.batch
+            .protect(header_key, payload)
+            .expect("header protection should always work");
+
+        for datagram in self.datagrams.drain(..) {
+            let range = datagram.offset..datagram.offset + datagram.len;
+            debug_assert!(range.end <= payload.len());
+            if let Some(bytes) = payload.get_mut(range) {
+                on_datagram(datagram.path_id, bytes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        crypto::{
+            application::limited,
+            key::testing::{HeaderKey, Key},
+        },
+        packet::number::PacketNumberSpace,
+        varint::VarInt,
+    };
+
+    fn on_packet(deferred: &mut Deferred, position: usize, key_phase: KeyPhase) {
+        let space = PacketNumberSpace::ApplicationData;
+        let packet = UnsealedPacket {
+            packet_number: space.new_packet_number(VarInt::from_u8(1)),
+            packet_number_len: space.new_packet_number_len(0),
+            header_len: 1,
+            len: 32,
+        };
+        deferred.on_packet_start(position);
+        deferred.on_packet(packet, key_phase);
+    }
+
+    #[test]
+    fn datagram_test() {
+        let mut deferred = Deferred::default();
+        let mut key_set = KeySet::new(Key::new(), limited::Limits::default());
+        let first_path = unsafe { path::Id::new(0) };
+        let second_path = unsafe { path::Id::new(1) };
+
+        // two coalesced packets in the first segment
+        deferred.on_datagram_start(0);
+        on_packet(&mut deferred, 0, KeyPhase::Zero);
+        on_packet(&mut deferred, 50, KeyPhase::Zero);
+        assert!(deferred.on_datagram_end(0, 100, first_path));
+
+        // the second segment doesn't contain any deferred packets
+        deferred.on_datagram_start(1);
+        assert!(!deferred.on_datagram_end(1, 100, first_path));
+
+        // the last segment is allowed to be shorter and its packet was encoded for the next phase
+        deferred.on_datagram_start(2);
+        on_packet(&mut deferred, 0, KeyPhase::One);
+        assert!(deferred.on_datagram_end(2, 40, second_path));
+
+        let mut payload: Vec<u8> = (0..240).map(|v| v as u8).collect();
+        let mut datagrams = vec![];
+        deferred.finish(
+            &mut key_set,
+            &HeaderKey::new(),
+            &mut payload,
+            |path_id, datagram| {
+                datagrams.push((path_id, datagram.to_vec()));
+            },
+        );
+
+        assert_eq!(
+            datagrams,
+            vec![
+                (first_path, payload[..100].to_vec()),
+                (second_path, payload[200..].to_vec()),
+            ]
+        );
+
+        // the next payload starts with an empty batch
+        deferred.on_datagram_start(0);
+        assert!(!deferred.on_datagram_end(0, 100, first_path));
+        deferred.finish(&mut key_set, &HeaderKey::new(), &mut payload, |_, _| {
+            panic!()
+        });
+    }
+}
@@ -6,7 +6,9 @@ use context::Context;
 
 pub mod application;
 pub mod connection_close;
+pub mod deficit;
 pub mod early;
+pub mod header_protection;
 
 pub use crate::contexts::WriteContext;
 
@@ -100,7 +102,7 @@ impl<'a, 'sub, Config: endpoint::Config, P: Payload> PacketPayloadEncoder
             }
 
             if length > 0 {
-                context.write_frame(&Padding { length });
+                context.write_padding_frame(&Padding { length });
             }
 
             {
@@ -17,13 +17,14 @@ mod builder;
 mod providers;
 
 pub use builder::*;
-pub use connect::Connect;
+pub use connect::{Connect, RetryPolicy};
 pub use providers::*;
 
 /// A QUIC client endpoint, capable of opening connections
 #[derive(Clone)]
 pub struct Client {
     connector: Connector,
+    manager: connection::Manager,
     local_addr: s2n_quic_core::inet::SocketAddress,
 }
 
@@ -144,6 +145,31 @@ impl Client {
         futures::future::poll_fn(|cx| self.connector.poll_close(cx)).await
     }
 
+    /// Returns a [`connection::Manager`] for inspecting and closing the client's connections
+    ///
+    /// The manager is shared by all of the handles to the client endpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// # use s2n_quic::{application, Client};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::bind("0.0.0.0:0")?;
+    ///
+    /// let manager = client.manager();
+    ///
+    /// for connection in manager.connections() {
+    ///     manager.close(connection.id, application::Error::UNKNOWN);
+    /// }
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn manager(&self) -> connection::Manager {
+        self.manager.clone()
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port `0` to figure out which
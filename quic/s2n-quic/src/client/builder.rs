@@ -301,7 +301,7 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
-    #[cfg(feature = "unstable-provider-datagram")]
+    #[cfg(any(test, feature = "unstable-provider-datagram"))]
     impl_provider_method!(
         /// Sets the datagram provider for the [`Client`]
         with_datagram,
@@ -317,6 +317,38 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-transport-parameters"))]
+    impl_provider_method!(
+        /// Sets the custom transport parameters provider for the [`Client`]
+        with_transport_parameters,
+        transport_parameters,
+        ClientProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-custom-frame"))]
+    impl_provider_method!(
+        /// Sets the custom frame provider for the [`Client`]
+        with_custom_frame,
+        custom_frame,
+        ClientProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-demux"))]
+    impl_provider_method!(
+        /// Sets the demultiplexer provider for the [`Client`]
+        with_demux,
+        demux,
+        ClientProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-token-cache"))]
+    impl_provider_method!(
+        /// Sets the token cache provider for the [`Client`]
+        with_token_cache,
+        token_cache,
+        ClientProviders
+    );
+
     impl_provider_method!(
         /// Sets the congestion controller provider for the [`Client`]
         with_congestion_controller,
@@ -23,6 +23,10 @@ impl_providers_state! {
         tls: Tls,
         datagram: Datagram,
         dc: Dc,
+        transport_parameters: TransportParameters,
+        custom_frame: CustomFrame,
+        demux: Demux,
+        token_cache: TokenCache,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -44,6 +48,10 @@ impl<
         Tls: tls::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        TransportParameters: transport_parameters::Provider,
+        CustomFrame: custom_frame::Provider,
+        Demux: demux::Provider,
+        TokenCache: token_cache::Provider,
     >
     Providers<
         CongestionController,
@@ -60,6 +68,10 @@ impl<
         Tls,
         Datagram,
         Dc,
+        TransportParameters,
+        CustomFrame,
+        Demux,
+        TokenCache,
     >
 {
     pub fn start(self) -> Result<Client, StartError> {
@@ -78,6 +90,10 @@ impl<
             tls,
             datagram,
             dc,
+            transport_parameters,
+            custom_frame,
+            demux,
+            token_cache,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -92,12 +108,18 @@ impl<
         let limits = limits.start().map_err(StartError::new)?;
         let mtu = mtu.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
+        // Maintain the counters for `Connection::stats` alongside the application's subscriber
+        let event = (event, connection::statistics::Subscriber);
         let token = Token;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = PathMigration;
         let tls = tls.start_client().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let transport_parameters = transport_parameters.start().map_err(StartError::new)?;
+        let custom_frame = custom_frame.start().map_err(StartError::new)?;
+        let demux = demux.start().map_err(StartError::new)?;
+        let token_cache = token_cache.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -130,15 +152,21 @@ impl<
             path_migration,
             datagram,
             dc,
+            transport_parameters,
+            custom_frame,
+            demux,
+            token_cache,
         };
 
         let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
+        let manager = endpoint.manager();
 
         // Start the IO last
         let local_addr = io.start(endpoint).map_err(StartError::new)?;
 
         Ok(Client {
             connector,
+            manager,
             local_addr,
         })
     }
@@ -217,6 +245,10 @@ struct EndpointConfig<
     Tls,
     Datagram,
     Dc,
+    TransportParameters,
+    CustomFrame,
+    Demux,
+    TokenCache,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -235,6 +267,10 @@ struct EndpointConfig<
     path_migration: PathMigration,
     datagram: Datagram,
     dc: Dc,
+    transport_parameters: TransportParameters,
+    custom_frame: CustomFrame,
+    demux: Demux,
+    token_cache: TokenCache,
 }
 
 impl<
@@ -252,6 +288,10 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        TransportParameters: s2n_quic_core::transport::parameters::custom::Endpoint,
+        CustomFrame: s2n_quic_core::custom_frame::Endpoint,
+        Demux: s2n_quic_core::demux::Demultiplexer,
+        TokenCache: s2n_quic_core::token::cache::Cache,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -268,6 +308,10 @@ impl<
         Tls,
         Datagram,
         Dc,
+        TransportParameters,
+        CustomFrame,
+        Demux,
+        TokenCache,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -290,6 +334,10 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        TransportParameters: s2n_quic_core::transport::parameters::custom::Endpoint,
+        CustomFrame: s2n_quic_core::custom_frame::Endpoint,
+        Demux: s2n_quic_core::demux::Demultiplexer,
+        TokenCache: s2n_quic_core::token::cache::Cache,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -306,6 +354,10 @@ impl<
         Tls,
         Datagram,
         Dc,
+        TransportParameters,
+        CustomFrame,
+        Demux,
+        TokenCache,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -328,6 +380,10 @@ impl<
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type CustomTransportParameters = TransportParameters;
+    type CustomFrameEndpoint = CustomFrame;
+    type Demultiplexer = Demux;
+    type TokenCache = TokenCache;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Client;
 
@@ -348,6 +404,10 @@ impl<
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            custom_transport_parameters: &mut self.transport_parameters,
+            custom_frame: &mut self.custom_frame,
+            demux: &mut self.demux,
+            token_cache: &mut self.token_cache,
         }
     }
 }
@@ -6,12 +6,23 @@ use s2n_quic_transport::connection::Connection as Inner;
 
 #[macro_use]
 mod acceptor;
+mod connecting;
 #[macro_use]
 mod handle;
+pub mod request;
 
 pub use acceptor::*;
+pub use connecting::Connecting;
 pub use handle::*;
-pub use s2n_quic_core::connection::Error;
+pub use request::{Request, Response};
+pub use s2n_quic_core::connection::{
+    close::DropBehavior, Error, FlushMode, PathInfo, PathStatistics, PathValidation, PriorityClass,
+    Statistics,
+};
+pub use s2n_quic_transport::endpoint::manager::{Manager, Summary};
+
+#[cfg(any(test, feature = "unstable-connection-handoff"))]
+pub use s2n_quic_core::connection::handoff;
 
 pub mod error {
     pub use s2n_quic_core::transport::error::Code;
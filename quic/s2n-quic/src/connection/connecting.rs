@@ -0,0 +1,106 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Connection, Error, Result};
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use s2n_quic_transport::connection::Connection as Inner;
+
+/// A connection which was accepted by the server before completing its handshake
+///
+/// The peer's address, server name and application protocol are available immediately, which
+/// allows the application to prepare for the connection concurrently with the rest of the
+/// handshake. The `Connecting` handle resolves to the [`Connection`] once the handshake
+/// completes, or once the 1-RTT keys are available if
+/// [0.5-RTT data](crate::provider::limits::Limits::with_half_rtt_data) is enabled.
+///
+/// Connections are only accepted early if
+/// [`Limits::with_early_accept`](crate::provider::limits::Limits::with_early_accept) is
+/// enabled. Dropping the handle before it resolves closes the connection.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Connecting(Option<Inner>);
+
+impl fmt::Debug for Connecting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Connecting")
+            .field("id", &self.0.as_ref().map(|inner| inner.id()))
+            .finish()
+    }
+}
+
+impl Connecting {
+    #[inline]
+    pub(crate) fn new(inner: Inner) -> Self {
+        Self(Some(inner))
+    }
+
+    #[inline]
+    fn inner(&self) -> Result<&Inner> {
+        self.0.as_ref().ok_or_else(Error::unspecified)
+    }
+
+    /// Returns the endpoint-unique identifier of the connection
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.0.as_ref().map_or(0, |inner| inner.id())
+    }
+
+    /// Returns the local address that this connection is bound to.
+    #[inline]
+    pub fn local_addr(&self) -> Result<std::net::SocketAddr> {
+        self.inner()?
+            .local_address()
+            .map(std::net::SocketAddr::from)
+    }
+
+    /// Returns the remote address that this connection is connected to.
+    #[inline]
+    pub fn remote_addr(&self) -> Result<std::net::SocketAddr> {
+        self.inner()?
+            .remote_address()
+            .map(std::net::SocketAddr::from)
+    }
+
+    /// Returns the server name the client requested, if any
+    #[inline]
+    pub fn server_name(&self) -> Result<Option<crate::server::Name>> {
+        self.inner()?.server_name()
+    }
+
+    /// Returns the application protocol negotiated with the client
+    #[inline]
+    pub fn application_protocol(&self) -> Result<bytes::Bytes> {
+        self.inner()?.application_protocol()
+    }
+
+    /// Polls for the connection to be usable
+    ///
+    /// # Return value
+    ///
+    /// This function returns:
+    ///
+    /// - `Poll::Pending` if the handshake is still in progress.
+    /// - `Poll::Ready(Ok(connection))` once the handshake has completed.
+    /// - `Poll::Ready(Err(error))` if the connection was closed before completing the handshake.
+    ///
+    /// Once `Poll::Ready` is returned, this function should not be called again.
+    pub fn poll_connected(&mut self, cx: &mut Context) -> Poll<Result<Connection>> {
+        let inner = self.inner()?;
+        core::task::ready!(inner.poll_established(cx))?;
+        let inner = self.0.take().expect("inner connection was checked above");
+        Poll::Ready(Ok(Connection::new(inner)))
+    }
+}
+
+impl Future for Connecting {
+    type Output = Result<Connection>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_connected(cx)
+    }
+}
@@ -9,6 +9,10 @@ macro_rules! impl_handle_api {
         ///  - `Ok(stream)` if a stream of the requested type was opened
         ///  - `Err(stream_error)` if the stream could not be opened due to an error
         ///
+        /// Streams of each type are assigned monotonically increasing IDs in the order in which
+        /// they are opened. Concurrent calls which are blocked on the peer's stream limit are
+        /// assigned IDs in the order in which they complete.
+        ///
         /// # Examples
         ///
         /// ```rust,no_run
@@ -59,6 +63,90 @@ macro_rules! impl_handle_api {
             })
         }
 
+        /// Opens a new [`LocalStream`](`crate::stream::LocalStream`) with a specific type, only
+        /// if it's assigned the provided stream ID
+        ///
+        /// This can be used by protocols which identify streams by their IDs, such as the
+        /// control streams in HTTP/3, to ensure the stream is assigned the expected ID even
+        /// when other tasks open streams on the same connection.
+        ///
+        /// The method will return
+        ///  - `Ok(stream)` if a stream of the requested type was opened with the provided ID
+        ///  - `Err(Error::InvalidConfiguration)` if the next stream of the requested type would be
+        ///    assigned a different ID. No stream is opened in this case.
+        ///  - `Err(stream_error)` if the stream could not be opened due to an error
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   use s2n_quic::stream;
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// // the first client-initiated unidirectional stream is assigned ID 2
+        /// let control = handle
+        ///     .open_stream_with_id_hint(stream::Type::Unidirectional, 2)
+        ///     .await?;
+        /// assert_eq!(control.id(), 2);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn open_stream_with_id_hint(
+            &mut self,
+            stream_type: $crate::stream::Type,
+            id: u64,
+        ) -> $crate::connection::Result<$crate::stream::LocalStream> {
+            futures::future::poll_fn(|cx| self.poll_open_stream_with_id_hint(stream_type, id, cx))
+                .await
+        }
+
+        /// Polls opening a [`LocalStream`](`crate::stream::LocalStream`) with a specific type,
+        /// only if it's assigned the provided stream ID
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(stream))` if a stream of the requested type was opened with the
+        ///   provided ID
+        /// - `Poll::Ready(Err(stream_error))` if the stream could not be opened due to an error
+        ///   or the next stream of the requested type would be assigned a different ID
+        /// - `Poll::Pending` if the stream has not been opened yet
+        #[inline]
+        pub fn poll_open_stream_with_id_hint(
+            &mut self,
+            stream_type: $crate::stream::Type,
+            id: u64,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<$crate::stream::LocalStream>> {
+            s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                use s2n_quic_core::{
+                    stream::{StreamId, StreamType},
+                    varint::VarInt,
+                };
+                use $crate::stream::{BidirectionalStream, SendStream};
+
+                let id = VarInt::new(id).map(StreamId::from_varint).map_err(|_| {
+                    $crate::connection::Error::invalid_configuration(
+                        "the stream ID hint is not a valid stream ID",
+                    )
+                })?;
+
+                Ok(
+                    match core::task::ready!(self.0.poll_open_stream_with_id_hint(
+                        stream_type,
+                        id,
+                        cx
+                    ))? {
+                        stream if stream_type == StreamType::Unidirectional => {
+                            SendStream::new(stream.into()).into()
+                        }
+                        stream => BidirectionalStream::new(stream).into(),
+                    },
+                )
+                .into()
+            })
+        }
+
         /// Opens a new [`BidirectionalStream`](`crate::stream::BidirectionalStream`)
         ///
         /// The method will return
@@ -107,6 +195,31 @@ macro_rules! impl_handle_api {
             })
         }
 
+        /// Sends a request on a new [`BidirectionalStream`](`crate::stream::BidirectionalStream`)
+        /// and waits for the response
+        ///
+        /// The payload is written and the stream is finished. The returned
+        /// [`Request`](`crate::connection::Request`) then resolves once the peer has finished its
+        /// side of the stream. See [`Request`](`crate::connection::Request`) for configuring the
+        /// response size limit and timeout.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> Result<(), s2n_quic::connection::request::Error> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let response = handle.request(bytes::Bytes::from_static(b"ping")).await?;
+        /// println!("response: {:?}", response.body());
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn request(&self, payload: bytes::Bytes) -> $crate::connection::Request {
+            $crate::connection::Request::new($crate::connection::Handle(self.0.clone()), payload)
+        }
+
         /// Opens a [`SendStream`](`crate::stream::SendStream`)
         ///
         /// # Examples
@@ -157,6 +270,150 @@ macro_rules! impl_handle_api {
             self.0.remote_address().map(std::net::SocketAddr::from)
         }
 
+        /// Returns the largest UDP payload size currently used on the active path
+        ///
+        /// This value reflects the path MTU which has been validated so far, minus the
+        /// size of the UDP and IP headers. It will never exceed the limit configured with
+        /// [`Limits::with_max_udp_payload_size`](crate::provider::limits::Limits::with_max_udp_payload_size).
+        #[inline]
+        pub fn max_datagram_size(&self) -> $crate::connection::Result<usize> {
+            self.0.max_datagram_size()
+        }
+
+        /// Returns the largest DATAGRAM frame payload which can currently be sent to the peer
+        ///
+        /// This accounts for the path MTU, the overhead of a 1-RTT packet and the peer's
+        /// `max_datagram_frame_size` transport parameter. The value changes as the path MTU is
+        /// probed, so applications which size their datagrams to fit should use
+        /// [`Self::max_datagram_payload_changed`] to be notified of updates. A value of `0`
+        /// indicates that datagrams can't currently be sent.
+        #[inline]
+        pub fn max_datagram_payload(&self) -> $crate::connection::Result<usize> {
+            self.0.max_datagram_payload()
+        }
+
+        /// Waits for the largest DATAGRAM frame payload to change from the `previous` value
+        ///
+        /// The new value is returned once it differs from `previous`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let mut max_payload = handle.max_datagram_payload()?;
+        /// loop {
+        ///     println!("frames can be up to {max_payload} bytes");
+        ///     max_payload = handle.max_datagram_payload_changed(max_payload).await?;
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn max_datagram_payload_changed(
+            &mut self,
+            previous: usize,
+        ) -> $crate::connection::Result<usize> {
+            futures::future::poll_fn(|cx| self.poll_max_datagram_payload(previous, cx)).await
+        }
+
+        /// Polls for the largest DATAGRAM frame payload to change from the `previous` value
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(value))` if the value differs from `previous`
+        /// - `Poll::Ready(Err(connection_error))` if the connection was closed with an error
+        /// - `Poll::Pending` if the value hasn't changed yet
+        ///
+        /// Only the most recent task to poll for a change is notified.
+        #[inline]
+        pub fn poll_max_datagram_payload(
+            &mut self,
+            previous: usize,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<usize>> {
+            s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                self.0.poll_max_datagram_payload(previous, cx)
+            })
+        }
+
+        /// Returns a snapshot of the connection's transport statistics
+        ///
+        /// This includes the round-trip time and congestion window of the active path, as well as
+        /// the packet and byte counters for the lifetime of the connection. The statistics are
+        /// maintained regardless of the configured event subscriber and can still be queried after
+        /// the connection has closed.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let stats = handle.stats()?;
+        /// println!("rtt={:?} lost={}", stats.smoothed_rtt, stats.packets_lost);
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn stats(&self) -> $crate::connection::Result<$crate::connection::Statistics> {
+            self.0.statistics()
+        }
+
+        /// Returns the round-trip time and bandwidth estimate of the active path
+        ///
+        /// This is a lighter-weight alternative to [`Self::stats`] which is suitable for frequent
+        /// polling, e.g. by applications adapting their encoding rate to the network conditions.
+        /// The bandwidth estimate is only available with congestion controllers that model the
+        /// path bandwidth, such as BBR.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let info = handle.path_info()?;
+        /// if let Some(bandwidth) = info.bandwidth_estimate {
+        ///     println!("rtt={:?} bandwidth={bandwidth}B/s", info.smoothed_rtt);
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn path_info(&self) -> $crate::connection::Result<$crate::connection::PathInfo> {
+            self.0.path_info()
+        }
+
+        /// Returns a snapshot of the statistics of each network path of the connection
+        ///
+        /// This includes the round-trip time, loss and validation state of each path, which can
+        /// be used to decide which path the application should prefer, for example after a
+        /// migration. The same snapshots can be emitted periodically in the `PathStatistics`
+        /// event by configuring `Limits::with_path_statistics_interval`.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// for path in handle.path_stats()? {
+        ///     println!(
+        ///         "path={} rtt={:?} loss={:.2}",
+        ///         path.remote_address,
+        ///         path.smoothed_rtt,
+        ///         path.loss_rate()
+        ///     );
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn path_stats(
+            &self,
+        ) -> $crate::connection::Result<Vec<$crate::connection::PathStatistics>> {
+            self.0.path_statistics()
+        }
+
         /// Returns the negotiated server name the connection is using.
         #[inline]
         pub fn server_name(&self) -> $crate::connection::Result<Option<$crate::server::Name>> {
@@ -169,6 +426,89 @@ macro_rules! impl_handle_api {
             self.0.application_protocol()
         }
 
+        /// Returns the value of a custom transport parameter sent by the peer
+        ///
+        /// Only parameters that are unknown to the transport are available, and only if
+        /// the endpoint was configured with a transport parameters provider.
+        #[inline]
+        pub fn peer_transport_parameter(
+            &self,
+            id: u64,
+        ) -> $crate::connection::Result<Option<::bytes::Bytes>> {
+            self.0.peer_transport_parameter(id)
+        }
+
+        /// Returns the application settings sent by the peer for the negotiated application
+        /// protocol
+        ///
+        /// Settings are only available if the endpoint was configured with a transport
+        /// parameters provider and the peer sent settings for the negotiated protocol.
+        #[inline]
+        pub fn peer_application_settings(
+            &self,
+        ) -> $crate::connection::Result<Option<::bytes::Bytes>> {
+            use s2n_quic_core::transport::parameters::custom;
+
+            let protocol = self.0.application_protocol()?;
+            let settings = self
+                .0
+                .peer_transport_parameter(custom::APPLICATION_SETTINGS_ID.as_u64())?
+                .and_then(|value| {
+                    // malformed settings are rejected when the parameters are received
+                    custom::decode_application_settings(&value, &protocol)
+                        .ok()
+                        .flatten()
+                });
+            Ok(settings)
+        }
+
+        /// Exports `len` bytes of keying material bound to the TLS session of the connection
+        ///
+        /// The `label` and `context` are passed to the TLS exporter defined in
+        /// [RFC 8446](https://www.rfc-editor.org/rfc/rfc8446#section-7.5), so both peers derive
+        /// the same value when called with the same arguments. Keying material can only be
+        /// exported once the handshake has completed, and only if the connection was configured
+        /// with [`Limits::with_keying_material_export`](crate::provider::limits::Limits::with_keying_material_export).
+        ///
+        /// At most `255 * 48` bytes can be exported, which is the limit of the TLS exporter with
+        /// the largest supported hash.
+        #[inline]
+        pub fn export_keying_material(
+            &self,
+            label: &[u8],
+            context: &[u8],
+            len: usize,
+        ) -> $crate::connection::Result<::std::vec::Vec<u8>> {
+            if len > s2n_quic_core::crypto::tls::MAX_EXPORTED_KEYING_MATERIAL_LEN {
+                return Err($crate::connection::Error::keying_material_export(
+                    "the requested length exceeds the maximum exporter output",
+                ));
+            }
+
+            let mut output = ::std::vec![0; len];
+            self.0.export_keying_material(label, context, &mut output)?;
+            Ok(output)
+        }
+
+        /// Hands off the connection to another server endpoint
+        ///
+        /// Returns the state needed to resume the connection with
+        /// [`Server::import`](crate::Server::import), after which this endpoint forgets about
+        /// the connection without notifying the peer. The server must have been configured with
+        /// [`Limits::with_connection_handoff`](crate::provider::limits::Limits::with_connection_handoff)
+        /// so the 1-RTT keys are kept out of this endpoint, and the handoff is only possible
+        /// once the handshake has completed.
+        ///
+        /// The state contains the traffic secrets of the connection, so it must be protected
+        /// while it's transferred to the other endpoint. The other endpoint must be configured
+        /// with the same limits and connection ID format, and the application is responsible
+        /// for routing the connection's datagrams to it from then on.
+        #[cfg(any(test, feature = "unstable-connection-handoff"))]
+        #[inline]
+        pub fn handoff(&self) -> $crate::connection::Result<$crate::connection::handoff::State> {
+            self.0.export_handoff()
+        }
+
         /// Returns the internal identifier for the [`Connection`](`crate::Connection`)
         ///
         /// Note: This internal identifier is not the same as the connection ID included in packet
@@ -184,6 +524,118 @@ macro_rules! impl_handle_api {
             self.0.ping()
         }
 
+        /// Sends a Ping frame to the peer and waits for it to be acknowledged
+        ///
+        /// Returns the RTT sample measured from the acknowledgement, which can be used to probe
+        /// the liveness and latency of the peer without opening a stream.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let rtt = handle.ping_rtt().await?;
+        /// println!("peer responded in {rtt:?}");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn ping_rtt(&mut self) -> $crate::connection::Result<core::time::Duration> {
+            futures::future::poll_fn(|cx| self.poll_ping_rtt(cx)).await
+        }
+
+        /// Polls for a Ping frame to be acknowledged by the peer
+        ///
+        /// A Ping frame is sent if one isn't already outstanding. The method will return
+        /// - `Poll::Ready(Ok(rtt))` once the peer acknowledged the Ping with the measured RTT sample
+        /// - `Poll::Ready(Err(connection_error))` if the connection was closed with an error
+        /// - `Poll::Pending` if the Ping has not been acknowledged yet
+        ///
+        /// Only the most recent task to poll the Ping is notified.
+        #[inline]
+        pub fn poll_ping_rtt(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<core::time::Duration>> {
+            s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| self.0.poll_ping(cx))
+        }
+
+        /// Waits for all of the currently buffered stream and datagram data to be transmitted
+        ///
+        /// The method returns once the data has been written into packets and handed off to
+        /// the endpoint's IO provider. Use [`Self::flush_acknowledged`] to also wait for the
+        /// peer to acknowledge the stream data.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// handle.flush().await?;
+        /// println!("response sent");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn flush(&mut self) -> $crate::connection::Result<()> {
+            let mode = $crate::connection::FlushMode::Transmitted;
+            futures::future::poll_fn(|cx| self.poll_flush(mode, cx)).await
+        }
+
+        /// Waits for all of the currently buffered stream data to be acknowledged by the peer
+        ///
+        /// Datagrams are not acknowledged, so they are only required to be transmitted.
+        #[inline]
+        pub async fn flush_acknowledged(&mut self) -> $crate::connection::Result<()> {
+            let mode = $crate::connection::FlushMode::Acknowledged;
+            futures::future::poll_fn(|cx| self.poll_flush(mode, cx)).await
+        }
+
+        /// Waits for the connection to become idle
+        ///
+        /// The method returns once all of the currently buffered stream data has been
+        /// acknowledged by the peer and no ack-eliciting packets remain in flight. This can be
+        /// used before dropping or closing the connection to ensure nothing is left outstanding.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// handle.wait_idle().await?;
+        /// handle.close(0u8.into());
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn wait_idle(&mut self) -> $crate::connection::Result<()> {
+            let mode = $crate::connection::FlushMode::Idle;
+            futures::future::poll_fn(|cx| self.poll_flush(mode, cx)).await
+        }
+
+        /// Polls for all of the currently buffered stream and datagram data to be flushed
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(()))` if the data was flushed, as determined by the `mode`
+        /// - `Poll::Ready(Err(connection_error))` if the connection was closed with an error
+        /// - `Poll::Pending` if the data has not been flushed yet
+        ///
+        /// Only the most recent task to poll the flush is notified.
+        #[inline]
+        pub fn poll_flush(
+            &mut self,
+            mode: $crate::connection::FlushMode,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<()>> {
+            s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| self.0.poll_flush(mode, cx))
+        }
+
         /// Enables or disables the connection to actively keep the connection alive with the peer
         ///
         /// This can be useful for maintaining connections beyond the configured idle timeout. The
@@ -194,6 +646,165 @@ macro_rules! impl_handle_api {
             self.0.keep_alive(enabled)
         }
 
+        /// Attaches an event subscriber to the connection
+        ///
+        /// The subscriber receives the events of this connection in addition to the subscriber
+        /// configured on the endpoint, which makes it possible to enable verbose debugging for a
+        /// single peer. Attaching a subscriber replaces any previously attached one. Events
+        /// emitted before the subscriber was attached are not replayed; use
+        /// [`Connect::with_event_subscriber`](crate::client::Connect::with_event_subscriber) to
+        /// observe the handshake of client connections.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// use s2n_quic::provider::event::{events, ConnectionInfo, ConnectionMeta, Subscriber};
+        ///
+        /// struct LossLogger;
+        ///
+        /// impl Subscriber for LossLogger {
+        ///     type ConnectionContext = ();
+        ///
+        ///     fn create_connection_context(&mut self, _: &ConnectionMeta, _: &ConnectionInfo) {}
+        ///
+        ///     fn on_packet_lost(&mut self, _: &mut (), meta: &ConnectionMeta, event: &events::PacketLost) {
+        ///         println!("{meta:?} {event:?}");
+        ///     }
+        /// }
+        ///
+        /// handle.attach_event_subscriber(LossLogger)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn attach_event_subscriber<Sub: $crate::provider::event::Subscriber>(
+            &mut self,
+            subscriber: Sub,
+        ) -> $crate::connection::Result<()> {
+            self.0.attach_event_subscriber(subscriber)
+        }
+
+        /// Sets what happens to the connection once the last handle is dropped
+        ///
+        /// This overrides the behavior configured on the endpoint with
+        /// [`Limits::with_drop_behavior`](crate::provider::limits::Limits::with_drop_behavior).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// use s2n_quic::connection::DropBehavior;
+        ///
+        /// const MY_ERROR_CODE: u32 = 99;
+        /// connection.set_drop_behavior(DropBehavior::Close(MY_ERROR_CODE.into()))?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_drop_behavior(
+            &mut self,
+            behavior: $crate::connection::DropBehavior,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_drop_behavior(behavior)
+        }
+
+        /// Sets the class the connection is scheduled in when competing with other connections
+        /// on the endpoint
+        ///
+        /// This overrides the class configured on the endpoint with
+        /// [`Limits::with_priority_class`](crate::provider::limits::Limits::with_priority_class).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// use s2n_quic::connection::PriorityClass;
+        ///
+        /// connection.set_priority_class(PriorityClass::Bulk)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_priority_class(
+            &mut self,
+            class: $crate::connection::PriorityClass,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_priority_class(class)
+        }
+
+        /// Returns the effective idle timeout of the connection
+        ///
+        /// This is the lower of the values advertised by each endpoint, or the value set with
+        /// [`Self::set_idle_timeout`]. `None` is returned if neither endpoint enabled the idle
+        /// timeout.
+        #[inline]
+        pub fn idle_timeout(&self) -> $crate::connection::Result<Option<core::time::Duration>> {
+            self.0.idle_timeout()
+        }
+
+        /// Lowers the idle timeout of the connection
+        ///
+        /// This is useful for servers which want to use a shorter idle timeout for some
+        /// connections, e.g. immediately after accepting them. The timeout can't be raised above
+        /// the value negotiated with the peer. Since the peer expects the negotiated value, the
+        /// peer is notified with a `CONNECTION_CLOSE` frame if the lowered timeout expires.
+        ///
+        /// A `timeout` of zero restores the negotiated value.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Connection = todo!();
+        /// #
+        /// connection.set_idle_timeout(core::time::Duration::from_secs(5))?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_idle_timeout(
+            &mut self,
+            timeout: core::time::Duration,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_idle_timeout(timeout)
+        }
+
+        /// Closes the connection with the provided error code if no stream data is sent or
+        /// received within `timeout`
+        ///
+        /// Unlike the idle timeout, the inactivity timer isn't restarted by packets which don't
+        /// carry stream data, such as those sent while [`Self::keep_alive`] is enabled.
+        ///
+        /// A `timeout` of zero disables the timer.
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Connection = todo!();
+        /// #
+        /// const MY_ERROR_CODE: u32 = 99;
+        /// connection.set_inactivity_timeout(
+        ///     core::time::Duration::from_secs(60),
+        ///     MY_ERROR_CODE.into(),
+        /// )?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_inactivity_timeout(
+            &mut self,
+            timeout: core::time::Duration,
+            error_code: $crate::application::Error,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_inactivity_timeout(timeout, error_code)
+        }
+
         /// Closes the Connection with the provided error code
         ///
         /// This will immediately terminate all outstanding streams.
@@ -215,6 +826,59 @@ macro_rules! impl_handle_api {
             self.0.close(error_code)
         }
 
+        /// Closes the Connection with the provided error code, retaining the closing state for
+        /// the provided duration
+        ///
+        /// By default, a closed connection remains in the closing state for three times the
+        /// probe timeout (PTO), responding to any packets from the peer with a CONNECTION_CLOSE
+        /// frame. This method overrides that period, which can be used to keep the connection
+        /// state around for longer on lossy networks or release it sooner on busy endpoints.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// const MY_ERROR_CODE:u32 = 99;
+        /// connection.close_with_linger(MY_ERROR_CODE.into(), core::time::Duration::from_secs(1));
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn close_with_linger(
+            &self,
+            error_code: $crate::application::Error,
+            linger: core::time::Duration,
+        ) {
+            self.0.close_with_linger(error_code, linger)
+        }
+
+        /// Aborts the Connection with the provided error code
+        ///
+        /// Unlike [`Self::close`], the connection skips the closing state entirely: no
+        /// CONNECTION_CLOSE frame is sent and the connection state is dropped immediately. The
+        /// peer only learns about the closure through a stateless reset or its idle timeout, so
+        /// this should be reserved for tests and forcefully evicting connections.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// const MY_ERROR_CODE:u32 = 99;
+        /// connection.abort(MY_ERROR_CODE.into());
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn abort(&self, error_code: $crate::application::Error) {
+            self.0.abort(error_code)
+        }
+
         /// API for querying the connection's
         /// [`Subscriber::ConnectionContext`](crate::provider::event::Subscriber::ConnectionContext).
         ///
@@ -0,0 +1,277 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{application, connection::Handle, stream::BidirectionalStream};
+use bytes::{Bytes, BytesMut};
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+/// The default limit on the size of a response
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+
+/// A request which is sent on its own [`BidirectionalStream`]
+///
+/// The request opens a stream, writes the payload and finishes the stream. It then reads the
+/// response until the peer finishes its side of the stream. The response is read while the
+/// payload is being written so peers which respond before consuming the entire request can't
+/// block the exchange on flow control.
+///
+/// If the request fails, both halves of the stream are closed with the configured
+/// [error code](Self::with_error_code).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn test() -> Result<(), s2n_quic::connection::request::Error> {
+/// #   let connection: s2n_quic::connection::Connection = todo!();
+/// #
+/// let response = connection
+///     .request(bytes::Bytes::from_static(b"ping"))
+///     .with_max_response_size(1024)
+///     .with_timeout(tokio::time::sleep(core::time::Duration::from_secs(1)))
+///     .await?;
+///
+/// println!("response: {:?}", response.body());
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[must_use = "requests do nothing unless `.await`ed"]
+pub struct Request {
+    handle: Handle,
+    stream: Option<BidirectionalStream>,
+    payload: Bytes,
+    is_finished: bool,
+    response: Vec<Bytes>,
+    response_len: usize,
+    max_response_size: usize,
+    timeout: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    error_code: application::Error,
+}
+
+impl fmt::Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("stream", &self.stream)
+            .field("payload_len", &self.payload.len())
+            .field("is_finished", &self.is_finished)
+            .field("response_len", &self.response_len)
+            .field("max_response_size", &self.max_response_size)
+            .field("has_timeout", &self.timeout.is_some())
+            .field("error_code", &self.error_code)
+            .finish()
+    }
+}
+
+impl Request {
+    #[inline]
+    pub(crate) fn new(handle: Handle, payload: Bytes) -> Self {
+        Self {
+            handle,
+            stream: None,
+            payload,
+            is_finished: false,
+            response: vec![],
+            response_len: 0,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            timeout: None,
+            error_code: application::Error::UNKNOWN,
+        }
+    }
+
+    /// Sets the maximum number of bytes accepted in the response
+    ///
+    /// The request fails with [`Error::ResponseTooLarge`] if the peer sends more than `limit`
+    /// bytes. Defaults to [`DEFAULT_MAX_RESPONSE_SIZE`].
+    #[inline]
+    pub fn with_max_response_size(mut self, limit: usize) -> Self {
+        self.max_response_size = limit;
+        self
+    }
+
+    /// Fails the request with [`Error::TimedOut`] once the `timeout` future completes
+    ///
+    /// Any timer can be used, which allows the request to be driven by the same runtime as the
+    /// endpoint (e.g. `tokio::time::sleep`).
+    #[inline]
+    pub fn with_timeout<F>(mut self, timeout: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.timeout = Some(Box::pin(timeout));
+        self
+    }
+
+    /// Sets the error code used to close the stream when the request fails
+    ///
+    /// Defaults to [`application::Error::UNKNOWN`].
+    #[inline]
+    pub fn with_error_code(mut self, error_code: application::Error) -> Self {
+        self.error_code = error_code;
+        self
+    }
+
+    fn poll_request(&mut self, cx: &mut Context) -> Poll<Result<Response, Error>> {
+        if let Some(timeout) = self.timeout.as_mut() {
+            if timeout.as_mut().poll(cx).is_ready() {
+                return Err(Error::TimedOut).into();
+            }
+        }
+
+        let stream = match self.stream.as_mut() {
+            Some(stream) => stream,
+            None => {
+                let stream = ready!(self.handle.poll_open_bidirectional_stream(cx))?;
+                self.stream.insert(stream)
+            }
+        };
+
+        // read the response before writing the request in case the peer already finished it
+        loop {
+            match stream.poll_receive(cx)? {
+                Poll::Ready(Some(chunk)) => {
+                    self.response_len += chunk.len();
+                    if self.response_len > self.max_response_size {
+                        return Err(Error::ResponseTooLarge {
+                            limit: self.max_response_size,
+                        })
+                        .into();
+                    }
+                    self.response.push(chunk);
+                }
+                Poll::Ready(None) => {
+                    // the peer responded without reading the entire request so make sure it
+                    // isn't delivered as if it was complete
+                    if !self.is_finished {
+                        let _ = stream.reset(self.error_code);
+                    }
+
+                    return Ok(Response {
+                        id: stream.id(),
+                        body: self.take_body(),
+                    })
+                    .into();
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if !self.is_finished {
+            if !self.payload.is_empty() && stream.poll_send(&mut self.payload, cx)?.is_pending() {
+                return Poll::Pending;
+            }
+
+            stream.finish()?;
+            self.is_finished = true;
+        }
+
+        Poll::Pending
+    }
+
+    fn take_body(&mut self) -> Bytes {
+        match self.response.len() {
+            0 => Bytes::new(),
+            1 => self.response.pop().unwrap(),
+            _ => {
+                let mut body = BytesMut::with_capacity(self.response_len);
+                for chunk in self.response.drain(..) {
+                    body.extend_from_slice(&chunk);
+                }
+                body.freeze()
+            }
+        }
+    }
+}
+
+impl Future for Request {
+    type Output = Result<Response, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let result = ready!(self.poll_request(cx));
+
+        if result.is_err() {
+            let error_code = self.error_code;
+            if let Some(stream) = self.stream.as_mut() {
+                let _ = stream.stop_sending(error_code);
+                let _ = stream.reset(error_code);
+            }
+        }
+
+        // the stream is closed once the exchange is complete
+        self.stream = None;
+
+        result.into()
+    }
+}
+
+/// The response to a [`Request`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    id: u64,
+    body: Bytes,
+}
+
+impl Response {
+    /// Returns the identifier of the stream which carried the request
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the body of the response
+    #[inline]
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Consumes the response and returns its body
+    #[inline]
+    pub fn into_body(self) -> Bytes {
+        self.body
+    }
+}
+
+/// Errors that a [`Request`] can encounter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The stream or connection encountered an error
+    Stream(crate::stream::Error),
+    /// The peer sent more than the configured maximum response size
+    #[non_exhaustive]
+    ResponseTooLarge { limit: usize },
+    /// The request did not complete before the timeout
+    TimedOut,
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Stream(error) => error.fmt(f),
+            Self::ResponseTooLarge { limit } => {
+                write!(f, "The response exceeded the limit of {limit} bytes")
+            }
+            Self::TimedOut => write!(f, "The request timed out"),
+        }
+    }
+}
+
+impl From<crate::stream::Error> for Error {
+    #[inline]
+    fn from(error: crate::stream::Error) -> Self {
+        Self::Stream(error)
+    }
+}
+
+impl From<crate::connection::Error> for Error {
+    #[inline]
+    fn from(error: crate::connection::Error) -> Self {
+        Self::Stream(error.into())
+    }
+}
@@ -112,7 +112,7 @@ pub mod server;
 pub mod stream;
 
 pub mod application {
-    pub use s2n_quic_core::application::Error;
+    pub use s2n_quic_core::application::{error::TryInto, error_codes, Error};
 }
 
 pub use client::Client;
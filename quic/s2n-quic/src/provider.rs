@@ -62,6 +62,42 @@ cfg_if!(
     }
 );
 
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-transport-parameters"))] {
+        pub mod transport_parameters;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod transport_parameters;
+    }
+);
+
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-custom-frame"))] {
+        pub mod custom_frame;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod custom_frame;
+    }
+);
+
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-demux"))] {
+        pub mod demux;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod demux;
+    }
+);
+
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-token-cache"))] {
+        pub mod token_cache;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod token_cache;
+    }
+);
+
 /// An error indicating a failure to start an endpoint
 pub struct StartError(Box<dyn 'static + fmt::Display + Send + Sync>);
 
@@ -6,7 +6,9 @@
 //! The default provider will randomly generate a 256 bit key. This key will be used to sign and
 //! verify tokens. The key can be rotated at a duration set by the user.
 //!
-//! The default provider does not support tokens delivered in a NEW_TOKEN frame.
+//! The default provider does not support tokens delivered in a NEW_TOKEN frame. As such, its replay
+//! protection and the `EndpointAddressTokenReused` event only cover tokens delivered in Retry
+//! packets.
 
 use core::{mem::size_of, time::Duration};
 use hash_hasher::HashHasher;
@@ -203,6 +205,7 @@ impl Format {
             .duplicate_filter
             .contains(token)
         {
+            context.on_token_reused(Source::RetryPacket);
             return None;
         }
 
@@ -689,8 +692,11 @@ mod tests {
 
         assert_eq!(format.validate_token(&mut context, &buf), Some(odcid));
 
+        assert_eq!(context.reused_token(), None);
+
         // Second attempt with the same token should fail because the token is a duplicate
         assert!(format.validate_token(&mut context, &buf).is_none());
+        assert_eq!(context.reused_token(), Some(Source::RetryPacket));
     }
 
     #[test]
@@ -16,8 +16,9 @@ cfg_if! {
     if #[cfg(feature = "unstable-congestion-controller")] {
         // Export the types needed to implement the CongestionController trait
         pub use s2n_quic_core::{
+            event::builder::CongestionPhase,
             random::Generator as RandomGenerator,
-            recovery::{congestion_controller::{CongestionController, PathInfo, Publisher}, RttEstimator},
+            recovery::{congestion_controller::{CongestionController, CongestionState, PathInfo, Publisher}, delivery_rate, RttEstimator},
             time::Timestamp,
         };
     }
@@ -26,6 +27,8 @@ cfg_if! {
 pub use s2n_quic_core::recovery::{bbr::Endpoint as Bbr, cubic::Endpoint as Cubic};
 // Build congestion controllers with application provided overrides
 pub use s2n_quic_core::recovery::{bbr::builder as bbr, cubic::builder as cubic};
+// Resume the congestion window of previous connections over the same path
+pub use s2n_quic_core::recovery::careful_resume;
 pub type Default = Cubic;
 
 impl_provider_utils!();
@@ -31,9 +31,12 @@ pub mod default {
         time::Duration,
     };
     use rand::prelude::*;
-    use s2n_quic_core::connection::{
-        self,
-        id::{ConnectionInfo, Generator, Validator},
+    use s2n_quic_core::{
+        connection::{
+            self,
+            id::{ConnectionInfo, Generator, Validator},
+        },
+        random,
     };
 
     #[derive(Debug, Default)]
@@ -51,6 +54,11 @@ pub mod default {
     /// 16 bytes should be big enough for a randomly generated Id
     const DEFAULT_LEN: usize = 16;
 
+    /// The minimum number of random bytes which follow the host Id prefix
+    ///
+    /// This ensures connection Ids remain unpredictable when a host Id is configured.
+    pub const MIN_RANDOM_LEN: usize = 8;
+
     /// Randomly generated connection Id format.
     ///
     /// By default, connection Ids of length 16 bytes are generated.
@@ -59,6 +67,7 @@ pub mod default {
         len: usize,
         lifetime: Option<Duration>,
         rotate_handshake_connection_id: bool,
+        host_id: HostId,
     }
 
     impl Default for Format {
@@ -67,10 +76,33 @@ pub mod default {
                 len: DEFAULT_LEN,
                 lifetime: None,
                 rotate_handshake_connection_id: true,
+                host_id: HostId::default(),
             }
         }
     }
 
+    /// A fixed prefix written to the beginning of every generated connection Id
+    #[derive(Clone, Copy, Debug, Default)]
+    struct HostId {
+        bytes: [u8; connection::id::MAX_LEN],
+        len: usize,
+    }
+
+    impl HostId {
+        #[inline]
+        fn as_bytes(&self) -> &[u8] {
+            &self.bytes[..self.len]
+        }
+
+        /// Writes the host Id to the beginning of `id` and returns the remaining bytes
+        #[inline]
+        fn write<'a>(&self, id: &'a mut [u8]) -> &'a mut [u8] {
+            let (prefix, remaining) = id.split_at_mut(self.len);
+            prefix.copy_from_slice(self.as_bytes());
+            remaining
+        }
+    }
+
     impl Format {
         /// Creates a builder for the format
         pub fn builder() -> Builder {
@@ -84,6 +116,7 @@ pub mod default {
         len: usize,
         lifetime: Option<Duration>,
         rotate_handshake_connection_id: bool,
+        host_id: HostId,
     }
 
     impl Default for Builder {
@@ -92,6 +125,7 @@ pub mod default {
                 len: DEFAULT_LEN,
                 lifetime: None,
                 rotate_handshake_connection_id: true,
+                host_id: HostId::default(),
             }
         }
     }
@@ -102,6 +136,9 @@ pub mod default {
             if !(connection::LocalId::MIN_LEN..=connection::id::MAX_LEN).contains(&len) {
                 return Err(connection::id::Error::InvalidLength);
             }
+            if self.host_id.len > 0 && self.host_id.len + MIN_RANDOM_LEN > len {
+                return Err(connection::id::Error::InvalidLength);
+            }
             self.len = len;
             Ok(self)
         }
@@ -129,12 +166,63 @@ pub mod default {
             Ok(self)
         }
 
+        /// Sets a host Id which prefixes every generated connection Id
+        ///
+        /// When several servers share an anycast address, a load balancer or packet forwarder in
+        /// front of them can route packets by the host Id, rather than the client's address. This
+        /// keeps connections pinned to the same host when the client's address changes, for example
+        /// after migrating to a server's preferred address or a NAT rebinding.
+        ///
+        /// The remainder of the connection Id is randomly generated and must be at least
+        /// [`MIN_RANDOM_LEN`] bytes.
+        ///
+        /// # Examples
+        ///
+        /// Each host behind an anycast address sets its own host Id along with a unicast preferred
+        /// address, which clients can migrate to once the handshake is confirmed.
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{
+        ///     provider::{connection_id, limits::Limits},
+        ///     Server,
+        /// };
+        ///
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// let connection_id = connection_id::default::Format::builder()
+        ///     .with_host_id(&[0x2a, 0x01])
+        ///     .expect("host id leaves room for random bytes")
+        ///     .build()?;
+        ///
+        /// let limits = Limits::default()
+        ///     .with_active_connection_migration(false)?
+        ///     .with_preferred_address("192.0.2.1:443".parse::<std::net::SocketAddr>()?.into())?;
+        ///
+        /// let server = Server::builder()
+        ///     .with_io("0.0.0.0:443")?
+        ///     .with_connection_id(connection_id)?
+        ///     .with_limits(limits)?
+        ///     .start()?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        pub fn with_host_id(mut self, host_id: &[u8]) -> Result<Self, connection::id::Error> {
+            if host_id.len() + MIN_RANDOM_LEN > self.len {
+                return Err(connection::id::Error::InvalidLength);
+            }
+            self.host_id.bytes[..host_id.len()].copy_from_slice(host_id);
+            self.host_id.len = host_id.len();
+            Ok(self)
+        }
+
         /// Builds the [`Format`] into a provider
         pub fn build(self) -> Result<Format, core::convert::Infallible> {
             Ok(Format {
                 len: self.len,
                 lifetime: self.lifetime,
                 rotate_handshake_connection_id: self.rotate_handshake_connection_id,
+                host_id: self.host_id,
             })
         }
     }
@@ -143,7 +231,19 @@ pub mod default {
         fn generate(&mut self, _connection_info: &ConnectionInfo) -> connection::LocalId {
             let mut id = [0u8; connection::id::MAX_LEN];
             let id = &mut id[..self.len];
-            rand::thread_rng().fill_bytes(id);
+            rand::thread_rng().fill_bytes(self.host_id.write(id));
+            (&*id).try_into().expect("length already checked")
+        }
+
+        fn generate_with_random(
+            &mut self,
+            _connection_info: &ConnectionInfo,
+            random: &mut dyn random::Generator,
+        ) -> connection::LocalId {
+            let mut id = [0u8; connection::id::MAX_LEN];
+            let id = &mut id[..self.len];
+            // connection IDs are visible on the wire
+            random.public_random_fill(self.host_id.write(id));
             (&*id).try_into().expect("length already checked")
         }
 
@@ -154,6 +254,11 @@ pub mod default {
         fn rotate_handshake_connection_id(&self) -> bool {
             self.rotate_handshake_connection_id
         }
+
+        fn generates_random_ids(&self) -> bool {
+            // the host Id prefix is fixed so the Ids aren't uniformly random
+            self.host_id.len == 0
+        }
     }
 
     impl Validator for Format {
@@ -246,5 +351,72 @@ pub mod default {
                 .unwrap();
             assert!(!format.rotate_handshake_connection_id());
         }
+
+        #[test]
+        fn generator_with_random_test() {
+            let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+            let connection_info = ConnectionInfo::new(remote_address);
+            let mut format = Format::default();
+
+            let mut random = random::testing::Generator(123);
+            let id_1 = format.generate_with_random(&connection_info, &mut random);
+
+            let mut random = random::testing::Generator(123);
+            let id_2 = format.generate_with_random(&connection_info, &mut random);
+
+            // the same random state produces the same connection ID
+            assert_eq!(id_1, id_2);
+            assert_eq!(format.validate(&connection_info, id_1.as_ref()), Some(16));
+        }
+
+        #[test]
+        fn host_id_test() {
+            let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+            let connection_info = ConnectionInfo::new(remote_address);
+            let host_id = [1, 2, 3, 4];
+
+            let mut format = Format::builder()
+                .with_host_id(&host_id)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert!(!format.generates_random_ids());
+
+            let id_1 = format.generate(&connection_info);
+            let mut random = random::testing::Generator(123);
+            let id_2 = format.generate_with_random(&connection_info, &mut random);
+
+            for id in [id_1, id_2] {
+                assert_eq!(id.len(), DEFAULT_LEN);
+                assert_eq!(&id.as_bytes()[..host_id.len()], &host_id);
+                assert_eq!(format.validate(&connection_info, id.as_ref()), Some(16));
+            }
+            assert_ne!(id_1, id_2);
+
+            // the host Id must leave room for the random bytes
+            assert_eq!(
+                Some(connection::id::Error::InvalidLength),
+                Format::builder()
+                    .with_len(12)
+                    .unwrap()
+                    .with_host_id(&[0; 5])
+                    .err()
+            );
+            assert_eq!(
+                Some(connection::id::Error::InvalidLength),
+                Format::builder()
+                    .with_host_id(&[0; 8])
+                    .unwrap()
+                    .with_len(15)
+                    .err()
+            );
+            assert!(Format::builder()
+                .with_len(12)
+                .unwrap()
+                .with_host_id(&[0; 4])
+                .is_ok());
+
+            assert!(Format::default().generates_random_ids());
+        }
     }
 }
@@ -0,0 +1,45 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides support for application-defined extension frames
+//!
+//! Frame types that are not known to the transport are passed to the [`Handler`] for the
+//! connection, along with the path and packet number they were received in. The handler
+//! can also write its own frames into outgoing 1-RTT packets.
+//!
+//! The frame types should be negotiated with the peer before use, for example with a
+//! custom [transport parameter](crate::provider::transport_parameters). Custom frames are
+//! ack-eliciting but are not retransmitted if the packet carrying them is lost.
+//!
+//! The [`bdp`] module uses custom frames to resume the congestion window of returning clients.
+
+use s2n_quic_core::custom_frame::Disabled;
+
+pub mod bdp;
+
+// these imports are only accessible if the unstable feature is enabled
+#[allow(unused_imports)]
+pub use s2n_quic_core::custom_frame::{
+    ConnectionInfo, Endpoint, Handler, Packet, ReceiveContext, WriteError,
+};
+
+pub trait Provider {
+    type Endpoint: Endpoint;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error>;
+}
+
+impl_provider_utils!();
+
+// This provider is disabled by default
+pub type Default = Disabled;
+
+impl<T: 'static + Send + Endpoint> Provider for T {
+    type Endpoint = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error> {
+        Ok(self)
+    }
+}
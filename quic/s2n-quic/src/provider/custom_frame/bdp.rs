@@ -0,0 +1,479 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shares the path characteristics observed by a server with its clients in a BDP frame
+//!
+//! The server sends the congestion window and minimum RTT of the path to the client, which saves
+//! them and echoes them back when it reconnects. The server saves the echoed values so its
+//! congestion controller can resume the window with Careful Resume, without keeping any state
+//! for clients between connections.
+//!
+//! See <https://datatracker.ietf.org/doc/html/draft-kuhn-quic-bdpframe-extension>
+//!
+//! The values are authenticated with a key which is only known to the server and are bound to
+//! the IP address of the client, so a client can't make the server use a larger window than it
+//! observed. Echoed values which fail validation, or are older than a day, are ignored.
+//!
+//! The client can also bootstrap the flow-control windows of a connection from the saved
+//! congestion window, so the server isn't blocked on flow control while the resumed window is in
+//! use. This requires the [`Client`] to be passed as the [`limits`](crate::provider::limits)
+//! provider as well. Only the receive windows of the client are raised, as the server can't read
+//! the values before the handshake has completed.
+//!
+//! Receiving an unknown frame type closes the connection, so both peers need to enable the
+//! provider, for example as part of the application protocol.
+//!
+// the example only compiles when the provider is public
+#![cfg_attr(feature = "unstable-provider-custom-frame", doc = "```rust,no_run")]
+#![cfg_attr(
+    not(feature = "unstable-provider-custom-frame"),
+    doc = "```rust,ignore"
+)]
+//! # use std::error::Error;
+//! use s2n_quic::{
+//!     provider::{
+//!         congestion_controller::{careful_resume, cubic},
+//!         custom_frame::bdp,
+//!     },
+//!     Client, Server,
+//! };
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let saved_paths = Arc::new(careful_resume::Cache::default());
+//! let congestion_controller = cubic::Builder::default()
+//!     .with_careful_resume(saved_paths.clone())
+//!     .build();
+//!
+//! let server = Server::builder()
+//!     .with_congestion_controller(congestion_controller)?
+//!     .with_custom_frame(bdp::Server::new(saved_paths))?
+//!     .with_io("127.0.0.1:443")?
+//!     .start()?;
+//!
+//! let bdp = bdp::Client::default();
+//! let client = Client::builder()
+//!     .with_custom_frame(bdp.clone())?
+//!     .with_limits(bdp)?
+//!     .with_io("0.0.0.0:0")?
+//!     .start()?;
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::provider::congestion_controller::careful_resume::{Cache, SavedPath};
+use bytes::Bytes;
+use core::time::Duration;
+use rand::prelude::*;
+use s2n_codec::{DecoderBuffer, Encoder, EncoderValue};
+use s2n_quic_core::{
+    connection::{
+        self,
+        limits::{self, Limiter, Limits, ValidationError},
+    },
+    custom_frame::{ConnectionInfo, Endpoint, Handler, Packet, ReceiveContext},
+    transport,
+    varint::VarInt,
+};
+use s2n_quic_crypto::{constant_time, digest, hmac};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The type of the BDP frame
+///
+/// The draft doesn't have a frame type assigned yet, so this value is provisional.
+pub const FRAME_TYPE: VarInt = VarInt::from_u16(0x2bd9);
+
+/// Echoed values older than this are ignored by the server
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The server sends a new frame once the congestion window differs from the last value it sent
+/// by more than the value divided by this
+const UPDATE_DIVISOR: u32 = 4;
+
+const TAG_LEN: usize = 16;
+
+/// The largest flow-control window the client bootstraps from a saved congestion window
+const MAX_DATA_WINDOW: u32 = 16 * 1024 * 1024;
+
+/// The issue time, congestion window and RTT, each encoded as a varint, followed by the tag
+const MAX_PAYLOAD_LEN: usize = 3 * 8 + TAG_LEN;
+
+/// The values carried in a BDP frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fields {
+    /// The number of seconds since the UNIX epoch at which the values were sent
+    issued_at: VarInt,
+    congestion_window: VarInt,
+    /// The minimum RTT in microseconds
+    rtt: VarInt,
+}
+
+impl Fields {
+    fn new(saved: SavedPath, now: Duration) -> Option<Self> {
+        Some(Self {
+            issued_at: VarInt::new(now.as_secs()).ok()?,
+            congestion_window: VarInt::from_u32(saved.congestion_window),
+            rtt: VarInt::new(saved.rtt.as_micros().try_into().ok()?).ok()?,
+        })
+    }
+
+    /// Encodes the fields followed by a tag over the fields and the IP address of the client
+    fn sign(&self, key: &hmac::Key, client: IpAddr) -> Vec<u8> {
+        let mut payload = self.encode_to_vec();
+        let tag = Self::tag(key, &payload, client);
+        payload.extend_from_slice(&tag.as_ref()[..TAG_LEN]);
+        payload
+    }
+
+    /// Decodes the fields of a payload, returning `None` if the tag doesn't match
+    fn verify(
+        payload: &[u8],
+        key: &hmac::Key,
+        client: IpAddr,
+    ) -> Result<Option<Self>, transport::Error> {
+        let error = || transport::Error::FRAME_ENCODING_ERROR.with_reason("invalid BDP frame");
+
+        let (fields, tag) = Self::decode(payload).ok_or_else(error)?;
+
+        let signed = &payload[..payload.len() - TAG_LEN];
+        let expected = Self::tag(key, signed, client);
+        if constant_time::verify_slices_are_equal(&expected.as_ref()[..TAG_LEN], tag).is_err() {
+            return Ok(None);
+        }
+
+        Ok(Some(fields))
+    }
+
+    /// Decodes the fields and the tag of a payload without verifying the tag
+    fn decode(payload: &[u8]) -> Option<(Self, &[u8])> {
+        let buffer = DecoderBuffer::new(payload);
+        let (issued_at, buffer) = buffer.decode().ok()?;
+        let (congestion_window, buffer) = buffer.decode().ok()?;
+        let (rtt, buffer) = buffer.decode::<VarInt>().ok()?;
+        let fields = Self {
+            issued_at,
+            congestion_window,
+            rtt,
+        };
+
+        let tag = buffer.into_less_safe_slice();
+        if tag.len() != TAG_LEN {
+            return None;
+        }
+
+        Some((fields, tag))
+    }
+
+    fn tag(key: &hmac::Key, fields: &[u8], client: IpAddr) -> hmac::Tag {
+        let mut ctx = hmac::Context::with_key(key);
+        ctx.update(fields);
+        match client {
+            IpAddr::V4(ip) => ctx.update(&ip.octets()),
+            IpAddr::V6(ip) => ctx.update(&ip.octets()),
+        }
+        ctx.sign()
+    }
+
+    /// Returns the saved path, if the values aren't too old
+    fn saved_path(&self, now: Duration) -> Option<SavedPath> {
+        let age = now.checked_sub(Duration::from_secs(self.issued_at.as_u64()))?;
+        if age > MAX_AGE {
+            return None;
+        }
+
+        Some(SavedPath::new(
+            self.congestion_window.as_u64().try_into().ok()?,
+            Duration::from_micros(self.rtt.as_u64()),
+        ))
+    }
+}
+
+impl EncoderValue for Fields {
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        encoder.encode(&self.issued_at);
+        encoder.encode(&self.congestion_window);
+        encoder.encode(&self.rtt);
+    }
+}
+
+fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+fn unknown_frame_type() -> transport::Error {
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-12.4
+    //# An endpoint MUST treat the receipt of a frame of unknown type as a
+    //# connection error of type FRAME_ENCODING_ERROR.
+    transport::Error::FRAME_ENCODING_ERROR.with_reason("unknown frame type")
+}
+
+/// Sends BDP frames to clients and saves the values they echo back
+#[derive(Debug)]
+pub struct Server {
+    key: hmac::Key,
+    saved_paths: Arc<Cache>,
+}
+
+impl Server {
+    /// Creates an endpoint which saves the values echoed by clients in `saved_paths`
+    ///
+    /// The same cache should be passed to the congestion controller with
+    /// [`with_careful_resume`](crate::provider::congestion_controller::cubic::Builder::with_careful_resume).
+    pub fn new(saved_paths: Arc<Cache>) -> Self {
+        let mut key_material = [0; digest::SHA256_OUTPUT_LEN];
+        rand::thread_rng().fill_bytes(&mut key_material);
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &key_material);
+
+        Self { key, saved_paths }
+    }
+}
+
+impl Endpoint for Server {
+    type Handler = ServerHandler;
+
+    fn create_connection(&mut self, info: &ConnectionInfo) -> Self::Handler {
+        ServerHandler {
+            key: self.key.clone(),
+            saved_paths: self.saved_paths.clone(),
+            client: SocketAddr::from(&info.remote_address).ip(),
+            sent_congestion_window: None,
+        }
+    }
+}
+
+pub struct ServerHandler {
+    key: hmac::Key,
+    saved_paths: Arc<Cache>,
+    client: IpAddr,
+    /// The congestion window in the last frame sent to the client
+    sent_congestion_window: Option<u32>,
+}
+
+impl Handler for ServerHandler {
+    fn on_frame(
+        &mut self,
+        context: &ReceiveContext<'_>,
+        frame_type: VarInt,
+        payload: &[u8],
+    ) -> Result<(), transport::Error> {
+        if frame_type != FRAME_TYPE {
+            return Err(unknown_frame_type());
+        }
+
+        let remote_address = SocketAddr::from(&context.path.remote_addr);
+        if let Some(saved) = Fields::verify(payload, &self.key, remote_address.ip())?
+            .and_then(|fields| fields.saved_path(unix_time()))
+        {
+            self.saved_paths.insert(remote_address, saved);
+        }
+
+        Ok(())
+    }
+
+    fn on_transmit<P: Packet>(&mut self, packet: &mut P) {
+        let info = packet.path_info();
+
+        // the RTT hasn't been sampled yet
+        if info.min_rtt.is_zero() {
+            return;
+        }
+
+        // frames are only sent when the window has changed significantly
+        if let Some(sent) = self.sent_congestion_window {
+            if info.congestion_window.abs_diff(sent) <= sent / UPDATE_DIVISOR {
+                return;
+            }
+        }
+
+        let saved = SavedPath::new(info.congestion_window, info.min_rtt);
+        let Some(fields) = Fields::new(saved, unix_time()) else {
+            return;
+        };
+
+        if packet
+            .write_frame(FRAME_TYPE, &fields.sign(&self.key, self.client))
+            .is_ok()
+        {
+            self.sent_congestion_window = Some(info.congestion_window);
+        }
+    }
+
+    fn has_transmission_interest(&self) -> bool {
+        // frames are only sent in packets which are already being transmitted
+        false
+    }
+
+    fn on_connection_error(&mut self, _error: connection::Error) {}
+}
+
+/// Saves the BDP frames sent by servers and echoes them back on the next connection
+///
+/// The frames are kept in memory, keyed by the address of the server. When the client is also
+/// used as the [`limits`](crate::provider::limits) provider, the receive windows of connections
+/// to a server with a saved frame are raised to the congestion window it carries, up to 16MiB.
+#[derive(Clone, Debug, Default)]
+pub struct Client {
+    saved: Arc<Mutex<HashMap<SocketAddr, Bytes>>>,
+    limits: Limits,
+}
+
+impl Client {
+    /// Sets the limits for connections, before the flow-control windows are bootstrapped
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the congestion window saved for `server`, if it isn't too old
+    fn saved_congestion_window(&self, server: SocketAddr) -> Option<u32> {
+        let saved = self.saved.lock().unwrap();
+        let (fields, _tag) = Fields::decode(saved.get(&server)?)?;
+        let saved = fields.saved_path(unix_time())?;
+        Some(saved.congestion_window.min(MAX_DATA_WINDOW))
+    }
+
+    /// Raises the receive windows of `limits` to at least `window`
+    fn bootstrap(limits: Limits, window: u32) -> Result<Limits, ValidationError> {
+        let window = u64::from(window);
+        let flow_control = limits.initial_flow_control_limits();
+        let stream = flow_control.stream_limits;
+
+        limits
+            .with_data_window(flow_control.max_data.as_u64().max(window))?
+            .with_bidirectional_local_data_window(stream.max_data_bidi_local.as_u64().max(window))?
+            .with_bidirectional_remote_data_window(
+                stream.max_data_bidi_remote.as_u64().max(window),
+            )?
+            .with_unidirectional_data_window(stream.max_data_uni.as_u64().max(window))
+    }
+}
+
+impl Endpoint for Client {
+    type Handler = ClientHandler;
+
+    fn create_connection(&mut self, info: &ConnectionInfo) -> Self::Handler {
+        let server = SocketAddr::from(&info.remote_address);
+        let pending = self.saved.lock().unwrap().get(&server).cloned();
+
+        ClientHandler {
+            server,
+            pending,
+            saved: self.saved.clone(),
+        }
+    }
+}
+
+impl Limiter for Client {
+    fn on_connection(&mut self, info: &limits::ConnectionInfo) -> Limits {
+        let server = SocketAddr::from(&info.remote_address);
+        self.saved_congestion_window(server)
+            .and_then(|window| Self::bootstrap(self.limits, window).ok())
+            .unwrap_or(self.limits)
+    }
+}
+
+pub struct ClientHandler {
+    server: SocketAddr,
+    /// The frame saved from a previous connection which hasn't been echoed yet
+    pending: Option<Bytes>,
+    saved: Arc<Mutex<HashMap<SocketAddr, Bytes>>>,
+}
+
+impl Handler for ClientHandler {
+    fn on_frame(
+        &mut self,
+        _context: &ReceiveContext<'_>,
+        frame_type: VarInt,
+        payload: &[u8],
+    ) -> Result<(), transport::Error> {
+        if frame_type != FRAME_TYPE {
+            return Err(unknown_frame_type());
+        }
+
+        // the payload is opaque to the client, but is bounded to limit the memory it can take
+        if payload.len() > MAX_PAYLOAD_LEN {
+            return Err(transport::Error::FRAME_ENCODING_ERROR.with_reason("invalid BDP frame"));
+        }
+
+        self.saved
+            .lock()
+            .unwrap()
+            .insert(self.server, Bytes::copy_from_slice(payload));
+
+        Ok(())
+    }
+
+    fn on_transmit<P: Packet>(&mut self, packet: &mut P) {
+        if let Some(payload) = self.pending.as_ref() {
+            if packet.write_frame(FRAME_TYPE, payload).is_ok() {
+                self.pending = None;
+            }
+        }
+    }
+
+    fn has_transmission_interest(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn on_connection_error(&mut self, _error: connection::Error) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> hmac::Key {
+        hmac::Key::new(hmac::HMAC_SHA256, &[1; digest::SHA256_OUTPUT_LEN])
+    }
+
+    fn fields(now: Duration) -> Fields {
+        Fields::new(SavedPath::new(1_000_000, Duration::from_millis(100)), now).unwrap()
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let now = unix_time();
+        let client: IpAddr = "192.0.2.1".parse().unwrap();
+        let payload = fields(now).sign(&key(), client);
+        assert!(payload.len() <= MAX_PAYLOAD_LEN);
+
+        let decoded = Fields::verify(&payload, &key(), client).unwrap().unwrap();
+        assert_eq!(decoded, fields(now));
+        assert_eq!(
+            decoded.saved_path(now),
+            Some(SavedPath::new(1_000_000, Duration::from_millis(100)))
+        );
+    }
+
+    #[test]
+    fn reject_test() {
+        let now = unix_time();
+        let client: IpAddr = "192.0.2.1".parse().unwrap();
+        let payload = fields(now).sign(&key(), client);
+
+        // the values were issued to another client
+        let other: IpAddr = "192.0.2.2".parse().unwrap();
+        assert_eq!(Fields::verify(&payload, &key(), other), Ok(None));
+
+        // the values were modified by the client
+        let mut modified = fields(now);
+        modified.congestion_window = VarInt::from_u32(2_000_000);
+        let mut forged = modified.encode_to_vec();
+        forged.extend_from_slice(&payload[payload.len() - TAG_LEN..]);
+        assert_eq!(Fields::verify(&forged, &key(), client), Ok(None));
+
+        // the tag is truncated
+        assert!(Fields::verify(&payload[..payload.len() - 1], &key(), client).is_err());
+
+        // the values are too old
+        assert_eq!(fields(now).saved_path(now + MAX_AGE * 2), None);
+    }
+}
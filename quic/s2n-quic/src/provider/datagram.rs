@@ -8,7 +8,7 @@ use s2n_quic_core::datagram::Disabled;
 // these imports are only accessible if the unstable feature is enabled
 #[allow(unused_imports)]
 pub use s2n_quic_core::datagram::{
-    default,
+    default, fec,
     traits::{
         ConnectionInfo, Endpoint, Packet, PreConnectionInfo, ReceiveContext, Receiver, Sender,
         WriteError,
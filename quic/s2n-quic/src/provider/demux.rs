@@ -0,0 +1,36 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides support for sharing the endpoint's socket with other protocols
+//!
+//! Datagrams which don't belong to QUIC, such as STUN messages when using ICE, are passed to
+//! the [`Demultiplexer`] instead of being dropped. By default, datagrams are classified
+//! according to RFC 9443. The demultiplexer can respond to the sender of a datagram from the
+//! endpoint's socket, which keeps the 5-tuple shared with the QUIC connections.
+
+use s2n_quic_core::demux::Disabled;
+
+// these imports are only accessible if the unstable feature is enabled
+#[allow(unused_imports)]
+pub use s2n_quic_core::demux::{Datagram, Demultiplexer, Responder};
+
+pub trait Provider {
+    type Demultiplexer: 'static + Demultiplexer;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Demultiplexer, Self::Error>;
+}
+
+impl_provider_utils!();
+
+// This provider is disabled by default
+pub type Default = Disabled;
+
+impl<T: 'static + Send + Demultiplexer> Provider for T {
+    type Demultiplexer = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Demultiplexer, Self::Error> {
+        Ok(self)
+    }
+}
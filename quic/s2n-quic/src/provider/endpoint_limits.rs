@@ -4,7 +4,11 @@
 //! Allows applications to limit peer's ability to open new connections
 
 pub use s2n_quic_core::endpoint::{
-    limits::{ConnectionAttempt, Outcome},
+    limits::{
+        ConnectionAttempt, ConnectionCounts, EvictionCheck, EvictionCriteria, EvictionOutcome,
+        HandshakeAttempt, HandshakeOutcome, InitialPacket, Outcome, OverloadOutcome, RxOverflow,
+        DEFAULT_CONNECTION_CAPACITY, DEFAULT_EVICTION_ERROR, DEFAULT_TIMER_GRANULARITY,
+    },
     Limiter,
 };
 use s2n_quic_core::{event::Timestamp, path::THROTTLED_PORTS_LEN};
@@ -180,6 +184,11 @@ pub mod default {
     #[derive(Default)]
     pub struct Builder {
         max_inflight_handshake_limit: Option<usize>,
+        timer_granularity: Option<Duration>,
+        connection_capacity: Option<usize>,
+        max_connections: Option<usize>,
+        eviction_criteria: Option<EvictionCriteria>,
+        overload_shedding: Option<Duration>,
     }
 
     impl Builder {
@@ -189,10 +198,67 @@ pub mod default {
             Ok(self)
         }
 
+        /// Sets the granularity of the endpoint's connection timers (default: 1ms)
+        ///
+        /// Connection timers expire within one unit of granularity of their deadline. A finer
+        /// granularity (e.g. 250µs) results in more precise timers at the cost of more frequent
+        /// wakeups. A coarser granularity can reduce timer churn for endpoints with a large number
+        /// of mostly-idle connections.
+        pub fn with_timer_granularity(mut self, granularity: Duration) -> Result<Self, Infallible> {
+            self.timer_granularity = Some(granularity);
+            Ok(self)
+        }
+
+        /// Sets the number of connections to preallocate storage for (default: 0)
+        ///
+        /// Connection storage grows as needed beyond this value. Endpoints which expect a large
+        /// number of concurrent connections can set this to avoid reallocations while ramping up.
+        pub fn with_connection_capacity(mut self, capacity: usize) -> Result<Self, Infallible> {
+            self.connection_capacity = Some(capacity);
+            Ok(self)
+        }
+
+        /// Sets the number of open connections above which connections are evicted (default: unlimited)
+        ///
+        /// Once the threshold is exceeded, the connections selected by the eviction criteria are
+        /// closed with the [`DEFAULT_EVICTION_ERROR`] until the endpoint is back at the threshold.
+        /// Applications which need to react to other resource thresholds, such as memory usage,
+        /// should implement [`Limiter::on_eviction_check`] instead.
+        pub fn with_max_connections(mut self, limit: usize) -> Result<Self, Infallible> {
+            self.max_connections = Some(limit);
+            Ok(self)
+        }
+
+        /// Sets the criteria used to select connections for eviction (default: idle longest)
+        pub fn with_eviction_criteria(
+            mut self,
+            criteria: EvictionCriteria,
+        ) -> Result<Self, Infallible> {
+            self.eviction_criteria = Some(criteria);
+            Ok(self)
+        }
+
+        /// Sheds non-essential work for `duration` each time the receive queue overflows
+        /// (default: disabled)
+        ///
+        /// While shedding work, the endpoint skips emitting events for dropped datagrams and defers
+        /// non-urgent timers. See [`OverloadOutcome::shed`] for details.
+        pub fn with_overload_shedding(mut self, duration: Duration) -> Result<Self, Infallible> {
+            self.overload_shedding = Some(duration);
+            Ok(self)
+        }
+
         /// Build the limits
         pub fn build(self) -> Result<Limits, Infallible> {
             Ok(Limits {
                 max_inflight_handshake_limit: self.max_inflight_handshake_limit,
+                timer_granularity: self.timer_granularity.unwrap_or(DEFAULT_TIMER_GRANULARITY),
+                connection_capacity: self
+                    .connection_capacity
+                    .unwrap_or(DEFAULT_CONNECTION_CAPACITY),
+                max_connections: self.max_connections,
+                eviction_criteria: self.eviction_criteria.unwrap_or_default(),
+                overload_shedding: self.overload_shedding,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             })
         }
@@ -202,6 +268,16 @@ pub mod default {
     pub struct Limits {
         /// Maximum number of handshakes to allow before Retry packets are queued
         max_inflight_handshake_limit: Option<usize>,
+        /// The granularity of the endpoint's connection timers
+        timer_granularity: Duration,
+        /// The number of connections to preallocate storage for
+        connection_capacity: usize,
+        /// Maximum number of open connections before connections are evicted
+        max_connections: Option<usize>,
+        /// The criteria used to select connections for eviction
+        eviction_criteria: EvictionCriteria,
+        /// The amount of time to shed work for after the receive queue overflows
+        overload_shedding: Option<Duration>,
         rate_limiter: [BasicRateLimiter; THROTTLED_PORTS_LEN],
     }
 
@@ -235,6 +311,35 @@ pub mod default {
 
             Outcome::allow()
         }
+
+        fn on_eviction_check(&mut self, info: &EvictionCheck) -> EvictionOutcome {
+            if let Some(limit) = self.max_connections {
+                if info.counts.connection_count > limit {
+                    return EvictionOutcome::evict(
+                        info.counts.connection_count - limit,
+                        self.eviction_criteria,
+                    );
+                }
+            }
+
+            EvictionOutcome::none()
+        }
+
+        fn on_rx_overflow(&mut self, _info: &RxOverflow) -> OverloadOutcome {
+            if let Some(duration) = self.overload_shedding {
+                OverloadOutcome::shed(duration)
+            } else {
+                OverloadOutcome::none()
+            }
+        }
+
+        fn timer_granularity(&self) -> Duration {
+            self.timer_granularity
+        }
+
+        fn connection_capacity(&self) -> usize {
+            self.connection_capacity
+        }
     }
 
     /// Default limit values are as non-intrusive as possible
@@ -242,6 +347,11 @@ pub mod default {
         fn default() -> Self {
             Self {
                 max_inflight_handshake_limit: None,
+                timer_granularity: DEFAULT_TIMER_GRANULARITY,
+                connection_capacity: DEFAULT_CONNECTION_CAPACITY,
+                max_connections: None,
+                eviction_criteria: EvictionCriteria::default(),
+                overload_shedding: None,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             }
         }
@@ -252,9 +362,48 @@ pub mod default {
         let elp = Limits::builder()
             .with_inflight_handshake_limit(100)
             .unwrap()
+            .with_timer_granularity(Duration::from_micros(250))
+            .unwrap()
+            .with_connection_capacity(1024)
+            .unwrap()
+            .with_max_connections(10)
+            .unwrap()
+            .with_eviction_criteria(EvictionCriteria::Oldest)
+            .unwrap()
+            .with_overload_shedding(Duration::from_millis(100))
+            .unwrap()
             .build()
             .unwrap();
         assert_eq!(elp.max_inflight_handshake_limit, Some(100));
+        assert_eq!(elp.timer_granularity(), Duration::from_micros(250));
+        assert_eq!(elp.connection_capacity(), 1024);
+        assert_eq!(elp.max_connections, Some(10));
+        assert_eq!(elp.eviction_criteria, EvictionCriteria::Oldest);
+        assert_eq!(elp.overload_shedding, Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn eviction_check() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            time::{testing::Clock as MockClock, Clock},
+        };
+
+        let mut limits = Limits::builder()
+            .with_max_connections(10)
+            .unwrap()
+            .build()
+            .unwrap();
+        let timestamp = MockClock::default().get_time().into_event();
+
+        let info = EvictionCheck::new(ConnectionCounts::new(0, 10), timestamp);
+        assert_eq!(limits.on_eviction_check(&info), EvictionOutcome::none());
+
+        let info = EvictionCheck::new(ConnectionCounts::new(0, 13), timestamp);
+        assert_eq!(
+            limits.on_eviction_check(&info),
+            EvictionOutcome::evict(3, EvictionCriteria::IdleLongest)
+        );
     }
 
     #[test]
@@ -0,0 +1,285 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::provider::event::{self, events};
+use core::time::Duration;
+use s2n_quic_core::{
+    connection,
+    io::pcapng::{self, Direction},
+};
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter, Write},
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The default number of datagrams retained for each connection
+const DEFAULT_CAPACITY: usize = 128;
+
+/// The default number of bytes retained for each datagram
+///
+/// This is enough to capture the header of the first packet in the datagram.
+const DEFAULT_SNAP_LEN: usize = 64;
+
+/// An event subscriber that keeps a bounded ring of the most recent datagrams sent by each
+/// connection
+///
+/// Each datagram is recorded with the headers of the packets it carries. By default, only the
+/// first bytes of each encrypted datagram are retained. When a connection is closed with an error,
+/// the ring is written to a pcapng file in the configured directory so the failure can be
+/// investigated with the traffic that preceded it.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use s2n_quic::{provider::event::capture, Server};
+///
+/// let subscriber = capture::Subscriber::builder()
+///     .with_capacity(256)
+///     .with_directory("/tmp/captures")
+///     .build();
+///
+/// let server = Server::builder()
+///     .with_event(subscriber)?
+///     .start()?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct Subscriber {
+    config: Arc<Config>,
+}
+
+#[derive(Debug)]
+struct Config {
+    capacity: usize,
+    snap_len: usize,
+    directory: Option<PathBuf>,
+}
+
+impl Default for Subscriber {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Subscriber {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+#[derive(Debug)]
+pub struct Builder {
+    capacity: usize,
+    snap_len: usize,
+    directory: Option<PathBuf>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            snap_len: DEFAULT_SNAP_LEN,
+            directory: None,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the number of datagrams retained for each connection
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the number of bytes retained for each datagram
+    pub fn with_snap_len(mut self, snap_len: usize) -> Self {
+        self.snap_len = snap_len;
+        self
+    }
+
+    /// Retains the full payload of each datagram
+    ///
+    /// This considerably increases the memory used by each connection and should only be enabled
+    /// while debugging.
+    pub fn with_full_payloads(self) -> Self {
+        self.with_snap_len(usize::MAX)
+    }
+
+    /// Sets the directory in which captures are written when a connection fails
+    ///
+    /// Each capture is named after the internal id of the connection. If no directory is
+    /// configured, captures are only available through [`Ring::write_pcapng`].
+    pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Builds the [`Subscriber`]
+    pub fn build(self) -> Subscriber {
+        Subscriber {
+            config: Arc::new(Config {
+                capacity: self.capacity,
+                snap_len: self.snap_len,
+                directory: self.directory,
+            }),
+        }
+    }
+}
+
+/// A datagram retained in the ring
+#[derive(Debug)]
+struct Record {
+    timestamp: Duration,
+    local_address: SocketAddr,
+    remote_address: SocketAddr,
+    payload: Box<[u8]>,
+    payload_len: usize,
+    packets: String,
+}
+
+/// The most recent datagrams sent by a connection
+///
+/// The ring can be queried from a connection handle with
+/// [`query_event_context`](crate::connection::Handle::query_event_context).
+#[derive(Debug)]
+pub struct Ring {
+    config: Arc<Config>,
+    records: VecDeque<Record>,
+    /// The headers of the packets written since the last datagram was sent
+    packets: String,
+    /// The offset between connection timestamps and the UNIX epoch
+    epoch: Duration,
+}
+
+impl Ring {
+    /// Returns the number of retained datagrams
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no datagrams have been retained
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Writes the retained datagrams to `output` in the pcapng format
+    pub fn write_pcapng<W: Write>(&self, output: W) -> io::Result<()> {
+        let mut writer = pcapng::Writer::new(output)?;
+
+        for record in &self.records {
+            writer.write_datagram(&pcapng::Datagram {
+                timestamp: self.epoch + record.timestamp,
+                direction: Direction::Outbound,
+                local_address: record.local_address,
+                remote_address: record.remote_address,
+                payload: &record.payload,
+                payload_len: record.payload_len,
+                comment: Some(record.packets.as_str()).filter(|packets| !packets.is_empty()),
+            })?;
+        }
+
+        writer.flush()
+    }
+
+    fn on_datagram(&mut self, meta: &event::ConnectionMeta, event: &events::DatagramPayloadSent) {
+        if self.config.capacity == 0 {
+            return;
+        }
+
+        if self.records.len() == self.config.capacity {
+            self.records.pop_front();
+        }
+
+        let snap_len = event.payload.len().min(self.config.snap_len);
+
+        self.records.push_back(Record {
+            timestamp: meta.timestamp.duration_since_start(),
+            local_address: (&event.local_addr).into(),
+            remote_address: (&event.remote_addr).into(),
+            payload: event.payload[..snap_len].into(),
+            payload_len: event.payload.len(),
+            packets: core::mem::take(&mut self.packets),
+        });
+    }
+
+    fn dump(&self, meta: &event::ConnectionMeta) -> io::Result<()> {
+        let Some(directory) = self.config.directory.as_ref() else {
+            return Ok(());
+        };
+
+        let path = directory.join(format!("{}.pcapng", meta.id));
+        let file = BufWriter::new(File::create(path)?);
+        self.write_pcapng(file)
+    }
+}
+
+impl event::Subscriber for Subscriber {
+    type ConnectionContext = Ring;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        meta: &event::ConnectionMeta,
+        _info: &event::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let epoch = now.saturating_sub(meta.timestamp.duration_since_start());
+
+        Ring {
+            config: self.config.clone(),
+            records: VecDeque::new(),
+            packets: String::new(),
+            epoch,
+        }
+    }
+
+    #[inline]
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        if !context.packets.is_empty() {
+            context.packets.push('\n');
+        }
+        let _ = write!(
+            context.packets,
+            "{:?} ({} bytes)",
+            event.packet_header, event.packet_len
+        );
+    }
+
+    #[inline]
+    fn on_datagram_payload_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::DatagramPayloadSent,
+    ) {
+        context.on_datagram(meta, event);
+    }
+
+    #[inline]
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &events::ConnectionClosed,
+    ) {
+        // connections closed without an error don't need to be investigated
+        if matches!(event.error, connection::Error::Closed { .. }) {
+            return;
+        }
+
+        // the capture is best-effort so failing to write it shouldn't affect the endpoint
+        let _ = context.dump(meta);
+    }
+}
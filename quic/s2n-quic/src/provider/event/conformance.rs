@@ -0,0 +1,726 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::provider::event::{self, events};
+use core::{fmt, ops::RangeInclusive};
+use s2n_quic_core::packet::number::PacketNumberSpace;
+use std::collections::{HashMap, VecDeque};
+
+/// The number of skipped packet numbers retained for each packet number space
+///
+/// Packet numbers are only skipped occasionally so this covers the acknowledgements which are
+/// still being sent by a well-behaved peer.
+const MAX_SKIPPED_PACKET_NUMBERS: usize = 64;
+
+/// An event subscriber which checks that both endpoints of each connection follow the protocol
+///
+/// The subscriber validates invariants which can be observed from the packets and frames of a
+/// connection, for example:
+///
+/// * packet numbers sent in each packet number space strictly increase
+/// * acknowledgements sent by the peer only include packets which were sent
+/// * acknowledgements sent by the endpoint only include packets which were received
+/// * stream data sent by the endpoint stays within the flow control limits announced by the peer
+/// * streams opened by the endpoint stay within the stream limits announced by the peer
+///
+/// This is useful for testing custom providers and for interop testing against other
+/// implementations. Validating each frame adds overhead so it should only be enabled while
+/// debugging. The subscriber needs to observe every event of the connection and shouldn't be
+/// sampled.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// use s2n_quic::{provider::event::conformance, Client};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// // fail fast on the first violation while testing
+/// let subscriber = conformance::Subscriber::builder()
+///     .with_action(conformance::Action::Panic)
+///     .build();
+///
+/// let client = Client::builder()
+///     .with_event(subscriber)?
+///     .with_io("0.0.0.0:0")?
+///     .start()?;
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Subscriber {
+    action: Action,
+}
+
+impl Subscriber {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Builder {
+    action: Action,
+}
+
+impl Builder {
+    /// Sets the action taken when a violation is found
+    pub fn with_action(mut self, action: Action) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Builds the [`Subscriber`]
+    pub fn build(self) -> Subscriber {
+        Subscriber {
+            action: self.action,
+        }
+    }
+}
+
+/// The action taken by the [`Subscriber`] when a violation is found
+///
+/// In all cases, the violation is retained by the [`Checker`] of the connection.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Action {
+    /// Prints a description of the violation to STDERR
+    #[default]
+    Log,
+    /// Panics with a description of the violation
+    ///
+    /// A peer which violates the protocol will abort the process, so this should only be used
+    /// in tests.
+    Panic,
+}
+
+/// A protocol invariant which was violated by one of the endpoints of a connection
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// The endpoint sent a packet number which doesn't increase in its packet number space
+    PacketNumberNotIncreasing {
+        space: PacketNumberSpace,
+        previous: u64,
+        packet_number: u64,
+    },
+    /// The peer acknowledged a packet number which wasn't sent by the endpoint
+    AckOfUnsentPacket {
+        space: PacketNumberSpace,
+        packet_number: u64,
+    },
+    /// The endpoint acknowledged a packet number which wasn't received from the peer
+    AckOfUnreceivedPacket {
+        space: PacketNumberSpace,
+        packet_number: u64,
+    },
+    /// The endpoint sent stream data beyond the stream limit announced by the peer
+    StreamDataLimitExceeded {
+        stream_id: u64,
+        offset: u64,
+        limit: u64,
+    },
+    /// The endpoint sent stream data beyond the connection limit announced by the peer
+    ConnectionDataLimitExceeded { offset: u64, limit: u64 },
+    /// The endpoint opened a stream beyond the stream limit announced by the peer
+    StreamLimitExceeded { stream_id: u64, limit: u64 },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PacketNumberNotIncreasing {
+                space,
+                previous,
+                packet_number,
+            } => write!(
+                f,
+                "packet number {packet_number} was sent after {previous} in the {space:?} space"
+            ),
+            Self::AckOfUnsentPacket {
+                space,
+                packet_number,
+            } => write!(
+                f,
+                "the peer acknowledged unsent packet number {packet_number} in the {space:?} space"
+            ),
+            Self::AckOfUnreceivedPacket {
+                space,
+                packet_number,
+            } => write!(
+                f,
+                "unreceived packet number {packet_number} was acknowledged in the {space:?} space"
+            ),
+            Self::StreamDataLimitExceeded {
+                stream_id,
+                offset,
+                limit,
+            } => write!(
+                f,
+                "stream {stream_id} sent data up to offset {offset} beyond the peer's limit of {limit}"
+            ),
+            Self::ConnectionDataLimitExceeded { offset, limit } => write!(
+                f,
+                "the connection sent {offset} bytes of stream data beyond the peer's limit of {limit}"
+            ),
+            Self::StreamLimitExceeded { stream_id, limit } => write!(
+                f,
+                "stream {stream_id} was opened beyond the peer's limit of {limit} streams"
+            ),
+        }
+    }
+}
+
+/// The packet numbers observed in a packet number space
+#[derive(Debug, Default)]
+struct Space {
+    largest_sent: Option<u64>,
+    largest_received: Option<u64>,
+    skipped: VecDeque<u64>,
+}
+
+/// The limits announced by the peer for the data sent by the endpoint
+#[derive(Debug)]
+struct PeerLimits {
+    max_data: u64,
+    max_stream_data_bidi_local: u64,
+    max_stream_data_bidi_remote: u64,
+    max_stream_data_uni: u64,
+    max_streams_bidi: u64,
+    max_streams_uni: u64,
+}
+
+/// The data sent on a stream
+#[derive(Debug, Default)]
+struct Stream {
+    is_open: bool,
+    sent: u64,
+    max_stream_data: u64,
+}
+
+/// Validates the events of a single connection
+///
+/// The checker can be queried from a connection handle with
+/// [`query_event_context`](crate::connection::Handle::query_event_context).
+#[derive(Debug)]
+pub struct Checker {
+    action: Action,
+    id: u64,
+    is_client: bool,
+    spaces: [Space; 3],
+    /// The limits announced by the peer, which are unknown until its transport parameters are
+    /// received
+    peer_limits: Option<PeerLimits>,
+    streams: HashMap<u64, Stream>,
+    /// The sum of the highest offsets sent on each stream
+    sent_data: u64,
+    violations: Vec<Violation>,
+}
+
+impl Checker {
+    fn new(action: Action, id: u64, is_client: bool) -> Self {
+        Self {
+            action,
+            id,
+            is_client,
+            spaces: Default::default(),
+            peer_limits: None,
+            streams: HashMap::new(),
+            sent_data: 0,
+            violations: Vec::new(),
+        }
+    }
+
+    /// Returns the violations found on the connection
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    fn report(&mut self, violation: Violation) {
+        match self.action {
+            Action::Panic => panic!(
+                "conformance violation on connection {}: {violation}",
+                self.id
+            ),
+            Action::Log => eprintln!(
+                "conformance violation on connection {}: {violation}",
+                self.id
+            ),
+        }
+        self.violations.push(violation);
+    }
+
+    fn space(&mut self, space: PacketNumberSpace) -> &mut Space {
+        match space {
+            PacketNumberSpace::Initial => &mut self.spaces[0],
+            PacketNumberSpace::Handshake => &mut self.spaces[1],
+            PacketNumberSpace::ApplicationData => &mut self.spaces[2],
+        }
+    }
+
+    fn on_packet_sent(&mut self, space: PacketNumberSpace, packet_number: u64) {
+        let previous = self.space(space).largest_sent.replace(packet_number);
+
+        if let Some(previous) = previous.filter(|previous| *previous >= packet_number) {
+            self.report(Violation::PacketNumberNotIncreasing {
+                space,
+                previous,
+                packet_number,
+            });
+        }
+    }
+
+    fn on_packet_skipped(&mut self, space: PacketNumberSpace, packet_number: u64) {
+        let skipped = &mut self.space(space).skipped;
+        if skipped.len() == MAX_SKIPPED_PACKET_NUMBERS {
+            skipped.pop_front();
+        }
+        skipped.push_back(packet_number);
+    }
+
+    fn on_packet_received(&mut self, space: PacketNumberSpace, packet_number: u64) {
+        let largest_received = &mut self.space(space).largest_received;
+        *largest_received = Some(largest_received.map_or(packet_number, |v| v.max(packet_number)));
+    }
+
+    fn on_ack_range_received(&mut self, space: PacketNumberSpace, range: &RangeInclusive<u64>) {
+        let state = self.space(space);
+
+        let unsent = if state.largest_sent.map_or(true, |v| v < *range.end()) {
+            Some(*range.end())
+        } else {
+            state
+                .skipped
+                .iter()
+                .copied()
+                .find(|packet_number| range.contains(packet_number))
+        };
+
+        if let Some(packet_number) = unsent {
+            self.report(Violation::AckOfUnsentPacket {
+                space,
+                packet_number,
+            });
+        }
+    }
+
+    fn on_ack_range_sent(&mut self, space: PacketNumberSpace, range: &RangeInclusive<u64>) {
+        if self
+            .space(space)
+            .largest_received
+            .map_or(true, |v| v < *range.end())
+        {
+            self.report(Violation::AckOfUnreceivedPacket {
+                space,
+                packet_number: *range.end(),
+            });
+        }
+    }
+
+    fn on_peer_transport_parameters(&mut self, parameters: &events::TransportParameters) {
+        self.peer_limits = Some(PeerLimits {
+            max_data: parameters.initial_max_data,
+            max_stream_data_bidi_local: parameters.initial_max_stream_data_bidi_local,
+            max_stream_data_bidi_remote: parameters.initial_max_stream_data_bidi_remote,
+            max_stream_data_uni: parameters.initial_max_stream_data_uni,
+            max_streams_bidi: parameters.initial_max_streams_bidi,
+            max_streams_uni: parameters.initial_max_streams_uni,
+        });
+    }
+
+    fn on_frame_received(&mut self, frame: &events::Frame) {
+        match *frame {
+            events::Frame::MaxData { value, .. } => {
+                if let Some(limits) = self.peer_limits.as_mut() {
+                    limits.max_data = limits.max_data.max(value);
+                }
+            }
+            events::Frame::MaxStreamData { id, value, .. } => {
+                let stream = self.streams.entry(id).or_default();
+                stream.max_stream_data = stream.max_stream_data.max(value);
+            }
+            events::Frame::MaxStreams {
+                ref stream_type,
+                value,
+                ..
+            } => {
+                if let Some(limits) = self.peer_limits.as_mut() {
+                    let max_streams =
+                        if matches!(stream_type, events::StreamType::Bidirectional { .. }) {
+                            &mut limits.max_streams_bidi
+                        } else {
+                            &mut limits.max_streams_uni
+                        };
+                    *max_streams = (*max_streams).max(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_frame_sent(&mut self, frame: &events::Frame) {
+        match *frame {
+            events::Frame::Stream {
+                id, offset, len, ..
+            } => self.on_stream_data_sent(id, offset + len as u64),
+            events::Frame::ResetStream { id, final_size, .. }
+            | events::Frame::ResetStreamAt { id, final_size, .. } => {
+                self.on_stream_data_sent(id, final_size)
+            }
+            _ => {}
+        }
+    }
+
+    fn on_stream_data_sent(&mut self, stream_id: u64, offset: u64) {
+        let is_bidirectional = stream_id & 0b10 == 0;
+        let is_local = (stream_id & 0b01 == 0) == self.is_client;
+
+        let stream = self.streams.entry(stream_id).or_default();
+        let is_new = !core::mem::replace(&mut stream.is_open, true);
+        let new_data = offset.saturating_sub(stream.sent);
+        stream.sent += new_data;
+        self.sent_data += new_data;
+        let max_stream_data = stream.max_stream_data;
+
+        // the limits can't be checked until the peer's transport parameters are received
+        let Some(limits) = self.peer_limits.as_ref() else {
+            return;
+        };
+
+        let max_streams = if is_bidirectional {
+            limits.max_streams_bidi
+        } else {
+            limits.max_streams_uni
+        };
+        let max_stream_data = match (is_bidirectional, is_local) {
+            (true, true) => limits.max_stream_data_bidi_remote,
+            (true, false) => limits.max_stream_data_bidi_local,
+            (false, _) => limits.max_stream_data_uni,
+        }
+        .max(max_stream_data);
+        let max_data = limits.max_data;
+
+        if is_new && is_local && stream_id >> 2 >= max_streams {
+            self.report(Violation::StreamLimitExceeded {
+                stream_id,
+                limit: max_streams,
+            });
+        }
+
+        // retransmissions of data which was already reported don't need to be checked again
+        if new_data == 0 {
+            return;
+        }
+
+        if offset > max_stream_data {
+            self.report(Violation::StreamDataLimitExceeded {
+                stream_id,
+                offset,
+                limit: max_stream_data,
+            });
+        }
+
+        if self.sent_data > max_data {
+            self.report(Violation::ConnectionDataLimitExceeded {
+                offset: self.sent_data,
+                limit: max_data,
+            });
+        }
+    }
+}
+
+/// Returns the packet number space and packet number of a packet header
+fn packet_number(header: &events::PacketHeader) -> Option<(PacketNumberSpace, u64)> {
+    match *header {
+        events::PacketHeader::Initial { number, .. } => Some((PacketNumberSpace::Initial, number)),
+        events::PacketHeader::Handshake { number, .. } => {
+            Some((PacketNumberSpace::Handshake, number))
+        }
+        events::PacketHeader::ZeroRtt { number, .. }
+        | events::PacketHeader::OneRtt { number, .. } => {
+            Some((PacketNumberSpace::ApplicationData, number))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the packet number space of a key space
+fn key_space(space: &events::KeySpace) -> Option<PacketNumberSpace> {
+    match space {
+        events::KeySpace::Initial { .. } => Some(PacketNumberSpace::Initial),
+        events::KeySpace::Handshake { .. } => Some(PacketNumberSpace::Handshake),
+        events::KeySpace::ZeroRtt { .. } | events::KeySpace::OneRtt { .. } => {
+            Some(PacketNumberSpace::ApplicationData)
+        }
+        _ => None,
+    }
+}
+
+impl event::Subscriber for Subscriber {
+    type ConnectionContext = Checker;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        meta: &event::ConnectionMeta,
+        _info: &event::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let is_client = matches!(meta.endpoint_type, events::EndpointType::Client { .. });
+        Checker::new(self.action, meta.id, is_client)
+    }
+
+    #[inline]
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        if let Some((space, packet_number)) = packet_number(&event.packet_header) {
+            context.on_packet_sent(space, packet_number);
+        }
+    }
+
+    #[inline]
+    fn on_packet_skipped(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::PacketSkipped,
+    ) {
+        if let Some(space) = key_space(&event.space) {
+            context.on_packet_skipped(space, event.number);
+        }
+    }
+
+    #[inline]
+    fn on_packet_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::PacketReceived,
+    ) {
+        if let Some((space, packet_number)) = packet_number(&event.packet_header) {
+            context.on_packet_received(space, packet_number);
+        }
+    }
+
+    #[inline]
+    fn on_ack_range_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::AckRangeReceived,
+    ) {
+        if let Some((space, _)) = packet_number(&event.packet_header) {
+            context.on_ack_range_received(space, &event.ack_range);
+        }
+    }
+
+    #[inline]
+    fn on_ack_range_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::AckRangeSent,
+    ) {
+        if let Some((space, _)) = packet_number(&event.packet_header) {
+            context.on_ack_range_sent(space, &event.ack_range);
+        }
+    }
+
+    #[inline]
+    fn on_transport_parameters_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::TransportParametersReceived,
+    ) {
+        context.on_peer_transport_parameters(&event.transport_parameters);
+    }
+
+    #[inline]
+    fn on_frame_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::FrameSent,
+    ) {
+        context.on_frame_sent(&event.frame);
+    }
+
+    #[inline]
+    fn on_frame_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &events::FrameReceived,
+    ) {
+        context.on_frame_received(&event.frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::event::{builder, IntoEvent};
+
+    fn checker() -> Checker {
+        let mut checker = Checker::new(Action::Log, 0, true);
+        checker.peer_limits = Some(PeerLimits {
+            max_data: 100,
+            max_stream_data_bidi_local: 10,
+            max_stream_data_bidi_remote: 20,
+            max_stream_data_uni: 30,
+            max_streams_bidi: 2,
+            max_streams_uni: 1,
+        });
+        checker
+    }
+
+    #[test]
+    fn packet_number_test() {
+        let mut checker = checker();
+        let space = PacketNumberSpace::ApplicationData;
+
+        checker.on_packet_sent(space, 0);
+        checker.on_packet_sent(space, 2);
+        // packet numbers are tracked separately in each space
+        checker.on_packet_sent(PacketNumberSpace::Initial, 1);
+        assert!(checker.violations().is_empty());
+
+        checker.on_packet_sent(space, 2);
+        assert_eq!(
+            checker.violations(),
+            [Violation::PacketNumberNotIncreasing {
+                space,
+                previous: 2,
+                packet_number: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn ack_test() {
+        let mut checker = checker();
+        let space = PacketNumberSpace::Handshake;
+
+        checker.on_packet_sent(space, 0);
+        checker.on_packet_skipped(space, 1);
+        checker.on_packet_sent(space, 2);
+        checker.on_packet_received(space, 5);
+        checker.on_ack_range_received(space, &(2..=2));
+        checker.on_ack_range_sent(space, &(0..=5));
+        assert!(checker.violations().is_empty());
+
+        checker.on_ack_range_received(space, &(0..=2));
+        checker.on_ack_range_received(space, &(3..=3));
+        checker.on_ack_range_sent(space, &(6..=6));
+        checker.on_ack_range_sent(PacketNumberSpace::Initial, &(0..=0));
+        assert_eq!(
+            checker.violations(),
+            [
+                Violation::AckOfUnsentPacket {
+                    space,
+                    packet_number: 1
+                },
+                Violation::AckOfUnsentPacket {
+                    space,
+                    packet_number: 3
+                },
+                Violation::AckOfUnreceivedPacket {
+                    space,
+                    packet_number: 6
+                },
+                Violation::AckOfUnreceivedPacket {
+                    space: PacketNumberSpace::Initial,
+                    packet_number: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_limits_test() {
+        let mut checker = checker();
+
+        // client-initiated bidirectional streams use the peer's remote limit
+        checker.on_stream_data_sent(0, 20);
+        // server-initiated bidirectional streams use the peer's local limit
+        checker.on_stream_data_sent(1, 10);
+        // client-initiated unidirectional streams use the peer's uni limit
+        checker.on_stream_data_sent(2, 30);
+        // retransmissions don't count twice
+        checker.on_stream_data_sent(0, 15);
+        assert!(checker.violations().is_empty());
+
+        checker.on_stream_data_sent(1, 11);
+        checker.on_frame_received(
+            &builder::Frame::MaxStreamData {
+                stream_type: builder::StreamType::Bidirectional,
+                id: 0,
+                value: 25,
+            }
+            .into_event(),
+        );
+        checker.on_stream_data_sent(0, 25);
+        checker.on_stream_data_sent(6, 1);
+        checker.on_frame_received(
+            &builder::Frame::MaxStreams {
+                stream_type: builder::StreamType::Bidirectional,
+                value: 3,
+            }
+            .into_event(),
+        );
+        checker.on_stream_data_sent(8, 1);
+        checker.on_stream_data_sent(12, 1);
+
+        assert_eq!(
+            checker.violations(),
+            [
+                Violation::StreamDataLimitExceeded {
+                    stream_id: 1,
+                    offset: 11,
+                    limit: 10
+                },
+                Violation::StreamLimitExceeded {
+                    stream_id: 6,
+                    limit: 1
+                },
+                Violation::StreamLimitExceeded {
+                    stream_id: 12,
+                    limit: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn connection_limit_test() {
+        let mut checker = checker();
+
+        checker.on_stream_data_sent(0, 20);
+        checker.on_frame_received(
+            &builder::Frame::MaxStreamData {
+                stream_type: builder::StreamType::Bidirectional,
+                id: 0,
+                value: 200,
+            }
+            .into_event(),
+        );
+        checker.on_stream_data_sent(0, 100);
+        assert!(checker.violations().is_empty());
+
+        checker.on_stream_data_sent(0, 101);
+        checker.on_frame_received(&builder::Frame::MaxData { value: 150 }.into_event());
+        checker.on_stream_data_sent(0, 150);
+        assert_eq!(
+            checker.violations(),
+            [Violation::ConnectionDataLimitExceeded {
+                offset: 101,
+                limit: 100
+            }]
+        );
+    }
+}
@@ -209,6 +209,21 @@ impl event::Subscriber for Subscriber {
         self.counters.lost_packets.fetch_add(1, Ordering::Relaxed);
     }
 
+    #[inline]
+    fn on_handshake_status_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::HandshakeStatusUpdated,
+    ) {
+        if matches!(
+            event.status,
+            event::events::HandshakeStatus::Complete { .. }
+        ) {
+            self.counters.handshakes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
     #[inline]
     fn on_platform_event_loop_wakeup(
         &mut self,
@@ -272,6 +287,7 @@ struct Counters {
     pto_count: AtomicU64,
     max_pacing_rate: AtomicU64,
     max_delivery_rate: AtomicU64,
+    handshakes: AtomicU64,
     last_updated_micros: AtomicU64,
 }
 
@@ -292,7 +308,8 @@ impl Counters {
                     Max SRTT\t\
                     PTO Count\t\
                     Max Pacing Rate\t\
-                    Max Delivery Rate"
+                    Max Delivery Rate\t\
+                    Handshake Rate"
                 );
             }
         }
@@ -312,6 +329,9 @@ impl Counters {
         // The goodput of data received from the peer
         let receive_progress = self.receive_progress.swap(0, Ordering::Relaxed);
         let receive_rate = rate(receive_progress, duration, format);
+        // The number of handshakes completed per second during the interval
+        let handshakes = self.handshakes.swap(0, Ordering::Relaxed);
+        let handshake_rate = (handshakes as f64 / duration.as_secs_f64()) as u64;
         // The maximum congestion window observed during the interval
         let max_cwnd = self.max_cwnd.swap(0, Ordering::Relaxed);
         let max_cwnd = bytes(max_cwnd, format);
@@ -355,7 +375,8 @@ impl Counters {
                     {max_smoothed_rtt:?}\t\
                     {pto_count}\t\
                     {max_pacing_rate}\t\
-                    {max_delivery_rate}",
+                    {max_delivery_rate}\t\
+                    {handshake_rate}/s",
                 );
             }
             Format::TSV_RAW => {
@@ -372,7 +393,8 @@ impl Counters {
                     {max_smoothed_rtt_micros}\t\
                     {pto_count}\t\
                     {max_pacing_rate}\t\
-                    {max_delivery_rate}",
+                    {max_delivery_rate}\t\
+                    {handshake_rate}",
                     duration_micros = duration.as_micros(),
                     max_rtt_micros = max_rtt.as_micros(),
                     max_smoothed_rtt_micros = max_smoothed_rtt.as_micros()
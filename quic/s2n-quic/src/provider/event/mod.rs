@@ -9,7 +9,7 @@ pub use s2n_quic_core::{
     event::{
         api as events,
         api::{ConnectionInfo, ConnectionMeta},
-        supervisor, Event, Meta, Subscriber, Timestamp,
+        supervisor, Event, Level, Meta, SampleRate, Subscriber, Timestamp,
     },
     query,
 };
@@ -25,6 +25,12 @@ pub trait Provider {
 /// Provides an implementation to disable all events
 pub mod disabled;
 
+/// Provides a subscriber which captures the most recent datagrams sent by each connection
+pub mod capture;
+
+/// Provides a subscriber which checks that the endpoints of each connection follow the protocol
+pub mod conformance;
+
 /// This module contains event integration with [`tracing`](https://docs.rs/tracing)
 #[cfg(any(feature = "provider-event-tracing", test))]
 pub mod tracing;
@@ -3,7 +3,9 @@
 
 //! Provides IO support for an endpoint
 
+use cfg_if::cfg_if;
 use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress, path::Handle as PathHandle};
+#[cfg(feature = "tokio-runtime")]
 use std::io;
 
 pub trait Provider: 'static {
@@ -16,6 +18,15 @@ pub trait Provider: 'static {
     ) -> Result<SocketAddress, Self::Error>;
 }
 
+#[cfg(any(test, feature = "unstable-provider-io-capture"))]
+pub mod capture;
+
+#[cfg(any(test, feature = "unstable-provider-io-detached"))]
+pub mod detached;
+
+#[cfg(any(test, feature = "unstable-provider-io-runtime"))]
+pub mod runtime;
+
 #[cfg(any(test, feature = "unstable-provider-io-testing"))]
 pub mod testing;
 
@@ -25,12 +36,46 @@ pub mod turmoil;
 #[cfg(feature = "unstable-provider-io-xdp")]
 pub mod xdp;
 
+#[cfg(feature = "tokio-runtime")]
 pub mod tokio;
 
-pub use self::tokio as default;
+cfg_if! {
+    if #[cfg(feature = "tokio-runtime")] {
+        pub use self::tokio as default;
+    } else {
+        pub mod default {
+            //! Provides a placeholder IO provider for when the `tokio-runtime` feature is disabled
+            //!
+            //! An IO provider must be configured on the endpoint builder with `with_io`.
+
+            use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress, path::Tuple};
+            use std::io;
+
+            #[derive(Debug, Default)]
+            #[non_exhaustive]
+            pub struct Provider;
+
+            impl super::Provider for Provider {
+                type PathHandle = Tuple;
+                type Error = io::Error;
+
+                fn start<E: Endpoint<PathHandle = Self::PathHandle>>(
+                    self,
+                    _endpoint: E,
+                ) -> Result<SocketAddress, Self::Error> {
+                    Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "the `tokio-runtime` feature is disabled; an IO provider must be configured",
+                    ))
+                }
+            }
+        }
+    }
+}
 
 pub use default::Provider as Default;
 
+#[cfg(feature = "tokio-runtime")]
 impl TryInto for u16 {
     type Error = io::Error;
     type Provider = Default;
@@ -42,6 +87,7 @@ impl TryInto for u16 {
 
 macro_rules! impl_socket_addrs {
     ($ty:ty) => {
+        #[cfg(feature = "tokio-runtime")]
         impl TryInto for $ty {
             type Error = io::Error;
             type Provider = Default;
@@ -0,0 +1,407 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An IO provider which captures every datagram sent and received by the endpoint to a pcapng
+//! file
+//!
+//! If TLS key logging is enabled, the logged secrets are embedded in the capture as decryption
+//! secrets blocks, which allows tools like Wireshark to decrypt the capture without any other
+//! configuration.
+
+use core::task::{Context, Poll};
+use s2n_quic_core::{
+    endpoint::{self, CloseError},
+    inet::{datagram, ExplicitCongestionNotification, SocketAddress},
+    io::{
+        pcapng::{self, Direction},
+        rx, tx,
+    },
+    path::{mtu, Handle as _, LocalAddress, RemoteAddress},
+    time::{Clock, Timestamp},
+};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Wraps an IO provider and captures the datagrams it sends and receives
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use s2n_quic::{provider::io::{self, capture}, Server};
+///
+/// let io = io::Default::new("127.0.0.1:4433")?;
+/// let io = capture::Provider::new(io, "server.pcapng")?;
+///
+/// let server = Server::builder()
+///     .with_io(io)?
+///     .with_tls((cert, key))?
+///     .start()?;
+/// ```
+pub struct Provider<P> {
+    io: P,
+    capture: Capture,
+}
+
+impl<P: super::Provider> Provider<P> {
+    /// Captures the datagrams of the `io` provider into a new file at `path`
+    pub fn new<F: AsRef<Path>>(io: P, path: F) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Self::with_output(io, BufWriter::new(file))
+    }
+
+    /// Captures the datagrams of the `io` provider into `output`
+    ///
+    /// If the `SSLKEYLOGFILE` environment variable is set, the secrets logged to it are embedded
+    /// in the capture.
+    pub fn with_output<W: 'static + Write + Send>(io: P, output: W) -> io::Result<Self> {
+        let writer = pcapng::Writer::new(Box::new(output) as Box<dyn Write + Send>)?;
+        let key_log = std::env::var_os("SSLKEYLOGFILE").map(|path| KeyLog::new(path.into()));
+
+        Ok(Self {
+            io,
+            capture: Capture { writer, key_log },
+        })
+    }
+
+    /// Embeds the secrets logged to the key log file at `path` in the capture
+    ///
+    /// This overrides the file configured with the `SSLKEYLOGFILE` environment variable.
+    pub fn with_key_log_file<F: Into<PathBuf>>(mut self, path: F) -> Self {
+        self.capture.key_log = Some(KeyLog::new(path.into()));
+        self
+    }
+}
+
+impl<P: super::Provider> super::Provider for Provider<P> {
+    type PathHandle = P::PathHandle;
+    type Error = P::Error;
+
+    fn start<E: endpoint::Endpoint<PathHandle = Self::PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> Result<SocketAddress, Self::Error> {
+        let endpoint = Endpoint {
+            endpoint,
+            capture: self.capture,
+        };
+        self.io.start(endpoint)
+    }
+}
+
+struct Capture {
+    writer: pcapng::Writer<Box<dyn Write + Send>>,
+    key_log: Option<KeyLog>,
+}
+
+impl Capture {
+    fn on_datagram(
+        &mut self,
+        direction: Direction,
+        local_address: LocalAddress,
+        remote_address: RemoteAddress,
+        payload: &[u8],
+    ) {
+        // the capture is best-effort so failing to write it shouldn't affect the endpoint
+        let _ = self.write_datagram(direction, local_address, remote_address, payload);
+    }
+
+    fn write_datagram(
+        &mut self,
+        direction: Direction,
+        local_address: LocalAddress,
+        remote_address: RemoteAddress,
+        payload: &[u8],
+    ) -> io::Result<()> {
+        // write any new secrets first so they precede the packets they protect
+        if let Some(key_log) = self.key_log.as_mut() {
+            let secrets = key_log.poll();
+            self.writer.write_key_log(secrets)?;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+
+        self.writer.write_datagram(&pcapng::Datagram {
+            timestamp,
+            direction,
+            local_address: (*local_address).into(),
+            remote_address: (*remote_address).into(),
+            payload,
+            payload_len: payload.len(),
+            comment: None,
+        })
+    }
+
+    fn flush(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+/// Follows the secrets appended to a key log file
+struct KeyLog {
+    path: PathBuf,
+    file: Option<File>,
+    /// The length of the file when the capture started
+    ///
+    /// Only secrets logged after the capture started are relevant.
+    offset: u64,
+    /// Secrets which have been read from the file, up to the last complete line
+    buffer: Vec<u8>,
+    /// The number of bytes in `buffer` which have already been written to the capture
+    consumed: usize,
+}
+
+impl KeyLog {
+    fn new(path: PathBuf) -> Self {
+        let offset = std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        Self {
+            path,
+            file: None,
+            offset,
+            buffer: Vec::new(),
+            consumed: 0,
+        }
+    }
+
+    /// Returns the complete lines which have been appended to the key log since the last call
+    fn poll(&mut self) -> &[u8] {
+        // discard the lines returned by the previous call, keeping any partial line
+        self.buffer.drain(..self.consumed);
+        self.consumed = 0;
+
+        if self.file.is_none() {
+            // the file is created by the TLS provider once the first secret is logged
+            let Ok(mut file) = File::open(&self.path) else {
+                return &[];
+            };
+            if file.seek(SeekFrom::Start(self.offset)).is_err() {
+                return &[];
+            }
+            self.file = Some(file);
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            let _ = file.read_to_end(&mut self.buffer);
+        }
+
+        self.consumed = self
+            .buffer
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |index| index + 1);
+
+        &self.buffer[..self.consumed]
+    }
+}
+
+struct Endpoint<E> {
+    endpoint: E,
+    capture: Capture,
+}
+
+impl<E: endpoint::Endpoint> endpoint::Endpoint for Endpoint<E> {
+    type PathHandle = E::PathHandle;
+    type Subscriber = E::Subscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = E::ENDPOINT_TYPE;
+
+    #[inline]
+    fn receive<Rx, C>(&mut self, rx: &mut Rx, clock: &C)
+    where
+        Rx: rx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        let mut rx = RxQueue {
+            rx,
+            capture: &mut self.capture,
+        };
+        self.endpoint.receive(&mut rx, clock);
+        self.capture.flush();
+    }
+
+    #[inline]
+    fn transmit<Tx, C>(&mut self, tx: &mut Tx, clock: &C)
+    where
+        Tx: tx::Queue<Handle = Self::PathHandle>,
+        C: Clock,
+    {
+        let mut tx = TxQueue {
+            tx,
+            capture: &mut self.capture,
+        };
+        self.endpoint.transmit(&mut tx, clock);
+        self.capture.flush();
+    }
+
+    #[inline]
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        cx: &mut Context<'_>,
+        clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        self.endpoint.poll_wakeups(cx, clock)
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Timestamp> {
+        self.endpoint.timeout()
+    }
+
+    #[inline]
+    fn set_mtu_config(&mut self, mtu_config: mtu::Config) {
+        self.endpoint.set_mtu_config(mtu_config)
+    }
+
+    #[inline]
+    fn set_max_wakeups(&mut self, max_wakeups: usize) {
+        self.endpoint.set_max_wakeups(max_wakeups)
+    }
+
+    #[inline]
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        self.endpoint.subscriber()
+    }
+}
+
+struct RxQueue<'a, Rx> {
+    rx: &'a mut Rx,
+    capture: &'a mut Capture,
+}
+
+impl<'a, Rx: rx::Queue> rx::Queue for RxQueue<'a, Rx> {
+    type Handle = Rx::Handle;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        let capture = &mut *self.capture;
+        self.rx.for_each(|header, payload| {
+            // capture the datagram before the endpoint decrypts it in place
+            capture.on_datagram(
+                Direction::Inbound,
+                header.path.local_address(),
+                header.path.remote_address(),
+                payload,
+            );
+            on_packet(header, payload)
+        })
+    }
+
+    #[inline]
+    fn for_each_error<F: FnMut(RemoteAddress, rx::SocketError, &[u8])>(&mut self, on_error: F) {
+        self.rx.for_each_error(on_error)
+    }
+
+    #[inline]
+    fn take_dropped_datagrams(&mut self) -> usize {
+        self.rx.take_dropped_datagrams()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.rx.is_empty()
+    }
+}
+
+struct TxQueue<'a, Tx> {
+    tx: &'a mut Tx,
+    capture: &'a mut Capture,
+}
+
+impl<'a, Tx: tx::Queue> tx::Queue for TxQueue<'a, Tx> {
+    type Handle = Tx::Handle;
+
+    const SUPPORTS_ECN: bool = Tx::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Tx::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Tx::SUPPORTS_FLOW_LABELS;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        let message = TxMessage {
+            message,
+            capture: self.capture,
+        };
+        self.tx.push(message)
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.tx.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.tx.capacity()
+    }
+
+    #[inline]
+    fn has_capacity(&self) -> bool {
+        self.tx.has_capacity()
+    }
+}
+
+struct TxMessage<'a, M> {
+    message: M,
+    capture: &'a mut Capture,
+}
+
+impl<'a, M: tx::Message> tx::Message for TxMessage<'a, M> {
+    type Handle = M::Handle;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        self.message.path_handle()
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        self.message.ecn()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> core::time::Duration {
+        self.message.delay()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        self.message.ipv6_flow_label()
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, segment_count: usize) -> bool {
+        self.message.can_gso(segment_len, segment_count)
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        buffer: tx::PayloadBuffer,
+        gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        // Safety: the buffer is handed back to the message unchanged and is only read after the
+        // message is done writing to it
+        let buffer = unsafe { buffer.into_mut_slice() };
+        let len = self
+            .message
+            .write_payload(tx::PayloadBuffer::new(&mut *buffer), gso_offset)?;
+
+        let path = self.message.path_handle();
+        let (local_address, remote_address) = (path.local_address(), path.remote_address());
+        self.capture.on_datagram(
+            Direction::Outbound,
+            local_address,
+            remote_address,
+            &buffer[..len],
+        );
+
+        Ok(len)
+    }
+}
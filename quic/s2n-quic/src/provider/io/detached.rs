@@ -0,0 +1,43 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides an implementation of the [`io::Provider`](crate::provider::io::Provider)
+//! which is driven by the application.
+//!
+//! The application owns the socket and the event loop. It passes received datagrams to the
+//! [`Handle`], polls it for datagrams to send, and calls back into it when the timeout expires:
+//!
+//! ```rust,ignore
+//! let io = detached::Provider::builder().build()?;
+//! let handle = io.handle();
+//! let server = Server::builder().with_io(io)?.with_tls((cert, key))?.start()?;
+//!
+//! loop {
+//!     let now = Instant::now();
+//!     while let Some((len, remote_address)) = socket.try_recv_from(&mut buffer) {
+//!         handle.receive(now, remote_address.into(), Default::default(), &mut buffer[..len]);
+//!     }
+//!     while let Some(transmit) = handle.poll_transmit(now, &mut buffer) {
+//!         socket.send_to(&buffer[..transmit.len], transmit.remote_address.into());
+//!     }
+//!     // wait for the socket to be readable, `handle.timeout()`, or an application wakeup
+//! }
+//! ```
+
+use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress};
+use s2n_quic_platform::io::detached;
+use std::io;
+
+pub use self::detached::{Builder, Handle, Io as Provider, PathHandle, Transmit};
+
+impl super::Provider for Provider {
+    type PathHandle = PathHandle;
+    type Error = io::Error;
+
+    fn start<E: Endpoint<PathHandle = Self::PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> Result<SocketAddress, Self::Error> {
+        Provider::start(self, endpoint)
+    }
+}
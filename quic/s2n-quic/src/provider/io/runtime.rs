@@ -0,0 +1,43 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides an implementation of the [`io::Provider`](crate::provider::io::Provider)
+//! which can be driven by any async runtime.
+//!
+//! The runtime is responsible for spawning tasks, providing timers, and notifying the socket
+//! tasks of readiness, as defined by the [`Runtime`] trait:
+//!
+//! ```rust,ignore
+//! use s2n_quic::{provider::io::runtime, Server};
+//!
+//! let runtime = runtime::AsyncIo::new(|task| smol::spawn(task).detach());
+//! let io = runtime::Provider::new(runtime, "127.0.0.1:443")?;
+//! let server = Server::builder().with_io(io)?.with_tls((cert, key))?.start()?;
+//! ```
+
+use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress, time::ClockWithTimer};
+use s2n_quic_platform::io::runtime;
+use std::io;
+
+pub use self::runtime::{Builder, Io as Provider, PathHandle, Runtime, Socket};
+
+#[cfg(feature = "unstable-provider-io-async-io")]
+pub use self::runtime::async_io::{AsyncIo, Task};
+#[cfg(all(feature = "tokio-runtime", unix))]
+pub use self::runtime::tokio::Tokio;
+
+impl<R> super::Provider for Provider<R>
+where
+    R: Runtime,
+    <R::Clock as ClockWithTimer>::Timer: Send,
+{
+    type PathHandle = PathHandle;
+    type Error = io::Error;
+
+    fn start<E: Endpoint<PathHandle = Self::PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> Result<SocketAddress, Self::Error> {
+        Provider::start(self, endpoint)
+    }
+}
@@ -24,6 +24,34 @@ impl super::Provider for Io {
     }
 }
 
+/// A random provider backed by the seeded generator of the test executor
+///
+/// Endpoints configured with this provider draw connection IDs, packet number skips, and other
+/// endpoint randomness from the executor's seed, which makes simulations reproducible.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Random;
+
+impl crate::provider::random::Generator for Random {
+    #[inline]
+    fn public_random_fill(&mut self, dest: &mut [u8]) {
+        rand::fill_bytes(dest);
+    }
+
+    #[inline]
+    fn private_random_fill(&mut self, dest: &mut [u8]) {
+        rand::fill_bytes(dest);
+    }
+}
+
+impl crate::provider::random::Provider for Random {
+    type Generator = Self;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Generator, Self::Error> {
+        Ok(self)
+    }
+}
+
 /// Runs a single test with the given network
 ///
 /// Returns the total runtime of the test
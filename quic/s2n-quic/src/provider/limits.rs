@@ -3,7 +3,8 @@
 
 //! Provides limits support for a connection
 
-pub use s2n_quic_core::connection::limits::{ConnectionInfo, Limiter, Limits};
+pub use s2n_quic_core::connection::limits::{ConnectionInfo, InitialPadding, Limiter, Limits};
+use std::sync::{Arc, Mutex};
 
 pub trait Provider {
     type Limits: 'static + Send + Limiter;
@@ -25,6 +26,75 @@ impl<T: 'static + Send + Limiter> Provider for T {
     }
 }
 
+/// A [`Limiter`] with limits which can be updated while the endpoint is running
+///
+/// Connections pick up the limits which are current at the time they are created. Existing
+/// connections are not affected by an update.
+///
+/// Only the per-connection [`Limits`] can be reloaded. Endpoint-wide limits, such as the number
+/// of in-flight handshakes, are configured with the
+/// [`endpoint_limits`](crate::provider::endpoint_limits) provider and are fixed once the endpoint
+/// has started.
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// use s2n_quic::{provider::limits, Server};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let (limits, handle) = limits::Reloadable::new(limits::Limits::default());
+///
+/// let server = Server::builder()
+///     .with_limits(limits)?
+///     .with_io("127.0.0.1:443")?
+///     .start()?;
+///
+/// // later, while the server is running
+/// handle.update(limits::Limits::default().with_data_window(1_000_000)?);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Reloadable {
+    limits: Arc<Mutex<Limits>>,
+}
+
+impl Reloadable {
+    /// Creates a reloadable limiter along with a handle for updating its limits
+    pub fn new(limits: Limits) -> (Self, ReloadHandle) {
+        let limits = Arc::new(Mutex::new(limits));
+        let handle = ReloadHandle {
+            limits: limits.clone(),
+        };
+        (Self { limits }, handle)
+    }
+}
+
+impl Limiter for Reloadable {
+    #[inline]
+    fn on_connection(&mut self, _info: &ConnectionInfo) -> Limits {
+        *self.limits.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Updates the limits of a [`Reloadable`] limiter
+#[derive(Clone, Debug)]
+pub struct ReloadHandle {
+    limits: Arc<Mutex<Limits>>,
+}
+
+impl ReloadHandle {
+    /// Replaces the limits applied to new connections
+    pub fn update(&self, limits: Limits) {
+        *self.limits.lock().unwrap_or_else(|e| e.into_inner()) = limits;
+    }
+
+    /// Returns the limits currently applied to new connections
+    pub fn limits(&self) -> Limits {
+        *self.limits.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
 pub mod default {
     #[derive(Debug, Default)]
     pub struct Provider(());
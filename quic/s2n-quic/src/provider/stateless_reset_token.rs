@@ -38,7 +38,9 @@ impl_provider_utils!();
 mod random {
     use core::convert::Infallible;
     use rand::prelude::*;
-    use s2n_quic_core::{frame::new_connection_id::STATELESS_RESET_TOKEN_LEN, stateless_reset};
+    use s2n_quic_core::{
+        frame::new_connection_id::STATELESS_RESET_TOKEN_LEN, random, stateless_reset,
+    };
 
     /// Randomly generated stateless reset token.
     ///
@@ -86,6 +88,16 @@ mod random {
             rand::thread_rng().fill_bytes(&mut token);
             token.into()
         }
+
+        fn generate_with_random(
+            &mut self,
+            _local_connection_id: &[u8],
+            random: &mut dyn random::Generator,
+        ) -> stateless_reset::Token {
+            let mut token = [0u8; STATELESS_RESET_TOKEN_LEN];
+            random.private_random_fill(&mut token);
+            token.into()
+        }
     }
 
     #[cfg(test)]
@@ -103,5 +115,20 @@ mod random {
 
             assert_ne!(token_1, token_2);
         }
+
+        #[test]
+        fn stateless_reset_token_with_random_test() {
+            let id = connection::LocalId::try_from_bytes(b"id01").unwrap();
+
+            let mut generator = Generator::default();
+            let mut random = random::testing::Generator(123);
+            let token_1 = generator.generate_with_random(id.as_bytes(), &mut random);
+
+            let mut random = random::testing::Generator(123);
+            let token_2 = generator.generate_with_random(id.as_bytes(), &mut random);
+
+            // the same random state produces the same token
+            assert_eq!(token_1, token_2);
+        }
     }
 }
@@ -4,7 +4,9 @@
 //! Provides TLS support for an endpoint
 
 use cfg_if::cfg_if;
-use s2n_quic_core::crypto;
+use s2n_codec::EncoderValue;
+use s2n_quic_core::{application::ServerName, crypto};
+use std::sync::{Arc, Mutex};
 
 pub trait Provider {
     type Server: 'static + crypto::tls::Endpoint;
@@ -187,6 +189,163 @@ impl Provider for &str {
     }
 }
 
+/// A TLS endpoint which can be replaced while the QUIC endpoint is running
+///
+/// This can be used to rotate certificates without restarting the endpoint. New connections
+/// use the endpoint which is current at the time they are created. Existing connections keep
+/// the TLS configuration they were created with.
+///
+/// The reported `max_tag_length` covers both the current endpoint and one which has been
+/// reloaded but not yet picked up by a new connection.
+///
+/// ```rust,no_run
+/// # use std::{error::Error, path::Path};
+/// use s2n_quic::{provider::tls, Server};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let endpoint = tls::default::Server::builder()
+///     .with_certificate(Path::new("./certs/cert.pem"), Path::new("./certs/key.pem"))?
+///     .build()?;
+/// let (endpoint, handle) = tls::Reloadable::new(endpoint);
+///
+/// let server = Server::builder()
+///     .with_tls(endpoint)?
+///     .with_io("127.0.0.1:443")?
+///     .start()?;
+///
+/// // later, once the certificate has been renewed
+/// let endpoint = tls::default::Server::builder()
+///     .with_certificate(Path::new("./certs/cert.pem"), Path::new("./certs/key.pem"))?
+///     .build()?;
+/// handle.reload(endpoint);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+pub struct Reloadable<E> {
+    endpoint: E,
+    pending: Arc<Mutex<Option<E>>>,
+}
+
+impl<E> Reloadable<E> {
+    /// Creates a reloadable endpoint along with a handle for replacing it
+    pub fn new(endpoint: E) -> (Self, ReloadHandle<E>) {
+        let pending = Arc::new(Mutex::new(None));
+        let handle = ReloadHandle {
+            pending: pending.clone(),
+        };
+        (Self { endpoint, pending }, handle)
+    }
+
+    #[inline]
+    fn poll_reload(&mut self) {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(endpoint) = pending {
+            self.endpoint = endpoint;
+        }
+    }
+}
+
+impl<E> core::fmt::Debug for Reloadable<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Reloadable").finish_non_exhaustive()
+    }
+}
+
+impl<E: crypto::tls::Endpoint> crypto::tls::Endpoint for Reloadable<E> {
+    type Session = E::Session;
+
+    fn new_server_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        self.poll_reload();
+        self.endpoint.new_server_session(transport_parameters)
+    }
+
+    fn new_client_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+        server_name: ServerName,
+    ) -> Self::Session {
+        self.poll_reload();
+        self.endpoint
+            .new_client_session(transport_parameters, server_name)
+    }
+
+    /// Returns the largest tag length of the current and pending endpoints
+    ///
+    /// The transport uses this value to size stateless resets, which may be sent on behalf of
+    /// connections created by either endpoint.
+    fn max_tag_length(&self) -> usize {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map_or(0, |endpoint| endpoint.max_tag_length());
+        self.endpoint.max_tag_length().max(pending)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn new_server_one_rtt_keys(
+        &mut self,
+        secrets: &crypto::tls::OneRttSecrets,
+    ) -> Option<(
+        <Self::Session as crypto::CryptoSuite>::OneRttKey,
+        <Self::Session as crypto::CryptoSuite>::OneRttHeaderKey,
+    )> {
+        self.poll_reload();
+        self.endpoint.new_server_one_rtt_keys(secrets)
+    }
+}
+
+impl<E: crypto::tls::Endpoint> Provider for Reloadable<E> {
+    type Server = Self;
+    type Client = Self;
+    type Error = core::convert::Infallible;
+
+    fn start_server(self) -> Result<Self::Server, Self::Error> {
+        Ok(self)
+    }
+
+    fn start_client(self) -> Result<Self::Client, Self::Error> {
+        Ok(self)
+    }
+}
+
+/// Replaces the endpoint of a [`Reloadable`] TLS endpoint
+pub struct ReloadHandle<E> {
+    pending: Arc<Mutex<Option<E>>>,
+}
+
+impl<E> ReloadHandle<E> {
+    /// Replaces the endpoint used for new connections
+    ///
+    /// The new endpoint is used starting with the next connection.
+    pub fn reload(&self, endpoint: E) {
+        *self.pending.lock().unwrap_or_else(|e| e.into_inner()) = Some(endpoint);
+    }
+}
+
+impl<E> Clone for ReloadHandle<E> {
+    fn clone(&self) -> Self {
+        Self {
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<E> core::fmt::Debug for ReloadHandle<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReloadHandle").finish_non_exhaustive()
+    }
+}
+
 #[cfg(feature = "provider-tls-default")]
 mod default_tls {
     pub use s2n_quic_tls_default::*;
@@ -0,0 +1,199 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides storage for the address validation tokens a client receives from servers
+//!
+//! Servers may deliver tokens in NEW_TOKEN frames, which the client presents in the first
+//! Initial packet of a later connection to the same server. This allows the server to validate
+//! the client's address without the round trip of a Retry packet. By default, tokens are kept
+//! in memory by [`Lru`]. Applications which want tokens to outlive the client can implement
+//! [`Cache`] on top of persistent storage.
+
+use bytes::Bytes;
+use s2n_quic_core::application::ServerName;
+use std::{collections::HashMap, net::SocketAddr};
+
+// these imports are only accessible if the unstable feature is enabled
+#[allow(unused_imports)]
+pub use s2n_quic_core::token::cache::{Cache, Disabled, Server};
+
+pub trait Provider {
+    type Cache: 'static + Cache;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Cache, Self::Error>;
+}
+
+impl_provider_utils!();
+
+pub type Default = Lru;
+
+impl<T: 'static + Send + Cache> Provider for T {
+    type Cache = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Cache, Self::Error> {
+        Ok(self)
+    }
+}
+
+/// An in-memory [`Cache`] which keeps the most recent token for each server
+///
+/// Once the cache is full, the server which least recently delivered a token is evicted.
+#[derive(Debug)]
+pub struct Lru {
+    capacity: usize,
+    entries: HashMap<Key, Entry>,
+    generation: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    server_name: Option<ServerName>,
+    remote_address: SocketAddr,
+}
+
+impl From<&Server<'_>> for Key {
+    fn from(server: &Server) -> Self {
+        Self {
+            server_name: server.server_name.cloned(),
+            remote_address: (&server.remote_address).into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    token: Bytes,
+    generation: u64,
+}
+
+impl Lru {
+    /// The number of servers tracked by the default cache
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Creates a cache which holds tokens for up to `capacity` servers
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            generation: 0,
+        }
+    }
+}
+
+impl core::default::Default for Lru {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl Cache for Lru {
+    fn on_new_token(&mut self, server: &Server, token: &[u8]) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.generation += 1;
+        self.entries.insert(
+            server.into(),
+            Entry {
+                token: Bytes::copy_from_slice(token),
+                generation: self.generation,
+            },
+        );
+
+        if self.entries.len() > self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_key, entry)| entry.generation)
+                .map(|(key, _entry)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn take_token(&mut self, server: &Server) -> Option<Bytes> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A client MUST NOT include
+        //# a token that is not applicable to the server that it is connecting
+        //# to, unless the client has the knowledge that the server that issued
+        //# the token and the server the client is connecting to are jointly
+        //# managing the tokens.
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.3
+        //# A client SHOULD NOT reuse a token from a NEW_TOKEN frame for
+        //# different connection attempts.
+        self.entries
+            .remove(&Key::from(server))
+            .map(|entry| entry.token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::inet::SocketAddress;
+
+    fn server(name: &'static str, port: u16) -> (ServerName, SocketAddress) {
+        let address: SocketAddr = ([127, 0, 0, 1], port).into();
+        (name.into(), address.into())
+    }
+
+    #[test]
+    fn single_use_test() {
+        let mut cache = Lru::default();
+        let (name, address) = server("a.example.com", 443);
+        let server = Server::new(Some(&name), &address);
+
+        cache.on_new_token(&server, b"first");
+        cache.on_new_token(&server, b"second");
+
+        // only the most recent token is kept and it can only be taken once
+        assert_eq!(cache.take_token(&server).as_deref(), Some(&b"second"[..]));
+        assert_eq!(cache.take_token(&server), None);
+    }
+
+    #[test]
+    fn server_isolation_test() {
+        let mut cache = Lru::default();
+        let (name, address) = server("a.example.com", 443);
+        let (other_name, other_address) = server("b.example.com", 4433);
+
+        cache.on_new_token(&Server::new(Some(&name), &address), b"token");
+
+        assert_eq!(
+            cache.take_token(&Server::new(Some(&other_name), &address)),
+            None
+        );
+        assert_eq!(
+            cache.take_token(&Server::new(Some(&name), &other_address)),
+            None
+        );
+        assert_eq!(cache.take_token(&Server::new(None, &address)), None);
+        assert!(cache
+            .take_token(&Server::new(Some(&name), &address))
+            .is_some());
+    }
+
+    #[test]
+    fn eviction_test() {
+        let mut cache = Lru::new(2);
+        let servers: Vec<_> = (0..3).map(|port| server("example.com", port)).collect();
+
+        for (name, address) in &servers {
+            cache.on_new_token(&Server::new(Some(name), address), b"token");
+        }
+
+        // the oldest server is evicted once the capacity is exceeded
+        let (name, address) = &servers[0];
+        assert_eq!(cache.take_token(&Server::new(Some(name), address)), None);
+        for (name, address) in &servers[1..] {
+            assert!(cache
+                .take_token(&Server::new(Some(name), address))
+                .is_some());
+        }
+    }
+}
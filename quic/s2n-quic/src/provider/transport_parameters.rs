@@ -0,0 +1,51 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides support for application-defined transport parameters
+//!
+//! Custom parameters are sent to the peer along with the transport parameters of the
+//! connection. Parameters received from the peer can be read with
+//! [`Connection::peer_transport_parameter`](crate::Connection::peer_transport_parameter).
+//!
+//! Application settings can be added for each supported application protocol with
+//! [`Parameters::with_application_settings`]. Only the settings sent by the peer for the
+//! negotiated protocol are made available by
+//! [`Connection::peer_application_settings`](crate::Connection::peer_application_settings).
+//!
+//! ```rust,ignore
+//! use s2n_quic::{provider::transport_parameters::Parameters, Server};
+//! use s2n_quic_core::varint::VarInt;
+//!
+//! let parameters = Parameters::default().with(VarInt::from_u16(0x2ab2), &b"hello"[..])?;
+//!
+//! let server = Server::builder()
+//!     .with_transport_parameters(parameters)?
+//!     .start()?;
+//! ```
+
+use s2n_quic_core::transport::parameters::custom::Disabled;
+
+// these imports are only accessible if the unstable feature is enabled
+#[allow(unused_imports)]
+pub use s2n_quic_core::transport::parameters::custom::{ConnectionInfo, Endpoint, Parameters};
+
+pub trait Provider {
+    type Endpoint: Endpoint;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error>;
+}
+
+impl_provider_utils!();
+
+// This provider is disabled by default
+pub type Default = Disabled;
+
+impl<T: 'static + Send + Endpoint> Provider for T {
+    type Endpoint = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error> {
+        Ok(self)
+    }
+}
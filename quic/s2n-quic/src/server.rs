@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{connection::Connection, provider::*};
+use crate::{
+    connection::{self, Connecting, Connection},
+    provider::*,
+};
 use core::{
     fmt,
     task::{Context, Poll},
@@ -18,6 +21,11 @@ pub use s2n_quic_core::application::ServerName as Name;
 /// A QUIC server endpoint, capable of accepting connections
 pub struct Server {
     acceptor: Acceptor,
+    /// Connections which were accepted early and are waiting on the handshake to complete
+    connecting: Vec<Connecting>,
+    manager: connection::Manager,
+    #[cfg(any(test, feature = "unstable-connection-handoff"))]
+    importer: s2n_quic_transport::endpoint::import::Importer,
     local_addr: s2n_quic_core::inet::SocketAddress,
 }
 
@@ -95,14 +103,169 @@ impl Server {
     ///   None is returned, this function should not be called again.
     pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
         s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+            // connections are handed over early if the limits allow it, so wait for their
+            // handshakes to complete without blocking the rest of the queue
+            let is_open = loop {
+                match self.acceptor.poll_accept(cx) {
+                    Poll::Ready(Some(connection)) => {
+                        self.connecting.push(Connecting::new(connection))
+                    }
+                    Poll::Ready(None) => break false,
+                    Poll::Pending => break true,
+                }
+            };
+
+            let mut idx = 0;
+            while let Some(connecting) = self.connecting.get_mut(idx) {
+                match connecting.poll_connected(cx) {
+                    Poll::Ready(Ok(connection)) => {
+                        drop(self.connecting.remove(idx));
+                        return Poll::Ready(Some(connection));
+                    }
+                    // the connection failed the handshake so move on to the next one
+                    Poll::Ready(Err(_)) => {
+                        drop(self.connecting.remove(idx));
+                    }
+                    Poll::Pending => idx += 1,
+                }
+            }
+
+            if is_open || !self.connecting.is_empty() {
+                Poll::Pending
+            } else {
+                Poll::Ready(None)
+            }
+        })
+    }
+
+    /// Accepts a new incoming [`Connecting`] handle from this [`Server`].
+    ///
+    /// If [`Limits::with_early_accept`](crate::provider::limits::Limits::with_early_accept) is
+    /// enabled, this function will yield as soon as the client's first flight has been
+    /// processed. Otherwise, the returned handle resolves immediately since the handshake has
+    /// already completed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::{provider::limits::Limits, Server};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let mut server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .with_limits(Limits::default().with_early_accept(true)?)?
+    ///     .start()?;
+    ///
+    /// while let Some(connecting) = server.accept_connecting().await {
+    ///     tokio::spawn(async move {
+    ///         println!("connecting to {:?}", connecting.server_name());
+    ///         // perform any setup for the tenant while the handshake completes
+    ///         let connection = connecting.await?;
+    ///         # let _ = connection;
+    ///         Ok::<_, s2n_quic::connection::Error>(())
+    ///     });
+    /// }
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn accept_connecting(&mut self) -> Option<Connecting> {
+        futures::future::poll_fn(|cx| self.poll_accept_connecting(cx)).await
+    }
+
+    /// Attempts to accept a new incoming [`Connecting`] handle from this [`Server`].
+    ///
+    /// # Return value
+    ///
+    /// This function returns:
+    ///
+    /// - `Poll::Pending` if no new connections have been accepted.
+    /// - `Poll::Ready(Some(connecting))` once a new connection has been accepted.
+    ///   This function can be called again to try and accept new connections.
+    /// - `Poll::Ready(None)` the attempt failed because the server has closed. Once
+    ///   None is returned, this function should not be called again.
+    pub fn poll_accept_connecting(&mut self, cx: &mut Context) -> Poll<Option<Connecting>> {
+        s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+            // hand out any connections previously buffered by `poll_accept` first
+            if !self.connecting.is_empty() {
+                return Poll::Ready(Some(self.connecting.remove(0)));
+            }
+
             match self.acceptor.poll_accept(cx) {
-                Poll::Ready(Some(connection)) => Poll::Ready(Some(Connection::new(connection))),
+                Poll::Ready(Some(connection)) => Poll::Ready(Some(Connecting::new(connection))),
                 Poll::Ready(None) => Poll::Ready(None),
                 Poll::Pending => Poll::Pending,
             }
         })
     }
 
+    /// Returns a [`connection::Manager`] for inspecting and closing the server's connections
+    ///
+    /// The manager can be used to build an administrative interface, for example to list the
+    /// active connections and their statistics or evict a misbehaving peer, without the
+    /// application having to keep track of the accepted connection handles.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let manager = server.manager();
+    ///
+    /// for connection in manager.connections() {
+    ///     if let Some(stats) = manager.statistics(connection.id) {
+    ///         println!("{}: {:?}", connection.remote_address, stats.smoothed_rtt);
+    ///     }
+    /// }
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn manager(&self) -> connection::Manager {
+        self.manager.clone()
+    }
+
+    /// Resumes a connection handed off by another server endpoint
+    ///
+    /// The `state` is returned by [`Connection::handoff`] on the endpoint which completed the
+    /// handshake. The returned future resolves once the connection is ready to be used by the
+    /// application. Imported connections are not returned by [`Self::accept`].
+    ///
+    /// This endpoint must be configured with the same limits and connection ID format as the
+    /// endpoint which handed off the connection, and should only start receiving the
+    /// connection's datagrams after the handoff.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::{connection::handoff, Server};
+    /// #
+    /// # async fn import(bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let state = handoff::State::from_bytes(bytes)?;
+    /// let connection = server.import(state).await?;
+    /// println!("resumed connection: {:?}", connection.remote_addr());
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(test, feature = "unstable-connection-handoff"))]
+    pub fn import(&self, state: connection::handoff::State) -> ConnectionImport {
+        ConnectionImport(self.importer.import(state))
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port `0` to figure out which
@@ -140,3 +303,21 @@ impl futures::stream::Stream for Server {
         self.poll_accept(cx)
     }
 }
+
+/// A future which resolves once a handed off connection has been imported
+#[cfg(any(test, feature = "unstable-connection-handoff"))]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ConnectionImport(s2n_quic_transport::endpoint::import::Import);
+
+#[cfg(any(test, feature = "unstable-connection-handoff"))]
+impl core::future::Future for ConnectionImport {
+    type Output = Result<Connection, connection::Error>;
+
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match core::pin::Pin::new(&mut self.0).poll(cx) {
+            Poll::Ready(Ok(conn)) => Poll::Ready(Ok(Connection::new(conn))),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
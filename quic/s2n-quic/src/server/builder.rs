@@ -351,7 +351,7 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
-    #[cfg(feature = "unstable-provider-datagram")]
+    #[cfg(any(test, feature = "unstable-provider-datagram"))]
     impl_provider_method!(
         /// Sets the datagram provider for the [`Server`]
         with_datagram,
@@ -367,6 +367,30 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-transport-parameters"))]
+    impl_provider_method!(
+        /// Sets the custom transport parameters provider for the [`Server`]
+        with_transport_parameters,
+        transport_parameters,
+        ServerProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-custom-frame"))]
+    impl_provider_method!(
+        /// Sets the custom frame provider for the [`Server`]
+        with_custom_frame,
+        custom_frame,
+        ServerProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-demux"))]
+    impl_provider_method!(
+        /// Sets the demultiplexer provider for the [`Server`]
+        with_demux,
+        demux,
+        ServerProviders
+    );
+
     impl_provider_method!(
         /// Sets the congestion controller provider for the [`Server`]
         with_congestion_controller,
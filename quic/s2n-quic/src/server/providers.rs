@@ -26,6 +26,9 @@ impl_providers_state! {
         address_token: AddressToken,
         datagram: Datagram,
         dc: Dc,
+        transport_parameters: TransportParameters,
+        custom_frame: CustomFrame,
+        demux: Demux,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -50,6 +53,9 @@ impl<
         AddressToken: address_token::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        TransportParameters: transport_parameters::Provider,
+        CustomFrame: custom_frame::Provider,
+        Demux: demux::Provider,
     >
     Providers<
         CongestionController,
@@ -69,6 +75,9 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        TransportParameters,
+        CustomFrame,
+        Demux,
     >
 {
     pub fn start(self) -> Result<Server, StartError> {
@@ -90,6 +99,9 @@ impl<
             tls,
             datagram,
             dc,
+            transport_parameters,
+            custom_frame,
+            demux,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -104,12 +116,17 @@ impl<
         let limits = limits.start().map_err(StartError::new)?;
         let mtu = mtu.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
+        // Maintain the counters for `Connection::stats` alongside the application's subscriber
+        let event = (event, connection::statistics::Subscriber);
         let address_token = address_token.start().map_err(StartError::new)?;
         let sync = sync.start().map_err(StartError::new)?;
         let path_migration = path_migration.start().map_err(StartError::new)?;
         let tls = tls.start_server().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let transport_parameters = transport_parameters.start().map_err(StartError::new)?;
+        let custom_frame = custom_frame.start().map_err(StartError::new)?;
+        let demux = demux.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -142,15 +159,26 @@ impl<
             path_migration,
             datagram,
             dc,
+            transport_parameters,
+            custom_frame,
+            demux,
+            token_cache: Default::default(),
         };
 
         let (endpoint, acceptor) = endpoint::Endpoint::new_server(endpoint_config);
+        let manager = endpoint.manager();
+        #[cfg(any(test, feature = "unstable-connection-handoff"))]
+        let importer = endpoint.importer();
 
         // Start the IO last
         let local_addr = io.start(endpoint).map_err(StartError::new)?;
 
         Ok(Server {
             acceptor,
+            connecting: Vec::new(),
+            manager,
+            #[cfg(any(test, feature = "unstable-connection-handoff"))]
+            importer,
             local_addr,
         })
     }
@@ -175,6 +203,9 @@ struct EndpointConfig<
     AddressToken,
     Datagram,
     Dc,
+    TransportParameters,
+    CustomFrame,
+    Demux,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -193,6 +224,10 @@ struct EndpointConfig<
     path_migration: PathMigration,
     datagram: Datagram,
     dc: Dc,
+    transport_parameters: TransportParameters,
+    custom_frame: CustomFrame,
+    demux: Demux,
+    token_cache: s2n_quic_core::token::cache::Disabled,
 }
 
 impl<
@@ -213,6 +248,9 @@ impl<
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        TransportParameters: s2n_quic_core::transport::parameters::custom::Endpoint,
+        CustomFrame: s2n_quic_core::custom_frame::Endpoint,
+        Demux: s2n_quic_core::demux::Demultiplexer,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -232,6 +270,9 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        TransportParameters,
+        CustomFrame,
+        Demux,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -257,6 +298,9 @@ impl<
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        TransportParameters: s2n_quic_core::transport::parameters::custom::Endpoint,
+        CustomFrame: s2n_quic_core::custom_frame::Endpoint,
+        Demux: s2n_quic_core::demux::Demultiplexer,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -276,6 +320,9 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        TransportParameters,
+        CustomFrame,
+        Demux,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -298,6 +345,10 @@ impl<
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type CustomTransportParameters = TransportParameters;
+    type CustomFrameEndpoint = CustomFrame;
+    type Demultiplexer = Demux;
+    type TokenCache = s2n_quic_core::token::cache::Disabled;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
 
@@ -318,6 +369,10 @@ impl<
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            custom_transport_parameters: &mut self.transport_parameters,
+            custom_frame: &mut self.custom_frame,
+            demux: &mut self.demux,
+            token_cache: &mut self.token_cache,
         }
     }
 }
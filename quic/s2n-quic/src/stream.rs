@@ -12,10 +12,13 @@ mod send;
 #[macro_use]
 mod bidirectional;
 
+mod file;
 mod local;
 mod peer;
 
-pub use s2n_quic_core::stream::{StreamError as Error, StreamType as Type};
+pub mod framed;
+
+pub use s2n_quic_core::stream::{Group, StreamError as Error, StreamType as Type};
 
 pub use bidirectional::*;
 pub use local::*;
@@ -0,0 +1,62 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use bytes::{Bytes, BytesMut};
+use core::ops::{Bound, RangeBounds};
+use std::{fs::File, io};
+
+/// Resolves the byte range of the file to send
+pub(crate) fn range(file: &File, range: impl RangeBounds<u64>) -> io::Result<(u64, u64)> {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(end) => end.saturating_add(1),
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => file.metadata()?.len(),
+    };
+
+    Ok((start, end.max(start)))
+}
+
+/// Reads up to `len` bytes of the file at `offset`
+///
+/// Returns `None` once the end of the file has been reached.
+pub(crate) fn read_chunk(file: &File, offset: u64, len: usize) -> io::Result<Option<Bytes>> {
+    let mut chunk = BytesMut::zeroed(len);
+
+    let len = loop {
+        match read_at(file, &mut chunk, offset) {
+            Ok(len) => break len,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    };
+
+    if len == 0 {
+        return Ok(None);
+    }
+
+    chunk.truncate(len);
+    Ok(Some(chunk.freeze()))
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn read_at(mut file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::io::{Read, Seek, SeekFrom};
+    file.seek(SeekFrom::Start(offset))?;
+    file.read(buf)
+}
@@ -0,0 +1,507 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Length-delimited message framing over streams
+//!
+//! A [`Framed`] stream prefixes each message with its length, which allows the peer to read
+//! messages rather than arbitrary chunks of bytes. Received messages are returned as slices of
+//! the received chunks whenever a message is contained in a single chunk, so they are only copied
+//! if the message was split across several chunks.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # async fn test() -> Result<(), s2n_quic::stream::framed::Error> {
+//! #   let mut connection: s2n_quic::connection::Connection = todo!();
+//! #
+//! use futures::{SinkExt, StreamExt};
+//! use s2n_quic::stream::framed::{Framed, LengthPrefix};
+//!
+//! let stream = connection.open_bidirectional_stream().await?;
+//! let mut framed = Framed::new(stream)
+//!     .with_length_prefix(LengthPrefix::U32)
+//!     .with_max_message_size(64 * 1024);
+//!
+//! framed.send(bytes::Bytes::from_static(b"hello")).await?;
+//!
+//! while let Some(message) = framed.next().await {
+//!     println!("received: {:?}", message?);
+//! }
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::stream::{BidirectionalStream, SendStream, Stream};
+use bytes::{Buf, Bytes, BytesMut};
+use core::{
+    fmt,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use s2n_codec::{DecoderBuffer, EncoderValue};
+use s2n_quic_core::varint::VarInt;
+use std::collections::VecDeque;
+
+/// The default limit on the size of a single message
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 8 * 1024 * 1024;
+
+/// The encoding of the length which precedes each message
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LengthPrefix {
+    /// A QUIC variable-length integer of 1 to 8 bytes
+    #[default]
+    VarInt,
+    /// A 4 byte big-endian integer
+    U32,
+}
+
+impl LengthPrefix {
+    /// Returns the maximum message length the prefix can encode
+    #[inline]
+    fn max_len(self) -> u64 {
+        match self {
+            Self::VarInt => VarInt::MAX.as_u64(),
+            Self::U32 => u32::MAX as u64,
+        }
+    }
+
+    /// Returns the length of the prefix given its first byte
+    #[inline]
+    fn prefix_len(self, first: u8) -> usize {
+        match self {
+            Self::VarInt => 1 << (first >> 6),
+            Self::U32 => 4,
+        }
+    }
+
+    #[inline]
+    fn encode(self, len: usize) -> Bytes {
+        match self {
+            Self::VarInt => {
+                // the length is checked against `max_len` before encoding
+                let len = VarInt::new(len as u64).expect("message length exceeds varint limit");
+                len.encode_to_vec().into()
+            }
+            Self::U32 => Bytes::copy_from_slice(&(len as u32).to_be_bytes()),
+        }
+    }
+
+    #[inline]
+    fn decode(self, prefix: &[u8]) -> u64 {
+        match self {
+            Self::VarInt => {
+                let (len, _) = DecoderBuffer::new(prefix)
+                    .decode::<VarInt>()
+                    .expect("prefix length is derived from the first byte");
+                len.as_u64()
+            }
+            Self::U32 => {
+                let mut bytes = [0; 4];
+                bytes.copy_from_slice(prefix);
+                u32::from_be_bytes(bytes) as u64
+            }
+        }
+    }
+}
+
+/// A stream which sends and receives length-delimited messages
+///
+/// Receiving is supported for any stream which implements [`futures::Stream`], i.e.
+/// [`BidirectionalStream`], [`ReceiveStream`](crate::stream::ReceiveStream) and [`Stream`].
+/// Sending is supported through the [`futures::Sink`] implementation for
+/// [`BidirectionalStream`], [`SendStream`] and [`Stream`]. Closing the sink finishes the stream
+/// and waits for the peer to acknowledge all of the messages.
+pub struct Framed<S> {
+    stream: S,
+    length_prefix: LengthPrefix,
+    max_message_size: usize,
+    read: ReadState,
+    write: WriteState,
+}
+
+impl<S: fmt::Debug> fmt::Debug for Framed<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Framed")
+            .field("stream", &self.stream)
+            .field("length_prefix", &self.length_prefix)
+            .field("max_message_size", &self.max_message_size)
+            .field("buffered", &self.read.buffered)
+            .finish()
+    }
+}
+
+impl<S> Framed<S> {
+    /// Wraps the stream with [`LengthPrefix::VarInt`] framing
+    #[inline]
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            length_prefix: LengthPrefix::default(),
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            read: ReadState::default(),
+            write: WriteState::default(),
+        }
+    }
+
+    /// Sets the encoding of the message lengths
+    ///
+    /// Both peers must use the same encoding.
+    #[inline]
+    pub fn with_length_prefix(mut self, length_prefix: LengthPrefix) -> Self {
+        self.length_prefix = length_prefix;
+        self
+    }
+
+    /// Sets the maximum size of a single message
+    ///
+    /// Sending or receiving a larger message fails with [`Error::MessageTooLarge`]. Defaults to
+    /// [`DEFAULT_MAX_MESSAGE_SIZE`].
+    #[inline]
+    pub fn with_max_message_size(mut self, limit: usize) -> Self {
+        self.max_message_size = limit;
+        self
+    }
+
+    /// Returns a reference to the underlying stream
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the underlying stream
+    ///
+    /// Reading from or writing to the stream directly will corrupt the framing.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Returns the underlying stream
+    ///
+    /// Any received data which hasn't been returned as a message is discarded.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    #[inline]
+    fn max_message_len(&self) -> u64 {
+        (self.max_message_size as u64).min(self.length_prefix.max_len())
+    }
+}
+
+impl<S> futures::Stream for Framed<S>
+where
+    S: futures::Stream<Item = crate::stream::Result<Bytes>> + Unpin,
+{
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let max_message_len = this.max_message_len();
+
+        loop {
+            if let Some(message) = this.read.decode(this.length_prefix, max_message_len)? {
+                return Some(Ok(message)).into();
+            }
+
+            if this.read.is_finished {
+                if this.read.is_empty() {
+                    return None.into();
+                }
+
+                // only report the truncated message once
+                this.read.clear();
+                return Some(Err(Error::Truncated)).into();
+            }
+
+            match ready!(Pin::new(&mut this.stream).poll_next(cx)) {
+                Some(Ok(chunk)) => this.read.push(chunk),
+                Some(Err(error)) => return Some(Err(error.into())).into(),
+                None => this.read.is_finished = true,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReadState {
+    chunks: VecDeque<Bytes>,
+    buffered: usize,
+    /// The length of the current message, once its prefix has been read
+    message_len: Option<usize>,
+    is_finished: bool,
+}
+
+impl ReadState {
+    #[inline]
+    fn push(&mut self, chunk: Bytes) {
+        if !chunk.is_empty() {
+            self.buffered += chunk.len();
+            self.chunks.push_back(chunk);
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.buffered == 0 && self.message_len.is_none()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.chunks.clear();
+        self.buffered = 0;
+        self.message_len = None;
+    }
+
+    /// Returns the next message if it has been completely received
+    fn decode(
+        &mut self,
+        length_prefix: LengthPrefix,
+        max_message_len: u64,
+    ) -> Result<Option<Bytes>, Error> {
+        let message_len = match self.message_len {
+            Some(len) => len,
+            None => {
+                let Some(first) = self.chunks.front().map(|chunk| chunk[0]) else {
+                    return Ok(None);
+                };
+
+                let prefix_len = length_prefix.prefix_len(first);
+                if self.buffered < prefix_len {
+                    return Ok(None);
+                }
+
+                let len = length_prefix.decode(&self.take(prefix_len));
+                if len > max_message_len {
+                    return Err(Error::MessageTooLarge {
+                        limit: max_message_len as usize,
+                    });
+                }
+
+                *self.message_len.insert(len as usize)
+            }
+        };
+
+        if self.buffered < message_len {
+            return Ok(None);
+        }
+
+        self.message_len = None;
+        Ok(Some(self.take(message_len)))
+    }
+
+    /// Removes `len` bytes from the buffered chunks, only copying if they span several chunks
+    fn take(&mut self, len: usize) -> Bytes {
+        debug_assert!(self.buffered >= len);
+        self.buffered -= len;
+
+        if len == 0 {
+            return Bytes::new();
+        }
+
+        let front = self.chunks.front_mut().expect("buffered data is available");
+        if front.len() >= len {
+            let bytes = front.split_to(len);
+            if front.is_empty() {
+                self.chunks.pop_front();
+            }
+            return bytes;
+        }
+
+        let mut bytes = BytesMut::with_capacity(len);
+        while bytes.len() < len {
+            let front = self.chunks.front_mut().expect("buffered data is available");
+            let count = front.len().min(len - bytes.len());
+            bytes.extend_from_slice(&front[..count]);
+            front.advance(count);
+            if front.is_empty() {
+                self.chunks.pop_front();
+            }
+        }
+
+        bytes.freeze()
+    }
+}
+
+#[derive(Debug, Default)]
+struct WriteState {
+    /// The length prefix and payload of the message being written
+    pending: [Bytes; 2],
+    /// The number of pending chunks which have been written to the stream
+    written: usize,
+    /// The number of pending chunks which need to be written to the stream
+    len: usize,
+}
+
+impl WriteState {
+    #[inline]
+    fn push(&mut self, prefix: Bytes, payload: Bytes) {
+        debug_assert_eq!(self.written, self.len, "previous message is still pending");
+        self.len = if payload.is_empty() { 1 } else { 2 };
+        self.pending = [prefix, payload];
+        self.written = 0;
+    }
+}
+
+macro_rules! impl_framed_sink {
+    ($name:ty) => {
+        impl Framed<$name> {
+            /// Writes the pending message to the stream
+            fn poll_write_pending(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+                let write = &mut self.write;
+                while write.written < write.len {
+                    let pending = &mut write.pending[write.written..write.len];
+                    write.written += ready!(self.stream.poll_send_vectored(pending, cx))?;
+                }
+
+                Ok(()).into()
+            }
+        }
+
+        impl futures::Sink<Bytes> for Framed<$name> {
+            type Error = Error;
+
+            #[inline]
+            fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+                self.poll_write_pending(cx)
+            }
+
+            fn start_send(mut self: Pin<&mut Self>, message: Bytes) -> Result<(), Error> {
+                let max_message_len = self.max_message_len();
+                if message.len() as u64 > max_message_len {
+                    return Err(Error::MessageTooLarge {
+                        limit: max_message_len as usize,
+                    });
+                }
+
+                let prefix = self.length_prefix.encode(message.len());
+                self.write.push(prefix, message);
+                Ok(())
+            }
+
+            #[inline]
+            fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+                ready!(self.poll_write_pending(cx))?;
+                ready!(self.stream.poll_flush(cx))?;
+                Ok(()).into()
+            }
+
+            #[inline]
+            fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+                ready!(self.poll_write_pending(cx))?;
+                ready!(self.stream.poll_close(cx))?;
+                Ok(()).into()
+            }
+        }
+    };
+}
+
+impl_framed_sink!(BidirectionalStream);
+impl_framed_sink!(SendStream);
+impl_framed_sink!(Stream);
+
+/// Errors that a [`Framed`] stream can encounter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The stream encountered an error
+    Stream(crate::stream::Error),
+    /// A message exceeded the configured maximum message size
+    #[non_exhaustive]
+    MessageTooLarge { limit: usize },
+    /// The peer finished the stream in the middle of a message
+    Truncated,
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Stream(error) => error.fmt(f),
+            Self::MessageTooLarge { limit } => {
+                write!(f, "The message exceeded the limit of {limit} bytes")
+            }
+            Self::Truncated => write!(f, "The stream finished in the middle of a message"),
+        }
+    }
+}
+
+impl From<crate::stream::Error> for Error {
+    #[inline]
+    fn from(error: crate::stream::Error) -> Self {
+        Self::Stream(error)
+    }
+}
+
+impl From<crate::connection::Error> for Error {
+    #[inline]
+    fn from(error: crate::connection::Error) -> Self {
+        Self::Stream(error.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bolero::check;
+
+    /// Ensures messages are decoded regardless of how the encoded stream is split into chunks
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn decode_chunks_test() {
+        check!()
+            .with_type::<(bool, Vec<Vec<u8>>, Vec<u16>)>()
+            .for_each(|(is_u32, messages, splits)| {
+                let length_prefix = if *is_u32 {
+                    LengthPrefix::U32
+                } else {
+                    LengthPrefix::VarInt
+                };
+
+                let mut encoded = vec![];
+                for message in messages {
+                    encoded.extend_from_slice(&length_prefix.encode(message.len()));
+                    encoded.extend_from_slice(message);
+                }
+                let mut encoded = Bytes::from(encoded);
+
+                let mut read = ReadState::default();
+                let mut splits = splits.iter();
+                let mut decoded = vec![];
+
+                loop {
+                    while let Some(message) = read.decode(length_prefix, u64::MAX).unwrap() {
+                        decoded.push(message.to_vec());
+                    }
+
+                    if encoded.is_empty() {
+                        break;
+                    }
+
+                    let len = splits
+                        .next()
+                        .map_or(encoded.len(), |len| *len as usize)
+                        .clamp(1, encoded.len());
+                    read.push(encoded.split_to(len));
+                }
+
+                assert_eq!(&decoded, messages);
+                assert!(read.is_empty());
+            });
+    }
+
+    #[test]
+    fn max_message_size_test() {
+        let mut read = ReadState::default();
+        read.push(LengthPrefix::VarInt.encode(1_000));
+
+        assert_eq!(
+            read.decode(LengthPrefix::VarInt, 999),
+            Err(Error::MessageTooLarge { limit: 999 })
+        );
+    }
+}
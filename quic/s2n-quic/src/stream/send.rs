@@ -205,6 +205,58 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Sends a range of a file on the stream
+        ///
+        /// The file is read in chunks sized to the stream's available send capacity, which means
+        /// flow control is respected and the data is only buffered once, by the stream. The
+        /// `range` is clamped to the end of the file. Reads are performed on the current task,
+        /// so files on slow storage should be read through the runtime's file API instead.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(len)` with the number of bytes of the file which were enqueued for sending.
+        /// - `Err(e)` if reading the file failed or the stream encountered a
+        ///   [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> std::io::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let file = std::fs::File::open("index.html")?;
+        /// stream.send_file(&file, ..).await?;
+        /// stream.close().await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        pub async fn send_file(
+            &mut self,
+            file: &std::fs::File,
+            range: impl core::ops::RangeBounds<u64>,
+        ) -> std::io::Result<u64> {
+            let (start, end) = $crate::stream::file::range(file, range)?;
+            let mut offset = start;
+
+            while offset < end {
+                let capacity =
+                    ::futures::future::poll_fn(|cx| self.poll_send_ready(cx)).await? as u64;
+                let len = (end - offset).min(capacity) as usize;
+
+                let chunk = match $crate::stream::file::read_chunk(file, offset, len)? {
+                    Some(chunk) => chunk,
+                    None => break,
+                };
+
+                offset += chunk.len() as u64;
+                self.send_data(chunk)?;
+            }
+
+            Ok(offset - start)
+        }
+
         /// Flushes the stream and waits for the peer to receive all outstanding data.
         ///
         /// # Return value
@@ -269,7 +321,8 @@ macro_rules! impl_send_stream_api {
         /// This method returns immediately without notifying the caller that all of the outstanding
         /// data has been received by the peer. An application wanting to both [`finish`](Self::finish)
         /// and [`flush`](Self::flush) the outstanding data can use [`close`](Self::close) to accomplish
-        /// this.
+        /// this, or [`stopped`](Self::stopped) to also be notified if the peer stopped reading the
+        /// stream.
         ///
         /// __NOTE__: This method will be called when the [`stream`](Self) is dropped.
         ///
@@ -296,13 +349,19 @@ macro_rules! impl_send_stream_api {
         /// Marks the stream as finished and waits for all outstanding data to be acknowledged.
         ///
         /// This method is equivalent to calling [`finish`](Self::finish) and [`flush`](Self::flush).
+        /// The returned future resolves only once the peer has acknowledged all of the data sent on
+        /// the stream as well as the stream being finished, which makes it the awaitable
+        /// counterpart of [`finish`](Self::finish). If the peer requests the stream to stop
+        /// sending before then, an error is returned. Use [`stopped`](Self::stopped) instead to
+        /// observe the peer's error code.
         ///
         /// # Return value
         ///
         /// The function returns:
-        /// - `Ok(())` if the send buffer was completely flushed and acknowledged by
-        ///   the peer.
-        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        /// - `Ok(())` if the send buffer was completely flushed and the peer acknowledged both the
+        ///   data and the stream being finished.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error),
+        ///   including the peer requesting the stream to stop sending.
         ///
         /// # Examples
         ///
@@ -357,6 +416,81 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Waits for the peer to either acknowledge the stream being finished or request the
+        /// stream to stop sending.
+        ///
+        /// This is useful for request/response protocols, where the sender wants to know if the
+        /// peer stopped reading the stream before all of the data was delivered. Waiting for all
+        /// of the data to be acknowledged without reporting the peer's error code can be
+        /// accomplished with [`close`](Self::close).
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(Some(error_code))` if the peer requested the stream to stop sending with the
+        ///   provided [error code](crate::application::Error).
+        /// - `Ok(None)` if the stream was finished and all of the data was acknowledged by the
+        ///   peer.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let data = bytes::Bytes::from_static(&[1, 2, 3, 4]);
+        /// stream.send(data).await?;
+        /// stream.finish()?;
+        ///
+        /// if let Some(error_code) = stream.stopped().await? {
+        ///     eprintln!("the peer stopped reading the stream: {error_code:?}");
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn stopped(
+            &mut self,
+        ) -> $crate::stream::Result<Option<$crate::application::Error>> {
+            ::futures::future::poll_fn(|cx| self.poll_stopped(cx)).await
+        }
+
+        /// Polls for the peer to either acknowledge the stream being finished or request the
+        /// stream to stop sending.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Poll::Pending` if the stream is still sending data. In this case,
+        ///   the caller should retry after the [`Waker`](core::task::Waker) on the provided
+        ///   [`Context`](core::task::Context) is notified.
+        /// - `Poll::Ready(Ok(Some(error_code)))` if the peer requested the stream to stop sending
+        ///   with the provided [error code](crate::application::Error).
+        /// - `Poll::Ready(Ok(None))` if the stream was finished and all of the data was
+        ///   acknowledged by the peer.
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn poll_stopped(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<Option<$crate::application::Error>>> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable()).into()
+                };
+                ($variant: expr) => {
+                    s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                        $variant.poll_stopped(cx)
+                    })
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Closes the stream with an [error code](crate::application::Error).
         ///
         /// After calling this, the stream is closed and will not accept any additional data to be
@@ -386,6 +520,86 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Closes the stream with an [error code](crate::application::Error) while still
+        /// delivering the first `reliable_size` bytes of the stream to the peer.
+        ///
+        /// This can be used when the beginning of the stream must arrive at the peer (e.g. a
+        /// header) but the remaining data can be discarded. Any data beyond `reliable_size` which
+        /// has not yet been acknowledged will no longer be transmitted. Reliable resets require
+        /// both endpoints to enable them with
+        /// [`Limits::with_reset_stream_at`](crate::provider::limits::Limits::with_reset_stream_at).
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the stream was reset successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, `reliable_size` may exceed the amount of data
+        ///   that was sent, the peer may not support reliable resets, or the connection itself
+        ///   was closed.
+        #[inline]
+        pub fn reset_at(
+            &mut self,
+            error_code: $crate::application::Error,
+            reliable_size: u64,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.reset_at(error_code, reliable_size)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Assigns the stream to a [`Group`](crate::stream::Group) which shares an aggregate
+        /// send budget with the other streams in the group.
+        ///
+        /// All of the streams on the connection which are assigned to a group with the same
+        /// identifier are limited to buffering the group's `max_send_buffer_size` bytes in
+        /// total. This can be used to bound how much one tenant's streams can buffer relative to
+        /// the others when multiplexing several tenants over a single connection. The most
+        /// recently provided size for a group applies to all of the streams in that group.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// use s2n_quic::stream::Group;
+        ///
+        /// stream.set_group(Group::new(1, 64 * 1024))?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the stream was assigned to the group.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        #[inline]
+        pub fn set_group(&mut self, group: $crate::stream::Group) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_group(group)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
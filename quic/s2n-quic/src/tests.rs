@@ -28,16 +28,63 @@ mod resumption;
 mod setup;
 use setup::*;
 
+mod admission;
+mod application_error;
+mod attached_subscriber;
 mod blackhole;
+mod bottleneck;
+mod capture;
+mod careful_resume;
+mod close;
+mod conformance;
+mod congestion_state;
+mod connect_retry;
 mod connection_migration;
+mod connection_refused;
+mod custom_frame;
+mod datagram;
 mod deduplicate;
+mod demux;
+mod detached;
+mod deterministic;
+mod drop_behavior;
+mod early_accept;
+mod event_sampling;
+mod eviction;
+mod flush;
+mod framed;
+mod half_rtt;
+mod handoff;
 mod handshake_cid_rotation;
+mod handshake_timing;
+mod idle_timeout;
 mod interceptor;
+mod manager;
 mod mtu;
 mod no_tls;
+mod padding;
+mod ping;
+mod preferred_address;
+mod priority_class;
 mod pto;
+mod receive_buffer_pool;
+mod reload;
+mod request;
+mod reset_stream_at;
 mod self_test;
+mod send_buffer;
+mod send_file;
 mod skip_packets;
+mod stats;
+mod stream_group;
+mod stream_id;
+mod stream_stopped;
+mod timestamps;
+mod token_cache;
+mod transmit_share;
+mod transport_parameters;
+mod unreachable;
+mod version_negotiation;
 
 // TODO: https://github.com/aws/s2n-quic/issues/1726
 //
@@ -0,0 +1,155 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::Error,
+    provider::endpoint_limits::{
+        ConnectionAttempt, HandshakeAttempt, HandshakeOutcome, InitialPacket, Limiter, Outcome,
+    },
+};
+use s2n_quic_core::{endpoint, transport};
+
+type Attempts = Arc<Mutex<Vec<(Option<String>, Bytes)>>>;
+
+/// Rejects handshakes for a single server name
+struct ServerNameLimiter {
+    blocked: &'static str,
+    attempts: Attempts,
+}
+
+impl Limiter for ServerNameLimiter {
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        Outcome::allow()
+    }
+
+    fn on_handshake_attempt(&mut self, info: &HandshakeAttempt) -> HandshakeOutcome {
+        self.attempts.lock().unwrap().push((
+            info.server_name.map(String::from),
+            Bytes::copy_from_slice(info.application_protocol),
+        ));
+
+        if info.server_name == Some(self.blocked) {
+            HandshakeOutcome::close(transport::Error::CONNECTION_REFUSED)
+        } else {
+            HandshakeOutcome::allow()
+        }
+    }
+}
+
+/// Ensures the endpoint limiter can reject a connection based on the ClientHello
+#[test]
+fn handshake_attempt_rejected_test() {
+    let model = Model::default();
+    let attempts: Attempts = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_endpoint_limits(ServerNameLimiter {
+                blocked: "blocked.localhost",
+                attempts: attempts.clone(),
+            })?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("blocked.localhost");
+            let error = client.connect(connect).await.unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    Error::Transport {
+                        code,
+                        initiator: endpoint::Location::Remote,
+                        ..
+                    } if code == transport::Error::CONNECTION_REFUSED.code
+                ),
+                "{error:?}"
+            );
+
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap(),
+                Some(Bytes::from_static(b"hello"))
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[0].0.as_deref(), Some("blocked.localhost"));
+    assert_eq!(attempts[1].0.as_deref(), Some("localhost"));
+    assert!(attempts.iter().all(|(_, alpn)| !alpn.is_empty()));
+}
+
+type InitialPackets = Arc<Mutex<Vec<(u32, usize, bool)>>>;
+
+/// Sends a Retry for any Initial packet without a token, before decrypting it
+struct InitialPacketLimiter {
+    packets: InitialPackets,
+}
+
+impl Limiter for InitialPacketLimiter {
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        panic!("packets without a token should be handled by the initial packet filter");
+    }
+
+    fn on_initial_packet(&mut self, info: &InitialPacket) -> Outcome {
+        self.packets.lock().unwrap().push((
+            info.version,
+            info.destination_connection_id.len(),
+            info.token.is_empty(),
+        ));
+
+        if info.token.is_empty() {
+            Outcome::retry()
+        } else {
+            Outcome::allow()
+        }
+    }
+}
+
+/// Ensures the endpoint limiter can filter Initial packets before they are decrypted
+#[test]
+fn initial_packet_filter_test() {
+    let model = Model::default();
+    let packets: InitialPackets = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_endpoint_limits(InitialPacketLimiter {
+                packets: packets.clone(),
+            })?
+            .start()?;
+        let addr = start_server(server)?;
+        client(handle, addr)?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let packets = packets.lock().unwrap();
+    // the first Initial is answered with a Retry and the second carries the Retry token
+    assert_eq!(packets.len(), 2, "{packets:?}");
+    assert!(packets[0].2);
+    assert!(!packets[1].2);
+    assert!(packets
+        .iter()
+        .all(|(version, dcid_len, _)| { *version == 0x1 && *dcid_len >= 8 }));
+}
@@ -0,0 +1,68 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::application::{error_codes, TryInto as _};
+
+error_codes! {
+    /// Error codes shared by the client and server in the test
+    enum TestError {
+        Shutdown = 0x1,
+        Cancelled = 0x2,
+    }
+}
+
+/// Ensures typed error codes can be used to reset streams and close connections and are
+/// recovered by the peer
+#[test]
+fn typed_application_error_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+
+            let error = loop {
+                match stream.receive().await {
+                    Ok(Some(_)) => continue,
+                    Ok(None) => panic!("the stream should be reset"),
+                    Err(error) => break error,
+                }
+            };
+            assert_eq!(
+                error.application_error_as::<TestError>(),
+                Some(TestError::Cancelled)
+            );
+
+            connection.close(TestError::Shutdown.into());
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.reset(TestError::Cancelled.into()).unwrap();
+
+            let error = connection.accept().await.unwrap_err();
+            assert_eq!(
+                error.application_error_as::<TestError>(),
+                Some(TestError::Shutdown)
+            );
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,88 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn confirmed_count(recorder: &recorder::HandshakeStatus) -> usize {
+    recorder
+        .events()
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|event| matches!(event.status, events::HandshakeStatus::Confirmed { .. }))
+        .count()
+}
+
+/// Ensures a subscriber attached with `Connect` only observes the events of its connection
+#[test]
+fn connect_with_event_subscriber_test() {
+    let model = Model::default();
+    let endpoint_subscriber = recorder::HandshakeStatus::new();
+    let attached_subscriber = recorder::HandshakeStatus::new();
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), endpoint_subscriber.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let attached_subscriber = attached_subscriber.clone();
+        primary::spawn(async move {
+            for attach in [true, false] {
+                let mut connect = Connect::new(addr).with_server_name("localhost");
+                if attach {
+                    connect = connect.with_event_subscriber(attached_subscriber.clone());
+                }
+                let mut connection = client.connect(connect).await.unwrap();
+                connection.keep_alive(false).unwrap();
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                stream.finish().unwrap();
+                while stream.receive().await.unwrap().is_some() {}
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(confirmed_count(&endpoint_subscriber), 2);
+    assert_eq!(confirmed_count(&attached_subscriber), 1);
+}
+
+/// Ensures a subscriber can be attached to a connection after it's accepted
+#[test]
+fn attach_event_subscriber_on_accept_test() {
+    let model = Model::default();
+    let closed = recorder::ConnectionClosed::new();
+    let closed_events = closed.events();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let addr = server.local_addr()?;
+
+        primary::spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            connection.attach_event_subscriber(closed).unwrap();
+            while let Ok(Some(_stream)) = connection.accept().await {}
+        });
+
+        let client = build_client(handle)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            // give the server a chance to accept the connection before closing it
+            delay(Duration::from_millis(100)).await;
+            connection.close(123u8.into());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(closed_events.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,77 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::io::testing::QueueDiscipline;
+
+/// Transfers data over a bandwidth-limited network and returns the median RTT sample taken by
+/// the client
+fn median_rtt(discipline: QueueDiscipline) -> Duration {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+    // 1MB/s with room for a few packets to be sent back-to-back
+    model.set_bandwidth(1_000_000);
+    model.set_burst(4_500);
+    model.set_queue_discipline(discipline);
+
+    let subscriber = recorder::Rtt::new();
+    let rtt_samples = subscriber.events();
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        start_client(client, addr, Data::new(2_000_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let mut rtt_samples = rtt_samples.lock().unwrap();
+    rtt_samples.sort();
+    rtt_samples[rtt_samples.len() / 2]
+}
+
+/// Ensures an unmanaged queue at the bottleneck inflates the RTT and CoDel keeps it in check
+#[test]
+fn bufferbloat_test() {
+    let tail_drop = median_rtt(QueueDiscipline::TailDrop);
+    let codel = median_rtt(QueueDiscipline::codel());
+
+    // the base RTT is 20ms so the queue adds significant delay without AQM
+    assert!(tail_drop > Duration::from_millis(100), "{tail_drop:?}");
+    assert!(
+        codel < tail_drop / 2,
+        "codel={codel:?} tail_drop={tail_drop:?}"
+    );
+}
+
+/// Ensures packets are dropped once the bottleneck queue is full
+#[test]
+fn queue_limit_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+    model.set_bandwidth(1_000_000);
+    model.set_queue_limit(15_000);
+
+    let subscriber = recorder::PacketLost::new();
+    let lost_packets = subscriber.events();
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        start_client(client, addr, Data::new(2_000_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert!(!lost_packets.lock().unwrap().is_empty());
+}
@@ -0,0 +1,174 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::capture;
+
+const CAPACITY: usize = 8;
+
+/// Ensures each connection retains its most recent datagrams and writes them out on failure
+#[test]
+fn capture_ring_test() {
+    let directory = std::env::temp_dir().join(format!("s2n-quic-capture-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let subscriber = capture::Subscriber::builder()
+        .with_capacity(CAPACITY)
+        .with_directory(&directory)
+        .build();
+
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((subscriber, tracing_events()))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(Bytes::from(vec![42; 100_000])).await.unwrap();
+            stream.flush().await.unwrap();
+            connection.flush_acknowledged().await.unwrap();
+
+            // the ring only retains the most recent datagrams
+            let capture = connection
+                .query_event_context(|ring: &capture::Ring| {
+                    assert_eq!(ring.len(), CAPACITY);
+                    let mut capture = vec![];
+                    ring.write_pcapng(&mut capture).unwrap();
+                    capture
+                })
+                .unwrap();
+            // the capture starts with a section header block
+            assert_eq!(&capture[..4], &[0x0a, 0x0d, 0x0d, 0x0a]);
+
+            connection.close(123u8.into());
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let captures: Vec<_> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    let _ = std::fs::remove_dir_all(&directory);
+
+    assert_eq!(captures.len(), 1, "{captures:?}");
+    assert_eq!(
+        captures[0].extension().and_then(|ext| ext.to_str()),
+        Some("pcapng")
+    );
+}
+
+/// A pcapng output which can be inspected after the endpoint is done writing to it
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Ensures the IO capture provider records datagrams in both directions along with any new
+/// secrets from the key log
+#[test]
+fn io_capture_test() {
+    use crate::provider::io::capture;
+
+    let key_log = std::env::temp_dir().join(format!("s2n-quic-keylog-{}", std::process::id()));
+    // secrets logged before the capture started aren't included
+    std::fs::write(&key_log, "CLIENT_RANDOM 00 00\n").unwrap();
+
+    let output = Output::default();
+    let client_output = output.clone();
+    let client_key_log = key_log.clone();
+
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = start_server(build_server(handle)?)?;
+
+        let io = capture::Provider::with_output(handle.builder().build()?, client_output)?
+            .with_key_log_file(&client_key_log);
+
+        let client = Client::builder()
+            .with_io(io)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            // emulate the TLS provider logging a secret
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&client_key_log)
+                .unwrap();
+            std::io::Write::write_all(&mut file, b"CLIENT_HANDSHAKE_TRAFFIC_SECRET 01 02\n")
+                .unwrap();
+
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.ping_rtt().await.unwrap();
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let _ = std::fs::remove_file(&key_log);
+
+    let capture = output.0.lock().unwrap();
+    let mut capture = &capture[..];
+    let mut secrets = vec![];
+    let mut flags = vec![];
+    while !capture.is_empty() {
+        let block_type = u32::from_le_bytes(capture[0..4].try_into().unwrap());
+        let len = u32::from_le_bytes(capture[4..8].try_into().unwrap()) as usize;
+        let body = &capture[8..len - 4];
+        match block_type {
+            // decryption secrets block
+            0x0a => {
+                let secrets_len = u32::from_le_bytes(body[4..8].try_into().unwrap()) as usize;
+                secrets.push(body[8..8 + secrets_len].to_vec());
+            }
+            // enhanced packet block
+            0x06 => {
+                assert!(secrets.len() == 1, "secrets precede the first datagram");
+                let captured_len = u32::from_le_bytes(body[12..16].try_into().unwrap()) as usize;
+                let options = &body[20 + ((captured_len + 3) & !3)..];
+                // the first option is the direction flags
+                flags.push(u32::from_le_bytes(options[4..8].try_into().unwrap()));
+            }
+            _ => {}
+        }
+        capture = &capture[len..];
+    }
+
+    assert_eq!(
+        secrets,
+        [b"CLIENT_HANDSHAKE_TRAFFIC_SECRET 01 02\n".to_vec()]
+    );
+    // both outbound and inbound datagrams are captured
+    assert!(flags.contains(&0b10), "{flags:?}");
+    assert!(flags.contains(&0b01), "{flags:?}");
+}
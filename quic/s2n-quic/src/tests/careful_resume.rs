@@ -0,0 +1,360 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    congestion_controller::{self, careful_resume},
+    custom_frame::bdp,
+};
+
+const RESPONSE_LEN: usize = 1_000_000;
+
+/// Ensures the server resumes the congestion window of a previous connection from the same client
+#[test]
+fn careful_resume_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let cache = Arc::new(careful_resume::Cache::default());
+    let durations = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let congestion_controller = congestion_controller::cubic::Builder::default()
+            .with_careful_resume(cache.clone())
+            .build();
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_congestion_controller(congestion_controller)?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        let server_cache = cache.clone();
+        spawn(async move {
+            let mut server = server;
+            while let Some(mut connection) = server.accept().await {
+                let cache = server_cache.clone();
+                spawn(async move {
+                    let mut stream = connection
+                        .accept_bidirectional_stream()
+                        .await
+                        .unwrap()
+                        .unwrap();
+                    while stream.receive().await.unwrap().is_some() {}
+
+                    let mut response = Data::new(RESPONSE_LEN as u64);
+                    while let Some(chunk) = response.send_one(usize::MAX) {
+                        stream.send(chunk).await.unwrap();
+                    }
+                    stream.finish().unwrap();
+                    stream.close().await.unwrap();
+
+                    // save the path characteristics for the next connection from the client
+                    let remote_addr = connection.remote_addr().unwrap();
+                    let path_info = connection.path_info().unwrap();
+                    cache.insert(remote_addr, path_info.into());
+                });
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        let durations = durations.clone();
+        primary::spawn(async move {
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let start = io::time::now();
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"request")).await.unwrap();
+                stream.finish().unwrap();
+
+                let mut received = 0;
+                while let Some(chunk) = stream.receive().await.unwrap() {
+                    received += chunk.len();
+                }
+                assert_eq!(received, RESPONSE_LEN);
+                durations.lock().unwrap().push(io::time::now() - start);
+
+                // give the server time to save the path characteristics
+                delay(Duration::from_millis(200)).await;
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let durations = durations.lock().unwrap();
+    let [first, resumed] = durations[..] else {
+        panic!("expected two requests: {durations:?}");
+    };
+    assert!(resumed < first, "{durations:?}");
+}
+
+/// Ensures the server resumes the congestion window from the values the client echoes back in a
+/// BDP frame, without saving them itself
+#[test]
+fn careful_resume_bdp_frame_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let cache = Arc::new(careful_resume::Cache::default());
+    let durations = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let congestion_controller = congestion_controller::cubic::Builder::default()
+            .with_careful_resume(cache.clone())
+            .build();
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_congestion_controller(congestion_controller)?
+            .with_custom_frame(bdp::Server::new(cache.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut server = server;
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    let mut stream = connection
+                        .accept_bidirectional_stream()
+                        .await
+                        .unwrap()
+                        .unwrap();
+                    while stream.receive().await.unwrap().is_some() {}
+
+                    let mut response = Data::new(RESPONSE_LEN as u64);
+                    while let Some(chunk) = response.send_one(usize::MAX) {
+                        stream.send(chunk).await.unwrap();
+                    }
+                    stream.finish().unwrap();
+                    stream.close().await.unwrap();
+                });
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_custom_frame(bdp::Client::default())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        let durations = durations.clone();
+        let cache = cache.clone();
+        primary::spawn(async move {
+            for i in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let start = io::time::now();
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"request")).await.unwrap();
+                stream.finish().unwrap();
+
+                let mut received = 0;
+                while let Some(chunk) = stream.receive().await.unwrap() {
+                    received += chunk.len();
+                }
+                assert_eq!(received, RESPONSE_LEN);
+                durations.lock().unwrap().push(io::time::now() - start);
+
+                // the server only learns about the path from the frame echoed on the second
+                // connection
+                let saved = cache.remove(connection.local_addr().unwrap());
+                assert_eq!(saved.is_some(), i == 1, "{saved:?}");
+
+                // give the server time to finish the stream
+                delay(Duration::from_millis(200)).await;
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let durations = durations.lock().unwrap();
+    let [first, resumed] = durations[..] else {
+        panic!("expected two requests: {durations:?}");
+    };
+    assert!(resumed < first, "{durations:?}");
+}
+
+/// The state observed by the server for each connection, in the order they were created
+#[derive(Clone, Default)]
+struct Connections {
+    connections: Arc<Mutex<Vec<ConnectionState>>>,
+}
+
+#[derive(Debug, Default)]
+struct ConnectionState {
+    congestion_windows: Vec<u32>,
+    /// The flow-control window of the client for streams it opens
+    client_stream_window: Option<u64>,
+}
+
+impl events::Subscriber for Connections {
+    type ConnectionContext = usize;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let mut connections = self.connections.lock().unwrap();
+        connections.push(ConnectionState::default());
+        connections.len() - 1
+    }
+
+    fn on_transport_parameters_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::TransportParametersReceived,
+    ) {
+        let window = event
+            .transport_parameters
+            .initial_max_stream_data_bidi_local;
+        self.connections.lock().unwrap()[*context].client_stream_window = Some(window);
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        let mut connections = self.connections.lock().unwrap();
+        let windows = &mut connections[*context].congestion_windows;
+        if windows.last() != Some(&event.congestion_window) {
+            windows.push(event.congestion_window);
+        }
+    }
+}
+
+/// Ensures the congestion window of a resumed connection is seeded from the BDP frame echoed by
+/// the client, which also bootstraps its flow-control windows from the frame
+#[test]
+fn careful_resume_bdp_frame_seeded_test() {
+    const CLIENT_WINDOW: u64 = 256 * 1024;
+
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let cache = Arc::new(careful_resume::Cache::default());
+    let connections = Connections::default();
+    let saved_paths = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let congestion_controller = congestion_controller::cubic::Builder::default()
+            .with_careful_resume(cache.clone())
+            .build();
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), connections.clone()))?
+            .with_congestion_controller(congestion_controller)?
+            .with_custom_frame(bdp::Server::new(cache.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut server = server;
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    let mut stream = connection
+                        .accept_bidirectional_stream()
+                        .await
+                        .unwrap()
+                        .unwrap();
+                    while stream.receive().await.unwrap().is_some() {}
+
+                    let mut response = Data::new(RESPONSE_LEN as u64);
+                    while let Some(chunk) = response.send_one(usize::MAX) {
+                        stream.send(chunk).await.unwrap();
+                    }
+                    stream.finish().unwrap();
+                    stream.close().await.unwrap();
+                });
+            }
+        });
+
+        let limits = crate::provider::limits::Limits::default()
+            .with_data_window(CLIENT_WINDOW)?
+            .with_bidirectional_local_data_window(CLIENT_WINDOW)?;
+        let bdp = bdp::Client::default().with_limits(limits);
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_custom_frame(bdp.clone())?
+            .with_limits(bdp)?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        let cache = cache.clone();
+        let saved_paths = saved_paths.clone();
+        primary::spawn(async move {
+            for _ in 0..2 {
+                let connect = Connect::new(addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"request")).await.unwrap();
+                stream.finish().unwrap();
+                while stream.receive().await.unwrap().is_some() {}
+
+                let saved = cache.remove(connection.local_addr().unwrap());
+                saved_paths.lock().unwrap().push(saved);
+
+                // give the server time to finish the stream
+                delay(Duration::from_millis(200)).await;
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let saved_paths = saved_paths.lock().unwrap();
+    let [None, Some(saved)] = saved_paths[..] else {
+        panic!("expected the path to be saved by the second connection: {saved_paths:?}");
+    };
+
+    let connections = connections.connections.lock().unwrap();
+    let [first, resumed] = &connections[..] else {
+        panic!("expected two connections: {connections:?}");
+    };
+
+    // the client only bootstraps its windows once it has a frame saved
+    assert_eq!(first.client_stream_window, Some(CLIENT_WINDOW));
+    assert_eq!(
+        resumed.client_stream_window,
+        Some(saved.congestion_window.into())
+    );
+
+    // the resumed connection jumps from the initial window to half of the saved window, while
+    // the first connection grows it gradually
+    let initial_window = first.congestion_windows[0];
+    let jump_window = saved.congestion_window / 2;
+    assert!(first.congestion_windows[1] < jump_window, "{first:?}");
+    assert_eq!(
+        resumed.congestion_windows[..2],
+        [initial_window, jump_window],
+        "{resumed:?}"
+    );
+}
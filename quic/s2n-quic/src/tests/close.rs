@@ -0,0 +1,144 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::Error,
+    provider::io::testing::{Handle, Result},
+};
+use s2n_quic_core::{endpoint, event::api::ConnectionCloseLinger};
+
+const ERROR_CODE: u32 = 99;
+
+type Outcome = Arc<Mutex<Option<core::result::Result<(), Error>>>>;
+
+/// Starts a server which reports how the first accepted connection was closed
+///
+/// The simulation runs until the server observes the connection closing.
+fn start_close_server(handle: &Handle) -> Result<(SocketAddr, Outcome)> {
+    let mut server = build_server(handle)?;
+    let addr = server.local_addr()?;
+    let outcome: Outcome = Default::default();
+    let server_outcome = outcome.clone();
+
+    primary::spawn(async move {
+        let mut connection = server.accept().await.unwrap();
+        let result = loop {
+            match connection.accept().await {
+                Ok(Some(_stream)) => continue,
+                Ok(None) => break Ok(()),
+                Err(error) => break Err(error),
+            }
+        };
+        *server_outcome.lock().unwrap() = Some(result);
+    });
+
+    Ok((addr, outcome))
+}
+
+/// Ensures the peer is notified of a close with a custom linger duration
+#[test]
+fn close_with_linger_test() {
+    let model = Model::default();
+    let closed = recorder::ConnectionClosed::new();
+    let closed_events = closed.events();
+    let linger = Duration::from_secs(5);
+    let mut server_outcome = None;
+
+    test(model, |handle| {
+        let (addr, outcome) = start_close_server(handle)?;
+        server_outcome = Some(outcome);
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), closed))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            connection.close_with_linger(ERROR_CODE.into(), linger);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let closed_events = closed_events.lock().unwrap();
+    assert!(
+        matches!(
+            closed_events[..],
+            [ConnectionCloseLinger::Duration { duration, .. }] if duration == linger
+        ),
+        "{closed_events:?}"
+    );
+
+    // the peer should have received the CONNECTION_CLOSE frame
+    let outcome = server_outcome.unwrap().lock().unwrap().take().unwrap();
+    assert!(
+        matches!(
+            outcome,
+            Err(Error::Application {
+                error,
+                initiator: endpoint::Location::Remote,
+                ..
+            }) if error == ERROR_CODE.into()
+        ),
+        "{outcome:?}"
+    );
+}
+
+/// Ensures an aborted connection drops its state without notifying the peer
+#[test]
+fn abort_test() {
+    let model = Model::default();
+    let closed = recorder::ConnectionClosed::new();
+    let closed_events = closed.events();
+    let frames = recorder::FrameSent::new();
+    let frame_events = frames.events();
+    let mut server_outcome = None;
+
+    test(model, |handle| {
+        let (addr, outcome) = start_close_server(handle)?;
+        server_outcome = Some(outcome);
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), (closed, frames)))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            connection.abort(ERROR_CODE.into());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let closed_events = closed_events.lock().unwrap();
+    assert!(
+        matches!(closed_events[..], [ConnectionCloseLinger::Abort { .. }]),
+        "{closed_events:?}"
+    );
+
+    // the client should not have sent a CONNECTION_CLOSE frame
+    let frame_events = frame_events.lock().unwrap();
+    assert!(!frame_events
+        .iter()
+        .any(|event| matches!(event.frame, events::Frame::ConnectionClose { .. })));
+
+    // the peer should only find out about the closure through its idle timeout
+    let outcome = server_outcome.unwrap().lock().unwrap().take().unwrap();
+    assert!(
+        !matches!(outcome, Err(Error::Application { .. })),
+        "{outcome:?}"
+    );
+}
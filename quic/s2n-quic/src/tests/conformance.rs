@@ -0,0 +1,96 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::conformance;
+
+/// Ensures a connection between two s2n-quic endpoints doesn't violate any protocol invariants
+///
+/// The connection exceeds the initial stream and flow control limits and recovers from loss so
+/// the limit updates and acknowledgements of both endpoints are checked.
+#[test]
+fn conformance_test() {
+    let model = Model::default();
+    model.set_drop_rate(0.05);
+
+    let server_violations = Arc::new(Mutex::new(None));
+    let client_violations = Arc::new(Mutex::new(None));
+
+    let server_violations_out = server_violations.clone();
+    let client_violations_out = client_violations.clone();
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((conformance::Subscriber::default(), tracing_events()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let server_addr = server.local_addr()?;
+        let server_violations = server_violations.clone();
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                while let Ok(Some(chunk)) = stream.receive().await {
+                    let _ = stream.send(chunk).await;
+                }
+                let _ = stream.finish();
+
+                // the connection can't be queried once the client closes it
+                *server_violations.lock().unwrap() = connection
+                    .query_event_context(|checker: &conformance::Checker| {
+                        checker.violations().to_vec()
+                    })
+                    .ok();
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((conformance::Subscriber::default(), tracing_events()))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        let client_violations = client_violations.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // open more streams than the initial limit allows
+            for _ in 0..150 {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                stream.finish().unwrap();
+                while stream.receive().await.unwrap().is_some() {}
+            }
+
+            // send more data than the initial flow control windows allow
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+            let mut send_data = Data::new(5_000_000);
+            let mut recv_data = send_data;
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+
+            *client_violations.lock().unwrap() = connection
+                .query_event_context(|checker: &conformance::Checker| checker.violations().to_vec())
+                .ok();
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    assert_eq!(client_violations_out.lock().unwrap().take(), Some(vec![]));
+    assert_eq!(server_violations_out.lock().unwrap().take(), Some(vec![]));
+}
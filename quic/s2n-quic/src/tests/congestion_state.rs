@@ -0,0 +1,59 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn congestion_state_events(interval: Duration) -> Vec<(u32, u64)> {
+    let model = Model::default();
+    let subscriber = recorder::CongestionState::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_congestion_state_interval(interval)
+                    .unwrap(),
+            )?
+            .start()?;
+
+        start_client(client, addr, Data::new(1_000_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    events.clone()
+}
+
+/// Ensures the congestion state is published periodically once an interval is configured
+#[test]
+fn congestion_state_test() {
+    let events = congestion_state_events(Duration::from_millis(10));
+
+    assert!(!events.is_empty());
+    for (congestion_window, _) in events.iter() {
+        assert!(*congestion_window > 0);
+    }
+
+    // loss epochs never go backwards
+    for pair in events.windows(2) {
+        assert!(pair[0].1 <= pair[1].1);
+    }
+}
+
+/// Ensures the congestion state isn't published by default
+#[test]
+fn congestion_state_disabled_test() {
+    let events = congestion_state_events(Duration::ZERO);
+
+    assert!(events.is_empty());
+}
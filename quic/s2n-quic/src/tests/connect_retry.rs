@@ -0,0 +1,235 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    client::RetryPolicy,
+    connection::Error,
+    provider::{event::events::EndpointMeta, limits::Limits},
+};
+
+/// Records the scheduled retries as `(attempt, backoff)` pairs
+#[derive(Clone, Default)]
+struct Retries(Arc<Mutex<Vec<(u32, Duration)>>>);
+
+impl events::Subscriber for Retries {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_endpoint_connection_attempt_retried(
+        &mut self,
+        _meta: &EndpointMeta,
+        event: &events::EndpointConnectionAttemptRetried,
+    ) {
+        self.0.lock().unwrap().push((event.attempt, event.backoff));
+    }
+}
+
+const UNBOUND_ADDR: SocketAddr = SocketAddr::V4(std::net::SocketAddrV4::new(
+    std::net::Ipv4Addr::new(10, 0, 0, 1),
+    4433,
+));
+
+fn build_retry_client(handle: &io::Handle, retries: &Retries) -> io::Result<Client> {
+    let limits = Limits::new().with_max_handshake_duration(Duration::from_secs(1))?;
+
+    Ok(Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event((tracing_events(), retries.clone()))?
+        .with_random(Random::with_seed(123))?
+        .with_limits(limits)?
+        .start()?)
+}
+
+/// Ensures a failed attempt moves on to the next address
+#[test]
+fn alternate_address_test() {
+    let model = Model::default();
+    model.set_port_unreachable(true);
+    let retries = Retries::default();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_retry_client(handle, &retries)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(UNBOUND_ADDR)
+                .with_server_name("localhost")
+                .with_alternate_address(server_addr)
+                .with_retry_policy(
+                    RetryPolicy::new(2).with_initial_backoff(Duration::from_millis(100)),
+                );
+            let connection = client.connect(connect).await.unwrap();
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let retries = retries.0.lock().unwrap();
+    assert_eq!(*retries, [(2, Duration::from_millis(100))]);
+}
+
+/// Ensures handshake timeouts are retried with an exponential backoff until the attempt budget
+/// is exhausted
+#[test]
+fn attempt_budget_test() {
+    let model = Model::default();
+    let retries = Retries::default();
+    let result = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let client = build_retry_client(handle, &retries)?;
+        let result = result.clone();
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(UNBOUND_ADDR)
+                .with_server_name("localhost")
+                .with_retry_policy(
+                    RetryPolicy::new(3).with_initial_backoff(Duration::from_millis(100)),
+                );
+            let error = client.connect(connect).await.unwrap_err();
+            *result.lock().unwrap() = Some((error, io::time::now() - start));
+        });
+
+        Ok(UNBOUND_ADDR)
+    })
+    .unwrap();
+
+    let (error, elapsed) = result.lock().unwrap().take().unwrap();
+    assert!(
+        matches!(error, Error::MaxHandshakeDurationExceeded { .. }),
+        "{error:?}"
+    );
+    // three handshake timeouts plus the backoff between each
+    assert!(elapsed > Duration::from_millis(3250), "{elapsed:?}");
+    assert!(elapsed < Duration::from_secs(4), "{elapsed:?}");
+
+    let retries = retries.0.lock().unwrap();
+    assert_eq!(
+        *retries,
+        [
+            (2, Duration::from_millis(100)),
+            (3, Duration::from_millis(200))
+        ]
+    );
+}
+
+/// Ensures an unresponsive address is abandoned once the attempt timeout elapses, rather than
+/// the limit's handshake duration
+#[test]
+fn attempt_timeout_test() {
+    let model = Model::default();
+    let retries = Retries::default();
+    let elapsed = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_retry_client(handle, &retries)?;
+        let elapsed = elapsed.clone();
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(UNBOUND_ADDR)
+                .with_server_name("localhost")
+                .with_alternate_address(server_addr)
+                .with_retry_policy(
+                    RetryPolicy::new(2)
+                        .with_initial_backoff(Duration::from_millis(100))
+                        .with_attempt_timeout(Duration::from_millis(200)),
+                );
+            let connection = client.connect(connect).await.unwrap();
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+            *elapsed.lock().unwrap() = Some(io::time::now() - start);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let elapsed = elapsed.lock().unwrap().take().unwrap();
+    // the attempt timeout plus the backoff, well short of the 1s handshake duration
+    assert!(elapsed > Duration::from_millis(300), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(500), "{elapsed:?}");
+
+    let retries = retries.0.lock().unwrap();
+    assert_eq!(*retries, [(2, Duration::from_millis(100))]);
+}
+
+/// Ensures the next address joins the race when the first one is slow to respond and the
+/// first handshake to complete wins
+#[test]
+fn race_test() {
+    let model = Model::default();
+    let retries = Retries::default();
+    let elapsed = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_retry_client(handle, &retries)?;
+        let elapsed = elapsed.clone();
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(UNBOUND_ADDR)
+                .with_server_name("localhost")
+                .with_alternate_address(server_addr)
+                .with_retry_policy(RetryPolicy::new(2).with_race_delay(Duration::from_millis(100)));
+            let connection = client.connect(connect).await.unwrap();
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+            *elapsed.lock().unwrap() = Some(io::time::now() - start);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let elapsed = elapsed.lock().unwrap().take().unwrap();
+    // the race delay plus the handshake, without waiting for the first attempt to time out
+    assert!(elapsed > Duration::from_millis(100), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(500), "{elapsed:?}");
+
+    // joining a race isn't a retry
+    assert!(retries.0.lock().unwrap().is_empty());
+}
+
+/// Ensures a failed attempt immediately hands over to the next address while racing
+#[test]
+fn race_failover_test() {
+    let model = Model::default();
+    model.set_port_unreachable(true);
+    let retries = Retries::default();
+    let elapsed = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_retry_client(handle, &retries)?;
+        let elapsed = elapsed.clone();
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(UNBOUND_ADDR)
+                .with_server_name("localhost")
+                .with_alternate_address(server_addr)
+                .with_retry_policy(RetryPolicy::new(2).with_race_delay(Duration::from_secs(5)));
+            let connection = client.connect(connect).await.unwrap();
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+            *elapsed.lock().unwrap() = Some(io::time::now() - start);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let elapsed = elapsed.lock().unwrap().take().unwrap();
+    assert!(elapsed < Duration::from_millis(500), "{elapsed:?}");
+}
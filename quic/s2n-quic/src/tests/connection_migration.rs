@@ -218,3 +218,173 @@ fn port_rebind_test() {
 fn ip_and_port_rebind_test() {
     run_test(|addr| rebind_ip(rebind_port(addr)));
 }
+
+/// Ensures a client moves its connection to a new local address after the interface of the
+/// previous one goes down
+#[test]
+fn interface_change_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    let addresses = Arc::new(Mutex::new(None));
+    let addresses_pub = addresses.clone();
+
+    let on_socket = move |socket: io::Socket| {
+        spawn(async move {
+            delay(rtt * 4).await;
+            let prev_addr = socket.local_addr().unwrap();
+            let new_addr = rebind_ip(prev_addr);
+            *addresses_pub.lock().unwrap() = Some((prev_addr, new_addr));
+            socket.change_interface(new_addr);
+        });
+    };
+
+    let lost_addresses = recorder::LocalAddressLost::new();
+    let lost_addresses_sub = lost_addresses.clone();
+    let active_paths = recorder::ActivePathUpdated::new();
+    let active_paths_sub = active_paths.clone();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), active_paths_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().on_socket(on_socket).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), lost_addresses_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(100_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let (prev_addr, new_addr) = addresses.lock().unwrap().unwrap();
+    // the client stops sending from the address which went away
+    assert_eq!(*lost_addresses.events().lock().unwrap(), [prev_addr]);
+    // the server follows the client to its new address
+    assert_eq!(*active_paths.events().lock().unwrap(), [new_addr]);
+}
+
+/// Ensures the path created after a local address is lost starts with fresh congestion control
+/// and RTT state rather than inheriting them from the path that went away
+#[test]
+fn interface_change_resets_recovery_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    let new_rtt = rtt * 4;
+    let on_socket = {
+        let model = model.clone();
+        move |socket: io::Socket| {
+            spawn(async move {
+                delay(rtt * 4).await;
+                let prev_addr = socket.local_addr().unwrap();
+                // the new interface routes over a slower network
+                model.set_delay(new_rtt / 2);
+                socket.change_interface(rebind_ip(prev_addr));
+            });
+        }
+    };
+
+    let metrics = recorder::RecoveryMetrics::new();
+    let metrics_sub = metrics.clone();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().on_socket(on_socket).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), metrics_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(100_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let metrics = metrics.events();
+    let metrics = metrics.lock().unwrap();
+
+    let prev_path = metrics.iter().filter(|(id, _, _)| *id == 0);
+    let max_prev_window = prev_path.clone().map(|(_, _, cwnd)| *cwnd).max().unwrap();
+    let (_, prev_min_rtt, _) = prev_path.last().unwrap();
+    assert!(*prev_min_rtt < new_rtt);
+
+    let mut new_path = metrics.iter().filter(|(id, _, _)| *id != 0).peekable();
+    let (_, _, first_window) = **new_path.peek().expect("a new path should be created");
+    // the new path doesn't inherit the congestion window grown on the old path
+    assert!(first_window < max_prev_window);
+    // the new path doesn't inherit the min RTT observed on the old path
+    assert!(new_path.all(|(_, min_rtt, _)| *min_rtt >= new_rtt));
+}
+
+/// Ensures path validation follows the configured PATH_CHALLENGE cadence and timeout
+#[test]
+fn path_validation_limits_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    let on_socket = move |socket: io::Socket| {
+        spawn(async move {
+            delay(rtt * 4).await;
+            let local_addr = socket.local_addr().unwrap();
+            socket.rebind(rebind_port(local_addr));
+        });
+    };
+
+    let challenges = recorder::PathChallengeUpdated::new();
+    let challenges_sub = challenges.clone();
+
+    test(model, move |handle| {
+        let limits = provider::limits::Limits::default()
+            .with_path_challenge_transmissions(3)?
+            .with_path_challenge_interval(rtt / 5)?
+            .with_path_validation_timeout(rtt * 3)?;
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), challenges_sub))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().on_socket(on_socket).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(100_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let challenges = challenges.events();
+    let challenges = challenges.lock().unwrap();
+    // the client's new address is validated
+    assert!(challenges.iter().any(|(validated, _)| *validated));
+    // the client's previous address is no longer reachable so the server gives up on it after
+    // sending all of the challenges
+    assert!(challenges.contains(&(false, 3)), "{challenges:?}");
+    assert!(challenges.iter().all(|(_, sent)| *sent <= 3));
+}
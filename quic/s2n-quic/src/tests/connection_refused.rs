@@ -0,0 +1,231 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    client::RetryPolicy,
+    connection::Error,
+    provider::{
+        endpoint_limits::{ConnectionAttempt, Limiter, Outcome},
+        io::testing::{Handle, Result},
+    },
+};
+use s2n_quic_core::{endpoint, transport};
+
+/// Refuses the first `refusals` connection attempts
+struct Busy {
+    refusals: usize,
+    retry_after: Option<Duration>,
+}
+
+impl Limiter for Busy {
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        if self.refusals == 0 {
+            return Outcome::allow();
+        }
+
+        self.refusals -= 1;
+        match self.retry_after {
+            Some(retry_after) => Outcome::close_with_retry_after(retry_after),
+            None => Outcome::close(),
+        }
+    }
+}
+
+fn server(handle: &Handle, limiter: Busy) -> Result<SocketAddr> {
+    let server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_endpoint_limits(limiter)?
+        .start()?;
+    start_server(server)
+}
+
+/// Connects to a server which refuses the attempt and returns the error along with how long the
+/// attempt took
+fn refused(retry_after: Option<Duration>) -> (Error, Duration) {
+    let model = Model::default();
+    let result = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let addr = server(
+            handle,
+            Busy {
+                refusals: usize::MAX,
+                retry_after,
+            },
+        )?;
+        let client = build_client(handle)?;
+        let result = result.clone();
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let error = client.connect(connect).await.unwrap_err();
+            *result.lock().unwrap() = Some((error, io::time::now() - start));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let result = result.lock().unwrap().take();
+    result.unwrap()
+}
+
+/// Ensures refused connection attempts fail immediately with a CONNECTION_REFUSED error instead
+/// of timing out
+#[test]
+fn connection_refused_test() {
+    let (error, elapsed) = refused(None);
+
+    assert!(
+        matches!(
+            error,
+            Error::Transport {
+                code,
+                initiator: endpoint::Location::Remote,
+                ..
+            } if code == transport::Error::CONNECTION_REFUSED.code
+        ),
+        "{error:?}"
+    );
+    assert_eq!(error.retry_after(), None);
+    assert_eq!(
+        std::io::ErrorKind::from(error),
+        std::io::ErrorKind::ConnectionRefused
+    );
+    // a single round trip rather than the handshake timeout
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+}
+
+/// Ensures the retry-after hint is made available to the client application
+#[test]
+fn retry_after_hint_test() {
+    let (error, _elapsed) = refused(Some(Duration::from_millis(1500)));
+
+    assert!(
+        matches!(
+            error,
+            Error::Transport { code, .. } if code == transport::Error::CONNECTION_REFUSED.code
+        ),
+        "{error:?}"
+    );
+    assert_eq!(error.retry_after(), Some(Duration::from_millis(1500)));
+}
+
+/// Ensures clients with a retry policy wait for the hinted duration before connecting again
+#[test]
+fn retry_after_reconnect_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let addr = server(
+            handle,
+            Busy {
+                refusals: 1,
+                retry_after: Some(Duration::from_millis(500)),
+            },
+        )?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_retry_policy(
+                    RetryPolicy::new(2).with_initial_backoff(Duration::from_millis(100)),
+                );
+            client.connect(connect).await.unwrap();
+
+            // the second attempt waits for the hint instead of the shorter backoff
+            let elapsed = io::time::now() - start;
+            assert!(elapsed > Duration::from_millis(500), "{elapsed:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures an oversized hint is clamped instead of holding off the next attempt
+#[test]
+fn retry_after_oversized_hint_test() {
+    let (error, _elapsed) = refused(Some(Duration::from_millis(u64::MAX)));
+    assert_eq!(
+        error.retry_after(),
+        Some(s2n_quic_core::connection::close::RetryAfter::MAX)
+    );
+
+    let model = Model::default();
+
+    test(model, |handle| {
+        let addr = server(
+            handle,
+            Busy {
+                refusals: 1,
+                retry_after: Some(Duration::from_millis(u64::MAX)),
+            },
+        )?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_retry_policy(RetryPolicy::new(2).with_max_backoff(Duration::from_secs(1)));
+            client.connect(connect).await.unwrap();
+
+            // the hint is limited to the policy's maximum backoff
+            let elapsed = io::time::now() - start;
+            assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures a refusal which is injected in an Initial packet by someone other than the intended
+/// server can't hold off the connection
+#[test]
+fn retry_after_injected_refusal_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        // the refusals are unauthenticated, so an on-path attacker can answer the client's
+        // Initial with one of its own, which is indistinguishable from this endpoint's refusals
+        let attacker = server(
+            handle,
+            Busy {
+                refusals: usize::MAX,
+                retry_after: Some(Duration::from_millis(u64::MAX)),
+            },
+        )?;
+        let addr = server(
+            handle,
+            Busy {
+                refusals: 0,
+                retry_after: None,
+            },
+        )?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(attacker)
+                .with_alternate_address(addr)
+                .with_server_name("localhost")
+                .with_retry_policy(RetryPolicy::new(2).with_max_backoff(Duration::from_secs(1)));
+            client.connect(connect).await.unwrap();
+
+            let elapsed = io::time::now() - start;
+            assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
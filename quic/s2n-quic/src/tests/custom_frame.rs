@@ -0,0 +1,175 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::custom_frame::{ConnectionInfo, Endpoint, Handler, Packet, ReceiveContext};
+use s2n_quic_core::{connection::Error, endpoint, transport, varint::VarInt};
+
+const FRAME_TYPE: VarInt = VarInt::from_u16(0x2ab2);
+
+type Received = Arc<Mutex<Vec<(u64, Bytes)>>>;
+
+/// Sends a single frame on each connection and records the frames it receives
+struct Frames {
+    frame_type: VarInt,
+    payload: &'static [u8],
+    received: Received,
+}
+
+impl Frames {
+    fn new(frame_type: VarInt, payload: &'static [u8]) -> (Self, Received) {
+        let received = Received::default();
+        let frames = Self {
+            frame_type,
+            payload,
+            received: received.clone(),
+        };
+        (frames, received)
+    }
+}
+
+impl Endpoint for Frames {
+    type Handler = FramesHandler;
+
+    fn create_connection(&mut self, _info: &ConnectionInfo) -> Self::Handler {
+        FramesHandler {
+            frame_type: self.frame_type,
+            pending: Some(self.payload),
+            received: self.received.clone(),
+        }
+    }
+}
+
+struct FramesHandler {
+    frame_type: VarInt,
+    pending: Option<&'static [u8]>,
+    received: Received,
+}
+
+impl Handler for FramesHandler {
+    fn on_frame(
+        &mut self,
+        context: &ReceiveContext<'_>,
+        frame_type: VarInt,
+        payload: &[u8],
+    ) -> Result<(), transport::Error> {
+        // only the negotiated frame type is accepted
+        if frame_type != FRAME_TYPE {
+            return Err(transport::Error::FRAME_ENCODING_ERROR.with_reason("unknown frame type"));
+        }
+
+        self.received
+            .lock()
+            .unwrap()
+            .push((context.packet_number, Bytes::copy_from_slice(payload)));
+        Ok(())
+    }
+
+    fn on_transmit<P: Packet>(&mut self, packet: &mut P) {
+        if let Some(payload) = self.pending {
+            if packet.write_frame(self.frame_type, payload).is_ok() {
+                self.pending = None;
+            }
+        }
+    }
+
+    fn has_transmission_interest(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn on_connection_error(&mut self, _error: Error) {}
+}
+
+/// Ensures custom frames are exchanged between the client and server
+#[test]
+fn custom_frame_test() {
+    let model = Model::default();
+    let (server_frames, server_received) = Frames::new(FRAME_TYPE, b"server");
+    let (client_frames, client_received) = Frames::new(FRAME_TYPE, b"client");
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_custom_frame(server_frames)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_custom_frame(client_frames)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let _connection = client.connect(connect).await.unwrap();
+
+            // give both peers time to exchange their frames
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    for (received, payload) in [(server_received, b"client"), (client_received, b"server")] {
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].1, Bytes::from_static(payload));
+    }
+}
+
+/// Ensures a frame type that wasn't negotiated closes the connection
+#[test]
+fn custom_frame_unknown_type_test() {
+    let model = Model::default();
+    let (server_frames, server_received) = Frames::new(FRAME_TYPE, b"server");
+    let (client_frames, _client_received) = Frames::new(VarInt::from_u16(0x2ab3), b"client");
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_custom_frame(server_frames)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_custom_frame(client_frames)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let error = connection.accept_receive_stream().await.unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    Error::Transport {
+                        code,
+                        initiator: endpoint::Location::Remote,
+                        ..
+                    } if code == transport::Error::FRAME_ENCODING_ERROR.code
+                ),
+                "{error:?}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert!(server_received.lock().unwrap().is_empty());
+}
@@ -0,0 +1,238 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::datagram::{
+    ConnectionInfo, Endpoint, Packet, PreConnectionInfo, ReceiveContext, Receiver, Sender,
+};
+use s2n_quic_core::connection;
+
+#[derive(Debug, Default)]
+struct Outcomes {
+    sent: Vec<u64>,
+    acked: Vec<u64>,
+    lost: Vec<u64>,
+}
+
+/// Sends a fixed number of datagrams and records the outcome of each packet carrying them
+struct TrackingEndpoint {
+    datagrams: usize,
+    outcomes: Arc<Mutex<Outcomes>>,
+}
+
+impl Endpoint for TrackingEndpoint {
+    type Sender = TrackingSender;
+    type Receiver = NoopReceiver;
+
+    fn create_connection(&mut self, _info: &ConnectionInfo) -> (Self::Sender, Self::Receiver) {
+        let sender = TrackingSender {
+            remaining: self.datagrams,
+            outcomes: self.outcomes.clone(),
+        };
+        (sender, NoopReceiver)
+    }
+
+    fn max_datagram_frame_size(&self, _info: &PreConnectionInfo) -> u64 {
+        0
+    }
+}
+
+struct TrackingSender {
+    remaining: usize,
+    outcomes: Arc<Mutex<Outcomes>>,
+}
+
+impl Sender for TrackingSender {
+    fn on_transmit<P: Packet>(&mut self, packet: &mut P) {
+        let mut has_written = false;
+        while self.remaining > 0 && packet.remaining_capacity() >= 100 {
+            if packet.write_datagram(&[1; 100]).is_err() {
+                break;
+            }
+            self.remaining -= 1;
+            has_written = true;
+        }
+
+        if has_written {
+            self.outcomes
+                .lock()
+                .unwrap()
+                .sent
+                .push(packet.packet_number());
+        }
+    }
+
+    fn has_transmission_interest(&self) -> bool {
+        self.remaining > 0
+    }
+
+    fn on_connection_error(&mut self, _error: connection::Error) {}
+
+    fn on_datagram_ack(&mut self, packet_number: u64) {
+        self.outcomes.lock().unwrap().acked.push(packet_number);
+    }
+
+    fn on_datagram_loss(&mut self, packet_number: u64) {
+        self.outcomes.lock().unwrap().lost.push(packet_number);
+    }
+}
+
+struct NoopReceiver;
+
+impl Receiver for NoopReceiver {
+    fn on_datagram(&mut self, _context: &ReceiveContext<'_>, _datagram: &[u8]) {}
+
+    fn on_connection_error(&mut self, _error: connection::Error) {}
+}
+
+// Each packet carrying datagrams is reported to the sender as either acknowledged or lost
+#[test]
+fn datagram_delivery_notifications() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    model.set_drop_rate(0.1);
+    let outcomes = Arc::new(Mutex::new(Outcomes::default()));
+    let client_outcomes = outcomes.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_random(Random::with_seed(456))?
+            .with_datagram(
+                provider::datagram::default::Endpoint::builder()
+                    .with_recv_capacity(1000)
+                    .unwrap()
+                    .build()
+                    .unwrap(),
+            )?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_random(Random::with_seed(123))?
+            .with_datagram(TrackingEndpoint {
+                datagrams: 1000,
+                outcomes: client_outcomes,
+            })?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            // give the connection time to resolve every packet carrying datagrams
+            delay(Duration::from_secs(5)).await;
+            drop(connection);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let outcomes = outcomes.lock().unwrap();
+    assert!(!outcomes.acked.is_empty(), "{outcomes:?}");
+    assert!(!outcomes.lost.is_empty(), "{outcomes:?}");
+
+    // every packet is reported exactly once
+    let mut resolved = outcomes.acked.clone();
+    resolved.extend(&outcomes.lost);
+    resolved.sort_unstable();
+    assert_eq!(resolved, outcomes.sent);
+}
+
+// Datagrams lost on the network are reconstructed when both endpoints enable FEC
+#[test]
+fn datagram_fec_recovery() {
+    use crate::provider::datagram::{default, fec};
+
+    const COUNT: u8 = 100;
+
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    model.set_drop_rate(0.05);
+    let received = Arc::new(Mutex::new(vec![]));
+    let server_received = received.clone();
+
+    let datagram = || {
+        default::Endpoint::builder()
+            .with_send_capacity(COUNT as usize)
+            .unwrap()
+            .with_recv_capacity(COUNT as usize)
+            .unwrap()
+            .with_fec(fec::Scheme::ReedSolomon {
+                data_shards: 4,
+                parity_shards: 2,
+            })
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_random(Random::with_seed(456))?
+            .with_datagram(datagram())?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let connection = server.accept().await.unwrap();
+            loop {
+                let datagram = futures::future::poll_fn(|cx| {
+                    connection
+                        .datagram_mut(|receiver: &mut default::Receiver| {
+                            receiver.poll_recv_datagram(cx)
+                        })
+                        .unwrap()
+                })
+                .await;
+
+                match datagram {
+                    Ok(datagram) => server_received.lock().unwrap().push(datagram),
+                    Err(_) => break,
+                }
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_random(Random::with_seed(123))?
+            .with_datagram(datagram())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            // pace the datagrams so each one is sent in its own packet
+            for i in 0..COUNT {
+                connection
+                    .datagram_mut(|sender: &mut default::Sender| {
+                        sender.send_datagram(Bytes::from(vec![i; 200]))
+                    })
+                    .unwrap()
+                    .unwrap();
+                delay(Duration::from_millis(10)).await;
+            }
+
+            delay(Duration::from_secs(1)).await;
+            drop(connection);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let received = received.lock().unwrap();
+    assert!(received.iter().all(|datagram| datagram.len() == 200));
+    let mut received: Vec<_> = received.iter().map(|datagram| datagram[0]).collect();
+    received.sort_unstable();
+    let expected: Vec<_> = (0..COUNT).collect();
+    assert_eq!(received, expected);
+}
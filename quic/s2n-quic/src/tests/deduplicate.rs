@@ -108,6 +108,31 @@ fn deduplicate_successfully() {
     assert_eq!(client_started_count, 3);
 }
 
+#[test]
+fn deduplicate_without_dc_fails() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr)
+                .with_server_name("localhost")
+                .with_deduplicate(true);
+            let error = client.connect(connect).await.unwrap_err();
+            assert!(
+                matches!(error, crate::connection::Error::InvalidConfiguration { .. }),
+                "{error:?}"
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
 #[track_caller]
 fn confirm_conn_works(
     conn: &mut crate::connection::Connection,
@@ -0,0 +1,101 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::demux::{Datagram, Demultiplexer, Responder};
+use s2n_quic_core::inet::ExplicitCongestionNotification;
+
+/// The first bytes of a STUN binding request and success response
+const BINDING_REQUEST: [u8; 2] = [0x00, 0x01];
+const BINDING_RESPONSE: [u8; 2] = [0x01, 0x01];
+
+/// Responds to STUN binding requests and records every datagram it's handed
+#[derive(Clone, Default)]
+struct Stun(Arc<Mutex<Vec<Vec<u8>>>>);
+
+impl Demultiplexer for Stun {
+    fn on_datagram(
+        &mut self,
+        _datagram: &Datagram,
+        payload: &[u8],
+        responder: &mut dyn Responder,
+    ) -> bool {
+        self.0.lock().unwrap().push(payload.to_vec());
+
+        let Some(transaction) = payload.strip_prefix(&BINDING_REQUEST) else {
+            return false;
+        };
+
+        let mut response = BINDING_RESPONSE.to_vec();
+        response.extend_from_slice(transaction);
+        responder.respond(&response);
+
+        true
+    }
+}
+
+/// Ensures datagrams for other protocols are handed to the demultiplexer and can be responded to
+/// while QUIC connections share the socket
+#[test]
+fn demux_test() {
+    let model = Model::default();
+    let stun = Stun::default();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_demux(stun.clone())?
+            .start()?;
+        let server_addr = start_server(server)?;
+        let client = build_client(handle)?;
+        let socket = handle.builder().build()?.socket();
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let send = |payload: &[u8]| {
+                socket
+                    .send_to(
+                        server_addr,
+                        ExplicitCongestionNotification::NotEct,
+                        payload.to_vec(),
+                    )
+                    .unwrap()
+            };
+
+            // the first byte marks the datagram as STUN
+            send(&[0x00, 0x01, 1, 2, 3]);
+            let (addr, _ecn, response) = socket.recv_from().await.unwrap();
+            assert_eq!(addr, server_addr);
+            assert_eq!(response, [0x01, 0x01, 1, 2, 3]);
+
+            // datagrams which look like QUIC but fail to decode are also handed over
+            send(&[0x40]);
+            // unrecognized datagrams aren't responded to
+            send(&[0x02]);
+
+            // the QUIC connection is unaffected
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap().unwrap(),
+                Bytes::from_static(b"hello")
+            );
+
+            assert!(socket.try_recv_from().unwrap().is_none());
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let datagrams = stun.0.lock().unwrap();
+    assert_eq!(
+        *datagrams,
+        [vec![0x00, 0x01, 1, 2, 3], vec![0x40], vec![0x02]]
+    );
+}
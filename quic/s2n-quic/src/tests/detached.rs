@@ -0,0 +1,127 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{event, io::detached};
+use core::task::Poll;
+use futures::future::poll_fn;
+use s2n_quic_core::inet::{SocketAddress, SocketAddressV4};
+use std::time::Instant;
+
+/// Forwards all of the pending datagrams from one endpoint to the other
+///
+/// Returns the number of datagrams that were forwarded
+fn forward(
+    from: &detached::Handle,
+    from_address: SocketAddress,
+    to: &detached::Handle,
+    now: Instant,
+) -> usize {
+    let mut buffer = vec![0; from.max_mtu()];
+    let mut count = 0;
+    while let Some(transmit) = from.poll_transmit(now, &mut buffer) {
+        to.receive(now, from_address, transmit.ecn, &mut buffer[..transmit.len]);
+        count += 1;
+    }
+    count
+}
+
+#[test]
+fn detached_echo_test() {
+    let server_address: SocketAddress = SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+    let client_address: SocketAddress = SocketAddressV4::new([127, 0, 0, 1], 4434).into();
+
+    let server_io = detached::Provider::builder()
+        .with_local_address(server_address)
+        .unwrap()
+        .build()
+        .unwrap();
+    let server_handle = server_io.handle();
+    // The tracing subscriber used by the other tests reads the time from the simulated network,
+    // which isn't available on this thread
+    let mut server = Server::builder()
+        .with_io(server_io)
+        .unwrap()
+        .with_event(event::disabled::Provider)
+        .unwrap()
+        .with_tls(SERVER_CERTS)
+        .unwrap()
+        .start()
+        .unwrap();
+    assert_eq!(server.local_addr().unwrap(), server_address.into());
+
+    let client_io = detached::Provider::builder()
+        .with_local_address(client_address)
+        .unwrap()
+        .build()
+        .unwrap();
+    let client_handle = client_io.handle();
+    let client = Client::builder()
+        .with_io(client_io)
+        .unwrap()
+        .with_event(event::disabled::Provider)
+        .unwrap()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async move {
+        tokio::spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                stream.send(chunk).await.unwrap();
+            }
+            stream.finish().unwrap();
+            // keep the connection open until the client closes it
+            let _ = connection.accept().await;
+        });
+
+        let client = tokio::spawn(async move {
+            let connect =
+                Connect::new(SocketAddr::from(server_address)).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut received = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received.extend_from_slice(&chunk);
+            }
+            received
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        while !client.is_finished() {
+            let now = Instant::now();
+            assert!(now < deadline, "test timed out");
+
+            forward(&client_handle, client_address, &server_handle, now);
+            forward(&server_handle, server_address, &client_handle, now);
+
+            // register interest in application wakeups from both endpoints
+            poll_fn(|cx| {
+                let _ = client_handle.poll_wakeups(cx, now);
+                let _ = server_handle.poll_wakeups(cx, now);
+                Poll::Ready(())
+            })
+            .await;
+
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(client.await.unwrap(), b"hello");
+        assert!(client_handle.timeout().is_some());
+    });
+}
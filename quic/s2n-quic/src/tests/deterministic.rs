@@ -0,0 +1,45 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Completes a handshake with both endpoints drawing randomness from the executor and returns
+/// the connection IDs the client observed from the server
+fn connection_ids(seed: u64) -> Vec<Vec<u8>> {
+    let model = Model::default();
+    let subscriber = recorder::ConnectionIdUpdated::new();
+    let events = subscriber.events();
+
+    io::test_seed(model, seed, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(io::Random)?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(io::Random)?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    events.clone()
+}
+
+#[test]
+fn deterministic_connection_ids_test() {
+    let ids = connection_ids(123);
+    assert!(!ids.is_empty());
+
+    // the same seed produces the same connection IDs
+    assert_eq!(ids, connection_ids(123));
+    // a different seed produces different connection IDs
+    assert_ne!(ids, connection_ids(456));
+}
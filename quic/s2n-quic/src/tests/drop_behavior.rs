@@ -0,0 +1,140 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::{DropBehavior, Error};
+
+/// Connects to `server_addr` with the provided client limits and drops the connection after
+/// sending `data` on a bidirectional stream
+fn drop_client(
+    handle: &io::Handle,
+    server_addr: SocketAddr,
+    limits: provider::limits::Limits,
+    configure: fn(&mut crate::Connection),
+) -> io::Result<()> {
+    let client = Client::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(123))?
+        .with_limits(limits)?
+        .start()?;
+
+    primary::spawn(async move {
+        let connect = Connect::new(server_addr).with_server_name("localhost");
+        let mut connection = client.connect(connect).await.unwrap();
+        configure(&mut connection);
+
+        let mut stream = connection.open_bidirectional_stream().await.unwrap();
+        stream.send(Bytes::from_static(b"hello")).await.unwrap();
+        stream.finish().unwrap();
+
+        // dropping the stream and the last connection handle triggers the drop behavior
+        drop(stream);
+        drop(connection);
+
+        // keep the client endpoint around while the server observes the behavior
+        delay(Duration::from_secs(5)).await;
+    });
+
+    Ok(())
+}
+
+type Outcome = Arc<Mutex<Option<(Error, Duration)>>>;
+
+/// Starts a server which records how each connection was closed and how long it took to be
+/// closed after the first stream was accepted
+fn start_recording_server(handle: &io::Handle, outcome: &Outcome) -> io::Result<SocketAddr> {
+    let mut server = build_server(handle)?;
+    let server_addr = server.local_addr()?;
+
+    let recorded = outcome.clone();
+    spawn(async move {
+        let mut connection = server.accept().await.unwrap();
+        let start = io::time::now();
+
+        let result = async {
+            while let Some(mut stream) = connection.accept_bidirectional_stream().await? {
+                spawn(async move { while let Ok(Some(_)) = stream.receive().await {} });
+            }
+            Ok::<_, Error>(())
+        }
+        .await;
+
+        // the connection was closed without an error if the stream acceptor returned `None`
+        let error = result.err().or_else(|| connection.ping().err()).unwrap();
+        *recorded.lock().unwrap() = Some((error, io::time::now() - start));
+    });
+
+    Ok(server_addr)
+}
+
+/// Ensures the default behavior flushes the streams and closes the connection without an error
+#[test]
+fn linger_test() {
+    let model = Model::default();
+    let outcome = Outcome::default();
+    test(model, |handle| {
+        let server_addr = start_recording_server(handle, &outcome)?;
+        drop_client(handle, server_addr, Default::default(), |_| {})?;
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let (error, elapsed) = outcome.lock().unwrap().take().unwrap();
+    assert!(
+        matches!(error, Error::Closed { initiator, .. } if initiator.is_remote()),
+        "{error:?}"
+    );
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+}
+
+/// Ensures a connection configured to close on drop is closed with the provided error code
+#[test]
+fn close_test() {
+    let model = Model::default();
+    let outcome = Outcome::default();
+    test(model, |handle| {
+        let server_addr = start_recording_server(handle, &outcome)?;
+        drop_client(handle, server_addr, Default::default(), |connection| {
+            connection
+                .set_drop_behavior(DropBehavior::Close(42u8.into()))
+                .unwrap();
+        })?;
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let (error, elapsed) = outcome.lock().unwrap().take().unwrap();
+    assert!(
+        matches!(error, Error::Application { error, initiator, .. } if initiator.is_remote() && *error == 42),
+        "{error:?}"
+    );
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+}
+
+/// Ensures a connection configured to be kept alive on drop remains open until it idles out
+#[test]
+fn keep_alive_test() {
+    let model = Model::default();
+    let idle_timeout = Duration::from_secs(2);
+    let outcome = Outcome::default();
+    test(model, |handle| {
+        let server_addr = start_recording_server(handle, &outcome)?;
+        let limits = provider::limits::Limits::default()
+            .with_max_idle_timeout(idle_timeout)
+            .unwrap()
+            .with_drop_behavior(DropBehavior::KeepAlive)
+            .unwrap();
+        drop_client(handle, server_addr, limits, |connection| {
+            // PINGs are disabled once the last handle is dropped
+            connection.keep_alive(true).unwrap();
+        })?;
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let (error, elapsed) = outcome.lock().unwrap().take().unwrap();
+    assert!(matches!(error, Error::IdleTimerExpired { .. }), "{error:?}");
+    assert!(elapsed >= idle_timeout, "{elapsed:?}");
+}
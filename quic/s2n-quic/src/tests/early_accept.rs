@@ -0,0 +1,91 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::io::testing::{Handle, Result};
+
+fn build_early_accept_server(handle: &Handle) -> Result<Server> {
+    let server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(123))?
+        .with_limits(
+            provider::limits::Limits::default()
+                .with_early_accept(true)
+                .unwrap(),
+        )?
+        .start()?;
+    Ok(server)
+}
+
+/// Ensures the server can inspect a connection before its handshake completes
+#[test]
+fn early_accept_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    let timings = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let mut server = build_early_accept_server(handle)?;
+        let addr = server.local_addr()?;
+
+        let server_timings = timings.clone();
+        spawn(async move {
+            let start = io::time::now();
+            let connecting = server.accept_connecting().await.unwrap();
+            let accepted = io::time::now();
+
+            assert_eq!(
+                connecting.server_name().unwrap().as_deref(),
+                Some("localhost")
+            );
+            assert!(connecting.remote_addr().is_ok());
+            let id = connecting.id();
+
+            let mut connection = connecting.await.unwrap();
+            let connected = io::time::now();
+            assert_eq!(connection.id(), id);
+
+            *server_timings.lock().unwrap() = Some((accepted - start, connected - start));
+
+            while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                spawn(async move {
+                    while let Ok(Some(chunk)) = stream.receive().await {
+                        let _ = stream.send(chunk).await;
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(10_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let (accepted, connected) = timings.lock().unwrap().take().unwrap();
+    // the connection is accepted once the ClientHello arrives and connected once the client's
+    // Finished message arrives, a round trip later
+    assert!(
+        connected >= accepted + Duration::from_millis(100),
+        "{accepted:?} {connected:?}"
+    );
+}
+
+/// Ensures `accept` only yields connections once the handshake completes, even if they were
+/// handed over early
+#[test]
+fn early_accept_connected_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = build_early_accept_server(handle)?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(10_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,104 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::{Event, Level, Meta, SampleRate};
+
+/// Records the level of every event it receives
+#[derive(Clone)]
+struct LevelRecorder {
+    rate: fn(Level) -> SampleRate,
+    levels: Arc<Mutex<Vec<Level>>>,
+}
+
+impl LevelRecorder {
+    fn new(rate: fn(Level) -> SampleRate) -> Self {
+        Self {
+            rate,
+            levels: Default::default(),
+        }
+    }
+
+    fn levels(&self) -> Vec<Level> {
+        self.levels.lock().unwrap().clone()
+    }
+}
+
+impl events::Subscriber for LevelRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn sample_rate<E: Event>(&self) -> SampleRate {
+        (self.rate)(E::LEVEL)
+    }
+
+    fn on_event<M: Meta, E: Event>(&mut self, _meta: &M, _event: &E) {
+        self.levels.lock().unwrap().push(E::LEVEL);
+    }
+}
+
+fn run(subscriber: impl events::Subscriber) {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(subscriber)?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(10_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures events below a subscriber's level are never published to it
+#[test]
+fn level_filter_test() {
+    let info = LevelRecorder::new(|level| {
+        if level >= Level::Info {
+            SampleRate::ALL
+        } else {
+            SampleRate::NONE
+        }
+    });
+
+    run(info.clone());
+
+    let levels = info.levels();
+    assert!(!levels.is_empty());
+    assert!(levels.iter().all(|level| *level >= Level::Info));
+}
+
+/// Ensures each subscriber only receives the events allowed by its sample rate
+#[test]
+fn sample_rate_test() {
+    let all = LevelRecorder::new(|_| SampleRate::ALL);
+    let none = LevelRecorder::new(|_| SampleRate::NONE);
+    let sampled = LevelRecorder::new(|_| SampleRate::one_in(4));
+
+    // a disabled subscriber composed with an enabled one shouldn't receive any events
+    run((all.clone(), none.clone()));
+    run((all.clone(), sampled.clone()));
+
+    let all = all.levels();
+    assert!(all.iter().any(|level| *level == Level::Trace));
+    assert!(none.levels().is_empty());
+
+    // the sampled subscriber should see roughly a quarter of the frequent events
+    let trace_count = |levels: &[Level]| levels.iter().filter(|l| **l == Level::Trace).count();
+    let sampled = trace_count(&sampled.levels());
+    let expected = trace_count(&all) / 2 / 4;
+    assert!(
+        sampled.abs_diff(expected) <= expected / 2,
+        "{sampled} {expected}"
+    );
+}
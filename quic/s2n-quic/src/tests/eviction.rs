@@ -0,0 +1,76 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::Error,
+    provider::endpoint_limits::{self, EvictionCriteria},
+};
+use s2n_quic_core::{endpoint, event::api::EvictionCriteria as EventCriteria, transport};
+
+/// Ensures the oldest connection is evicted once the endpoint exceeds its connection threshold
+#[test]
+fn evict_oldest_test() {
+    let model = Model::default();
+    let evicted = recorder::ConnectionEvicted::new();
+    let evicted_events = evicted.events();
+
+    test(model, |handle| {
+        let limits = endpoint_limits::Default::builder()
+            .with_max_connections(1)?
+            .with_eviction_criteria(EvictionCriteria::Oldest)?
+            .build()?;
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), evicted))?
+            .with_random(Random::with_seed(456))?
+            .with_endpoint_limits(limits)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move { while let Ok(Some(_)) = connection.accept().await {} });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut oldest = client.connect(connect.clone()).await.unwrap();
+
+            delay(Duration::from_millis(100)).await;
+            let mut newest = client.connect(connect).await.unwrap();
+
+            // the first connection should be closed by the server with the eviction error
+            let error = oldest.accept().await.unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    Error::Transport {
+                        code,
+                        initiator: endpoint::Location::Remote,
+                        ..
+                    } if code == transport::Error::CONNECTION_REFUSED.code
+                ),
+                "{error:?}"
+            );
+
+            // the newest connection should remain open
+            delay(Duration::from_millis(100)).await;
+            newest.ping().unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let evicted_events = evicted_events.lock().unwrap();
+    assert!(
+        matches!(evicted_events[..], [EventCriteria::Oldest { .. }]),
+        "{evicted_events:?}"
+    );
+}
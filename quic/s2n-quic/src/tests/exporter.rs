@@ -152,3 +152,111 @@ fn happy_case() {
         assert_eq!(client_key, &server_key[..]);
     });
 }
+
+const LABEL: &[u8] = b"EXPERIMENTAL EXPORTER s2n-quic";
+
+/// Ensures keying material can be exported on demand once the handshake has completed
+#[test]
+fn export_keying_material_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let limits = crate::provider::limits::Limits::new()
+            .with_keying_material_export(true)
+            .unwrap();
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((Exporter, tracing_events()))?
+            .with_limits(limits)?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let key = connection
+                .export_keying_material(LABEL, b"server", 16)
+                .unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(Bytes::from(key)).await.unwrap();
+            stream.finish().unwrap();
+            let _ = connection.accept().await;
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((Exporter, tracing_events()))?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the exporter is the same one which is available to the event subscriber
+            let key = connection
+                .export_keying_material(LABEL, b"some context", 32)
+                .unwrap();
+            let event_key = connection
+                .query_event_context(|ctx: &ExporterContext| ctx.key.unwrap())
+                .unwrap();
+            assert_eq!(key, event_key);
+
+            // different contexts produce different keys
+            let key = connection
+                .export_keying_material(LABEL, b"server", 16)
+                .unwrap();
+            assert_ne!(&key[..], &event_key[..16]);
+
+            // lengths beyond what the TLS exporter can produce are rejected up front
+            let error = connection
+                .export_keying_material(
+                    LABEL,
+                    b"some context",
+                    s2n_quic_core::crypto::tls::MAX_EXPORTED_KEYING_MATERIAL_LEN + 1,
+                )
+                .unwrap_err();
+            assert!(
+                matches!(error, crate::connection::Error::KeyingMaterialExport { .. }),
+                "{error:?}"
+            );
+
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+            let server_key = stream.receive().await.unwrap().unwrap();
+            assert_eq!(key, server_key);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Ensures keying material can't be exported unless the connection was configured to retain
+/// the TLS session
+#[test]
+fn export_keying_material_disabled_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let error = connection
+                .export_keying_material(LABEL, b"some context", 32)
+                .unwrap_err();
+            assert!(
+                matches!(error, crate::connection::Error::InvalidConfiguration { .. }),
+                "{error:?}"
+            );
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,96 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::io::testing::now;
+
+/// Ensures flushing a connection waits for the buffered data to be transmitted and acknowledged
+#[test]
+fn connection_flush_test() {
+    let delay = Duration::from_millis(100);
+    let model = Model::default();
+    model.set_delay(delay);
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+            let mut received = 0;
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received += chunk.len();
+            }
+            assert_eq!(received, 10_000);
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            // an idle connection is already flushed
+            connection.flush_acknowledged().await.unwrap();
+
+            stream.send(Bytes::from(vec![42; 10_000])).await.unwrap();
+            let start = now();
+
+            // the data only needs to be handed off for transmission
+            connection.flush().await.unwrap();
+            assert!(now() - start < delay, "{:?}", now() - start);
+
+            // the acknowledgement requires at least a round trip
+            connection.flush_acknowledged().await.unwrap();
+            assert!(now() - start >= delay * 2, "{:?}", now() - start);
+
+            stream.finish().unwrap();
+            stream.flush().await.unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures waiting for the connection to become idle also waits for ack-eliciting control
+/// frames which aren't associated with any stream data
+#[test]
+fn wait_idle_test() {
+    let delay = Duration::from_millis(100);
+    let model = Model::default();
+    model.set_delay(delay);
+
+    test(model, |handle| {
+        let server_addr = start_server(build_server(handle)?)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // wait for any packets from the handshake to settle
+            connection.wait_idle().await.unwrap();
+
+            // a PING doesn't carry any stream data so the connection is already flushed
+            connection.ping().unwrap();
+            let start = now();
+            connection.flush_acknowledged().await.unwrap();
+            assert_eq!(now(), start);
+
+            // the PING needs to be acknowledged before the connection is idle
+            connection.wait_idle().await.unwrap();
+            assert!(now() - start >= delay * 2, "{:?}", now() - start);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::stream::framed::{Error, Framed, LengthPrefix};
+use futures::{SinkExt, StreamExt};
+
+fn messages() -> Vec<Bytes> {
+    [0, 1, 63, 64, 1_000, 16_383, 16_384, 100_000]
+        .into_iter()
+        .map(|len| Bytes::from(vec![len as u8; len]))
+        .collect()
+}
+
+/// Ensures messages are echoed back by the peer over framed streams
+#[test]
+fn framed_echo_test() {
+    for length_prefix in [LengthPrefix::VarInt, LengthPrefix::U32] {
+        let model = Model::default();
+        test(model, |handle| {
+            let mut server = build_server(handle)?;
+            let server_addr = server.local_addr()?;
+
+            spawn(async move {
+                while let Some(mut connection) = server.accept().await {
+                    spawn(async move {
+                        while let Ok(Some(stream)) = connection.accept_bidirectional_stream().await
+                        {
+                            let mut framed = Framed::new(stream).with_length_prefix(length_prefix);
+                            while let Some(message) = framed.next().await {
+                                framed.send(message.unwrap()).await.unwrap();
+                            }
+                            framed.close().await.unwrap();
+                        }
+                    });
+                }
+            });
+
+            let client = build_client(handle)?;
+
+            primary::spawn(async move {
+                let connect = Connect::new(server_addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+                let stream = connection.open_bidirectional_stream().await.unwrap();
+                let (mut sender, mut receiver) = Framed::new(stream)
+                    .with_length_prefix(length_prefix)
+                    .split();
+
+                primary::spawn(async move {
+                    for message in messages() {
+                        sender.feed(message).await.unwrap();
+                    }
+                    sender.close().await.unwrap();
+                });
+
+                let mut received = vec![];
+                while let Some(message) = receiver.next().await {
+                    received.push(message.unwrap());
+                }
+                assert_eq!(received, messages());
+            });
+
+            Ok(())
+        })
+        .unwrap();
+    }
+}
+
+/// Ensures messages which exceed the maximum size are rejected by both peers
+#[test]
+fn framed_max_message_size_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    while let Ok(Some(stream)) = connection.accept_receive_stream().await {
+                        let mut framed = Framed::new(stream).with_max_message_size(100);
+                        assert_eq!(framed.next().await.unwrap().unwrap().len(), 100);
+                        assert_eq!(
+                            framed.next().await.unwrap(),
+                            Err(Error::MessageTooLarge { limit: 100 })
+                        );
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let stream = connection.open_send_stream().await.unwrap();
+
+            let mut framed = Framed::new(stream).with_max_message_size(100);
+            assert_eq!(
+                framed.send(Bytes::from(vec![42; 101])).await,
+                Err(Error::MessageTooLarge { limit: 100 })
+            );
+            framed.send(Bytes::from(vec![42; 100])).await.unwrap();
+
+            // bypass the local limit to make sure the peer enforces its own
+            let mut framed = Framed::new(framed.into_inner());
+            framed.feed(Bytes::from(vec![42; 101])).await.unwrap();
+
+            // the peer stops reading the stream after rejecting the message
+            let _ = framed.close().await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
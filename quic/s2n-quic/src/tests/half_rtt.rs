@@ -0,0 +1,75 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const GREETING: &[u8] = b"hello from the server";
+
+/// Returns how long it takes the client to receive a greeting from the server, which sends it as
+/// soon as the connection is accepted
+fn greeting_duration(half_rtt_data: bool) -> Duration {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    let duration = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_half_rtt_data(half_rtt_data)
+                    .unwrap(),
+            )?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    let mut stream = connection.open_send_stream().await.unwrap();
+                    stream.send(Bytes::from_static(GREETING)).await.unwrap();
+                    stream.finish().unwrap();
+                    // keep the connection open until the client is done
+                    let _ = connection.accept().await;
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+        let duration = duration.clone();
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+            let mut received = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received.extend_from_slice(&chunk);
+            }
+            assert_eq!(received, GREETING);
+
+            *duration.lock().unwrap() = Some(io::time::now() - start);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let duration = duration.lock().unwrap().take().unwrap();
+    duration
+}
+
+/// Servers which allow 0.5-RTT data can send a greeting with their first flight, which saves a
+/// round trip
+#[test]
+fn half_rtt_data_test() {
+    let full_rtt = greeting_duration(false);
+    let half_rtt = greeting_duration(true);
+
+    assert!(full_rtt > Duration::from_millis(150), "{full_rtt:?}");
+    assert!(half_rtt < Duration::from_millis(150), "{half_rtt:?}");
+}
@@ -0,0 +1,131 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::{handoff, Connection},
+    provider::limits::Limits,
+    stream::PeerStream,
+};
+
+fn limits() -> Limits {
+    Limits::default().with_connection_handoff(true).unwrap()
+}
+
+fn echo(mut connection: Connection) {
+    spawn(async move {
+        while let Ok(Some(stream)) = connection.accept().await {
+            if let PeerStream::Bidirectional(mut stream) = stream {
+                spawn(async move {
+                    while let Ok(Some(chunk)) = stream.receive().await {
+                        let _ = stream.send(chunk).await;
+                    }
+                });
+            }
+        }
+    });
+}
+
+/// Completes the handshake on a broker endpoint and resumes the connection on a worker endpoint
+/// which takes over the broker's address
+#[test]
+fn handoff_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    test(model, |handle| {
+        let broker_socket = Arc::new(Mutex::new(None));
+        let worker_socket = Arc::new(Mutex::new(None));
+
+        let mut broker = Server::builder()
+            .with_io(
+                handle
+                    .builder()
+                    .on_socket({
+                        let broker_socket = broker_socket.clone();
+                        move |socket| *broker_socket.lock().unwrap() = Some(socket)
+                    })
+                    .build()?,
+            )?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits())?
+            .start()?;
+
+        let worker = Server::builder()
+            .with_io(
+                handle
+                    .builder()
+                    .on_socket({
+                        let worker_socket = worker_socket.clone();
+                        move |socket| *worker_socket.lock().unwrap() = Some(socket)
+                    })
+                    .build()?,
+            )?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits())?
+            .start()?;
+
+        let broker_addr = broker.local_addr()?;
+
+        spawn(async move {
+            let connection = broker.accept().await.unwrap();
+            let state = connection.handoff().unwrap();
+
+            // the handed off connection can no longer be used on the broker
+            assert!(connection.handoff().is_err());
+
+            // transfer the state as the application would
+            let state = handoff::State::from_bytes(&state.to_vec()).unwrap();
+
+            // move the worker to the broker's address so it receives the connection's datagrams
+            let broker_socket = broker_socket.lock().unwrap().take().unwrap();
+            let worker_socket = worker_socket.lock().unwrap().take().unwrap();
+            broker_socket.rebind("192.0.2.1:443".parse().unwrap());
+            worker_socket.rebind(broker_addr);
+
+            let connection = worker.import(state).await.unwrap();
+            assert_eq!(&*connection.server_name().unwrap().unwrap(), "localhost");
+            echo(connection);
+
+            // keep both endpoints open while the client is using the connection
+            let _broker = broker;
+            let _worker = worker;
+            core::future::pending::<()>().await;
+        });
+
+        let client = build_client(handle)?;
+        start_client(client, broker_addr, Data::new(10_000))?;
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the connection can't be handed off if the limit is disabled
+#[test]
+fn handoff_disabled_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let connection = server.accept().await.unwrap();
+            assert!(connection.handoff().is_err());
+            echo(connection);
+            let _server = server;
+            core::future::pending::<()>().await;
+        });
+
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(1_000))?;
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,125 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::endpoint_limits::{ConnectionAttempt, InitialPacket, Limiter, Outcome};
+
+fn run(model: Model, retry: bool) -> (Vec<events::HandshakeTiming>, Vec<events::HandshakeTiming>) {
+    let server_timing = recorder::HandshakeTiming::new();
+    let client_timing = recorder::HandshakeTiming::new();
+    let server_events = server_timing.events();
+    let client_events = client_timing.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_timing))?
+            .with_random(Random::with_seed(456))?;
+        let server = if retry {
+            server.with_endpoint_limits(AlwaysRetry)?.start()?
+        } else {
+            server.start()?
+        };
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_timing))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        start_client(client, addr, Data::new(1000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_events = server_events.lock().unwrap().clone();
+    let client_events = client_events.lock().unwrap().clone();
+    (server_events, client_events)
+}
+
+/// Sends a Retry for every Initial packet without a token
+struct AlwaysRetry;
+
+impl Limiter for AlwaysRetry {
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        Outcome::allow()
+    }
+
+    fn on_initial_packet(&mut self, info: &InitialPacket) -> Outcome {
+        if info.token.is_empty() {
+            Outcome::retry()
+        } else {
+            Outcome::allow()
+        }
+    }
+}
+
+fn assert_ordered(timing: &events::HandshakeTiming) {
+    let initial_sent = timing.initial_sent.unwrap();
+    let initial_received = timing.initial_received.unwrap();
+    let handshake_keys_available = timing.handshake_keys_available.unwrap();
+
+    assert!(initial_received <= handshake_keys_available, "{timing:?}");
+    assert!(initial_sent <= timing.handshake_complete, "{timing:?}");
+    assert!(
+        handshake_keys_available <= timing.handshake_complete,
+        "{timing:?}"
+    );
+    assert!(
+        timing.handshake_complete <= timing.handshake_done,
+        "{timing:?}"
+    );
+    assert!(!timing.version_negotiation, "{timing:?}");
+}
+
+/// Ensures each endpoint emits a single timing breakdown once the handshake is done
+#[test]
+fn handshake_timing_test() {
+    let delay = Duration::from_millis(50);
+    let model = Model::default();
+    model.set_delay(delay);
+
+    let (server, client) = run(model, false);
+
+    assert_eq!(server.len(), 1, "{server:?}");
+    assert_eq!(client.len(), 1, "{client:?}");
+
+    for timing in server.iter().chain(client.iter()) {
+        assert_ordered(timing);
+        assert!(!timing.retry, "{timing:?}");
+    }
+
+    let client = &client[0];
+    // the client sends its Initial right away and waits a round trip for the server's response
+    assert!(client.initial_sent.unwrap() < delay, "{client:?}");
+    assert!(client.initial_received.unwrap() > delay, "{client:?}");
+    // the client confirms the handshake once it receives HANDSHAKE_DONE
+    assert!(client.handshake_done > delay * 3, "{client:?}");
+
+    let server = &server[0];
+    // the server's clock starts when it receives the client's Initial
+    assert!(server.initial_received.unwrap() < delay, "{server:?}");
+    // the server confirms the handshake as soon as it completes
+    assert!(server.handshake_complete > delay, "{server:?}");
+    assert_eq!(
+        server.handshake_done, server.handshake_complete,
+        "{server:?}"
+    );
+}
+
+/// Ensures the timing breakdown reports the Retry exchange on both endpoints
+#[test]
+fn handshake_timing_retry_test() {
+    let (server, client) = run(Model::default(), true);
+
+    assert_eq!(server.len(), 1, "{server:?}");
+    assert_eq!(client.len(), 1, "{client:?}");
+
+    for timing in server.iter().chain(client.iter()) {
+        assert_ordered(timing);
+        assert!(timing.retry, "{timing:?}");
+    }
+}
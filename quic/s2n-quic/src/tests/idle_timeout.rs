@@ -0,0 +1,165 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::Error;
+
+/// Starts a server which applies `configure` to each accepted connection and drains its streams
+fn start_server_with<F>(handle: &io::Handle, configure: F) -> io::Result<SocketAddr>
+where
+    F: Fn(&mut crate::Connection) + Send + 'static,
+{
+    let mut server = build_server(handle)?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            configure(&mut connection);
+            spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    spawn(async move { while let Ok(Some(_)) = stream.receive().await {} });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Ensures both endpoints report the lower of the advertised idle timeouts
+#[test]
+fn negotiated_idle_timeout_test() {
+    let model = Model::default();
+    let server_idle_timeout = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server_idle_timeout = server_idle_timeout.clone();
+        let server_addr = start_server_with(handle, move |connection| {
+            *server_idle_timeout.lock().unwrap() = Some(connection.idle_timeout().unwrap());
+        })?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_max_idle_timeout(Duration::from_secs(10))
+                    .unwrap(),
+            )?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            assert_eq!(
+                connection.idle_timeout().unwrap(),
+                Some(Duration::from_secs(10))
+            );
+
+            // the timeout can be lowered but never raised
+            connection
+                .set_idle_timeout(Duration::from_secs(60))
+                .unwrap();
+            assert_eq!(
+                connection.idle_timeout().unwrap(),
+                Some(Duration::from_secs(10))
+            );
+            connection.set_idle_timeout(Duration::from_secs(5)).unwrap();
+            assert_eq!(
+                connection.idle_timeout().unwrap(),
+                Some(Duration::from_secs(5))
+            );
+            connection.set_idle_timeout(Duration::ZERO).unwrap();
+            assert_eq!(
+                connection.idle_timeout().unwrap(),
+                Some(Duration::from_secs(10))
+            );
+
+            // give the server time to accept the connection
+            delay(Duration::from_millis(100)).await;
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    assert_eq!(
+        *server_idle_timeout.lock().unwrap(),
+        Some(Some(Duration::from_secs(10)))
+    );
+}
+
+/// Ensures a server can lower the idle timeout of an accepted connection and notifies the peer
+#[test]
+fn lowered_idle_timeout_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server_with(handle, |connection| {
+            connection.set_idle_timeout(Duration::from_secs(1)).unwrap();
+        })?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let start = io::time::now();
+
+            let result = connection.accept_bidirectional_stream().await;
+            let elapsed = io::time::now() - start;
+
+            // the server closes the connection instead of silently discarding it, which would
+            // leave the client waiting for its own idle timeout
+            assert!(matches!(result, Ok(None)), "{result:?}");
+            assert!(elapsed < Duration::from_secs(5), "{elapsed:?}");
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the inactivity timer closes the connection with the application error code even
+/// while the connection is kept alive
+#[test]
+fn inactivity_timeout_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server_with(handle, |connection| {
+            connection
+                .set_inactivity_timeout(Duration::from_secs(2), 42u8.into())
+                .unwrap();
+        })?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.keep_alive(true).unwrap();
+
+            // stream data keeps the connection active
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            for _ in 0..3 {
+                delay(Duration::from_secs(1)).await;
+                stream.send(Bytes::from_static(b"ping")).await.unwrap();
+            }
+            let start = io::time::now();
+
+            let error = connection.accept_bidirectional_stream().await.unwrap_err();
+            let elapsed = io::time::now() - start;
+
+            assert!(
+                matches!(error, Error::Application { error, initiator, .. } if initiator.is_remote() && *error == 42),
+                "{error:?}"
+            );
+            assert!(elapsed < Duration::from_secs(3), "{elapsed:?}");
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
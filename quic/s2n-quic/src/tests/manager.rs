@@ -0,0 +1,78 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::Error;
+use s2n_quic_core::endpoint;
+
+const ERROR_CODE: u32 = 42;
+
+/// Ensures connections can be listed, inspected and closed through the endpoint manager
+#[test]
+fn manager_test() {
+    let model = Model::default();
+    let outcome: Arc<Mutex<Option<Error>>> = Default::default();
+    let client_outcome = outcome.clone();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let addr = server.local_addr()?;
+        let manager = server.manager();
+        assert!(manager.connections().is_empty());
+
+        primary::spawn(async move {
+            let connection = server.accept().await.unwrap();
+
+            let connections = manager.connections();
+            assert_eq!(connections.len(), 1, "{connections:?}");
+            let summary = connections[0];
+            assert_eq!(summary.id, connection.id());
+            assert_eq!(
+                summary.remote_address,
+                connection.remote_addr().unwrap().into()
+            );
+
+            let stats = manager.statistics(summary.id).unwrap();
+            assert!(stats.packets_received > 0, "{stats:?}");
+
+            // unknown connections are ignored
+            assert!(manager.statistics(summary.id + 1).is_none());
+            assert!(!manager.close(summary.id + 1, ERROR_CODE.into()));
+
+            assert!(manager.close(summary.id, ERROR_CODE.into()));
+
+            // the connection is removed once its closing state is discarded, even while the
+            // application still holds a handle to it
+            delay(Duration::from_secs(10)).await;
+            assert!(manager.connections().is_empty());
+            assert!(manager.statistics(summary.id).is_none());
+            assert!(!manager.close(summary.id, ERROR_CODE.into()));
+            drop(connection);
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let error = connection.accept().await.unwrap_err();
+            *client_outcome.lock().unwrap() = Some(error);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let outcome = outcome.lock().unwrap().take().unwrap();
+    assert!(
+        matches!(
+            outcome,
+            Error::Application {
+                error,
+                initiator: endpoint::Location::Remote,
+                ..
+            } if error == ERROR_CODE.into()
+        ),
+        "{outcome:?}"
+    );
+}
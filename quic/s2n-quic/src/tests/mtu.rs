@@ -457,3 +457,124 @@ impl Interceptor for TruncatePadding {
         }
     }
 }
+
+// A connection-level max UDP payload size clamps the MTU probed on the path,
+// even if the endpoint and network support larger datagrams.
+#[test]
+fn conn_max_udp_payload_size() {
+    let model = Model::default();
+    model.set_max_udp_payload(10_000);
+    let max_datagram_size = Arc::new(Mutex::new(0));
+    let client_max_datagram_size = max_datagram_size.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(9_001).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(9_001).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_max_udp_payload_size(1400)
+                    .unwrap(),
+            )?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            stream.send(Bytes::from(vec![42; 1_000_000])).await.unwrap();
+            stream.finish().unwrap();
+            while stream.receive().await.unwrap().is_some() {}
+
+            *client_max_datagram_size.lock().unwrap() = connection.max_datagram_size().unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let max_datagram_size = *max_datagram_size.lock().unwrap();
+    // probing should have raised the MTU above the minimum without exceeding the limit
+    assert!(
+        (1300..=1400).contains(&max_datagram_size),
+        "{max_datagram_size}"
+    );
+}
+
+// The maximum datagram payload follows the path MTU as it is probed and changes are reported to
+// the application
+#[test]
+fn max_datagram_payload_changes() {
+    let model = Model::default();
+    model.set_max_udp_payload(1500);
+    let payloads = Arc::new(Mutex::new(vec![]));
+    let client_payloads = payloads.clone();
+
+    test(model, |handle| {
+        let datagram = || {
+            provider::datagram::default::Endpoint::builder()
+                .with_send_capacity(10)
+                .unwrap()
+                .with_recv_capacity(10)
+                .unwrap()
+                .build()
+                .unwrap()
+        };
+
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(1500).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_datagram(datagram())?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(1500).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_datagram(datagram())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut payload = connection.max_datagram_payload().unwrap();
+            client_payloads.lock().unwrap().push(payload);
+
+            // wait for MTU probing to raise the payload
+            while payload < 1400 {
+                payload = connection
+                    .max_datagram_payload_changed(payload)
+                    .await
+                    .unwrap();
+                client_payloads.lock().unwrap().push(payload);
+            }
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let payloads = payloads.lock().unwrap();
+    // the initial payload accounts for the packet overhead of the minimum MTU
+    assert!((1100..1200).contains(&payloads[0]), "{payloads:?}");
+    // each reported value is a change from the previous one
+    assert!(payloads.windows(2).all(|w| w[0] != w[1]), "{payloads:?}");
+    let last = *payloads.last().unwrap();
+    assert!((1400..1472).contains(&last), "{payloads:?}");
+}
@@ -0,0 +1,175 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::{InitialPadding, Limits};
+use s2n_quic_core::event::api::PacketHeader;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Initial,
+    Handshake,
+    OneRtt,
+    Other,
+}
+
+#[derive(Debug)]
+struct Datagram {
+    packets: Vec<(Kind, usize)>,
+    len: usize,
+}
+
+impl Datagram {
+    fn contains(&self, kind: Kind) -> bool {
+        self.packets.iter().any(|(k, _)| *k == kind)
+    }
+}
+
+/// Records the packets coalesced into each of the sent datagrams
+#[derive(Clone, Default)]
+struct Datagrams {
+    datagrams: Arc<Mutex<Vec<Datagram>>>,
+}
+
+struct ConnectionContext {
+    packets: Vec<(Kind, usize)>,
+    datagrams: Arc<Mutex<Vec<Datagram>>>,
+}
+
+impl events::Subscriber for Datagrams {
+    type ConnectionContext = ConnectionContext;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        ConnectionContext {
+            packets: vec![],
+            datagrams: self.datagrams.clone(),
+        }
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::PacketSent,
+    ) {
+        let kind = match event.packet_header {
+            PacketHeader::Initial { .. } => Kind::Initial,
+            PacketHeader::Handshake { .. } => Kind::Handshake,
+            PacketHeader::OneRtt { .. } => Kind::OneRtt,
+            _ => Kind::Other,
+        };
+        context.packets.push((kind, event.packet_len));
+    }
+
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::DatagramSent,
+    ) {
+        let packets = core::mem::take(&mut context.packets);
+        context.datagrams.lock().unwrap().push(Datagram {
+            packets,
+            len: event.len as usize,
+        });
+    }
+}
+
+/// Runs a connection and returns the datagrams sent by the server and the client
+fn run(server_limits: Limits, client_limits: Limits) -> (Vec<Datagram>, Vec<Datagram>) {
+    let model = Model::default();
+    let server_datagrams = Datagrams::default();
+    let client_datagrams = Datagrams::default();
+    let server_events = server_datagrams.datagrams.clone();
+    let client_events = client_datagrams.datagrams.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_datagrams))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(server_limits)?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_datagrams))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(client_limits)?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_events = core::mem::take(&mut *server_events.lock().unwrap());
+    let client_events = core::mem::take(&mut *client_events.lock().unwrap());
+    (server_events, client_events)
+}
+
+fn assert_initial_datagrams_padded(datagrams: &[Datagram]) {
+    for datagram in datagrams.iter().filter(|d| d.contains(Kind::Initial)) {
+        assert!(datagram.len >= 1200, "{datagram:?}");
+    }
+}
+
+#[test]
+fn coalesced_initial_padding() {
+    let (server, client) = run(Limits::default(), Limits::default());
+
+    assert_initial_datagrams_padded(&server);
+    assert_initial_datagrams_padded(&client);
+
+    // the server coalesces its Handshake packet with the Initial packet, which carries the padding
+    // instead
+    let datagram = server
+        .iter()
+        .find(|d| d.contains(Kind::Initial) && d.contains(Kind::Handshake))
+        .expect("the server should coalesce Initial and Handshake packets");
+    let (_, initial_len) = datagram.packets[0];
+    assert!(initial_len < 1200, "{datagram:?}");
+}
+
+#[test]
+fn initial_frames_padding() {
+    let limits = Limits::default()
+        .with_initial_padding(InitialPadding::Initial)
+        .unwrap();
+    let (server, client) = run(limits, limits);
+
+    for datagrams in [&server, &client] {
+        assert_initial_datagrams_padded(datagrams);
+
+        // the Initial packet fills the datagram on its own
+        for datagram in datagrams.iter().filter(|d| d.contains(Kind::Initial)) {
+            assert_eq!(datagram.packets.len(), 1, "{datagram:?}");
+            assert_eq!(datagram.packets[0].1, datagram.len, "{datagram:?}");
+        }
+    }
+}
+
+#[test]
+fn min_datagram_size() {
+    const MIN_SIZE: u16 = 1000;
+
+    let (server, client) = run(Limits::default(), Limits::default());
+    assert!(server
+        .iter()
+        .chain(&client)
+        .any(|d| d.len < MIN_SIZE as usize));
+
+    let limits = Limits::default().with_min_datagram_size(MIN_SIZE).unwrap();
+    let (server, client) = run(limits, limits);
+
+    for datagram in server.iter().chain(&client) {
+        assert!(datagram.len >= MIN_SIZE as usize, "{datagram:?}");
+    }
+}
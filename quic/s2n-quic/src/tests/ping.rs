@@ -0,0 +1,38 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::io::testing::now;
+
+/// Ensures an application PING resolves with the RTT sample of its acknowledgement
+#[test]
+fn ping_rtt_test() {
+    let delay = Duration::from_millis(100);
+    let model = Model::default();
+    model.set_delay(delay);
+
+    test(model.clone(), |handle| {
+        let server_addr = start_server(build_server(handle)?)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            for _ in 0..3 {
+                let start = now();
+                let rtt = connection.ping_rtt().await.unwrap();
+                assert!(rtt >= delay * 2, "{rtt:?}");
+                assert!(rtt <= now() - start, "{rtt:?}");
+            }
+
+            // the sample reflects the current conditions of the path
+            model.set_delay(delay * 3);
+            let rtt = connection.ping_rtt().await.unwrap();
+            assert!(rtt >= delay * 6, "{rtt:?}");
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,51 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{connection_id, limits::Limits};
+
+const HOST_ID: [u8; 4] = [1, 2, 3, 4];
+
+/// Ensures the server sends its preferred address and a host-pinned connection ID to the client
+#[test]
+fn preferred_address_test() {
+    let model = Model::default();
+    let preferred_address: SocketAddr = "192.0.2.1:4433".parse().unwrap();
+    let subscriber = recorder::PreferredAddressReceived::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let limits = Limits::default()
+            .with_active_connection_migration(false)?
+            .with_preferred_address(preferred_address.into())?;
+        let connection_id = connection_id::default::Format::builder()
+            .with_host_id(&HOST_ID)
+            .unwrap()
+            .build()?;
+
+        let server = Server::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .with_connection_id(connection_id)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        start_client(client, addr, Data::new(10_000))
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    let (ipv4_address, connection_id) = &events[0];
+    assert_eq!(*ipv4_address, Some(preferred_address));
+    assert!(connection_id.starts_with(&HOST_ID));
+}
@@ -0,0 +1,257 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::{Error, PriorityClass},
+    provider::endpoint_limits::{self, EvictionCriteria},
+};
+use s2n_quic_core::{endpoint, event::api::PriorityClass as EventClass, transport};
+
+/// Ensures the class requested with `Connect` is reported in the transmission events
+#[test]
+fn connect_priority_class_test() {
+    let model = Model::default();
+    let subscriber = recorder::TransmitPriorityClass::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        for class in [PriorityClass::Control, PriorityClass::Bulk] {
+            let client = client.clone();
+            primary::spawn(async move {
+                let connect = Connect::new(addr)
+                    .with_server_name("localhost")
+                    .with_priority_class(class);
+                let mut connection = client.connect(connect).await.unwrap();
+                let mut stream = connection.open_send_stream().await.unwrap();
+                stream.send(Bytes::from_static(&[1; 10_000])).await.unwrap();
+                stream.finish().unwrap();
+                delay(Duration::from_millis(100)).await;
+            });
+        }
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    let count = |f: fn(&EventClass) -> bool| events.iter().filter(|class| f(class)).count();
+    assert!(
+        count(|class| matches!(class, EventClass::Control { .. })) > 0,
+        "{events:?}"
+    );
+    assert!(
+        count(|class| matches!(class, EventClass::Bulk { .. })) > 0,
+        "{events:?}"
+    );
+    assert_eq!(
+        count(|class| matches!(class, EventClass::Normal { .. })),
+        0,
+        "{events:?}"
+    );
+}
+
+/// Ensures connections in more urgent classes are evicted after less urgent connections, even
+/// if the eviction criteria would select them first
+#[test]
+fn evict_less_urgent_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let limits = endpoint_limits::Default::builder()
+            .with_max_connections(1)?
+            .with_eviction_criteria(EvictionCriteria::Oldest)?
+            .build()?;
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_endpoint_limits(limits)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut is_first = true;
+            while let Some(mut connection) = server.accept().await {
+                // the oldest connection carries the control plane
+                if core::mem::take(&mut is_first) {
+                    connection
+                        .set_priority_class(PriorityClass::Control)
+                        .unwrap();
+                }
+                spawn(async move { while let Ok(Some(_)) = connection.accept().await {} });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut oldest = client.connect(connect.clone()).await.unwrap();
+
+            delay(Duration::from_millis(100)).await;
+            // the newest connection should be evicted during its handshake instead of the oldest
+            let error = client.connect(connect).await.unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    Error::Transport {
+                        code,
+                        initiator: endpoint::Location::Remote,
+                        ..
+                    } if code == transport::Error::CONNECTION_REFUSED.code
+                ),
+                "{error:?}"
+            );
+
+            delay(Duration::from_millis(100)).await;
+            oldest.ping().unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Records the order of the frames sent by each connection, along with the class of each
+/// connection
+#[derive(Clone, Default)]
+struct FrameOrder {
+    /// The time the frame was sent, the connection which sent it and if it was a STREAM frame
+    frames: Arc<Mutex<Vec<(Duration, u64, bool)>>>,
+    classes: Arc<Mutex<std::collections::HashMap<u64, EventClass>>>,
+}
+
+impl events::Subscriber for FrameOrder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_frame_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &events::ConnectionMeta,
+        event: &events::FrameSent,
+    ) {
+        let is_stream = matches!(event.frame, events::Frame::Stream { .. });
+        let timestamp = meta.timestamp.duration_since_start();
+        self.frames
+            .lock()
+            .unwrap()
+            .push((timestamp, meta.id, is_stream));
+    }
+
+    fn on_transmit_share(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &events::ConnectionMeta,
+        event: &events::TransmitShare,
+    ) {
+        self.classes
+            .lock()
+            .unwrap()
+            .insert(meta.id, event.priority_class.clone());
+    }
+}
+
+/// Ensures a connection with an ACK to send doesn't transmit its data ahead of connections in
+/// more urgent classes
+#[test]
+fn urgent_transmission_class_order_test() {
+    let model = Model::default();
+    let subscriber = FrameOrder::default();
+    let frames = subscriber.frames.clone();
+    let classes = subscriber.classes.clone();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        for class in [PriorityClass::Control, PriorityClass::Bulk] {
+            let client = client.clone();
+            primary::spawn(async move {
+                let connect = Connect::new(addr)
+                    .with_server_name("localhost")
+                    .with_priority_class(class);
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let mut send = if class == PriorityClass::Bulk {
+                    // the server echoes the data back, so the bulk connection has ACKs to send
+                    // while it transmits its own data
+                    let stream = connection.open_bidirectional_stream().await.unwrap();
+                    let (mut recv, send) = stream.split();
+                    primary::spawn(async move { while let Ok(Some(_)) = recv.receive().await {} });
+                    send
+                } else {
+                    connection.open_send_stream().await.unwrap()
+                };
+
+                let mut data = Data::new(200_000);
+                while let Some(chunk) = data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+        }
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let classes = classes.lock().unwrap();
+    let is_class = |id: &u64, f: fn(&EventClass) -> bool| classes.get(id).map_or(false, f);
+    let is_control = |id: &u64| is_class(id, |class| matches!(class, EventClass::Control { .. }));
+    let is_bulk = |id: &u64| is_class(id, |class| matches!(class, EventClass::Bulk { .. }));
+
+    let frames = frames.lock().unwrap();
+    let mut checked = 0;
+    let mut remaining = &frames[..];
+    while let Some((timestamp, _, _)) = remaining.first() {
+        // the frames sent at the same time belong to the same flush of the endpoint
+        let len = remaining
+            .iter()
+            .take_while(|(t, _, _)| t == timestamp)
+            .count();
+        let (flush, rest) = remaining.split_at(len);
+        remaining = rest;
+
+        let first_data = |is_class: &dyn Fn(&u64) -> bool| {
+            flush
+                .iter()
+                .position(|(_, id, is_stream)| *is_stream && is_class(id))
+        };
+
+        // only check the flushes where both connections transmit data
+        if let (Some(control), Some(bulk)) = (first_data(&is_control), first_data(&is_bulk)) {
+            // the bulk connection may send its ACKs first, but not its data
+            assert!(control < bulk, "{timestamp:?}");
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0);
+}
@@ -2,10 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use s2n_codec::encoder::scatter;
+use s2n_codec::{encoder::scatter, DecoderBufferMut};
 use s2n_quic_core::{
     event::api::{PacketHeader, Subject},
-    packet::interceptor::{Interceptor, Packet},
+    packet::interceptor::{Datagram, Interceptor, Packet},
 };
 
 /// This test ensures the PTO timer in the Handshake space is armed even
@@ -95,3 +95,99 @@ impl Interceptor for DropHandshakeTx {
         }
     }
 }
+
+/// Drops the first datagram received by the endpoint
+#[derive(Default)]
+struct DropFirstDatagramRx {
+    dropped: bool,
+}
+
+impl Interceptor for DropFirstDatagramRx {
+    #[inline]
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &Subject,
+        _datagram: &Datagram,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        if self.dropped {
+            return payload;
+        }
+
+        self.dropped = true;
+        DecoderBufferMut::new(&mut payload.into_less_safe_slice()[..0])
+    }
+}
+
+/// Returns how long the client takes to complete the handshake when the server doesn't receive
+/// its first datagram
+fn lossy_first_flight_handshake_duration(limits: provider::limits::Limits) -> Duration {
+    let model = Model::default();
+    let duration = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_packet_interceptor(DropFirstDatagramRx::default())?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits)?
+            .start()?;
+
+        let duration = duration.clone();
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(addr).with_server_name("localhost");
+            client.connect(connect).await.unwrap();
+            *duration.lock().unwrap() = Some(io::time::now() - start);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let duration = duration.lock().unwrap().take().unwrap();
+    duration
+}
+
+/// A lower handshake probe timeout recovers from a lost first flight sooner
+#[test]
+fn handshake_probe_timeout_test() {
+    let default = lossy_first_flight_handshake_duration(provider::limits::Limits::default());
+
+    let limits = provider::limits::Limits::default()
+        .with_handshake_probe_timeout(Duration::from_millis(100))
+        .unwrap();
+    let tuned = lossy_first_flight_handshake_duration(limits);
+
+    assert!(default > Duration::from_millis(999), "{default:?}");
+    assert!(tuned < Duration::from_millis(200), "{tuned:?}");
+}
+
+/// Duplicating the first flight recovers from a lost first packet without waiting for the PTO
+#[test]
+fn duplicate_first_flight_test() {
+    let limits = provider::limits::Limits::default()
+        .with_duplicate_first_flight(true)
+        .unwrap();
+    let duration = lossy_first_flight_handshake_duration(limits);
+
+    assert!(duration < Duration::from_millis(100), "{duration:?}");
+}
+
+#[should_panic]
+#[test]
+fn invalid_handshake_probe_timeout() {
+    provider::limits::Limits::default()
+        .with_handshake_probe_timeout(Duration::from_micros(999))
+        .unwrap();
+}
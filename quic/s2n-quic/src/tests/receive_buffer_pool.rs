@@ -0,0 +1,37 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+
+/// Ensures streams can transfer more data than the receive buffer pool retains
+#[test]
+fn receive_buffer_pool_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let limits = Limits::default()
+        .with_receive_buffer_pool(1024, 8 * 1024)
+        .unwrap();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits)?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits)?
+            .start()?;
+        start_client(client, addr, Data::new(100_000))
+    })
+    .unwrap();
+}
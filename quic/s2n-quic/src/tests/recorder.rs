@@ -65,7 +65,7 @@ event_recorder!(
     on_recovery_metrics,
     SocketAddr,
     |event: &events::RecoveryMetrics, storage: &mut Vec<SocketAddr>| {
-        let addr: SocketAddr = event.path.local_addr.to_string().parse().unwrap();
+        let addr = (&event.path.local_addr).into();
         if storage.last().map_or(true, |prev| *prev != addr) {
             storage.push(addr);
         }
@@ -80,6 +80,24 @@ event_recorder!(
         storage.push(event.pto_count);
     }
 );
+event_recorder!(
+    PacketLost,
+    PacketLost,
+    on_packet_lost,
+    u16,
+    |event: &events::PacketLost, storage: &mut Vec<u16>| {
+        storage.push(event.bytes_lost);
+    }
+);
+event_recorder!(
+    Rtt,
+    RecoveryMetrics,
+    on_recovery_metrics,
+    Duration,
+    |event: &events::RecoveryMetrics, storage: &mut Vec<Duration>| {
+        storage.push(event.latest_rtt);
+    }
+);
 event_recorder!(
     HandshakeStatus,
     HandshakeStatusUpdated,
@@ -97,6 +115,20 @@ event_recorder!(
     }
 );
 
+event_recorder!(
+    PathChallengeUpdated,
+    PathChallengeUpdated,
+    on_path_challenge_updated,
+    (bool, u8),
+    |event: &events::PathChallengeUpdated, storage: &mut Vec<(bool, u8)>| {
+        let validated = matches!(
+            event.path_challenge_status,
+            events::PathChallengeStatus::Validated { .. }
+        );
+        storage.push((validated, event.challenges_sent));
+    }
+);
+
 event_recorder!(
     PacketDropped,
     PacketDropped,
@@ -155,15 +187,136 @@ event_recorder!(
     }
 );
 
+event_recorder!(
+    LocalAddressLost,
+    LocalAddressLost,
+    on_local_address_lost,
+    SocketAddr,
+    |event: &events::LocalAddressLost, storage: &mut Vec<SocketAddr>| {
+        let addr = (&event.path.local_addr).into();
+        storage.push(addr);
+    }
+);
+
 event_recorder!(
     ConnectionStarted,
     ConnectionStarted,
     on_connection_started,
     SocketAddr,
     |event: &events::ConnectionStarted, storage: &mut Vec<SocketAddr>| {
-        let addr: SocketAddr = event.path.local_addr.to_string().parse().unwrap();
+        let addr = (&event.path.local_addr).into();
         if storage.last().map_or(true, |prev| *prev != addr) {
             storage.push(addr);
         }
     }
 );
+
+event_recorder!(
+    TransmitShare,
+    TransmitShare,
+    on_transmit_share,
+    (usize, bool),
+    |event: &events::TransmitShare, storage: &mut Vec<(usize, bool)>| {
+        storage.push((event.bytes, event.is_limited));
+    }
+);
+
+event_recorder!(
+    TransmitPriorityClass,
+    TransmitShare,
+    on_transmit_share,
+    events::PriorityClass,
+    |event: &events::TransmitShare, storage: &mut Vec<events::PriorityClass>| {
+        storage.push(event.priority_class.clone());
+    }
+);
+
+event_recorder!(
+    ConnectionClosed,
+    ConnectionClosed,
+    on_connection_closed,
+    events::ConnectionCloseLinger,
+    |event: &events::ConnectionClosed, storage: &mut Vec<events::ConnectionCloseLinger>| {
+        storage.push(event.linger.clone());
+    }
+);
+
+event_recorder!(
+    ConnectionEvicted,
+    ConnectionEvicted,
+    on_connection_evicted,
+    events::EvictionCriteria,
+    |event: &events::ConnectionEvicted, storage: &mut Vec<events::EvictionCriteria>| {
+        storage.push(event.criteria.clone());
+    }
+);
+
+event_recorder!(
+    CongestionState,
+    CongestionState,
+    on_congestion_state,
+    (u32, u64),
+    |event: &events::CongestionState, storage: &mut Vec<(u32, u64)>| {
+        storage.push((event.congestion_window, event.loss_epoch));
+    }
+);
+
+event_recorder!(HandshakeTiming, HandshakeTiming, on_handshake_timing);
+
+event_recorder!(
+    RecoveryMetrics,
+    RecoveryMetrics,
+    on_recovery_metrics,
+    (u64, Duration, u32),
+    |event: &events::RecoveryMetrics, storage: &mut Vec<(u64, Duration, u32)>| {
+        storage.push((event.path.id, event.min_rtt, event.congestion_window));
+    }
+);
+
+event_recorder!(
+    PathStatistics,
+    PathStatistics,
+    on_path_statistics,
+    (u64, u64),
+    |event: &events::PathStatistics, storage: &mut Vec<(u64, u64)>| {
+        storage.push((event.bytes_sent, event.bytes_received));
+    }
+);
+
+event_recorder!(
+    ConnectionIdUpdated,
+    ConnectionIdUpdated,
+    on_connection_id_updated,
+    Vec<u8>,
+    |event: &events::ConnectionIdUpdated, storage: &mut Vec<Vec<u8>>| {
+        storage.push(event.current.bytes.to_vec());
+    }
+);
+
+event_recorder!(
+    VersionNegotiationReceived,
+    VersionNegotiationReceived,
+    on_version_negotiation_received,
+    (Vec<u32>, events::VersionNegotiationOutcome),
+    |event: &events::VersionNegotiationReceived,
+     storage: &mut Vec<(Vec<u32>, events::VersionNegotiationOutcome)>| {
+        storage.push((event.server_versions.to_vec(), event.outcome.clone()));
+    }
+);
+
+event_recorder!(
+    PreferredAddressReceived,
+    TransportParametersReceived,
+    on_transport_parameters_received,
+    (Option<SocketAddr>, Vec<u8>),
+    |event: &events::TransportParametersReceived,
+     storage: &mut Vec<(Option<SocketAddr>, Vec<u8>)>| {
+        if let Some(preferred_address) = &event.transport_parameters.preferred_address {
+            let ipv4_address = preferred_address
+                .ipv4_address
+                .as_ref()
+                .map(|addr| addr.into());
+            storage.push((ipv4_address, preferred_address.connection_id.bytes.to_vec()));
+        }
+    }
+);
@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::tls;
+
+/// Ensures a reloaded TLS endpoint is used for new connections without restarting the server
+#[test]
+fn tls_reload_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_tls = tls::default::Server::builder()
+            .with_certificate(
+                certificates::UNTRUSTED_CERT_PEM,
+                certificates::UNTRUSTED_KEY_PEM,
+            )?
+            .build()?;
+        let (server_tls, reload_handle) = tls::Reloadable::new(server_tls);
+
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            // the client doesn't trust the initial certificate
+            let connect = Connect::new(addr).with_server_name("localhost");
+            assert!(client.connect(connect).await.is_err());
+
+            let server_tls = tls::default::Server::builder()
+                .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)
+                .unwrap()
+                .build()
+                .unwrap();
+            reload_handle.reload(server_tls);
+
+            // new connections use the reloaded certificate
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap(),
+                Some(Bytes::from_static(b"hello"))
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
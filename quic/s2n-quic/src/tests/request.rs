@@ -0,0 +1,135 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::request::Error;
+
+/// Starts a server which responds to each request with `response_len` bytes after `response_delay`
+fn start_responder(
+    handle: &io::Handle,
+    response_len: usize,
+    response_delay: Duration,
+) -> io::Result<SocketAddr> {
+    let mut server = build_server(handle)?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    spawn(async move {
+                        let mut request = vec![];
+                        while let Ok(Some(chunk)) = stream.receive().await {
+                            request.extend_from_slice(&chunk);
+                        }
+
+                        delay(response_delay).await;
+
+                        // echo the request back, padded to the response length
+                        request.resize(response_len.max(request.len()), 42);
+                        let _ = stream.send(Bytes::from(request)).await;
+                        let _ = stream.close().await;
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Ensures a request receives the complete response from the peer
+#[test]
+fn request_response_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let server_addr = start_responder(handle, 100_000, Duration::ZERO)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let response = connection
+                .request(Bytes::from_static(b"ping"))
+                .await
+                .unwrap();
+            assert_eq!(response.body().len(), 100_000);
+            assert_eq!(&response.body()[..4], b"ping");
+
+            // larger requests are written while the response is being read
+            let payload = Bytes::from(vec![1; 500_000]);
+            let response = connection.request(payload.clone()).await.unwrap();
+            assert_eq!(response.into_body(), payload);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures a request fails if the response exceeds the configured limit
+#[test]
+fn request_response_too_large_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_responder(handle, 10_000, Duration::ZERO)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let result = connection
+                .request(Bytes::from_static(b"ping"))
+                .with_max_response_size(1_000)
+                .await;
+            assert_eq!(result, Err(Error::ResponseTooLarge { limit: 1_000 }));
+
+            // the connection can still be used for other requests
+            let response = connection
+                .request(Bytes::from_static(b"ping"))
+                .await
+                .unwrap();
+            assert_eq!(response.body().len(), 10_000);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures a request fails if the response doesn't arrive before the timeout
+#[test]
+fn request_timeout_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_responder(handle, 100, Duration::from_secs(5))?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let result = connection
+                .request(Bytes::from_static(b"ping"))
+                .with_timeout(delay(Duration::from_secs(1)))
+                .await;
+            assert_eq!(result, Err(Error::TimedOut));
+
+            let response = connection
+                .request(Bytes::from_static(b"ping"))
+                .with_timeout(delay(Duration::from_secs(10)))
+                .await
+                .unwrap();
+            assert_eq!(response.body().len(), 100);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,116 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::stream;
+
+fn limits(reset_stream_at: bool) -> provider::limits::Limits {
+    provider::limits::Limits::default()
+        .with_reset_stream_at(reset_stream_at)
+        .unwrap()
+}
+
+/// Ensures the peer receives all of the data up to the reliable size before observing the reset
+#[test]
+fn reset_stream_at_delivers_reliable_data_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits(true))?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    while let Some(mut stream) = connection.accept_receive_stream().await.unwrap() {
+                        let mut received = 0;
+                        let error = loop {
+                            match stream.receive().await {
+                                Ok(Some(chunk)) => received += chunk.len(),
+                                Ok(None) => panic!("the stream should be reset"),
+                                Err(error) => break error,
+                            }
+                        };
+
+                        assert!(received >= 4_000);
+                        assert!(matches!(
+                            error,
+                            stream::Error::StreamReset { error, .. } if error == 123u8.into()
+                        ));
+                    }
+                });
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits(true))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            stream
+                .send(Bytes::from_static(&[42; 10_000]))
+                .await
+                .unwrap();
+            stream.reset_at(123u8.into(), 4_000).unwrap();
+
+            // the stream should no longer accept any data
+            assert!(stream.send(Bytes::from_static(&[42])).await.is_err());
+
+            // give the peer time to read the data
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures reliable resets are rejected if the peer doesn't support them
+#[test]
+fn reset_stream_at_unsupported_by_peer_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let server_addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits(true))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(&[42; 100])).await.unwrap();
+
+            assert!(matches!(
+                stream.reset_at(123u8.into(), 50),
+                Err(stream::Error::ReliableResetUnsupported { .. })
+            ));
+
+            // the stream can still be reset without a reliable size
+            stream.reset(123u8.into()).unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,84 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+use futures::future::poll_fn;
+
+/// Ensures the streams on a connection are limited by the connection-wide send buffer watermarks
+#[test]
+fn connection_send_buffer_watermark_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    let received = Arc::new(Mutex::new(0));
+    let server_received = received.clone();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let received = server_received.clone();
+                spawn(async move {
+                    while let Some(mut stream) = connection.accept_receive_stream().await.unwrap() {
+                        let received = received.clone();
+                        spawn(async move {
+                            while let Some(chunk) = stream.receive().await.unwrap() {
+                                *received.lock().unwrap() += chunk.len();
+                            }
+                        });
+                    }
+                });
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(
+                Limits::default()
+                    .with_connection_send_buffer_watermarks(8192, 1024)
+                    .unwrap(),
+            )?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream_a = connection.open_send_stream().await.unwrap();
+            let mut stream_b = connection.open_send_stream().await.unwrap();
+
+            // the first stream reaches the high watermark of the connection
+            stream_a
+                .send_data(Bytes::from_static(&[42; 10_000]))
+                .unwrap();
+            assert_eq!(connection.stats().unwrap().send_buffer_occupancy, 10_000);
+
+            // the second stream can't buffer anything until the connection drains to the low
+            // watermark
+            assert!(futures::poll!(poll_fn(|cx| stream_b.poll_send_ready(cx))).is_pending());
+
+            stream_b
+                .send(Bytes::from_static(&[42; 10_000]))
+                .await
+                .unwrap();
+            stream_a.close().await.unwrap();
+            stream_b.close().await.unwrap();
+
+            // all of the data has been acknowledged once the streams are closed
+            assert_eq!(connection.stats().unwrap().send_buffer_occupancy, 0);
+
+            // give the peer time to read the data
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(*received.lock().unwrap(), 20_000);
+}
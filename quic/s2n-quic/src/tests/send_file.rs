@@ -0,0 +1,80 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use std::io::Write;
+
+/// Ensures ranges of a file are sent on the stream
+#[test]
+fn send_file_test() {
+    let contents: Vec<u8> = (0..1_000_000u32).map(|v| v as u8).collect();
+
+    let path = std::env::temp_dir().join(format!("s2n-quic-send-file-{}", std::process::id()));
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(&contents)
+        .unwrap();
+    let file = Arc::new(std::fs::File::open(&path).unwrap());
+    std::fs::remove_file(&path).ok();
+
+    let received = Arc::new(Mutex::new(vec![]));
+    let server_received = received.clone();
+
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let received = server_received.clone();
+                spawn(async move {
+                    while let Ok(Some(mut stream)) = connection.accept_receive_stream().await {
+                        let mut data = vec![];
+                        while let Ok(Some(chunk)) = stream.receive().await {
+                            data.extend_from_slice(&chunk);
+                        }
+                        received.lock().unwrap().push(data);
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            for (range, expected) in [
+                (0..u64::MAX, 1_000_000),
+                (1_000..500_000, 499_000),
+                (999_000..2_000_000, 1_000),
+                (2_000_000..3_000_000, 0),
+            ] {
+                let mut stream = connection.open_send_stream().await.unwrap();
+                let len = stream.send_file(&file, range).await.unwrap();
+                assert_eq!(len, expected);
+                stream.close().await.unwrap();
+            }
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            assert_eq!(stream.send_file(&file, ..=9).await.unwrap(), 10);
+            stream.close().await.unwrap();
+
+            // give the peer time to read the data
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 5);
+    assert_eq!(received[0], contents);
+    assert_eq!(received[1], contents[1_000..500_000]);
+    assert_eq!(received[2], contents[999_000..]);
+    assert!(received[3].is_empty());
+    assert_eq!(received[4], contents[..10]);
+}
@@ -95,6 +95,68 @@ fn optimistic_ack_mitigation() {
     assert_eq!(client_skip_count, 5);
 }
 
+// Ensure packet numbers aren't skipped for Optimistic Ack mitigation once the limit is disabled
+#[test]
+fn optimistic_ack_mitigation_disabled() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+    const LEN: usize = 1_000_000;
+
+    let server_subscriber = recorder::PacketSkipped::new();
+    let server_events = server_subscriber.events();
+    test(model, |handle| {
+        let limits = crate::provider::limits::Limits::new()
+            .with_packet_number_skipping(false)
+            .unwrap();
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .start()?;
+
+        let addr = server.local_addr()?;
+        spawn(async move {
+            let mut conn = server.accept().await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+            stream.send(vec![42; LEN].into()).await.unwrap();
+            stream.flush().await.unwrap();
+        });
+
+        let client = build_client(handle)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.accept_bidirectional_stream().await.unwrap().unwrap();
+
+            let mut recv_len = 0;
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                recv_len += chunk.len();
+            }
+            assert_eq!(LEN, recv_len);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_skip_count = server_events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|reason| {
+            matches!(
+                reason,
+                events::PacketSkipReason::OptimisticAckMitigation { .. }
+            )
+        })
+        .count();
+
+    assert_eq!(server_skip_count, 0);
+}
+
 // Mimic an Optimistic Ack attack and confirm the connection is closed with
 // the appropriate error.
 //
@@ -0,0 +1,254 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::congestion_controller;
+
+/// Ensures the connection statistics are maintained without a user-provided subscriber
+#[test]
+fn connection_stats_test() {
+    let delay = Duration::from_millis(50);
+    let model = Model::default();
+    model.set_delay(delay);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let stats = connection.stats().unwrap();
+            assert_eq!(stats.streams_opened, 0);
+            assert_eq!(stats.paths, 1);
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(&[1; 10_000])).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut received = 0;
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received += chunk.len();
+            }
+            assert_eq!(received, 10_000);
+
+            let stats = connection.stats().unwrap();
+            assert_eq!(stats.streams_opened, 1);
+            assert_eq!(stats.paths, 1);
+            assert!(stats.smoothed_rtt > delay, "{stats:?}");
+            assert!(stats.min_rtt > delay, "{stats:?}");
+            assert!(stats.congestion_window > 0, "{stats:?}");
+            assert!(stats.delivery_rate.unwrap_or_default() > 0, "{stats:?}");
+            assert!(stats.bytes_sent > 10_000, "{stats:?}");
+            assert!(stats.bytes_received > 10_000, "{stats:?}");
+            assert!(stats.packets_sent > 0, "{stats:?}");
+            assert!(stats.packets_received > 0, "{stats:?}");
+            assert_eq!(stats.packets_lost, 0, "{stats:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the path info reports the BBR bandwidth estimate
+#[test]
+fn path_info_bbr_test() {
+    let delay = Duration::from_millis(50);
+    let model = Model::default();
+    model.set_delay(delay);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_congestion_controller(congestion_controller::Bbr::default())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_congestion_controller(congestion_controller::Bbr::default())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream
+                .send(Bytes::from_static(&[1; 100_000]))
+                .await
+                .unwrap();
+            stream.finish().unwrap();
+
+            let mut received = 0;
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received += chunk.len();
+            }
+            assert_eq!(received, 100_000);
+
+            let info = connection.path_info().unwrap();
+            assert!(info.smoothed_rtt > delay, "{info:?}");
+            assert!(info.min_rtt > delay, "{info:?}");
+            let bandwidth = info
+                .bandwidth_estimate
+                .expect("BBR should estimate bandwidth");
+            assert!(bandwidth > 0, "{info:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the path info omits the bandwidth estimate for controllers that don't model it
+#[test]
+fn path_info_cubic_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_congestion_controller(congestion_controller::Cubic::default())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let info = connection.path_info().unwrap();
+            assert!(info.smoothed_rtt > Duration::ZERO, "{info:?}");
+            assert_eq!(info.bandwidth_estimate, None);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the per-path statistics account for the data sent and lost on the active path
+#[test]
+fn path_stats_test() {
+    let delay = Duration::from_millis(50);
+    let model = Model::default();
+    model.set_delay(delay);
+    model.set_drop_rate(0.05);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream
+                .send(Bytes::from_static(&[1; 100_000]))
+                .await
+                .unwrap();
+            stream.finish().unwrap();
+
+            let mut received = 0;
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received += chunk.len();
+            }
+            assert_eq!(received, 100_000);
+
+            let stats = connection.stats().unwrap();
+            let paths = connection.path_stats().unwrap();
+            assert_eq!(paths.len(), 1);
+
+            let path = &paths[0];
+            assert!(path.is_active, "{path:?}");
+            assert_eq!(
+                path.validation,
+                crate::connection::PathValidation::Validated
+            );
+            assert_eq!(SocketAddr::from(path.remote_address), addr);
+            assert!(path.smoothed_rtt > delay, "{path:?}");
+            assert_eq!(path.bytes_sent, stats.bytes_sent, "{path:?}");
+            assert!(path.bytes_received > 100_000, "{path:?}");
+            assert!(path.packets_sent > 0, "{path:?}");
+            assert_eq!(path.packets_lost, stats.packets_lost, "{path:?}");
+            assert!(path.packets_lost > 0, "{path:?}");
+            assert!(path.loss_rate() > 0.0 && path.loss_rate() < 1.0, "{path:?}");
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the path statistics are published periodically once an interval is configured
+#[test]
+fn path_statistics_event_test() {
+    let model = Model::default();
+    let subscriber = recorder::PathStatistics::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_path_statistics_interval(Duration::from_millis(10))
+                    .unwrap(),
+            )?
+            .start()?;
+
+        start_client(client, addr, Data::new(1_000_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    assert!(!events.is_empty());
+
+    // the counters never go backwards
+    for pair in events.windows(2) {
+        assert!(pair[0].0 <= pair[1].0, "{pair:?}");
+        assert!(pair[0].1 <= pair[1].1, "{pair:?}");
+    }
+}
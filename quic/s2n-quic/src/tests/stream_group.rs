@@ -0,0 +1,71 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::stream::Group;
+use futures::future::poll_fn;
+
+/// Ensures streams in the same group are limited by the group's aggregate send budget
+#[test]
+fn stream_group_send_budget_test() {
+    let model = Model::default();
+    let received = Arc::new(Mutex::new(0));
+    let server_received = received.clone();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let received = server_received.clone();
+                spawn(async move {
+                    while let Some(mut stream) = connection.accept_receive_stream().await.unwrap() {
+                        let received = received.clone();
+                        spawn(async move {
+                            while let Some(chunk) = stream.receive().await.unwrap() {
+                                *received.lock().unwrap() += chunk.len();
+                            }
+                        });
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let group = Group::new(1, 4096);
+
+            let mut stream_a = connection.open_send_stream().await.unwrap();
+            stream_a.set_group(group).unwrap();
+            let mut stream_b = connection.open_send_stream().await.unwrap();
+            stream_b.set_group(group).unwrap();
+
+            // the first stream consumes the entire budget of the group
+            stream_a
+                .send_data(Bytes::from_static(&[42; 10_000]))
+                .unwrap();
+
+            // the second stream can't buffer anything until the first stream's data is acknowledged
+            assert!(futures::poll!(poll_fn(|cx| stream_b.poll_send_ready(cx))).is_pending());
+
+            stream_b
+                .send(Bytes::from_static(&[42; 10_000]))
+                .await
+                .unwrap();
+            stream_a.close().await.unwrap();
+            stream_b.close().await.unwrap();
+
+            // give the peer time to read the data
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(*received.lock().unwrap(), 20_000);
+}
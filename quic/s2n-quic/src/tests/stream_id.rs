@@ -0,0 +1,85 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{connection::Error, stream::Type};
+
+/// Ensures locally opened streams are assigned increasing IDs in the order they were opened
+#[test]
+fn open_order_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server(build_server(handle)?)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            for expected in [0, 4, 8] {
+                let stream = connection.open_stream(Type::Bidirectional).await.unwrap();
+                assert_eq!(stream.id(), expected);
+            }
+
+            for expected in [2, 6] {
+                let stream = connection.open_send_stream().await.unwrap();
+                assert_eq!(stream.id(), expected);
+            }
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Ensures a stream is only opened with an ID hint if the hint matches the next stream ID
+#[test]
+fn open_with_id_hint_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server(build_server(handle)?)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let control = connection
+                .open_stream_with_id_hint(Type::Unidirectional, 2)
+                .await
+                .unwrap();
+            assert_eq!(control.id(), 2);
+
+            // the ID was already used, the type doesn't match or the ID isn't valid
+            for (stream_type, id) in [
+                (Type::Unidirectional, 2),
+                (Type::Unidirectional, 10),
+                (Type::Bidirectional, 6),
+                (Type::Bidirectional, u64::MAX),
+            ] {
+                let error = connection
+                    .open_stream_with_id_hint(stream_type, id)
+                    .await
+                    .unwrap_err();
+                assert!(
+                    matches!(error, Error::InvalidConfiguration { .. }),
+                    "{error:?}"
+                );
+            }
+
+            // the mismatched hints don't consume any IDs or close the connection
+            let encoder = connection
+                .open_stream_with_id_hint(Type::Unidirectional, 6)
+                .await
+                .unwrap();
+            assert_eq!(encoder.id(), 6);
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            assert_eq!(stream.id(), 0);
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
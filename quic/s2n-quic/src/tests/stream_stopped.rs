@@ -0,0 +1,140 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures `stopped` reports the error code from the peer's STOP_SENDING frame
+#[test]
+fn stream_stopped_by_peer_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    while let Some(mut stream) = connection.accept_receive_stream().await.unwrap() {
+                        // read a single chunk and then stop the stream
+                        stream.receive().await.unwrap();
+                        stream.stop_sending(123u8.into()).unwrap();
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(&[42; 1024])).await.unwrap();
+
+            let error_code = stream.stopped().await.unwrap();
+            assert_eq!(error_code, Some(123u8.into()));
+
+            // the stream should no longer accept any data
+            assert!(stream.send(Bytes::from_static(&[42])).await.is_err());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures `stopped` resolves once the peer has acknowledged all of the data on a finished stream
+#[test]
+fn stream_stopped_after_finish_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    while let Some(mut stream) = connection.accept_receive_stream().await.unwrap() {
+                        // drain the receive stream
+                        while stream.receive().await.unwrap().is_some() {}
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(&[42; 1024])).await.unwrap();
+            stream.finish().unwrap();
+
+            let error_code = stream.stopped().await.unwrap();
+            assert_eq!(error_code, None);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures `close` resolves only after the peer has acknowledged the FIN
+#[test]
+fn stream_close_after_ack_test() {
+    let delay = Duration::from_millis(100);
+    let model = Model::default();
+    model.set_delay(delay);
+
+    let fin_received = Arc::new(Mutex::new(None));
+    let finished = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        let fin_received = fin_received.clone();
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                let fin_received = fin_received.clone();
+                spawn(async move {
+                    while let Some(mut stream) = connection.accept_receive_stream().await.unwrap() {
+                        while stream.receive().await.unwrap().is_some() {}
+                        *fin_received.lock().unwrap() = Some(io::time::now());
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        let finished = finished.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(&[42; 1024])).await.unwrap();
+            stream.close().await.unwrap();
+            *finished.lock().unwrap() = Some(io::time::now());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let fin_received = fin_received
+        .lock()
+        .unwrap()
+        .expect("the server received the FIN");
+    let finished = finished.lock().unwrap().expect("the stream finished");
+
+    // the acknowledgement for the FIN takes at least one network delay to reach the client
+    assert!(
+        finished >= fin_received + delay,
+        "finished at {finished:?} before the FIN was acknowledged at {fin_received:?}"
+    );
+}
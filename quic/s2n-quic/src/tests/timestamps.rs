@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn limits(timestamps: bool) -> provider::limits::Limits {
+    provider::limits::Limits::default()
+        .with_timestamps(timestamps)
+        .unwrap()
+}
+
+/// Runs a request between a client and server with the given timestamp support and returns the
+/// number of TIMESTAMP frames sent by each
+fn timestamp_frames(client_timestamps: bool, server_timestamps: bool) -> (usize, usize) {
+    let model = Model::default();
+    let client_frames = recorder::FrameSent::new();
+    let client_events = client_frames.events();
+    let server_frames = recorder::FrameSent::new();
+    let server_events = server_frames.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_frames))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits(server_timestamps))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_frames))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits(client_timestamps))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            stream
+                .send(Bytes::from_static(&[42; 10_000]))
+                .await
+                .unwrap();
+            stream.finish().unwrap();
+            while stream.receive().await.unwrap().is_some() {}
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let count = |events: &[events::FrameSent]| {
+        events
+            .iter()
+            .filter(|event| matches!(event.frame, events::Frame::Timestamp { .. }))
+            .count()
+    };
+
+    let client_count = count(&client_events.lock().unwrap());
+    let server_count = count(&server_events.lock().unwrap());
+    (client_count, server_count)
+}
+
+/// Ensures both endpoints attach TIMESTAMP frames to their acknowledgements when negotiated
+#[test]
+fn timestamps_negotiated_test() {
+    let (client, server) = timestamp_frames(true, true);
+    assert!(client > 0);
+    assert!(server > 0);
+}
+
+/// Ensures TIMESTAMP frames are only sent to peers which advertised support for receiving them
+#[test]
+fn timestamps_unsupported_by_peer_test() {
+    let (client, server) = timestamp_frames(true, false);
+    assert_eq!(client, 0);
+    assert_eq!(server, 0);
+
+    let (client, server) = timestamp_frames(false, true);
+    assert_eq!(client, 0);
+    assert_eq!(server, 0);
+}
@@ -0,0 +1,144 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    endpoint_limits::{ConnectionAttempt, InitialPacket, Limiter, Outcome},
+    limits::Limits,
+    token_cache::{Cache, Lru, Server as TokenServer},
+};
+use s2n_quic_core::{application::ServerName, inet};
+
+/// A token cache which can be inspected by the test
+#[derive(Clone, Default)]
+struct SharedCache(Arc<Mutex<Lru>>);
+
+impl SharedCache {
+    fn insert(&self, server_name: &str, remote_address: SocketAddr, token: &[u8]) {
+        let server_name = ServerName::from(server_name);
+        let remote_address = inet::SocketAddress::from(remote_address);
+        let server = TokenServer::new(Some(&server_name), &remote_address);
+        self.0.lock().unwrap().on_new_token(&server, token);
+    }
+
+    fn take(&self, server_name: &str, remote_address: SocketAddr) -> Option<Bytes> {
+        let server_name = ServerName::from(server_name);
+        let remote_address = inet::SocketAddress::from(remote_address);
+        let server = TokenServer::new(Some(&server_name), &remote_address);
+        self.0.lock().unwrap().take_token(&server)
+    }
+}
+
+impl Cache for SharedCache {
+    fn on_new_token(&mut self, server: &TokenServer, token: &[u8]) {
+        self.0.lock().unwrap().on_new_token(server, token)
+    }
+
+    fn take_token(&mut self, server: &TokenServer) -> Option<Bytes> {
+        self.0.lock().unwrap().take_token(server)
+    }
+}
+
+/// Records the token of each Initial packet the server receives
+#[derive(Clone, Default)]
+struct Tokens(Arc<Mutex<Vec<Vec<u8>>>>);
+
+impl Limiter for Tokens {
+    fn on_connection_attempt(&mut self, _info: &ConnectionAttempt) -> Outcome {
+        Outcome::allow()
+    }
+
+    fn on_initial_packet(&mut self, info: &InitialPacket) -> Outcome {
+        self.0.lock().unwrap().push(info.token.to_vec());
+        Outcome::allow()
+    }
+}
+
+fn start(
+    handle: &io::Handle,
+    cache: &SharedCache,
+    tokens: &Tokens,
+) -> io::Result<(Client, SocketAddr)> {
+    let server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_endpoint_limits(tokens.clone())?
+        .start()?;
+    let addr = start_server(server)?;
+
+    let limits = Limits::new().with_max_handshake_duration(Duration::from_secs(1))?;
+    let client = Client::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(123))?
+        .with_limits(limits)?
+        .with_token_cache(cache.clone())?
+        .start()?;
+
+    Ok((client, addr))
+}
+
+/// Ensures a cached token is presented to the server which issued it, and only once
+#[test]
+fn cached_token_test() {
+    let model = Model::default();
+    let cache = SharedCache::default();
+    let tokens = Tokens::default();
+    let mut server_addr = None;
+
+    test(model, |handle| {
+        let (client, addr) = start(handle, &cache, &tokens)?;
+        server_addr = Some(addr);
+        cache.insert("localhost", addr, b"cached token");
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            // the server doesn't recognize the token so it drops the client's Initial packets
+            client.connect(connect).await.unwrap_err();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let tokens = tokens.0.lock().unwrap();
+    assert!(!tokens.is_empty());
+    assert!(
+        tokens.iter().all(|token| token == b"cached token"),
+        "{tokens:?}"
+    );
+    drop(tokens);
+    // the token was removed from the cache when it was presented
+    assert!(cache.take("localhost", server_addr.unwrap()).is_none());
+}
+
+/// Ensures tokens for other servers aren't presented
+#[test]
+fn unrelated_token_test() {
+    let model = Model::default();
+    let cache = SharedCache::default();
+    let tokens = Tokens::default();
+    let mut server_addr = None;
+
+    test(model, |handle| {
+        let (client, addr) = start(handle, &cache, &tokens)?;
+        server_addr = Some(addr);
+        cache.insert("example.com", addr, b"other server");
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            client.connect(connect).await.unwrap();
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let tokens = tokens.0.lock().unwrap();
+    assert!(!tokens.is_empty());
+    assert!(tokens.iter().all(|token| token.is_empty()), "{tokens:?}");
+    assert!(cache.take("example.com", server_addr.unwrap()).is_some());
+}
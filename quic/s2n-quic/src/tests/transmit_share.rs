@@ -0,0 +1,55 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures connections sharing an endpoint take turns transmitting within their quantum
+#[test]
+fn transmit_share_test() {
+    let model = Model::default();
+    let quantum = 4_000;
+    let subscriber = recorder::TransmitShare::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_transmit_quantum(quantum)
+                    .unwrap(),
+            )?
+            .start()?;
+
+        for _ in 0..2 {
+            start_client(client.clone(), addr, Data::new(100_000))?;
+        }
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+
+    // the connections should have been cut short by their quantum
+    assert!(events.iter().any(|(_, is_limited)| *is_limited));
+
+    // a round can only overshoot the quantum by a single datagram
+    let max_datagram = 1500;
+    for (bytes, _) in events.iter() {
+        assert!(*bytes < quantum as usize + max_datagram, "{bytes}");
+    }
+}
+
+#[test]
+fn transmit_quantum_validation_test() {
+    assert!(provider::limits::Limits::default()
+        .with_transmit_quantum(0)
+        .is_err());
+}
@@ -0,0 +1,149 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::transport_parameters::Parameters;
+use s2n_quic_core::varint::VarInt;
+
+const CLIENT_PARAM: u64 = 0x2ab2;
+const SERVER_PARAM: u64 = 0x2ab3;
+
+/// Ensures custom transport parameters are exchanged between the client and server
+#[test]
+fn custom_transport_parameters_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_params = Parameters::default()
+            .with(VarInt::from_u16(SERVER_PARAM as u16), &b"server"[..])
+            .unwrap();
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_transport_parameters(server_params)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            assert_eq!(
+                connection.peer_transport_parameter(CLIENT_PARAM).unwrap(),
+                Some(Bytes::from_static(b"client"))
+            );
+            // the server didn't receive its own parameter
+            assert_eq!(
+                connection.peer_transport_parameter(SERVER_PARAM).unwrap(),
+                None
+            );
+
+            // notify the client that the checks are complete
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"done")).await.unwrap();
+            stream.finish().unwrap();
+            stream.flush().await.unwrap();
+        });
+
+        let client_params = Parameters::default()
+            .with(VarInt::from_u16(CLIENT_PARAM as u16), &b"client"[..])
+            .unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_transport_parameters(client_params)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            assert_eq!(
+                connection.peer_transport_parameter(SERVER_PARAM).unwrap(),
+                Some(Bytes::from_static(b"server"))
+            );
+            // the known transport parameters are not exposed
+            assert_eq!(connection.peer_transport_parameter(0x01).unwrap(), None);
+
+            // keep the connection open until the server is done with its checks
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap(),
+                Some(Bytes::from_static(b"done"))
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the application settings for the negotiated protocol are exchanged
+#[test]
+fn application_settings_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_params = Parameters::default()
+            .with_application_settings(b"h3", b"server h3")
+            .unwrap()
+            .with_application_settings(b"other", b"server other")
+            .unwrap();
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_transport_parameters(server_params)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            assert_eq!(
+                connection.peer_application_settings().unwrap(),
+                Some(Bytes::from_static(b"client h3"))
+            );
+
+            // notify the client that the checks are complete
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"done")).await.unwrap();
+            stream.finish().unwrap();
+            stream.flush().await.unwrap();
+        });
+
+        let client_params = Parameters::default()
+            .with_application_settings(b"other", b"client other")
+            .unwrap()
+            .with_application_settings(b"h3", b"client h3")
+            .unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_transport_parameters(client_params)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            assert_eq!(&connection.application_protocol().unwrap()[..], b"h3");
+            assert_eq!(
+                connection.peer_application_settings().unwrap(),
+                Some(Bytes::from_static(b"server h3"))
+            );
+
+            // keep the connection open until the server is done with its checks
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap(),
+                Some(Bytes::from_static(b"done"))
+            );
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::Error;
+
+/// Connects to an address with nothing bound to it and returns the resulting error along with
+/// how long it took to be reported
+fn connect_to_unbound(model: Model) -> (Error, Duration) {
+    let result = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let client = build_client(handle)?;
+        let result = result.clone();
+
+        primary::spawn(async move {
+            let start = io::time::now();
+            let connect = Connect::new(SERVER_ADDR).with_server_name("localhost");
+            let error = client.connect(connect).await.unwrap_err();
+            *result.lock().unwrap() = Some((error, io::time::now() - start));
+        });
+
+        Ok(SERVER_ADDR)
+    })
+    .unwrap();
+
+    let result = result.lock().unwrap().take();
+    result.expect("the connection attempt should complete")
+}
+
+const SERVER_ADDR: SocketAddr = SocketAddr::V4(std::net::SocketAddrV4::new(
+    std::net::Ipv4Addr::new(10, 0, 0, 1),
+    4433,
+));
+
+/// Ensures a port unreachable error fails the connection attempt without waiting for the
+/// handshake to time out
+#[test]
+fn port_unreachable_test() {
+    let model = Model::default();
+    model.set_port_unreachable(true);
+
+    let (error, elapsed) = connect_to_unbound(model);
+
+    assert!(matches!(error, Error::Unreachable { .. }), "{error:?}");
+    assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+}
+
+/// Ensures the connection attempt still times out when the network doesn't report errors
+#[test]
+fn port_unreachable_disabled_test() {
+    let model = Model::default();
+
+    let (error, elapsed) = connect_to_unbound(model);
+
+    assert!(!matches!(error, Error::Unreachable { .. }), "{error:?}");
+    assert!(elapsed >= Duration::from_secs(1), "{elapsed:?}");
+}
@@ -0,0 +1,133 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection::Error;
+use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+use s2n_quic_core::{
+    event::api::Subject,
+    packet::interceptor::{Datagram, Interceptor},
+};
+
+/// A reserved version which servers won't support
+const GREASE_VERSION: u32 = 0x0a0a_0a0a;
+
+/// Rewrites the version of the client's long header packets and optionally the versions listed in
+/// the Version Negotiation packets it receives
+struct VersionRewriter {
+    server_versions: Option<u32>,
+}
+
+impl Interceptor for VersionRewriter {
+    fn intercept_tx_datagram(
+        &mut self,
+        _subject: &Subject,
+        _datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        let payload = payload.as_mut_slice();
+        if payload[0] & 0x80 != 0 {
+            payload[1..5].copy_from_slice(&GREASE_VERSION.to_be_bytes());
+        }
+    }
+
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &Subject,
+        _datagram: &Datagram,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        let payload = payload.into_less_safe_slice();
+
+        if let Some(version) = self.server_versions {
+            let is_version_negotiation = payload[0] & 0x80 != 0 && payload[1..5] == [0; 4];
+            if is_version_negotiation {
+                let destination_connection_id_len = payload[5] as usize;
+                let source_connection_id_len = payload[6 + destination_connection_id_len] as usize;
+                let versions = 7 + destination_connection_id_len + source_connection_id_len;
+                for chunk in payload[versions..].chunks_exact_mut(4) {
+                    chunk.copy_from_slice(&version.to_be_bytes());
+                }
+            }
+        }
+
+        DecoderBufferMut::new(payload)
+    }
+}
+
+fn version_negotiation(
+    server_versions: Option<u32>,
+) -> (Error, Vec<(Vec<u32>, events::VersionNegotiationOutcome)>) {
+    let model = Model::default();
+    let subscriber = recorder::VersionNegotiationReceived::new();
+    let events = subscriber.events();
+    let result = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(123))?
+            .with_packet_interceptor(VersionRewriter { server_versions })?
+            .start()?;
+
+        let result = result.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let error = client.connect(connect).await.unwrap_err();
+            *result.lock().unwrap() = Some(error);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let error = result.lock().unwrap().take().unwrap();
+    let events = events.lock().unwrap().clone();
+    (error, events)
+}
+
+/// The client abandons the connection attempt if the server doesn't support the chosen version
+#[test]
+fn unsupported_version_test() {
+    let (error, events) = version_negotiation(Some(GREASE_VERSION));
+
+    assert!(
+        matches!(error, Error::UnsupportedVersion { .. }),
+        "{error:?}"
+    );
+    assert_eq!(events.len(), 1);
+    let (server_versions, outcome) = &events[0];
+    assert!(server_versions.iter().all(|v| *v == GREASE_VERSION));
+    assert!(matches!(
+        outcome,
+        events::VersionNegotiationOutcome::Abandoned { .. }
+    ));
+}
+
+/// The client discards Version Negotiation packets which list the chosen version
+#[test]
+fn chosen_version_listed_test() {
+    let (error, events) = version_negotiation(None);
+
+    assert!(
+        matches!(error, Error::MaxHandshakeDurationExceeded { .. }),
+        "{error:?}"
+    );
+    assert!(!events.is_empty());
+    let (server_versions, outcome) = &events[0];
+    assert!(server_versions.contains(&0x1));
+    assert!(matches!(
+        outcome,
+        events::VersionNegotiationOutcome::ChosenVersionListed { .. }
+    ));
+    // every following packet was received after the first one was processed
+    assert!(events[1..].iter().all(|(_, outcome)| matches!(
+        outcome,
+        events::VersionNegotiationOutcome::AlreadyProcessedPacket { .. }
+    )));
+}